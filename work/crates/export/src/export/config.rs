@@ -38,6 +38,7 @@ use syn::{Item, Result};
 
 pub struct ExportConfig {
     pub dump: Option<Span>,
+    pub detached: bool,
     pub stream: Option<TokenStream>,
 }
 
@@ -45,7 +46,9 @@ impl ExportConfig {
     pub fn export(self, item: &Item) -> Result<TokenStream> {
         match self.dump {
             None => match self.stream {
+                Some(stream) if self.detached => Ok(stream),
                 Some(stream) => Ok(quote!(#item #stream)),
+                None if self.detached => Ok(TokenStream::new()),
                 None => Ok(item.to_token_stream()),
             },
 
@@ -44,8 +44,10 @@ use syn::{
 use crate::{
     export::{
         item_const::export_item_const,
+        item_enum::export_item_enum,
         item_fn::export_item_fn,
         item_impl::export_item_impl,
+        item_mod::export_item_mod,
         item_static::export_item_static,
         item_struct::export_item_struct,
         item_trait::export_item_trait,
@@ -65,10 +67,7 @@ impl Parse for ExportItem {
         let config = match &mut item {
             Item::Const(item) => export_item_const(item),
 
-            Item::Enum(item) => Err(Error::new(
-                item.enum_token.span,
-                "Enum types cannot be exported.",
-            )),
+            Item::Enum(item) => export_item_enum(item),
 
             Item::ExternCrate(item) => Err(Error::new(
                 item.extern_token.span,
@@ -89,10 +88,7 @@ impl Parse for ExportItem {
                 "Macro invocation cannot be exported.",
             )),
 
-            Item::Mod(item) => Err(Error::new(
-                item.mod_token.span,
-                "Modules cannot be exported.",
-            )),
+            Item::Mod(item) => export_item_mod(item),
 
             Item::Static(item) => export_item_static(item),
 
@@ -40,17 +40,22 @@ use syn::{spanned::Spanned, Error, ItemConst, Result};
 use crate::{
     export::ExportConfig,
     utils::{
+        check_script_name,
         Component,
         Context,
         EmptyPolymorphism,
         Exportable,
         Facade,
         Group,
+        Namespace,
         Prototype,
         Shallow,
+        DEPRECATED,
         DUMP,
         EXCLUDED,
+        HIDDEN,
         INCLUDED,
+        NAMESPACE,
         RENAME,
         SHALLOW,
     },
@@ -66,7 +71,7 @@ pub fn export_item_const(item: &mut ItemConst) -> Result<ExportConfig> {
 
     let attrs = item.drain_attrs()?;
 
-    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | RENAME)?;
+    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | RENAME | DEPRECATED | HIDDEN | NAMESPACE)?;
 
     Shallow.init(attrs.shallow());
 
@@ -75,12 +80,28 @@ pub fn export_item_const(item: &mut ItemConst) -> Result<ExportConfig> {
 
     let mut group = Group::default();
 
-    let mut package_prototype = Prototype::for_package(span);
+    let namespace = attrs.namespace().map(Namespace::new);
+
+    let mut package_prototype = match &namespace {
+        Some(namespace) => {
+            namespace.declare(&mut group);
+
+            Prototype::for_type(namespace.ty())
+        }
+
+        None => Prototype::for_package(span),
+    };
 
     let name = attrs
         .rename_checked(&EmptyPolymorphism)?
         .unwrap_or_else(|| item.ident.to_string());
 
+    check_script_name(span, name.as_str())?;
+
+    if !attrs.disabled() && !attrs.shallow() {
+        Context.claim_package_name(attrs.namespace(), name.as_str(), span)?;
+    }
+
     let name_ref = Context.make_unique_identifier(name.as_str(), span);
 
     let constructor = {
@@ -108,6 +129,14 @@ pub fn export_item_const(item: &mut ItemConst) -> Result<ExportConfig> {
         constructor,
         hint: Cow::Borrowed(item.ty.as_ref()),
         doc: item.rust_doc(),
+        owned: false,
+        pure: false,
+        property: false,
+        setter: None,
+        deprecated: attrs.deprecated().cloned(),
+        hidden: attrs.hidden(),
+        observed: false,
+        casted: false,
     });
 
     Shallow.assert_type_impls_script_type(item.ty.as_ref(), item.ty.span());
@@ -116,6 +145,7 @@ pub fn export_item_const(item: &mut ItemConst) -> Result<ExportConfig> {
 
     Ok(ExportConfig {
         dump: attrs.dump(),
+        detached: false,
         stream: match attrs.disabled() {
             true => None,
             false => match attrs.shallow() {
@@ -0,0 +1,596 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::borrow::Cow;
+
+use proc_macro2::{Ident, Span};
+use quote::{format_ident, quote_spanned, ToTokens};
+use syn::{
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Error,
+    Fields,
+    FnArg,
+    Generics,
+    ItemEnum,
+    LitStr,
+    Pat,
+    PatIdent,
+    PatType,
+    Result,
+    ReturnType,
+    Signature,
+    Type,
+    Variant,
+};
+
+use crate::{
+    export::ExportConfig,
+    utils::{
+        check_script_name,
+        Coercion,
+        Component,
+        Context,
+        EmptyPolymorphism,
+        Exportable,
+        Facade,
+        FunctionPolymorphism,
+        Group,
+        Invocation,
+        Operator,
+        OperatorOrigin,
+        PolymorphicScope,
+        Printer,
+        Prototype,
+        ScriptAssign,
+        ScriptClone,
+        ScriptConcat,
+        ScriptDebug,
+        ScriptDefault,
+        ScriptHash,
+        ScriptOrd,
+        ScriptPartialEq,
+        ScriptPartialOrd,
+        Shallow,
+        SignaturePolymorphism,
+        TypeMeta,
+        TypePolymorphism,
+        TypeUtils,
+        DUMP,
+        EXCLUDED,
+        FAMILY,
+        INCLUDED,
+        RENAME,
+        SHALLOW,
+        UNSPECIFIED,
+    },
+};
+
+pub fn export_item_enum(item: &mut ItemEnum) -> Result<ExportConfig> {
+    let attrs = item.drain_attrs()?;
+
+    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | RENAME | FAMILY)?;
+
+    Shallow.init(attrs.shallow());
+
+    let span = item.ident.span();
+
+    if !item.generics.params.is_empty() {
+        return Err(Error::new(
+            item.generics.span(),
+            "An exported enum type cannot have generics.",
+        ));
+    }
+
+    if item.variants.is_empty() {
+        return Err(Error::new(
+            item.brace_token.span.join(),
+            "An exported enum type must have at least one variant.",
+        ));
+    }
+
+    let core = span.face_core();
+
+    let doc = item.rust_doc();
+    let family = attrs.family();
+
+    let polymorphism = TypePolymorphism::new(&item.ident, &mut item.generics)?;
+
+    let ty = polymorphism.make_type();
+
+    let name = attrs
+        .rename_unchecked(&polymorphism)?
+        .map(|name| LitStr::new(name.as_str(), span))
+        .unwrap_or_else(|| ty.to_display_literal());
+
+    let mut group = Group::default();
+
+    let variant_set = VariantSet::new(&ty, name.value().as_str(), &mut item.variants)?;
+
+    let clone = match attrs.derive().impls_clone() {
+        None => None,
+        Some(span) => Some(Context.make_origin("derive_clone", span)),
+    };
+
+    let debug = match attrs.derive().impls_debug() {
+        None => None,
+        Some(span) => Some(Context.make_origin("derive_debug", span)),
+    };
+
+    let partial_eq = match attrs.derive().impls_partial_eq() {
+        None => None,
+        Some(span) => Some(Context.make_origin("derive_partial_eq", span)),
+    };
+
+    let default = match attrs.derive().impls_default() {
+        None => None,
+        Some(span) => Some(Context.make_origin("derive_default", span)),
+    };
+
+    let partial_ord = match attrs.derive().impls_partial_ord() {
+        None => None,
+        Some(span) => Some(Context.make_origin("derive_partial_ord", span)),
+    };
+
+    let ord = match attrs.derive().impls_ord() {
+        None => None,
+        Some(span) => Some(Context.make_origin("derive_ord", span)),
+    };
+
+    let hash = match attrs.derive().impls_hash() {
+        None => None,
+        Some(span) => Some(Context.make_origin("derive_hash", span)),
+    };
+
+    group.type_meta(TypeMeta {
+        name: &name,
+        doc: doc.as_ref(),
+        ty: &ty,
+        family,
+    });
+
+    let coercion = Coercion {
+        downcast_own: true,
+        downcast_ref: true,
+        downcast_mut: true,
+        upcast_own: true,
+        upcast_ref: true,
+        upcast_mut: true,
+        script_struct: false,
+    };
+
+    group.custom(ty.impl_registered_type());
+    group.custom(ty.impl_coercion(coercion));
+
+    Shallow.impl_registered_type(&ty);
+    Shallow.impl_coercion(&ty, coercion);
+
+    let mut prototype = Prototype::for_type(&ty);
+
+    prototype.operator(OperatorOrigin::Primary, Operator::Assign);
+    group.custom(ScriptAssign { span, ty: &ty });
+
+    Shallow.impl_operator(&ty, None, Operator::Assign, span);
+
+    prototype.operator(OperatorOrigin::Primary, Operator::Concat);
+    group.custom(ScriptConcat { span, ty: &ty });
+
+    Shallow.impl_operator(&ty, None, Operator::Concat, span);
+
+    if let Some(origin) = &clone {
+        let span = origin.span();
+
+        prototype.operator(OperatorOrigin::Origin(origin), Operator::Clone);
+        group.custom(ScriptClone { span, lhs: &ty });
+
+        Shallow.impl_operator(&ty, None, Operator::Clone, span);
+    }
+
+    if let Some(origin) = &debug {
+        let span = origin.span();
+
+        prototype.operator(OperatorOrigin::Origin(origin), Operator::Debug);
+        group.custom(ScriptDebug { span, lhs: &ty });
+
+        Shallow.impl_operator(&ty, None, Operator::Debug, span);
+    }
+
+    if let Some(origin) = &partial_eq {
+        let span = origin.span();
+
+        prototype.operator(OperatorOrigin::Origin(origin), Operator::PartialEq);
+        group.custom(ScriptPartialEq {
+            span,
+            lhs: &ty,
+            rhs: &ty,
+        });
+
+        Shallow.impl_operator(&ty, None, Operator::PartialEq, span);
+    }
+
+    if let Some(origin) = &default {
+        let span = origin.span();
+
+        prototype.operator(OperatorOrigin::Origin(origin), Operator::Default);
+        group.custom(ScriptDefault { span, lhs: &ty });
+
+        Shallow.impl_operator(&ty, None, Operator::Default, span);
+    }
+
+    if let Some(origin) = &partial_ord {
+        let span = origin.span();
+
+        prototype.operator(OperatorOrigin::Origin(origin), Operator::PartialOrd);
+        group.custom(ScriptPartialOrd {
+            span,
+            lhs: &ty,
+            rhs: &ty,
+        });
+
+        Shallow.impl_operator(&ty, None, Operator::PartialOrd, span);
+    }
+
+    if let Some(origin) = &ord {
+        let span = origin.span();
+
+        prototype.operator(OperatorOrigin::Origin(origin), Operator::Ord);
+        group.custom(ScriptOrd { span, lhs: &ty });
+
+        Shallow.impl_operator(&ty, None, Operator::Ord, span);
+    }
+
+    if let Some(origin) = &hash {
+        let span = origin.span();
+
+        prototype.operator(OperatorOrigin::Origin(origin), Operator::Hash);
+        group.custom(ScriptHash { span, lhs: &ty });
+
+        Shallow.impl_operator(&ty, None, Operator::Hash, span);
+    }
+
+    let variant_name = variant_set.make_variant_name(&ty);
+
+    prototype.component(Component {
+        name_ref: Cow::Owned(Context.make_unique_identifier("variant", span)),
+        constructor: quote_spanned!(span=> {
+            fn component(
+                origin: #core::runtime::Origin,
+                lhs: #core::runtime::Arg,
+            ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                let mut data = lhs.data;
+
+                let this = #core::runtime::Cell::borrow_ref::<#ty>(&mut data, origin)?;
+
+                #core::runtime::Cell::give(origin, #variant_name)
+            }
+
+            component as fn(
+                #core::runtime::Origin,
+                #core::runtime::Arg,
+            ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
+        }),
+        hint: Cow::Owned(Type::Verbatim(quote_spanned!(span=> str))),
+        doc: Some(LitStr::new(
+            "The name of the active variant of this enum value.",
+            span,
+        )),
+        owned: true,
+        pure: false,
+        property: false,
+        setter: None,
+        deprecated: None,
+        hidden: false,
+        observed: false,
+        casted: false,
+    });
+
+    group.prototype(prototype);
+
+    let mut package_prototype = Prototype::for_package(span);
+
+    for variant in &variant_set.variants {
+        variant.export(&ty, &mut group, &mut package_prototype)?;
+    }
+
+    group.prototype(package_prototype);
+
+    Ok(ExportConfig {
+        dump: attrs.dump(),
+        detached: false,
+        stream: match attrs.disabled() {
+            true => None,
+            false => match attrs.shallow() {
+                true => Some(Shallow.to_token_stream()),
+                false => Some(group.to_token_stream()),
+            },
+        },
+    })
+}
+
+struct VariantSet {
+    variants: Vec<VariantMeta>,
+}
+
+impl VariantSet {
+    fn new(
+        ty: &Type,
+        enum_name: &str,
+        source: &mut Punctuated<Variant, syn::token::Comma>,
+    ) -> Result<Self> {
+        let mut variants = Vec::with_capacity(source.len());
+
+        for variant in source.iter_mut() {
+            let variant_attrs = variant.drain_attrs()?;
+
+            variant_attrs.check(UNSPECIFIED | EXCLUDED | RENAME)?;
+
+            let span = variant.ident.span();
+
+            let name = variant_attrs
+                .rename_checked(&EmptyPolymorphism)?
+                .unwrap_or_else(|| variant.ident.to_string());
+
+            check_script_name(span, name.as_str())?;
+
+            let constructor = match variant_attrs.excluded() {
+                true => None,
+                false => Some(Constructor::new(ty, enum_name, &name, variant)?),
+            };
+
+            variants.push(VariantMeta {
+                span,
+                ident: variant.ident.clone(),
+                fields: match &variant.fields {
+                    Fields::Named(..) => VariantFields::Named,
+                    Fields::Unnamed(..) => VariantFields::Unnamed,
+                    Fields::Unit => VariantFields::Unit,
+                },
+                name,
+                constructor,
+            });
+        }
+
+        Ok(Self { variants })
+    }
+
+    fn make_variant_name(&self, ty: &Type) -> impl ToTokens {
+        let arms = self.variants.iter().map(|variant| {
+            let span = variant.span;
+            let ident = &variant.ident;
+            let name = LitStr::new(variant.name.as_str(), span);
+
+            match variant.fields {
+                VariantFields::Named => quote_spanned!(span=> #ty::#ident { .. } => #name),
+                VariantFields::Unnamed => quote_spanned!(span=> #ty::#ident(..) => #name),
+                VariantFields::Unit => quote_spanned!(span=> #ty::#ident => #name),
+            }
+        });
+
+        quote_spanned!(ty.span()=> match this {
+            #(
+            #arms,
+            )*
+        })
+    }
+}
+
+struct VariantMeta {
+    span: Span,
+    ident: Ident,
+    fields: VariantFields,
+    name: String,
+    constructor: Option<Constructor>,
+}
+
+impl VariantMeta {
+    fn export(&self, ty: &Type, group: &mut Group, prototype: &mut Prototype) -> Result<()> {
+        let Some(constructor) = &self.constructor else {
+            return Ok(());
+        };
+
+        let span = self.span;
+        let core = span.face_core();
+
+        let mut inputs = constructor.signature.inputs.clone();
+        let mut generics = Generics::default();
+
+        let output = match &constructor.signature.output {
+            ReturnType::Type(_, output) => output.as_ref(),
+            ReturnType::Default => unreachable!("Internal error. Missing constructor output."),
+        };
+
+        let signature_polymorphism = SignaturePolymorphism::new(
+            &constructor.signature.ident,
+            &mut generics,
+            &mut inputs,
+            &constructor.signature.output,
+        )?;
+
+        let function_polymorphism = FunctionPolymorphism {
+            scope: &EmptyPolymorphism,
+            signature: &signature_polymorphism,
+        };
+
+        if !function_polymorphism.check_bounds(group)? {
+            return Ok(());
+        }
+
+        let invocation = Invocation::new(&constructor.signature, None)?;
+
+        let name_ref = Context.make_unique_identifier(constructor.name.as_str(), span);
+
+        let function = &constructor.signature.ident;
+        let variant = &self.ident;
+
+        let params = constructor
+            .params
+            .iter()
+            .map(|(param, _)| param)
+            .collect::<Vec<_>>();
+
+        let arguments = constructor.signature.inputs.iter().collect::<Vec<_>>();
+
+        let body = match self.fields {
+            VariantFields::Named => {
+                let members = constructor.params.iter().map(|(_, member)| member);
+
+                quote_spanned!(span=> #ty::#variant { #( #members: #params ),* })
+            }
+
+            VariantFields::Unnamed => quote_spanned!(span=> #ty::#variant( #( #params ),* )),
+
+            VariantFields::Unit => quote_spanned!(span=> #ty::#variant),
+        };
+
+        group.custom(quote_spanned!(span=>
+            #[inline(always)]
+            #[allow(non_snake_case)]
+            fn #function(#( #arguments ),*) -> #output {
+                #body
+            }
+        ));
+
+        let function_type = invocation.make_function_type(
+            group,
+            &function_polymorphism,
+            constructor.name.as_str(),
+            &name_ref,
+            constructor.doc.clone(),
+        )?;
+
+        prototype.component(Component {
+            name_ref: Cow::Owned(name_ref),
+            constructor: quote_spanned!(span=> {
+                fn component(
+                    origin: #core::runtime::Origin,
+                    _lhs: #core::runtime::Arg,
+                ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                    #core::runtime::Cell::give(origin, #function_type)
+                }
+
+                component as fn(
+                    #core::runtime::Origin,
+                    #core::runtime::Arg,
+                ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
+            }),
+            hint: Cow::Owned(function_type),
+            doc: constructor.doc.clone(),
+            owned: true,
+            pure: true,
+            property: false,
+            setter: None,
+            deprecated: None,
+            hidden: false,
+            observed: false,
+            casted: false,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum VariantFields {
+    Named,
+    Unnamed,
+    Unit,
+}
+
+// A synthetic Rust function that constructs the variant from its fields.
+struct Constructor {
+    name: String,
+    doc: Option<LitStr>,
+    signature: Signature,
+    params: Vec<(Ident, Ident)>,
+}
+
+impl Constructor {
+    fn new(ty: &Type, enum_name: &str, name: &str, variant: &Variant) -> Result<Self> {
+        let span = variant.ident.span();
+
+        let name = format!("{enum_name}_{name}");
+
+        let mut inputs = Punctuated::new();
+        let mut params = Vec::with_capacity(variant.fields.len());
+
+        for (index, field) in variant.fields.iter().enumerate() {
+            let (param, member) = match &field.ident {
+                Some(ident) => (ident.clone(), ident.clone()),
+                None => {
+                    let ident = format_ident!("_{index}", span = field.span());
+
+                    (ident.clone(), ident)
+                }
+            };
+
+            inputs.push(FnArg::Typed(PatType {
+                attrs: Vec::new(),
+                pat: Box::new(Pat::Ident(PatIdent {
+                    attrs: Vec::new(),
+                    by_ref: None,
+                    mutability: None,
+                    ident: param.clone(),
+                    subpat: None,
+                })),
+                colon_token: Default::default(),
+                ty: Box::new(field.ty.clone()),
+            }));
+
+            params.push((param, member));
+        }
+
+        let ident = Context.make_function_name(name.as_str(), span);
+
+        let signature = Signature {
+            constness: None,
+            asyncness: None,
+            unsafety: None,
+            abi: None,
+            fn_token: Default::default(),
+            ident,
+            generics: Generics::default(),
+            paren_token: Default::default(),
+            inputs,
+            variadic: None,
+            output: ReturnType::Type(Default::default(), Box::new(ty.clone())),
+        };
+
+        Ok(Self {
+            name,
+            doc: variant.rust_doc(),
+            signature,
+            params,
+        })
+    }
+}
@@ -35,11 +35,13 @@
 use std::borrow::Cow;
 
 use quote::{quote_spanned, ToTokens};
-use syn::{ItemFn, Result};
+use syn::{parse_quote_spanned, spanned::Spanned, ItemFn, Result, ReturnType};
 
 use crate::{
     export::ExportConfig,
     utils::{
+        check_script_name,
+        is_iterator_output,
         Component,
         Context,
         EmptyPolymorphism,
@@ -48,12 +50,20 @@ use crate::{
         FunctionPolymorphism,
         Group,
         Invocation,
+        Namespace,
         Prototype,
         Shallow,
         SignaturePolymorphism,
+        BLOCK_ON,
+        CONTRACT,
+        DEPRECATED,
         DUMP,
         EXCLUDED,
+        HIDDEN,
         INCLUDED,
+        MEMOIZE,
+        NAMESPACE,
+        PURE,
         RENAME,
         SHALLOW,
     },
@@ -62,7 +72,19 @@ use crate::{
 pub fn export_item_fn(item: &mut ItemFn) -> Result<ExportConfig> {
     let attrs = item.drain_attrs()?;
 
-    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | RENAME)?;
+    attrs.check(
+        DUMP | INCLUDED
+            | EXCLUDED
+            | SHALLOW
+            | RENAME
+            | PURE
+            | MEMOIZE
+            | CONTRACT
+            | DEPRECATED
+            | HIDDEN
+            | BLOCK_ON
+            | NAMESPACE,
+    )?;
 
     Shallow.init(attrs.shallow());
 
@@ -72,16 +94,47 @@ pub fn export_item_fn(item: &mut ItemFn) -> Result<ExportConfig> {
 
     let mut group = Group::default();
 
-    let mut package_prototype = Prototype::for_package(span);
+    let namespace = attrs.namespace().map(Namespace::new);
+
+    let mut package_prototype = match &namespace {
+        Some(namespace) => {
+            namespace.declare(&mut group);
+
+            Prototype::for_type(namespace.ty())
+        }
+
+        None => Prototype::for_package(span),
+    };
+
+    // Iterator-returning functions are exposed to scripts as the lazy
+    // ScriptIterator wrapper.
+    let iterator_output: Option<ReturnType> = match is_iterator_output(&item.sig.output) {
+        false => None,
+        true => {
+            let output_span = item.sig.output.span();
+
+            Some(parse_quote_spanned!(output_span=> -> #core::runtime::ScriptIterator))
+        }
+    };
 
     let mut signature_polymorphism = SignaturePolymorphism::new(
         &item.sig.ident,
         &mut item.sig.generics,
         &mut item.sig.inputs,
-        &item.sig.output,
+        iterator_output.as_ref().unwrap_or(&item.sig.output),
     )?;
 
-    let invocation = Invocation::new(&item.sig)?;
+    let mut invocation = Invocation::new(&item.sig, attrs.block_on())?;
+
+    if let Some(span) = attrs.memoize() {
+        invocation.memoize(span, &item.sig)?;
+    }
+
+    invocation.contracts(attrs.contracts())?;
+
+    if iterator_output.is_some() {
+        invocation.iterator(item.sig.output.span())?;
+    }
 
     loop {
         let function_polymorphism = FunctionPolymorphism {
@@ -89,40 +142,56 @@ pub fn export_item_fn(item: &mut ItemFn) -> Result<ExportConfig> {
             signature: &signature_polymorphism,
         };
 
-        let name = attrs
-            .rename_checked(&function_polymorphism)?
-            .unwrap_or_else(|| item.sig.ident.to_string());
-
-        let name_ref = Context.make_unique_identifier(name.as_str(), span);
-
-        let function_type = invocation.make_function_type(
-            &mut group,
-            &function_polymorphism,
-            name.as_str(),
-            &name_ref,
-            item.rust_doc(),
-        )?;
-
-        let constructor = quote_spanned!(span=> {
-            fn component(
-                origin: #core::runtime::Origin,
-                _lhs: #core::runtime::Arg,
-            ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
-                #core::runtime::Cell::give(origin, #function_type)
-            }
+        if function_polymorphism.check_bounds(&mut group)? {
+            let name = attrs
+                .rename_checked(&function_polymorphism)?
+                .unwrap_or_else(|| item.sig.ident.to_string());
+
+            check_script_name(span, name.as_str())?;
 
-            component as fn(
-                #core::runtime::Origin,
-                #core::runtime::Arg,
-            ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
-        });
+            if !attrs.disabled() && !attrs.shallow() {
+                Context.claim_package_name(attrs.namespace(), name.as_str(), span)?;
+            }
 
-        package_prototype.component(Component {
-            name_ref: Cow::Owned(name_ref),
-            constructor,
-            hint: Cow::Owned(function_type),
-            doc: item.rust_doc(),
-        });
+            let name_ref = Context.make_unique_identifier(name.as_str(), span);
+
+            let function_type = invocation.make_function_type(
+                &mut group,
+                &function_polymorphism,
+                name.as_str(),
+                &name_ref,
+                item.rust_doc(),
+            )?;
+
+            let constructor = quote_spanned!(span=> {
+                fn component(
+                    origin: #core::runtime::Origin,
+                    _lhs: #core::runtime::Arg,
+                ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                    #core::runtime::Cell::give(origin, #function_type)
+                }
+
+                component as fn(
+                    #core::runtime::Origin,
+                    #core::runtime::Arg,
+                ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
+            });
+
+            package_prototype.component(Component {
+                name_ref: Cow::Owned(name_ref),
+                constructor,
+                hint: Cow::Owned(function_type),
+                doc: item.rust_doc(),
+                owned: true,
+                pure: attrs.pure(),
+                property: false,
+                setter: None,
+                deprecated: attrs.deprecated().cloned(),
+                hidden: attrs.hidden(),
+                observed: false,
+                casted: false,
+            });
+        }
 
         if !signature_polymorphism.rotate() {
             break;
@@ -133,6 +202,7 @@ pub fn export_item_fn(item: &mut ItemFn) -> Result<ExportConfig> {
 
     Ok(ExportConfig {
         dump: attrs.dump(),
+        detached: false,
         stream: match attrs.disabled() {
             true => None,
             false => match attrs.shallow() {
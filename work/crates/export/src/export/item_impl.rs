@@ -34,15 +34,18 @@
 
 use std::borrow::Cow;
 
-use proc_macro2::{Ident, Span};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote_spanned, ToTokens};
 use syn::{
     spanned::Spanned,
     Error,
+    Expr,
+    FnArg,
     ImplItem,
     ItemImpl,
     LitStr,
     Result,
+    ReturnType,
     Signature,
     TraitItem,
     Type,
@@ -52,6 +55,7 @@ use syn::{
 use crate::{
     export::ExportConfig,
     utils::{
+        check_script_name,
         Attrs,
         Component,
         Context,
@@ -85,6 +89,7 @@ use crate::{
         ScriptDiv,
         ScriptDivAssign,
         ScriptHash,
+        ScriptIterate,
         ScriptMul,
         ScriptMulAssign,
         ScriptNeg,
@@ -103,9 +108,15 @@ use crate::{
         Shallow,
         SignaturePolymorphism,
         COMPONENT,
+        DEPRECATED,
         DUMP,
         EXCLUDED,
+        FAMILY,
+        HIDDEN,
         INCLUDED,
+        INCLUDE_DEFAULTS,
+        OBSERVED,
+        PROPERTY,
         RENAME,
         SHALLOW,
         UNSPECIFIED,
@@ -115,15 +126,49 @@ use crate::{
 pub fn export_item_impl(item: &mut ItemImpl) -> Result<ExportConfig> {
     let attrs = item.drain_attrs()?;
 
-    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW)?;
+    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | INCLUDE_DEFAULTS | FAMILY)?;
 
     Shallow.init(attrs.shallow());
 
+    let defaults = match attrs.include_defaults() {
+        None => None,
+        Some(span) => Some(include_defaults(item, span)?),
+    };
+
     let mut impl_polymorphism = ImplPolymorphism::new(item)?;
 
     let mut group = Group::default();
 
     loop {
+        if let Some((span, family)) = attrs.family_marker() {
+            let operator = match &item.trait_ {
+                Some((_, trait_path, _)) => trait_path.matches_operator(),
+                None => None,
+            };
+
+            let Some(operator) = operator else {
+                return Err(Error::new(
+                    span,
+                    "Type family marker is applicable to the Script operator \
+                    implementations only.",
+                ));
+            };
+
+            if !operator.is_family_applicable() {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "Script{operator} operator cannot be exported for a type \
+                        family, because its implementation depends on the receiver \
+                        type.",
+                    ),
+                ));
+            }
+
+            export_family_operator(&mut group, &mut impl_polymorphism, operator, family)?;
+            break;
+        }
+
         if let Some((_, trait_path, _)) = &item.trait_ {
             if let Some(operator) = trait_path.matches_operator() {
                 export_custom_operator(&mut group, &mut impl_polymorphism, operator)?;
@@ -170,6 +215,26 @@ pub fn export_item_impl(item: &mut ItemImpl) -> Result<ExportConfig> {
                 break;
             }
 
+            if let Some(meta) = trait_path.matches_iterator() {
+                export_default_operator::<ScriptIterate>(
+                    &mut group,
+                    &mut impl_polymorphism,
+                    Operator::Iterate,
+                    meta,
+                )?;
+                break;
+            }
+
+            if let Some(meta) = trait_path.matches_into_iterator() {
+                export_default_operator::<ScriptIterate>(
+                    &mut group,
+                    &mut impl_polymorphism,
+                    Operator::Iterate,
+                    meta,
+                )?;
+                break;
+            }
+
             if let Some(meta) = trait_path.matches_partial_eq() {
                 export_default_operator::<ScriptPartialEq>(
                     &mut group,
@@ -465,8 +530,13 @@ pub fn export_item_impl(item: &mut ItemImpl) -> Result<ExportConfig> {
         break;
     }
 
+    if let Some(defaults) = defaults {
+        group.custom(defaults);
+    }
+
     Ok(ExportConfig {
         dump: attrs.dump(),
+        detached: false,
         stream: match attrs.disabled() {
             true => None,
             false => match attrs.shallow() {
@@ -477,23 +547,106 @@ pub fn export_item_impl(item: &mut ItemImpl) -> Result<ExportConfig> {
     })
 }
 
+// Invokes the macro generated by the exported trait that exports the default
+// methods not overridden by this implementation.
+fn include_defaults(item: &ItemImpl, span: Span) -> Result<TokenStream> {
+    let Some((_, trait_path, _)) = &item.trait_ else {
+        return Err(Error::new(
+            span,
+            "Default members inclusion is applicable to trait implementations only.",
+        ));
+    };
+
+    if !item.generics.params.is_empty() {
+        return Err(Error::new(
+            item.generics.span(),
+            "Default members inclusion is not supported for generic implementations.",
+        ));
+    }
+
+    let Some(segment) = trait_path.segments.last() else {
+        return Err(Error::new(trait_path.span(), "Empty trait path."));
+    };
+
+    if !segment.arguments.is_empty() {
+        return Err(Error::new(
+            segment.arguments.span(),
+            "Default members inclusion is not supported for generic traits.",
+        ));
+    }
+
+    let ident = &segment.ident;
+    let self_ty = &item.self_ty;
+
+    let import = match trait_path.segments.len() > 1 {
+        true => Some(quote_spanned!(span=> use #trait_path as #ident;)),
+        false => None,
+    };
+
+    let overridden = item.items.iter().filter_map(|member| match member {
+        ImplItem::Fn(function) => Some(&function.sig.ident),
+        _ => None,
+    });
+
+    Ok(quote_spanned!(span=>
+        const _: () = {
+            #import
+
+            #ident!([#self_ty] #( #overridden )*);
+        };
+    ))
+}
+
 fn export_custom_operator(
     group: &mut Group,
     impl_polymorphism: &mut ImplPolymorphism,
     operator: Operator,
 ) -> Result<()> {
     loop {
-        let ty = impl_polymorphism
-            .get_self_type()?
-            .expect("Internal error. Missing self type.");
+        if impl_polymorphism.check_bounds(group)? {
+            let ty = impl_polymorphism
+                .get_self_type()?
+                .expect("Internal error. Missing self type.");
 
-        let mut prototype = Prototype::for_type(ty);
+            let mut prototype = Prototype::for_type(ty);
 
-        prototype.operator(OperatorOrigin::Primary, operator);
+            prototype.operator(OperatorOrigin::Primary, operator);
 
-        group.prototype(prototype);
+            group.prototype(prototype);
 
-        Shallow.assert_type_meets_op_requirements(ty, operator, Context.span());
+            Shallow.assert_type_meets_op_requirements(ty, operator, Context.span());
+        }
+
+        if !impl_polymorphism.rotate()? {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// Exports the operator for the whole type family. The Prototype registry
+// falls back to this implementation for the family types that do not
+// implement the operator themselves.
+fn export_family_operator(
+    group: &mut Group,
+    impl_polymorphism: &mut ImplPolymorphism,
+    operator: Operator,
+    family: &Expr,
+) -> Result<()> {
+    loop {
+        if impl_polymorphism.check_bounds(group)? {
+            let ty = impl_polymorphism
+                .get_self_type()?
+                .expect("Internal error. Missing self type.");
+
+            let mut prototype = Prototype::for_type(ty);
+
+            prototype.family(family);
+            prototype.operator(OperatorOrigin::Primary, operator);
+
+            group.prototype(prototype);
+        }
 
         if !impl_polymorphism.rotate()? {
             break;
@@ -510,30 +663,32 @@ fn export_default_operator<D: DefaultScriptOperator>(
     meta: PathMeta<'_>,
 ) -> Result<()> {
     loop {
-        let ty = impl_polymorphism
-            .get_self_type()?
-            .expect("Internal error. Missing self type.");
+        if impl_polymorphism.check_bounds(group)? {
+            let ty = impl_polymorphism
+                .get_self_type()?
+                .expect("Internal error. Missing self type.");
 
-        let arg = match meta.args.first() {
-            None => None,
-            Some(arg) => {
-                let mut arg = (*arg).clone();
+            let arg = match meta.args.first() {
+                None => None,
+                Some(arg) => {
+                    let mut arg = (*arg).clone();
 
-                impl_polymorphism.specialize_type(&mut arg)?;
+                    impl_polymorphism.specialize_type(&mut arg)?;
 
-                Some(arg)
-            }
-        };
+                    Some(arg)
+                }
+            };
 
-        group.custom(D::new_stream(meta.span, ty, arg.as_ref()));
+            group.custom(D::new_stream(meta.span, ty, arg.as_ref()));
 
-        let mut prototype = Prototype::for_type(ty);
+            let mut prototype = Prototype::for_type(ty);
 
-        prototype.operator(OperatorOrigin::Primary, operator);
+            prototype.operator(OperatorOrigin::Primary, operator);
 
-        group.prototype(prototype);
+            group.prototype(prototype);
 
-        Shallow.impl_operator(ty, arg.as_ref(), operator, meta.span);
+            Shallow.impl_operator(ty, arg.as_ref(), operator, meta.span);
+        }
 
         if !impl_polymorphism.rotate()? {
             break;
@@ -558,27 +713,29 @@ fn export_impl_body(
     };
 
     loop {
-        let mut self_prototype = match item_set.has_self_items {
-            false => None,
+        if impl_polymorphism.check_bounds(group)? {
+            let mut self_prototype = match item_set.has_self_items {
+                false => None,
 
-            true => {
-                let ty = impl_polymorphism
-                    .get_self_type()?
-                    .expect("Internal error. Missing self type.");
+                true => {
+                    let ty = impl_polymorphism
+                        .get_self_type()?
+                        .expect("Internal error. Missing self type.");
 
-                Some(Prototype::for_type(ty))
-            }
-        };
+                    Some(Prototype::for_type(ty))
+                }
+            };
 
-        item_set.export::<ImplPolymorphism>(
-            &impl_polymorphism,
-            group,
-            &mut self_prototype,
-            &mut package_prototype,
-        )?;
+            item_set.export::<ImplPolymorphism>(
+                &impl_polymorphism,
+                group,
+                &mut self_prototype,
+                &mut package_prototype,
+            )?;
 
-        if let Some(prototype) = self_prototype {
-            group.prototype(prototype);
+            if let Some(prototype) = self_prototype {
+                group.prototype(prototype);
+            }
         }
 
         if !impl_polymorphism.rotate()? {
@@ -634,7 +791,9 @@ impl<'a> ItemSet<'a> {
                     match FnMeta::new(attrs, true, source.rust_doc(), &mut source.sig)? {
                         Some(item) => {
                             match &item.kind {
-                                FnKind::Component(..) => has_self_items = true,
+                                FnKind::Component(..)
+                                | FnKind::Getter { .. }
+                                | FnKind::Setter { .. } => has_self_items = true,
                                 FnKind::Invocation(invocation) if invocation.uses_receiver() => {
                                     has_self_items = true;
                                 }
@@ -653,6 +812,8 @@ impl<'a> ItemSet<'a> {
             items.push(item);
         }
 
+        Self::link_properties(&mut items)?;
+
         Ok(Self {
             has_self_items,
             has_package_items,
@@ -716,7 +877,9 @@ impl<'a> ItemSet<'a> {
                     )? {
                         Some(item) => {
                             match &item.kind {
-                                FnKind::Component(..) => has_self_items = true,
+                                FnKind::Component(..)
+                                | FnKind::Getter { .. }
+                                | FnKind::Setter { .. } => has_self_items = true,
                                 FnKind::Invocation(invocation) if invocation.uses_receiver() => {
                                     has_self_items = true;
                                 }
@@ -735,6 +898,8 @@ impl<'a> ItemSet<'a> {
             items.push(item);
         }
 
+        Self::link_properties(&mut items)?;
+
         Ok(Self {
             has_self_items,
             has_package_items,
@@ -742,6 +907,60 @@ impl<'a> ItemSet<'a> {
         })
     }
 
+    // Attaches each property setter function to the getter function of
+    // the same property.
+    fn link_properties(items: &mut [ItemMeta<'a>]) -> Result<()> {
+        let setters = items
+            .iter()
+            .filter_map(|item| match item {
+                ItemMeta::Fn(FnMeta {
+                    kind:
+                        FnKind::Setter {
+                            ident,
+                            ty,
+                            property,
+                            observed,
+                        },
+                    ..
+                }) => Some((*ident, ty.clone(), property.clone(), *observed)),
+
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        for (ident, ty, property, observed) in setters {
+            let getter = items.iter_mut().find_map(|item| match item {
+                ItemMeta::Fn(FnMeta {
+                    name: Some((name, _)),
+                    kind: FnKind::Getter { setter, .. },
+                    ..
+                }) if name == &property => Some(setter),
+
+                _ => None,
+            });
+
+            match getter {
+                None => {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!("Missing #[export(getter)] function of the {property:?} property."),
+                    ));
+                }
+
+                Some(Some(_)) => {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!("Duplicate setter of the {property:?} property."),
+                    ));
+                }
+
+                Some(setter) => *setter = Some((ident, ty, observed)),
+            }
+        }
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub(super) fn has_self_items(&self) -> bool {
         self.has_self_items
@@ -770,7 +989,9 @@ impl<'a> ItemSet<'a> {
                 }
 
                 ItemMeta::Fn(item) => match &item.kind {
-                    FnKind::Component(..) => {
+                    FnKind::Setter { .. } => (),
+
+                    FnKind::Component(..) | FnKind::Getter { .. } => {
                         let prototype = self_prototype
                             .as_mut()
                             .expect("Internal error. Missing self PrototypeDeclaration.");
@@ -836,14 +1057,21 @@ impl<'a> ConstMeta<'a> {
         let name_ref = match attrs.has_rename_variables() {
             true => None,
 
-            false => Some(
-                attrs
-                    .rename_checked(&EmptyPolymorphism)?
-                    .map(|name| Context.make_unique_identifier(name.as_str(), ident.span()))
-                    .unwrap_or_else(|| {
-                        Context.make_unique_identifier(ident.to_string().as_str(), ident.span())
-                    }),
-            ),
+            false => {
+                let name = match attrs.rename_checked(&EmptyPolymorphism)? {
+                    Some(name) => name,
+
+                    None => {
+                        let name = ident.to_string();
+
+                        check_script_name(ident.span(), name.as_str())?;
+
+                        name
+                    }
+                };
+
+                Some(Context.make_unique_identifier(name.as_str(), ident.span()))
+            }
         };
 
         Ok(Some(Self {
@@ -915,6 +1143,14 @@ impl<'a> ConstMeta<'a> {
             constructor,
             hint: Cow::Owned(ty),
             doc,
+            owned: false,
+            pure: false,
+            property: false,
+            setter: None,
+            deprecated: None,
+            hidden: false,
+            observed: false,
+            casted: false,
         });
 
         Ok(())
@@ -938,7 +1174,17 @@ impl<'a> FnMeta<'a> {
         doc: Option<LitStr>,
         sig: &'a mut Signature,
     ) -> Result<Option<Self>> {
-        attrs.check(UNSPECIFIED | INCLUDED | EXCLUDED | RENAME | COMPONENT)?;
+        attrs.check(
+            UNSPECIFIED
+                | INCLUDED
+                | EXCLUDED
+                | RENAME
+                | COMPONENT
+                | PROPERTY
+                | DEPRECATED
+                | HIDDEN
+                | OBSERVED,
+        )?;
 
         if attrs.excluded() {
             return Ok(None);
@@ -950,22 +1196,91 @@ impl<'a> FnMeta<'a> {
 
         let span = sig.ident.span();
 
-        let name = match attrs.has_rename_variables() {
-            true => None,
+        if attrs.getter().is_some() || attrs.setter().is_some() {
+            if attrs.has_rename_variables() {
+                return Err(Error::new(
+                    span,
+                    "Property functions cannot be renamed with variables.",
+                ));
+            }
 
-            false => attrs
-                .rename_checked(&EmptyPolymorphism)?
-                .map(|name| {
-                    let name_ref = Context.make_unique_identifier(name.as_str(), span);
+            if !sig.generics.params.is_empty() {
+                return Err(Error::new(
+                    sig.generics.span(),
+                    "Property functions with generic parameters are not allowed.",
+                ));
+            }
+        }
 
-                    Some((name, name_ref))
-                })
+        if let Some(span) = attrs.observed() {
+            if attrs.setter().is_none() {
+                return Err(Error::new(
+                    span,
+                    "Observation marker is applicable to property setters only.",
+                ));
+            }
+        }
+
+        if attrs.setter().is_some() {
+            let property = attrs
+                .rename_checked(&EmptyPolymorphism)?
                 .unwrap_or_else(|| {
                     let name = sig.ident.to_string();
-                    let name_ref = Context.make_unique_identifier(name.as_str(), span);
 
-                    Some((name, name_ref))
-                }),
+                    match name.strip_prefix("set_") {
+                        Some(property) => String::from(property),
+                        None => name,
+                    }
+                });
+
+            check_script_name(span, property.as_str())?;
+
+            let ty = Self::setter_type(sig)?;
+
+            let observed = attrs.observed().is_some();
+
+            let signature_polymorphism = SignaturePolymorphism::new(
+                &sig.ident,
+                &mut sig.generics,
+                &mut sig.inputs,
+                &sig.output,
+            )?;
+
+            return Ok(Some(Self {
+                attrs,
+                span,
+                name: None,
+                doc,
+                signature_polymorphism,
+                kind: FnKind::Setter {
+                    ident: &sig.ident,
+                    ty,
+                    property,
+                    observed,
+                },
+            }));
+        }
+
+        let name = match attrs.has_rename_variables() {
+            true => None,
+
+            false => {
+                let name = match attrs.rename_checked(&EmptyPolymorphism)? {
+                    Some(name) => name,
+
+                    None => {
+                        let name = sig.ident.to_string();
+
+                        check_script_name(span, name.as_str())?;
+
+                        name
+                    }
+                };
+
+                let name_ref = Context.make_unique_identifier(name.as_str(), span);
+
+                Some((name, name_ref))
+            }
         };
 
         let signature_polymorphism = SignaturePolymorphism::new(
@@ -975,8 +1290,25 @@ impl<'a> FnMeta<'a> {
             &sig.output,
         )?;
 
+        if attrs.getter().is_some() {
+            let ty = Self::getter_type(sig)?;
+
+            return Ok(Some(Self {
+                attrs,
+                span,
+                name,
+                doc,
+                signature_polymorphism,
+                kind: FnKind::Getter {
+                    ident: &sig.ident,
+                    ty,
+                    setter: None,
+                },
+            }));
+        }
+
         let kind = match attrs.component().is_some() {
-            false => FnKind::Invocation(Invocation::new(sig)?),
+            false => FnKind::Invocation(Invocation::new(sig, None)?),
 
             true => {
                 if !sig.generics.params.is_empty() {
@@ -1000,6 +1332,61 @@ impl<'a> FnMeta<'a> {
         }))
     }
 
+    // Checks that the signature is `fn(&self) -> T`, and returns `T`.
+    fn getter_type(sig: &Signature) -> Result<Type> {
+        let mut inputs = sig.inputs.iter();
+
+        match (inputs.next(), inputs.next()) {
+            (Some(FnArg::Receiver(receiver)), None)
+                if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+
+            _ => {
+                return Err(Error::new(
+                    sig.inputs.span(),
+                    "Property getter must have a single &self parameter.",
+                ))
+            }
+        }
+
+        match &sig.output {
+            ReturnType::Type(_, ty) => Ok(ty.as_ref().clone()),
+
+            ReturnType::Default => Err(Error::new(
+                sig.ident.span(),
+                "Property getter must return a value.",
+            )),
+        }
+    }
+
+    // Checks that the signature is `fn(&mut self, value: T)`, and returns `T`.
+    fn setter_type(sig: &Signature) -> Result<Type> {
+        let mut inputs = sig.inputs.iter();
+
+        let ty = match (inputs.next(), inputs.next(), inputs.next()) {
+            (Some(FnArg::Receiver(receiver)), Some(FnArg::Typed(value)), None)
+                if receiver.reference.is_some() && receiver.mutability.is_some() =>
+            {
+                value.ty.as_ref().clone()
+            }
+
+            _ => {
+                return Err(Error::new(
+                    sig.inputs.span(),
+                    "Property setter must have a &mut self parameter and a value parameter.",
+                ))
+            }
+        };
+
+        if let ReturnType::Type(_, output) = &sig.output {
+            return Err(Error::new(
+                output.span(),
+                "Property setter must not return a value.",
+            ));
+        }
+
+        Ok(ty)
+    }
+
     #[inline]
     fn export<S: PolymorphicScope>(
         &mut self,
@@ -1017,105 +1404,225 @@ impl<'a> FnMeta<'a> {
                 signature: &self.signature_polymorphism,
             };
 
-            let name;
-            let name_ref;
+            if function_polymorphism.check_bounds(group)? {
+                let name;
+                let name_ref;
 
-            match &self.name {
-                Some((name_string, name_reference)) => {
-                    name = Cow::Borrowed(name_string);
-                    name_ref = name_reference.clone();
-                }
+                match &self.name {
+                    Some((name_string, name_reference)) => {
+                        name = Cow::Borrowed(name_string);
+                        name_ref = name_reference.clone();
+                    }
 
-                None => {
-                    let name_string = self
-                        .attrs
-                        .rename_checked(&function_polymorphism)?
-                        .expect("Internal error. Missing function name.");
+                    None => {
+                        let name_string = self
+                            .attrs
+                            .rename_checked(&function_polymorphism)?
+                            .expect("Internal error. Missing function name.");
 
-                    name_ref = Context.make_shared_identifier(name_string.as_str(), span);
-                    name = Cow::Owned(name_string)
-                }
-            };
+                        name_ref = Context.make_shared_identifier(name_string.as_str(), span);
+                        name = Cow::Owned(name_string)
+                    }
+                };
+
+                match &self.kind {
+                    FnKind::Invocation(invocation) => {
+                        let function_type = invocation.make_function_type(
+                            group,
+                            &function_polymorphism,
+                            name.as_str(),
+                            &name_ref,
+                            self.doc.clone(),
+                        )?;
+
+                        let component = match invocation.uses_receiver() {
+                            true => quote_spanned!(span=> fn component(
+                                origin: #core::runtime::Origin,
+                                lhs: #core::runtime::Arg,
+                            ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                                #core::runtime::Cell::give(origin, #function_type(lhs))
+                            }),
+
+                            false => quote_spanned!(span=> fn component(
+                                origin: #core::runtime::Origin,
+                                _lhs: #core::runtime::Arg,
+                            ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                                #core::runtime::Cell::give(origin, #function_type)
+                            }),
+                        };
 
-            match &self.kind {
-                FnKind::Invocation(invocation) => {
-                    let function_type = invocation.make_function_type(
-                        group,
-                        &function_polymorphism,
-                        name.as_str(),
-                        &name_ref,
-                        self.doc.clone(),
-                    )?;
-
-                    let component = match invocation.uses_receiver() {
-                        true => quote_spanned!(span=> fn component(
-                            origin: #core::runtime::Origin,
-                            lhs: #core::runtime::Arg,
-                        ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
-                            #core::runtime::Cell::give(origin, #function_type(lhs))
-                        }),
-
-                        false => quote_spanned!(span=> fn component(
-                            origin: #core::runtime::Origin,
-                            _lhs: #core::runtime::Arg,
-                        ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
-                            #core::runtime::Cell::give(origin, #function_type)
-                        }),
-                    };
-
-                    let constructor = quote_spanned!(span=> {
-                        #component
-
-                        component as fn(
-                            #core::runtime::Origin,
-                            #core::runtime::Arg,
-                        ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
-                    });
-
-                    prototype.component(Component {
-                        name_ref: Cow::Owned(name_ref),
-                        constructor,
-                        hint: Cow::Owned(function_type),
-                        doc: self.doc.clone(),
-                    });
-                }
+                        let constructor = quote_spanned!(span=> {
+                            #component
+
+                            component as fn(
+                                #core::runtime::Origin,
+                                #core::runtime::Arg,
+                            ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
+                        });
+
+                        prototype.component(Component {
+                            name_ref: Cow::Owned(name_ref),
+                            constructor,
+                            hint: Cow::Owned(function_type),
+                            doc: self.doc.clone(),
+                            owned: true,
+                            pure: false,
+                            property: false,
+                            setter: None,
+                            deprecated: self.attrs.deprecated().cloned(),
+                            hidden: self.attrs.hidden(),
+                            observed: false,
+                            casted: false,
+                        });
+                    }
 
-                FnKind::Component(ident) => {
-                    let mut ty = self
-                        .attrs
-                        .component()
-                        .expect("Internal error. Missing component type.")
-                        .clone();
+                    FnKind::Component(ident) => {
+                        let mut ty = self
+                            .attrs
+                            .component()
+                            .expect("Internal error. Missing component type.")
+                            .clone();
 
-                    function_polymorphism.specialize_type(&mut ty)?;
+                        function_polymorphism.specialize_type(&mut ty)?;
 
-                    let self_type = function_polymorphism
-                        .get_self_type()?
-                        .expect("Internal error. Missing self type.");
-                    let trait_type = function_polymorphism.get_trait_type()?;
-
-                    let component = match trait_type {
-                        None => quote_spanned!(span=> <#self_type>::#ident),
-
-                        Some(trait_type) => quote_spanned!(span=>
-                            <#self_type as #trait_type>::#ident),
-                    };
-
-                    let constructor = quote_spanned!(span=>
-                        #component as fn(
-                            #core::runtime::Origin,
-                            #core::runtime::Arg,
-                        ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
-                    );
-
-                    Shallow.assert_ref_type_impls_static_upcast(&ty, span);
-
-                    prototype.component(Component {
-                        name_ref: Cow::Owned(name_ref),
-                        constructor,
-                        hint: Cow::Owned(ty),
-                        doc: self.doc.clone(),
-                    });
+                        let self_type = function_polymorphism
+                            .get_self_type()?
+                            .expect("Internal error. Missing self type.");
+                        let trait_type = function_polymorphism.get_trait_type()?;
+
+                        let component = match trait_type {
+                            None => quote_spanned!(span=> <#self_type>::#ident),
+
+                            Some(trait_type) => quote_spanned!(span=>
+                                <#self_type as #trait_type>::#ident),
+                        };
+
+                        let constructor = quote_spanned!(span=>
+                            #component as fn(
+                                #core::runtime::Origin,
+                                #core::runtime::Arg,
+                            ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
+                        );
+
+                        Shallow.assert_ref_type_impls_static_upcast(&ty, span);
+
+                        prototype.component(Component {
+                            name_ref: Cow::Owned(name_ref),
+                            constructor,
+                            hint: Cow::Owned(ty),
+                            doc: self.doc.clone(),
+                            owned: false,
+                            pure: false,
+                            property: false,
+                            setter: None,
+                            deprecated: self.attrs.deprecated().cloned(),
+                            hidden: self.attrs.hidden(),
+                            observed: false,
+                            casted: false,
+                        });
+                    }
+
+                    FnKind::Getter { ident, ty, setter } => {
+                        let mut ty = ty.clone();
+
+                        function_polymorphism.specialize_type(&mut ty)?;
+
+                        let self_type = function_polymorphism
+                            .get_self_type()?
+                            .expect("Internal error. Missing self type.");
+                        let trait_type = function_polymorphism.get_trait_type()?;
+
+                        let path = |ident: &Ident| match &trait_type {
+                            None => quote_spanned!(span=> <#self_type>::#ident),
+
+                            Some(trait_type) => quote_spanned!(span=>
+                                <#self_type as #trait_type>::#ident),
+                        };
+
+                        let getter = path(ident);
+
+                        let constructor = quote_spanned!(span=> {
+                            fn component(
+                                origin: #core::runtime::Origin,
+                                mut lhs: #core::runtime::Arg,
+                            ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                                let receiver = #core::runtime::Cell::borrow_ref::<#self_type>(
+                                    &mut lhs.data,
+                                    lhs.origin,
+                                )?;
+
+                                #core::runtime::Cell::give(origin, #getter(receiver))
+                            }
+
+                            component as fn(
+                                #core::runtime::Origin,
+                                #core::runtime::Arg,
+                            ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
+                        });
+
+                        let observed = matches!(setter, Some((_, _, true)));
+
+                        let setter = match setter {
+                            None => None,
+
+                            Some((ident, value_type, _)) => {
+                                let mut value_type = value_type.clone();
+
+                                function_polymorphism.specialize_type(&mut value_type)?;
+
+                                Shallow.assert_type_impls_downcast(&value_type, span);
+
+                                let setter = path(ident);
+
+                                Some(quote_spanned!(span=> {
+                                    fn setter(
+                                        _origin: #core::runtime::Origin,
+                                        mut lhs: #core::runtime::Arg,
+                                        mut rhs: #core::runtime::Arg,
+                                    ) -> #core::runtime::RuntimeResult<()> {
+                                        let value = <#value_type as #core::runtime::Downcast>::downcast(
+                                            rhs.origin,
+                                            #core::runtime::Provider::Borrowed(&mut rhs.data),
+                                        )?;
+
+                                        let receiver = #core::runtime::Cell::borrow_mut::<#self_type>(
+                                            &mut lhs.data,
+                                            lhs.origin,
+                                        )?;
+
+                                        #setter(receiver, value);
+
+                                        #core::runtime::RuntimeResult::<()>::Ok(())
+                                    }
+
+                                    setter as fn(
+                                        #core::runtime::Origin,
+                                        #core::runtime::Arg,
+                                        #core::runtime::Arg,
+                                    ) -> #core::runtime::RuntimeResult::<()>
+                                }))
+                            }
+                        };
+
+                        Shallow.assert_type_impls_script_type(&ty, span);
+
+                        prototype.component(Component {
+                            name_ref: Cow::Owned(name_ref),
+                            constructor,
+                            hint: Cow::Owned(ty),
+                            doc: self.doc.clone(),
+                            owned: false,
+                            pure: false,
+                            property: true,
+                            setter,
+                            deprecated: self.attrs.deprecated().cloned(),
+                            hidden: self.attrs.hidden(),
+                            observed,
+                            casted: false,
+                        });
+                    }
+
+                    FnKind::Setter { .. } => (),
                 }
             }
 
@@ -1131,4 +1638,15 @@ impl<'a> FnMeta<'a> {
 enum FnKind<'a> {
     Invocation(Invocation<'a>),
     Component(&'a Ident),
+    Getter {
+        ident: &'a Ident,
+        ty: Type,
+        setter: Option<(&'a Ident, Type, bool)>,
+    },
+    Setter {
+        ident: &'a Ident,
+        ty: Type,
+        property: String,
+        observed: bool,
+    },
 }
@@ -0,0 +1,376 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::borrow::Cow;
+
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote_spanned, ToTokens};
+use syn::{spanned::Spanned, Error, Item, ItemConst, ItemMod, LitStr, Result, Type, Visibility};
+
+use crate::{
+    export::ExportConfig,
+    utils::{
+        check_script_name,
+        new_type,
+        seed_hash_set,
+        Coercion,
+        Component,
+        Context,
+        EmptyPolymorphism,
+        Exportable,
+        Facade,
+        Group,
+        OriginRef,
+        Prototype,
+        Shallow,
+        TypeFamily,
+        TypeMeta,
+        TypeUtils,
+        CONSTS,
+        DUMP,
+        EXCLUDED,
+        INCLUDED,
+        RENAME,
+        SHALLOW,
+        UNSPECIFIED,
+    },
+};
+
+pub fn export_item_mod(item: &mut ItemMod) -> Result<ExportConfig> {
+    let attrs = item.drain_attrs()?;
+
+    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | RENAME | CONSTS)?;
+
+    let flat = attrs.consts()?;
+
+    Shallow.init(attrs.shallow());
+
+    let span = item.ident.span();
+    let doc = item.rust_doc();
+
+    let rename = attrs.rename_checked(&EmptyPolymorphism)?;
+
+    if flat && rename.is_some() {
+        return Err(Error::new(
+            span,
+            "Flat constants table has no name and cannot be renamed.",
+        ));
+    }
+
+    let Some((_, content)) = &mut item.content else {
+        return Err(Error::new(
+            span,
+            "Only inline modules can be exported as constants tables.",
+        ));
+    };
+
+    let origin = Context.primary_origin();
+    let table = Context.make_static_name("consts", span);
+
+    let mut entries = Vec::new();
+    let mut names = seed_hash_set();
+
+    for content_item in content.iter_mut() {
+        let Item::Const(constant) = content_item else {
+            continue;
+        };
+
+        let Some(entry) = ConstEntry::new(constant)? else {
+            continue;
+        };
+
+        if !names.insert(entry.name.value()) {
+            return Err(Error::new(
+                entry.name.span(),
+                "Duplicate constant name in the constants table.",
+            ));
+        }
+
+        entries.push(entry);
+    }
+
+    let mut group = Group::default();
+
+    {
+        let intrinsics = span.face_intrinsics();
+        let len = entries.len();
+        let entries = entries.iter().map(|entry| entry.to_stream(&origin));
+
+        group.custom(quote_spanned!(span=>
+            static #table: [#intrinsics::ConstDeclaration; #len] = [#(
+                #entries,
+            )*];
+        ));
+    }
+
+    match flat {
+        true => {
+            let mut package_prototype = Prototype::for_package(span);
+
+            package_prototype.constants(table);
+
+            group.prototype(package_prototype);
+        }
+
+        false => {
+            let name = rename.unwrap_or_else(|| item.ident.to_string());
+            let name_ref = Context.make_unique_identifier(name.as_str(), span);
+
+            let ty = new_type(Context.make_type_name(name.as_str(), span));
+
+            group.custom(quote_spanned!(span=>
+                #[allow(non_camel_case_types)]
+                struct #ty;
+            ));
+
+            group.custom(ty.impl_registered_type());
+            group.custom(ty.impl_coercion(Coercion {
+                upcast_own: true,
+
+                ..Coercion::default()
+            }));
+
+            group.type_meta(TypeMeta {
+                name: &LitStr::new(name.to_case(Case::UpperCamel).as_str(), span),
+                doc: doc.as_ref(),
+                ty: &ty,
+                family: TypeFamily::Unique,
+            });
+
+            let mut table_prototype = Prototype::for_type(&ty);
+
+            table_prototype.constants(table);
+
+            group.prototype(table_prototype);
+
+            let constructor = {
+                let core = span.face_core();
+
+                quote_spanned!(span=> {
+                    fn component(
+                        origin: #core::runtime::Origin,
+                        _lhs: #core::runtime::Arg,
+                    ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                        #core::runtime::Cell::give(origin, #ty)
+                    }
+
+                    component as fn(
+                        #core::runtime::Origin,
+                        #core::runtime::Arg,
+                    ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
+                })
+            };
+
+            let mut package_prototype = Prototype::for_package(span);
+
+            package_prototype.component(Component {
+                name_ref: Cow::Owned(name_ref),
+                constructor,
+                hint: Cow::Borrowed(&ty),
+                doc,
+                owned: true,
+                pure: false,
+                property: false,
+                setter: None,
+                deprecated: None,
+                hidden: false,
+                observed: false,
+                casted: false,
+            });
+
+            group.prototype(package_prototype);
+        }
+    }
+
+    Ok(ExportConfig {
+        dump: attrs.dump(),
+        detached: false,
+        stream: match attrs.disabled() {
+            true => None,
+            false => {
+                let stream = match attrs.shallow() {
+                    true => Shallow.to_token_stream(),
+                    false => group.to_token_stream(),
+                };
+
+                content.push(Item::Verbatim(stream));
+
+                Some(TokenStream::new())
+            }
+        },
+    })
+}
+
+struct ConstEntry {
+    ident: Ident,
+    name: LitStr,
+    doc: Option<LitStr>,
+    variant: Ident,
+}
+
+impl ConstEntry {
+    fn new(item: &mut ItemConst) -> Result<Option<Self>> {
+        let attrs = item.drain_attrs()?;
+
+        attrs.check(UNSPECIFIED | INCLUDED | EXCLUDED | RENAME)?;
+
+        if attrs.excluded() {
+            return Ok(None);
+        }
+
+        let included_explicitly = match &item.vis {
+            Visibility::Public(..) => true,
+            _ => false,
+        };
+
+        if !attrs.included() && !included_explicitly && !attrs.specified() {
+            return Ok(None);
+        }
+
+        if !item.generics.params.is_empty() {
+            return Err(Error::new(
+                item.generics.span(),
+                "Constants with generics are not supported by the introspection system.",
+            ));
+        }
+
+        let Some(variant) = Self::variant(item.ty.as_ref()) else {
+            return Err(Error::new(
+                item.ty.span(),
+                "Only boolean, numeric, and string constants can be exported \
+                within the constants table. Exclude this constant using the \
+                #[export(exclude)] attribute.",
+            ));
+        };
+
+        let span = item.ident.span();
+
+        let name = attrs
+            .rename_checked(&EmptyPolymorphism)?
+            .unwrap_or_else(|| item.ident.to_string());
+
+        check_script_name(span, name.as_str())?;
+
+        let doc = match Shallow.enabled() {
+            true => None,
+
+            false => {
+                let value = format!("Value: `{}`", item.expr.to_token_stream());
+
+                Some(match item.rust_doc() {
+                    Some(doc) => LitStr::new(&format!("{}\n\n{value}", doc.value()), doc.span()),
+                    None => LitStr::new(&value, span),
+                })
+            }
+        };
+
+        Ok(Some(Self {
+            ident: item.ident.clone(),
+            name: LitStr::new(name.as_str(), span),
+            doc,
+            variant: Ident::new(variant, span),
+        }))
+    }
+
+    fn variant(ty: &Type) -> Option<&'static str> {
+        match ty {
+            Type::Group(ty) => Self::variant(ty.elem.as_ref()),
+
+            Type::Paren(ty) => Self::variant(ty.elem.as_ref()),
+
+            Type::Reference(ty) if ty.mutability.is_none() => {
+                let Type::Path(elem) = ty.elem.as_ref() else {
+                    return None;
+                };
+
+                match elem.qself.is_none() && elem.path.is_ident("str") {
+                    true => Some("Str"),
+                    false => None,
+                }
+            }
+
+            Type::Path(ty) if ty.qself.is_none() => {
+                let ident = ty.path.get_ident()?.to_string();
+
+                Some(match ident.as_str() {
+                    "bool" => "Bool",
+                    "u8" => "U8",
+                    "u16" => "U16",
+                    "u32" => "U32",
+                    "u64" => "U64",
+                    "u128" => "U128",
+                    "usize" => "Usize",
+                    "i8" => "I8",
+                    "i16" => "I16",
+                    "i32" => "I32",
+                    "i64" => "I64",
+                    "i128" => "I128",
+                    "isize" => "Isize",
+                    "f32" => "F32",
+                    "f64" => "F64",
+                    _ => return None,
+                })
+            }
+
+            _ => None,
+        }
+    }
+
+    fn to_stream(&self, origin: &OriginRef) -> TokenStream {
+        let span = self.ident.span();
+
+        let core = span.face_core();
+        let intrinsics = span.face_intrinsics();
+        let option = span.face_option();
+
+        let ident = &self.ident;
+        let name = &self.name;
+        let variant = &self.variant;
+
+        let doc = match &self.doc {
+            Some(doc) => quote_spanned!(span=> #option::Some(#doc)),
+            None => quote_spanned!(span=> #option::None),
+        };
+
+        quote_spanned!(span=> #intrinsics::ConstDeclaration {
+            name: #core::runtime::RustIdent {
+                origin: &#origin,
+                string: #name,
+            },
+            doc: #doc,
+            value: #intrinsics::ConstValue::#variant(#ident),
+        })
+    }
+}
@@ -35,22 +35,28 @@
 use std::borrow::Cow;
 
 use quote::{quote_spanned, ToTokens};
-use syn::{spanned::Spanned, ItemStatic, Result};
+use syn::{spanned::Spanned, Error, GenericArgument, ItemStatic, PathArguments, Result, Type};
 
 use crate::{
     export::ExportConfig,
     utils::{
+        check_script_name,
         Component,
         Context,
         EmptyPolymorphism,
         Exportable,
         Facade,
         Group,
+        Namespace,
         Prototype,
         Shallow,
+        ACCESS,
+        DEPRECATED,
         DUMP,
         EXCLUDED,
+        HIDDEN,
         INCLUDED,
+        NAMESPACE,
         RENAME,
         SHALLOW,
     },
@@ -59,7 +65,16 @@ use crate::{
 pub fn export_item_static(item: &mut ItemStatic) -> Result<ExportConfig> {
     let attrs = item.drain_attrs()?;
 
-    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | RENAME)?;
+    attrs.check(
+        DUMP | INCLUDED | EXCLUDED | SHALLOW | RENAME | ACCESS | DEPRECATED | HIDDEN | NAMESPACE,
+    )?;
+
+    if let Some(span) = attrs.writeonly_span() {
+        return Err(Error::new(
+            span,
+            "Exported statics are read-only. Writeonly marker is not applicable here.",
+        ));
+    }
 
     Shallow.init(attrs.shallow());
 
@@ -68,25 +83,79 @@ pub fn export_item_static(item: &mut ItemStatic) -> Result<ExportConfig> {
 
     let mut group = Group::default();
 
-    let mut package_prototype = Prototype::for_package(span);
+    let namespace = attrs.namespace().map(Namespace::new);
+
+    let mut package_prototype = match &namespace {
+        Some(namespace) => {
+            namespace.declare(&mut group);
+
+            Prototype::for_type(namespace.ty())
+        }
+
+        None => Prototype::for_package(span),
+    };
 
     let name = attrs
         .rename_checked(&EmptyPolymorphism)?
         .unwrap_or_else(|| item.ident.to_string());
 
+    check_script_name(span, name.as_str())?;
+
+    if !attrs.disabled() && !attrs.shallow() {
+        Context.claim_package_name(attrs.namespace(), name.as_str(), span)?;
+    }
+
     let name_ref = Context.make_unique_identifier(name.as_str(), span);
 
+    let lock = StaticLock::of(item.ty.as_ref());
+
+    let ty = match &lock {
+        Some((_, ty)) => *ty,
+        None => item.ty.as_ref(),
+    };
+
     let constructor = {
         let core = span.face_core();
 
-        Shallow.assert_ref_type_impls_static_upcast(item.ty.as_ref(), item.ty.span());
+        Shallow.assert_ref_type_impls_static_upcast(ty, ty.span());
+
+        let body = match lock {
+            None => quote_spanned!(span=> #core::runtime::Cell::give(origin, &#ident)),
+
+            Some((StaticLock::Once, _)) => {
+                let option = span.face_option();
+                let once_lock = span.face_once_lock();
+                let string = span.face_string();
+
+                quote_spanned!(span=> match #once_lock::get(&#ident) {
+                    #option::Some(value) => #core::runtime::Cell::give(origin, value),
+
+                    #option::None => #core::runtime::RuntimeResult::Err(
+                        #core::runtime::RuntimeError::UninitializedStatic {
+                            access_origin: origin,
+                            name: #string::from(#name),
+                        },
+                    ),
+                })
+            }
+
+            Some((StaticLock::Lazy, _)) => {
+                let static_type = item.ty.as_ref();
+                let deref = span.face_deref();
+
+                quote_spanned!(span=> #core::runtime::Cell::give(
+                    origin,
+                    <#static_type as #deref>::deref(&#ident),
+                ))
+            }
+        };
 
         quote_spanned!(span=> {
             fn component(
                 origin: #core::runtime::Origin,
                 _lhs: #core::runtime::Arg,
             ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
-                #core::runtime::Cell::give(origin, &#ident)
+                #body
             }
 
             component as fn(
@@ -99,16 +168,25 @@ pub fn export_item_static(item: &mut ItemStatic) -> Result<ExportConfig> {
     package_prototype.component(Component {
         name_ref: Cow::Owned(name_ref),
         constructor,
-        hint: Cow::Borrowed(item.ty.as_ref()),
+        hint: Cow::Borrowed(ty),
         doc: item.rust_doc(),
+        owned: false,
+        pure: false,
+        property: false,
+        setter: None,
+        deprecated: attrs.deprecated().cloned(),
+        hidden: attrs.hidden(),
+        observed: false,
+        casted: false,
     });
 
-    Shallow.assert_type_impls_script_type(item.ty.as_ref(), item.ty.span());
+    Shallow.assert_type_impls_script_type(ty, ty.span());
 
     group.prototype(package_prototype);
 
     Ok(ExportConfig {
         dump: attrs.dump(),
+        detached: false,
         stream: match attrs.disabled() {
             true => None,
             false => match attrs.shallow() {
@@ -118,3 +196,45 @@ pub fn export_item_static(item: &mut ItemStatic) -> Result<ExportConfig> {
         },
     })
 }
+
+// A lazily initialized static value exported through the lock's inner type.
+enum StaticLock {
+    // `static FOO: OnceLock<T>`. The script reads the value if the host has
+    // initialized the lock, and fails with a runtime error otherwise.
+    Once,
+
+    // `static FOO: LazyLock<T>`. The script initializes the value on first
+    // access.
+    Lazy,
+}
+
+impl StaticLock {
+    // Returns the kind of the lock and the type of the locked value if the
+    // `ty` is a OnceLock or LazyLock type.
+    fn of(ty: &Type) -> Option<(Self, &Type)> {
+        let Type::Path(path) = ty else {
+            return None;
+        };
+
+        if path.qself.is_some() {
+            return None;
+        }
+
+        let segment = path.path.segments.last()?;
+
+        let lock = match segment.ident.to_string().as_str() {
+            "OnceLock" => Self::Once,
+            "LazyLock" => Self::Lazy,
+            _ => return None,
+        };
+
+        let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+            return None;
+        };
+
+        match arguments.args.first()? {
+            GenericArgument::Type(inner) => Some((lock, inner)),
+            _ => None,
+        }
+    }
+}
@@ -34,15 +34,21 @@
 
 use std::{borrow::Cow, path::PathBuf};
 
+use proc_macro2::TokenStream;
 use quote::{quote_spanned, ToTokens};
 use syn::{
+    parenthesized,
     spanned::Spanned,
+    token::Paren,
+    Attribute,
     Error,
     Fields,
+    GenericArgument,
     Index,
     ItemStruct,
     LitStr,
     Member,
+    PathArguments,
     Result,
     Type,
     Visibility,
@@ -50,7 +56,9 @@ use syn::{
 
 use crate::{
     export::ExportConfig,
+    schema::is_option,
     utils::{
+        check_script_name,
         Attrs,
         Coercion,
         Component,
@@ -64,6 +72,7 @@ use crate::{
         Operator,
         OperatorOrigin,
         Package,
+        PathUtils,
         PolymorphicScope,
         Printer,
         Prototype,
@@ -82,10 +91,13 @@ use crate::{
         TypePolymorphism,
         TypeUtils,
         ACCESS,
+        DEPRECATED,
         DUMP,
         EXCLUDED,
         FAMILY,
+        HIDDEN,
         INCLUDED,
+        OPAQUE,
         PACKAGE,
         RENAME,
         SHALLOW,
@@ -96,7 +108,7 @@ use crate::{
 pub fn export_item_struct(item: &mut ItemStruct) -> Result<ExportConfig> {
     let attrs = item.drain_attrs()?;
 
-    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | PACKAGE | RENAME | FAMILY)?;
+    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | PACKAGE | RENAME | FAMILY | OPAQUE)?;
 
     Shallow.init(attrs.shallow());
 
@@ -137,11 +149,37 @@ pub fn export_item_struct(item: &mut ItemStruct) -> Result<ExportConfig> {
         }
     };
 
+    if !attrs.opaque() {
+        check_repr(&item.attrs)?;
+    }
+
+    let generic = !item.generics.params.is_empty();
+
     let mut polymorphism = TypePolymorphism::new(&item.ident, &mut item.generics)?;
 
     let mut group = Group::default();
 
-    let field_set = FieldSet::new(&mut item.fields)?;
+    let non_exhaustive = item
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("non_exhaustive"));
+
+    let has_fields = !item.fields.is_empty();
+
+    let field_set = FieldSet::new(&mut item.fields, attrs.opaque())?;
+
+    // A non-exhaustive struct with private fields can only be constructed
+    // through the Rust API, so the default assignment operator would
+    // overwrite the instances bypassing this API.
+    if non_exhaustive && !attrs.opaque() && has_fields && field_set.fields.is_empty() {
+        return Err(Error::new(
+            span,
+            "Cannot export non-exhaustive struct with private fields as a \
+            regular struct.\n\nConsider using the #[export(opaque)] attribute \
+            to export this struct as an opaque type whose instances are created \
+            and manipulated through the exported functions only.",
+        ));
+    }
 
     let clone = match attrs.derive().impls_clone() {
         None => None,
@@ -179,139 +217,163 @@ pub fn export_item_struct(item: &mut ItemStruct) -> Result<ExportConfig> {
     };
 
     loop {
-        let ty = polymorphism.make_type();
+        if polymorphism.check_bounds(&mut group)? {
+            let ty = polymorphism.make_type();
 
-        let name = match &name {
-            Some(name) => Cow::Borrowed(name),
+            let name = match &name {
+                Some(name) => Cow::Borrowed(name),
 
-            None => {
-                let name = attrs
-                    .rename_unchecked(&polymorphism)?
-                    .map(|name| LitStr::new(name.as_str(), span))
-                    .unwrap_or_else(|| ty.to_display_literal());
+                None => {
+                    let name = attrs
+                        .rename_unchecked(&polymorphism)?
+                        .map(|name| LitStr::new(name.as_str(), span))
+                        .unwrap_or_else(|| ty.to_display_literal());
 
-                Cow::Owned(name)
-            }
-        };
+                    Cow::Owned(name)
+                }
+            };
 
-        group.type_meta(TypeMeta {
-            name: name.as_ref(),
-            doc: doc.as_ref(),
-            ty: &ty,
-            family,
-        });
+            group.type_meta(TypeMeta {
+                name: name.as_ref(),
+                doc: doc.as_ref(),
+                ty: &ty,
+                family,
+            });
 
-        let coercion = Coercion {
-            downcast_own: true,
-            downcast_ref: true,
-            downcast_mut: true,
-            upcast_own: true,
-            upcast_ref: true,
-            upcast_mut: true,
-        };
+            let script_struct = match !generic
+                && !attrs.opaque()
+                && !attrs.shallow()
+                && manifest.is_none()
+                && default.is_some()
+            {
+                true => field_set.impl_from_script_struct(&ty, name.as_ref(), doc.as_ref())?,
+                false => None,
+            };
 
-        group.custom(ty.impl_registered_type());
-        group.custom(ty.impl_coercion(coercion));
+            let coercion = Coercion {
+                downcast_own: true,
+                downcast_ref: true,
+                downcast_mut: true,
+                upcast_own: true,
+                upcast_ref: true,
+                upcast_mut: true,
+                script_struct: script_struct.is_some(),
+            };
 
-        Shallow.impl_registered_type(&ty);
-        Shallow.impl_coercion(&ty, coercion);
+            if let Some(script_struct) = script_struct {
+                group.custom(script_struct);
+            }
 
-        let mut prototype = Prototype::for_type(&ty);
+            group.custom(ty.impl_registered_type());
+            group.custom(ty.impl_coercion(coercion));
 
-        prototype.operator(OperatorOrigin::Primary, Operator::Assign);
-        group.custom(ScriptAssign { span, ty: &ty });
+            Shallow.impl_registered_type(&ty);
+            Shallow.impl_coercion(&ty, coercion);
 
-        Shallow.impl_operator(&ty, None, Operator::Assign, span);
+            let mut prototype = Prototype::for_type(&ty);
 
-        prototype.operator(OperatorOrigin::Primary, Operator::Concat);
-        group.custom(ScriptConcat { span, ty: &ty });
+            // Opaque types cannot be overwritten by the scripts.
+            if !attrs.opaque() {
+                prototype.operator(OperatorOrigin::Primary, Operator::Assign);
+                group.custom(ScriptAssign { span, ty: &ty });
 
-        Shallow.impl_operator(&ty, None, Operator::Concat, span);
+                Shallow.impl_operator(&ty, None, Operator::Assign, span);
+            }
 
-        if let Some(origin) = &clone {
-            let span = origin.span();
+            prototype.operator(OperatorOrigin::Primary, Operator::Concat);
+            group.custom(ScriptConcat { span, ty: &ty });
 
-            prototype.operator(OperatorOrigin::Origin(origin), Operator::Clone);
-            group.custom(ScriptClone { span, lhs: &ty });
+            Shallow.impl_operator(&ty, None, Operator::Concat, span);
 
-            Shallow.impl_operator(&ty, None, Operator::Clone, span);
-        }
+            if let Some(origin) = &clone {
+                let span = origin.span();
 
-        if let Some(origin) = &debug {
-            let span = origin.span();
+                prototype.operator(OperatorOrigin::Origin(origin), Operator::Clone);
+                group.custom(ScriptClone { span, lhs: &ty });
 
-            prototype.operator(OperatorOrigin::Origin(origin), Operator::Debug);
-            group.custom(ScriptDebug { span, lhs: &ty });
+                Shallow.impl_operator(&ty, None, Operator::Clone, span);
+            }
 
-            Shallow.impl_operator(&ty, None, Operator::Debug, span);
-        }
+            if let Some(origin) = &debug {
+                let span = origin.span();
 
-        if let Some(origin) = &partial_eq {
-            let span = origin.span();
+                prototype.operator(OperatorOrigin::Origin(origin), Operator::Debug);
+                group.custom(ScriptDebug { span, lhs: &ty });
 
-            prototype.operator(OperatorOrigin::Origin(origin), Operator::PartialEq);
-            group.custom(ScriptPartialEq {
-                span,
-                lhs: &ty,
-                rhs: &ty,
-            });
+                Shallow.impl_operator(&ty, None, Operator::Debug, span);
+            }
 
-            Shallow.impl_operator(&ty, None, Operator::PartialEq, span);
-        }
+            if let Some(origin) = &partial_eq {
+                let span = origin.span();
 
-        if let Some(origin) = &default {
-            let span = origin.span();
+                prototype.operator(OperatorOrigin::Origin(origin), Operator::PartialEq);
+                group.custom(ScriptPartialEq {
+                    span,
+                    lhs: &ty,
+                    rhs: &ty,
+                });
 
-            prototype.operator(OperatorOrigin::Origin(origin), Operator::Default);
-            group.custom(ScriptDefault { span, lhs: &ty });
+                Shallow.impl_operator(&ty, None, Operator::PartialEq, span);
+            }
 
-            Shallow.impl_operator(&ty, None, Operator::Default, span);
-        }
+            if let Some(origin) = &default {
+                let span = origin.span();
 
-        if let Some(origin) = &partial_ord {
-            let span = origin.span();
+                prototype.operator(OperatorOrigin::Origin(origin), Operator::Default);
+                group.custom(ScriptDefault { span, lhs: &ty });
 
-            prototype.operator(OperatorOrigin::Origin(origin), Operator::PartialOrd);
-            group.custom(ScriptPartialOrd {
-                span,
-                lhs: &ty,
-                rhs: &ty,
-            });
+                Shallow.impl_operator(&ty, None, Operator::Default, span);
+            }
 
-            Shallow.impl_operator(&ty, None, Operator::PartialOrd, span);
-        }
+            if let Some(origin) = &partial_ord {
+                let span = origin.span();
 
-        if let Some(origin) = &ord {
-            let span = origin.span();
+                prototype.operator(OperatorOrigin::Origin(origin), Operator::PartialOrd);
+                group.custom(ScriptPartialOrd {
+                    span,
+                    lhs: &ty,
+                    rhs: &ty,
+                });
 
-            prototype.operator(OperatorOrigin::Origin(origin), Operator::Ord);
-            group.custom(ScriptOrd { span, lhs: &ty });
+                Shallow.impl_operator(&ty, None, Operator::PartialOrd, span);
+            }
 
-            Shallow.impl_operator(&ty, None, Operator::Ord, span);
-        }
+            if let Some(origin) = &ord {
+                let span = origin.span();
 
-        if let Some(origin) = &hash {
-            let span = origin.span();
+                prototype.operator(OperatorOrigin::Origin(origin), Operator::Ord);
+                group.custom(ScriptOrd { span, lhs: &ty });
 
-            prototype.operator(OperatorOrigin::Origin(origin), Operator::Hash);
-            group.custom(ScriptHash { span, lhs: &ty });
+                Shallow.impl_operator(&ty, None, Operator::Ord, span);
+            }
 
-            Shallow.impl_operator(&ty, None, Operator::Hash, span);
-        }
+            if let Some(origin) = &hash {
+                let span = origin.span();
 
-        if let Some(manifest) = &manifest {
-            group.package(Package { ty: &ty, manifest });
+                prototype.operator(OperatorOrigin::Origin(origin), Operator::Hash);
+                group.custom(ScriptHash { span, lhs: &ty });
 
-            prototype.manifest(&manifest);
+                Shallow.impl_operator(&ty, None, Operator::Hash, span);
+            }
 
-            Shallow.impl_package(&ty, Context.span());
-        }
+            if let Some(manifest) = &manifest {
+                group.package(Package {
+                    ty: &ty,
+                    manifest,
+                    dependencies: attrs.dependencies(),
+                });
 
-        for field in &field_set.fields {
-            field.export(&ty, &polymorphism, &mut prototype)?;
-        }
+                prototype.manifest(&manifest);
+
+                Shallow.impl_package(&ty, Context.span());
+            }
+
+            for field in &field_set.fields {
+                field.export(&ty, &polymorphism, &mut prototype)?;
+            }
 
-        group.prototype(prototype);
+            group.prototype(prototype);
+        }
 
         if !polymorphism.rotate() {
             break;
@@ -320,6 +382,7 @@ pub fn export_item_struct(item: &mut ItemStruct) -> Result<ExportConfig> {
 
     Ok(ExportConfig {
         dump: attrs.dump(),
+        detached: false,
         stream: match attrs.disabled() {
             true => None,
             false => match attrs.shallow() {
@@ -330,18 +393,158 @@ pub fn export_item_struct(item: &mut ItemStruct) -> Result<ExportConfig> {
     })
 }
 
+// The scripts access the fields of the exported struct through references,
+// which could be unaligned in the packed structs. Since the layout of the
+// structs with C-like and primitive representations is usually controlled by
+// the foreign code, the macro refuses to export any of them as regular
+// structs. The transparent representation and the alignment modifier preserve
+// the Rust layout of the fields.
+fn check_repr(attrs: &[Attribute]) -> Result<()> {
+    for attr in attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("Rust") || meta.path.is_ident("transparent") {
+                return Ok(());
+            }
+
+            if meta.path.is_ident("align") {
+                if meta.input.peek(Paren) {
+                    let content;
+                    parenthesized!(content in meta.input);
+                    let _ = content.parse::<TokenStream>()?;
+                }
+
+                return Ok(());
+            }
+
+            Err(Error::new(
+                meta.path.span(),
+                "Cannot export a struct with non-Rust representation as a \
+                regular struct.\n\nThe scripts access the struct fields by \
+                reference, which is undefined behavior for unaligned or \
+                foreign-owned fields. Consider exporting this struct through \
+                a type alias (#[export] type Alias = Struct;), or using the \
+                #[export(opaque)] attribute.",
+            ))
+        })?;
+    }
+
+    Ok(())
+}
+
+// Raw pointers and interior mutable cells cannot be safely aliased by the
+// script references, so the macro refuses to export the fields that contain
+// them.
+fn check_field_type(ty: &Type) -> Result<()> {
+    match ty {
+        Type::Ptr(..) => Err(Error::new(
+            ty.span(),
+            "Cannot export a field of raw pointer type.\n\nThe scripts \
+            access the fields by reference, and aliasing the raw pointer is \
+            undefined behavior. Consider excluding this field using the \
+            #[export(exclude)] attribute, or exporting the struct through a \
+            type alias (#[export] type Alias = Struct;).",
+        )),
+
+        Type::Array(ty) => check_field_type(&ty.elem),
+        Type::Group(ty) => check_field_type(&ty.elem),
+        Type::Paren(ty) => check_field_type(&ty.elem),
+        Type::Reference(ty) => check_field_type(&ty.elem),
+        Type::Slice(ty) => check_field_type(&ty.elem),
+
+        Type::Tuple(ty) => {
+            for elem in &ty.elems {
+                check_field_type(elem)?;
+            }
+
+            Ok(())
+        }
+
+        Type::Path(ty) => {
+            for segment in &ty.path.segments {
+                if segment.ident == "UnsafeCell" {
+                    return Err(Error::new(
+                        segment.ident.span(),
+                        "Cannot export a field of UnsafeCell type.\n\nThe \
+                        scripts access the fields by reference, and mutating \
+                        the cell content through the shared reference \
+                        bypasses the script borrowing rules. Consider \
+                        excluding this field using the #[export(exclude)] \
+                        attribute, or exporting the struct through a type \
+                        alias (#[export] type Alias = Struct;).",
+                    ));
+                }
+
+                let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                    continue;
+                };
+
+                for argument in &arguments.args {
+                    if let GenericArgument::Type(ty) = argument {
+                        check_field_type(ty)?;
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        _ => Ok(()),
+    }
+}
+
+// The fields of the standard types that are not Script Types, but can be
+// casted to and from the Script Types (e.g., `Option<usize>` or `String`),
+// cannot be exposed to the scripts by reference. Instead, the macro exports
+// such fields as properties that upcast the field value on read and downcast
+// the assigned value on write.
+fn is_casted_type(ty: &Type) -> bool {
+    match ty {
+        Type::Group(ty) => is_casted_type(&ty.elem),
+        Type::Paren(ty) => is_casted_type(&ty.elem),
+        Type::Tuple(ty) => !ty.elems.is_empty(),
+
+        Type::Path(ty) if ty.qself.is_none() => {
+            let path = &ty.path;
+
+            path.matches_option().is_some()
+                || path.matches_result().is_some()
+                || path.matches_box().is_some()
+                || path.matches_arc().is_some()
+                || path.matches_string().is_some()
+        }
+
+        _ => false,
+    }
+}
+
 struct FieldSet<'a> {
     fields: Vec<FieldMeta<'a>>,
 }
 
 impl<'a> FieldSet<'a> {
-    fn new(source: &'a mut Fields) -> Result<Self> {
+    fn new(source: &'a mut Fields, opaque: bool) -> Result<Self> {
         let mut fields = Vec::with_capacity(source.len());
 
         for (index, field) in source.iter_mut().enumerate() {
             let field_attrs = field.drain_attrs()?;
 
-            field_attrs.check(UNSPECIFIED | INCLUDED | EXCLUDED | RENAME | ACCESS)?;
+            if opaque {
+                if field_attrs.specified() {
+                    return Err(Error::new(
+                        field.span(),
+                        "The fields of opaque structs cannot be exported.",
+                    ));
+                }
+
+                continue;
+            }
+
+            field_attrs
+                .check(UNSPECIFIED | INCLUDED | EXCLUDED | RENAME | ACCESS | DEPRECATED | HIDDEN)?;
 
             if field_attrs.excluded() {
                 continue;
@@ -356,6 +559,10 @@ impl<'a> FieldSet<'a> {
                 continue;
             }
 
+            check_field_type(&field.ty)?;
+
+            let casted = is_casted_type(&field.ty);
+
             let readable = field_attrs.readable();
             let writeable = field_attrs.writeable();
 
@@ -393,6 +600,10 @@ impl<'a> FieldSet<'a> {
                 Some(ident) => {
                     member = Member::Named(ident.clone());
 
+                    if field_attrs.rename_unchecked(&EmptyPolymorphism)?.is_none() {
+                        check_script_name(ident.span(), ident.to_string().as_str())?;
+                    }
+
                     name = match field_attrs.has_rename_variables() {
                         true => None,
 
@@ -421,11 +632,122 @@ impl<'a> FieldSet<'a> {
                 name,
                 readable,
                 writeable,
+                casted,
             })
         }
 
         Ok(Self { fields })
     }
+
+    // Implements the FromScriptStruct trait for the exported struct that has
+    // the Default constructor. The implementation constructs the default
+    // instance and assigns the writeable named fields from the script struct
+    // entries.
+    //
+    // Returns None if the struct does not have such fields.
+    fn impl_from_script_struct(
+        &self,
+        ty: &Type,
+        name: &LitStr,
+        doc: Option<&LitStr>,
+    ) -> Result<Option<TokenStream>> {
+        let span = ty.span();
+
+        let core = span.face_core();
+        let option = span.face_option();
+        let result = span.face_result();
+        let default = span.face_default();
+
+        let mut schema_fields = Vec::with_capacity(self.fields.len());
+        let mut assignments = Vec::with_capacity(self.fields.len());
+
+        for field in &self.fields {
+            if !field.writeable {
+                continue;
+            }
+
+            let Member::Named(ident) = &field.member else {
+                continue;
+            };
+
+            let span = ident.span();
+            let field_ty = field.ty;
+
+            let field_name = match field.attrs.rename_checked(&EmptyPolymorphism)? {
+                Some(name) => name,
+
+                None => {
+                    let name = ident.to_string();
+
+                    match name.strip_prefix("r#") {
+                        Some(name) => String::from(name),
+                        None => name,
+                    }
+                }
+            };
+
+            let field_name = LitStr::new(&field_name, span);
+
+            let field_doc = match &field.doc {
+                Some(doc) => quote_spanned!(span=> #option::Some(#doc)),
+                None => quote_spanned!(span=> #option::None),
+            };
+
+            let required = !is_option(field_ty);
+
+            schema_fields.push(quote_spanned!(span=>
+                #core::runtime::FieldSchema {
+                    name: #field_name,
+                    doc: #field_doc,
+                    hint: <#field_ty as #core::runtime::Downcast<'static>>::hint,
+                    required: #required,
+                }
+            ));
+
+            assignments.push(quote_spanned!(span=>
+                if #core::runtime::ScriptStructFields::contains(fields, #field_name) {
+                    this.#ident = #core::runtime::ScriptStructFields::take::<#field_ty>(
+                        fields,
+                        #field_name,
+                    )?;
+                }
+            ));
+        }
+
+        if schema_fields.is_empty() {
+            return Ok(None);
+        }
+
+        let doc = match doc {
+            Some(doc) => quote_spanned!(span=> #option::Some(#doc)),
+            None => quote_spanned!(span=> #option::None),
+        };
+
+        Ok(Some(quote_spanned!(span=>
+            #[allow(non_local_definitions)]
+            impl #core::runtime::FromScriptStruct for #ty {
+                fn schema() -> &'static #core::runtime::StructSchema {
+                    static SCHEMA: #core::runtime::StructSchema = #core::runtime::StructSchema {
+                        name: #name,
+                        doc: #doc,
+                        fields: &[#(#schema_fields),*],
+                    };
+
+                    &SCHEMA
+                }
+
+                fn from_script_struct(
+                    fields: &mut #core::runtime::ScriptStructFields,
+                ) -> #core::runtime::RuntimeResult<Self> {
+                    let mut this = <#ty as #default>::default();
+
+                    #(#assignments)*
+
+                    #result::Ok(this)
+                }
+            }
+        )))
+    }
 }
 
 struct FieldMeta<'a> {
@@ -436,6 +758,7 @@ struct FieldMeta<'a> {
     name: Option<IdRef>,
     readable: bool,
     writeable: bool,
+    casted: bool,
 }
 
 impl<'a> FieldMeta<'a> {
@@ -458,6 +781,10 @@ impl<'a> FieldMeta<'a> {
             ty
         };
 
+        if self.casted {
+            return self.export_casted(receiver_type, component_type, polymorphism, prototype);
+        }
+
         let member = &self.member;
 
         let by_ref = match self.readable {
@@ -529,28 +856,146 @@ impl<'a> FieldMeta<'a> {
 
         Shallow.assert_type_impls_script_type(&component_type, span);
 
-        let name_ref = match &self.name {
-            Some(name) => Cow::Borrowed(name),
+        prototype.component(Component {
+            name_ref: self.name_ref(polymorphism)?,
+            constructor,
+            hint: Cow::Owned(component_type),
+            doc: self.doc.clone(),
+            owned: false,
+            pure: false,
+            property: false,
+            setter: None,
+            deprecated: self.attrs.deprecated().cloned(),
+            hidden: self.attrs.hidden(),
+            observed: false,
+            casted: false,
+        });
+
+        Ok(())
+    }
+
+    fn export_casted(
+        &'a self,
+        receiver_type: &Type,
+        component_type: Type,
+        polymorphism: &TypePolymorphism<'_>,
+        prototype: &mut Prototype<'a>,
+    ) -> Result<()> {
+        let span = self.member.span();
+
+        let core = span.face_core();
+        let clone = span.face_clone();
+
+        let member = &self.member;
+
+        let read = match self.readable {
+            false => quote_spanned!(span=>
+                #core::runtime::RuntimeResult::<#core::runtime::Cell>::Err(
+                    #core::runtime::RuntimeError::WriteOnly {
+                        access_origin: origin,
+                        data_origin: lhs.origin,
+                    },
+                )
+            ),
+
+            true => quote_spanned!(span=> {
+                let receiver = #core::runtime::Cell::borrow_ref::<#receiver_type>(
+                    &mut lhs.data,
+                    lhs.origin,
+                )?;
+
+                #core::runtime::Cell::give(
+                    origin,
+                    <#component_type as #clone>::clone(&receiver.#member),
+                )
+            }),
+        };
 
-            None => {
-                let name = self
-                    .attrs
-                    .rename_checked(polymorphism)?
-                    .expect("Missing name.");
+        let constructor = quote_spanned!(span=> {
+            #[allow(unused_mut)]
+            fn component(
+                origin: #core::runtime::Origin,
+                mut lhs: #core::runtime::Arg,
+            ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                #read
+            }
 
-                let name_ref = Context.make_shared_identifier(name.as_str(), member.span());
+            component as fn(
+                #core::runtime::Origin,
+                #core::runtime::Arg,
+            ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
+        });
 
-                Cow::Owned(name_ref)
+        let setter = match self.writeable {
+            false => None,
+
+            true => {
+                Shallow.assert_type_impls_downcast(&component_type, span);
+
+                Some(quote_spanned!(span=> {
+                    fn setter(
+                        _origin: #core::runtime::Origin,
+                        mut lhs: #core::runtime::Arg,
+                        mut rhs: #core::runtime::Arg,
+                    ) -> #core::runtime::RuntimeResult<()> {
+                        let value = <#component_type as #core::runtime::Downcast>::downcast(
+                            rhs.origin,
+                            #core::runtime::Provider::Borrowed(&mut rhs.data),
+                        )?;
+
+                        let receiver = #core::runtime::Cell::borrow_mut::<#receiver_type>(
+                            &mut lhs.data,
+                            lhs.origin,
+                        )?;
+
+                        receiver.#member = value;
+
+                        #core::runtime::RuntimeResult::<()>::Ok(())
+                    }
+
+                    setter as fn(
+                        #core::runtime::Origin,
+                        #core::runtime::Arg,
+                        #core::runtime::Arg,
+                    ) -> #core::runtime::RuntimeResult::<()>
+                }))
             }
         };
 
+        Shallow.assert_type_impls_upcast(&component_type, span);
+
         prototype.component(Component {
-            name_ref,
+            name_ref: self.name_ref(polymorphism)?,
             constructor,
             hint: Cow::Owned(component_type),
             doc: self.doc.clone(),
+            owned: false,
+            pure: false,
+            property: true,
+            setter,
+            deprecated: self.attrs.deprecated().cloned(),
+            hidden: self.attrs.hidden(),
+            // Unlike the property setters, the assignments to the struct
+            // fields are always observed.
+            observed: true,
+            casted: true,
         });
 
         Ok(())
     }
+
+    fn name_ref(&'a self, polymorphism: &TypePolymorphism<'_>) -> Result<Cow<'a, IdRef>> {
+        if let Some(name) = &self.name {
+            return Ok(Cow::Borrowed(name));
+        }
+
+        let name = self
+            .attrs
+            .rename_checked(polymorphism)?
+            .expect("Missing name.");
+
+        Ok(Cow::Owned(
+            Context.make_shared_identifier(name.as_str(), self.member.span()),
+        ))
+    }
 }
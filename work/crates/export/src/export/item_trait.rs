@@ -32,18 +32,21 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use quote::ToTokens;
-use syn::{ItemTrait, Result};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{ItemTrait, Result, TraitItem};
 
 use crate::{
     export::{item_impl::ItemSet, ExportConfig},
     utils::{
         Exportable,
+        Facade,
         Group,
         PolymorphicScope,
         Prototype,
         Shallow,
         TraitPolymorphism,
+        DEFAULTS,
         DUMP,
         EXCLUDED,
         INCLUDED,
@@ -55,63 +58,145 @@ use crate::{
 pub fn export_item_trait(item: &mut ItemTrait) -> Result<ExportConfig> {
     let attrs = item.drain_attrs()?;
 
-    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | TYPE)?;
+    attrs.check(DUMP | INCLUDED | EXCLUDED | SHALLOW | TYPE | DEFAULTS)?;
 
     Shallow.init(attrs.shallow());
 
-    let mut trait_polymorphism =
-        TraitPolymorphism::new(&item.ident, &mut item.generics, attrs.types()?)?;
+    let detached = match attrs.defaults() {
+        None => false,
 
-    let mut group = Group::default();
+        Some(overridden) => {
+            item.items.retain(|member| match member {
+                TraitItem::Fn(function) => !overridden.contains(&function.sig.ident),
+                _ => false,
+            });
 
-    let mut item_set = ItemSet::from_trait_items(&mut item.items)?;
+            true
+        }
+    };
 
-    let mut package_prototype = match item_set.has_package_items() {
-        false => None,
-        true => Some(Prototype::for_package(item.trait_token.span)),
+    let defaults = match detached || attrs.disabled() {
+        true => None,
+        false => export_defaults(item),
     };
 
-    loop {
-        let mut self_prototype = match item_set.has_self_items() {
-            false => None,
+    let mut group = Group::default();
 
-            true => {
-                let ty = trait_polymorphism
-                    .get_self_type()?
-                    .expect("Internal error. Missing self type.");
+    let mut item_set = ItemSet::from_trait_items(&mut item.items)?;
 
-                Some(Prototype::for_type(ty))
-            }
+    if attrs.has_types() {
+        let mut trait_polymorphism =
+            TraitPolymorphism::new(&item.ident, &mut item.generics, attrs.types()?)?;
+
+        let mut package_prototype = match item_set.has_package_items() {
+            false => None,
+            true => Some(Prototype::for_package(item.trait_token.span)),
         };
 
-        item_set.export::<TraitPolymorphism>(
-            &trait_polymorphism,
-            &mut group,
-            &mut self_prototype,
-            &mut package_prototype,
-        )?;
+        loop {
+            if trait_polymorphism.check_bounds(&mut group)? {
+                let mut self_prototype = match item_set.has_self_items() {
+                    false => None,
+
+                    true => {
+                        let ty = trait_polymorphism
+                            .get_self_type()?
+                            .expect("Internal error. Missing self type.");
+
+                        Some(Prototype::for_type(ty))
+                    }
+                };
+
+                item_set.export::<TraitPolymorphism>(
+                    &trait_polymorphism,
+                    &mut group,
+                    &mut self_prototype,
+                    &mut package_prototype,
+                )?;
+
+                if let Some(prototype) = self_prototype {
+                    group.prototype(prototype);
+                }
+            }
 
-        if let Some(prototype) = self_prototype {
-            group.prototype(prototype);
+            if !trait_polymorphism.rotate()? {
+                break;
+            }
         }
 
-        if !trait_polymorphism.rotate()? {
-            break;
+        if let Some(prototype) = package_prototype {
+            group.prototype(prototype);
         }
     }
 
-    if let Some(prototype) = package_prototype {
-        group.prototype(prototype);
-    }
-
     Ok(ExportConfig {
         dump: attrs.dump(),
+        detached,
         stream: match attrs.disabled() {
             true => None,
             false => match attrs.shallow() {
                 true => Some(Shallow.to_token_stream()),
-                false => Some(group.to_token_stream()),
+                false => {
+                    let mut stream = group.to_token_stream();
+
+                    stream.extend(defaults);
+
+                    Some(stream)
+                }
             },
         },
     })
 }
+
+// Generates a hidden macro that exports the default methods of the trait for
+// the implementations annotated with `#[export(include_defaults)]`.
+//
+// The macro shares the name with the trait in the macros namespace, such that
+// importing the trait imports the macro too.
+fn export_defaults(item: &ItemTrait) -> Option<TokenStream> {
+    if !item.generics.params.is_empty() {
+        return None;
+    }
+
+    let members = item
+        .items
+        .iter()
+        .filter_map(|member| match member {
+            TraitItem::Fn(function) if function.default.is_some() => {
+                let mut function = function.clone();
+
+                function.default = None;
+                function.semi_token = Some(Default::default());
+
+                Some(function)
+            }
+
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if members.is_empty() {
+        return None;
+    }
+
+    let ident = &item.ident;
+    let core = ident.face_core();
+    let macro_ident = format_ident!("__ad_astra_defaults_{ident}");
+
+    Some(quote! {
+        #[doc(hidden)]
+        macro_rules! #macro_ident {
+            ([$($ty:tt)*] $($overridden:ident)*) => {
+                #[#core::export(type $($ty)*)]
+                #[export(defaults($($overridden),*))]
+                trait #ident {
+                    #( #members )*
+                }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #macro_ident as #ident;
+    })
+}
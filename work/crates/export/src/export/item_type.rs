@@ -71,30 +71,32 @@ pub fn export_item_type(item: &mut ItemType) -> Result<ExportConfig> {
     let mut group = Group::default();
 
     loop {
-        let ty = polymorphism.make_type();
+        if polymorphism.check_bounds(&mut group)? {
+            let ty = polymorphism.make_type();
 
-        let name = match attrs.rename_unchecked(&polymorphism)? {
-            Some(name) => LitStr::new(name.as_str(), span),
+            let name = match attrs.rename_unchecked(&polymorphism)? {
+                Some(name) => LitStr::new(name.as_str(), span),
 
-            None => {
-                let mut target_type = item.ty.as_ref().clone();
+                None => {
+                    let mut target_type = item.ty.as_ref().clone();
 
-                polymorphism.specialize_type(&mut target_type)?;
+                    polymorphism.specialize_type(&mut target_type)?;
 
-                target_type.to_display_literal()
-            }
-        };
+                    target_type.to_display_literal()
+                }
+            };
 
-        group.type_meta(TypeMeta {
-            name: &name,
-            doc: doc.as_ref(),
-            ty: &ty,
-            family,
-        });
+            group.type_meta(TypeMeta {
+                name: &name,
+                doc: doc.as_ref(),
+                ty: &ty,
+                family,
+            });
 
-        group.custom(ty.impl_registered_type());
+            group.custom(ty.impl_registered_type());
 
-        Shallow.impl_registered_type(&ty);
+            Shallow.impl_registered_type(&ty);
+        }
 
         if !polymorphism.rotate() {
             break;
@@ -103,6 +105,7 @@ pub fn export_item_type(item: &mut ItemType) -> Result<ExportConfig> {
 
     Ok(ExportConfig {
         dump: attrs.dump(),
+        detached: false,
         stream: match attrs.disabled() {
             true => None,
             false => match attrs.shallow() {
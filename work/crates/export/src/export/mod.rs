@@ -35,8 +35,10 @@
 mod config;
 mod item;
 mod item_const;
+mod item_enum;
 mod item_fn;
 mod item_impl;
+mod item_mod;
 mod item_static;
 mod item_struct;
 mod item_trait;
@@ -68,15 +68,14 @@
 //! Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин). All rights reserved.
 
 mod export;
+mod schema;
 mod utils;
 
 use proc_macro::TokenStream;
 use quote::quote_spanned;
-use syn::{parse_macro_input, spanned::Spanned};
+use syn::{parse_macro_input, spanned::Spanned, DeriveInput};
 
-use crate::export::ExportItem;
-
-//todo prevent export of non-Rust reprs.
+use crate::{export::ExportItem, schema::derive_from_script_struct};
 
 /// Exports Rust code to the Script Runtime.
 ///
@@ -123,11 +122,13 @@ use crate::export::ExportItem;
 /// The macro can be applied to the following Rust source code items:
 ///
 /// - Struct declarations: `struct Foo {}`.
+/// - Enum declarations: `enum Foo {}`.
 /// - Static and constant values: `static FOO: usize = 10;`.
 /// - Crate functions: `fn foo() {}`.
 /// - Implementation blocks for types: `impl Foo {}`.
 /// - Implementation blocks for traits: `impl Trait for Foo {}`.
 /// - Trait declarations: `trait Foo {}`.
+/// - Inline modules of constants: `#[export(consts)] mod foo {}`.
 ///
 /// ## Export Options
 ///
@@ -210,6 +211,154 @@ use crate::export::ExportItem;
 /// In the example above, the `Vector` type will be exported even if it is a
 /// private type.
 ///
+/// If a non-generic struct derives the `Default` trait, the exported functions
+/// that accept this struct as a parameter also accept script struct literals:
+/// `foo(struct { x: 1, y: 2 })`. The macro implements the `FromScriptStruct`
+/// trait for such a struct, which constructs the default instance and assigns
+/// the writeable named fields from the script struct entries. Fields of the
+/// `Option` type are optional, and all other writeable fields are required.
+///
+/// The fields of the `Option`, `Result`, `Box`, `String`, and tuple types
+/// are not Script Types, but the macro exports them as well. The scripts
+/// access such fields by value: reading the field upcasts a clone of the
+/// field's value (so the field type must implement `Clone`), and assigning
+/// to the field downcasts the assigned value and overwrites the field. The
+/// static analyzer hints such fields by their casted type (e.g., an
+/// `Option<u8>` field is hinted as a number). The `#[export(readonly)]` and
+/// `#[export(writeonly)]` attributes apply to these fields too.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export]
+/// pub struct Profile {
+///     pub nickname: Option<String>,
+///
+///     #[export(readonly)]
+///     pub position: (u8, u8),
+/// }
+/// ```
+///
+/// The scripts access the other exported fields by reference. For this
+/// reason, the macro refuses to export a regular struct with a non-Rust
+/// representation (such as `#[repr(C)]` or `#[repr(packed)]`), and the
+/// exported fields that contain raw pointers or `UnsafeCell`. The `#[repr(transparent)]` and
+/// `#[repr(align(N))]` representations are allowed. To export such a struct,
+/// exclude the offending fields using the `#[export(exclude)]` attribute, or
+/// export the struct as an opaque type through a type alias or the
+/// `#[export(opaque)]` attribute.
+///
+/// ```compile_fail
+/// # use ad_astra_export::export;
+/// #
+/// // Error: Cannot export a struct with non-Rust representation.
+/// #[export]
+/// #[repr(C, packed)]
+/// pub struct Header {
+///     pub tag: u8,
+///     pub size: u32,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra_export::export;
+/// #
+/// // Error: Cannot export a field of raw pointer type.
+/// #[export]
+/// pub struct Buffer {
+///     pub data: *mut u8,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra_export::export;
+/// # use std::cell::UnsafeCell;
+/// #
+/// // Error: Cannot export a field of UnsafeCell type.
+/// #[export]
+/// pub struct Counter {
+///     pub value: UnsafeCell<usize>,
+/// }
+/// ```
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export]
+/// pub struct Buffer {
+///     pub size: usize,
+///
+///     // The excluded fields are not accessible from scripts.
+///     #[export(exclude)]
+///     pub data: *mut u8,
+/// }
+/// ```
+///
+/// If the struct encapsulates its state entirely, you can export it with the
+/// `#[export(opaque)]` attribute. The macro exports an opaque type without any
+/// fields and without the assignment operator, such that the scripts create
+/// and manipulate the instances solely through the exported functions and
+/// methods. The macro refuses to export a `#[non_exhaustive]` struct with
+/// private fields as a regular struct.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export(opaque)]
+/// #[non_exhaustive]
+/// #[derive(Clone)]
+/// pub struct Body {
+///     mass: f64,
+/// }
+///
+/// #[export]
+/// impl Body {
+///     pub fn body(mass: f64) -> Self {
+///         Self { mass }
+///     }
+///
+///     pub fn mass(&self) -> f64 {
+///         self.mass
+///     }
+/// }
+/// ```
+///
+/// ## Enums Exporting
+///
+/// When applied to the `enum Foo {}` item, the macro exports the type and
+/// exposes a package-level constructor function for each variant. The function
+/// is named `<enum name>_<variant name>` and accepts the variant's fields as
+/// arguments.
+///
+/// - The exported values have a `variant` component that returns the name of
+///   the active variant as a string.
+/// - You can rename the enum and any of its variants using the
+///   `#[export(name <name expression>)]` attribute.
+/// - You can exclude the constructor of a variant using the
+///   `#[export(exclude)]` attribute.
+/// - The `#[derive(...)]` specification is exported in the same way as for
+///   structs.
+/// - Generic enums cannot be exported.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export]
+/// #[derive(Clone, PartialEq)]
+/// pub enum Color {
+///     // Available in scripts as `Color_Red()`.
+///     Red,
+///
+///     // Available in scripts as `Color_Rgb(10, 20, 30)`.
+///     Rgb { r: u8, g: u8, b: u8 },
+///
+///     // Available in scripts as `Color_Gray(50)`, and the `variant`
+///     // component of this value is "Gray".
+///     #[export(name "Gray")]
+///     Grey(u8),
+/// }
+/// ```
+///
 /// ## Exported Types
 ///
 /// Every type exported from Rust to the script environment, regardless of the
@@ -246,8 +395,10 @@ use crate::export::ExportItem;
 ///
 /// Ad Astra also automatically supports upcasting and downcasting for some
 /// standard Rust types, including [Option], [Result],
-/// [BTreeMap](std::collections::BTreeMap), [Box], [Cow](std::borrow::Cow),
-/// [String], standard range types, tuples, static arrays, and slices.
+/// [BTreeMap](std::collections::BTreeMap), [Box], [Arc](std::sync::Arc),
+/// [Rc](std::rc::Rc) (upcasting only), [Cow](std::borrow::Cow), [String],
+/// [Duration](std::time::Duration), [PathBuf](std::path::PathBuf),
+/// standard range types, tuples, static arrays, and slices.
 /// Primitive types (numbers, boolean, unit, and str) are also casted types.
 ///
 /// When exporting a struct, all of its exported fields must be Script Types.
@@ -288,6 +439,20 @@ use crate::export::ExportItem;
 /// The Script Engine interprets script source code semantics on behalf of
 /// the Package.
 ///
+/// If your package relies on the setup of other crates' packages (e.g., a glue
+/// package that registers handlers into a core package), you can declare these
+/// dependencies in the package attribute:
+///
+/// ```ignore
+/// #[export(package, deps(core_crate::Package, other_crate::Package))]
+/// #[derive(Default)]
+/// pub struct Package;
+/// ```
+///
+/// The declared dependencies are available through the
+/// `PackageMeta::dependencies` function, and the `PackageMeta::compose`
+/// function orders a composition of packages according to these declarations.
+///
 /// ## Functions Exporting
 ///
 /// You can export crate-global functions using the Export macro. All such
@@ -311,6 +476,11 @@ use crate::export::ExportItem;
 /// }
 /// ```
 ///
+/// The macro reports a compile-time error if multiple polymorphic instances of
+/// the generic function have the same exported name. The conflicts between
+/// distinct exported items of the crate are detected at runtime by the
+/// `PackageMeta::validate` function.
+///
 /// An exported function may have parameters and a return type. The parameter
 /// types must be downcasted types, and the return type must be an upcasted
 /// type.
@@ -336,7 +506,7 @@ use crate::export::ExportItem;
 ///
 /// You can also use callback functions as parameters and return types.
 ///
-/// Callback functions must be boxed functions with up to 7 upcasted arguments
+/// Callback functions must be boxed functions with up to 12 upcasted arguments
 /// that return a `RuntimeResult` containing a downcasted value.
 ///
 /// ```ignore
@@ -370,6 +540,143 @@ use crate::export::ExportItem;
 /// }
 /// ```
 ///
+/// If the last parameter has the `VariadicArguments` type from the
+/// `runtime::ops` module of the main crate, the function accepts any number of
+/// arguments in this position. The parameter collects the remaining invocation
+/// arguments as they are, together with their origins.
+///
+/// ```ignore
+/// use ad_astra::runtime::ops::{DynamicType, VariadicArguments};
+///
+/// // In scripts, this function can be called as `log()`, `log(1, "two")`, etc.
+/// #[export]
+/// fn log(values: VariadicArguments<DynamicType>) {
+///     println!("{} values", values.len());
+/// }
+/// ```
+///
+/// If the function has no side effects and its result depends only on its
+/// arguments, you can mark it with the `#[export(pure)]` attribute. The static
+/// analyzer may then invoke this function at analysis time when all arguments
+/// of the script call are constants (e.g., `rgb(255, 0, 0)`) to show the
+/// computed value in the code editor. The compiler precomputes such calls too,
+/// if the result's type supports cloning.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export(pure)]
+/// fn rgb(r: u8, g: u8, b: u8) -> u32 {
+///     ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+/// }
+/// ```
+///
+/// The purity marker is the author's promise that the analyzer does not
+/// enforce. However, if a "pure" function returns an error or panics during
+/// the analysis or the compilation, the call is simply not folded.
+///
+/// The `#[export(memoize)]` attribute caches the results of an expensive
+/// function during the script evaluation. When the script calls the function
+/// again with equal hashable arguments, the runtime returns a clone of the
+/// previous result instead of calling the Rust function. The cache is cleared
+/// when the evaluation ends, or manually with
+/// `ad_astra::interpret::clear_memo_cache`.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export(memoize)]
+/// fn fibonacci(n: u64) -> u64 {
+///     (1..n).fold((0, 1), |(a, b), _| (b, a + b)).1
+/// }
+/// ```
+///
+/// Functions with receivers, mutable reference arguments, callback
+/// arguments, or variadic arguments cannot be memoized. The memoization does not affect the static
+/// analysis.
+///
+/// The `#[export(requires <condition>)]` and `#[export(ensures <condition>)]`
+/// attributes declare the function's pre- and postconditions. The runtime
+/// checks the preconditions before calling the Rust function, and the
+/// postconditions over the function's `result` after the call. If a condition
+/// does not hold, the invocation fails with the `ContractViolation` runtime
+/// error that names the violated condition. The static analyzer also reports
+/// constant arguments of the script calls that violate the preconditions.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export(requires alpha >= 0.0 && alpha <= 1.0)]
+/// #[export(ensures result >= 0.0)]
+/// fn opacity(alpha: f32) -> f32 {
+///     alpha * 255.0
+/// }
+/// ```
+///
+/// The conditions are restricted to boolean and numeric literals, references
+/// to the function parameters (or to the `result` in postconditions), the `!`
+/// and `-` unary operators, and the logical, comparison, and arithmetic binary
+/// operators. Functions with receivers cannot declare contracts.
+///
+/// The `#[export(deprecated "<message>")]` attribute marks the function as
+/// deprecated. The function remains available to scripts, but the static
+/// analyzer warns about each reference to it, and the language server marks
+/// the function in the completion lists and hovers with the deprecation
+/// message. The attribute is also applicable to the exported struct fields,
+/// methods, constants, and statics.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export(deprecated "use 'spawn_at' instead")]
+/// fn spawn() {}
+/// ```
+///
+/// The `#[export(hidden)]` attribute excludes the function from the code
+/// completion lists. Otherwise, the hidden function is an ordinary export:
+/// the scripts can call it, the static analyzer resolves it as usual, and the
+/// hovers over its explicit usages show its documentation. The attribute is
+/// useful for migration shims and internal helpers, and it is also applicable
+/// to the exported struct fields, methods, constants, and statics.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export(hidden)]
+/// fn legacy_spawn() {}
+/// ```
+///
+/// A crate-global function may return a Rust iterator
+/// (`impl Iterator<Item = T>`). The export system wraps the iterator into the
+/// lazy `ScriptIterator` object that the script consumes with the `for` loop.
+/// The item type must be an upcasted type, and the iterator must be `Send` and
+/// `'static`.
+///
+/// ```ignore
+/// #[export]
+/// fn countdown(from: usize) -> impl Iterator<Item = usize> {
+///     (0..=from).rev()
+/// }
+/// ```
+///
+/// Functions returning iterators cannot be memoized and cannot declare
+/// postconditions.
+///
+/// The `#[export(block_on)]` attribute exports a crate-global async function.
+/// The exported function passes the function's future to the async runner of
+/// the package (see the `PackageMeta::set_async_runner` function), which
+/// blocks the script's thread until the future completes.
+///
+/// ```ignore
+/// #[export(block_on)]
+/// async fn load_asset(path: &str) -> RuntimeResult<Asset> {
+///     Asset::load(path).await
+/// }
+/// ```
+///
+/// Functions with receivers cannot be async, and async functions cannot
+/// return iterators.
+///
 /// ## Implementation Blocks Exporting
 ///
 /// When you apply the Export macro to a type's implementation block (`impl`),
@@ -464,12 +771,197 @@ use crate::export::ExportItem;
 ///
 /// The function's return value is a `Cell` instance representing the script
 /// data returned to the script. The function can also return a runtime error.
+/// To return composite data, such as script structs and arrays, construct
+/// the Cell using the `ScriptStructBuilder` and `ScriptArrayBuilder` builders
+/// from the `runtime` module.
 ///
 /// The `origin` parameter specifies a script source code range that points to
 /// the accessed field in the script code. The `arg` parameter specifies an
 /// instance of the "Foo" object from which this field has been accessed
 /// (essentially, the "self" receiver).
 ///
+/// ## Properties Exporting
+///
+/// A pair of methods annotated with the `#[export(getter)]` and
+/// `#[export(setter)]` attributes is exported as a single readable and writable
+/// component (a property) of the type.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export]
+/// #[derive(Clone)]
+/// pub struct Speaker {
+///     level: f32,
+/// }
+///
+/// #[export]
+/// impl Speaker {
+///     #[export(getter)]
+///     pub fn volume(&self) -> f32 {
+///         self.level
+///     }
+///
+///     #[export(setter)]
+///     pub fn set_volume(&mut self, volume: f32) {
+///         self.level = volume.clamp(0.0, 1.0);
+///     }
+/// }
+/// ```
+///
+/// In scripts, `speaker.volume` calls the getter, and `speaker.volume = 0.3`
+/// (as well as `speaker.volume += 0.1`) calls the setter.
+///
+/// The getter must have the `fn(&self) -> T` signature, where `T` is a
+/// registered script type. The setter must have the
+/// `fn(&mut self, value: V)` signature, where `V` is a downcasted type. The
+/// property name is the getter's name. The setter's name is either the
+/// property name with the `set_` prefix or the property name itself. Both
+/// names can be changed with the `#[export(name "<name>")]` attribute.
+///
+/// The setter is optional. Assigning to a property that does not have a setter
+/// fails with the read-only data runtime error, and the static analyzer warns
+/// about such assignments.
+///
+/// By default, the script assignments to the properties are not reported to
+/// the write observers of the type (see the `set_write_observer` function of
+/// the `ad_astra::interpret` module). The setter annotated with the additional
+/// `#[export(observed)]` attribute opts in to these notifications.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// # #[export]
+/// # #[derive(Clone)]
+/// # pub struct Speaker {
+/// #     level: f32,
+/// # }
+/// #
+/// #[export]
+/// impl Speaker {
+///     #[export(getter)]
+///     pub fn volume(&self) -> f32 {
+///         self.level
+///     }
+///
+///     #[export(setter)]
+///     #[export(observed)]
+///     pub fn set_volume(&mut self, volume: f32) {
+///         self.level = volume.clamp(0.0, 1.0);
+///     }
+/// }
+/// ```
+///
+/// ## Constants Tables
+///
+/// Exporting a large number of constants one by one is tedious, and each
+/// exported item produces its own portion of the introspection code. Instead,
+/// you can export all constants of an inline module at once using the
+/// `#[export(consts)]` attribute.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// /// Color palette.
+/// #[export(consts)]
+/// pub mod colors {
+///     /// Pure red.
+///     pub const RED: u32 = 0xFF0000;
+///
+///     pub const GREEN: u32 = 0x00FF00;
+///
+///     #[export(name "NAVY")]
+///     pub const DARK_BLUE: u32 = 0x000080;
+///
+///     #[export(exclude)]
+///     pub const PRIMARY: [u32; 2] = [RED, GREEN];
+/// }
+/// ```
+///
+/// The macro registers all public constants of the module as a single
+/// read-only table, accessible in scripts as `crate.colors.RED`. Non-public
+/// constants can be added with the `#[export(include)]` attribute, and public
+/// constants can be skipped with `#[export(exclude)]`. Other items of the
+/// module are left untouched.
+///
+/// The table can be renamed with an additional `#[export(name "palette")]`
+/// attribute. If you prefer to access the constants directly from the package, use the
+/// `#[export(consts flat)]` form, in which case the above constants become
+/// available as `crate.RED`.
+///
+/// Only boolean, numeric, and string (`&str`) constants can be exported this
+/// way. The constant values and names are stored in one static array, and the
+/// static analyzer shows each constant's documentation together with its
+/// value.
+///
+/// ## Lazy Statics
+///
+/// The exported statics usually have constant initializers. If the value is
+/// only known at runtime (e.g., the configuration loaded at startup), you can
+/// export a `OnceLock<T>` or `LazyLock<T>` static, where `T` is a script
+/// type. The scripts see such a static as a read-only value of the `T` type.
+///
+/// ```
+/// # use std::sync::OnceLock;
+/// #
+/// # use ad_astra_export::export;
+/// #
+/// /// The application's configuration.
+/// #[export]
+/// pub static CONFIG_LIMIT: OnceLock<usize> = OnceLock::new();
+///
+/// fn startup() {
+///     let _ = CONFIG_LIMIT.set(100);
+/// }
+/// ```
+///
+/// A `LazyLock` static is initialized on the first script access. If the
+/// script reads a `OnceLock` static before the host initializes it, the
+/// script fails with the `UninitializedStatic` runtime error that points to
+/// the access site.
+///
+/// The exported statics are read-only, and the `#[export(writeonly)]`
+/// attribute is not applicable to them.
+///
+/// ```compile_fail
+/// # use std::sync::OnceLock;
+/// #
+/// # use ad_astra_export::export;
+/// #
+/// #[export]
+/// #[export(writeonly)]
+/// pub static CONFIG_LIMIT: OnceLock<usize> = OnceLock::new();
+/// ```
+///
+/// ## Namespaces
+///
+/// By default, the exported functions, constants, and statics are members of
+/// the package's flat namespace. You can place them into a nested namespace
+/// using the `#[export(namespace "<path>")]` attribute, where `<path>` is a
+/// dot-separated list of namespace names.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export(namespace "math.vec")]
+/// pub fn add(a: f64, b: f64) -> f64 {
+///     a + b
+/// }
+///
+/// #[export(namespace "math")]
+/// pub const PI: f64 = 3.14159;
+/// ```
+///
+/// The scripts access these items as `crate.math.vec.add(1, 2)` and
+/// `crate.math.PI`. The namespaces are shared between all items of the crate
+/// that specify the same path, and the static analyzer suggests the namespace
+/// members in the code completions after each dot.
+///
+/// The exported names must be unique within their namespace only. For
+/// instance, `crate.add` and `crate.math.add` do not conflict with each other.
+/// However, the name of the outermost namespace (`math` in the example above)
+/// must not conflict with other package members.
+///
 /// ## Parametric Polymorphism
 ///
 /// In general, all Script Types are monomorphic concrete Rust types. The Script
@@ -499,6 +991,66 @@ use crate::export::ExportItem;
 /// fn bar<#[export(const 1..=3)] const N: usize, #[export(type bool)] T>(x: [T; N]) {}
 /// ```
 ///
+/// The generic parameters may have trait bounds, both inline and in the `where`
+/// clause, including the bounds that reference other generic parameters, const
+/// generics, or the `Self` type of the `impl` block. The macro substitutes the
+/// enumerated types into these bounds for each specialization.
+///
+/// If the specialization does not satisfy a bound that the macro can check
+/// on its own (e.g., the `Eq` bound on the `f32` type), the macro skips this
+/// specialization and emits a warning pointing to the bound. Otherwise, the
+/// macro asserts the bound at compile time, and the compiler reports an
+/// unsatisfied bound as an error pointing to the bound that names both the
+/// specialization type and the bound.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export]
+/// #[derive(Clone, Default)]
+/// struct Foo<#[export(type usize, f32)] T>
+/// where
+///     T: Clone + Default,
+/// {
+///     pub field: T,
+/// }
+///
+/// // The macro will export the `get` function of the `Foo<usize>` type only,
+/// // because the `f32` type does not implement the `Eq` trait.
+/// #[export]
+/// impl<#[export(type usize, f32)] T> Foo<T>
+/// where
+///     T: Copy + Default + Eq,
+///     Self: Clone,
+/// {
+///     pub fn get(&self) -> T {
+///         self.field
+///     }
+/// }
+///
+/// #[export(name "baz_" Expr:Lower[N])]
+/// fn baz<#[export(const 1..=3)] const N: usize>(x: [u8; N]) -> usize
+/// where
+///     [u8; N]: Default,
+/// {
+///     x.len()
+/// }
+/// ```
+///
+/// ```ignore
+/// # use ad_astra_export::export;
+/// #
+/// // Error: The specialization type "[u8; 40]" does not satisfy the "Default"
+/// // bound of the exported generic item.
+/// #[export(name "baz_" Expr:Lower[N])]
+/// fn baz<#[export(const 1..=3, 40)] const N: usize>(x: [u8; N]) -> usize
+/// where
+///     [u8; N]: Default,
+/// {
+///     x.len()
+/// }
+/// ```
+///
 /// ## Advanced Renaming
 ///
 /// When exporting Rust code with generics, such as the `fn bar` function in the
@@ -586,6 +1138,58 @@ use crate::export::ExportItem;
 /// For example, `Type::Lower[Foo<Bar, Baz>]` will be sanitized to
 /// "foo_bar_baz".
 ///
+/// The macro validates the final names of the referable objects (both the
+/// default names and the names produced by the name expressions) at compile
+/// time. The name must not start with a digit, and it must not be one of the
+/// script language's reserved words: `fn`, `let`, `struct`, `use`, `for`, `in`,
+/// `loop`, `break`, `continue`, `return`, `if`, `else`, `match`, `true`,
+/// `false`, `max`, `len`, `crate`, and `self`. Additionally, the `__adastra`
+/// prefix is reserved for the Ad Astra internals, and the exported names
+/// cannot start with this prefix.
+///
+/// ```compile_fail
+/// # use ad_astra_export::export;
+/// #
+/// // Error: "struct" is a reserved word of the script language.
+/// #[export(name "struct")]
+/// pub fn make_struct() {}
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra_export::export;
+/// #
+/// pub struct Vector;
+///
+/// #[export]
+/// impl Vector {
+///     // Error: "len" is a reserved word of the script language.
+///     pub fn len(&self) -> usize {
+///         0
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra_export::export;
+/// #
+/// // Error: The name starts with the reserved "__adastra" prefix.
+/// #[export(name "__adastra_foo")]
+/// pub fn foo() {}
+/// ```
+///
+/// The validation applies to the final names, so the name expressions that
+/// sanitize into reserved names are rejected as well:
+///
+/// ```compile_fail
+/// # use ad_astra_export::export;
+/// #
+/// pub struct Crate;
+///
+/// // Error: The name "crate" is a reserved word of the script language.
+/// #[export(name Type:Lower[T])]
+/// pub fn foo<#[export(type Crate)] T>() {}
+/// ```
+///
 /// ## Traits Exporting
 ///
 /// The Script Engine does not have a concept of traits. When you export a
@@ -675,7 +1279,43 @@ use crate::export::ExportItem;
 ///
 /// The macro will ignore "method_3" because it is explicitly excluded.
 /// Additionally, the macro will ignore "method_2" because the introspection
-/// system cannot recognize default members of the trait.
+/// system cannot see the default members of the trait from the `impl` block.
+///
+/// To export the default members as well, export the trait itself with the
+/// `#[export]` attribute, and annotate the implementation with the
+/// `#[export(include_defaults)]` attribute. The macro will export each default
+/// method that the implementation does not override.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// #[export]
+/// struct Foo;
+///
+/// // The types list is optional here. Without the list, the macro only
+/// // prepares the default members for the implementations.
+/// #[export]
+/// trait MyTrait {
+///     fn method_1(&self);
+///
+///     // Exported for `Foo` as a default member.
+///     fn method_2(&self) {
+///         todo!()
+///     }
+/// }
+///
+/// #[export(include_defaults)]
+/// impl MyTrait for Foo {
+///     fn method_1(&self) {
+///         todo!()
+///     }
+/// }
+/// ```
+///
+/// This mechanism works for non-generic traits exported within the same
+/// crate. The implementation must have access to the trait by name (e.g.,
+/// through a `use` import or a path), and to the types that the default
+/// members' signatures refer to.
 ///
 /// ## Operators Exporting
 ///
@@ -689,6 +1329,11 @@ use crate::export::ExportItem;
 /// operators, as well as special low-level Script Engine exporting traits from
 /// the `runtime::ops` module of the main crate.
 ///
+/// Exported [Iterator] and [IntoIterator] implementations enable script
+/// iteration of the type's values in the `for` loop: `for item in foo {}`.
+/// The iterator must be `Send` and `'static`, and the item type must be an
+/// upcasted type.
+///
 /// ```
 /// # use ad_astra_export::export;
 /// #
@@ -763,6 +1408,11 @@ use crate::export::ExportItem;
 /// }
 /// ```
 ///
+/// Note that the export system does not see the `#[derive(...)]` attributes of
+/// the aliased type. If you want the Script Engine to format the values of this
+/// type through their Debug implementation (e.g., in the LSP tooltips), export
+/// the operator explicitly: `#[export] impl ScriptDebug for Foo {}`.
+///
 /// Manual exporting is an advanced topic. For more information, refer to the
 /// `runtime` and `runtime::ops` module documentation of the main crate.
 ///
@@ -799,6 +1449,34 @@ use crate::export::ExportItem;
 /// If you do not specify a type family, the type will belong to a unique,
 /// dedicated family consisting solely of that type.
 ///
+/// You can also export a low-level operator once for all types of a family
+/// by annotating the `runtime::ops` trait implementation with the family
+/// attribute. The implementing type does not have to be a registered type;
+/// it only serves as a holder of the implementation. The operator's functions
+/// receive the values of the family types, so the implementation usually
+/// dispatches them dynamically.
+///
+/// ```ignore
+/// struct FooAndBar;
+///
+/// #[export(family &FOO_AND_BAR_FAMILY)]
+/// impl ScriptAdd for FooAndBar {
+///     type RHS = Foo;
+///     type Result = Foo;
+///
+///     fn script_add(origin: Origin, lhs: Arg, rhs: Arg) -> RuntimeResult<Cell> {
+///         todo!()
+///     }
+/// }
+/// ```
+///
+/// If a type of the family exports the same operator itself, the type's own
+/// implementation takes precedence. Exporting the same operator for the same
+/// family twice is an error detected when the Script Engine registers the
+/// exported items. The Clone, Debug, Display, Default, and Hash operators
+/// cannot be exported for a family, because their implementations access the
+/// receiver's data directly.
+///
 /// ## Comments Exporting
 ///
 /// When an exported type, function, constant, or implementation method has a
@@ -822,6 +1500,30 @@ use crate::export::ExportItem;
 /// }
 /// ```
 ///
+/// If a function's RustDoc comment lists its arguments, the macro also
+/// associates each item's text with the corresponding parameter, and the LSP
+/// server displays it in signature help. The macro recognizes Markdown list
+/// items such as ``* `name` - description`` or `- name: description`
+/// (typically under an `# Arguments` heading), as well as
+/// `@param name description` lines. Indented lines following an item continue
+/// its description. Comments that do not follow these conventions are still
+/// exported as is; they just yield no per-parameter text.
+///
+/// ```
+/// # use ad_astra_export::export;
+/// #
+/// /// Computes the sum of two numbers.
+/// ///
+/// /// # Arguments
+/// ///
+/// /// * `a` - The first summand.
+/// /// * `b` - The second summand.
+/// #[export]
+/// pub fn sum(a: usize, b: usize) -> usize {
+///     a + b
+/// }
+/// ```
+///
 /// ## Export Disabling and Debugging
 ///
 /// The main Ad Astra crate has a feature called `export`. When this feature is
@@ -861,3 +1563,30 @@ pub fn export(attr: TokenStream, item: TokenStream) -> TokenStream {
     let output = parse_macro_input!(input as ExportItem);
     output.into()
 }
+
+/// Implements the `FromScriptStruct` and `Downcast` traits for a Rust struct
+/// with named fields.
+///
+/// Exported functions can accept such structs as parameters, and the scripts
+/// pass struct literals to these parameters: `foo(struct { bar: 10 })`.
+///
+/// At runtime, the entries of the script struct are matched to the Rust
+/// struct fields by name and are downcasted to the field types. The fields of
+/// the `Option` type are optional; all other fields are required. Unknown and
+/// missing fields lead to a runtime error.
+///
+/// The static analyzer uses the RustDoc documentation and the types of the
+/// fields to validate struct literals and to suggest field names in code
+/// completions.
+///
+/// See the `FromScriptStruct` trait documentation in the main crate for
+/// details.
+#[proc_macro_derive(FromScriptStruct)]
+pub fn from_script_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match derive_from_script_struct(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
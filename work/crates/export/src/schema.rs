@@ -0,0 +1,210 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use syn::{
+    spanned::Spanned,
+    Attribute,
+    Data,
+    DeriveInput,
+    Error,
+    Expr,
+    Fields,
+    Lit,
+    LitStr,
+    Meta,
+    Result,
+    Type,
+};
+
+use crate::utils::{Facade, PathUtils};
+
+pub fn derive_from_script_struct(input: DeriveInput) -> Result<TokenStream> {
+    let span = input.ident.span();
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            span,
+            "FromScriptStruct can only be derived for structs.",
+        ));
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "FromScriptStruct cannot be derived for generic structs.",
+        ));
+    }
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            span,
+            "FromScriptStruct can only be derived for structs with named fields.",
+        ));
+    };
+
+    let core = span.face_core();
+    let intrinsics = span.face_intrinsics();
+    let option = span.face_option();
+    let result = span.face_result();
+
+    let ident = &input.ident;
+    let name = LitStr::new(&ident.to_string(), span);
+    let doc = make_doc(&input.attrs, span);
+
+    let mut schema_fields = Vec::with_capacity(fields.named.len());
+    let mut constructors = Vec::with_capacity(fields.named.len());
+
+    for field in &fields.named {
+        let Some(field_ident) = &field.ident else {
+            continue;
+        };
+
+        let span = field_ident.span();
+        let ty = &field.ty;
+
+        let field_name = field_ident.to_string();
+        let field_name = LitStr::new(field_name.strip_prefix("r#").unwrap_or(&field_name), span);
+        let field_doc = make_doc(&field.attrs, span);
+        let required = !is_option(ty);
+
+        schema_fields.push(quote_spanned!(span=>
+            #core::runtime::FieldSchema {
+                name: #field_name,
+                doc: #field_doc,
+                hint: <#ty as #core::runtime::Downcast<'static>>::hint,
+                required: #required,
+            }
+        ));
+
+        constructors.push(quote_spanned!(span=>
+            #field_ident: #core::runtime::ScriptStructFields::take::<#ty>(fields, #field_name)?
+        ));
+    }
+
+    Ok(quote_spanned!(span=>
+        impl #core::runtime::FromScriptStruct for #ident {
+            fn schema() -> &'static #core::runtime::StructSchema {
+                static SCHEMA: #core::runtime::StructSchema = #core::runtime::StructSchema {
+                    name: #name,
+                    doc: #doc,
+                    fields: &[#(#schema_fields),*],
+                };
+
+                &SCHEMA
+            }
+
+            fn from_script_struct(
+                fields: &mut #core::runtime::ScriptStructFields,
+            ) -> #core::runtime::RuntimeResult<Self> {
+                #result::Ok(Self {
+                    #(#constructors,)*
+                })
+            }
+        }
+
+        impl<'a> #core::runtime::Downcast<'a> for #ident {
+            #[inline(always)]
+            fn downcast(
+                origin: #core::runtime::Origin,
+                provider: #core::runtime::Provider<'a>,
+            ) -> #core::runtime::RuntimeResult<Self> {
+                #intrinsics::downcast_script_struct::<Self>(origin, provider)
+            }
+
+            #[inline(always)]
+            fn hint() -> #core::runtime::TypeHint {
+                #intrinsics::script_struct_hint()
+            }
+
+            #[inline(always)]
+            fn schema() -> #option<&'static #core::runtime::StructSchema> {
+                #option::Some(<Self as #core::runtime::FromScriptStruct>::schema())
+            }
+        }
+    ))
+}
+
+pub fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Group(ty) => is_option(ty.elem.as_ref()),
+        Type::Paren(ty) => is_option(ty.elem.as_ref()),
+        Type::Path(ty) if ty.qself.is_none() => ty.path.matches_option().is_some(),
+        _ => false,
+    }
+}
+
+fn make_doc(attrs: &[Attribute], span: Span) -> TokenStream {
+    let option = span.face_option();
+
+    let mut result = None;
+
+    for attribute in attrs {
+        let Meta::NameValue(meta) = &attribute.meta else {
+            continue;
+        };
+
+        if !meta.path.is_ident("doc") {
+            continue;
+        }
+
+        let Expr::Lit(value) = &meta.value else {
+            continue;
+        };
+
+        let Lit::Str(value) = &value.lit else {
+            continue;
+        };
+
+        match &mut result {
+            None => result = Some(value.value()),
+
+            Some(result) => {
+                result.push('\n');
+                result.push_str(&value.value());
+            }
+        }
+    }
+
+    match result {
+        None => quote_spanned!(span=> #option::None),
+
+        Some(text) => {
+            let text = LitStr::new(&text, span);
+
+            quote_spanned!(span=> #option::Some(#text))
+        }
+    }
+}
@@ -38,6 +38,7 @@ use convert_case::{Case, Casing};
 use proc_macro2::Span;
 use syn::{
     bracketed,
+    parenthesized,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
@@ -58,18 +59,40 @@ use syn::{
 
 use crate::utils::{seed_hash_set, DeriveMeta, PolymorphicScope, Printer, Shallow, TypeFamily};
 
-pub const UNSPECIFIED: u16 = 1 << 0;
-pub const DUMP: u16 = 1 << 1;
-pub const INCLUDED: u16 = 1 << 2;
-pub const EXCLUDED: u16 = 1 << 3;
-pub const SHALLOW: u16 = 1 << 4;
-pub const RENAME: u16 = 1 << 5;
-pub const TYPE: u16 = 1 << 6;
-pub const CONST: u16 = 1 << 7;
-pub const ACCESS: u16 = 1 << 8;
-pub const FAMILY: u16 = 1 << 9;
-pub const PACKAGE: u16 = 1 << 10;
-pub const COMPONENT: u16 = 1 << 11;
+// Must be kept in sync with the keywords of the script language lexer
+// (see `ScriptToken` in the main crate).
+const RESERVED_WORDS: [&'static str; 19] = [
+    "fn", "let", "struct", "use", "for", "in", "loop", "break", "continue", "return", "if", "else",
+    "match", "true", "false", "max", "len", "crate", "self",
+];
+
+pub const RESERVED_PREFIX: &'static str = "__adastra";
+
+pub const UNSPECIFIED: u32 = 1 << 0;
+pub const DUMP: u32 = 1 << 1;
+pub const INCLUDED: u32 = 1 << 2;
+pub const EXCLUDED: u32 = 1 << 3;
+pub const SHALLOW: u32 = 1 << 4;
+pub const RENAME: u32 = 1 << 5;
+pub const TYPE: u32 = 1 << 6;
+pub const CONST: u32 = 1 << 7;
+pub const ACCESS: u32 = 1 << 8;
+pub const FAMILY: u32 = 1 << 9;
+pub const PACKAGE: u32 = 1 << 10;
+pub const COMPONENT: u32 = 1 << 11;
+pub const PURE: u32 = 1 << 12;
+pub const MEMOIZE: u32 = 1 << 13;
+pub const CONSTS: u32 = 1 << 14;
+pub const CONTRACT: u32 = 1 << 15;
+pub const PROPERTY: u32 = 1 << 16;
+pub const DEPRECATED: u32 = 1 << 17;
+pub const HIDDEN: u32 = 1 << 18;
+pub const OBSERVED: u32 = 1 << 19;
+pub const BLOCK_ON: u32 = 1 << 20;
+pub const OPAQUE: u32 = 1 << 21;
+pub const INCLUDE_DEFAULTS: u32 = 1 << 22;
+pub const DEFAULTS: u32 = 1 << 23;
+pub const NAMESPACE: u32 = 1 << 24;
 
 pub struct Attrs {
     span: Span,
@@ -85,12 +108,33 @@ pub struct Attrs {
     writeonly: Option<Span>,
     family: Option<(Span, Expr)>,
     package: Option<LitStr>,
+    dependencies: Vec<Type>,
     component: Option<(Span, Type)>,
+    pure: Option<Span>,
+    memoize: Option<Span>,
+    consts: Option<(Span, bool)>,
+    contracts: Vec<ContractAttr>,
+    getter: Option<Span>,
+    setter: Option<Span>,
+    deprecated: Option<LitStr>,
+    hidden: Option<Span>,
+    observed: Option<Span>,
+    block_on: Option<Span>,
+    opaque: Option<Span>,
+    include_defaults: Option<Span>,
+    defaults: Option<(Span, Vec<Ident>)>,
+    namespace: Option<LitStr>,
     derive: DeriveMeta,
 }
 
+pub struct ContractAttr {
+    pub span: Span,
+    pub postcondition: bool,
+    pub condition: Expr,
+}
+
 impl Attrs {
-    pub fn check(&self, mask: u16) -> Result<()> {
+    pub fn check(&self, mask: u32) -> Result<()> {
         if mask & UNSPECIFIED == 0 {
             if !self.specified {
                 return Err(self.error(mask));
@@ -216,6 +260,112 @@ impl Attrs {
             }
         }
 
+        if mask & PURE == 0 {
+            if let Some(span) = &self.pure {
+                return Err(Error::new(*span, "Purity marker is not applicable here."));
+            }
+        }
+
+        if mask & MEMOIZE == 0 {
+            if let Some(span) = &self.memoize {
+                return Err(Error::new(
+                    *span,
+                    "Memoization marker is not applicable here.",
+                ));
+            }
+        }
+
+        if mask & CONSTS == 0 {
+            if let Some((span, _)) = &self.consts {
+                return Err(Error::new(
+                    *span,
+                    "Constants table marker is not applicable here.",
+                ));
+            }
+        }
+
+        if mask & CONTRACT == 0 {
+            if let Some(contract) = self.contracts.first() {
+                return Err(Error::new(
+                    contract.span,
+                    "Contract condition is not applicable here.",
+                ));
+            }
+        }
+
+        if mask & PROPERTY == 0 {
+            if let Some(span) = &self.getter {
+                return Err(Error::new(*span, "Getter marker is not applicable here."));
+            }
+
+            if let Some(span) = &self.setter {
+                return Err(Error::new(*span, "Setter marker is not applicable here."));
+            }
+        }
+
+        if mask & DEPRECATED == 0 {
+            if let Some(message) = &self.deprecated {
+                return Err(Error::new(
+                    message.span(),
+                    "Deprecation marker is not applicable here.",
+                ));
+            }
+        }
+
+        if mask & HIDDEN == 0 {
+            if let Some(span) = &self.hidden {
+                return Err(Error::new(*span, "Hidden marker is not applicable here."));
+            }
+        }
+
+        if mask & OBSERVED == 0 {
+            if let Some(span) = &self.observed {
+                return Err(Error::new(
+                    *span,
+                    "Observation marker is not applicable here.",
+                ));
+            }
+        }
+
+        if mask & BLOCK_ON == 0 {
+            if let Some(span) = &self.block_on {
+                return Err(Error::new(*span, "Blocking marker is not applicable here."));
+            }
+        }
+
+        if mask & OPAQUE == 0 {
+            if let Some(span) = &self.opaque {
+                return Err(Error::new(*span, "Opaque marker is not applicable here."));
+            }
+        }
+
+        if mask & INCLUDE_DEFAULTS == 0 {
+            if let Some(span) = &self.include_defaults {
+                return Err(Error::new(
+                    *span,
+                    "Default members inclusion marker is not applicable here.",
+                ));
+            }
+        }
+
+        if mask & DEFAULTS == 0 {
+            if let Some((span, _)) = &self.defaults {
+                return Err(Error::new(
+                    *span,
+                    "Default members specification is not applicable here.",
+                ));
+            }
+        }
+
+        if mask & NAMESPACE == 0 {
+            if let Some(path) = &self.namespace {
+                return Err(Error::new(
+                    path.span(),
+                    "Namespace specification is not applicable here.",
+                ));
+            }
+        }
+
         Ok(())
     }
 
@@ -345,6 +495,19 @@ impl Attrs {
         self.readonly.is_none()
     }
 
+    #[inline]
+    pub fn writeonly_span(&self) -> Option<Span> {
+        self.writeonly
+    }
+
+    #[inline]
+    pub fn family_marker(&self) -> Option<(Span, &Expr)> {
+        match &self.family {
+            Some((span, family)) => Some((*span, family)),
+            None => None,
+        }
+    }
+
     #[inline]
     pub fn family(&self) -> TypeFamily {
         match &self.family {
@@ -358,6 +521,11 @@ impl Attrs {
         self.package.as_ref()
     }
 
+    #[inline]
+    pub fn dependencies(&self) -> &[Type] {
+        &self.dependencies
+    }
+
     #[inline]
     pub fn derive(&self) -> &DeriveMeta {
         &self.derive
@@ -371,6 +539,82 @@ impl Attrs {
         }
     }
 
+    #[inline]
+    pub fn pure(&self) -> bool {
+        self.pure.is_some()
+    }
+
+    #[inline]
+    pub fn getter(&self) -> Option<Span> {
+        self.getter
+    }
+
+    #[inline]
+    pub fn setter(&self) -> Option<Span> {
+        self.setter
+    }
+
+    #[inline]
+    pub fn deprecated(&self) -> Option<&LitStr> {
+        self.deprecated.as_ref()
+    }
+
+    #[inline]
+    pub fn hidden(&self) -> bool {
+        self.hidden.is_some()
+    }
+
+    #[inline]
+    pub fn observed(&self) -> Option<Span> {
+        self.observed
+    }
+
+    #[inline]
+    pub fn block_on(&self) -> Option<Span> {
+        self.block_on
+    }
+
+    #[inline]
+    pub fn opaque(&self) -> bool {
+        self.opaque.is_some()
+    }
+
+    #[inline]
+    pub fn include_defaults(&self) -> Option<Span> {
+        self.include_defaults
+    }
+
+    #[inline]
+    pub fn defaults(&self) -> Option<&[Ident]> {
+        match &self.defaults {
+            Some((_, overridden)) => Some(overridden),
+            None => None,
+        }
+    }
+
+    #[inline]
+    pub fn namespace(&self) -> Option<&LitStr> {
+        self.namespace.as_ref()
+    }
+
+    #[inline]
+    pub fn memoize(&self) -> Option<Span> {
+        self.memoize
+    }
+
+    #[inline]
+    pub fn contracts(&self) -> &[ContractAttr] {
+        &self.contracts
+    }
+
+    #[inline]
+    pub fn consts(&self) -> Result<bool> {
+        match &self.consts {
+            Some((_, flat)) => Ok(*flat),
+            None => Err(self.error(CONSTS)),
+        }
+    }
+
     #[inline]
     fn rename<'a>(&self, scope: &impl PolymorphicScope, check: bool) -> Result<Option<String>> {
         match &self.name {
@@ -385,6 +629,7 @@ impl Attrs {
 
                 if check {
                     NameComponent::check_validity(span, target.as_str())?;
+                    check_script_name(*span, target.as_str())?;
                 }
 
                 Ok(Some(target))
@@ -526,7 +771,7 @@ impl Attrs {
                 self.family = Some((span, family));
             }
 
-            Attr::Package(path) => {
+            Attr::Package((path, dependencies)) => {
                 if self.package.is_some() {
                     return Err(Error::new(
                         path.span(),
@@ -548,7 +793,16 @@ impl Attrs {
                     ));
                 }
 
+                let mut checked = seed_hash_set();
+
+                for dependency in &dependencies {
+                    if !checked.insert(dependency) {
+                        return Err(Error::new(dependency.span(), "Duplicate dependency."));
+                    }
+                }
+
                 self.package = Some(path);
+                self.dependencies = dependencies;
             }
 
             Attr::Component((span, ty)) => {
@@ -556,14 +810,163 @@ impl Attrs {
                     return Err(Error::new(span, "Duplicate component marker."));
                 }
 
+                if self.getter.is_some() || self.setter.is_some() {
+                    return Err(Error::new(
+                        span,
+                        "Component marker conflicts with property marker.",
+                    ));
+                }
+
                 self.component = Some((span, ty));
             }
+
+            Attr::Pure(span) => {
+                if self.pure.is_some() {
+                    return Err(Error::new(span, "Duplicate purity marker."));
+                }
+
+                self.pure = Some(span);
+            }
+
+            Attr::Memoize(span) => {
+                if self.memoize.is_some() {
+                    return Err(Error::new(span, "Duplicate memoization marker."));
+                }
+
+                self.memoize = Some(span);
+            }
+
+            Attr::Consts((span, flat)) => {
+                if self.consts.is_some() {
+                    return Err(Error::new(span, "Duplicate constants table marker."));
+                }
+
+                self.consts = Some((span, flat));
+            }
+
+            Attr::Contract(contract) => {
+                self.contracts.push(contract);
+            }
+
+            Attr::Getter(span) => {
+                if self.getter.is_some() {
+                    return Err(Error::new(span, "Duplicate getter marker."));
+                }
+
+                if self.setter.is_some() {
+                    return Err(Error::new(
+                        span,
+                        "Getter marker conflicts with setter marker.",
+                    ));
+                }
+
+                if self.component.is_some() {
+                    return Err(Error::new(
+                        span,
+                        "Getter marker conflicts with component marker.",
+                    ));
+                }
+
+                self.getter = Some(span);
+            }
+
+            Attr::Setter(span) => {
+                if self.setter.is_some() {
+                    return Err(Error::new(span, "Duplicate setter marker."));
+                }
+
+                if self.getter.is_some() {
+                    return Err(Error::new(
+                        span,
+                        "Setter marker conflicts with getter marker.",
+                    ));
+                }
+
+                if self.component.is_some() {
+                    return Err(Error::new(
+                        span,
+                        "Setter marker conflicts with component marker.",
+                    ));
+                }
+
+                self.setter = Some(span);
+            }
+
+            Attr::Deprecated(message) => {
+                if self.deprecated.is_some() {
+                    return Err(Error::new(message.span(), "Duplicate deprecation marker."));
+                }
+
+                self.deprecated = Some(message);
+            }
+
+            Attr::Hidden(span) => {
+                if self.hidden.is_some() {
+                    return Err(Error::new(span, "Duplicate hidden marker."));
+                }
+
+                self.hidden = Some(span);
+            }
+
+            Attr::Observed(span) => {
+                if self.observed.is_some() {
+                    return Err(Error::new(span, "Duplicate observation marker."));
+                }
+
+                self.observed = Some(span);
+            }
+
+            Attr::BlockOn(span) => {
+                if self.block_on.is_some() {
+                    return Err(Error::new(span, "Duplicate blocking marker."));
+                }
+
+                self.block_on = Some(span);
+            }
+
+            Attr::Opaque(span) => {
+                if self.opaque.is_some() {
+                    return Err(Error::new(span, "Duplicate opaque marker."));
+                }
+
+                self.opaque = Some(span);
+            }
+
+            Attr::IncludeDefaults(span) => {
+                if self.include_defaults.is_some() {
+                    return Err(Error::new(
+                        span,
+                        "Duplicate default members inclusion marker.",
+                    ));
+                }
+
+                self.include_defaults = Some(span);
+            }
+
+            Attr::Defaults((span, overridden)) => {
+                if self.defaults.is_some() {
+                    return Err(Error::new(span, "Duplicate default members specification."));
+                }
+
+                self.defaults = Some((span, overridden));
+            }
+
+            Attr::Namespace(path) => {
+                if self.namespace.is_some() {
+                    return Err(Error::new(
+                        path.span(),
+                        "Duplicate namespace specification.",
+                    ));
+                }
+
+                self.namespace = Some(path);
+            }
         }
 
         Ok(())
     }
 
-    fn error(&self, mask: u16) -> Error {
+    fn error(&self, mask: u32) -> Error {
         let mut variants = Vec::new();
 
         if mask & INCLUDED > 0 {
@@ -605,12 +1008,64 @@ impl Attrs {
         if mask & PACKAGE > 0 {
             variants.push("#[export(manifest)] package declaration");
             variants.push("#[export(manifest \"<Cargo.toml path>\")] package declaration");
+            variants.push("#[export(package, deps(<package_1>, ...))] package declaration");
         }
 
         if mask & COMPONENT > 0 {
             variants.push("#[export(component <type>)] component marker");
         }
 
+        if mask & PURE > 0 {
+            variants.push("#[export(pure)] purity marker");
+        }
+
+        if mask & MEMOIZE > 0 {
+            variants.push("#[export(memoize)] memoization marker");
+        }
+
+        if mask & CONSTS > 0 {
+            variants.push("#[export(consts)] constants table marker");
+            variants.push("#[export(consts flat)] constants table marker");
+        }
+
+        if mask & CONTRACT > 0 {
+            variants.push("#[export(requires <condition>)] precondition");
+            variants.push("#[export(ensures <condition>)] postcondition");
+        }
+
+        if mask & PROPERTY > 0 {
+            variants.push("#[export(getter)] property getter marker");
+            variants.push("#[export(setter)] property setter marker");
+        }
+
+        if mask & DEPRECATED > 0 {
+            variants.push("#[export(deprecated \"<message>\")] deprecation marker");
+        }
+
+        if mask & HIDDEN > 0 {
+            variants.push("#[export(hidden)] tooling visibility marker");
+        }
+
+        if mask & OBSERVED > 0 {
+            variants.push("#[export(observed)] setter observation marker");
+        }
+
+        if mask & BLOCK_ON > 0 {
+            variants.push("#[export(block_on)] async function blocking marker");
+        }
+
+        if mask & OPAQUE > 0 {
+            variants.push("#[export(opaque)] opaque type marker");
+        }
+
+        if mask & INCLUDE_DEFAULTS > 0 {
+            variants.push("#[export(include_defaults)] default members inclusion marker");
+        }
+
+        if mask & NAMESPACE > 0 {
+            variants.push("#[export(namespace \"<path>\")] nested namespace specification");
+        }
+
         if variants.len() == 1 {
             return Error::new(self.span, format!("Missing {} attribute.", variants[0]));
         }
@@ -844,7 +1299,22 @@ pub trait Exportable: inner::WithAttributes {
             writeonly: None,
             family: None,
             package: None,
+            dependencies: Vec::new(),
             component: None,
+            pure: None,
+            memoize: None,
+            consts: None,
+            contracts: Vec::new(),
+            getter: None,
+            setter: None,
+            deprecated: None,
+            hidden: None,
+            observed: None,
+            block_on: None,
+            opaque: None,
+            include_defaults: None,
+            defaults: None,
+            namespace: None,
             derive,
         };
 
@@ -931,8 +1401,10 @@ mod inner {
         ImplItemConst,
         ImplItemFn,
         ItemConst,
+        ItemEnum,
         ItemFn,
         ItemImpl,
+        ItemMod,
         ItemStatic,
         ItemStruct,
         ItemTrait,
@@ -947,6 +1419,7 @@ mod inner {
         TraitItemConst,
         TraitItemFn,
         TypeParam,
+        Variant,
     };
 
     pub trait WithAttributes: Spanned {
@@ -979,6 +1452,30 @@ mod inner {
         }
     }
 
+    impl WithAttributes for ItemEnum {
+        #[inline(always)]
+        fn attributes(&self) -> &Vec<Attribute> {
+            &self.attrs
+        }
+
+        #[inline(always)]
+        fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+            &mut self.attrs
+        }
+    }
+
+    impl WithAttributes for Variant {
+        #[inline(always)]
+        fn attributes(&self) -> &Vec<Attribute> {
+            &self.attrs
+        }
+
+        #[inline(always)]
+        fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+            &mut self.attrs
+        }
+    }
+
     impl WithAttributes for ItemStruct {
         #[inline(always)]
         fn attributes(&self) -> &Vec<Attribute> {
@@ -1039,6 +1536,18 @@ mod inner {
         }
     }
 
+    impl WithAttributes for ItemMod {
+        #[inline(always)]
+        fn attributes(&self) -> &Vec<Attribute> {
+            &self.attrs
+        }
+
+        #[inline(always)]
+        fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+            &mut self.attrs
+        }
+    }
+
     impl WithAttributes for ItemTrait {
         #[inline(always)]
         fn attributes(&self) -> &Vec<Attribute> {
@@ -1181,8 +1690,22 @@ enum Attr {
     Readonly(Span),
     Writeonly(Span),
     Family((Span, Expr)),
-    Package(LitStr),
+    Package((LitStr, Vec<Type>)),
     Component((Span, Type)),
+    Pure(Span),
+    Memoize(Span),
+    Consts((Span, bool)),
+    Contract(ContractAttr),
+    Getter(Span),
+    Setter(Span),
+    Deprecated(LitStr),
+    Hidden(Span),
+    Observed(Span),
+    BlockOn(Span),
+    Opaque(Span),
+    IncludeDefaults(Span),
+    Defaults((Span, Vec<Ident>)),
+    Namespace(LitStr),
 }
 
 impl Parse for Attr {
@@ -1243,6 +1766,202 @@ impl Parse for Attr {
             return Ok(Self::Readonly(keyword.span));
         }
 
+        if lookahead.peek(keyword::pure) {
+            let keyword = input.parse::<keyword::pure>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Pure(keyword.span));
+        }
+
+        if lookahead.peek(keyword::getter) {
+            let keyword = input.parse::<keyword::getter>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Getter(keyword.span));
+        }
+
+        if lookahead.peek(keyword::setter) {
+            let keyword = input.parse::<keyword::setter>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Setter(keyword.span));
+        }
+
+        if lookahead.peek(keyword::block_on) {
+            let keyword = input.parse::<keyword::block_on>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::BlockOn(keyword.span));
+        }
+
+        if lookahead.peek(keyword::include_defaults) {
+            let keyword = input.parse::<keyword::include_defaults>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::IncludeDefaults(keyword.span));
+        }
+
+        if lookahead.peek(keyword::defaults) {
+            let keyword = input.parse::<keyword::defaults>()?;
+
+            let content;
+
+            parenthesized!(content in input);
+
+            let overridden = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Defaults((
+                keyword.span,
+                overridden.into_iter().collect(),
+            )));
+        }
+
+        if lookahead.peek(keyword::namespace) {
+            let keyword = input.parse::<keyword::namespace>()?;
+
+            let path = match input.peek(LitStr) {
+                true => input.parse::<LitStr>()?,
+                false => return Err(Error::new(keyword.span, "Missing namespace path.")),
+            };
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            for segment in path.value().split('.') {
+                NameComponent::check_validity(&path.span(), segment)?;
+                check_script_name(path.span(), segment)?;
+            }
+
+            return Ok(Self::Namespace(path));
+        }
+
+        if lookahead.peek(keyword::opaque) {
+            let keyword = input.parse::<keyword::opaque>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Opaque(keyword.span));
+        }
+
+        if lookahead.peek(keyword::observed) {
+            let keyword = input.parse::<keyword::observed>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Observed(keyword.span));
+        }
+
+        if lookahead.peek(keyword::hidden) {
+            let keyword = input.parse::<keyword::hidden>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Hidden(keyword.span));
+        }
+
+        if lookahead.peek(keyword::deprecated) {
+            let keyword = input.parse::<keyword::deprecated>()?;
+
+            let message = match input.peek(LitStr) {
+                true => input.parse::<LitStr>()?,
+                false => return Err(Error::new(keyword.span, "Missing deprecation message.")),
+            };
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Deprecated(message));
+        }
+
+        if lookahead.peek(keyword::memoize) {
+            let keyword = input.parse::<keyword::memoize>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Memoize(keyword.span));
+        }
+
+        if lookahead.peek(keyword::requires) {
+            let keyword = input.parse::<keyword::requires>()?;
+
+            let condition = input.parse::<Expr>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Contract(ContractAttr {
+                span: keyword.span,
+                postcondition: false,
+                condition,
+            }));
+        }
+
+        if lookahead.peek(keyword::ensures) {
+            let keyword = input.parse::<keyword::ensures>()?;
+
+            let condition = input.parse::<Expr>()?;
+
+            if !input.is_empty() {
+                return Err(input.error("Unexpected token."));
+            }
+
+            return Ok(Self::Contract(ContractAttr {
+                span: keyword.span,
+                postcondition: true,
+                condition,
+            }));
+        }
+
+        if lookahead.peek(keyword::consts) {
+            let keyword = input.parse::<keyword::consts>()?;
+
+            let flat = match input.is_empty() {
+                true => false,
+
+                false => {
+                    let _ = input.parse::<keyword::flat>()?;
+
+                    if !input.is_empty() {
+                        return Err(input.error("Unexpected token."));
+                    }
+
+                    true
+                }
+            };
+
+            return Ok(Self::Consts((keyword.span, flat)));
+        }
+
         if lookahead.peek(keyword::writeonly) {
             let keyword = input.parse::<keyword::writeonly>()?;
 
@@ -1332,8 +2051,10 @@ impl Parse for Attr {
         if lookahead.peek(keyword::package) {
             let keyword = input.parse::<keyword::package>()?;
 
-            let manifest_path = match input.is_empty() {
-                true => match var("CARGO_MANIFEST_DIR") {
+            let manifest_path = match input.peek(LitStr) {
+                true => input.parse::<LitStr>()?,
+
+                false => match var("CARGO_MANIFEST_DIR") {
                     Ok(directory) => LitStr::new(&format!("{directory}/Cargo.toml"), keyword.span),
 
                     Err(error) => {
@@ -1343,15 +2064,28 @@ impl Parse for Attr {
                         ))
                     }
                 },
-
-                false => input.parse::<LitStr>()?,
             };
 
+            let mut dependencies = Vec::new();
+
+            if !input.is_empty() {
+                let _ = input.parse::<Token![,]>()?;
+                let _ = input.parse::<keyword::deps>()?;
+
+                let content;
+
+                parenthesized!(content in input);
+
+                dependencies = Punctuated::<Type, Token![,]>::parse_terminated(&content)?
+                    .into_iter()
+                    .collect::<Vec<_>>();
+            }
+
             if !input.is_empty() {
                 return Err(input.error("Unexpected token."));
             }
 
-            return Ok(Self::Package(manifest_path));
+            return Ok(Self::Package((manifest_path, dependencies)));
         }
 
         if lookahead.peek(keyword::component) {
@@ -1560,6 +2294,55 @@ impl NameComponent {
     }
 }
 
+// Checks that the exported name can be referred to from the script code:
+// the name must be a valid script identifier, must not be a reserved word of
+// the script language, and must not start with the reserved prefix.
+pub fn check_script_name(span: Span, name: &str) -> Result<()> {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+
+    if RESERVED_WORDS.contains(&name) {
+        return Err(Error::new(
+            span,
+            format!(
+                "{name:?} is a reserved word of the script language and cannot be \
+                used as an exported name.\nConsider renaming the item using the \
+                #[export(name \"{name}_\")] attribute.",
+            ),
+        ));
+    }
+
+    if name.starts_with(RESERVED_PREFIX) {
+        return Err(Error::new(
+            span,
+            format!(
+                "The identifier {name:?} starts with the {RESERVED_PREFIX:?} prefix \
+                that is reserved for the Ad Astra internals.\nConsider renaming the \
+                item using the #[export(name ...)] attribute.",
+            ),
+        ));
+    }
+
+    match name.chars().next() {
+        None => return Err(Error::new(span, "Exported identifier cannot be empty.")),
+
+        Some(first) if first.is_ascii_digit() => {
+            return Err(Error::new(
+                span,
+                format!(
+                    "The identifier {name:?} starts with a digit.\nExported \
+                    identifiers must start with ['a'..'z', 'A'..'Z', '_'] \
+                    characters. Consider renaming the item using the \
+                    #[export(name \"_{name}\")] attribute.",
+                ),
+            ))
+        }
+
+        _ => (),
+    }
+
+    Ok(())
+}
+
 #[repr(transparent)]
 struct NameCase(Case);
 
@@ -2000,7 +2783,24 @@ mod keyword {
     syn::custom_keyword!(writeonly);
     syn::custom_keyword!(family);
     syn::custom_keyword!(package);
+    syn::custom_keyword!(deps);
     syn::custom_keyword!(component);
+    syn::custom_keyword!(pure);
+    syn::custom_keyword!(memoize);
+    syn::custom_keyword!(requires);
+    syn::custom_keyword!(ensures);
+    syn::custom_keyword!(consts);
+    syn::custom_keyword!(flat);
+    syn::custom_keyword!(getter);
+    syn::custom_keyword!(setter);
+    syn::custom_keyword!(deprecated);
+    syn::custom_keyword!(hidden);
+    syn::custom_keyword!(observed);
+    syn::custom_keyword!(block_on);
+    syn::custom_keyword!(opaque);
+    syn::custom_keyword!(include_defaults);
+    syn::custom_keyword!(defaults);
+    syn::custom_keyword!(namespace);
 }
 
 mod names {
@@ -0,0 +1,312 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use syn::{
+    spanned::Spanned,
+    GenericParam,
+    Generics,
+    Path,
+    Result,
+    TraitBound,
+    TraitBoundModifier,
+    Type,
+    TypeParamBound,
+    TypePath,
+    WherePredicate,
+};
+
+use crate::utils::{Group, PathUtils, PolymorphicScope, Printer, Shallow};
+
+// Trait bounds of the generic item checked against every specialization
+// before the specialization is exported.
+pub struct Bounds {
+    predicates: Vec<BoundPredicate>,
+}
+
+impl<'a> From<&'a Generics> for Bounds {
+    fn from(generics: &'a Generics) -> Self {
+        let mut predicates = Vec::new();
+
+        for param in &generics.params {
+            let GenericParam::Type(param) = param else {
+                continue;
+            };
+
+            let ty = new_path_type(Path::from(param.ident.clone()));
+
+            BoundPredicate::collect(&mut predicates, &ty, param.bounds.iter());
+        }
+
+        if let Some(where_clause) = &generics.where_clause {
+            for predicate in &where_clause.predicates {
+                let WherePredicate::Type(predicate) = predicate else {
+                    continue;
+                };
+
+                if predicate.lifetimes.is_some() {
+                    continue;
+                }
+
+                BoundPredicate::collect(
+                    &mut predicates,
+                    &predicate.bounded_ty,
+                    predicate.bounds.iter(),
+                );
+            }
+        }
+
+        Self { predicates }
+    }
+}
+
+impl Bounds {
+    // Returns false if the current specialization trivially violates one of
+    // the bounds. In this case the specialization should be skipped, and
+    // the function emits a warning into the group.
+    //
+    // Otherwise, emits compile-time assertions of the bounds that cannot be
+    // checked trivially, such that an unsatisfied bound would be reported at
+    // the bound's span.
+    pub fn check(&self, scope: &impl PolymorphicScope, group: &mut Group) -> Result<bool> {
+        if self.predicates.is_empty() {
+            return Ok(true);
+        }
+
+        let mut assertions = Vec::new();
+
+        for predicate in &self.predicates {
+            let mut ty = predicate.ty.clone();
+
+            scope.specialize_type(&mut ty)?;
+
+            let mut bound = new_path_type(predicate.bound.clone());
+
+            scope.specialize_type(&mut bound)?;
+
+            let Type::Path(TypePath { path: bound, .. }) = bound else {
+                continue;
+            };
+
+            match trivially_satisfies(&ty, &bound) {
+                Some(true) => (),
+
+                Some(false) => {
+                    let warning = format!(
+                        "Specialization skipped. The type \"{}\" does not satisfy the \"{}\" \
+                        bound.",
+                        ty.to_display_string(),
+                        new_path_type(bound).to_display_string(),
+                    );
+
+                    group.custom(skip_warning(predicate.span, warning));
+
+                    return Ok(false);
+                }
+
+                None => assertions.push(bound_assertion(predicate.span, &ty, bound)),
+            }
+        }
+
+        for assertion in assertions {
+            group.custom(assertion);
+        }
+
+        Ok(true)
+    }
+}
+
+struct BoundPredicate {
+    span: Span,
+    ty: Type,
+    bound: Path,
+}
+
+impl BoundPredicate {
+    fn collect<'a>(
+        predicates: &mut Vec<Self>,
+        ty: &Type,
+        bounds: impl Iterator<Item = &'a TypeParamBound>,
+    ) {
+        for bound in bounds {
+            let TypeParamBound::Trait(TraitBound {
+                modifier: TraitBoundModifier::None,
+                lifetimes: None,
+                path,
+                ..
+            }) = bound
+            else {
+                continue;
+            };
+
+            predicates.push(Self {
+                span: bound.span(),
+                ty: ty.clone(),
+                bound: path.clone(),
+            });
+        }
+    }
+}
+
+fn skip_warning(span: Span, warning: String) -> TokenStream {
+    if Shallow.enabled() {
+        return TokenStream::new();
+    }
+
+    quote_spanned!(span=> {
+        #[deprecated(note = #warning)]
+        #[allow(non_camel_case_types)]
+        struct skipped_specialization;
+
+        let _ = skipped_specialization;
+    })
+}
+
+// The helper trait carries the diagnostic message, such that the compiler
+// error names both the specialization and the unsatisfied bound rather than
+// the helper function.
+fn bound_assertion(span: Span, ty: &Type, bound: Path) -> TokenStream {
+    let ty_string = ty.to_display_string();
+    let bound_string = new_path_type(bound.clone()).to_display_string();
+
+    let message = format!(
+        "The specialization type \"{ty_string}\" does not satisfy the \"{bound_string}\" bound \
+        of the exported generic item.",
+    );
+
+    let label = format!("\"{bound_string}\" is not implemented for \"{ty_string}\"");
+
+    quote_spanned!(span=> {
+        #[diagnostic::on_unimplemented(message = #message, label = #label)]
+        trait SpecializationBound {}
+
+        impl<T: ?Sized + #bound> SpecializationBound for T {}
+
+        fn unsatisfied_bound<T: ?Sized + SpecializationBound>() {}
+
+        let _ = unsatisfied_bound::<#ty>;
+    })
+}
+
+// Returns Some if the type is a well-known type for which the satisfaction
+// of the bound can be determined without the help of the compiler.
+fn trivially_satisfies(ty: &Type, bound: &Path) -> Option<bool> {
+    let primitive = Primitive::from_type(ty)?;
+
+    let satisfies = if bound.matches_copy().is_some() {
+        !matches!(primitive, Primitive::Str | Primitive::String)
+    } else if bound.matches_clone().is_some() || bound.matches_default().is_some() {
+        !matches!(primitive, Primitive::Str)
+    } else if bound.matches_eq().is_some() || bound.matches_hash().is_some() {
+        !matches!(primitive, Primitive::Float)
+    } else if bound.matches_ord().is_some() {
+        !matches!(primitive, Primitive::Float)
+    } else if let Some(meta) = bound.matches_partial_eq() {
+        if !meta.args.is_empty() {
+            return None;
+        }
+
+        true
+    } else if let Some(meta) = bound.matches_partial_ord() {
+        if !meta.args.is_empty() {
+            return None;
+        }
+
+        true
+    } else if bound.matches_debug().is_some() {
+        true
+    } else if bound.matches_display().is_some() {
+        !matches!(primitive, Primitive::Unit)
+    } else {
+        return None;
+    };
+
+    Some(satisfies)
+}
+
+#[derive(Clone, Copy)]
+enum Primitive {
+    Unit,
+    Bool,
+    Char,
+    Integer,
+    Float,
+    Str,
+    String,
+}
+
+impl Primitive {
+    fn from_type(ty: &Type) -> Option<Self> {
+        match ty {
+            Type::Group(ty) => Self::from_type(&ty.elem),
+
+            Type::Paren(ty) => Self::from_type(&ty.elem),
+
+            Type::Tuple(ty) if ty.elems.is_empty() => Some(Self::Unit),
+
+            Type::Path(TypePath { qself: None, path }) => {
+                if path.matches_bracketed(&["String"], 0..=0).is_some()
+                    || path
+                        .matches_bracketed(&["std", "string", "String"], 0..=0)
+                        .is_some()
+                    || path
+                        .matches_bracketed(&["alloc", "string", "String"], 0..=0)
+                        .is_some()
+                {
+                    return Some(Self::String);
+                }
+
+                let ident = path.get_ident()?.to_string();
+
+                match ident.as_str() {
+                    "bool" => Some(Self::Bool),
+                    "char" => Some(Self::Char),
+                    "str" => Some(Self::Str),
+                    "f32" | "f64" => Some(Self::Float),
+                    "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32"
+                    | "u64" | "u128" | "usize" => Some(Self::Integer),
+                    _ => None,
+                }
+            }
+
+            _ => None,
+        }
+    }
+}
+
+#[inline(always)]
+fn new_path_type(path: Path) -> Type {
+    Type::Path(TypePath { qself: None, path })
+}
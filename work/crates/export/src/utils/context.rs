@@ -39,13 +39,13 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use ahash::{AHashMap, AHasher};
+use ahash::{AHashMap, AHashSet, AHasher};
 use convert_case::{Case, Casing};
 use proc_macro2::{Delimiter, Ident, Spacing, Span, TokenStream, TokenTree};
 use quote::{quote_spanned, ToTokens};
-use syn::{spanned::Spanned, Item, LitStr, Type};
+use syn::{spanned::Spanned, Error, Item, LitStr, Result, Type};
 
-use crate::utils::{seed_hash_map, seed_hasher, Facade};
+use crate::utils::{seed_hash_map, seed_hash_set, seed_hasher, Facade};
 
 pub type OriginRef = Ident;
 pub type IdRef = Ident;
@@ -166,6 +166,50 @@ impl Context {
         })
     }
 
+    // Reserves the script name of the package component (exported function,
+    // static, or constant) for the current item.
+    //
+    // Returns an error if the name is already reserved by another instance of
+    // the current item (e.g., by another polymorphic instance of the generic
+    // function).
+    //
+    // The conflicts between distinct items are not detected here, because the
+    // macro expands each item independently and does not share any state
+    // between the expansions. The PackageMeta::validate function reports them
+    // at runtime.
+    //
+    // The names exported into the nested namespaces are claimed within the
+    // `namespace` only.
+    pub fn claim_package_name(
+        self,
+        namespace: Option<&LitStr>,
+        name: &str,
+        span: Span,
+    ) -> Result<()> {
+        let qualified = match namespace {
+            Some(namespace) => format!("{}.{name}", namespace.value()),
+            None => String::from(name),
+        };
+
+        let name = qualified.as_str();
+
+        self.borrow(|inner| {
+            if !inner.package_names.insert(String::from(name)) {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "Duplicate exported name \"{name}\". Multiple polymorphic \
+                        instances of this item are exported under the same name.\n\n\
+                        Consider using distinct names for each instance in \
+                        the #[export(name ...)] attribute.",
+                    ),
+                ));
+            }
+
+            Ok(())
+        })
+    }
+
     #[inline(always)]
     pub fn make_section_name(self, name: &str, span: Span) -> SectionName {
         self.borrow(move |inner| {
@@ -240,6 +284,7 @@ struct ContextInner {
     origins: Vec<Ident>,
     unique_identifiers: Vec<(LitStr, Ident)>,
     shared_identifiers: AHashMap<LitStr, Ident>,
+    package_names: AHashSet<String>,
 }
 
 impl ToTokens for ContextInner {
@@ -353,6 +398,11 @@ impl ContextInner {
                 name = format!("const {}", item.ident);
             }
 
+            Item::Enum(item) => {
+                span = item.ident.span();
+                name = format!("enum {}", item.ident);
+            }
+
             Item::Fn(item) => {
                 span = item.sig.ident.span();
                 name = format!("fn {}", item.sig.ident);
@@ -383,6 +433,11 @@ impl ContextInner {
                 };
             }
 
+            Item::Mod(item) => {
+                span = item.ident.span();
+                name = format!("mod {}", item.ident);
+            }
+
             Item::Static(item) => {
                 span = item.ident.span();
                 name = format!("static {}", item.ident);
@@ -423,6 +478,7 @@ impl ContextInner {
             origins: vec![primary_origin],
             unique_identifiers: Vec::new(),
             shared_identifiers: seed_hash_map(),
+            package_names: seed_hash_set(),
         }
     }
 
@@ -0,0 +1,266 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::{format_ident, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, BinOp, Error, Expr, Lit, Result, UnOp};
+
+use crate::utils::{ContractAttr, Facade};
+
+pub struct Contract {
+    pub span: Span,
+    pub postcondition: bool,
+    pub text: String,
+    pub param: Option<usize>,
+    pub check: TokenStream,
+    pub meta: TokenStream,
+}
+
+impl Contract {
+    pub fn compile(attr: &ContractAttr, params: &[&Ident]) -> Result<Vec<Self>> {
+        let mut clauses = Vec::new();
+
+        split_conjunction(&attr.condition, &mut clauses);
+
+        let mut result = Vec::with_capacity(clauses.len());
+
+        for clause in clauses {
+            let mut compiler = Compiler {
+                postcondition: attr.postcondition,
+                params,
+                param: None,
+            };
+
+            let (text, check, meta) = compiler.compile(clause)?;
+
+            result.push(Self {
+                span: clause.span(),
+                postcondition: attr.postcondition,
+                text,
+                param: compiler.param,
+                check,
+                meta,
+            });
+        }
+
+        Ok(result)
+    }
+}
+
+fn split_conjunction<'a>(expr: &'a Expr, clauses: &mut Vec<&'a Expr>) {
+    match expr {
+        Expr::Binary(binary) if matches!(binary.op, BinOp::And(..)) => {
+            split_conjunction(&binary.left, clauses);
+            split_conjunction(&binary.right, clauses);
+        }
+
+        Expr::Group(group) => split_conjunction(&group.expr, clauses),
+
+        _ => clauses.push(expr),
+    }
+}
+
+struct Compiler<'a, 'b> {
+    postcondition: bool,
+    params: &'a [&'b Ident],
+    param: Option<usize>,
+}
+
+impl<'a, 'b> Compiler<'a, 'b> {
+    // Returns the condition's display text, the Rust expression that checks
+    // the condition over the downcasted arguments, and the constructor of the
+    // condition's ContractExpr.
+    fn compile(&mut self, expr: &Expr) -> Result<(String, TokenStream, TokenStream)> {
+        let span = expr.span();
+        let core = span.face_core();
+        let boxed = span.face_box();
+
+        match expr {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Bool(value) => Ok((
+                    value.value.to_string(),
+                    value.to_token_stream(),
+                    quote_spanned!(span=> #core::runtime::ContractExpr::Bool(#value)),
+                )),
+
+                Lit::Int(value) => {
+                    let number = Literal::f64_suffixed(value.base10_parse::<f64>()?);
+
+                    Ok((
+                        value.to_string(),
+                        value.to_token_stream(),
+                        quote_spanned!(span=> #core::runtime::ContractExpr::Number(#number)),
+                    ))
+                }
+
+                Lit::Float(value) => {
+                    let number = Literal::f64_suffixed(value.base10_parse::<f64>()?);
+
+                    Ok((
+                        value.to_string(),
+                        value.to_token_stream(),
+                        quote_spanned!(span=> #core::runtime::ContractExpr::Number(#number)),
+                    ))
+                }
+
+                _ => Err(Error::new(
+                    span,
+                    "Contract conditions support only boolean and numeric literals.",
+                )),
+            },
+
+            Expr::Path(path) => {
+                let Some(ident) = path.path.get_ident() else {
+                    return Err(Error::new(
+                        span,
+                        "Contract conditions can only refer to the function parameters.",
+                    ));
+                };
+
+                if self.postcondition {
+                    if ident != "result" {
+                        return Err(Error::new(
+                            span,
+                            "Postconditions can only refer to the function's result.",
+                        ));
+                    }
+
+                    return Ok((
+                        String::from("result"),
+                        quote_spanned!(span=> result),
+                        quote_spanned!(span=> #core::runtime::ContractExpr::Result),
+                    ));
+                }
+
+                let Some(index) = self.params.iter().position(|param| *param == ident) else {
+                    return Err(Error::new(
+                        span,
+                        "Unknown function parameter. Preconditions can only refer to the \
+                        function parameters.",
+                    ));
+                };
+
+                if self.param.is_none() {
+                    self.param = Some(index);
+                }
+
+                let data = format_ident!("data_{ident}", span = self.params[index].span());
+
+                Ok((
+                    ident.to_string(),
+                    quote_spanned!(span=> #data),
+                    quote_spanned!(span=> #core::runtime::ContractExpr::Param(#index)),
+                ))
+            }
+
+            Expr::Paren(paren) => {
+                let (text, check, meta) = self.compile(&paren.expr)?;
+
+                Ok((format!("({text})"), quote_spanned!(span=> (#check)), meta))
+            }
+
+            Expr::Group(group) => self.compile(&group.expr),
+
+            Expr::Unary(unary) => {
+                let (text, check, meta) = self.compile(&unary.expr)?;
+
+                match &unary.op {
+                    UnOp::Not(..) => Ok((
+                        format!("!{text}"),
+                        quote_spanned!(span=> !(#check)),
+                        quote_spanned!(span=> #core::runtime::ContractExpr::Not(#boxed::new(#meta))),
+                    )),
+
+                    UnOp::Neg(..) => Ok((
+                        format!("-{text}"),
+                        quote_spanned!(span=> -(#check)),
+                        quote_spanned!(span=> #core::runtime::ContractExpr::Neg(#boxed::new(#meta))),
+                    )),
+
+                    _ => Err(Error::new(
+                        unary.op.span(),
+                        "Unsupported unary operator in contract condition.",
+                    )),
+                }
+            }
+
+            Expr::Binary(binary) => {
+                let op = match &binary.op {
+                    BinOp::And(..) => quote_spanned!(span=> And),
+                    BinOp::Or(..) => quote_spanned!(span=> Or),
+                    BinOp::Eq(..) => quote_spanned!(span=> Eq),
+                    BinOp::Ne(..) => quote_spanned!(span=> Ne),
+                    BinOp::Lt(..) => quote_spanned!(span=> Lt),
+                    BinOp::Le(..) => quote_spanned!(span=> Le),
+                    BinOp::Gt(..) => quote_spanned!(span=> Gt),
+                    BinOp::Ge(..) => quote_spanned!(span=> Ge),
+                    BinOp::Add(..) => quote_spanned!(span=> Add),
+                    BinOp::Sub(..) => quote_spanned!(span=> Sub),
+                    BinOp::Mul(..) => quote_spanned!(span=> Mul),
+                    BinOp::Div(..) => quote_spanned!(span=> Div),
+                    BinOp::Rem(..) => quote_spanned!(span=> Rem),
+
+                    _ => {
+                        return Err(Error::new(
+                            binary.op.span(),
+                            "Unsupported binary operator in contract condition.",
+                        ))
+                    }
+                };
+
+                let (left_text, left_check, left_meta) = self.compile(&binary.left)?;
+                let (right_text, right_check, right_meta) = self.compile(&binary.right)?;
+
+                let operator = &binary.op;
+                let operator_text = operator.to_token_stream().to_string();
+
+                Ok((
+                    format!("{left_text} {operator_text} {right_text}"),
+                    quote_spanned!(span=> (#left_check) #operator (#right_check)),
+                    quote_spanned!(span=> #core::runtime::ContractExpr::Binary(
+                        #core::runtime::ContractOp::#op,
+                        #boxed::new(#left_meta),
+                        #boxed::new(#right_meta),
+                    )),
+                ))
+            }
+
+            _ => Err(Error::new(
+                span,
+                "Unsupported contract condition syntax. Contract conditions are restricted \
+                to literals, function parameters, and unary and binary operators.",
+            )),
+        }
+    }
+}
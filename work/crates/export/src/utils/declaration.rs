@@ -34,6 +34,7 @@
 
 use std::{
     borrow::Cow,
+    env::var,
     fmt::{Debug, Display, Formatter},
     hash::Hash,
 };
@@ -41,10 +42,10 @@ use std::{
 use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote_spanned, ToTokens};
-use syn::{spanned::Spanned, Expr, LitStr, Type};
+use syn::{parse_quote_spanned, spanned::Spanned, Expr, LitInt, LitStr, Type};
 
 use crate::utils::{
-    context::{Context, SectionName},
+    context::{Context, SectionName, StaticName},
     ty::is_str_type,
     Facade,
     IdRef,
@@ -106,6 +107,7 @@ impl ToTokens for Group {
 
         quote_spanned! (span=>
             #[no_mangle]
+            #[allow(clippy::result_large_err)]
             extern "C" fn #section() {
                 #[used]
                 #[cfg_attr(
@@ -222,6 +224,7 @@ impl Group {
 pub struct Package<'a> {
     pub ty: &'a Type,
     pub manifest: &'a ManifestMeta,
+    pub dependencies: &'a [Type],
 }
 
 impl<'a> ToTokens for Package<'a> {
@@ -236,9 +239,11 @@ impl<'a> ToTokens for Package<'a> {
         let default = span.face_default();
         let option = span.face_option();
         let deref = span.face_deref();
+        let vec = span.face_vec_macro();
 
         let ty = self.ty;
         let name = &self.manifest.name;
+        let dependencies = self.dependencies;
         let version = &self.manifest.version;
 
         let version_mismatch = LitStr::new(
@@ -275,8 +280,8 @@ impl<'a> ToTokens for Package<'a> {
 
         quote_spanned!(span=> {
             fn package() -> #intrinsics::PackageDeclaration {
-                static PACKAGE_NAME: &'static str = #env("CARGO_PKG_NAME");
-                static PACKAGE_VERSION: &'static str = #env("CARGO_PKG_VERSION");
+                static PACKAGE_NAME: &str = #env("CARGO_PKG_NAME");
+                static PACKAGE_VERSION: &str = #env("CARGO_PKG_VERSION");
 
                 #[allow(non_local_definitions)]
                 impl #core::runtime::ScriptPackage for #ty {
@@ -320,6 +325,10 @@ impl<'a> ToTokens for Package<'a> {
                     name: PACKAGE_NAME,
                     version: PACKAGE_VERSION,
                     doc: #doc,
+                    dependencies: #vec[#(
+                        <#dependencies as #core::runtime::ScriptPackage>::meta
+                            as fn() -> &'static #core::runtime::PackageMeta,
+                    )*],
                     instance: #intrinsics::Lazy::<#core::runtime::Cell>::new(|| {
                         let instance: #ty = <#ty as #default>::default();
 
@@ -383,6 +392,11 @@ impl<'a> ToTokens for TypeMeta<'a> {
                     #option::<&'static #core::runtime::TypeFamily>::Some(&#intrinsics::FUNCTION_FAMILY))
             }
 
+            TypeFamily::Namespace => {
+                quote_spanned!(span=>
+                    #option::<&'static #core::runtime::TypeFamily>::Some(&#intrinsics::NAMESPACE_FAMILY))
+            }
+
             TypeFamily::Custom(family) => {
                 quote_spanned!(span=>
                     #option::<&'static #core::runtime::TypeFamily>::Some(#family))
@@ -411,14 +425,159 @@ pub enum TypeFamily<'a> {
     Unique,
     Package,
     Function,
+    Namespace,
     Custom(&'a Expr),
 }
 
+// A nested namespace of the package specified by the
+// `#[export(namespace "...")]` attribute.
+//
+// Each namespace level is represented by a distinct instance of the
+// `Namespace<ID>` type, where the ID is a hash of the crate and the
+// namespace path. Every exported item re-declares the namespace types and
+// the links between the namespace levels, and the Script Engine merges the
+// repeated declarations at runtime.
+pub struct Namespace {
+    span: Span,
+    levels: Vec<NamespaceLevel>,
+}
+
+struct NamespaceLevel {
+    segment: String,
+    path: String,
+    ty: Type,
+    constructor: TokenStream,
+}
+
+impl Namespace {
+    pub fn new(path: &LitStr) -> Self {
+        let span = path.span();
+        let intrinsics = span.face_intrinsics();
+
+        let krate = format!(
+            "{}@{}:{}",
+            var("CARGO_PKG_NAME").unwrap_or_default(),
+            var("CARGO_PKG_VERSION").unwrap_or_default(),
+            var("CARGO_CRATE_NAME").unwrap_or_default(),
+        );
+
+        let package = var("CARGO_PKG_NAME").unwrap_or_default();
+
+        let mut levels = Vec::new();
+        let mut prefix = String::new();
+
+        for segment in path.value().split('.') {
+            if !prefix.is_empty() {
+                prefix.push('.');
+            }
+
+            prefix.push_str(segment);
+
+            let id = LitInt::new(&format!("{}u64", Self::hash(&krate, &prefix)), span);
+
+            let ty = parse_quote_spanned!(span=> #intrinsics::Namespace<#id>);
+
+            let constructor = {
+                let core = span.face_core();
+
+                quote_spanned!(span=> {
+                    fn component(
+                        origin: #core::runtime::Origin,
+                        _lhs: #core::runtime::Arg,
+                    ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                        #core::runtime::Cell::give(origin, #intrinsics::Namespace::<#id>)
+                    }
+
+                    component as fn(
+                        #core::runtime::Origin,
+                        #core::runtime::Arg,
+                    ) -> #core::runtime::RuntimeResult::<#core::runtime::Cell>
+                })
+            };
+
+            levels.push(NamespaceLevel {
+                segment: String::from(segment),
+                path: format!("{package}.{prefix}"),
+                ty,
+                constructor,
+            });
+        }
+
+        Self { span, levels }
+    }
+
+    // The type of the innermost namespace level.
+    #[inline(always)]
+    pub fn ty(&self) -> &Type {
+        let Some(level) = self.levels.last() else {
+            panic!("Internal error. Empty namespace.");
+        };
+
+        &level.ty
+    }
+
+    // Declares the types of all namespace levels, and links each level to
+    // the parent level (or to the package for the outermost level).
+    pub fn declare(&self, group: &mut Group) {
+        let span = self.span;
+
+        let mut parent = None;
+
+        for level in &self.levels {
+            group.type_meta(TypeMeta {
+                name: &LitStr::new(level.path.as_str(), span),
+                doc: None,
+                ty: &level.ty,
+                family: TypeFamily::Namespace,
+            });
+
+            let mut prototype = match parent {
+                None => Prototype::for_package(span),
+                Some(parent) => Prototype::for_type(parent),
+            };
+
+            prototype.component(Component {
+                name_ref: Cow::Owned(Context.make_shared_identifier(level.segment.as_str(), span)),
+                constructor: level.constructor.clone(),
+                hint: Cow::Borrowed(&level.ty),
+                doc: None,
+                owned: true,
+                pure: false,
+                property: false,
+                setter: None,
+                deprecated: None,
+                hidden: false,
+                observed: false,
+                casted: false,
+            });
+
+            group.prototype(prototype);
+
+            parent = Some(&level.ty);
+        }
+    }
+
+    // FNV-1a hash of the crate and the namespace path. The hash must be
+    // stable between the macro invocations.
+    fn hash(krate: &str, path: &str) -> u64 {
+        let mut hash = 0xcbf29ce484222325u64;
+
+        for byte in krate.bytes().chain([0]).chain(path.bytes()) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+
+        hash
+    }
+}
+
 pub struct Prototype<'a> {
     receiver_ty: Option<&'a Type>,
     receiver_id: TokenStream,
+    family: Option<&'a Expr>,
     manifest: Option<&'a ManifestMeta>,
     components: Vec<Component<'a>>,
+    constants: Option<StaticName>,
     operators: Vec<(OperatorOrigin<'a>, Operator)>,
 }
 
@@ -426,12 +585,21 @@ impl<'a> ToTokens for Prototype<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let span = Context.span();
 
+        let core = span.face_core();
         let intrinsics = span.face_intrinsics();
+        let option = span.face_option();
         let vec = span.face_vec();
         let vec_macro = span.face_vec_macro();
 
         let receiver_id = &self.receiver_id;
 
+        let family = match self.family {
+            Some(family) => quote_spanned!(span=>
+                #option::<&'static #core::runtime::TypeFamily>::Some(#family)),
+            None => quote_spanned!(span=>
+                #option::<&'static #core::runtime::TypeFamily>::None),
+        };
+
         let components = match self.manifest {
             None => {
                 let components = &self.components;
@@ -473,13 +641,22 @@ impl<'a> ToTokens for Prototype<'a> {
             _ => quote_spanned!(span=> #vec::new()),
         };
 
+        let constants = match &self.constants {
+            Some(table) => quote_spanned!(span=> &#table),
+            None => quote_spanned!(span=> &[]),
+        };
+
         quote_spanned!(span=> {
             fn prototype() -> #intrinsics::PrototypeDeclaration {
                 #intrinsics::PrototypeDeclaration {
                     receiver: #receiver_id,
 
+                    family: #family,
+
                     components: #components,
 
+                    constants: #constants,
+
                     operators: #operators,
                 }
             }
@@ -499,8 +676,10 @@ impl<'a> Prototype<'a> {
         Self {
             receiver_ty: Some(receiver),
             receiver_id,
+            family: None,
             manifest: None,
             components: Vec::new(),
+            constants: None,
             operators: Vec::with_capacity(1),
         }
     }
@@ -529,12 +708,21 @@ impl<'a> Prototype<'a> {
         Self {
             receiver_ty: None,
             receiver_id,
+            family: None,
             manifest: None,
             components: Vec::new(),
+            constants: None,
             operators: Vec::with_capacity(1),
         }
     }
 
+    #[inline(always)]
+    pub fn family(&mut self, family: &'a Expr) -> &mut Self {
+        self.family = Some(family);
+
+        self
+    }
+
     #[inline(always)]
     pub fn manifest(&mut self, manifest: &'a ManifestMeta) -> &mut Self {
         self.manifest = Some(manifest);
@@ -549,6 +737,13 @@ impl<'a> Prototype<'a> {
         self
     }
 
+    #[inline(always)]
+    pub fn constants(&mut self, table: StaticName) -> &mut Self {
+        self.constants = Some(table);
+
+        self
+    }
+
     #[inline(always)]
     pub fn operator(&mut self, origin: OperatorOrigin<'a>, operator: Operator) -> &mut Self {
         if self.receiver_ty.is_none() {
@@ -566,6 +761,16 @@ pub struct Component<'a> {
     pub constructor: TokenStream,
     pub hint: Cow<'a, Type>,
     pub doc: Option<LitStr>,
+    pub owned: bool,
+    pub pure: bool,
+    pub property: bool,
+    pub setter: Option<TokenStream>,
+    pub deprecated: Option<LitStr>,
+    pub hidden: bool,
+    pub observed: bool,
+    // The hint type is not a Script Type, but can be upcasted to a Script
+    // Type (e.g., `Option<usize>`).
+    pub casted: bool,
 }
 
 impl<'a> ToTokens for Component<'a> {
@@ -578,18 +783,50 @@ impl<'a> ToTokens for Component<'a> {
         let name_ref = self.name_ref.as_ref();
         let constructor = &self.constructor;
 
-        let hint = self.hint.type_hint();
+        let hint = match self.casted {
+            false => self.hint.type_hint(),
+
+            true => {
+                let upcast_hint = self.hint.upcast_hint();
+
+                quote_spanned!(span=> #intrinsics::casted_type_meta(#upcast_hint))
+            }
+        };
+
+        let owned = self.owned;
+        let pure = self.pure;
+        let property = self.property;
+
+        let setter = match &self.setter {
+            Some(setter) => quote_spanned!(span=> #option::Some(#setter)),
+            None => quote_spanned!(span=> #option::None),
+        };
 
         let doc = match &self.doc {
             Some(doc) => quote_spanned!(span=> #option::Some(#doc)),
             None => quote_spanned!(span=> #option::None),
         };
 
+        let deprecated = match &self.deprecated {
+            Some(message) => quote_spanned!(span=> #option::Some(#message)),
+            None => quote_spanned!(span=> #option::None),
+        };
+
+        let hidden = self.hidden;
+        let observed = self.observed;
+
         quote_spanned!(span=> #intrinsics::ComponentDeclaration {
             name: &#name_ref,
             constructor: #constructor,
             hint: #hint,
             doc: #doc,
+            owned: #owned,
+            pure: #pure,
+            property: #property,
+            setter: #setter,
+            deprecated: #deprecated,
+            hidden: #hidden,
+            observed: #observed,
         })
         .to_tokens(tokens);
     }
@@ -610,6 +847,7 @@ pub enum Operator {
     Hash,
     Invocation,
     Binding,
+    Iterate,
     Add,
     AddAssign,
     Sub,
@@ -649,7 +887,7 @@ impl Operator {
     pub fn enumerate() -> impl Iterator<Item = &'static Self> {
         use Operator::*;
 
-        static ALL: [Operator; 38] = [
+        static ALL: [Operator; 39] = [
             Assign,
             Concat,
             Field,
@@ -663,6 +901,7 @@ impl Operator {
             Hash,
             Invocation,
             Binding,
+            Iterate,
             Add,
             AddAssign,
             Sub,
@@ -693,6 +932,17 @@ impl Operator {
         ALL.iter()
     }
 
+    // Returns false if the operator's declaration accesses the data of the
+    // receiver type directly, and therefore cannot be shared between the
+    // members of a type family.
+    #[inline]
+    pub fn is_family_applicable(&self) -> bool {
+        !matches!(
+            self,
+            Self::Clone | Self::Debug | Self::Display | Self::Default | Self::Hash,
+        )
+    }
+
     #[inline]
     pub(super) fn describe(&self) -> OperatorDescription {
         let mut rhs = false;
@@ -738,6 +988,10 @@ impl Operator {
                 rhs = true;
             }
 
+            Self::Iterate => {
+                result = true;
+            }
+
             Self::Add => {
                 rhs = true;
                 result = true;
@@ -1213,6 +1467,28 @@ impl Operator {
                 );
             }
 
+            Self::Iterate => {
+                let hint_item = quote_spanned!(span=>
+                    <#lhs as #core::runtime::ops::ScriptIterate>::Item
+                )
+                .type_hint();
+
+                return quote_spanned!(span=>
+                    #intrinsics::OperatorDeclaration::Iterate(
+                        #intrinsics::IterateOperator {
+                            origin: #origin,
+
+                            invoke: <#lhs as #core::runtime::ops::ScriptIterate>::script_iterate as fn(
+                                #core::runtime::Origin,
+                                #core::runtime::Arg,
+                            ) -> #core::runtime::RuntimeResult<#core::runtime::Cell>,
+
+                            hint_item: #hint_item,
+                        }
+                    )
+                );
+            }
+
             Self::None => {
                 return quote_spanned!(span=>
                     #intrinsics::OperatorDeclaration::None(
@@ -121,6 +121,20 @@ pub trait Facade: Spanned {
         quote_spanned!(span=> ::std::ops::Deref)
     }
 
+    #[inline(always)]
+    fn face_once_lock(&self) -> TokenStream {
+        let span = self.span();
+
+        quote_spanned!(span=> ::std::sync::OnceLock)
+    }
+
+    #[inline(always)]
+    fn face_string(&self) -> TokenStream {
+        let span = self.span();
+
+        quote_spanned!(span=> ::std::string::String)
+    }
+
     #[inline(always)]
     fn face_format(&self) -> TokenStream {
         let span = self.span();
@@ -184,6 +198,13 @@ pub trait Facade: Spanned {
         quote_spanned!(span=> ::std::fmt::Formatter)
     }
 
+    #[inline(always)]
+    fn face_into_iterator(&self) -> TokenStream {
+        let span = self.span();
+
+        quote_spanned!(span=> ::std::iter::IntoIterator)
+    }
+
     #[inline(always)]
     fn face_debug(&self) -> TokenStream {
         let span = self.span();
@@ -38,6 +38,7 @@ use syn::{
     spanned::Spanned,
     Error,
     FnArg,
+    GenericArgument,
     LitStr,
     Pat,
     PathArguments,
@@ -46,6 +47,7 @@ use syn::{
     Signature,
     Token,
     Type,
+    TypeParamBound,
 };
 
 use crate::utils::{
@@ -54,6 +56,8 @@ use crate::utils::{
     ty::make_param_fn_meta,
     Coercion,
     Context,
+    Contract,
+    ContractAttr,
     Facade,
     Group,
     IdRef,
@@ -73,10 +77,15 @@ pub struct Invocation<'a> {
     receiver: ReceiverMeta,
     arguments: Vec<Argument<'a>>,
     output_span: Span,
+    memoize: bool,
+    iterator: bool,
+    block_on: bool,
+    variadic: bool,
+    contracts: Vec<Contract>,
 }
 
 impl<'a> Invocation<'a> {
-    pub fn new(signature: &'a Signature) -> Result<Self> {
+    pub fn new(signature: &'a Signature, block_on: Option<Span>) -> Result<Self> {
         if let Some(abi) = &signature.abi {
             return Err(Error::new(abi.span(), "Cannot register ABI function."));
         }
@@ -95,15 +104,28 @@ impl<'a> Invocation<'a> {
             ));
         }
 
-        if let Some(asyncness) = &signature.asyncness {
-            return Err(Error::new(
-                asyncness.span(),
-                "Cannot register async function.",
-            ));
+        match (&signature.asyncness, block_on) {
+            (Some(asyncness), None) => {
+                return Err(Error::new(
+                    asyncness.span(),
+                    "Cannot register async function. Consider blocking on the \
+                    function's future with the #[export(block_on)] attribute.",
+                ));
+            }
+
+            (None, Some(span)) => {
+                return Err(Error::new(
+                    span,
+                    "Blocking marker is applicable to async functions only.",
+                ));
+            }
+
+            _ => (),
         }
 
         let mut receiver = ReceiverMeta::None;
         let mut arguments = Vec::with_capacity(signature.inputs.len());
+        let mut variadic = false;
 
         for arg in &signature.inputs {
             match arg {
@@ -119,6 +141,15 @@ impl<'a> Invocation<'a> {
                         }
                     };
 
+                    if variadic {
+                        return Err(Error::new(
+                            arg.span(),
+                            "Variadic arguments must be the last parameter of the function.",
+                        ));
+                    }
+
+                    variadic = is_variadic(arg.ty.as_ref());
+
                     let name_ref =
                         Context.make_unique_identifier(ident.to_string().as_str(), ident.span());
 
@@ -154,6 +185,11 @@ impl<'a> Invocation<'a> {
             receiver,
             arguments,
             output_span,
+            memoize: false,
+            iterator: false,
+            block_on: block_on.is_some(),
+            variadic,
+            contracts: Vec::new(),
         })
     }
 
@@ -162,6 +198,106 @@ impl<'a> Invocation<'a> {
         self.receiver.is_some()
     }
 
+    pub fn memoize(&mut self, span: Span, signature: &Signature) -> Result<()> {
+        if self.receiver.is_some() {
+            return Err(Error::new(
+                span,
+                "Functions with receivers cannot be memoized.",
+            ));
+        }
+
+        if self.variadic {
+            return Err(Error::new(span, "Variadic functions cannot be memoized."));
+        }
+
+        for arg in &signature.inputs {
+            let FnArg::Typed(arg) = arg else {
+                continue;
+            };
+
+            if let Type::Reference(reference) = arg.ty.as_ref() {
+                if let Some(mutability) = &reference.mutability {
+                    return Err(Error::new(
+                        mutability.span(),
+                        "Functions with mutable reference arguments cannot be memoized.",
+                    ));
+                }
+            }
+
+            if is_callback(arg.ty.as_ref()) {
+                return Err(Error::new(
+                    arg.ty.span(),
+                    "Functions with callback arguments cannot be memoized.",
+                ));
+            }
+        }
+
+        self.memoize = true;
+
+        Ok(())
+    }
+
+    pub fn iterator(&mut self, span: Span) -> Result<()> {
+        if self.receiver.is_some() {
+            return Err(Error::new(
+                span,
+                "Functions with receivers cannot return iterators.",
+            ));
+        }
+
+        if self.memoize {
+            return Err(Error::new(
+                span,
+                "Functions returning iterators cannot be memoized.",
+            ));
+        }
+
+        if self.block_on {
+            return Err(Error::new(span, "Async functions cannot return iterators."));
+        }
+
+        if let Some(contract) = self
+            .contracts
+            .iter()
+            .find(|contract| contract.postcondition)
+        {
+            return Err(Error::new(
+                contract.span,
+                "Functions returning iterators cannot declare postconditions.",
+            ));
+        }
+
+        self.iterator = true;
+
+        Ok(())
+    }
+
+    pub fn contracts(&mut self, contracts: &[ContractAttr]) -> Result<()> {
+        let Some(first) = contracts.first() else {
+            return Ok(());
+        };
+
+        if self.receiver.is_some() {
+            return Err(Error::new(
+                first.span,
+                "Functions with receivers cannot declare contracts.",
+            ));
+        }
+
+        let params = self
+            .arguments
+            .iter()
+            .map(|argument| argument.ident)
+            .collect::<Vec<_>>();
+
+        for contract in contracts {
+            self.contracts
+                .append(&mut Contract::compile(contract, &params)?);
+        }
+
+        Ok(())
+    }
+
     pub fn make_function_type<S: PolymorphicScope>(
         &self,
         group: &mut Group,
@@ -364,6 +500,16 @@ impl<'a> Invocation<'a> {
             let provider = format_ident!("provider_{ident}", span = arg_type_span);
             let data = format_ident!("data_{ident}", span = arg_type_span);
 
+            if self.variadic && index + 1 == arguments_count {
+                arguments_downcast.push(quote_spanned!(arg_type_span=>
+                    let #data = <#ty>::from_arguments(&mut arguments[#index..]);
+                ));
+
+                arguments_list.push(data);
+
+                continue;
+            }
+
             let provider_creation = quote_spanned!(arg_type_span=>
                 let #provider = #core::runtime::Provider::Borrowed(&mut #cell);
 
@@ -384,20 +530,90 @@ impl<'a> Invocation<'a> {
 
         let core = function_span.face_core();
 
-        let arity_check = quote_spanned!(function_span=>
-            let arguments_count = arguments.len();
-
-            if arguments_count != #arguments_count {
-                return #core::runtime::RuntimeResult::<#core::runtime::Cell>::Err(
-                    #core::runtime::RuntimeError::ArityMismatch {
-                        invocation_origin: origin,
-                        function_origin: #core::runtime::Origin::Rust(#name_ref.origin),
-                        parameters: #arguments_count,
-                        arguments: arguments_count,
-                    },
-                );
+        let mut preconditions = Vec::new();
+        let mut postconditions = Vec::new();
+
+        for contract in &self.contracts {
+            let span = contract.span;
+            let check = &contract.check;
+            let condition = LitStr::new(contract.text.as_str(), span);
+
+            let (access_origin, kind) = match (contract.postcondition, contract.param) {
+                (false, Some(index)) => {
+                    let arg = &arguments[index];
+                    let origin = format_ident!("origin_{}", arg.ident, span = arg.ident.span());
+
+                    (
+                        quote_spanned!(span=> #origin),
+                        quote_spanned!(span=> Requires),
+                    )
+                }
+
+                (false, None) => (
+                    quote_spanned!(span=> origin),
+                    quote_spanned!(span=> Requires),
+                ),
+
+                (true, _) => (
+                    quote_spanned!(span=> origin),
+                    quote_spanned!(span=> Ensures),
+                ),
+            };
+
+            let check = quote_spanned!(span=>
+                if !(#check) {
+                    return #core::runtime::RuntimeResult::Err(
+                        #core::runtime::RuntimeError::ContractViolation {
+                            access_origin: #access_origin,
+                            function_origin: #core::runtime::Origin::Rust(#name_ref.origin),
+                            kind: #core::runtime::ContractKind::#kind,
+                            condition: #condition,
+                        },
+                    );
+                }
+            );
+
+            match contract.postcondition {
+                false => preconditions.push(check),
+                true => postconditions.push(check),
+            }
+        }
+
+        let arity_check = match self.variadic {
+            false => quote_spanned!(function_span=>
+                let arguments_count = arguments.len();
+
+                if arguments_count != #arguments_count {
+                    return #core::runtime::RuntimeResult::<#core::runtime::Cell>::Err(
+                        #core::runtime::RuntimeError::ArityMismatch {
+                            invocation_origin: origin,
+                            function_origin: #core::runtime::Origin::Rust(#name_ref.origin),
+                            parameters: #arguments_count,
+                            arguments: arguments_count,
+                        },
+                    );
+                }
+            ),
+
+            true => {
+                let required_count = arguments_count - 1;
+
+                quote_spanned!(function_span=>
+                    let arguments_count = arguments.len();
+
+                    if arguments_count < #required_count {
+                        return #core::runtime::RuntimeResult::<#core::runtime::Cell>::Err(
+                            #core::runtime::RuntimeError::ArityMismatch {
+                                invocation_origin: origin,
+                                function_origin: #core::runtime::Origin::Rust(#name_ref.origin),
+                                parameters: #required_count,
+                                arguments: arguments_count,
+                            },
+                        );
+                    }
+                )
             }
-        );
+        };
 
         let output_span = self.output_span;
         let function = self.ident;
@@ -422,11 +638,61 @@ impl<'a> Invocation<'a> {
 
         match receiver {
             ReceiverMeta::None => {
-                let application = quote_spanned!(output_span=>
-                    let result: #output = #function(#(
-                        #arguments_list
-                    ),*);
-                );
+                let application = match (self.iterator, self.block_on) {
+                    (false, false) => quote_spanned!(output_span=>
+                        let result: #output = #function(#(
+                            #arguments_list
+                        ),*);
+                    ),
+
+                    (false, true) => {
+                        let intrinsics = function_span.face_intrinsics();
+                        let env = function_span.face_env();
+
+                        quote_spanned!(output_span=>
+                            let result: #output = #intrinsics::block_on(
+                                origin,
+                                #env("CARGO_PKG_NAME"),
+                                #env("CARGO_PKG_VERSION"),
+                                #function(#(
+                                    #arguments_list
+                                ),*),
+                            )?;
+                        )
+                    }
+
+                    (true, _) => quote_spanned!(output_span=>
+                        let result: #output = <#output>::new(#function(#(
+                            #arguments_list
+                        ),*));
+                    ),
+                };
+
+                if self.memoize {
+                    let intrinsics = function_span.face_intrinsics();
+
+                    return quote_spanned!(function_span=> {
+                        #arity_check
+
+                        #intrinsics::memoize::<#function_type>(
+                            origin,
+                            arguments,
+                            |arguments| {
+                                #(
+                                #arguments_downcast
+                                )*
+
+                                #( #preconditions )*
+
+                                #application
+
+                                #( #postconditions )*
+
+                                #core::runtime::Cell::give(origin, result)
+                            },
+                        )
+                    });
+                }
 
                 quote_spanned!(function_span=> {
                     #arity_check
@@ -435,8 +701,12 @@ impl<'a> Invocation<'a> {
                     #arguments_downcast
                     )*
 
+                    #( #preconditions )*
+
                     #application
 
+                    #( #postconditions )*
+
                     #core::runtime::Cell::give(origin, result)
                 })
             }
@@ -573,19 +843,38 @@ impl<'a> Invocation<'a> {
             _ => quote_spanned!(span=> #option::None),
         };
 
+        let param_docs = match &doc {
+            Some(doc) => parse_param_docs(&doc.value()),
+            None => Vec::new(),
+        };
+
         let inputs = arguments.iter().map(|arg| {
             let span = arg.ty.span();
             let name_ref = arg.name_ref;
 
+            let ident = arg.ident.to_string();
+            let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+
+            let param_doc = match param_docs.iter().find(|(name, _)| name == ident) {
+                Some((_, text)) => {
+                    let text = LitStr::new(text, span);
+                    quote_spanned!(span=> #option::Some(#text))
+                }
+                None => quote_spanned!(span=> #option::None),
+            };
+
             let signature = make_param_fn_meta(
                 &quote_spanned!(span=> #core::runtime::Origin::Rust(#name_ref.origin)),
                 &arg.ty,
                 true,
             );
 
-            let hint = match signature {
-                Some(meta) => quote_spanned!(span=> #core::runtime::TypeHint::Invocation(#meta)),
-                None => arg.ty.downcast_hint(),
+            let (hint, schema) = match signature {
+                Some(meta) => (
+                    quote_spanned!(span=> #core::runtime::TypeHint::Invocation(#meta)),
+                    quote_spanned!(span=> #option::None),
+                ),
+                None => (arg.ty.downcast_hint(), arg.ty.downcast_schema()),
             };
 
             Shallow.assert_type_impls_downcast(&arg.ty, span);
@@ -595,6 +884,8 @@ impl<'a> Invocation<'a> {
                     #core::runtime::Ident::Rust(&#name_ref),
                 ),
                 hint: #hint,
+                schema: #schema,
+                doc: #param_doc,
             })
         });
 
@@ -602,11 +893,30 @@ impl<'a> Invocation<'a> {
 
         let output = output.upcast_hint();
 
+        let contracts = self.contracts.iter().map(|contract| {
+            let span = contract.span;
+            let condition = LitStr::new(contract.text.as_str(), span);
+            let expr = &contract.meta;
+
+            let kind = match contract.postcondition {
+                false => quote_spanned!(span=> Requires),
+                true => quote_spanned!(span=> Ensures),
+            };
+
+            quote_spanned!(span=> #core::runtime::Contract {
+                kind: #core::runtime::ContractKind::#kind,
+                condition: #condition,
+                expr: #expr,
+            })
+        });
+
         let doc = match doc {
             None => quote_spanned!(span=> #option::None),
             Some(doc) => quote_spanned!(span=> #option::Some(#doc)),
         };
 
+        let variadic = self.variadic;
+
         quote_spanned!(span=>
             #core::runtime::InvocationMeta {
                 origin: #core::runtime::Origin::Rust(#name_ref.origin),
@@ -617,6 +927,10 @@ impl<'a> Invocation<'a> {
                     #( #inputs ),*
                 ]),
                 output: #output,
+                contracts: #vec_macro[
+                    #( #contracts ),*
+                ],
+                variadic: #variadic,
             }
         )
     }
@@ -668,6 +982,216 @@ impl ReceiverMeta {
     }
 }
 
+// Returns true if the function returns `impl Iterator<...>`.
+pub fn is_iterator_output(output: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+
+    let Type::ImplTrait(ty) = ty.as_ref() else {
+        return false;
+    };
+
+    ty.bounds.iter().any(|bound| {
+        let TypeParamBound::Trait(bound) = bound else {
+            return false;
+        };
+
+        let Some(segment) = bound.path.segments.last() else {
+            return false;
+        };
+
+        segment.ident == "Iterator"
+    })
+}
+
+// Returns true if the type is `VariadicArguments<...>`.
+fn is_variadic(ty: &Type) -> bool {
+    let Type::Path(ty) = ty else {
+        return false;
+    };
+
+    let Some(segment) = ty.path.segments.last() else {
+        return false;
+    };
+
+    segment.ident == "VariadicArguments"
+}
+
+// Returns true if the type is, or contains, a Rust or script callback.
+fn is_callback(ty: &Type) -> bool {
+    match ty {
+        Type::BareFn(_) => true,
+
+        Type::TraitObject(ty) => ty.bounds.iter().any(is_callback_bound),
+
+        Type::ImplTrait(ty) => ty.bounds.iter().any(is_callback_bound),
+
+        Type::Reference(ty) => is_callback(ty.elem.as_ref()),
+
+        Type::Paren(ty) => is_callback(ty.elem.as_ref()),
+
+        Type::Group(ty) => is_callback(ty.elem.as_ref()),
+
+        Type::Slice(ty) => is_callback(ty.elem.as_ref()),
+
+        Type::Array(ty) => is_callback(ty.elem.as_ref()),
+
+        Type::Tuple(ty) => ty.elems.iter().any(is_callback),
+
+        Type::Path(ty) => {
+            let Some(segment) = ty.path.segments.last() else {
+                return false;
+            };
+
+            let name = segment.ident.to_string();
+
+            if name == "ScriptFn" {
+                return true;
+            }
+
+            if let Some(arity) = name.strip_prefix("Fn") {
+                if !arity.is_empty() && arity.bytes().all(|byte| byte.is_ascii_digit()) {
+                    return true;
+                }
+            }
+
+            let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                return false;
+            };
+
+            arguments.args.iter().any(|argument| match argument {
+                GenericArgument::Type(ty) => is_callback(ty),
+                _ => false,
+            })
+        }
+
+        _ => false,
+    }
+}
+
+fn is_callback_bound(bound: &TypeParamBound) -> bool {
+    let TypeParamBound::Trait(bound) = bound else {
+        return false;
+    };
+
+    let Some(segment) = bound.path.segments.last() else {
+        return false;
+    };
+
+    segment.ident == "Fn" || segment.ident == "FnMut" || segment.ident == "FnOnce"
+}
+
+// Extracts per-parameter descriptions from the function's doc comment.
+//
+// Recognizes Markdown list items such as "* `name` - text", "- name: text"
+// (typically under an "# Arguments" heading), and "@param name text" lines.
+// Indented lines following an item continue its description. Anything that
+// does not match these forms is silently ignored.
+fn parse_param_docs(doc: &str) -> Vec<(String, String)> {
+    let mut result = Vec::<(String, String)>::new();
+    let mut current = None;
+
+    for line in doc.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with("```") {
+            current = None;
+            continue;
+        }
+
+        if let Some((name, text)) = parse_param_doc_item(line) {
+            current = None;
+
+            if result.iter().any(|(previous, _)| previous == name) {
+                continue;
+            }
+
+            current = Some((result.len(), indent));
+            result.push((name.to_string(), text.to_string()));
+            continue;
+        }
+
+        match current {
+            Some((index, item_indent)) if indent > item_indent => {
+                let text = &mut result[index].1;
+
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+
+                text.push_str(line);
+            }
+
+            _ => current = None,
+        }
+    }
+
+    result.retain(|(_, text)| !text.is_empty());
+
+    result
+}
+
+fn parse_param_doc_item(line: &str) -> Option<(&str, &str)> {
+    if let Some(rest) = line.strip_prefix("@param ") {
+        let rest = rest.trim_start();
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let name = rest[..end].trim_matches('`');
+
+        if !is_param_doc_name(name) {
+            return None;
+        }
+
+        return Some((name, rest[end..].trim()));
+    }
+
+    let rest = line
+        .strip_prefix("* ")
+        .or_else(|| line.strip_prefix("- "))?
+        .trim_start();
+
+    let (name, rest) = match rest.strip_prefix('`') {
+        Some(rest) => {
+            let end = rest.find('`')?;
+            (&rest[..end], &rest[(end + 1)..])
+        }
+
+        None => {
+            let end = rest
+                .find(|ch: char| !ch.is_alphanumeric() && ch != '_')
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        }
+    };
+
+    if !is_param_doc_name(name) {
+        return None;
+    }
+
+    let rest = rest.trim_start();
+
+    let text = rest
+        .strip_prefix("—")
+        .or_else(|| rest.strip_prefix("--"))
+        .or_else(|| rest.strip_prefix('-'))
+        .or_else(|| rest.strip_prefix(':'))?;
+
+    Some((name, text.trim()))
+}
+
+#[inline(always)]
+fn is_param_doc_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) if first.is_alphabetic() || first == '_' => (),
+        _ => return false,
+    }
+
+    chars.all(|ch| ch.is_alphanumeric() || ch == '_')
+}
+
 struct Argument<'a> {
     ident: &'a Ident,
     name_ref: IdRef,
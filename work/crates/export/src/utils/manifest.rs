@@ -120,6 +120,20 @@ impl ToTokens for ManifestMeta {
                                 doc: #core::runtime::TypeMeta::doc(hint),
 
                                 hint,
+
+                                owned: false,
+
+                                pure: false,
+
+                                property: false,
+
+                                setter: #option::None,
+
+                                deprecated: #option::None,
+
+                                hidden: false,
+
+                                observed: false,
                             }
                         );
                     }
@@ -33,7 +33,9 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 mod attr;
+mod bounds;
 mod context;
+mod contract;
 mod declaration;
 mod derive;
 mod facade;
@@ -51,10 +53,12 @@ mod ty;
 #[allow(unused_imports)]
 pub use crate::utils::{
     attr::*,
-    context::{Context, IdRef, OriginRef},
+    context::{Context, IdRef, OriginRef, StaticName},
+    contract::Contract,
     declaration::{
         Component,
         Group,
+        Namespace,
         Operator,
         OperatorOrigin,
         Package,
@@ -64,7 +68,7 @@ pub use crate::utils::{
     },
     derive::DeriveMeta,
     facade::Facade,
-    invoke::Invocation,
+    invoke::{is_iterator_output, Invocation},
     manifest::ManifestMeta,
     morphism::{
         EmptyPolymorphism,
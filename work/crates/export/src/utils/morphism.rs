@@ -61,9 +61,11 @@ use syn::{
 };
 
 use crate::utils::{
+    bounds::Bounds,
     resolve::{ResolveConstraints, Resolver},
     seed_hash_map_with_capacity,
     Exportable,
+    Group,
     CONST,
     TYPE,
 };
@@ -105,6 +107,7 @@ impl PolymorphicScope for EmptyPolymorphism {
 pub struct TypePolymorphism<'a> {
     ident: &'a Ident,
     generics: GenericsPolymorphism,
+    bounds: Bounds,
 }
 
 impl<'a> PolymorphicScope for TypePolymorphism<'a> {
@@ -153,9 +156,14 @@ impl<'a> PolymorphicScope for TypePolymorphism<'a> {
 
 impl<'a> TypePolymorphism<'a> {
     pub fn new(ident: &'a Ident, generics: &mut Generics) -> Result<Self> {
+        let bounds = Bounds::from(&*generics);
         let generics = GenericsPolymorphism::try_from(generics)?;
 
-        Ok(Self { ident, generics })
+        Ok(Self {
+            ident,
+            generics,
+            bounds,
+        })
     }
 
     #[inline(always)]
@@ -186,10 +194,16 @@ impl<'a> TypePolymorphism<'a> {
     pub fn rotate(&mut self) -> bool {
         self.generics.rotate()
     }
+
+    #[inline(always)]
+    pub fn check_bounds(&self, group: &mut Group) -> Result<bool> {
+        self.bounds.check(self, group)
+    }
 }
 
 pub struct ImplPolymorphism {
     generics: GenericsPolymorphism,
+    bounds: Bounds,
     polymorphic_trait_type: Option<Type>,
     polymorphic_self_type: Type,
     monomorphic_trait_type: Option<Type>,
@@ -258,6 +272,7 @@ impl PolymorphicScope for ImplPolymorphism {
 
 impl ImplPolymorphism {
     pub fn new(item: &mut ItemImpl) -> Result<Self> {
+        let bounds = Bounds::from(&item.generics);
         let generics = GenericsPolymorphism::try_from(&mut item.generics)?;
 
         let polymorphic_trait_type;
@@ -302,6 +317,7 @@ impl ImplPolymorphism {
 
         Ok(Self {
             generics,
+            bounds,
             polymorphic_trait_type,
             polymorphic_self_type,
             monomorphic_trait_type,
@@ -341,10 +357,16 @@ impl ImplPolymorphism {
 
         Ok(result)
     }
+
+    #[inline(always)]
+    pub fn check_bounds(&self, group: &mut Group) -> Result<bool> {
+        self.bounds.check(self, group)
+    }
 }
 
 pub struct TraitPolymorphism<'a> {
     trait_type: TypePolymorphism<'a>,
+    bounds: Bounds,
     self_types: VecDeque<&'a Type>,
     self_rotation: usize,
     monomorphic_trait_type: Type,
@@ -413,6 +435,7 @@ impl<'a> PolymorphicScope for TraitPolymorphism<'a> {
 
 impl<'a> TraitPolymorphism<'a> {
     pub fn new(ident: &'a Ident, generics: &mut Generics, self_types: &'a [Type]) -> Result<Self> {
+        let bounds = Bounds::from(&*generics);
         let trait_type = TypePolymorphism::new(ident, generics)?;
 
         let monomorphic_trait_type = trait_type.make_type();
@@ -432,6 +455,7 @@ impl<'a> TraitPolymorphism<'a> {
 
         Ok(Self {
             trait_type,
+            bounds,
             self_types: self_types.iter().collect(),
             self_rotation: 0,
             monomorphic_trait_type,
@@ -456,6 +480,11 @@ impl<'a> TraitPolymorphism<'a> {
         Ok(result)
     }
 
+    #[inline(always)]
+    pub fn check_bounds(&self, group: &mut Group) -> Result<bool> {
+        self.bounds.check(self, group)
+    }
+
     #[inline]
     fn specialize_self_type(&mut self) -> Result<()> {
         let polymorphic_self_type = self
@@ -480,6 +509,7 @@ impl<'a> TraitPolymorphism<'a> {
 pub struct SignaturePolymorphism<'a> {
     ident: &'a Ident,
     generics: GenericsPolymorphism,
+    bounds: Bounds,
     inputs: ArgumentsPolymorphism,
     output: Option<&'a Type>,
 }
@@ -491,6 +521,7 @@ impl<'a> SignaturePolymorphism<'a> {
         inputs: &mut Punctuated<FnArg, Token![,]>,
         output: &'a ReturnType,
     ) -> Result<Self> {
+        let bounds = Bounds::from(&*generics);
         let generics = GenericsPolymorphism::try_from(generics)?;
         let inputs = ArgumentsPolymorphism::try_from(inputs)?;
 
@@ -503,6 +534,7 @@ impl<'a> SignaturePolymorphism<'a> {
         Ok(Self {
             ident,
             generics,
+            bounds,
             inputs,
             output,
         })
@@ -532,6 +564,13 @@ pub struct FunctionPolymorphism<'a, S: PolymorphicScope> {
     pub signature: &'a SignaturePolymorphism<'a>,
 }
 
+impl<'a, S: PolymorphicScope> FunctionPolymorphism<'a, S> {
+    #[inline(always)]
+    pub fn check_bounds(&self, group: &mut Group) -> Result<bool> {
+        self.signature.bounds.check(self, group)
+    }
+}
+
 impl<'a, S: PolymorphicScope> PolymorphicScope for FunctionPolymorphism<'a, S> {
     #[inline]
     fn specialize_expr(&self, expr: &mut Expr) -> Result<()> {
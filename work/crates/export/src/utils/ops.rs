@@ -189,6 +189,54 @@ impl<'a> ToTokens for ScriptDisplay<'a> {
     }
 }
 
+pub struct ScriptIterate<'a> {
+    pub span: Span,
+    pub lhs: &'a Type,
+}
+
+impl<'a> DefaultScriptOperator for ScriptIterate<'a> {
+    #[inline(always)]
+    fn new_stream(span: Span, lhs: &Type, _rhs: Option<&Type>) -> TokenStream {
+        ScriptIterate { span, lhs }.to_token_stream()
+    }
+}
+
+impl<'a> ToTokens for ScriptIterate<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let span = self.span;
+
+        let core = span.face_core();
+        let into_iterator = span.face_into_iterator();
+
+        let lhs = self.lhs;
+
+        quote_spanned!(span=>
+            #[allow(non_local_definitions)]
+            impl #core::runtime::ops::ScriptIterate for #lhs {
+                type Item = <#lhs as #into_iterator>::Item;
+
+                fn script_iterate(
+                    origin: #core::runtime::Origin,
+                    lhs: #core::runtime::Arg,
+                ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                    let lhs = #core::runtime::Cell::take::<#lhs>(
+                        lhs.data,
+                        lhs.origin,
+                    )?;
+
+                    #core::runtime::Cell::give(
+                        origin,
+                        #core::runtime::ScriptIterator::new(
+                            <#lhs as #into_iterator>::into_iter(lhs),
+                        ),
+                    )
+                }
+            }
+        )
+        .to_tokens(tokens)
+    }
+}
+
 pub struct ScriptPartialEq<'a> {
     pub span: Span,
     pub lhs: &'a Type,
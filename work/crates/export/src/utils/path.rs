@@ -39,6 +39,10 @@ use syn::{spanned::Spanned, GenericArgument, Path, PathArguments, ReturnType, Ty
 
 use crate::utils::Operator;
 
+// The greatest number of parameters of the predefined callback aliases
+// (`Fn0` ... `Fn12`) in the `ad_astra::runtime::ops` module.
+const MAX_CALLBACK_ARITY: usize = 12;
+
 pub trait PathUtils<'a> {
     fn matches_bracketed(
         self,
@@ -56,6 +60,14 @@ pub trait PathUtils<'a> {
 
     fn matches_box(self) -> Option<PathMeta<'a>>;
 
+    fn matches_arc(self) -> Option<PathMeta<'a>>;
+
+    fn matches_option(self) -> Option<PathMeta<'a>>;
+
+    fn matches_result(self) -> Option<PathMeta<'a>>;
+
+    fn matches_string(self) -> Option<PathMeta<'a>>;
+
     fn matches_clone(self) -> Option<PathMeta<'a>>;
 
     fn matches_copy(self) -> Option<PathMeta<'a>>;
@@ -76,6 +88,10 @@ pub trait PathUtils<'a> {
 
     fn matches_display(self) -> Option<PathMeta<'a>>;
 
+    fn matches_iterator(self) -> Option<PathMeta<'a>>;
+
+    fn matches_into_iterator(self) -> Option<PathMeta<'a>>;
+
     fn matches_add(self) -> Option<PathMeta<'a>>;
 
     fn matches_add_assign(self) -> Option<PathMeta<'a>>;
@@ -225,6 +241,10 @@ impl<'a> PathUtils<'a> for &'a Path {
                 return Some(*operator);
             }
 
+            if let Some(_) = self.matches_bracketed(&["ops", trait_name.as_str()], 0..=0) {
+                return Some(*operator);
+            }
+
             if let Some(_) =
                 self.matches_bracketed(&["ad_astra", "runtime", "ops", trait_name.as_str()], 0..=0)
             {
@@ -256,7 +276,7 @@ impl<'a> PathUtils<'a> for &'a Path {
     }
 
     fn matches_rust_fn(self) -> Option<PathMeta<'a>> {
-        for params in 0..8 {
+        for params in 0..=MAX_CALLBACK_ARITY {
             let type_name = format!("Fn{params}");
             let args = params + 1;
 
@@ -295,6 +315,86 @@ impl<'a> PathUtils<'a> for &'a Path {
         None
     }
 
+    fn matches_arc(self) -> Option<PathMeta<'a>> {
+        static FREE: [&'static str; 1] = ["Arc"];
+        static STD: [&'static str; 3] = ["std", "sync", "Arc"];
+        static ALLOC: [&'static str; 3] = ["alloc", "sync", "Arc"];
+
+        if let Some(result) = self.matches_bracketed(&FREE, 1..=1) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&STD, 1..=1) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&ALLOC, 1..=1) {
+            return Some(result);
+        }
+
+        None
+    }
+
+    fn matches_option(self) -> Option<PathMeta<'a>> {
+        static FREE: [&'static str; 1] = ["Option"];
+        static STD: [&'static str; 3] = ["std", "option", "Option"];
+        static CORE: [&'static str; 3] = ["core", "option", "Option"];
+
+        if let Some(result) = self.matches_bracketed(&FREE, 1..=1) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&STD, 1..=1) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&CORE, 1..=1) {
+            return Some(result);
+        }
+
+        None
+    }
+
+    fn matches_result(self) -> Option<PathMeta<'a>> {
+        static FREE: [&'static str; 1] = ["Result"];
+        static STD: [&'static str; 3] = ["std", "result", "Result"];
+        static CORE: [&'static str; 3] = ["core", "result", "Result"];
+
+        if let Some(result) = self.matches_bracketed(&FREE, 2..=2) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&STD, 2..=2) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&CORE, 2..=2) {
+            return Some(result);
+        }
+
+        None
+    }
+
+    fn matches_string(self) -> Option<PathMeta<'a>> {
+        static FREE: [&'static str; 1] = ["String"];
+        static STD: [&'static str; 3] = ["std", "string", "String"];
+        static ALLOC: [&'static str; 3] = ["alloc", "string", "String"];
+
+        if let Some(result) = self.matches_bracketed(&FREE, 0..=0) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&STD, 0..=0) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&ALLOC, 0..=0) {
+            return Some(result);
+        }
+
+        None
+    }
+
     fn matches_clone(self) -> Option<PathMeta<'a>> {
         static FREE: [&'static str; 1] = ["Clone"];
         static STD: [&'static str; 3] = ["std", "clone", "Clone"];
@@ -459,11 +559,16 @@ impl<'a> PathUtils<'a> for &'a Path {
         static FREE: [&'static str; 1] = ["Debug"];
         static STD: [&'static str; 3] = ["std", "fmt", "Debug"];
         static CORE: [&'static str; 3] = ["core", "fmt", "Debug"];
+        static FMT: [&'static str; 2] = ["fmt", "Debug"];
 
         if let Some(result) = self.matches_bracketed(&FREE, 0..=0) {
             return Some(result);
         }
 
+        if let Some(result) = self.matches_bracketed(&FMT, 0..=0) {
+            return Some(result);
+        }
+
         if let Some(result) = self.matches_bracketed(&STD, 0..=0) {
             return Some(result);
         }
@@ -479,11 +584,66 @@ impl<'a> PathUtils<'a> for &'a Path {
         static FREE: [&'static str; 1] = ["Display"];
         static STD: [&'static str; 3] = ["std", "fmt", "Display"];
         static CORE: [&'static str; 3] = ["core", "fmt", "Display"];
+        static FMT: [&'static str; 2] = ["fmt", "Display"];
+
+        if let Some(result) = self.matches_bracketed(&FREE, 0..=0) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&FMT, 0..=0) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&STD, 0..=0) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&CORE, 0..=0) {
+            return Some(result);
+        }
+
+        None
+    }
+
+    fn matches_iterator(self) -> Option<PathMeta<'a>> {
+        static FREE: [&'static str; 1] = ["Iterator"];
+        static STD: [&'static str; 3] = ["std", "iter", "Iterator"];
+        static CORE: [&'static str; 3] = ["core", "iter", "Iterator"];
+        static ITER: [&'static str; 2] = ["iter", "Iterator"];
+
+        if let Some(result) = self.matches_bracketed(&FREE, 0..=0) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&ITER, 0..=0) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&STD, 0..=0) {
+            return Some(result);
+        }
+
+        if let Some(result) = self.matches_bracketed(&CORE, 0..=0) {
+            return Some(result);
+        }
+
+        None
+    }
+
+    fn matches_into_iterator(self) -> Option<PathMeta<'a>> {
+        static FREE: [&'static str; 1] = ["IntoIterator"];
+        static STD: [&'static str; 3] = ["std", "iter", "IntoIterator"];
+        static CORE: [&'static str; 3] = ["core", "iter", "IntoIterator"];
+        static ITER: [&'static str; 2] = ["iter", "IntoIterator"];
 
         if let Some(result) = self.matches_bracketed(&FREE, 0..=0) {
             return Some(result);
         }
 
+        if let Some(result) = self.matches_bracketed(&ITER, 0..=0) {
+            return Some(result);
+        }
+
         if let Some(result) = self.matches_bracketed(&STD, 0..=0) {
             return Some(result);
         }
@@ -474,6 +474,29 @@ impl ShallowInner {
                 return;
             }
 
+            Operator::Iterate => {
+                let into_iterator = span.face_into_iterator();
+
+                quote_spanned!(span=>
+                    #[allow(non_local_definitions)]
+                    impl #core::runtime::ops::ScriptIterate for #lhs {
+                        type Item = <#lhs as #into_iterator>::Item;
+
+                        fn script_iterate(
+                            _origin: #core::runtime::Origin,
+                            _lhs: #core::runtime::Arg,
+                        ) -> #core::runtime::RuntimeResult<#core::runtime::Cell> {
+                            let name = #type_name::<Self>();
+                            #panic("{name} type was not registered. Probably because export has been \
+                            disabled for this type.");
+                        }
+                    }
+                )
+                .to_tokens(body);
+
+                return;
+            }
+
             Operator::PartialEq => {
                 let rhs = rhs.as_ref().unwrap_or(lhs);
 
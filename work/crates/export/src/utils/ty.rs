@@ -51,6 +51,7 @@ pub trait TypeUtils<'a> {
     fn impl_registered_type(self) -> TokenStream;
     fn type_hint(self) -> TokenStream;
     fn downcast_hint(self) -> TokenStream;
+    fn downcast_schema(self) -> TokenStream;
     fn upcast_hint(self) -> TokenStream;
     fn impl_coercion(self, coercion: Coercion) -> TokenStream;
     fn impl_shallow_coercion(self, coercion: Coercion) -> TokenStream;
@@ -86,6 +87,15 @@ impl<'a, T: ToTokens> TypeUtils<'a> for &'a T {
         quote_spanned!(span=> <#ty as #core::runtime::Downcast>::hint())
     }
 
+    fn downcast_schema(self) -> TokenStream {
+        let ty = self;
+        let span = ty.span();
+
+        let core = span.face_core();
+
+        quote_spanned!(span=> <#ty as #core::runtime::Downcast>::schema())
+    }
+
     fn upcast_hint(self) -> TokenStream {
         let ty = self;
         let span = ty.span();
@@ -105,6 +115,42 @@ impl<'a, T: ToTokens> TypeUtils<'a> for &'a T {
 
         let hint = self.type_hint();
 
+        // Exported structs that implement FromScriptStruct can also be
+        // constructed from the script structs.
+        let (downcast_own_body, coerce, schema) = match coercion.script_struct {
+            false => (
+                quote_spanned!(span=>
+                    let cell = #core::runtime::Provider::to_owned(provider);
+
+                    #core::runtime::Cell::take::<#ty>(cell, origin)
+                ),
+                None,
+                None,
+            ),
+
+            true => {
+                let intrinsics = span.face_intrinsics();
+                let option = span.face_option();
+
+                (
+                    quote_spanned!(span=>
+                        #intrinsics::downcast_exported_struct::<#ty>(origin, provider)
+                    ),
+                    Some(quote_spanned!(span=>
+                        #intrinsics::coerce_exported_struct::<#ty>(origin, cell)?;
+                    )),
+                    Some(quote_spanned!(span=>
+                        #[inline(always)]
+                        fn schema() -> #option<&'static #core::runtime::StructSchema> {
+                            #option::Some(
+                                <#ty as #core::runtime::FromScriptStruct>::schema(),
+                            )
+                        }
+                    )),
+                )
+            }
+        };
+
         let upcast_own = match coercion.upcast_own {
             false => None,
 
@@ -188,15 +234,15 @@ impl<'a, T: ToTokens> TypeUtils<'a> for &'a T {
                         origin: #core::runtime::Origin,
                         provider: #core::runtime::Provider<'a>,
                     ) -> #core::runtime::RuntimeResult<Self> {
-                        let cell = #core::runtime::Provider::to_owned(provider);
-
-                        #core::runtime::Cell::take::<#ty>(cell, origin)
+                        #downcast_own_body
                     }
 
                     #[inline(always)]
                     fn hint() -> #core::runtime::TypeHint {
                         #core::runtime::TypeHint::Type(#hint)
                     }
+
+                    #schema
                 }
             )),
         };
@@ -217,6 +263,8 @@ impl<'a, T: ToTokens> TypeUtils<'a> for &'a T {
                             &origin,
                         )?;
 
+                        #coerce
+
                         #core::runtime::Cell::borrow_ref::<#ty>(cell, origin)
                     }
 
@@ -224,6 +272,8 @@ impl<'a, T: ToTokens> TypeUtils<'a> for &'a T {
                     fn hint() -> #core::runtime::TypeHint {
                         #core::runtime::TypeHint::Type(#hint)
                     }
+
+                    #schema
                 }
             )),
         };
@@ -244,6 +294,8 @@ impl<'a, T: ToTokens> TypeUtils<'a> for &'a T {
                             &origin,
                         )?;
 
+                        #coerce
+
                         #core::runtime::Cell::borrow_mut::<#ty>(cell, origin)
                     }
 
@@ -251,6 +303,8 @@ impl<'a, T: ToTokens> TypeUtils<'a> for &'a T {
                     fn hint() -> #core::runtime::TypeHint {
                         #core::runtime::TypeHint::Type(#hint)
                     }
+
+                    #schema
                 }
             )),
         };
@@ -471,6 +525,7 @@ pub struct Coercion {
     pub upcast_own: bool,
     pub upcast_ref: bool,
     pub upcast_mut: bool,
+    pub script_struct: bool,
 }
 
 impl Coercion {
@@ -482,6 +537,7 @@ impl Coercion {
         self.upcast_own = self.upcast_own || other.upcast_own;
         self.upcast_ref = self.upcast_ref || other.upcast_ref;
         self.upcast_mut = self.upcast_mut || other.upcast_mut;
+        self.script_struct = self.script_struct || other.script_struct;
     }
 }
 
@@ -570,6 +626,7 @@ pub(super) fn make_param_fn_meta(
     let core = span.face_core();
     let intrinsics = span.face_intrinsics();
     let vec_macro = span.face_vec_macro();
+    let vec = span.face_vec();
     let option = span.face_option();
 
     let inputs = meta.inputs.into_iter().map(|ty| {
@@ -598,6 +655,8 @@ pub(super) fn make_param_fn_meta(
         quote_spanned!(span=> #core::runtime::Param {
             name: #option::None,
             hint: #hint,
+            schema: #option::None,
+            doc: #option::None,
         })
     });
 
@@ -630,6 +689,8 @@ pub(super) fn make_param_fn_meta(
                     #( #inputs ),*
                 ]),
                 output: #output,
+                contracts: #vec::new(),
+                variadic: false,
             }
         });
 
@@ -0,0 +1,329 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::time::Duration;
+
+use ad_astra::{
+    analysis::{ModuleRead, ScriptModule},
+    export,
+    interpret::{ScriptFn, VmObserver},
+    lady_deirdre::analysis::TriggerHandle,
+    runtime::{Cell, Origin, ScriptPackage},
+};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+#[export(package)]
+#[derive(Default)]
+struct Package;
+
+/// A 2D point used to measure exported field access.
+#[export]
+#[derive(Clone, Copy)]
+pub struct BenchPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Constructs a new BenchPoint.
+#[export]
+pub fn bench_point(x: f64, y: f64) -> BenchPoint {
+    BenchPoint { x, y }
+}
+
+/// Adds two numbers. Used to measure exported function call overhead.
+#[export]
+pub fn bench_add(a: usize, b: usize) -> usize {
+    a + b
+}
+
+/// Creates an array of `count` floats. The Script Engine stores the returned
+/// vector as is, without boxing individual elements.
+#[export]
+pub fn bench_floats(count: usize) -> Vec<f64> {
+    (0..count).map(|index| index as f64 * 0.5).collect()
+}
+
+/// Sums the array of floats. The slice borrows the script array's memory
+/// directly, without per-element conversion.
+#[export]
+pub fn bench_sum(values: &[f64]) -> f64 {
+    values.iter().sum()
+}
+
+const ARITHMETIC: &str = include_str!("fixtures/arithmetic.adastra");
+const EXPORTED_FIELDS: &str = include_str!("fixtures/exported_fields.adastra");
+const STRUCT_ENTRIES: &str = include_str!("fixtures/struct_entries.adastra");
+const EXPORTED_CALLS: &str = include_str!("fixtures/exported_calls.adastra");
+const STRING_CONCAT: &str = include_str!("fixtures/string_concat.adastra");
+const CLOSURES: &str = include_str!("fixtures/closures.adastra");
+const COMPILATION: &str = include_str!("fixtures/compilation.adastra");
+const ARRAY_SUM: &str = include_str!("fixtures/array_sum.adastra");
+const SLICE_DOWNCAST: &str = include_str!("fixtures/slice_downcast.adastra");
+const STRING_APPEND: &str = include_str!("fixtures/string_append.adastra");
+const STRING_BUILDER: &str = include_str!("fixtures/string_builder.adastra");
+
+fn compile(name: &str, text: &str) -> ScriptFn {
+    let module = ScriptModule::new(Package::meta(), text);
+    module.rename(name);
+
+    let handle = TriggerHandle::new();
+    let module_read = module.read(&handle, 1).expect("Module read error.");
+
+    for depth in 1..=3 {
+        let diagnostics = module_read
+            .diagnostics(depth)
+            .expect("Module analysis error.");
+
+        if !diagnostics.is_empty() {
+            panic!(
+                "Fixture {name:?} has diagnostic issues:\n{}",
+                diagnostics.highlight(&module_read.text(), !0),
+            );
+        }
+    }
+
+    module_read.compile().expect("Script compilation error.")
+}
+
+fn run(script_fn: &ScriptFn) -> Cell {
+    match script_fn.run() {
+        Ok(result) => result,
+        Err(error) => panic!("Fixture evaluation error: {error}"),
+    }
+}
+
+fn bench_runtime<T: PartialEq + std::fmt::Debug>(
+    criterion: &mut Criterion,
+    name: &str,
+    text: &str,
+    expected: T,
+    read: impl Fn(Cell) -> T,
+) {
+    let script_fn = compile(name, text);
+
+    // The fixture result is verified before measuring to ensure that the
+    // benchmark measures correct code.
+    assert_eq!(
+        read(run(&script_fn)),
+        expected,
+        "Fixture {name:?} mismatch."
+    );
+
+    criterion.bench_function(name, |bencher| bencher.iter(|| run(&script_fn)));
+}
+
+fn read_usize(cell: Cell) -> usize {
+    cell.take::<usize>(Origin::nil())
+        .expect("Expected usize result.")
+}
+
+fn read_f64(cell: Cell) -> f64 {
+    cell.take::<f64>(Origin::nil())
+        .expect("Expected f64 result.")
+}
+
+fn arithmetic(criterion: &mut Criterion) {
+    let expected = (0..20000usize).map(|i| i * 3 % 7 + i / 5).sum::<usize>();
+
+    bench_runtime(criterion, "arithmetic", ARITHMETIC, expected, read_usize);
+}
+
+// Measures the overhead of the Virtual Machine notifications when an observer
+// that ignores all events is installed. Compare with the "arithmetic"
+// benchmark.
+fn observed(criterion: &mut Criterion) {
+    struct Noop;
+
+    impl VmObserver for Noop {}
+
+    let script_fn = compile("observed", ARITHMETIC);
+
+    criterion.bench_function("observed", |bencher| {
+        bencher.iter(|| {
+            let mut observer = Noop;
+
+            match script_fn.instrument(&mut observer).run() {
+                Ok(result) => result,
+                Err(error) => panic!("Fixture evaluation error: {error}"),
+            }
+        })
+    });
+}
+
+fn exported_fields(criterion: &mut Criterion) {
+    let mut point = BenchPoint { x: 1.5, y: 2.5 };
+    let mut expected = 0.0;
+
+    for _ in 0..10000 {
+        point.x += 1.0;
+        expected += point.x + point.y;
+    }
+
+    bench_runtime(
+        criterion,
+        "exported_fields",
+        EXPORTED_FIELDS,
+        expected,
+        read_f64,
+    );
+}
+
+fn struct_entries(criterion: &mut Criterion) {
+    bench_runtime(
+        criterion,
+        "struct_entries",
+        STRUCT_ENTRIES,
+        30000,
+        read_usize,
+    );
+}
+
+fn exported_calls(criterion: &mut Criterion) {
+    let expected = (0..10000usize).sum::<usize>();
+
+    bench_runtime(
+        criterion,
+        "exported_calls",
+        EXPORTED_CALLS,
+        expected,
+        read_usize,
+    );
+}
+
+fn string_concat(criterion: &mut Criterion) {
+    let expected = (0..2000usize)
+        .map(|i| format!("item {i}: abcdef").len())
+        .sum::<usize>();
+
+    bench_runtime(
+        criterion,
+        "string_concat",
+        STRING_CONCAT,
+        expected,
+        read_usize,
+    );
+}
+
+fn closures(criterion: &mut Criterion) {
+    bench_runtime(criterion, "closures", CLOSURES, 20000, read_usize);
+}
+
+fn array_sum(criterion: &mut Criterion) {
+    let expected = bench_floats(1000000).iter().sum::<f64>();
+
+    bench_runtime(criterion, "array_sum", ARRAY_SUM, expected, read_f64);
+}
+
+fn slice_downcast(criterion: &mut Criterion) {
+    let values = bench_floats(1000000);
+    let mut expected = 0.0;
+
+    for _ in 0..10 {
+        expected += bench_sum(&values);
+    }
+
+    bench_runtime(
+        criterion,
+        "slice_downcast",
+        SLICE_DOWNCAST,
+        expected,
+        read_f64,
+    );
+}
+
+// Builds a 1 MB string by appending to a local variable. The assembler turns
+// the `text = [text, piece]` pattern into an in-place append, so the running
+// time should grow linearly with the string length.
+fn string_append(criterion: &mut Criterion) {
+    bench_runtime(
+        criterion,
+        "string_append",
+        STRING_APPEND,
+        1000000,
+        read_usize,
+    );
+}
+
+fn string_builder(criterion: &mut Criterion) {
+    bench_runtime(
+        criterion,
+        "string_builder",
+        STRING_BUILDER,
+        1000000,
+        read_usize,
+    );
+}
+
+fn compilation(criterion: &mut Criterion) {
+    let script_fn = compile("compilation", COMPILATION);
+
+    assert_eq!(
+        read_usize(run(&script_fn)),
+        4051,
+        "Fixture \"compilation\" mismatch.",
+    );
+
+    criterion.bench_function("compilation", |bencher| {
+        bencher.iter_batched(
+            || ScriptModule::new(Package::meta(), COMPILATION),
+            |module| {
+                let handle = TriggerHandle::new();
+                let module_read = module.read(&handle, 1).expect("Module read error.");
+
+                module_read.compile().expect("Script compilation error.")
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(
+    name = runtime;
+    config = Criterion::default().measurement_time(Duration::from_secs(10));
+    targets = arithmetic, observed, exported_fields, struct_entries, exported_calls, string_concat, closures,
+);
+
+criterion_group!(
+    name = arrays;
+    config = Criterion::default().sample_size(10);
+    targets = array_sum, slice_downcast, string_append, string_builder,
+);
+
+criterion_group!(
+    name = compiler;
+    config = Criterion::default().sample_size(20);
+    targets = compilation,
+);
+
+criterion_main!(runtime, arrays, compiler);
@@ -0,0 +1,269 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::sync::Arc;
+
+use lady_deirdre::lexis::SiteSpan;
+
+/// An opaque piece of the host's data attached to a fragment of the script
+/// module's source code.
+///
+/// Annotations are carried metadata: they never affect the analysis of the
+/// module. The typical use case is attributing the regions of the script to
+/// their authors in collaborative editing environments.
+///
+/// You can attach an annotation using the
+/// [annotate](crate::analysis::ModuleWrite::annotate) function and query the
+/// annotations using the [annotations](crate::analysis::ModuleRead::annotations)
+/// function.
+///
+/// The module automatically moves the annotation spans on each source code
+/// edit, such that each annotation stays attached to the surviving fragment of
+/// the annotated text. When an edit rewrites the annotated text entirely,
+/// the module removes the annotation.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub struct ModuleAnnotation {
+    /// The identifier of the annotation, unique within the script module.
+    pub id: u64,
+
+    /// The current span of the annotated source code fragment.
+    pub span: SiteSpan,
+
+    /// The host's data.
+    pub data: Arc<[u8]>,
+}
+
+// The annotations of the script module ordered by their identifiers.
+pub(super) struct ModuleAnnotations {
+    next_id: u64,
+    items: Vec<ModuleAnnotation>,
+}
+
+impl ModuleAnnotations {
+    #[inline(always)]
+    pub(super) fn new() -> Self {
+        Self {
+            next_id: 0,
+            items: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub(super) fn insert(&mut self, span: SiteSpan, data: Arc<[u8]>) -> u64 {
+        let id = self.next_id;
+
+        self.next_id += 1;
+
+        self.items.push(ModuleAnnotation { id, span, data });
+
+        id
+    }
+
+    pub(super) fn remove(&mut self, id: u64) -> bool {
+        let Ok(index) = self.items.binary_search_by_key(&id, |item| item.id) else {
+            return false;
+        };
+
+        let _ = self.items.remove(index);
+
+        true
+    }
+
+    #[inline(always)]
+    pub(super) fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    // Returns the annotations that intersect the `span`. An empty span
+    // matches the annotations that contain the spanned site.
+    pub(super) fn query(&self, span: &SiteSpan) -> Vec<ModuleAnnotation> {
+        let end = span.end.max(span.start + 1);
+
+        let mut result = self
+            .items
+            .iter()
+            .filter(|item| item.span.start < end && item.span.end > span.start)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        result.sort_by_key(|item| (item.span.start, item.id));
+
+        result
+    }
+
+    // Moves the annotation spans according to the edit that replaced
+    // the `span` fragment with `inserted` characters.
+    pub(super) fn remap(&mut self, span: &SiteSpan, inserted: usize) {
+        self.items.retain_mut(|item| {
+            let annotated = &mut item.span;
+
+            if annotated.end <= span.start {
+                return true;
+            }
+
+            if span.end <= annotated.start {
+                annotated.start = annotated.start - (span.end - span.start) + inserted;
+                annotated.end = annotated.end - (span.end - span.start) + inserted;
+
+                return true;
+            }
+
+            // The edit overlaps the annotated fragment. The inserted text
+            // becomes a part of the annotation only if the edit is strictly
+            // inside the fragment.
+            let start = match span.start <= annotated.start {
+                true => span.start + inserted,
+                false => annotated.start,
+            };
+
+            let end = match span.end >= annotated.end {
+                true => span.start,
+                false => annotated.end - (span.end - span.start) + inserted,
+            };
+
+            if start >= end {
+                return false;
+            }
+
+            *annotated = start..end;
+
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::{
+        analysis::TriggerHandle,
+        lexis::{SiteSpan, SourceCode},
+    };
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ModuleWrite, ScriptModule},
+        format::ScriptFormatConfig,
+        runtime::ScriptPackage,
+    };
+
+    fn fragment(text: &str, span: &SiteSpan) -> String {
+        text.chars()
+            .skip(span.start)
+            .take(span.end - span.start)
+            .collect()
+    }
+
+    #[test]
+    fn test_annotations_remap() {
+        let module = ScriptModule::new(TestPackage::meta(), "let foo = 10;\nlet bar = foo;\n");
+
+        let handle = TriggerHandle::new();
+        let mut write = module.write(&handle, 1).unwrap();
+
+        let foo = write.annotate(4..7, "alice").unwrap();
+        let bar = write.annotate(18..21, "bob").unwrap();
+        let tail = write.annotate(24..28, "carol").unwrap();
+
+        let _ = write.edit(0..0, "// Header.\n").unwrap();
+        let _ = write.edit(38..38, "_value").unwrap();
+        let _ = write.edit(29..32, "qux").unwrap();
+
+        let text = write.text().substring(..).into_owned();
+        let annotations = write.annotations(..).unwrap();
+
+        assert_eq!(annotations.len(), 2);
+
+        assert_eq!(annotations[0].id, foo);
+        assert_eq!(fragment(&text, &annotations[0].span), "foo");
+
+        assert_eq!(annotations[1].id, tail);
+        assert_eq!(fragment(&text, &annotations[1].span), "foo_value;");
+        assert_eq!(annotations[1].data.as_ref(), b"carol");
+
+        assert!(!write.remove_annotation(bar));
+        assert!(write.remove_annotation(foo));
+
+        assert_eq!(write.annotations(..).unwrap().len(), 1);
+        assert!(write.annotations(0..10).unwrap().is_empty());
+        assert!(write.annotate(5..5, "empty").is_err());
+    }
+
+    #[test]
+    fn test_annotations_format() {
+        static TEXT: &str = "let   first=10;\n\
+        let result = [first_argument_value, second_argument_value, third_argument_value, 4];\n";
+
+        let module = ScriptModule::new(TestPackage::meta(), TEXT);
+
+        let handle = TriggerHandle::new();
+        let mut write = module.write(&handle, 1).unwrap();
+
+        let first = write.annotate(6..11, "alice").unwrap();
+        let third = write.annotate(75..95, "bob").unwrap();
+
+        let summary = write
+            .apply_format(ScriptFormatConfig::default())
+            .unwrap()
+            .unwrap();
+
+        assert!(!summary.is_empty());
+
+        let text = write.text().substring(..).into_owned();
+
+        assert_eq!(
+            text,
+            "let first = 10;\n\
+            let result = [first_argument_value, second_argument_value, \
+            third_argument_value,\n    4];\n",
+        );
+
+        let annotations = write.annotations(..).unwrap();
+
+        assert_eq!(annotations.len(), 2);
+
+        assert_eq!(annotations[0].id, first);
+        assert_eq!(fragment(&text, &annotations[0].span), "first");
+
+        assert_eq!(annotations[1].id, third);
+        assert_eq!(
+            fragment(&text, &annotations[1].span),
+            "third_argument_value"
+        );
+        assert_eq!(annotations[1].data.as_ref(), b"bob");
+    }
+}
@@ -0,0 +1,249 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use lady_deirdre::lexis::SiteSpan;
+
+/// A source code span of a compiled [ScriptFn](crate::interpret::ScriptFn)
+/// carried across the script module's edits.
+///
+/// The host tools usually record the data of the script evaluation, such as
+/// breakpoints and coverage, against the source code text from which the
+/// script function was compiled. Once the user edits the module's text, these
+/// spans no longer match the current text.
+///
+/// The [bookmarks_to_current](crate::analysis::ModuleRead::bookmarks_to_current)
+/// function maps the spans of the compiled text to the spans of the current
+/// text, and the
+/// [bookmarks_to_compiled](crate::analysis::ModuleRead::bookmarks_to_compiled)
+/// function maps the spans in the opposite direction.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Bookmark {
+    /// None of the edits touched the spanned fragment.
+    ///
+    /// The value is the span of the same fragment in the target text.
+    Intact(SiteSpan),
+
+    /// Some of the edits rewrote the spanned fragment partially or entirely.
+    ///
+    /// The value is an approximate span in the target text that covers the
+    /// edited region. The data recorded against this span is unreliable:
+    /// for example, the LSP server can display the breakpoint as stale.
+    Edited(SiteSpan),
+
+    /// The span cannot be mapped.
+    ///
+    /// This happens if the script function was not compiled from this module,
+    /// or if the compiled text revision is outside of the module's edit
+    /// history retention window (see
+    /// [set_edit_history_capacity](crate::analysis::ModuleWrite::set_edit_history_capacity)).
+    Lost,
+}
+
+impl Bookmark {
+    /// Returns the mapped span if the spanned fragment is
+    /// [intact](Self::Intact).
+    #[inline(always)]
+    pub fn intact(&self) -> Option<&SiteSpan> {
+        match self {
+            Self::Intact(span) => Some(span),
+            _ => None,
+        }
+    }
+
+    /// Returns the mapped span (exact or approximate) unless the bookmark is
+    /// [lost](Self::Lost).
+    #[inline(always)]
+    pub fn span(&self) -> Option<&SiteSpan> {
+        match self {
+            Self::Intact(span) | Self::Edited(span) => Some(span),
+            Self::Lost => None,
+        }
+    }
+}
+
+// Moves the `span` through a single edit that replaces `old_len` characters
+// starting from the `start` site with `new_len` characters. Returns true if
+// the edit rewrote a part of the span.
+//
+// Text inserted exactly at the span's boundaries goes outside of the span.
+pub(super) fn shift_span(
+    span: &mut SiteSpan,
+    start: usize,
+    old_len: usize,
+    new_len: usize,
+) -> bool {
+    let end = start + old_len;
+
+    let edited = start < span.end && end > span.start;
+
+    span.start = match span.start {
+        site if site >= end => site - old_len + new_len,
+        site if site > start => start,
+        site => site,
+    };
+
+    span.end = match span.end {
+        site if site >= end => site - old_len + new_len,
+        site if site > start => start + new_len,
+        site => site,
+    };
+
+    edited
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::{analysis::TriggerHandle, lexis::SourceCode};
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, Bookmark, ModuleRead, ModuleWrite, ScriptModule},
+        runtime::ScriptPackage,
+    };
+
+    #[test]
+    fn test_bookmarks_edits() {
+        // 0        1         2         3
+        // 0123456789012345678901234567890123
+        // let a = 1;\nlet b = 2;\nlet c = 3;
+        let module = ScriptModule::new(TestPackage::meta(), "let a = 1;\nlet b = 2;\nlet c = 3;");
+        let handle = TriggerHandle::new();
+
+        let script_fn = module.read(&handle, 1).unwrap().compile().unwrap();
+
+        let lines = [0..10, 11..21, 22..32];
+
+        let mut write = module.write(&handle, 1).unwrap();
+
+        assert_eq!(script_fn.text_revision(), Some(0));
+
+        assert_eq!(
+            write.bookmarks_to_current(&script_fn, lines.clone()),
+            [
+                Bookmark::Intact(0..10),
+                Bookmark::Intact(11..21),
+                Bookmark::Intact(22..32),
+            ],
+        );
+
+        // Insertion at the start of the second line.
+        let _ = write.edit(11..11, "foo();\n").unwrap();
+
+        assert_eq!(
+            write.bookmarks_to_current(&script_fn, lines.clone()),
+            [
+                Bookmark::Intact(0..10),
+                Bookmark::Intact(18..28),
+                Bookmark::Intact(29..39),
+            ],
+        );
+
+        // Deletion of the first line.
+        let _ = write.edit(0..11, "").unwrap();
+
+        assert_eq!(
+            write.bookmarks_to_current(&script_fn, lines.clone()),
+            [
+                Bookmark::Edited(0..0),
+                Bookmark::Intact(7..17),
+                Bookmark::Intact(18..28),
+            ],
+        );
+
+        // Replacement inside the last line.
+        let _ = write.edit(26..27, "30").unwrap();
+
+        assert_eq!(
+            write.bookmarks_to_current(&script_fn, lines.clone()),
+            [
+                Bookmark::Edited(0..0),
+                Bookmark::Intact(7..17),
+                Bookmark::Edited(18..29),
+            ],
+        );
+
+        assert_eq!(write.text().substring(7..17), "let b = 2;");
+        assert_eq!(write.text().substring(18..29), "let c = 30;");
+
+        assert_eq!(
+            write.bookmarks_to_compiled(&script_fn, [0..6, 7..17, 18..29, 26..28]),
+            [
+                Bookmark::Edited(11..11),
+                Bookmark::Intact(11..21),
+                Bookmark::Edited(22..32),
+                Bookmark::Edited(30..31),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_bookmarks_lost() {
+        let module = ScriptModule::new(TestPackage::meta(), "let a = 1;");
+        let other = ScriptModule::new(TestPackage::meta(), "let a = 1;");
+        let handle = TriggerHandle::new();
+
+        let script_fn = other.read(&handle, 1).unwrap().compile().unwrap();
+
+        assert_eq!(
+            module
+                .read(&handle, 1)
+                .unwrap()
+                .bookmarks_to_current(&script_fn, [0..10]),
+            [Bookmark::Lost],
+        );
+
+        let script_fn = module.read(&handle, 1).unwrap().compile().unwrap();
+
+        let mut write = module.write(&handle, 1).unwrap();
+
+        write.set_edit_history_capacity(1);
+
+        let _ = write.edit(0..0, "\n").unwrap();
+
+        assert_eq!(
+            write.bookmarks_to_current(&script_fn, [4..5]),
+            [Bookmark::Intact(5..6)],
+        );
+
+        let _ = write.edit(0..0, "\n").unwrap();
+
+        assert_eq!(
+            write.bookmarks_to_current(&script_fn, [4..5]),
+            [Bookmark::Lost]
+        );
+        assert_eq!(
+            write.bookmarks_to_compiled(&script_fn, [6..7]),
+            [Bookmark::Lost]
+        );
+    }
+}
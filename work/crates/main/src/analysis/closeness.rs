@@ -128,6 +128,14 @@ impl Closeness {
         Self(1.0)
     }
 
+    /// Creates a closeness value from the specified percentage.
+    ///
+    /// Values greater than 100 are treated as "100%".
+    #[inline(always)]
+    pub fn from_percents(percents: u16) -> Self {
+        Self(percents.min(100) as f32 / 100.0)
+    }
+
     /// Returns the underlying percentage value rounded up to the nearest
     /// integer.
     #[inline(always)]
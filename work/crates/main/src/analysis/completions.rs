@@ -46,12 +46,12 @@ use lady_deirdre::{
 
 use crate::{
     analysis::{
-        symbols::{EntrySymbol, PackageSymbol, VarSymbol},
+        symbols::{EntrySymbol, ModuleSymbol, PackageSymbol, VarSymbol},
         Description,
         ModuleResultEx,
     },
     report::system_panic,
-    runtime::{Ident, PackageMeta, ScriptIdent, TypeHint},
+    runtime::{Ident, PackageMeta, ScriptIdent, StructSchema, TypeHint},
     semantics::{IdentSemantics, LocalNamespace, PackageSemantics, Tag},
     syntax::{ScriptDoc, ScriptNode},
 };
@@ -89,6 +89,11 @@ pub struct Completions {
     pub scope: CompletionScope,
 
     /// The list of completion candidates.
+    ///
+    /// The candidates are ranked deterministically: the items whose labels
+    /// start with the [pattern](Self::pattern) go first, followed by the
+    /// rest of the items. Within each group, the items preserve their
+    /// declaration order (see [CompletionItem::index]).
     pub items: Vec<CompletionItem>,
 }
 
@@ -130,14 +135,33 @@ impl Completions {
 
         doc.traverse_subtree(&subtree, &mut prompt_analyzer);
 
-        Ok(prompt_analyzer.result?.unwrap_or_else(|| Self {
+        let mut completions = prompt_analyzer.result?.unwrap_or_else(|| Self {
             id,
             site,
             place: site..site,
             pattern: String::new(),
             scope: CompletionScope::Unknown,
             items: Vec::new(),
-        }))
+        });
+
+        completions.rank_items();
+
+        Ok(completions)
+    }
+
+    // The prompt analyzer collects the items in their declaration order.
+    fn rank_items(&mut self) {
+        for (index, item) in self.items.iter_mut().enumerate() {
+            item.index = index;
+        }
+
+        let pattern = self.pattern.as_str();
+
+        self.items.sort_by_cached_key(|item| {
+            let label = item.label.to_string();
+
+            (!label.starts_with(pattern), item.index)
+        });
     }
 }
 
@@ -154,6 +178,18 @@ pub struct CompletionItem {
 
     /// Additional metadata about the completed item.
     pub desc: Description,
+
+    /// The declaration order of the item among the completion candidates.
+    ///
+    /// The order depends on the kind of the candidates: struct entries follow
+    /// the order of the struct literal's entries, exported components follow
+    /// their [registration order](crate::runtime::ComponentHint::index), and
+    /// the variables follow the package members in the order of their
+    /// declarations in the source code.
+    ///
+    /// Clients that re-sort the [items](Completions::items) could use this
+    /// value as a secondary sorting key.
+    pub index: usize,
 }
 
 /// A type of language construct targeted by the code completion.
@@ -206,6 +242,14 @@ pub enum CompletionScope {
     /// foo.bar.<completion site>
     /// ```
     Field,
+
+    /// A code completion for an entry key of a struct literal passed to a
+    /// function that expects a [Rust struct](crate::runtime::FromScriptStruct):
+    ///
+    /// ```text
+    /// foo(struct { bar: 10, <completion site> })
+    /// ```
+    StructEntry,
 }
 
 struct PromptAnalyzer<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> {
@@ -243,6 +287,11 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> Visitor
             } => self.analyze_ident(parent, token, semantics),
 
             ScriptNode::Field { parent, token, .. } => self.analyze_field(parent, token),
+
+            ScriptNode::StructEntryKey { parent, token, .. } => {
+                self.analyze_struct_entry_key(parent, token)
+            }
+
             _ => return true,
         };
 
@@ -294,13 +343,14 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
             let parent_prototype = parent.ty().prototype();
 
             for component in parent_prototype.hint_all_components() {
-                if !component.ty.is_package() {
+                if !component.ty.is_package() || component.hidden {
                     continue;
                 }
 
                 items.push(CompletionItem {
                     label: Ident::Rust(component.name),
                     desc: Description::from_component(&component),
+                    index: 0,
                 });
             }
         };
@@ -357,6 +407,12 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
             self.prompt_namespace(fn_namespace.as_ref(), &mut accumulator)?;
         }
 
+        let mut items = accumulator.into_values().collect::<Vec<_>>();
+
+        items.sort_by_cached_key(|(order, item)| (*order, item.label.to_string()));
+
+        let items = items.into_iter().map(|(_, item)| item).collect();
+
         let mut scope = CompletionScope::Expression;
 
         if let Some(ScriptNode::Expr { parent, node, .. }) = parent.deref(self.doc) {
@@ -381,7 +437,7 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
             place: chunk.start()..(chunk.end() - PROMPT_STRING.len()),
             pattern: String::from(pattern),
             scope,
-            items: accumulator.into_values().collect(),
+            items,
         }))
     }
 
@@ -419,6 +475,7 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
 
             items = match &left_type_resolution.tag {
                 Tag::Struct(struct_ref) => self.prompt_struct(&struct_ref)?,
+                Tag::Enum(enum_ref) => self.prompt_enum(&enum_ref),
                 other => self.prompt_type(other)?,
             };
 
@@ -437,10 +494,160 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
         }))
     }
 
+    fn analyze_struct_entry_key(
+        &self,
+        parent: &NodeRef,
+        token: &TokenRef,
+    ) -> AnalysisResult<Option<Completions>> {
+        let Some(chunk) = token.chunk(self.doc) else {
+            return Ok(None);
+        };
+
+        let Some(byte_index) = chunk.string.find(PROMPT_STRING) else {
+            return Ok(None);
+        };
+
+        let pattern = &chunk.string[0..byte_index];
+
+        let mut items = Vec::new();
+
+        if let Some(schema) = self.struct_entry_schema(parent)? {
+            let mut existing = AHashSet::new();
+
+            if let Some(ScriptNode::StructEntry { parent, .. }) = parent.deref(self.doc) {
+                if let Some(ScriptNode::StructBody { entries, .. }) = parent.deref(self.doc) {
+                    for entry_ref in entries {
+                        let Some(ScriptNode::StructEntry { key, .. }) = entry_ref.deref(self.doc)
+                        else {
+                            continue;
+                        };
+
+                        if let Some(key) = ScriptNode::extract_atom_string(self.doc, key) {
+                            let _ = existing.insert(key);
+                        }
+                    }
+                }
+            }
+
+            for field in schema.fields {
+                if existing.contains(field.name) {
+                    continue;
+                }
+
+                let type_hint = (field.hint)();
+
+                items.push(CompletionItem {
+                    label: Ident::Script(ScriptIdent::from_string(*token, field.name)),
+                    desc: Description {
+                        type_hint,
+                        impl_symbol: ModuleSymbol::Nil,
+                        doc: field.doc.or(type_hint.doc()),
+                        deprecated: None,
+                    },
+                    index: 0,
+                });
+            }
+        }
+
+        let place = chunk.start()..(chunk.end() - PROMPT_STRING.len());
+
+        Ok(Some(Completions {
+            id: self.doc.id(),
+            site: self.site,
+            place,
+            pattern: String::from(pattern),
+            scope: CompletionScope::StructEntry,
+            items,
+        }))
+    }
+
+    fn struct_entry_schema(
+        &self,
+        entry_ref: &NodeRef,
+    ) -> AnalysisResult<Option<&'static StructSchema>> {
+        let Some(ScriptNode::StructEntry { parent, .. }) = entry_ref.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        let Some(ScriptNode::StructBody { parent, .. }) = parent.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        let Some(ScriptNode::Struct { parent, .. }) = parent.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        let mut arg_ref = *parent;
+
+        let (args_ref, args) = loop {
+            match arg_ref.deref(self.doc) {
+                Some(ScriptNode::Expr { parent, .. } | ScriptNode::Arg { parent, .. }) => {
+                    match parent.deref(self.doc) {
+                        Some(ScriptNode::Expr { .. } | ScriptNode::Arg { .. }) => arg_ref = *parent,
+                        Some(ScriptNode::CallArgs { node, args, .. }) => break (node, args),
+                        _ => return Ok(None),
+                    }
+                }
+
+                _ => return Ok(None),
+            }
+        };
+
+        let Some(index) = args.iter().position(|arg| arg == &arg_ref) else {
+            return Ok(None);
+        };
+
+        let arg_name = match arg_ref.deref(self.doc) {
+            Some(ScriptNode::Arg { name, .. }) => name.string(self.doc),
+            _ => None,
+        };
+
+        let Some(ScriptNode::CallArgs { parent, .. }) = args_ref.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        let Some(ScriptNode::Call { left, .. }) = parent.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        let Some(left_node) = left.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        let (_, left_type_resolution) = left_node
+            .type_resolution()
+            .forward()?
+            .snapshot(self.task)
+            .forward()?;
+
+        let Some(meta) = left_type_resolution.tag.invocation_meta() else {
+            return Ok(None);
+        };
+
+        let Some(inputs) = &meta.inputs else {
+            return Ok(None);
+        };
+
+        let param = match arg_name {
+            None => inputs.get(index),
+
+            Some(arg_name) => inputs.iter().find(|param| match &param.name {
+                Some(name) => name.as_ref() == arg_name,
+                None => false,
+            }),
+        };
+
+        let Some(param) = param else {
+            return Ok(None);
+        };
+
+        Ok(param.schema)
+    }
+
     fn prompt_namespace(
         &self,
         namespace: &LocalNamespace,
-        accumulator: &mut AHashMap<CompactString, CompletionItem>,
+        accumulator: &mut AHashMap<CompactString, ((usize, Site), CompletionItem)>,
     ) -> AnalysisResult<()> {
         for (key, value) in &namespace.map {
             if accumulator.contains_key(key) {
@@ -451,6 +658,8 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
                 continue;
             };
 
+            // Package members go first in their registration order, followed
+            // by the variables in the order of their declarations.
             let item = match decl_node {
                 ScriptNode::Root { .. } => {
                     let Some(package) = PackageMeta::by_id(self.doc.id()) else {
@@ -462,10 +671,18 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
                         continue;
                     };
 
-                    CompletionItem {
-                        label: Ident::Rust(component.name),
-                        desc: Description::from_component(&component),
+                    if component.hidden {
+                        continue;
                     }
+
+                    (
+                        (0, component.index),
+                        CompletionItem {
+                            label: Ident::Rust(component.name),
+                            desc: Description::from_component(&component),
+                            index: 0,
+                        },
+                    )
                 }
 
                 ScriptNode::Use { .. } => {
@@ -494,14 +711,23 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
                         continue;
                     };
 
-                    CompletionItem {
-                        label: Ident::Rust(component.name),
-                        desc: Description {
-                            type_hint: component.ty,
-                            impl_symbol: PackageSymbol::from_package_ref(package_ref),
-                            doc: component.doc.or(component.ty.doc()),
-                        },
+                    if component.hidden {
+                        continue;
                     }
+
+                    (
+                        (0, component.index),
+                        CompletionItem {
+                            label: Ident::Rust(component.name),
+                            desc: Description {
+                                type_hint: component.ty,
+                                impl_symbol: PackageSymbol::from_package_ref(package_ref),
+                                doc: component.doc.or(component.ty.doc()),
+                                deprecated: component.deprecated,
+                            },
+                            index: 0,
+                        },
+                    )
                 }
 
                 ScriptNode::For { .. } | ScriptNode::FnParams { .. } => {
@@ -525,14 +751,19 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
 
                     let ty = var_type_resolution.tag.type_hint();
 
-                    CompletionItem {
-                        label: Ident::Script(ScriptIdent::from_string(*token, key.clone())),
-                        desc: Description {
-                            type_hint: ty,
-                            impl_symbol: VarSymbol::from_var_ref(var_ref),
-                            doc: ty.doc(),
+                    (
+                        (1, token.site(self.doc).unwrap_or_default()),
+                        CompletionItem {
+                            label: Ident::Script(ScriptIdent::from_string(*token, key.clone())),
+                            desc: Description {
+                                type_hint: ty,
+                                impl_symbol: VarSymbol::from_var_ref(var_ref),
+                                doc: ty.doc(),
+                                deprecated: None,
+                            },
+                            index: 0,
                         },
-                    }
+                    )
                 }
 
                 ScriptNode::Let { name, .. } => {
@@ -562,14 +793,19 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
 
                     let ty = resolution.type_hint();
 
-                    CompletionItem {
-                        label: Ident::Script(ScriptIdent::from_string(*token, key.clone())),
-                        desc: Description {
-                            type_hint: ty,
-                            impl_symbol: VarSymbol::from_var_ref(name),
-                            doc: ty.doc(),
+                    (
+                        (1, token.site(self.doc).unwrap_or_default()),
+                        CompletionItem {
+                            label: Ident::Script(ScriptIdent::from_string(*token, key.clone())),
+                            desc: Description {
+                                type_hint: ty,
+                                impl_symbol: VarSymbol::from_var_ref(name),
+                                doc: ty.doc(),
+                                deprecated: None,
+                            },
+                            index: 0,
                         },
-                    }
+                    )
                 }
 
                 _ => continue,
@@ -628,7 +864,9 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
                     type_hint: ty,
                     impl_symbol: EntrySymbol::from_struct_entry_key_ref(key_ref),
                     doc: ty.doc(),
+                    deprecated: None,
                 },
+                index: 0,
             });
 
             let _ = visited.insert(key);
@@ -637,6 +875,38 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
         Ok(result)
     }
 
+    fn prompt_enum(&self, enum_ref: &NodeRef) -> Vec<CompletionItem> {
+        let variants = ScriptNode::enum_variants(self.doc, enum_ref);
+
+        let mut result = Vec::with_capacity(variants.len());
+
+        let tag = Tag::Variant(*enum_ref);
+        let ty = tag.type_hint();
+
+        for variant_ref in variants {
+            let Some(ScriptNode::EnumVariant { token, .. }) = variant_ref.deref(self.doc) else {
+                continue;
+            };
+
+            let Some(name) = token.string(self.doc) else {
+                continue;
+            };
+
+            result.push(CompletionItem {
+                label: Ident::Script(ScriptIdent::from_string(*token, name)),
+                desc: Description {
+                    type_hint: ty,
+                    impl_symbol: ModuleSymbol::from(tag),
+                    doc: ty.doc(),
+                    deprecated: None,
+                },
+                index: 0,
+            });
+        }
+
+        result
+    }
+
     fn prompt_type(&self, tag: &Tag) -> AnalysisResult<Vec<CompletionItem>> {
         let mut result = Vec::new();
 
@@ -647,12 +917,281 @@ impl<'a, H: TaskHandle, T: SemanticAccess<ScriptNode, H, RandomState>> PromptAna
         result.reserve(ty.prototype().components_len());
 
         for component in ty.prototype().hint_all_components() {
+            if component.hidden {
+                continue;
+            }
+
             result.push(CompletionItem {
                 label: Ident::Rust(component.name),
                 desc: Description::from_component(&component),
+                index: 0,
             });
         }
 
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{
+            symbols::{tests::TestPackage, LookupOptions, ModuleSymbol, SymbolKind},
+            DescriptionConfig,
+            ModuleRead,
+            ModuleWrite,
+            ScriptModule,
+        },
+        export,
+        runtime::{Arg, Cell, Origin, RuntimeResult, ScriptPackage},
+    };
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct Config {
+        pub window: Window,
+    }
+
+    #[export]
+    impl Config {
+        #[export(name "main")]
+        pub fn main_window(&self) -> &Window {
+            &self.window
+        }
+
+        #[export(component Window)]
+        fn popup(origin: Origin, _arg: Arg) -> RuntimeResult<Cell> {
+            Cell::give(origin, Window::default())
+        }
+    }
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct Window {
+        #[export(name "dims")]
+        pub size: Size,
+    }
+
+    #[export]
+    impl Window {
+        pub fn bounds(&self) -> Size {
+            self.size.clone()
+        }
+    }
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct Size {
+        pub width: usize,
+        pub height: usize,
+    }
+
+    #[export]
+    pub fn config() -> Config {
+        Config::default()
+    }
+
+    /// Creates a window size.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The horizontal
+    ///   extent.
+    /// - height: The vertical extent.
+    #[export]
+    pub fn size(width: usize, height: usize) -> Size {
+        Size { width, height }
+    }
+
+    #[export(name "settings")]
+    pub const SETTINGS: Config = Config {
+        window: Window {
+            size: Size {
+                width: 0,
+                height: 0,
+            },
+        },
+    };
+
+    /// Palette colors.
+    #[export(consts)]
+    pub mod palette {
+        /// Pure red.
+        pub const RED: u32 = 0xFF0000;
+
+        #[export(name "LIME")]
+        pub const GREEN: u32 = 0x00FF00;
+
+        #[export(exclude)]
+        pub const PRIMARY: [u32; 1] = [RED];
+    }
+
+    fn complete(source: &str) -> Vec<String> {
+        let site = source.find('$').unwrap();
+        let module = ScriptModule::new(TestPackage::meta(), source.replace('$', ""));
+        let handle = TriggerHandle::new();
+        let mut write = module.write(&handle, 1).unwrap();
+
+        write
+            .completions(site)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| item.label.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_crate_chain_completions() {
+        let config = ["window", "main", "popup"];
+        let window = ["dims", "bounds"];
+        let size = ["width", "height"];
+
+        assert!(complete("crate.$").contains(&String::from("settings")));
+        assert_eq!(complete("crate.settings.$"), config);
+        assert_eq!(complete("crate.config().$"), config);
+        assert_eq!(complete("crate.settings.window.$"), window);
+        assert_eq!(complete("crate.settings.popup.$"), window);
+        assert_eq!(complete("crate.settings.main().$"), window);
+        assert_eq!(complete("crate.settings.window.dims.$"), size);
+        assert_eq!(complete("crate.config().main().dims.$"), size);
+        assert_eq!(complete("crate.settings.popup.bounds().$"), size);
+        assert_eq!(complete("crate.config().window.d$"), ["dims", "bounds"]);
+        assert_eq!(complete("let x = crate.settings.main().$;"), window);
+        assert_eq!(complete("let x = 1\ncrate.settings.window.$"), window);
+        assert_eq!(complete("foo()\ncrate.config().popup.dims.$"), size);
+    }
+
+    #[test]
+    fn test_declaration_order() {
+        assert_eq!(
+            complete("let s = struct { zeta: 1, alpha: 2, mid: 3 };\ns.$"),
+            ["zeta", "alpha", "mid"],
+        );
+        assert_eq!(
+            complete("let s = struct { zeta: 1, alpha: 2, mid: 3 };\ns.m$"),
+            ["mid", "zeta", "alpha"],
+        );
+        assert_eq!(
+            complete("let s = struct { amber: 1 };\ns.amber = 2;\ns.$"),
+            ["amber"],
+        );
+    }
+
+    #[test]
+    fn test_crate_chain_hover() {
+        static SOURCE: &str = "crate.config().popup.dims.width;";
+
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let hover = |needle: &str, kind: SymbolKind| {
+            let offset = SOURCE.find(needle).unwrap();
+
+            let symbols = read
+                .symbols(offset..offset, LookupOptions::default().filter(kind as u32))
+                .unwrap();
+
+            let description = match symbols.first() {
+                Some(ModuleSymbol::Ident(symbol)) => symbol.ty(&read).unwrap(),
+                Some(ModuleSymbol::Field(symbol)) => symbol.ty(&read).unwrap(),
+                _ => panic!("{needle:?} lookup failed"),
+            };
+
+            description.to_string()
+        };
+
+        assert_eq!(hover("crate", SymbolKind::Ident), "Package");
+        assert_eq!(hover("config", SymbolKind::Field), "fn config() -> Config");
+        assert_eq!(hover("popup", SymbolKind::Field), "Window");
+        assert_eq!(hover("dims", SymbolKind::Field), "Size");
+        assert_eq!(hover("width", SymbolKind::Field), "number");
+
+        let offset = SOURCE.find("crate").unwrap();
+
+        let symbols = read
+            .symbols(
+                offset..offset,
+                LookupOptions::default().filter(SymbolKind::Ident as u32),
+            )
+            .unwrap();
+
+        let Some(ModuleSymbol::Ident(symbol)) = symbols.first() else {
+            panic!("crate lookup failed");
+        };
+
+        let description = symbol.ty(&read).unwrap();
+        let package = TestPackage::meta();
+
+        assert!(description.package() == Some(package));
+        assert!(description
+            .render(&DescriptionConfig::detailed())
+            .contains(&format!(
+                "package: {} {}",
+                package.name(),
+                package.version()
+            )));
+    }
+
+    #[test]
+    fn test_consts_table() {
+        static SOURCE: &str = "crate.palette.RED;";
+
+        assert!(complete("crate.$").contains(&String::from("palette")));
+        assert_eq!(complete("crate.palette.$"), ["RED", "LIME"]);
+
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let offset = SOURCE.find("RED").unwrap();
+
+        let symbols = read
+            .symbols(
+                offset..offset,
+                LookupOptions::default().filter(SymbolKind::Field as u32),
+            )
+            .unwrap();
+
+        let Some(ModuleSymbol::Field(symbol)) = symbols.first() else {
+            panic!("Field lookup failed.");
+        };
+
+        let description = symbol.ty(&read).unwrap();
+
+        assert_eq!(description.to_string(), "number");
+        assert_eq!(description.doc, Some(" Pure red.\n\nValue: `0xFF0000`"));
+    }
+
+    #[test]
+    fn test_param_docs() {
+        static SOURCE: &str = "crate.size(1, 2);";
+
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let offset = SOURCE.find("size").unwrap();
+
+        let symbols = read
+            .symbols(
+                offset..offset,
+                LookupOptions::default().filter(SymbolKind::Field as u32),
+            )
+            .unwrap();
+
+        let Some(ModuleSymbol::Field(symbol)) = symbols.first() else {
+            panic!("Field lookup failed.");
+        };
+
+        let description = symbol.ty(&read).unwrap();
+        let invocation = description.type_hint.invocation().unwrap();
+        let inputs = invocation.inputs.as_ref().unwrap();
+
+        assert_eq!(inputs[0].doc, Some("The horizontal extent."));
+        assert_eq!(inputs[1].doc, Some("The vertical extent."));
+    }
+}
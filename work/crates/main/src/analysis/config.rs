@@ -0,0 +1,405 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A set of limits that bound the amount of work the semantic analyzer spends
+/// on a single script construct, and the options of the analyzer's
+/// diagnostics.
+///
+/// Pathological source code, such as an expression with thousands of nested
+/// parentheses or a variable reassigned thousands of times, could otherwise
+/// make type inference unreasonably slow or even exhaust the thread's stack,
+/// freezing the code editor.
+///
+/// When the analyzer reaches any of these limits, it stops inferring the type
+/// of the offending construct, treats the construct as dynamically typed, and
+/// reports an [AnalysisLimit](crate::analysis::IssueCode::AnalysisLimit) hint
+/// on it. The limits affect the static analysis only: the compiled assembly of
+/// the module and its runtime behavior remain the same.
+///
+/// Additionally, the configuration controls whether the module's analysis and
+/// compilation may invoke the host's Rust functions (see
+/// [pure_calls](Self::pure_calls)).
+///
+/// You can change the limits of a particular module using the
+/// [set_analysis_config](crate::analysis::ModuleWrite::set_analysis_config)
+/// function.
+///
+/// The [Default] implementation of this object provides canonical limits.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub struct AnalysisConfig {
+    /// The maximum nesting depth of an expression within the function's body
+    /// (or within the module's top-level code).
+    ///
+    /// Each operator, call, index, array constructor, struct constructor, and
+    /// parenthesized expression increases the nesting depth by one.
+    ///
+    /// The default value is 48.
+    pub max_expr_depth: usize,
+
+    /// The maximum number of nested type resolution steps that a single type
+    /// inference request may trigger.
+    ///
+    /// A resolution step occurs, for example, when the analyzer infers the
+    /// type of a variable from the variable's initializer expression, which
+    /// in turn refers to another variable.
+    ///
+    /// The default value is 64.
+    pub max_resolution_steps: usize,
+
+    /// The maximum number of constructs that the analyzer takes into account
+    /// when inferring the type of a single construct: the items of an array
+    /// constructor, the entries of a struct checked against the Rust struct
+    /// schema, and the assignments of a variable that the analyzer merges
+    /// into the variable's type.
+    ///
+    /// The default value is 1024.
+    pub max_fan_out: usize,
+
+    /// If true, the analyzer warns about local variables and function
+    /// parameters that shadow the script package members (see
+    /// [ShadowedMember](crate::analysis::IssueCode::ShadowedMember)).
+    ///
+    /// Turn this option off for code bases that shadow the package members
+    /// intentionally.
+    ///
+    /// The default value is true.
+    pub shadowing_warnings: bool,
+
+    /// A table of additional keyword spellings that the lexer of the module
+    /// recognizes.
+    ///
+    /// Each entry maps an alias to the canonical spelling of an existing
+    /// keyword (e.g., `("si", "if")` or `("verdadero", "true")`). The alias
+    /// must be a valid identifier. The lexer treats the aliased words as the
+    /// corresponding keywords, so the aliased words cannot be used as
+    /// variable or field names in the module.
+    ///
+    /// The table affects the lexis of the module only: the
+    /// [formatter](crate::analysis::ModuleText::format) always prints the
+    /// canonical keyword spellings, and the diagnostics refer to the keywords
+    /// by their canonical names. The entries with an unknown canonical keyword
+    /// are ignored.
+    ///
+    /// The default value is an empty table.
+    pub keyword_aliases: &'static [(&'static str, &'static str)],
+
+    /// If true, the compiler precomputes the calls of the exported functions
+    /// marked with the `#[export(pure)]` attribute with constant arguments,
+    /// and the
+    /// [expr_value](crate::analysis::symbols::ModuleSymbol::expr_value)
+    /// function folds such calls.
+    ///
+    /// In both cases, the Rust code of the pure functions runs on the
+    /// analyzing thread within a small budget of the number and the total
+    /// time of the invocations. Turn this option off for untrusted scripts,
+    /// so that the analysis and compilation of the module never invoke the
+    /// host's Rust functions.
+    ///
+    /// The default value is true.
+    pub pure_calls: bool,
+}
+
+impl Default for AnalysisConfig {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnalysisConfig {
+    /// The default constructor for the configuration.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            max_expr_depth: 48,
+            max_resolution_steps: 64,
+            max_fan_out: 1024,
+            shadowing_warnings: true,
+            keyword_aliases: &[],
+            pure_calls: true,
+        }
+    }
+
+    /// Returns a configuration without any limits.
+    ///
+    /// Use this configuration with caution: the analysis of pathological
+    /// source code may take unreasonably long or exhaust the thread's stack.
+    #[inline(always)]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_expr_depth: usize::MAX,
+            max_resolution_steps: usize::MAX,
+            max_fan_out: usize::MAX,
+            shadowing_warnings: true,
+            keyword_aliases: &[],
+            pure_calls: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use lady_deirdre::{
+        analysis::TriggerHandle,
+        lexis::{SourceCode, ToSpan},
+    };
+
+    use crate::{
+        analysis::{
+            symbols::tests::TestPackage,
+            AnalysisConfig,
+            IssueCode,
+            ModuleRead,
+            ModuleWrite,
+            ScriptModule,
+        },
+        format::{format_script_text, ScriptFormatConfig},
+        runtime::ScriptPackage,
+    };
+
+    // A CI-friendly upper bound of the analysis time of each fixture, even in
+    // the debug build.
+    const TIME_BOUND: Duration = Duration::from_secs(20);
+
+    fn limit_hints(text: &str, config: Option<AnalysisConfig>) -> Vec<String> {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+
+        if let Some(config) = config {
+            module
+                .write(&handle, 1)
+                .unwrap()
+                .set_analysis_config(config)
+                .unwrap();
+        }
+
+        let read = module.read(&handle, 1).unwrap();
+        let module_text = read.text();
+
+        let start = Instant::now();
+
+        let mut hints = Vec::new();
+
+        for depth in 1..=3 {
+            let diagnostics = read.diagnostics(depth).unwrap();
+
+            for issue in &diagnostics {
+                if issue.code() == IssueCode::AnalysisLimit {
+                    let span = issue
+                        .origin(&module_text)
+                        .to_site_span(&module_text)
+                        .unwrap();
+
+                    hints.push(module_text.substring(span).to_string());
+                }
+            }
+        }
+
+        assert!(start.elapsed() < TIME_BOUND, "Analysis took too long.");
+
+        hints
+    }
+
+    #[test]
+    fn test_expr_depth_limit() {
+        let text = format!("let x = {}1{};", "(".repeat(100), ")".repeat(100));
+        let hints = limit_hints(&text, None);
+        assert_eq!(hints.len(), 1);
+
+        let text = format!("let x = 1{};", " + 1".repeat(100));
+        let hints = limit_hints(&text, None);
+        assert_eq!(hints.len(), 1);
+
+        let text = format!("let x = 1{};", " + 1".repeat(40));
+        assert!(limit_hints(&text, None).is_empty());
+
+        let mut config = AnalysisConfig::new();
+        config.max_expr_depth = 20;
+        assert_eq!(limit_hints(&text, Some(config)).len(), 1);
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let check = |text: String| {
+            let module = ScriptModule::new(TestPackage::meta(), text);
+            let handle = TriggerHandle::new();
+            let read = module.read(&handle, 1).unwrap();
+
+            let mut parse_issues = Vec::new();
+
+            for depth in 1..=3 {
+                for issue in &read.diagnostics(depth).unwrap() {
+                    match issue.code() {
+                        IssueCode::Parse => parse_issues.push(issue.verbose_message(&read.text())),
+                        code => assert_eq!(code, IssueCode::AnalysisLimit),
+                    }
+                }
+            }
+
+            assert_eq!(
+                parse_issues,
+                vec![String::from("expression is nested too deeply")]
+            );
+        };
+
+        check(format!(
+            "let x = {}1{};",
+            "(".repeat(1000),
+            ")".repeat(1000)
+        ));
+        check(format!(
+            "let x = {}1{};",
+            "[".repeat(1000),
+            "]".repeat(1000)
+        ));
+        check(format!("let x = 1{};", " + 1".repeat(1000)));
+    }
+
+    #[test]
+    fn test_resolution_steps_limit() {
+        let chain = |length: usize| {
+            let mut text = String::from("let v0 = 1;\n");
+
+            for index in 1..length {
+                text += &format!("let v{index} = v{};\n", index - 1);
+            }
+
+            text
+        };
+
+        assert!(!limit_hints(&chain(2000), None).is_empty());
+        assert!(limit_hints(&chain(20), None).is_empty());
+
+        let mut config = AnalysisConfig::new();
+        config.max_resolution_steps = 10;
+
+        assert!(!limit_hints(&chain(200), Some(config)).is_empty());
+        assert!(limit_hints(&chain(20), Some(AnalysisConfig::unlimited())).is_empty());
+    }
+
+    #[test]
+    fn test_fan_out_limit() {
+        let mut text = String::from("let flag = true;\nlet a;\n");
+
+        for index in 0..2000 {
+            text += &format!("if flag {{ a = {index}; }}\n");
+        }
+
+        assert_eq!(limit_hints(&text, None), vec![String::from("a")]);
+
+        let items = (0..2000)
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let text = format!("let x = [{items}];");
+        assert_eq!(limit_hints(&text, None).len(), 1);
+    }
+
+    #[test]
+    fn test_keyword_aliases() {
+        static ALIASES: &[(&str, &str)] = &[
+            ("si", "if"),
+            ("elegir", "match"),
+            ("sino", "else"),
+            ("verdadero", "true"),
+            ("falso", "false"),
+            ("devolver", "return"),
+            ("nada", "unknown_keyword"),
+        ];
+
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "si falso { devolver 10; } elegir { verdadero => { devolver 20; }, sino => {} }",
+        );
+        let handle = TriggerHandle::new();
+
+        assert!(!module.read(&handle, 1).unwrap().text().is_well_formed());
+
+        let mut config = AnalysisConfig::new();
+        config.keyword_aliases = ALIASES;
+
+        module
+            .write(&handle, 1)
+            .unwrap()
+            .set_analysis_config(config)
+            .unwrap();
+
+        {
+            let read = module.read(&handle, 1).unwrap();
+
+            assert!(read.text().is_well_formed());
+            assert_eq!(
+                read.compile().unwrap().run().unwrap().stringify(false),
+                "20"
+            );
+
+            assert_eq!(
+                read.text().format(ScriptFormatConfig::default()).unwrap(),
+                format_script_text(
+                    ScriptFormatConfig::default(),
+                    "if false { return 10; } match { true => { return 20; }, else => {} }",
+                )
+                .unwrap(),
+            );
+        }
+
+        let _ = module
+            .write(&handle, 1)
+            .unwrap()
+            .edit(0..0, "let nada = verdadero; si nada { devolver 30; }\n")
+            .unwrap();
+
+        {
+            let read = module.read(&handle, 1).unwrap();
+
+            assert!(read.text().is_well_formed());
+            assert_eq!(
+                read.compile().unwrap().run().unwrap().stringify(false),
+                "30"
+            );
+        }
+
+        module
+            .write(&handle, 1)
+            .unwrap()
+            .set_analysis_config(AnalysisConfig::new())
+            .unwrap();
+
+        assert!(!module.read(&handle, 1).unwrap().text().is_well_formed());
+    }
+}
@@ -0,0 +1,273 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{iter::FusedIterator, slice::Iter};
+
+use lady_deirdre::{
+    analysis::Revision,
+    arena::{Id, Identifiable},
+    lexis::ToSpan,
+    syntax::PolyRef,
+};
+
+use crate::{
+    analysis::{
+        symbols::{ModuleSymbol, SymbolKind},
+        IssueCode,
+        ScriptIssue,
+    },
+    runtime::ScriptOrigin,
+    syntax::ScriptDoc,
+};
+
+/// A module-level report of the script constructions that are never used.
+///
+/// Created by the [dead_code](crate::analysis::ModuleRead::dead_code)
+/// function.
+///
+/// Currently, the report includes the following kinds of
+/// [items](DeadCodeItem):
+///
+///  - Variables initialized with script functions that are never called or
+///    referenced outside of the function's own body: `let foo = fn() {};`.
+///  - Struct entries that are never read, provided that the module's code
+///    accesses other entries of the same struct: `struct { foo: 1, bar: 2 }`.
+///  - Import statements that import names not used by the module, or import
+///    paths with a redundant last component.
+///
+/// Constructions with names matching the patterns set by the
+/// [set_dead_code_allow](crate::analysis::ModuleWrite::set_dead_code_allow)
+/// function are excluded from the report.
+///
+/// The same items are also reported by the
+/// [diagnostics](crate::analysis::ModuleRead::diagnostics) of depth `3` as
+/// [hints](crate::analysis::IssueSeverity::Hint).
+#[derive(Clone)]
+pub struct DeadCodeReport {
+    id: Id,
+    items: Vec<DeadCodeItem>,
+    revision: Revision,
+}
+
+impl Identifiable for DeadCodeReport {
+    #[inline(always)]
+    fn id(&self) -> Id {
+        self.id
+    }
+}
+
+impl<'a> IntoIterator for &'a DeadCodeReport {
+    type Item = &'a DeadCodeItem;
+    type IntoIter = DeadCodeIter<'a>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl DeadCodeReport {
+    pub(super) fn new<'a>(
+        id: Id,
+        doc: &ScriptDoc,
+        issues: impl Iterator<Item = &'a ScriptIssue>,
+        revision: Revision,
+    ) -> Self {
+        let mut items = issues
+            .filter_map(|issue| {
+                let (kind, symbol) = match issue {
+                    ScriptIssue::UnusedFunction { var_ref } => (
+                        DeadCodeKind::UnusedFunction,
+                        ModuleSymbol::new(SymbolKind::Var, var_ref),
+                    ),
+
+                    ScriptIssue::UnreadEntry { entry_key_ref } => (
+                        DeadCodeKind::UnreadEntry,
+                        ModuleSymbol::new(SymbolKind::Entry, entry_key_ref),
+                    ),
+
+                    ScriptIssue::UnusedImport {
+                        use_ref,
+                        package_ref,
+                    } => match package_ref.is_nil() {
+                        true => (
+                            DeadCodeKind::UnusedImport,
+                            ModuleSymbol::new(SymbolKind::Use, use_ref),
+                        ),
+
+                        false => (
+                            DeadCodeKind::UnusedImport,
+                            ModuleSymbol::new(SymbolKind::Package, package_ref),
+                        ),
+                    },
+
+                    _ => return None,
+                };
+
+                let origin = issue.span(doc);
+                let site = origin.to_site_span(doc).map(|span| span.start);
+
+                Some((
+                    site,
+                    DeadCodeItem {
+                        kind,
+                        symbol,
+                        origin,
+                    },
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        items.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Self {
+            id,
+            items: items.into_iter().map(|(_, item)| item).collect(),
+            revision,
+        }
+    }
+
+    /// Returns the number of items in this report.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if the analyzer did not find any dead code in the module.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the revision number at which this report was constructed.
+    ///
+    /// If two reports of the same script module have the same revision number,
+    /// their content can be considered identical.
+    #[inline(always)]
+    pub fn revision(&self) -> Revision {
+        self.revision
+    }
+
+    /// Returns an iterator over the report items in the order of their
+    /// appearance in the source code.
+    #[inline(always)]
+    pub fn iter(&self) -> DeadCodeIter {
+        DeadCodeIter {
+            inner: self.items.iter(),
+        }
+    }
+}
+
+/// An iterator over the items of the [DeadCodeReport].
+///
+/// Created by the [DeadCodeReport::iter] function and the [IntoIterator]
+/// implementation of the DeadCodeReport.
+pub struct DeadCodeIter<'a> {
+    inner: Iter<'a, DeadCodeItem>,
+}
+
+impl<'a> Iterator for DeadCodeIter<'a> {
+    type Item = &'a DeadCodeItem;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> FusedIterator for DeadCodeIter<'a> {}
+
+impl<'a> ExactSizeIterator for DeadCodeIter<'a> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+/// An individual item of the [DeadCodeReport].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DeadCodeItem {
+    /// The kind of the unused construction.
+    pub kind: DeadCodeKind,
+
+    /// The symbol of the unused construction.
+    ///
+    /// Depending on the `kind`, the symbol is a
+    /// [VarSymbol](crate::analysis::symbols::VarSymbol) of the unused function
+    /// variable, an [EntrySymbol](crate::analysis::symbols::EntrySymbol) of
+    /// the unread struct entry, a
+    /// [UseSymbol](crate::analysis::symbols::UseSymbol) of the unused import
+    /// statement, or a [PackageSymbol](crate::analysis::symbols::PackageSymbol)
+    /// of the redundant import path component.
+    pub symbol: ModuleSymbol,
+
+    /// The source code fragment that can be removed to eliminate the dead
+    /// code.
+    pub origin: ScriptOrigin,
+}
+
+/// A kind of the [DeadCodeItem].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub enum DeadCodeKind {
+    /// A variable initialized with a script function that is never referenced:
+    /// `let foo = fn() {};`.
+    UnusedFunction,
+
+    /// A struct entry that is never read: `struct { foo: 10 }`.
+    UnreadEntry,
+
+    /// An unused import statement (`use foo;`), or a redundant last component
+    /// of the import path (`use foo.bar;`).
+    UnusedImport,
+}
+
+impl DeadCodeKind {
+    /// Returns the [IssueCode] of the diagnostic hint through which the
+    /// analyzer reports this kind of dead code.
+    #[inline(always)]
+    pub fn issue_code(self) -> IssueCode {
+        match self {
+            Self::UnusedFunction => IssueCode::UnusedFunction,
+            Self::UnreadEntry => IssueCode::UnreadEntry,
+            Self::UnusedImport => IssueCode::UnusedImport,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::runtime::{RustOrigin, ScriptOrigin};
+
+/// A target of the "go to type definition" request.
+///
+/// Created by the [type_definition](crate::analysis::ModuleRead::type_definition)
+/// function.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DefinitionTarget {
+    /// The type is defined in the script code, such as a script struct
+    /// constructor or a script function.
+    Script(ScriptOrigin),
+
+    /// The type is an exported Rust type.
+    ///
+    /// The [RustOrigin] points to the Rust construct exported by the
+    /// [export](crate::export) macro. If the Rust sources are not available,
+    /// you can use the [docs_url](Self::docs_url) function to get the location
+    /// of the generated API documentation instead.
+    Rust(&'static RustOrigin),
+}
+
+impl DefinitionTarget {
+    /// Returns the URL of the docs.rs documentation page of the Rust module
+    /// in which the exported type is defined.
+    ///
+    /// The function returns None if the target is a [Script](Self::Script)
+    /// target, or if the Rust origin does not specify the crate and the module
+    /// of the type.
+    pub fn docs_url(&self) -> Option<String> {
+        let Self::Rust(origin) = self else {
+            return None;
+        };
+
+        let (name, version) = origin.package?;
+        let code = origin.code.as_ref()?;
+
+        let mut url = format!("https://docs.rs/{name}/{version}");
+
+        for component in code.module.split("::") {
+            url.push('/');
+            url.push_str(component);
+        }
+
+        url.push_str("/index.html");
+
+        Some(url)
+    }
+}
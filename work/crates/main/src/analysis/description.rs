@@ -32,6 +32,8 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
+use std::fmt::{Display, Formatter, Write};
+
 use crate::{
     analysis::symbols::{FnSymbol, LiteralSymbol, ModuleSymbol, StructSymbol},
     exports::Struct,
@@ -78,9 +80,224 @@ pub struct Description {
     /// specific to that field, which differs from the `type_hint` documentation
     /// that contains only the Rustdoc documentation of the field's type.
     pub doc: Option<&'static str>,
+
+    /// The deprecation message of the analyzed entity, if the entity is a Rust
+    /// component that the Rust author marked as deprecated
+    /// (see [ComponentHint::deprecated]).
+    pub deprecated: Option<&'static str>,
+}
+
+impl Display for Description {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.type_hint, formatter)
+    }
 }
 
 impl Description {
+    /// Returns true if the described entity is a function (either a Rust
+    /// function or a script function).
+    #[inline(always)]
+    pub fn is_fn(&self) -> bool {
+        self.type_hint.is_fn()
+    }
+
+    /// Returns the number of parameters of the described function, if the
+    /// entity is a function and the analyzer knows its signature.
+    #[inline]
+    pub fn arity(&self) -> Option<usize> {
+        self.type_hint.invocation()?.arity()
+    }
+
+    /// Returns the list of statically known components (fields and methods)
+    /// of the described entity's type, sorted by name.
+    ///
+    /// For functions, type families, and dynamic types, the list is empty.
+    pub fn components(&self) -> Vec<ComponentHint> {
+        let TypeHint::Type(meta) = self.type_hint else {
+            return Vec::new();
+        };
+
+        if meta.is_fn() {
+            return Vec::new();
+        }
+
+        let mut components = meta.prototype().hint_all_components().collect::<Vec<_>>();
+
+        components.sort_by(|a, b| a.name.string.cmp(b.name.string));
+
+        components
+    }
+
+    /// Returns the metadata of the Script Package, if the described entity is
+    /// a package (e.g., the `crate` identifier or a package in the `use`
+    /// statement).
+    #[inline]
+    pub fn package(&self) -> Option<&'static PackageMeta> {
+        if !self.type_hint.is_package() {
+            return None;
+        }
+
+        self.type_hint.package()
+    }
+
+    /// Returns the type family of the described entity's type, if the type
+    /// belongs to a family with more than one type (e.g., the `number` family
+    /// of the `usize` type).
+    #[inline]
+    pub fn family(&self) -> Option<&'static TypeFamily> {
+        let family = match self.type_hint {
+            TypeHint::Type(meta) => meta.family(),
+            TypeHint::Family(family) => family,
+            TypeHint::Invocation(_) => return None,
+        };
+
+        if family.len() <= 1 || family.is_package() || family.is_fn() {
+            return None;
+        }
+
+        Some(family)
+    }
+
+    /// Renders this description into a string according to the specified
+    /// `config`.
+    ///
+    /// The [short](DescriptionVerbosity::Short) rendering of a Description
+    /// without documentation is the same as the [Display] implementation of
+    /// the object: a short type name such as `number`.
+    pub fn render(&self, config: &DescriptionConfig) -> String {
+        let mut result = String::new();
+
+        let _ = self.render_header(config, &mut result);
+
+        if let DescriptionVerbosity::Detailed = config.verbosity {
+            let _ = self.render_details(config, &mut result);
+        }
+
+        if let Some(message) = self.deprecated {
+            match config.markdown {
+                false => result.push_str("\ndeprecated"),
+                true => result.push_str("\n\n**Deprecated**"),
+            }
+
+            if !message.is_empty() {
+                result.push_str(": ");
+                result.push_str(message);
+            }
+        }
+
+        if config.show_doc {
+            if let Some(doc) = self.doc {
+                let doc = doc.trim();
+
+                if !doc.is_empty() {
+                    result.push_str("\n\n");
+
+                    if config.markdown {
+                        result.push_str("---\n\n");
+                    }
+
+                    result.push_str(doc);
+                }
+            }
+        }
+
+        result
+    }
+
+    fn render_header(&self, config: &DescriptionConfig, result: &mut String) -> std::fmt::Result {
+        let header = match (config.verbosity, self.type_hint) {
+            (DescriptionVerbosity::Detailed, TypeHint::Type(meta)) => {
+                match meta.prototype().hint_invocation() {
+                    Some(invocation) if meta.is_fn() => invocation.to_string(),
+                    _ => format!("{:#}", self.type_hint),
+                }
+            }
+
+            _ => self.type_hint.to_string(),
+        };
+
+        if !config.markdown {
+            return result.write_str(&header);
+        }
+
+        match config.verbosity {
+            DescriptionVerbosity::Short if !self.is_fn() => result.write_str(&config.link(&header)),
+
+            _ => result.write_fmt(format_args!("```{}\n{header}\n```", config.language_id)),
+        }
+    }
+
+    fn render_details(&self, config: &DescriptionConfig, result: &mut String) -> std::fmt::Result {
+        if let Some(package) = self.package() {
+            let name = package.name();
+            let version = package.version();
+
+            match config.markdown {
+                false => result.write_fmt(format_args!("\npackage: {name} {version}"))?,
+                true => result.write_fmt(format_args!("\n\nPackage: `{name}` {version}"))?,
+            }
+
+            if let Some(doc) = package.doc() {
+                match config.markdown {
+                    false => result.write_fmt(format_args!(" ({doc})"))?,
+                    true => result.write_fmt(format_args!(" ([docs]({doc}))"))?,
+                }
+            }
+        }
+
+        if let Some(family) = self.family() {
+            let mut members = family.into_iter().map(|ty| ty.name()).collect::<Vec<_>>();
+
+            members.sort();
+
+            match config.markdown {
+                false => result.write_fmt(format_args!("\nfamily: {}", family.name()))?,
+                true => {
+                    result.write_fmt(format_args!("\n\nFamily: {}", config.link(family.name())))?
+                }
+            }
+
+            if !members.is_empty() {
+                let members = members
+                    .iter()
+                    .map(|name| match config.markdown {
+                        false => String::from(*name),
+                        true => config.link(name),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                result.write_fmt(format_args!(" ({members})"))?;
+            }
+        }
+
+        let components = self.components();
+
+        if components.is_empty() {
+            return Ok(());
+        }
+
+        match config.markdown {
+            false => result.write_str("\ncomponents:")?,
+            true => result.write_str("\n\nComponents:\n")?,
+        }
+
+        for component in components {
+            let name = component.name.string;
+            let ty = component.ty;
+
+            match (config.markdown, ty.is_dynamic()) {
+                (false, true) => result.write_fmt(format_args!("\n    {name}"))?,
+                (false, false) => result.write_fmt(format_args!("\n    {name}: {ty}"))?,
+                (true, true) => result.write_fmt(format_args!("\n- `{name}`"))?,
+                (true, false) => result.write_fmt(format_args!("\n- `{name}`: `{ty}`"))?,
+            }
+        }
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub(super) fn dynamic() -> Self {
         let type_hint = TypeHint::dynamic();
@@ -90,6 +307,7 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Nil,
             doc,
+            deprecated: None,
         }
     }
 
@@ -102,6 +320,7 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Nil,
             doc,
+            deprecated: None,
         }
     }
 
@@ -114,6 +333,7 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Literal(symbol),
             doc,
+            deprecated: None,
         }
     }
 
@@ -126,6 +346,7 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Literal(symbol),
             doc,
+            deprecated: None,
         }
     }
 
@@ -138,6 +359,7 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Literal(symbol),
             doc,
+            deprecated: None,
         }
     }
 
@@ -150,6 +372,7 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Fn(symbol),
             doc,
+            deprecated: None,
         }
     }
 
@@ -166,6 +389,7 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Fn(symbol),
             doc,
+            deprecated: None,
         }
     }
 
@@ -178,6 +402,7 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Struct(symbol),
             doc,
+            deprecated: None,
         }
     }
 
@@ -191,6 +416,7 @@ impl Description {
             type_hint,
             impl_symbol,
             doc,
+            deprecated: None,
         }
     }
 
@@ -203,6 +429,7 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Nil,
             doc,
+            deprecated: component.deprecated,
         }
     }
 
@@ -215,6 +442,98 @@ impl Description {
             type_hint,
             impl_symbol: ModuleSymbol::Nil,
             doc,
+            deprecated: None,
+        }
+    }
+}
+
+/// A level of detail of the [Description::render] function output.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[non_exhaustive]
+pub enum DescriptionVerbosity {
+    /// A short type name, such as `number` or `fn(x: number) -> number`.
+    #[default]
+    Short,
+
+    /// A multi-line description that includes the full type signature, the
+    /// type family membership, and the list of the type's fields and methods.
+    Detailed,
+}
+
+/// A configuration of options for rendering the [Description] object using
+/// the [Description::render] function.
+///
+/// The [Default] implementation of this object provides canonical configuration
+/// options.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub struct DescriptionConfig {
+    /// The level of detail of the rendered text.
+    ///
+    /// The default value is [DescriptionVerbosity::Short].
+    pub verbosity: DescriptionVerbosity,
+
+    /// If set to true, the rendered text is Markdown: type signatures are
+    /// rendered as code blocks, and type names are enclosed in backticks.
+    ///
+    /// The default value is `false`.
+    pub markdown: bool,
+
+    /// The language identifier of the Markdown code blocks.
+    ///
+    /// The default value is "adastra".
+    pub language_id: &'static str,
+
+    /// A base URL of the API documentation of the exported types.
+    ///
+    /// If specified, the Markdown rendering turns type names into links. The
+    /// link address is the concatenation of this URL and the type name.
+    ///
+    /// The default value is None.
+    pub docs_url: Option<&'static str>,
+
+    /// Whether the [documentation](Description::doc) should be appended to the
+    /// rendered text.
+    ///
+    /// The default value is `true`.
+    pub show_doc: bool,
+}
+
+impl Default for DescriptionConfig {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DescriptionConfig {
+    /// The default constructor for the configuration.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            verbosity: DescriptionVerbosity::Short,
+            markdown: false,
+            language_id: "adastra",
+            docs_url: None,
+            show_doc: true,
+        }
+    }
+
+    /// Returns a configuration for the [detailed](DescriptionVerbosity::Detailed)
+    /// plain-text rendering.
+    #[inline(always)]
+    pub const fn detailed() -> Self {
+        let mut config = Self::new();
+
+        config.verbosity = DescriptionVerbosity::Detailed;
+
+        config
+    }
+
+    fn link(&self, name: &str) -> String {
+        match self.docs_url {
+            Some(url) => format!("[`{name}`]({url}{name})"),
+            None => format!("`{name}`"),
         }
     }
 }
@@ -38,13 +38,15 @@ use ahash::AHashSet;
 use lady_deirdre::{
     analysis::Revision,
     arena::{Id, Identifiable},
+    format::AnnotationPriority,
+    lexis::{Site, SiteSpan, ToSpan},
     sync::Shared,
 };
 
 use crate::{
     analysis::{IssueCode, IssueSeverity, ModuleText, ScriptIssue},
-    format::ScriptSnippet,
-    runtime::ScriptOrigin,
+    format::{localize, ScriptSnippet},
+    runtime::{RustOrigin, ScriptOrigin},
 };
 
 /// A level indicating the depth of diagnostic analysis.
@@ -58,8 +60,9 @@ use crate::{
 /// - Level `2`: All semantic errors and warnings that can be inferred directly
 ///   from the local syntax context, without requiring deep source code
 ///   analysis.
-/// - Level `3`: All warnings that require deep semantic analysis of the
-///   interconnections between source code constructs.
+/// - Level `3`: All warnings and hints that require deep semantic analysis of
+///   the interconnections between source code constructs, including the
+///   module's [dead code](crate::analysis::ModuleRead::dead_code) report.
 ///
 /// Issues at lower diagnostic levels are more critical for the end user,
 /// while issues at higher levels require more computational resources to infer.
@@ -71,8 +74,8 @@ use crate::{
 
 pub type DiagnosticsDepth = u8;
 
-/// A collection of diagnostic issues (errors and warnings) in the script
-/// module's source code.
+/// A collection of diagnostic issues (errors, warnings, and hints) in the
+/// script module's source code.
 ///
 /// Created by the [diagnostics](crate::analysis::ModuleRead::diagnostics)
 /// function.
@@ -107,27 +110,19 @@ impl ModuleDiagnostics {
     ///
     /// For example, `len(IssueSeverity::Error as u8)` returns the number of
     /// errors, while `len(!0)` returns the total number of issues, including
-    /// both errors and warnings.
+    /// errors, warnings, and hints.
     #[inline(always)]
     pub fn len(&self, severity_mask: u8) -> usize {
-        let errors = severity_mask & (IssueSeverity::Error as u8) > 0;
-        let warnings = severity_mask & (IssueSeverity::Warning as u8) > 0;
+        let all =
+            IssueSeverity::Error as u8 | IssueSeverity::Warning as u8 | IssueSeverity::Hint as u8;
 
-        match (errors, warnings) {
-            (false, false) => 0,
-
-            (true, false) => self
-                .iter()
-                .filter(|error| error.severity() == IssueSeverity::Error)
-                .count(),
-
-            (false, true) => self
-                .iter()
-                .filter(|error| error.severity() == IssueSeverity::Warning)
-                .count(),
-
-            (true, true) => self.issues.as_ref().len(),
+        if severity_mask & all == all {
+            return self.issues.as_ref().len();
         }
+
+        self.iter()
+            .filter(|issue| severity_mask & (issue.severity() as u8) > 0)
+            .count()
     }
 
     /// Returns true if this collection does not contain any diagnostic issues.
@@ -136,6 +131,166 @@ impl ModuleDiagnostics {
         self.issues.as_ref().is_empty()
     }
 
+    /// Returns the number of errors, warnings, and hints in this collection.
+    ///
+    /// Unlike the [len](Self::len) function, this function counts the issues
+    /// of all severities in a single pass.
+    pub fn count_by_severity(&self) -> SeverityCount {
+        let mut count = SeverityCount::default();
+
+        for issue in self.iter() {
+            match issue.severity() {
+                IssueSeverity::Error => count.errors += 1,
+                IssueSeverity::Warning => count.warnings += 1,
+                IssueSeverity::Hint => count.hints += 1,
+            }
+        }
+
+        count
+    }
+
+    /// Returns an iterator over the issues of the specified `severity`.
+    ///
+    /// The issues are returned in an unspecified order.
+    #[inline(always)]
+    pub fn with_severity(&self, severity: IssueSeverity) -> impl Iterator<Item = ModuleIssue> {
+        self.iter()
+            .filter(move |issue| issue.severity() == severity)
+    }
+
+    /// Returns an iterator over the issues of the specified class (`code`).
+    ///
+    /// The issues are returned in an unspecified order.
+    #[inline(always)]
+    pub fn with_code(&self, code: IssueCode) -> impl Iterator<Item = ModuleIssue> {
+        self.iter().filter(move |issue| issue.code() == code)
+    }
+
+    /// Returns the issues whose [origins](ModuleIssue::origin) intersect with
+    /// the specified source code `span`, ordered by their start positions.
+    ///
+    /// An empty span (e.g., a cursor position) selects the issues that
+    /// include this position, including the issues that start or end at it.
+    ///
+    /// The `text` should be obtained from the same module content access
+    /// guard as this collection. If the `span` is not valid for the text, the
+    /// function returns an empty vector.
+    ///
+    /// This function scans all issues of the collection. If you need to query
+    /// several spans, build the [span index](Self::span_index) once instead.
+    pub fn in_span(&self, text: &ModuleText, span: impl ToSpan) -> Vec<ModuleIssue> {
+        let doc = text.doc_read.deref();
+
+        let Some(span) = span.to_site_span(doc) else {
+            return Vec::new();
+        };
+
+        let mut issues = self
+            .issues
+            .as_ref()
+            .iter()
+            .filter_map(|issue| {
+                let issue_span = issue.span(doc).to_site_span(doc)?;
+
+                if !intersects(&issue_span, &span) {
+                    return None;
+                }
+
+                Some((issue_span, issue))
+            })
+            .collect::<Vec<_>>();
+
+        issues.sort_by_key(|(span, _)| (span.start, span.end));
+
+        issues
+            .into_iter()
+            .map(|(_, issue)| ModuleIssue { id: self.id, issue })
+            .collect()
+    }
+
+    /// Returns true if the specified source code `span` intersects with the
+    /// origin of at least one error issue.
+    ///
+    /// See [in_span](Self::in_span) for the span matching rules.
+    pub fn has_errors_in(&self, text: &ModuleText, span: impl ToSpan) -> bool {
+        let doc = text.doc_read.deref();
+
+        let Some(span) = span.to_site_span(doc) else {
+            return false;
+        };
+
+        self.with_severity(IssueSeverity::Error).any(|issue| {
+            let Some(issue_span) = issue.issue.span(doc).to_site_span(doc) else {
+                return false;
+            };
+
+            intersects(&issue_span, &span)
+        })
+    }
+
+    /// Builds an index of the issue spans that answers the span queries
+    /// without scanning all issues of the collection.
+    ///
+    /// The index resolves the issue spans using the provided `text`, which
+    /// should be obtained from the same module content access guard as this
+    /// collection. Issues that do not have a valid span in the text are not
+    /// indexed.
+    ///
+    /// ```
+    /// # use ad_astra::{
+    /// #     analysis::{IssueSeverity, ModuleRead, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::{analysis::TriggerHandle, lexis::Position},
+    /// #     runtime::ScriptPackage,
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::new(Package::meta(), "let x = 1;\nlet = 10;\nlet y = ;");
+    ///
+    /// let handle = TriggerHandle::new();
+    /// let module_read = module.read(&handle, 1).unwrap();
+    ///
+    /// let diagnostics = module_read.diagnostics(1).unwrap();
+    /// let text = module_read.text();
+    /// let index = diagnostics.span_index(&text);
+    ///
+    /// assert_eq!(index.len(), 2);
+    /// assert!(!index.has_errors_in(&text, Position::new(1, 1)..Position::new(2, 1)));
+    /// assert!(index.has_errors_in(&text, Position::new(2, 1)..Position::new(3, 1)));
+    /// assert_eq!(index.in_span(&text, ..).len(), 2);
+    /// ```
+    pub fn span_index(&self, text: &ModuleText) -> DiagnosticsIndex {
+        let doc = text.doc_read.deref();
+
+        let mut issues = self
+            .issues
+            .as_ref()
+            .iter()
+            .filter_map(|issue| Some((issue.span(doc).to_site_span(doc)?, issue)))
+            .collect::<Vec<_>>();
+
+        issues.sort_by_key(|(span, _)| (span.start, span.end));
+
+        let mut max = 0;
+
+        let ends = issues
+            .iter()
+            .map(|(span, _)| {
+                max = max.max(span.end);
+                max
+            })
+            .collect();
+
+        DiagnosticsIndex {
+            id: self.id,
+            issues,
+            ends,
+        }
+    }
+
     /// Returns the diagnostic analysis depth at which this collection was
     /// constructed.
     ///
@@ -189,7 +344,7 @@ impl ModuleDiagnostics {
     ///
     /// The `severity_mask` allows you to filter issues by their severity:
     /// `IssueSeverity::Error as u8` shows only error issues, while `!0` shows
-    /// both error and warning issues.
+    /// all error, warning, and hint issues.
     ///
     /// ## Example
     ///
@@ -232,13 +387,15 @@ impl ModuleDiagnostics {
     pub fn highlight<'a>(&self, text: &'a ModuleText, severity_mask: u8) -> ScriptSnippet<'a> {
         let mut snippet = text.snippet();
 
-        snippet.set_caption("diagnostics");
+        snippet.set_caption(localize!(DiagnosticsCaption));
 
         let include_errors = severity_mask & (IssueSeverity::Error as u8) > 0;
         let include_warnings = severity_mask & (IssueSeverity::Warning as u8) > 0;
+        let include_hints = severity_mask & (IssueSeverity::Hint as u8) > 0;
 
         let mut total_errors = 0;
         let mut total_warnings = 0;
+        let mut total_hints = 0;
         #[allow(unused)]
         let mut annotations = 0;
 
@@ -259,6 +416,14 @@ impl ModuleDiagnostics {
                         continue;
                     }
                 }
+
+                IssueSeverity::Hint => {
+                    total_hints += 1;
+
+                    if !include_hints {
+                        continue;
+                    }
+                }
             }
 
             annotations += 1;
@@ -268,26 +433,44 @@ impl ModuleDiagnostics {
                 issue.severity().priority(),
                 issue.verbose_message(text),
             );
+
+            for related in issue.related(text) {
+                snippet.annotate(
+                    related.origin,
+                    AnnotationPriority::Secondary,
+                    related.message,
+                );
+            }
         }
 
         let mut summary = String::with_capacity(1024);
 
-        match total_errors == 0 && total_warnings == 0 {
-            true => summary.push_str("No issues detected."),
+        match total_errors == 0 && total_warnings == 0 && total_hints == 0 {
+            true => summary.push_str(&localize!(DiagnosticsClean)),
 
             false => {
-                summary.push_str(&format!("Errors: {}", total_errors));
+                summary.push_str(&localize!(DiagnosticsErrors, count = total_errors));
 
                 if !include_errors {
-                    summary.push_str(" (omitted).");
+                    summary.push_str(&localize!(DiagnosticsOmitted));
                 }
 
                 summary.push('\n');
 
-                summary.push_str(&format!("Warnings: {}", total_warnings));
+                summary.push_str(&localize!(DiagnosticsWarnings, count = total_warnings));
 
                 if !include_warnings {
-                    summary.push_str(" (omitted).");
+                    summary.push_str(&localize!(DiagnosticsOmitted));
+                }
+
+                if total_hints > 0 {
+                    summary.push('\n');
+
+                    summary.push_str(&localize!(DiagnosticsHints, count = total_hints));
+
+                    if !include_hints {
+                        summary.push_str(&localize!(DiagnosticsOmitted));
+                    }
                 }
             }
         };
@@ -298,6 +481,111 @@ impl ModuleDiagnostics {
     }
 }
 
+/// The number of issues of each severity in the [ModuleDiagnostics]
+/// collection.
+///
+/// Created by the [ModuleDiagnostics::count_by_severity] function.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct SeverityCount {
+    /// The number of [errors](IssueSeverity::Error).
+    pub errors: usize,
+
+    /// The number of [warnings](IssueSeverity::Warning).
+    pub warnings: usize,
+
+    /// The number of [hints](IssueSeverity::Hint).
+    pub hints: usize,
+}
+
+impl SeverityCount {
+    /// Returns the total number of issues.
+    #[inline(always)]
+    pub fn total(&self) -> usize {
+        self.errors + self.warnings + self.hints
+    }
+}
+
+/// An index of the issue spans of the [ModuleDiagnostics] collection.
+///
+/// Created by the [ModuleDiagnostics::span_index] function.
+///
+/// The index keeps the issues sorted by their start sites, so each span query
+/// performs a binary search instead of scanning all issues.
+pub struct DiagnosticsIndex<'a> {
+    id: Id,
+    issues: Vec<(SiteSpan, &'a ScriptIssue)>,
+    // A running maximum of the span ends, which allows skipping the prefix
+    // of the issues that end before the queried span.
+    ends: Vec<Site>,
+}
+
+impl<'a> Identifiable for DiagnosticsIndex<'a> {
+    #[inline(always)]
+    fn id(&self) -> Id {
+        self.id
+    }
+}
+
+impl<'a> DiagnosticsIndex<'a> {
+    /// Returns the number of indexed issues.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    /// Returns true if the index does not contain any issues.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the indexed issues whose origins intersect with the specified
+    /// source code `span`, ordered by their start positions.
+    ///
+    /// See [ModuleDiagnostics::in_span] for details.
+    pub fn in_span(&self, text: &ModuleText, span: impl ToSpan) -> Vec<ModuleIssue<'a>> {
+        let Some(span) = span.to_site_span(text.doc_read.deref()) else {
+            return Vec::new();
+        };
+
+        self.query(span).collect()
+    }
+
+    /// Returns true if the specified source code `span` intersects with the
+    /// origin of at least one indexed error issue.
+    pub fn has_errors_in(&self, text: &ModuleText, span: impl ToSpan) -> bool {
+        let Some(span) = span.to_site_span(text.doc_read.deref()) else {
+            return false;
+        };
+
+        self.query(span)
+            .any(|issue| issue.severity() == IssueSeverity::Error)
+    }
+
+    fn query(&self, span: SiteSpan) -> impl Iterator<Item = ModuleIssue<'a>> + '_ {
+        let first = self.ends.partition_point(|end| *end < span.start);
+        let last = self
+            .issues
+            .partition_point(|(issue_span, _)| issue_span.start <= span.end);
+
+        self.issues
+            .get(first..last)
+            .unwrap_or_default()
+            .iter()
+            .filter(move |(issue_span, _)| intersects(issue_span, &span))
+            .map(|(_, issue)| ModuleIssue { id: self.id, issue })
+    }
+}
+
+#[inline(always)]
+fn intersects(a: &SiteSpan, b: &SiteSpan) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return a.start <= b.end && b.start <= a.end;
+    }
+
+    a.start < b.end && b.start < a.end
+}
+
 /// An iterator over the diagnostic issues in the [ModuleDiagnostics]
 /// collection.
 ///
@@ -360,12 +648,23 @@ impl<'a> ModuleIssue<'a> {
         self.issue.code()
     }
 
-    /// Indicates whether this issue is a hard error or a warning.
+    /// Indicates whether this issue is a hard error, a warning, or a hint.
     ///
-    /// Equivalent to `issue.code().severity()`.
+    /// Equivalent to `issue.code().severity()`, except for the issues reported
+    /// by the host's [lint passes](crate::analysis::LintPass), whose severity
+    /// is chosen by the lint pass.
     #[inline(always)]
     pub fn severity(&self) -> IssueSeverity {
-        self.code().severity()
+        self.issue.severity()
+    }
+
+    /// Returns the host-defined code of the issue if this issue was reported
+    /// by the host's [lint pass](crate::analysis::LintPass).
+    ///
+    /// For all other issues, returns None.
+    #[inline(always)]
+    pub fn lint_code(&self) -> Option<&'a str> {
+        self.issue.lint_code()
     }
 
     /// Returns a short description of the class of issues to which this issue
@@ -385,7 +684,7 @@ impl<'a> ModuleIssue<'a> {
     /// text when annotating the source code snippet.
     #[inline(always)]
     pub fn verbose_message(&self, text: &ModuleText) -> String {
-        self.issue.message(text.doc_read.deref()).to_string()
+        self.issue.message(text.doc_read.deref())
     }
 
     /// Returns a reference to the source code fragment where the issue appears.
@@ -400,6 +699,37 @@ impl<'a> ModuleIssue<'a> {
         self.issue.span(text.doc_read.deref())
     }
 
+    /// Returns a reference to the Rust source code related to this issue, if
+    /// any.
+    ///
+    /// For example, if the issue is caused by the way an exported Rust
+    /// construct behaves, this function returns the location of that
+    /// construct's declaration.
+    #[inline(always)]
+    pub fn rust_origin(&self) -> Option<&'static RustOrigin> {
+        self.issue.rust_origin()
+    }
+
+    /// Returns secondary source code fragments related to this issue.
+    ///
+    /// For example, for a function parameter that duplicates the name of
+    /// another parameter, the result includes the first parameter with this
+    /// name. For a read of a possibly uninitialized variable, the result
+    /// includes the variable's declaration.
+    ///
+    /// Most issues do not have related fragments, in which case the function
+    /// returns an empty vector.
+    ///
+    /// The [highlight](ModuleIssue::highlight) functions annotate these
+    /// fragments together with the issue's primary [origin](Self::origin).
+    pub fn related(&self, text: &ModuleText) -> Vec<RelatedOrigin> {
+        self.issue
+            .related(text.doc_read.deref())
+            .into_iter()
+            .map(|(origin, message)| RelatedOrigin { origin, message })
+            .collect()
+    }
+
     /// Returns a quick-fix suggestion that could potentially resolve the
     /// underlying issue.
     ///
@@ -429,6 +759,21 @@ impl<'a> ModuleIssue<'a> {
                 implement_use_of: (!import.is_empty()).then(|| import.to_string()),
             }),
 
+            ScriptIssue::UnknownArgument { quickfix, .. }
+            | ScriptIssue::DuplicateArgument { quickfix, .. }
+                if !quickfix.is_empty() =>
+            {
+                Some(IssueQuickfix {
+                    set_text_to_origin: Some(quickfix.to_string()),
+                    implement_use_of: None,
+                })
+            }
+
+            ScriptIssue::AmbiguousPrecedence { quickfix, .. } => Some(IssueQuickfix {
+                set_text_to_origin: Some(quickfix.to_string()),
+                implement_use_of: None,
+            }),
+
             ScriptIssue::UnknownComponent { quickfix, .. } if !quickfix.is_empty() => {
                 Some(IssueQuickfix {
                     set_text_to_origin: Some(quickfix.to_string()),
@@ -436,6 +781,13 @@ impl<'a> ModuleIssue<'a> {
                 })
             }
 
+            ScriptIssue::UnusedFunction { .. }
+            | ScriptIssue::UnreadEntry { .. }
+            | ScriptIssue::UnusedImport { .. } => Some(IssueQuickfix {
+                set_text_to_origin: Some(String::new()),
+                implement_use_of: None,
+            }),
+
             _ => None,
         }
     }
@@ -451,12 +803,20 @@ impl<'a> ModuleIssue<'a> {
     pub fn highlight<'b>(&self, text: &'b ModuleText) -> ScriptSnippet<'b> {
         let mut snippet = text.snippet();
 
-        snippet.set_caption(self.severity().to_string()).annotate(
+        snippet.set_caption(self.severity().title()).annotate(
             self.origin(text),
             self.severity().priority(),
             self.verbose_message(text),
         );
 
+        for related in self.related(text) {
+            snippet.annotate(
+                related.origin,
+                AnnotationPriority::Secondary,
+                related.message,
+            );
+        }
+
         snippet
     }
 
@@ -472,6 +832,19 @@ impl<'a> ModuleIssue<'a> {
     }
 }
 
+/// A secondary source code fragment related to a module diagnostics issue.
+///
+/// Created by the [ModuleIssue::related] function.
+#[derive(Clone, Debug)]
+pub struct RelatedOrigin {
+    /// A reference to the related source code fragment.
+    pub origin: ScriptOrigin,
+
+    /// A short message that explains the relation between this fragment and
+    /// the issue (e.g., "first declared here").
+    pub message: String,
+}
+
 /// A heuristic suggestion that could potentially fix a module diagnostics
 /// issue.
 ///
@@ -483,9 +856,160 @@ impl<'a> ModuleIssue<'a> {
 pub struct IssueQuickfix {
     /// To resolve this issue, the [ModuleIssue::origin] fragment of the source
     /// code must be replaced with this text.
+    ///
+    /// An empty string means that the fragment must be removed (e.g., for dead
+    /// code hints).
     pub set_text_to_origin: Option<String>,
 
     /// To resolve this issue, the `use <implement_use_of>;` import statement
     /// must be added to the source code.
     pub implement_use_of: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::{
+        analysis::TriggerHandle,
+        lexis::{Position, ToSpan},
+    };
+
+    use crate::{
+        analysis::{
+            symbols::tests::TestPackage,
+            IssueCode,
+            IssueSeverity,
+            ModuleRead,
+            ScriptModule,
+        },
+        runtime::ScriptPackage,
+    };
+
+    #[test]
+    fn test_diagnostics_queries() {
+        static SOURCE: &str = "let x = 1;\nlet = 2;\nlet y = ;\nx;";
+
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+        let text = read.text();
+
+        let diagnostics = read.diagnostics(1).unwrap();
+
+        let count = diagnostics.count_by_severity();
+
+        assert_eq!(count.errors, 2);
+        assert_eq!(count.total(), diagnostics.len(!0));
+        assert_eq!(diagnostics.with_severity(IssueSeverity::Error).count(), 2);
+        assert_eq!(diagnostics.with_severity(IssueSeverity::Hint).count(), 0);
+        assert_eq!(diagnostics.with_code(IssueCode::Parse).count(), 2);
+
+        let first_line = Position::new(1, 1)..Position::new(1, 11);
+        let second_line = Position::new(2, 1)..Position::new(2, 9);
+
+        assert!(diagnostics.in_span(&text, first_line.clone()).is_empty());
+        assert_eq!(diagnostics.in_span(&text, second_line.clone()).len(), 1);
+        assert_eq!(diagnostics.in_span(&text, ..).len(), 2);
+        assert!(!diagnostics.has_errors_in(&text, first_line.clone()));
+        assert!(diagnostics.has_errors_in(&text, second_line.clone()));
+
+        let index = diagnostics.span_index(&text);
+
+        assert_eq!(index.len(), 2);
+        assert!(index.in_span(&text, first_line).is_empty());
+        assert_eq!(index.in_span(&text, second_line).len(), 1);
+
+        let lines = index
+            .in_span(&text, ..)
+            .iter()
+            .map(|issue| {
+                issue
+                    .origin(&text)
+                    .to_position_span(&text)
+                    .unwrap()
+                    .start
+                    .line
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines, [2, 3]);
+    }
+
+    #[test]
+    fn test_precedence_hints() {
+        crate::assert_script_err!(
+            TestPackage,
+            "let a = 1; let b = 2; let c = a + b << 1;",
+            IssueCode::AmbiguousPrecedence,
+            "a + b",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "let a = 1; let c = a & 1 == 0 || a << 2 * 3 > 1;",
+            IssueCode::AmbiguousPrecedence,
+            "a & 1",
+            "a << 2 * 3",
+            "2 * 3",
+        );
+
+        static NO_HINTS: &str = "let a = 1; let b = (a + 1) << 2; let c = a + b * 2 == 3 || a | b;";
+
+        let module = ScriptModule::new(TestPackage::meta(), NO_HINTS);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let diagnostics = read.diagnostics(2).unwrap();
+
+        assert_eq!(diagnostics.len(!0), 0);
+
+        let module = ScriptModule::new(TestPackage::meta(), "let a = 1; a = a + 1 >> 2;");
+        let read = module.read(&handle, 1).unwrap();
+
+        let diagnostics = read.diagnostics(2).unwrap();
+
+        let issue = diagnostics.iter().next().unwrap();
+
+        assert_eq!(issue.code(), IssueCode::AmbiguousPrecedence);
+        assert_eq!(issue.severity(), IssueSeverity::Hint);
+        assert_eq!(
+            issue.quickfix().unwrap().set_text_to_origin.unwrap(),
+            "(a + 1)",
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_index_scale() {
+        const LINES: usize = 10_000;
+
+        let source = "let = 1;\n".repeat(LINES);
+
+        let module = ScriptModule::new(TestPackage::meta(), source);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+        let text = read.text();
+
+        let diagnostics = read.diagnostics(1).unwrap();
+
+        assert_eq!(diagnostics.len(IssueSeverity::Error as u8), LINES);
+
+        let index = diagnostics.span_index(&text);
+
+        assert_eq!(index.len(), LINES);
+
+        for line in 1..=LINES {
+            let span = Position::new(line, 1)..Position::new(line, 9);
+
+            assert_eq!(index.in_span(&text, span.clone()).len(), 1);
+            assert!(index.has_errors_in(&text, span));
+        }
+
+        let middle = LINES / 2;
+
+        let span = Position::new(middle, 1)..Position::new(middle + 10, 1);
+
+        assert_eq!(
+            index.in_span(&text, span.clone()).len(),
+            diagnostics.in_span(&text, span).len(),
+        );
+    }
+}
@@ -0,0 +1,334 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    hash::{BuildHasher, Hash},
+    ops::Range,
+};
+
+use ahash::RandomState;
+
+// The maximum number of line insertions and deletions the diff algorithm
+// looks for within the changed region. If the distance is larger, the entire
+// region is treated as a single replacement.
+const MAX_DISTANCE: usize = 1024;
+
+// A contiguous fragment of the old text lines (or characters) that should be
+// replaced with a fragment of the new text lines (or characters).
+pub(super) struct Hunk {
+    pub(super) old: Range<usize>,
+    pub(super) new: Range<usize>,
+}
+
+// Computes the minimal set of line hunks that turns `old` lines into `new`
+// lines using the Myers' diff algorithm.
+//
+// The hunks are returned in ascending order and do not overlap.
+#[inline(always)]
+pub(super) fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Hunk> {
+    diff(old, new)
+}
+
+// Computes the character hunks that turn the `old` text into the `new` text.
+//
+// The texts are compared line by line first, and then the changed lines are
+// compared character by character, which keeps the edit distance of each
+// comparison small. The `"\r\n"` line breaks are compared as single units,
+// such that the hunk bounds never split them. The hunks are returned in
+// ascending order and do not overlap.
+pub(super) fn diff_chars(old: &str, new: &str) -> Vec<Hunk> {
+    let old_lines = old.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = new.split_inclusive('\n').collect::<Vec<_>>();
+
+    let old_starts = char_offsets(0, &old_lines);
+    let new_starts = char_offsets(0, &new_lines);
+
+    let mut result = Vec::new();
+
+    for hunk in diff_lines(&old_lines, &new_lines) {
+        let old_units = char_units(&old_lines[hunk.old.clone()]);
+        let new_units = char_units(&new_lines[hunk.new.clone()]);
+
+        let old_offsets = char_offsets(old_starts[hunk.old.start], &old_units);
+        let new_offsets = char_offsets(new_starts[hunk.new.start], &new_units);
+
+        for inner in diff(&old_units, &new_units) {
+            result.push(Hunk {
+                old: old_offsets[inner.old.start]..old_offsets[inner.old.end],
+                new: new_offsets[inner.new.start]..new_offsets[inner.new.end],
+            });
+        }
+    }
+
+    result
+}
+
+// Splits the lines into the character units: the `"\r\n"` line breaks, and
+// the individual characters otherwise.
+fn char_units<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let mut result = Vec::new();
+
+    for line in lines {
+        let (text, line_break) = match line.strip_suffix("\r\n") {
+            Some(text) => (text, Some(&line[text.len()..])),
+            None => (*line, None),
+        };
+
+        result.extend(text.char_indices().map(|(offset, ch)| {
+            let end = offset + ch.len_utf8();
+
+            &text[offset..end]
+        }));
+
+        if let Some(line_break) = line_break {
+            result.push(line_break);
+        }
+    }
+
+    result
+}
+
+// Returns the character offsets of the text fragments (lines or character
+// units) starting from the `base` offset, including the end of the last
+// fragment.
+fn char_offsets(base: usize, fragments: &[&str]) -> Vec<usize> {
+    let mut result = Vec::with_capacity(fragments.len() + 1);
+    let mut site = base;
+
+    result.push(site);
+
+    for fragment in fragments {
+        site += fragment.chars().count();
+        result.push(site);
+    }
+
+    result
+}
+
+// The Myers' diff of arbitrary item sequences. See diff_lines for details.
+fn diff<T: Hash + Eq>(old: &[T], new: &[T]) -> Vec<Hunk> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let old_middle = &old[prefix..(old.len() - suffix)];
+    let new_middle = &new[prefix..(new.len() - suffix)];
+
+    if old_middle.is_empty() && new_middle.is_empty() {
+        return Vec::new();
+    }
+
+    let hashes = RandomState::new();
+
+    let old_hashes = old_middle
+        .iter()
+        .map(|item| hashes.hash_one(item))
+        .collect::<Vec<_>>();
+
+    let new_hashes = new_middle
+        .iter()
+        .map(|item| hashes.hash_one(item))
+        .collect::<Vec<_>>();
+
+    let matches = match myers(&old_hashes, &new_hashes, old_middle, new_middle) {
+        Some(matches) => matches,
+        None => Vec::new(),
+    };
+
+    let mut result = Vec::new();
+    let mut old_cursor = 0;
+    let mut new_cursor = 0;
+
+    for (old_index, new_index) in matches {
+        if old_index > old_cursor || new_index > new_cursor {
+            result.push(Hunk {
+                old: (prefix + old_cursor)..(prefix + old_index),
+                new: (prefix + new_cursor)..(prefix + new_index),
+            });
+        }
+
+        old_cursor = old_index + 1;
+        new_cursor = new_index + 1;
+    }
+
+    if old_cursor < old_middle.len() || new_cursor < new_middle.len() {
+        result.push(Hunk {
+            old: (prefix + old_cursor)..(prefix + old_middle.len()),
+            new: (prefix + new_cursor)..(prefix + new_middle.len()),
+        });
+    }
+
+    result
+}
+
+// Returns matching line pairs in ascending order, or None if the edit
+// distance exceeds the MAX_DISTANCE limit.
+fn myers<T: Eq>(
+    old_hashes: &[u64],
+    new_hashes: &[u64],
+    old: &[T],
+    new: &[T],
+) -> Option<Vec<(usize, usize)>> {
+    let n = old.len() as isize;
+    let m = new.len() as isize;
+    let max = (n + m).min(MAX_DISTANCE as isize);
+    let offset = max + 1;
+
+    let equal = |x: isize, y: isize| {
+        let (x, y) = (x as usize, y as usize);
+
+        old_hashes[x] == new_hashes[y] && old[x] == new[y]
+    };
+
+    let mut frontier = vec![0isize; (2 * max + 3) as usize];
+    let mut trace = Vec::new();
+
+    let mut distance = None;
+
+    'outer: for d in 0..=max {
+        trace.push(frontier.clone());
+
+        let mut k = -d;
+
+        while k <= d {
+            let index = (k + offset) as usize;
+
+            let mut x = match k == -d || (k != d && frontier[index - 1] < frontier[index + 1]) {
+                true => frontier[index + 1],
+                false => frontier[index - 1] + 1,
+            };
+
+            let mut y = x - k;
+
+            while x < n && y < m && equal(x, y) {
+                x += 1;
+                y += 1;
+            }
+
+            frontier[index] = x;
+
+            if x >= n && y >= m {
+                distance = Some(d);
+                break 'outer;
+            }
+
+            k += 2;
+        }
+    }
+
+    let distance = distance?;
+
+    let mut matches = Vec::new();
+    let mut x = n;
+    let mut y = m;
+
+    for d in (0..=distance).rev() {
+        let frontier = &trace[d as usize];
+        let k = x - y;
+        let index = (k + offset) as usize;
+
+        let prev_k = match k == -d || (k != d && frontier[index - 1] < frontier[index + 1]) {
+            true => k + 1,
+            false => k - 1,
+        };
+
+        let prev_x = match d == 0 {
+            true => 0,
+            false => frontier[(prev_k + offset) as usize],
+        };
+
+        let prev_y = match d == 0 {
+            true => 0,
+            false => prev_x - prev_k,
+        };
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            matches.push((x as usize, y as usize));
+        }
+
+        x = prev_x;
+        y = prev_y;
+    }
+
+    matches.reverse();
+
+    Some(matches)
+}
+
+// Returns the byte and character lengths of the longest common prefix of the
+// `a` and `b` strings.
+pub(super) fn common_prefix(a: &str, b: &str) -> (usize, usize) {
+    let mut bytes = 0;
+    let mut chars = 0;
+
+    for (a, b) in a.chars().zip(b.chars()) {
+        if a != b {
+            break;
+        }
+
+        bytes += a.len_utf8();
+        chars += 1;
+    }
+
+    (bytes, chars)
+}
+
+// Returns the byte and character lengths of the longest common suffix of the
+// `a` and `b` strings.
+pub(super) fn common_suffix(a: &str, b: &str) -> (usize, usize) {
+    let mut bytes = 0;
+    let mut chars = 0;
+
+    for (a, b) in a.chars().rev().zip(b.chars().rev()) {
+        if a != b {
+            break;
+        }
+
+        bytes += a.len_utf8();
+        chars += 1;
+    }
+
+    (bytes, chars)
+}
@@ -0,0 +1,130 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{ops::Range, sync::RwLock};
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+use lady_deirdre::sync::Lazy;
+
+use crate::analysis::IssueSeverity;
+
+static VALIDATORS: Lazy<RwLock<AHashMap<CompactString, EmbeddedValidator>>> =
+    Lazy::new(|| RwLock::new(AHashMap::new()));
+
+/// A host-provided function that validates the content of the embedded data
+/// blocks written in a particular language:
+///
+/// ````text
+/// let query = ```sql
+/// SELECT * FROM users;
+/// ```;
+/// ````
+///
+/// The function receives the text of the block's body (the text between the
+/// line break that follows the language id and the closing fence), and returns
+/// a list of diagnostic issues found in this text.
+///
+/// See [set_embedded_validator] for details.
+pub type EmbeddedValidator = fn(body: &str) -> Vec<EmbeddedIssue>;
+
+/// A diagnostic issue reported by the [EmbeddedValidator] function.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct EmbeddedIssue {
+    /// A range of Unicode characters within the block's body text to which
+    /// the issue relates.
+    ///
+    /// The analyzer maps this range into the script's source code, aligning
+    /// it to the nearest script tokens.
+    pub span: Range<usize>,
+
+    /// The severity of the issue.
+    ///
+    /// The analyzer reports [errors](IssueSeverity::Error) with the
+    /// [EmbeddedError](crate::analysis::IssueCode::EmbeddedError) code, and
+    /// any other issues with the
+    /// [EmbeddedWarning](crate::analysis::IssueCode::EmbeddedWarning) code.
+    pub severity: IssueSeverity,
+
+    /// A human-readable description of the issue.
+    pub message: String,
+}
+
+/// Registers a validator of the embedded data blocks written in the specified
+/// `language`.
+///
+/// The analyzer invokes the validator during the
+/// [depth-2](crate::analysis::DiagnosticsDepth) diagnostics analysis for each
+/// embedded block ```` ```<language> ...``` ```` in the script modules, and
+/// reports the issues returned by the validator as the diagnostics of the
+/// script code.
+///
+/// If the `validator` argument is None, the function unregisters the
+/// previously registered validator.
+///
+/// The registry is global for the entire process. The analyzer caches the
+/// results of the diagnostics analysis, so you should register the validators
+/// before creating the [ScriptModules](crate::analysis::ScriptModule) that
+/// should be affected by them.
+pub fn set_embedded_validator(language: &str, validator: Option<EmbeddedValidator>) {
+    let mut validators = VALIDATORS
+        .write()
+        .unwrap_or_else(|poison| poison.into_inner());
+
+    match validator {
+        Some(validator) => {
+            let _ = validators.insert(CompactString::from(language), validator);
+        }
+
+        None => {
+            let _ = validators.remove(language);
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn validate_embedded(language: &str, body: &str) -> Vec<EmbeddedIssue> {
+    let validator = {
+        let validators = VALIDATORS
+            .read()
+            .unwrap_or_else(|poison| poison.into_inner());
+
+        match validators.get(language) {
+            Some(validator) => *validator,
+            None => return Vec::new(),
+        }
+    };
+
+    validator(body)
+}
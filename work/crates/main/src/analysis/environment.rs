@@ -0,0 +1,722 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs::{metadata, read_to_string},
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread::sleep,
+    time::{Duration, SystemTime},
+};
+
+use lady_deirdre::analysis::TriggerHandle;
+
+#[cfg(feature = "lsp")]
+use crate::server::{
+    LspLoggerConfig,
+    LspLoggerServerConfig,
+    LspServer,
+    LspServerConfig,
+    LspTransportConfig,
+};
+use crate::{
+    analysis::{
+        DiagnosticsDepth,
+        LintPass,
+        ModuleDiagnostics,
+        ModuleError,
+        ModuleRead,
+        ModuleWrite,
+        ScriptModule,
+    },
+    runtime::{Cell, PackageMeta, RuntimeError},
+};
+
+// The deepest level of diagnostics checked by the ScriptEnvironment.
+const CHECK_DEPTH: DiagnosticsDepth = 3;
+
+// The delay between the file checks of the ScriptEnvironment::watch function.
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// An alias type for the [ScriptEnvironment] results.
+pub type EnvironmentResult<T> = Result<T, EnvironmentError>;
+
+/// An error type of the [ScriptEnvironment] operations.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EnvironmentError {
+    /// The operation requires a script module, but the environment does not
+    /// have one. Use the [load](ScriptEnvironment::load) function to load the
+    /// script module.
+    NotLoaded,
+
+    /// The [watch](ScriptEnvironment::watch) function requires a script module
+    /// loaded from a file, but the module's source code was loaded from
+    /// a string.
+    NotWatchable,
+
+    /// The source code file cannot be read from disk.
+    Io {
+        /// The path of the source code file.
+        path: PathBuf,
+
+        /// The underlying I/O error.
+        error: io::Error,
+    },
+
+    /// The script module analysis error.
+    Module(ModuleError),
+
+    /// The script evaluation error.
+    Runtime(RuntimeError),
+}
+
+impl Error for EnvironmentError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::Module(error) => Some(error),
+            Self::Runtime(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Display for EnvironmentError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotLoaded => formatter.write_str("Script module is not loaded."),
+
+            Self::NotWatchable => formatter.write_str("Script module was not loaded from a file."),
+
+            Self::Io { path, error } => formatter.write_fmt(format_args!(
+                "Cannot read script file {}: {error}",
+                path.display(),
+            )),
+
+            Self::Module(error) => Display::fmt(error, formatter),
+
+            Self::Runtime(error) => Display::fmt(error, formatter),
+        }
+    }
+}
+
+impl From<ModuleError> for EnvironmentError {
+    #[inline(always)]
+    fn from(error: ModuleError) -> Self {
+        Self::Module(error)
+    }
+}
+
+impl From<RuntimeError> for EnvironmentError {
+    #[inline(always)]
+    fn from(error: RuntimeError) -> Self {
+        Self::Runtime(error)
+    }
+}
+
+/// A source of the script module's code loaded by the
+/// [ScriptEnvironment::load] function.
+///
+/// Note that string values convert into the [Text](ScriptSource::Text)
+/// variant. To load a file, use a [Path] or a [PathBuf] value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ScriptSource {
+    /// The source code is stored in the file with the specified path.
+    File(PathBuf),
+
+    /// The source code text.
+    Text(String),
+}
+
+impl From<&Path> for ScriptSource {
+    #[inline(always)]
+    fn from(path: &Path) -> Self {
+        Self::File(path.to_path_buf())
+    }
+}
+
+impl From<PathBuf> for ScriptSource {
+    #[inline(always)]
+    fn from(path: PathBuf) -> Self {
+        Self::File(path)
+    }
+}
+
+impl From<&str> for ScriptSource {
+    #[inline(always)]
+    fn from(text: &str) -> Self {
+        Self::Text(String::from(text))
+    }
+}
+
+impl From<String> for ScriptSource {
+    #[inline(always)]
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+/// A single-module scripting environment with sane defaults.
+///
+/// The ScriptEnvironment is a thin facade over the [ScriptModule] analysis,
+/// the script evaluation, and the [LSP server](crate::server::LspServer)
+/// APIs that covers the typical needs of a host application: loading
+/// the script, checking its diagnostics, running it, reloading it on file
+/// changes, and serving it to the code editor. For advanced scenarios, use
+/// the underlying APIs directly.
+///
+/// ```rust
+/// # use ad_astra::{analysis::ScriptEnvironment, export, runtime::ScriptPackage};
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// let mut environment = ScriptEnvironment::new(Package::meta());
+///
+/// environment.load("return 10 + 20;").unwrap();
+///
+/// let diagnostics = environment.check().unwrap();
+///
+/// assert!(diagnostics.is_empty());
+///
+/// match environment.run() {
+///     Ok(result) => assert_eq!(result.stringify(false), "30"),
+///     Err(error) => panic!("{}", environment.report(&error)),
+/// }
+/// ```
+pub struct ScriptEnvironment {
+    package: &'static PackageMeta,
+    lint_passes: &'static [&'static dyn LintPass<TriggerHandle>],
+    #[cfg(feature = "lsp")]
+    server_config: LspServerConfig,
+    module: Option<Arc<ScriptModule>>,
+    path: Option<PathBuf>,
+    stamp: Option<FileStamp>,
+    text: String,
+}
+
+impl ScriptEnvironment {
+    /// Creates an environment that analyzes and runs scripts under
+    /// the specified script `package` (or a
+    /// [composition](PackageMeta::compose) of packages).
+    ///
+    /// Initially, the environment does not have a script module.
+    #[inline(always)]
+    pub fn new(package: &'static PackageMeta) -> Self {
+        Self {
+            package,
+            lint_passes: &[],
+            #[cfg(feature = "lsp")]
+            server_config: LspServerConfig::new(),
+            module: None,
+            path: None,
+            stamp: None,
+            text: String::new(),
+        }
+    }
+
+    /// Sets the host's [lint passes](LintPass) of the modules loaded by this
+    /// environment.
+    ///
+    /// The passes take effect on the subsequent [load](Self::load) calls.
+    #[inline(always)]
+    pub fn with_lint_passes(
+        mut self,
+        passes: &'static [&'static dyn LintPass<TriggerHandle>],
+    ) -> Self {
+        self.lint_passes = passes;
+
+        self
+    }
+
+    /// Sets the configuration of the LSP server started by the
+    /// [serve_lsp](Self::serve_lsp) function.
+    ///
+    /// By default, the environment uses [LspServerConfig::new].
+    #[cfg(feature = "lsp")]
+    #[inline(always)]
+    pub fn with_server_config(mut self, config: LspServerConfig) -> Self {
+        self.server_config = config;
+
+        self
+    }
+
+    /// Returns the script package of this environment.
+    #[inline(always)]
+    pub fn package(&self) -> &'static PackageMeta {
+        self.package
+    }
+
+    /// Returns the currently loaded script module, if any.
+    #[inline(always)]
+    pub fn module(&self) -> Option<&Arc<ScriptModule>> {
+        self.module.as_ref()
+    }
+
+    /// Loads a new script module from the `source`, replacing the previously
+    /// loaded module.
+    ///
+    /// If the source is a [file](ScriptSource::File), the module is named
+    /// after the file path, and the module can be [watched](Self::watch) for
+    /// the file changes.
+    pub fn load(
+        &mut self,
+        source: impl Into<ScriptSource>,
+    ) -> EnvironmentResult<Arc<ScriptModule>> {
+        let (path, stamp, text) = match source.into() {
+            ScriptSource::File(path) => {
+                let stamp = FileStamp::of(&path);
+
+                match read_to_string(&path) {
+                    Ok(text) => (Some(path), stamp, text),
+                    Err(error) => return Err(EnvironmentError::Io { path, error }),
+                }
+            }
+
+            ScriptSource::Text(text) => (None, None, text),
+        };
+
+        let module = ScriptModule::new(self.package, &text);
+
+        if let Some(path) = &path {
+            module.rename(path.display().to_string());
+        }
+
+        if !self.lint_passes.is_empty() {
+            let handle = TriggerHandle::new();
+            let mut write_guard = module.write(&handle, 1)?;
+
+            write_guard.set_lint_passes(self.lint_passes);
+        }
+
+        let module = Arc::new(module);
+
+        self.module = Some(module.clone());
+        self.path = path;
+        self.stamp = stamp;
+        self.text = text;
+
+        Ok(module)
+    }
+
+    /// Checks the diagnostics of the loaded script module.
+    ///
+    /// The function checks the diagnostics levels from 1 to 3 one by one, and
+    /// returns the first non-empty diagnostics. If the module does not have
+    /// any issues, the function returns the empty diagnostics of the deepest
+    /// level.
+    pub fn check(&self) -> EnvironmentResult<ModuleDiagnostics> {
+        let module = self.loaded()?;
+        let handle = TriggerHandle::new();
+        let read_guard = module.read(&handle, 1)?;
+
+        let mut depth = 1;
+
+        loop {
+            let diagnostics = read_guard.diagnostics(depth)?;
+
+            if !diagnostics.is_empty() || depth == CHECK_DEPTH {
+                return Ok(diagnostics);
+            }
+
+            depth += 1;
+        }
+    }
+
+    /// Compiles and runs the loaded script module, returning the result of
+    /// the script evaluation.
+    ///
+    /// Note that the function does not [check](Self::check) the module's
+    /// diagnostics before running the script.
+    pub fn run(&self) -> EnvironmentResult<Cell> {
+        let module = self.loaded()?;
+        let handle = TriggerHandle::new();
+        let read_guard = module.read(&handle, 1)?;
+
+        let script_fn = read_guard.compile()?;
+
+        Ok(script_fn.run()?)
+    }
+
+    /// Synchronizes the loaded script module with its source file.
+    ///
+    /// The function compares the file's modification time and size with
+    /// the ones observed on the previous load, and re-reads the file only if
+    /// they differ. If the new file content differs from the module's text,
+    /// the function updates the module incrementally and returns true.
+    /// Otherwise, including the case when the file is temporarily missing,
+    /// the function returns false.
+    ///
+    /// This function is useful for the hosts that track the file changes on
+    /// their own (e.g., through the file system events).
+    ///
+    /// The module must be [loaded](Self::load) from a file. Otherwise, the
+    /// function returns the [NotWatchable](EnvironmentError::NotWatchable)
+    /// error.
+    pub fn reload(&mut self) -> EnvironmentResult<bool> {
+        let Some(module) = &self.module else {
+            return Err(EnvironmentError::NotLoaded);
+        };
+
+        let Some(path) = &self.path else {
+            return Err(EnvironmentError::NotWatchable);
+        };
+
+        refresh(module, path, &mut self.stamp, &mut self.text)
+    }
+
+    /// Blocks the current thread, and reloads the script module each time
+    /// the content of the module's source file changes.
+    ///
+    /// After each reload, the function calls the `callback` function. If the
+    /// callback returns false, the function stops watching and returns.
+    ///
+    /// The function periodically checks the file's modification time and
+    /// size, and re-reads the file only when they change (see
+    /// [reload](Self::reload)).
+    ///
+    /// If the source file is temporarily missing (e.g., while the code editor
+    /// replaces the file on save), the function keeps watching until the file
+    /// appears again.
+    ///
+    /// The module must be [loaded](Self::load) from a file. Otherwise, the
+    /// function returns the [NotWatchable](EnvironmentError::NotWatchable)
+    /// error.
+    pub fn watch(&self, mut callback: impl FnMut(&Self) -> bool) -> EnvironmentResult<()> {
+        let module = self.loaded()?;
+
+        let Some(path) = &self.path else {
+            return Err(EnvironmentError::NotWatchable);
+        };
+
+        let mut stamp = self.stamp;
+        let mut text = self.text.clone();
+
+        loop {
+            sleep(WATCH_INTERVAL);
+
+            if !refresh(module, path, &mut stamp, &mut text)? {
+                continue;
+            }
+
+            if !callback(self) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Starts the [LSP server](LspServer) that serves the script files of
+    /// this environment's package to the code editor through the specified
+    /// `transport`.
+    ///
+    /// The function blocks the current thread until the server finishes.
+    ///
+    /// The server uses the [server config](Self::with_server_config) of this
+    /// environment and the default logger configuration. When the transport
+    /// is [Stdio](LspTransportConfig::Stdio), the server-side logger is turned
+    /// off, because the STD-IO stream is occupied by the communication
+    /// channel.
+    #[cfg(feature = "lsp")]
+    pub fn serve_lsp(&self, transport: LspTransportConfig) {
+        let mut logger_config = LspLoggerConfig::new();
+
+        if let LspTransportConfig::Stdio = &transport {
+            logger_config.server = LspLoggerServerConfig::Off;
+        }
+
+        LspServer::startup(self.server_config, logger_config, transport, self.package);
+    }
+
+    /// Renders the `diagnostics` of the loaded module into a string with
+    /// the annotated source code snippets, ready to be printed to the terminal.
+    pub fn highlight(&self, diagnostics: &ModuleDiagnostics) -> EnvironmentResult<String> {
+        let module = self.loaded()?;
+        let handle = TriggerHandle::new();
+        let read_guard = module.read(&handle, 1)?;
+
+        let text = read_guard.text();
+        let highlight = diagnostics.highlight(&text, !0).to_string();
+
+        Ok(highlight)
+    }
+
+    /// Renders the `error` into a string, ready to be printed to the terminal.
+    ///
+    /// If the error is a runtime error, and the environment has a loaded
+    /// module, the rendered string includes the annotated source code snippet.
+    pub fn report(&self, error: &EnvironmentError) -> String {
+        let EnvironmentError::Runtime(error) = error else {
+            return error.to_string();
+        };
+
+        let Some(module) = &self.module else {
+            return error.to_string();
+        };
+
+        let handle = TriggerHandle::new();
+
+        let Ok(read_guard) = module.read(&handle, 1) else {
+            return error.to_string();
+        };
+
+        let text = read_guard.text();
+        let report = error.display(&text).to_string();
+
+        report
+    }
+
+    #[inline(always)]
+    fn loaded(&self) -> EnvironmentResult<&Arc<ScriptModule>> {
+        match &self.module {
+            Some(module) => Ok(module),
+            None => Err(EnvironmentError::NotLoaded),
+        }
+    }
+}
+
+// The source file's properties that indicate a change of the file's content
+// without reading the file.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    // Returns None if the file metadata is not available (e.g., if the file is
+    // missing).
+    #[inline(always)]
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = metadata(path).ok()?;
+
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+// Updates the `module` with the content of the file at `path` if the file's
+// stamp and the content differ from the previously observed `stamp` and
+// `text`. Returns true if the module has been updated.
+fn refresh(
+    module: &ScriptModule,
+    path: &Path,
+    stamp: &mut Option<FileStamp>,
+    text: &mut String,
+) -> EnvironmentResult<bool> {
+    // Some editors save files by removing and recreating them.
+    let Some(new_stamp) = FileStamp::of(path) else {
+        return Ok(false);
+    };
+
+    if stamp.as_ref() == Some(&new_stamp) {
+        return Ok(false);
+    }
+
+    let new_text = match read_to_string(path) {
+        Ok(new_text) => new_text,
+
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(false),
+
+        Err(error) => {
+            return Err(EnvironmentError::Io {
+                path: path.to_path_buf(),
+                error,
+            })
+        }
+    };
+
+    *stamp = Some(new_stamp);
+
+    if &new_text == text {
+        return Ok(false);
+    }
+
+    {
+        let handle = TriggerHandle::new();
+        let mut write_guard = module.write(&handle, 1)?;
+
+        let _ = write_guard.replace_from_reader(new_text.as_bytes())?;
+    }
+
+    *text = new_text;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{remove_file, write},
+        process::id,
+        thread::{sleep, spawn},
+        time::Duration,
+    };
+
+    use lady_deirdre::arena::Identifiable;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, EnvironmentError, ScriptEnvironment},
+        runtime::{Origin, ScriptPackage},
+    };
+
+    #[test]
+    fn test_environment() {
+        let mut environment = ScriptEnvironment::new(TestPackage::meta());
+
+        assert!(matches!(
+            environment.run(),
+            Err(EnvironmentError::NotLoaded)
+        ));
+
+        environment.load("return 1 + ;").unwrap();
+
+        assert!(!environment.check().unwrap().is_empty());
+
+        environment.load("return 1 + 2;").unwrap();
+
+        assert!(environment.check().unwrap().is_empty());
+
+        assert_eq!(
+            environment
+                .run()
+                .unwrap()
+                .take::<usize>(Origin::nil())
+                .unwrap(),
+            3,
+        );
+
+        assert!(matches!(
+            environment.watch(|_| false),
+            Err(EnvironmentError::NotWatchable)
+        ));
+
+        assert!(matches!(
+            environment.reload(),
+            Err(EnvironmentError::NotWatchable)
+        ));
+
+        environment.load("let x = 1; return x.y;").unwrap();
+
+        let error = environment.run().unwrap_err();
+
+        assert!(matches!(error, EnvironmentError::Runtime(_)));
+        assert!(environment
+            .report(&error)
+            .contains("does not have field 'y'"));
+    }
+
+    #[test]
+    fn test_environment_watch() {
+        let path = temp_dir().join(format!("ad-astra-watch-{}.adastra", id()));
+
+        write(&path, "return 1;").unwrap();
+
+        let mut environment = ScriptEnvironment::new(TestPackage::meta());
+
+        let module = environment.load(path.as_path()).unwrap();
+
+        assert_eq!(module.id().name(), path.display().to_string());
+
+        let writer = spawn({
+            let path = path.clone();
+
+            move || {
+                sleep(Duration::from_millis(500));
+                remove_file(&path).unwrap();
+                sleep(Duration::from_millis(600));
+                write(&path, "return 100;").unwrap();
+            }
+        });
+
+        let mut results = Vec::new();
+
+        environment
+            .watch(|environment| {
+                results.push(
+                    environment
+                        .run()
+                        .unwrap()
+                        .take::<usize>(Origin::nil())
+                        .unwrap(),
+                );
+
+                false
+            })
+            .unwrap();
+
+        writer.join().unwrap();
+        remove_file(&path).unwrap();
+
+        assert_eq!(results, [100]);
+    }
+
+    #[test]
+    fn test_environment_reload() {
+        let path = temp_dir().join(format!("ad-astra-reload-{}.adastra", id()));
+
+        write(&path, "return 1;").unwrap();
+
+        let mut environment = ScriptEnvironment::new(TestPackage::meta());
+
+        let _ = environment.load(path.as_path()).unwrap();
+
+        assert!(!environment.reload().unwrap());
+
+        write(&path, "return 200;").unwrap();
+
+        assert!(environment.reload().unwrap());
+        assert!(!environment.reload().unwrap());
+
+        remove_file(&path).unwrap();
+
+        assert!(!environment.reload().unwrap());
+
+        assert_eq!(
+            environment
+                .run()
+                .unwrap()
+                .take::<usize>(Origin::nil())
+                .unwrap(),
+            200,
+        );
+    }
+}
@@ -93,6 +93,15 @@ pub enum ModuleError {
     /// Indicates that the addressed source code character or range of
     /// characters is not valid for the underlying script module.
     Cursor(Id),
+
+    /// Indicates that the source code text cannot be read from the input
+    /// source (e.g., due to an I/O error, or because the input is not a valid
+    /// UTF-8 text).
+    ///
+    /// Returned by the
+    /// [replace_from_reader](crate::analysis::ModuleWrite::replace_from_reader)
+    /// function.
+    Input(Id),
 }
 
 impl Error for ModuleError {}
@@ -104,6 +113,7 @@ impl Identifiable for ModuleError {
             Self::Interrupted(id) => *id,
             Self::Timeout(id) => *id,
             Self::Cursor(id) => *id,
+            Self::Input(id) => *id,
         }
     }
 }
@@ -123,6 +133,10 @@ impl Display for ModuleError {
             Self::Cursor(id) => formatter.write_fmt(format_args!(
                 "The specified source code site or range of sites is not valid for module {id}.",
             )),
+
+            Self::Input(id) => formatter.write_fmt(format_args!(
+                "Cannot read the source code text for module {id} from the input source.",
+            )),
         }
     }
 }
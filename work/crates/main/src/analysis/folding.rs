@@ -0,0 +1,541 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    marker::PhantomData,
+    panic::{catch_unwind, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use lady_deirdre::{
+    analysis::{AbstractTask, TaskHandle},
+    arena::Identifiable,
+    lexis::{SourceCode, ToSpan},
+    syntax::NodeRef,
+};
+
+use crate::{
+    analysis::{AnalysisConfig, ModuleRead, ModuleResult, ModuleResultEx},
+    runtime::{Arg, Cell, Ident, Origin, PackageMeta, RustIdent, ScriptType},
+    semantics::{IdentCrossResolution, LocalNumberValue},
+    syntax::{ScriptDoc, ScriptNode, ScriptToken},
+};
+
+// The maximum number of pure function invocations per folded expression, or
+// per compiled assembly.
+const CALLS_BUDGET: usize = 32;
+
+// The maximum total time of pure function invocations per folded expression,
+// or per compiled assembly. The folder cannot interrupt a running Rust
+// function, but it stops folding as soon as the budget is exceeded.
+const TIME_BUDGET: Duration = Duration::from_millis(20);
+
+// Invokes the exported pure functions within the shared budget of the number
+// and the total time of the invocations.
+pub(crate) struct PureCalls {
+    enabled: bool,
+    calls: usize,
+    spent: Duration,
+}
+
+impl PureCalls {
+    // If the `config` disables the pure calls, the object does not invoke
+    // any functions.
+    #[inline(always)]
+    pub(crate) fn new(config: &AnalysisConfig) -> Self {
+        Self {
+            enabled: config.pure_calls,
+            calls: 0,
+            spent: Duration::ZERO,
+        }
+    }
+
+    // Returns None if the function returns an error, panics, or if the budget
+    // is exceeded.
+    pub(crate) fn invoke(
+        &mut self,
+        package: &'static PackageMeta,
+        name: &'static RustIdent,
+        mut arguments: Vec<Arg>,
+    ) -> Option<Cell> {
+        if !self.enabled || self.calls >= CALLS_BUDGET || self.spent >= TIME_BUDGET {
+            return None;
+        }
+
+        self.calls += 1;
+
+        let start = Instant::now();
+
+        // A function marked as pure may still panic. A panic in the host code
+        // must not crash the analyzer, so the expression is just not folded.
+        let result = catch_unwind(AssertUnwindSafe(move || {
+            let function = package.instance().into_object().component(
+                Origin::nil(),
+                Origin::nil(),
+                Ident::Rust(name),
+            )?;
+
+            function
+                .into_object()
+                .invoke(Origin::nil(), Origin::nil(), &mut arguments)
+        }));
+
+        self.spent += start.elapsed();
+
+        if self.spent >= TIME_BUDGET {
+            return None;
+        }
+
+        result.ok()?.ok()
+    }
+}
+
+pub(super) struct ConstFolder<'a, H: TaskHandle, R: ModuleRead<H>> {
+    read: &'a R,
+    doc: &'a ScriptDoc,
+    calls: PureCalls,
+    _handle: PhantomData<H>,
+}
+
+impl<'a, H: TaskHandle, R: ModuleRead<H>> ConstFolder<'a, H, R> {
+    pub(super) fn fold(
+        read: &'a R,
+        doc: &'a ScriptDoc,
+        expr: &NodeRef,
+    ) -> ModuleResult<Option<Cell>> {
+        let task = read.task();
+
+        let (_, config) = task
+            .common()
+            .analysis_config
+            .snapshot(task)
+            .into_module_result(read.id())?;
+
+        let mut folder = Self {
+            read,
+            doc,
+            calls: PureCalls::new(&config),
+            _handle: PhantomData,
+        };
+
+        folder.fold_expr(expr)
+    }
+
+    fn fold_expr(&mut self, expr: &NodeRef) -> ModuleResult<Option<Cell>> {
+        let Some(script_node) = expr.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        match script_node {
+            ScriptNode::Expr { inner, .. } => self.fold_expr(inner),
+
+            ScriptNode::Number { semantics, .. } => {
+                let number_semantics = semantics.get().into_module_result(self.read.id())?;
+
+                let (_, number_value) = number_semantics
+                    .number_value
+                    .snapshot(self.read.task())
+                    .into_module_result(self.read.id())?;
+
+                Ok(match number_value {
+                    LocalNumberValue::Usize(Ok(value)) => Cell::give(Origin::nil(), value).ok(),
+                    LocalNumberValue::Isize(Ok(value)) => Cell::give(Origin::nil(), value).ok(),
+                    LocalNumberValue::Float(Ok(value)) => Cell::give(Origin::nil(), value.0).ok(),
+                    _ => None,
+                })
+            }
+
+            ScriptNode::Max { .. } => Ok(Cell::give(Origin::nil(), usize::MAX).ok()),
+
+            // Variables assigned exactly once with an integer constant
+            // expression.
+            ScriptNode::Ident { .. } => {
+                let (_, type_resolution) = script_node
+                    .type_resolution()
+                    .into_module_result(self.read.id())?
+                    .snapshot(self.read.task())
+                    .into_module_result(self.read.id())?;
+
+                let Some(value) = type_resolution.constant else {
+                    return Ok(None);
+                };
+
+                match type_resolution.tag.type_meta() {
+                    Some(ty) if ty == <usize>::type_meta() => {
+                        Ok(Cell::give(Origin::nil(), value as usize).ok())
+                    }
+
+                    _ => Ok(Cell::give(Origin::nil(), value).ok()),
+                }
+            }
+
+            ScriptNode::Bool { token, .. } => Ok(match token.deref(self.doc) {
+                Some(ScriptToken::True) => Cell::give(Origin::nil(), true).ok(),
+                Some(ScriptToken::False) => Cell::give(Origin::nil(), false).ok(),
+                _ => None,
+            }),
+
+            ScriptNode::String { start, end, .. } => {
+                let (Some(start), Some(end)) = (start.site(self.doc), end.site(self.doc)) else {
+                    return Ok(None);
+                };
+
+                let span = (start + 1)..end;
+
+                if !span.is_valid_span(self.doc) {
+                    return Ok(None);
+                }
+
+                let string = self.doc.substring(span).into_owned();
+
+                Ok(Cell::give(Origin::nil(), string).ok())
+            }
+
+            ScriptNode::Embedded { start, end, .. } => {
+                let Some((_, span)) = ScriptNode::extract_embedded(self.doc, start, end) else {
+                    return Ok(None);
+                };
+
+                let string = self.doc.substring(span).into_owned();
+
+                Ok(Cell::give(Origin::nil(), string).ok())
+            }
+
+            ScriptNode::UnaryLeft { op, right, .. } => {
+                let Some(ScriptNode::Op { token, .. }) = op.deref(self.doc) else {
+                    return Ok(None);
+                };
+
+                let Some(ScriptToken::Minus) = token.deref(self.doc) else {
+                    return Ok(None);
+                };
+
+                let Some(rhs) = self.fold_expr(right)? else {
+                    return Ok(None);
+                };
+
+                Ok(rhs.into_object().neg(Origin::nil(), Origin::nil()).ok())
+            }
+
+            ScriptNode::Call { left, args, .. } => self.fold_call(left, args),
+
+            _ => Ok(None),
+        }
+    }
+
+    fn fold_call(&mut self, left: &NodeRef, args: &NodeRef) -> ModuleResult<Option<Cell>> {
+        let Some((package, name)) = resolve_pure_fn(self.read, self.doc, left)? else {
+            return Ok(None);
+        };
+
+        let Some(args) = args
+            .deref(self.doc)
+            .and_then(|node| node.call_args(self.doc))
+        else {
+            return Ok(None);
+        };
+
+        let mut arguments = Vec::with_capacity(args.len());
+
+        for arg in &args {
+            let Some(data) = self.fold_expr(arg)? else {
+                return Ok(None);
+            };
+
+            arguments.push(Arg {
+                origin: Origin::nil(),
+                data,
+            });
+        }
+
+        Ok(self.calls.invoke(package, name, arguments))
+    }
+}
+
+// Resolves the invocation target of a call expression into an exported
+// package function marked as pure. Returns None if the callee is anything else.
+pub(super) fn resolve_pure_fn<H: TaskHandle>(
+    read: &impl ModuleRead<H>,
+    doc: &ScriptDoc,
+    left: &NodeRef,
+) -> ModuleResult<Option<(&'static PackageMeta, &'static RustIdent)>> {
+    let mut left = left;
+
+    while let Some(ScriptNode::Expr { inner, .. }) = left.deref(doc) {
+        left = inner;
+    }
+
+    let Some(ScriptNode::Ident {
+        token, semantics, ..
+    }) = left.deref(doc)
+    else {
+        return Ok(None);
+    };
+
+    let Some(name) = token.string(doc) else {
+        return Ok(None);
+    };
+
+    let id = read.id();
+
+    let ident_semantics = semantics.get().into_module_result(id)?;
+
+    let (_, cross_resolution) = ident_semantics
+        .cross_resolution
+        .snapshot(read.task())
+        .into_module_result(id)?;
+
+    let IdentCrossResolution::Read { name: resolution } = cross_resolution else {
+        return Ok(None);
+    };
+
+    let package = match resolution.as_ref().decl.deref(doc) {
+        Some(ScriptNode::Root { .. }) => read.package(),
+
+        Some(ScriptNode::Use { .. }) => {
+            let Some(package_ref) = resolution.as_ref().defs.iter().next() else {
+                return Ok(None);
+            };
+
+            let Some(ScriptNode::Package { semantics, .. }) = package_ref.deref(doc) else {
+                return Ok(None);
+            };
+
+            let package_semantics = semantics.get().into_module_result(id)?;
+
+            let (_, package_resolution) = package_semantics
+                .package_resolution
+                .snapshot(read.task())
+                .into_module_result(id)?;
+
+            let Some(package) = package_resolution.package else {
+                return Ok(None);
+            };
+
+            package
+        }
+
+        _ => return Ok(None),
+    };
+
+    let Some(component) = package.ty().prototype().hint_component(name) else {
+        return Ok(None);
+    };
+
+    if !component.pure {
+        return Ok(None);
+    }
+
+    Ok(Some((package, component.name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{
+            symbols::tests::TestPackage,
+            AnalysisConfig,
+            ModuleRead,
+            ModuleWrite,
+            ScriptModule,
+        },
+        export,
+        interpret::ScriptFn,
+        runtime::ScriptPackage,
+    };
+
+    thread_local! {
+        // The modules are compiled on the test's thread, so the counter is
+        // not affected by the tests running in parallel.
+        static MIX_CALLS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[export(pure)]
+    pub fn test_pure_mix(a: usize, b: usize) -> usize {
+        MIX_CALLS.set(MIX_CALLS.get() + 1);
+
+        a * 10 + b
+    }
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct TestPurePoint {
+        pub x: usize,
+    }
+
+    #[export(pure)]
+    pub fn test_pure_point(x: usize) -> TestPurePoint {
+        TestPurePoint { x }
+    }
+
+    #[export(pure)]
+    pub fn test_pure_panic(x: usize) -> usize {
+        match x {
+            0 => panic!("Pure function panic."),
+            _ => x,
+        }
+    }
+
+    fn compile(text: &str) -> ScriptFn {
+        compile_with(text, AnalysisConfig::new())
+    }
+
+    fn compile_with(text: &str, config: AnalysisConfig) -> ScriptFn {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+
+        module
+            .write(&handle, 1)
+            .unwrap()
+            .set_analysis_config(config)
+            .unwrap();
+
+        let read = module.read(&handle, 1).unwrap();
+
+        read.compile().unwrap()
+    }
+
+    #[test]
+    fn test_pure_precompute() {
+        MIX_CALLS.set(0);
+
+        let script_fn = compile(
+            r#"
+            let a = 2;
+            let b = test_pure_mix(test_pure_mix(a, 1), -(-3));
+            let c = test_pure_mix(a, 4);
+            return [b, c];
+        "#,
+        );
+
+        let compile_calls = MIX_CALLS.get();
+
+        assert_eq!(compile_calls, 3);
+        assert!(format!("{script_fn:?}").contains("push const"));
+
+        for _ in 0..3 {
+            assert_eq!(script_fn.run().unwrap().stringify(false), "[213, 24]");
+        }
+
+        assert_eq!(MIX_CALLS.get(), compile_calls);
+
+        let script_fn = compile(
+            r#"
+            let a = 2;
+            a += 1;
+            return test_pure_mix(a, 1);
+        "#,
+        );
+
+        assert!(!format!("{script_fn:?}").contains("push const"));
+        assert_eq!(script_fn.run().unwrap().stringify(false), "31");
+        assert_eq!(MIX_CALLS.get(), compile_calls + 1);
+    }
+
+    #[test]
+    fn test_pure_precompute_copies() {
+        let script_fn = compile(
+            r#"
+            let f = fn() {
+                let p = test_pure_point(1);
+                p.x += 10;
+                return p.x;
+            };
+
+            return [f(), f()];
+        "#,
+        );
+
+        assert_eq!(script_fn.run().unwrap().stringify(false), "[11, 11]");
+        assert_eq!(script_fn.run().unwrap().stringify(false), "[11, 11]");
+    }
+
+    #[test]
+    fn test_pure_precompute_panic() {
+        let script_fn = compile(
+            r#"
+            let f = fn() { return test_pure_panic(0); };
+            return [f, test_pure_panic(5)];
+        "#,
+        );
+
+        let dump = format!("{script_fn:?}");
+
+        assert_eq!(dump.matches("push const").count(), 1);
+    }
+
+    #[test]
+    fn test_pure_precompute_budget() {
+        MIX_CALLS.set(0);
+
+        let calls = vec!["test_pure_mix(1, 2)"; 40].join(", ");
+
+        let script_fn = compile(&format!("return [{calls}];"));
+
+        assert_eq!(MIX_CALLS.get(), 32);
+        assert_eq!(format!("{script_fn:?}").matches("push const").count(), 32);
+        assert_eq!(
+            script_fn
+                .run()
+                .unwrap()
+                .stringify(false)
+                .matches("12")
+                .count(),
+            40
+        );
+        assert_eq!(MIX_CALLS.get(), 40);
+    }
+
+    #[test]
+    fn test_pure_precompute_disabled() {
+        MIX_CALLS.set(0);
+
+        let script_fn = compile_with(
+            "return test_pure_mix(2, 1);",
+            AnalysisConfig {
+                pure_calls: false,
+                ..AnalysisConfig::new()
+            },
+        );
+
+        assert_eq!(MIX_CALLS.get(), 0);
+        assert!(!format!("{script_fn:?}").contains("push const"));
+        assert_eq!(script_fn.run().unwrap().stringify(false), "21");
+        assert_eq!(MIX_CALLS.get(), 1);
+    }
+}
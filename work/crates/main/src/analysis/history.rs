@@ -0,0 +1,251 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::VecDeque;
+
+use lady_deirdre::{
+    analysis::Revision,
+    lexis::{SiteSpan, SourceCode},
+};
+
+use crate::{
+    analysis::{
+        annotation::ModuleAnnotations,
+        bookmark::{shift_span, Bookmark},
+        diff::Hunk,
+    },
+    runtime::TextHasher,
+    syntax::ScriptDoc,
+};
+
+// The default number of the most recent edits kept by the module's edit
+// history.
+pub(super) const DEFAULT_HISTORY_CAPACITY: usize = 1024;
+
+// A bounded log of the source code edits applied to the script module.
+//
+// Each edit increments the module's text revision. The log keeps the spans of
+// the last `capacity` edits, which is enough to map the sites of the older
+// text revisions within this window to the sites of the current text.
+//
+// The history also owns the module's annotations, whose spans are remapped
+// eagerly on each edit.
+pub struct EditHistory {
+    revision: Revision,
+    // The hash of the text at the current revision, if already computed.
+    text_hash: Option<u64>,
+    capacity: usize,
+    edits: VecDeque<EditRecord>,
+    annotations: ModuleAnnotations,
+}
+
+struct EditRecord {
+    span: SiteSpan,
+    inserted: usize,
+}
+
+impl EditHistory {
+    #[inline(always)]
+    pub(super) fn new(text_hash: u64) -> Self {
+        Self {
+            revision: 0,
+            text_hash: Some(text_hash),
+            capacity: DEFAULT_HISTORY_CAPACITY,
+            edits: VecDeque::new(),
+            annotations: ModuleAnnotations::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn revision(&self) -> Revision {
+        self.revision
+    }
+
+    #[inline(always)]
+    pub(super) fn text_hash(&self) -> Option<u64> {
+        self.text_hash
+    }
+
+    #[inline(always)]
+    pub(super) fn set_text_hash(&mut self, text_hash: u64) {
+        self.text_hash = Some(text_hash);
+    }
+
+    pub(super) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+
+        while self.edits.len() > capacity {
+            let _ = self.edits.pop_front();
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn annotations(&self) -> &ModuleAnnotations {
+        &self.annotations
+    }
+
+    #[inline(always)]
+    pub(super) fn annotations_mut(&mut self) -> &mut ModuleAnnotations {
+        &mut self.annotations
+    }
+
+    // The `refinement` is an optional set of the character hunks (relative to
+    // the `span` start) that describes the edit more precisely. The
+    // refinement is used to remap the annotations only.
+    pub(super) fn record(
+        &mut self,
+        span: SiteSpan,
+        inserted: usize,
+        refinement: Option<Vec<Hunk>>,
+    ) -> Revision {
+        self.revision += 1;
+        self.text_hash = None;
+
+        match refinement {
+            None => self.annotations.remap(&span, inserted),
+
+            // The hunks are applied in reverse order such that the sites of
+            // the preceding hunks remain valid.
+            Some(hunks) => {
+                for hunk in hunks.iter().rev() {
+                    let start = span.start + hunk.old.start;
+                    let end = span.start + hunk.old.end;
+
+                    self.annotations.remap(&(start..end), hunk.new.len());
+                }
+            }
+        }
+
+        if self.capacity == 0 {
+            return self.revision;
+        }
+
+        if self.edits.len() == self.capacity {
+            let _ = self.edits.pop_front();
+        }
+
+        self.edits.push_back(EditRecord { span, inserted });
+
+        self.revision
+    }
+
+    // Returns None if the `revision` is outside of the retention window, or if
+    // any of the later edits rewrote the spanned fragment.
+    pub(super) fn remap(&self, mut span: SiteSpan, revision: Revision) -> Option<SiteSpan> {
+        if revision > self.revision {
+            return None;
+        }
+
+        let behind = (self.revision - revision) as usize;
+
+        let Some(first) = self.edits.len().checked_sub(behind) else {
+            return None;
+        };
+
+        for edit in self.edits.range(first..) {
+            let start = edit.span.start;
+            let end = edit.span.end;
+
+            if start < span.end && end > span.start {
+                return None;
+            }
+
+            if span.start >= end {
+                span.start = span.start - (end - start) + edit.inserted;
+            }
+
+            if span.end >= end {
+                span.end = span.end - (end - start) + edit.inserted;
+            }
+        }
+
+        Some(span)
+    }
+
+    // Maps the `span` of the `revision` text to the current text if `forward`
+    // is true, or the `span` of the current text to the `revision` text
+    // otherwise.
+    pub(super) fn bookmark(
+        &self,
+        mut span: SiteSpan,
+        revision: Revision,
+        forward: bool,
+    ) -> Bookmark {
+        if revision > self.revision {
+            return Bookmark::Lost;
+        }
+
+        let behind = (self.revision - revision) as usize;
+
+        let Some(first) = self.edits.len().checked_sub(behind) else {
+            return Bookmark::Lost;
+        };
+
+        let mut edited = false;
+
+        match forward {
+            true => {
+                for edit in self.edits.range(first..) {
+                    let old_len = edit.span.end - edit.span.start;
+
+                    edited |= shift_span(&mut span, edit.span.start, old_len, edit.inserted);
+                }
+            }
+
+            false => {
+                for edit in self.edits.range(first..).rev() {
+                    let old_len = edit.span.end - edit.span.start;
+
+                    edited |= shift_span(&mut span, edit.span.start, edit.inserted, old_len);
+                }
+            }
+        }
+
+        match edited {
+            false => Bookmark::Intact(span),
+            true => Bookmark::Edited(span),
+        }
+    }
+}
+
+// Computes the stable hash of the document's text.
+pub(super) fn hash_text(doc: &ScriptDoc) -> u64 {
+    let mut hasher = TextHasher::new();
+
+    for chunk in doc.chunks(..) {
+        hasher.write(chunk.string);
+    }
+
+    hasher.finish()
+}
@@ -0,0 +1,741 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    fmt::{Display, Formatter},
+    ops::Deref,
+};
+
+use compact_str::CompactString;
+use lady_deirdre::{
+    analysis::{AbstractTask, TaskHandle},
+    lexis::{SiteSpan, SourceCode, ToSpan},
+    syntax::{AbstractNode, NodeRef, PolyRef},
+};
+
+use crate::{
+    analysis::{folding::resolve_pure_fn, ModuleRead, ModuleResult, ModuleResultEx},
+    runtime::ScriptOrigin,
+    semantics::IdentCrossResolution,
+    syntax::{Assoc, PolyRefOrigin, Precedence, ScriptClass, ScriptDoc, ScriptNode, SpanBounds},
+};
+
+// The maximum number of variable reads that the inliner agrees to replace.
+const USES_LIMIT: usize = 3;
+
+/// A result of the [inline_variable](ModuleRead::inline_variable) analysis.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum InlinePlan {
+    /// The variable can be inlined by applying all of these source code edits.
+    ///
+    /// The edits do not overlap and are ordered by their positions in the
+    /// source code. The first edit removes the let-statement, and the rest
+    /// replace each read of the variable with its initialization expression.
+    Edits(Vec<InlineEdit>),
+
+    /// Inlining the variable could change the script's behavior, or the
+    /// analyzer is unable to prove otherwise.
+    Refused(InlineRefusal),
+}
+
+impl InlinePlan {
+    /// Returns true if the plan is [Edits](Self::Edits).
+    #[inline(always)]
+    pub fn is_inlinable(&self) -> bool {
+        match self {
+            Self::Edits(_) => true,
+            Self::Refused(_) => false,
+        }
+    }
+}
+
+/// A single source code replacement of the [InlinePlan].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct InlineEdit {
+    /// The source code fragment that must be replaced.
+    pub origin: ScriptOrigin,
+
+    /// The replacement text. An empty string means that the fragment must be
+    /// removed.
+    pub text: String,
+}
+
+/// A reason why the variable cannot be [inlined](ModuleRead::inline_variable).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum InlineRefusal {
+    /// The symbol is not a let-statement variable with an initialization
+    /// expression (`let x = <expr>;`), or the statement has syntax errors.
+    NotInlinable,
+
+    /// The variable is assigned or mutated after initialization.
+    Reassigned,
+
+    /// The variable is read too many times.
+    TooManyUses,
+
+    /// The initialization expression may have side effects, such as calls to
+    /// exported functions that are not marked as pure.
+    SideEffects,
+
+    /// One of the variables the initialization expression depends on may be
+    /// mutated between the declaration and a read of the inlined variable, or
+    /// the read happens in a function that could be called later.
+    EvaluationOrder,
+
+    /// One of the variables the initialization expression depends on is
+    /// shadowed by another declaration at a read of the inlined variable.
+    Shadowed,
+}
+
+impl Display for InlineRefusal {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(match self {
+            Self::NotInlinable => "not a let-statement with an initializer",
+            Self::Reassigned => "the variable is reassigned or mutated",
+            Self::TooManyUses => "the variable is used too many times",
+            Self::SideEffects => "the initializer may have side effects",
+            Self::EvaluationOrder => "inlining would change the evaluation order",
+            Self::Shadowed => "the initializer's dependencies are shadowed",
+        })
+    }
+}
+
+pub(super) struct VarInliner<'a, H: TaskHandle, R: ModuleRead<H>> {
+    read: &'a R,
+    doc: &'a ScriptDoc,
+    deps: Vec<(CompactString, NodeRef)>,
+    this: bool,
+    side_effects: bool,
+    _handle: std::marker::PhantomData<H>,
+}
+
+impl<'a, H: TaskHandle, R: ModuleRead<H>> VarInliner<'a, H, R> {
+    pub(super) fn plan(read: &'a R, var_ref: &NodeRef) -> ModuleResult<InlinePlan> {
+        let doc_read = read.read_doc();
+
+        let mut inliner = VarInliner {
+            read,
+            doc: doc_read.deref(),
+            deps: Vec::new(),
+            this: false,
+            side_effects: false,
+            _handle: std::marker::PhantomData,
+        };
+
+        match inliner.make_plan(var_ref)? {
+            Ok(edits) => Ok(InlinePlan::Edits(edits)),
+            Err(refusal) => Ok(InlinePlan::Refused(refusal)),
+        }
+    }
+
+    fn make_plan(
+        &mut self,
+        var_ref: &NodeRef,
+    ) -> ModuleResult<Result<Vec<InlineEdit>, InlineRefusal>> {
+        let doc = self.doc;
+
+        let Some(ScriptNode::Var { parent, token, .. }) = var_ref.deref(doc) else {
+            return Ok(Err(InlineRefusal::NotInlinable));
+        };
+
+        let let_ref = *parent;
+
+        let Some(ScriptNode::Let { parent, value, .. }) = let_ref.deref(doc) else {
+            return Ok(Err(InlineRefusal::NotInlinable));
+        };
+
+        let scope_ref = *parent;
+
+        let (Some(name), Some(value_node), Some(let_span), Some(value_span)) = (
+            token.string(doc),
+            value.deref(doc),
+            span_of(doc, &let_ref),
+            span_of(doc, value),
+        ) else {
+            return Ok(Err(InlineRefusal::NotInlinable));
+        };
+
+        let mut uses = Vec::new();
+
+        for (ident_ref, write) in self.var_refs(name, &let_ref)? {
+            if write || self.is_mutation(&ident_ref, false)? {
+                return Ok(Err(InlineRefusal::Reassigned));
+            }
+
+            uses.push(ident_ref);
+        }
+
+        if uses.len() > USES_LIMIT {
+            return Ok(Err(InlineRefusal::TooManyUses));
+        }
+
+        // A value used more than once would be evaluated into distinct
+        // objects, so none of the uses may mutate it.
+        if uses.len() > 1 {
+            for ident_ref in &uses {
+                if self.is_mutation(ident_ref, true)? {
+                    return Ok(Err(InlineRefusal::Reassigned));
+                }
+            }
+        }
+
+        self.scan(value, false)?;
+
+        if self.side_effects {
+            return Ok(Err(InlineRefusal::SideEffects));
+        }
+
+        let has_deps = self.this || !self.deps.is_empty();
+
+        let mut window = let_span.end..let_span.end;
+
+        for ident_ref in &uses {
+            let Some(span) = span_of(doc, ident_ref) else {
+                return Ok(Err(InlineRefusal::NotInlinable));
+            };
+
+            window.end = window.end.max(span.end);
+
+            let mut node_ref = *ident_ref;
+
+            while node_ref != scope_ref {
+                let Some(node) = node_ref.deref(doc) else {
+                    break;
+                };
+
+                match node {
+                    ScriptNode::Fn { .. } if has_deps => {
+                        return Ok(Err(InlineRefusal::EvaluationOrder));
+                    }
+
+                    ScriptNode::Loop { .. } | ScriptNode::For { .. } => {
+                        if let Some(span) = span_of(doc, &node_ref) {
+                            window.end = window.end.max(span.end);
+                        }
+                    }
+
+                    _ => (),
+                }
+
+                node_ref = node.parent_ref();
+            }
+
+            if self.is_shadowed(ident_ref, &scope_ref, let_span.end, span.start) {
+                return Ok(Err(InlineRefusal::Shadowed));
+            }
+        }
+
+        for (dep_name, decl) in self.deps.clone() {
+            for (ident_ref, write) in self.var_refs(dep_name.as_str(), &decl)? {
+                if !in_window(doc, &ident_ref, &window) {
+                    continue;
+                }
+
+                if write || self.is_mutation(&ident_ref, true)? {
+                    return Ok(Err(InlineRefusal::EvaluationOrder));
+                }
+            }
+        }
+
+        if self.this {
+            let id = self.read.id();
+
+            let these = self
+                .read
+                .task()
+                .snapshot_class(id, &ScriptClass::AllThese)
+                .into_module_result(id)?;
+
+            for this_ref in these.as_ref() {
+                if !in_window(doc, this_ref, &window) {
+                    continue;
+                }
+
+                if self.is_mutation(this_ref, true)? {
+                    return Ok(Err(InlineRefusal::EvaluationOrder));
+                }
+            }
+        }
+
+        let value_text = doc.substring(value_span);
+
+        // The top-level expression wrapper without parentheses.
+        let value_node = match value_node {
+            ScriptNode::Expr { start, inner, .. } if start.is_nil() => match inner.deref(doc) {
+                Some(inner) => inner,
+                None => value_node,
+            },
+
+            _ => value_node,
+        };
+
+        let mut edits = Vec::with_capacity(uses.len() + 1);
+
+        edits.push(InlineEdit {
+            origin: let_ref.script_origin(doc, SpanBounds::Cover),
+            text: String::new(),
+        });
+
+        for ident_ref in &uses {
+            let text = match self.needs_group(value_node, ident_ref) {
+                true => format!("({value_text})"),
+                false => value_text.to_string(),
+            };
+
+            edits.push(InlineEdit {
+                origin: ident_ref.script_origin(doc, SpanBounds::Cover),
+                text,
+            });
+        }
+
+        Ok(Ok(edits))
+    }
+
+    // Returns all identifiers that refer to the variable declared in `decl`,
+    // ordered by position. The flag is true for the initializing writes.
+    fn var_refs(&self, name: &str, decl: &NodeRef) -> ModuleResult<Vec<(NodeRef, bool)>> {
+        let doc = self.doc;
+        let id = self.read.id();
+
+        let class = ScriptClass::Ident(CompactString::from(name));
+
+        let ident_refs = self
+            .read
+            .task()
+            .snapshot_class(id, &class)
+            .into_module_result(id)?;
+
+        let mut result = Vec::new();
+
+        for ident_ref in ident_refs.as_ref() {
+            let Some(ScriptNode::Ident { semantics, .. }) = ident_ref.deref(doc) else {
+                continue;
+            };
+
+            let ident_semantics = semantics.get().into_module_result(id)?;
+
+            let (_, resolution) = ident_semantics
+                .cross_resolution
+                .snapshot(self.read.task())
+                .into_module_result(id)?;
+
+            match resolution {
+                IdentCrossResolution::Read { name } if &name.as_ref().decl == decl => {
+                    result.push((*ident_ref, false))
+                }
+
+                IdentCrossResolution::Write { decl: write } if &write == decl => {
+                    result.push((*ident_ref, true))
+                }
+
+                _ => (),
+            }
+        }
+
+        result.sort_by_key(|(ident_ref, _)| span_of(doc, ident_ref).map(|span| span.start));
+
+        Ok(result)
+    }
+
+    // Collects the initializer's dependencies and detects side effects.
+    // Inside function literals, the code is not evaluated at the point of
+    // initialization, so only the dependencies are collected.
+    fn scan(&mut self, node_ref: &NodeRef, in_fn: bool) -> ModuleResult<()> {
+        let doc = self.doc;
+        let id = self.read.id();
+
+        let Some(node) = node_ref.deref(doc) else {
+            return Ok(());
+        };
+
+        match node {
+            ScriptNode::Ident {
+                token, semantics, ..
+            } => {
+                let ident_semantics = semantics.get().into_module_result(id)?;
+
+                let (_, resolution) = ident_semantics
+                    .cross_resolution
+                    .snapshot(self.read.task())
+                    .into_module_result(id)?;
+
+                match resolution {
+                    IdentCrossResolution::Read { name } => match name.as_ref().decl.deref(doc) {
+                        Some(ScriptNode::Root { .. } | ScriptNode::Use { .. }) => (),
+
+                        _ => {
+                            if let Some(string) = token.string(doc) {
+                                self.deps
+                                    .push((CompactString::from(string), name.as_ref().decl));
+                            }
+                        }
+                    },
+
+                    _ => self.side_effects = self.side_effects || !in_fn,
+                }
+
+                return Ok(());
+            }
+
+            ScriptNode::This { .. } => {
+                self.this = true;
+                return Ok(());
+            }
+
+            ScriptNode::Call { left, .. } if !in_fn => {
+                if resolve_pure_fn(self.read, doc, left)?.is_none() {
+                    self.side_effects = true;
+                }
+            }
+
+            ScriptNode::Binary { op, .. } if !in_fn => {
+                if op_precedence(doc, op) == Precedence::Assign {
+                    self.side_effects = true;
+                }
+            }
+
+            ScriptNode::Fn { .. } => {
+                for child in node.children_iter() {
+                    if child.kind().is_node() {
+                        self.scan(child.as_node_ref(), true)?;
+                    }
+                }
+
+                return Ok(());
+            }
+
+            _ => (),
+        }
+
+        for child in node.children_iter() {
+            if child.kind().is_node() {
+                self.scan(child.as_node_ref(), in_fn)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Returns true if the expression that starts with the `ident_ref` atom
+    // assigns to the atom's data or to its part. If `strict` is true, the
+    // function also considers method calls on the data and passing it to
+    // functions that are not pure as mutations.
+    fn is_mutation(&self, ident_ref: &NodeRef, strict: bool) -> ModuleResult<bool> {
+        let doc = self.doc;
+
+        let mut node_ref = *ident_ref;
+
+        loop {
+            let Some(node) = node_ref.deref(doc) else {
+                return Ok(false);
+            };
+
+            let parent_ref = node.parent_ref();
+
+            let Some(parent) = parent_ref.deref(doc) else {
+                return Ok(false);
+            };
+
+            match parent {
+                ScriptNode::Expr { .. } => (),
+
+                ScriptNode::Binary { left, op, .. } if left == &node_ref => {
+                    match op_precedence(doc, op) {
+                        Precedence::Assign => return Ok(true),
+                        Precedence::UnaryRight => (),
+                        _ => return Ok(false),
+                    }
+                }
+
+                ScriptNode::Index { left, .. } if left == &node_ref => (),
+
+                ScriptNode::Call { left, .. } if left == &node_ref => {
+                    return Ok(strict && &node_ref != ident_ref);
+                }
+
+                ScriptNode::Arg { .. } => (),
+
+                ScriptNode::CallArgs { parent, .. } if strict => {
+                    let Some(ScriptNode::Call { left, .. }) = parent.deref(doc) else {
+                        return Ok(true);
+                    };
+
+                    return Ok(resolve_pure_fn(self.read, doc, left)?.is_none());
+                }
+
+                _ => return Ok(false),
+            }
+
+            node_ref = parent_ref;
+        }
+    }
+
+    // Returns true if any dependency name is redeclared between the inlined
+    // let-statement and the `ident_ref` read in the scopes enclosing the read.
+    fn is_shadowed(
+        &self,
+        ident_ref: &NodeRef,
+        scope_ref: &NodeRef,
+        from: usize,
+        to: usize,
+    ) -> bool {
+        let doc = self.doc;
+
+        let declares = |var_ref: &NodeRef| {
+            let Some(ScriptNode::Var { token, .. }) = var_ref.deref(doc) else {
+                return false;
+            };
+
+            let Some(string) = token.string(doc) else {
+                return false;
+            };
+
+            self.deps.iter().any(|(name, _)| name.as_str() == string)
+        };
+
+        let mut node_ref = *ident_ref;
+
+        loop {
+            let Some(node) = node_ref.deref(doc) else {
+                return false;
+            };
+
+            let parent_ref = node.parent_ref();
+
+            let Some(parent) = parent_ref.deref(doc) else {
+                return false;
+            };
+
+            match parent {
+                ScriptNode::Root { statements, .. } | ScriptNode::Block { statements, .. } => {
+                    for statement in statements {
+                        let Some(ScriptNode::Let { name, .. }) = statement.deref(doc) else {
+                            continue;
+                        };
+
+                        let Some(span) = span_of(doc, statement) else {
+                            continue;
+                        };
+
+                        if span.start >= from && span.start < to && declares(name) {
+                            return true;
+                        }
+                    }
+                }
+
+                ScriptNode::For { iterator, body, .. } if body == &node_ref => {
+                    if declares(iterator) {
+                        return true;
+                    }
+                }
+
+                ScriptNode::Fn { params, body, .. } if body == &node_ref => {
+                    if let Some(ScriptNode::FnParams { params, .. }) = params.deref(doc) {
+                        if params.iter().any(declares) {
+                            return true;
+                        }
+                    }
+                }
+
+                _ => (),
+            }
+
+            if &parent_ref == scope_ref {
+                return false;
+            }
+
+            node_ref = parent_ref;
+        }
+    }
+
+    // Returns true if the inlined `value_node` must be wrapped in parentheses
+    // to preserve the operator precedence at the `ident_ref` read.
+    fn needs_group(&self, value_node: &ScriptNode, ident_ref: &NodeRef) -> bool {
+        let doc = self.doc;
+
+        let Some(ident_node) = ident_ref.deref(doc) else {
+            return false;
+        };
+
+        let Some(parent) = ident_node.parent_ref().deref(doc) else {
+            return false;
+        };
+
+        let assoc = match parent {
+            ScriptNode::Binary { left, .. } => match left == ident_ref {
+                true => Assoc::Left,
+                false => Assoc::Right,
+            },
+
+            ScriptNode::UnaryLeft { .. } => Assoc::Right,
+
+            ScriptNode::Query { .. } | ScriptNode::Call { .. } | ScriptNode::Index { .. } => {
+                Assoc::Left
+            }
+
+            _ => return false,
+        };
+
+        if let ScriptNode::Fn { .. } | ScriptNode::Struct { .. } = value_node {
+            return true;
+        }
+
+        !value_node
+            .precedence(doc)
+            .as_operand(assoc)
+            .of(parent.precedence(doc))
+    }
+}
+
+#[inline(always)]
+fn span_of(doc: &ScriptDoc, node_ref: &NodeRef) -> Option<SiteSpan> {
+    node_ref
+        .script_origin(doc, SpanBounds::Cover)
+        .to_site_span(doc)
+}
+
+#[inline(always)]
+fn in_window(doc: &ScriptDoc, node_ref: &NodeRef, window: &SiteSpan) -> bool {
+    let Some(span) = span_of(doc, node_ref) else {
+        return false;
+    };
+
+    span.start >= window.start && span.start < window.end
+}
+
+#[inline(always)]
+fn op_precedence(doc: &ScriptDoc, op: &NodeRef) -> Precedence {
+    let Some(ScriptNode::Op { token, .. }) = op.deref(doc) else {
+        return Precedence::Outer;
+    };
+
+    let Some(token) = token.deref(doc) else {
+        return Precedence::Outer;
+    };
+
+    token.bin_precedence()
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::{analysis::TriggerHandle, lexis::ToSpan};
+
+    use crate::{
+        analysis::{
+            symbols::{tests::TestPackage, LookupOptions, ModuleSymbol, SymbolKind},
+            InlinePlan,
+            InlineRefusal,
+            ModuleRead,
+            ScriptModule,
+        },
+        runtime::ScriptPackage,
+    };
+
+    fn inline(code: &str, name: &str) -> Result<String, InlineRefusal> {
+        let module = ScriptModule::new(TestPackage::meta(), code);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let symbols = read
+            .symbols(.., LookupOptions::new().filter(SymbolKind::Var as u32))
+            .unwrap();
+
+        let Some(symbol) = symbols.iter().find_map(|symbol| match symbol {
+            ModuleSymbol::Var(var) if var.var_name(&read).unwrap().as_ref() == name => Some(var),
+            _ => None,
+        }) else {
+            panic!("Missing variable.");
+        };
+
+        let edits = match read.inline_variable(symbol).unwrap() {
+            InlinePlan::Edits(edits) => edits,
+            InlinePlan::Refused(refusal) => return Err(refusal),
+        };
+
+        let text = read.text();
+        let mut result = code.chars().collect::<Vec<_>>();
+
+        for edit in edits.iter().rev() {
+            let span = edit.origin.to_site_span(&text).unwrap();
+
+            let _ = result.splice(span, edit.text.chars());
+        }
+
+        Ok(result.into_iter().collect())
+    }
+
+    #[test]
+    fn test_inline_variable() {
+        assert_eq!(
+            inline("let x = 1 + 2; let y = x * x;", "x").unwrap().trim(),
+            "let y = (1 + 2) * (1 + 2);",
+        );
+
+        assert_eq!(
+            inline("let a = 1; let x = a; let y = -x;", "x").unwrap(),
+            "let a = 1;  let y = -a;",
+        );
+
+        assert_eq!(
+            inline("let x = 2 * 3; let y = 1 + x;", "x").unwrap().trim(),
+            "let y = 1 + 2 * 3;",
+        );
+
+        assert_eq!(
+            inline("let x = 1; x = 2; let y = x;", "x"),
+            Err(InlineRefusal::Reassigned),
+        );
+
+        assert_eq!(
+            inline("let x = 1; let y = [x, x, x, x];", "x"),
+            Err(InlineRefusal::TooManyUses),
+        );
+
+        assert_eq!(
+            inline("let x = f(); let f = fn() {}; let y = x;", "x"),
+            Err(InlineRefusal::SideEffects),
+        );
+
+        assert_eq!(
+            inline("let a = 1; let x = a + 1; a = 5; let y = x;", "x"),
+            Err(InlineRefusal::EvaluationOrder),
+        );
+
+        assert_eq!(
+            inline("let a = 1; let x = a; { let a = 2; let y = x; }", "x"),
+            Err(InlineRefusal::Shadowed),
+        );
+    }
+}
@@ -33,7 +33,6 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use std::{
-    borrow::Cow,
     fmt::{Display, Formatter},
     ops::Range,
 };
@@ -41,13 +40,26 @@ use std::{
 use compact_str::CompactString;
 use lady_deirdre::{
     format::AnnotationPriority,
-    syntax::{ErrorRef, NodeRef, NodeRule, RecoveryResult},
+    lexis::{SourceCode, TokenCursor, TokenRef},
+    syntax::{ErrorRef, NodeRef, NodeRule, PolyRef, RecoveryResult},
 };
 
 use crate::{
     analysis::DiagnosticsDepth,
-    runtime::{ops::OperatorKind, ScriptOrigin, ScriptType, TypeFamily, TypeHint, TypeMeta},
-    syntax::{PolyRefOrigin, ScriptDoc, ScriptNode, SpanBounds},
+    format::{localize, MessageKey},
+    runtime::{
+        ops::OperatorKind,
+        RustIdent,
+        RustOrigin,
+        ScriptOrigin,
+        ScriptType,
+        StructSchema,
+        TypeFamily,
+        TypeHint,
+        TypeMeta,
+    },
+    syntax::{PolyRefOrigin, ScriptDoc, ScriptNode, ScriptToken, SpanBounds},
+    META_VERSION,
 };
 
 /// A classification of module diagnostic issues.
@@ -57,7 +69,10 @@ use crate::{
 ///
 /// From an IssueCode, you can obtain additional metadata about the diagnostic
 /// issue, such as a short description (via the Display implementation of
-/// IssueCode) or the severity of the issue.
+/// IssueCode) or the severity of the issue. The description is a
+/// human-readable text that the host can translate using the
+/// [MessageLocalizer](crate::format::MessageLocalizer), whereas the IssueCode
+/// itself and its numeric representation do not depend on the localization.
 ///
 /// Additionally, you can convert an IssueCode into a numeric representation,
 /// which can be used to categorize diagnostics by their numeric codes in a
@@ -135,6 +150,73 @@ pub enum IssueCode {
     /// An attempt to assign to an orphaned literal. This assignment is
     /// semantically meaningless.
     LiteralAssignment = 212,
+    /// Semantics Error.
+    ///
+    /// The host's validator of the embedded data block reported an error in
+    /// the block's content.
+    EmbeddedError = 213,
+    /// Semantics Warning.
+    ///
+    /// The host's validator of the embedded data block reported a warning in
+    /// the block's content.
+    EmbeddedWarning = 214,
+    /// Semantics Warning.
+    ///
+    /// A diagnostic reported by the host's [lint pass](crate::analysis::LintPass)
+    /// running at the second diagnostics depth. The actual severity and the
+    /// host-defined code of the issue are chosen by the lint pass (see
+    /// [ModuleIssue::lint_code](crate::analysis::ModuleIssue::lint_code)).
+    Lint = 215,
+    /// Semantics Warning.
+    ///
+    /// A line of the module's [metadata header](crate::analysis::ModuleMetadata)
+    /// starts with a directive keyword, but the directive is malformed.
+    InvalidMetadata = 216,
+    /// Semantics Error.
+    ///
+    /// The package declared by the `//! requires package` directive of the
+    /// module's [metadata header](crate::analysis::ModuleMetadata) is neither
+    /// the package under which the module is analyzed nor its dependency, or
+    /// the package's version does not satisfy the declared requirement.
+    MissingPackage = 217,
+    /// Semantics Error.
+    ///
+    /// The engine version requirement declared by the `//! adastra` directive
+    /// of the module's [metadata header](crate::analysis::ModuleMetadata) is
+    /// not satisfied by the [META_VERSION](crate::META_VERSION).
+    IncompatibleEngine = 218,
+    /// Semantics Error.
+    ///
+    /// The named argument of the function call (`foo(<name>: arg)`) does not
+    /// correspond to any parameter of the invoked function. Named arguments
+    /// are supported by the exported Rust functions only.
+    UnknownArgument = 219,
+    /// Semantics Error.
+    ///
+    /// The function's parameter is provided by more than one argument of the
+    /// function call.
+    DuplicateArgument = 220,
+    /// Semantics Error.
+    ///
+    /// The positional argument of the function call follows a named argument.
+    /// Positional arguments must precede the named arguments.
+    MisplacedArgument = 221,
+    /// Semantics Hint.
+    ///
+    /// The expression mixes bitwise operators with arithmetic or comparison
+    /// operators without parentheses (e.g., `a + b << c`). The order of
+    /// evaluation follows the operator precedence rules, which might differ
+    /// from the intention. Consider adding the parentheses.
+    AmbiguousPrecedence = 222,
+    /// Semantics Warning.
+    ///
+    /// The local variable or the function parameter has the same name as
+    /// a member of the script package or a built-in function (e.g.,
+    /// `let dbg = 5;`). The package member is inaccessible by this name below
+    /// the declaration. The warning can be turned off with the
+    /// [shadowing_warnings](crate::analysis::AnalysisConfig::shadowing_warnings)
+    /// option.
+    ShadowedMember = 223,
 
     /// Semantics Warning.
     ///
@@ -180,45 +262,152 @@ pub enum IssueCode {
     /// non-nil values, while others return nil values. This issue likely
     /// indicates that a trailing `return <expr>;` statement is missing.
     InconsistentReturns = 310,
+    /// Semantics Hint.
+    ///
+    /// The function assigned to this variable is never called or referenced
+    /// outside of its own body. Consider removing the variable.
+    UnusedFunction = 311,
+    /// Semantics Hint.
+    ///
+    /// The struct entry is never read by the module's code, even though other
+    /// entries of this struct are accessed.
+    UnreadEntry = 312,
+    /// Semantics Hint.
+    ///
+    /// None of the names imported by the `use` statement are used within the
+    /// module, or the last component of the import path is redundant.
+    UnusedImport = 313,
+    /// Semantics Warning.
+    ///
+    /// The assignment target is accessed through a component that creates a
+    /// new value on each access (e.g., a method of the object). The assignment
+    /// mutates a temporary value, and the change does not persist.
+    TemporaryAssignment = 314,
+    /// Semantics Warning.
+    ///
+    /// The struct passed to the function has an entry that does not
+    /// correspond to any field of the Rust struct expected by the function.
+    UnknownStructField = 315,
+    /// Semantics Warning.
+    ///
+    /// The struct passed to the function does not have an entry for the
+    /// required field of the Rust struct expected by the function.
+    MissingStructField = 316,
+    /// Semantics Warning.
+    ///
+    /// The constant index is out of the bounds of the array or string literal.
+    IndexOutOfBounds = 317,
+    /// Semantics Hint.
+    ///
+    /// The construct exceeds one of the semantic analysis limits (see
+    /// [AnalysisConfig](crate::analysis::AnalysisConfig)). The analyzer does
+    /// not infer the construct's type and treats it as dynamically typed.
+    AnalysisLimit = 318,
+    /// Semantics Warning.
+    ///
+    /// The script function captures a variable that is initialized after the
+    /// function's creation (e.g., `let x; let f = fn() x; x = 10;`). The
+    /// function keeps the variable's value from the moment of its creation
+    /// and does not observe the later initialization.
+    LateCapture = 319,
+    /// Semantics Warning.
+    ///
+    /// A diagnostic reported by the host's [lint pass](crate::analysis::LintPass)
+    /// running at the third diagnostics depth. The actual severity and the
+    /// host-defined code of the issue are chosen by the lint pass (see
+    /// [ModuleIssue::lint_code](crate::analysis::ModuleIssue::lint_code)).
+    DeepLint = 320,
+    /// Semantics Warning.
+    ///
+    /// The constant argument of the function call violates the function's
+    /// precondition declared by the `#[export(requires <condition>)]`
+    /// attribute (see [Contract](crate::runtime::Contract)).
+    ContractViolation = 321,
+    /// Semantics Warning.
+    ///
+    /// The assignment target is a property of the object that has a getter
+    /// function (`#[export(getter)]`) but no setter function
+    /// (`#[export(setter)]`). Such an assignment fails at runtime.
+    ReadOnlyProperty = 322,
+    /// Semantics Warning.
+    ///
+    /// The code refers to a package member, a field, or a method that the
+    /// Rust author marked as deprecated (`#[export(deprecated "<message>")]`).
+    /// The member still works, but it may be removed in future versions of
+    /// the API. The issue message explains the recommended replacement.
+    DeprecatedMember = 323,
+    /// Semantics Warning.
+    ///
+    /// The match statement's subject is a variant of a script enum
+    /// (`enum Color { Red, Green, Blue }`), but the match arms do not cover
+    /// all variants of this enum, and there is no `else` arm.
+    NonExhaustiveMatch = 324,
 }
 
 impl Display for IssueCode {
+    #[inline(always)]
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
-        let message = match self {
-            Self::Parse => "Parse error.",
-
-            Self::UnresolvedPackage => "Unresolved import.",
-            Self::NotAPackage => "Importing a component that is not a package.",
-            Self::OrphanedBreak => "Break outside of a loop.",
-            Self::DuplicateParam => "Duplicate function parameter name.",
-            Self::ReadUninit => "Use of possibly uninitialized variable.",
-            Self::UnresolvedIdent => "Unresolved reference.",
-            Self::IntParse => "Invalid integer literal.",
-            Self::FloatParse => "Invalid float literal.",
-            Self::UnreachableStatement => "Unreachable statement.",
-            Self::UnreachableArm => "Unreachable match arm.",
-            Self::DuplicateEntry => "Duplicate struct entry.",
-            Self::LiteralAssignment => "Assignment to literal is meaningless.",
-
-            Self::TypeMismatch => "Type mismatch.",
-            Self::NilIndex => "Index operator is not applicable to nil type.",
-            Self::IndexTypeMismatch => "Index type must be an integer or an integer range.",
-            Self::UndefinedOperator => "Unresolved operator.",
-            Self::UndefinedDisplay => "Type does not implement the Display operator.",
-            Self::CallArityMismatch => "Call arity mismatch.",
-            Self::FnArityMismatch => "Function arity mismatch.",
-            Self::ResultMismatch => "Function result type mismatch.",
-            Self::UnknownComponent => "Unknown field.",
-            Self::InconsistentReturns => "Missing trailing return statement.",
-        };
-
-        formatter.write_str(message)
+        formatter.write_str(&self.title_key().render(&[]))
     }
 }
 
 impl IssueCode {
-    /// Returns the issue's [severity](IssueSeverity), which is either an error
-    /// or a warning.
+    // The catalog key of the issue's human-readable title.
+    fn title_key(self) -> MessageKey {
+        match self {
+            Self::Parse => MessageKey::IssueParse,
+            Self::UnresolvedPackage => MessageKey::IssueUnresolvedPackage,
+            Self::NotAPackage => MessageKey::IssueNotAPackage,
+            Self::OrphanedBreak => MessageKey::IssueOrphanedBreak,
+            Self::DuplicateParam => MessageKey::IssueDuplicateParam,
+            Self::ReadUninit => MessageKey::IssueReadUninit,
+            Self::UnresolvedIdent => MessageKey::IssueUnresolvedIdent,
+            Self::IntParse => MessageKey::IssueIntParse,
+            Self::FloatParse => MessageKey::IssueFloatParse,
+            Self::UnreachableStatement => MessageKey::IssueUnreachableStatement,
+            Self::UnreachableArm => MessageKey::IssueUnreachableArm,
+            Self::DuplicateEntry => MessageKey::IssueDuplicateEntry,
+            Self::LiteralAssignment => MessageKey::IssueLiteralAssignment,
+            Self::EmbeddedError => MessageKey::IssueEmbeddedError,
+            Self::EmbeddedWarning => MessageKey::IssueEmbeddedWarning,
+            Self::Lint => MessageKey::IssueLint,
+            Self::InvalidMetadata => MessageKey::IssueInvalidMetadata,
+            Self::MissingPackage => MessageKey::IssueMissingPackage,
+            Self::IncompatibleEngine => MessageKey::IssueIncompatibleEngine,
+            Self::UnknownArgument => MessageKey::IssueUnknownArgument,
+            Self::DuplicateArgument => MessageKey::IssueDuplicateArgument,
+            Self::MisplacedArgument => MessageKey::IssueMisplacedArgument,
+            Self::AmbiguousPrecedence => MessageKey::IssueAmbiguousPrecedence,
+            Self::ShadowedMember => MessageKey::IssueShadowedMember,
+            Self::TypeMismatch => MessageKey::IssueTypeMismatch,
+            Self::NilIndex => MessageKey::IssueNilIndex,
+            Self::IndexTypeMismatch => MessageKey::IssueIndexTypeMismatch,
+            Self::UndefinedOperator => MessageKey::IssueUndefinedOperator,
+            Self::UndefinedDisplay => MessageKey::IssueUndefinedDisplay,
+            Self::CallArityMismatch => MessageKey::IssueCallArityMismatch,
+            Self::FnArityMismatch => MessageKey::IssueFnArityMismatch,
+            Self::ResultMismatch => MessageKey::IssueResultMismatch,
+            Self::UnknownComponent => MessageKey::IssueUnknownComponent,
+            Self::InconsistentReturns => MessageKey::IssueInconsistentReturns,
+            Self::UnusedFunction => MessageKey::IssueUnusedFunction,
+            Self::UnreadEntry => MessageKey::IssueUnreadEntry,
+            Self::UnusedImport => MessageKey::IssueUnusedImport,
+            Self::TemporaryAssignment => MessageKey::IssueTemporaryAssignment,
+            Self::UnknownStructField => MessageKey::IssueUnknownStructField,
+            Self::MissingStructField => MessageKey::IssueMissingStructField,
+            Self::IndexOutOfBounds => MessageKey::IssueIndexOutOfBounds,
+            Self::AnalysisLimit => MessageKey::IssueAnalysisLimit,
+            Self::LateCapture => MessageKey::IssueLateCapture,
+            Self::DeepLint => MessageKey::IssueDeepLint,
+            Self::ContractViolation => MessageKey::IssueContractViolation,
+            Self::ReadOnlyProperty => MessageKey::IssueReadOnlyProperty,
+            Self::DeprecatedMember => MessageKey::IssueDeprecatedMember,
+            Self::NonExhaustiveMatch => MessageKey::IssueNonExhaustiveMatch,
+        }
+    }
+
+    /// Returns the issue's [severity](IssueSeverity), which is either an error,
+    /// a warning, or a hint.
     #[inline(always)]
     pub fn severity(self) -> IssueSeverity {
         match self {
@@ -236,6 +425,17 @@ impl IssueCode {
             Self::UnreachableArm => IssueSeverity::Warning,
             Self::DuplicateEntry => IssueSeverity::Warning,
             Self::LiteralAssignment => IssueSeverity::Warning,
+            Self::EmbeddedError => IssueSeverity::Error,
+            Self::EmbeddedWarning => IssueSeverity::Warning,
+            Self::Lint => IssueSeverity::Warning,
+            Self::InvalidMetadata => IssueSeverity::Warning,
+            Self::MissingPackage => IssueSeverity::Error,
+            Self::IncompatibleEngine => IssueSeverity::Error,
+            Self::UnknownArgument => IssueSeverity::Error,
+            Self::DuplicateArgument => IssueSeverity::Error,
+            Self::MisplacedArgument => IssueSeverity::Error,
+            Self::AmbiguousPrecedence => IssueSeverity::Hint,
+            Self::ShadowedMember => IssueSeverity::Warning,
 
             Self::TypeMismatch => IssueSeverity::Warning,
             Self::NilIndex => IssueSeverity::Warning,
@@ -247,6 +447,20 @@ impl IssueCode {
             Self::ResultMismatch => IssueSeverity::Warning,
             Self::UnknownComponent => IssueSeverity::Warning,
             Self::InconsistentReturns => IssueSeverity::Warning,
+            Self::UnusedFunction => IssueSeverity::Hint,
+            Self::UnreadEntry => IssueSeverity::Hint,
+            Self::UnusedImport => IssueSeverity::Hint,
+            Self::TemporaryAssignment => IssueSeverity::Warning,
+            Self::UnknownStructField => IssueSeverity::Warning,
+            Self::MissingStructField => IssueSeverity::Warning,
+            Self::IndexOutOfBounds => IssueSeverity::Warning,
+            Self::AnalysisLimit => IssueSeverity::Hint,
+            Self::LateCapture => IssueSeverity::Warning,
+            Self::DeepLint => IssueSeverity::Warning,
+            Self::ContractViolation => IssueSeverity::Warning,
+            Self::ReadOnlyProperty => IssueSeverity::Warning,
+            Self::DeprecatedMember => IssueSeverity::Warning,
+            Self::NonExhaustiveMatch => IssueSeverity::Warning,
         }
     }
 
@@ -271,6 +485,10 @@ pub enum IssueSeverity {
     /// However, the analyzer is not confident enough to classify them as hard
     /// errors due to the dynamic nature of the script execution model.
     Warning = 1 << 1,
+
+    /// Suggestions that do not indicate runtime problems, such as dead code
+    /// that can be safely removed from the source code.
+    Hint = 1 << 2,
 }
 
 impl Display for IssueSeverity {
@@ -279,16 +497,28 @@ impl Display for IssueSeverity {
         match self {
             IssueSeverity::Error => formatter.write_str("error"),
             IssueSeverity::Warning => formatter.write_str("warning"),
+            IssueSeverity::Hint => formatter.write_str("hint"),
         }
     }
 }
 
 impl IssueSeverity {
+    // The human-readable name of the severity in the snippet captions.
+    #[inline(always)]
+    pub(crate) fn title(self) -> String {
+        match self {
+            Self::Error => localize!(SeverityError),
+            Self::Warning => localize!(SeverityWarning),
+            Self::Hint => localize!(SeverityHint),
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn priority(self) -> AnnotationPriority {
         match self {
             Self::Error => AnnotationPriority::Primary,
             Self::Warning => AnnotationPriority::Note,
+            Self::Hint => AnnotationPriority::Default,
         }
     }
 }
@@ -320,6 +550,7 @@ pub(crate) enum ScriptIssue {
 
     ReadUninit {
         ident_ref: NodeRef,
+        decl_ref: NodeRef,
     },
 
     UnresolvedIdent {
@@ -352,6 +583,13 @@ pub(crate) enum ScriptIssue {
         op_ref: NodeRef,
     },
 
+    Embedded {
+        embedded_ref: NodeRef,
+        span: Range<usize>,
+        severity: IssueSeverity,
+        message: CompactString,
+    },
+
     TypeMismatch {
         expr_ref: NodeRef,
         expected: &'static TypeFamily,
@@ -400,6 +638,135 @@ pub(crate) enum ScriptIssue {
     InconsistentReturns {
         fn_ref: NodeRef,
     },
+
+    UnusedFunction {
+        var_ref: NodeRef,
+    },
+
+    UnreadEntry {
+        entry_key_ref: NodeRef,
+    },
+
+    UnusedImport {
+        use_ref: NodeRef,
+        package_ref: NodeRef,
+    },
+
+    TemporaryAssignment {
+        field_ref: NodeRef,
+        receiver: &'static TypeMeta,
+        component: &'static RustIdent,
+    },
+
+    ReadOnlyProperty {
+        field_ref: NodeRef,
+        receiver: &'static TypeMeta,
+        component: &'static RustIdent,
+    },
+
+    UnknownStructField {
+        entry_key_ref: NodeRef,
+        schema: &'static StructSchema,
+    },
+
+    MissingStructField {
+        arg_ref: NodeRef,
+        schema: &'static StructSchema,
+        field: &'static str,
+    },
+
+    IndexOutOfBounds {
+        arg_ref: NodeRef,
+        index: isize,
+        length: usize,
+    },
+
+    ContractViolation {
+        arg_ref: NodeRef,
+        argument: usize,
+        condition: &'static str,
+    },
+
+    AnalysisLimit {
+        node_ref: NodeRef,
+        limit: AnalysisLimit,
+    },
+
+    LateCapture {
+        fn_ref: NodeRef,
+        var_ref: NodeRef,
+        init_ref: NodeRef,
+    },
+
+    Lint {
+        origin: ScriptOrigin,
+        depth: DiagnosticsDepth,
+        severity: IssueSeverity,
+        code: CompactString,
+        message: CompactString,
+    },
+
+    InvalidMetadata {
+        origin: ScriptOrigin,
+        message: CompactString,
+    },
+
+    MissingPackage {
+        origin: ScriptOrigin,
+        name: CompactString,
+        version: Option<CompactString>,
+        found: Option<&'static str>,
+    },
+
+    IncompatibleEngine {
+        origin: ScriptOrigin,
+        version: CompactString,
+    },
+
+    UnknownArgument {
+        name_ref: TokenRef,
+        quickfix: CompactString,
+    },
+
+    DuplicateArgument {
+        arg_ref: NodeRef,
+        name_ref: TokenRef,
+        quickfix: CompactString,
+    },
+
+    MisplacedArgument {
+        arg_ref: NodeRef,
+    },
+
+    AmbiguousPrecedence {
+        operand_ref: NodeRef,
+        quickfix: CompactString,
+    },
+
+    ShadowedMember {
+        var_ref: NodeRef,
+        component: &'static RustIdent,
+    },
+
+    DeprecatedMember {
+        name_ref: NodeRef,
+        component: &'static RustIdent,
+        message: &'static str,
+    },
+
+    NonExhaustiveMatch {
+        subject_ref: NodeRef,
+        missing: CompactString,
+    },
+}
+
+// A semantic analysis limit reached by the analyzer, together with the limit's
+// configured value.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum AnalysisLimit {
+    ExprDepth(usize),
+    ResolutionSteps(usize),
+    FanOut(usize),
 }
 
 impl ScriptIssue {
@@ -418,6 +785,10 @@ impl ScriptIssue {
             Self::UnreachableArm { .. } => IssueCode::UnreachableArm,
             Self::DuplicateEntry { .. } => IssueCode::DuplicateEntry,
             Self::LiteralAssignment { .. } => IssueCode::LiteralAssignment,
+            Self::Embedded { severity, .. } => match severity {
+                IssueSeverity::Error => IssueCode::EmbeddedError,
+                _ => IssueCode::EmbeddedWarning,
+            },
             Self::TypeMismatch { .. } => IssueCode::TypeMismatch,
             Self::NilIndex { .. } => IssueCode::NilIndex,
             Self::IndexTypeMismatch { .. } => IssueCode::IndexTypeMismatch,
@@ -427,6 +798,46 @@ impl ScriptIssue {
             Self::ResultMismatch { .. } => IssueCode::ResultMismatch,
             Self::UnknownComponent { .. } => IssueCode::UnknownComponent,
             Self::InconsistentReturns { .. } => IssueCode::InconsistentReturns,
+            Self::UnusedFunction { .. } => IssueCode::UnusedFunction,
+            Self::UnreadEntry { .. } => IssueCode::UnreadEntry,
+            Self::UnusedImport { .. } => IssueCode::UnusedImport,
+            Self::TemporaryAssignment { .. } => IssueCode::TemporaryAssignment,
+            Self::UnknownStructField { .. } => IssueCode::UnknownStructField,
+            Self::MissingStructField { .. } => IssueCode::MissingStructField,
+            Self::IndexOutOfBounds { .. } => IssueCode::IndexOutOfBounds,
+            Self::ContractViolation { .. } => IssueCode::ContractViolation,
+            Self::ReadOnlyProperty { .. } => IssueCode::ReadOnlyProperty,
+            Self::AnalysisLimit { .. } => IssueCode::AnalysisLimit,
+            Self::LateCapture { .. } => IssueCode::LateCapture,
+            Self::Lint { depth, .. } => match *depth <= 2 {
+                true => IssueCode::Lint,
+                false => IssueCode::DeepLint,
+            },
+            Self::InvalidMetadata { .. } => IssueCode::InvalidMetadata,
+            Self::MissingPackage { .. } => IssueCode::MissingPackage,
+            Self::IncompatibleEngine { .. } => IssueCode::IncompatibleEngine,
+            Self::UnknownArgument { .. } => IssueCode::UnknownArgument,
+            Self::DuplicateArgument { .. } => IssueCode::DuplicateArgument,
+            Self::MisplacedArgument { .. } => IssueCode::MisplacedArgument,
+            Self::AmbiguousPrecedence { .. } => IssueCode::AmbiguousPrecedence,
+            Self::ShadowedMember { .. } => IssueCode::ShadowedMember,
+            Self::DeprecatedMember { .. } => IssueCode::DeprecatedMember,
+            Self::NonExhaustiveMatch { .. } => IssueCode::NonExhaustiveMatch,
+        }
+    }
+
+    // Lint issues carry their own severity chosen by the host's lint pass.
+    pub(crate) fn severity(&self) -> IssueSeverity {
+        match self {
+            Self::Lint { severity, .. } => *severity,
+            _ => self.code().severity(),
+        }
+    }
+
+    pub(crate) fn lint_code(&self) -> Option<&str> {
+        match self {
+            Self::Lint { code, .. } => Some(code.as_str()),
+            _ => None,
         }
     }
 
@@ -473,6 +884,10 @@ impl ScriptIssue {
 
             Self::LiteralAssignment { op_ref, .. } => op_ref.script_origin(doc, SpanBounds::Cover),
 
+            Self::Embedded {
+                embedded_ref, span, ..
+            } => Self::span_embedded(doc, embedded_ref, span),
+
             Self::TypeMismatch { expr_ref, .. } => expr_ref.script_origin(doc, SpanBounds::Cover),
 
             Self::NilIndex { op_ref, .. } => op_ref.script_origin(doc, SpanBounds::Cover),
@@ -498,119 +913,188 @@ impl ScriptIssue {
             Self::InconsistentReturns { fn_ref, .. } => {
                 fn_ref.script_origin(doc, SpanBounds::Header)
             }
+
+            Self::UnusedFunction { var_ref, .. } => match var_ref.deref(doc) {
+                Some(ScriptNode::Var { parent, .. }) => {
+                    parent.script_origin(doc, SpanBounds::Cover)
+                }
+                _ => var_ref.script_origin(doc, SpanBounds::Cover),
+            },
+
+            Self::UnreadEntry { entry_key_ref, .. } => Self::span_entry(doc, entry_key_ref),
+
+            Self::UnusedImport {
+                use_ref,
+                package_ref,
+            } => match package_ref.is_nil() {
+                true => use_ref.script_origin(doc, SpanBounds::Cover),
+                false => Self::span_package(doc, use_ref, package_ref),
+            },
+
+            Self::TemporaryAssignment { field_ref, .. }
+            | Self::ReadOnlyProperty { field_ref, .. } => {
+                field_ref.script_origin(doc, SpanBounds::Cover)
+            }
+
+            Self::UnknownStructField { entry_key_ref, .. } => {
+                entry_key_ref.script_origin(doc, SpanBounds::Cover)
+            }
+
+            Self::MissingStructField { arg_ref, .. } => {
+                arg_ref.script_origin(doc, SpanBounds::Header)
+            }
+
+            Self::IndexOutOfBounds { arg_ref, .. } => arg_ref.script_origin(doc, SpanBounds::Cover),
+
+            Self::ContractViolation { arg_ref, .. } => {
+                arg_ref.script_origin(doc, SpanBounds::Cover)
+            }
+
+            Self::AnalysisLimit { node_ref, .. } => node_ref.script_origin(doc, SpanBounds::Cover),
+
+            Self::LateCapture { fn_ref, .. } => fn_ref.script_origin(doc, SpanBounds::Header),
+
+            Self::Lint { origin, .. } => *origin,
+            Self::InvalidMetadata { origin, .. } => *origin,
+            Self::MissingPackage { origin, .. } => *origin,
+            Self::IncompatibleEngine { origin, .. } => *origin,
+
+            Self::UnknownArgument { name_ref, .. } => {
+                name_ref.script_origin(doc, SpanBounds::Cover)
+            }
+
+            Self::DuplicateArgument {
+                arg_ref, name_ref, ..
+            } => match name_ref.is_nil() {
+                true => arg_ref.script_origin(doc, SpanBounds::Cover),
+                false => name_ref.script_origin(doc, SpanBounds::Cover),
+            },
+
+            Self::MisplacedArgument { arg_ref } => arg_ref.script_origin(doc, SpanBounds::Cover),
+            Self::AmbiguousPrecedence { operand_ref, .. } => {
+                operand_ref.script_origin(doc, SpanBounds::Cover)
+            }
+            Self::ShadowedMember { var_ref, .. } => var_ref.script_origin(doc, SpanBounds::Cover),
+
+            Self::DeprecatedMember { name_ref, .. } => {
+                name_ref.script_origin(doc, SpanBounds::Cover)
+            }
+
+            Self::NonExhaustiveMatch { subject_ref, .. } => {
+                subject_ref.script_origin(doc, SpanBounds::Cover)
+            }
         }
     }
 
-    pub(crate) fn message(&self, doc: &ScriptDoc) -> Cow<'static, str> {
+    pub(crate) fn message(&self, doc: &ScriptDoc) -> String {
         match self {
             Self::Parse { error_ref, .. } => Self::message_parse(doc, error_ref),
 
             Self::UnresolvedPackage { base, quickfix, .. } => {
-                match (base.is_nil(), quickfix.is_empty()) {
-                    (true, true) => Cow::from("unresolved import"),
-                    (false, true) => Cow::from(format!("unresolved import from {base}")),
-                    (true, false) => {
-                        Cow::from(format!("unresolved import. did you mean {quickfix:?}?"))
-                    }
-                    (false, false) => Cow::from(format!(
-                        "unresolved import from {base}. did you mean {quickfix:?}?"
-                    )),
+                let empty = quickfix.is_empty();
+                let quickfix = format!("{quickfix:?}");
+
+                match (base.is_nil(), empty) {
+                    (true, true) => localize!(AnalysisUnresolvedImport),
+                    (false, true) => localize!(AnalysisUnresolvedImportFrom, base = base),
+                    (true, false) => localize!(AnalysisUnresolvedImportHint, quickfix = quickfix),
+                    (false, false) => localize!(
+                        AnalysisUnresolvedImportFromHint,
+                        base = base,
+                        quickfix = quickfix,
+                    ),
                 }
             }
 
-            Self::NotAPackage { ty, .. } => Cow::from(format!("type '{ty}' is not a package")),
+            Self::NotAPackage { ty, .. } => localize!(AnalysisNotAPackage, ty = ty),
 
             Self::OrphanedBreak { break_ref, .. } => match break_ref.deref(doc) {
-                Some(ScriptNode::Continue { .. }) => {
-                    Cow::from("continue statement outside of a loop")
-                }
-                _ => Cow::from("break statement outside of a loop"),
+                Some(ScriptNode::Continue { .. }) => localize!(AnalysisOrphanedContinue),
+                _ => localize!(AnalysisOrphanedBreak),
             },
 
-            Self::DuplicateParam { .. } => Cow::from("duplicate fn parameter name"),
+            Self::DuplicateParam { .. } => localize!(AnalysisDuplicateParam),
 
-            Self::ReadUninit { .. } => Cow::from("use of possibly uninitialized variable"),
+            Self::ReadUninit { .. } => localize!(AnalysisReadUninit),
 
             Self::UnresolvedIdent {
                 quickfix, import, ..
             } => match (quickfix.is_empty(), import.is_empty()) {
-                (true, true) => Cow::from("unresolved reference"),
+                (true, true) => localize!(AnalysisUnresolvedIdent),
 
-                (false, true) => Cow::from(format!(
-                    "unresolved reference. did you mean \"{quickfix}\"?"
-                )),
+                (false, true) => localize!(AnalysisUnresolvedIdentHint, quickfix = quickfix),
 
-                _ => Cow::from(format!(
-                    "unresolved reference. did you mean \"{import}.{quickfix}\"?"
-                )),
+                _ => localize!(
+                    AnalysisUnresolvedIdentImportHint,
+                    import = import,
+                    quickfix = quickfix,
+                ),
             },
 
-            Self::IntParse { .. } => Cow::from("invalid integer literal"),
+            Self::IntParse { .. } => localize!(AnalysisIntParse),
+
+            Self::FloatParse { .. } => localize!(AnalysisFloatParse),
 
-            Self::FloatParse { .. } => Cow::from("invalid float literal"),
+            Self::UnreachableStatement { .. } => localize!(AnalysisUnreachableStatement),
 
-            Self::UnreachableStatement { .. } => Cow::from("unreachable statement"),
+            Self::UnreachableArm { .. } => localize!(AnalysisUnreachableArm),
 
-            Self::UnreachableArm { .. } => Cow::from("unreachable match arm"),
+            Self::DuplicateEntry { .. } => localize!(AnalysisDuplicateEntry),
 
-            Self::DuplicateEntry { .. } => Cow::from("duplicate struct entry"),
+            Self::LiteralAssignment { .. } => localize!(AnalysisLiteralAssignment),
 
-            Self::LiteralAssignment { .. } => Cow::from("assignment to literal is meaningless"),
+            Self::Embedded { message, .. } => String::from(message.as_str()),
 
             Self::TypeMismatch {
                 expected, provided, ..
-            } => {
-                let expected = TypeHint::from(*expected);
-                let provided = TypeHint::from(*provided);
-
-                Cow::from(format!(
-                    "expected '{expected}' type, but '{provided}' found"
-                ))
-            }
-
-            Self::NilIndex { .. } => Cow::from("nil type cannot be indexed"),
-
-            Self::IndexTypeMismatch { provided, .. } => {
-                let numeric_family = <usize>::type_meta().family();
-                let range_family = <Range<usize>>::type_meta().family();
-
-                Cow::from(format!(
-                    "expected '{numeric_family}' or '{range_family}' type, but '{provided}' found",
-                ))
-            }
-
-            Self::UndefinedOperator { receiver, op, .. } => {
-                let receiver = TypeHint::from(*receiver);
-
-                Cow::from(format!("'{receiver}' does not implement {op}"))
-            }
+            } => localize!(
+                AnalysisTypeMismatch,
+                expected = TypeHint::from(*expected),
+                provided = TypeHint::from(*provided),
+            ),
+
+            Self::NilIndex { .. } => localize!(AnalysisNilIndex),
+
+            Self::IndexTypeMismatch { provided, .. } => localize!(
+                AnalysisIndexTypeMismatch,
+                numeric = <usize>::type_meta().family(),
+                range = <Range<usize>>::type_meta().family(),
+                provided = provided,
+            ),
+
+            Self::UndefinedOperator { receiver, op, .. } => localize!(
+                AnalysisUndefinedOperator,
+                receiver = TypeHint::from(*receiver),
+                op = op,
+            ),
 
             Self::CallArityMismatch {
                 expected, provided, ..
             } => match *expected {
-                1 => Cow::from(format!("expected 1 argument, but {provided} provided",)),
+                1 => localize!(AnalysisCallArityMismatchOne, provided = provided),
 
-                _ => Cow::from(format!(
-                    "expected {expected} arguments, but {provided} provided",
-                )),
+                _ => localize!(
+                    AnalysisCallArityMismatch,
+                    expected = expected,
+                    provided = provided,
+                ),
             },
 
             Self::FnArityMismatch {
                 expected, provided, ..
-            } => Cow::from(format!(
-                "expected fn({expected}) function, but fn({provided}) provided",
-            )),
+            } => localize!(
+                AnalysisFnArityMismatch,
+                expected = expected,
+                provided = provided,
+            ),
 
             Self::ResultMismatch {
                 expected, provided, ..
-            } => {
-                let expected = TypeHint::from(*expected);
-                let provided = TypeHint::from(*provided);
-
-                Cow::from(format!(
-                    "expected a function with '{expected}' return type, but the function returns '{provided}'"
-                ))
-            }
+            } => localize!(
+                AnalysisResultMismatch,
+                expected = TypeHint::from(*expected),
+                provided = TypeHint::from(*provided),
+            ),
 
             Self::UnknownComponent {
                 receiver, quickfix, ..
@@ -618,39 +1102,472 @@ impl ScriptIssue {
                 let receiver = TypeHint::from(*receiver);
 
                 match quickfix.is_empty() {
-                    true => Cow::from(format!("unknown '{receiver}' field",)),
+                    true => localize!(AnalysisUnknownComponent, receiver = receiver),
+
+                    false => localize!(
+                        AnalysisUnknownComponentHint,
+                        receiver = receiver,
+                        quickfix = format!("{quickfix:?}"),
+                    ),
+                }
+            }
+
+            Self::InconsistentReturns { .. } => localize!(AnalysisInconsistentReturns),
+
+            Self::UnusedFunction { var_ref, .. } => {
+                match ScriptNode::extract_atom_string(doc, var_ref) {
+                    Some(name) => localize!(AnalysisUnusedFunctionNamed, name = name),
+                    None => localize!(AnalysisUnusedFunction),
+                }
+            }
+
+            Self::UnreadEntry { entry_key_ref, .. } => {
+                match ScriptNode::extract_atom_string(doc, entry_key_ref) {
+                    Some(name) => localize!(AnalysisUnreadEntryNamed, name = name),
+                    None => localize!(AnalysisUnreadEntry),
+                }
+            }
+
+            Self::UnusedImport { package_ref, .. } => match package_ref.is_nil() {
+                true => localize!(AnalysisUnusedImport),
+
+                false => match ScriptNode::extract_atom_string(doc, package_ref) {
+                    Some(name) => localize!(AnalysisRedundantImportNamed, name = name),
+                    None => localize!(AnalysisRedundantImport),
+                },
+            },
 
-                    false => Cow::from(format!(
-                        "unknown '{receiver}' field. did you mean {quickfix:?}?",
-                    )),
+            Self::TemporaryAssignment {
+                receiver,
+                component,
+                ..
+            } => localize!(
+                AnalysisTemporaryAssignment,
+                receiver = TypeHint::from(*receiver),
+                component = component,
+            ),
+
+            Self::ReadOnlyProperty {
+                receiver,
+                component,
+                ..
+            } => localize!(
+                AnalysisReadOnlyProperty,
+                receiver = TypeHint::from(*receiver),
+                component = component,
+            ),
+
+            Self::UnknownStructField {
+                entry_key_ref,
+                schema,
+            } => {
+                let expected = schema
+                    .fields
+                    .iter()
+                    .map(|field| field.name)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                match ScriptNode::extract_atom_string(doc, entry_key_ref) {
+                    Some(name) => localize!(
+                        AnalysisUnknownStructFieldNamed,
+                        schema = schema.name,
+                        name = name,
+                        expected = expected,
+                    ),
+                    None => localize!(
+                        AnalysisUnknownStructField,
+                        schema = schema.name,
+                        expected = expected,
+                    ),
                 }
             }
 
-            Self::InconsistentReturns { .. } => Cow::from("missing trailing return expression"),
+            Self::MissingStructField { schema, field, .. } => localize!(
+                AnalysisMissingStructField,
+                schema = schema.name,
+                field = field,
+            ),
+
+            Self::IndexOutOfBounds { index, length, .. } => {
+                localize!(AnalysisIndexOutOfBounds, index = index, length = length)
+            }
+
+            Self::ContractViolation {
+                argument,
+                condition,
+                ..
+            } => localize!(
+                AnalysisContractViolation,
+                argument = argument,
+                condition = condition,
+            ),
+
+            Self::AnalysisLimit { limit, .. } => match limit {
+                AnalysisLimit::ExprDepth(max) => localize!(AnalysisExprDepthLimit, max = max),
+
+                AnalysisLimit::ResolutionSteps(max) => {
+                    localize!(AnalysisResolutionStepsLimit, max = max)
+                }
+
+                AnalysisLimit::FanOut(max) => localize!(AnalysisFanOutLimit, max = max),
+            },
+
+            Self::LateCapture { var_ref, .. } => {
+                match ScriptNode::extract_atom_string(doc, var_ref) {
+                    Some(name) => localize!(AnalysisLateCaptureNamed, name = name),
+                    None => localize!(AnalysisLateCapture),
+                }
+            }
+
+            Self::Lint { message, .. } => String::from(message.as_str()),
+
+            Self::InvalidMetadata { message, .. } => String::from(message.as_str()),
+
+            Self::MissingPackage {
+                name,
+                version,
+                found,
+                ..
+            } => match (version, found) {
+                (Some(version), Some(found)) => localize!(
+                    AnalysisMissingPackageVersion,
+                    name = name,
+                    found = found,
+                    version = version,
+                ),
+                _ => localize!(AnalysisMissingPackage, name = name),
+            },
+
+            Self::IncompatibleEngine { version, .. } => localize!(
+                AnalysisIncompatibleEngine,
+                version = version,
+                current = META_VERSION,
+            ),
+
+            Self::UnknownArgument { name_ref, quickfix } => {
+                let name = name_ref.string(doc).unwrap_or("?");
+
+                match quickfix.is_empty() {
+                    true => localize!(AnalysisUnknownArgument, name = name),
+
+                    false => localize!(
+                        AnalysisUnknownArgumentHint,
+                        name = name,
+                        quickfix = format!("{quickfix:?}"),
+                    ),
+                }
+            }
+
+            Self::DuplicateArgument {
+                name_ref, quickfix, ..
+            } => match (name_ref.string(doc), quickfix.is_empty()) {
+                (Some(name), true) => localize!(AnalysisDuplicateArgument, name = name),
+
+                (Some(name), false) => localize!(
+                    AnalysisDuplicateArgumentHint,
+                    name = name,
+                    quickfix = format!("{quickfix:?}"),
+                ),
+
+                (None, _) => localize!(AnalysisDuplicateTrailingClosure),
+            },
+
+            Self::MisplacedArgument { .. } => localize!(AnalysisMisplacedArgument),
+
+            Self::AmbiguousPrecedence { .. } => localize!(AnalysisAmbiguousPrecedence),
+
+            Self::ShadowedMember { component, .. } => {
+                localize!(AnalysisShadowedMember, component = component)
+            }
+
+            Self::DeprecatedMember {
+                component, message, ..
+            } => match message.is_empty() {
+                true => localize!(AnalysisDeprecatedMember, component = component),
+                false => localize!(
+                    AnalysisDeprecatedMemberNote,
+                    component = component,
+                    message = message,
+                ),
+            },
+
+            Self::NonExhaustiveMatch { missing, .. } => {
+                localize!(AnalysisNonExhaustiveMatch, missing = missing)
+            }
+        }
+    }
+
+    pub(crate) fn rust_origin(&self) -> Option<&'static RustOrigin> {
+        match self {
+            Self::TemporaryAssignment { component, .. }
+            | Self::ReadOnlyProperty { component, .. }
+            | Self::ShadowedMember { component, .. }
+            | Self::DeprecatedMember { component, .. } => Some(component.origin),
+
+            _ => None,
         }
     }
 
-    fn message_parse(doc: &ScriptDoc, error_ref: &ErrorRef) -> Cow<'static, str> {
+    // Returns secondary source code fragments related to the issue, such as
+    // the declaration of a variable that the issue's primary span uses.
+    pub(crate) fn related(&self, doc: &ScriptDoc) -> Vec<(ScriptOrigin, String)> {
+        let mut result = Vec::new();
+
+        match self {
+            Self::DuplicateParam { var_ref } => {
+                if let Some(first_ref) = Self::first_param(doc, var_ref) {
+                    result.push((
+                        first_ref.script_origin(doc, SpanBounds::Header),
+                        localize!(AnalysisFirstDeclared),
+                    ));
+                }
+            }
+
+            Self::ReadUninit { decl_ref, .. } if !decl_ref.is_nil() => {
+                let origin = match decl_ref.deref(doc) {
+                    Some(ScriptNode::Let { name, .. }) => {
+                        name.script_origin(doc, SpanBounds::Cover)
+                    }
+                    _ => decl_ref.script_origin(doc, SpanBounds::Cover),
+                };
+
+                result.push((origin, localize!(AnalysisDeclared)));
+            }
+
+            Self::DuplicateEntry { entry_key_ref } => {
+                if let Some(first_ref) = Self::first_entry_key(doc, entry_key_ref) {
+                    result.push((
+                        first_ref.script_origin(doc, SpanBounds::Cover),
+                        localize!(AnalysisFirstDefined),
+                    ));
+                }
+            }
+
+            Self::LateCapture { init_ref, .. } => {
+                result.push((
+                    init_ref.script_origin(doc, SpanBounds::Cover),
+                    localize!(AnalysisInitialized),
+                ));
+            }
+
+            _ => (),
+        }
+
+        result
+    }
+
+    // Returns the first function parameter with the same name as the
+    // specified parameter, if it is not the specified parameter itself.
+    fn first_param(doc: &ScriptDoc, var_ref: &NodeRef) -> Option<NodeRef> {
+        let Some(ScriptNode::Var { parent, token, .. }) = var_ref.deref(doc) else {
+            return None;
+        };
+
+        let Some(ScriptNode::FnParams { params, .. }) = parent.deref(doc) else {
+            return None;
+        };
+
+        let name = token.string(doc)?;
+
+        params
+            .iter()
+            .find(|param_ref| match param_ref.deref(doc) {
+                Some(ScriptNode::Var { token, .. }) => token.string(doc) == Some(name),
+                _ => false,
+            })
+            .filter(|param_ref| *param_ref != var_ref)
+            .copied()
+    }
+
+    // Returns the key of the first struct entry with the same name as the
+    // specified entry key, if it is not the specified key itself.
+    fn first_entry_key(doc: &ScriptDoc, entry_key_ref: &NodeRef) -> Option<NodeRef> {
+        let Some(ScriptNode::StructEntryKey { parent, token, .. }) = entry_key_ref.deref(doc)
+        else {
+            return None;
+        };
+
+        let Some(ScriptNode::StructEntry { parent, .. }) = parent.deref(doc) else {
+            return None;
+        };
+
+        let Some(ScriptNode::StructBody { entries, .. }) = parent.deref(doc) else {
+            return None;
+        };
+
+        let name = token.string(doc)?;
+
+        entries
+            .iter()
+            .filter_map(|entry_ref| match entry_ref.deref(doc) {
+                Some(ScriptNode::StructEntry { key, .. }) => Some(key),
+                _ => None,
+            })
+            .find(|key_ref| match key_ref.deref(doc) {
+                Some(ScriptNode::StructEntryKey { token, .. }) => token.string(doc) == Some(name),
+                _ => false,
+            })
+            .filter(|key_ref| *key_ref != entry_key_ref)
+            .copied()
+    }
+
+    // Covers the entry together with the trailing comma, such that removing
+    // the fragment keeps the struct body well-formed.
+    fn span_entry(doc: &ScriptDoc, entry_key_ref: &NodeRef) -> ScriptOrigin {
+        let Some(ScriptNode::StructEntryKey {
+            parent: entry_ref, ..
+        }) = entry_key_ref.deref(doc)
+        else {
+            return entry_key_ref.script_origin(doc, SpanBounds::Cover);
+        };
+
+        let mut origin = entry_ref.script_origin(doc, SpanBounds::Cover);
+
+        let Some(ScriptNode::StructEntry { parent, .. }) = entry_ref.deref(doc) else {
+            return origin;
+        };
+
+        let Some(ScriptNode::StructBody { entries, .. }) = parent.deref(doc) else {
+            return origin;
+        };
+
+        let Some(index) = entries.iter().position(|probe| probe == entry_ref) else {
+            return origin;
+        };
+
+        if let Some(next) = entries.get(index + 1) {
+            origin.union_until(&next.script_origin(doc, SpanBounds::Header));
+        }
+
+        origin
+    }
+
+    // Maps the span of the embedded block's body text to the tokens of the
+    // block that cover this span.
+    fn span_embedded(doc: &ScriptDoc, embedded_ref: &NodeRef, span: &Range<usize>) -> ScriptOrigin {
+        let origin = embedded_ref.script_origin(doc, SpanBounds::Cover);
+
+        let Some(ScriptNode::Embedded { start, end, .. }) = embedded_ref.deref(doc) else {
+            return origin;
+        };
+
+        let Some((_, body)) = ScriptNode::extract_embedded(doc, start, end) else {
+            return origin;
+        };
+
+        let from = body.start.saturating_add(span.start).min(body.end);
+        let to = body.start.saturating_add(span.end).clamp(from, body.end);
+
+        let mut cursor = doc.cursor(from..to);
+        let mut first = None;
+        let mut last = None;
+
+        loop {
+            let (Some(site), Some(length)) = (cursor.site(0), cursor.length(0)) else {
+                break;
+            };
+
+            let touches_only = from < to && (site + length <= from || site >= to);
+
+            if !touches_only {
+                let token_ref = cursor.token_ref(0);
+
+                if first.is_none() {
+                    first = Some(token_ref);
+                }
+
+                last = Some(token_ref);
+            }
+
+            if !cursor.advance() {
+                break;
+            }
+        }
+
+        let (Some(first), Some(last)) = (first, last) else {
+            return origin;
+        };
+
+        ScriptOrigin::from(first..=last)
+    }
+
+    // Covers the package of the import path together with the preceding dot:
+    // `use foo<.bar>;`.
+    fn span_package(doc: &ScriptDoc, use_ref: &NodeRef, package_ref: &NodeRef) -> ScriptOrigin {
+        let origin = package_ref.script_origin(doc, SpanBounds::Cover);
+
+        let Some(ScriptNode::Use { packages, .. }) = use_ref.deref(doc) else {
+            return origin;
+        };
+
+        let Some(index) = packages.iter().position(|probe| probe == package_ref) else {
+            return origin;
+        };
+
+        let Some(previous_ref) = index.checked_sub(1).and_then(|index| packages.get(index)) else {
+            return origin;
+        };
+
+        let (
+            Some(ScriptNode::Package {
+                token: previous_token,
+                ..
+            }),
+            Some(ScriptNode::Package { token, .. }),
+        ) = (previous_ref.deref(doc), package_ref.deref(doc))
+        else {
+            return origin;
+        };
+
+        let (Some(start), Some(end)) = (previous_token.site(doc), token.site(doc)) else {
+            return origin;
+        };
+
+        let mut cursor = doc.cursor(start..end);
+        let mut distance = 0;
+
+        loop {
+            match cursor.token(distance) {
+                ScriptToken::EOI => return origin,
+
+                ScriptToken::Dot => {
+                    let dot_token = cursor.token_ref(distance);
+
+                    return ScriptOrigin::from(&dot_token..=token);
+                }
+
+                _ => distance += 1,
+            }
+        }
+    }
+
+    fn message_parse(doc: &ScriptDoc, error_ref: &ErrorRef) -> String {
         let Some(issue) = error_ref.deref(doc) else {
-            return Cow::from("parse error");
+            return localize!(ParseError);
         };
 
         match issue.context {
-            ScriptNode::STRING => Cow::from("unenclosed string literal"),
-            ScriptNode::MULTILINE_COMMENT => Cow::from("unenclosed comment"),
+            ScriptNode::STRING => localize!(ParseUnenclosedString),
+            ScriptNode::EMBEDDED => localize!(ParseUnenclosedEmbedded),
+            ScriptNode::MULTILINE_COMMENT => localize!(ParseUnenclosedComment),
             ScriptNode::BLOCK if issue.recovery == RecoveryResult::UnexpectedEOI => {
-                Cow::from("unenclosed code block")
+                localize!(ParseUnenclosedBlock)
             }
             ScriptNode::EXPR if issue.recovery == RecoveryResult::PanicRecover => {
-                Cow::from("unexpected operator")
+                localize!(ParseUnexpectedOperator)
+            }
+
+            _ if issue.expected_tokens.is_empty() && issue.expected_nodes.is_empty() => {
+                localize!(ParseNestingLimit)
             }
 
             _ => {
                 if Self::is_operator_rule(issue.context) {
-                    return Cow::from("missing operand");
+                    return localize!(ParseMissingOperand);
                 }
 
-                Cow::from(issue.message::<ScriptNode>(doc).to_string())
+                issue.message::<ScriptNode>(doc).to_string()
             }
         }
     }
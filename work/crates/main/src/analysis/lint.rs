@@ -0,0 +1,565 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    any::Any,
+    cell::Cell,
+    ops::Deref,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use ahash::AHashSet;
+use compact_str::CompactString;
+use lady_deirdre::{
+    analysis::{AbstractTask, Revision, TaskHandle, TriggerHandle},
+    arena::Identifiable,
+    sync::Shared,
+    syntax::SyntaxTree,
+};
+
+use crate::{
+    analysis::{
+        read::ModuleReadSealed,
+        DiagnosticsDepth,
+        IssueSeverity,
+        ModuleDiagnostics,
+        ModuleError,
+        ModuleReadGuard,
+        ModuleResult,
+        ScriptIssue,
+    },
+    runtime::ScriptOrigin,
+    syntax::{PolyRefOrigin, SpanBounds},
+};
+
+thread_local! {
+    // Set while the lint passes of any module are running on this thread.
+    // The diagnostics requested by a lint pass don't include the lint issues.
+    static LINTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A host-defined diagnostic analysis of the script code.
+///
+/// Lint passes extend the built-in [diagnostics](crate::analysis::ModuleRead::diagnostics)
+/// of the [ScriptModule](crate::analysis::ScriptModule) with the host's own
+/// rules: project-specific conventions, discouraged API usage, and similar
+/// checks that the Ad Astra analyzer is unaware of.
+///
+/// The lint pass receives a read guard of the module and can explore the
+/// module's syntax and semantics using the [symbols](crate::analysis::ModuleRead::symbols)
+/// lookup and the [symbol](crate::analysis::symbols) API. The pass reports
+/// the found issues into the [LintContext].
+///
+/// To register the lint passes of the module, use the
+/// [set_lint_passes](crate::analysis::ModuleWrite::set_lint_passes) function.
+/// The LSP server registers the passes from the
+/// [LspServerConfig::lint_passes](crate::server::LspServerConfig::lint_passes)
+/// configuration in each opened module.
+///
+/// The analyzer runs the lint passes lazily when the diagnostics of the pass's
+/// [depth](Self::depth) are requested, and caches their results until the next
+/// edit of the module's source code.
+///
+/// If the pass panics or returns an error other than
+/// [Interrupted](ModuleError::Interrupted), the analyzer reports a single
+/// [error](IssueSeverity::Error) in the diagnostics instead of the pass's
+/// issues. The Interrupted error interrupts the diagnostics request.
+///
+/// ```rust
+/// # use ad_astra::{
+/// #     analysis::{
+/// #         symbols::{LookupOptions, ModuleSymbol, SymbolKind},
+/// #         IssueSeverity,
+/// #         LintContext,
+/// #         LintPass,
+/// #         ModuleRead,
+/// #         ModuleReadGuard,
+/// #         ModuleResult,
+/// #         ModuleWrite,
+/// #         ScriptModule,
+/// #     },
+/// #     export,
+/// #     lady_deirdre::analysis::{TaskHandle, TriggerHandle},
+/// #     runtime::ScriptPackage,
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// struct NoShortNames;
+///
+/// impl<H: TaskHandle> LintPass<H> for NoShortNames {
+///     fn name(&self) -> &str {
+///         "no-short-names"
+///     }
+///
+///     fn check(&self, read: &ModuleReadGuard<H>, context: &mut LintContext) -> ModuleResult<()> {
+///         let vars = read.symbols(.., LookupOptions::default().filter(SymbolKind::Var as u32))?;
+///
+///         for symbol in vars {
+///             let ModuleSymbol::Var(var) = symbol else {
+///                 continue;
+///             };
+///
+///             let Some(name) = var.var_name(read) else {
+///                 continue;
+///             };
+///
+///             if name.as_ref().len() < 2 {
+///                 context.report(
+///                     var.origin(read),
+///                     IssueSeverity::Hint,
+///                     "no-short-names",
+///                     "variable name is too short",
+///                 );
+///             }
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let module = ScriptModule::<TriggerHandle>::new(Package::meta(), "let x = 10; let yy = x;");
+///
+/// module
+///     .write(&TriggerHandle::new(), 1)
+///     .unwrap()
+///     .set_lint_passes(&[&NoShortNames]);
+///
+/// let handle = TriggerHandle::new();
+/// let module_read = module.read(&handle, 1).unwrap();
+///
+/// let diagnostics = module_read.diagnostics(3).unwrap();
+/// let issue = diagnostics.iter().next().unwrap();
+///
+/// assert_eq!(issue.lint_code(), Some("no-short-names"));
+/// assert_eq!(issue.severity(), IssueSeverity::Hint);
+/// ```
+pub trait LintPass<H: TaskHandle = TriggerHandle>: Send + Sync + 'static {
+    /// A short name of the lint pass.
+    ///
+    /// The analyzer uses this name as the code of the issue that reports
+    /// the pass's failure.
+    fn name(&self) -> &str;
+
+    /// The [diagnostics depth](DiagnosticsDepth) at which the analyzer reports
+    /// the issues of this pass.
+    ///
+    /// The value is either 2 (shallow semantic analysis) or 3 (deep semantic
+    /// analysis). Other values are clamped into this range. The default value
+    /// is 3.
+    #[inline(always)]
+    fn depth(&self) -> DiagnosticsDepth {
+        3
+    }
+
+    /// Runs the analysis of the module and reports the found issues into
+    /// the `context`.
+    ///
+    /// The implementation should propagate the
+    /// [Interrupted](ModuleError::Interrupted) errors of the `read` functions
+    /// to the caller as is.
+    fn check(&self, read: &ModuleReadGuard<H>, context: &mut LintContext) -> ModuleResult<()>;
+}
+
+/// A collector of the diagnostic issues reported by the [LintPass].
+pub struct LintContext {
+    depth: DiagnosticsDepth,
+    issues: Vec<ScriptIssue>,
+}
+
+impl LintContext {
+    /// Returns the diagnostics depth of the running lint pass.
+    #[inline(always)]
+    pub fn depth(&self) -> DiagnosticsDepth {
+        self.depth
+    }
+
+    /// Reports a diagnostic issue.
+    ///
+    /// The `origin` argument specifies the source code range of the issue
+    /// (e.g., the [origin](crate::analysis::symbols::ModuleSymbol::origin) of
+    /// a symbol).
+    ///
+    /// The `code` argument is a host-defined code of the issue, which is
+    /// available through the [ModuleIssue::lint_code](crate::analysis::ModuleIssue::lint_code)
+    /// function. The LSP server sends this code to the client as the
+    /// diagnostic's code.
+    ///
+    /// The `message` argument is the issue's
+    /// [verbose message](crate::analysis::ModuleIssue::verbose_message).
+    pub fn report(
+        &mut self,
+        origin: ScriptOrigin,
+        severity: IssueSeverity,
+        code: &str,
+        message: impl AsRef<str>,
+    ) {
+        self.issues.push(ScriptIssue::Lint {
+            origin,
+            depth: self.depth,
+            severity,
+            code: CompactString::from(code),
+            message: CompactString::from(message.as_ref()),
+        });
+    }
+}
+
+// The lint passes registered in the script module, and the cached results of
+// their analysis for the diagnostics depths 2 and 3.
+pub struct ModuleLints<H: TaskHandle> {
+    passes: Vec<&'static dyn LintPass<H>>,
+    // Incremented each time the set of passes is replaced.
+    generation: usize,
+    cache: [LintCache; 2],
+}
+
+#[derive(Default)]
+struct LintCache {
+    // The text revision and the revision of the built-in diagnostics for which
+    // the `issues` were computed.
+    key: Option<(Revision, Revision)>,
+    issues: Shared<AHashSet<ScriptIssue>>,
+    revision: Revision,
+}
+
+impl<H: TaskHandle> ModuleLints<H> {
+    #[inline(always)]
+    pub(super) fn new() -> Self {
+        Self {
+            passes: Vec::new(),
+            generation: 0,
+            cache: Default::default(),
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn passes(&self) -> &[&'static dyn LintPass<H>] {
+        &self.passes
+    }
+
+    #[inline(always)]
+    pub(super) fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub(super) fn set_passes(&mut self, passes: &[&'static dyn LintPass<H>]) {
+        self.passes = passes.to_vec();
+        self.generation += 1;
+
+        for cache in &mut self.cache {
+            cache.key = None;
+        }
+    }
+
+    // Returns the previously merged diagnostics if they are still relevant to
+    // the specified built-in diagnostics.
+    pub(super) fn cached(
+        &self,
+        diagnostics: &ModuleDiagnostics,
+        text_revision: Revision,
+    ) -> Option<ModuleDiagnostics> {
+        let cache = self.cache.get(Self::slot(diagnostics.depth)?)?;
+
+        if cache.key != Some((text_revision, diagnostics.revision)) {
+            return None;
+        }
+
+        Some(ModuleDiagnostics {
+            id: diagnostics.id,
+            issues: cache.issues.clone(),
+            depth: diagnostics.depth,
+            revision: cache.revision,
+        })
+    }
+
+    // Stores the merged issues in the cache and returns the diagnostics object.
+    //
+    // The revision of the merged diagnostics never decreases, and it increases
+    // whenever the merged set of issues changes.
+    fn commit(
+        &mut self,
+        mut diagnostics: ModuleDiagnostics,
+        text_revision: Revision,
+        issues: Shared<AHashSet<ScriptIssue>>,
+    ) -> ModuleDiagnostics {
+        let Some(slot) = Self::slot(diagnostics.depth) else {
+            return diagnostics;
+        };
+
+        let cache = &mut self.cache[slot];
+
+        let floor = match cache.issues.as_ref() == issues.as_ref() {
+            true => cache.revision,
+            false => cache.revision + 1,
+        };
+
+        cache.issues = issues;
+        cache.revision = diagnostics.revision.max(floor);
+
+        cache.key = Some((text_revision, diagnostics.revision));
+
+        diagnostics.issues = cache.issues.clone();
+        diagnostics.revision = cache.revision;
+
+        diagnostics
+    }
+
+    #[inline(always)]
+    fn slot(depth: DiagnosticsDepth) -> Option<usize> {
+        match depth {
+            2 => Some(0),
+            3 => Some(1),
+            _ => None,
+        }
+    }
+}
+
+// Merges the issues of the module's lint passes into the built-in diagnostics
+// of depth 2 or 3.
+pub(super) fn run_lints<H: TaskHandle>(
+    read: &ModuleReadGuard<H>,
+    diagnostics: ModuleDiagnostics,
+) -> ModuleResult<ModuleDiagnostics> {
+    let depth = diagnostics.depth;
+
+    if ModuleLints::<H>::slot(depth).is_none() || LINTING.get() {
+        return Ok(diagnostics);
+    }
+
+    let text_revision = read.lock_history().revision();
+
+    let passes = {
+        let lints = read.lock_lints();
+
+        if let Some(cached) = lints.cached(&diagnostics, text_revision) {
+            return Ok(cached);
+        }
+
+        lints
+            .passes
+            .iter()
+            .filter(|pass| pass.depth().clamp(2, 3) == depth)
+            .copied()
+            .collect::<Vec<_>>()
+    };
+
+    if passes.is_empty() {
+        let issues = diagnostics.issues.clone();
+
+        return Ok(read.lock_lints().commit(diagnostics, text_revision, issues));
+    }
+
+    let mut issues = diagnostics.issues.as_ref().clone();
+
+    for pass in passes {
+        if read.task().handle().is_triggered() {
+            return Err(ModuleError::Interrupted(read.id()));
+        }
+
+        let mut context = LintContext {
+            depth,
+            issues: Vec::new(),
+        };
+
+        LINTING.set(true);
+        let result = catch_unwind(AssertUnwindSafe(|| pass.check(read, &mut context)));
+        LINTING.set(false);
+
+        let failure = match result {
+            Ok(Ok(())) => None,
+            Ok(Err(ModuleError::Interrupted(id))) => return Err(ModuleError::Interrupted(id)),
+            Ok(Err(error)) => Some(error.to_string()),
+            Err(payload) => Some(panic_message(payload)),
+        };
+
+        match failure {
+            None => issues.extend(context.issues),
+
+            Some(failure) => {
+                let doc_read = read.read_doc();
+                let doc = doc_read.deref();
+
+                let _ = issues.insert(ScriptIssue::Lint {
+                    origin: doc.root_node_ref().script_origin(doc, SpanBounds::Header),
+                    depth,
+                    severity: IssueSeverity::Error,
+                    code: CompactString::from(pass.name()),
+                    message: CompactString::from(format!(
+                        "lint pass '{}' failed: {failure}",
+                        pass.name(),
+                    )),
+                });
+            }
+        }
+    }
+
+    let issues = Shared::new(issues);
+
+    Ok(read.lock_lints().commit(diagnostics, text_revision, issues))
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return String::from(*message);
+    }
+
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+
+    String::from("the pass panicked")
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::analysis::{TaskHandle, TriggerHandle};
+
+    use crate::{
+        analysis::{
+            symbols::{tests::TestPackage, LookupOptions, ModuleSymbol, SymbolKind},
+            IssueCode,
+            IssueSeverity,
+            LintContext,
+            LintPass,
+            ModuleRead,
+            ModuleReadGuard,
+            ModuleResult,
+            ModuleWrite,
+            ScriptModule,
+        },
+        runtime::ScriptPackage,
+    };
+
+    struct ShortNames;
+
+    impl<H: TaskHandle> LintPass<H> for ShortNames {
+        fn name(&self) -> &str {
+            "short-names"
+        }
+
+        fn check(&self, read: &ModuleReadGuard<H>, context: &mut LintContext) -> ModuleResult<()> {
+            let vars = read.symbols(.., LookupOptions::default().filter(SymbolKind::Var as u32))?;
+
+            for symbol in vars {
+                let ModuleSymbol::Var(var) = symbol else {
+                    continue;
+                };
+
+                let Some(name) = var.var_name(read) else {
+                    continue;
+                };
+
+                if name.as_ref().len() < 2 {
+                    context.report(
+                        var.origin(read),
+                        IssueSeverity::Hint,
+                        "short-names",
+                        "short name",
+                    );
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    struct Broken;
+
+    impl<H: TaskHandle> LintPass<H> for Broken {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn depth(&self) -> u8 {
+            2
+        }
+
+        fn check(
+            &self,
+            _read: &ModuleReadGuard<H>,
+            _context: &mut LintContext,
+        ) -> ModuleResult<()> {
+            panic!("broken lint");
+        }
+    }
+
+    #[test]
+    fn test_lint_passes() {
+        let module = ScriptModule::new(TestPackage::meta(), "let x = 1; let yy = x;");
+        let handle = TriggerHandle::new();
+
+        let initial = module.read(&handle, 1).unwrap().diagnostics(3).unwrap();
+
+        assert!(initial.is_empty());
+
+        module
+            .write(&handle, 1)
+            .unwrap()
+            .set_lint_passes(&[&ShortNames, &Broken]);
+
+        let read = module.read(&handle, 1).unwrap();
+
+        let deep = read.diagnostics(3).unwrap();
+        let issues = deep.iter().collect::<Vec<_>>();
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code(), IssueCode::DeepLint);
+        assert_eq!(issues[0].severity(), IssueSeverity::Hint);
+        assert_eq!(issues[0].lint_code(), Some("short-names"));
+        assert!(deep.revision() > initial.revision());
+        assert_eq!(read.diagnostics(3).unwrap().revision(), deep.revision());
+
+        let shallow = read.diagnostics(2).unwrap();
+        let issues = shallow.iter().collect::<Vec<_>>();
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code(), IssueCode::Lint);
+        assert_eq!(issues[0].severity(), IssueSeverity::Error);
+        assert_eq!(issues[0].lint_code(), Some("broken"));
+        assert!(issues[0]
+            .verbose_message(&read.text())
+            .contains("lint pass 'broken' failed: broken lint"));
+
+        drop(read);
+
+        module.write(&handle, 1).unwrap().set_lint_passes(&[]);
+
+        let cleared = module.read(&handle, 1).unwrap().diagnostics(3).unwrap();
+
+        assert!(cleared.is_empty());
+        assert!(cleared.revision() > deep.revision());
+    }
+}
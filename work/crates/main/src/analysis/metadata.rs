@@ -0,0 +1,549 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use compact_str::CompactString;
+use lady_deirdre::lexis::{SourceCode, TokenCursor};
+use semver::{Version, VersionReq};
+
+use crate::{
+    runtime::{PackageMeta, ScriptOrigin},
+    syntax::{ScriptDoc, ScriptToken},
+    META_VERSION,
+};
+
+/// The metadata header of a script module.
+///
+/// The header consists of the `//!` inline comments at the beginning of
+/// the module's source code, before the first statement. The script author
+/// uses the header to describe the module and to declare the environment that
+/// the script expects:
+///
+/// ```text
+/// //! Computes the orbit of a satellite.
+/// //!
+/// //! adastra 1.0
+/// //! requires package math >= 1.2
+/// //! requires package physics
+///
+/// let orbit = math.sqrt(physics.g * 10);
+/// ```
+///
+/// The header recognizes two directives:
+///
+/// - `//! adastra <version>` declares a requirement on the engine version.
+///   The analyzer reports an error if the [META_VERSION](crate::META_VERSION)
+///   does not satisfy the requirement.
+///
+/// - `//! requires package <name> [<version>]` declares a package that
+///   the script imports. The analyzer reports an error if the package under
+///   which the module is analyzed is neither this package nor has it as
+///   a dependency.
+///
+/// The versions follow the Cargo's
+/// [version requirement](https://doc.rust-lang.org/cargo/reference/specifying-dependencies.html)
+/// syntax. A bare version such as `1.2` is a caret requirement (`^1.2`).
+///
+/// Any other `//!` line is a part of the module's
+/// [documentation](Self::doc). The analyzer reports malformed directives as
+/// [warnings](crate::analysis::IssueCode::InvalidMetadata).
+///
+/// You can obtain this object using the
+/// [ModuleRead::metadata](crate::analysis::ModuleRead::metadata) function.
+/// For example, the host can inspect the required packages to choose which
+/// package to analyze the module under.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ModuleMetadata {
+    doc: String,
+    engine: Option<EngineRequirement>,
+    packages: Vec<PackageRequirement>,
+    pub(crate) invalid: Vec<(ScriptOrigin, CompactString)>,
+}
+
+impl ModuleMetadata {
+    /// Returns the documentation text of the module: the content of
+    /// the header's `//!` lines that are not directives.
+    ///
+    /// The returned string is empty if the module has no documentation.
+    #[inline(always)]
+    pub fn doc(&self) -> &str {
+        &self.doc
+    }
+
+    /// Returns the engine version requirement declared by the
+    /// `//! adastra <version>` directive, if any.
+    #[inline(always)]
+    pub fn engine(&self) -> Option<&EngineRequirement> {
+        self.engine.as_ref()
+    }
+
+    /// Returns the packages declared by the `//! requires package` directives,
+    /// in the order of their appearance in the header.
+    #[inline(always)]
+    pub fn packages(&self) -> &[PackageRequirement] {
+        &self.packages
+    }
+
+    /// Returns true if the module does not have a metadata header.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.doc.is_empty()
+            && self.engine.is_none()
+            && self.packages.is_empty()
+            && self.invalid.is_empty()
+    }
+
+    pub(crate) fn parse(doc: &ScriptDoc) -> Self {
+        let mut metadata = Self::default();
+        let mut cursor = doc.cursor(..);
+
+        loop {
+            match cursor.token(0) {
+                ScriptToken::Whitespace | ScriptToken::Linebreak => {
+                    if !cursor.advance() {
+                        break;
+                    }
+                }
+
+                ScriptToken::InlineComment => {
+                    let first = cursor.token_ref(0);
+                    let mut last = first;
+                    let mut line = String::new();
+
+                    loop {
+                        if let Some(string) = cursor.string(0) {
+                            line.push_str(string);
+                        }
+
+                        last = cursor.token_ref(0);
+
+                        if !cursor.advance() {
+                            break;
+                        }
+
+                        if let ScriptToken::Linebreak | ScriptToken::EOI = cursor.token(0) {
+                            break;
+                        }
+                    }
+
+                    if let Some(body) = line.strip_prefix("//!") {
+                        metadata.parse_line(body, ScriptOrigin::from(first..=last));
+                    }
+                }
+
+                _ => break,
+            }
+        }
+
+        if metadata.doc.ends_with('\n') {
+            let _ = metadata.doc.pop();
+        }
+
+        metadata
+    }
+
+    fn parse_line(&mut self, body: &str, origin: ScriptOrigin) {
+        let text = body.trim();
+
+        if let Some(version) = directive(text, "adastra") {
+            if self.engine.is_some() {
+                self.invalid.push((
+                    origin,
+                    CompactString::from("duplicate engine version directive"),
+                ));
+
+                return;
+            }
+
+            if VersionReq::parse(version).is_err() {
+                self.invalid.push((
+                    origin,
+                    CompactString::from(format!("invalid engine version requirement '{version}'")),
+                ));
+
+                return;
+            }
+
+            self.engine = Some(EngineRequirement {
+                version: CompactString::from(version),
+                origin,
+            });
+
+            return;
+        }
+
+        if let Some(rest) = directive(text, "requires") {
+            let Some(rest) = directive(rest, "package") else {
+                self.invalid.push((
+                    origin,
+                    CompactString::from("expected 'requires package <name> [<version>]'"),
+                ));
+
+                return;
+            };
+
+            let name_end = rest
+                .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_' && ch != '-')
+                .unwrap_or(rest.len());
+
+            let (name, version) = rest.split_at(name_end);
+            let version = version.trim();
+
+            if name.is_empty() {
+                self.invalid
+                    .push((origin, CompactString::from("missing package name")));
+
+                return;
+            }
+
+            if !version.is_empty() && VersionReq::parse(version).is_err() {
+                self.invalid.push((
+                    origin,
+                    CompactString::from(format!(
+                        "invalid package '{name}' version requirement '{version}'"
+                    )),
+                ));
+
+                return;
+            }
+
+            self.packages.push(PackageRequirement {
+                name: CompactString::from(name),
+                version: match version.is_empty() {
+                    true => None,
+                    false => Some(CompactString::from(version)),
+                },
+                origin,
+            });
+
+            return;
+        }
+
+        self.doc
+            .push_str(body.strip_prefix(' ').unwrap_or(body).trim_end());
+        self.doc.push('\n');
+    }
+}
+
+/// A requirement on the engine version declared in the [ModuleMetadata]
+/// header: `//! adastra <version>`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EngineRequirement {
+    version: CompactString,
+    origin: ScriptOrigin,
+}
+
+impl EngineRequirement {
+    /// Returns the version requirement string as written in the header.
+    #[inline(always)]
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the source code range of the directive.
+    #[inline(always)]
+    pub fn origin(&self) -> ScriptOrigin {
+        self.origin
+    }
+
+    /// Returns true if the [META_VERSION](crate::META_VERSION) of this engine
+    /// satisfies the requirement.
+    pub fn is_satisfied(&self) -> bool {
+        let (Ok(requirement), Ok(version)) = (
+            VersionReq::parse(&self.version),
+            Version::parse(META_VERSION),
+        ) else {
+            return false;
+        };
+
+        requirement.matches(&version)
+    }
+}
+
+/// A required package declared in the [ModuleMetadata] header:
+/// `//! requires package <name> [<version>]`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PackageRequirement {
+    name: CompactString,
+    version: Option<CompactString>,
+    origin: ScriptOrigin,
+}
+
+impl PackageRequirement {
+    /// Returns the name of the required package's crate.
+    #[inline(always)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the version requirement string as written in the header, or
+    /// None if the directive does not restrict the version.
+    #[inline(always)]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns the source code range of the directive.
+    #[inline(always)]
+    pub fn origin(&self) -> ScriptOrigin {
+        self.origin
+    }
+
+    /// Looks up the registered [PackageMeta] with the required name and
+    /// the highest version that satisfies the requirement.
+    ///
+    /// This is a shortcut for [PackageMeta::of].
+    #[inline(always)]
+    pub fn find(&self) -> Option<&'static PackageMeta> {
+        PackageMeta::of(&self.name, self.version().unwrap_or("*"))
+    }
+
+    /// Returns true if the `package` is the required package, or if it has
+    /// the required package as a dependency.
+    pub fn is_satisfied_by(&self, package: &'static PackageMeta) -> bool {
+        let Some(candidate) = self.resolve_in(package) else {
+            return false;
+        };
+
+        let Some(version) = &self.version else {
+            return true;
+        };
+
+        let (Ok(requirement), Ok(version)) = (
+            VersionReq::parse(version),
+            Version::parse(candidate.version()),
+        ) else {
+            return false;
+        };
+
+        requirement.matches(&version)
+    }
+
+    // Finds the package with the required name (regardless of the version)
+    // among the `package` and its direct dependencies.
+    pub(crate) fn resolve_in(&self, package: &'static PackageMeta) -> Option<&'static PackageMeta> {
+        if package.name() == self.name {
+            return Some(package);
+        }
+
+        let component = package.ty().prototype().hint_component(&self.name)?;
+
+        if !component.ty.is_package() {
+            return None;
+        }
+
+        component.ty.package()
+    }
+}
+
+// Returns the remainder of the text if it starts with the keyword followed by
+// a whitespace or the end of the text.
+#[inline]
+fn directive<'a>(text: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = text.strip_prefix(keyword)?;
+
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    Some(rest.trim_start())
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::{
+        analysis::TriggerHandle,
+        lexis::{SourceCode, ToSpan},
+    };
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, IssueCode, ModuleRead, ModuleWrite, ScriptModule},
+        runtime::ScriptPackage,
+        META_VERSION,
+    };
+
+    fn issues(read: &impl ModuleRead<TriggerHandle>) -> Vec<(IssueCode, String)> {
+        let text = read.text();
+
+        let mut result = read
+            .diagnostics(2)
+            .unwrap()
+            .iter()
+            .filter(|issue| {
+                matches!(
+                    issue.code(),
+                    IssueCode::InvalidMetadata
+                        | IssueCode::MissingPackage
+                        | IssueCode::IncompatibleEngine
+                )
+            })
+            .map(|issue| {
+                let span = issue.origin(&text).to_site_span(&text).unwrap();
+
+                (issue.code(), text.substring(span).to_string())
+            })
+            .collect::<Vec<_>>();
+
+        result.sort_by(|a, b| a.1.cmp(&b.1));
+
+        result
+    }
+
+    #[test]
+    fn test_metadata_header() {
+        let package = TestPackage::meta();
+
+        let text = format!(
+            "// License header.\n\
+            //! Computes the orbit.\n\
+            //!\n\
+            //!   Indented line.\n\
+            //! adastra {META_VERSION}\n\
+            //! requires package {} >= 0.1\n\
+            //! requires package math\n\
+            \n\
+            //! adastra 1.0\n\
+            let x = 10;\n\
+            //! requires package physics\n",
+            package.name(),
+        );
+
+        let module = ScriptModule::new(package, text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let metadata = read.metadata().unwrap();
+
+        assert_eq!(metadata.doc(), "Computes the orbit.\n\n  Indented line.");
+        assert_eq!(metadata.engine().unwrap().version(), META_VERSION);
+        assert!(metadata.engine().unwrap().is_satisfied());
+        assert_eq!(metadata.packages().len(), 2);
+        assert_eq!(metadata.packages()[0].name(), package.name());
+        assert_eq!(metadata.packages()[0].version(), Some(">= 0.1"));
+        assert!(metadata.packages()[0].is_satisfied_by(package));
+        assert_eq!(metadata.packages()[1].name(), "math");
+        assert_eq!(metadata.packages()[1].version(), None);
+
+        assert_eq!(
+            issues(&read),
+            vec![
+                (IssueCode::InvalidMetadata, String::from("//! adastra 1.0")),
+                (
+                    IssueCode::MissingPackage,
+                    String::from("//! requires package math"),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_metadata_diagnostics() {
+        let package = TestPackage::meta();
+
+        let module = ScriptModule::new(
+            package,
+            "//! adastra 0.1\n//! requires math\n//! adastra ?\nlet x = 10;",
+        );
+
+        let handle = TriggerHandle::new();
+
+        {
+            let read = module.read(&handle, 1).unwrap();
+
+            assert!(!read.metadata().unwrap().engine().unwrap().is_satisfied());
+
+            assert_eq!(
+                issues(&read),
+                vec![
+                    (
+                        IssueCode::IncompatibleEngine,
+                        String::from("//! adastra 0.1")
+                    ),
+                    (IssueCode::InvalidMetadata, String::from("//! adastra ?")),
+                    (
+                        IssueCode::InvalidMetadata,
+                        String::from("//! requires math")
+                    ),
+                ],
+            );
+        }
+
+        let version = META_VERSION.to_string();
+
+        {
+            let mut write = module.write(&handle, 1).unwrap();
+
+            let _ = write.edit(12..15, &version).unwrap();
+
+            let _ = write
+                .edit(version.len() + 13..version.len() + 45, "")
+                .unwrap();
+        }
+
+        {
+            let read = module.read(&handle, 1).unwrap();
+
+            assert!(read.metadata().unwrap().engine().unwrap().is_satisfied());
+            assert!(issues(&read).is_empty());
+        }
+
+        {
+            let mut write = module.write(&handle, 1).unwrap();
+
+            let requirement = format!(
+                "//! requires package {} < {}\n",
+                package.name(),
+                package.version()
+            );
+
+            let _ = write.edit(0..0, &requirement).unwrap();
+        }
+
+        {
+            let read = module.read(&handle, 1).unwrap();
+
+            let metadata = read.metadata().unwrap();
+            let requirement = &metadata.packages()[0];
+
+            assert!(!requirement.is_satisfied_by(package));
+
+            assert_eq!(
+                issues(&read)
+                    .into_iter()
+                    .map(|(code, _)| code)
+                    .collect::<Vec<_>>(),
+                vec![IssueCode::MissingPackage],
+            );
+        }
+    }
+}
@@ -32,14 +32,29 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
+mod annotation;
+mod bookmark;
 mod closeness;
 mod completions;
+mod config;
+mod dead_code;
+mod definition;
 mod description;
 mod diagnostics;
+mod diff;
+mod embedded;
+mod environment;
 mod error;
+mod folding;
+mod history;
+mod inline;
 mod issues;
+mod lint;
+mod metadata;
 mod module;
 mod read;
+mod snapshot;
+mod stats;
 mod text;
 mod write;
 
@@ -53,21 +68,41 @@ mod write;
 pub mod symbols;
 
 pub use crate::analysis::{
+    annotation::ModuleAnnotation,
+    bookmark::Bookmark,
     closeness::{Closeness, StringEstimation},
     completions::{CompletionItem, CompletionScope, Completions},
-    description::Description,
+    config::AnalysisConfig,
+    dead_code::{DeadCodeItem, DeadCodeIter, DeadCodeKind, DeadCodeReport},
+    definition::DefinitionTarget,
+    description::{Description, DescriptionConfig, DescriptionVerbosity},
     diagnostics::{
         DiagnosticsDepth,
+        DiagnosticsIndex,
         DiagnosticsIter,
         IssueQuickfix,
         ModuleDiagnostics,
         ModuleIssue,
+        RelatedOrigin,
+        SeverityCount,
     },
+    embedded::{set_embedded_validator, EmbeddedIssue, EmbeddedValidator},
+    environment::{EnvironmentError, EnvironmentResult, ScriptEnvironment, ScriptSource},
     error::{ModuleError, ModuleResult},
+    inline::{InlineEdit, InlinePlan, InlineRefusal},
     issues::{IssueCode, IssueSeverity},
+    lint::{LintContext, LintPass},
+    metadata::{EngineRequirement, ModuleMetadata, PackageRequirement},
     module::ScriptModule,
     read::{ModuleRead, ModuleReadGuard},
-    text::{ModuleText, ModuleTextResolver},
-    write::{ModuleWrite, ModuleWriteGuard},
+    snapshot::ModuleSnapshot,
+    stats::{ModuleStats, QueryKind, QueryStats},
+    text::{ModuleText, ModuleTextResolver, TextPatch},
+    write::{EditSummary, ModuleWrite, ModuleWriteGuard},
+};
+pub(crate) use crate::analysis::{
+    embedded::validate_embedded,
+    error::ModuleResultEx,
+    folding::PureCalls,
+    issues::{AnalysisLimit, ScriptIssue},
 };
-pub(crate) use crate::analysis::{error::ModuleResultEx, issues::ScriptIssue};
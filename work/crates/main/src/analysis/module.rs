@@ -32,19 +32,36 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::fmt::{Debug, Display, Formatter};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    sync::Mutex,
+};
 
 use ahash::RandomState;
 use lady_deirdre::{
     analysis::{Analyzer, AnalyzerConfig, MutationAccess, TaskHandle, TaskPriority, TriggerHandle},
     arena::{Id, Identifiable},
+    lexis::SourceCode,
 };
 
 use crate::{
-    analysis::{ModuleReadGuard, ModuleResult, ModuleResultEx, ModuleWriteGuard},
+    analysis::{
+        history::EditHistory,
+        lint::ModuleLints,
+        read::ModuleReadSealed,
+        snapshot::SnapshotKey,
+        stats::{ModuleStats, StatsCollector},
+        ModuleError,
+        ModuleRead,
+        ModuleReadGuard,
+        ModuleResult,
+        ModuleResultEx,
+        ModuleSnapshot,
+        ModuleWriteGuard,
+    },
     format::format_script_path,
     report::system_panic,
-    runtime::PackageMeta,
+    runtime::{PackageMeta, ScriptProvenance, TextHasher},
     syntax::ScriptNode,
 };
 
@@ -134,6 +151,8 @@ use crate::{
 /// - Querying for semantic metadata about specific syntax constructs within
 ///   specified source code ranges via the
 ///   [symbols](crate::analysis::ModuleRead::symbols) function.
+/// - Requesting a report of unused functions, struct entries, and imports via
+///   the [dead_code](crate::analysis::ModuleRead::dead_code) function.
 /// - Compiling the module into Ad Astra assembly for execution via the
 ///   [compile](crate::analysis::ModuleRead::compile) function.
 ///
@@ -146,10 +165,23 @@ use crate::{
 ///
 /// - Editing the source code text within a specified range via the
 ///   [edit](crate::analysis::ModuleWrite::edit) function.
+/// - Synchronizing the source code text with a new version of the text via
+///   the [replace_from_reader](crate::analysis::ModuleWrite::replace_from_reader)
+///   function, which patches only the changed lines.
 /// - Probing the source code for code-completion candidates via the
 ///   [completions](crate::analysis::ModuleWrite::completions) function. Even
 ///   though this function does not ultimately change the source code text, it
 ///   requires write access to probe the code through temporary mutation.
+/// - Configuring the names excluded from the dead code report via the
+///   [set_dead_code_allow](crate::analysis::ModuleWrite::set_dead_code_allow)
+///   function.
+/// - Configuring the limits of the semantic analysis via the
+///   [set_analysis_config](crate::analysis::ModuleWrite::set_analysis_config)
+///   function.
+/// - Registering the host's custom [lint passes](crate::analysis::LintPass)
+///   that extend the module's diagnostics via the
+///   [set_lint_passes](crate::analysis::ModuleWrite::set_lint_passes)
+///   function.
 ///
 /// ## Multi-Threaded Design
 ///
@@ -217,6 +249,11 @@ use crate::{
 /// other when requested from independent threads. Ad Astra's semantic analyzer
 /// can infer module semantics concurrently.
 ///
+/// If a thread only queries the module's semantics and can tolerate slightly
+/// stale results, it can use a [snapshot](ScriptModule::snapshot) of the
+/// module instead of the read guard. The snapshot's queries neither block the
+/// module's writers nor get interrupted by them.
+///
 /// ## Incremental Analysis
 ///
 /// When you [edit](crate::analysis::ModuleWrite::edit) the source code of the
@@ -258,14 +295,34 @@ use crate::{
 /// API functions that print a module's content to the terminal will use the
 /// assigned name of the ScriptModule as a content header, which helps
 /// simplify script identification.
+///
+/// ## Provenance
+///
+/// To correlate the module's diagnostics and the runtime errors of its
+/// compiled code with the original asset, you can assign a
+/// [ScriptProvenance] record to the module using the
+/// [with_provenance](ScriptModule::with_provenance) constructor or the
+/// [set_provenance](ScriptModule::set_provenance) function. The record
+/// contains the asset name, version, and host-defined tags. The compiled
+/// [ScriptFn](crate::interpret::ScriptFn) objects keep a copy of this record.
+///
+/// Additionally, each module computes a stable hash of its source code text
+/// (see [text_hash](crate::analysis::ModuleRead::text_hash)), which you can
+/// use as a cache key of the module's content.
 pub struct ScriptModule<H: TaskHandle = TriggerHandle> {
     id: Id,
     package: &'static PackageMeta,
     analyzer: Analyzer<ScriptNode, H, RandomState>,
+    history: Mutex<EditHistory>,
+    lints: Mutex<ModuleLints<H>>,
+    stats: StatsCollector,
+    snapshot: Mutex<Option<ModuleSnapshot<H>>>,
 }
 
 impl<H: TaskHandle> Drop for ScriptModule<H> {
     fn drop(&mut self) {
+        ScriptProvenance::register(self.id, None);
+
         // Safety: Module was attached during creation.
         unsafe { self.package.detach_module(self.id) }
     }
@@ -311,6 +368,16 @@ impl<H: TaskHandle> ScriptModule<H> {
     ///
     /// The `text` argument is the source code of the script.
     pub fn new(package: &'static PackageMeta, text: impl AsRef<str>) -> Self {
+        let text = text.as_ref();
+
+        let text_hash = {
+            let mut hasher = TextHasher::new();
+
+            hasher.write(text);
+
+            hasher.finish()
+        };
+
         let mut config = AnalyzerConfig::default();
 
         config.single_document = true;
@@ -335,9 +402,32 @@ impl<H: TaskHandle> ScriptModule<H> {
             id,
             package,
             analyzer,
+            history: Mutex::new(EditHistory::new(text_hash)),
+            lints: Mutex::new(ModuleLints::new()),
+            stats: StatsCollector::new(),
+            snapshot: Mutex::new(None),
         }
     }
 
+    /// Constructs a ScriptModule with the specified `provenance` record.
+    ///
+    /// The module's name is set to the [name](ScriptProvenance::name) of the
+    /// record.
+    ///
+    /// See [ScriptModule::new] for details about the `package` and `text`
+    /// arguments.
+    pub fn with_provenance(
+        package: &'static PackageMeta,
+        text: impl AsRef<str>,
+        provenance: ScriptProvenance,
+    ) -> Self {
+        let module = Self::new(package, text);
+
+        module.set_provenance(Some(provenance));
+
+        module
+    }
+
     /// Returns the metadata object of the script package under which this
     /// script module is being analyzed.
     ///
@@ -372,9 +462,51 @@ impl<H: TaskHandle> ScriptModule<H> {
     /// To unset the name, you can supply an empty string to this function.
     /// By default, script modules do not have names (their names are empty
     /// strings).
+    ///
+    /// If the module has a [provenance](Self::provenance) record, this
+    /// function also updates the record's name.
     #[inline(always)]
     pub fn rename(&self, name: impl AsRef<str>) {
-        self.id.set_name(String::from(name.as_ref()))
+        let name = name.as_ref();
+
+        ScriptProvenance::update(self.id, |provenance| provenance.set_name(name));
+
+        self.id.set_name(String::from(name))
+    }
+
+    /// Returns a copy of the module's provenance record, if set.
+    ///
+    /// See [ScriptProvenance] for details.
+    #[inline(always)]
+    pub fn provenance(&self) -> Option<ScriptProvenance> {
+        ScriptProvenance::of(self.id)
+    }
+
+    /// Sets the provenance record of the module, replacing the previous one.
+    ///
+    /// The module's name is set to the [name](ScriptProvenance::name) of the
+    /// record. The record's [text hash](ScriptProvenance::text_hash) is
+    /// assigned by the module.
+    ///
+    /// If the `provenance` argument is None, the function removes the
+    /// module's provenance record but keeps the module's name.
+    pub fn set_provenance(&self, provenance: Option<ScriptProvenance>) {
+        let Some(mut provenance) = provenance else {
+            ScriptProvenance::register(self.id, None);
+            return;
+        };
+
+        let text_hash = self
+            .history
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+            .text_hash();
+
+        provenance.set_text_hash(text_hash);
+
+        self.id.set_name(String::from(provenance.name()));
+
+        ScriptProvenance::register(self.id, Some(provenance));
     }
 
     /// Requests access for [read operations](ScriptModule#available-operations).
@@ -406,6 +538,9 @@ impl<H: TaskHandle> ScriptModule<H> {
             id: self.id,
             package: self.package,
             task,
+            history: &self.history,
+            lints: &self.lints,
+            stats: &self.stats,
         })
     }
 
@@ -428,6 +563,9 @@ impl<H: TaskHandle> ScriptModule<H> {
             id: self.id,
             package: self.package,
             task,
+            history: &self.history,
+            lints: &self.lints,
+            stats: &self.stats,
         })
     }
 
@@ -461,6 +599,9 @@ impl<H: TaskHandle> ScriptModule<H> {
             id: self.id,
             package: self.package,
             task,
+            history: &self.history,
+            lints: &self.lints,
+            stats: &self.stats,
         })
     }
 
@@ -483,9 +624,87 @@ impl<H: TaskHandle> ScriptModule<H> {
             id: self.id,
             package: self.package,
             task,
+            history: &self.history,
+            lints: &self.lints,
+            stats: &self.stats,
         })
     }
 
+    /// Returns an immutable view of the module's content that is detached
+    /// from the module's access guards.
+    ///
+    /// The snapshot's queries never block the module's writers and are never
+    /// interrupted by them, at the cost of possibly serving slightly stale
+    /// results: if the module is currently locked by a writer (or a writer
+    /// with a higher priority is pending), the function returns the previously
+    /// taken snapshot without waiting for the writer. Otherwise, the function
+    /// returns a snapshot of the module's current content, reusing the
+    /// previous snapshot if the content has not changed since then.
+    ///
+    /// Taking a snapshot of the new content involves a full analysis of the
+    /// module's copy, which the function performs in the current thread.
+    ///
+    /// The function blocks only if the module has no snapshots yet and is
+    /// locked by a writer. It returns an
+    /// [Interrupted](crate::analysis::ModuleError::Interrupted) error only in
+    /// this case, if the module's access is [denied](Self::deny_access).
+    ///
+    /// See [ModuleSnapshot] for details.
+    pub fn snapshot(&self) -> ModuleResult<ModuleSnapshot<H>> {
+        let mut cache = self
+            .snapshot
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+
+        let handle = H::default();
+
+        loop {
+            let read = match self.try_read(&handle, 0) {
+                Ok(read) => read,
+
+                Err(_) => match cache.as_ref() {
+                    Some(snapshot) => return Ok(snapshot.clone()),
+
+                    None => match self.read(&handle, 0) {
+                        Ok(read) => read,
+                        Err(ModuleError::Interrupted(_)) if self.is_access_allowed() => continue,
+                        Err(error) => return Err(error),
+                    },
+                },
+            };
+
+            let key = match SnapshotKey::capture(&read) {
+                Ok(key) => key,
+
+                Err(ModuleError::Interrupted(_)) => match cache.as_ref() {
+                    Some(snapshot) => return Ok(snapshot.clone()),
+                    None => continue,
+                },
+
+                Err(error) => return Err(error),
+            };
+
+            if let Some(snapshot) = cache.as_ref() {
+                if snapshot.key() == &key {
+                    return Ok(snapshot.clone());
+                }
+            }
+
+            let text = read.read_doc().substring(..).into_owned();
+            let passes = read.lock_lints().passes().to_vec();
+
+            // The analysis of the copy does not need the module's grant.
+            drop(read);
+
+            let module = Self::new(self.package, text);
+            let snapshot = ModuleSnapshot::new(self.id, module, key, &passes);
+
+            *cache = Some(snapshot.clone());
+
+            return Ok(snapshot);
+        }
+    }
+
     /// Reverts the [deny_access](Self::deny_access) action back to its default
     /// state, enabling read/write operation requests.
     #[inline(always)]
@@ -517,4 +736,46 @@ impl<H: TaskHandle> ScriptModule<H> {
     pub fn is_access_allowed(&self) -> bool {
         self.analyzer.get_access_level() < TaskPriority::MAX
     }
+
+    /// Enables or disables the collection of the module's query statistics.
+    ///
+    /// When enabled, the module measures the wall time of the
+    /// [diagnostics](crate::analysis::ModuleRead::diagnostics),
+    /// [completions](crate::analysis::ModuleWrite::completions),
+    /// [symbols](crate::analysis::ModuleRead::symbols), and
+    /// [compile](crate::analysis::ModuleRead::compile) queries. You can
+    /// retrieve the collected statistics using the [stats](Self::stats)
+    /// function.
+    ///
+    /// The collection is disabled by default. When disabled, the module does
+    /// not access the system clock at all, so the collection does not add
+    /// any overhead, and the module remains usable on targets without a
+    /// clock (e.g., `wasm32-unknown-unknown`).
+    ///
+    /// Disabling the collection does not reset already collected statistics.
+    #[inline(always)]
+    pub fn set_stats_enabled(&self, enabled: bool) {
+        self.stats.set_enabled(enabled);
+    }
+
+    /// Returns true if the collection of the module's query statistics is
+    /// enabled by the [set_stats_enabled](Self::set_stats_enabled) function.
+    #[inline(always)]
+    pub fn is_stats_enabled(&self) -> bool {
+        self.stats.is_enabled()
+    }
+
+    /// Returns a snapshot of the query statistics collected so far.
+    ///
+    /// See [set_stats_enabled](Self::set_stats_enabled) for details.
+    #[inline(always)]
+    pub fn stats(&self) -> ModuleStats {
+        self.stats.snapshot()
+    }
+
+    /// Clears the query statistics collected so far.
+    #[inline(always)]
+    pub fn reset_stats(&self) {
+        self.stats.reset();
+    }
 }
@@ -32,7 +32,10 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::ops::Deref;
+use std::{
+    ops::Deref,
+    sync::{Mutex, MutexGuard},
+};
 
 use ahash::RandomState;
 use lady_deirdre::{
@@ -40,29 +43,40 @@ use lady_deirdre::{
         AbstractTask,
         AnalysisTask,
         DocumentReadGuard,
+        Revision,
         SemanticAccess,
         TaskHandle,
         TriggerHandle,
     },
     arena::{Id, Identifiable},
-    lexis::ToSpan,
+    lexis::{SiteSpan, ToSite, ToSpan},
     sync::Shared,
     syntax::SyntaxTree,
 };
 
 use crate::{
     analysis::{
-        symbols::{LookupOptions, ModuleSymbol, SymbolsLookup},
+        history::{hash_text, EditHistory},
+        inline::VarInliner,
+        lint::{run_lints, ModuleLints},
+        stats::{QueryKind, StatsCollector},
+        symbols::{LookupOptions, ModuleSymbol, SymbolKind, SymbolsLookup, VarSymbol},
+        Bookmark,
+        DeadCodeReport,
+        DefinitionTarget,
         DiagnosticsDepth,
+        InlinePlan,
+        ModuleAnnotation,
         ModuleDiagnostics,
         ModuleError,
+        ModuleMetadata,
         ModuleResult,
         ModuleResultEx,
         ModuleText,
     },
     interpret::ScriptFn,
     report::system_panic,
-    runtime::{PackageMeta, ScriptOrigin},
+    runtime::{PackageMeta, ScriptOrigin, ScriptProvenance, TypeHint},
     syntax::{PolyRefOrigin, ScriptNode, SpanBounds},
 };
 
@@ -77,6 +91,9 @@ pub struct ModuleReadGuard<'a, H: TaskHandle = TriggerHandle> {
     pub(super) id: Id,
     pub(super) package: &'static PackageMeta,
     pub(super) task: AnalysisTask<'a, ScriptNode, H, RandomState>,
+    pub(super) history: &'a Mutex<EditHistory>,
+    pub(super) lints: &'a Mutex<ModuleLints<H>>,
+    pub(super) stats: &'a StatsCollector,
 }
 
 impl<'a, H: TaskHandle> Identifiable for ModuleReadGuard<'a, H> {
@@ -100,6 +117,26 @@ impl<'a, H: TaskHandle> ModuleReadSealed<H> for ModuleReadGuard<'a, H> {
     fn task(&self) -> &Self::Task {
         &self.task
     }
+
+    #[inline(always)]
+    fn history(&self) -> &Mutex<EditHistory> {
+        self.history
+    }
+
+    #[inline(always)]
+    fn lints(&self) -> &Mutex<ModuleLints<H>> {
+        self.lints
+    }
+
+    #[inline(always)]
+    fn stats(&self) -> &StatsCollector {
+        self.stats
+    }
+
+    #[inline(always)]
+    fn lint(&self, diagnostics: ModuleDiagnostics) -> ModuleResult<ModuleDiagnostics> {
+        run_lints(self, diagnostics)
+    }
 }
 
 /// A set of read functions for the
@@ -138,6 +175,194 @@ pub trait ModuleRead<H: TaskHandle>: Identifiable + ModuleReadSealed<H> {
         }
     }
 
+    /// Returns the current revision number of the script module's source code
+    /// text.
+    ///
+    /// The text revision of a newly created module is zero. Each source code
+    /// edit increments this number (see [edit](crate::analysis::ModuleWrite::edit)).
+    #[inline(always)]
+    fn text_revision(&self) -> Revision {
+        self.lock_history().revision()
+    }
+
+    /// Returns a 64-bit hash of the script module's source code text.
+    ///
+    /// The hash depends only on the text content: two modules with the same
+    /// text have the same hash regardless of their names, and the value is
+    /// stable across the process runs and the crate builds. Thus, you can use
+    /// it as a persistent cache key of the module's content.
+    ///
+    /// The module computes the hash once per [text revision](Self::text_revision).
+    /// The module's [provenance](crate::runtime::ScriptProvenance) record
+    /// receives the computed value.
+    fn text_hash(&self) -> u64 {
+        if let Some(text_hash) = self.lock_history().text_hash() {
+            return text_hash;
+        }
+
+        let text_hash = hash_text(self.read_doc().deref());
+
+        self.lock_history().set_text_hash(text_hash);
+
+        ScriptProvenance::update(self.id(), |provenance| {
+            provenance.set_text_hash(Some(text_hash))
+        });
+
+        text_hash
+    }
+
+    /// Maps the `span` of the source code text of the specified text
+    /// `revision` to the corresponding span of the current text.
+    ///
+    /// Using this function, you can carry selections, breakpoints, and other
+    /// annotation anchors across source code edits without looking up the
+    /// script constructions again.
+    ///
+    /// The `span` is a range of Unicode character offsets in the text of the
+    /// `revision`. The edits that occur before the span shift it, and the
+    /// edits that occur after the span leave it unchanged. Text inserted
+    /// exactly at the span's start or end boundary goes outside of the span.
+    ///
+    /// The function returns None if any of the later edits rewrote a fragment
+    /// inside the span, or if the `revision` is outside of the module's edit
+    /// history retention window. By default, the module remembers the last
+    /// 1024 edits; you can change this number using the
+    /// [set_edit_history_capacity](crate::analysis::ModuleWrite::set_edit_history_capacity)
+    /// function.
+    ///
+    /// ```rust
+    /// # use ad_astra::{
+    /// #     analysis::{ModuleRead, ModuleWrite, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::analysis::TriggerHandle,
+    /// #     runtime::ScriptPackage,
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::new(Package::meta(), "let foo = 10;");
+    /// let handle = TriggerHandle::new();
+    ///
+    /// let mut write = module.write(&handle, 1).unwrap();
+    ///
+    /// let revision = write.text_revision();
+    ///
+    /// // The span of the `10` literal.
+    /// let span = 10..12;
+    ///
+    /// let _ = write.edit(4..7, "foo_bar").unwrap();
+    ///
+    /// assert_eq!(write.remap_span(span.clone(), revision), Some(14..16));
+    ///
+    /// let _ = write.edit(15..16, "5").unwrap();
+    ///
+    /// assert_eq!(write.remap_span(span, revision), None);
+    /// ```
+    #[inline(always)]
+    fn remap_span(&self, span: SiteSpan, revision: Revision) -> Option<SiteSpan> {
+        self.lock_history().remap(span, revision)
+    }
+
+    /// Maps the `spans` of the source code text from which the `script_fn`
+    /// was [compiled](Self::compile) to the spans of the current text.
+    ///
+    /// Using this function, you can carry the data recorded against a compiled
+    /// script function, such as breakpoints and coverage, across the module's
+    /// source code edits. The [Bookmark] result of each span tells whether
+    /// the later edits touched the spanned fragment: the data of the
+    /// [edited](Bookmark::Edited) fragments is unreliable.
+    ///
+    /// The `spans` are ranges of Unicode character offsets in the compiled
+    /// text (see [ScriptFn::text_revision]). The edits that occur before a span
+    /// shift it, and the edits that occur after the span leave it unchanged.
+    ///
+    /// The result vector has the same length as the `spans` sequence. Each
+    /// bookmark is [lost](Bookmark::Lost) if the `script_fn` was not compiled
+    /// from this module, or if the compiled revision is outside of the module's
+    /// edit history retention window.
+    ///
+    /// ```rust
+    /// # use ad_astra::{
+    /// #     analysis::{Bookmark, ModuleRead, ModuleWrite, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::analysis::TriggerHandle,
+    /// #     runtime::ScriptPackage,
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::new(Package::meta(), "let x = 1;\nlet y = 2;");
+    /// let handle = TriggerHandle::new();
+    ///
+    /// let script_fn = module.read(&handle, 1).unwrap().compile().unwrap();
+    ///
+    /// let mut write = module.write(&handle, 1).unwrap();
+    ///
+    /// let _ = write.edit(0..0, "// Header.\n").unwrap();
+    /// let _ = write.edit(30..31, "10").unwrap();
+    ///
+    /// assert_eq!(
+    ///     write.bookmarks_to_current(&script_fn, [0..10, 11..21]),
+    ///     [Bookmark::Intact(11..21), Bookmark::Edited(22..33)],
+    /// );
+    /// ```
+    fn bookmarks_to_current(
+        &self,
+        script_fn: &ScriptFn,
+        spans: impl IntoIterator<Item = SiteSpan>,
+    ) -> Vec<Bookmark> {
+        self.bookmarks(script_fn, spans, true)
+    }
+
+    /// Maps the `spans` of the current source code text to the spans of the
+    /// text from which the `script_fn` was [compiled](Self::compile).
+    ///
+    /// This function is the inverse of the
+    /// [bookmarks_to_current](Self::bookmarks_to_current) function. For
+    /// example, when the user sets a breakpoint in the edited text, you can
+    /// use this function to find the corresponding site of the compiled
+    /// script function. The bookmark is [edited](Bookmark::Edited) if the
+    /// spanned fragment did not exist in the compiled text entirely.
+    fn bookmarks_to_compiled(
+        &self,
+        script_fn: &ScriptFn,
+        spans: impl IntoIterator<Item = SiteSpan>,
+    ) -> Vec<Bookmark> {
+        self.bookmarks(script_fn, spans, false)
+    }
+
+    /// Returns the [annotations](ModuleAnnotation) of the script module that
+    /// intersect the specified `span`.
+    ///
+    /// The `span` argument can be an absolute Unicode character range, such
+    /// as `10..20`, a [line-column](lady_deirdre::lexis::Position) range, or
+    /// a [ScriptOrigin](crate::runtime::ScriptOrigin) instance. The `..`
+    /// range returns all annotations of the module. An empty span (e.g.,
+    /// `10..10`) returns the annotations that contain the spanned site.
+    ///
+    /// The annotations are ordered by the start of their spans.
+    ///
+    /// The function returns a [ModuleError::Cursor] error if the provided
+    /// `span` is not [valid](ToSpan::is_valid_span) for this module.
+    fn annotations(&self, span: impl ToSpan) -> ModuleResult<Vec<ModuleAnnotation>> {
+        let id = self.id();
+
+        let span = {
+            let doc_read = self.read_doc();
+
+            match span.to_site_span(doc_read.deref()) {
+                Some(span) => span,
+                None => return Err(ModuleError::Cursor(id)),
+            }
+        };
+
+        Ok(self.lock_history().annotations().query(&span))
+    }
+
     /// Computes script module diagnostics (errors and warnings).
     ///
     /// The returned [ModuleDiagnostics] object is a collection of inferred
@@ -150,6 +375,8 @@ pub trait ModuleRead<H: TaskHandle>: Identifiable + ModuleReadSealed<H> {
     /// analysis), and 3 (deep semantic analysis). For details, see the
     /// [DiagnosticsDepth] documentation.
     fn diagnostics(&self, depth: DiagnosticsDepth) -> ModuleResult<ModuleDiagnostics> {
+        let _timer = self.stats().measure(QueryKind::Diagnostics(depth));
+
         let doc_read = self.read_doc();
 
         let ScriptNode::Root { semantics, .. } = doc_read.deref().root() else {
@@ -181,7 +408,7 @@ pub trait ModuleRead<H: TaskHandle>: Identifiable + ModuleReadSealed<H> {
                     .snapshot(self.task())
                     .into_module_result(id)?;
 
-                Ok(ModuleDiagnostics {
+                self.lint(ModuleDiagnostics {
                     id,
                     issues: snapshot.issues.clone(),
                     depth,
@@ -195,7 +422,7 @@ pub trait ModuleRead<H: TaskHandle>: Identifiable + ModuleReadSealed<H> {
                     .snapshot(self.task())
                     .into_module_result(id)?;
 
-                Ok(ModuleDiagnostics {
+                self.lint(ModuleDiagnostics {
                     id,
                     issues: snapshot.issues.clone(),
                     depth,
@@ -212,6 +439,117 @@ pub trait ModuleRead<H: TaskHandle>: Identifiable + ModuleReadSealed<H> {
         }
     }
 
+    /// Returns the module's metadata header: the `//!` comments at the
+    /// beginning of the source code that document the module and declare
+    /// the engine version and the packages the script requires.
+    ///
+    /// The analyzer reports unsatisfied requirements by the
+    /// [diagnostics](Self::diagnostics) function of depth `2`. See
+    /// [ModuleMetadata] for details.
+    ///
+    /// ```rust
+    /// # use ad_astra::{
+    /// #     analysis::{ModuleRead, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::analysis::TriggerHandle,
+    /// #     runtime::ScriptPackage,
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::<TriggerHandle>::new(
+    ///     Package::meta(),
+    ///     "//! Greets the user.\n//! requires package math >= 1.2\n\nlet x = 10;",
+    /// );
+    ///
+    /// let handle = TriggerHandle::new();
+    /// let read = module.read(&handle, 1).unwrap();
+    ///
+    /// let metadata = read.metadata().unwrap();
+    ///
+    /// assert_eq!(metadata.doc(), "Greets the user.");
+    /// assert_eq!(metadata.packages()[0].name(), "math");
+    /// assert_eq!(metadata.packages()[0].version(), Some(">= 1.2"));
+    /// ```
+    fn metadata(&self) -> ModuleResult<ModuleMetadata> {
+        let doc_read = self.read_doc();
+
+        let ScriptNode::Root { semantics, .. } = doc_read.deref().root() else {
+            system_panic!("Incorrect root variant.");
+        };
+
+        let id = self.id();
+
+        let (_, module_header) = semantics
+            .get()
+            .into_module_result(id)?
+            .module_header
+            .snapshot(self.task())
+            .into_module_result(id)?;
+
+        Ok(module_header.metadata.as_ref().clone())
+    }
+
+    /// Computes the module's dead code report: script functions that are never
+    /// used, struct entries that are never read, and redundant imports.
+    ///
+    /// The returned [DeadCodeReport] object is a collection of the unused
+    /// constructions ordered by their appearance in the source code. The same
+    /// items are also reported as [hints](crate::analysis::IssueSeverity::Hint)
+    /// by the [diagnostics](Self::diagnostics) function of depth `3`.
+    ///
+    /// To exclude specific names from the report, use the
+    /// [set_dead_code_allow](crate::analysis::ModuleWrite::set_dead_code_allow)
+    /// function.
+    ///
+    /// ```rust
+    /// # use ad_astra::{
+    /// #     analysis::{DeadCodeKind, ModuleRead, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::analysis::TriggerHandle,
+    /// #     runtime::ScriptPackage,
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::<TriggerHandle>::new(
+    ///     Package::meta(),
+    ///     "let foo = fn() {};",
+    /// );
+    ///
+    /// let report = module.read(&TriggerHandle::new(), 1).unwrap().dead_code().unwrap();
+    ///
+    /// assert_eq!(report.len(), 1);
+    /// assert_eq!(report.iter().next().unwrap().kind, DeadCodeKind::UnusedFunction);
+    /// ```
+    fn dead_code(&self) -> ModuleResult<DeadCodeReport> {
+        let doc_read = self.read_doc();
+
+        let ScriptNode::Root { semantics, .. } = doc_read.deref().root() else {
+            system_panic!("Incorrect root variant.");
+        };
+
+        let id = self.id();
+
+        let root_semantics = semantics.get().into_module_result(id)?;
+
+        let (revision, snapshot) = root_semantics
+            .dead_code
+            .snapshot(self.task())
+            .into_module_result(id)?;
+
+        Ok(DeadCodeReport::new(
+            id,
+            doc_read.deref(),
+            snapshot.issues.as_ref().iter(),
+            revision,
+        ))
+    }
+
     /// Looks up syntax constructions within the specified `span` (source code
     /// range) based on the `options` filter.
     ///
@@ -239,6 +577,8 @@ pub trait ModuleRead<H: TaskHandle>: Identifiable + ModuleReadSealed<H> {
         span: impl ToSpan,
         options: LookupOptions,
     ) -> ModuleResult<Vec<ModuleSymbol>> {
+        let _timer = self.stats().measure(QueryKind::Symbols);
+
         let doc_read = self.read_doc();
 
         let span = {
@@ -253,6 +593,142 @@ pub trait ModuleRead<H: TaskHandle>: Identifiable + ModuleReadSealed<H> {
         Ok(SymbolsLookup::lookup(doc_read.deref(), span, options))
     }
 
+    /// Looks up the definition of the type of the expression, variable, or
+    /// struct entry at the specified `position` in the source code.
+    ///
+    /// If the inferred type is a script struct, the function returns the
+    /// origin of the struct constructor. If the type is a script function, the
+    /// function returns the origin of the function declaration. For the
+    /// exported Rust types, the function returns the [RustOrigin](crate::runtime::RustOrigin)
+    /// of the exported construct.
+    ///
+    /// The `position` argument can be an absolute Unicode character index or
+    /// a [line-column](lady_deirdre::lexis::Position) position.
+    ///
+    /// The function returns None if there is no suitable construction at the
+    /// specified position, or if the analyzer is unable to infer its type
+    /// precisely enough (e.g., if the type is dynamic or ambiguous).
+    ///
+    /// The function may return an [Interrupted](ModuleError::Interrupted)
+    /// error if the type inference is interrupted by the revocation of the
+    /// module content access guard, and a [ModuleError::Cursor] error if the
+    /// `position` is not [valid](ToSite::is_valid_site) for this module.
+    fn type_definition(&self, position: impl ToSite) -> ModuleResult<Option<DefinitionTarget>>
+    where
+        Self: Sized,
+    {
+        const TYPED_SYMBOLS: u32 = (SymbolKind::Var as u32)
+            | (SymbolKind::Entry as u32)
+            | (SymbolKind::Ident as u32)
+            | (SymbolKind::Field as u32)
+            | (SymbolKind::Call as u32);
+
+        let site = {
+            let doc_read = self.read_doc();
+
+            match position.to_site(doc_read.deref()) {
+                Some(site) => site,
+                None => return Err(ModuleError::Cursor(self.id())),
+            }
+        };
+
+        let symbols = self.symbols(site..site, LookupOptions::new().filter(TYPED_SYMBOLS))?;
+
+        let description = match symbols.first() {
+            Some(ModuleSymbol::Var(symbol)) => symbol.var_type(self)?,
+            Some(ModuleSymbol::Entry(symbol)) => symbol.ty(self)?,
+            Some(symbol) => symbol.expr_ty(self)?,
+            None => return Ok(None),
+        };
+
+        match &description.impl_symbol {
+            ModuleSymbol::Struct(symbol) => {
+                return Ok(Some(DefinitionTarget::Script(symbol.origin(self))))
+            }
+
+            ModuleSymbol::Fn(symbol) => {
+                return Ok(Some(DefinitionTarget::Script(symbol.origin(self))))
+            }
+
+            ModuleSymbol::Enum(symbol) => {
+                return Ok(Some(DefinitionTarget::Script(symbol.origin(self))))
+            }
+
+            _ => (),
+        }
+
+        let TypeHint::Type(ty) = description.type_hint else {
+            return Ok(None);
+        };
+
+        if ty.is_nil() || ty.is_dynamic() {
+            return Ok(None);
+        }
+
+        let origin = ty.origin();
+
+        if origin.is_nil() {
+            return Ok(None);
+        }
+
+        Ok(Some(DefinitionTarget::Rust(origin)))
+    }
+
+    /// Computes the source code edits that inline the let-statement variable
+    /// `symbol` into each of its reads and remove the declaration.
+    ///
+    /// The function returns [InlinePlan::Refused] if the transformation could
+    /// change the script's behavior: when the variable is reassigned or
+    /// mutated, when the initialization expression may have side effects
+    /// (e.g., it calls an exported function not marked as pure), or when
+    /// inlining would move the expression evaluation across a mutation of
+    /// a variable the expression depends on.
+    ///
+    /// The inlined expression is parenthesized where operator precedence
+    /// requires it.
+    ///
+    /// ```rust
+    /// # use ad_astra::{
+    /// #     analysis::{symbols::ModuleSymbol, InlinePlan, ModuleRead, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::analysis::TriggerHandle,
+    /// #     runtime::ScriptPackage,
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::<TriggerHandle>::new(
+    ///     Package::meta(),
+    ///     "let x = 1 + 2; let y = x * 3;",
+    /// );
+    ///
+    /// let handle = TriggerHandle::new();
+    /// let module_read = module.read(&handle, 1).unwrap();
+    ///
+    /// let Some(ModuleSymbol::Var(x)) = module_read.symbols(4..5, Default::default())
+    ///     .unwrap()
+    ///     .into_iter()
+    ///     .next()
+    /// else {
+    ///     panic!();
+    /// };
+    ///
+    /// let InlinePlan::Edits(edits) = module_read.inline_variable(&x).unwrap() else {
+    ///     panic!();
+    /// };
+    ///
+    /// assert_eq!(edits.len(), 2);
+    /// assert_eq!(edits[1].text, "(1 + 2)");
+    /// ```
+    fn inline_variable(&self, symbol: &VarSymbol) -> ModuleResult<InlinePlan>
+    where
+        Self: Sized,
+    {
+        VarInliner::plan(self, symbol.var_ref())
+    }
+
     /// Returns a range of the source code without the header and footer
     /// comments.
     ///
@@ -310,11 +786,25 @@ pub trait ModuleRead<H: TaskHandle>: Identifiable + ModuleReadSealed<H> {
     /// recommended to avoid running ScriptFn objects in production that have
     /// been compiled from script modules with diagnostic errors.
     fn compile(&self) -> ModuleResult<ScriptFn> {
+        let _timer = self.stats().measure(QueryKind::Compile);
+
+        let _ = self.text_hash();
+
+        let revision = self.text_revision();
+
         let task = self.task();
         let doc_read = self.read_doc();
 
-        ScriptFn::compile(task, doc_read.deref(), &doc_read.root_node_ref())
-            .into_module_result(self.id())
+        let mut script_fn = ScriptFn::compile(task, doc_read.deref(), &doc_read.root_node_ref())
+            .into_module_result(self.id())?;
+
+        if let Some(provenance) = ScriptProvenance::of(self.id()) {
+            script_fn.attach_provenance(&Shared::new(provenance));
+        }
+
+        script_fn.attach_revision(self.id(), revision);
+
+        Ok(script_fn)
     }
 }
 
@@ -323,6 +813,60 @@ pub trait ModuleReadSealed<H: TaskHandle>: Identifiable {
 
     fn task(&self) -> &Self::Task;
 
+    fn history(&self) -> &Mutex<EditHistory>;
+
+    #[inline(always)]
+    fn lock_history(&self) -> MutexGuard<EditHistory> {
+        self.history()
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+    }
+
+    fn bookmarks(
+        &self,
+        script_fn: &ScriptFn,
+        spans: impl IntoIterator<Item = SiteSpan>,
+        forward: bool,
+    ) -> Vec<Bookmark> {
+        let revision = match script_fn.compiled_from() {
+            Some((id, revision)) if id == self.id() => Some(revision),
+            _ => None,
+        };
+
+        let history = self.lock_history();
+
+        spans
+            .into_iter()
+            .map(|span| match revision {
+                Some(revision) => history.bookmark(span, revision, forward),
+                None => Bookmark::Lost,
+            })
+            .collect()
+    }
+
+    fn lints(&self) -> &Mutex<ModuleLints<H>>;
+
+    fn stats(&self) -> &StatsCollector;
+
+    #[inline(always)]
+    fn lock_lints(&self) -> MutexGuard<ModuleLints<H>> {
+        self.lints()
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+    }
+
+    // Merges the issues of the module's lint passes into the built-in
+    // diagnostics. The write guard only reuses the previously merged issues.
+    #[inline(always)]
+    fn lint(&self, diagnostics: ModuleDiagnostics) -> ModuleResult<ModuleDiagnostics> {
+        let text_revision = self.lock_history().revision();
+
+        Ok(self
+            .lock_lints()
+            .cached(&diagnostics, text_revision)
+            .unwrap_or(diagnostics))
+    }
+
     #[track_caller]
     #[inline(always)]
     fn read_doc(&self) -> DocumentReadGuard<ScriptNode, RandomState> {
@@ -0,0 +1,460 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+
+use lady_deirdre::{
+    analysis::{AbstractTask, Revision, SemanticAccess, TaskHandle, TriggerHandle},
+    arena::{Id, Identifiable},
+};
+
+use crate::{
+    analysis::{
+        read::ModuleReadSealed,
+        AnalysisConfig,
+        LintPass,
+        ModuleRead,
+        ModuleReadGuard,
+        ModuleResult,
+        ModuleResultEx,
+        ModuleWrite,
+        ScriptModule,
+    },
+    format::format_script_path,
+    report::system_panic,
+    runtime::ScriptProvenance,
+    semantics::DeadCodeAllow,
+};
+
+/// An immutable view of the [ScriptModule] content pinned to a particular
+/// text revision.
+///
+/// Created by the [ScriptModule::snapshot] function.
+///
+/// Unlike the [read](ScriptModule::read) guards, the snapshot is detached from
+/// the module it was taken from: it never blocks the module's writers, and the
+/// writers never interrupt the snapshot's queries. The object is cheap to
+/// clone, and you can freely move its clones between threads.
+///
+/// ## Staleness
+///
+/// The snapshot reflects the module's source code text, the
+/// [analysis config](crate::analysis::ModuleWrite::set_analysis_config), the
+/// [dead code allow list](crate::analysis::ModuleWrite::set_dead_code_allow),
+/// and the [lint passes](crate::analysis::ModuleWrite::set_lint_passes) as of
+/// the module's text [revision](Self::revision). The module's later edits are
+/// not visible through the snapshot object.
+///
+/// The [ScriptModule::snapshot] function returns the snapshot of the latest
+/// revision if the module is not currently locked by a writer. Otherwise, it
+/// returns the previously taken snapshot, which may lag behind the module's
+/// content. Compare the snapshot's [revision](Self::revision) with the
+/// module's [text revision](ModuleRead::text_revision) if you need to know
+/// whether the results are up to date.
+///
+/// ## Symbols
+///
+/// The snapshot has its own [Id], distinct from the [origin](Self::origin)
+/// module's Id. The [symbols](crate::analysis::symbols::ModuleSymbol) and
+/// other analysis objects obtained from the snapshot's
+/// [read](Self::read) guard belong to the snapshot: they validate against the
+/// snapshot's read guard, but not against the origin module's guards.
+///
+/// Since the snapshot's text never changes, its read guard always reports the
+/// zero [text revision](ModuleRead::text_revision). Use the
+/// [ModuleSnapshot::revision] function to get the origin module's revision
+/// instead.
+///
+/// ```rust
+/// # use ad_astra::{
+/// #     analysis::{
+/// #         symbols::{LookupOptions, SymbolKind},
+/// #         ModuleRead,
+/// #         ScriptModule,
+/// #     },
+/// #     export,
+/// #     lady_deirdre::{analysis::TriggerHandle, arena::Identifiable},
+/// #     runtime::ScriptPackage,
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// let module = ScriptModule::<TriggerHandle>::new(Package::meta(), "let foo = 10;");
+///
+/// let snapshot = module.snapshot().unwrap();
+///
+/// assert_eq!(snapshot.origin(), module.id());
+/// assert_eq!(snapshot.revision(), 0);
+///
+/// let snapshot_read = snapshot.read();
+///
+/// let symbols = snapshot_read
+///     .symbols(.., LookupOptions::new().filter(SymbolKind::Var as u32))
+///     .unwrap();
+///
+/// assert!(symbols[0].is_valid(&snapshot_read));
+/// ```
+pub struct ModuleSnapshot<H: TaskHandle = TriggerHandle> {
+    inner: Arc<SnapshotInner<H>>,
+}
+
+impl<H: TaskHandle> Clone for ModuleSnapshot<H> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<H: TaskHandle> Debug for ModuleSnapshot<H> {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_fmt(format_args!(
+            "ModuleSnapshot({} @ {})",
+            format_script_path(self.inner.origin, Some(self.inner.module.package())),
+            self.inner.key.revision,
+        ))
+    }
+}
+
+impl<H: TaskHandle> Identifiable for ModuleSnapshot<H> {
+    #[inline(always)]
+    fn id(&self) -> Id {
+        self.inner.module.id()
+    }
+}
+
+impl<H: TaskHandle> ModuleSnapshot<H> {
+    // Creates a snapshot of the `origin` module's `text` and configuration.
+    //
+    // The function eagerly analyzes the snapshot's semantics such that the
+    // snapshot's queries are served from the warm caches.
+    pub(super) fn new(
+        origin: Id,
+        module: ScriptModule<H>,
+        key: SnapshotKey,
+        passes: &[&'static dyn LintPass<H>],
+    ) -> Self {
+        let handle = H::default();
+
+        match ScriptProvenance::of(origin) {
+            Some(provenance) => module.set_provenance(Some(provenance)),
+            None => module.rename(origin.name()),
+        }
+
+        {
+            let mut write = match module.write(&handle, 0) {
+                Ok(write) => write,
+                Err(error) => system_panic!("Snapshot creation failure. {error}",),
+            };
+
+            let setup = write
+                .set_analysis_config(key.config)
+                .and_then(|_| write.set_dead_code_allow(key.allow.patterns.iter()));
+
+            if let Err(error) = setup {
+                system_panic!("Snapshot creation failure. {error}",);
+            }
+
+            write.set_lint_passes(passes);
+        }
+
+        let snapshot = Self {
+            inner: Arc::new(SnapshotInner {
+                origin,
+                module,
+                handle,
+                key,
+            }),
+        };
+
+        let _ = snapshot.read().diagnostics(3);
+
+        snapshot
+    }
+
+    /// Returns the [Id] of the [ScriptModule] this snapshot was taken from.
+    #[inline(always)]
+    pub fn origin(&self) -> Id {
+        self.inner.origin
+    }
+
+    /// Returns the [text revision](ModuleRead::text_revision) of the origin
+    /// module at which the snapshot was taken.
+    #[inline(always)]
+    pub fn revision(&self) -> Revision {
+        self.inner.key.revision
+    }
+
+    /// Grants read access to the snapshot's content.
+    ///
+    /// Unlike the [ScriptModule::read] function, this function never blocks
+    /// and never fails, and the [ModuleRead] operations of the returned guard
+    /// never return the
+    /// [Interrupted](crate::analysis::ModuleError::Interrupted) error.
+    #[inline(always)]
+    pub fn read(&self) -> ModuleReadGuard<H> {
+        match self.inner.module.read(&self.inner.handle, 0) {
+            Ok(read) => read,
+            Err(error) => system_panic!("Snapshot access failure. {error}",),
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn key(&self) -> &SnapshotKey {
+        &self.inner.key
+    }
+}
+
+struct SnapshotInner<H: TaskHandle> {
+    origin: Id,
+    module: ScriptModule<H>,
+    // The handle of the snapshot's read guards. Since nobody else has access
+    // to this handle and nobody mutates the snapshot's module, the snapshot's
+    // guards are never revoked.
+    handle: H,
+    key: SnapshotKey,
+}
+
+// The module's state that the snapshot reflects. The snapshot is outdated
+// whenever any of these components changes.
+#[derive(Clone, PartialEq)]
+pub(super) struct SnapshotKey {
+    revision: Revision,
+    config: AnalysisConfig,
+    allow: DeadCodeAllow,
+    lints: usize,
+}
+
+impl SnapshotKey {
+    pub(super) fn capture<H: TaskHandle>(read: &ModuleReadGuard<H>) -> ModuleResult<Self> {
+        let id = read.id();
+        let task = read.task();
+
+        let (_, config) = task
+            .common()
+            .analysis_config
+            .snapshot(task)
+            .into_module_result(id)?;
+
+        let (_, allow) = task
+            .common()
+            .dead_code_allow
+            .snapshot(task)
+            .into_module_result(id)?;
+
+        Ok(Self {
+            revision: read.text_revision(),
+            config,
+            allow,
+            lints: read.lock_lints().generation(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use lady_deirdre::{analysis::TriggerHandle, arena::Identifiable, lexis::SourceCode};
+
+    use crate::{
+        analysis::{
+            symbols::{tests::TestPackage, LookupOptions, ModuleSymbol, SymbolKind},
+            ModuleRead,
+            ModuleWrite,
+            ScriptModule,
+        },
+        runtime::ScriptPackage,
+    };
+
+    #[test]
+    fn test_snapshot_stress() {
+        static WRITES: usize = 1000;
+        static READERS: usize = 4;
+
+        fn text(value: usize) -> String {
+            format!("let x = {value};\nlet y = x + {value};\n")
+        }
+
+        let module = Arc::new(ScriptModule::new(TestPackage::meta(), text(0)));
+
+        let writer = {
+            let module = module.clone();
+
+            thread::spawn(move || {
+                let mut value = 1;
+
+                while value <= WRITES {
+                    let handle = TriggerHandle::new();
+
+                    let Ok(mut write) = module.write(&handle, 1) else {
+                        continue;
+                    };
+
+                    if write.edit(.., text(value)).is_err() {
+                        continue;
+                    }
+
+                    value += 1;
+                }
+            })
+        };
+
+        let readers = (0..READERS)
+            .map(|_| {
+                let module = module.clone();
+
+                thread::spawn(move || {
+                    let mut last_revision = 0;
+
+                    loop {
+                        let snapshot = module.snapshot().unwrap();
+
+                        assert!(snapshot.revision() >= last_revision);
+                        assert_eq!(snapshot.origin(), module.id());
+                        assert_ne!(snapshot.id(), module.id());
+
+                        last_revision = snapshot.revision();
+
+                        let read = snapshot.read();
+
+                        let source = read.text().substring(..).into_owned();
+                        let values = source
+                            .split(|ch: char| !ch.is_ascii_digit())
+                            .filter(|value| !value.is_empty())
+                            .collect::<Vec<_>>();
+
+                        assert_eq!(values.len(), 2, "Torn snapshot text: {source}");
+                        assert_eq!(values[0], values[1], "Torn snapshot text: {source}");
+
+                        let diagnostics = read.diagnostics(3).unwrap();
+
+                        assert!(diagnostics.is_empty(), "Snapshot diagnostics: {source}");
+
+                        let symbols = read
+                            .symbols(.., LookupOptions::new().filter(SymbolKind::Var as u32))
+                            .unwrap();
+
+                        assert!(!symbols.is_empty());
+
+                        for symbol in &symbols {
+                            assert!(symbol.is_valid(&read));
+
+                            let ModuleSymbol::Var(var) = symbol else {
+                                panic!("Unexpected symbol.");
+                            };
+
+                            let _ = var.var_name(&read).unwrap();
+                        }
+
+                        if values[0] == WRITES.to_string() {
+                            break;
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        writer.join().unwrap();
+
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        let snapshot = module.snapshot().unwrap();
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        assert_eq!(snapshot.revision(), read.text_revision());
+        assert_eq!(
+            snapshot.read().text().substring(..),
+            read.text().substring(..),
+        );
+
+        let symbols = snapshot
+            .read()
+            .symbols(.., LookupOptions::new().filter(SymbolKind::Var as u32))
+            .unwrap();
+
+        assert!(!symbols[0].is_valid(&read));
+    }
+
+    #[test]
+    fn test_snapshot_pending_writer() {
+        let module = Arc::new(ScriptModule::new(TestPackage::meta(), "let x = 1;"));
+
+        let initial = module.snapshot().unwrap();
+
+        let handle = TriggerHandle::new();
+        let mut write = module.write(&handle, 1).unwrap();
+
+        write.edit(.., "let x = 2;").unwrap();
+
+        // The writer still holds the module, so the snapshot query does not
+        // wait for it and serves the previous revision.
+        let stale = thread::spawn({
+            let module = module.clone();
+
+            move || {
+                let snapshot = module.snapshot().unwrap();
+                let read = snapshot.read();
+
+                assert!(read.diagnostics(3).unwrap().is_empty());
+
+                let text = read.text().substring(..).into_owned();
+
+                (snapshot.revision(), text)
+            }
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(stale, (initial.revision(), String::from("let x = 1;")));
+
+        drop(write);
+
+        let fresh = module.snapshot().unwrap();
+
+        assert!(fresh.revision() > initial.revision());
+        assert_eq!(fresh.read().text().substring(..), "let x = 2;");
+        assert_eq!(initial.read().text().substring(..), "let x = 1;");
+    }
+}
@@ -0,0 +1,426 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    fmt::{Display, Formatter},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+        MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::analysis::DiagnosticsDepth;
+
+const KINDS: usize = 6;
+
+/// A kind of the [ScriptModule](crate::analysis::ScriptModule) query measured
+/// by the module's [stats](crate::analysis::ScriptModule::stats) collector.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub enum QueryKind {
+    /// The [diagnostics](crate::analysis::ModuleRead::diagnostics) query of
+    /// the specified depth (`1`, `2`, or `3`).
+    Diagnostics(DiagnosticsDepth),
+
+    /// The [completions](crate::analysis::ModuleWrite::completions) query.
+    Completions,
+
+    /// The [symbols](crate::analysis::ModuleRead::symbols) lookup query.
+    Symbols,
+
+    /// The [compile](crate::analysis::ModuleRead::compile) query.
+    Compile,
+}
+
+impl Display for QueryKind {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Diagnostics(depth) => formatter.write_fmt(format_args!("diagnostics-{depth}")),
+            Self::Completions => formatter.write_str("completions"),
+            Self::Symbols => formatter.write_str("symbols"),
+            Self::Compile => formatter.write_str("compile"),
+        }
+    }
+}
+
+impl QueryKind {
+    const ALL: [Self; KINDS] = [
+        Self::Diagnostics(1),
+        Self::Diagnostics(2),
+        Self::Diagnostics(3),
+        Self::Completions,
+        Self::Symbols,
+        Self::Compile,
+    ];
+
+    #[inline(always)]
+    fn index(self) -> Option<usize> {
+        match self {
+            Self::Diagnostics(depth @ 1..=3) => Some(depth as usize - 1),
+            Self::Diagnostics(_) => None,
+            Self::Completions => Some(3),
+            Self::Symbols => Some(4),
+            Self::Compile => Some(5),
+        }
+    }
+}
+
+/// Wall time measurements of a single [kind](QueryKind) of the script module
+/// queries.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub struct QueryStats {
+    /// The number of the measured queries.
+    pub count: u64,
+
+    /// The total wall time of the measured queries.
+    pub total: Duration,
+
+    /// The wall time of the most recent query.
+    pub last: Duration,
+}
+
+impl QueryStats {
+    /// Returns the average wall time of the measured queries, or zero if no
+    /// queries have been measured.
+    #[inline(always)]
+    pub fn average(&self) -> Duration {
+        match self.count {
+            0 => Duration::ZERO,
+            count => self.total / count.min(u32::MAX as u64) as u32,
+        }
+    }
+
+    #[inline(always)]
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.last = elapsed;
+    }
+
+    #[inline(always)]
+    fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+
+        self.count += other.count;
+        self.total += other.total;
+        self.last = other.last;
+    }
+}
+
+/// Per-query wall time statistics of the script module.
+///
+/// The object is returned by the
+/// [ScriptModule::stats](crate::analysis::ScriptModule::stats) function. The
+/// module collects the statistics only if the collection is enabled by the
+/// [ScriptModule::set_stats_enabled](crate::analysis::ScriptModule::set_stats_enabled)
+/// function.
+///
+/// The [Display] implementation of this object prints a table of the measured
+/// queries, which is useful for logging.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct ModuleStats {
+    queries: [QueryStats; KINDS],
+}
+
+impl Display for ModuleStats {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut is_first = true;
+
+        for (kind, stats) in self.iter() {
+            if stats.count == 0 {
+                continue;
+            }
+
+            match is_first {
+                true => is_first = false,
+                false => formatter.write_str("\n")?,
+            }
+
+            formatter.write_fmt(format_args!(
+                "{kind:<14} count: {:<8} total: {:<12?} average: {:<12?} last: {:?}",
+                stats.count,
+                stats.total,
+                stats.average(),
+                stats.last,
+            ))?;
+        }
+
+        if is_first {
+            formatter.write_str("no queries measured")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ModuleStats {
+    /// Returns the statistics of the specified query `kind`.
+    ///
+    /// For the unmeasured kinds (e.g., the diagnostics of unknown depth),
+    /// returns empty statistics.
+    #[inline(always)]
+    pub fn get(&self, kind: QueryKind) -> QueryStats {
+        match kind.index() {
+            Some(index) => self.queries[index],
+            None => QueryStats::default(),
+        }
+    }
+
+    /// Returns an iterator over the statistics of all query kinds, including
+    /// the kinds without measured queries.
+    #[inline(always)]
+    pub fn iter(&self) -> impl Iterator<Item = (QueryKind, QueryStats)> + '_ {
+        QueryKind::ALL.into_iter().zip(self.queries.iter().copied())
+    }
+
+    /// Returns the total number of the measured queries of all kinds.
+    #[inline(always)]
+    pub fn count(&self) -> u64 {
+        self.queries.iter().map(|stats| stats.count).sum()
+    }
+
+    /// Adds the statistics of the `other` object to this object.
+    ///
+    /// This function is useful for aggregating the statistics of multiple
+    /// modules. The [last](QueryStats::last) durations are taken from
+    /// the `other` object, if it has measured queries of the corresponding
+    /// kind.
+    pub fn merge(&mut self, other: &Self) {
+        for (this, other) in self.queries.iter_mut().zip(other.queries.iter()) {
+            this.merge(other);
+        }
+    }
+}
+
+// A collector of the ScriptModule query statistics.
+pub(super) struct StatsCollector {
+    enabled: AtomicBool,
+    stats: Mutex<ModuleStats>,
+}
+
+impl StatsCollector {
+    #[inline(always)]
+    pub(super) fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            stats: Mutex::new(ModuleStats::default()),
+        }
+    }
+
+    #[inline(always)]
+    pub(super) fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub(super) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub(super) fn snapshot(&self) -> ModuleStats {
+        self.lock().clone()
+    }
+
+    #[inline(always)]
+    pub(super) fn reset(&self) {
+        *self.lock() = ModuleStats::default();
+    }
+
+    // Starts measuring of the query. The measurement is recorded when the
+    // returned timer is dropped.
+    #[inline(always)]
+    pub(super) fn measure(&self, kind: QueryKind) -> QueryTimer {
+        QueryTimer {
+            collector: self,
+            start: self.start(kind),
+        }
+    }
+
+    // Starts measuring of the query without borrowing the collector. The
+    // measurement should be recorded manually using the "finish" function.
+    //
+    // If the collection is disabled, the function does not touch the clock.
+    #[inline(always)]
+    pub(super) fn start(&self, kind: QueryKind) -> QueryStart {
+        let start = match self.is_enabled() {
+            true => Some(Instant::now()),
+            false => None,
+        };
+
+        QueryStart { kind, start }
+    }
+
+    #[inline(always)]
+    pub(super) fn finish(&self, query: QueryStart) {
+        let Some(start) = query.start else {
+            return;
+        };
+
+        let Some(index) = query.kind.index() else {
+            return;
+        };
+
+        let elapsed = start.elapsed();
+
+        self.lock().queries[index].record(elapsed);
+    }
+
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<ModuleStats> {
+        self.stats
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+    }
+}
+
+pub(super) struct QueryStart {
+    kind: QueryKind,
+    start: Option<Instant>,
+}
+
+pub(super) struct QueryTimer<'a> {
+    collector: &'a StatsCollector,
+    start: QueryStart,
+}
+
+impl<'a> Drop for QueryTimer<'a> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.collector.finish(QueryStart {
+            kind: self.start.kind,
+            start: self.start.start.take(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{
+            symbols::{tests::TestPackage, LookupOptions},
+            ModuleRead,
+            ModuleStats,
+            ModuleWrite,
+            QueryKind,
+            ScriptModule,
+        },
+        runtime::ScriptPackage,
+    };
+
+    #[test]
+    fn test_module_stats() {
+        let module = ScriptModule::<TriggerHandle>::new(TestPackage::meta(), "let x = 10;");
+        let handle = TriggerHandle::new();
+
+        {
+            let read = module.read(&handle, 1).unwrap();
+
+            let _ = read.diagnostics(1).unwrap();
+        }
+
+        assert!(!module.is_stats_enabled());
+        assert_eq!(module.stats().count(), 0);
+
+        module.set_stats_enabled(true);
+
+        {
+            let read = module.read(&handle, 1).unwrap();
+
+            let _ = read.diagnostics(1).unwrap();
+            let _ = read.diagnostics(3).unwrap();
+            let _ = read.diagnostics(3).unwrap();
+            let _ = read.symbols(.., LookupOptions::new()).unwrap();
+            let _ = read.compile().unwrap();
+        }
+
+        {
+            let mut write = module.write(&handle, 1).unwrap();
+
+            let _ = write.completions(4).unwrap();
+            let _ = write.completions(8).unwrap();
+        }
+
+        let stats = module.stats();
+
+        assert_eq!(stats.get(QueryKind::Diagnostics(1)).count, 1);
+        assert_eq!(stats.get(QueryKind::Diagnostics(2)).count, 0);
+        assert_eq!(stats.get(QueryKind::Diagnostics(3)).count, 2);
+        assert_eq!(stats.get(QueryKind::Diagnostics(4)).count, 0);
+        assert_eq!(stats.get(QueryKind::Symbols).count, 1);
+        assert_eq!(stats.get(QueryKind::Compile).count, 1);
+        assert_eq!(stats.get(QueryKind::Completions).count, 2);
+        assert_eq!(stats.count(), 7);
+
+        let diagnostics = stats.get(QueryKind::Diagnostics(3));
+
+        assert!(diagnostics.total >= diagnostics.last);
+        assert!(diagnostics.average() <= diagnostics.total);
+
+        let display = stats.to_string();
+
+        assert!(display.contains("diagnostics-3"));
+        assert!(display.contains("completions"));
+        assert!(!display.contains("diagnostics-2"));
+
+        let mut total = ModuleStats::default();
+
+        total.merge(&stats);
+        total.merge(&stats);
+
+        assert_eq!(total.count(), 14);
+
+        module.set_stats_enabled(false);
+
+        {
+            let read = module.read(&handle, 1).unwrap();
+
+            let _ = read.diagnostics(1).unwrap();
+        }
+
+        assert_eq!(module.stats(), stats);
+
+        module.reset_stats();
+
+        assert_eq!(module.stats().count(), 0);
+        assert_eq!(ModuleStats::default().to_string(), "no queries measured");
+    }
+}
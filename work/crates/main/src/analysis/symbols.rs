@@ -38,20 +38,31 @@ use compact_str::CompactString;
 use lady_deirdre::{
     analysis::{AbstractTask, TaskHandle},
     arena::{Id, Identifiable},
-    lexis::{SiteSpan, SourceCode, TokenRef},
+    lexis::{Site, SiteSpan, SourceCode, TokenRef},
     syntax::{AbstractNode, NodeRef, PolyRef, SyntaxTree, Visitor},
     units::CompilationUnit,
 };
 
 use crate::{
-    analysis::{Description, ModuleRead, ModuleResult, ModuleResultEx},
-    runtime::{PackageMeta, ScriptIdent, ScriptOrigin},
+    analysis::{folding::ConstFolder, Description, ModuleRead, ModuleResult, ModuleResultEx},
+    runtime::{ops::OperatorKind, Cell, PackageMeta, ScriptIdent, ScriptOrigin},
     semantics::{IdentCrossResolution, LocalReturnPoint, Tag},
     syntax::{PolyRefOrigin, ScriptClass, ScriptDoc, ScriptNode, ScriptToken, SpanBounds},
 };
 
 /// A variant type that enumerates all language constructions currently
 /// available for inspection.
+///
+/// ## Result Ordering
+///
+/// The analysis functions that return collections of symbols (such as
+/// [ModuleSymbol::references], [VarSymbol::references], or
+/// [IdentSymbol::definitions]) return them in a deterministic order: the
+/// symbols are sorted by the starting positions of their constructions in the
+/// source code. If two constructions start at the same position, the shorter
+/// one goes first. The order is stable between runs and between repeated calls
+/// on the same source code, so external tools can rely on it, for example, in
+/// snapshot tests.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[non_exhaustive]
 pub enum ModuleSymbol {
@@ -119,6 +130,9 @@ pub enum ModuleSymbol {
     /// the content surrounded by the brackets, as well as the brackets
     /// themselves.
     Index(IndexSymbol),
+
+    /// An enum declaration: `enum Color { Red, Green, Blue }`.
+    Enum(EnumSymbol),
 }
 
 impl From<Tag> for ModuleSymbol {
@@ -131,6 +145,8 @@ impl From<Tag> for ModuleSymbol {
             Tag::Struct(struct_ref) => ModuleSymbol::Struct(StructSymbol(*struct_ref)),
             Tag::Fn((fn_ref, _)) => ModuleSymbol::Fn(FnSymbol(*fn_ref)),
             Tag::Invocation(_) => ModuleSymbol::Nil,
+            Tag::Enum(enum_ref) => ModuleSymbol::Enum(EnumSymbol(*enum_ref)),
+            Tag::Variant(enum_ref) => ModuleSymbol::Enum(EnumSymbol(*enum_ref)),
         }
     }
 }
@@ -155,6 +171,7 @@ impl Identifiable for ModuleSymbol {
             Self::Operator(symbol) => symbol.id(),
             Self::Call(symbol) => symbol.id(),
             Self::Index(symbol) => symbol.id(),
+            Self::Enum(symbol) => symbol.id(),
         }
     }
 }
@@ -168,7 +185,7 @@ impl Default for ModuleSymbol {
 
 impl ModuleSymbol {
     #[inline(always)]
-    fn new(kind: SymbolKind, node_ref: &NodeRef) -> Self {
+    pub(crate) fn new(kind: SymbolKind, node_ref: &NodeRef) -> Self {
         match kind {
             SymbolKind::Nil => Self::Nil,
             SymbolKind::Use => Self::Use(UseSymbol(*node_ref)),
@@ -187,6 +204,31 @@ impl ModuleSymbol {
             SymbolKind::Operator => Self::Operator(OperatorSymbol(*node_ref)),
             SymbolKind::Call => Self::Call(CallSymbol(*node_ref)),
             SymbolKind::Index => Self::Index(IndexSymbol(*node_ref)),
+            SymbolKind::Enum => Self::Enum(EnumSymbol(*node_ref)),
+        }
+    }
+
+    #[inline(always)]
+    fn node_ref(&self) -> NodeRef {
+        match self {
+            Self::Nil => NodeRef::nil(),
+            Self::Use(symbol) => symbol.0,
+            Self::Package(symbol) => symbol.0,
+            Self::Var(symbol) => symbol.0,
+            Self::Loop(symbol) => symbol.0,
+            Self::Break(symbol) => symbol.0,
+            Self::Fn(symbol) => symbol.0,
+            Self::Return(symbol) => symbol.0,
+            Self::Struct(symbol) => symbol.0,
+            Self::Array(symbol) => symbol.0,
+            Self::Entry(symbol) => symbol.0,
+            Self::Ident(symbol) => symbol.0,
+            Self::Field(symbol) => symbol.0,
+            Self::Literal(symbol) => symbol.0,
+            Self::Operator(symbol) => symbol.0,
+            Self::Call(symbol) => symbol.0,
+            Self::Index(symbol) => symbol.0,
+            Self::Enum(symbol) => symbol.0,
         }
     }
 
@@ -218,8 +260,12 @@ impl ModuleSymbol {
             ScriptNode::StructBody { .. } => Self::Nil,
             ScriptNode::StructEntry { .. } => Self::Nil,
             ScriptNode::StructEntryKey { .. } => Self::Nil,
+            ScriptNode::Enum { .. } => Self::Nil,
+            ScriptNode::EnumBody { .. } => Self::Nil,
+            ScriptNode::EnumVariant { .. } => Self::Nil,
             ScriptNode::Array { node, .. } => Self::Array(ArraySymbol(*node)),
             ScriptNode::String { node, .. } => Self::Literal(LiteralSymbol(*node)),
+            ScriptNode::Embedded { node, .. } => Self::Literal(LiteralSymbol(*node)),
             ScriptNode::Crate { node, .. } => Self::Ident(IdentSymbol(*node)),
             ScriptNode::This { node, .. } => Self::Ident(IdentSymbol(*node)),
             ScriptNode::Ident { node, .. } => Self::Ident(IdentSymbol(*node)),
@@ -232,6 +278,7 @@ impl ModuleSymbol {
             ScriptNode::Query { op, .. } => Self::Operator(OperatorSymbol(*op)),
             ScriptNode::Call { node, .. } => Self::Call(CallSymbol(*node)),
             ScriptNode::CallArgs { .. } => Self::Nil,
+            ScriptNode::Arg { .. } => Self::Nil,
             ScriptNode::Index { node, .. } => Self::Call(CallSymbol(*node)),
             ScriptNode::IndexArg { .. } => Self::Nil,
             ScriptNode::Field { node, .. } => Self::Field(FieldSymbol(*node)),
@@ -273,6 +320,7 @@ impl ModuleSymbol {
             Self::Operator(_) => SymbolKind::Operator,
             Self::Call(_) => SymbolKind::Call,
             Self::Index(_) => SymbolKind::Index,
+            Self::Enum(_) => SymbolKind::Enum,
         }
     }
 
@@ -308,6 +356,7 @@ impl ModuleSymbol {
             Self::Operator(symbol) => symbol.is_valid(read),
             Self::Call(symbol) => symbol.is_valid(read),
             Self::Index(symbol) => symbol.is_valid(read),
+            Self::Enum(symbol) => symbol.is_valid(read),
         }
     }
 
@@ -356,6 +405,7 @@ impl ModuleSymbol {
             Self::Operator(symbol) => symbol.origin(read),
             Self::Call(symbol) => symbol.origin(read),
             Self::Index(symbol) => symbol.origin(read),
+            Self::Enum(symbol) => symbol.origin(read),
         }
     }
 
@@ -398,6 +448,7 @@ impl ModuleSymbol {
             Self::Operator(symbol) => symbol.outer_origin(read),
             Self::Call(symbol) => symbol.outer_origin(read),
             Self::Index(symbol) => symbol.outer_origin(read),
+            Self::Enum(_) => ScriptOrigin::nil(),
         }
     }
 
@@ -437,6 +488,255 @@ impl ModuleSymbol {
             Self::Operator(symbol) => symbol.ty(read),
             Self::Call(symbol) => symbol.ty(read),
             Self::Index(symbol) => symbol.ty(read),
+            Self::Enum(_) => Ok(Description::dynamic()),
+        }
+    }
+
+    /// Attempts to evaluate this expression at analysis time.
+    ///
+    /// The analyzer folds number, string, and boolean literals, negations of
+    /// foldable expressions, and invocations of the package functions marked
+    /// with the `#[export(pure)]` attribute if all arguments of the invocation
+    /// are foldable too: `rgb(255, 0, 0)`.
+    ///
+    /// For an identifier symbol, the function returns the value of
+    /// the variable if the variable is assigned exactly once, and its
+    /// initializer is an integer expression over constants and other such
+    /// variables: `let i = 10; let j = i * 2;`.
+    ///
+    /// Note that this function executes the Rust code of the pure functions
+    /// on the current thread. The total number and the total time of the
+    /// invocations are limited by a small budget. If a pure function returns
+    /// an error, panics, or exceeds the budget, the expression is considered
+    /// unfoldable. Do not call this function if the module is analyzed in
+    /// a context where invoking the host's Rust functions is undesirable.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function may return an
+    /// [Interrupted](crate::analysis::ModuleError::Interrupted) error if the
+    /// folding requires deep source code analysis, and the analysis procedure
+    /// is interrupted by the revocation of the module content access guard (see
+    /// [ScriptModule](crate::analysis::ScriptModule) documentation for
+    /// details).
+    ///
+    /// The function returns None if the expression cannot be folded, or if
+    /// this symbol is not [valid](Self::is_valid).
+    pub fn expr_value<H: TaskHandle>(
+        &self,
+        read: &impl ModuleRead<H>,
+    ) -> ModuleResult<Option<Cell>> {
+        let doc_read = read.read_doc();
+
+        let expr_ref = match self {
+            Self::Ident(symbol) => symbol.0,
+            Self::Literal(symbol) => symbol.0,
+            Self::Call(symbol) => symbol.0,
+
+            Self::Operator(symbol) => match symbol.0.deref(doc_read.deref()) {
+                Some(ScriptNode::Op { parent, .. }) => *parent,
+                _ => return Ok(None),
+            },
+
+            _ => return Ok(None),
+        };
+
+        ConstFolder::fold(read, doc_read.deref(), &expr_ref)
+    }
+
+    /// Returns the name of the symbol, if the symbol has a name in the source
+    /// code.
+    ///
+    /// This is a shortcut that dispatches to the specific symbol function:
+    ///
+    /// - For [VarSymbol], returns the variable's
+    ///   [name](VarSymbol::var_name).
+    /// - For [EnumSymbol], returns the name of the declared enum.
+    /// - For [EntrySymbol], [IdentSymbol], and [FieldSymbol], returns the
+    ///   result of their `name` functions.
+    /// - For [PackageSymbol], returns the package's name in the import
+    ///   statement: `use foo.<bar>;`.
+    /// - For [UseSymbol], returns the name of the
+    ///   [last package](UseSymbol::last_package) in the import statement.
+    /// - For all other variants, including anonymous functions and structures,
+    ///   returns None.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function also returns None if the symbol is not
+    /// [valid](Self::is_valid), or if the construction has syntax errors.
+    pub fn name<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> Option<String> {
+        match self {
+            Self::Use(symbol) => Self::Package(symbol.last_package(read)?).name(read),
+
+            Self::Package(symbol) => {
+                let doc_read = read.read_doc();
+
+                let Some(ScriptNode::Package { token, .. }) = symbol.0.deref(doc_read.deref())
+                else {
+                    return None;
+                };
+
+                Some(String::from(token.string(doc_read.deref())?))
+            }
+
+            Self::Var(symbol) => Some(symbol.var_name(read)?.to_string()),
+            Self::Enum(symbol) => Some(symbol.var(read)?.var_name(read)?.to_string()),
+            Self::Entry(symbol) => symbol.name(read),
+            Self::Ident(symbol) => symbol.name(read),
+            Self::Field(symbol) => symbol.name(read),
+            _ => None,
+        }
+    }
+
+    /// Returns the symbols within the module that refer to the declaration of
+    /// this symbol.
+    ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
+    /// This is a shortcut that dispatches to the specific symbol function:
+    ///
+    /// - For [VarSymbol], returns the [identifiers](ModuleSymbol::Ident) that
+    ///   read or write the variable (see [VarSymbol::references]). The access
+    ///   kind is not preserved.
+    /// - For [FnSymbol], [StructSymbol], and [EnumSymbol], returns the result
+    ///   of their `references` functions.
+    /// - For [EntrySymbol], returns the [fields](ModuleSymbol::Field) that
+    ///   refer to the structure entry.
+    /// - For [UseSymbol], returns all identifiers that refer to the imported
+    ///   semantics (see [UseSymbol::all_references]).
+    /// - For [PackageSymbol], returns the same result as for the import
+    ///   statement if this package is the [last](PackageSymbol::is_last) one
+    ///   in the statement. Otherwise, returns an empty vector.
+    /// - For [IdentSymbol] and [FieldSymbol], resolves the
+    ///   [declaration](Self::declaration) first and returns the references of
+    ///   the declaration. Note that the result includes this symbol too.
+    /// - For all other variants, returns an empty vector.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function may return an
+    /// [Interrupted](crate::analysis::ModuleError::Interrupted) error if the
+    /// references lookup requires deep source code analysis, and the analysis
+    /// procedure is interrupted by the revocation of the module content access
+    /// guard (see [ScriptModule](crate::analysis::ScriptModule) documentation
+    /// for details).
+    ///
+    /// The function also returns an empty vector if the symbol is not
+    /// [valid](Self::is_valid), or if the analyzer fails to infer the
+    /// references.
+    pub fn references<H: TaskHandle>(
+        &self,
+        read: &impl ModuleRead<H>,
+    ) -> ModuleResult<Vec<ModuleSymbol>> {
+        match self {
+            Self::Use(symbol) => Ok(symbol
+                .all_references(read)?
+                .into_iter()
+                .map(ModuleSymbol::Ident)
+                .collect()),
+
+            Self::Package(symbol) => {
+                if !symbol.is_last(read) {
+                    return Ok(Vec::new());
+                }
+
+                let Some(use_symbol) = symbol.use_symbol(read) else {
+                    return Ok(Vec::new());
+                };
+
+                Self::Use(use_symbol).references(read)
+            }
+
+            Self::Var(symbol) => Ok(symbol
+                .references(read)?
+                .into_iter()
+                .map(|var_ref| match var_ref {
+                    VarRef::Access(ident) => ModuleSymbol::Ident(ident),
+                    VarRef::Definition(ident) => ModuleSymbol::Ident(ident),
+                })
+                .collect()),
+
+            Self::Fn(symbol) => symbol.references(read),
+            Self::Struct(symbol) => symbol.references(read),
+            Self::Enum(symbol) => symbol.references(read),
+
+            Self::Entry(symbol) => Ok(symbol
+                .references(read)?
+                .into_iter()
+                .map(ModuleSymbol::Field)
+                .collect()),
+
+            Self::Ident(_) | Self::Field(_) => match self.declaration(read)? {
+                Self::Ident(_) | Self::Field(_) => Ok(Vec::new()),
+                declaration => declaration.references(read),
+            },
+
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Returns the symbol that declares the semantics of this symbol.
+    ///
+    /// This is a shortcut that dispatches to the specific symbol function:
+    ///
+    /// - For [IdentSymbol], returns the result of the
+    ///   [IdentSymbol::declaration] function.
+    /// - For [FieldSymbol], returns the [entry](ModuleSymbol::Entry) of the
+    ///   script structure to which the field refers (see
+    ///   [FieldSymbol::declaration]), or [Nil](ModuleSymbol::Nil) if the
+    ///   field does not refer to a script structure entry.
+    /// - For declaration symbols ([UseSymbol], [PackageSymbol], [VarSymbol],
+    ///   [FnSymbol], [StructSymbol], [EntrySymbol], and [EnumSymbol]), returns
+    ///   the symbol itself.
+    /// - For all other variants, returns [Nil](ModuleSymbol::Nil).
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function may return an
+    /// [Interrupted](crate::analysis::ModuleError::Interrupted) error if the
+    /// declaration lookup requires deep source code analysis, and the analysis
+    /// procedure is interrupted by the revocation of the module content access
+    /// guard (see [ScriptModule](crate::analysis::ScriptModule) documentation
+    /// for details).
+    ///
+    /// The function also returns [Nil](ModuleSymbol::Nil) if the symbol is
+    /// not [valid](Self::is_valid), or if the analyzer fails to resolve the
+    /// declaration.
+    pub fn declaration<H: TaskHandle>(
+        &self,
+        read: &impl ModuleRead<H>,
+    ) -> ModuleResult<ModuleSymbol> {
+        if !self.is_valid(read) {
+            return Ok(Self::Nil);
+        }
+
+        match self {
+            Self::Use(_)
+            | Self::Package(_)
+            | Self::Var(_)
+            | Self::Fn(_)
+            | Self::Struct(_)
+            | Self::Entry(_)
+            | Self::Enum(_) => Ok(*self),
+
+            Self::Ident(symbol) => symbol.declaration(read),
+
+            Self::Field(symbol) => Ok(match symbol.declaration(read)? {
+                Some(entry) => Self::Entry(entry),
+                None => Self::Nil,
+            }),
+
+            _ => Ok(Self::Nil),
         }
     }
 }
@@ -510,6 +810,9 @@ pub enum SymbolKind {
     /// the content surrounded by the brackets, as well as the brackets
     /// themselves.
     Index = 1u32 << 16,
+
+    /// An enum declaration: `enum Color { Red, Green, Blue }`.
+    Enum = 1u32 << 17,
 }
 
 impl SymbolKind {
@@ -546,6 +849,7 @@ impl SymbolKind {
             Self::Operator => true,
             Self::Call => true,
             Self::Index => true,
+            Self::Enum => false,
         }
     }
 }
@@ -680,6 +984,9 @@ impl UseSymbol {
     /// Returns all identifiers within the module that refer to the semantics
     /// imported by this import statement into the module's namespace.
     ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
     /// The function may return an
     /// [Interrupted](crate::analysis::ModuleError::Interrupted) error if the
     /// package metadata inference requires deep source code analysis, and the
@@ -722,6 +1029,9 @@ impl UseSymbol {
     /// Similar to [all_references](Self::references_by_name), but filters the
     /// references by the specified `name`.
     ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
     /// If you need to find all identifiers with a specific name that belong to
     /// the imported package, this function will perform notably faster than the
     /// all_references function.
@@ -793,6 +1103,8 @@ impl UseSymbol {
             result.push(IdentSymbol(*ident_ref));
         }
 
+        sort_by_position(doc, &mut result, |symbol| symbol.0);
+
         Ok(result)
     }
 }
@@ -961,6 +1273,11 @@ impl VarSymbol {
         ModuleSymbol::Var(Self(*var_ref))
     }
 
+    #[inline(always)]
+    pub(super) fn var_ref(&self) -> &NodeRef {
+        &self.0
+    }
+
     /// Returns true if this symbol still exists in the
     /// [ScriptModule](crate::analysis::ScriptModule).
     ///
@@ -996,6 +1313,7 @@ impl VarSymbol {
             ScriptNode::Let { .. } => VarKind::LetVar,
             ScriptNode::FnParams { .. } => VarKind::FnParam,
             ScriptNode::For { .. } => VarKind::ForIterator,
+            ScriptNode::Enum { .. } => VarKind::EnumName,
             _ => VarKind::Invalid,
         }
     }
@@ -1132,6 +1450,9 @@ impl VarSymbol {
     /// Returns all identifiers across the module's code that semantically
     /// refer to this variable.
     ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
     /// The resulting vector consists of [VarRef] enums with two variants:
     ///  - [VarRef::Access]: An identifier that refers to this variable
     ///    when the variable is already initialized through the control flow.
@@ -1203,6 +1524,11 @@ impl VarSymbol {
             }
         }
 
+        sort_by_position(doc_read.deref(), &mut result, |var_ref| match var_ref {
+            VarRef::Access(ident) => ident.0,
+            VarRef::Definition(ident) => ident.0,
+        });
+
         Ok(result)
     }
 }
@@ -1224,6 +1550,10 @@ pub enum VarKind {
 
     /// The VarSymbol represents a for-loop iterator: `for <var> in range {}`.
     ForIterator,
+
+    /// The VarSymbol represents the name of an enum declaration:
+    /// `enum <var> { Foo, Bar }`.
+    EnumName,
 }
 
 /// An identifier that refers to the variable.
@@ -1661,6 +1991,57 @@ impl FnSymbol {
         params.iter().map(|var_ref| VarSymbol(*var_ref)).collect()
     }
 
+    /// Returns a list of variables declared outside of this script function
+    /// that the function captures (the free variables of the function body,
+    /// including the variables captured by the nested functions).
+    ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function may return an
+    /// [Interrupted](crate::analysis::ModuleError::Interrupted) error if the
+    /// captures inference requires deep source code analysis, and the
+    /// analysis procedure is interrupted by the revocation of the module
+    /// content access guard (see [ScriptModule](crate::analysis::ScriptModule)
+    /// documentation for details).
+    ///
+    /// The function returns an empty vector if the analyzer fails to infer
+    /// the captured variables (e.g., if the construction has syntax errors,
+    /// or if the FnSymbol is not [valid](Self::is_valid)).
+    pub fn captures<H: TaskHandle>(
+        &self,
+        read: &impl ModuleRead<H>,
+    ) -> ModuleResult<Vec<VarSymbol>> {
+        let doc_read = read.read_doc();
+
+        let Some(ScriptNode::Fn { semantics, .. }) = self.0.deref(doc_read.deref()) else {
+            return Ok(Vec::new());
+        };
+
+        let id = doc_read.id();
+
+        let fn_semantics = semantics.get().into_module_result(id)?;
+
+        let (_, captures) = fn_semantics
+            .captures
+            .snapshot(read.task())
+            .into_module_result(id)?;
+
+        let mut result = captures
+            .set
+            .iter()
+            .map(|var_ref| VarSymbol(*var_ref))
+            .collect::<Vec<_>>();
+
+        sort_by_position(doc_read.deref(), &mut result, |symbol| symbol.0);
+
+        Ok(result)
+    }
+
     /// Returns a type description that formally describes this expression's
     /// type.
     ///
@@ -1789,6 +2170,9 @@ impl FnSymbol {
     /// or indirectly refer to this script function instance (usually, these
     /// symbols are [IdentSymbol]s).
     ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
     /// The `read` argument can be any content access guard object, such as
     /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
     /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
@@ -1878,6 +2262,8 @@ impl FnSymbol {
             result.push(ModuleSymbol::Ident(IdentSymbol(*field_ref)));
         }
 
+        sort_by_position(doc_read.deref(), &mut result, ModuleSymbol::node_ref);
+
         Ok(result)
     }
 
@@ -2336,6 +2722,9 @@ impl StructSymbol {
     /// or indirectly refer to this structure instance (usually, these symbols
     /// are [IdentSymbol]s or [FieldSymbol]s).
     ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
     /// The `read` argument can be any content access guard object, such as
     /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
     /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
@@ -2462,6 +2851,8 @@ impl StructSymbol {
             result.push(ModuleSymbol::from_expr_node(left));
         }
 
+        sort_by_position(doc_read.deref(), &mut result, ModuleSymbol::node_ref);
+
         Ok(result)
     }
 }
@@ -2764,6 +3155,9 @@ impl EntrySymbol {
     /// Infers all field access symbols within the source code that directly
     /// refer to this entry.
     ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
     /// The `read` argument can be any content access guard object, such as
     /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
     /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
@@ -2843,6 +3237,8 @@ impl EntrySymbol {
             result.push(FieldSymbol(*field_ref));
         }
 
+        sort_by_position(doc_read.deref(), &mut result, |symbol| symbol.0);
+
         Ok(result)
     }
 }
@@ -2921,7 +3317,10 @@ impl IdentSymbol {
                 Some(ScriptNode::Use { .. }) => Ok(IdentKind::PackageAccess),
 
                 Some(
-                    ScriptNode::Let { .. } | ScriptNode::For { .. } | ScriptNode::FnParams { .. },
+                    ScriptNode::Let { .. }
+                    | ScriptNode::For { .. }
+                    | ScriptNode::FnParams { .. }
+                    | ScriptNode::Enum { .. },
                 ) => Ok(IdentKind::VarAccess),
 
                 _ => Ok(IdentKind::Invalid),
@@ -2983,6 +3382,9 @@ impl IdentSymbol {
     /// Looks up all identifiers (including this one) across the source code
     /// that are semantically similar to the current identifier.
     ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
     /// The identifier's similarity depends on its [kind](Self::kind) and the
     /// context:
     ///
@@ -3035,6 +3437,8 @@ impl IdentSymbol {
                     result.push(Self(*crate_ref));
                 }
 
+                sort_by_position(doc_read.deref(), &mut result, |symbol| symbol.0);
+
                 Ok(result)
             }
 
@@ -3077,6 +3481,8 @@ impl IdentSymbol {
                     result.push(Self(*this_ref));
                 }
 
+                sort_by_position(doc_read.deref(), &mut result, |symbol| symbol.0);
+
                 Ok(result)
             }
 
@@ -3137,6 +3543,8 @@ impl IdentSymbol {
                     result.push(Self(*ident_ref));
                 }
 
+                sort_by_position(doc_read.deref(), &mut result, |symbol| symbol.0);
+
                 Ok(result)
             }
 
@@ -3151,7 +3559,9 @@ impl IdentSymbol {
         let doc_read = read.read_doc();
 
         match self.0.deref(doc_read.deref()) {
-            Some(ScriptNode::Ident { semantics, .. }) => {
+            Some(ScriptNode::Ident {
+                token, semantics, ..
+            }) => {
                 let id = doc_read.id();
 
                 let ident_semantics = semantics.get().into_module_result(id)?;
@@ -3161,6 +3571,31 @@ impl IdentSymbol {
                     .snapshot(read.task())
                     .into_module_result(id)?;
 
+                let mut description = Description::from_tag(type_resolution.tag);
+
+                if let IdentKind::CrateAccess = self.kind(read)? {
+                    if let (Some(package), Some(name)) =
+                        (PackageMeta::by_id(id), token.string(doc_read.deref()))
+                    {
+                        if let Some(component) = package.ty().prototype().hint_component(name) {
+                            description.deprecated = component.deprecated;
+                        }
+                    }
+                }
+
+                Ok(description)
+            }
+
+            Some(ScriptNode::Crate { semantics, .. }) => {
+                let id = doc_read.id();
+
+                let crate_semantics = semantics.get().into_module_result(id)?;
+
+                let (_, type_resolution) = crate_semantics
+                    .type_resolution
+                    .snapshot(read.task())
+                    .into_module_result(id)?;
+
                 Ok(Description::from_tag(type_resolution.tag))
             }
 
@@ -3251,6 +3686,10 @@ impl IdentSymbol {
 
                             ScriptNode::Let { name, .. } => Ok(ModuleSymbol::Var(VarSymbol(*name))),
 
+                            ScriptNode::Enum { .. } => {
+                                Ok(ModuleSymbol::Enum(EnumSymbol(name.as_ref().decl)))
+                            }
+
                             ScriptNode::FnParams { .. } => {
                                 let Some(param_ref) = name.as_ref().defs.iter().next() else {
                                     return Ok(ModuleSymbol::Nil);
@@ -3292,6 +3731,9 @@ impl IdentSymbol {
     /// Infers symbols that initialize the value referred to by this
     /// IdentSymbol.
     ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
     /// The identifier's definitions depend on its [kind](Self::kind) and the
     /// context:
     ///
@@ -3354,6 +3796,8 @@ impl IdentSymbol {
 
                 ScriptNode::Var { .. } => result.push(ModuleSymbol::Var(VarSymbol(*def_ref))),
 
+                ScriptNode::Enum { .. } => result.push(ModuleSymbol::Enum(EnumSymbol(*def_ref))),
+
                 ScriptNode::Expr { parent, .. } => {
                     let Some(parent_node) = parent.deref(doc_read.deref()) else {
                         continue;
@@ -3381,6 +3825,8 @@ impl IdentSymbol {
             }
         }
 
+        sort_by_position(doc_read.deref(), &mut result, ModuleSymbol::node_ref);
+
         Ok(result)
     }
 
@@ -3543,6 +3989,9 @@ impl FieldSymbol {
     /// Looks up all field access operators (including this one)
     /// across the source code that access the same object's field.
     ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
     /// The `read` argument can be any content access guard object, such as
     /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
     /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
@@ -3629,6 +4078,8 @@ impl FieldSymbol {
             result.push(FieldSymbol(*field_ref));
         }
 
+        sort_by_position(doc_read.deref(), &mut result, |symbol| symbol.0);
+
         Ok(result)
     }
 
@@ -3638,11 +4089,14 @@ impl FieldSymbol {
     pub fn ty<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> ModuleResult<Description> {
         let doc_read = read.read_doc();
 
-        let Some(ScriptNode::Field { parent, .. }) = self.0.deref(doc_read.deref()) else {
+        let Some(ScriptNode::Field { parent, token, .. }) = self.0.deref(doc_read.deref()) else {
             return Ok(Description::dynamic());
         };
 
-        let Some(ScriptNode::Binary { semantics, .. }) = parent.deref(doc_read.deref()) else {
+        let Some(ScriptNode::Binary {
+            semantics, left, ..
+        }) = parent.deref(doc_read.deref())
+        else {
             return Ok(Description::dynamic());
         };
 
@@ -3655,7 +4109,31 @@ impl FieldSymbol {
             .snapshot(read.task())
             .into_module_result(id)?;
 
-        Ok(Description::from_tag(type_resolution.tag))
+        let mut description = Description::from_tag(type_resolution.tag);
+
+        // Prefers the documentation of the Rust component over the
+        // documentation of the component's type.
+        if let (Some(left_node), Some(field_string)) =
+            (left.deref(doc_read.deref()), token.string(doc_read.deref()))
+        {
+            let (_, left_type_resolution) = left_node
+                .type_resolution()
+                .into_module_result(id)?
+                .snapshot(read.task())
+                .into_module_result(id)?;
+
+            if let Some(receiver) = left_type_resolution.tag.type_meta() {
+                if let Some(component) = receiver.prototype().hint_component(field_string) {
+                    if component.doc.is_some() {
+                        description.doc = component.doc;
+                    }
+
+                    description.deprecated = component.deprecated;
+                }
+            }
+        }
+
+        Ok(description)
     }
 
     /// Returns the script struct entry symbol where this field has been
@@ -3806,11 +4284,36 @@ impl LiteralSymbol {
         match self.0.deref(doc_read.deref()) {
             Some(ScriptNode::Number { .. } | ScriptNode::Max { .. }) => LiteralKind::Number,
             Some(ScriptNode::Bool { .. }) => LiteralKind::Bool,
-            Some(ScriptNode::String { .. }) => LiteralKind::String,
+            Some(ScriptNode::String { .. } | ScriptNode::Embedded { .. }) => LiteralKind::String,
             _ => LiteralKind::Invalid,
         }
     }
 
+    /// Returns the language id of the embedded data block:
+    /// ```` ```<language> ...``` ````.
+    ///
+    /// Editors can use this value to highlight the content of the block
+    /// according to the syntax of the embedded language.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function returns None if the literal is not an embedded block, or
+    /// if the analyzer fails to resolve this construction. The function returns
+    /// an empty string if the block does not specify the language.
+    pub fn embedded_language<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> Option<String> {
+        let doc_read = read.read_doc();
+
+        let Some(ScriptNode::Embedded { start, end, .. }) = self.0.deref(doc_read.deref()) else {
+            return None;
+        };
+
+        let (language, _) = ScriptNode::extract_embedded(doc_read.deref(), start, end)?;
+
+        Some(String::from(language))
+    }
+
     /// Returns the source code range of the underlying symbol.
     ///
     /// See [ModuleSymbol::origin] for details.
@@ -3838,7 +4341,9 @@ impl LiteralSymbol {
         match self.0.deref(doc_read.deref()) {
             Some(ScriptNode::Number { .. }) => Description::number_family(*self),
             Some(ScriptNode::Bool { .. }) => Description::bool_type(*self),
-            Some(ScriptNode::String { .. }) => Description::string_family(*self),
+            Some(ScriptNode::String { .. } | ScriptNode::Embedded { .. }) => {
+                Description::string_family(*self)
+            }
             _ => Description::dynamic(),
         }
     }
@@ -3861,6 +4366,7 @@ impl LiteralSymbol {
             Some(ScriptNode::Number { parent, .. }) => parent,
             Some(ScriptNode::Bool { parent, .. }) => parent,
             Some(ScriptNode::String { parent, .. }) => parent,
+            Some(ScriptNode::Embedded { parent, .. }) => parent,
             _ => return ModuleSymbol::Nil,
         };
 
@@ -4005,6 +4511,96 @@ impl OperatorSymbol {
         Ok(Description::from_tag(type_resolution.tag))
     }
 
+    /// Returns a script structure entry that overloads this operator.
+    ///
+    /// Script structures can overload operators with specially named entries
+    /// (see [OperatorKind::entry_name](crate::runtime::ops::OperatorKind::entry_name)).
+    /// For example, in the `a + b` expression, where `a` is a structure with
+    /// the `__add` entry, the function returns the symbol of this entry.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function may return an
+    /// [Interrupted](crate::analysis::ModuleError::Interrupted) error if the
+    /// entry lookup requires deep source code analysis, and
+    /// the analysis procedure is interrupted by the revocation of the module
+    /// content access guard (see [ScriptModule](crate::analysis::ScriptModule)
+    /// documentation for details).
+    ///
+    /// The function returns None if the operand is not a script structure, if
+    /// the structure does not overload this operator, or if the analyzer fails
+    /// to resolve this construction (e.g., if the construction has syntax or
+    /// semantic errors, or if the OperatorSymbol is not
+    /// [valid](Self::is_valid)).
+    pub fn declaration<H: TaskHandle>(
+        &self,
+        read: &impl ModuleRead<H>,
+    ) -> ModuleResult<Option<EntrySymbol>> {
+        let doc_read = read.read_doc();
+
+        let Some(ScriptNode::Op { parent, token, .. }) = self.0.deref(doc_read.deref()) else {
+            return Ok(None);
+        };
+
+        let Some(token) = token.deref(doc_read.deref()) else {
+            return Ok(None);
+        };
+
+        let (operand_ref, operator) = match parent.deref(doc_read.deref()) {
+            Some(ScriptNode::UnaryLeft { right, .. }) => match token {
+                ScriptToken::Minus => (right, OperatorKind::Neg),
+                ScriptToken::Not => (right, OperatorKind::Not),
+                _ => return Ok(None),
+            },
+
+            Some(ScriptNode::Binary { left, .. }) => match token.binary_operator() {
+                Some(operator) => (left, operator),
+                None => return Ok(None),
+            },
+
+            _ => return Ok(None),
+        };
+
+        let Some(name) = operator.entry_name() else {
+            return Ok(None);
+        };
+
+        let Some(operand_node) = operand_ref.deref(doc_read.deref()) else {
+            return Ok(None);
+        };
+
+        let id = doc_read.id();
+
+        let (_, type_resolution) = operand_node
+            .type_resolution()
+            .into_module_result(id)?
+            .snapshot(read.task())
+            .into_module_result(id)?;
+
+        let Tag::Struct(struct_ref) = type_resolution.tag else {
+            return Ok(None);
+        };
+
+        let Some(ScriptNode::Struct { semantics, .. }) = struct_ref.deref(doc_read.deref()) else {
+            return Ok(None);
+        };
+
+        let struct_semantics = semantics.get().into_module_result(id)?;
+
+        let (_, struct_entries_map_syntax) = struct_semantics
+            .struct_entries_map_syntax
+            .snapshot(read.task())
+            .into_module_result(id)?;
+
+        let Some((struct_key_ref, _)) = struct_entries_map_syntax.as_ref().map.get(name) else {
+            return Ok(None);
+        };
+
+        Ok(Some(EntrySymbol(*struct_key_ref)))
+    }
+
     /// Returns an expression symbol that points to the left-hand side (LHS) of
     /// this operation. If the operation does not have an LHS, returns
     /// [ModuleSymbol::Nil].
@@ -4112,7 +4708,8 @@ pub struct OperationPattern {
 /// An invocation operator: `foo(a, b, c)`.
 ///
 /// The invocation is the content surrounded by the parentheses, including the
-/// parentheses themselves.
+/// parentheses themselves, and the trailing closure argument (if any):
+/// `foo(a, b) fn(x) {...}`.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct CallSymbol(NodeRef);
 
@@ -4237,6 +4834,9 @@ impl CallSymbol {
     /// Returns a list of invocation arguments:
     /// `foo(<comma separated arguments>)`.
     ///
+    /// If the invocation has a trailing closure (`foo(a, b) fn(x) {...}`),
+    /// the closure is the last argument in the list.
+    ///
     /// The `read` argument can be any content access guard object, such as
     /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
     /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
@@ -4251,13 +4851,16 @@ impl CallSymbol {
             return Vec::new();
         };
 
-        let Some(ScriptNode::CallArgs { args, .. }) = args.deref(doc_read.deref()) else {
+        let Some(args) = args
+            .deref(doc_read.deref())
+            .and_then(|node| node.call_args(doc_read.deref()))
+        else {
             return Vec::new();
         };
 
         let mut result = Vec::with_capacity(args.len());
 
-        for arg_ref in args {
+        for arg_ref in &args {
             let Some(arg_node) = descend_expr(doc_read.deref(), arg_ref) else {
                 result.push(ModuleSymbol::Nil);
                 continue;
@@ -4268,6 +4871,65 @@ impl CallSymbol {
 
         result
     }
+
+    /// Returns the names of the invocation arguments in the order of the
+    /// [args](Self::args) list: `foo(a, y: b)` has `[None, Some("y")]` names.
+    ///
+    /// The positional arguments and the trailing closure do not have names.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    pub fn arg_names<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> Vec<Option<String>> {
+        let doc_read = read.read_doc();
+
+        let Some(ScriptNode::Call { args, .. }) = self.0.deref(doc_read.deref()) else {
+            return Vec::new();
+        };
+
+        let Some(names) = args
+            .deref(doc_read.deref())
+            .and_then(|node| node.call_arg_names(doc_read.deref()))
+        else {
+            return Vec::new();
+        };
+
+        names
+            .iter()
+            .map(|name| name.string(doc_read.deref()).map(String::from))
+            .collect()
+    }
+
+    /// Returns the trailing closure argument of the invocation:
+    /// `foo(a, b) fn(x) {...}`.
+    ///
+    /// The trailing closure is also the last item of the [args](Self::args)
+    /// list.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function returns [ModuleSymbol::Nil] if the invocation does not
+    /// have a trailing closure, or if the analyzer fails to resolve this
+    /// construction.
+    pub fn closure<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> ModuleSymbol {
+        let doc_read = read.read_doc();
+
+        let Some(ScriptNode::Call { args, .. }) = self.0.deref(doc_read.deref()) else {
+            return ModuleSymbol::Nil;
+        };
+
+        let Some(ScriptNode::CallArgs { closure, .. }) = args.deref(doc_read.deref()) else {
+            return ModuleSymbol::Nil;
+        };
+
+        let Some(closure_node) = closure.deref(doc_read.deref()) else {
+            return ModuleSymbol::Nil;
+        };
+
+        ModuleSymbol::from_expr_node(closure_node)
+    }
 }
 
 /// An index operator: `foo[10]` or `foo[10..20]`.
@@ -4424,6 +5086,156 @@ impl IndexSymbol {
     }
 }
 
+/// An enum declaration: `enum Color { Red, Green, Blue }`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EnumSymbol(NodeRef);
+
+impl From<EnumSymbol> for ModuleSymbol {
+    #[inline(always)]
+    fn from(symbol: EnumSymbol) -> Self {
+        Self::Enum(symbol)
+    }
+}
+
+impl Identifiable for EnumSymbol {
+    #[inline(always)]
+    fn id(&self) -> Id {
+        self.0.id
+    }
+}
+
+impl EnumSymbol {
+    /// Returns true if this symbol still exists in the
+    /// [ScriptModule](crate::analysis::ScriptModule).
+    ///
+    /// See [ModuleSymbol::is_valid] for details.
+    pub fn is_valid<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> bool {
+        let doc_read = read.read_doc();
+
+        self.0.is_valid_ref(doc_read.deref())
+    }
+
+    /// Returns the source code range of the underlying symbol.
+    ///
+    /// See [ModuleSymbol::origin] for details.
+    pub fn origin<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> ScriptOrigin {
+        let doc_read = read.read_doc();
+
+        let Some(ScriptNode::Enum { keyword, .. }) = self.0.deref(doc_read.deref()) else {
+            return ScriptOrigin::nil();
+        };
+
+        ScriptOrigin::from(keyword)
+    }
+
+    /// Returns the source code range that covers only the enum body.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function returns [ScriptOrigin::nil] if the analyzer fails to
+    /// resolve this construction (e.g., if the construction has syntax errors,
+    /// or if the EnumSymbol is not [valid](Self::is_valid)).
+    pub fn body_origin<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> ScriptOrigin {
+        let doc_read = read.read_doc();
+
+        let Some(ScriptNode::Enum { body, .. }) = self.0.deref(doc_read.deref()) else {
+            return ScriptOrigin::nil();
+        };
+
+        body.script_origin(doc_read.deref(), SpanBounds::Cover)
+    }
+
+    /// Returns the variable introduced by the enum declaration:
+    /// `enum <var> { Foo, Bar }`.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function returns None if the analyzer fails to resolve this
+    /// construction (e.g., if the construction has syntax errors, or if the
+    /// EnumSymbol is not [valid](Self::is_valid)).
+    pub fn var<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> Option<VarSymbol> {
+        let doc_read = read.read_doc();
+
+        let Some(ScriptNode::Enum { name, .. }) = self.0.deref(doc_read.deref()) else {
+            return None;
+        };
+
+        let Some(ScriptNode::Var { .. }) = name.deref(doc_read.deref()) else {
+            return None;
+        };
+
+        Some(VarSymbol(*name))
+    }
+
+    /// Returns the names of the enum variants in the order of their
+    /// declaration.
+    ///
+    /// The variant's tag value is the index of the variant's name in the
+    /// resulting vector.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function returns an empty vector if the analyzer fails to
+    /// recognize the enum variants (e.g., if the construction has syntax
+    /// errors, or if the EnumSymbol is not [valid](Self::is_valid)).
+    pub fn variants<H: TaskHandle>(&self, read: &impl ModuleRead<H>) -> Vec<String> {
+        let doc_read = read.read_doc();
+
+        ScriptNode::enum_variants(doc_read.deref(), &self.0)
+            .iter()
+            .filter_map(|variant_ref| {
+                ScriptNode::extract_atom_string(doc_read.deref(), variant_ref)
+            })
+            .map(String::from)
+            .collect()
+    }
+
+    /// Returns all identifiers across the module's code that refer to this
+    /// enum by name (e.g., the `Color` identifier in the `Color.Red`
+    /// expression).
+    ///
+    /// The resulting vector is ordered by the symbols' positions in the
+    /// source code (see [ModuleSymbol#result-ordering]).
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The function may return an
+    /// [Interrupted](crate::analysis::ModuleError::Interrupted) error if the
+    /// references lookup requires deep source code analysis, and the analysis
+    /// procedure is interrupted by the revocation of the module content access
+    /// guard (see [ScriptModule](crate::analysis::ScriptModule) documentation
+    /// for details).
+    ///
+    /// The function returns an empty vector if the EnumSymbol is not
+    /// [valid](Self::is_valid), or if the analyzer fails to find any reference
+    /// that clearly belongs to this enum.
+    pub fn references<H: TaskHandle>(
+        &self,
+        read: &impl ModuleRead<H>,
+    ) -> ModuleResult<Vec<ModuleSymbol>> {
+        let Some(var) = self.var(read) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(var
+            .references(read)?
+            .into_iter()
+            .map(|var_ref| match var_ref {
+                VarRef::Access(ident) => ModuleSymbol::Ident(ident),
+                VarRef::Definition(ident) => ModuleSymbol::Ident(ident),
+            })
+            .collect())
+    }
+}
+
 /// A symbol lookup filter options.
 ///
 /// This object is an argument of the [ModuleRead::symbols] function.
@@ -4545,8 +5357,12 @@ impl<'a> Visitor for SymbolsLookup<'a> {
             ScriptNode::StructEntryKey { token, .. } => {
                 self.visit(SymbolKind::Entry, node_ref, token)
             }
+            ScriptNode::Enum { keyword, .. } => self.visit(SymbolKind::Enum, node_ref, keyword),
+            ScriptNode::EnumBody { .. } => (),
+            ScriptNode::EnumVariant { .. } => (),
             ScriptNode::Array { start, end, .. } => self.visit_array(node_ref, start, end),
             ScriptNode::String { start, end, .. } => self.visit_string(node_ref, start, end),
+            ScriptNode::Embedded { start, end, .. } => self.visit_string(node_ref, start, end),
             ScriptNode::Crate { token, .. } => self.visit(SymbolKind::Ident, node_ref, token),
             ScriptNode::This { token, .. } => self.visit(SymbolKind::Ident, node_ref, token),
             ScriptNode::Ident { token, .. } => self.visit(SymbolKind::Ident, node_ref, token),
@@ -4559,6 +5375,7 @@ impl<'a> Visitor for SymbolsLookup<'a> {
             ScriptNode::Query { .. } => (),
             ScriptNode::Call { args, .. } => self.visit_call(node_ref, args),
             ScriptNode::CallArgs { .. } => (),
+            ScriptNode::Arg { .. } => (),
             ScriptNode::Index { arg, .. } => self.visit_index(node_ref, arg),
             ScriptNode::IndexArg { .. } => (),
             ScriptNode::Field { token, .. } => self.visit(SymbolKind::Field, node_ref, token),
@@ -4642,11 +5459,17 @@ impl<'a> SymbolsLookup<'a> {
             return;
         }
 
-        let Some(ScriptNode::CallArgs { start, end, .. }) = args.deref(self.doc) else {
+        let Some(ScriptNode::CallArgs {
+            start,
+            end,
+            closure,
+            ..
+        }) = args.deref(self.doc)
+        else {
             return;
         };
 
-        if !self.touches_token_span(start, end) {
+        if !self.touches_token_span(start, end) && !self.touches_closure_params(closure) {
             return;
         }
 
@@ -4731,6 +5554,20 @@ impl<'a> SymbolsLookup<'a> {
         self.touches(&(start_site..end_span.end))
     }
 
+    // Returns true if the lookup span touches the parameters of the trailing
+    // closure: `foo(a, b) fn(<params>) {...}`.
+    fn touches_closure_params(&self, closure: &NodeRef) -> bool {
+        let Some(ScriptNode::Fn { params, .. }) = closure.deref(self.doc) else {
+            return false;
+        };
+
+        let Some(ScriptNode::FnParams { start, end, .. }) = params.deref(self.doc) else {
+            return false;
+        };
+
+        self.touches_token_span(start, end)
+    }
+
     #[inline(always)]
     fn token_span(&self, token_ref: &TokenRef) -> Option<SiteSpan> {
         let site = token_ref.site(self.doc)?;
@@ -4787,3 +5624,330 @@ fn descend_expr<'a>(doc: &'a ScriptDoc, mut expr: &'a NodeRef) -> Option<&'a Scr
         }
     }
 }
+
+// Orders the symbols by the source code position of their nodes. The symbols
+// that start at the same site are ordered by their end sites, and then by their
+// node references. Class-based queries produce symbols in hash order, so this
+// function makes their results deterministic.
+fn sort_by_position<T>(doc: &ScriptDoc, symbols: &mut [T], node_ref: impl Fn(&T) -> NodeRef) {
+    if symbols.len() < 2 {
+        return;
+    }
+
+    symbols.sort_by_cached_key(|symbol| {
+        let node_ref = node_ref(symbol);
+
+        let span = node_ref.span(doc).unwrap_or(Site::MAX..Site::MAX);
+
+        (span.start, span.end, node_ref)
+    });
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{
+            symbols::{LookupOptions, ModuleSymbol, SymbolKind},
+            IssueCode,
+            ModuleRead,
+            ModuleReadGuard,
+            ScriptModule,
+        },
+        export,
+        interpret::ScriptFn,
+        runtime::{Origin, ScriptPackage},
+    };
+
+    #[export(package)]
+    #[derive(Default)]
+    pub(crate) struct TestPackage;
+
+    static SOURCE: &str = r#"use foo.bar;
+let x = 10;
+x = x + 1;
+let s = struct { key: 1, };
+s.key;
+let f = fn() { return 1; };
+f();
+loop { break; }
+"#;
+
+    fn lookup(
+        read: &ModuleReadGuard<TriggerHandle>,
+        needle: &str,
+        nth: usize,
+        kind: SymbolKind,
+    ) -> ModuleSymbol {
+        let offset = SOURCE.match_indices(needle).nth(nth).unwrap().0;
+
+        let symbols = read
+            .symbols(
+                offset..(offset + needle.len()),
+                LookupOptions::default().filter(kind as u32),
+            )
+            .unwrap();
+
+        assert_eq!(symbols.len(), 1, "{needle:?} lookup failed");
+
+        symbols[0]
+    }
+
+    #[test]
+    fn test_symbol_shortcuts() {
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        assert_eq!(ModuleSymbol::Nil.name(&read), None);
+        assert!(ModuleSymbol::Nil.references(&read).unwrap().is_empty());
+        assert!(ModuleSymbol::Nil.declaration(&read).unwrap().is_nil());
+
+        let use_symbol = lookup(&read, "use", 0, SymbolKind::Use);
+        assert_eq!(use_symbol.name(&read).as_deref(), Some("bar"));
+        assert!(use_symbol.declaration(&read).unwrap() == use_symbol);
+
+        let package = lookup(&read, "foo", 0, SymbolKind::Package);
+        assert_eq!(package.name(&read).as_deref(), Some("foo"));
+        assert!(package.references(&read).unwrap().is_empty());
+        assert!(package.declaration(&read).unwrap() == package);
+
+        let var = lookup(&read, "x", 0, SymbolKind::Var);
+        assert_eq!(var.name(&read).as_deref(), Some("x"));
+        assert!(var.declaration(&read).unwrap() == var);
+
+        let var_refs = var.references(&read).unwrap();
+        assert_eq!(var_refs.len(), 2);
+        assert!(
+            var_refs
+                == vec![
+                    lookup(&read, "x", 1, SymbolKind::Ident),
+                    lookup(&read, "x", 2, SymbolKind::Ident),
+                ]
+        );
+
+        let ident = lookup(&read, "x", 2, SymbolKind::Ident);
+        assert_eq!(ident.name(&read).as_deref(), Some("x"));
+        assert!(ident.declaration(&read).unwrap() == var);
+        assert!(ident.references(&read).unwrap() == var_refs);
+        assert!(var_refs.contains(&ident));
+
+        let structure = lookup(&read, "struct", 0, SymbolKind::Struct);
+        assert_eq!(structure.name(&read), None);
+        assert!(structure.declaration(&read).unwrap() == structure);
+
+        let entry = lookup(&read, "key", 0, SymbolKind::Entry);
+        assert_eq!(entry.name(&read).as_deref(), Some("key"));
+        assert!(entry.declaration(&read).unwrap() == entry);
+
+        let field = lookup(&read, "key", 1, SymbolKind::Field);
+        assert_eq!(field.name(&read).as_deref(), Some("key"));
+        assert!(field.declaration(&read).unwrap() == entry);
+        assert!(entry.references(&read).unwrap() == vec![field]);
+        assert!(field.references(&read).unwrap() == vec![field]);
+
+        let function = lookup(&read, "fn", 0, SymbolKind::Fn);
+        assert_eq!(function.name(&read), None);
+        assert!(function.declaration(&read).unwrap() == function);
+
+        let ret = lookup(&read, "return", 0, SymbolKind::Return);
+        assert_eq!(ret.name(&read), None);
+        assert!(ret.references(&read).unwrap().is_empty());
+        assert!(ret.declaration(&read).unwrap().is_nil());
+
+        let call = lookup(&read, "f()", 0, SymbolKind::Call);
+        assert_eq!(call.name(&read), None);
+        assert!(call.declaration(&read).unwrap().is_nil());
+
+        let literal = lookup(&read, "10", 0, SymbolKind::Literal);
+        assert_eq!(literal.name(&read), None);
+        assert!(literal.references(&read).unwrap().is_empty());
+        assert!(literal.declaration(&read).unwrap().is_nil());
+
+        let brk = lookup(&read, "break", 0, SymbolKind::Break);
+        assert_eq!(brk.name(&read), None);
+        assert!(brk.declaration(&read).unwrap().is_nil());
+    }
+
+    #[test]
+    fn test_fn_captures() {
+        static SOURCE: &str = r#"let a = 1;
+let outer = fn(p) {
+    let c = p + 1;
+    let inner = fn() a + c + p;
+    return inner;
+};
+let b;
+let late = fn() b;
+b = 2;
+return outer(5);
+"#;
+
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let captures = |nth: usize| {
+            let offset = SOURCE.match_indices("fn").nth(nth).unwrap().0;
+
+            let symbols = read
+                .symbols(
+                    offset..offset,
+                    LookupOptions::default().filter(SymbolKind::Fn as u32),
+                )
+                .unwrap();
+
+            let Some(ModuleSymbol::Fn(symbol)) = symbols.first() else {
+                panic!("Missing function.");
+            };
+
+            symbol
+                .captures(&read)
+                .unwrap()
+                .into_iter()
+                .map(|var| var.var_name(&read).unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(captures(0), ["a"]);
+        assert_eq!(captures(1), ["a", "p", "c"]);
+        assert_eq!(captures(2), ["b"]);
+
+        let issues = read.diagnostics(3).unwrap();
+
+        assert_eq!(
+            issues
+                .iter()
+                .filter(|issue| issue.code() == IssueCode::LateCapture)
+                .count(),
+            1,
+        );
+
+        let mut inner = read.compile().unwrap().run().unwrap();
+
+        let inner = inner.borrow_ref::<ScriptFn>(Origin::nil()).unwrap();
+
+        assert_eq!(
+            inner.captures(80),
+            [
+                ("a", String::from("1")),
+                ("c", String::from("6")),
+                ("p", String::from("5"))
+            ],
+        );
+    }
+
+    #[test]
+    fn test_operator_declaration() {
+        static SOURCE: &str = r#"let a = struct {
+    __add: fn(rhs) rhs,
+    __neg: fn() self,
+};
+a + a;
+a - a;
+-a;
+1 + 2;
+"#;
+
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let lookup = |pattern: &str, kind: SymbolKind| {
+            let offset = SOURCE.find(pattern).unwrap();
+
+            read.symbols(offset..offset, LookupOptions::default().filter(kind as u32))
+                .unwrap()
+                .into_iter()
+                .next()
+                .unwrap()
+        };
+
+        let declaration = |pattern: &str| {
+            let ModuleSymbol::Operator(symbol) = lookup(pattern, SymbolKind::Operator) else {
+                panic!("Missing operator.");
+            };
+
+            symbol.declaration(&read).unwrap().map(ModuleSymbol::Entry)
+        };
+
+        assert!(declaration("+ a") == Some(lookup("__add", SymbolKind::Entry)));
+        assert!(declaration("-a") == Some(lookup("__neg", SymbolKind::Entry)));
+        assert!(declaration("- a").is_none());
+        assert!(declaration("+ 2").is_none());
+    }
+    #[test]
+    fn test_trailing_closure() {
+        static SOURCE: &str = r#"let each = fn(count, f) {
+    let sum = 0;
+    for i in 0..count {
+        sum += f(i);
+    }
+    return sum;
+};
+let one = fn(a) a;
+let a = each(3) fn(x) x * 2;
+let b = each(2)
+fn(x) {
+    return x + 1;
+};
+one(1);
+fn(x) x;
+if a > 100 {
+    one(1) fn(x) x;
+}
+return [a, b];
+"#;
+
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let call = |nth: usize| {
+            let offset = SOURCE.match_indices('(').nth(nth).unwrap().0;
+
+            let symbols = read
+                .symbols(
+                    offset..offset,
+                    LookupOptions::default().filter(SymbolKind::Call as u32),
+                )
+                .unwrap();
+
+            let Some(ModuleSymbol::Call(symbol)) = symbols.first() else {
+                panic!("Missing invocation.");
+            };
+
+            *symbol
+        };
+
+        // `each(3) fn(x) x * 2`
+        let each_3 = call(3);
+        assert_eq!(each_3.args(&read).len(), 2);
+        assert!(matches!(each_3.closure(&read), ModuleSymbol::Fn(_)));
+
+        // A `fn` on the next line still continues the invocation.
+        let each_2 = call(5);
+        assert_eq!(each_2.args(&read).len(), 2);
+        assert!(matches!(each_2.closure(&read), ModuleSymbol::Fn(_)));
+
+        // The semicolon ends the statement before the `fn` expression.
+        let one_1 = call(7);
+        assert_eq!(one_1.args(&read).len(), 1);
+        assert!(one_1.closure(&read).is_nil());
+
+        let issues = read.diagnostics(3).unwrap();
+
+        assert_eq!(
+            issues
+                .iter()
+                .filter(|issue| issue.code() == IssueCode::CallArityMismatch)
+                .count(),
+            1,
+        );
+
+        let mut result = read.compile().unwrap().run().unwrap();
+
+        assert_eq!(result.stringify(false), "[6, 3]");
+    }
+}
@@ -33,20 +33,30 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use std::{
+    borrow::Cow,
     fmt::{Debug, Display, Formatter},
-    ops::Deref,
+    ops::{Bound, Deref, RangeBounds},
 };
 
 use ahash::RandomState;
 use lady_deirdre::{
     analysis::DocumentReadGuard,
     arena::{Id, Identifiable},
+    lexis::{ByteIndex, Line, Position, PositionSpan, Site, SiteSpan, SourceCode, ToSite, ToSpan},
     syntax::SyntaxTree,
     units::Lexis,
 };
 
 use crate::{
-    format::{format_script_doc, format_script_path, ScriptFormatConfig, ScriptSnippet},
+    analysis::diff::diff_chars,
+    format::{
+        format_script_doc,
+        format_script_path,
+        ScriptFormatConfig,
+        ScriptFormatOutput,
+        ScriptSnippet,
+    },
+    report::system_panic,
     runtime::PackageMeta,
     syntax::{ScriptDoc, ScriptNode},
 };
@@ -173,8 +183,279 @@ impl<'a> ModuleText<'a> {
     /// See [format_script_text](crate::format::format_script_text) for details.
     #[inline(always)]
     pub fn format(&self, config: ScriptFormatConfig) -> Option<String> {
+        format_script_doc(config, self.doc_read.deref()).map(|output| output.text)
+    }
+
+    /// Similar to the [format](Self::format) function, but additionally
+    /// returns the formatter's [warnings](crate::format::FormatWarning).
+    ///
+    /// See
+    /// [format_script_text_with_warnings](crate::format::format_script_text_with_warnings)
+    /// for details.
+    #[inline(always)]
+    pub fn format_with_warnings(&self, config: ScriptFormatConfig) -> Option<ScriptFormatOutput> {
         format_script_doc(config, self.doc_read.deref())
     }
+
+    /// Computes a minimal set of edits that turns the source code text into
+    /// the `target` string.
+    ///
+    /// The texts are compared line by line first, and then the changed lines
+    /// are refined character by character. The resulting patches are
+    /// returned in ascending order and do not overlap. All patch spans refer
+    /// to the current source code text, so the patches can be applied
+    /// all at once (e.g., as a list of LSP TextEdits) or one by one in
+    /// reverse order.
+    ///
+    /// This function is useful for turning a complete rewrite of the text
+    /// (such as the [formatter](Self::format) output) into small edits that
+    /// preserve the editor's cursor positions and undo history.
+    ///
+    /// ```
+    /// # use ad_astra::{
+    /// #     analysis::{ModuleRead, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::{analysis::TriggerHandle, lexis::Position},
+    /// #     runtime::ScriptPackage,
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::new(Package::meta(), "let foo = 10;\nfoo;");
+    ///
+    /// let handle = TriggerHandle::new();
+    /// let module_read = module.read(&handle, 1).unwrap();
+    ///
+    /// let patches = module_read.text().diff("let foo = 20;\nfoo;");
+    ///
+    /// assert_eq!(patches.len(), 1);
+    /// assert_eq!(patches[0].site_span, 10..11);
+    /// assert_eq!(
+    ///     patches[0].position_span,
+    ///     Position::new(1, 11)..Position::new(1, 12),
+    /// );
+    /// assert_eq!(patches[0].text, "2");
+    /// ```
+    pub fn diff(&self, target: &str) -> Vec<TextPatch> {
+        let source = self.doc_read.substring(..);
+
+        let hunks = diff_chars(&source, target);
+
+        if hunks.is_empty() {
+            return Vec::new();
+        }
+
+        let mut target_offsets = target
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .collect::<Vec<_>>();
+
+        target_offsets.push(target.len());
+
+        let mut result = Vec::with_capacity(hunks.len());
+
+        for hunk in hunks {
+            let site_span = hunk.old;
+
+            let Some(position_span) = site_span.to_position_span(self.doc_read.deref()) else {
+                system_panic!("Span to position conversion failure.");
+            };
+
+            let text =
+                String::from(&target[target_offsets[hunk.new.start]..target_offsets[hunk.new.end]]);
+
+            result.push(TextPatch {
+                site_span,
+                position_span,
+                text,
+            });
+        }
+
+        result
+    }
+
+    /// Returns the number of lines in the source code text.
+    ///
+    /// The result is always positive: an empty text consists of one empty
+    /// line. If the text ends with a line break, the last line is empty.
+    #[inline(always)]
+    pub fn line_count(&self) -> usize {
+        self.doc_read.lines().lines_count()
+    }
+
+    /// Returns an iterator over the source code lines within the specified
+    /// `range` of line numbers.
+    ///
+    /// Line numbers are one-based, consistent with the
+    /// [Position](lady_deirdre::lexis::Position) object. For example,
+    /// `lines(10..20)` yields lines 10 through 19. The range is clamped to the
+    /// existing lines of the text, so `lines(..)` yields all lines.
+    ///
+    /// The iterator yields pairs of the line number and the line's text. The
+    /// line text does not include the line break characters (`"\n"` or
+    /// `"\r\n"`).
+    ///
+    /// ```
+    /// # use ad_astra::{
+    /// #     analysis::{ModuleRead, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::analysis::TriggerHandle,
+    /// #     runtime::ScriptPackage,
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::new(Package::meta(), "let x = 1;\r\nlet y = 2;\nx + y;");
+    ///
+    /// let handle = TriggerHandle::new();
+    /// let module_read = module.read(&handle, 1).unwrap();
+    /// let module_text = module_read.text();
+    ///
+    /// let lines = module_text
+    ///     .lines(2..)
+    ///     .map(|(line, text)| format!("{line}: {text}"))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(lines, ["2: let y = 2;", "3: x + y;"]);
+    /// ```
+    pub fn lines(
+        &self,
+        range: impl RangeBounds<Line>,
+    ) -> impl Iterator<Item = (Line, Cow<str>)> + '_ {
+        let first = match range.start_bound() {
+            Bound::Included(line) => *line,
+            Bound::Excluded(line) => line.saturating_add(1),
+            Bound::Unbounded => 1,
+        };
+
+        let last = match range.end_bound() {
+            Bound::Included(line) => *line,
+            Bound::Excluded(line) => line.saturating_sub(1),
+            Bound::Unbounded => usize::MAX,
+        };
+
+        (first.max(1)..=last.min(self.line_count())).map(|line| (line, self.line_text(line)))
+    }
+
+    /// Returns a substring of the source code text between two
+    /// [positions](lady_deirdre::lexis::Position).
+    ///
+    /// Unlike the [substring](lady_deirdre::lexis::SourceCode::substring)
+    /// function, which clamps the positions to the text bounds, this function
+    /// returns None if the span is out of the text bounds, or if the start
+    /// position is greater than the end position.
+    ///
+    /// A position is within the text bounds if its line exists in the text,
+    /// and its column is not greater than the length of the line's text (not
+    /// including the line break characters) plus one.
+    pub fn substring_at(&self, span: PositionSpan) -> Option<Cow<str>> {
+        let start = self.site_of(&span.start)?;
+        let end = self.site_of(&span.end)?;
+
+        if start > end {
+            return None;
+        }
+
+        Some(self.doc_read.substring(start..end))
+    }
+
+    /// Returns a character at the specified
+    /// [position](lady_deirdre::lexis::Position).
+    ///
+    /// Returns None if the position is out of the text bounds, or if the
+    /// position points to the end of the line (i.e., to the line break
+    /// characters or to the end of the text).
+    pub fn char_at(&self, position: Position) -> Option<char> {
+        let site = self.site_of(&position)?;
+
+        if position.column > self.line_text(position.line).chars().count() {
+            return None;
+        }
+
+        self.doc_read.chars(site..(site + 1)).next()
+    }
+
+    /// Converts a UTF-8 byte offset in the source code text into a
+    /// [position](lady_deirdre::lexis::Position).
+    ///
+    /// The offset equal to the byte length of the text is the position of the
+    /// end of the text.
+    ///
+    /// Returns None if the offset exceeds the text length, or if the offset
+    /// does not point to the first byte of a character.
+    pub fn position_of_offset(&self, offset: ByteIndex) -> Option<Position> {
+        let text = self.doc_read.substring(..);
+
+        if !text.is_char_boundary(offset) {
+            return None;
+        }
+
+        let site: Site = text[..offset].chars().count();
+
+        site.to_position(self.doc_read.deref())
+    }
+
+    fn line_text(&self, line: Line) -> Cow<str> {
+        let span = self.doc_read.lines().line_span(line);
+
+        match self.doc_read.substring(span) {
+            Cow::Borrowed(text) => Cow::Borrowed(trim_line_break(text)),
+
+            Cow::Owned(mut text) => {
+                text.truncate(trim_line_break(&text).len());
+
+                Cow::Owned(text)
+            }
+        }
+    }
+
+    fn site_of(&self, position: &Position) -> Option<Site> {
+        if position.line < 1 || position.line > self.line_count() {
+            return None;
+        }
+
+        if position.column < 1 {
+            return None;
+        }
+
+        if position.column > self.line_text(position.line).chars().count() + 1 {
+            return None;
+        }
+
+        position.to_site(self.doc_read.deref())
+    }
+}
+
+/// A replacement of a source code text fragment.
+///
+/// Created by the [ModuleText::diff] function.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TextPatch {
+    /// The span of the replaced fragment in terms of the character offsets of
+    /// the source code text.
+    pub site_span: SiteSpan,
+
+    /// The span of the replaced fragment in terms of the lines and columns of
+    /// the source code text.
+    pub position_span: PositionSpan,
+
+    /// The text that replaces the fragment.
+    ///
+    /// If the text is empty, the patch is a deletion. If the span is empty,
+    /// the patch is an insertion.
+    pub text: String,
+}
+
+#[inline(always)]
+fn trim_line_break(text: &str) -> &str {
+    match text.strip_suffix('\n') {
+        Some(text) => text.strip_suffix('\r').unwrap_or(text),
+        None => text,
+    }
 }
 
 /// An interface that provides access to script module texts by module [Id].
@@ -204,3 +485,339 @@ impl<'a> ModuleTextResolver for ModuleText<'a> {
         Some(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, ops::Bound};
+
+    use lady_deirdre::{
+        analysis::TriggerHandle,
+        lexis::{Position, SourceCode},
+    };
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        runtime::ScriptPackage,
+    };
+
+    #[test]
+    fn test_text_lines() {
+        let module = ScriptModule::new(TestPackage::meta(), "let a = 1;\r\nlet b = \"é\";\n\nb;");
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+        let text = read.text();
+
+        assert_eq!(text.line_count(), 4);
+
+        let lines = text.lines(..).collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], (1, "let a = 1;".into()));
+        assert_eq!(lines[1], (2, "let b = \"é\";".into()));
+        assert_eq!(lines[2], (3, "".into()));
+        assert_eq!(lines[3], (4, "b;".into()));
+
+        assert_eq!(text.lines(2..3).count(), 1);
+        assert_eq!(text.lines(2..=3).count(), 2);
+        assert_eq!(text.lines(0..2).count(), 1);
+        assert_eq!(text.lines(4..100).count(), 1);
+        assert_eq!(text.lines(5..).count(), 0);
+        assert_eq!(
+            text.lines((Bound::Excluded(2), Bound::Excluded(3))).count(),
+            0
+        );
+
+        let module = ScriptModule::new(TestPackage::meta(), "");
+        let read = module.read(&handle, 1).unwrap();
+        let text = read.text();
+
+        assert_eq!(text.line_count(), 1);
+        assert_eq!(text.lines(..).collect::<Vec<_>>(), [(1, "".into())]);
+
+        let module = ScriptModule::new(TestPackage::meta(), "a;\n");
+        let read = module.read(&handle, 1).unwrap();
+        let text = read.text();
+
+        assert_eq!(text.line_count(), 2);
+        assert_eq!(
+            text.lines(..).collect::<Vec<_>>(),
+            [(1, "a;".into()), (2, "".into())],
+        );
+    }
+
+    #[test]
+    fn test_text_positions() {
+        let module = ScriptModule::new(TestPackage::meta(), "let a = 1;\r\nlet b = \"é\";\nb;");
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+        let text = read.text();
+
+        let at = |line, column| Position::new(line, column);
+
+        assert_eq!(text.substring_at(at(1, 5)..at(1, 6)).as_deref(), Some("a"));
+        assert_eq!(
+            text.substring_at(at(1, 1)..at(1, 11)).as_deref(),
+            Some("let a = 1;")
+        );
+        assert_eq!(
+            text.substring_at(at(1, 9)..at(2, 4)).as_deref(),
+            Some("1;\r\nlet")
+        );
+        assert_eq!(
+            text.substring_at(at(2, 9)..at(2, 12)).as_deref(),
+            Some("\"é\"")
+        );
+        assert_eq!(text.substring_at(at(3, 1)..at(3, 3)).as_deref(), Some("b;"));
+        assert_eq!(text.substring_at(at(2, 3)..at(2, 3)).as_deref(), Some(""));
+        assert_eq!(text.substring_at(at(1, 12)..at(2, 1)), None);
+        assert_eq!(text.substring_at(at(1, 0)..at(1, 2)), None);
+        assert_eq!(text.substring_at(at(0, 1)..at(1, 2)), None);
+        assert_eq!(text.substring_at(at(3, 1)..at(4, 1)), None);
+        assert_eq!(text.substring_at(at(2, 1)..at(1, 1)), None);
+
+        assert_eq!(text.char_at(at(1, 1)), Some('l'));
+        assert_eq!(text.char_at(at(2, 10)), Some('é'));
+        assert_eq!(text.char_at(at(2, 12)), Some(';'));
+        assert_eq!(text.char_at(at(1, 11)), None);
+        assert_eq!(text.char_at(at(2, 13)), None);
+        assert_eq!(text.char_at(at(3, 3)), None);
+        assert_eq!(text.char_at(at(4, 1)), None);
+
+        assert_eq!(text.position_of_offset(0), Some(at(1, 1)));
+        assert_eq!(text.position_of_offset(10), Some(at(1, 11)));
+        assert_eq!(text.position_of_offset(12), Some(at(2, 1)));
+        assert_eq!(text.position_of_offset(21), Some(at(2, 10)));
+        assert_eq!(text.position_of_offset(22), None);
+        assert_eq!(text.position_of_offset(23), Some(at(2, 11)));
+        assert_eq!(text.position_of_offset(26), Some(at(3, 1)));
+        assert_eq!(text.position_of_offset(28), Some(at(3, 3)));
+        assert_eq!(text.position_of_offset(29), None);
+    }
+
+    #[test]
+    fn test_text_diff() {
+        let handle = TriggerHandle::new();
+
+        let check = |source: &str, target: &str| {
+            let module = ScriptModule::new(TestPackage::meta(), source);
+            let read = module.read(&handle, 1).unwrap();
+            let text = read.text();
+
+            let patches = text.diff(target);
+            let mut result = source.chars().collect::<Vec<_>>();
+            let mut last = None;
+
+            for patch in patches.iter().rev() {
+                if let Some(last) = last {
+                    assert!(patch.site_span.end <= last, "Overlapping patches.");
+                }
+
+                last = Some(patch.site_span.start);
+
+                assert_eq!(
+                    text.substring_at(patch.position_span.clone()),
+                    Some(text.substring(patch.site_span.clone())),
+                );
+
+                let _ = result.splice(patch.site_span.clone(), patch.text.chars());
+            }
+
+            assert_eq!(result.into_iter().collect::<String>(), target);
+
+            patches.len()
+        };
+
+        assert_eq!(check("", ""), 0);
+        assert_eq!(check("let a = 1;", "let a = 1;"), 0);
+        assert_eq!(check("let a = 1;", "let a = 2;"), 1);
+        assert_eq!(check("let a=1;\nlet b=2;\n", "let a = 1;\nlet b=2;\n"), 2);
+        assert_eq!(check("a;\r\nb;\r\n", "a;\r\nc;\nb;\r\n"), 1);
+        assert_eq!(check("a;\r\nb;", "a;\rb;"), 1);
+        assert_eq!(check("é\r\né", "é\r\r\né"), 1);
+        assert_eq!(check("let b = \"é\";", "let b = \"ёé\";"), 1);
+        assert_eq!(check("a;\nb;", ""), 1);
+        assert_eq!(check("", "a;\nb;"), 1);
+
+        const ALPHABET: [&str; 8] = ["a", "b", " ", ";", "\n", "\r\n", "é", "{}"];
+
+        let mut seed = 0x2545F4914F6CDD1D_u64;
+
+        let mut random = move |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+
+            (seed % bound as u64) as usize
+        };
+
+        for _ in 0..200 {
+            let mut source = String::new();
+            let mut target = String::new();
+
+            for _ in 0..random(40) {
+                let fragment = ALPHABET[random(ALPHABET.len())];
+
+                source.push_str(fragment);
+
+                match random(4) {
+                    0 => (),
+                    1 => target.push_str(ALPHABET[random(ALPHABET.len())]),
+                    _ => target.push_str(fragment),
+                }
+            }
+
+            let _ = check(&source, &target);
+        }
+    }
+
+    #[test]
+    fn test_text_matrix() {
+        static TEXTS: [&str; 10] = [
+            "",
+            "a;",
+            "a;\n",
+            "a;\r\n",
+            "\n",
+            "\r\n\r\n",
+            "a;\nb;\r\nc;",
+            "a;\r\n\nb;\n",
+            "\"é\";\r\n\"ж\";",
+            "\"😀\";\n\n\r\n",
+        ];
+
+        let handle = TriggerHandle::new();
+
+        for source in TEXTS {
+            let module = ScriptModule::new(TestPackage::meta(), source);
+            let read = module.read(&handle, 1).unwrap();
+            let text = read.text();
+
+            // The reference model of the text: the lines without the line
+            // breaks, and the position and the character of each site.
+            let lines = source
+                .split('\n')
+                .map(|line| line.strip_suffix('\r').unwrap_or(line))
+                .collect::<Vec<_>>();
+
+            let mut positions = Vec::new();
+            let mut line = 1;
+            let mut column = 1;
+
+            for (offset, ch) in source.char_indices() {
+                positions.push((offset, Position::new(line, column), ch));
+
+                match ch {
+                    '\n' => {
+                        line += 1;
+                        column = 1;
+                    }
+
+                    _ => column += 1,
+                }
+            }
+
+            let end = Position::new(line, column);
+
+            assert_eq!(text.line_count(), lines.len(), "{source:?}");
+
+            assert_eq!(
+                text.lines(..).collect::<Vec<_>>(),
+                lines
+                    .iter()
+                    .enumerate()
+                    .map(|(index, line)| (index + 1, Cow::Borrowed(*line)))
+                    .collect::<Vec<_>>(),
+                "{source:?}",
+            );
+
+            for first in 0..=(lines.len() + 1) {
+                for last in 0..=(lines.len() + 1) {
+                    let expected = (first.max(1)..=last.min(lines.len())).count();
+
+                    assert_eq!(text.lines(first..=last).count(), expected, "{source:?}");
+                }
+            }
+
+            for &(offset, position, ch) in &positions {
+                assert_eq!(
+                    text.position_of_offset(offset),
+                    Some(position),
+                    "{source:?} at {offset}",
+                );
+
+                let line_length = lines[position.line - 1].chars().count();
+
+                let expected = match position.column <= line_length {
+                    true => Some(ch),
+                    false => None,
+                };
+
+                assert_eq!(text.char_at(position), expected, "{source:?} at {offset}");
+
+                if position.column <= line_length + 1 {
+                    assert_eq!(
+                        text.substring_at(Position::new(1, 1)..position).as_deref(),
+                        Some(&source[..offset]),
+                        "{source:?} at {offset}",
+                    );
+
+                    assert_eq!(
+                        text.substring_at(position..end).as_deref(),
+                        Some(&source[offset..]),
+                        "{source:?} at {offset}",
+                    );
+                }
+
+                for inner in 1..ch.len_utf8() {
+                    assert_eq!(text.position_of_offset(offset + inner), None);
+                }
+            }
+
+            assert_eq!(text.position_of_offset(source.len()), Some(end));
+            assert_eq!(text.position_of_offset(source.len() + 1), None);
+            assert_eq!(text.char_at(end), None);
+
+            assert_eq!(
+                text.substring_at(Position::new(1, 1)..end).as_deref(),
+                Some(source),
+            );
+
+            for (index, line) in lines.iter().enumerate() {
+                let length = line.chars().count();
+
+                assert_eq!(text.char_at(Position::new(index + 1, 0)), None);
+                assert_eq!(text.char_at(Position::new(index + 1, length + 2)), None);
+
+                assert_eq!(
+                    text.substring_at(
+                        Position::new(index + 1, 1)..Position::new(index + 1, length + 1)
+                    )
+                    .as_deref(),
+                    Some(*line),
+                    "{source:?}",
+                );
+
+                assert_eq!(
+                    text.substring_at(
+                        Position::new(index + 1, 1)..Position::new(index + 1, length + 2)
+                    ),
+                    None,
+                    "{source:?}",
+                );
+            }
+
+            let after = Position::new(lines.len() + 1, 1);
+
+            assert_eq!(text.char_at(Position::new(0, 1)), None);
+            assert_eq!(text.char_at(after), None);
+            assert_eq!(text.substring_at(Position::new(1, 1)..after), None);
+            assert_eq!(
+                text.substring_at(end..Position::new(1, 1)),
+                match source.is_empty() {
+                    true => Some(Cow::Borrowed("")),
+                    false => None,
+                }
+            );
+        }
+    }
+}
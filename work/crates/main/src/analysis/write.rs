@@ -32,27 +32,41 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::ops::Deref;
+use std::{
+    fmt::{Display, Formatter},
+    io::BufRead,
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
 
 use ahash::RandomState;
+use compact_str::CompactString;
 use lady_deirdre::{
-    analysis::{ExclusiveTask, MutationAccess, TaskHandle, TriggerHandle},
+    analysis::{AbstractTask, ExclusiveTask, MutationAccess, Revision, TaskHandle, TriggerHandle},
     arena::{Id, Identifiable},
-    lexis::{ToSite, ToSpan},
+    lexis::{SiteSpan, SourceCode, ToSite, ToSpan},
 };
 
 use crate::{
     analysis::{
         completions::PROMPT_STRING,
+        diff::{common_prefix, common_suffix, diff_chars, diff_lines},
+        history::EditHistory,
+        lint::ModuleLints,
         read::ModuleReadSealed,
+        stats::{QueryKind, StatsCollector},
+        AnalysisConfig,
         Completions,
+        LintPass,
         ModuleError,
         ModuleRead,
         ModuleResult,
         ModuleResultEx,
+        ModuleText,
     },
+    format::ScriptFormatConfig,
     runtime::PackageMeta,
-    syntax::ScriptNode,
+    syntax::{ScriptNode, ScriptToken},
 };
 
 /// An object that grants exclusive access to the
@@ -68,6 +82,9 @@ pub struct ModuleWriteGuard<'a, H: TaskHandle = TriggerHandle> {
     pub(super) id: Id,
     pub(super) package: &'static PackageMeta,
     pub(super) task: ExclusiveTask<'a, ScriptNode, H, RandomState>,
+    pub(super) history: &'a Mutex<EditHistory>,
+    pub(super) lints: &'a Mutex<ModuleLints<H>>,
+    pub(super) stats: &'a StatsCollector,
 }
 
 impl<'a, H: TaskHandle> Identifiable for ModuleWriteGuard<'a, H> {
@@ -93,6 +110,21 @@ impl<'a, H: TaskHandle> ModuleReadSealed<H> for ModuleWriteGuard<'a, H> {
     fn task(&self) -> &Self::Task {
         &self.task
     }
+
+    #[inline(always)]
+    fn history(&self) -> &Mutex<EditHistory> {
+        self.history
+    }
+
+    #[inline(always)]
+    fn lints(&self) -> &Mutex<ModuleLints<H>> {
+        self.lints
+    }
+
+    #[inline(always)]
+    fn stats(&self) -> &StatsCollector {
+        self.stats
+    }
 }
 
 impl<'a, H: TaskHandle> ModuleWriteSealed<H> for ModuleWriteGuard<'a, H> {
@@ -129,9 +161,14 @@ where
     /// Therefore, it is acceptable to call this function on each end-user
     /// keystroke.
     ///
+    /// The function returns the module's new
+    /// [text revision](ModuleRead::text_revision). You can use this number to
+    /// [remap](ModuleRead::remap_span) the spans obtained before the edit to
+    /// the spans of the later text revisions.
+    ///
     /// The function returns a [ModuleError::Cursor] error if the provided
     /// `span` is not [valid](ToSpan::is_valid_span) for this module.
-    fn edit(&mut self, span: impl ToSpan, text: impl AsRef<str>) -> ModuleResult<()> {
+    fn edit(&mut self, span: impl ToSpan, text: impl AsRef<str>) -> ModuleResult<Revision> {
         let id = self.id();
 
         let span = {
@@ -143,11 +180,345 @@ where
             }
         };
 
-        self.task_mut()
-            .write_to_doc(id, span, text)
+        self.write_text(span, text)
+    }
+
+    /// Replaces the entire source code text of the script module with the
+    /// text read from the `reader`, patching only the lines that have been
+    /// changed.
+    ///
+    /// Unlike `edit(.., text)`, which rewrites the whole module, this
+    /// function compares the incoming text with the current module text
+    /// line by line and applies the differences as a series of incremental
+    /// [edits](Self::edit). The analysis results for the untouched regions of
+    /// the source code are preserved. This makes the function suitable for
+    /// synchronizing large documents (e.g., when the script file has been
+    /// changed on disk) when the exact set of changes is unknown.
+    ///
+    /// The function is most efficient when the changes modify existing lines.
+    /// If the changes insert or remove lines in several places of the text,
+    /// the function rewrites the entire text at once, which is as fast as
+    /// the `edit(.., text)` call.
+    ///
+    /// The function returns an [EditSummary] object that describes the
+    /// applied changes. If the incoming text matches the current module text,
+    /// the function does not change the module, and the summary is
+    /// [empty](EditSummary::is_empty).
+    ///
+    /// The function returns a [ModuleError::Input] error if the reader fails
+    /// to provide a valid UTF-8 text. In this case, the module remains
+    /// unchanged.
+    ///
+    /// If the function returns an
+    /// [Interrupted](ModuleError::Interrupted) error, some of the changes may
+    /// already be applied to the module. To finish the synchronization, call
+    /// this function again with the same input text.
+    fn replace_from_reader(&mut self, mut reader: impl BufRead) -> ModuleResult<EditSummary> {
+        let id = self.id();
+
+        let mut new_text = Vec::new();
+
+        loop {
+            let mut line = String::new();
+
+            match reader.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => new_text.push(line),
+                Err(_) => return Err(ModuleError::Input(id)),
+            }
+        }
+
+        let new_lines = new_text.iter().map(String::as_str).collect::<Vec<_>>();
+
+        let doc_read = self.read_doc();
+        let old_text = doc_read.substring(..).into_owned();
+
+        drop(doc_read);
+
+        let old_lines = old_text.split_inclusive('\n').collect::<Vec<_>>();
+
+        // Byte and character offsets of the line starts.
+        let mut old_starts = Vec::with_capacity(old_lines.len() + 1);
+        let mut byte = 0;
+        let mut site = 0;
+
+        old_starts.push((byte, site));
+
+        for line in &old_lines {
+            byte += line.len();
+            site += line.chars().count();
+            old_starts.push((byte, site));
+        }
+
+        let hunks = diff_lines(&old_lines, &new_lines);
+
+        let mut summary = EditSummary::default();
+
+        for hunk in &hunks {
+            summary.hunks += 1;
+            summary.lines_added += hunk.new.len();
+            summary.lines_removed += hunk.old.len();
+        }
+
+        // The hunks that insert or remove lines usually insert or remove
+        // statements, and each such edit may lead to reparsing of the
+        // enclosing block, which is nearly as expensive as rewriting the
+        // entire text. If there are many such hunks, rewriting the text at
+        // once is cheaper.
+        let structural = hunks
+            .iter()
+            .filter(|hunk| hunk.old.len() != hunk.new.len())
+            .count();
+
+        if structural > 1 {
+            let span = 0..old_starts[old_lines.len()].1;
+
+            summary.revision = self.write_text(span, new_lines.concat())?;
+
+            return Ok(summary);
+        }
+
+        // The hunks are applied in reverse order such that the sites of
+        // the preceding hunks remain valid.
+        for hunk in hunks.iter().rev() {
+            let (old_start_byte, old_start_site) = old_starts[hunk.old.start];
+            let (old_end_byte, old_end_site) = old_starts[hunk.old.end];
+
+            let old_fragment = &old_text[old_start_byte..old_end_byte];
+            let new_fragment = new_lines[hunk.new.clone()].concat();
+
+            // Narrowing the edit to the changed characters keeps the
+            // incremental reparsing local to the changed statement.
+            let (prefix_bytes, prefix_chars) = common_prefix(old_fragment, &new_fragment);
+
+            let (suffix_bytes, suffix_chars) =
+                common_suffix(&old_fragment[prefix_bytes..], &new_fragment[prefix_bytes..]);
+
+            let span = (old_start_site + prefix_chars)..(old_end_site - suffix_chars);
+            let text = &new_fragment[prefix_bytes..(new_fragment.len() - suffix_bytes)];
+
+            summary.revision = self.write_text(span, text)?;
+        }
+
+        if hunks.is_empty() {
+            summary.revision = self.text_revision();
+        }
+
+        Ok(summary)
+    }
+
+    /// Reformats the source code of the script module according to
+    /// the formatting rules, patching only the changed fragments of the text.
+    ///
+    /// Unlike rewriting the module with the [formatted](ModuleText::format)
+    /// text using the [edit](Self::edit) function, this function preserves
+    /// the analysis results of the untouched regions of the source code, and
+    /// the [annotations](Self::annotate) stay attached to the reformatted
+    /// text.
+    ///
+    /// If the module has syntax errors, the function does not change the
+    /// module and returns None. Otherwise, the function returns an
+    /// [EditSummary] object that describes the applied changes (see
+    /// [replace_from_reader](Self::replace_from_reader) for details).
+    fn apply_format(&mut self, config: ScriptFormatConfig) -> ModuleResult<Option<EditSummary>> {
+        let Some(text) = self.text().format(config) else {
+            return Ok(None);
+        };
+
+        self.replace_from_reader(text.as_bytes()).map(Some)
+    }
+
+    /// Attaches an opaque piece of the host's `data` to the `span` fragment of
+    /// the source code.
+    ///
+    /// The `span` argument can be an absolute Unicode character range, such
+    /// as `10..20`, a [line-column](lady_deirdre::lexis::Position) range, or
+    /// a [ScriptOrigin](crate::runtime::ScriptOrigin) instance.
+    ///
+    /// The annotations never affect the analysis of the module. The module
+    /// moves the annotation spans on each subsequent source code edit, such
+    /// that the annotation stays attached to the surviving fragment of the
+    /// annotated text. See [ModuleAnnotation] for details.
+    ///
+    /// The function returns the identifier of the new annotation, through
+    /// which you can [remove](Self::remove_annotation) it later.
+    ///
+    /// The function returns a [ModuleError::Cursor] error if the provided
+    /// `span` is not [valid](ToSpan::is_valid_span) for this module, or if
+    /// the span is empty.
+    ///
+    /// ```rust
+    /// # use ad_astra::{
+    /// #     analysis::{ModuleRead, ModuleWrite, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::analysis::TriggerHandle,
+    /// #     runtime::ScriptPackage,
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::new(Package::meta(), "let foo = 10;");
+    ///
+    /// let handle = TriggerHandle::new();
+    /// let mut write = module.write(&handle, 1).unwrap();
+    ///
+    /// let id = write.annotate(4..7, "alice").unwrap();
+    ///
+    /// let _ = write.edit(0..0, "// Counter.\n").unwrap();
+    ///
+    /// let annotations = write.annotations(..).unwrap();
+    ///
+    /// assert_eq!(annotations[0].id, id);
+    /// assert_eq!(annotations[0].span, 16..19);
+    /// assert_eq!(annotations[0].data.as_ref(), b"alice");
+    /// ```
+    fn annotate(&mut self, span: impl ToSpan, data: impl AsRef<[u8]>) -> ModuleResult<u64> {
+        let id = self.id();
+
+        let span = {
+            let doc_read = self.read_doc();
+
+            match span.to_site_span(doc_read.deref()) {
+                Some(span) if span.start < span.end => span,
+                _ => return Err(ModuleError::Cursor(id)),
+            }
+        };
+
+        Ok(self
+            .lock_history()
+            .annotations_mut()
+            .insert(span, Arc::from(data.as_ref())))
+    }
+
+    /// Removes the annotation previously attached by the
+    /// [annotate](Self::annotate) function.
+    ///
+    /// Returns false if the module does not have an annotation with the
+    /// specified `id` (e.g., if a later edit has rewritten the annotated text
+    /// entirely).
+    #[inline(always)]
+    fn remove_annotation(&mut self, id: u64) -> bool {
+        self.lock_history().annotations_mut().remove(id)
+    }
+
+    /// Removes all annotations of the script module.
+    #[inline(always)]
+    fn clear_annotations(&mut self) {
+        self.lock_history().annotations_mut().clear();
+    }
+
+    /// Sets the list of name patterns that the module's
+    /// [dead code report](crate::analysis::ModuleRead::dead_code) should
+    /// ignore.
+    ///
+    /// A function variable, struct entry, or imported package whose name
+    /// matches any of the `patterns` is never reported as unused. Each pattern
+    /// is either an exact name (`"main"`) or a wildcard pattern, where `*`
+    /// matches any sequence of characters and `?` matches exactly one
+    /// character (`"on_*"`, `"_*"`).
+    ///
+    /// Each call replaces the previously set list. By default, the list is
+    /// empty.
+    fn set_dead_code_allow(
+        &mut self,
+        patterns: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> ModuleResult<()> {
+        let id = self.id();
+
+        let patterns = patterns
+            .into_iter()
+            .map(|pattern| CompactString::from(pattern.as_ref()))
+            .collect::<Vec<_>>();
+
+        let task = self.task();
+
+        task.common()
+            .dead_code_allow
+            .mutate(task, |allow| {
+                if allow.patterns == patterns {
+                    return false;
+                }
+
+                allow.patterns = patterns;
+
+                true
+            })
             .into_module_result(id)
     }
 
+    /// Sets the limits of the module's semantic analysis.
+    ///
+    /// The limits protect the code editor from pathological source code that
+    /// could otherwise make the analysis unreasonably slow. See
+    /// [AnalysisConfig] for details.
+    ///
+    /// Each call replaces the previously set configuration. By default, the
+    /// module uses the [AnalysisConfig::default] limits.
+    ///
+    /// If the new configuration changes the
+    /// [keyword aliases](AnalysisConfig::keyword_aliases), the function
+    /// rescans the module's source code, which advances the module's
+    /// [text revision](ModuleRead::text_revision).
+    fn set_analysis_config(&mut self, config: AnalysisConfig) -> ModuleResult<()> {
+        let id = self.id();
+
+        let task = self.task();
+
+        let mut rescan = false;
+
+        task.common()
+            .analysis_config
+            .mutate(task, |current| {
+                if current == &config {
+                    return false;
+                }
+
+                rescan = current.keyword_aliases != config.keyword_aliases;
+
+                *current = config;
+
+                true
+            })
+            .into_module_result(id)?;
+
+        if rescan {
+            let _ = self.rescan_text()?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the number of the most recent source code edits that the module
+    /// remembers to [remap](ModuleRead::remap_span) the spans of the older
+    /// text revisions.
+    ///
+    /// Each call to the [edit](Self::edit) function and each hunk applied by
+    /// the [replace_from_reader](Self::replace_from_reader) function is a
+    /// separate edit. The spans of the text revisions older than the
+    /// `capacity` most recent edits cannot be remapped.
+    ///
+    /// Reducing the capacity discards the oldest edits immediately. By
+    /// default, the module remembers 1024 edits. Setting the capacity to zero
+    /// turns off the edit history.
+    fn set_edit_history_capacity(&mut self, capacity: usize) {
+        self.lock_history().set_capacity(capacity);
+    }
+
+    /// Sets the host's [lint passes](LintPass) of this module, replacing the
+    /// previously registered passes.
+    ///
+    /// The analyzer runs the passes when the diagnostics of the corresponding
+    /// depth are requested through the
+    /// [ModuleReadGuard](crate::analysis::ModuleReadGuard), and merges the
+    /// issues reported by the passes into the built-in
+    /// [diagnostics](ModuleRead::diagnostics). Passing an empty slice turns
+    /// off the custom lints.
+    fn set_lint_passes(&mut self, passes: &[&'static dyn LintPass<H>]) {
+        self.lock_lints().set_passes(passes);
+    }
+
     /// Returns a [Completions] description object that describes potential
     /// completions for the script module's source code at the specified
     /// `site` position.
@@ -159,6 +530,67 @@ where
     /// The function returns a [ModuleError::Cursor] error if the provided
     /// `site` is not [valid](ToSite::is_valid_site) for this module.
     fn completions(&mut self, site: impl ToSite) -> ModuleResult<Completions> {
+        let query = self.stats().start(QueryKind::Completions);
+
+        let result = self.analyze_completions(site);
+
+        self.stats().finish(query);
+
+        result
+    }
+}
+
+/// A summary of the changes applied by the
+/// [replace_from_reader](ModuleWrite::replace_from_reader) function.
+///
+/// The Display implementation of this object prints a short human-readable
+/// description of the changes, which is useful for logging.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub struct EditSummary {
+    /// The number of the changed regions. Each region is a contiguous range
+    /// of lines.
+    pub hunks: usize,
+
+    /// The number of the inserted lines.
+    pub lines_added: usize,
+
+    /// The number of the removed lines.
+    pub lines_removed: usize,
+
+    /// The module's [text revision](ModuleRead::text_revision) after the
+    /// changes.
+    pub revision: Revision,
+}
+
+impl Display for EditSummary {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return formatter.write_str("no changes");
+        }
+
+        formatter.write_fmt(format_args!(
+            "+{} -{} lines in {} hunk(s)",
+            self.lines_added, self.lines_removed, self.hunks,
+        ))
+    }
+}
+
+impl EditSummary {
+    /// Returns true if no changes have been applied to the module.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.hunks == 0
+    }
+}
+
+pub trait ModuleWriteSealed<H: TaskHandle>: ModuleReadSealed<H>
+where
+    <Self as ModuleReadSealed<H>>::Task: MutationAccess<ScriptNode, H, RandomState>,
+{
+    fn task_mut(&mut self) -> &mut Self::Task;
+
+    fn analyze_completions(&mut self, site: impl ToSite) -> ModuleResult<Completions> {
         let id = self.id();
 
         let site = {
@@ -170,26 +602,91 @@ where
             }
         };
 
-        let _ = self
-            .task_mut()
-            .write_to_doc(id, site..site, PROMPT_STRING)
-            .into_module_result(id)?;
+        let aliases = self.keyword_aliases()?;
+
+        let _ = ScriptToken::with_keyword_aliases(aliases, || {
+            self.task_mut().write_to_doc(id, site..site, PROMPT_STRING)
+        })
+        .into_module_result(id)?;
 
         let task = self.task();
         let result = Completions::analyze(id, site, task).forward();
 
-        let _ = self
-            .task_mut()
-            .write_to_doc(id, site..(site + PROMPT_STRING.len()), "")
-            .into_module_result(id)?;
+        let _ = ScriptToken::with_keyword_aliases(aliases, || {
+            self.task_mut()
+                .write_to_doc(id, site..(site + PROMPT_STRING.len()), "")
+        })
+        .into_module_result(id)?;
 
         result.into_module_result(id)
     }
-}
 
-pub trait ModuleWriteSealed<H: TaskHandle>: ModuleReadSealed<H>
-where
-    <Self as ModuleReadSealed<H>>::Task: MutationAccess<ScriptNode, H, RandomState>,
-{
-    fn task_mut(&mut self) -> &mut Self::Task;
+    fn keyword_aliases(&self) -> ModuleResult<&'static [(&'static str, &'static str)]> {
+        let id = self.id();
+        let task = self.task();
+
+        let (_, config) = task
+            .common()
+            .analysis_config
+            .snapshot(task)
+            .into_module_result(id)?;
+
+        Ok(config.keyword_aliases)
+    }
+
+    fn write_text(&mut self, span: SiteSpan, text: impl AsRef<str>) -> ModuleResult<Revision> {
+        let id = self.id();
+        let text = text.as_ref();
+        let inserted = text.chars().count();
+
+        // The character-level difference lets the annotations survive
+        // the edits that rewrite the annotated text with the same text
+        // (e.g., reformatting of the surrounding code).
+        let refinement = match self.lock_history().annotations().is_empty() {
+            true => None,
+
+            false => {
+                let doc_read = self.read_doc();
+
+                Some(diff_chars(&doc_read.substring(span.clone()), text))
+            }
+        };
+
+        let aliases = self.keyword_aliases()?;
+
+        ScriptToken::with_keyword_aliases(aliases, || {
+            self.task_mut().write_to_doc(id, span.clone(), text)
+        })
+        .into_module_result(id)?;
+
+        Ok(self.lock_history().record(span, inserted, refinement))
+    }
+
+    // The lexer recognizes the keyword aliases while scanning the text, so
+    // the entire text has to be rescanned when the alias table changes.
+    //
+    // The document reuses the rescanned tokens that have the same text as the
+    // previous tokens regardless of their kinds. Therefore, the function clears
+    // the document before inserting the text back.
+    fn rescan_text(&mut self) -> ModuleResult<Revision> {
+        let id = self.id();
+        let aliases = self.keyword_aliases()?;
+        let text = self.read_doc().substring(..).into_owned();
+        let length = text.chars().count();
+
+        let refinement = match self.lock_history().annotations().is_empty() {
+            true => None,
+            false => Some(diff_chars(&text, &text)),
+        };
+
+        ScriptToken::with_keyword_aliases(aliases, || {
+            let task = self.task_mut();
+
+            task.write_to_doc(id, 0..length, "")?;
+            task.write_to_doc(id, 0..0, text.as_str())
+        })
+        .into_module_result(id)?;
+
+        Ok(self.lock_history().record(0..length, length, refinement))
+    }
 }
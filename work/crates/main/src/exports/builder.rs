@@ -0,0 +1,256 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::fmt::{Display, Formatter};
+
+use crate::{
+    export,
+    exports::string::StringType,
+    runtime::{ops::ScriptDisplay, Arg, Cell, Origin, RuntimeResult},
+};
+
+/// A mutable string buffer that builds a string piece by piece:
+/// `let out = "".builder;`
+///
+/// In contrast to the string concatenation operator, which allocates a new
+/// string on each call, the builder appends the pieces to the same buffer with
+/// amortized memory growth.
+///
+/// The `.len` field of the builder returns the number of Unicode characters
+/// of the content built so far.
+#[export(include)]
+#[derive(Clone, Default, Debug)]
+pub(crate) struct StringBuilder {
+    string: String,
+    chars: usize,
+}
+
+impl Display for StringBuilder {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(&self.string)
+    }
+}
+
+impl<'a> From<&'a str> for StringBuilder {
+    #[inline(always)]
+    fn from(string: &'a str) -> Self {
+        Self {
+            string: String::from(string),
+            chars: string.chars().count(),
+        }
+    }
+}
+
+#[export(include)]
+impl StringBuilder {
+    /// Appends the `string` to the end of the content.
+    ///
+    /// If the argument is not a string, the function appends its string
+    /// representation: `out.push(10);`.
+    pub fn push(&mut self, string: String) {
+        self.chars += string.chars().count();
+        self.string.push_str(&string);
+    }
+
+    /// Appends the `string` followed by a line break to the end of the
+    /// content.
+    pub fn push_line(&mut self, string: String) {
+        self.push(string);
+        self.chars += 1;
+        self.string.push('\n');
+    }
+
+    /// Returns the content built so far as a string.
+    pub fn build(&self) -> String {
+        self.string.clone()
+    }
+}
+
+impl StringBuilder {
+    #[inline(always)]
+    pub(crate) fn chars(&self) -> usize {
+        self.chars
+    }
+}
+
+#[export(include)]
+impl ScriptDisplay for StringBuilder {}
+
+trait StringBuilderImpl {
+    fn builder(origin: Origin, lhs: Arg) -> RuntimeResult<Cell>;
+}
+
+#[export(include)]
+impl StringBuilderImpl for StringType {
+    /// A new [string builder](StringBuilder) with this string as the initial
+    /// content: `let out = "Header: ".builder;`
+    #[export(component StringBuilder)]
+    fn builder(origin: Origin, mut lhs: Arg) -> RuntimeResult<Cell> {
+        let string = lhs.data.borrow_str(lhs.origin)?;
+
+        Cell::give(origin, StringBuilder::from(string))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        runtime::ScriptPackage,
+    };
+
+    fn eval(text: &str) -> String {
+        crate::eval!(TestPackage, text).stringify(false)
+    }
+
+    // Evaluates the script that the analyzer considers correct.
+    fn eval_checked(text: &str) -> String {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let diagnostics = read.diagnostics(3).unwrap();
+
+        assert!(
+            diagnostics.is_empty(),
+            "{}",
+            diagnostics.highlight(&read.text(), !0),
+        );
+
+        read.compile().unwrap().run().unwrap().stringify(false)
+    }
+
+    #[test]
+    fn test_string_builder() {
+        assert_eq!(
+            eval(
+                r#"
+                let out = "Header: ".builder;
+                out.push("ab");
+                out.push(10);
+                out.push_line("");
+                out.push("ж");
+                return out.build();
+            "#
+            ),
+            "Header: ab10\nж",
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                r#"let out = "ж".builder; out.push_line("ab"); return out.len;"#
+            ),
+            4,
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                r#"
+                let out = "".builder;
+                for i in 0..100000 { out.push("abcdefghij"); }
+                return out.build().len;
+            "#
+            ),
+            1_000_000,
+        );
+    }
+
+    #[test]
+    fn test_string_self_append() {
+        // Builds a 1 MB string. Without the in-place append, each iteration
+        // would copy the whole string built so far.
+        assert_eq!(
+            eval_checked(
+                r#"
+                let text = "";
+                for i in 0..100000 { text = [text, "abcdefghij"]; }
+                return text.len;
+            "#
+            ),
+            "1000000",
+        );
+
+        // The appended variable does not share its string with other
+        // variables.
+        assert_eq!(
+            eval_checked(r#"let a = "x"; let b = a; b = [b, "y"]; return a;"#),
+            "x",
+        );
+
+        assert_eq!(
+            eval_checked(r#"let a = "x"; let b = a; b = [b, "y"]; return b;"#),
+            "xy",
+        );
+
+        assert_eq!(
+            eval_checked(
+                r#"
+                let text = "";
+                for i in 0..1000 { text = text + i; }
+                return text.len;
+            "#
+            ),
+            "2890",
+        );
+
+        assert_eq!(
+            eval_checked(r#"let a = "x"; let b = a; b = b + "y"; return a;"#),
+            "x",
+        );
+
+        assert_eq!(eval_checked(r#"return "x" + 1 + "y";"#), "x1y");
+    }
+
+    #[test]
+    fn test_string_reassignment() {
+        // Strings do not implement the assignment operator. The self-append is
+        // the only form of the string variable reassignment, and the analyzer
+        // agrees with the interpreter on both forms.
+        for text in [r#"let s = "a"; s = "b";"#, r#"let s = "a"; s = ["b", s];"#] {
+            let module = ScriptModule::new(TestPackage::meta(), text);
+            let handle = TriggerHandle::new();
+            let read = module.read(&handle, 1).unwrap();
+
+            assert!(!read.diagnostics(3).unwrap().is_empty(), "{text}");
+            assert!(read.compile().unwrap().run().is_err(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,338 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::cell::RefCell;
+
+use lady_deirdre::lexis::Scannable;
+
+use crate::{
+    export,
+    exports::{utils::Stringifier, Bytes},
+    runtime::{
+        ops::{DynamicArgument, DynamicType},
+        Arg,
+        Cell,
+        Downcast,
+        Ident,
+        Origin,
+        Provider,
+        RuntimeResult,
+        TypeFamily,
+        TypeHint,
+        Upcast,
+    },
+    semantics::{FloatRepr, LocalNumberValue},
+    syntax::ScriptToken,
+};
+
+// The names of the built-in functions available in every script package.
+pub(crate) static BUILTINS: [(&str, fn(Origin, Arg) -> RuntimeResult<Cell>); 5] = [
+    ("to_number", builtin::<0>),
+    ("to_string", builtin::<1>),
+    ("to_bool", builtin::<2>),
+    ("to_bytes", builtin::<3>),
+    ("type_name", builtin::<4>),
+];
+
+fn builtin<const INDEX: usize>(origin: Origin, _lhs: Arg) -> RuntimeResult<Cell> {
+    let (name, _) = BUILTINS[INDEX];
+
+    Cell::give(origin, Builtins)?
+        .into_object()
+        .component(origin, origin, Ident::from_string(name))
+}
+
+/// A set of built-in conversion functions available in every script module
+/// regardless of the exported package semantics.
+///
+/// The Script Engine injects these functions into each package namespace
+/// unless the package exports a component with the same name.
+#[export(include)]
+#[derive(Clone, Copy, Default, Debug)]
+pub(crate) struct Builtins;
+
+#[export(include)]
+impl Builtins {
+    /// Converts the value into a number, or returns nil if the conversion is
+    /// not possible.
+    ///
+    /// - A number is returned as a copy.
+    /// - A string is parsed using the script's numeric literal syntax:
+    ///   `to_number("-12")`, `to_number(" 1.5e3 ")`. The surrounding
+    ///   whitespaces are ignored.
+    /// - A boolean turns into `1` or `0`.
+    /// - Any other value, including nil, results in nil.
+    pub fn to_number(&self, value: DynamicArgument<DynamicType>) -> Option<ScriptNumber> {
+        let DynamicArgument {
+            origin, mut data, ..
+        } = value;
+
+        let mut type_match = data.type_match();
+
+        if type_match.belongs_to::<usize>() {
+            return data
+                .into_object()
+                .clone(origin, origin)
+                .ok()
+                .map(ScriptNumber);
+        }
+
+        if type_match.is::<bool>() {
+            let flag = data.take::<bool>(origin).ok()?;
+
+            return Cell::give(origin, flag as usize).ok().map(ScriptNumber);
+        }
+
+        if type_match.is::<str>() {
+            let string = data.borrow_str(origin).ok()?;
+
+            return parse_number(origin, string).map(ScriptNumber);
+        }
+
+        None
+    }
+
+    /// Converts the value into a string, or returns nil if the conversion is
+    /// not possible.
+    ///
+    /// - A string is returned as a copy.
+    /// - Any value that has a displayable representation (numbers, booleans,
+    ///   string builders, etc.) turns into its display string:
+    ///   `to_string(10) == "10"`.
+    /// - Any other value, including nil, results in nil.
+    pub fn to_string(&self, value: DynamicArgument<DynamicType>) -> Option<String> {
+        let DynamicArgument {
+            origin, mut data, ..
+        } = value;
+
+        if data.is_nil() {
+            return None;
+        }
+
+        if data.type_match().is::<str>() {
+            return data.take_string(origin).ok();
+        }
+
+        if !data.ty().prototype().implements_display() {
+            return None;
+        }
+
+        let stringifier = Stringifier {
+            origin,
+            cell: &data,
+            error: RefCell::new(None),
+            fallback_to_type: false,
+        };
+
+        let string = stringifier.to_string();
+
+        if stringifier.error.take().is_some() {
+            return None;
+        }
+
+        Some(string)
+    }
+
+    /// Converts the value into a boolean, or returns nil if the conversion is
+    /// not possible.
+    ///
+    /// - A boolean is returned as a copy.
+    /// - A number is true if it is not zero: `to_bool(0) == false`. NaN
+    ///   results in nil.
+    /// - The strings `"true"` and `"false"` turn into the corresponding
+    ///   boolean values. The surrounding whitespaces are ignored.
+    /// - Any other value, including nil, results in nil.
+    pub fn to_bool(&self, value: DynamicArgument<DynamicType>) -> Option<bool> {
+        let DynamicArgument {
+            origin, mut data, ..
+        } = value;
+
+        let mut type_match = data.type_match();
+
+        if type_match.is::<bool>() {
+            return data.take::<bool>(origin).ok();
+        }
+
+        if type_match.belongs_to::<usize>() {
+            let number = <FloatRepr>::downcast(origin, Provider::Owned(data)).ok()?;
+
+            if number.is_nan() {
+                return None;
+            }
+
+            return Some(number != 0.0);
+        }
+
+        if type_match.is::<str>() {
+            return match data.borrow_str(origin).ok()?.trim() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            };
+        }
+
+        None
+    }
+
+    /// Converts the `text` string into bytes using the specified `encoding`,
+    /// or returns nil if the conversion is not possible.
+    ///
+    /// - `"utf8"` takes the UTF-8 encoding of the text:
+    ///   `to_bytes("Hello", "utf8")`.
+    /// - `"hex"` decodes pairs of hexadecimal digits:
+    ///   `to_bytes("48656c6c6f", "hex")`.
+    /// - `"base64"` decodes the standard padded Base64 encoding:
+    ///   `to_bytes("SGVsbG8=", "base64")`.
+    ///
+    /// The inverse conversion is the `to_text` function of the bytes object:
+    /// `data.to_text("hex")`.
+    pub fn to_bytes(&self, text: &str, encoding: &str) -> Option<Bytes> {
+        Bytes::from_text(text, encoding)
+    }
+
+    /// Returns the name of the value's type family: `type_name(10) == "number"`,
+    /// `type_name("foo") == "str"`.
+    pub fn type_name(&self, value: DynamicArgument<DynamicType>) -> String {
+        String::from(value.data.ty().family().name())
+    }
+}
+
+/// A number of any numeric type produced by the [Builtins::to_number]
+/// function.
+pub(crate) struct ScriptNumber(Cell);
+
+impl<'a> Upcast<'a> for ScriptNumber {
+    type Output = Cell;
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(this.0)
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Family(TypeFamily::number())
+    }
+}
+
+// Parses the `string` exactly as the script's numeric literal would be
+// interpreted by the analyzer.
+fn parse_number(origin: Origin, string: &str) -> Option<Cell> {
+    let string = string.trim();
+
+    let mut chunks = string.chunks::<ScriptToken>();
+
+    let chunk = chunks.next()?;
+
+    if chunks.next().is_some() || chunk.string.len() != string.len() {
+        return None;
+    }
+
+    if !matches!(chunk.token, ScriptToken::Int | ScriptToken::Float) {
+        return None;
+    }
+
+    match LocalNumberValue::parse(string) {
+        LocalNumberValue::Usize(Ok(value)) => Cell::give(origin, value).ok(),
+        LocalNumberValue::Isize(Ok(value)) => Cell::give(origin, value).ok(),
+        LocalNumberValue::Float(Ok(value)) => Cell::give(origin, value.0).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        runtime::{ScriptPackage, TypeFamily},
+    };
+
+    fn eval(text: &str) -> String {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        assert!(read.diagnostics(3).unwrap().is_empty(), "{text}");
+
+        read.compile().unwrap().run().unwrap().stringify(false)
+    }
+
+    #[test]
+    fn test_builtins() {
+        assert_eq!(eval("return to_number(\"-12\") + 2;"), "-10");
+        assert_eq!(eval("return to_number(\" 1.5e3 \");"), "1500");
+        assert_eq!(eval("return to_number(\"12\") * 2;"), "24");
+        assert_eq!(eval("return to_number(true);"), "1");
+        assert_eq!(eval("return to_number(\"1_000\");"), "<nil>");
+        assert_eq!(eval("return to_number(\"12 13\");"), "<nil>");
+        assert_eq!(eval("return to_number(\"0x10\");"), "<nil>");
+        assert_eq!(eval("return to_number([]);"), "<nil>");
+        assert_eq!(eval("return [to_string(10), to_string(\"a\")];"), "10a");
+        assert_eq!(eval("return to_string(fn() {});"), "<nil>");
+        assert_eq!(eval("return to_bool(\"true\");"), "true");
+        assert_eq!(eval("return to_bool(0);"), "false");
+        assert_eq!(eval("return to_bool(\"yes\");"), "<nil>");
+        assert_eq!(eval("return type_name(10);"), "number");
+        assert_eq!(eval("return type_name(\"foo\");"), "str");
+        assert_eq!(eval("return to_bytes(\"Hello\", \"utf8\");"), "48656c6c6f");
+        assert_eq!(
+            eval("return to_bytes(\"SGVsbG8=\", \"base64\").to_text(\"utf8\");"),
+            "Hello"
+        );
+        assert_eq!(
+            eval("return to_bytes(\"48656c6c6f\", \"hex\").to_text(\"base64\");"),
+            "SGVsbG8="
+        );
+        assert_eq!(eval("return to_bytes(\"4G\", \"hex\");"), "<nil>");
+        assert_eq!(eval("return to_bytes(\"Hello\", \"utf8\").len;"), "5");
+        assert_eq!(eval("return to_bytes(\"Hello\", \"utf8\")[1];"), "101");
+        assert_eq!(eval("return to_bytes(\"Hello\", \"utf8\")[1..3];"), "656c");
+        assert_eq!(eval("let b = to_bytes(\"He\", \"utf8\"); return [b, b] == to_bytes(\"HeHe\", \"utf8\");"), "true");
+        assert_eq!(eval("return type_name(to_bytes(\"\", \"utf8\"));"), "bytes");
+
+        let package = TestPackage::meta().ty().prototype();
+
+        let output = package
+            .hint_component("to_number")
+            .unwrap()
+            .ty
+            .invocation()
+            .unwrap()
+            .output;
+
+        assert_eq!(output.type_family(), TypeFamily::number());
+    }
+}
@@ -0,0 +1,466 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    any::{Any, TypeId},
+    fmt::{Debug, Display, Formatter},
+    mem::take,
+};
+
+use crate::{
+    export,
+    exports::utils::transparent_upcast,
+    runtime::{
+        ops::{ScriptClone, ScriptConcat, ScriptDebug, ScriptDisplay, ScriptHash, ScriptPartialEq},
+        Arg,
+        Cell,
+        Downcast,
+        Origin,
+        Provider,
+        RuntimeError,
+        RuntimeResult,
+        ScriptType,
+        TypeHint,
+    },
+    type_family,
+};
+
+type_family!(
+    /// A binary data buffer: an array of bytes.
+    pub(crate) static BYTES_FAMILY = "bytes";
+);
+
+// The maximum number of bytes rendered by the Display and Debug
+// implementations.
+const RENDER_LIMIT: usize = 32;
+
+/// A binary data buffer, such as a network payload or an asset blob:
+/// `let data = to_bytes("48656c6c6f", "hex");`
+///
+/// The exported Rust functions that return `Vec<u8>`, `&[u8]`, or `Box<[u8]>`
+/// produce bytes objects, and the functions that accept these types borrow
+/// the buffer of the bytes argument without per-byte conversion.
+///
+/// The `.len` field returns the number of bytes. Indexing by a number returns
+/// a byte as a number: `data[0]`, and indexing by a range returns a copy of
+/// the subrange as a new bytes object: `data[1..3]`.
+///
+/// The string representation of the bytes is a hexadecimal encoding of the
+/// first few bytes. To convert the bytes into a string in full, use the
+/// `to_text` function.
+#[export(include)]
+#[export(name "bytes")]
+#[export(family(&BYTES_FAMILY))]
+pub(crate) type BytesType = Bytes;
+
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Bytes {
+    data: Vec<u8>,
+}
+
+impl<'a> Downcast<'a> for Bytes {
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let mut type_match = provider.type_match();
+
+        if type_match.is::<Self>() {
+            return provider.to_owned().take(origin);
+        }
+
+        Err(type_match.mismatch(origin))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(Self::type_meta())
+    }
+}
+
+impl<'a> Downcast<'a> for &'a Bytes {
+    #[inline]
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let mut type_match = provider.type_match();
+
+        if type_match.is::<Bytes>() {
+            return provider.to_borrowed(&origin)?.borrow_ref(origin);
+        }
+
+        Err(type_match.mismatch(origin))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(Bytes::type_meta())
+    }
+}
+
+transparent_upcast!(Bytes);
+
+impl Display for Bytes {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let (head, truncated) = self.head();
+
+        formatter.write_str(&encode_hex(head))?;
+
+        if truncated {
+            formatter.write_fmt(format_args!("... ({} bytes)", self.data.len()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Bytes {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let (head, truncated) = self.head();
+
+        formatter.write_fmt(format_args!("bytes({:?}", encode_base64(head)))?;
+
+        if truncated {
+            formatter.write_fmt(format_args!("... {} bytes", self.data.len()))?;
+        }
+
+        formatter.write_str(")")
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    #[inline(always)]
+    fn from(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+}
+
+#[export(include)]
+impl BytesType {
+    /// Converts the bytes into a string using the specified `encoding`.
+    ///
+    /// - `"utf8"` decodes the bytes as a UTF-8 string.
+    /// - `"hex"` encodes each byte as two lowercase hexadecimal digits.
+    /// - `"base64"` encodes the bytes using the standard padded Base64
+    ///   alphabet.
+    ///
+    /// Returns nil if the bytes are not a valid UTF-8 sequence, or if the
+    /// encoding is unknown.
+    pub fn to_text(&self, encoding: &str) -> Option<String> {
+        match encoding {
+            "utf8" => String::from_utf8(self.data.clone()).ok(),
+            "hex" => Some(encode_hex(&self.data)),
+            "base64" => Some(encode_base64(&self.data)),
+            _ => None,
+        }
+    }
+}
+
+impl Bytes {
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline(always)]
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Decodes the `text` into bytes using the specified `encoding`, the
+    /// inverse of the [to_text](Self::to_text) function.
+    pub(crate) fn from_text(text: &str, encoding: &str) -> Option<Self> {
+        let data = match encoding {
+            "utf8" => Vec::from(text.as_bytes()),
+            "hex" => decode_hex(text)?,
+            "base64" => decode_base64(text)?,
+            _ => return None,
+        };
+
+        Some(Self { data })
+    }
+
+    // Returns the underlying vector if `T` is `u8`.
+    #[inline(always)]
+    pub(crate) fn into_vec<T: 'static>(mut self) -> Option<Vec<T>> {
+        (&mut self.data as &mut dyn Any)
+            .downcast_mut::<Vec<T>>()
+            .map(take)
+    }
+
+    // Returns the underlying vector's slice if `T` is `u8`.
+    #[inline(always)]
+    pub(crate) fn as_slice_of<T: 'static>(&self) -> Option<&[T]> {
+        (&self.data as &dyn Any)
+            .downcast_ref::<Vec<T>>()
+            .map(Vec::as_slice)
+    }
+
+    // Returns the underlying vector's mutable slice if `T` is `u8`.
+    #[inline(always)]
+    pub(crate) fn as_mut_slice_of<T: 'static>(&mut self) -> Option<&mut [T]> {
+        (&mut self.data as &mut dyn Any)
+            .downcast_mut::<Vec<T>>()
+            .map(Vec::as_mut_slice)
+    }
+
+    // Wraps the vector into bytes if `T` is `u8`, or returns the vector back.
+    #[inline(always)]
+    pub(crate) fn try_from_vec<T: 'static>(mut data: Vec<T>) -> Result<Self, Vec<T>> {
+        match (&mut data as &mut dyn Any).downcast_mut::<Vec<u8>>() {
+            Some(bytes) => Ok(Self { data: take(bytes) }),
+            None => Err(data),
+        }
+    }
+
+    #[inline(always)]
+    fn head(&self) -> (&[u8], bool) {
+        match self.data.len() > RENDER_LIMIT {
+            true => (&self.data[..RENDER_LIMIT], true),
+            false => (&self.data, false),
+        }
+    }
+}
+
+#[export(include)]
+impl ScriptClone for Bytes {}
+
+#[export(include)]
+impl ScriptDisplay for Bytes {}
+
+#[export(include)]
+impl ScriptDebug for Bytes {}
+
+#[export(include)]
+impl ScriptHash for Bytes {}
+
+#[export(include)]
+impl ScriptPartialEq for Bytes {
+    type RHS = Bytes;
+
+    fn script_eq(_origin: Origin, mut lhs: Arg, mut rhs: Arg) -> RuntimeResult<bool> {
+        let lhs = lhs.data.borrow_ref::<Bytes>(lhs.origin)?;
+        let rhs = rhs.data.borrow_ref::<Bytes>(rhs.origin)?;
+
+        Ok(lhs == rhs)
+    }
+}
+
+#[export(include)]
+impl ScriptConcat for Bytes {
+    type Result = Bytes;
+
+    fn script_concat(origin: Origin, items: &mut [Arg]) -> RuntimeResult<Cell> {
+        let mut data = Vec::new();
+
+        for item in items {
+            if item.data.is_nil() {
+                continue;
+            }
+
+            let cell = take(&mut item.data);
+
+            data.append(&mut <Vec<u8>>::downcast(
+                item.origin,
+                Provider::Owned(cell),
+            )?);
+        }
+
+        Cell::give(origin, Bytes { data })
+    }
+}
+
+/// Returns the type hint of the array of `T` items: the [bytes](Bytes) type
+/// if `T` is `u8`, and the `T` type otherwise.
+#[inline(always)]
+pub(crate) fn array_hint<T: ScriptType>() -> TypeHint {
+    match TypeId::of::<T>() == TypeId::of::<u8>() {
+        true => TypeHint::Type(Bytes::type_meta()),
+        false => TypeHint::Type(T::type_meta()),
+    }
+}
+
+// Returns true if `T` is `u8`, and the `cell` points to a bytes object.
+#[inline(always)]
+pub(crate) fn is_bytes_of<T: 'static>(cell: &Cell) -> bool {
+    TypeId::of::<T>() == TypeId::of::<u8>() && cell.is::<Bytes>()
+}
+
+// Reports an error if the bytes object cannot be reinterpreted as an array
+// of `T` items.
+#[inline(always)]
+pub(crate) fn bytes_mismatch<T: ScriptType>(origin: Origin) -> RuntimeError {
+    RuntimeError::TypeMismatch {
+        access_origin: origin,
+        data_type: Bytes::type_meta(),
+        expected_types: Vec::from([T::type_meta()]),
+    }
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn encode_hex(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len() * 2);
+
+    for byte in data {
+        result.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        result.push(HEX_DIGITS[(byte & 0xF) as usize] as char);
+    }
+
+    result
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim().as_bytes();
+
+    if text.len() % 2 != 0 {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(text.len() / 2);
+
+    for pair in text.chunks(2) {
+        let high = (pair[0] as char).to_digit(16)?;
+        let low = (pair[1] as char).to_digit(16)?;
+
+        result.push((high << 4 | low) as u8);
+    }
+
+    Some(result)
+}
+
+fn encode_base64(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let mut triple = [0u8; 3];
+
+        triple[..chunk.len()].copy_from_slice(chunk);
+
+        let bits = (triple[0] as u32) << 16 | (triple[1] as u32) << 8 | triple[2] as u32;
+
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => {
+                    let sextet = (bits >> (18 - index * 6)) & 0x3F;
+
+                    result.push(BASE64_ALPHABET[sextet as usize] as char);
+                }
+
+                false => result.push('='),
+            }
+        }
+    }
+
+    result
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim().as_bytes();
+
+    if text.len() % 4 != 0 {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(text.len() / 4 * 3);
+
+    for (chunk_index, chunk) in text.chunks(4).enumerate() {
+        let is_last = (chunk_index + 1) * 4 == text.len();
+
+        let padding = chunk.iter().rev().take_while(|byte| **byte == b'=').count();
+
+        if padding > 2 || (padding > 0 && !is_last) {
+            return None;
+        }
+
+        let mut bits = 0u32;
+
+        for byte in &chunk[..4 - padding] {
+            let sextet = BASE64_ALPHABET.iter().position(|digit| digit == byte)?;
+
+            bits = bits << 6 | sextet as u32;
+        }
+
+        bits <<= padding * 6;
+
+        let bytes = [(bits >> 16) as u8, (bits >> 8) as u8, bits as u8];
+
+        result.extend_from_slice(&bytes[..3 - padding]);
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        exports::bytes::{decode_base64, decode_hex, encode_base64, encode_hex, Bytes},
+        runtime::{Cell, Downcast, Origin, Provider},
+    };
+
+    #[test]
+    fn test_bytes_coercion() {
+        let mut cell = Cell::give(Origin::nil(), vec![1u8, 2, 3]).unwrap();
+
+        assert!(cell.is::<Bytes>());
+        assert_eq!(cell.ty().family().name(), "bytes");
+
+        let slice = <&[u8]>::downcast(Origin::nil(), Provider::Borrowed(&mut cell)).unwrap();
+
+        assert_eq!(slice, &[1, 2, 3]);
+
+        let boxed = Cell::give(Origin::nil(), Box::<[u8]>::from([4, 5])).unwrap();
+        let vector = <Vec<u8>>::downcast(Origin::nil(), Provider::Owned(boxed)).unwrap();
+
+        assert_eq!(vector, [4, 5]);
+
+        let raw = Cell::give_vec(Origin::nil(), vec![1u8, 2, 3]).unwrap();
+
+        assert!(raw.is::<u8>());
+        assert_eq!(raw.length(), 3);
+    }
+
+    #[test]
+    fn test_bytes_encodings() {
+        for data in [&b""[..], b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"] {
+            assert_eq!(decode_hex(&encode_hex(data)).unwrap(), data);
+            assert_eq!(decode_base64(&encode_base64(data)).unwrap(), data);
+        }
+
+        assert_eq!(encode_hex(b"Hello"), "48656c6c6f");
+        assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode_base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(encode_base64(b"foob"), "Zm9vYg==");
+        assert_eq!(decode_hex("4G"), None);
+        assert_eq!(decode_base64("Zm9=YmFy"), None);
+    }
+}
@@ -0,0 +1,162 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    export,
+    runtime::{
+        Downcast,
+        NumberCastCause,
+        Origin,
+        Provider,
+        RuntimeError,
+        RuntimeResult,
+        ScriptType,
+        TypeHint,
+        Upcast,
+    },
+    type_family,
+};
+
+type_family!(
+    /// A time span, such as a timeout or a delay, measured in seconds.
+    pub(crate) static DURATION_FAMILY = "duration";
+);
+
+/// A time span, such as a timeout or a delay: `let timeout = 2.5;`
+///
+/// The scripts represent durations as numbers of seconds. The exported Rust
+/// functions that accept [Duration] parameters receive any non-negative finite
+/// number, including fractional numbers, and the functions that return
+/// Durations produce numbers of seconds.
+#[export(include)]
+#[export(name "duration")]
+#[export(family(&DURATION_FAMILY))]
+pub(crate) type DurationType = Duration;
+
+impl<'a> Downcast<'a> for Duration {
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let secs = <f64 as Downcast<'a>>::downcast(origin, provider)?;
+
+        if let Ok(duration) = Duration::try_from_secs_f64(secs) {
+            return Ok(duration);
+        }
+
+        let cause = match secs {
+            _ if secs.is_nan() => NumberCastCause::NAN,
+            _ if secs.is_infinite() => NumberCastCause::Infinite,
+            _ if secs < 0.0 => NumberCastCause::Underflow,
+            _ => NumberCastCause::Overflow,
+        };
+
+        Err(RuntimeError::NumberCast {
+            access_origin: origin,
+            from: <f64>::type_meta(),
+            to: DurationType::type_meta(),
+            cause,
+            value: Arc::new(secs),
+        })
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(DurationType::type_meta())
+    }
+}
+
+impl<'a> Upcast<'a> for Duration {
+    type Output = <f64 as Upcast<'a>>::Output;
+
+    #[inline(always)]
+    fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        <f64 as Upcast<'a>>::upcast(origin, this.as_secs_f64())
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(DurationType::type_meta())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, IssueCode, ModuleRead, ScriptModule},
+        export,
+        runtime::{NumberCastCause, RuntimeError, ScriptPackage},
+    };
+
+    #[export]
+    pub fn test_delay(timeout: Duration) -> Duration {
+        timeout * 2
+    }
+
+    #[test]
+    fn test_duration_casting() {
+        assert_eq!(
+            crate::eval_as!(TestPackage, f64, "return test_delay(1.25);"),
+            2.5,
+        );
+        assert_eq!(
+            crate::eval_as!(TestPackage, f64, "return test_delay(3) + 1;"),
+            7.0,
+        );
+
+        crate::assert_script_ok!(TestPackage, "return test_delay(0.5) * 2;");
+        crate::assert_script_err!(
+            TestPackage,
+            "test_delay(\"1\");",
+            IssueCode::TypeMismatch,
+            "\"1\"",
+        );
+
+        let module = ScriptModule::new(TestPackage::meta(), "test_delay(-1);");
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert!(matches!(
+            error,
+            RuntimeError::NumberCast {
+                cause: NumberCastCause::Underflow,
+                ..
+            }
+        ));
+    }
+}
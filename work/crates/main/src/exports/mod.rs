@@ -35,16 +35,35 @@
 mod array;
 mod boolean;
 mod boxed;
+mod builder;
+mod builtins;
+mod bytes;
 mod cow;
+mod duration;
 mod number;
 mod option;
+mod path;
 mod range;
 mod result;
+mod shared;
 mod slice;
 mod string;
 mod structure;
 mod tuple;
 mod unit;
 mod utils;
+mod variant;
+mod vec;
 
-pub(crate) use crate::exports::{number::NUMBER_FAMILY, structure::Struct};
+pub(crate) use crate::exports::{
+    builder::StringBuilder,
+    builtins::{Builtins, BUILTINS},
+    bytes::{array_hint, bytes_mismatch, is_bytes_of, Bytes, BYTES_FAMILY},
+    duration::DURATION_FAMILY,
+    number::NUMBER_FAMILY,
+    path::PATH_FAMILY,
+    string::append_items,
+    structure::Struct,
+    utils::NestingGuard,
+    variant::Variant,
+};
@@ -49,6 +49,7 @@ use crate::{
     report::system_panic,
     runtime::{
         ops::{
+            OperatorKind,
             ScriptAdd,
             ScriptAssign,
             ScriptBitAnd,
@@ -292,22 +293,6 @@ macro_rules! impl_num {
         #[export(include)]
         impl ScriptDisplay for $ty {}
 
-        #[export(include)]
-        impl ScriptPartialEq for $ty {
-            type RHS = $ty;
-
-            fn script_eq(
-                _origin: Origin,
-                lhs: Arg,
-                mut rhs: Arg,
-            ) -> RuntimeResult<bool> {
-                let lhs = lhs.data.take::<$ty>(lhs.origin)?;
-                let rhs = <$ty>::downcast(rhs.origin, rhs.provider())?;
-
-                Ok(lhs == rhs)
-            }
-        }
-
         #[export(include)]
         impl ScriptDefault for $ty {
             fn script_default(origin: Origin) -> RuntimeResult<Cell> {
@@ -581,6 +566,29 @@ macro_rules! impl_num {
 
 macro_rules! impl_int {
     ($ty:ty) => {
+        #[export(include)]
+        impl ScriptPartialEq for $ty {
+            type RHS = $ty;
+
+            fn script_eq(_origin: Origin, lhs: Arg, mut rhs: Arg) -> RuntimeResult<bool> {
+                let lhs = lhs.data.take::<$ty>(lhs.origin)?;
+
+                if rhs.data.is::<f32>() || rhs.data.is::<f64>() {
+                    let rhs = <f64>::downcast(rhs.origin, rhs.provider())?;
+
+                    return Ok(int_float_eq(lhs, rhs));
+                }
+
+                // The integers that do not fit into the left-hand side type
+                // are not equal to it.
+                match <$ty>::downcast(rhs.origin, rhs.provider()) {
+                    Ok(rhs) => Ok(lhs == rhs),
+                    Err(RuntimeError::NumberCast { .. }) => Ok(false),
+                    Err(error) => Err(error),
+                }
+            }
+        }
+
         #[export(include)]
         impl ScriptPartialOrd for $ty {
             type RHS = $ty;
@@ -588,22 +596,30 @@ macro_rules! impl_int {
             fn script_partial_cmp(
                 _origin: Origin,
                 mut lhs: Arg,
-                mut rhs: Arg,
+                rhs: Arg,
             ) -> RuntimeResult<Option<Ordering>> {
-                let lhs = lhs.data.borrow_ref::<$ty>(lhs.origin)?;
-                let rhs = <$ty>::downcast(rhs.origin, rhs.provider())?;
+                let lhs = *lhs.data.borrow_ref::<$ty>(lhs.origin)?;
 
-                Ok(lhs.partial_cmp(&rhs))
+                int_cmp(lhs, rhs)
             }
         }
 
         #[export(include)]
         impl ScriptOrd for $ty {
-            fn script_cmp(_origin: Origin, mut lhs: Arg, mut rhs: Arg) -> RuntimeResult<Ordering> {
-                let lhs = lhs.data.borrow_ref::<$ty>(lhs.origin)?;
-                let rhs = rhs.data.borrow_ref::<$ty>(rhs.origin)?;
-
-                Ok(lhs.cmp(&rhs))
+            fn script_cmp(origin: Origin, mut lhs: Arg, rhs: Arg) -> RuntimeResult<Ordering> {
+                let lhs_origin = lhs.origin;
+                let lhs = *lhs.data.borrow_ref::<$ty>(lhs_origin)?;
+
+                match int_cmp(lhs, rhs)? {
+                    Some(ordering) => Ok(ordering),
+
+                    None => Err(RuntimeError::UndefinedOperator {
+                        access_origin: origin,
+                        receiver_origin: Some(lhs_origin),
+                        receiver_type: <$ty>::type_meta(),
+                        operator: OperatorKind::Ord,
+                    }),
+                }
             }
         }
 
@@ -845,6 +861,18 @@ macro_rules! impl_int {
 
 macro_rules! impl_float {
     ($ty:ty) => {
+        #[export(include)]
+        impl ScriptPartialEq for $ty {
+            type RHS = $ty;
+
+            fn script_eq(_origin: Origin, lhs: Arg, mut rhs: Arg) -> RuntimeResult<bool> {
+                let lhs = lhs.data.take::<$ty>(lhs.origin)?;
+                let rhs = <$ty>::downcast(rhs.origin, rhs.provider())?;
+
+                Ok(lhs == rhs)
+            }
+        }
+
         #[export(include)]
         impl ScriptPartialOrd for $ty {
             type RHS = $ty;
@@ -957,6 +985,100 @@ impl_int!(u8);
 impl_num!(type USIZE("usize") = usize as bool);
 impl_int!(usize);
 
+// Compares the integer with the float without truncating the float's
+// fractional part. The floats outside of the integer's range are unequal.
+fn int_float_eq<T: Copy + TryInto<i128> + TryInto<u128>>(int: T, float: f64) -> bool {
+    if float.fract() != 0.0 {
+        return false;
+    }
+
+    if let Ok(int) = TryInto::<i128>::try_into(int) {
+        return float >= i128::MIN as f64 && float < -(i128::MIN as f64) && float as i128 == int;
+    }
+
+    if let Ok(int) = TryInto::<u128>::try_into(int) {
+        return float >= 0.0 && float < u128::MAX as f64 && float as u128 == int;
+    }
+
+    false
+}
+
+// Compares the integer with any number exactly. Returns None if
+// the right-hand side is a NaN float.
+fn int_cmp<T>(lhs: T, mut rhs: Arg) -> RuntimeResult<Option<Ordering>>
+where
+    T: Copy + TryInto<i128> + TryInto<u128>,
+{
+    if rhs.data.is::<f32>() || rhs.data.is::<f64>() {
+        let rhs = <f64>::downcast(rhs.origin, rhs.provider())?;
+
+        return Ok(int_float_cmp(lhs, rhs));
+    }
+
+    // Every integer except the large u128 values fits into i128.
+    if rhs.data.is::<u128>() {
+        let rhs = <u128>::downcast(rhs.origin, rhs.provider())?;
+
+        return Ok(Some(match TryInto::<u128>::try_into(lhs) {
+            Ok(lhs) => lhs.cmp(&rhs),
+            Err(_) => Ordering::Less,
+        }));
+    }
+
+    let rhs = <i128>::downcast(rhs.origin, rhs.provider())?;
+
+    Ok(Some(match TryInto::<i128>::try_into(lhs) {
+        Ok(lhs) => lhs.cmp(&rhs),
+        Err(_) => Ordering::Greater,
+    }))
+}
+
+// Compares the integer with the float without truncating the float's
+// fractional part.
+fn int_float_cmp<T: Copy + TryInto<i128> + TryInto<u128>>(int: T, float: f64) -> Option<Ordering> {
+    if float.is_nan() {
+        return None;
+    }
+
+    let fract = float.fract();
+
+    let ordering = match TryInto::<i128>::try_into(int) {
+        Ok(int) => {
+            if float < i128::MIN as f64 {
+                return Some(Ordering::Greater);
+            }
+
+            if float >= -(i128::MIN as f64) {
+                return Some(Ordering::Less);
+            }
+
+            int.cmp(&(float as i128))
+        }
+
+        Err(_) => {
+            let Ok(int) = TryInto::<u128>::try_into(int) else {
+                return None;
+            };
+
+            if float < 0.0 {
+                return Some(Ordering::Greater);
+            }
+
+            if float >= u128::MAX as f64 {
+                return Some(Ordering::Less);
+            }
+
+            int.cmp(&(float as u128))
+        }
+    };
+
+    match ordering {
+        Ordering::Equal if fract > 0.0 => Some(Ordering::Less),
+        Ordering::Equal if fract < 0.0 => Some(Ordering::Greater),
+        ordering => Some(ordering),
+    }
+}
+
 #[inline(always)]
 fn canonical_num_concat(origin: Origin, items: &mut [Arg]) -> RuntimeResult<Cell> {
     #[derive(Clone, Copy, PartialEq, Eq)]
@@ -1324,3 +1446,79 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{analysis::symbols::tests::TestPackage, eval, eval_as};
+
+    #[test]
+    fn test_numeric_equality() {
+        assert!(eval_as!(TestPackage, bool, "let x = 1; return x == 1.0;"));
+        assert!(eval_as!(TestPackage, bool, "let x = 1.0; return x == 1;"));
+        assert!(!eval_as!(TestPackage, bool, "let x = 1; return x == 1.5;"));
+        assert!(!eval_as!(TestPackage, bool, "let x = 0; return x == 0.5;"));
+        assert!(eval_as!(TestPackage, bool, "let x = -0.0; return x == 0;"));
+        assert!(eval_as!(TestPackage, bool, "let x = 0; return x == -0.0;"));
+        assert!(!eval_as!(
+            TestPackage,
+            bool,
+            "let x = 300; return x == 1e300;"
+        ));
+
+        assert!(!eval_as!(
+            TestPackage,
+            bool,
+            "let x = 18446744073709551615; return x == -1;",
+        ));
+
+        assert!(eval_as!(
+            TestPackage,
+            bool,
+            "let x = -1; return x != 18446744073709551615;",
+        ));
+    }
+
+    #[test]
+    fn test_numeric_match_arms() {
+        static ARMS: &str = r#"
+            let classify = fn(x) {
+                match x {
+                    -1 => { return "m"; },
+                    -0.0 => { return "z"; },
+                    0.5 => { return "h"; },
+                    1.0 => { return "o"; },
+                    18446744073709551615 => { return "x"; },
+                    else => { return "_"; },
+                }
+            };
+
+            return [
+                classify(-1), classify(0), classify(0.0), classify(0.5),
+                classify(1), classify(1.5), classify(18446744073709551615),
+                classify(-1.0), classify(2),
+            ];
+        "#;
+
+        assert_eq!(eval!(TestPackage, ARMS).stringify(false), "mzzho_xm_");
+    }
+
+    #[test]
+    fn test_numeric_ordering() {
+        assert!(eval_as!(TestPackage, bool, "return -5 < 0;"));
+        assert!(eval_as!(TestPackage, bool, "let x = 0; return x > -5;"));
+        assert!(eval_as!(TestPackage, bool, "let x = 1; return x < 1.5;"));
+        assert!(eval_as!(TestPackage, bool, "let x = 2; return x > 1.5;"));
+        assert!(eval_as!(TestPackage, bool, "let x = -2; return x < -1.5;"));
+        assert!(eval_as!(TestPackage, bool, "let x = 1; return x <= 1.0;"));
+        assert!(eval_as!(
+            TestPackage,
+            bool,
+            "let x = 18446744073709551615; return x > -1;",
+        ));
+        assert!(eval_as!(
+            TestPackage,
+            bool,
+            "let x = -1; return x < 18446744073709551615;",
+        ));
+    }
+}
@@ -32,7 +32,16 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use crate::runtime::{Downcast, Either, Origin, Provider, RuntimeResult, TypeHint, Upcast};
+use crate::runtime::{
+    Downcast,
+    Either,
+    Origin,
+    Provider,
+    RuntimeResult,
+    StructSchema,
+    TypeHint,
+    Upcast,
+};
 
 impl<'a, T> Downcast<'a> for Option<T>
 where
@@ -56,6 +65,11 @@ where
     fn hint() -> TypeHint {
         <T as Downcast<'a>>::hint()
     }
+
+    #[inline(always)]
+    fn schema() -> Option<&'static StructSchema> {
+        <T as Downcast<'a>>::schema()
+    }
 }
 
 impl<'a, T> Upcast<'a> for Option<T>
@@ -0,0 +1,193 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    ffi::CString,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    export,
+    runtime::{
+        Downcast,
+        Origin,
+        Provider,
+        RuntimeError,
+        RuntimeResult,
+        ScriptType,
+        TypeHint,
+        Upcast,
+    },
+    type_family,
+};
+
+type_family!(
+    /// A file system path.
+    pub(crate) static PATH_FAMILY = "path";
+);
+
+/// A file system path: `let config = "assets/config.json";`
+///
+/// The scripts represent paths as strings. The exported Rust functions that
+/// accept [PathBuf] or [Path] parameters receive strings without interior NUL
+/// characters, and the functions that return paths produce strings.
+#[export(include)]
+#[export(name "path")]
+#[export(family(&PATH_FAMILY))]
+pub(crate) type PathType = PathBuf;
+
+impl<'a> Downcast<'a> for PathBuf {
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let string = <String as Downcast<'a>>::downcast(origin, provider)?;
+
+        validate_path(origin, &string)?;
+
+        Ok(PathBuf::from(string))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(PathType::type_meta())
+    }
+}
+
+impl<'a> Downcast<'a> for &'a Path {
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let string = <&'a str as Downcast<'a>>::downcast(origin, provider)?;
+
+        validate_path(origin, string)?;
+
+        Ok(Path::new(string))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(PathType::type_meta())
+    }
+}
+
+// The paths that are not valid Unicode strings are converted lossily.
+impl<'a> Upcast<'a> for PathBuf {
+    type Output = String;
+
+    #[inline]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        match this.into_os_string().into_string() {
+            Ok(string) => Ok(string),
+            Err(string) => Ok(string.to_string_lossy().into_owned()),
+        }
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(PathType::type_meta())
+    }
+}
+
+impl<'a> Upcast<'a> for &'a Path {
+    type Output = String;
+
+    #[inline]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(this.to_string_lossy().into_owned())
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(PathType::type_meta())
+    }
+}
+
+// Reports an error if the `string` cannot be interpreted as a path
+// of the host file system.
+fn validate_path(origin: Origin, string: &str) -> RuntimeResult<()> {
+    if !string.contains('\0') {
+        return Ok(());
+    }
+
+    let Err(cause) = CString::new(string) else {
+        return Ok(());
+    };
+
+    Err(RuntimeError::PrimitiveParse {
+        access_origin: origin,
+        from: String::from(string),
+        to: PathType::type_meta(),
+        cause: Arc::new(cause),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, IssueCode, ModuleRead, ScriptModule},
+        export,
+        runtime::{RuntimeError, ScriptPackage},
+    };
+
+    #[export]
+    pub fn test_asset_path(name: &Path) -> PathBuf {
+        Path::new("assets").join(name)
+    }
+
+    #[test]
+    fn test_path_casting() {
+        let expected = PathBuf::from("assets").join("config.json");
+
+        assert_eq!(
+            crate::eval!(TestPackage, "return test_asset_path(\"config.json\");").stringify(false),
+            expected.to_string_lossy(),
+        );
+
+        crate::assert_script_ok!(TestPackage, "return test_asset_path(\"config.json\");");
+        crate::assert_script_err!(
+            TestPackage,
+            "test_asset_path(10);",
+            IssueCode::TypeMismatch,
+            "10",
+        );
+
+        let module = ScriptModule::new(TestPackage::meta(), "test_asset_path(\"a\0b\");");
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert!(matches!(error, RuntimeError::PrimitiveParse { .. }));
+    }
+}
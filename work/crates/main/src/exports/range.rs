@@ -56,14 +56,21 @@ use crate::{
         TypeHint,
         Upcast,
     },
+    type_family,
 };
 
+type_family!(
+    /// A range of integer numbers, with or without a sign.
+    pub(crate) static RANGE_FAMILY = "range";
+);
+
 /// A range of integer numbers: `10..125`.
 ///
 /// This range includes all integer numbers starting from the lower value
 /// (inclusive) up to the upper value (exclusive).
 #[export(include)]
 #[export(name "range")]
+#[export(family(&RANGE_FAMILY))]
 pub(crate) type RangeType = Range<usize>;
 
 impl<'a> Downcast<'a> for RangeType {
@@ -145,6 +152,11 @@ impl ScriptPartialEq for RangeType {
             return Ok(lhs == rhs);
         }
 
+        // Signed ranges have at least one negative bound.
+        if type_match.is::<SignedRangeType>() {
+            return Ok(false);
+        }
+
         if type_match.belongs_to::<usize>() {
             let singleton =
                 <usize as Downcast>::downcast(rhs.origin, Provider::Borrowed(&mut rhs.data))?;
@@ -200,6 +212,165 @@ impl RangeImpl for RangeType {
     }
 }
 
+/// A range of integer numbers with a negative bound: `-10..125`.
+///
+/// The script engine creates a signed range instead of the unsigned one
+/// if any of the range bounds is negative. Signed ranges can be iterated,
+/// compared, and used in match arms, but they cannot index arrays or strings.
+#[export(include)]
+#[export(name "range")]
+#[export(family(&RANGE_FAMILY))]
+pub(crate) type SignedRangeType = Range<isize>;
+
+impl<'a> Downcast<'a> for SignedRangeType {
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let mut type_match = provider.type_match();
+
+        if type_match.is::<Self>() {
+            return provider.to_owned().take(origin);
+        }
+
+        if type_match.is::<RangeType>() {
+            let range = provider.to_owned().take::<RangeType>(origin)?;
+
+            let (Ok(start), Ok(end)) = (isize::try_from(range.start), isize::try_from(range.end))
+            else {
+                return Err(RuntimeError::RangeCast {
+                    access_origin: origin,
+                    from: range,
+                    to: type_name::<SignedRangeType>(),
+                });
+            };
+
+            return Ok(start..end);
+        }
+
+        Err(type_match.mismatch(origin))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(Self::type_meta())
+    }
+}
+
+impl<'a> Downcast<'a> for &'a SignedRangeType {
+    #[inline]
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let mut type_match = provider.type_match();
+
+        if type_match.is::<SignedRangeType>() {
+            return provider.to_borrowed(&origin)?.borrow_ref(origin);
+        }
+
+        Err(type_match.mismatch(origin))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(SignedRangeType::type_meta())
+    }
+}
+
+impl<'a> Downcast<'a> for &'a mut SignedRangeType {
+    #[inline]
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let mut type_match = provider.type_match();
+
+        if type_match.is::<SignedRangeType>() {
+            return provider.to_borrowed(&origin)?.borrow_mut(origin);
+        }
+
+        Err(type_match.mismatch(origin))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(SignedRangeType::type_meta())
+    }
+}
+
+transparent_upcast!(SignedRangeType);
+
+#[export(include)]
+impl ScriptClone for SignedRangeType {}
+
+#[export(include)]
+impl ScriptHash for SignedRangeType {}
+
+#[export(include)]
+impl ScriptDebug for SignedRangeType {}
+
+#[export(include)]
+impl ScriptPartialEq for SignedRangeType {
+    type RHS = Self;
+
+    fn script_eq(_origin: Origin, mut lhs: Arg, mut rhs: Arg) -> RuntimeResult<bool> {
+        let lhs = lhs.data.borrow_ref::<Self>(lhs.origin)?;
+
+        let mut type_match = rhs.data.type_match();
+
+        if type_match.is::<Self>() {
+            let rhs = rhs.data.borrow_ref::<Self>(rhs.origin)?;
+
+            return Ok(lhs == rhs);
+        }
+
+        // Unsigned ranges don't have negative bounds.
+        if type_match.is::<RangeType>() {
+            return Ok(false);
+        }
+
+        if type_match.belongs_to::<isize>() {
+            let singleton =
+                <isize as Downcast>::downcast(rhs.origin, Provider::Borrowed(&mut rhs.data))?;
+
+            return match singleton == isize::MAX {
+                true => Ok(false),
+
+                false => Ok(lhs.start == singleton && lhs.end == singleton + 1),
+            };
+        }
+
+        Err(type_match.mismatch(rhs.origin))
+    }
+}
+
+#[export(include)]
+impl RangeImpl for SignedRangeType {
+    #[export(component SignedRangeType)]
+    fn start(origin: Origin, lhs: Arg) -> RuntimeResult<Cell> {
+        unsafe fn by_ref(range: *const SignedRangeType) -> *const isize {
+            // Safety: Upheld by the Cell::map_component specification.
+            unsafe { addr_of!((*range).start) }
+        }
+
+        unsafe fn by_mut(range: *mut SignedRangeType) -> *mut isize {
+            // Safety: Upheld by the Cell::map_component specification.
+            unsafe { addr_of_mut!((*range).start) }
+        }
+
+        lhs.data
+            .map_ptr::<Self, isize>(origin, Some(by_ref), Some(by_mut))
+    }
+
+    #[export(component SignedRangeType)]
+    fn end(origin: Origin, lhs: Arg) -> RuntimeResult<Cell> {
+        unsafe fn by_ref(range: *const SignedRangeType) -> *const isize {
+            // Safety: Upheld by the Cell::map_component specification.
+            unsafe { addr_of!((*range).end) }
+        }
+
+        unsafe fn by_mut(range: *mut SignedRangeType) -> *mut isize {
+            // Safety: Upheld by the Cell::map_component specification.
+            unsafe { addr_of_mut!((*range).end) }
+        }
+
+        lhs.data
+            .map_ptr::<Self, isize>(origin, Some(by_ref), Some(by_mut))
+    }
+}
+
 impl<'a> Downcast<'a> for RangeFrom<usize> {
     #[inline]
     fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
@@ -415,3 +586,28 @@ impl<'a> Downcast<'a> for RangeToInclusive<usize> {
         TypeHint::Type(RangeType::type_meta())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use crate::{analysis::symbols::tests::TestPackage, export};
+
+    #[export]
+    pub fn test_signed_range_width(range: Range<isize>) -> usize {
+        range.len()
+    }
+
+    #[test]
+    fn test_signed_range_casting() {
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_signed_range_width(-2..3);"),
+            5,
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_signed_range_width(2..3);"),
+            1,
+        );
+    }
+}
@@ -65,10 +65,7 @@ where
     fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
         match this {
             Ok(inner) => <T as Upcast<'a>>::upcast(origin, inner),
-            Err(error) => Err(RuntimeError::UpcastResult {
-                access_origin: origin,
-                cause: Arc::new(error),
-            }),
+            Err(error) => Err(upcast_error(origin, error)),
         }
     }
 
@@ -89,10 +86,7 @@ where
     fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
         match this {
             Ok(inner) => <&'a T as Upcast<'a>>::upcast(origin, inner),
-            Err(error) => Err(RuntimeError::UpcastResult {
-                access_origin: origin,
-                cause: Arc::new(error.clone()),
-            }),
+            Err(error) => Err(upcast_error(origin, error.clone())),
         }
     }
 
@@ -113,10 +107,7 @@ where
     fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
         match this {
             Ok(inner) => <&'a mut T as Upcast<'a>>::upcast(origin, inner),
-            Err(error) => Err(RuntimeError::UpcastResult {
-                access_origin: origin,
-                cause: Arc::new(error.clone()),
-            }),
+            Err(error) => Err(upcast_error(origin, error.clone())),
         }
     }
 
@@ -125,3 +116,98 @@ where
         <&'a mut T as Upcast<'a>>::hint()
     }
 }
+
+// Turns the Err value of the exported function's result into a RuntimeError.
+//
+// Custom runtime errors are passed through as they are to preserve their
+// host-defined codes and data. All other errors become the causes of the
+// UpcastResult error.
+fn upcast_error<E: StdError + Send + Sync + 'static>(origin: Origin, error: E) -> RuntimeError {
+    let cause: Arc<dyn StdError + Send + Sync + 'static> = Arc::new(error);
+
+    if let Some(error @ RuntimeError::Custom { .. }) = cause.downcast_ref::<RuntimeError>() {
+        let mut error = error.clone();
+
+        if let RuntimeError::Custom { access_origin, .. } = &mut error {
+            if access_origin.is_nil() {
+                *access_origin = origin;
+            }
+        }
+
+        return error;
+    }
+
+    RuntimeError::UpcastResult {
+        access_origin: origin,
+        cause,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        runtime::{Cell, Origin, RuntimeError, RuntimeResult, ScriptPackage},
+        script_bail,
+    };
+
+    #[export]
+    pub fn read_asset(name: &str) -> RuntimeResult<usize> {
+        match name {
+            "denied" => Err(RuntimeError::custom(
+                Origin::nil(),
+                13,
+                "permission denied",
+                Some(Cell::give(Origin::nil(), 7usize)?),
+            )),
+
+            "missing" => script_bail!(Origin::nil(), 2, "asset {name:?} not found"),
+
+            _ => Ok(name.len()),
+        }
+    }
+
+    #[test]
+    fn test_custom_errors() {
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "let f = fn(x) { return read_asset(x); }; return f(\"missing\");",
+        );
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert_eq!(error.custom_code(), Some(2));
+        assert_eq!(error.custom_message(), Some("asset \"missing\" not found"));
+        assert!(error.custom_data().is_none());
+        assert!(!error.primary_origin().is_nil());
+
+        let display = error.display(&read.text()).to_string();
+
+        assert!(
+            display.contains("error 2: asset \"missing\" not found"),
+            "{display}"
+        );
+
+        let module = ScriptModule::new(TestPackage::meta(), "return read_asset(\"denied\");");
+        let read = module.read(&handle, 1).unwrap();
+
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert_eq!(error.custom_code(), Some(13));
+        assert_eq!(error.to_string(), "error 13: permission denied");
+
+        let data = error.custom_data().unwrap().clone();
+
+        assert_eq!(data.take::<usize>(Origin::nil()).unwrap(), 7);
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return read_asset(\"abc\");"),
+            3,
+        );
+    }
+}
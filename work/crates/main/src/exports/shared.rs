@@ -0,0 +1,242 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{ops::Deref, rc::Rc, sync::Arc};
+
+use crate::runtime::{Downcast, Origin, Provider, RuntimeResult, ScriptType, TypeHint, Upcast};
+
+// Arc data is shared between the host and the script. The script receives
+// a read-only Cell that points into the Arc allocation, and any attempt to
+// borrow this Cell's data mutably results in the RuntimeError::ReadOnly error.
+impl<'a, T> Upcast<'a> for Arc<T>
+where
+    T: ScriptType,
+{
+    type Output = Arc<T>;
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(this)
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(T::type_meta())
+    }
+}
+
+impl<'a, T> Upcast<'a> for &'a Arc<T>
+where
+    &'a T: Upcast<'a>,
+{
+    type Output = <&'a T as Upcast<'a>>::Output;
+
+    #[inline(always)]
+    fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        <&'a T as Upcast<'a>>::upcast(origin, this.deref())
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        <&'a T as Upcast<'a>>::hint()
+    }
+}
+
+// The mutable access to the Arc data follows the copy-on-write semantics of
+// the `Arc::make_mut` function: if the allocation is shared, the data will be
+// cloned into a new allocation owned by the Arc.
+impl<'a, T> Upcast<'a> for &'a mut Arc<T>
+where
+    T: Clone,
+    &'a mut T: Upcast<'a>,
+{
+    type Output = <&'a mut T as Upcast<'a>>::Output;
+
+    #[inline(always)]
+    fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        <&'a mut T as Upcast<'a>>::upcast(origin, Arc::make_mut(this))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        <&'a mut T as Upcast<'a>>::hint()
+    }
+}
+
+// If the provided Cell shares an Arc allocation previously returned to the
+// script, the downcasting returns a clone of this Arc. Otherwise, the data
+// will be downcasted by value and wrapped into a new Arc.
+impl<'a, T> Downcast<'a> for Arc<T>
+where
+    T: ScriptType + Downcast<'a>,
+{
+    #[inline(always)]
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        if let Some(arc) = provider.as_ref().to_arc::<T>() {
+            return Ok(arc);
+        }
+
+        let inner = <T as Downcast<'a>>::downcast(origin, provider)?;
+
+        Ok(Arc::new(inner))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        <T as Downcast<'a>>::hint()
+    }
+}
+
+// Script data is always thread-safe. Therefore, the Rc allocation cannot be
+// shared with the script: if the Rc is unique, the upcasting moves the data
+// out of the Rc; otherwise, the data will be cloned.
+//
+// For the same reason, the Rc type does not implement the Downcast trait.
+impl<'a, T> Upcast<'a> for Rc<T>
+where
+    T: Clone + Upcast<'a>,
+{
+    type Output = <T as Upcast<'a>>::Output;
+
+    #[inline(always)]
+    fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        let inner = Rc::try_unwrap(this).unwrap_or_else(|this| this.deref().clone());
+
+        <T as Upcast<'a>>::upcast(origin, inner)
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        <T as Upcast<'a>>::hint()
+    }
+}
+
+impl<'a, T> Upcast<'a> for &'a Rc<T>
+where
+    &'a T: Upcast<'a>,
+{
+    type Output = <&'a T as Upcast<'a>>::Output;
+
+    #[inline(always)]
+    fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        <&'a T as Upcast<'a>>::upcast(origin, this.deref())
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        <&'a T as Upcast<'a>>::hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        runtime::{Cell, Origin, RuntimeError, ScriptPackage},
+    };
+
+    #[export]
+    #[derive(Clone)]
+    pub struct TestMesh {
+        pub vertices: usize,
+    }
+
+    #[export]
+    pub fn test_shared_mesh(vertices: usize) -> Arc<TestMesh> {
+        Arc::new(TestMesh { vertices })
+    }
+
+    #[export]
+    pub fn test_owned_mesh(vertices: usize) -> TestMesh {
+        TestMesh { vertices }
+    }
+
+    #[export]
+    pub fn test_mesh_owners(mesh: Arc<TestMesh>) -> usize {
+        Arc::strong_count(&mesh)
+    }
+
+    #[test]
+    fn test_arc_sharing() {
+        let mesh = Arc::new(TestMesh { vertices: 3 });
+        let cell = Cell::give(Origin::nil(), mesh.clone()).unwrap();
+
+        assert_eq!(Arc::strong_count(&mesh), 2);
+        assert!(Arc::ptr_eq(&cell.to_arc::<TestMesh>().unwrap(), &mesh));
+
+        drop(cell);
+
+        assert_eq!(Arc::strong_count(&mesh), 1);
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "let mesh = test_shared_mesh(3); return test_mesh_owners(mesh);"
+            ),
+            2,
+        );
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "let mesh = test_owned_mesh(3); return test_mesh_owners(mesh);"
+            ),
+            1,
+        );
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "let mesh = test_shared_mesh(3); return mesh.vertices;"
+            ),
+            3,
+        );
+
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "let mesh = test_shared_mesh(3); mesh.vertices = 5;",
+        );
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert!(matches!(error, RuntimeError::ReadOnly { .. }));
+    }
+}
@@ -32,7 +32,10 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use crate::runtime::{Downcast, Origin, Provider, RuntimeResult, ScriptType, TypeHint, Upcast};
+use crate::{
+    exports::{array_hint, bytes_mismatch, is_bytes_of, Bytes},
+    runtime::{Downcast, Origin, Provider, RuntimeResult, ScriptType, TypeHint, Upcast},
+};
 
 impl<'a, T> Downcast<'a> for &'a [T]
 where
@@ -40,12 +43,21 @@ where
 {
     #[inline]
     fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
-        provider.to_borrowed(&origin)?.borrow_slice_ref::<T>(origin)
+        let cell = provider.to_borrowed(&origin)?;
+
+        if is_bytes_of::<T>(cell) {
+            return cell
+                .borrow_ref::<Bytes>(origin)?
+                .as_slice_of::<T>()
+                .ok_or_else(|| bytes_mismatch::<T>(origin));
+        }
+
+        cell.borrow_slice_ref::<T>(origin)
     }
 
     #[inline(always)]
     fn hint() -> TypeHint {
-        TypeHint::Type(T::type_meta())
+        array_hint::<T>()
     }
 }
 
@@ -55,12 +67,21 @@ where
 {
     #[inline]
     fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
-        provider.to_borrowed(&origin)?.borrow_slice_mut::<T>(origin)
+        let cell = provider.to_borrowed(&origin)?;
+
+        if is_bytes_of::<T>(cell) {
+            return cell
+                .borrow_mut::<Bytes>(origin)?
+                .as_mut_slice_of::<T>()
+                .ok_or_else(|| bytes_mismatch::<T>(origin));
+        }
+
+        cell.borrow_slice_mut::<T>(origin)
     }
 
     #[inline(always)]
     fn hint() -> TypeHint {
-        TypeHint::Type(T::type_meta())
+        array_hint::<T>()
     }
 }
 
@@ -77,7 +98,7 @@ where
 
     #[inline(always)]
     fn hint() -> TypeHint {
-        TypeHint::Type(T::type_meta())
+        array_hint::<T>()
     }
 }
 
@@ -97,3 +118,35 @@ where
         TypeHint::Type(T::type_meta())
     }
 }
+
+impl<'a, T> Downcast<'a> for Box<[T]>
+where
+    T: ScriptType,
+{
+    #[inline]
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        Ok(<Vec<T>>::downcast(origin, provider)?.into_boxed_slice())
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        array_hint::<T>()
+    }
+}
+
+impl<'a, T> Upcast<'a> for Box<[T]>
+where
+    T: ScriptType,
+{
+    type Output = Vec<T>;
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(this.into_vec())
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        array_hint::<T>()
+    }
+}
@@ -38,7 +38,7 @@ use crate::{
     export,
     exports::utils::Stringifier,
     runtime::{
-        ops::{ScriptConcat, ScriptDisplay, ScriptPartialEq},
+        ops::{DynamicType, ScriptAdd, ScriptConcat, ScriptDisplay, ScriptHash, ScriptPartialEq},
         Arg,
         Cell,
         Downcast,
@@ -128,31 +128,53 @@ impl ScriptConcat for str {
     fn script_concat(origin: Origin, items: &mut [Arg]) -> RuntimeResult<Cell> {
         let mut result = String::new();
 
-        for item in items {
-            if item.data.is_nil() {
-                continue;
-            }
+        append_items(&mut result, items)?;
 
-            let cell = take(&mut item.data);
+        Cell::give(origin, result)
+    }
+}
 
-            let stringifier = Stringifier {
-                origin: item.origin,
-                cell: &cell,
-                error: RefCell::new(None),
-                fallback_to_type: false,
-            };
+#[export(include)]
+impl ScriptAdd for str {
+    type RHS = DynamicType;
+    type Result = str;
 
-            let string = stringifier.to_string();
+    fn script_add(origin: Origin, lhs: Arg, rhs: Arg) -> RuntimeResult<Cell> {
+        let mut result = lhs.data.take_string(lhs.origin)?;
 
-            if let Some(error) = stringifier.error.take() {
-                return Err(error);
-            }
+        append_items(&mut result, &mut [rhs])?;
 
-            result.push_str(&string);
+        Cell::give(origin, result)
+    }
+}
+
+// Stringifies each non-nil item and appends it to the `target` string, exactly
+// as the string concatenation operator does.
+pub(crate) fn append_items(target: &mut String, items: &mut [Arg]) -> RuntimeResult<()> {
+    for item in items {
+        if item.data.is_nil() {
+            continue;
         }
 
-        Cell::give(origin, result)
+        let cell = take(&mut item.data);
+
+        let stringifier = Stringifier {
+            origin: item.origin,
+            cell: &cell,
+            error: RefCell::new(None),
+            fallback_to_type: false,
+        };
+
+        let string = stringifier.to_string();
+
+        if let Some(error) = stringifier.error.take() {
+            return Err(error);
+        }
+
+        target.push_str(&string);
     }
+
+    Ok(())
 }
 
 #[export(include)]
@@ -167,6 +189,9 @@ impl ScriptPartialEq for str {
     }
 }
 
+#[export(include)]
+impl ScriptHash for str {}
+
 impl<'a> Downcast<'a> for char {
     fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
         let mut type_match = provider.type_match();
@@ -36,14 +36,15 @@ use std::{
     cell::RefCell,
     collections::BTreeMap,
     fmt::{Debug, Display, Formatter},
-    mem::transmute,
+    mem::{size_of, take, transmute},
 };
 
 use crate::{
     export,
-    exports::utils::Stringifier,
+    exports::utils::{NestingGuard, Stringifier},
     runtime::{
-        ops::{DynamicType, ScriptAssign, ScriptField, ScriptNone, ScriptPartialEq},
+        ops::{DynamicType, OperatorKind, ScriptAssign, ScriptField, ScriptNone, ScriptPartialEq},
+        provide_entry,
         Arg,
         Cell,
         Downcast,
@@ -77,7 +78,22 @@ use crate::{
 /// st.124? == true;
 /// st.new_field? == true;
 /// st.unknown_field? == false;
+///
+/// let key = "dynamic";
+///
+/// st[key] = 100;
+/// st["dynamic"] == 100;
+///
+/// st.has("foo") == true;
+/// st.remove("foo") == true;
+/// st.has("foo") == false;
+///
+/// st.keys() == ["124", "bar", "dynamic", "func", "new_field"];
 /// ```
+///
+/// The entries of the struct are ordered by their names in lexicographical
+/// order, and the [keys](Struct::keys) function enumerates them in this order.
+/// Removing an entry does not affect the order of the remaining entries.
 #[export(include)]
 #[export(name "struct")]
 #[derive(Clone, Default)]
@@ -86,10 +102,183 @@ pub struct Struct {
     pub(crate) map: BTreeMap<Ident, Cell>,
 }
 
+impl Drop for Struct {
+    // Dismantles the nested structs iteratively, such that dropping of deeply
+    // nested data does not overflow the thread stack.
+    fn drop(&mut self) {
+        let mut stack = take(&mut self.map).into_values().collect::<Vec<_>>();
+
+        while let Some(cell) = stack.pop() {
+            if !cell.is_unique() || !cell.is::<Struct>() {
+                continue;
+            }
+
+            let Ok(nested) = cell.take_vec::<Struct>(Origin::nil()) else {
+                continue;
+            };
+
+            for mut nested in nested {
+                stack.extend(take(&mut nested.map).into_values());
+            }
+        }
+    }
+}
+
+impl Struct {
+    // The name of the struct entry that overloads the index operator
+    // (`foo[index]`).
+    pub(crate) const INDEX_ENTRY: &'static str = "__index";
+
+    // The approximate number of bytes that a single struct entry occupies,
+    // charged to the evaluation's memory budget.
+    const ENTRY_SIZE: usize = size_of::<(Ident, Cell)>();
+
+    // Returns true if the struct entry with the specified name overloads one of
+    // the script operators.
+    pub(crate) fn is_operator_entry(name: &str) -> bool {
+        if name == Self::INDEX_ENTRY {
+            return true;
+        }
+
+        OPERATOR_ENTRIES
+            .iter()
+            .any(|operator| operator.entry_name() == Some(name))
+    }
+
+    // Inserts the `value` into the `structure` under the `key` entry the same
+    // way as the script assignment to the struct field does: binds the value
+    // (e.g., a function) to the structure, and charges the structure's memory
+    // budget if the entry is new.
+    //
+    // The caller is responsible for rejecting the values that are not allowed
+    // to be assigned (e.g., Nil values).
+    pub(crate) fn insert_entry(
+        origin: Origin,
+        structure_origin: Origin,
+        mut structure: Cell,
+        key: Ident,
+        value: Arg,
+    ) -> RuntimeResult<()> {
+        if value.data.ty().prototype().implements_binding() {
+            let value = value.clone();
+
+            value.data.into_object().bind(
+                value.origin,
+                value.origin,
+                Arg {
+                    origin: structure_origin,
+                    data: structure.clone(),
+                },
+            )?;
+        }
+
+        let map = &structure.borrow_mut::<Self>(origin)?.map;
+
+        if !map.contains_key(&key) {
+            structure.charge_memory(origin, Self::ENTRY_SIZE)?;
+        }
+
+        let map = &mut structure.borrow_mut::<Self>(origin)?.map;
+
+        let _ = map.insert(key, value.data);
+
+        Ok(())
+    }
+
+    // Returns the entry of the `receiver` structure with the specified name.
+    //
+    // Returns None if the receiver is not a structure, or if the structure
+    // does not have such an entry.
+    pub(crate) fn entry(
+        receiver: &Cell,
+        receiver_origin: Origin,
+        key: &str,
+    ) -> RuntimeResult<Option<Cell>> {
+        if !receiver.is::<Self>() || receiver.length() != 1 {
+            return Ok(None);
+        }
+
+        let mut data = receiver.clone();
+
+        let structure = data.borrow_ref::<Self>(receiver_origin)?;
+
+        Ok(structure.map.get(&Ident::from_string(key)).cloned())
+    }
+
+    // Calls the struct entry that overloads an operator of the `receiver`
+    // structure with the specified arguments.
+    //
+    // Returns None if the receiver is not a structure, or if the structure
+    // does not have such an entry.
+    pub(crate) fn overload(
+        origin: Origin,
+        receiver: &Arg,
+        entry: &str,
+        arguments: &mut [Arg],
+    ) -> Option<RuntimeResult<Cell>> {
+        if !receiver.data.is::<Self>() {
+            return None;
+        }
+
+        let mut data = receiver.data.clone();
+
+        let entry = match data.borrow_ref::<Self>(receiver.origin) {
+            Ok(structure) => structure.map.get(&Ident::from_string(entry))?.clone(),
+            Err(error) => return Some(Err(error)),
+        };
+
+        Some(
+            entry
+                .into_object()
+                .invoke(origin, receiver.origin, arguments),
+        )
+    }
+}
+
+#[export(include)]
+impl Struct {
+    /// Returns true if the struct has an entry with the specified `key`.
+    pub fn has(&self, key: &str) -> bool {
+        self.map.contains_key(&Ident::from_string(key))
+    }
+
+    /// Returns an array of the struct's entry names in lexicographical order.
+    pub fn keys(&self) -> Vec<String> {
+        self.map
+            .keys()
+            .map(|key| String::from(key.as_ref()))
+            .collect()
+    }
+
+    /// Removes the entry with the specified `key` from the struct.
+    ///
+    /// Returns true if the struct had such an entry.
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.map.remove(&Ident::from_string(key)).is_some()
+    }
+}
+
+const OPERATOR_ENTRIES: [OperatorKind; 14] = [
+    OperatorKind::PartialEq,
+    OperatorKind::Invocation,
+    OperatorKind::Add,
+    OperatorKind::Sub,
+    OperatorKind::Mul,
+    OperatorKind::Div,
+    OperatorKind::Not,
+    OperatorKind::Neg,
+    OperatorKind::BitAnd,
+    OperatorKind::BitOr,
+    OperatorKind::BitXor,
+    OperatorKind::Shl,
+    OperatorKind::Shr,
+    OperatorKind::Rem,
+];
+
 impl<'a> Downcast<'a> for BTreeMap<Ident, Cell> {
     #[inline(always)]
     fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
-        Ok(provider.to_owned().take::<Struct>(origin)?.map)
+        Ok(take(&mut provider.to_owned().take::<Struct>(origin)?.map))
     }
 
     #[inline(always)]
@@ -221,7 +410,19 @@ impl Display for Struct {
 impl ScriptPartialEq for Struct {
     type RHS = Self;
 
-    fn script_eq(_origin: Origin, mut lhs: Arg, mut rhs: Arg) -> RuntimeResult<bool> {
+    fn script_eq(origin: Origin, mut lhs: Arg, mut rhs: Arg) -> RuntimeResult<bool> {
+        let Some(_guard) = NestingGuard::enter() else {
+            return Err(RuntimeError::StackOverflow { origin });
+        };
+
+        if let Some(name) = OperatorKind::PartialEq.entry_name() {
+            let mut arguments = [rhs.clone()];
+
+            if let Some(result) = Self::overload(origin, &lhs, name, &mut arguments) {
+                return result?.take::<bool>(origin);
+            }
+        }
+
         let lhs = <&Self as Downcast>::downcast(lhs.origin, lhs.provider())?;
         let rhs = <&Self as Downcast>::downcast(rhs.origin, rhs.provider())?;
 
@@ -247,16 +448,20 @@ impl ScriptPartialEq for Struct {
 
             let lhs = lhs.1.clone().into_object();
 
-            let equal = lhs
-                .partial_eq(
-                    Origin::default(),
-                    Origin::default(),
-                    Arg {
-                        origin: Origin::default(),
-                        data: rhs.1.clone(),
-                    },
-                )
-                .unwrap_or(false);
+            let equal = lhs.partial_eq(
+                Origin::default(),
+                Origin::default(),
+                Arg {
+                    origin: Origin::default(),
+                    data: rhs.1.clone(),
+                },
+            );
+
+            let equal = match equal {
+                Ok(equal) => equal,
+                Err(error @ RuntimeError::StackOverflow { .. }) => return Err(error),
+                Err(_) => false,
+            };
 
             if !equal {
                 return Ok(false);
@@ -282,11 +487,26 @@ impl ScriptField for Struct {
             }
         }
 
+        let provided = provide_entry(origin, &lhs.data, rhs.as_ref());
+
+        if let Some(Some(entry)) = provided {
+            let mut structure = lhs.data;
+
+            structure.charge_memory(origin, Self::ENTRY_SIZE)?;
+
+            let structure = structure.borrow_mut::<Self>(lhs.origin)?;
+
+            let _ = structure.map.insert(rhs, entry.clone());
+
+            return Ok(entry);
+        }
+
         Cell::give(
             origin,
             Vacant {
                 structure: lhs.data,
                 key: rhs,
+                declined: provided.is_some(),
             },
         )
     }
@@ -298,6 +518,9 @@ type VacantType = Vacant;
 pub struct Vacant {
     structure: Cell,
     key: Ident,
+    // True if the entry provider has been consulted, but it did not supply
+    // the entry.
+    declined: bool,
 }
 
 impl<'a> Upcast<'a> for Vacant {
@@ -319,7 +542,13 @@ impl Display for Vacant {
     #[inline(always)]
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
         formatter.write_str("struct.")?;
-        Display::fmt(&self.key, formatter)
+        Display::fmt(&self.key, formatter)?;
+
+        if self.declined {
+            formatter.write_str(" (no such entry and no provider supplied it)")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -342,28 +571,285 @@ impl ScriptAssign for Vacant {
             });
         }
 
-        let mut vacant = lhs.data.take::<Self>(lhs.origin)?;
+        let vacant = lhs.data.take::<Self>(lhs.origin)?;
+
+        Struct::insert_entry(origin, lhs.origin, vacant.structure, vacant.key, rhs)
+    }
+}
+
+#[export(include)]
+impl ScriptNone for Vacant {}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell as StdCell, collections::BTreeMap, rc::Rc, thread::spawn};
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, IssueCode, ModuleRead, ScriptModule},
+        exports::Struct,
+        runtime::{
+            remove_entry_provider,
+            set_entry_provider,
+            Arg,
+            Cell,
+            Downcast,
+            FromScriptStruct,
+            Ident,
+            Origin,
+            Provider,
+            RuntimeError,
+            ScriptPackage,
+        },
+    };
+
+    const DEEP: usize = 100_000;
+
+    // Builds `DEEP` levels of nested structs. If `arrays` is true, each level
+    // is an array of two structs, and the first struct refers to the next
+    // level.
+    fn deep_structure(arrays: bool) -> Cell {
+        let mut cell = Cell::nil();
+
+        for _ in 0..DEEP {
+            let mut map = BTreeMap::new();
+
+            let _ = map.insert(Ident::from_string("inner"), cell);
+
+            let level = match arrays {
+                true => vec![Struct { map }, Struct::default()],
+                false => vec![Struct { map }],
+            };
+
+            cell = Cell::give_vec(Origin::nil(), level).unwrap();
+        }
+
+        cell
+    }
+
+    fn eval(text: &str) -> String {
+        crate::eval!(TestPackage, text).stringify(false)
+    }
+
+    #[test]
+    fn test_entry_provider() {
+        assert_eq!(eval("let s = struct {}; return s.foo;"), "struct.foo");
+
+        let calls = Rc::new(StdCell::new(0));
+
+        set_entry_provider({
+            let calls = calls.clone();
+
+            move |origin, _structure, key| {
+                calls.set(calls.get() + 1);
+
+                match key {
+                    "sprite" => Cell::give(origin, 10usize).ok(),
+                    _ => None,
+                }
+            }
+        });
+
+        assert_eq!(eval("let s = struct {}; return s.sprite + s.sprite;"), "20");
+        assert_eq!(calls.get(), 1);
+
+        assert_eq!(
+            eval("let s = struct {}; return s.foo;"),
+            "struct.foo (no such entry and no provider supplied it)",
+        );
+
+        assert_eq!(eval("let s = struct {}; s.foo = 5; return s.foo;"), "5");
+        assert_eq!(eval("let s = struct { sprite: 3 }; return s.sprite;"), "3");
+
+        remove_entry_provider();
 
-        if rhs.data.ty().prototype().implements_binding() {
-            let rhs = rhs.clone();
+        calls.set(0);
 
-            rhs.data.into_object().bind(
-                rhs.origin,
-                rhs.origin,
+        assert_eq!(eval("let s = struct {}; return s.sprite;"), "struct.sprite");
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[test]
+    fn test_operator_entries() {
+        static VEC: &str = r#"
+            let vec = struct {
+                x: 3,
+                y: 4,
+                __add: fn(rhs) struct { x: self.x + rhs.x, y: self.y + rhs.y },
+                __mul: fn(factor) struct { x: self.x * factor, y: self.y * factor },
+                __not: fn() self.x == 0,
+                __eq: fn(rhs) self.x == rhs.x,
+                __index: fn(index) self.x * index,
+                __call: fn(a, b) self.y + a + b,
+            };
+        "#;
+
+        let eval_vec = |source: &str| eval(&format!("{VEC}{source}"));
+
+        assert_eq!(
+            eval_vec("let sum = vec + vec; return sum.x * 10 + sum.y;"),
+            "68"
+        );
+        assert_eq!(eval_vec("return (vec * 2).y;"), "8");
+        assert_eq!(eval_vec("return !vec;"), "false");
+        assert_eq!(eval_vec("return vec == struct { x: 3, y: 100 };"), "true");
+        assert_eq!(eval_vec("return vec != struct { x: 3, y: 100 };"), "false");
+        assert_eq!(eval_vec("return vec[5];"), "15");
+        assert_eq!(eval_vec("return vec(10, 20);"), "34");
+
+        assert_eq!(eval("return struct { x: 1 } == struct { x: 1 };"), "true");
+
+        crate::assert_script_err!(
+            TestPackage,
+            "let a = struct { __add: fn(rhs) rhs }; a - a;",
+            IssueCode::UndefinedOperator,
+            "-",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "let a = struct { __add: fn() 1 }; a + a;",
+            IssueCode::CallArityMismatch,
+            "+",
+        );
+
+        crate::assert_script_ok!(
+            TestPackage,
+            "let a = struct { __sub: fn(rhs) rhs, __call: fn(x) x }; a - a; a(1);",
+        );
+    }
+
+    #[test]
+    fn test_entry_management() {
+        assert_eq!(
+            eval("let s = struct { b: 2, a: 1 }; return s.keys().len;"),
+            "2"
+        );
+        assert_eq!(
+            eval("let s = struct { b: 2, a: 1 }; return s.keys()[0];"),
+            "a"
+        );
+        assert_eq!(
+            eval("let s = struct { a: 1 }; return s.has(\"a\");"),
+            "true"
+        );
+        assert_eq!(
+            eval("let s = struct { a: 1 }; return s.has(\"b\");"),
+            "false"
+        );
+
+        assert_eq!(
+            eval("let s = struct { a: 1, b: 2 }; s.remove(\"a\"); return s.has(\"a\");"),
+            "false",
+        );
+        assert_eq!(
+            eval("let s = struct { a: 1, b: 2, c: 3 }; s.remove(\"b\"); return s.keys()[1];"),
+            "c",
+        );
+        assert_eq!(
+            eval("let s = struct { a: 1 }; return s.remove(\"b\");"),
+            "false"
+        );
+
+        assert_eq!(
+            eval("let s = struct { foo: 3 }; return s[\"foo\"] + 1;"),
+            "4"
+        );
+        assert_eq!(
+            eval("let s = struct {}; let k = \"foo\"; s[k] = 5; return s.foo;"),
+            "5",
+        );
+        assert_eq!(eval("let s = struct { keys: 7 }; return s[\"keys\"];"), "7");
+        assert_eq!(eval("let s = struct { keys: 7 }; return s.keys + 1;"), "8");
+        assert_eq!(eval("let s = struct {}; s.has = 1; return s.has;"), "1");
+
+        crate::assert_script_ok!(
+            TestPackage,
+            "let s = struct { a: 1 }; let k = \"a\"; s[k] = 2; s.remove(k);",
+        );
+    }
+
+    #[test]
+    fn test_deep_structures() {
+        // The test threads have the default (small) stack size.
+        spawn(|| {
+            let structure = deep_structure(true);
+
+            let short = structure.stringify(false);
+            let long = structure.stringify(true);
+
+            assert!(short.starts_with(r#"[{"inner": [{"inner": "#));
+            assert!(short.contains("…"));
+            assert!(long.contains("…"));
+
+            drop(structure);
+
+            let lhs = deep_structure(false);
+            let rhs = deep_structure(false);
+
+            let result = lhs.clone().into_object().partial_eq(
+                Origin::nil(),
+                Origin::nil(),
                 Arg {
-                    origin: lhs.origin,
-                    data: vacant.structure.clone(),
+                    origin: Origin::nil(),
+                    data: rhs,
                 },
-            )?;
-        }
+            );
+
+            match result {
+                Err(RuntimeError::StackOverflow { .. }) => (),
+                _ => panic!("Missing stack overflow error."),
+            }
 
-        let structure = vacant.structure.borrow_mut::<Struct>(origin)?;
+            drop(lhs);
 
-        let _ = structure.map.insert(vacant.key, rhs.data);
+            let mut projection = Cell::give(Origin::nil(), 10usize).unwrap();
 
-        Ok(())
+            for _ in 0..DEEP {
+                projection = projection.map_slice(Origin::nil(), 0..=0).unwrap();
+            }
+
+            assert_eq!(projection.stringify(false), "10");
+
+            drop(projection);
+
+            let node = <DeepNode as Downcast>::downcast(
+                Origin::nil(),
+                Provider::Owned(crate::eval!(
+                    TestPackage,
+                    "return struct { inner: struct { inner: struct {} } };"
+                )),
+            )
+            .unwrap();
+
+            assert_eq!(node.depth(), 3);
+
+            let result = <DeepNode as Downcast>::downcast(
+                Origin::nil(),
+                Provider::Owned(deep_structure(false)),
+            );
+
+            match result {
+                Err(RuntimeError::StackOverflow { .. }) => (),
+                _ => panic!("Missing stack overflow error."),
+            }
+        })
+        .join()
+        .unwrap();
     }
-}
 
-#[export(include)]
-impl ScriptNone for Vacant {}
+    #[derive(FromScriptStruct)]
+    struct DeepNode {
+        inner: Option<Box<DeepNode>>,
+    }
+
+    impl DeepNode {
+        fn depth(&self) -> usize {
+            match &self.inner {
+                Some(inner) => inner.depth() + 1,
+                None => 1,
+            }
+        }
+    }
+}
@@ -33,12 +33,54 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use std::{
-    cell::RefCell,
+    cell::{Cell as StdCell, RefCell},
     fmt::{Debug, Display, Formatter},
 };
 
 use crate::runtime::{Cell, Origin, RuntimeError};
 
+// The maximum nesting depth of the script data (e.g., structs within structs)
+// that the recursive traversals of the data, such as formatting and
+// comparison, descend into.
+//
+// The limit keeps these traversals within the native thread stack regardless
+// of the data depth.
+const NESTING_LIMIT: usize = 128;
+
+thread_local! {
+    static NESTING_DEPTH: StdCell<usize> = const { StdCell::new(0) };
+}
+
+// Tracks the current nesting depth of the recursive traversal of the script
+// data on this thread.
+pub(crate) struct NestingGuard;
+
+impl Drop for NestingGuard {
+    #[inline(always)]
+    fn drop(&mut self) {
+        NESTING_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+impl NestingGuard {
+    // Enters the next nesting level. Returns None if the traversal is too
+    // deep.
+    #[inline(always)]
+    pub(crate) fn enter() -> Option<Self> {
+        NESTING_DEPTH.with(|depth| {
+            let current = depth.get();
+
+            if current >= NESTING_LIMIT {
+                return None;
+            }
+
+            depth.set(current + 1);
+
+            Some(Self)
+        })
+    }
+}
+
 pub(crate) struct Stringifier<'a> {
     pub(crate) origin: Origin,
     pub(crate) cell: &'a Cell,
@@ -49,6 +91,10 @@ pub(crate) struct Stringifier<'a> {
 impl<'a> Display for Stringifier<'a> {
     #[inline(always)]
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some(_guard) = NestingGuard::enter() else {
+            return formatter.write_str("…");
+        };
+
         let result = self
             .cell
             .clone()
@@ -77,13 +123,21 @@ impl<'a> Display for Stringifier<'a> {
 }
 
 impl<'a> Debug for Stringifier<'a> {
-    #[inline(always)]
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
-        let result = self
-            .cell
-            .clone()
-            .into_object()
-            .debug(self.origin, self.origin, formatter);
+        let Some(_guard) = NestingGuard::enter() else {
+            return formatter.write_str("…");
+        };
+
+        if self.is_array() {
+            return self.fmt_array(formatter);
+        }
+
+        let object = self.cell.clone().into_object();
+
+        let result = match object.prototype().implements_debug() {
+            true => object.debug(self.origin, self.origin, formatter),
+            false => object.display(self.origin, self.origin, formatter),
+        };
 
         if let Err(error) = result {
             let is_format_error = match &error {
@@ -106,24 +160,83 @@ impl<'a> Debug for Stringifier<'a> {
     }
 }
 
+impl<'a> Stringifier<'a> {
+    // Returns true if the Cell is an array of several elements, whose elements
+    // should be formatted one by one.
+    //
+    // Strings are stored as arrays of bytes, but they are formatted as a whole.
+    #[inline(always)]
+    fn is_array(&self) -> bool {
+        self.cell.length() > 1 && !self.cell.is::<str>()
+    }
+
+    fn fmt_array(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut debug_list = formatter.debug_list();
+
+        for index in 0..self.cell.length() {
+            let element = match self.cell.clone().map_slice(self.origin, index..=index) {
+                Ok(element) => element,
+
+                Err(error) => {
+                    *self.error.borrow_mut() = Some(error);
+
+                    if self.fallback_to_type {
+                        debug_list.entry(&format_args!("<{}>", self.cell.ty()));
+                    }
+
+                    continue;
+                }
+            };
+
+            let stringifier = Stringifier {
+                origin: self.origin,
+                cell: &element,
+                error: RefCell::new(None),
+                fallback_to_type: self.fallback_to_type,
+            };
+
+            debug_list.entry(&stringifier);
+
+            if let Some(error) = stringifier.error.take() {
+                *self.error.borrow_mut() = Some(error);
+            }
+        }
+
+        debug_list.finish()
+    }
+}
+
 impl Cell {
     /// A convenient utility function that formats Cell's data for debugging
     /// purposes.
     ///
-    /// If the underlying [type](Cell::ty) of the Cell implements
-    /// [display operations](crate::runtime::Prototype::implements_display), the
-    /// function uses the Display implementation to format the data. Otherwise,
-    /// if the type implements
-    /// [debug operations](crate::runtime::Prototype::implements_debug),
-    /// the function falls back to the Debug implementation.
+    /// The `alt` parameter specifies whether the function should produce a
+    /// short message or a verbose tooltip-like description:
+    ///
+    /// - If `alt` is false and the underlying [type](Cell::ty) of the Cell
+    ///   implements
+    ///   [display operations](crate::runtime::Prototype::implements_display),
+    ///   the function uses the Display implementation to format the data.
+    ///   Otherwise, the function falls back to the
+    ///   [Debug implementation](crate::runtime::Prototype::implements_debug).
+    ///
+    /// - If `alt` is true, the function prefers the alternate Debug formatting
+    ///   (`format!("{:#?}")`) and falls back to the alternate Display
+    ///   formatting (`format!("{:#}")`) if the type does not implement Debug.
+    ///
+    /// If the Cell is an array of several elements, the function formats each
+    /// element in turn and prints them as a list. Nested values (e.g., the
+    /// fields of a script struct, or the elements of an array) are formatted
+    /// using their Debug implementations when available.
+    ///
+    /// Values nested too deeply (e.g., a struct within a struct within a
+    /// struct, and so on, more than a hundred levels deep) are formatted
+    /// as `…`.
     ///
     /// This function temporarily [borrows](Cell::borrow_ref) the underlying
     /// data. If the borrowing fails, or if the data type does not implement
     /// either Display or Debug traits, the result will be the signature of
     /// the type.
-    ///
-    /// The `alt` parameter specifies whether the function should use alternate
-    /// formatting (such as `format!("{:#}")`) or not.
     #[inline(always)]
     pub fn stringify(&self, alt: bool) -> String {
         let stringifier = Stringifier {
@@ -133,11 +246,19 @@ impl Cell {
             fallback_to_type: true,
         };
 
-        let result = match (self.ty().prototype().implements_display(), alt) {
-            (true, true) => format!("{stringifier:#}"),
-            (true, false) => format!("{stringifier:}"),
-            (false, true) => format!("{stringifier:#?}"),
-            (false, false) => format!("{stringifier:?}"),
+        let prototype = self.ty().prototype();
+
+        let debug = match alt {
+            _ if stringifier.is_array() => true,
+            true => prototype.implements_debug() || !prototype.implements_display(),
+            false => !prototype.implements_display(),
+        };
+
+        let result = match (debug, alt) {
+            (false, true) => format!("{stringifier:#}"),
+            (false, false) => format!("{stringifier:}"),
+            (true, true) => format!("{stringifier:#?}"),
+            (true, false) => format!("{stringifier:?}"),
         };
 
         result
@@ -200,3 +321,169 @@ macro_rules! transparent_upcast {
 }
 
 pub(crate) use transparent_upcast;
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::{self, Formatter};
+
+    use crate::{
+        analysis::symbols::tests::TestPackage,
+        eval,
+        export,
+        exports::utils::transparent_upcast,
+        runtime::{ops, Cell, Origin, ScriptType},
+    };
+
+    #[export]
+    #[derive(Clone, Debug)]
+    pub struct Marker {
+        pub id: usize,
+    }
+
+    #[export]
+    #[derive(Clone)]
+    pub struct Holder {
+        pub marker: Marker,
+    }
+
+    #[export]
+    impl fmt::Debug for Holder {
+        fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+            write!(formatter, "Holder({})", self.marker.id)
+        }
+    }
+
+    #[export]
+    #[derive(Clone, Debug)]
+    pub struct Gauge {
+        pub level: usize,
+    }
+
+    #[export]
+    impl std::fmt::Display for Gauge {
+        fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+            write!(formatter, "{}%", self.level)
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub enum Flavor {
+        Sweet,
+    }
+
+    #[export]
+    pub type FlavorAlias = Flavor;
+
+    #[export]
+    impl ops::ScriptDebug for Flavor {}
+
+    transparent_upcast!(Flavor);
+
+    #[export]
+    pub fn marker(id: usize) -> Marker {
+        Marker { id }
+    }
+
+    #[export]
+    pub fn holder(id: usize) -> Holder {
+        Holder {
+            marker: Marker { id },
+        }
+    }
+
+    #[export]
+    pub fn gauge(level: usize) -> Gauge {
+        Gauge { level }
+    }
+
+    #[export]
+    pub fn flavor() -> Flavor {
+        Flavor::Sweet
+    }
+
+    #[test]
+    fn test_stringify_debug() {
+        let cell = Cell::give(Origin::nil(), Marker { id: 1 }).unwrap();
+
+        assert_eq!(cell.stringify(false), "Marker { id: 1 }");
+        assert_eq!(cell.stringify(true), "Marker {\n    id: 1,\n}");
+
+        assert_eq!(
+            eval!(TestPackage, "return holder(2);").stringify(true),
+            "Holder(2)"
+        );
+        assert_eq!(
+            eval!(TestPackage, "return flavor();").stringify(false),
+            "Sweet"
+        );
+        assert_eq!(
+            eval!(TestPackage, "return flavor();").stringify(true),
+            "Sweet"
+        );
+        assert_eq!(eval!(TestPackage, "return 10;").stringify(true), "10");
+        assert_eq!(eval!(TestPackage, "return \"foo\";").stringify(true), "foo");
+    }
+
+    #[test]
+    fn test_stringify_display() {
+        let prototype = <Gauge>::type_meta().prototype();
+
+        assert!(prototype.implements_display());
+        assert!(prototype.implements_debug());
+        assert!(!<Marker>::type_meta().prototype().implements_display());
+
+        let cell = Cell::give(Origin::nil(), Gauge { level: 40 }).unwrap();
+
+        assert_eq!(cell.stringify(false), "40%");
+        assert_eq!(cell.stringify(true), "Gauge {\n    level: 40,\n}");
+
+        assert_eq!(
+            eval!(TestPackage, "return to_string(gauge(75));").stringify(false),
+            "75%",
+        );
+        assert_eq!(
+            eval!(TestPackage, "return [\"level: \", gauge(5)];").stringify(false),
+            "level: 5%",
+        );
+        assert_eq!(
+            eval!(TestPackage, "return [gauge(1), gauge(2)];").stringify(false),
+            "[Gauge { level: 1 }, Gauge { level: 2 }]",
+        );
+    }
+
+    #[test]
+    fn test_stringify_nested() {
+        assert_eq!(
+            eval!(TestPackage, "return holder(3).marker;").stringify(false),
+            "Marker { id: 3 }",
+        );
+
+        assert_eq!(
+            eval!(TestPackage, "return [marker(1), marker(2)];").stringify(false),
+            "[Marker { id: 1 }, Marker { id: 2 }]",
+        );
+
+        assert_eq!(
+            eval!(TestPackage, "return [1, 2, 3];").stringify(false),
+            "[1, 2, 3]"
+        );
+        assert_eq!(
+            eval!(TestPackage, "return [\"a\", \"b\"];").stringify(false),
+            "ab"
+        );
+
+        assert_eq!(
+            eval!(
+                TestPackage,
+                "return struct { a: flavor(), b: [marker(4), marker(5)], c: holder(6) };",
+            )
+            .stringify(false),
+            "{\"a\": Sweet, \"b\": [Marker { id: 4 }, Marker { id: 5 }], \"c\": Holder(6)}",
+        );
+
+        assert_eq!(
+            eval!(TestPackage, "return struct { a: [1, 2], b: marker(6) };").stringify(true),
+            "{\n    \"a\": [\n        1,\n        2,\n    ],\n    \"b\": Marker {\n        id: 6,\n    },\n}",
+        );
+    }
+}
@@ -0,0 +1,219 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+};
+
+use compact_str::CompactString;
+
+use crate::{
+    export,
+    runtime::{
+        ops::{ScriptDebug, ScriptDisplay, ScriptHash, ScriptPartialEq},
+        Arg,
+        Origin,
+        RuntimeResult,
+    },
+};
+
+/// A value of a script enum declaration:
+///
+/// ```text
+/// enum Color { Red, Green, Blue }
+///
+/// let color = Color.Green;
+///
+/// color == Color.Green;
+/// color != Color.Blue;
+/// color.tag() == 1;
+/// ```
+///
+/// The string representation of the variant is the variant's name (`"Green"`).
+///
+/// Two variants are equal if they belong to the enums with the same name and
+/// have the same [tag](Variant::tag). A variant is never equal to a value of
+/// any other type.
+#[export(include)]
+#[export(name "variant")]
+#[derive(Clone)]
+pub(crate) struct Variant {
+    enumeration: CompactString,
+    name: CompactString,
+    tag: usize,
+}
+
+impl PartialEq for Variant {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag && self.enumeration == other.enumeration
+    }
+}
+
+impl Eq for Variant {}
+
+impl Hash for Variant {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.enumeration.hash(state);
+        self.tag.hash(state);
+    }
+}
+
+impl Display for Variant {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(&self.name)
+    }
+}
+
+impl Debug for Variant {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_fmt(format_args!("{}.{}", self.enumeration, self.name))
+    }
+}
+
+#[export(include)]
+impl Variant {
+    /// Returns the index of the variant in its enum declaration, starting
+    /// from zero: `Color.Blue.tag() == 2`.
+    pub fn tag(&self) -> usize {
+        self.tag
+    }
+}
+
+impl Variant {
+    #[inline(always)]
+    pub(crate) fn new(enumeration: &str, name: &str, tag: usize) -> Self {
+        Self {
+            enumeration: CompactString::from(enumeration),
+            name: CompactString::from(name),
+            tag,
+        }
+    }
+}
+
+#[export(include)]
+impl ScriptDisplay for Variant {}
+
+#[export(include)]
+impl ScriptDebug for Variant {}
+
+#[export(include)]
+impl ScriptHash for Variant {}
+
+#[export(include)]
+impl ScriptPartialEq for Variant {
+    type RHS = Variant;
+
+    fn script_eq(_origin: Origin, mut lhs: Arg, mut rhs: Arg) -> RuntimeResult<bool> {
+        if !rhs.data.is::<Variant>() {
+            return Ok(false);
+        }
+
+        let lhs = lhs.data.borrow_ref::<Variant>(lhs.origin)?;
+        let rhs = rhs.data.borrow_ref::<Variant>(rhs.origin)?;
+
+        Ok(lhs == rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::{symbols::tests::TestPackage, IssueCode};
+
+    fn eval(text: &str) -> String {
+        crate::eval!(TestPackage, text).stringify(false)
+    }
+
+    #[test]
+    fn test_script_enum() {
+        assert_eq!(
+            eval("enum Color { Red, Green, Blue } return Color.Green;"),
+            "Green"
+        );
+        assert_eq!(
+            eval("enum Color { Red, Green, Blue } return Color.Blue.tag();"),
+            "2"
+        );
+        assert_eq!(
+            eval("enum Color { Red, Green } return Color.Red == Color.Red;"),
+            "true"
+        );
+        assert_eq!(
+            eval("enum Color { Red, Green } return Color.Red == Color.Green;"),
+            "false"
+        );
+        assert_eq!(
+            eval("enum Color { Red, Green } return Color.Red == 0;"),
+            "false"
+        );
+
+        assert_eq!(
+            eval(
+                "enum Color { Red, Green, Blue } let c = Color.Blue; let x; \
+                match c { Color.Red => x = 1, Color.Green => x = 2, Color.Blue => x = 3 } \
+                return x;"
+            ),
+            "3",
+        );
+
+        assert_eq!(
+            eval(
+                "let Color = struct { Red: 1, Green: 2 }; let c = 2; let x; \
+                match c { Color.Red => x = 1, Color.Green => x = 2 } \
+                return x;"
+            ),
+            "2",
+        );
+
+        crate::assert_script_ok!(
+            TestPackage,
+            "enum Color { Red, Green } let c = Color.Red; let x; \
+            match c { Color.Red => x = 1, Color.Green => x = 2 } x;",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "enum Color { Red, Green, Blue } let c = Color.Red; \
+            match c { Color.Red => {}, Color.Green => {} }",
+            IssueCode::NonExhaustiveMatch,
+            "c",
+        );
+
+        crate::assert_script_err!(TestPackage, "let enum = 5;", IssueCode::Parse);
+    }
+}
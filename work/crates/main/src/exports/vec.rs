@@ -0,0 +1,324 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    exports::{array_hint, bytes_mismatch, is_bytes_of, Bytes},
+    runtime::{Arg, Cell, Downcast, Origin, Provider, RuntimeResult, ScriptType, TypeHint, Upcast},
+};
+
+impl<'a, T> Downcast<'a> for Vec<T>
+where
+    T: ScriptType,
+{
+    #[inline]
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let cell = provider.to_owned();
+
+        if is_bytes_of::<T>(&cell) {
+            return cell
+                .take::<Bytes>(origin)?
+                .into_vec::<T>()
+                .ok_or_else(|| bytes_mismatch::<T>(origin));
+        }
+
+        cell.take_vec::<T>(origin)
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        array_hint::<T>()
+    }
+}
+
+impl<'a, T> Upcast<'a> for Vec<T>
+where
+    T: ScriptType,
+{
+    type Output = Self;
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(this)
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        array_hint::<T>()
+    }
+}
+
+impl<'a, T> Upcast<'a> for &'a Vec<T>
+where
+    T: ScriptType,
+{
+    type Output = &'a [T];
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(this.deref())
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        array_hint::<T>()
+    }
+}
+
+impl<'a, T> Upcast<'a> for &'a mut Vec<T>
+where
+    T: ScriptType,
+{
+    type Output = &'a mut [T];
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(this.deref_mut())
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        array_hint::<T>()
+    }
+}
+
+impl<'a, T> Downcast<'a> for Vec<Option<T>>
+where
+    T: Downcast<'static>,
+{
+    #[inline(always)]
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        downcast_items(origin, provider.to_owned())
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        <T as Downcast<'static>>::hint()
+    }
+}
+
+impl<'a, T> Upcast<'a> for Vec<Option<T>>
+where
+    T: Upcast<'static>,
+{
+    type Output = Cell;
+
+    #[inline(always)]
+    fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        upcast_items(origin, this)
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        <T as Upcast<'static>>::hint()
+    }
+}
+
+impl<'a> Downcast<'a> for Vec<String> {
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let cell = provider.to_owned();
+
+        // The script strings are arrays of bytes. A single string is a single
+        // item.
+        if cell.is::<str>() {
+            return Ok(Vec::from([cell.take_string(origin)?]));
+        }
+
+        downcast_items(origin, cell)
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(<str>::type_meta())
+    }
+}
+
+impl<'a> Upcast<'a> for Vec<String> {
+    type Output = Cell;
+
+    #[inline(always)]
+    fn upcast(origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        upcast_items(origin, this)
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(<str>::type_meta())
+    }
+}
+
+// Downcasts each element of the Cell's array separately. The Nil Cell is an
+// empty vector.
+fn downcast_items<T: Downcast<'static>>(origin: Origin, cell: Cell) -> RuntimeResult<Vec<T>> {
+    if cell.is_nil() {
+        return Ok(Vec::new());
+    }
+
+    let length = cell.length();
+    let mut result = Vec::with_capacity(length);
+
+    for index in 0..length {
+        let item = cell.clone().map_slice(origin, index..=index)?;
+
+        result.push(<T as Downcast<'static>>::downcast(
+            origin,
+            Provider::Owned(item),
+        )?);
+    }
+
+    Ok(result)
+}
+
+// Upcasts each item into a separate Cell, and then joins these Cells the same
+// way as the script array constructor does: Nil items are skipped, and the
+// first non-nil item's type concatenates the rest.
+fn upcast_items<T: Upcast<'static>>(origin: Origin, items: Vec<T>) -> RuntimeResult<Cell> {
+    let mut receiver = None;
+    let mut arguments = Vec::with_capacity(items.len());
+
+    for item in items {
+        let data = Cell::give(origin, item)?;
+
+        if receiver.is_none() && !data.is_nil() {
+            receiver = Some(data.ty());
+        }
+
+        arguments.push(Arg { origin, data });
+    }
+
+    let Some(receiver) = receiver else {
+        return Ok(Cell::nil());
+    };
+
+    receiver.concat(origin, &mut arguments)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        analysis::symbols::tests::TestPackage,
+        export,
+        exports::Bytes,
+        runtime::{ScriptType, TypeHint, Upcast},
+    };
+
+    #[export]
+    pub fn test_vec_names() -> Vec<String> {
+        Vec::from([String::from("foo"), String::from("bar")])
+    }
+
+    #[export]
+    pub fn test_vec_sum(items: Vec<f64>) -> f64 {
+        items.into_iter().sum()
+    }
+
+    #[export]
+    pub fn test_vec_options() -> Vec<Option<u32>> {
+        Vec::from([Some(1), None, Some(3)])
+    }
+
+    #[export]
+    pub fn test_vec_options_count(items: Vec<Option<u32>>) -> usize {
+        items.into_iter().flatten().count()
+    }
+
+    #[export]
+    pub fn test_vec_strings_count(items: Vec<String>) -> usize {
+        items.len()
+    }
+
+    #[test]
+    fn test_vec_casting() {
+        assert_eq!(
+            crate::eval!(TestPackage, "return test_vec_names();").stringify(false),
+            "foobar",
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, f64, "return test_vec_sum([1.5, 2.5, 3]);"),
+            7.0,
+        );
+
+        assert_eq!(
+            crate::eval!(TestPackage, "return test_vec_options();").stringify(false),
+            "[1, 3]",
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "return test_vec_options_count(test_vec_options());"
+            ),
+            2,
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "return test_vec_strings_count([1, 2, 3]);"
+            ),
+            3,
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "return test_vec_strings_count(\"foo\");"
+            ),
+            1,
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_vec_strings_count(nil);"),
+            0,
+        );
+    }
+
+    #[test]
+    fn test_vec_hints() {
+        assert_eq!(
+            <&mut Vec<u8> as Upcast>::hint(),
+            TypeHint::Type(Bytes::type_meta()),
+        );
+
+        assert_eq!(
+            <&mut Vec<f64> as Upcast>::hint(),
+            <Vec<f64> as Upcast>::hint(),
+        );
+    }
+}
@@ -32,9 +32,11 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
+use std::fmt::{Display, Formatter};
+
 use lady_deirdre::{
     format::{PrettyPrintConfig, PrettyPrinter},
-    lexis::{Line, SourceCode, TokenBuffer, TokenRule},
+    lexis::{Line, SiteSpan, SourceCode, TokenBuffer, TokenRule},
     syntax::{AbstractNode, ParseNode, ParseNodeChild, ParseToken, ParseTree, PolyRef, SyntaxTree},
 };
 
@@ -152,8 +154,46 @@ impl ScriptFormatConfig {
 /// function, this function does not require creating a dedicated
 /// [ScriptModule](crate::analysis::ScriptModule) when you need to format
 /// arbitrary Ad Astra text one time.
+///
+/// ## Protected Regions
+///
+/// The formatter emits the statements between the `// fmt: off` and
+/// `// fmt: on` comments as they are, byte-identically, except for the
+/// indentation of the first protected line, which the formatter adjusts to the
+/// surrounding code. This is useful for manually aligned code, such as
+/// matrices or tables.
+///
+/// ```text
+/// // fmt: off
+/// let matrix = [
+///     1, 0, 0,
+///     0, 1, 0,
+///     0, 0, 1,
+/// ];
+/// // fmt: on
+/// ```
+///
+/// The formatter recognizes these directives between the statements of the
+/// same code block. If the block does not have a closing `// fmt: on`
+/// directive, the protected region continues to the end of the block.
+///
+/// The function ignores misplaced directives. To get the warnings about the
+/// nested or unbalanced directives, use the
+/// [format_script_text_with_warnings] function instead.
 #[inline(always)]
 pub fn format_script_text(config: ScriptFormatConfig, text: impl AsRef<str>) -> Option<String> {
+    format_script_text_with_warnings(config, text).map(|output| output.text)
+}
+
+/// Similar to the [format_script_text] function, but additionally returns the
+/// formatter's [warnings](FormatWarning).
+///
+/// The function returns None if the source code contains parse errors.
+#[inline(always)]
+pub fn format_script_text_with_warnings(
+    config: ScriptFormatConfig,
+    text: impl AsRef<str>,
+) -> Option<ScriptFormatOutput> {
     let buffer = TokenBuffer::from(text);
     let tree = ParseTree::new(&buffer, ..);
 
@@ -161,24 +201,100 @@ pub fn format_script_text(config: ScriptFormatConfig, text: impl AsRef<str>) ->
 }
 
 #[inline(always)]
-pub(crate) fn format_script_doc(config: ScriptFormatConfig, doc: &ScriptDoc) -> Option<String> {
+pub(crate) fn format_script_doc(
+    config: ScriptFormatConfig,
+    doc: &ScriptDoc,
+) -> Option<ScriptFormatOutput> {
     let tree = ParseTree::new(doc, ..);
 
     ScriptFormatter::format(config, &tree)
 }
 
+/// The result of the
+/// [format_script_text_with_warnings](format_script_text_with_warnings)
+/// function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ScriptFormatOutput {
+    /// The formatted source code text.
+    pub text: String,
+
+    /// The issues the formatter encountered in the source code.
+    ///
+    /// The warnings do not prevent formatting: the formatter resolves each
+    /// issue as described in the [FormatWarning] variants' documentation.
+    pub warnings: Vec<FormatWarning>,
+}
+
+/// An issue with the formatter
+/// [directives](format_script_text#protected-regions) in the source code.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FormatWarning {
+    /// The `// fmt: off` directive appears within a region already protected
+    /// by another `// fmt: off` directive.
+    ///
+    /// The formatter ignores the nested directive.
+    NestedOff {
+        /// The source code range of the directive comment.
+        span: SiteSpan,
+    },
+
+    /// The `// fmt: on` directive does not close any `// fmt: off` directive
+    /// of the same code block.
+    ///
+    /// The formatter ignores this directive.
+    UnmatchedOn {
+        /// The source code range of the directive comment.
+        span: SiteSpan,
+    },
+
+    /// The `// fmt: off` directive is not closed by a `// fmt: on` directive
+    /// within the same code block.
+    ///
+    /// The formatter protects the rest of the block.
+    UnclosedOff {
+        /// The source code range of the directive comment.
+        span: SiteSpan,
+    },
+}
+
+impl Display for FormatWarning {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NestedOff { .. } => formatter.write_str("nested \"fmt: off\" directive"),
+            Self::UnmatchedOn { .. } => formatter.write_str("unmatched \"fmt: on\" directive"),
+            Self::UnclosedOff { .. } => formatter.write_str("unclosed \"fmt: off\" directive"),
+        }
+    }
+}
+
+impl FormatWarning {
+    /// Returns the source code range of the directive comment that caused
+    /// the warning.
+    #[inline(always)]
+    pub fn span(&self) -> &SiteSpan {
+        match self {
+            Self::NestedOff { span } | Self::UnmatchedOn { span } | Self::UnclosedOff { span } => {
+                span
+            }
+        }
+    }
+}
+
 struct ScriptFormatter<'a, C: SourceCode<Token = ScriptToken>> {
     config: ScriptFormatConfig,
     tree: &'a ParseTree<'a, ScriptNode, C>,
     printer: PrettyPrinter,
     state: State,
+    warnings: Vec<FormatWarning>,
 }
 
 impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
     fn format(
         config: ScriptFormatConfig,
         tree: &'a ParseTree<'a, ScriptNode, C>,
-    ) -> Option<String> {
+    ) -> Option<ScriptFormatOutput> {
         if tree.errors().next().is_some() {
             return None;
         }
@@ -197,6 +313,7 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
             tree,
             printer,
             state: State::Break1,
+            warnings: Vec::new(),
         };
 
         formatter.format_node(tree.parse_tree_root());
@@ -206,7 +323,10 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
             _ => formatter.printer.hardbreak(),
         }
 
-        Some(formatter.printer.finish())
+        Some(ScriptFormatOutput {
+            text: formatter.printer.finish(),
+            warnings: formatter.warnings,
+        })
     }
 
     fn format_node(&mut self, parse_node: &ParseNode) {
@@ -230,7 +350,7 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
             ScriptNode::Package { .. } => self.format_concat(parse_node),
             ScriptNode::If { .. } => self.format_concat(parse_node),
             ScriptNode::Match { .. } => self.format_concat(parse_node),
-            ScriptNode::MatchBody { .. } => self.format_list(parse_node),
+            ScriptNode::MatchBody { .. } => self.format_list(&parse_node.children),
             ScriptNode::MatchArm { .. } => {
                 let _ = self.format_match_arm(parse_node);
             }
@@ -246,13 +366,17 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
             ScriptNode::Continue { .. } => self.format_concat(parse_node),
             ScriptNode::Return { .. } => self.format_concat(parse_node),
             ScriptNode::Fn { .. } => self.format_fn(parse_node),
-            ScriptNode::FnParams { .. } => self.format_list(parse_node),
+            ScriptNode::FnParams { .. } => self.format_list(&parse_node.children),
             ScriptNode::Struct { .. } => self.format_concat(parse_node),
-            ScriptNode::StructBody { .. } => self.format_list(parse_node),
+            ScriptNode::StructBody { .. } => self.format_list(&parse_node.children),
             ScriptNode::StructEntry { .. } => self.format_concat(parse_node),
             ScriptNode::StructEntryKey { .. } => self.format_concat(parse_node),
-            ScriptNode::Array { .. } => self.format_list(parse_node),
+            ScriptNode::Enum { .. } => self.format_concat(parse_node),
+            ScriptNode::EnumBody { .. } => self.format_list(&parse_node.children),
+            ScriptNode::EnumVariant { .. } => self.format_concat(parse_node),
+            ScriptNode::Array { .. } => self.format_list(&parse_node.children),
             ScriptNode::String { .. } => self.print_node_as_is(parse_node),
+            ScriptNode::Embedded { .. } => self.print_node_verbatim(parse_node),
             ScriptNode::Crate { .. } => self.format_concat(parse_node),
             ScriptNode::This { .. } => self.format_concat(parse_node),
             ScriptNode::Ident { .. } => self.format_concat(parse_node),
@@ -264,9 +388,10 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
             ScriptNode::Op { .. } => self.format_concat(parse_node),
             ScriptNode::Query { .. } => self.format_expr(parse_node),
             ScriptNode::Call { .. } => self.format_expr(parse_node),
-            ScriptNode::CallArgs { .. } => self.format_list(parse_node),
+            ScriptNode::CallArgs { .. } => self.format_call_args(parse_node),
+            ScriptNode::Arg { .. } => self.format_concat(parse_node),
             ScriptNode::Index { .. } => self.format_expr(parse_node),
-            ScriptNode::IndexArg { .. } => self.format_list(parse_node),
+            ScriptNode::IndexArg { .. } => self.format_list(&parse_node.children),
             ScriptNode::Field { .. } => self.format_concat(parse_node),
             ScriptNode::Expr { .. } => self.format_expr(parse_node),
         }
@@ -539,7 +664,8 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
                     if let ParseNodeChild::Node(child) = child {
                         if let ScriptNode::INLINE_COMMENT
                         | ScriptNode::MULTILINE_COMMENT
-                        | ScriptNode::LET = child.rule
+                        | ScriptNode::LET
+                        | ScriptNode::ENUM = child.rule
                         {
                             unwrap = BlockUnwrap::AsIs;
                             break;
@@ -591,8 +717,14 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
 
         let mut inner_empty = true;
         let mut st_printed = false;
+        let mut protected_end = 0;
+        let mut protected_close = None;
 
         for (index, child) in parse_node.children.iter().enumerate() {
+            if index < protected_end {
+                continue;
+            }
+
             match child {
                 ParseNodeChild::Blank(child) => {
                     if !self.config.preserve_blank_lines {
@@ -664,6 +796,27 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
                     ScriptNode::INLINE_COMMENT => {
                         self.format_node(child);
                         inner_empty = false;
+
+                        match self.directive(child) {
+                            Some(Directive::Off) => {
+                                (protected_end, protected_close) =
+                                    self.format_protected(parse_node, index);
+
+                                if protected_end > index + 1 {
+                                    st_printed = true;
+                                }
+                            }
+
+                            Some(Directive::On) => {
+                                if protected_close != Some(index) {
+                                    self.warnings.push(FormatWarning::UnmatchedOn {
+                                        span: child.site_span.clone(),
+                                    });
+                                }
+                            }
+
+                            None => (),
+                        }
                     }
 
                     ScriptNode::MULTILINE_COMMENT => {
@@ -700,7 +853,171 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
         unwrap
     }
 
-    fn format_list(&mut self, parse_node: &ParseNode) {
+    // Prints the statements of the block `parse_node` that follow the
+    // `// fmt: off` directive at the `off` child index as is, up to the closing
+    // `// fmt: on` directive, or up to the end of the block.
+    //
+    // Returns the index of the first child after the protected region, and
+    // the index of the closing directive, if any.
+    fn format_protected(&mut self, parse_node: &ParseNode, off: usize) -> (usize, Option<usize>) {
+        let mut first = None;
+        let mut last = None;
+        let mut close = None;
+
+        for (index, child) in parse_node.children.iter().enumerate().skip(off + 1) {
+            match child {
+                ParseNodeChild::Blank(_) => continue,
+
+                ParseNodeChild::Token(token) => {
+                    if let Some((_, OpenOrClose::Close)) = Group::from_token_rule(token.rule) {
+                        break;
+                    }
+                }
+
+                ParseNodeChild::Node(node) => match self.directive(node) {
+                    Some(Directive::On) => {
+                        close = Some(index);
+                        break;
+                    }
+
+                    Some(Directive::Off) => self.warnings.push(FormatWarning::NestedOff {
+                        span: node.site_span.clone(),
+                    }),
+
+                    None => self.scan_protected(node),
+                },
+            }
+
+            let _ = first.get_or_insert(child);
+            last = Some((index, child));
+        }
+
+        if close.is_none() {
+            if let Some(ParseNodeChild::Node(node)) = parse_node.children.get(off) {
+                self.warnings.push(FormatWarning::UnclosedOff {
+                    span: node.site_span.clone(),
+                });
+            }
+        }
+
+        let (Some(first), Some((last_index, last))) = (first, last) else {
+            return (off + 1, close);
+        };
+
+        if self.config.preserve_blank_lines {
+            if let Some(ParseNodeChild::Blank(blank)) = parse_node.children.get(off + 1) {
+                self.format_break(blank.breaks(), false);
+            }
+        }
+
+        let start = child_span(first).start;
+        let mut end = child_span(last).end;
+
+        let ends_with_comment = match last {
+            ParseNodeChild::Node(node) => {
+                node.rule == ScriptNode::INLINE_COMMENT && node.breaks() > 0
+            }
+            _ => false,
+        };
+
+        if ends_with_comment {
+            end -= 1;
+        }
+
+        let text = self.tree.substring(start..end).into_owned();
+
+        match &self.state {
+            State::Break1 | State::PendingBreak2 => self.flush(first.start_line()),
+            _ => self.format_break(1, true),
+        }
+
+        self.print_word(&text, first.start_line(), true, false, false);
+
+        match ends_with_comment {
+            true => {
+                self.printer.hardbreak();
+                self.state = State::Break1;
+            }
+
+            false => {
+                if let State::Word { line, .. } = &mut self.state {
+                    *line = last.end_line();
+                }
+            }
+        }
+
+        (last_index + 1, close)
+    }
+
+    // Reports the formatter directives nested in the protected region.
+    fn scan_protected(&mut self, parse_node: &ParseNode) {
+        for child in &parse_node.children {
+            let ParseNodeChild::Node(child) = child else {
+                continue;
+            };
+
+            match self.directive(child) {
+                Some(Directive::Off) => self.warnings.push(FormatWarning::NestedOff {
+                    span: child.site_span.clone(),
+                }),
+
+                Some(Directive::On) => self.warnings.push(FormatWarning::UnmatchedOn {
+                    span: child.site_span.clone(),
+                }),
+
+                None => self.scan_protected(child),
+            }
+        }
+    }
+
+    fn directive(&self, parse_node: &ParseNode) -> Option<Directive> {
+        if parse_node.rule != ScriptNode::INLINE_COMMENT {
+            return None;
+        }
+
+        let text = self.tree.substring(&parse_node.site_span);
+
+        match text.strip_prefix("//")?.trim() {
+            "fmt: off" => Some(Directive::Off),
+            "fmt: on" => Some(Directive::On),
+            _ => None,
+        }
+    }
+
+    // Formats the parenthesized argument list as a regular list, and then
+    // the trailing closure right after the closing parenthesis, such that the
+    // closure's block is aligned with the call.
+    fn format_call_args(&mut self, parse_node: &ParseNode) {
+        let split = parse_node
+            .children
+            .iter()
+            .rposition(|child| match child {
+                ParseNodeChild::Token(child) => child.rule == ScriptToken::ParenClose as u8,
+                _ => false,
+            })
+            .map(|index| index + 1)
+            .unwrap_or(parse_node.children.len());
+
+        let (list, closure) = parse_node.children.split_at(split);
+
+        self.format_list(list);
+
+        for child in closure {
+            match child {
+                ParseNodeChild::Blank(_) => (),
+
+                ParseNodeChild::Token(child) => {
+                    self.format_token(child, true, false);
+                }
+
+                ParseNodeChild::Node(child) => {
+                    self.format_node(child);
+                }
+            }
+        }
+    }
+
+    fn format_list(&mut self, children: &[ParseNodeChild]) {
         enum ListState {
             Begin,
             Next,
@@ -714,7 +1031,7 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
         let mut last_item = 0;
         let mut consistent = false;
 
-        for (index, child) in parse_node.children.iter().enumerate() {
+        for (index, child) in children.iter().enumerate() {
             match child {
                 ParseNodeChild::Blank(_) => (),
 
@@ -748,7 +1065,7 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
 
         let mut state = ListState::Begin;
 
-        'outer: for (index, child) in parse_node.children.iter().enumerate() {
+        'outer: for (index, child) in children.iter().enumerate() {
             match child {
                 ParseNodeChild::Blank(child) => {
                     if !self.config.preserve_blank_lines {
@@ -765,7 +1082,7 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
                         ListState::ItemPrinted { .. } => {
                             let mut lookahead = index + 1;
 
-                            while let Some(next) = parse_node.children.get(lookahead) {
+                            while let Some(next) = children.get(lookahead) {
                                 match next {
                                     ParseNodeChild::Blank(..) => (),
                                     ParseNodeChild::Token(..) => continue 'outer,
@@ -785,7 +1102,7 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
                         ListState::Next => (),
                     }
 
-                    let next = parse_node.children.get(index + 1);
+                    let next = children.get(index + 1);
 
                     if let Some(ParseNodeChild::Token(token)) = next {
                         if let Some((_, open_or_close)) = Group::from_token_rule(token.rule) {
@@ -1259,7 +1576,7 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
     }
 
     fn format_token(&mut self, parse_token: &ParseToken, concat: bool, concat_next: bool) {
-        let Some(text) = parse_token.token_ref.string(self.tree) else {
+        let Some(text) = ScriptToken::canonical_string(&parse_token.token_ref, self.tree) else {
             return;
         };
 
@@ -1296,6 +1613,18 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
         }
     }
 
+    // Unlike print_node_as_is, this function does not realign the inner lines
+    // of the node, keeping the node's text byte-identical.
+    fn print_node_verbatim(&mut self, parse_node: &ParseNode) {
+        let text = self.tree.substring(&parse_node.site_span).into_owned();
+
+        self.print_word(&text, parse_node.start_line(), true, false, false);
+
+        if let State::Word { line, .. } = &mut self.state {
+            *line = parse_node.end_line();
+        }
+    }
+
     fn print_sep(&mut self, sep: &'static str, line: Line, is_last: bool) {
         self.state = State::PendingSep {
             sep,
@@ -1620,6 +1949,7 @@ impl<'a, C: SourceCode<Token = ScriptToken>> ScriptFormatter<'a, C> {
             }
 
             ScriptNode::STRING
+            | ScriptNode::EMBEDDED
             | ScriptNode::CRATE
             | ScriptNode::THIS
             | ScriptNode::IDENT
@@ -1939,6 +2269,11 @@ enum OpenOrClose {
     Close,
 }
 
+enum Directive {
+    Off,
+    On,
+}
+
 enum BlockUnwrap {
     AsIs,
     UnwrapOuter,
@@ -2018,6 +2353,15 @@ impl<'a> FlatChild<'a> {
     }
 }
 
+#[inline(always)]
+fn child_span(child: &ParseNodeChild) -> &SiteSpan {
+    match child {
+        ParseNodeChild::Blank(child) => &child.site_span,
+        ParseNodeChild::Token(child) => &child.site_span,
+        ParseNodeChild::Node(child) => &child.site_span,
+    }
+}
+
 fn is_simple_expr(parse_node: &ParseNode) -> bool {
     for child in &parse_node.children {
         let ParseNodeChild::Node(child) = &child else {
@@ -2065,3 +2409,124 @@ fn dedent_line(string: &str, mut alignment: usize) -> &str {
 
     &string[index..]
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::format::{format_script_text_with_warnings, FormatWarning, ScriptFormatConfig};
+
+    fn format(text: &str) -> (String, Vec<FormatWarning>) {
+        let output = format_script_text_with_warnings(ScriptFormatConfig::new(), text).unwrap();
+
+        (output.text, output.warnings)
+    }
+
+    #[test]
+    fn test_format_protected_matrix() {
+        static MATRIX: &str = r#"let before = 1;
+
+// fmt: off
+let matrix = [
+    1,   0,   0,
+    0,   1,   0,
+    0,   0,   1,
+];
+let table = [["a",  1],
+             ["bb", 22]];
+// fmt: on
+
+let after = 2;
+"#;
+
+        let (text, warnings) = format(MATRIX);
+
+        assert_eq!(text, MATRIX);
+        assert!(warnings.is_empty(), "{warnings:?}");
+
+        let (text, _) = format(&text);
+
+        assert_eq!(text, MATRIX);
+    }
+
+    #[test]
+    fn test_format_protected_fixtures() {
+        static INPUT: &str = include_str!("fixtures/matrix.input.adastra");
+        static EXPECTED: &str = include_str!("fixtures/matrix.adastra");
+
+        let (text, warnings) = format(INPUT);
+
+        assert_eq!(text, EXPECTED);
+        assert!(warnings.is_empty(), "{warnings:?}");
+
+        let (text, warnings) = format(EXPECTED);
+
+        assert_eq!(text, EXPECTED);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn test_format_named_arguments() {
+        let (text, warnings) = format("foo(1,y :2,  z:3);\nbar(x:1) fn(a) {\nreturn a;\n};\n");
+
+        assert_eq!(
+            text,
+            "foo(1, y: 2, z: 3);\nbar(x: 1) fn(a) {\n    return a;\n};\n",
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn test_format_protected_context() {
+        let (text, warnings) = format(
+            "let foo = fn() {\n  let   a=1;\n// fmt: off\n      let m = [1,  0,\n               0,  1];\n  // fmt: on\n  let   b=2;\n};\n",
+        );
+
+        assert_eq!(
+            text,
+            "let foo = fn() {\n    let a = 1;\n    // fmt: off\n    let m = [1,  0,\n               0,  1];\n    // fmt: on\n    let b = 2;\n};\n",
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_format_directive_warnings() {
+        let (text, warnings) = format("let   a=1;\n// fmt: on\nlet   b=2;\n");
+
+        assert_eq!(text, "let a = 1;\n// fmt: on\nlet b = 2;\n");
+        assert!(matches!(
+            warnings.as_slice(),
+            [FormatWarning::UnmatchedOn { .. }]
+        ));
+
+        let (text, warnings) = format(
+            "{\n    // fmt: off\n    let   a=1;\n    // fmt: off\n    let   b=2;\n}\nlet   c=3;\n",
+        );
+
+        assert_eq!(
+            text,
+            "{\n    // fmt: off\n    let   a=1;\n    // fmt: off\n    let   b=2;\n}\nlet c = 3;\n",
+        );
+        assert!(matches!(
+            warnings.as_slice(),
+            [
+                FormatWarning::NestedOff { .. },
+                FormatWarning::UnclosedOff { .. },
+            ],
+        ));
+        assert_eq!(warnings[0].span().start, 37);
+    }
+    #[test]
+    fn test_format_trailing_closure() {
+        let (text, warnings) =
+            format("each(list,  2)   fn(x) {\nfoo(x);\n};\nmap(list)fn(x)x * 2;\n");
+
+        assert_eq!(
+            text,
+            "each(list, 2) fn(x) {\n    foo(x);\n};\nmap(list) fn(x) x * 2;\n",
+        );
+        assert!(warnings.is_empty());
+
+        let (text, _) = format("each(list) // comment\nfn(x) {\nfoo(x);\n};\n");
+
+        assert_eq!(text, "each(list) // comment\nfn(x) {\n    foo(x);\n};\n");
+    }
+}
@@ -58,6 +58,12 @@ impl Highlighter<ScriptToken> for ScriptHighlighter {
 
                     DoubleQuote => Class::String,
 
+                    Fence => {
+                        self.mode = Mode::Embedded;
+
+                        return Class::String.style(dim);
+                    }
+
                     _ => Class::Other,
                 };
 
@@ -80,6 +86,14 @@ impl Highlighter<ScriptToken> for ScriptHighlighter {
                 Class::String.style(dim)
             }
 
+            Mode::Embedded => {
+                if let Fence = token {
+                    self.mode = Mode::Normal;
+                }
+
+                Class::String.style(dim)
+            }
+
             Mode::Inline => {
                 match token {
                     Linebreak => self.mode = Mode::Normal,
@@ -120,6 +134,7 @@ impl ScriptHighlighter {
 enum Mode {
     Normal,
     String,
+    Embedded,
     Inline,
     Multiline(usize),
 }
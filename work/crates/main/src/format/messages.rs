@@ -0,0 +1,702 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter},
+    sync::{Arc, RwLock},
+};
+
+use lady_deirdre::sync::Lazy;
+
+static LOCALIZER: Lazy<RwLock<Option<Arc<dyn MessageLocalizer>>>> = Lazy::new(|| RwLock::new(None));
+
+/// A provider of the translated templates of the user-facing messages.
+///
+/// The Ad Astra analyzer and the script runtime produce human-readable texts
+/// for the diagnostics issues and the runtime errors: the issue titles and
+/// messages, the [RuntimeError](crate::runtime::RuntimeError) descriptions and
+/// summaries, and the captions of the diagnostics snippets. Each such text
+/// corresponds to a [MessageKey] with a stable string identifier and a
+/// built-in English template.
+///
+/// By implementing this trait and installing the implementation through the
+/// [set_message_localizer] function, you can replace these templates with
+/// translated ones.
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use ad_astra::format::{MessageKey, MessageLocalizer};
+///
+/// struct Spanish;
+///
+/// impl MessageLocalizer for Spanish {
+///     fn template(&self, key: MessageKey) -> Option<Cow<'static, str>> {
+///         match key.id() {
+///             "runtime.nil" => Some(Cow::Borrowed("datos inaccesibles")),
+///             "analysis.type_mismatch" => Some(Cow::Borrowed(
+///                 "se esperaba el tipo '{expected}', pero se encontró '{provided}'",
+///             )),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+///
+/// The templates refer to the message arguments using the `{name}`
+/// placeholders. The set of placeholders of each message is the same as in the
+/// message's [default template](MessageKey::template). The placeholders
+/// without a corresponding argument are printed as they are.
+///
+/// Localization affects the human-readable texts only. The
+/// [IssueCode](crate::analysis::IssueCode) numbers, the issue severities, and
+/// the other machine-readable data remain the same regardless of the
+/// installed localizer.
+pub trait MessageLocalizer: Send + Sync + 'static {
+    /// Returns a translated template of the message with the specified `key`.
+    ///
+    /// If the function returns None, the message uses its default English
+    /// template.
+    fn template(&self, key: MessageKey) -> Option<Cow<'static, str>>;
+}
+
+/// Installs the process-wide [MessageLocalizer] of the user-facing messages,
+/// replacing the previously installed localizer.
+///
+/// The localizer affects the messages produced after the installation on any
+/// thread, including the threads of the
+/// [language server](crate::server::LspServer).
+///
+/// To restore the default English messages, use the
+/// [remove_message_localizer] function.
+pub fn set_message_localizer(localizer: impl MessageLocalizer) {
+    let localizer = Arc::new(localizer) as Arc<dyn MessageLocalizer>;
+
+    let previous = match LOCALIZER.write() {
+        Ok(mut guard) => guard.replace(localizer),
+        Err(poison) => poison.into_inner().replace(localizer),
+    };
+
+    drop(previous);
+}
+
+/// Removes the [MessageLocalizer] previously installed by the
+/// [set_message_localizer] function.
+///
+/// If a localizer has not been installed, this function does nothing.
+pub fn remove_message_localizer() {
+    let previous = match LOCALIZER.write() {
+        Ok(mut guard) => guard.take(),
+        Err(poison) => poison.into_inner().take(),
+    };
+
+    drop(previous);
+}
+
+macro_rules! catalog {
+    ($($key:ident => $id:literal, $template:literal;)*) => {
+        /// A key of a user-facing message in the Ad Astra message catalog.
+        ///
+        /// Each key has a stable string [identifier](Self::id) and a default
+        /// English [template](Self::template). See [MessageLocalizer] for
+        /// details.
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        #[non_exhaustive]
+        pub enum MessageKey {
+            $(
+            #[doc = concat!(
+                "The `", $id, "` message.\n\nDefault template:\n\n```text\n",
+                $template,
+                "\n```",
+            )]
+            $key,
+            )*
+        }
+
+        impl MessageKey {
+            /// The list of all keys of the message catalog.
+            pub const ALL: &'static [Self] = &[$(Self::$key,)*];
+
+            /// Returns the stable string identifier of the message, such as
+            /// `"analysis.type_mismatch"`.
+            ///
+            /// The identifiers do not change between the Ad Astra versions,
+            /// and you can use them as the keys of the translation files.
+            #[inline(always)]
+            pub const fn id(self) -> &'static str {
+                match self {
+                    $(Self::$key => $id,)*
+                }
+            }
+
+            /// Returns the default English template of the message.
+            #[inline(always)]
+            pub const fn template(self) -> &'static str {
+                match self {
+                    $(Self::$key => $template,)*
+                }
+            }
+        }
+    };
+}
+
+catalog! {
+    IssueParse => "issue.parse", "Parse error.";
+    IssueUnresolvedPackage => "issue.unresolved_package", "Unresolved import.";
+    IssueNotAPackage => "issue.not_a_package", "Importing a component that is not a package.";
+    IssueOrphanedBreak => "issue.orphaned_break", "Break outside of a loop.";
+    IssueDuplicateParam => "issue.duplicate_param", "Duplicate function parameter name.";
+    IssueReadUninit => "issue.read_uninit", "Use of possibly uninitialized variable.";
+    IssueUnresolvedIdent => "issue.unresolved_ident", "Unresolved reference.";
+    IssueIntParse => "issue.int_parse", "Invalid integer literal.";
+    IssueFloatParse => "issue.float_parse", "Invalid float literal.";
+    IssueUnreachableStatement => "issue.unreachable_statement", "Unreachable statement.";
+    IssueUnreachableArm => "issue.unreachable_arm", "Unreachable match arm.";
+    IssueDuplicateEntry => "issue.duplicate_entry", "Duplicate struct entry.";
+    IssueLiteralAssignment => "issue.literal_assignment", "Assignment to literal is meaningless.";
+    IssueEmbeddedError => "issue.embedded_error", "Embedded block error.";
+    IssueEmbeddedWarning => "issue.embedded_warning", "Embedded block warning.";
+    IssueLint => "issue.lint", "Lint diagnostic.";
+    IssueInvalidMetadata => "issue.invalid_metadata", "Invalid metadata directive.";
+    IssueMissingPackage => "issue.missing_package", "Required package is not available.";
+    IssueIncompatibleEngine => "issue.incompatible_engine", "Incompatible engine version.";
+    IssueUnknownArgument => "issue.unknown_argument", "Unknown named argument.";
+    IssueDuplicateArgument => "issue.duplicate_argument", "Duplicate argument.";
+    IssueMisplacedArgument => "issue.misplaced_argument", "Positional argument after named arguments.";
+    IssueAmbiguousPrecedence => "issue.ambiguous_precedence", "Operator precedence might be unclear.";
+    IssueShadowedMember => "issue.shadowed_member", "Variable shadows a package member.";
+    IssueTypeMismatch => "issue.type_mismatch", "Type mismatch.";
+    IssueNilIndex => "issue.nil_index", "Index operator is not applicable to nil type.";
+    IssueIndexTypeMismatch => "issue.index_type_mismatch", "Index type must be an integer or an integer range.";
+    IssueUndefinedOperator => "issue.undefined_operator", "Unresolved operator.";
+    IssueUndefinedDisplay => "issue.undefined_display", "Type does not implement the Display operator.";
+    IssueCallArityMismatch => "issue.call_arity_mismatch", "Call arity mismatch.";
+    IssueFnArityMismatch => "issue.fn_arity_mismatch", "Function arity mismatch.";
+    IssueResultMismatch => "issue.result_mismatch", "Function result type mismatch.";
+    IssueUnknownComponent => "issue.unknown_component", "Unknown field.";
+    IssueInconsistentReturns => "issue.inconsistent_returns", "Missing trailing return statement.";
+    IssueUnusedFunction => "issue.unused_function", "Unused function.";
+    IssueUnreadEntry => "issue.unread_entry", "Struct entry is never read.";
+    IssueUnusedImport => "issue.unused_import", "Unused import.";
+    IssueTemporaryAssignment => "issue.temporary_assignment", "Assignment to a temporary value.";
+    IssueUnknownStructField => "issue.unknown_struct_field", "Unknown struct field.";
+    IssueMissingStructField => "issue.missing_struct_field", "Missing struct field.";
+    IssueIndexOutOfBounds => "issue.index_out_of_bounds", "Index out of bounds.";
+    IssueAnalysisLimit => "issue.analysis_limit", "Analysis limit reached.";
+    IssueLateCapture => "issue.late_capture", "Closure captures a variable before its initialization.";
+    IssueDeepLint => "issue.deep_lint", "Lint diagnostic.";
+    IssueContractViolation => "issue.contract_violation", "Function contract violation.";
+    IssueReadOnlyProperty => "issue.read_only_property", "Assignment to a read-only property.";
+    IssueDeprecatedMember => "issue.deprecated_member", "Use of a deprecated member.";
+    IssueNonExhaustiveMatch => "issue.non_exhaustive_match", "Non-exhaustive match over enum variants.";
+    SeverityError => "severity.error", "error";
+    SeverityWarning => "severity.warning", "warning";
+    SeverityHint => "severity.hint", "hint";
+    DiagnosticsCaption => "diagnostics.caption", "diagnostics";
+    DiagnosticsClean => "diagnostics.clean", "No issues detected.";
+    DiagnosticsErrors => "diagnostics.errors", "Errors: {count}";
+    DiagnosticsWarnings => "diagnostics.warnings", "Warnings: {count}";
+    DiagnosticsHints => "diagnostics.hints", "Hints: {count}";
+    DiagnosticsOmitted => "diagnostics.omitted", " (omitted).";
+    ParseError => "parse.error", "parse error";
+    ParseUnenclosedString => "parse.unenclosed_string", "unenclosed string literal";
+    ParseUnenclosedEmbedded => "parse.unenclosed_embedded", "unenclosed embedded block";
+    ParseUnenclosedComment => "parse.unenclosed_comment", "unenclosed comment";
+    ParseUnenclosedBlock => "parse.unenclosed_block", "unenclosed code block";
+    ParseUnexpectedOperator => "parse.unexpected_operator", "unexpected operator";
+    ParseMissingOperand => "parse.missing_operand", "missing operand";
+    ParseNestingLimit => "parse.nesting_limit", "expression is nested too deeply";
+    AnalysisUnresolvedImport => "analysis.unresolved_import", "unresolved import";
+    AnalysisUnresolvedImportFrom => "analysis.unresolved_import_from", "unresolved import from {base}";
+    AnalysisUnresolvedImportHint => "analysis.unresolved_import_hint", "unresolved import. did you mean {quickfix}?";
+    AnalysisUnresolvedImportFromHint => "analysis.unresolved_import_from_hint", "unresolved import from {base}. did you mean {quickfix}?";
+    AnalysisNotAPackage => "analysis.not_a_package", "type '{ty}' is not a package";
+    AnalysisOrphanedBreak => "analysis.orphaned_break", "break statement outside of a loop";
+    AnalysisOrphanedContinue => "analysis.orphaned_continue", "continue statement outside of a loop";
+    AnalysisDuplicateParam => "analysis.duplicate_param", "duplicate fn parameter name";
+    AnalysisReadUninit => "analysis.read_uninit", "use of possibly uninitialized variable";
+    AnalysisUnresolvedIdent => "analysis.unresolved_ident", "unresolved reference";
+    AnalysisUnresolvedIdentHint => "analysis.unresolved_ident_hint", "unresolved reference. did you mean \"{quickfix}\"?";
+    AnalysisUnresolvedIdentImportHint => "analysis.unresolved_ident_import_hint", "unresolved reference. did you mean \"{import}.{quickfix}\"?";
+    AnalysisIntParse => "analysis.int_parse", "invalid integer literal";
+    AnalysisFloatParse => "analysis.float_parse", "invalid float literal";
+    AnalysisUnreachableStatement => "analysis.unreachable_statement", "unreachable statement";
+    AnalysisUnreachableArm => "analysis.unreachable_arm", "unreachable match arm";
+    AnalysisDuplicateEntry => "analysis.duplicate_entry", "duplicate struct entry";
+    AnalysisLiteralAssignment => "analysis.literal_assignment", "assignment to literal is meaningless";
+    AnalysisTypeMismatch => "analysis.type_mismatch", "expected '{expected}' type, but '{provided}' found";
+    AnalysisNilIndex => "analysis.nil_index", "nil type cannot be indexed";
+    AnalysisIndexTypeMismatch => "analysis.index_type_mismatch", "expected '{numeric}' or '{range}' type, but '{provided}' found";
+    AnalysisUndefinedOperator => "analysis.undefined_operator", "'{receiver}' does not implement {op}";
+    AnalysisCallArityMismatchOne => "analysis.call_arity_mismatch_one", "expected 1 argument, but {provided} provided";
+    AnalysisCallArityMismatch => "analysis.call_arity_mismatch", "expected {expected} arguments, but {provided} provided";
+    AnalysisFnArityMismatch => "analysis.fn_arity_mismatch", "expected fn({expected}) function, but fn({provided}) provided";
+    AnalysisResultMismatch => "analysis.result_mismatch", "expected a function with '{expected}' return type, but the function returns '{provided}'";
+    AnalysisUnknownComponent => "analysis.unknown_component", "unknown '{receiver}' field";
+    AnalysisUnknownComponentHint => "analysis.unknown_component_hint", "unknown '{receiver}' field. did you mean {quickfix}?";
+    AnalysisInconsistentReturns => "analysis.inconsistent_returns", "missing trailing return expression";
+    AnalysisUnusedFunction => "analysis.unused_function", "function is never used";
+    AnalysisUnusedFunctionNamed => "analysis.unused_function_named", "function '{name}' is never used";
+    AnalysisUnreadEntry => "analysis.unread_entry", "struct entry is never read";
+    AnalysisUnreadEntryNamed => "analysis.unread_entry_named", "struct entry '{name}' is never read";
+    AnalysisUnusedImport => "analysis.unused_import", "unused import";
+    AnalysisRedundantImport => "analysis.redundant_import", "redundant import path component";
+    AnalysisRedundantImportNamed => "analysis.redundant_import_named", "redundant import path component '{name}'. the parent package exports the same items";
+    AnalysisTemporaryAssignment => "analysis.temporary_assignment", "'{receiver}' creates a new '{component}' value on each access. the assignment does not persist";
+    AnalysisReadOnlyProperty => "analysis.read_only_property", "'{receiver}' property '{component}' does not have a setter";
+    AnalysisUnknownStructField => "analysis.unknown_struct_field", "unknown '{schema}' field. expected fields: {expected}";
+    AnalysisUnknownStructFieldNamed => "analysis.unknown_struct_field_named", "unknown '{schema}' field '{name}'. expected fields: {expected}";
+    AnalysisMissingStructField => "analysis.missing_struct_field", "missing required '{schema}' field '{field}'";
+    AnalysisIndexOutOfBounds => "analysis.index_out_of_bounds", "index {index} is out of 0..{length} bounds";
+    AnalysisContractViolation => "analysis.contract_violation", "argument {argument} violates `{condition}`";
+    AnalysisExprDepthLimit => "analysis.expr_depth_limit", "analysis limit reached: expression is nested deeper than {max} levels. the type is not inferred";
+    AnalysisResolutionStepsLimit => "analysis.resolution_steps_limit", "analysis limit reached: type inference takes more than {max} steps. the type is not inferred";
+    AnalysisFanOutLimit => "analysis.fan_out_limit", "analysis limit reached: more than {max} items to analyze. the construct is not fully analyzed";
+    AnalysisLateCapture => "analysis.late_capture", "closure captures a variable before its initialization. the closure does not observe the assigned value";
+    AnalysisLateCaptureNamed => "analysis.late_capture_named", "closure captures '{name}' before its initialization. the closure does not observe the assigned value";
+    AnalysisMissingPackage => "analysis.missing_package", "package '{name}' is not available in this package composition";
+    AnalysisMissingPackageVersion => "analysis.missing_package_version", "package '{name}' version {found} does not satisfy '{version}'";
+    AnalysisIncompatibleEngine => "analysis.incompatible_engine", "the script requires engine version '{version}'. current engine version is {current}";
+    AnalysisUnknownArgument => "analysis.unknown_argument", "the function does not have parameter '{name}'";
+    AnalysisUnknownArgumentHint => "analysis.unknown_argument_hint", "the function does not have parameter '{name}'. did you mean {quickfix}?";
+    AnalysisDuplicateArgument => "analysis.duplicate_argument", "parameter '{name}' is provided more than once";
+    AnalysisDuplicateArgumentHint => "analysis.duplicate_argument_hint", "parameter '{name}' is provided more than once. did you mean {quickfix}?";
+    AnalysisDuplicateTrailingClosure => "analysis.duplicate_trailing_closure", "the trailing closure binds to the last parameter that is already provided";
+    AnalysisMisplacedArgument => "analysis.misplaced_argument", "positional argument follows named arguments";
+    AnalysisAmbiguousPrecedence => "analysis.ambiguous_precedence", "this operation is evaluated first. consider adding parentheses";
+    AnalysisShadowedMember => "analysis.shadowed_member", "'{component}' shadows the package member. the package member is inaccessible below this point";
+    AnalysisDeprecatedMember => "analysis.deprecated_member", "'{component}' is deprecated";
+    AnalysisDeprecatedMemberNote => "analysis.deprecated_member_note", "'{component}' is deprecated. {message}";
+    AnalysisNonExhaustiveMatch => "analysis.non_exhaustive_match", "the match arms do not cover the variants: {missing}";
+    AnalysisFirstDeclared => "analysis.first_declared", "first declared here";
+    AnalysisDeclared => "analysis.declared", "declared here";
+    AnalysisFirstDefined => "analysis.first_defined", "first defined here";
+    AnalysisInitialized => "analysis.initialized", "initialized here";
+    RuntimeErrorCaption => "runtime.caption", "runtime error";
+    RuntimeNil => "runtime.nil", "inaccessible data";
+    RuntimeNonSingleton => "runtime.non_singleton", "expected a single data instance, but the array with {actual} elements provided";
+    RuntimeShortSlice => "runtime.short_slice", "expected an array with at least {minimum} elements, but the array with {actual} elements provided";
+    RuntimeLengthMismatch => "runtime.length_mismatch", "expected an array with {expected} elements, but the array with {actual} elements provided";
+    RuntimeOutOfBounds => "runtime.out_of_bounds", "index {index} out of 0..{length} bounds";
+    RuntimeIndexOutOfBounds => "runtime.index_out_of_bounds", "index {index} out of 0..{length} bounds ({policy} indexing)";
+    RuntimeReadOnly => "runtime.read_only", "read-only data";
+    RuntimeWriteOnly => "runtime.write_only", "write-only data";
+    RuntimeReadToWrite => "runtime.read_to_write", "cannot access data for write while it is being read";
+    RuntimeWriteToRead => "runtime.write_to_read", "cannot access data for read while it is being written";
+    RuntimeWriteToWrite => "runtime.write_to_write", "cannot access data for write more than once";
+    RuntimeUtf8Decoding => "runtime.utf8_decoding", "invalid utf-8 encoding";
+    RuntimeBorrowLimit => "runtime.borrow_limit", "too many simultaneous data accesses";
+    RuntimeBorrowTimeout => "runtime.borrow_timeout", "{data_type} data access timed out after {deadline}";
+    RuntimeUnexpectedType => "runtime.unexpected_type", "unexpected '{data_type}' data type";
+    RuntimeTypeMismatch => "runtime.type_mismatch", "expected {expected}, but '{data_type}' data type provided";
+    RuntimeTypeAlternative => "runtime.type_alternative", ", or ";
+    RuntimeDowncastStatic => "runtime.downcast_static", "cannot get static reference to the data owned by the script engine";
+    RuntimeUpcastResult => "runtime.upcast_result", "the function returned explicit error";
+    RuntimeCastInfinite => "runtime.cast_infinite", "cannot cast infinity value of {from} type to {to}";
+    RuntimeCastNan => "runtime.cast_nan", "cannot cast NAN value of {from} type to {to}";
+    RuntimeCastBounds => "runtime.cast_bounds", "cannot cast {value}{from} to {to} type";
+    RuntimeAdd => "runtime.add", "cannot add {rhs} to {lhs}";
+    RuntimeSub => "runtime.sub", "cannot subtract {rhs} from {lhs}";
+    RuntimeMul => "runtime.mul", "cannot multiply {rhs} by {lhs}";
+    RuntimeDiv => "runtime.div", "cannot divide {rhs} by {lhs}";
+    RuntimeNeg => "runtime.neg", "cannot get negative number of {lhs}";
+    RuntimeShl => "runtime.shl", "cannot shift {lhs} left by {rhs} bits";
+    RuntimeShr => "runtime.shr", "cannot shift {lhs} right by {rhs} bits";
+    RuntimeRem => "runtime.rem", "cannot get {lhs} reminder of division by {lhs}";
+    RuntimeNumericOperation => "runtime.numeric_operation", "invalid numeric operation";
+    RuntimeRangeCast => "runtime.range_cast", "cannot cast {from} range to {to} type. target type bounds mismatch";
+    RuntimeMalformedRange => "runtime.malformed_range", "range {start} start bound is greater than the range end bound {end}";
+    RuntimePrimitiveParse => "runtime.primitive_parse", "failed to parse {from} as {to}";
+    RuntimeArityMismatchOne => "runtime.arity_mismatch_one", "the function requires 1 argument, but {arguments} provided";
+    RuntimeArityMismatch => "runtime.arity_mismatch", "the function requires {parameters} arguments, but {arguments} provided";
+    RuntimeMisplacedArgument => "runtime.misplaced_argument", "positional argument follows named arguments";
+    RuntimeDuplicateArgument => "runtime.duplicate_argument", "parameter '{name}' is provided more than once";
+    RuntimeUnknownArgument => "runtime.unknown_argument", "the function does not have parameter '{name}'";
+    RuntimeRequiresViolation => "runtime.requires_violation", "the function's precondition `{condition}` is violated";
+    RuntimeEnsuresViolation => "runtime.ensures_violation", "the function's postcondition `{condition}` is violated";
+    RuntimeUndefinedOperator => "runtime.undefined_operator", "type '{receiver}' does not implement {operator}";
+    RuntimeUnknownField => "runtime.unknown_field", "type '{receiver}' does not have field '{field}'";
+    RuntimeShadowedMember => "runtime.shadowed_member", "{cause} (variable '{name}' shadows the package member)";
+    RuntimeStructFields => "runtime.struct_fields", "struct does not match '{schema}'";
+    RuntimeMissingFields => "runtime.missing_fields", ". missing fields: {fields}";
+    RuntimeUnknownFields => "runtime.unknown_fields", ". unknown fields: {fields}";
+    RuntimeFormatError => "runtime.format_error", "an error occurred during Debug or Display format function call";
+    RuntimeUnknownPackage => "runtime.unknown_package", "unknown {name}@{version} script package";
+    RuntimeMissingAsyncRunner => "runtime.missing_async_runner", "{name}@{version} script package does not have an async runner";
+    RuntimeInterrupted => "runtime.interrupted", "script evaluation interrupted";
+    RuntimeStackOverflow => "runtime.stack_overflow", "script engine stack overflow";
+    RuntimeMemoryLimitExceeded => "runtime.memory_limit_exceeded", "script memory limit exceeded ({current} of {limit} bytes)";
+    RuntimeObserverReentrance => "runtime.observer_reentrance", "script evaluation inside a write observer";
+    RuntimeUninitializedStatic => "runtime.uninitialized_static", "static '{name}' is not initialized yet";
+    RuntimeFinalizerReentrance => "runtime.finalizer_reentrance", "script evaluation inside a finalizer";
+    RuntimeSharedRelease => "runtime.shared_release", "cannot release data shared with other references";
+    RuntimeAccessDenied => "runtime.access_denied", "{access} access to '{receiver}.{member}' denied: {reason}";
+    RuntimeTemporaryAssignment => "runtime.temporary_assignment", "'{receiver}' creates a new '{component}' value on each access. the assignment does not persist";
+    RuntimeCustom => "runtime.custom", "error {code}: {message}";
+    RuntimeDataOrigin => "runtime.data_origin", "data object origin";
+    RuntimeActiveRead => "runtime.active_read", "active read access";
+    RuntimeActiveWrite => "runtime.active_write", "active write access";
+    RuntimeConflictingAccess => "runtime.conflicting_access", "active conflicting access";
+    RuntimeFunctionOrigin => "runtime.function_origin", "function origin";
+    RuntimeReceiverOrigin => "runtime.receiver_origin", "receiver origin";
+    RuntimeReceiverObject => "runtime.receiver_object", "receiver object";
+    RuntimeShadowingDeclaration => "runtime.shadowing_declaration", "variable '{name}' declaration";
+    SummaryNil => "summary.nil", r#"The requested operation has been applied on the void data.
+
+The source of the void data could be:
+    - an empty array "[]",
+    - a struct field that does not exists in the struct,
+    - a function or operator that does not return any value,
+    - a function that returns "Option::None",
+    - or any other source.
+
+Use the ? operator to check if the value is void: "if a? {...}"."#;
+    SummaryNonSingleton => "summary.non_singleton", r#"Most script operations require singleton objects (just normal objects)
+and cannot be applied to arrays with zero or more than one element.
+
+Consider using the index operator to retrieve a single element from the array:
+    "my_array[3] + 10" instead of "my_array + 10"."#;
+    SummaryShortSlice => "summary.short_slice", r#"The underlying operation requires an array of longer length."#;
+    SummaryLengthMismatch => "summary.length_mismatch", r#"Assignments to arrays are performed element by element and cannot
+change the length of the target array.
+
+The assigned array must have the same length as the target: "my_array[1..3] = [x, y]"."#;
+    SummaryOutOfBounds => "summary.out_of_bounds", r#"The specified range or an index is out of the array bounds."#;
+    SummaryStrictIndex => "summary.strict_index", r#"The specified range or an index is out of the array bounds.
+
+The script engine uses the "strict" indexing policy: indices must be
+non-negative and less than the array length."#;
+    SummaryClampIndex => "summary.clamp_index", r#"The specified index cannot be clamped to the array bounds.
+
+The script engine uses the "clamp" indexing policy, but the array is empty."#;
+    SummaryWrapNegativeIndex => "summary.wrap_negative_index", r#"The specified range or an index is out of the array bounds.
+
+The script engine uses the "wrap-negative" indexing policy: negative indices
+count from the end of the array ("foo[-1]" is the last element), but their
+absolute value must not exceed the array length."#;
+    SummaryReadOnly => "summary.read_only", r#"The underlying operation requires write access to one of its arguments,
+but the argument reference provides read-only access."#;
+    SummaryWriteOnly => "summary.write_only", r#"The underlying operation requires read access to one of its arguments,
+but the argument reference provides write-only access."#;
+    SummaryReadToWrite => "summary.read_to_write", r#"The underlying operation requires write access to one of its arguments,
+but the argument object is currently being read.
+
+The script engine does not allow simultaneous read and write access
+to the same data.
+
+For instance, if the script calls a function (or an operator) with this object
+as an argument and the function returns a reference that indirectly points
+to the argument's data, the object is blocked for writing until the reference's
+lifetime ends."#;
+    SummaryWriteToRead => "summary.write_to_read", r#"The underlying operation requires read access to one of its arguments,
+but the argument object is currently being written.
+
+The script engine does not allow simultaneous read and write access
+to the same data.
+
+For instance, if the script calls a function (or an operator) with this object
+as an argument and the function returns a reference that indirectly modifies
+argument's data, the object is blocked for reading until the reference's
+lifetime ends."#;
+    SummaryWriteToWrite => "summary.write_to_write", r#"The underlying operation requires write access to one of its arguments,
+but the argument object is currently being written.
+
+The script engine mandates that the ongoing write access to the data object
+is exclusive.
+
+For instance, if the script calls a function (or an operator) with this object
+as an argument and the function returns a reference that indirectly modifies
+argument's data, the object is blocked from another write access until
+the reference's lifetime ends."#;
+    SummaryUtf8Decoding => "summary.utf8_decoding", r#"The underlying operation is attempting to reinterpret an array of bytes
+as a UTF-8 encoding of the Unicode text, but the script engine has detected
+that this encoding is invalid.
+
+Error description:{cause}"#;
+    SummaryBorrowLimit => "summary.borrow_limit", r#"The script engine has a predefined limit for active references
+to the same data object.
+
+This limit may be exceeded, for example, if a recursive function attempts
+to access the same object too many times."#;
+    SummaryBorrowTimeout => "summary.borrow_timeout", r#"The underlying operation requires access to one of its arguments,
+but the argument object has been held by a conflicting access for longer
+than the thread's borrow deadline permits.
+
+Usually, the conflicting access belongs to another thread that evaluates
+a script or a host function over the same data object. Make sure that
+the host does not keep the object borrowed for long, or increase
+the deadline using the set_borrow_deadline function."#;
+    SummaryTypeMismatch => "summary.type_mismatch", r#"The underlying function (or operator) requires an argument of a different type
+than the one being provided."#;
+    SummaryDowncastStatic => "summary.downcast_static", r#"The underlying function (or operator) requested a data object
+with a lifetime that may be different from the actual object's lifetime."#;
+    SummaryUpcastResult => "summary.upcast_result", r#"The invoked function (or operator) returned explicit error.
+
+Error description:{cause}"#;
+    SummaryNumberCast => "summary.number_cast", r#"Failed to cast primitive numeric type to another numeric type."#;
+    SummaryCastOverflow => "summary.cast_overflow", r#"Failed to cast primitive numeric type to another numeric type.
+
+The source value is bigger than the target type upper bound.
+"#;
+    SummaryCastUnderflow => "summary.cast_underflow", r#"Failed to cast primitive numeric type to another numeric type.
+
+The source value is lesser than the target type lower bound.
+"#;
+    SummaryNumericOperation => "summary.numeric_operation", r#"Failed to perform numeric operation between two primitive types
+
+The result overflows {target} bounds."#;
+    SummaryRangeCast => "summary.range_cast", r#"Failed to cast a range to another range type."#;
+    SummaryMalformedRange => "summary.malformed_range", r#"Malformed range bounds."#;
+    SummaryPrimitiveParse => "summary.primitive_parse", r#"String parse error:{cause}"#;
+    SummaryNotEnoughArguments => "summary.not_enough_arguments", r#"Not enough arguments."#;
+    SummaryTooManyArguments => "summary.too_many_arguments", r#"Too many arguments."#;
+    SummaryMisplacedArgument => "summary.misplaced_argument", r#"Positional arguments must precede the named arguments."#;
+    SummaryDuplicateArgument => "summary.duplicate_argument", r#"Each parameter must be provided by a single argument."#;
+    SummaryUnknownArgument => "summary.unknown_argument", r#"The argument name must match one of the function's parameter names."#;
+    SummaryRequiresViolation => "summary.requires_violation", r#"The argument does not satisfy the condition declared by the
+function's "requires" contract."#;
+    SummaryEnsuresViolation => "summary.ensures_violation", r#"The function's result does not satisfy the condition declared by the
+function's "ensures" contract."#;
+    SummaryUndefinedOperator => "summary.undefined_operator", r#"The object's type that is responsible to perform specified operation does not
+implement this operator."#;
+    SummaryUnknownField => "summary.unknown_field", r#"The object does not have specified field."#;
+    SummaryShadowedMember => "summary.shadowed_member", "{cause}
+
+The local variable '{name}' shadows the package member with the same name.
+The package member is inaccessible below the variable's declaration.";
+    SummaryStructFields => "summary.struct_fields", "The function expects a struct with the following fields:
+    {schema}";
+    SummaryOptionalFields => "summary.optional_fields", "
+
+The fields marked with \"?\" are optional.";
+    SummaryFormatError => "summary.format_error", r#"Failed to turn the object into string representation."#;
+    SummaryUnknownPackage => "summary.unknown_package", r#"Package lookup failure."#;
+    SummaryMissingAsyncRunner => "summary.missing_async_runner", r#"The function is an async Rust function,
+but the host did not install the executor hook that runs such functions."#;
+    SummaryInterrupted => "summary.interrupted", r#"The script explicitly terminated by the host request."#;
+    SummaryStackOverflow => "summary.stack_overflow", r#"The script engine failed to invoke the function,
+because the engine's thread stack exceeded its limit.
+                
+This situation may occur in functions with unlimited recursion."#;
+    SummaryMemoryLimitExceeded => "summary.memory_limit_exceeded", r#"The script allocated more data than the host allows for a single evaluation.
+
+This situation may occur in scripts that build unbounded strings or arrays."#;
+    SummaryObserverReentrance => "summary.observer_reentrance", r#"The host's write observer attempted to evaluate a script function.
+
+The write observers cannot evaluate scripts on the thread that notifies them."#;
+    SummaryUninitializedStatic => "summary.uninitialized_static", r#"The script attempted to read a lazily initialized static value
+before the host initialized it.
+
+The host usually initializes such values at startup. Make sure the script runs
+after the initialization."#;
+    SummaryFinalizerReentrance => "summary.finalizer_reentrance", r#"The host's finalizer attempted to evaluate a script function.
+
+The finalizers cannot evaluate scripts on the thread that drops the data."#;
+    SummarySharedRelease => "summary.shared_release", r#"The host attempted to release the data early, but other references
+still share this data.
+
+Release the other references first, or let the data drop when the last
+reference is released."#;
+    SummaryAccessDenied => "summary.access_denied", r#"The host's access guard denied the script's access to the object's member.
+
+The guard decides whether the script may read, write, or invoke the members
+of particular objects. Refer to the host's documentation for the access rules."#;
+    SummaryTemporaryAssignment => "summary.temporary_assignment", r#"The script assigns to a temporary value.
+
+The component creates a new value on each access, so the assignment would
+not affect the receiver object. Assign the value to a variable, mutate the
+variable, and pass it back to the object explicitly."#;
+    SummaryCustom => "summary.custom", r#"The function reported an error with a host-defined code.
+
+Refer to the documentation of the function for the meaning of this code."#;
+}
+
+impl Display for MessageKey {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(self.id())
+    }
+}
+
+impl MessageKey {
+    /// Looks up a message key by its stable string [identifier](Self::id).
+    ///
+    /// Returns None if the catalog does not have a message with the specified
+    /// identifier.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|key| key.id() == id)
+    }
+
+    // Renders the message using the installed localizer's template, or the
+    // default template, substituting the placeholders with the `args`.
+    pub(crate) fn render(self, args: &[(&str, &dyn Display)]) -> String {
+        let localizer = match LOCALIZER.read() {
+            Ok(guard) => guard.clone(),
+            Err(poison) => poison.into_inner().clone(),
+        };
+
+        let template = localizer
+            .and_then(|localizer| localizer.template(self))
+            .unwrap_or(Cow::Borrowed(self.template()));
+
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template.as_ref();
+
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let argument = rest.find('}').and_then(|end| {
+                let name = &rest[1..end];
+
+                args.iter()
+                    .find(|(probe, _)| *probe == name)
+                    .map(|(_, value)| (end, value))
+            });
+
+            match argument {
+                Some((end, value)) => {
+                    result.push_str(&value.to_string());
+                    rest = &rest[(end + 1)..];
+                }
+
+                None => {
+                    result.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+
+        result.push_str(rest);
+
+        result
+    }
+}
+
+// Renders a message of the catalog: `localize!(RuntimeNil)`, or
+// `localize!(RuntimeOutOfBounds, index = index, length = length)`.
+macro_rules! localize (
+    ($key:ident) => {
+        $crate::format::MessageKey::$key.render(&[])
+    };
+
+    ($key:ident, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::format::MessageKey::$key.render(&[
+            $((::std::stringify!($name), &$value as &dyn ::std::fmt::Display),)+
+        ])
+    };
+);
+
+pub(crate) use localize;
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        borrow::Cow,
+        thread::{current, ThreadId},
+    };
+
+    use ahash::AHashSet;
+
+    use crate::{
+        analysis::IssueCode,
+        format::{remove_message_localizer, set_message_localizer, MessageKey, MessageLocalizer},
+        runtime::{Origin, RuntimeError},
+    };
+
+    // Translates the messages produced on the test's thread only, such that
+    // the concurrently running tests observe the default messages.
+    struct TestLocalizer(ThreadId);
+
+    impl MessageLocalizer for TestLocalizer {
+        fn template(&self, key: MessageKey) -> Option<Cow<'static, str>> {
+            if current().id() != self.0 {
+                return None;
+            }
+
+            match key {
+                MessageKey::RuntimeCustom => Some(Cow::Borrowed("erreur {code} : {message}")),
+                MessageKey::IssueTypeMismatch => Some(Cow::Borrowed("Types incompatibles.")),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_message_catalog() {
+        let mut ids = AHashSet::new();
+
+        for key in MessageKey::ALL {
+            assert!(ids.insert(key.id()), "Duplicate key {key}.");
+            assert_eq!(MessageKey::from_id(key.id()), Some(*key));
+        }
+
+        assert_eq!(MessageKey::from_id("unknown.key"), None);
+
+        assert_eq!(
+            MessageKey::RuntimeOutOfBounds.render(&[("index", &5), ("length", &3)]),
+            "index 5 out of 0..3 bounds",
+        );
+
+        assert_eq!(
+            MessageKey::RuntimeOutOfBounds.render(&[("index", &5)]),
+            "index 5 out of 0..{length} bounds",
+        );
+
+        assert!(MessageKey::SummaryNil
+            .render(&[])
+            .contains("\"if a? {...}\""));
+    }
+
+    #[test]
+    fn test_message_localizer() {
+        let error = RuntimeError::custom(Origin::nil(), 404, "file not found", None);
+
+        set_message_localizer(TestLocalizer(current().id()));
+
+        assert_eq!(error.to_string(), "erreur 404 : file not found");
+        assert_eq!(IssueCode::TypeMismatch.to_string(), "Types incompatibles.");
+        assert_eq!(IssueCode::TypeMismatch as u16, 301);
+        assert_eq!(
+            IssueCode::NilIndex.to_string(),
+            "Index operator is not applicable to nil type."
+        );
+
+        remove_message_localizer();
+
+        assert_eq!(error.to_string(), "error 404: file not found");
+        assert_eq!(IssueCode::TypeMismatch.to_string(), "Type mismatch.");
+    }
+}
@@ -34,10 +34,22 @@
 
 mod formatter;
 mod highlight;
+mod messages;
 mod snippet;
 
-pub(crate) use crate::format::{formatter::format_script_doc, snippet::format_script_path};
+pub(crate) use crate::format::{
+    formatter::format_script_doc,
+    messages::localize,
+    snippet::format_script_path,
+};
 pub use crate::format::{
-    formatter::{format_script_text, ScriptFormatConfig},
+    formatter::{
+        format_script_text,
+        format_script_text_with_warnings,
+        FormatWarning,
+        ScriptFormatConfig,
+        ScriptFormatOutput,
+    },
+    messages::{remove_message_localizer, set_message_localizer, MessageKey, MessageLocalizer},
     snippet::{ScriptSnippet, ScriptSnippetConfig},
 };
@@ -42,7 +42,7 @@ use lady_deirdre::{
 
 use crate::{
     format::highlight::ScriptHighlighter,
-    runtime::PackageMeta,
+    runtime::{PackageMeta, ScriptProvenance},
     syntax::{ScriptDoc, ScriptToken},
 };
 
@@ -69,6 +69,10 @@ pub struct ScriptSnippetConfig {
     /// If set to true, the snippet caption will look like:
     /// `‹package name›.‹module name› [<custom caption>]`.
     ///
+    /// If the script module has a [provenance](crate::runtime::ScriptProvenance)
+    /// record, the path is followed by the asset's revision:
+    /// `‹package name›.‹module name› @ rev <revision>`.
+    ///
     /// Otherwise, the printer will only use the
     /// [custom caption](ScriptSnippet::set_caption) if specified.
     ///
@@ -197,6 +201,12 @@ impl<'a> Display for ScriptSnippet<'a> {
                         .as_str(),
                 );
 
+                if let Some(revision) =
+                    ScriptProvenance::of(id).and_then(|provenance| provenance.revision())
+                {
+                    caption.push_str(&format!(" @ rev {revision}"));
+                }
+
                 if self.caption.is_some() {
                     caption.push(']');
                 }
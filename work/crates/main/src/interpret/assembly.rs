@@ -40,7 +40,7 @@ use lady_deirdre::syntax::NodeRef;
 use crate::{
     interpret::{ScriptFn, StackDepth},
     report::system_panic,
-    runtime::{Origin, PackageMeta},
+    runtime::{Cell, Origin, PackageMeta},
     semantics::Float,
 };
 
@@ -48,6 +48,7 @@ pub(crate) type ClosureIndex = usize;
 pub(crate) type SubroutineIndex = usize;
 pub(crate) type StringIndex = usize;
 pub(crate) type OriginIndex = usize;
+pub(crate) type ConstIndex = usize;
 pub(crate) type CmdIndex = usize;
 
 pub(crate) const RET: CmdIndex = CmdIndex::MAX;
@@ -57,11 +58,22 @@ pub(crate) struct Assembly {
     pub(crate) arity: StackDepth,
     pub(crate) frame: StackDepth,
     pub(crate) closures: usize,
+    pub(crate) captures: Vec<(CompactString, ClosureIndex)>,
     pub(crate) subroutines: Subroutines,
     pub(crate) strings: Vec<CompactString>,
+    // The values precomputed by the compiler (e.g., the results of the pure
+    // function calls with constant arguments).
+    pub(crate) consts: Vec<ConstCell>,
     pub(crate) origins: Vec<Origin>,
     pub(crate) commands: Vec<Cmd>,
     pub(crate) sources: Vec<Source>,
+    // The commands that access a local variable shadowing a package member:
+    // (command, variable declaration origin, variable name).
+    pub(crate) shadows: Vec<(CmdIndex, OriginIndex, StringIndex)>,
+    // The commands that assign a new value to a local variable: (command,
+    // variable's stack depth, variable declaration origin, variable name,
+    // assignment origin).
+    pub(crate) var_writes: Vec<(CmdIndex, StackDepth, OriginIndex, StringIndex, OriginIndex)>,
 }
 
 impl Default for Assembly {
@@ -87,14 +99,18 @@ impl Assembly {
             arity,
             frame: arity,
             closures: closures + 1,
+            captures: Vec::new(),
             subroutines: match BUILDER {
                 true => Subroutines::Refs(Vec::with_capacity(subroutines)),
                 false => Subroutines::Len(subroutines),
             },
             strings: Vec::new(),
+            consts: Vec::new(),
             origins: vec![origin.into()],
             commands: Vec::new(),
             sources: Vec::new(),
+            shadows: Vec::new(),
+            var_writes: Vec::new(),
         }
     }
 
@@ -107,6 +123,32 @@ impl Assembly {
         *origin
     }
 
+    // Returns the declaration origin and the name of the local variable that
+    // the command accesses if the variable shadows a package member.
+    pub(super) fn cmd_shadow(&self, cmd: CmdIndex) -> Option<(Origin, &CompactString)> {
+        let (_, origin, name) = self.shadows.iter().find(|(index, ..)| *index == cmd)?;
+
+        Some((*self.origins.get(*origin)?, self.strings.get(*name)?))
+    }
+
+    // Returns the stack depth, the declaration origin, and the name of
+    // the local variable that the command assigns, and the origin of
+    // the assignment.
+    pub(super) fn cmd_var_write(
+        &self,
+        cmd: CmdIndex,
+    ) -> Option<(StackDepth, Origin, &CompactString, Origin)> {
+        let (_, depth, decl, name, origin) =
+            self.var_writes.iter().find(|(index, ..)| *index == cmd)?;
+
+        Some((
+            *depth,
+            *self.origins.get(*decl)?,
+            self.strings.get(*name)?,
+            *self.origins.get(*origin)?,
+        ))
+    }
+
     #[inline(always)]
     pub(super) fn cmd_1_source(&self, cmd: CmdIndex) -> Origin {
         let Some(Source { origins }) = self.sources.get(cmd) else {
@@ -217,6 +259,21 @@ impl Assembly {
             format_args!("closures: {}", self.closures),
         )?;
 
+        if !self.captures.is_empty() {
+            println(
+                formatter,
+                indent,
+                format_args!(
+                    "captures: {}",
+                    self.captures
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+            )?;
+        }
+
         if !self.commands.is_empty() {
             println(formatter, indent, format_args!("commands:"))?;
 
@@ -241,6 +298,22 @@ impl Assembly {
             indent -= 1;
         }
 
+        if !self.consts.is_empty() {
+            println(formatter, indent, format_args!("consts:"))?;
+
+            indent += 1;
+
+            for (index, ConstCell(cell)) in self.consts.iter().enumerate() {
+                println(
+                    formatter,
+                    indent,
+                    format_args!("const{index}: {}", cell.ty()),
+                )?;
+            }
+
+            indent -= 1;
+        }
+
         if !subroutines.is_empty() {
             println(formatter, indent, format_args!("fns:"))?;
 
@@ -284,18 +357,24 @@ pub(crate) enum Cmd {
     PushIsize(PushIsizeCmd),
     PushFloat(PushFloatCmd),
     PushString(PushStringCmd),
+    PushConst(PushConstCmd),
     PushPackage(PushPackageCmd),
     PushClosure(PushClosureCmd),
     PushFn(PushFnCmd),
     PushStruct(PushStructCmd),
+    PushVariant(PushVariantCmd),
     Range(RangeCmd),
     Bind(BindCmd),
     Concat(ConcatCmd),
+    Append(AppendCmd),
     Field(FieldCmd),
+    AssignField(AssignFieldCmd),
     Len(LenCmd),
     Query(QueryCmd),
     Op(OpCmd),
     Invoke(InvokeCmd),
+    TailInvoke(TailInvokeCmd),
+    NamedInvoke(NamedInvokeCmd),
     Index(IndexCmd),
 }
 
@@ -387,6 +466,12 @@ impl Cmd {
                 ),
             },
 
+            Self::PushConst(PushConstCmd { const_index }) => println(
+                formatter,
+                indent,
+                format_args!("{cmd}: push const{const_index}"),
+            ),
+
             Self::PushPackage(PushPackageCmd { package }) => {
                 println(formatter, indent, format_args!("{cmd}: push ‹{package}›"))
             }
@@ -403,6 +488,24 @@ impl Cmd {
 
             Self::PushStruct(..) => println(formatter, indent, format_args!("{cmd}: push struct")),
 
+            Self::PushVariant(PushVariantCmd {
+                enum_index,
+                name_index,
+                tag,
+            }) => match (strings.get(*enum_index), strings.get(*name_index)) {
+                (Some(enumeration), Some(name)) => println(
+                    formatter,
+                    indent,
+                    format_args!("{cmd}: push variant{tag}({enumeration}.{name})"),
+                ),
+
+                _ => println(
+                    formatter,
+                    indent,
+                    format_args!("{cmd}: push variant{tag}(?)"),
+                ),
+            },
+
             Self::Range(..) => println(formatter, indent, format_args!("{cmd}: range")),
 
             Self::Bind(BindCmd { index }) => {
@@ -413,19 +516,51 @@ impl Cmd {
                 println(formatter, indent, format_args!("{cmd}: concat {items}"))
             }
 
-            Self::Field(FieldCmd { field_index }) => match strings.get(*field_index) {
-                Some(string) => println(
-                    formatter,
-                    indent,
-                    format_args!("{cmd}: field string{field_index}({string:?})"),
-                ),
+            Self::Append(AppendCmd { depth, items }) => println(
+                formatter,
+                indent,
+                format_args!("{cmd}: append s{depth} {items}"),
+            ),
 
-                None => println(
-                    formatter,
-                    indent,
-                    format_args!("{cmd}: field string{field_index}(?)"),
-                ),
-            },
+            Self::Field(FieldCmd {
+                field_index,
+                target,
+            }) => {
+                let name = match target {
+                    true => "target-field",
+                    false => "field",
+                };
+
+                match strings.get(*field_index) {
+                    Some(string) => println(
+                        formatter,
+                        indent,
+                        format_args!("{cmd}: {name} string{field_index}({string:?})"),
+                    ),
+
+                    None => println(
+                        formatter,
+                        indent,
+                        format_args!("{cmd}: {name} string{field_index}(?)"),
+                    ),
+                }
+            }
+
+            Self::AssignField(AssignFieldCmd { field_index, op }) => {
+                match strings.get(*field_index) {
+                    Some(string) => println(
+                        formatter,
+                        indent,
+                        format_args!("{cmd}: assign-field {op} string{field_index}({string:?})"),
+                    ),
+
+                    None => println(
+                        formatter,
+                        indent,
+                        format_args!("{cmd}: assign-field {op} string{field_index}(?)"),
+                    ),
+                }
+            }
 
             Self::Len(..) => println(formatter, indent, format_args!("{cmd}: len")),
 
@@ -437,6 +572,29 @@ impl Cmd {
                 println(formatter, indent, format_args!("{cmd}: invoke {arity}"))
             }
 
+            Self::TailInvoke(TailInvokeCmd { arity }) => println(
+                formatter,
+                indent,
+                format_args!("{cmd}: tail-invoke {arity}"),
+            ),
+
+            Self::NamedInvoke(NamedInvokeCmd { names, .. }) => {
+                let names = names
+                    .iter()
+                    .map(|name| match name.and_then(|index| strings.get(index)) {
+                        Some(string) => string.as_str(),
+                        None => "_",
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                println(
+                    formatter,
+                    indent,
+                    format_args!("{cmd}: named-invoke ({names})"),
+                )
+            }
+
             Self::Index(..) => println(formatter, indent, format_args!("{cmd}: index")),
         }
     }
@@ -541,6 +699,32 @@ pub(crate) struct PushStringCmd {
     pub(crate) string_index: StringIndex,
 }
 
+// Stack: () -> (const)
+// Origins: (const)
+//
+// Pushes a copy of the precomputed value, so that the script cannot mutate
+// the value stored in the assembly.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct PushConstCmd {
+    pub(crate) const_index: ConstIndex,
+}
+
+// A value precomputed by the compiler.
+//
+// The Cell type does not implement equality, so the assembly's constants are
+// compared by their data object pointers.
+#[derive(Clone)]
+pub(crate) struct ConstCell(pub(crate) Cell);
+
+impl PartialEq for ConstCell {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+}
+
+impl Eq for ConstCell {}
+
 // Stack: () -> (package)
 // Origins: ()
 #[derive(Clone, PartialEq, Eq)]
@@ -567,6 +751,15 @@ pub(crate) struct PushFnCmd {
 #[derive(Clone, PartialEq, Eq)]
 pub(crate) struct PushStructCmd;
 
+// Stack: () -> (variant)
+// Origins: (variant)
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct PushVariantCmd {
+    pub(crate) enum_index: StringIndex,
+    pub(crate) name_index: StringIndex,
+    pub(crate) tag: usize,
+}
+
 // Stack: (lhs, rhs) -> (range)
 // Origins: (range, lhs, rhs)
 #[derive(Clone, PartialEq, Eq)]
@@ -586,11 +779,34 @@ pub(crate) struct ConcatCmd {
     pub(crate) items: usize,
 }
 
+// Stack: (items..) -> (nil) /* pops items, appends them to the depth */
+// Origins: (depth, items.., array, op)
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct AppendCmd {
+    pub(crate) depth: StackDepth,
+    pub(crate) items: usize,
+}
+
 // Stack: (lhs) -> (result)
 // Origins: (lhs, field)
+//
+// The target flag indicates that the field access is a part of the
+// assignment's left-hand side (`a.b` in `a.b.c = x`).
 #[derive(Clone, PartialEq, Eq)]
 pub(crate) struct FieldCmd {
     pub(crate) field_index: StringIndex,
+    pub(crate) target: bool,
+}
+
+// Stack: (rhs, lhs) -> ()
+// Origins: (op, rhs, target, lhs, field)
+//
+// Assigns (or updates with the assignment operator `op`) the field of
+// the lhs object. The target origin spans the entire `lhs.field` expression.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct AssignFieldCmd {
+    pub(crate) field_index: StringIndex,
+    pub(crate) op: OpCmd,
 }
 
 // Stack: (lhs) -> (result)
@@ -612,7 +828,7 @@ pub(crate) struct QueryCmd;
 // For unary:
 //     Stack: (rhs) -> (result)
 //     Origins: (op, rhs)
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum OpCmd {
     Clone,
     Neg,
@@ -694,6 +910,31 @@ pub(crate) struct InvokeCmd {
     pub(crate) arity: usize,
 }
 
+// Stack: (args.., lhs) -> (result) /* reduces args */
+// Origins: (args.., lhs, rhs)
+//
+// An invocation in the tail position of the function. If the lhs is the
+// currently executing function itself, the command restarts the current frame
+// with the new arguments instead of entering a new one.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct TailInvokeCmd {
+    pub(crate) arity: usize,
+}
+
+// Stack: (args.., lhs) -> (result) /* reduces args */
+// Origins: (args.., lhs, rhs)
+//
+// An invocation with named arguments: `foo(a, name: b)`. The `names` are the
+// string indices of the argument names in the invocation order, or None for
+// the positional arguments. If `closure` is true, the last argument is a
+// trailing closure. The command arranges the arguments according to the
+// invoked function's parameter names.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct NamedInvokeCmd {
+    pub(crate) names: Vec<Option<StringIndex>>,
+    pub(crate) closure: bool,
+}
+
 // Stack: (index, lhs) -> (result)
 // Origins: (index, lhs, rhs)
 #[derive(Clone, PartialEq, Eq)]
@@ -0,0 +1,259 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    future::{poll_fn, Future},
+    pin::pin,
+    task::Poll,
+};
+
+use crate::{
+    interpret::observer::{is_trusted, notify_step},
+    runtime::{Origin, PackageMeta, RuntimeError, RuntimeResult},
+};
+
+// Blocks the current thread until the `future` of the exported async function
+// (`#[export(block_on)]`) completes, using the async runner of the function's
+// package.
+//
+// The `name` and the `version` parameters specify the crate of the package.
+//
+// On each poll of the future, the function consults the thread's runtime hook
+// and the VM observers, such that the evaluation can be interrupted while
+// the runner waits for the future.
+pub fn block_on<F: Future>(
+    origin: Origin,
+    name: &'static str,
+    version: &'static str,
+    future: F,
+) -> RuntimeResult<F::Output> {
+    let Some(package) = PackageMeta::of(name, &format!("={version}")) else {
+        return Err(RuntimeError::UnknownPackage {
+            access_origin: origin,
+            name,
+            version,
+        });
+    };
+
+    let Some(runner) = package.async_runner() else {
+        return Err(RuntimeError::MissingAsyncRunner {
+            access_origin: origin,
+            name,
+            version,
+        });
+    };
+
+    let mut future = pin!(future);
+    let mut output = None;
+
+    {
+        let mut task = pin!(poll_fn(|context| {
+            let trusted = is_trusted();
+
+            if !trusted && !notify_step(&origin) {
+                return Poll::Ready(());
+            }
+
+            match future.as_mut().poll(context) {
+                Poll::Ready(value) => {
+                    output = Some(value);
+
+                    Poll::Ready(())
+                }
+
+                Poll::Pending => {
+                    // Reschedules the task, such that the hook would be
+                    // consulted again even if the future is idle.
+                    if !trusted {
+                        context.waker().wake_by_ref();
+                    }
+
+                    Poll::Pending
+                }
+            }
+        }));
+
+        runner(task.as_mut());
+    }
+
+    match output {
+        Some(output) => Ok(output),
+
+        // Either the evaluation has been interrupted, or the runner returned
+        // without completing the future.
+        None => Err(RuntimeError::Interrupted { origin }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell as StdCell,
+        future::{pending, Future},
+        pin::Pin,
+        rc::Rc,
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+        thread::{current, park, Thread},
+    };
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        interpret::{remove_runtime_hook, set_runtime_hook},
+        runtime::{Cell, Origin, RuntimeError, RuntimeResult, ScriptPackage},
+    };
+
+    // A future that returns Pending on the first poll.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+
+            self.0 = true;
+            context.waker().wake_by_ref();
+
+            Poll::Pending
+        }
+    }
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn run_to_completion(mut future: Pin<&mut (dyn Future<Output = ()> + '_)>) {
+        let waker = Waker::from(Arc::new(ThreadWaker(current())));
+        let mut context = Context::from_waker(&waker);
+
+        while future.as_mut().poll(&mut context).is_pending() {
+            park();
+        }
+    }
+
+    #[export(block_on)]
+    pub async fn test_load_asset(path: &str) -> usize {
+        YieldOnce(false).await;
+
+        path.len()
+    }
+
+    #[export(block_on)]
+    pub async fn test_broken_asset() -> RuntimeResult<usize> {
+        YieldOnce(false).await;
+
+        Err(RuntimeError::custom(
+            Origin::nil(),
+            42,
+            "broken asset",
+            None,
+        ))
+    }
+
+    #[export(block_on)]
+    pub async fn test_endless_asset() -> usize {
+        pending::<usize>().await
+    }
+
+    fn run(text: &str) -> RuntimeResult<Cell> {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        read.compile().unwrap().run()
+    }
+
+    fn custom_code(error: &RuntimeError) -> Option<u32> {
+        match error {
+            RuntimeError::UpcastResult { cause, .. } => {
+                cause.downcast_ref::<RuntimeError>().and_then(custom_code)
+            }
+            _ => error.custom_code(),
+        }
+    }
+
+    #[test]
+    fn test_block_on() {
+        let error = run(r#"return test_load_asset("foo");"#).unwrap_err();
+
+        assert!(matches!(error, RuntimeError::MissingAsyncRunner { .. }));
+        assert!(matches!(error.primary_origin(), Origin::Script(_)));
+
+        TestPackage::meta().set_async_runner(run_to_completion);
+
+        assert_eq!(
+            run(r#"return test_load_asset("foo");"#)
+                .unwrap()
+                .take::<usize>(Origin::nil())
+                .unwrap(),
+            3,
+        );
+
+        let error = run("return test_broken_asset();").unwrap_err();
+
+        assert_eq!(custom_code(&error), Some(42));
+        assert!(matches!(error.primary_origin(), Origin::Script(_)));
+
+        let steps = Rc::new(StdCell::new(0usize));
+
+        set_runtime_hook({
+            let steps = steps.clone();
+
+            move |_| {
+                steps.set(steps.get() + 1);
+
+                steps.get() < 100
+            }
+        });
+
+        let result = run("return test_endless_asset();");
+
+        remove_runtime_hook();
+
+        assert!(matches!(result, Err(RuntimeError::Interrupted { .. })));
+        assert_eq!(steps.get(), 100);
+
+        TestPackage::meta().remove_async_runner();
+    }
+}
@@ -36,7 +36,8 @@ use std::fmt::{Debug, Formatter};
 
 use ahash::RandomState;
 use lady_deirdre::{
-    analysis::{AnalysisResult, SemanticAccess, TaskHandle},
+    analysis::{AnalysisResult, Revision, SemanticAccess, TaskHandle},
+    arena::Id,
     sync::Shared,
     syntax::NodeRef,
 };
@@ -45,7 +46,7 @@ use crate::{
     analysis::ModuleResultEx,
     interpret::{ScriptFn, Subroutines},
     report::system_panic,
-    runtime::Cell,
+    runtime::{Cell, ScriptProvenance},
     syntax::{ScriptDoc, ScriptNode},
 };
 
@@ -92,6 +93,35 @@ impl ScriptFn {
             assembly,
             closures,
             subroutines: Shared::new(compiled_subroutines),
+            provenance: None,
+            revision: None,
         })
     }
+
+    pub(crate) fn attach_provenance(&mut self, provenance: &Shared<ScriptProvenance>) {
+        self.provenance = Some(provenance.clone());
+
+        if let Some(subroutines) = self.subroutines.get_mut() {
+            for subroutine in subroutines {
+                subroutine.attach_provenance(provenance);
+            }
+        }
+    }
+
+    // Returns the script module and its text revision from which this function
+    // was compiled.
+    #[inline(always)]
+    pub(crate) fn compiled_from(&self) -> Option<(Id, Revision)> {
+        self.revision
+    }
+
+    pub(crate) fn attach_revision(&mut self, id: Id, revision: Revision) {
+        self.revision = Some((id, revision));
+
+        if let Some(subroutines) = self.subroutines.get_mut() {
+            for subroutine in subroutines {
+                subroutine.attach_revision(id, revision);
+            }
+        }
+    }
 }
@@ -32,17 +32,42 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::{cell::UnsafeCell, cmp::Ordering, iter::repeat, ops::Range};
+use std::{
+    borrow::Cow,
+    cell::Cell as StdCell,
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    iter::{once, repeat},
+    ops::Range,
+    ptr,
+};
 
 use crate::{
-    exports::Struct,
+    exports::{append_items, Bytes, StringBuilder, Struct, Variant},
     interpret::{
+        env::ScriptEnv,
+        guards::{check_access, MemberAccess},
+        observer::{
+            notify_allocate,
+            notify_enter,
+            notify_exit,
+            notify_iterate,
+            notify_raise,
+            notify_step,
+            notify_write_var,
+            VarWrite,
+            VmCall,
+        },
         stack::Stack,
+        writes::{is_notifying, ObservedWrite},
+        AppendCmd,
         Assembly,
+        AssignFieldCmd,
         BindCmd,
         Cmd,
         CmdIndex,
         ConcatCmd,
+        ConstCell,
         DupCmd,
         FieldCmd,
         IfFalseCmd,
@@ -51,90 +76,158 @@ use crate::{
         IterateCmd,
         JumpCmd,
         LiftCmd,
+        NamedInvokeCmd,
         OpCmd,
         PushClosureCmd,
+        PushConstCmd,
         PushFloatCmd,
         PushFnCmd,
         PushIsizeCmd,
         PushPackageCmd,
         PushStringCmd,
         PushUsizeCmd,
+        PushVariantCmd,
         ScriptFn,
         ShrinkCmd,
         StackDepth,
         SwapCmd,
+        TailInvokeCmd,
+    },
+    runtime::{
+        arrange_args,
+        is_finalizing,
+        Arg,
+        ArgNameError,
+        Cell,
+        Downcast,
+        Ident,
+        Origin,
+        Provider,
+        RuntimeError,
+        RuntimeResult,
+        ScriptIterator,
     },
-    runtime::{Arg, Cell, Downcast, Origin, Provider, RuntimeError, RuntimeResult},
 };
 
 thread_local! {
-    static THREAD_HOOK: UnsafeCell<Option<Box<dyn Fn(&Origin) -> bool>>> = const {
-        UnsafeCell::new(None)
-    };
+    static INDEX_POLICY: StdCell<IndexPolicy> = const { StdCell::new(IndexPolicy::Strict) };
+    static STRICT_ASSIGNMENTS: StdCell<bool> = const { StdCell::new(false) };
 }
 
-/// Sets a script evaluation hook for the current OS thread.
-///
-/// The provided `hook` function will be called on each Ad Astra assembly
-/// instruction, allowing the hook function to interrupt the script's execution
-/// by returning `false`.
-///
-/// The hook function receives an [Origin] object as an argument, which roughly
-/// indicates the Script or Rust source code text range about to be evaluated.
+/// A policy of the built-in index operator (`foo[index]`) for out-of-range
+/// indices of arrays and strings.
 ///
-/// By default, the current OS thread does not have a configured runtime hook,
-/// meaning scripts will be evaluated until the end of the script's control
-/// flow. Setting up the runtime hook gives you control over script evaluation
-/// but generally slows down the script's computational performance.
-///
-/// For additional information, see the [ScriptFn] documentation.
-#[inline(always)]
-pub fn set_runtime_hook(hook: impl Fn(&Origin) -> bool + 'static) {
-    THREAD_HOOK.with(move |current| {
-        // Safety: Access is localized.
-        let current = unsafe { &mut *current.get() };
+/// The policy is configured per OS thread using the [set_index_policy]
+/// function. By default, the Script Engine uses the [strict](Self::Strict)
+/// policy.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum IndexPolicy {
+    /// The index must be non-negative and less than the array length.
+    /// Otherwise, the operator returns a [RuntimeError::IndexOutOfBounds]
+    /// error.
+    ///
+    /// A range index may exceed the array length at the end: `foo[1..100]`
+    /// returns all elements starting from the second one.
+    #[default]
+    Strict,
+
+    /// Out-of-range indices are clamped to the array bounds: `foo[100]`
+    /// returns the last element, and `foo[-1]` returns the first one. Range
+    /// indices are clamped at both ends.
+    ///
+    /// Indexing into an empty array is still an error.
+    Clamp,
+
+    /// Negative indices count from the end of the array: `foo[-1]` returns
+    /// the last element. Otherwise, this policy is similar to the
+    /// [strict](Self::Strict) policy.
+    WrapNegative,
+}
 
-        *current = Some(Box::new(hook));
-    })
+impl Display for IndexPolicy {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Strict => formatter.write_str("strict"),
+            Self::Clamp => formatter.write_str("clamp"),
+            Self::WrapNegative => formatter.write_str("wrap-negative"),
+        }
+    }
 }
 
-/// Unsets the script evaluation hook previously set by the [set_runtime_hook]
-/// function.
+/// Sets the [IndexPolicy] of the built-in index operator for the current OS
+/// thread.
 ///
-/// If a hook has not been configured for the current OS thread, this function
-/// does nothing.
+/// The policy affects all scripts evaluated on this thread until the policy
+/// is changed again.
 #[inline(always)]
-pub fn remove_runtime_hook() {
-    THREAD_HOOK.with(move |current| {
-        // Safety: Access is localized.
-        let current = unsafe { &mut *current.get() };
-
-        *current = None;
-    })
+pub fn set_index_policy(policy: IndexPolicy) {
+    INDEX_POLICY.with(|current| current.set(policy))
 }
 
+/// Returns the [IndexPolicy] of the built-in index operator configured for the
+/// current OS thread by the [set_index_policy] function.
 #[inline(always)]
-pub(super) fn is_trusted() -> bool {
-    THREAD_HOOK.with(move |current| {
-        // Safety: Access is localized.
-        let current = unsafe { &*current.get() };
+pub fn index_policy() -> IndexPolicy {
+    INDEX_POLICY.with(|current| current.get())
+}
 
-        current.is_none()
-    })
+/// Enables or disables the strict assignments mode for the current OS thread.
+///
+/// In the strict mode, the assignment operators (`a.b.c = x`, `a.b += x`)
+/// return a [RuntimeError::TemporaryAssignment] error instead of silently
+/// mutating a temporary value, when the left-hand side of the assignment is
+/// accessed through a component that creates a new value on each access
+/// (e.g., a method of the object, or a property getter on the intermediate
+/// path). The static analyzer reports the same cases as
+/// [TemporaryAssignment](crate::analysis::IssueCode::TemporaryAssignment)
+/// warnings.
+///
+/// By default, the strict mode is disabled.
+#[inline(always)]
+pub fn set_strict_assignments(strict: bool) {
+    STRICT_ASSIGNMENTS.with(|current| current.set(strict))
 }
 
+/// Returns true if the strict assignments mode is enabled for the current OS
+/// thread by the [set_strict_assignments] function.
 #[inline(always)]
-fn proceed(origin: &Origin) -> bool {
-    THREAD_HOOK.with(|hook| {
-        // Safety: Access is localized.
-        let hook = unsafe { &*hook.get() };
+pub fn strict_assignments() -> bool {
+    STRICT_ASSIGNMENTS.with(|current| current.get())
+}
 
-        let Some(hook) = hook else {
-            return true;
-        };
+// Returns an error if the strict assignments mode is enabled, and the `field`
+// component of the `receiver` does not persist the assignment to its value.
+//
+// The `direct` flag indicates that the component is the assignment target
+// itself rather than an intermediate component of the left-hand side. The
+// property setters persist the direct assignments.
+fn check_persistence(
+    receiver: &Cell,
+    field: &str,
+    direct: bool,
+    receiver_origin: Origin,
+    field_origin: Origin,
+) -> RuntimeResult<()> {
+    if !strict_assignments() {
+        return Ok(());
+    }
+
+    let receiver_type = receiver.ty();
+
+    let Some(component) = receiver_type.prototype().hint_component(field) else {
+        return Ok(());
+    };
+
+    if component.owned || (!direct && component.property) {
+        return Err(RuntimeError::TemporaryAssignment {
+            access_origin: field_origin,
+            receiver_origin,
+            receiver_type,
+            component: String::from(field),
+        });
+    }
 
-        hook(origin)
-    })
+    Ok(())
 }
 
 impl ScriptFn {
@@ -143,6 +236,14 @@ impl ScriptFn {
 
         let origin = assembly.decl_origin();
 
+        if is_notifying() {
+            return Err(RuntimeError::ObserverReentrance { origin });
+        }
+
+        if is_finalizing() {
+            return Err(RuntimeError::FinalizerReentrance { origin });
+        }
+
         let Some(frame_begin) = Stack::enter_frame(assembly.frame, assembly.arity) else {
             return Err(RuntimeError::StackOverflow { origin });
         };
@@ -150,14 +251,19 @@ impl ScriptFn {
         let mut engine = Engine::<'_, TRUSTED> {
             assembly,
             cmd_origin: origin,
-            closures: &self.closures,
+            closures: Cow::Borrowed(&self.closures),
             subroutines: self.subroutines.as_ref(),
             frame_begin,
             cmd_index: 0,
+            propagated: false,
         };
 
+        if !TRUSTED {
+            notify_enter(&VmCall::Script { origin });
+        }
+
         let result = loop {
-            if !TRUSTED && !proceed(&engine.cmd_origin) {
+            if !TRUSTED && !notify_step(&engine.cmd_origin) {
                 break Err(RuntimeError::Interrupted {
                     origin: engine.cmd_origin,
                 });
@@ -167,6 +273,11 @@ impl ScriptFn {
                 break Ok(());
             };
 
+            let var_write = match TRUSTED {
+                true => None,
+                false => engine.prepare_var_write(cmd),
+            };
+
             let result = match cmd {
                 Cmd::IfTrue(cmd) => engine.execute_if_true(cmd),
                 Cmd::IfFalse(cmd) => engine.execute_if_false(cmd),
@@ -183,14 +294,18 @@ impl ScriptFn {
                 Cmd::PushIsize(cmd) => engine.execute_push_isize(cmd),
                 Cmd::PushFloat(cmd) => engine.execute_push_float(cmd),
                 Cmd::PushString(cmd) => engine.execute_push_string(cmd),
+                Cmd::PushConst(cmd) => engine.execute_push_const(cmd),
                 Cmd::PushPackage(cmd) => engine.execute_push_package(cmd),
                 Cmd::PushClosure(cmd) => engine.execute_push_closure(cmd),
                 Cmd::PushFn(cmd) => engine.execute_push_fn(cmd),
                 Cmd::PushStruct(..) => engine.execute_push_struct(),
+                Cmd::PushVariant(cmd) => engine.execute_push_variant(cmd),
                 Cmd::Range(..) => engine.execute_range(),
                 Cmd::Bind(cmd) => engine.execute_bind(cmd),
                 Cmd::Concat(cmd) => engine.execute_concat(cmd),
+                Cmd::Append(cmd) => engine.execute_append(cmd),
                 Cmd::Field(cmd) => engine.execute_field(cmd),
+                Cmd::AssignField(cmd) => engine.execute_assign_field(cmd),
                 Cmd::Len(..) => engine.execute_len(),
                 Cmd::Query(..) => engine.execute_query(),
                 Cmd::Op(OpCmd::Clone) => engine.execute_op_clone(),
@@ -226,14 +341,34 @@ impl ScriptFn {
                 Cmd::Op(OpCmd::Shr) => engine.execute_op_shr(),
                 Cmd::Op(OpCmd::Rem) => engine.execute_op_rem(),
                 Cmd::Invoke(cmd) => engine.execute_invoke(cmd),
+                Cmd::TailInvoke(cmd) => engine.execute_tail_invoke(cmd),
+                Cmd::NamedInvoke(cmd) => engine.execute_named_invoke(cmd),
                 Cmd::Index(..) => engine.execute_index(),
             };
 
             if let Err(error) = result {
-                break Err(error);
+                break Err(engine.explain_shadowing(error));
+            }
+
+            if let Some(var_write) = var_write {
+                var_write.notify(frame_begin);
             }
         };
 
+        if !TRUSTED {
+            if let Err(error) = &result {
+                if !engine.propagated {
+                    notify_raise(error);
+                }
+            }
+
+            notify_exit(&VmCall::Script { origin });
+        }
+
+        // The frame's data is released before returning from the function,
+        // such that the values owned solely by the frame drop
+        // deterministically. The result value (if any) remains on top of
+        // the stack for the caller.
         match result {
             Ok(()) => {
                 Stack::leave_frame(frame_begin + 1);
@@ -241,7 +376,6 @@ impl ScriptFn {
 
             Err(..) => {
                 Stack::leave_frame(frame_begin);
-                Stack::push_nil();
             }
         }
 
@@ -252,13 +386,91 @@ impl ScriptFn {
 struct Engine<'a, const TRUSTED: bool> {
     assembly: &'a Assembly,
     cmd_origin: Origin,
-    closures: &'a [Cell],
+    closures: Cow<'a, [Cell]>,
     subroutines: &'a [ScriptFn],
     frame_begin: StackDepth,
     cmd_index: CmdIndex,
+    propagated: bool,
 }
 
 impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
+    // Prepares the notification of the observers if the command assigns
+    // a local variable.
+    //
+    // The Swap and Append commands replace or grow the Cell in the variable's
+    // stack slot, and the observers receive the slot's Cells before and after
+    // the command. The assignment operators write into the left-hand operand
+    // on top of the stack, which is the variable's Cell even if the variable
+    // has been lifted out of its slot by its last use. In this case, the old
+    // value is a copy of the operand's value, because the operator modifies
+    // the Cell's data in place.
+    fn prepare_var_write(&self, cmd: &Cmd) -> Option<PendingVarWrite<'a>> {
+        let (depth, decl, name, origin) = self.assembly.cmd_var_write(self.cmd_index)?;
+
+        let (target, old_value) = match cmd {
+            Cmd::Swap(..) => (None, Stack::peek(self.frame_begin, depth)),
+
+            Cmd::Append(..) => {
+                let current = Stack::peek(self.frame_begin, depth);
+
+                let old_value = match current.is::<str>() {
+                    true => current,
+                    false => current
+                        .into_object()
+                        .clone(origin, origin)
+                        .unwrap_or_default(),
+                };
+
+                (None, old_value)
+            }
+
+            _ => {
+                let target = Stack::peek_1(self.frame_begin);
+
+                let old_value = target
+                    .clone()
+                    .into_object()
+                    .clone(origin, origin)
+                    .unwrap_or_default();
+
+                (Some(target), old_value)
+            }
+        };
+
+        Some(PendingVarWrite {
+            depth,
+            decl,
+            name,
+            origin,
+            target,
+            old_value,
+        })
+    }
+
+    // Wraps the error of the command that accesses a local variable shadowing
+    // a package member, so that the error message points to the variable's
+    // declaration.
+    fn explain_shadowing(&self, error: RuntimeError) -> RuntimeError {
+        if self.propagated {
+            return error;
+        }
+
+        match &error {
+            RuntimeError::UndefinedOperator { .. } | RuntimeError::UnknownField { .. } => (),
+            _ => return error,
+        }
+
+        let Some((decl_origin, name)) = self.assembly.cmd_shadow(self.cmd_index) else {
+            return error;
+        };
+
+        RuntimeError::ShadowedMember {
+            decl_origin,
+            name: String::from(name.as_str()),
+            cause: Box::new(error),
+        }
+    }
+
     fn execute_if_true(&mut self, cmd: &IfTrueCmd) -> RuntimeResult<()> {
         let IfTrueCmd { otherwise } = cmd;
 
@@ -304,6 +516,14 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
     fn execute_jump(&mut self, cmd: &JumpCmd) -> RuntimeResult<()> {
         let JumpCmd { command } = cmd;
 
+        // The iterations of the "for" loops are reported by the Iterate
+        // command.
+        if !TRUSTED && *command < self.cmd_index {
+            if !matches!(self.assembly.commands.get(*command), Some(Cmd::Iterate(..))) {
+                notify_iterate(&self.cmd_origin);
+            }
+        }
+
         self.cmd_index = *command;
 
         Ok(())
@@ -320,14 +540,45 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
 
         let mut range_cell = self.peek_1();
 
-        let range = range_cell.borrow_mut::<Range<usize>>(range_origin)?;
+        let is_range = (range_cell.is::<Range<usize>>() || range_cell.is::<Range<isize>>())
+            && range_cell.length() == 1;
+
+        if !is_range && !range_cell.is::<ScriptIterator>() {
+            range_cell = ScriptIterator::of(range_origin, self.pop_1())?;
 
-        let Some(next) = range.next() else {
+            self.push(range_cell.clone());
+        }
+
+        let next = match range_cell.is::<ScriptIterator>() {
+            false if range_cell.is::<Range<isize>>() => {
+                let range = range_cell.borrow_mut::<Range<isize>>(range_origin)?;
+
+                range.next().map(|next| Cell::give(range_origin, next))
+            }
+
+            false => {
+                let range = range_cell.borrow_mut::<Range<usize>>(range_origin)?;
+
+                range.next().map(|next| Cell::give(range_origin, next))
+            }
+
+            true => {
+                let iterator = range_cell.borrow_mut::<ScriptIterator>(range_origin)?;
+
+                iterator.next(range_origin)
+            }
+        };
+
+        let Some(next) = next else {
             self.cmd_index = *finish;
             return Ok(());
         };
 
-        self.push(Cell::give(range_origin, next)?);
+        if !TRUSTED {
+            notify_iterate(&range_origin);
+        }
+
+        self.push(next?);
 
         self.cmd_index += 1;
 
@@ -468,6 +719,39 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
         Ok(())
     }
 
+    fn execute_push_const(&mut self, cmd: &PushConstCmd) -> RuntimeResult<()> {
+        let PushConstCmd { const_index } = cmd;
+
+        let const_origin = self.cmd_1_source();
+
+        if !TRUSTED {
+            self.cmd_origin = const_origin;
+        }
+
+        let Some(ConstCell(value)) = self.assembly.consts.get(*const_index) else {
+            self.push_nil();
+
+            self.cmd_index += 1;
+
+            return Ok(());
+        };
+
+        let const_cell = value
+            .clone()
+            .into_object()
+            .clone(const_origin, const_origin)?;
+
+        if !TRUSTED {
+            notify_allocate(&const_origin, &const_cell);
+        }
+
+        self.push(const_cell);
+
+        self.cmd_index += 1;
+
+        Ok(())
+    }
+
     fn execute_push_string(&mut self, cmd: &PushStringCmd) -> RuntimeResult<()> {
         let PushStringCmd { string_index } = cmd;
 
@@ -487,6 +771,10 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
 
         let const_cell = Cell::give(const_origin, value.clone().into_string())?;
 
+        if !TRUSTED {
+            notify_allocate(&const_origin, &const_cell);
+        }
+
         self.push(const_cell);
 
         self.cmd_index += 1;
@@ -543,6 +831,10 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
 
         let const_cell = Cell::give(const_origin, subroutine.clone())?;
 
+        if !TRUSTED {
+            notify_allocate(&const_origin, &const_cell);
+        }
+
         self.push(const_cell);
 
         self.cmd_index += 1;
@@ -559,6 +851,47 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
 
         let const_cell = Cell::give(const_origin, Struct::default())?;
 
+        if !TRUSTED {
+            notify_allocate(&const_origin, &const_cell);
+        }
+
+        self.push(const_cell);
+
+        self.cmd_index += 1;
+
+        Ok(())
+    }
+
+    fn execute_push_variant(&mut self, cmd: &PushVariantCmd) -> RuntimeResult<()> {
+        let PushVariantCmd {
+            enum_index,
+            name_index,
+            tag,
+        } = cmd;
+
+        let const_origin = self.cmd_1_source();
+
+        if !TRUSTED {
+            self.cmd_origin = const_origin;
+        }
+
+        let (Some(enumeration), Some(name)) = (
+            self.assembly.strings.get(*enum_index),
+            self.assembly.strings.get(*name_index),
+        ) else {
+            self.push_nil();
+
+            self.cmd_index += 1;
+
+            return Ok(());
+        };
+
+        let const_cell = Cell::give(const_origin, Variant::new(enumeration, name, *tag))?;
+
+        if !TRUSTED {
+            notify_allocate(&const_origin, &const_cell);
+        }
+
         self.push(const_cell);
 
         self.cmd_index += 1;
@@ -575,12 +908,33 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
 
         let (lhs_cell, rhs_cell) = self.pop_2();
 
-        let lhs = <usize as Downcast<'static>>::downcast(lhs_origin, Provider::Owned(lhs_cell))?;
-        let rhs = <usize as Downcast<'static>>::downcast(rhs_origin, Provider::Owned(rhs_cell))?;
+        let lhs =
+            <usize as Downcast<'static>>::downcast(lhs_origin, Provider::Owned(lhs_cell.clone()));
+
+        let rhs =
+            <usize as Downcast<'static>>::downcast(rhs_origin, Provider::Owned(rhs_cell.clone()));
+
+        let range_cell = match (lhs, rhs) {
+            (Ok(lhs), Ok(rhs)) => Cell::give(range_origin, lhs..rhs)?,
 
-        let range = lhs..rhs;
+            // Ranges with negative bounds (`-5..5`) cannot be represented by
+            // the unsigned range type, so they turn into signed ranges.
+            (lhs, rhs) => {
+                let signed_lhs =
+                    <isize as Downcast<'static>>::downcast(lhs_origin, Provider::Owned(lhs_cell))
+                        .map_err(|error| lhs.err().unwrap_or(error))?;
 
-        let range_cell = Cell::give(range_origin, range)?;
+                let signed_rhs =
+                    <isize as Downcast<'static>>::downcast(rhs_origin, Provider::Owned(rhs_cell))
+                        .map_err(|error| rhs.err().unwrap_or(error))?;
+
+                Cell::give(range_origin, signed_lhs..signed_rhs)?
+            }
+        };
+
+        if !TRUSTED {
+            notify_allocate(&range_origin, &range_cell);
+        }
 
         self.push(range_cell);
 
@@ -623,6 +977,20 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
 
         let item_cells = self.pop_many(*items);
 
+        let result = Self::concat(array_origin, origins, item_cells)?;
+
+        self.push(result);
+
+        self.cmd_index += 1;
+
+        Ok(())
+    }
+
+    fn concat(
+        array_origin: Origin,
+        origins: impl IntoIterator<Item = Origin>,
+        item_cells: Vec<Cell>,
+    ) -> RuntimeResult<Cell> {
         let mut item_args = Vec::with_capacity(item_cells.len());
 
         let mut receiver = None;
@@ -640,16 +1008,101 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
         }
 
         let Some(receiver) = receiver else {
-            self.push_nil();
+            return Ok(Cell::nil());
+        };
+
+        let result = receiver.concat(array_origin, &mut item_args)?;
+
+        if !TRUSTED {
+            notify_allocate(&array_origin, &result);
+        }
+
+        Ok(result)
+    }
+
+    // Assembled from the `s = [s, a, b]` pattern, where `s` is a local
+    // variable. If the variable is a string, rebinds the variable to the
+    // concatenation result, appending the items to the string in place when
+    // the variable's Cell is not referenced from anywhere else. Otherwise,
+    // falls back to the ordinary concatenation followed by the assignment.
+    fn execute_append(&mut self, cmd: &AppendCmd) -> RuntimeResult<()> {
+        let AppendCmd { depth, items } = cmd;
+
+        let mut origins = self.cmd_many_source();
+
+        let op_origin = origins.pop().unwrap_or_else(|| self.assembly.decl_origin());
+        let array_origin = origins.pop().unwrap_or(op_origin);
+
+        if !TRUSTED {
+            self.cmd_origin = op_origin;
+        }
+
+        let item_cells = self.pop_many(*items);
+
+        self.lift(*depth);
+
+        let lhs_cell = self.pop_1();
+
+        // The nil Cell that is left in place of the lifted variable becomes
+        // the result of the assignment expression.
+
+        if lhs_cell.is::<str>() {
+            let result = match lhs_cell.is_unique() {
+                true => {
+                    let mut origins = origins.into_iter();
+
+                    let lhs_origin = origins.next().unwrap_or(array_origin);
+
+                    let mut string = lhs_cell.take_string(lhs_origin)?;
+
+                    let mut item_args = origins
+                        .chain(repeat(Origin::nil()))
+                        .zip(item_cells.into_iter())
+                        .map(|(origin, data)| Arg { origin, data })
+                        .collect::<Vec<_>>();
+
+                    append_items(&mut string, &mut item_args)?;
+
+                    let result = Cell::give(array_origin, string)?;
+
+                    if !TRUSTED {
+                        notify_allocate(&array_origin, &result);
+                    }
+
+                    result
+                }
+
+                false => {
+                    let mut concat_cells = Vec::with_capacity(item_cells.len() + 1);
+
+                    concat_cells.push(lhs_cell);
+                    concat_cells.extend(item_cells);
+
+                    Self::concat(array_origin, origins, concat_cells)?
+                }
+            };
+
+            self.push(result);
+            self.swap(*depth);
 
             self.cmd_index += 1;
 
             return Ok(());
-        };
+        }
 
-        let result = receiver.concat(array_origin, &mut item_args)?;
+        self.push(lhs_cell.clone());
+        self.swap(*depth);
 
-        self.push(result);
+        let lhs_origin = origins.first().copied().unwrap_or(op_origin);
+
+        let mut concat_cells = Vec::with_capacity(item_cells.len() + 1);
+
+        concat_cells.push(lhs_cell.clone());
+        concat_cells.extend(item_cells);
+
+        let rhs_cell = Self::concat(array_origin, origins, concat_cells)?;
+
+        Self::assign(op_origin, lhs_origin, array_origin, lhs_cell, rhs_cell)?;
 
         self.cmd_index += 1;
 
@@ -657,7 +1110,10 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
     }
 
     fn execute_field(&mut self, cmd: &FieldCmd) -> RuntimeResult<()> {
-        let FieldCmd { field_index } = cmd;
+        let FieldCmd {
+            field_index,
+            target,
+        } = cmd;
 
         let (lhs_origin, field_origin) = self.cmd_2_source();
 
@@ -675,6 +1131,17 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
             return Ok(());
         };
 
+        let access = match lhs_cell.clone().into_object().is_method(field_string) {
+            true => MemberAccess::Invoke,
+            false => MemberAccess::Read,
+        };
+
+        check_access(&lhs_cell, field_string, access, field_origin)?;
+
+        if *target {
+            check_persistence(&lhs_cell, field_string, false, lhs_origin, field_origin)?;
+        }
+
         let result = lhs_cell.into_object().component_or_field(
             lhs_origin,
             lhs_origin,
@@ -688,6 +1155,211 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
         Ok(())
     }
 
+    fn execute_assign_field(&mut self, cmd: &AssignFieldCmd) -> RuntimeResult<()> {
+        let AssignFieldCmd { field_index, op } = cmd;
+
+        let mut origins = self.cmd_many_source().into_iter();
+        let mut next_origin = || {
+            origins
+                .next()
+                .unwrap_or_else(|| self.assembly.decl_origin())
+        };
+
+        let op_origin = next_origin();
+        let rhs_origin = next_origin();
+        let target_origin = next_origin();
+        let lhs_origin = next_origin();
+        let field_origin = next_origin();
+
+        if !TRUSTED {
+            self.cmd_origin = op_origin;
+        }
+
+        let (rhs_cell, lhs_cell) = self.pop_2();
+
+        let Some(field_string) = self.assembly.strings.get(*field_index) else {
+            self.cmd_index += 1;
+
+            return Ok(());
+        };
+
+        check_access(&lhs_cell, field_string, MemberAccess::Write, field_origin)?;
+
+        let field = field_origin.into_ident(field_string.clone());
+
+        if lhs_cell.clone().into_object().is_property(field.as_ref()) {
+            let observed = ObservedWrite::prepare(&lhs_cell, field.as_ref(), !TRUSTED, |object| {
+                object
+                    .into_object()
+                    .component(lhs_origin, lhs_origin, field.clone())
+            });
+
+            let value = match op {
+                OpCmd::Assign => rhs_cell,
+
+                _ => {
+                    let current = lhs_cell.clone().into_object().component(
+                        lhs_origin,
+                        lhs_origin,
+                        field.clone(),
+                    )?;
+
+                    Self::update(*op, op_origin, target_origin, rhs_origin, current, rhs_cell)?
+                }
+            };
+
+            lhs_cell.into_object().assign_property(
+                op_origin,
+                lhs_origin,
+                field,
+                Arg {
+                    origin: rhs_origin,
+                    data: value,
+                },
+            )?;
+
+            if let Some(observed) = observed {
+                observed.notify(op_origin, field_origin.into_ident(field_string.clone()));
+            }
+
+            self.cmd_index += 1;
+
+            return Ok(());
+        }
+
+        let observed = ObservedWrite::prepare(&lhs_cell, field.as_ref(), !TRUSTED, |object| {
+            object
+                .into_object()
+                .component(lhs_origin, lhs_origin, field.clone())?
+                .into_object()
+                .clone(op_origin, target_origin)
+        });
+
+        if !lhs_cell.is::<Struct>() {
+            check_persistence(&lhs_cell, field.as_ref(), true, lhs_origin, field_origin)?;
+        }
+
+        // The assignments to script structs always target the struct's entries,
+        // even if the entry name collides with a built-in struct method.
+        let target_cell = match lhs_cell.is::<Struct>() {
+            true => lhs_cell
+                .into_object()
+                .field(lhs_origin, lhs_origin, field)?,
+            false => lhs_cell
+                .into_object()
+                .component_or_field(lhs_origin, lhs_origin, field)?,
+        };
+
+        let rhs = Arg {
+            origin: rhs_origin,
+            data: rhs_cell,
+        };
+
+        match op {
+            OpCmd::AddAssign => {
+                target_cell
+                    .into_object()
+                    .add_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            OpCmd::SubAssign => {
+                target_cell
+                    .into_object()
+                    .sub_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            OpCmd::MulAssign => {
+                target_cell
+                    .into_object()
+                    .mul_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            OpCmd::DivAssign => {
+                target_cell
+                    .into_object()
+                    .div_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            OpCmd::BitAndAssign => {
+                target_cell
+                    .into_object()
+                    .bit_and_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            OpCmd::BitOrAssign => {
+                target_cell
+                    .into_object()
+                    .bit_or_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            OpCmd::BitXorAssign => {
+                target_cell
+                    .into_object()
+                    .bit_xor_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            OpCmd::ShlAssign => {
+                target_cell
+                    .into_object()
+                    .shl_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            OpCmd::ShrAssign => {
+                target_cell
+                    .into_object()
+                    .shr_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            OpCmd::RemAssign => {
+                target_cell
+                    .into_object()
+                    .rem_assign_fallback(op_origin, target_origin, rhs)?
+            }
+
+            _ => Self::assign(op_origin, target_origin, rhs_origin, target_cell, rhs.data)?,
+        }
+
+        if let Some(observed) = observed {
+            observed.notify(op_origin, field_origin.into_ident(field_string.clone()));
+        }
+
+        self.cmd_index += 1;
+
+        Ok(())
+    }
+
+    // Computes the new value of the compound assignment (`lhs += rhs`) without
+    // storing it into the lhs.
+    fn update(
+        op: OpCmd,
+        op_origin: Origin,
+        lhs_origin: Origin,
+        rhs_origin: Origin,
+        lhs_cell: Cell,
+        rhs_cell: Cell,
+    ) -> RuntimeResult<Cell> {
+        let lhs = lhs_cell.into_object();
+
+        let rhs = Arg {
+            origin: rhs_origin,
+            data: rhs_cell,
+        };
+
+        match op {
+            OpCmd::AddAssign => lhs.add(op_origin, lhs_origin, rhs),
+            OpCmd::SubAssign => lhs.sub(op_origin, lhs_origin, rhs),
+            OpCmd::MulAssign => lhs.mul(op_origin, lhs_origin, rhs),
+            OpCmd::DivAssign => lhs.div(op_origin, lhs_origin, rhs),
+            OpCmd::BitAndAssign => lhs.bit_and(op_origin, lhs_origin, rhs),
+            OpCmd::BitOrAssign => lhs.bit_or(op_origin, lhs_origin, rhs),
+            OpCmd::BitXorAssign => lhs.bit_xor(op_origin, lhs_origin, rhs),
+            OpCmd::ShlAssign => lhs.shl(op_origin, lhs_origin, rhs),
+            OpCmd::ShrAssign => lhs.shr(op_origin, lhs_origin, rhs),
+            OpCmd::RemAssign => lhs.rem(op_origin, lhs_origin, rhs),
+            _ => Ok(rhs.data),
+        }
+    }
+
     fn execute_len(&mut self) -> RuntimeResult<()> {
         let (lhs_origin, field_origin) = self.cmd_2_source();
 
@@ -699,7 +1371,16 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
 
         let length = match lhs_cell.is::<str>() {
             true => lhs_cell.borrow_str(lhs_origin)?.chars().count(),
-            false => lhs_cell.length(),
+
+            false => match lhs_cell.length() == 1 {
+                true if lhs_cell.is::<StringBuilder>() => {
+                    lhs_cell.borrow_ref::<StringBuilder>(lhs_origin)?.chars()
+                }
+
+                true if lhs_cell.is::<Bytes>() => lhs_cell.borrow_ref::<Bytes>(lhs_origin)?.len(),
+
+                _ => lhs_cell.length(),
+            },
         };
 
         self.push(Cell::give(field_origin, length)?);
@@ -792,6 +1473,24 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
 
         let (rhs_cell, lhs_cell) = self.pop_2();
 
+        Self::assign(op_origin, lhs_origin, rhs_origin, lhs_cell, rhs_cell)?;
+
+        self.cmd_index += 1;
+
+        Ok(())
+    }
+
+    fn assign(
+        op_origin: Origin,
+        lhs_origin: Origin,
+        rhs_origin: Origin,
+        lhs_cell: Cell,
+        rhs_cell: Cell,
+    ) -> RuntimeResult<()> {
+        if lhs_cell.length() != 1 && !lhs_cell.is_nil() {
+            return Self::assign_slice(op_origin, lhs_origin, rhs_origin, lhs_cell, rhs_cell);
+        }
+
         lhs_cell.into_object().assign(
             op_origin,
             lhs_origin,
@@ -799,9 +1498,60 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
                 origin: rhs_origin,
                 data: rhs_cell,
             },
-        )?;
+        )
+    }
+
+    // Assigns array elements one by one: `arr[1..3] = [x, y]`. The length of
+    // the array cannot be changed through the assignment, because the left-hand
+    // side is a projection into the memory allocation of the original array.
+    fn assign_slice(
+        op_origin: Origin,
+        lhs_origin: Origin,
+        rhs_origin: Origin,
+        lhs_cell: Cell,
+        rhs_cell: Cell,
+    ) -> RuntimeResult<()> {
+        let length = lhs_cell.length();
+        let rhs_length = rhs_cell.length();
+
+        if length != rhs_length {
+            return Err(RuntimeError::LengthMismatch {
+                access_origin: rhs_origin,
+                expected: length,
+                actual: rhs_length,
+            });
+        }
+
+        // The right-hand side may be a projection of the same array
+        // (`arr[1..3] = arr[0..2]`), so the source items are copied before
+        // any of them are overwritten.
+        let mut rhs_items = Vec::with_capacity(length);
+
+        for index in 0..length {
+            let rhs_item = rhs_cell.clone().map_slice(rhs_origin, index..(index + 1))?;
+
+            let rhs_item = match rhs_item.clone().into_object() {
+                object if object.prototype().implements_clone() => {
+                    object.clone(op_origin, rhs_origin)?
+                }
+                _ => rhs_item,
+            };
+
+            rhs_items.push(rhs_item);
+        }
 
-        self.cmd_index += 1;
+        for (index, rhs_item) in rhs_items.into_iter().enumerate() {
+            let lhs_item = lhs_cell.clone().map_slice(lhs_origin, index..(index + 1))?;
+
+            lhs_item.into_object().assign(
+                op_origin,
+                lhs_origin,
+                Arg {
+                    origin: rhs_origin,
+                    data: rhs_item,
+                },
+            )?;
+        }
 
         Ok(())
     }
@@ -1520,7 +2270,10 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
                 });
             }
 
-            script_fn.execute::<TRUSTED>()?;
+            if let Err(error) = script_fn.execute::<TRUSTED>() {
+                self.propagated = true;
+                return Err(error);
+            }
 
             self.cmd_index += 1;
 
@@ -1536,6 +2289,37 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
             args.push(Arg { origin, data });
         }
 
+        self.invoke_exported(lhs_cell, lhs_origin, invocation_origin, args)
+    }
+
+    fn invoke_exported(
+        &mut self,
+        lhs_cell: Cell,
+        lhs_origin: Origin,
+        invocation_origin: Origin,
+        mut args: Vec<Arg>,
+    ) -> RuntimeResult<()> {
+        if !TRUSTED {
+            let call = VmCall::Exported {
+                origin: invocation_origin,
+                meta: lhs_cell.ty().prototype().hint_invocation(),
+            };
+
+            notify_enter(&call);
+
+            let result = lhs_cell
+                .into_object()
+                .invoke(invocation_origin, lhs_origin, &mut args);
+
+            notify_exit(&call);
+
+            self.cmd_index += 1;
+
+            self.push(result?);
+
+            return Ok(());
+        }
+
         let result = lhs_cell
             .into_object()
             .invoke(invocation_origin, lhs_origin, &mut args)?;
@@ -1547,6 +2331,170 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
         Ok(())
     }
 
+    // Invocations with named arguments arrange the arguments according to the
+    // parameter names of the invoked function's signature. Script functions do
+    // not have such signatures, so they accept positional arguments only.
+    fn execute_named_invoke(&mut self, cmd: &NamedInvokeCmd) -> RuntimeResult<()> {
+        let NamedInvokeCmd { names, closure } = cmd;
+
+        let arity = names.len();
+
+        let mut origins = self.cmd_many_source();
+
+        let invocation_origin = origins.pop().unwrap_or_else(|| self.assembly.decl_origin());
+        let lhs_origin = origins.pop().unwrap_or_else(|| self.assembly.decl_origin());
+
+        if !TRUSTED {
+            self.cmd_origin = invocation_origin;
+        }
+
+        let lhs_cell = self.pop_1();
+        let arg_cells = self.pop_many(arity);
+
+        let meta = match lhs_cell.is::<ScriptFn>() {
+            true => None,
+            false => lhs_cell.ty().prototype().hint_invocation(),
+        };
+
+        let params = meta.and_then(|meta| meta.inputs.as_deref()).unwrap_or(&[]);
+
+        let function_origin = meta.map(|meta| meta.origin).unwrap_or(lhs_origin);
+
+        let arg_origins = origins
+            .into_iter()
+            .chain(repeat(invocation_origin))
+            .take(arity)
+            .collect::<Vec<_>>();
+
+        let names = names
+            .iter()
+            .map(|name| {
+                name.and_then(|index| self.assembly.strings.get(index))
+                    .map(|string| string.as_str())
+            })
+            .collect::<Vec<_>>();
+
+        let order = match arrange_args(params, names.iter().copied(), *closure) {
+            Ok(order) => order,
+
+            Err(error) => {
+                let (index, name, duplicate) = match error {
+                    ArgNameError::Unknown(index) => (index, names[index], false),
+
+                    ArgNameError::Duplicate(index, param) => {
+                        let name = params[param].name.as_ref().map(AsRef::as_ref);
+
+                        (index, name, true)
+                    }
+
+                    ArgNameError::Misplaced(index) => (index, None, false),
+                };
+
+                return Err(RuntimeError::ArgumentName {
+                    access_origin: arg_origins[index],
+                    function_origin,
+                    name: name.map(String::from),
+                    duplicate,
+                });
+            }
+        };
+
+        let variadic = meta.map(|meta| meta.variadic).unwrap_or_default() && !params.is_empty();
+
+        let arity_mismatch = match variadic {
+            false => params.len() != arity,
+            true => params.len() > arity + 1,
+        };
+
+        if arity_mismatch {
+            return Err(RuntimeError::ArityMismatch {
+                invocation_origin,
+                function_origin,
+                parameters: params.len() - variadic as usize,
+                arguments: arity,
+            });
+        }
+
+        let mut slots = Vec::with_capacity(arity.max(params.len()));
+
+        slots.resize_with(arity.max(params.len()), || None);
+
+        for ((param, origin), data) in order.into_iter().zip(arg_origins).zip(arg_cells) {
+            slots[param] = Some(Arg { origin, data });
+        }
+
+        // A named argument of the variadic parameter cannot substitute the
+        // preceding parameters.
+        if variadic && slots[..(params.len() - 1)].iter().any(Option::is_none) {
+            return Err(RuntimeError::ArityMismatch {
+                invocation_origin,
+                function_origin,
+                parameters: params.len() - 1,
+                arguments: arity,
+            });
+        }
+
+        let args = slots.into_iter().flatten().collect();
+
+        self.invoke_exported(lhs_cell, lhs_origin, invocation_origin, args)
+    }
+
+    // Self-recursive tail calls reuse the current frame: the arguments replace
+    // the frame's content, and the execution restarts from the first command.
+    // As a result, such calls neither consume the Script Engine stack nor the
+    // Rust stack. The elided frames are still reported to the VM observers.
+    fn execute_tail_invoke(&mut self, cmd: &TailInvokeCmd) -> RuntimeResult<()> {
+        let TailInvokeCmd { arity } = cmd;
+
+        let closures = {
+            let mut lhs_cell = self.peek_1();
+
+            match lhs_cell.is::<ScriptFn>() {
+                false => None,
+
+                true => match lhs_cell.borrow_ref::<ScriptFn>(self.assembly.decl_origin()) {
+                    Ok(script_fn) => {
+                        let callee = script_fn.assembly.as_ref();
+
+                        match ptr::eq(callee, self.assembly) && callee.arity == *arity {
+                            true => Some(script_fn.closures.clone()),
+                            false => None,
+                        }
+                    }
+
+                    Err(_) => None,
+                },
+            }
+        };
+
+        let Some(closures) = closures else {
+            return self.execute_invoke(&InvokeCmd { arity: *arity });
+        };
+
+        if !TRUSTED {
+            self.cmd_origin = self.cmd_many_source().pop().unwrap_or(self.cmd_origin);
+        }
+
+        let _ = self.pop_1();
+
+        Stack::reuse_frame(self.frame_begin, *arity);
+
+        self.closures = Cow::Owned(closures);
+
+        if !TRUSTED {
+            let call = VmCall::Script {
+                origin: self.assembly.decl_origin(),
+            };
+
+            notify_exit(&call);
+            notify_enter(&call);
+        }
+
+        self.cmd_index = 0;
+
+        Ok(())
+    }
+
     fn execute_index(&mut self) -> RuntimeResult<()> {
         let (op_origin, slice_origin, range_origin) = self.cmd_3_source();
 
@@ -1556,6 +2504,65 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
 
         let (range_cell, slice_cell) = self.pop_2();
 
+        let receiver = Arg {
+            origin: slice_origin,
+            data: slice_cell,
+        };
+
+        let mut arguments = [Arg {
+            origin: range_origin,
+            data: range_cell,
+        }];
+
+        if let Some(result) =
+            Struct::overload(op_origin, &receiver, Struct::INDEX_ENTRY, &mut arguments)
+        {
+            self.push(result?);
+
+            self.cmd_index += 1;
+
+            return Ok(());
+        }
+
+        let [Arg {
+            data: range_cell, ..
+        }] = arguments;
+
+        let mut slice_cell = receiver.data;
+
+        if slice_cell.is::<ScriptEnv>() {
+            let mut key_cell = range_cell;
+
+            let key = key_cell.borrow_str(range_origin)?;
+            let env = slice_cell.borrow_ref::<ScriptEnv>(slice_origin)?;
+
+            let result = env.entry(op_origin, key)?;
+
+            self.push(result);
+
+            self.cmd_index += 1;
+
+            return Ok(());
+        }
+
+        // The string index of a struct refers to the struct's entry by name
+        // (`foo["bar"]`), bypassing the struct's methods.
+        if slice_cell.is::<Struct>() && slice_cell.length() == 1 && range_cell.is::<str>() {
+            let mut key_cell = range_cell;
+
+            let key = Ident::from_string(key_cell.borrow_str(range_origin)?);
+
+            let result = slice_cell
+                .into_object()
+                .field(op_origin, slice_origin, key)?;
+
+            self.push(result);
+
+            self.cmd_index += 1;
+
+            return Ok(());
+        }
+
         let bounds = Self::slice_bounds(range_origin, range_cell)?;
 
         if slice_cell.is::<str>() {
@@ -1573,18 +2580,27 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
             return Ok(());
         }
 
-        let range = match bounds {
-            SliceBounds::Range(mut range) => {
-                let length = slice_cell.length();
+        if slice_cell.is::<Bytes>() && slice_cell.length() == 1 {
+            let result = Self::index_bytes(op_origin, range_origin, slice_cell, bounds)?;
 
-                if range.start < range.end && range.start <= length && range.end > length {
-                    range.end = length;
-                }
+            self.push(result);
 
-                range
-            }
+            self.cmd_index += 1;
+
+            return Ok(());
+        }
+
+        let range = match slice_cell.is_nil() || slice_cell.ty().size() == 0 {
+            true => match bounds {
+                SliceBounds::Range(range) => range,
+                SliceBounds::Index(index) => {
+                    let index = usize::try_from(index).unwrap_or_default();
 
-            SliceBounds::Index(index) => index..index.checked_add(1).unwrap_or(usize::MAX),
+                    index..index.checked_add(1).unwrap_or(usize::MAX)
+                }
+            },
+
+            false => Self::resolve_bounds(range_origin, bounds, slice_cell.length())?,
         };
 
         let result = slice_cell.map_slice(op_origin, range)?;
@@ -1596,6 +2612,26 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
         Ok(())
     }
 
+    // Indexes a bytes object by copying the data: an index turns into a byte
+    // number, and a range turns into a new bytes object.
+    fn index_bytes(
+        op_origin: Origin,
+        range_origin: Origin,
+        mut bytes_cell: Cell,
+        bounds: SliceBounds,
+    ) -> RuntimeResult<Cell> {
+        let is_index = matches!(bounds, SliceBounds::Index(_));
+
+        let bytes = bytes_cell.borrow_ref::<Bytes>(op_origin)?;
+
+        let range = Self::resolve_bounds(range_origin, bounds, bytes.len())?;
+
+        match is_index {
+            true => Cell::give(op_origin, bytes.as_slice()[range.start]),
+            false => Cell::give(op_origin, Bytes::from(Vec::from(&bytes.as_slice()[range]))),
+        }
+    }
+
     #[inline(always)]
     fn slice_bounds(range_origin: Origin, range_cell: Cell) -> RuntimeResult<SliceBounds> {
         let provider = Provider::Owned(range_cell);
@@ -1603,7 +2639,7 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
         let mut type_match = provider.type_match();
 
         if type_match.belongs_to::<usize>() || type_match.is::<bool>() || type_match.is::<str>() {
-            let index = <usize as Downcast<'static>>::downcast(range_origin, provider)?;
+            let index = <isize as Downcast<'static>>::downcast(range_origin, provider)?;
 
             return Ok(SliceBounds::Index(index));
         }
@@ -1623,63 +2659,104 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
         mut string_cell: Cell,
         bounds: SliceBounds,
     ) -> RuntimeResult<Range<usize>> {
-        let (is_index, range) = match bounds {
-            SliceBounds::Range(range) => (false, range),
-            SliceBounds::Index(index) => (true, index..index.checked_add(1).unwrap_or(usize::MAX)),
-        };
-
-        if range.start > range.end {
-            return Err(RuntimeError::MalformedRange {
-                access_origin: range_origin,
-                start_bound: range.start,
-                end_bound: range.end,
-            });
-        }
-
         let string = string_cell.borrow_str(string_origin)?;
 
-        let mut chars_iter = string.char_indices();
-        let mut chars_consumed = 0;
+        let range = Self::resolve_bounds(range_origin, bounds, string.chars().count())?;
 
-        let mut start = None;
+        let mut bytes = string
+            .char_indices()
+            .map(|(byte, _)| byte)
+            .chain(once(string.len()));
 
-        while let Some((byte, _)) = chars_iter.next() {
-            if chars_consumed == range.start {
-                start = Some(byte);
-                chars_consumed += 1;
-                break;
-            }
+        let start = bytes.nth(range.start).unwrap_or(string.len());
 
-            chars_consumed += 1;
+        if range.start == range.end {
+            return Ok(start..start);
         }
 
-        let start = match start {
-            Some(bound) => bound,
+        let end = bytes
+            .nth(range.end - range.start - 1)
+            .unwrap_or(string.len());
 
-            None if range.start == chars_consumed && !is_index => string.len(),
+        Ok(start..end)
+    }
 
-            None => {
-                return Err(RuntimeError::OutOfBounds {
-                    access_origin: range_origin,
-                    index: range.start,
-                    length: chars_consumed,
-                })
+    // Turns the index operator argument into a range within the array (or
+    // string characters) of the specified length according to the current
+    // thread's IndexPolicy.
+    fn resolve_bounds(
+        range_origin: Origin,
+        bounds: SliceBounds,
+        length: usize,
+    ) -> RuntimeResult<Range<usize>> {
+        let policy = index_policy();
+
+        match bounds {
+            SliceBounds::Index(index) => {
+                let resolved = match usize::try_from(index) {
+                    Ok(index) => Some(index),
+
+                    Err(_) => match policy {
+                        IndexPolicy::Strict => None,
+                        IndexPolicy::Clamp => Some(0),
+                        IndexPolicy::WrapNegative => length.checked_sub(index.unsigned_abs()),
+                    },
+                };
+
+                let resolved = match (resolved, policy) {
+                    (Some(resolved), _) if resolved < length => resolved,
+
+                    (_, IndexPolicy::Clamp) if length > 0 => length - 1,
+
+                    _ => {
+                        return Err(RuntimeError::IndexOutOfBounds {
+                            access_origin: range_origin,
+                            index,
+                            length,
+                            policy,
+                        })
+                    }
+                };
+
+                Ok(resolved..(resolved + 1))
             }
-        };
 
-        if range.start == range.end {
-            return Ok(start..start);
-        }
+            SliceBounds::Range(mut range) => {
+                if range.start > range.end {
+                    return Err(RuntimeError::MalformedRange {
+                        access_origin: range_origin,
+                        start_bound: range.start,
+                        end_bound: range.end,
+                    });
+                }
 
-        while let Some((byte, _)) = chars_iter.next() {
-            if chars_consumed == range.end {
-                return Ok(start..byte);
-            }
+                if range.end <= length {
+                    return Ok(range);
+                }
 
-            chars_consumed += 1;
-        }
+                match policy {
+                    IndexPolicy::Clamp => {
+                        range.start = range.start.min(length);
+                        range.end = length;
+                    }
+
+                    _ if range.start <= length => {
+                        range.end = length;
+                    }
+
+                    _ => {
+                        return Err(RuntimeError::IndexOutOfBounds {
+                            access_origin: range_origin,
+                            index: isize::try_from(range.end - 1).unwrap_or(isize::MAX),
+                            length,
+                            policy,
+                        })
+                    }
+                }
 
-        Ok(start..string.len())
+                Ok(range)
+            }
+        }
     }
 
     #[inline(always)]
@@ -1753,7 +2830,318 @@ impl<'a, const TRUSTED: bool> Engine<'a, TRUSTED> {
     }
 }
 
+// A pending notification of the observers about the assignment to a local
+// variable.
+struct PendingVarWrite<'a> {
+    depth: StackDepth,
+    decl: Origin,
+    name: &'a str,
+    origin: Origin,
+    target: Option<Cell>,
+    old_value: Cell,
+}
+
+impl<'a> PendingVarWrite<'a> {
+    fn notify(self, frame_begin: StackDepth) {
+        let new_value = match self.target {
+            Some(target) => target,
+            None => Stack::peek(frame_begin, self.depth),
+        };
+
+        notify_write_var(&VarWrite {
+            name: self.name,
+            decl: self.decl,
+            old_value: &self.old_value,
+            new_value: &new_value,
+            origin: self.origin,
+        });
+    }
+}
+
 enum SliceBounds {
     Range(Range<usize>),
-    Index(usize),
+    Index(isize),
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        interpret::{set_strict_assignments, ScriptFn, VmCall, VmObserver},
+        runtime::{Origin, RuntimeError, ScriptPackage},
+    };
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct TestRig {
+        pub dial: TestRigPart,
+        part: TestRigPart,
+    }
+
+    #[export]
+    impl TestRig {
+        #[export(getter)]
+        pub fn part(&self) -> TestRigPart {
+            self.part.clone()
+        }
+
+        #[export(setter)]
+        pub fn set_part(&mut self, part: TestRigPart) {
+            self.part = part;
+        }
+
+        pub fn spare(&self) -> TestRigPart {
+            self.part.clone()
+        }
+    }
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct TestRigPart {
+        pub value: usize,
+    }
+
+    #[export]
+    pub fn test_rig() -> TestRig {
+        TestRig::default()
+    }
+
+    fn compile(text: &str) -> ScriptFn {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        read.compile().unwrap()
+    }
+
+    #[test]
+    fn test_tail_calls() {
+        static SUM: &str = r#"
+            let m = struct {
+                sum: fn(n, acc) {
+                    if n == 0 {
+                        return acc;
+                    }
+
+                    return self.sum(n - 1, acc + n);
+                },
+            };
+
+            return m.sum(1000000, 0);
+        "#;
+
+        assert_eq!(crate::eval_as!(TestPackage, usize, SUM), 500000500000);
+
+        #[derive(Default)]
+        struct Counter {
+            calls: usize,
+            depth: usize,
+        }
+
+        impl VmObserver for Counter {
+            fn enter(&mut self, _call: &VmCall) {
+                self.calls += 1;
+                self.depth += 1;
+            }
+
+            fn exit(&mut self, _call: &VmCall) {
+                self.depth -= 1;
+            }
+        }
+
+        let mut counter = Counter::default();
+
+        let result = compile(&SUM.replace("1000000", "100"))
+            .instrument(&mut counter)
+            .run()
+            .unwrap();
+
+        assert_eq!(result.take::<usize>(Origin::nil()).unwrap(), 5050);
+        assert_eq!(counter.calls, 102);
+        assert_eq!(counter.depth, 0);
+    }
+
+    #[test]
+    fn test_index_assignment() {
+        let eval = |text: &str| crate::eval!(TestPackage, text).stringify(false);
+
+        assert_eq!(
+            eval("let grid = [[1, 2], [3, 4]]; grid[1][0] = 9; return grid;"),
+            "[1, 9, 3, 4]",
+        );
+
+        assert_eq!(
+            eval("let arr = [1, 2, 3, 4]; arr[1..4][1..3][1] = 9; return arr;"),
+            "[1, 2, 3, 9]",
+        );
+
+        assert_eq!(
+            eval("let arr = [1, 2, 3, 4]; arr[0..2] = arr[1..3]; return arr;"),
+            "[2, 3, 3, 4]",
+        );
+
+        assert_eq!(
+            eval("let arr = [1, 2, 3, 4]; arr[1..3] = arr[0..2]; return arr;"),
+            "[1, 1, 2, 4]",
+        );
+
+        assert_eq!(
+            eval("let arr = [1, 2, 3, 4]; arr[1..4] = arr[0..3]; return arr;"),
+            "[1, 1, 2, 3]",
+        );
+
+        let error = compile("let arr = [1, 2, 3, 4]; arr[1..3] = [5, 6, 7];")
+            .run()
+            .unwrap_err();
+
+        let RuntimeError::LengthMismatch {
+            expected, actual, ..
+        } = error
+        else {
+            panic!("Unexpected error: {error}");
+        };
+
+        assert_eq!(expected, 2);
+        assert_eq!(actual, 3);
+
+        let error = compile("let arr = [1, 2, 3, 4]; arr[1..3] = [9];")
+            .run()
+            .unwrap_err();
+
+        let RuntimeError::LengthMismatch {
+            expected, actual, ..
+        } = error
+        else {
+            panic!("Unexpected error: {error}");
+        };
+
+        assert_eq!(expected, 2);
+        assert_eq!(actual, 1);
+
+        assert_eq!(
+            eval("let arr = [1, 2, 3, 4]; let new = [arr[0..1], 9, arr[3..arr.len]]; return new;"),
+            "[1, 9, 4]",
+        );
+    }
+
+    #[test]
+    fn test_signed_range() {
+        let eval = |text: &str| crate::eval!(TestPackage, text).stringify(false);
+
+        assert_eq!(
+            eval("let acc = 0; for i in -3..2 { acc = acc * 10 + (i + 5); } return acc;"),
+            "23456",
+        );
+
+        assert_eq!(
+            eval("let acc = 0; for i in -5..-3 { acc = acc * 10 + (-i); } return acc;"),
+            "54",
+        );
+
+        assert_eq!(
+            eval("let acc = 0; for i in -1..-5 { acc += 1; } return acc;"),
+            "0",
+        );
+
+        assert_eq!(
+            eval("let acc = 0; for i in 1..4 { acc = acc * 10 + i; } return acc;"),
+            "123",
+        );
+
+        assert_eq!(eval("return -2..2;"), "-2..2");
+        assert_eq!(eval("let r = -2..2; return r.start * 10 + r.end;"), "-18");
+        assert_eq!(eval("return (-2..2) == (-2..2);"), "true");
+        assert_eq!(eval("return (-2..2) == (0..2);"), "false");
+        assert_eq!(eval("return (-2..-1) == -2;"), "true");
+    }
+
+    #[test]
+    fn test_range_arms() {
+        let eval = |text: &str| {
+            crate::assert_script_ok!(TestPackage, text);
+
+            crate::eval!(TestPackage, text).stringify(false)
+        };
+
+        let classify = |subject: &str| {
+            eval(&format!(
+                "let x; match {subject} {{ -5..0 => x = 1, 0..5 => x = 2, else => x = 3 }} \
+                return x;"
+            ))
+        };
+
+        assert_eq!(classify("-5"), "1");
+        assert_eq!(classify("-1"), "1");
+        assert_eq!(classify("0"), "2");
+        assert_eq!(classify("4"), "2");
+        assert_eq!(classify("5"), "3");
+        assert_eq!(classify("-6"), "3");
+        assert_eq!(classify("2.5"), "2");
+        assert_eq!(classify("-0.5"), "1");
+
+        assert_eq!(
+            eval("let x; match 100 { 0..10 => x = 1, 10..max => x = 2, else => x = 3 } return x;"),
+            "2",
+        );
+    }
+
+    #[test]
+    fn test_strict_assignments() {
+        let run = |text: &str| {
+            compile(&format!("let r = test_rig(); {text}"))
+                .run()
+                .map(|result| result.stringify(false))
+        };
+
+        static PERSISTENT: [&str; 3] = [
+            "r.dial.value = 5; return r.dial.value;",
+            "r.dial.value += 5; return r.dial.value;",
+            "let p = r.part; p.value = 5; r.part = p; return r.part.value;",
+        ];
+
+        static TEMPORARY: [&str; 2] = [
+            "r.part.value = 5; return r.part.value;",
+            "r.part.value += 5; return r.part.value;",
+        ];
+
+        static METHOD: &str = "r.spare = 5; return r.part.value;";
+
+        for text in PERSISTENT {
+            assert_eq!(run(text).unwrap(), "5");
+        }
+
+        for text in TEMPORARY {
+            assert_eq!(run(text).unwrap(), "0");
+        }
+
+        assert!(matches!(
+            run(METHOD),
+            Err(RuntimeError::UndefinedOperator { .. }),
+        ));
+
+        set_strict_assignments(true);
+
+        for text in PERSISTENT {
+            assert_eq!(run(text).unwrap(), "5");
+        }
+
+        for text in TEMPORARY {
+            let Err(RuntimeError::TemporaryAssignment { component, .. }) = run(text) else {
+                panic!("Strict assignment not rejected: {text}");
+            };
+
+            assert_eq!(component, "part");
+        }
+
+        let Err(RuntimeError::TemporaryAssignment { component, .. }) = run(METHOD) else {
+            panic!("Strict assignment not rejected: {METHOD}");
+        };
+
+        assert_eq!(component, "spare");
+
+        set_strict_assignments(false);
+    }
 }
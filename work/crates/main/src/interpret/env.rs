@@ -0,0 +1,370 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Display, Formatter},
+};
+
+use ahash::AHashMap;
+use lady_deirdre::sync::Shared;
+
+use crate::{
+    export,
+    runtime::{
+        ops::{DynamicType, ScriptField},
+        Arg,
+        Cell,
+        Ident,
+        Origin,
+        RuntimeResult,
+        RustIdent,
+        RustOrigin,
+        ScriptType,
+        TypeHint,
+        Upcast,
+    },
+};
+
+thread_local! {
+    static ENV: RefCell<Option<ScriptEnv>> = const { RefCell::new(None) };
+}
+
+static ENV_ORIGIN: RustOrigin = RustOrigin {
+    package: None,
+    code: None,
+};
+
+// The name of the built-in `env` variable available in every script package.
+pub(crate) static ENV_IDENT: RustIdent = RustIdent {
+    origin: &ENV_ORIGIN,
+    string: "env",
+};
+
+/// A host-defined set of values that the script can read through the built-in
+/// `env` variable: `env.locale` or `env["difficulty"]`.
+///
+/// The host supplies the environment per script evaluation using the
+/// [ScriptFn::run_with_env](crate::interpret::ScriptFn::run_with_env)
+/// function. This is a convenient way to pass configuration data to the
+/// script without exporting a dedicated Rust function per configuration key.
+///
+/// ```rust
+/// # use ad_astra::{
+/// #     analysis::{ModuleRead, ScriptModule},
+/// #     export,
+/// #     interpret::ScriptEnv,
+/// #     lady_deirdre::analysis::TriggerHandle,
+/// #     runtime::{Cell, Origin, ScriptPackage},
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// let module = ScriptModule::new(Package::meta(), r#"return env["difficulty"] * 2;"#);
+/// let handle = TriggerHandle::new();
+/// let read = module.read(&handle, 1).unwrap();
+/// let script_fn = read.compile().unwrap();
+///
+/// let mut env = ScriptEnv::new();
+///
+/// env.insert("difficulty", Cell::give(Origin::nil(), 3usize).unwrap());
+///
+/// let result = script_fn.run_with_env(&env).unwrap();
+///
+/// assert_eq!(result.take::<usize>(Origin::nil()).unwrap(), 6);
+/// ```
+///
+/// Reading a key that the environment does not have results in nil. The
+/// script cannot change the environment: the entries are read-only, and an
+/// attempt to mutate them results in a
+/// [RuntimeError](crate::runtime::RuntimeError).
+///
+/// The static analyzer treats the `env` variable as a dynamically typed
+/// struct, because the keys and the types of the entries are not known
+/// statically.
+///
+/// The ScriptEnv object is cheap to [Clone]: the clones share the entries
+/// until one of them is modified.
+#[derive(Clone, Default)]
+pub struct ScriptEnv {
+    entries: Shared<AHashMap<String, Cell>>,
+}
+
+impl Debug for ScriptEnv {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut debug_map = formatter.debug_map();
+
+        for (key, value) in self.entries.as_ref() {
+            let _ = debug_map.entry(key, value);
+        }
+
+        debug_map.finish()
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, Cell)> for ScriptEnv {
+    fn from_iter<T: IntoIterator<Item = (K, Cell)>>(iter: T) -> Self {
+        let entries = iter
+            .into_iter()
+            .map(|(key, value)| (key.into(), value))
+            .collect();
+
+        Self {
+            entries: Shared::new(entries),
+        }
+    }
+}
+
+impl ScriptEnv {
+    /// Creates an empty environment.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of the `key` entry, and returns the previous value of
+    /// this entry, if any.
+    #[inline(always)]
+    pub fn insert(&mut self, key: impl Into<String>, value: Cell) -> Option<Cell> {
+        self.entries.make_mut().insert(key.into(), value)
+    }
+
+    /// Removes the `key` entry, and returns its value, if any.
+    #[inline(always)]
+    pub fn remove(&mut self, key: &str) -> Option<Cell> {
+        self.entries.make_mut().remove(key)
+    }
+
+    /// Returns the value of the `key` entry, or None if the environment does
+    /// not have such an entry.
+    #[inline(always)]
+    pub fn get(&self, key: &str) -> Option<&Cell> {
+        self.entries.as_ref().get(key)
+    }
+
+    /// Returns the number of entries in this environment.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.as_ref().len()
+    }
+
+    /// Returns true if this environment does not have any entries.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.as_ref().is_empty()
+    }
+
+    // Returns a read-only projection of the `key` entry, or nil if there is no
+    // such entry.
+    pub(crate) fn entry(&self, origin: Origin, key: &str) -> RuntimeResult<Cell> {
+        match self.get(key) {
+            Some(value) => value.clone().into_read_only(origin),
+            None => Ok(Cell::nil()),
+        }
+    }
+
+    // The constructor of the built-in `env` variable's value: the environment
+    // of the current evaluation, or an empty environment outside of it.
+    pub(crate) fn component(origin: Origin, _lhs: Arg) -> RuntimeResult<Cell> {
+        let env = ENV.with(|env| env.borrow().clone().unwrap_or_default());
+
+        Cell::give(origin, env)
+    }
+}
+
+// A guard object that sets the environment of the script evaluation on the
+// current thread. The previous environment is restored when the guard is
+// dropped.
+pub(super) struct EnvScope {
+    previous: Option<ScriptEnv>,
+}
+
+impl Drop for EnvScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+
+        ENV.with(move |env| *env.borrow_mut() = previous);
+    }
+}
+
+impl EnvScope {
+    #[inline(always)]
+    pub(super) fn enter(env: ScriptEnv) -> Self {
+        let previous = ENV.with(|current| current.borrow_mut().replace(env));
+
+        Self { previous }
+    }
+}
+
+/// A host-defined environment of the script evaluation.
+///
+/// The entries of the environment are accessible as fields: `env.locale`, or
+/// by string keys: `env["difficulty"]`. Missing entries are nil.
+#[export(include)]
+#[export(name "env")]
+pub(crate) type ScriptEnvType = ScriptEnv;
+
+impl<'a> Upcast<'a> for ScriptEnvType {
+    type Output = Box<ScriptEnvType>;
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(Box::new(this))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(ScriptEnvType::type_meta())
+    }
+}
+
+#[export(include)]
+impl Display for ScriptEnvType {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("env")
+    }
+}
+
+#[export(include)]
+impl ScriptField for ScriptEnvType {
+    type Result = DynamicType;
+
+    fn script_field(origin: Origin, lhs: Arg, rhs: Ident) -> RuntimeResult<Cell> {
+        let mut lhs_data = lhs.data;
+
+        let env = lhs_data.borrow_ref::<ScriptEnvType>(lhs.origin)?;
+
+        env.entry(origin, rhs.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        interpret::{ScriptEnv, ScriptFn},
+        runtime::{Cell, Origin, RuntimeResult, ScriptPackage},
+    };
+
+    fn compile(text: &str) -> ScriptFn {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        read.compile().unwrap()
+    }
+
+    fn env() -> ScriptEnv {
+        [
+            ("locale", Cell::give(Origin::nil(), "en").unwrap()),
+            ("difficulty", Cell::give(Origin::nil(), 3usize).unwrap()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[export]
+    pub fn env_nested(f: ScriptFn) -> RuntimeResult<usize> {
+        let mut env = ScriptEnv::new();
+
+        let _ = env.insert("difficulty", Cell::give(Origin::nil(), 10usize)?);
+
+        f.run_with_env(&env)?.take::<usize>(Origin::nil())
+    }
+
+    #[test]
+    fn test_env_read() {
+        let env = env();
+
+        let result = compile(r#"return env.locale;"#).run_with_env(&env).unwrap();
+
+        assert_eq!(result.stringify(false), "en");
+
+        let result = compile(r#"return env["difficulty"] * 2;"#)
+            .run_with_env(&env)
+            .unwrap();
+
+        assert_eq!(result.take::<usize>(Origin::nil()).unwrap(), 6);
+
+        for text in [r#"return env.missing;"#, r#"return env["missing"];"#] {
+            assert!(compile(text).run_with_env(&env).unwrap().is_nil());
+        }
+
+        assert!(compile(r#"return env.difficulty;"#).run().unwrap().is_nil());
+    }
+
+    #[test]
+    fn test_env_read_only() {
+        let env = env();
+
+        assert!(compile(r#"env.difficulty = 5;"#)
+            .run_with_env(&env)
+            .is_err());
+        assert!(compile(r#"env["difficulty"] += 1;"#)
+            .run_with_env(&env)
+            .is_err());
+        assert!(compile(r#"env.missing = 5;"#).run_with_env(&env).is_err());
+
+        assert_eq!(
+            env.get("difficulty")
+                .unwrap()
+                .clone()
+                .take::<usize>(Origin::nil())
+                .unwrap(),
+            3,
+        );
+    }
+
+    #[test]
+    fn test_env_nested() {
+        let env = env();
+
+        let result = compile(
+            r#"
+            let inner = env_nested(fn() { return env.difficulty; });
+            return inner * 100 + env.difficulty;
+        "#,
+        )
+        .run_with_env(&env)
+        .unwrap();
+
+        assert_eq!(result.take::<usize>(Origin::nil()).unwrap(), 1003);
+    }
+}
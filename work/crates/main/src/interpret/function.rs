@@ -35,13 +35,21 @@
 use std::mem::take;
 
 use ad_astra_export::export;
-use lady_deirdre::sync::Shared;
+use lady_deirdre::{analysis::Revision, arena::Id, sync::Shared};
 
 use crate::{
-    interpret::{engine::is_trusted, stack::Stack, Assembly},
+    interpret::{
+        env::{EnvScope, ScriptEnv},
+        memo::EvaluationScope,
+        observer::is_trusted,
+        stack::Stack,
+        Assembly,
+    },
     runtime::{
+        __intrinsics::FUNCTION_FAMILY,
         ops::{DynamicType, ScriptBinding, ScriptClone, ScriptInvocation},
         Arg,
+        BudgetScope,
         Cell,
         Downcast,
         InvocationMeta,
@@ -49,10 +57,10 @@ use crate::{
         Provider,
         RuntimeError,
         RuntimeResult,
+        ScriptProvenance,
         ScriptType,
         TypeHint,
         Upcast,
-        __intrinsics::FUNCTION_FAMILY,
     },
 };
 
@@ -88,6 +96,13 @@ use crate::{
 /// [Cells](Cell) from the stack and pushing new Cells onto the stack.
 /// Therefore, the Virtual Machine is a stack-based machine.
 ///
+/// If a script function returns the result of invoking itself (a
+/// self-recursive call in the tail position), the Virtual Machine reuses the
+/// current function's frame instead of entering a new one, such that tail
+/// recursion does not exhaust the stack. The elided frames are not kept by the
+/// Virtual Machine, but the [observers](crate::interpret::VmObserver) still
+/// receive the exit and enter events for each such invocation.
+///
 /// ## Isolation
 ///
 /// Each assembly command is evaluated in a virtual environment. If for any
@@ -171,6 +186,8 @@ pub struct ScriptFn {
     pub(super) assembly: Shared<Assembly>,
     pub(super) closures: Vec<Cell>,
     pub(super) subroutines: Shared<Vec<ScriptFn>>,
+    pub(super) provenance: Option<Shared<ScriptProvenance>>,
+    pub(super) revision: Option<(Id, Revision)>,
 }
 
 impl Default for ScriptFn {
@@ -180,6 +197,8 @@ impl Default for ScriptFn {
             assembly: Shared::default(),
             closures: Vec::new(),
             subroutines: Default::default(),
+            provenance: None,
+            revision: None,
         }
     }
 }
@@ -217,6 +236,8 @@ impl ScriptFn {
             });
         }
 
+        let _scope = EvaluationScope::enter();
+
         match is_trusted() {
             true => self.execute::<true>()?,
             false => self.execute::<false>()?,
@@ -225,6 +246,73 @@ impl ScriptFn {
         Ok(Stack::pop_1(0))
     }
 
+    /// Evaluates the script the same way as the [run](Self::run) function,
+    /// but makes the specified `env` environment available to the script
+    /// through the built-in `env` variable.
+    ///
+    /// The environment is set for the duration of this evaluation only.
+    /// Nested evaluations on the same thread see their own environments, and
+    /// the outer environment is restored once the nested evaluation finishes.
+    ///
+    /// See [ScriptEnv] for details.
+    #[inline(always)]
+    pub fn run_with_env(&self, env: &ScriptEnv) -> RuntimeResult<Cell> {
+        let _env_scope = EnvScope::enter(env.clone());
+
+        self.run()
+    }
+
+    /// Evaluates the script the same way as the [run](Self::run) function,
+    /// but restricts the memory that the script-visible data objects created
+    /// during this evaluation may occupy at any moment to `limit` bytes.
+    ///
+    /// The limit accounts for the strings, arrays, struct entries, and
+    /// other objects that the Script Engine allocates on behalf of the script,
+    /// measured by their requested capacity rather than by the actual
+    /// allocator's behavior. When an object is freed during the evaluation,
+    /// its memory is credited back to the limit.
+    ///
+    /// If an allocation exceeds the limit, the evaluation fails with a
+    /// [RuntimeError::MemoryLimitExceeded] error that points to the
+    /// allocation site.
+    ///
+    /// The limit applies to the nested evaluations of the script functions
+    /// as well (e.g., invoked from the exported Rust functions). If a nested
+    /// evaluation sets its own limit, both limits apply to the nested
+    /// evaluation's allocations.
+    ///
+    /// ```rust
+    /// # use ad_astra::{
+    /// #     analysis::{ModuleRead, ScriptModule},
+    /// #     export,
+    /// #     lady_deirdre::analysis::TriggerHandle,
+    /// #     runtime::{RuntimeError, ScriptPackage},
+    /// # };
+    /// #
+    /// # #[export(package)]
+    /// # #[derive(Default)]
+    /// # struct Package;
+    /// #
+    /// let module = ScriptModule::new(
+    ///     Package::meta(),
+    ///     "let s = \"x\"; loop { s = [s, s]; }",
+    /// );
+    ///
+    /// let handle = TriggerHandle::new();
+    /// let read_guard = module.read(&handle, 1).unwrap();
+    /// let script_fn = read_guard.compile().unwrap();
+    ///
+    /// let result = script_fn.run_with_memory_limit(1024 * 1024);
+    ///
+    /// assert!(matches!(result, Err(RuntimeError::MemoryLimitExceeded { .. })));
+    /// ```
+    #[inline(always)]
+    pub fn run_with_memory_limit(&self, limit: usize) -> RuntimeResult<Cell> {
+        let _budget_scope = BudgetScope::enter(limit);
+
+        self.run()
+    }
+
     /// Sets the value of the `self` script variable, allowing the module's
     /// source code to read script input data.
     ///
@@ -271,6 +359,73 @@ impl ScriptFn {
 
         this
     }
+
+    /// Returns the names and the current values of the variables that this
+    /// script function captures from the outer scopes.
+    ///
+    /// The values are formatted using the [Cell::stringify] function, and each
+    /// value is truncated to `max_len` characters (with the "..." suffix) if it
+    /// exceeds this limit.
+    ///
+    /// This function is useful for debugging purposes, for example, to find
+    /// out why a script closure keeps a large object alive. If you have a
+    /// [Cell] that holds a script function, you can access the function using
+    /// the [Cell::borrow_ref] function:
+    /// `cell.borrow_ref::<ScriptFn>(Origin::nil())?.captures(80)`.
+    ///
+    /// The script's analyzer provides a static counterpart of this function:
+    /// [FnSymbol::captures](crate::analysis::symbols::FnSymbol::captures).
+    pub fn captures(&self, max_len: usize) -> Vec<(&str, String)> {
+        let assembly = self.assembly.as_ref();
+
+        let mut result = Vec::with_capacity(assembly.captures.len());
+
+        for (name, index) in &assembly.captures {
+            let mut value = match self.closures.get(*index) {
+                Some(cell) => cell.stringify(false),
+                None => Cell::nil().stringify(false),
+            };
+
+            if let Some((cut, _)) = value.char_indices().nth(max_len) {
+                value.truncate(cut);
+                value.push_str("...");
+            }
+
+            result.push((name.as_str(), value));
+        }
+
+        result
+    }
+
+    /// Returns the [provenance](ScriptProvenance) record of the script module
+    /// from which this function was compiled.
+    ///
+    /// The record is a copy of the module's provenance taken at the moment of
+    /// [compilation](crate::analysis::ModuleRead::compile), including the
+    /// [text hash](ScriptProvenance::text_hash) of the compiled source code.
+    /// The script closures created by this function share the same record.
+    ///
+    /// Returns None if the module did not have a provenance record.
+    #[inline(always)]
+    pub fn provenance(&self) -> Option<&ScriptProvenance> {
+        self.provenance.as_ref().map(Shared::as_ref)
+    }
+
+    /// Returns the [text revision](crate::analysis::ModuleRead::text_revision)
+    /// of the script module at the moment of this function's
+    /// [compilation](crate::analysis::ModuleRead::compile).
+    ///
+    /// The source code origins of this function refer to the text of this
+    /// revision. To carry the spans of this text across the later edits of the
+    /// module, use the
+    /// [bookmarks_to_current](crate::analysis::ModuleRead::bookmarks_to_current)
+    /// function.
+    ///
+    /// Returns None if the function was not compiled from a script module.
+    #[inline(always)]
+    pub fn text_revision(&self) -> Option<Revision> {
+        self.revision.map(|(_, revision)| revision)
+    }
 }
 
 /// A script function.
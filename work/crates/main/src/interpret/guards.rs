@@ -0,0 +1,416 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    fmt::{Display, Formatter},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        RwLock,
+    },
+};
+
+use ahash::AHashMap;
+use lady_deirdre::sync::Lazy;
+
+use crate::runtime::{Cell, Origin, RuntimeError, RuntimeResult, TypeMeta};
+
+type Guard = dyn Fn(&MemberRequest) -> Result<(), String> + Send + Sync + 'static;
+
+static GUARDS: Lazy<RwLock<AHashMap<&'static TypeMeta, Arc<Guard>>>> =
+    Lazy::new(|| RwLock::new(AHashMap::new()));
+
+// True if at least one access guard is installed, meaning that the Virtual
+// Machine cannot skip the access checks.
+static GUARDED: AtomicBool = AtomicBool::new(false);
+
+/// A kind of the script's access to a member of an object reported to the
+/// [access guard](set_access_guard).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MemberAccess {
+    /// The script reads the value of a field or a property: `foo.bar`.
+    Read,
+
+    /// The script assigns a value to a field or a property: `foo.bar = 10;`,
+    /// or a compound assignment such as `foo.bar += 1;`.
+    Write,
+
+    /// The script accesses a method of the object, usually to call it:
+    /// `foo.bar()`.
+    Invoke,
+}
+
+impl Display for MemberAccess {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read => formatter.write_str("read"),
+            Self::Write => formatter.write_str("write"),
+            Self::Invoke => formatter.write_str("invoke"),
+        }
+    }
+}
+
+/// A description of the script's access to a member of a guarded object
+/// reported to the [access guard](set_access_guard).
+#[derive(Clone, Copy)]
+#[non_exhaustive]
+pub struct MemberRequest<'a> {
+    /// A read-only handle to the object whose member is being accessed.
+    ///
+    /// Any attempt to borrow the object's data mutably through this handle
+    /// results in the [ReadOnly](RuntimeError::ReadOnly) error. The object's
+    /// data remains immutably borrowed while this handle (or its clone) is
+    /// alive, so the guard should not keep the clones of the handle after the
+    /// check.
+    pub object: &'a Cell,
+
+    /// The name of the accessed member.
+    pub member: &'a str,
+
+    /// The kind of the access.
+    pub access: MemberAccess,
+
+    /// The range in the script source code of the member access.
+    pub origin: Origin,
+}
+
+/// Installs a process-wide access guard of the members of the objects of
+/// the `ty` type, replacing the previously installed guard of this type.
+///
+/// The Virtual Machine calls the `guard` function before each script access to
+/// a member of such an object: before reading the fields and properties
+/// (`foo.bar`), before writing them (`foo.bar = 10;`), and before accessing
+/// the methods (`foo.bar()`). The guard function receives the object, the
+/// member's name, and the kind of access, and returns `Ok(())` to grant the
+/// access or `Err(reason)` to deny it.
+///
+/// A denial interrupts the script evaluation with the
+/// [AccessDenied](RuntimeError::AccessDenied) error that carries the
+/// `reason` string.
+///
+/// ```rust
+/// # use ad_astra::{
+/// #     analysis::{ModuleRead, ScriptModule},
+/// #     export,
+/// #     interpret::{remove_access_guard, set_access_guard, MemberAccess},
+/// #     lady_deirdre::analysis::TriggerHandle,
+/// #     runtime::{Origin, RuntimeError, ScriptPackage, ScriptType},
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// #[export]
+/// #[derive(Clone, Default)]
+/// pub struct Player {
+///     pub owner: usize,
+///     pub inventory: usize,
+/// }
+///
+/// #[export]
+/// pub fn player(owner: usize) -> Player {
+///     Player {
+///         owner,
+///         inventory: 10,
+///     }
+/// }
+///
+/// set_access_guard(Player::type_meta(), |request| {
+///     if request.member != "inventory" {
+///         return Ok(());
+///     }
+///
+///     let owner = request
+///         .object
+///         .clone()
+///         .borrow_ref::<Player>(Origin::nil())
+///         .map(|player| player.owner)
+///         .unwrap_or_default();
+///
+///     match owner == 1 {
+///         true => Ok(()),
+///         false => Err(String::from("the player belongs to another script")),
+///     }
+/// });
+///
+/// let run = |text: &str| {
+///     let module = ScriptModule::new(Package::meta(), text);
+///     let handle = TriggerHandle::new();
+///     let read_guard = module.read(&handle, 1).unwrap();
+///
+///     read_guard.compile().unwrap().run()
+/// };
+///
+/// assert!(run("return player(1).inventory;").is_ok());
+///
+/// let error = run("return player(2).inventory;").unwrap_err();
+///
+/// assert!(matches!(error, RuntimeError::AccessDenied { .. }));
+///
+/// remove_access_guard(Player::type_meta());
+/// ```
+///
+/// The guard receives a read-only handle to the object and cannot mutate
+/// the object during the check.
+///
+/// When no access guards are installed, the Virtual Machine skips the checks
+/// at virtually no cost. Otherwise, the member accesses are slightly slower,
+/// because the Virtual Machine looks up the guard of the object's type.
+pub fn set_access_guard(
+    ty: &'static TypeMeta,
+    guard: impl Fn(&MemberRequest) -> Result<(), String> + Send + Sync + 'static,
+) {
+    let guard = Arc::new(guard) as Arc<Guard>;
+
+    let mut guards = match GUARDS.write() {
+        Ok(guard) => guard,
+        Err(poison) => poison.into_inner(),
+    };
+
+    let previous = guards.insert(ty, guard);
+
+    GUARDED.store(true, Ordering::Release);
+
+    drop(guards);
+    drop(previous);
+}
+
+/// Removes the access guard of the `ty` type previously installed by the
+/// [set_access_guard] function.
+///
+/// If the type does not have a guard, this function does nothing.
+pub fn remove_access_guard(ty: &'static TypeMeta) {
+    let mut guards = match GUARDS.write() {
+        Ok(guard) => guard,
+        Err(poison) => poison.into_inner(),
+    };
+
+    let previous = guards.remove(ty);
+
+    GUARDED.store(!guards.is_empty(), Ordering::Release);
+
+    drop(guards);
+    drop(previous);
+}
+
+// Checks the script's access to the `member` of the `object` through
+// the access guard of the object's type, if any.
+#[inline(always)]
+pub(super) fn check_access(
+    object: &Cell,
+    member: &str,
+    access: MemberAccess,
+    origin: Origin,
+) -> RuntimeResult<()> {
+    if !GUARDED.load(Ordering::Acquire) {
+        return Ok(());
+    }
+
+    check_access_slow(object, member, access, origin)
+}
+
+#[inline(never)]
+fn check_access_slow(
+    object: &Cell,
+    member: &str,
+    access: MemberAccess,
+    origin: Origin,
+) -> RuntimeResult<()> {
+    let guard = {
+        let guards = match GUARDS.read() {
+            Ok(guard) => guard,
+            Err(poison) => poison.into_inner(),
+        };
+
+        match guards.get(object.ty()) {
+            Some(guard) => guard.clone(),
+            None => return Ok(()),
+        }
+    };
+
+    let read_only = object.clone().into_read_only(origin)?;
+
+    let result = guard(&MemberRequest {
+        object: &read_only,
+        member,
+        access,
+        origin,
+    });
+
+    drop(read_only);
+
+    match result {
+        Ok(()) => Ok(()),
+
+        Err(reason) => Err(RuntimeError::AccessDenied {
+            access_origin: origin,
+            receiver_origin: object.origin(),
+            receiver_type: object.ty(),
+            member: String::from(member),
+            access,
+            reason,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        interpret::{remove_access_guard, set_access_guard, MemberAccess},
+        runtime::{Cell, Origin, RuntimeError, ScriptPackage, ScriptType},
+    };
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct TestVault {
+        pub owner: usize,
+        pub gold: usize,
+    }
+
+    #[export]
+    impl TestVault {
+        pub fn total(&self) -> usize {
+            self.gold
+        }
+    }
+
+    #[export]
+    pub fn test_vault(owner: usize) -> TestVault {
+        TestVault { owner, gold: 10 }
+    }
+
+    fn run(text: &str) -> Result<Cell, RuntimeError> {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        read.compile().unwrap().run()
+    }
+
+    #[test]
+    fn test_access_guard() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        set_access_guard(TestVault::type_meta(), {
+            let requests = requests.clone();
+
+            move |request| {
+                requests
+                    .lock()
+                    .unwrap()
+                    .push((request.member.to_string(), request.access));
+
+                assert!(matches!(
+                    request
+                        .object
+                        .clone()
+                        .borrow_mut::<TestVault>(Origin::nil()),
+                    Err(RuntimeError::ReadOnly { .. }),
+                ));
+
+                let owner = request
+                    .object
+                    .clone()
+                    .borrow_ref::<TestVault>(Origin::nil())
+                    .unwrap()
+                    .owner;
+
+                match owner == 1 || request.member == "owner" {
+                    true => Ok(()),
+                    false => Err(String::from("foreign vault")),
+                }
+            }
+        });
+
+        let granted = run("let v = test_vault(1); v.gold += 5; return v.total();");
+
+        let read = run("let v = test_vault(2); return v.owner + v.gold;");
+        let write = run("let v = test_vault(2); v.gold = 0;");
+        let invoke = run("let v = test_vault(2); return v.total();");
+
+        remove_access_guard(TestVault::type_meta());
+
+        let unguarded = run("let v = test_vault(2); v.gold = 3; return v.total();");
+
+        assert_eq!(granted.unwrap().take::<usize>(Origin::nil()).unwrap(), 15);
+        assert_eq!(unguarded.unwrap().take::<usize>(Origin::nil()).unwrap(), 3);
+
+        for (result, expected) in [
+            (read, MemberAccess::Read),
+            (write, MemberAccess::Write),
+            (invoke, MemberAccess::Invoke),
+        ] {
+            let Err(error) = result else {
+                panic!("Access granted.");
+            };
+
+            let RuntimeError::AccessDenied {
+                member,
+                access,
+                reason,
+                ..
+            } = &error
+            else {
+                panic!("Unexpected error: {error}");
+            };
+
+            assert_eq!(*access, expected);
+            assert_eq!(reason, "foreign vault");
+            assert_eq!(
+                error.to_string(),
+                format!("{access} access to 'TestVault.{member}' denied: foreign vault"),
+            );
+        }
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                (String::from("gold"), MemberAccess::Write),
+                (String::from("total"), MemberAccess::Invoke),
+                (String::from("owner"), MemberAccess::Read),
+                (String::from("gold"), MemberAccess::Read),
+                (String::from("gold"), MemberAccess::Write),
+                (String::from("total"), MemberAccess::Invoke),
+            ],
+        );
+    }
+}
@@ -0,0 +1,492 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    any::TypeId,
+    cell::UnsafeCell,
+    collections::VecDeque,
+    hash::{BuildHasher, Hash, Hasher},
+};
+
+use ahash::{AHashMap, RandomState};
+
+use crate::runtime::{Arg, Cell, Origin, RuntimeResult};
+
+const LIMIT: usize = 1_024;
+
+thread_local! {
+    static MEMO: UnsafeCell<Memo> = const { UnsafeCell::new(Memo::new()) };
+}
+
+/// Clears the cache of the memoized function results for the current OS
+/// thread.
+///
+/// The functions exported with the `#[export(memoize)]` attribute cache their
+/// results for the duration of the script evaluation (see
+/// [ScriptFn::run](crate::interpret::ScriptFn::run)). The cache is cleared
+/// automatically when the evaluation ends, but an exported function may call
+/// this function to drop the cached results during the evaluation (e.g., if
+/// the host state that the memoized functions depend on has been changed).
+#[inline(always)]
+pub fn clear_memo_cache() {
+    let entries = MEMO.with(|memo| {
+        // Safety: Access is localized.
+        let memo = unsafe { &mut *memo.get() };
+
+        memo.entries.take()
+    });
+
+    drop(entries);
+}
+
+// Invokes the `compute` function, or returns a deep clone of the previous
+// result of the function with equal arguments.
+//
+// The `F` type uniquely identifies the memoized function.
+//
+// The results are only cached during the script evaluation, and only if all
+// arguments are hashable, comparable, and cloneable. The cache keeps a copy
+// of the arguments of each cached call to compare them with the arguments of
+// the subsequent calls, so the hash collisions do not produce false hits.
+pub fn memoize<F: 'static>(
+    origin: Origin,
+    arguments: &mut [Arg],
+    compute: impl FnOnce(&mut [Arg]) -> RuntimeResult<Cell>,
+) -> RuntimeResult<Cell> {
+    if !Memo::active() {
+        return compute(arguments);
+    }
+
+    let function = TypeId::of::<F>();
+
+    let Some(hash) = Memo::hash(function, origin, arguments) else {
+        return compute(arguments);
+    };
+
+    for candidate in Memo::candidates(function, hash) {
+        if !Memo::matches(origin, &candidate.keys, arguments) {
+            continue;
+        }
+
+        if let Ok(result) = Memo::deep_clone(origin, candidate.result) {
+            return Ok(result);
+        }
+    }
+
+    let Some(keys) = Memo::keys(origin, arguments) else {
+        return compute(arguments);
+    };
+
+    let result = compute(arguments)?;
+
+    if Memo::is_cloneable(&result) {
+        if let Ok(copy) = Memo::deep_clone(origin, result.clone()) {
+            Memo::insert(
+                hash,
+                MemoEntry {
+                    function,
+                    keys,
+                    result: copy,
+                },
+            );
+        }
+    }
+
+    Ok(result)
+}
+
+// A guard object that denotes the script evaluation scope on the current
+// thread. The memoization cache is cleared when the outermost scope ends.
+pub(super) struct EvaluationScope;
+
+impl Drop for EvaluationScope {
+    fn drop(&mut self) {
+        let entries = MEMO.with(|memo| {
+            // Safety: Access is localized.
+            let memo = unsafe { &mut *memo.get() };
+
+            memo.depth = memo.depth.saturating_sub(1);
+
+            match memo.depth == 0 {
+                true => memo.entries.take(),
+                false => None,
+            }
+        });
+
+        // The cached results are dropped outside of the cache access, because
+        // dropping of the data may call the host's finalizers.
+        drop(entries);
+    }
+}
+
+impl EvaluationScope {
+    #[inline(always)]
+    pub(super) fn enter() -> Self {
+        MEMO.with(|memo| {
+            // Safety: Access is localized.
+            let memo = unsafe { &mut *memo.get() };
+
+            memo.depth += 1;
+        });
+
+        Self
+    }
+}
+
+#[derive(Clone)]
+struct MemoEntry {
+    function: TypeId,
+    keys: Vec<Cell>,
+    result: Cell,
+}
+
+#[derive(Default)]
+struct MemoEntries {
+    buckets: AHashMap<u64, Vec<MemoEntry>>,
+
+    // The hashes of the cached entries in the order of insertion.
+    order: VecDeque<u64>,
+}
+
+impl MemoEntries {
+    // Removes the oldest cached entry.
+    fn evict(&mut self) -> Option<MemoEntry> {
+        let hash = self.order.pop_front()?;
+        let bucket = self.buckets.get_mut(&hash)?;
+
+        let entry = bucket.remove(0);
+
+        if bucket.is_empty() {
+            let _ = self.buckets.remove(&hash);
+        }
+
+        Some(entry)
+    }
+}
+
+struct Memo {
+    depth: usize,
+    entries: Option<MemoEntries>,
+}
+
+impl Memo {
+    #[inline(always)]
+    const fn new() -> Self {
+        Self {
+            depth: 0,
+            entries: None,
+        }
+    }
+
+    #[inline(always)]
+    fn active() -> bool {
+        MEMO.with(|memo| {
+            // Safety: Access is localized.
+            let memo = unsafe { &*memo.get() };
+
+            memo.depth > 0
+        })
+    }
+
+    // The candidates are copied out of the cache, because the comparison of
+    // the arguments may call the host's code.
+    #[inline(always)]
+    fn candidates(function: TypeId, hash: u64) -> Vec<MemoEntry> {
+        MEMO.with(|memo| {
+            // Safety: Access is localized.
+            let memo = unsafe { &*memo.get() };
+
+            let Some(bucket) = memo
+                .entries
+                .as_ref()
+                .and_then(|entries| entries.buckets.get(&hash))
+            else {
+                return Vec::new();
+            };
+
+            bucket
+                .iter()
+                .filter(|entry| entry.function == function)
+                .cloned()
+                .collect()
+        })
+    }
+
+    #[inline(always)]
+    fn insert(hash: u64, entry: MemoEntry) {
+        let evicted = MEMO.with(move |memo| {
+            // Safety: Access is localized.
+            let memo = unsafe { &mut *memo.get() };
+
+            let entries = memo.entries.get_or_insert_with(MemoEntries::default);
+
+            let evicted = match entries.order.len() >= LIMIT {
+                true => entries.evict(),
+                false => None,
+            };
+
+            entries.buckets.entry(hash).or_default().push(entry);
+            entries.order.push_back(hash);
+
+            evicted
+        });
+
+        // The evicted result is dropped outside of the cache access, because
+        // dropping of the data may call the host's finalizers.
+        drop(evicted);
+    }
+
+    fn hash(function: TypeId, origin: Origin, arguments: &[Arg]) -> Option<u64> {
+        let mut hasher = RandomState::with_seeds(
+            0x243F6A8885A308D3,
+            0x13198A2E03707344,
+            0xA4093822299F31D0,
+            0x082EFA98EC4E6C89,
+        )
+        .build_hasher();
+
+        function.hash(&mut hasher);
+        arguments.len().hash(&mut hasher);
+
+        for arg in arguments {
+            if arg.data.is_nil() {
+                false.hash(&mut hasher);
+                continue;
+            }
+
+            let ty = arg.data.ty();
+            let prototype = ty.prototype();
+
+            if !prototype.implements_hash() || !prototype.implements_partial_eq() {
+                return None;
+            }
+
+            true.hash(&mut hasher);
+            ty.hash(&mut hasher);
+
+            arg.data
+                .clone()
+                .into_object()
+                .hash(origin, arg.origin, &mut hasher)
+                .ok()?;
+        }
+
+        Some(hasher.finish())
+    }
+
+    // Copies the arguments of the call, such that the later changes of the
+    // script data do not affect the cached entry.
+    fn keys(origin: Origin, arguments: &[Arg]) -> Option<Vec<Cell>> {
+        arguments
+            .iter()
+            .map(|arg| {
+                if !Self::is_cloneable(&arg.data) {
+                    return None;
+                }
+
+                Self::deep_clone(origin, arg.data.clone()).ok()
+            })
+            .collect()
+    }
+
+    fn matches(origin: Origin, keys: &[Cell], arguments: &[Arg]) -> bool {
+        if keys.len() != arguments.len() {
+            return false;
+        }
+
+        for (key, arg) in keys.iter().zip(arguments) {
+            match (key.is_nil(), arg.data.is_nil()) {
+                (true, true) => continue,
+                (false, false) => (),
+                _ => return false,
+            }
+
+            if key.ty() != arg.data.ty() {
+                return false;
+            }
+
+            let equal = key.clone().into_object().partial_eq(
+                origin,
+                origin,
+                Arg {
+                    origin: arg.origin,
+                    data: arg.data.clone(),
+                },
+            );
+
+            if !matches!(equal, Ok(true)) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    #[inline(always)]
+    fn is_cloneable(cell: &Cell) -> bool {
+        cell.is_nil() || cell.ty().prototype().implements_clone()
+    }
+
+    #[inline(always)]
+    fn deep_clone(origin: Origin, cell: Cell) -> RuntimeResult<Cell> {
+        if cell.is_nil() {
+            return Ok(Cell::nil());
+        }
+
+        cell.into_object().clone(origin, origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{any::TypeId, cell::Cell as StdCell};
+
+    use crate::{
+        analysis::symbols::tests::TestPackage,
+        export,
+        interpret::memo::{memoize, EvaluationScope, Memo, MemoEntry, LIMIT, MEMO},
+        runtime::{Arg, Cell, Origin},
+    };
+
+    thread_local! {
+        static CALLS: StdCell<usize> = const { StdCell::new(0) };
+    }
+
+    fn calls() -> usize {
+        CALLS.with(|calls| calls.replace(0))
+    }
+
+    #[export(memoize)]
+    pub fn test_memo_square(x: usize) -> usize {
+        CALLS.with(|calls| calls.set(calls.get() + 1));
+
+        x * x
+    }
+
+    #[test]
+    fn test_memo_hits() {
+        let _ = calls();
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "return test_memo_square(3) + test_memo_square(3) + test_memo_square(4);",
+            ),
+            34,
+        );
+
+        assert_eq!(calls(), 2);
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_memo_square(3);"),
+            9,
+        );
+
+        assert_eq!(calls(), 1);
+    }
+
+    #[test]
+    fn test_memo_collisions() {
+        struct Function;
+
+        let _scope = EvaluationScope::enter();
+
+        let arg = |value: usize| Arg {
+            origin: Origin::nil(),
+            data: Cell::give(Origin::nil(), value).unwrap(),
+        };
+
+        // Forges a cached entry with the same hash but different arguments.
+        let hash = Memo::hash(TypeId::of::<Function>(), Origin::nil(), &[arg(1)]).unwrap();
+
+        Memo::insert(
+            hash,
+            MemoEntry {
+                function: TypeId::of::<Function>(),
+                keys: Vec::from([Cell::give(Origin::nil(), 2usize).unwrap()]),
+                result: Cell::give(Origin::nil(), 20usize).unwrap(),
+            },
+        );
+
+        let compute = |value: usize| move |_: &mut [Arg]| Cell::give(Origin::nil(), value);
+
+        let result = memoize::<Function>(Origin::nil(), &mut [arg(1)], compute(10)).unwrap();
+
+        assert_eq!(result.take::<usize>(Origin::nil()).unwrap(), 10);
+
+        let result = memoize::<Function>(Origin::nil(), &mut [arg(1)], compute(30)).unwrap();
+
+        assert_eq!(result.take::<usize>(Origin::nil()).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_memo_eviction() {
+        let _ = calls();
+
+        let _scope = EvaluationScope::enter();
+
+        let entries = || {
+            MEMO.with(|memo| {
+                // Safety: Access is localized.
+                let memo = unsafe { &*memo.get() };
+
+                memo.entries
+                    .as_ref()
+                    .map(|entries| entries.order.len())
+                    .unwrap_or_default()
+            })
+        };
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "for i in 0..1100 { test_memo_square(i); } return test_memo_square(1099);",
+            ),
+            1099 * 1099,
+        );
+
+        assert_eq!(calls(), 1100);
+        assert_eq!(entries(), LIMIT);
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_memo_square(0);"),
+            0,
+        );
+
+        assert_eq!(calls(), 1);
+        assert_eq!(entries(), LIMIT);
+    }
+}
@@ -33,13 +33,38 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 mod assembly;
+pub(crate) mod blocking;
 mod compiler;
 mod engine;
+mod env;
 mod function;
+mod guards;
+pub(crate) mod memo;
+mod observer;
 mod stack;
+mod subtask;
+mod watch;
+mod writes;
 
-pub(crate) use crate::interpret::{assembly::*, stack::StackDepth};
+pub(crate) use crate::interpret::{
+    assembly::*,
+    env::{ScriptEnvType, ENV_IDENT},
+    stack::StackDepth,
+};
 pub use crate::interpret::{
-    engine::{remove_runtime_hook, set_runtime_hook},
+    engine::{
+        index_policy,
+        set_index_policy,
+        set_strict_assignments,
+        strict_assignments,
+        IndexPolicy,
+    },
+    env::ScriptEnv,
     function::ScriptFn,
+    guards::{remove_access_guard, set_access_guard, MemberAccess, MemberRequest},
+    memo::clear_memo_cache,
+    observer::{remove_runtime_hook, set_runtime_hook, Instrumented, VarWrite, VmCall, VmObserver},
+    subtask::{Subtask, SubtaskExecutor, SubtaskHandle, SubtaskToken},
+    watch::{WatchHit, Watchpoints},
+    writes::{remove_write_observer, set_write_observer, FieldWrite},
 };
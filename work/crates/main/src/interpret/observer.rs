@@ -0,0 +1,552 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    mem::{replace, take, transmute},
+};
+
+use crate::{
+    interpret::{FieldWrite, ScriptFn},
+    runtime::{Cell, InvocationMeta, Origin, RuntimeError, RuntimeResult},
+};
+
+thread_local! {
+    static OBSERVERS: UnsafeCell<Observers> = const { UnsafeCell::new(Observers::new()) };
+}
+
+/// A middleware that observes the events of the Virtual Machine during script
+/// evaluation.
+///
+/// You can install one or several observers for the duration of the script
+/// evaluation using the [ScriptFn::instrument] function:
+///
+/// ```rust
+/// # use ad_astra::{
+/// #     analysis::{ModuleRead, ScriptModule},
+/// #     export,
+/// #     interpret::{VmCall, VmObserver},
+/// #     lady_deirdre::analysis::TriggerHandle,
+/// #     runtime::{Origin, ScriptPackage},
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// #[derive(Default)]
+/// struct Counter {
+///     calls: usize,
+///     iterations: usize,
+/// }
+///
+/// impl VmObserver for Counter {
+///     fn enter(&mut self, _call: &VmCall) {
+///         self.calls += 1;
+///     }
+///
+///     fn iterate(&mut self, _origin: &Origin) {
+///         self.iterations += 1;
+///     }
+/// }
+///
+/// let module = ScriptModule::new(
+///     Package::meta(),
+///     "let f = fn(x) { return x; }; for i in 0..3 { f(i); }",
+/// );
+///
+/// let handle = TriggerHandle::new();
+/// let read_guard = module.read(&handle, 1).unwrap();
+/// let script_fn = read_guard.compile().unwrap();
+///
+/// let mut counter = Counter::default();
+///
+/// script_fn.instrument(&mut counter).run().unwrap();
+///
+/// // The module's top-level code and three calls of the "f" function.
+/// assert_eq!(counter.calls, 4);
+/// assert_eq!(counter.iterations, 3);
+/// ```
+///
+/// All functions of this trait have default implementations that do nothing,
+/// so an observer implements only the events it is interested in.
+///
+/// The observers are notified on the thread that evaluates the script,
+/// including the nested evaluations of script functions invoked from the
+/// exported Rust functions. If the observer's function evaluates a script
+/// itself, the events of this nested evaluation are not reported to the
+/// observers.
+///
+/// When at least one observer or the
+/// [runtime hook](crate::interpret::set_runtime_hook) is installed on the
+/// current thread, the scripts are evaluated slightly slower than without
+/// observers.
+pub trait VmObserver {
+    /// Called before each assembly command.
+    ///
+    /// The `origin` argument roughly points to the source code about to be
+    /// evaluated.
+    ///
+    /// If this function returns false, the Virtual Machine interrupts the
+    /// evaluation with a [RuntimeError::Interrupted] error, and the rest of
+    /// the observers are not notified about this step.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn step(&mut self, origin: &Origin) -> bool {
+        true
+    }
+
+    /// Called when the Virtual Machine enters a script function or invokes an
+    /// exported Rust function.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn enter(&mut self, call: &VmCall) {}
+
+    /// Called when the function previously reported by the [enter](Self::enter)
+    /// function returns, either successfully or with an error.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn exit(&mut self, call: &VmCall) {}
+
+    /// Called each time the `for` statement takes the next value of the
+    /// iterated range, and each time the body of the `loop` statement
+    /// repeats.
+    ///
+    /// The `origin` argument points to the iterated range of the `for`
+    /// statement. For the `loop` statement, the `origin` roughly points to
+    /// the end of the loop's body.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn iterate(&mut self, origin: &Origin) {}
+
+    /// Called when the script creates a new data object from a string literal,
+    /// a struct literal, a range, an array constructor, or a function
+    /// declaration.
+    ///
+    /// The `value` argument is a handle to the created object. Cloning the
+    /// [Cell] is cheap, but it prolongs the lifetime of the object.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn allocate(&mut self, origin: &Origin, value: &Cell) {}
+
+    /// Called after the script assigns a new value to a local variable
+    /// (`x = 10;`, or a compound assignment such as `x += 1;`).
+    ///
+    /// The initialization of the variable in the let-statement (`let x = 10;`)
+    /// is not reported. The assignments to the variables captured by
+    /// a script function from its outer functions are not reported either.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn write_var(&mut self, write: &VarWrite) {}
+
+    /// Called after the script assigns a new value to a component of an
+    /// exported object (`foo.bar = 10;`, or a compound assignment such as
+    /// `foo.bar += 1;`).
+    ///
+    /// The assignments to the properties are reported only if the property's
+    /// setter function is marked with the `#[export(observed)]` attribute.
+    /// See [set_write_observer](crate::interpret::set_write_observer) for
+    /// details.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn write_field(&mut self, write: &FieldWrite) {}
+
+    /// Called when the evaluation of a script function ends with an error.
+    ///
+    /// The error is reported once, by the script function in which it was
+    /// raised, even though it propagates through the callers of this function.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn raise(&mut self, error: &RuntimeError) {}
+}
+
+/// A description of a function call reported to the [VmObserver].
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum VmCall {
+    /// The Virtual Machine enters a script function, or the top-level code of
+    /// the script module.
+    Script {
+        /// The source code range of the function's declaration.
+        origin: Origin,
+    },
+
+    /// The script invokes an exported Rust function (or any other object that
+    /// is not a script function).
+    Exported {
+        /// The source code range of the invocation expression.
+        origin: Origin,
+
+        /// The signature of the invoked function, if available.
+        meta: Option<&'static InvocationMeta>,
+    },
+}
+
+/// A description of a script assignment to a local variable reported to the
+/// [VmObserver::write_var] function.
+#[derive(Clone, Copy)]
+#[non_exhaustive]
+pub struct VarWrite<'a> {
+    /// The name of the assigned variable.
+    pub name: &'a str,
+
+    /// The range in the script source code of the variable's name in its
+    /// declaration.
+    ///
+    /// This range is equal to the
+    /// [VarSymbol::origin](crate::analysis::symbols::VarSymbol::origin) of
+    /// the variable.
+    pub decl: Origin,
+
+    /// The variable's value before the assignment.
+    ///
+    /// If the assignment modifies the value in place (e.g., `x += 1;`),
+    /// the old value is a copy created through the
+    /// [Clone operator](crate::runtime::Prototype::implements_clone) of
+    /// the value's type. If the copy cannot be created, the value is
+    /// [Nil](Cell::nil).
+    pub old_value: &'a Cell,
+
+    /// The variable's value after the assignment.
+    pub new_value: &'a Cell,
+
+    /// The range in the script source code of the assignment expression.
+    pub origin: Origin,
+}
+
+/// A [ScriptFn] with the [observers](VmObserver) installed for the duration
+/// of its evaluation.
+///
+/// Created by the [ScriptFn::instrument] function.
+pub struct Instrumented<'a> {
+    function: &'a ScriptFn,
+    observers: Vec<&'a mut dyn VmObserver>,
+}
+
+impl ScriptFn {
+    /// Installs the `observer` for the duration of the script evaluation.
+    ///
+    /// The function returns an [Instrumented] object, through which you can
+    /// stack more observers and then [run](Instrumented::run) the script.
+    ///
+    /// The observers are borrowed for the evaluation time, so you can read
+    /// the data they have collected once the evaluation ends.
+    ///
+    /// See [VmObserver] for details.
+    #[inline(always)]
+    pub fn instrument<'a>(&'a self, observer: &'a mut dyn VmObserver) -> Instrumented<'a> {
+        Instrumented {
+            function: self,
+            observers: vec![observer],
+        }
+    }
+}
+
+impl<'a> Instrumented<'a> {
+    /// Installs one more observer.
+    ///
+    /// The observers are notified about each event in the order in which
+    /// they were installed.
+    #[inline(always)]
+    pub fn instrument(mut self, observer: &'a mut dyn VmObserver) -> Self {
+        self.observers.push(observer);
+
+        self
+    }
+
+    /// Evaluates the script with the installed observers.
+    ///
+    /// The observers of the outer evaluations on the current thread (if any)
+    /// are notified as well.
+    ///
+    /// See [ScriptFn::run] for details.
+    pub fn run(mut self) -> RuntimeResult<Cell> {
+        let _scope = ObservationScope::enter(&mut self.observers);
+
+        self.function.run()
+    }
+}
+
+pub(super) struct ObservationScope<'a> {
+    base: usize,
+    _observers: PhantomData<&'a mut ()>,
+}
+
+impl<'a> Drop for ObservationScope<'a> {
+    fn drop(&mut self) {
+        OBSERVERS.with(|observers| {
+            // Safety: Access is localized.
+            let observers = unsafe { &mut *observers.get() };
+
+            observers.stack.truncate(self.base);
+        })
+    }
+}
+
+impl<'a> ObservationScope<'a> {
+    pub(super) fn enter(installed: &'a mut [&mut dyn VmObserver]) -> Self {
+        OBSERVERS.with(|observers| {
+            // Safety: Access is localized.
+            let observers = unsafe { &mut *observers.get() };
+
+            let base = observers.stack.len();
+
+            for observer in installed {
+                let observer = &mut **observer as *mut dyn VmObserver;
+
+                // Safety: The observers are mutably borrowed by the scope
+                //         object, which removes them from the stack on drop.
+                let observer = unsafe {
+                    transmute::<*mut dyn VmObserver, *mut (dyn VmObserver + 'static)>(observer)
+                };
+
+                observers.stack.push(observer);
+            }
+
+            Self {
+                base,
+                _observers: PhantomData,
+            }
+        })
+    }
+}
+
+/// Sets a script evaluation hook for the current OS thread.
+///
+/// The provided `hook` function will be called on each Ad Astra assembly
+/// instruction, allowing the hook function to interrupt the script's execution
+/// by returning `false`.
+///
+/// The hook function receives an [Origin] object as an argument, which roughly
+/// indicates the Script or Rust source code text range about to be evaluated.
+///
+/// By default, the current OS thread does not have a configured runtime hook,
+/// meaning scripts will be evaluated until the end of the script's control
+/// flow. Setting up the runtime hook gives you control over script evaluation
+/// but generally slows down the script's computational performance.
+///
+/// The hook is a thread-wide [VmObserver] that only observes the
+/// [steps](VmObserver::step) of the evaluation. It is notified before the
+/// observers installed by the [ScriptFn::instrument] function.
+///
+/// For additional information, see the [ScriptFn] documentation.
+#[inline(always)]
+pub fn set_runtime_hook(hook: impl Fn(&Origin) -> bool + 'static) {
+    struct RuntimeHook<F>(F);
+
+    impl<F: Fn(&Origin) -> bool> VmObserver for RuntimeHook<F> {
+        #[inline(always)]
+        fn step(&mut self, origin: &Origin) -> bool {
+            (self.0)(origin)
+        }
+    }
+
+    OBSERVERS.with(move |observers| {
+        // Safety: Access is localized.
+        let observers = unsafe { &mut *observers.get() };
+
+        observers.hook = Some(Box::new(RuntimeHook(hook)));
+    })
+}
+
+/// Unsets the script evaluation hook previously set by the [set_runtime_hook]
+/// function.
+///
+/// If a hook has not been configured for the current OS thread, this function
+/// does nothing.
+#[inline(always)]
+pub fn remove_runtime_hook() {
+    let hook = OBSERVERS.with(move |observers| {
+        // Safety: Access is localized.
+        let observers = unsafe { &mut *observers.get() };
+
+        take(&mut observers.hook)
+    });
+
+    drop(hook);
+}
+
+// Returns true if the current thread does not have any observers (including
+// the runtime hook), meaning that the Virtual Machine may skip
+// the notifications.
+#[inline(always)]
+pub(super) fn is_trusted() -> bool {
+    OBSERVERS.with(|observers| {
+        // Safety: Access is localized.
+        let observers = unsafe { &*observers.get() };
+
+        observers.is_empty()
+    })
+}
+
+#[inline(always)]
+pub(super) fn notify_step(origin: &Origin) -> bool {
+    Observers::notify(|observer| observer.step(origin))
+}
+
+#[inline(always)]
+pub(super) fn notify_enter(call: &VmCall) {
+    let _ = Observers::notify(|observer| {
+        observer.enter(call);
+        true
+    });
+}
+
+#[inline(always)]
+pub(super) fn notify_exit(call: &VmCall) {
+    let _ = Observers::notify(|observer| {
+        observer.exit(call);
+        true
+    });
+}
+
+#[inline(always)]
+pub(super) fn notify_iterate(origin: &Origin) {
+    let _ = Observers::notify(|observer| {
+        observer.iterate(origin);
+        true
+    });
+}
+
+#[inline(always)]
+pub(super) fn notify_allocate(origin: &Origin, value: &Cell) {
+    let _ = Observers::notify(|observer| {
+        observer.allocate(origin, value);
+        true
+    });
+}
+
+#[inline(always)]
+pub(super) fn notify_write_var(write: &VarWrite) {
+    let _ = Observers::notify(|observer| {
+        observer.write_var(write);
+        true
+    });
+}
+
+#[inline(always)]
+pub(super) fn notify_write_field(write: &FieldWrite) {
+    let _ = Observers::notify(|observer| {
+        observer.write_field(write);
+        true
+    });
+}
+
+#[inline(always)]
+pub(super) fn notify_raise(error: &RuntimeError) {
+    let _ = Observers::notify(|observer| {
+        observer.raise(error);
+        true
+    });
+}
+
+#[derive(Default)]
+struct Observers {
+    hook: Option<Box<dyn VmObserver>>,
+    stack: Vec<*mut dyn VmObserver>,
+}
+
+impl Observers {
+    #[inline(always)]
+    const fn new() -> Self {
+        Self {
+            hook: None,
+            stack: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        self.hook.is_none() && self.stack.is_empty()
+    }
+
+    // Notifies each observer until the `event` function returns false.
+    //
+    // The observers are detached from the thread for the time of
+    // the notification, such that the observer functions cannot access them
+    // through nested script evaluations.
+    fn notify(mut event: impl FnMut(&mut dyn VmObserver) -> bool) -> bool {
+        let detached = OBSERVERS.with(|observers| {
+            // Safety: Access is localized.
+            let observers = unsafe { &mut *observers.get() };
+
+            take(observers)
+        });
+
+        let mut detached = Detached(detached);
+
+        if let Some(hook) = &mut detached.0.hook {
+            if !event(hook.as_mut()) {
+                return false;
+            }
+        }
+
+        for observer in &detached.0.stack {
+            // Safety: The observer is borrowed by the ObservationScope that
+            //         keeps it in the stack.
+            let observer = unsafe { &mut **observer };
+
+            if !event(observer) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+// Attaches the observers back to the thread on drop.
+struct Detached(Observers);
+
+impl Drop for Detached {
+    fn drop(&mut self) {
+        let detached = take(&mut self.0);
+
+        let replaced = OBSERVERS.with(move |observers| {
+            // Safety: Access is localized.
+            let observers = unsafe { &mut *observers.get() };
+
+            let mut replaced = replace(observers, detached);
+
+            if let Some(hook) = take(&mut replaced.hook) {
+                observers.hook = Some(hook);
+            }
+
+            replaced
+        });
+
+        drop(replaced);
+    }
+}
@@ -97,6 +97,22 @@ impl Stack {
         })
     }
 
+    #[inline(always)]
+    pub(super) fn reuse_frame(begin: StackDepth, arity: StackDepth) {
+        STACK.with(|stack| {
+            // Safety: Access is localized.
+            let stack = unsafe { &mut *stack.get() };
+
+            let end = stack.cells.len().checked_sub(arity).unwrap_or_default();
+
+            if end <= begin {
+                return;
+            }
+
+            let _ = stack.cells.drain(begin..end);
+        })
+    }
+
     #[inline(always)]
     pub(super) fn push_nil() {
         STACK.with(move |stack| {
@@ -189,6 +205,21 @@ impl Stack {
         })
     }
 
+    #[inline(always)]
+    pub(super) fn peek(frame_begin: StackDepth, mut depth: StackDepth) -> Cell {
+        depth += frame_begin;
+
+        STACK.with(move |stack| {
+            // Safety: Access is localized.
+            let stack = unsafe { &*stack.get() };
+
+            match stack.cells.get(depth) {
+                Some(cell) => cell.clone(),
+                None => Cell::nil(),
+            }
+        })
+    }
+
     #[inline(always)]
     pub(super) fn lift(frame_begin: StackDepth, mut depth: StackDepth) {
         depth += frame_begin;
@@ -0,0 +1,635 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+        Condvar,
+        Mutex,
+    },
+};
+
+use crate::{
+    interpret::{
+        memo::EvaluationScope,
+        observer::{is_trusted, ObservationScope},
+        stack::Stack,
+        ScriptFn,
+        VmObserver,
+    },
+    runtime::{Cell, Origin, RuntimeError, RuntimeResult},
+};
+
+thread_local! {
+    static TASKS: RefCell<Vec<Arc<TaskState>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A structured sub-evaluation of a [ScriptFn].
+///
+/// The Subtask API allows the host's exported functions to evaluate other
+/// compiled scripts (e.g., per-entity behaviors) on behalf of the current
+/// script evaluation, with a controlled budget of Virtual Machine instructions.
+///
+/// A subtask created by the [Subtask::spawn] function from within another
+/// subtask's evaluation (e.g., inside an exported function called by the
+/// script) becomes a child of that subtask:
+///
+///  - The child's budget cannot exceed the parent's remaining budget, and the
+///    instructions evaluated by the child are charged to the parent as well.
+///  - Cancellation of the parent interrupts all of its children.
+///
+/// The subtask can be evaluated either [inline](Subtask::run) on the current
+/// thread (nested evaluations are re-entrant) or on a host-provided
+/// [executor](SubtaskExecutor) using the [Subtask::run_on] function.
+///
+/// ```rust
+/// # use ad_astra::{
+/// #     analysis::{ModuleRead, ScriptModule},
+/// #     export,
+/// #     interpret::Subtask,
+/// #     lady_deirdre::analysis::TriggerHandle,
+/// #     runtime::{ScriptPackage, RuntimeError},
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// let module = ScriptModule::new(Package::meta(), "for i in 0..1000 {}");
+///
+/// let handle = TriggerHandle::new();
+/// let module_read = module.read(&handle, 1).unwrap();
+/// let script_fn = module_read.compile().unwrap();
+///
+/// let result = Subtask::spawn(&script_fn, Vec::new(), Some(100)).run();
+///
+/// assert!(matches!(result, Err(RuntimeError::Interrupted { .. })));
+/// ```
+pub struct Subtask {
+    function: ScriptFn,
+    arguments: Vec<Cell>,
+    state: Arc<TaskState>,
+}
+
+impl Subtask {
+    /// Creates a subtask that evaluates the `function` with the specified
+    /// `arguments`.
+    ///
+    /// The `budget` parameter limits the number of the Virtual Machine
+    /// instructions the subtask is allowed to evaluate. If the budget is
+    /// exhausted, the evaluation ends with the [RuntimeError::Interrupted]
+    /// error. The None value means that the subtask's budget is limited by the
+    /// parent's budget only.
+    ///
+    /// If this function is called during the evaluation of another subtask on
+    /// the current thread, the new subtask becomes a child of that subtask,
+    /// and its budget is clamped to the parent's remaining budget.
+    ///
+    /// The function does not start the evaluation. Use the [run](Self::run)
+    /// or the [run_on](Self::run_on) functions to evaluate the subtask.
+    pub fn spawn(function: &ScriptFn, arguments: Vec<Cell>, budget: Option<usize>) -> Self {
+        let parent = TASKS.with(|tasks| tasks.borrow().last().cloned());
+
+        let mut remaining = budget.unwrap_or(usize::MAX);
+
+        if let Some(parent) = &parent {
+            remaining = remaining.min(parent.remaining());
+        }
+
+        Self {
+            function: function.clone(),
+            arguments,
+            state: Arc::new(TaskState {
+                remaining: AtomicUsize::new(remaining),
+                cancelled: AtomicBool::new(false),
+                parent,
+            }),
+        }
+    }
+
+    /// Returns a token of the subtask evaluated on the current thread (if
+    /// any).
+    ///
+    /// Through this token, an exported function called by the subtask's
+    /// script can check the remaining budget or cancel the subtask.
+    #[inline(always)]
+    pub fn current() -> Option<SubtaskToken> {
+        TASKS.with(|tasks| tasks.borrow().last().cloned().map(SubtaskToken))
+    }
+
+    /// Returns a token through which the subtask can be cancelled.
+    #[inline(always)]
+    pub fn token(&self) -> SubtaskToken {
+        SubtaskToken(self.state.clone())
+    }
+
+    /// Evaluates the subtask on the current thread and returns the
+    /// evaluation result.
+    ///
+    /// This function is re-entrant: an exported function called by the
+    /// subtask's script may spawn and run nested subtasks.
+    ///
+    /// If the subtask's budget is exhausted, or the subtask (or any of its
+    /// parents) has been cancelled, the function returns
+    /// the [RuntimeError::Interrupted] error.
+    #[inline(always)]
+    pub fn run(self) -> RuntimeResult<Cell> {
+        self.evaluate(false)
+    }
+
+    /// Submits the subtask to the host-provided `executor` and returns a
+    /// handle through which the caller can await the evaluation result.
+    ///
+    /// The instructions evaluated by the subtask are charged to the budgets
+    /// of all of its parents, and cancellation of any parent interrupts the
+    /// subtask, regardless of the thread on which the executor evaluates it.
+    pub fn run_on(self, executor: &impl SubtaskExecutor) -> SubtaskHandle {
+        let token = self.token();
+        let result = Arc::new((Mutex::new(None), Condvar::new()));
+
+        let handle = SubtaskHandle {
+            token,
+            result: result.clone(),
+        };
+
+        executor.execute(Box::new(move || {
+            let outcome = self.evaluate(true);
+
+            let (slot, ready) = result.as_ref();
+
+            let mut slot = slot.lock().unwrap_or_else(|poison| poison.into_inner());
+
+            *slot = Some(outcome);
+
+            ready.notify_all();
+        }));
+
+        handle
+    }
+
+    fn evaluate(self, remote: bool) -> RuntimeResult<Cell> {
+        let assembly = self.function.assembly.as_ref();
+
+        let parameters = assembly.arity;
+        let arguments = self.arguments.len();
+
+        if parameters != arguments {
+            return Err(RuntimeError::ArityMismatch {
+                invocation_origin: Origin::nil(),
+                function_origin: assembly.decl_origin(),
+                parameters,
+                arguments,
+            });
+        }
+
+        let mut observer = TaskObserver {
+            state: self.state.clone(),
+            remote,
+        };
+
+        let mut observers = [&mut observer as &mut dyn VmObserver];
+
+        let _task = TaskScope::enter(self.state);
+        let _observation = ObservationScope::enter(&mut observers);
+        let _evaluation = EvaluationScope::enter();
+
+        for argument in self.arguments {
+            Stack::push(argument);
+        }
+
+        match is_trusted() {
+            true => self.function.execute::<true>()?,
+            false => self.function.execute::<false>()?,
+        }
+
+        Ok(Stack::pop_1(0))
+    }
+}
+
+/// A cancellation token of the [Subtask].
+///
+/// The token can be cloned and sent to other threads.
+#[derive(Clone)]
+pub struct SubtaskToken(Arc<TaskState>);
+
+impl SubtaskToken {
+    /// Cancels the subtask and all of its children.
+    ///
+    /// The evaluation of the cancelled subtask ends with the
+    /// [RuntimeError::Interrupted] error on the next Virtual Machine
+    /// instruction.
+    #[inline(always)]
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if the subtask or any of its parents has been cancelled.
+    #[inline(always)]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+
+    /// Returns the number of the Virtual Machine instructions the subtask is
+    /// still allowed to evaluate, taking into account the parents' budgets.
+    ///
+    /// Returns None if neither the subtask nor its parents have a budget.
+    #[inline(always)]
+    pub fn remaining(&self) -> Option<usize> {
+        match self.0.remaining() {
+            usize::MAX => None,
+            remaining => Some(remaining),
+        }
+    }
+}
+
+/// A handle of the [Subtask] submitted to the [executor](SubtaskExecutor).
+///
+/// Created by the [Subtask::run_on] function.
+pub struct SubtaskHandle {
+    token: SubtaskToken,
+    result: Arc<(Mutex<Option<RuntimeResult<Cell>>>, Condvar)>,
+}
+
+impl SubtaskHandle {
+    /// Returns the cancellation token of the subtask.
+    #[inline(always)]
+    pub fn token(&self) -> &SubtaskToken {
+        &self.token
+    }
+
+    /// Returns true if the subtask's evaluation has ended.
+    pub fn is_finished(&self) -> bool {
+        let (slot, _) = self.result.as_ref();
+
+        let slot = slot.lock().unwrap_or_else(|poison| poison.into_inner());
+
+        slot.is_some()
+    }
+
+    /// Blocks the current thread until the subtask's evaluation ends and
+    /// returns the evaluation result.
+    ///
+    /// Note that the executor must eventually evaluate the submitted job;
+    /// otherwise, this function blocks forever.
+    pub fn join(self) -> RuntimeResult<Cell> {
+        let (slot, ready) = self.result.as_ref();
+
+        let mut slot = slot.lock().unwrap_or_else(|poison| poison.into_inner());
+
+        loop {
+            if let Some(result) = slot.take() {
+                return result;
+            }
+
+            slot = ready
+                .wait(slot)
+                .unwrap_or_else(|poison| poison.into_inner());
+        }
+    }
+}
+
+/// A host-provided executor of the [Subtask] evaluation jobs.
+///
+/// For example, the executor can evaluate the jobs on a thread pool:
+///
+/// ```rust
+/// # use ad_astra::interpret::SubtaskExecutor;
+/// #
+/// struct ThreadExecutor;
+///
+/// impl SubtaskExecutor for ThreadExecutor {
+///     fn execute(&self, job: Box<dyn FnOnce() + Send + 'static>) {
+///         let _ = std::thread::spawn(job);
+///     }
+/// }
+/// ```
+pub trait SubtaskExecutor {
+    /// Evaluates the `job` on any thread.
+    fn execute(&self, job: Box<dyn FnOnce() + Send + 'static>);
+}
+
+struct TaskState {
+    remaining: AtomicUsize,
+    cancelled: AtomicBool,
+    parent: Option<Arc<TaskState>>,
+}
+
+impl TaskState {
+    fn is_cancelled(&self) -> bool {
+        let mut next = Some(self);
+
+        while let Some(task) = next {
+            if task.cancelled.load(Ordering::Relaxed) {
+                return true;
+            }
+
+            next = task.parent.as_deref();
+        }
+
+        false
+    }
+
+    fn remaining(&self) -> usize {
+        let mut remaining = usize::MAX;
+        let mut next = Some(self);
+
+        while let Some(task) = next {
+            remaining = remaining.min(task.remaining.load(Ordering::Relaxed));
+            next = task.parent.as_deref();
+        }
+
+        remaining
+    }
+
+    // Returns false if the budget is exhausted.
+    #[inline(always)]
+    fn charge(&self) -> bool {
+        self.remaining
+            .fetch_update(
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+                |remaining| match remaining {
+                    usize::MAX => Some(usize::MAX),
+                    remaining => remaining.checked_sub(1),
+                },
+            )
+            .is_ok()
+    }
+}
+
+struct TaskObserver {
+    state: Arc<TaskState>,
+    remote: bool,
+}
+
+impl VmObserver for TaskObserver {
+    fn step(&mut self, _origin: &Origin) -> bool {
+        if !self.state.charge() {
+            return false;
+        }
+
+        // The observers of the parents evaluated on the current thread are
+        // notified by the Virtual Machine too. Otherwise, the subtask charges
+        // and checks its parents itself.
+        if !self.remote {
+            return !self.state.cancelled.load(Ordering::Relaxed);
+        }
+
+        let mut next = Some(self.state.as_ref());
+
+        while let Some(task) = next {
+            if task.cancelled.load(Ordering::Relaxed) {
+                return false;
+            }
+
+            if !std::ptr::eq(task, self.state.as_ref()) && !task.charge() {
+                return false;
+            }
+
+            next = task.parent.as_deref();
+        }
+
+        true
+    }
+}
+
+struct TaskScope;
+
+impl Drop for TaskScope {
+    fn drop(&mut self) {
+        TASKS.with(|tasks| {
+            let _ = tasks.borrow_mut().pop();
+        });
+    }
+}
+
+impl TaskScope {
+    #[inline(always)]
+    fn enter(state: Arc<TaskState>) -> Self {
+        TASKS.with(|tasks| tasks.borrow_mut().push(state));
+
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell as StdCell, RefCell};
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        interpret::{ScriptFn, Subtask, SubtaskExecutor, SubtaskToken},
+        runtime::{Cell, Origin, RuntimeError, RuntimeResult, ScriptPackage},
+    };
+
+    thread_local! {
+        static ROOT: RefCell<Option<SubtaskToken>> = const { RefCell::new(None) };
+        static TICKS: StdCell<usize> = const { StdCell::new(0) };
+    }
+
+    struct ThreadExecutor;
+
+    impl SubtaskExecutor for ThreadExecutor {
+        fn execute(&self, job: Box<dyn FnOnce() + Send + 'static>) {
+            let _ = std::thread::spawn(job);
+        }
+    }
+
+    #[export]
+    pub fn subtask_inline(f: ScriptFn, arg: usize, budget: usize) -> RuntimeResult<usize> {
+        let arg = Cell::give(Origin::nil(), arg)?;
+
+        Subtask::spawn(&f, vec![arg], Some(budget))
+            .run()?
+            .take::<usize>(Origin::nil())
+    }
+
+    #[export]
+    pub fn subtask_remote(f: ScriptFn, arg: usize) -> RuntimeResult<usize> {
+        let arg = Cell::give(Origin::nil(), arg)?;
+
+        Subtask::spawn(&f, vec![arg], None)
+            .run_on(&ThreadExecutor)
+            .join()?
+            .take::<usize>(Origin::nil())
+    }
+
+    #[export]
+    pub fn subtask_remaining() -> usize {
+        Subtask::current()
+            .and_then(|token| token.remaining())
+            .unwrap_or(usize::MAX)
+    }
+
+    #[export]
+    pub fn subtask_tick() {
+        let ticks = TICKS.with(|ticks| {
+            ticks.set(ticks.get() + 1);
+            ticks.get()
+        });
+
+        if ticks == 3 {
+            ROOT.with(|root| {
+                if let Some(root) = root.borrow().as_ref() {
+                    root.cancel();
+                }
+            });
+        }
+    }
+
+    fn compile(text: &str) -> ScriptFn {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        read.compile().unwrap()
+    }
+
+    // The errors of the nested subtasks reach the outer script through
+    // the exported functions' results.
+    fn is_interrupted(error: &RuntimeError) -> bool {
+        match error {
+            RuntimeError::Interrupted { .. } => true,
+            RuntimeError::UpcastResult { cause, .. } => cause
+                .downcast_ref::<RuntimeError>()
+                .map(is_interrupted)
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    fn run(text: &str, budget: Option<usize>) -> RuntimeResult<Cell> {
+        Subtask::spawn(&compile(text), Vec::new(), budget).run()
+    }
+
+    #[test]
+    fn test_subtask_nesting() {
+        static NESTED: &str = r#"
+            return subtask_inline(fn(x) {
+                return subtask_inline(fn(y) { return y + 1; }, x * 2, 1000);
+            }, 5, 1000);
+        "#;
+
+        let result = run(NESTED, Some(10_000)).unwrap();
+
+        assert_eq!(result.stringify(false), "11");
+
+        static CLAMPED: &str = r#"
+            return subtask_inline(fn(x) {
+                return subtask_inline(fn(y) { return subtask_remaining(); }, x, 1000000);
+            }, 0, 1000000);
+        "#;
+
+        let remaining = run(CLAMPED, Some(500))
+            .unwrap()
+            .take::<usize>(Origin::nil());
+
+        assert!(remaining.unwrap() < 500);
+
+        static EXHAUSTED: &str = r#"
+            return subtask_inline(fn(x) {
+                return subtask_inline(fn(y) {
+                    for i in 0..100000 {}
+                    return y;
+                }, x, 1000000);
+            }, 0, 1000000);
+        "#;
+
+        let error = run(EXHAUSTED, Some(500)).unwrap_err();
+
+        assert!(is_interrupted(&error), "{error:?}");
+
+        static REMOTE: &str = r#"
+            return subtask_remote(fn(x) {
+                return subtask_inline(fn(y) { return y * 3; }, x + 1, 1000);
+            }, 4);
+        "#;
+
+        let result = run(REMOTE, Some(10_000)).unwrap();
+
+        assert_eq!(result.stringify(false), "15");
+
+        static REMOTE_EXHAUSTED: &str = r#"
+            return subtask_remote(fn(x) {
+                for i in 0..100000 {}
+                return x;
+            }, 0);
+        "#;
+
+        let error = run(REMOTE_EXHAUSTED, Some(500)).unwrap_err();
+
+        assert!(is_interrupted(&error), "{error:?}");
+    }
+
+    #[test]
+    fn test_subtask_cancellation() {
+        static CANCELLED: &str = r#"
+            return subtask_inline(fn(x) {
+                return subtask_inline(fn(y) {
+                    for i in 0..100 {
+                        subtask_tick();
+                    }
+
+                    return y;
+                }, x, 1000000);
+            }, 1, 1000000);
+        "#;
+
+        let root = Subtask::spawn(&compile(CANCELLED), Vec::new(), None);
+
+        ROOT.with(|token| *token.borrow_mut() = Some(root.token()));
+
+        let error = root.run().unwrap_err();
+
+        assert!(is_interrupted(&error), "{error:?}");
+        assert_eq!(TICKS.with(|ticks| ticks.get()), 3);
+
+        let token = ROOT.with(|token| token.borrow_mut().take()).unwrap();
+
+        assert!(token.is_cancelled());
+
+        let child = Subtask::spawn(&compile("return 1;"), Vec::new(), Some(100));
+
+        child.token().cancel();
+
+        let error = child.run_on(&ThreadExecutor).join().unwrap_err();
+
+        assert!(is_interrupted(&error), "{error:?}");
+    }
+}
@@ -0,0 +1,480 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use lady_deirdre::analysis::TaskHandle;
+
+use crate::{
+    analysis::{symbols::VarSymbol, ModuleRead},
+    interpret::{FieldWrite, VarWrite, VmObserver},
+    runtime::{Cell, Origin, TypeMeta},
+};
+
+/// A [VmObserver] that tracks the assignments to the selected local variables
+/// and to the components of exported objects during script evaluation.
+///
+/// The watchpoints are configured before the evaluation, and then installed
+/// using the [ScriptFn::instrument](crate::interpret::ScriptFn::instrument)
+/// function:
+///
+/// ```rust
+/// # use ad_astra::{
+/// #     analysis::{
+/// #         symbols::{LookupOptions, ModuleSymbol, SymbolKind},
+/// #         ModuleRead,
+/// #         ScriptModule,
+/// #     },
+/// #     export,
+/// #     interpret::Watchpoints,
+/// #     lady_deirdre::analysis::TriggerHandle,
+/// #     runtime::ScriptPackage,
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// let module = ScriptModule::new(Package::meta(), "let hp = 10; hp -= 3; hp = hp * 2;");
+///
+/// let handle = TriggerHandle::new();
+/// let read_guard = module.read(&handle, 1).unwrap();
+///
+/// let mut watchpoints = Watchpoints::new();
+///
+/// let vars = read_guard
+///     .symbols(.., LookupOptions::new().filter(SymbolKind::Var as u32))
+///     .unwrap();
+///
+/// for symbol in vars {
+///     let ModuleSymbol::Var(var) = symbol else {
+///         continue;
+///     };
+///
+///     watchpoints = watchpoints.watch_var(&read_guard, &var);
+/// }
+///
+/// let script_fn = read_guard.compile().unwrap();
+///
+/// script_fn.instrument(&mut watchpoints).run().unwrap();
+///
+/// let changes = watchpoints
+///     .hits()
+///     .iter()
+///     .map(|hit| (hit.old_value.as_str(), hit.new_value.as_str()))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(changes, [("10", "7"), ("7", "14")]);
+/// ```
+///
+/// Each watched assignment produces a [WatchHit] record. By default, the
+/// observer collects these records into the [hits](Self::hits) log. If you
+/// set a handler function using the [on_hit](Self::on_hit) function,
+/// the observer passes the records to the handler instead.
+///
+/// Using the [break_on](Self::break_on) function, you can also interrupt
+/// the evaluation once the watched objects have been assigned the specified
+/// number of times.
+#[derive(Default)]
+pub struct Watchpoints<'a> {
+    watches: Vec<Watch>,
+    handler: Option<Box<dyn FnMut(&WatchHit) + 'a>>,
+    hits: Vec<WatchHit>,
+    count: usize,
+    limit: Option<usize>,
+}
+
+impl<'a> VmObserver for Watchpoints<'a> {
+    #[inline(always)]
+    fn step(&mut self, _origin: &Origin) -> bool {
+        let Some(limit) = self.limit else {
+            return true;
+        };
+
+        self.count < limit
+    }
+
+    fn write_var(&mut self, write: &VarWrite) {
+        let watched = self.watches.iter().any(|watch| match watch {
+            Watch::Var { decl } => *decl == write.decl,
+            _ => false,
+        });
+
+        if !watched {
+            return;
+        }
+
+        self.hit(write.name, write.old_value, write.new_value, write.origin);
+    }
+
+    fn write_field(&mut self, write: &FieldWrite) {
+        let ty = write.object.ty();
+
+        let watched = self.watches.iter().any(|watch| match watch {
+            Watch::Component { ty: watched, name } => {
+                *watched == ty && name.as_str() == write.component
+            }
+            _ => false,
+        });
+
+        if !watched {
+            return;
+        }
+
+        self.hit(
+            write.component,
+            write.old_value,
+            write.new_value,
+            write.origin,
+        );
+    }
+}
+
+impl<'a> Watchpoints<'a> {
+    /// Creates an observer without watchpoints.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Watches the assignments to the local variable introduced by the
+    /// `symbol`.
+    ///
+    /// The `read` argument can be any content access guard object, such as
+    /// a [ModuleReadGuard](crate::analysis::ModuleReadGuard) or
+    /// a [ModuleWriteGuard](crate::analysis::ModuleWriteGuard)).
+    ///
+    /// The observer recognizes the variable by the source code range of its
+    /// declaration. Therefore, the script module should not be edited between
+    /// the configuration of the watchpoint and the evaluation of the script.
+    ///
+    /// See [VmObserver::write_var] for the kinds of the reported assignments.
+    pub fn watch_var<H: TaskHandle>(
+        mut self,
+        read: &impl ModuleRead<H>,
+        symbol: &VarSymbol,
+    ) -> Self {
+        let decl = Origin::from(symbol.origin(read));
+
+        if !decl.is_nil() {
+            self.watches.push(Watch::Var { decl });
+        }
+
+        self
+    }
+
+    /// Watches the assignments to the component `name` of the objects of
+    /// the `ty` type.
+    ///
+    /// See [VmObserver::write_field] for the kinds of the reported
+    /// assignments.
+    pub fn watch_component(mut self, ty: &'static TypeMeta, name: impl Into<String>) -> Self {
+        self.watches.push(Watch::Component {
+            ty,
+            name: name.into(),
+        });
+
+        self
+    }
+
+    /// Sets a function that receives the records of the watched assignments
+    /// as soon as they occur.
+    ///
+    /// When the handler is set, the observer does not collect the records
+    /// into the [hits](Self::hits) log.
+    pub fn on_hit(mut self, handler: impl FnMut(&WatchHit) + 'a) -> Self {
+        self.handler = Some(Box::new(handler));
+
+        self
+    }
+
+    /// Interrupts the evaluation right after the `count`-th watched
+    /// assignment.
+    ///
+    /// The Virtual Machine ends the evaluation with the
+    /// [RuntimeError::Interrupted](crate::runtime::RuntimeError::Interrupted)
+    /// error before the next assembly command. If the `count` is zero, the
+    /// evaluation is interrupted before the first command.
+    #[inline(always)]
+    pub fn break_on(mut self, count: usize) -> Self {
+        self.limit = Some(count);
+
+        self
+    }
+
+    /// Returns the log of the watched assignments collected so far, in the
+    /// order in which they occurred.
+    ///
+    /// The log is empty if the [handler](Self::on_hit) is set.
+    #[inline(always)]
+    pub fn hits(&self) -> &[WatchHit] {
+        &self.hits
+    }
+
+    /// Returns the total number of the watched assignments that occurred so
+    /// far.
+    #[inline(always)]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    fn hit(&mut self, name: &str, old_value: &Cell, new_value: &Cell, origin: Origin) {
+        self.count += 1;
+
+        let hit = WatchHit {
+            name: String::from(name),
+            old_value: old_value.stringify(false),
+            new_value: new_value.stringify(false),
+            origin,
+        };
+
+        match &mut self.handler {
+            Some(handler) => handler(&hit),
+            None => self.hits.push(hit),
+        }
+    }
+}
+
+/// A record of an assignment observed by the [Watchpoints].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct WatchHit {
+    /// The name of the assigned variable or component.
+    pub name: String,
+
+    /// A string representation of the value before the assignment.
+    ///
+    /// See [Cell::stringify] for the formatting details.
+    pub old_value: String,
+
+    /// A string representation of the value after the assignment.
+    ///
+    /// See [Cell::stringify] for the formatting details.
+    pub new_value: String,
+
+    /// The range in the script source code of the assignment expression for
+    /// the variables, or of the assignment operator for the components.
+    pub origin: Origin,
+}
+
+enum Watch {
+    Var { decl: Origin },
+    Component { ty: &'static TypeMeta, name: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::{
+        analysis::TriggerHandle,
+        lexis::{SourceCode, ToSpan},
+    };
+
+    use crate::{
+        analysis::{
+            symbols::{tests::TestPackage, LookupOptions, ModuleSymbol, SymbolKind},
+            ModuleRead,
+            ScriptModule,
+        },
+        export,
+        interpret::Watchpoints,
+        runtime::{Origin, RuntimeError, ScriptPackage, ScriptType},
+    };
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct TestTank {
+        pub fuel: usize,
+        pub temperature: usize,
+    }
+
+    #[export]
+    pub fn test_tank() -> TestTank {
+        TestTank::default()
+    }
+
+    fn watch(
+        text: &str,
+        vars: &[&str],
+        setup: impl FnOnce(Watchpoints<'static>) -> Watchpoints<'static>,
+    ) -> (Result<(), RuntimeError>, Vec<(String, String, String)>) {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let mut watchpoints = setup(Watchpoints::new());
+
+        let symbols = read
+            .symbols(.., LookupOptions::new().filter(SymbolKind::Var as u32))
+            .unwrap();
+
+        for symbol in symbols {
+            let ModuleSymbol::Var(var) = symbol else {
+                continue;
+            };
+
+            if !vars.contains(&var.var_name(&read).unwrap().as_ref()) {
+                continue;
+            }
+
+            watchpoints = watchpoints.watch_var(&read, &var);
+        }
+
+        let result = read
+            .compile()
+            .unwrap()
+            .instrument(&mut watchpoints)
+            .run()
+            .map(|_| ());
+
+        let text = read.text();
+
+        let hits = watchpoints
+            .hits()
+            .iter()
+            .map(|hit| {
+                let Origin::Script(origin) = hit.origin else {
+                    panic!("Missing script origin.");
+                };
+
+                let span = origin.to_site_span(&text).unwrap();
+
+                (
+                    hit.name.clone(),
+                    format!("{} -> {}", hit.old_value, hit.new_value),
+                    text.substring(span).to_string(),
+                )
+            })
+            .collect();
+
+        (result, hits)
+    }
+
+    #[test]
+    fn test_watch_vars() {
+        let (result, hits) = watch(
+            r#"
+                let hp = 10;
+                let mp = 5;
+
+                hp -= 3;
+                mp = 1;
+                hp = hp * 2;
+
+                let f = fn(hp) { hp = 100; };
+
+                f(7);
+            "#,
+            &["hp"],
+            |watchpoints| watchpoints,
+        );
+
+        result.unwrap();
+
+        assert_eq!(
+            hits,
+            [
+                (
+                    String::from("hp"),
+                    String::from("10 -> 7"),
+                    String::from("hp -= 3"),
+                ),
+                (
+                    String::from("hp"),
+                    String::from("7 -> 14"),
+                    String::from("hp = hp * 2"),
+                ),
+                (
+                    String::from("hp"),
+                    String::from("7 -> 100"),
+                    String::from("hp = 100"),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_watch_components() {
+        let (result, hits) = watch(
+            r#"
+                let tank = test_tank();
+
+                tank.fuel = 30;
+                tank.temperature = 20;
+                tank.fuel -= 5;
+            "#,
+            &[],
+            |watchpoints| watchpoints.watch_component(TestTank::type_meta(), "fuel"),
+        );
+
+        result.unwrap();
+
+        assert_eq!(
+            hits,
+            [
+                (
+                    String::from("fuel"),
+                    String::from("0 -> 30"),
+                    String::from("="),
+                ),
+                (
+                    String::from("fuel"),
+                    String::from("30 -> 25"),
+                    String::from("-="),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_watch_break() {
+        let (result, hits) = watch(
+            r#"
+                let i = 0;
+
+                loop {
+                    i += 1;
+                }
+            "#,
+            &["i"],
+            |watchpoints| watchpoints.break_on(3),
+        );
+
+        assert!(matches!(result, Err(RuntimeError::Interrupted { .. })));
+
+        assert_eq!(
+            hits.into_iter()
+                .map(|(_, change, _)| change)
+                .collect::<Vec<_>>(),
+            ["0 -> 1", "1 -> 2", "2 -> 3"],
+        );
+    }
+}
@@ -0,0 +1,455 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    cell::Cell as StdCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        RwLock,
+    },
+};
+
+use ahash::AHashMap;
+use lady_deirdre::sync::Lazy;
+
+use crate::{
+    interpret::observer::notify_write_field,
+    runtime::{Cell, Ident, Origin, RuntimeResult, TypeMeta},
+};
+
+type Observer = dyn Fn(&FieldWrite) + Send + Sync + 'static;
+
+static OBSERVERS: Lazy<RwLock<AHashMap<&'static TypeMeta, Arc<Observer>>>> =
+    Lazy::new(|| RwLock::new(AHashMap::new()));
+
+// True if at least one write observer is installed, meaning that the Virtual
+// Machine cannot skip the write notifications.
+static OBSERVED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static NOTIFYING: StdCell<bool> = const { StdCell::new(false) };
+}
+
+/// A description of a script assignment to a component of an exported object
+/// reported to the [write observer](set_write_observer) and to the
+/// [VmObserver::write_field](crate::interpret::VmObserver::write_field)
+/// function.
+#[derive(Clone, Copy)]
+#[non_exhaustive]
+pub struct FieldWrite<'a> {
+    /// A handle to the object whose component has been assigned.
+    pub object: &'a Cell,
+
+    /// The name of the assigned component.
+    pub component: &'a str,
+
+    /// A copy of the component's value before the assignment.
+    ///
+    /// For the struct fields, the copy is created through the
+    /// [Clone operator](crate::runtime::Prototype::implements_clone) of the
+    /// field's type. For the properties, the copy is the result of the
+    /// property's getter function. If the copy cannot be created, the value is
+    /// [Nil](Cell::nil).
+    pub old_value: &'a Cell,
+
+    /// A handle to the component's value after the assignment.
+    pub new_value: &'a Cell,
+
+    /// The range in the script source code of the assignment operator.
+    pub origin: Origin,
+}
+
+/// Installs a process-wide observer of the script assignments to the
+/// components of the objects of the `ty` type, replacing the previously
+/// installed observer of this type.
+///
+/// The Virtual Machine calls the `observer` function after each successful
+/// assignment to a struct field of such an object (`foo.bar = 10;`, or a
+/// compound assignment such as `foo.bar += 1;`), on the thread that evaluates
+/// the script. The assignments to the properties are reported only if the
+/// property's setter function is marked with the `#[export(observed)]`
+/// attribute.
+///
+/// ```rust
+/// # use std::sync::{Arc, Mutex};
+/// #
+/// # use ad_astra::{
+/// #     analysis::{ModuleRead, ScriptModule},
+/// #     export,
+/// #     interpret::{remove_write_observer, set_write_observer},
+/// #     lady_deirdre::analysis::TriggerHandle,
+/// #     runtime::{ScriptPackage, ScriptType},
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// #[export]
+/// #[derive(Clone, Default)]
+/// pub struct Widget {
+///     pub width: usize,
+/// }
+///
+/// #[export]
+/// pub fn widget() -> Widget {
+///     Widget::default()
+/// }
+///
+/// let changes = Arc::new(Mutex::new(Vec::new()));
+///
+/// set_write_observer(Widget::type_meta(), {
+///     let changes = changes.clone();
+///
+///     move |write| {
+///         let old = write.old_value.clone().take::<usize>(write.origin).unwrap();
+///         let new = write.new_value.clone().take::<usize>(write.origin).unwrap();
+///
+///         changes.lock().unwrap().push((write.component.to_string(), old, new));
+///     }
+/// });
+///
+/// let module = ScriptModule::new(Package::meta(), "let w = widget(); w.width = 10;");
+///
+/// let handle = TriggerHandle::new();
+/// let read_guard = module.read(&handle, 1).unwrap();
+///
+/// read_guard.compile().unwrap().run().unwrap();
+///
+/// remove_write_observer(Widget::type_meta());
+///
+/// assert_eq!(*changes.lock().unwrap(), [(String::from("width"), 0, 10)]);
+/// ```
+///
+/// The observer must not evaluate scripts: any attempt to evaluate a script
+/// function on the notifying thread during the observer call ends with the
+/// [ObserverReentrance](crate::runtime::RuntimeError::ObserverReentrance)
+/// error.
+///
+/// When no write observers are installed, the Virtual Machine skips the
+/// notifications at virtually no cost. Otherwise, the assignments to the
+/// components of the observed types are slightly slower, because the Virtual
+/// Machine copies the old values of the assigned components.
+pub fn set_write_observer(
+    ty: &'static TypeMeta,
+    observer: impl Fn(&FieldWrite) + Send + Sync + 'static,
+) {
+    let observer = Arc::new(observer) as Arc<Observer>;
+
+    let mut observers = match OBSERVERS.write() {
+        Ok(guard) => guard,
+        Err(poison) => poison.into_inner(),
+    };
+
+    let previous = observers.insert(ty, observer);
+
+    OBSERVED.store(true, Ordering::Release);
+
+    drop(observers);
+    drop(previous);
+}
+
+/// Removes the write observer of the `ty` type previously installed by the
+/// [set_write_observer] function.
+///
+/// If the type does not have an observer, this function does nothing.
+pub fn remove_write_observer(ty: &'static TypeMeta) {
+    let mut observers = match OBSERVERS.write() {
+        Ok(guard) => guard,
+        Err(poison) => poison.into_inner(),
+    };
+
+    let previous = observers.remove(ty);
+
+    OBSERVED.store(!observers.is_empty(), Ordering::Release);
+
+    drop(observers);
+    drop(previous);
+}
+
+// Returns true if the current thread is notifying a write observer, meaning
+// that the script evaluation is not allowed.
+#[inline(always)]
+pub(super) fn is_notifying() -> bool {
+    NOTIFYING.with(|notifying| notifying.get())
+}
+
+// A pending notification of the write observer and the VM observers about
+// the assignment to the component of an observed object.
+pub(super) struct ObservedWrite {
+    observer: Option<Arc<Observer>>,
+    instrumented: bool,
+    object: Cell,
+    old_value: Cell,
+}
+
+impl ObservedWrite {
+    // Prepares the notification if the `object`'s type has a write observer
+    // or if the assignment is `instrumented` by the VM observers, and
+    // the `component` is observable.
+    //
+    // The `old_value` function creates a copy of the component's value before
+    // the assignment.
+    #[inline(always)]
+    pub(super) fn prepare(
+        object: &Cell,
+        component: &str,
+        instrumented: bool,
+        old_value: impl FnOnce(Cell) -> RuntimeResult<Cell>,
+    ) -> Option<Self> {
+        let observer = match OBSERVED.load(Ordering::Acquire) {
+            false => None,
+
+            true => {
+                let observers = match OBSERVERS.read() {
+                    Ok(guard) => guard,
+                    Err(poison) => poison.into_inner(),
+                };
+
+                observers.get(object.ty()).cloned()
+            }
+        };
+
+        if observer.is_none() && !instrumented {
+            return None;
+        }
+
+        if !object
+            .clone()
+            .into_object()
+            .is_observed_component(component)
+        {
+            return None;
+        }
+
+        Some(Self {
+            observer,
+            instrumented,
+            object: object.clone(),
+            old_value: old_value(object.clone()).unwrap_or_default(),
+        })
+    }
+
+    // Notifies the observers about the completed assignment.
+    pub(super) fn notify(self, origin: Origin, component: Ident) {
+        let new_value = self
+            .object
+            .clone()
+            .into_object()
+            .component(origin, origin, component.clone())
+            .unwrap_or_default();
+
+        struct Notifying(bool);
+
+        impl Drop for Notifying {
+            fn drop(&mut self) {
+                NOTIFYING.with(|notifying| notifying.set(self.0));
+            }
+        }
+
+        let write = FieldWrite {
+            object: &self.object,
+            component: component.as_ref(),
+            old_value: &self.old_value,
+            new_value: &new_value,
+            origin,
+        };
+
+        if let Some(observer) = &self.observer {
+            let _notifying = Notifying(NOTIFYING.with(|notifying| notifying.replace(true)));
+
+            observer(&write);
+        }
+
+        if self.instrumented {
+            notify_write_field(&write);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        interpret::{remove_write_observer, set_write_observer},
+        runtime::{Origin, RuntimeError, ScriptPackage, ScriptType},
+    };
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct TestGauge {
+        pub level: usize,
+        scale: usize,
+        offset: usize,
+    }
+
+    #[export]
+    impl TestGauge {
+        #[export(getter)]
+        pub fn scale(&self) -> usize {
+            self.scale
+        }
+
+        #[export(setter)]
+        #[export(observed)]
+        pub fn set_scale(&mut self, scale: usize) {
+            self.scale = scale;
+        }
+
+        #[export(getter)]
+        pub fn offset(&self) -> usize {
+            self.offset
+        }
+
+        #[export(setter)]
+        pub fn set_offset(&mut self, offset: usize) {
+            self.offset = offset;
+        }
+    }
+
+    #[export]
+    pub fn test_gauge() -> TestGauge {
+        TestGauge::default()
+    }
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct TestDial {
+        pub value: usize,
+    }
+
+    #[export]
+    pub fn test_dial() -> TestDial {
+        TestDial::default()
+    }
+
+    fn run(text: &str) -> Result<(), RuntimeError> {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        read.compile().unwrap().run().map(|_| ())
+    }
+
+    #[test]
+    fn test_write_observer() {
+        static SOURCE: &str = r#"
+            let g = test_gauge();
+
+            g.level = 3;
+            g.level += 4;
+            g.scale = 10;
+            g.offset = 20;
+
+            let s = struct { level: 1 };
+            s.level = 2;
+        "#;
+
+        let writes = Arc::new(Mutex::new(Vec::new()));
+
+        set_write_observer(TestGauge::type_meta(), {
+            let writes = writes.clone();
+
+            move |write| {
+                assert!(write.object.is::<TestGauge>());
+
+                let old = write
+                    .old_value
+                    .clone()
+                    .take::<usize>(Origin::nil())
+                    .unwrap();
+                let new = write
+                    .new_value
+                    .clone()
+                    .take::<usize>(Origin::nil())
+                    .unwrap();
+
+                writes
+                    .lock()
+                    .unwrap()
+                    .push((write.component.to_string(), old, new));
+            }
+        });
+
+        run(SOURCE).unwrap();
+
+        remove_write_observer(TestGauge::type_meta());
+
+        run(SOURCE).unwrap();
+
+        assert_eq!(
+            *writes.lock().unwrap(),
+            [
+                (String::from("level"), 0, 3),
+                (String::from("level"), 3, 7),
+                (String::from("scale"), 0, 10),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_write_observer_reentrance() {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+
+        set_write_observer(TestDial::type_meta(), {
+            let errors = errors.clone();
+
+            move |_| {
+                errors.lock().unwrap().push(run("return 1;"));
+            }
+        });
+
+        let result = run("let d = test_dial(); d.value = 1;");
+
+        remove_write_observer(TestDial::type_meta());
+
+        result.unwrap();
+
+        let errors = errors.lock().unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            Err(RuntimeError::ObserverReentrance { .. })
+        ));
+
+        run("return 1;").unwrap();
+    }
+}
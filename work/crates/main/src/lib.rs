@@ -298,7 +298,7 @@ mod exports;
 
 /// Scripts formatting and printing to the terminal.
 ///
-/// This module contains two useful components:
+/// This module contains three useful components:
 ///
 /// 1. A source code formatting algorithm (available through the
 ///    [format_script_text](format::format_script_text) function). This
@@ -308,6 +308,10 @@ mod exports;
 /// 2. The [ScriptSnippet](format::ScriptSnippet) object, which allows you
 ///    to print snippets with syntax highlighting and annotated fragments of
 ///    Ad Astra source code to the terminal.
+///
+/// 3. The catalog of the user-facing diagnostics and runtime error messages
+///    (see [MessageKey](format::MessageKey)), which you can translate by
+///    installing a [MessageLocalizer](format::MessageLocalizer).
 pub mod format;
 
 /// Ad Astra Virtual Machine.
@@ -350,6 +354,37 @@ pub mod runtime;
 
 mod semantics;
 
+/// Helpers for asserting script behavior in Rust unit tests.
+///
+/// The functions of this module, and the corresponding
+/// [assert_script_ok], [assert_script_err], [eval], and [eval_as] macros,
+/// compile and run scripts against the specified
+/// [package](runtime::ScriptPackage) in a single call. On failure, they panic
+/// with a snippet of the source code annotated with the diagnostic issues or
+/// the runtime error description.
+///
+/// ```rust
+/// use ad_astra::{analysis::IssueCode, assert_script_err, assert_script_ok, eval_as, export};
+///
+/// #[export(package)]
+/// #[derive(Default)]
+/// struct Package;
+///
+/// assert_script_ok!(Package, "let x = 10; return x * 2;");
+///
+/// assert_script_err!(Package, "let x = ;", IssueCode::Parse);
+///
+/// assert_eq!(eval_as!(Package, usize, "let x = 10; return x * 2;"), 20);
+/// ```
+///
+/// The [check_consistency](testing::check_consistency) and
+/// [assert_consistent](testing::assert_consistent) functions compare the
+/// static analyzer's type predictions with the actual script evaluation. You
+/// can use them to build a corpus of scripts covering your exported APIs, such
+/// that any divergence between the analysis and the interpretation of these
+/// APIs fails a single test.
+pub mod testing;
+
 /// Built-in language server for code editors that support the LSP protocol.
 ///
 /// This module is available under the `lsp` feature of the crate, which is
@@ -515,9 +550,25 @@ mod semantics;
 /// ```
 #[cfg(feature = "lsp")]
 pub mod server;
-mod syntax;
+
+/// Ad Astra language syntax introspection.
+///
+/// The [operator_table](syntax::operator_table) function returns the
+/// precedence and associativity of each operator of the language, as
+/// understood by the source code parser.
+pub mod syntax;
 
 extern crate self as ad_astra;
 
 pub use ad_astra_export::export;
 pub use lady_deirdre;
+
+/// The version of the Ad Astra engine, as specified in the crate's
+/// `Cargo.toml`.
+///
+/// Scripts can declare the engine versions they are compatible with using
+/// the `//! adastra <version>` directive of the
+/// [module metadata header](analysis::ModuleMetadata). The analyzer reports
+/// a diagnostic error if this version does not satisfy the declared
+/// requirement.
+pub const META_VERSION: &str = env!("CARGO_PKG_VERSION");
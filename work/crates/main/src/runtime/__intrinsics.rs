@@ -51,6 +51,15 @@ use std::{
 use ahash::RandomState;
 pub use lady_deirdre::sync::Lazy;
 
+pub use crate::{
+    interpret::{blocking::block_on, memo::memoize},
+    runtime::schema::{
+        coerce_exported_struct,
+        downcast_exported_struct,
+        downcast_script_struct,
+        script_struct_hint,
+    },
+};
 use crate::{
     runtime::{
         Arg,
@@ -58,11 +67,15 @@ use crate::{
         Ident,
         InvocationMeta,
         Origin,
+        PackageMeta,
         RuntimeResult,
         RustIdent,
         RustOrigin,
+        ScriptType,
         TypeFamily,
+        TypeHint,
         TypeMeta,
+        Upcast,
     },
     type_family,
 };
@@ -73,6 +86,9 @@ type_family! {
 
     /// Any function type.
     pub static FUNCTION_FAMILY = "fn";
+
+    /// Any package namespace type.
+    pub static NAMESPACE_FAMILY = "namespace";
 }
 
 type ExporterFn = extern "C" fn();
@@ -143,6 +159,19 @@ impl DeclarationGroup {
                 vector.push(declaration);
             }
 
+            // The map order depends on the exporter function addresses. Sorting
+            // by the source code location makes the registration order of the
+            // declarations deterministic.
+            vector.sort_by_key(|group| {
+                (
+                    group.origin.package,
+                    group
+                        .origin
+                        .code
+                        .map(|code| (code.module, code.line, code.column)),
+                )
+            });
+
             vector
         });
 
@@ -256,6 +285,7 @@ pub struct PackageDeclaration {
     pub name: &'static str,
     pub version: &'static str,
     pub doc: Option<&'static str>,
+    pub dependencies: Vec<fn() -> &'static PackageMeta>,
     pub instance: Lazy<Cell>,
 }
 
@@ -269,7 +299,9 @@ pub struct TypeMetaDeclaration {
 
 pub struct PrototypeDeclaration {
     pub receiver: TypeId,
+    pub family: Option<&'static TypeFamily>,
     pub components: Vec<ComponentDeclaration>,
+    pub constants: &'static [ConstDeclaration],
     pub operators: Vec<OperatorDeclaration>,
 }
 
@@ -278,6 +310,105 @@ pub struct ComponentDeclaration {
     pub constructor: fn(origin: Origin, lhs: Arg) -> RuntimeResult<Cell>,
     pub hint: &'static TypeMeta,
     pub doc: Option<&'static str>,
+    pub owned: bool,
+    pub pure: bool,
+    pub property: bool,
+    pub setter: Option<fn(origin: Origin, lhs: Arg, rhs: Arg) -> RuntimeResult<()>>,
+    pub deprecated: Option<&'static str>,
+    pub hidden: bool,
+    pub observed: bool,
+}
+
+// Resolves the component type of the exported struct field whose Rust type
+// is not a Script Type, but can be upcasted to a Script Type
+// (e.g., `Option<usize>` resolves to `usize`).
+pub fn casted_type_meta(hint: TypeHint) -> &'static TypeMeta {
+    match hint {
+        TypeHint::Type(meta) => meta,
+
+        TypeHint::Family(family) => {
+            let mut types = family.into_iter();
+
+            match (types.next(), types.next()) {
+                (Some(meta), None) => meta,
+                _ => TypeMeta::dynamic(),
+            }
+        }
+
+        TypeHint::Invocation(_) => TypeMeta::dynamic(),
+    }
+}
+
+pub struct ConstDeclaration {
+    pub name: RustIdent,
+    pub doc: Option<&'static str>,
+    pub value: ConstValue,
+}
+
+#[derive(Clone, Copy)]
+pub enum ConstValue {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(usize),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(isize),
+    F32(f32),
+    F64(f64),
+    Str(&'static str),
+}
+
+impl ConstValue {
+    #[inline]
+    pub(crate) fn give(self, origin: Origin) -> RuntimeResult<Cell> {
+        match self {
+            Self::Bool(value) => Cell::give(origin, value),
+            Self::U8(value) => Cell::give(origin, value),
+            Self::U16(value) => Cell::give(origin, value),
+            Self::U32(value) => Cell::give(origin, value),
+            Self::U64(value) => Cell::give(origin, value),
+            Self::U128(value) => Cell::give(origin, value),
+            Self::Usize(value) => Cell::give(origin, value),
+            Self::I8(value) => Cell::give(origin, value),
+            Self::I16(value) => Cell::give(origin, value),
+            Self::I32(value) => Cell::give(origin, value),
+            Self::I64(value) => Cell::give(origin, value),
+            Self::I128(value) => Cell::give(origin, value),
+            Self::Isize(value) => Cell::give(origin, value),
+            Self::F32(value) => Cell::give(origin, value),
+            Self::F64(value) => Cell::give(origin, value),
+            Self::Str(value) => Cell::give(origin, value),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn hint(&self) -> &'static TypeMeta {
+        match self {
+            Self::Bool(..) => <bool as ScriptType>::type_meta(),
+            Self::U8(..) => <u8 as ScriptType>::type_meta(),
+            Self::U16(..) => <u16 as ScriptType>::type_meta(),
+            Self::U32(..) => <u32 as ScriptType>::type_meta(),
+            Self::U64(..) => <u64 as ScriptType>::type_meta(),
+            Self::U128(..) => <u128 as ScriptType>::type_meta(),
+            Self::Usize(..) => <usize as ScriptType>::type_meta(),
+            Self::I8(..) => <i8 as ScriptType>::type_meta(),
+            Self::I16(..) => <i16 as ScriptType>::type_meta(),
+            Self::I32(..) => <i32 as ScriptType>::type_meta(),
+            Self::I64(..) => <i64 as ScriptType>::type_meta(),
+            Self::I128(..) => <i128 as ScriptType>::type_meta(),
+            Self::Isize(..) => <isize as ScriptType>::type_meta(),
+            Self::F32(..) => <f32 as ScriptType>::type_meta(),
+            Self::F64(..) => <f64 as ScriptType>::type_meta(),
+            Self::Str(..) => <str as ScriptType>::type_meta(),
+        }
+    }
 }
 
 pub enum OperatorDeclaration {
@@ -294,6 +425,7 @@ pub enum OperatorDeclaration {
     Hash(HashOperator),
     Invocation(InvocationOperator),
     Binding(BindingOperator),
+    Iterate(IterateOperator),
     Add(AddOperator),
     AddAssign(AddAssignOperator),
     Sub(SubOperator),
@@ -394,6 +526,12 @@ pub struct BindingOperator {
     pub hint_rhs: &'static TypeMeta,
 }
 
+pub struct IterateOperator {
+    pub origin: &'static RustOrigin,
+    pub invoke: fn(origin: Origin, lhs: Arg) -> RuntimeResult<Cell>,
+    pub hint_item: &'static TypeMeta,
+}
+
 pub struct AddOperator {
     pub origin: &'static RustOrigin,
     pub invoke: fn(origin: Origin, lhs: Arg, rhs: Arg) -> RuntimeResult<Cell>,
@@ -556,6 +694,27 @@ pub struct NoneOperator {
 
 pub trait RegisteredType: Send + Sync + 'static {}
 
+// A type of the nested namespace object of the package. The `ID` is a hash of
+// the package and the namespace path computed by the export macro, such that
+// each namespace has a distinct TypeId.
+pub struct Namespace<const ID: u64>;
+
+impl<const ID: u64> RegisteredType for Namespace<ID> {}
+
+impl<'a, const ID: u64> Upcast<'a> for Namespace<ID> {
+    type Output = Box<Self>;
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(Box::new(this))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(Self::type_meta())
+    }
+}
+
 pub trait SizeOf {
     const SIZE: usize;
 }
@@ -33,20 +33,105 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use std::{
-    cell::UnsafeCell,
+    cell::{Cell, UnsafeCell},
     fmt::{Debug, Formatter},
     hint::spin_loop,
     mem::replace,
     ops::{Deref, DerefMut},
-    sync::atomic::{AtomicBool, Ordering},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    thread::{sleep, yield_now},
+    time::{Duration, Instant},
 };
 
 use crate::{
     report::{debug_unreachable, system_panic},
-    runtime::{Origin, RuntimeError, RuntimeResult},
+    runtime::{Origin, RuntimeError, RuntimeResult, TypeMeta},
 };
 
 const BORROW_LIMIT: u32 = 64;
+const YIELD_LIMIT: u32 = 16;
+const WAIT_STEP: Duration = Duration::from_millis(1);
+
+// The zero mark is reserved for the borrow tables that have never been
+// mutably borrowed.
+static NEXT_THREAD_MARK: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    static DEADLINE: Cell<Option<Duration>> = const { Cell::new(None) };
+    static THREAD_MARK: usize = NEXT_THREAD_MARK.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Sets the maximum time the current OS thread waits for the release of a
+/// conflicting data borrow.
+///
+/// When the script (or the host code through the [Cell](crate::runtime::Cell)
+/// API) accesses data that is currently borrowed in a conflicting way (e.g.,
+/// reads an object that another thread holds for writing), the Script
+/// Engine, by default, immediately returns a borrowing error such as
+/// [WriteToRead](RuntimeError::WriteToRead).
+///
+/// If the deadline is specified, the Script Engine instead waits until the
+/// conflicting borrow is released, and only if the borrow outlives the
+/// `deadline`, it returns a [BorrowTimeout](RuntimeError::BorrowTimeout)
+/// error that names the contended data type and the origin of the conflicting
+/// borrow. The deadline is useful when several threads share the same data
+/// objects and hold them for a short time.
+///
+/// The thread never waits for the borrows that were granted on the thread
+/// itself, because the thread cannot release them while waiting. Such
+/// conflicting access fails immediately with the borrowing error regardless
+/// of the deadline.
+///
+/// If the `deadline` is None, the thread does not wait for the conflicting
+/// borrows, which is the default behavior.
+///
+/// ```
+/// # use std::{sync::mpsc::channel, thread::{sleep, spawn}, time::Duration};
+/// #
+/// # use ad_astra::runtime::{set_borrow_deadline, Cell, Origin, RuntimeError};
+/// #
+/// let mut writer = Cell::give(Origin::nil(), 10usize).unwrap();
+/// let mut reader = writer.clone();
+///
+/// let (borrowed, wait_borrowed) = channel();
+///
+/// let release = spawn(move || {
+///     *writer.borrow_mut::<usize>(Origin::nil()).unwrap() += 1;
+///     borrowed.send(()).unwrap();
+///     sleep(Duration::from_millis(10));
+///     drop(writer);
+/// });
+///
+/// wait_borrowed.recv().unwrap();
+///
+/// set_borrow_deadline(Some(Duration::from_secs(10)));
+///
+/// assert_eq!(*reader.borrow_ref::<usize>(Origin::nil()).unwrap(), 11);
+///
+/// release.join().unwrap();
+///
+/// let mut writer = Cell::give(Origin::nil(), 10usize).unwrap();
+/// let mut reader = writer.clone();
+///
+/// let _ = writer.borrow_mut::<usize>(Origin::nil()).unwrap();
+///
+/// let error = reader.borrow_ref::<usize>(Origin::nil()).unwrap_err();
+///
+/// assert!(matches!(error, RuntimeError::WriteToRead { .. }));
+/// ```
+#[inline(always)]
+pub fn set_borrow_deadline(deadline: Option<Duration>) {
+    DEADLINE.with(|current| current.set(deadline));
+}
+
+/// Returns the borrow deadline of the current OS thread previously set by the
+/// [set_borrow_deadline] function.
+///
+/// Returns None if the thread does not wait for the conflicting borrows.
+#[inline(always)]
+pub fn borrow_deadline() -> Option<Duration> {
+    DEADLINE.with(Cell::get)
+}
 
 #[repr(transparent)]
 pub(super) struct BorrowTable(SpinMutex<BorrowTableInner>);
@@ -107,6 +192,7 @@ impl BorrowTable {
         Self(SpinMutex::new(BorrowTableInner {
             value_refs: UnsafeCell::new(BorrowStack::new()),
             value_mut: UnsafeCell::new(None),
+            value_mut_thread: UnsafeCell::new(ThreadMark(0)),
             place_refs: UnsafeCell::new(BorrowStack::new()),
             place_muts: UnsafeCell::new(BorrowStack::new()),
         }))
@@ -116,6 +202,105 @@ impl BorrowTable {
     pub(super) fn access(&self) -> BorrowTableAccess {
         BorrowTableAccess(self.0.lock())
     }
+
+    // Tries to grant access using the `grant` function. If the access conflicts
+    // with an active borrow of another thread, waits until the thread's borrow
+    // deadline expires.
+    #[inline(always)]
+    pub(super) fn grant(
+        &self,
+        ty: &'static TypeMeta,
+        origin: Origin,
+        grant: impl Fn(&BorrowTableAccess) -> Result<u32, Conflict>,
+    ) -> RuntimeResult<u32> {
+        // The access guard must be released before waiting.
+        let result = grant(&self.access());
+
+        match result {
+            Ok(index) => Ok(index),
+            Err(Conflict { error, local: true }) => Err(error),
+            Err(Conflict { error, .. }) => self.wait(ty, origin, error, grant),
+        }
+    }
+
+    #[cold]
+    fn wait(
+        &self,
+        ty: &'static TypeMeta,
+        origin: Origin,
+        mut error: RuntimeError,
+        grant: impl Fn(&BorrowTableAccess) -> Result<u32, Conflict>,
+    ) -> RuntimeResult<u32> {
+        let Some(deadline) = borrow_deadline() else {
+            return Err(error);
+        };
+
+        let start = Instant::now();
+        let mut attempt = 0;
+
+        loop {
+            let borrow_origin = match &error {
+                RuntimeError::ReadToWrite { borrow_origin, .. }
+                | RuntimeError::WriteToRead { borrow_origin, .. }
+                | RuntimeError::WriteToWrite { borrow_origin, .. } => *borrow_origin,
+
+                _ => return Err(error),
+            };
+
+            let elapsed = start.elapsed();
+
+            if elapsed >= deadline {
+                return Err(RuntimeError::BorrowTimeout {
+                    access_origin: origin,
+                    borrow_origin,
+                    data_type: ty,
+                    deadline,
+                });
+            }
+
+            match attempt < YIELD_LIMIT {
+                true => {
+                    attempt += 1;
+                    yield_now();
+                }
+
+                false => sleep(WAIT_STEP.min(deadline - elapsed)),
+            }
+
+            error = match grant(&self.access()) {
+                Ok(index) => return Ok(index),
+                Err(Conflict { error, local: true }) => return Err(error),
+                Err(Conflict { error, .. }) => error,
+            };
+        }
+    }
+}
+
+// A denied access request.
+pub(super) struct Conflict {
+    error: RuntimeError,
+    // True if the conflicting borrow was granted on the current thread. The
+    // thread cannot release such borrow while it waits for the release.
+    local: bool,
+}
+
+impl From<RuntimeError> for Conflict {
+    #[inline(always)]
+    fn from(error: RuntimeError) -> Self {
+        Self { error, local: true }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct ThreadMark(usize);
+
+impl ThreadMark {
+    // Each thread receives a unique mark on first use. Unlike the thread-local
+    // variable addresses, the marks are never reused by later threads.
+    #[inline(always)]
+    fn current() -> Self {
+        THREAD_MARK.with(|mark| Self(*mark))
+    }
 }
 
 #[repr(transparent)]
@@ -123,7 +308,7 @@ pub(super) struct BorrowTableAccess<'a>(SpinGuard<'a, BorrowTableInner>);
 
 impl BorrowTableAccess<'_> {
     #[inline(always)]
-    pub(super) fn grant_value_ref(&self, origin: Origin) -> RuntimeResult<u32> {
+    pub(super) fn grant_value_ref(&self, origin: Origin) -> Result<u32, Conflict> {
         {
             // Safety:
             //   1. Data race is guarded by the mutex.
@@ -131,9 +316,12 @@ impl BorrowTableAccess<'_> {
             let value_mut = unsafe { &*self.0.value_mut.get() };
 
             if let Some(cause) = value_mut {
-                return Err(RuntimeError::WriteToRead {
-                    access_origin: origin,
-                    borrow_origin: *cause,
+                return Err(Conflict {
+                    error: RuntimeError::WriteToRead {
+                        access_origin: origin,
+                        borrow_origin: *cause,
+                    },
+                    local: self.is_value_mut_local(),
                 });
             }
         }
@@ -145,9 +333,12 @@ impl BorrowTableAccess<'_> {
             let place_muts = unsafe { &*self.0.place_muts.get() };
 
             if let Some(cause) = place_muts.last() {
-                return Err(RuntimeError::WriteToRead {
-                    access_origin: origin,
-                    borrow_origin: *cause,
+                return Err(Conflict {
+                    error: RuntimeError::WriteToRead {
+                        access_origin: origin,
+                        borrow_origin: *cause,
+                    },
+                    local: place_muts.is_local(),
                 });
             }
         }
@@ -164,7 +355,7 @@ impl BorrowTableAccess<'_> {
     }
 
     #[inline(always)]
-    pub(super) fn grant_value_mut(&self, origin: Origin) -> RuntimeResult<u32> {
+    pub(super) fn grant_value_mut(&self, origin: Origin) -> Result<u32, Conflict> {
         {
             // Safety:
             //   1. Data race is guarded by the mutex.
@@ -172,9 +363,12 @@ impl BorrowTableAccess<'_> {
             let value_refs = unsafe { &*self.0.value_refs.get() };
 
             if let Some(cause) = value_refs.last() {
-                return Err(RuntimeError::ReadToWrite {
-                    access_origin: origin,
-                    borrow_origin: *cause,
+                return Err(Conflict {
+                    error: RuntimeError::ReadToWrite {
+                        access_origin: origin,
+                        borrow_origin: *cause,
+                    },
+                    local: value_refs.is_local(),
                 });
             }
         }
@@ -186,9 +380,12 @@ impl BorrowTableAccess<'_> {
             let place_refs = unsafe { &*self.0.place_refs.get() };
 
             if let Some(cause) = place_refs.last() {
-                return Err(RuntimeError::ReadToWrite {
-                    access_origin: origin,
-                    borrow_origin: *cause,
+                return Err(Conflict {
+                    error: RuntimeError::ReadToWrite {
+                        access_origin: origin,
+                        borrow_origin: *cause,
+                    },
+                    local: place_refs.is_local(),
                 });
             }
         }
@@ -200,9 +397,12 @@ impl BorrowTableAccess<'_> {
             let place_muts = unsafe { &*self.0.place_muts.get() };
 
             if let Some(cause) = place_muts.last() {
-                return Err(RuntimeError::WriteToWrite {
-                    access_origin: origin,
-                    borrow_origin: *cause,
+                return Err(Conflict {
+                    error: RuntimeError::WriteToWrite {
+                        access_origin: origin,
+                        borrow_origin: *cause,
+                    },
+                    local: place_muts.is_local(),
                 });
             }
         }
@@ -214,20 +414,28 @@ impl BorrowTableAccess<'_> {
             let value_mut = unsafe { &mut *self.0.value_mut.get() };
 
             if let Some(cause) = value_mut {
-                return Err(RuntimeError::WriteToWrite {
-                    access_origin: origin,
-                    borrow_origin: *cause,
+                return Err(Conflict {
+                    error: RuntimeError::WriteToWrite {
+                        access_origin: origin,
+                        borrow_origin: *cause,
+                    },
+                    local: self.is_value_mut_local(),
                 });
             }
 
             *value_mut = Some(origin);
-        };
+        }
+
+        // Safety:
+        //   1. Data race is guarded by the mutex.
+        //   2. BorrowTable access is always localized within a single thread.
+        unsafe { *self.0.value_mut_thread.get() = ThreadMark::current() };
 
         Ok(0)
     }
 
     #[inline(always)]
-    pub(super) fn grant_place_ref(&self, origin: Origin) -> RuntimeResult<u32> {
+    pub(super) fn grant_place_ref(&self, origin: Origin) -> Result<u32, Conflict> {
         {
             // Safety:
             //   1. Data race is guarded by the mutex.
@@ -235,9 +443,12 @@ impl BorrowTableAccess<'_> {
             let value_mut = unsafe { &*self.0.value_mut.get() };
 
             if let Some(cause) = value_mut {
-                return Err(RuntimeError::WriteToRead {
-                    access_origin: origin,
-                    borrow_origin: *cause,
+                return Err(Conflict {
+                    error: RuntimeError::WriteToRead {
+                        access_origin: origin,
+                        borrow_origin: *cause,
+                    },
+                    local: self.is_value_mut_local(),
                 });
             }
         }
@@ -255,7 +466,7 @@ impl BorrowTableAccess<'_> {
     }
 
     #[inline(always)]
-    pub(super) fn grant_place_mut(&self, origin: Origin) -> RuntimeResult<u32> {
+    pub(super) fn grant_place_mut(&self, origin: Origin) -> Result<u32, Conflict> {
         {
             // Safety:
             //   1. Data race is guarded by the mutex.
@@ -263,9 +474,12 @@ impl BorrowTableAccess<'_> {
             let value_mut = unsafe { &*self.0.value_mut.get() };
 
             if let Some(cause) = value_mut {
-                return Err(RuntimeError::WriteToWrite {
-                    access_origin: origin,
-                    borrow_origin: *cause,
+                return Err(Conflict {
+                    error: RuntimeError::WriteToWrite {
+                        access_origin: origin,
+                        borrow_origin: *cause,
+                    },
+                    local: self.is_value_mut_local(),
                 });
             }
         }
@@ -277,9 +491,12 @@ impl BorrowTableAccess<'_> {
             let value_refs = unsafe { &*self.0.value_refs.get() };
 
             if let Some(cause) = value_refs.last() {
-                return Err(RuntimeError::ReadToWrite {
-                    access_origin: origin,
-                    borrow_origin: *cause,
+                return Err(Conflict {
+                    error: RuntimeError::ReadToWrite {
+                        access_origin: origin,
+                        borrow_origin: *cause,
+                    },
+                    local: value_refs.is_local(),
                 });
             }
         }
@@ -296,6 +513,16 @@ impl BorrowTableAccess<'_> {
         Ok(index)
     }
 
+    #[inline(always)]
+    fn is_value_mut_local(&self) -> bool {
+        // Safety:
+        //   1. Data race is guarded by the mutex.
+        //   2. BorrowTable access is always localized within a single thread.
+        let value_mut_thread = unsafe { &*self.0.value_mut_thread.get() };
+
+        *value_mut_thread == ThreadMark::current()
+    }
+
     // Safety: index was granted by corresponding function and never released before.
     #[inline(always)]
     pub(super) unsafe fn release_value_ref(&self, index: u32) {
@@ -514,6 +741,7 @@ impl BorrowTableAccess<'_> {
 struct BorrowTableInner {
     value_refs: UnsafeCell<BorrowStack>,
     value_mut: UnsafeCell<Option<Origin>>,
+    value_mut_thread: UnsafeCell<ThreadMark>,
     place_refs: UnsafeCell<BorrowStack>,
     place_muts: UnsafeCell<BorrowStack>,
 }
@@ -583,6 +811,17 @@ impl BorrowStack {
         }
     }
 
+    // Returns true if any of the borrows was granted on the current thread.
+    #[inline]
+    fn is_local(&self) -> bool {
+        let current = ThreadMark::current();
+
+        self.entries.iter().any(|entry| match entry {
+            BorrowEntry::Occupied { thread, .. } => *thread == current,
+            BorrowEntry::Vacant(..) => false,
+        })
+    }
+
     #[cfg(debug_assertions)]
     #[inline(always)]
     fn is_empty(&self) -> bool {
@@ -610,6 +849,7 @@ impl BorrowStack {
                     previous,
                     next: self.next,
                     origin,
+                    thread: ThreadMark::current(),
                 });
             }
 
@@ -635,6 +875,7 @@ impl BorrowStack {
                     previous,
                     next: self.next,
                     origin,
+                    thread: ThreadMark::current(),
                 }
             }
         }
@@ -686,6 +927,7 @@ impl BorrowStack {
                         previous,
                         next,
                         origin,
+                        ..
                     } => {
                         if index == self.last {
                             self.last = previous;
@@ -740,6 +982,7 @@ enum BorrowEntry {
         previous: u32,
         next: u32,
         origin: Origin,
+        thread: ThreadMark,
     },
     Vacant(u32),
 }
@@ -806,3 +1049,89 @@ impl<'a, T> DerefMut for SpinGuard<'a, T> {
         unsafe { &mut *self.mutex.data.get() }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::mpsc::channel,
+        thread::{sleep, spawn},
+        time::{Duration, Instant},
+    };
+
+    use crate::runtime::{set_borrow_deadline, Cell, Origin, RuntimeError};
+
+    #[test]
+    fn test_borrow_deadline_wait() {
+        let mut writer = Cell::give(Origin::nil(), 10usize).unwrap();
+        let mut reader = writer.clone();
+
+        let (borrowed, wait_borrowed) = channel();
+
+        let release = spawn(move || {
+            *writer.borrow_mut::<usize>(Origin::nil()).unwrap() += 1;
+            borrowed.send(()).unwrap();
+            sleep(Duration::from_millis(50));
+            drop(writer);
+        });
+
+        wait_borrowed.recv().unwrap();
+
+        set_borrow_deadline(Some(Duration::from_secs(10)));
+
+        assert_eq!(*reader.borrow_ref::<usize>(Origin::nil()).unwrap(), 11);
+
+        release.join().unwrap();
+
+        let mut writer = Cell::give(Origin::nil(), 10usize).unwrap();
+        let mut reader = writer.clone();
+
+        let (borrowed, wait_borrowed) = channel();
+        let (finished, wait_finished) = channel::<()>();
+
+        let release = spawn(move || {
+            let _ = writer.borrow_mut::<usize>(Origin::nil()).unwrap();
+            borrowed.send(()).unwrap();
+            let _ = wait_finished.recv();
+        });
+
+        wait_borrowed.recv().unwrap();
+
+        set_borrow_deadline(Some(Duration::from_millis(20)));
+
+        let error = reader.borrow_ref::<usize>(Origin::nil()).unwrap_err();
+
+        assert!(matches!(error, RuntimeError::BorrowTimeout { .. }));
+
+        drop(finished);
+        release.join().unwrap();
+    }
+
+    #[test]
+    fn test_borrow_deadline_local() {
+        set_borrow_deadline(Some(Duration::from_secs(10)));
+
+        let mut writer = Cell::give(Origin::nil(), 10usize).unwrap();
+        let mut reader = writer.clone();
+
+        let _ = writer.borrow_mut::<usize>(Origin::nil()).unwrap();
+
+        let start = Instant::now();
+
+        let error = reader.borrow_ref::<usize>(Origin::nil()).unwrap_err();
+
+        assert!(matches!(error, RuntimeError::WriteToRead { .. }));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        let mut reader = Cell::give(Origin::nil(), 10usize).unwrap();
+        let mut writer = reader.clone();
+
+        let _ = reader.borrow_ref::<usize>(Origin::nil()).unwrap();
+
+        let start = Instant::now();
+
+        let error = writer.borrow_mut::<usize>(Origin::nil()).unwrap_err();
+
+        assert!(matches!(error, RuntimeError::ReadToWrite { .. }));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,352 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    cell::RefCell,
+    mem::replace,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use crate::runtime::{Origin, RuntimeError, RuntimeResult};
+
+thread_local! {
+    static BUDGET: RefCell<Option<Arc<MemoryBudget>>> = const { RefCell::new(None) };
+}
+
+// A cap on the memory that the script-visible data objects allocate during
+// the script evaluation.
+//
+// The budget is charged by the data objects created while the budget is
+// active on the current thread, and each such object credits the budget back
+// when it is freed, regardless of the thread on which it is freed.
+//
+// The budgets of the nested evaluations are chained to the budgets of the
+// outer evaluations, such that the allocations of the nested evaluation are
+// charged against the outer limits as well.
+pub(crate) struct MemoryBudget {
+    parent: Option<Arc<MemoryBudget>>,
+    limit: usize,
+    used: AtomicUsize,
+}
+
+impl MemoryBudget {
+    fn charge(&self, origin: Origin, bytes: usize) -> RuntimeResult<()> {
+        let mut budget = self;
+
+        loop {
+            let current = budget
+                .used
+                .fetch_add(bytes, Ordering::Relaxed)
+                .saturating_add(bytes);
+
+            if current > budget.limit {
+                let _ = budget.used.fetch_sub(bytes, Ordering::Relaxed);
+
+                // Rolls back the charges of the inner budgets.
+                let mut rollback = self;
+
+                while !std::ptr::eq(rollback, budget) {
+                    let _ = rollback.used.fetch_sub(bytes, Ordering::Relaxed);
+
+                    let Some(parent) = &rollback.parent else {
+                        break;
+                    };
+
+                    rollback = parent;
+                }
+
+                return Err(RuntimeError::MemoryLimitExceeded {
+                    origin,
+                    current,
+                    limit: budget.limit,
+                });
+            }
+
+            let Some(parent) = &budget.parent else {
+                return Ok(());
+            };
+
+            budget = parent;
+        }
+    }
+
+    fn credit(&self, bytes: usize) {
+        let mut budget = self;
+
+        loop {
+            let _ = budget.used.fetch_sub(bytes, Ordering::Relaxed);
+
+            let Some(parent) = &budget.parent else {
+                return;
+            };
+
+            budget = parent;
+        }
+    }
+}
+
+// Activates a new memory budget on the current thread for the lifetime of
+// this object.
+pub(crate) struct BudgetScope {
+    previous: Option<Arc<MemoryBudget>>,
+}
+
+impl Drop for BudgetScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+
+        let _ = BUDGET.with_borrow_mut(|current| replace(current, previous));
+    }
+}
+
+impl BudgetScope {
+    #[inline(always)]
+    pub(crate) fn enter(limit: usize) -> Self {
+        BUDGET.with_borrow_mut(|current| {
+            let budget = Arc::new(MemoryBudget {
+                parent: current.clone(),
+                limit,
+                used: AtomicUsize::new(0),
+            });
+
+            Self {
+                previous: replace(current, Some(budget)),
+            }
+        })
+    }
+}
+
+// The amount of memory that a data object charged to the memory budget.
+//
+// The charge credits the budget back when dropped.
+#[derive(Default)]
+pub(crate) struct MemoryCharge {
+    budget: Option<Arc<MemoryBudget>>,
+    bytes: AtomicUsize,
+}
+
+impl Drop for MemoryCharge {
+    fn drop(&mut self) {
+        let Some(budget) = &self.budget else {
+            return;
+        };
+
+        budget.credit(*self.bytes.get_mut());
+    }
+}
+
+impl MemoryCharge {
+    // Charges the memory budget active on the current thread (if any) by the
+    // specified number of bytes.
+    //
+    // Returns a MemoryLimitExceeded error pointing to the `origin` if the
+    // budget is exhausted.
+    #[inline(always)]
+    pub(crate) fn new(origin: Origin, bytes: usize) -> RuntimeResult<Self> {
+        let Some(budget) = BUDGET.with_borrow(|current| current.clone()) else {
+            return Ok(Self::default());
+        };
+
+        budget.charge(origin, bytes)?;
+
+        Ok(Self {
+            budget: Some(budget),
+            bytes: AtomicUsize::new(bytes),
+        })
+    }
+
+    // Charges the budget of this charge by the additional number of bytes.
+    //
+    // If the data object was created without an active budget, the function
+    // does nothing.
+    #[inline(always)]
+    pub(crate) fn grow(&self, origin: Origin, bytes: usize) -> RuntimeResult<()> {
+        let Some(budget) = &self.budget else {
+            return Ok(());
+        };
+
+        budget.charge(origin, bytes)?;
+
+        let _ = self.bytes.fetch_add(bytes, Ordering::Relaxed);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        interpret::ScriptFn,
+        runtime::{Origin, RuntimeError, RuntimeResult, ScriptPackage},
+    };
+
+    fn compile(text: &str) -> ScriptFn {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        read.compile().unwrap()
+    }
+
+    #[export]
+    pub fn budget_nested(f: ScriptFn) -> RuntimeResult<usize> {
+        f.run_with_memory_limit(usize::MAX)?
+            .take::<usize>(Origin::nil())
+    }
+
+    #[test]
+    fn test_memory_limit_doubling() {
+        let script_fn = compile(
+            r#"
+            let s = "x";
+            let steps = 0;
+
+            loop {
+                s = [s, s];
+                steps += 1;
+            }
+            "#,
+        );
+
+        let mut reports = Vec::new();
+
+        for _ in 0..3 {
+            let Err(error) = script_fn.run_with_memory_limit(64 * 1024) else {
+                panic!("Evaluation succeeded.");
+            };
+
+            let RuntimeError::MemoryLimitExceeded {
+                origin,
+                current,
+                limit,
+            } = &error
+            else {
+                panic!("Unexpected error: {error}");
+            };
+
+            assert!(*current > *limit);
+            assert_eq!(*limit, 64 * 1024);
+
+            reports.push((*origin, *current));
+        }
+
+        assert!(reports.windows(2).all(|pair| pair[0] == pair[1]));
+    }
+
+    #[test]
+    fn test_memory_limit_credit() {
+        let script_fn = compile(
+            r#"
+            let total = 0;
+
+            for i in 0..1000 {
+                let s = "x";
+
+                for j in 0..10 {
+                    s = [s, s];
+                }
+
+                total += s.len;
+            }
+
+            return total;
+            "#,
+        );
+
+        let result = script_fn.run_with_memory_limit(64 * 1024).unwrap();
+
+        assert_eq!(result.take::<usize>(Origin::nil()).unwrap(), 1024 * 1000);
+
+        let script_fn = compile(
+            r#"
+            let builder = struct {
+                build: fn(depth) {
+                    if depth == 0 {
+                        return struct {};
+                    }
+
+                    let rest = self.build(depth - 1);
+
+                    return struct { depth: depth, rest: rest };
+                },
+            };
+
+            return builder.build(50);
+            "#,
+        );
+
+        assert!(script_fn.run_with_memory_limit(1024 * 1024).is_ok());
+
+        let result = script_fn.run_with_memory_limit(8 * 1024);
+
+        assert!(matches!(
+            result,
+            Err(RuntimeError::MemoryLimitExceeded { .. })
+        ));
+
+        let script_fn = compile(
+            r#"
+            let inner = fn() {
+                let s = "x";
+
+                loop {
+                    s = [s, s];
+                }
+            };
+
+            return budget_nested(inner);
+            "#,
+        );
+
+        let Err(RuntimeError::UpcastResult { cause, .. }) =
+            script_fn.run_with_memory_limit(64 * 1024)
+        else {
+            panic!("Nested evaluation succeeded.");
+        };
+
+        assert!(matches!(
+            cause.downcast_ref::<RuntimeError>(),
+            Some(RuntimeError::MemoryLimitExceeded { limit, .. }) if *limit == 64 * 1024
+        ));
+
+        assert!(compile(r#"let s = "x"; return [s, s];"#).run().is_ok());
+    }
+}
@@ -0,0 +1,390 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::{
+    exports::Struct,
+    runtime::{
+        Arg,
+        Cell,
+        Ident,
+        Origin,
+        RuntimeError,
+        RuntimeResult,
+        ScriptType,
+        TypeHint,
+        Upcast,
+    },
+};
+
+/// A builder of the script structs (`struct { x: 10, name: "foo" }`) from
+/// Rust code.
+///
+/// The builder is the recommended way to construct composite data returned
+/// from the [custom component](crate::export) functions and other low-level
+/// exporting interfaces:
+///
+/// ```
+/// # use ad_astra::runtime::{Origin, ScriptStructBuilder};
+/// #
+/// let cell = ScriptStructBuilder::new(Origin::nil())
+///     .field("x", 10usize)
+///     .unwrap()
+///     .field("name", "foo")
+///     .unwrap()
+///     .finish()
+///     .unwrap();
+///
+/// assert_eq!(cell.stringify(false), r#"{"name": "foo", "x": 10}"#);
+/// ```
+///
+/// The resulting [Cell] is indistinguishable from the struct constructed by
+/// the script code: its entries are accessible through the field access
+/// operator, the script code can assign new entries to it, and the
+/// function entries are bound to the struct the same way as if they were
+/// assigned by the script.
+///
+/// The builder implements [Upcast] into the resulting struct. Therefore, you
+/// can pass one builder as a field value of another builder to construct
+/// nested structs without calling [finish](Self::finish) explicitly.
+///
+/// ```ignore
+/// #[export]
+/// impl Foo {
+///     #[export(component DynamicType)]
+///     fn bounds(origin: Origin, _arg: Arg) -> RuntimeResult<Cell> {
+///         ScriptStructBuilder::new(origin)
+///             .field("min", ScriptStructBuilder::new(origin).field("x", 0usize)?)?
+///             .field("max", ScriptStructBuilder::new(origin).field("x", 10usize)?)?
+///             .finish()
+///     }
+/// }
+/// ```
+pub struct ScriptStructBuilder {
+    origin: Origin,
+    entries: Vec<(Ident, Cell)>,
+}
+
+impl<'a> Upcast<'a> for ScriptStructBuilder {
+    type Output = Cell;
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        this.finish()
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(Struct::type_meta())
+    }
+}
+
+impl ScriptStructBuilder {
+    /// Creates a builder of an empty struct.
+    ///
+    /// The `origin` parameter specifies the Rust or Script source code range
+    /// where the struct is created. Typically, this is the origin passed to
+    /// the custom component function.
+    #[inline(always)]
+    pub fn new(origin: Origin) -> Self {
+        Self {
+            origin,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds an entry with the specified `name` to the struct.
+    ///
+    /// The `value` can be any Rust value that can be [upcasted](Upcast) to a
+    /// type known to the Script Engine, including another builder.
+    ///
+    /// If the struct already has an entry with this name, the new value
+    /// replaces the previous one, similar to the script field assignment.
+    ///
+    /// The function returns a [RuntimeError] if the upcasting procedure fails,
+    /// or if the upcasted value is Nil (the script structs cannot have Nil
+    /// entries).
+    pub fn field(
+        mut self,
+        name: impl AsRef<str>,
+        value: impl Upcast<'static>,
+    ) -> RuntimeResult<Self> {
+        let data = Cell::give(self.origin, value)?;
+
+        if data.ty().prototype().implements_none() {
+            return Err(RuntimeError::Nil {
+                access_origin: self.origin,
+            });
+        }
+
+        self.entries.push((Ident::from_string(name.as_ref()), data));
+
+        Ok(self)
+    }
+
+    /// Finishes the building and returns a Cell that points to the script
+    /// struct.
+    ///
+    /// The function returns a [RuntimeError] if the evaluation's memory limit
+    /// is exceeded, or if any entry fails to bind to the struct.
+    pub fn finish(self) -> RuntimeResult<Cell> {
+        let structure = Cell::give(self.origin, Struct::default())?;
+
+        for (key, data) in self.entries {
+            Struct::insert_entry(
+                self.origin,
+                self.origin,
+                structure.clone(),
+                key,
+                Arg {
+                    origin: self.origin,
+                    data,
+                },
+            )?;
+        }
+
+        Ok(structure)
+    }
+}
+
+/// A builder of the script arrays (`[10, 20, 30]`) from Rust code.
+///
+/// Similar to the [ScriptStructBuilder], this builder is the recommended way to
+/// construct arrays of data returned from the
+/// [custom component](crate::export) functions.
+///
+/// ```
+/// # use ad_astra::runtime::{Origin, ScriptArrayBuilder};
+/// #
+/// let cell = ScriptArrayBuilder::new(Origin::nil())
+///     .push(10usize)
+///     .unwrap()
+///     .push(20usize)
+///     .unwrap()
+///     .finish()
+///     .unwrap();
+///
+/// assert_eq!(cell.take_vec::<usize>(Origin::nil()).unwrap(), vec![10, 20]);
+/// ```
+///
+/// The builder joins the pushed items the same way as the script array
+/// constructor does: Nil items are skipped, nested arrays are flattened, and
+/// the type of the first non-Nil item concatenates the rest of the items
+/// (possibly casting them to this type). If the builder has no items, the
+/// resulting Cell is [Nil](Cell::nil).
+///
+/// The builder implements [Upcast] into the resulting array, so you can
+/// pass it as a field value of the [ScriptStructBuilder].
+pub struct ScriptArrayBuilder {
+    origin: Origin,
+    items: Vec<Arg>,
+}
+
+impl<'a> Upcast<'a> for ScriptArrayBuilder {
+    type Output = Cell;
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        this.finish()
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::dynamic()
+    }
+}
+
+impl ScriptArrayBuilder {
+    /// Creates a builder of an empty array.
+    ///
+    /// The `origin` parameter specifies the Rust or Script source code range
+    /// where the array is created.
+    #[inline(always)]
+    pub fn new(origin: Origin) -> Self {
+        Self {
+            origin,
+            items: Vec::new(),
+        }
+    }
+
+    /// Appends the `item` to the end of the array.
+    ///
+    /// The `item` can be any Rust value that can be [upcasted](Upcast) to a
+    /// type known to the Script Engine, including another builder.
+    ///
+    /// The function returns a [RuntimeError] if the upcasting procedure fails.
+    pub fn push(mut self, item: impl Upcast<'static>) -> RuntimeResult<Self> {
+        let data = Cell::give(self.origin, item)?;
+
+        self.items.push(Arg {
+            origin: self.origin,
+            data,
+        });
+
+        Ok(self)
+    }
+
+    /// Finishes the building and returns a Cell that points to the script
+    /// array.
+    ///
+    /// The function returns a [RuntimeError] if the items cannot be
+    /// concatenated into a single array (e.g., if they have incompatible
+    /// types).
+    pub fn finish(mut self) -> RuntimeResult<Cell> {
+        let receiver = self
+            .items
+            .iter()
+            .find(|item| !item.data.is_nil())
+            .map(|item| item.data.ty());
+
+        let Some(receiver) = receiver else {
+            return Ok(Cell::nil());
+        };
+
+        receiver.concat(self.origin, &mut self.items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Error;
+
+    use crate::{
+        analysis::symbols::tests::TestPackage,
+        export,
+        exports::Struct,
+        runtime::{
+            Arg,
+            Cell,
+            Origin,
+            RuntimeError,
+            RuntimeResult,
+            ScriptArrayBuilder,
+            ScriptStructBuilder,
+        },
+    };
+
+    #[export]
+    #[derive(Clone, Debug)]
+    pub struct TestShape;
+
+    #[export]
+    impl TestShape {
+        #[export(component Struct)]
+        fn test_bounds(origin: Origin, _arg: Arg) -> RuntimeResult<Cell> {
+            ScriptStructBuilder::new(origin)
+                .field("name", "box")?
+                .field(
+                    "min",
+                    ScriptStructBuilder::new(origin)
+                        .field("x", 1usize)?
+                        .field("y", 2usize)?,
+                )?
+                .field(
+                    "points",
+                    ScriptArrayBuilder::new(origin)
+                        .push(3usize)?
+                        .push(ScriptArrayBuilder::new(origin).push(4usize)?.push(5usize)?)?,
+                )?
+                .finish()
+        }
+    }
+
+    #[export]
+    pub fn test_shape() -> TestShape {
+        TestShape
+    }
+
+    fn eval(text: &str) -> String {
+        crate::eval!(TestPackage, text).stringify(false)
+    }
+
+    #[test]
+    fn test_struct_builder() {
+        assert_eq!(eval("return test_shape().test_bounds.name;"), "box");
+        assert_eq!(eval("return test_shape().test_bounds.min.y;"), "2");
+        assert_eq!(eval("return test_shape().test_bounds.points;"), "[3, 4, 5]");
+        assert_eq!(eval("return test_shape().test_bounds.points[1];"), "4");
+
+        assert_eq!(
+            eval(
+                r#"
+                return test_shape().test_bounds.min == struct { x: 1, y: 2 };
+                "#
+            ),
+            "true",
+        );
+
+        assert_eq!(
+            eval(
+                r#"
+                let bounds = test_shape().test_bounds;
+                bounds.min.x = 10;
+                bounds.upper = struct { x: 20 };
+                bounds.area = fn() { return self.upper.x - self.min.x; };
+                return bounds.area();
+                "#
+            ),
+            "10",
+        );
+
+        assert_eq!(eval("return test_shape().test_bounds.unknown?;"), "false");
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        let result = ScriptStructBuilder::new(Origin::nil())
+            .field("x", 10usize)
+            .and_then(|builder| builder.field("y", ()));
+
+        assert!(matches!(result, Err(RuntimeError::Nil { .. })));
+
+        let result = ScriptArrayBuilder::new(Origin::nil()).push(Err::<usize, _>(Error));
+
+        assert!(matches!(result, Err(RuntimeError::UpcastResult { .. })));
+
+        let result = ScriptArrayBuilder::new(Origin::nil())
+            .push(10usize)
+            .and_then(|array| array.push(ScriptStructBuilder::new(Origin::nil())))
+            .and_then(|array| ScriptStructBuilder::new(Origin::nil()).field("inner", array));
+
+        assert!(result.is_err());
+
+        let empty = ScriptArrayBuilder::new(Origin::nil())
+            .push(())
+            .and_then(|builder| builder.finish())
+            .unwrap();
+
+        assert!(empty.is_nil());
+    }
+}
@@ -388,11 +388,16 @@ impl Cell {
     ///
     /// The generic parameter `T` is the type of the elements in the array and
     /// must be a type known to the Script Engine.
+    ///
+    /// In particular, unlike the give function, which turns a `Vec<u8>` into
+    /// a script `bytes` object, give_vec keeps such a vector as an array of
+    /// [u8] numbers.
     pub fn give_vec<T: ScriptType>(origin: Origin, data: Vec<T>) -> RuntimeResult<Self> {
-        let to = match data.into_chain(origin)? {
-            UpcastedChain::Cell(cell) => return Ok(cell),
-            UpcastedChain::Slice(memory_slice) => memory_slice,
-        };
+        if TypeId::of::<T>() == TypeId::of::<()>() {
+            return Ok(Self::nil());
+        }
+
+        let to = MemorySlice::register_vec(origin, data)?;
 
         Ok(Self(Some(Arc::new(Chain(ChainInner {
             from: Default::default(),
@@ -410,6 +415,106 @@ impl Cell {
         self.0.is_none()
     }
 
+    /// Releases this Cell immediately, turning it into a [Nil](Cell::nil) Cell.
+    ///
+    /// If this Cell is the only reference to the data owned by the Script
+    /// Engine, the data is dropped before the function returns. Otherwise, if
+    /// the Cell is a projection of another Cell's data (e.g., a field of
+    /// a struct), the function releases the projection's borrow, but the
+    /// underlying data remains alive.
+    ///
+    /// The function returns a [RuntimeError] and keeps this Cell unchanged in
+    /// the following cases:
+    ///
+    ///  - Other Cells currently borrow the data (e.g., the projections of this
+    ///    Cell created by the [Cell::map_ref] function are still alive).
+    ///  - The data is shared with other clones of this Cell. In this case,
+    ///    the function returns the [SharedRelease](RuntimeError::SharedRelease)
+    ///    error.
+    ///
+    /// The `origin` parameter specifies the Rust or Script source code range
+    /// where the release occurs.
+    ///
+    /// Normally, you don't need to call this function: the Script Engine drops
+    /// the data as soon as the last Cell referring to this data is dropped.
+    /// For instance, the values created by the script during the
+    /// [evaluation](crate::interpret::ScriptFn::run) are dropped before
+    /// the evaluation function returns, unless they are referred to by the
+    /// returned Cell, the script's context, or the Cells retained by the host.
+    /// The release function is useful when the data holds expensive resources,
+    /// and the host wants to ensure that the resources are freed at
+    /// a particular point of the program.
+    pub fn release(&mut self, origin: Origin) -> RuntimeResult<()> {
+        let Some(chain) = &self.0 else {
+            return Ok(());
+        };
+
+        if chain.0.grant.is_none() {
+            // Checks that no other Cell borrows the data.
+            let grant = chain.0.to.grant_value_mut(origin)?;
+
+            // Safety: The grant belongs to this MemorySlice.
+            unsafe { chain.0.to.release_grant(grant) };
+        }
+
+        if Arc::strong_count(chain) > 1
+            || (chain.0.from.is_nil() && Arc::strong_count(&chain.0.to) > 1)
+        {
+            return Err(RuntimeError::SharedRelease {
+                access_origin: origin,
+                data_origin: chain.0.data_origin(),
+            });
+        }
+
+        *self = Self::nil();
+
+        Ok(())
+    }
+
+    /// Returns true if this Cell is the only reference to the memory owned by
+    /// the Script Engine, and the memory is not borrowed.
+    ///
+    /// Taking the data out of such a Cell moves the data without cloning.
+    #[inline(always)]
+    pub(crate) fn is_unique(&self) -> bool {
+        let Some(chain) = &self.0 else {
+            return false;
+        };
+
+        Arc::strong_count(chain) == 1
+            && chain.0.grant.is_none()
+            && chain.0.from.is_nil()
+            && chain.0.to.is_owned()
+            && Arc::strong_count(&chain.0.to) == 1
+    }
+
+    // Returns true if both Cells reference the same data object, or if both
+    // Cells are Nil.
+    #[inline(always)]
+    pub(crate) fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (None, None) => true,
+            (Some(this), Some(other)) => Arc::ptr_eq(this, other),
+            _ => false,
+        }
+    }
+
+    // Charges the memory budget of the Cell's data object by the additional
+    // number of bytes that the object allocated internally (e.g., by the
+    // script struct entries). The budget is credited back when the object is
+    // freed.
+    //
+    // Does nothing if the object has been created without an active memory
+    // budget.
+    #[inline(always)]
+    pub(crate) fn charge_memory(&self, origin: Origin, bytes: usize) -> RuntimeResult<()> {
+        let Some(chain) = &self.0 else {
+            return Ok(());
+        };
+
+        chain.0.to.charge().grow(origin, bytes)
+    }
+
     /// Returns the Rust or Script source code range indicating where the Cell's
     /// data was created.
     #[inline(always)]
@@ -1376,6 +1481,43 @@ impl Cell {
         })))))
     }
 
+    // Returns a new strong reference to the Cell's data if the data is an
+    // allocation of `T` shared through the Arc (see Upcasted for Arc<T>).
+    //
+    // Otherwise, returns None.
+    #[inline(always)]
+    pub(crate) fn to_arc<T: ScriptType>(&self) -> Option<Arc<T>> {
+        let chain = self.0.as_ref()?;
+
+        chain.0.to.to_arc::<T>()
+    }
+
+    // Creates a projection of the Cell's data that shares the data with this
+    // Cell, but does not allow the data mutation: any attempt to borrow the
+    // projection mutably results in the RuntimeError::ReadOnly error.
+    //
+    // The original data remains immutably borrowed until all clones of the
+    // projection are dropped.
+    pub(crate) fn into_read_only(self, origin: Origin) -> RuntimeResult<Self> {
+        let chain = match self.0 {
+            Some(chain) => chain,
+            None => return Ok(Self::nil()),
+        };
+
+        if !chain.0.to.is_writeable() {
+            return Ok(Self(Some(chain)));
+        }
+
+        // Safety: PlaceRef access granted.
+        let to = unsafe { chain.clone().place_ref(origin)?.0.to.read_only(origin)? };
+
+        Ok(Self(Some(Arc::new(Chain(ChainInner {
+            from: Self(Some(chain)),
+            to,
+            grant: None,
+        })))))
+    }
+
     #[allow(unused)]
     fn value_ref(mut self, origin: Origin) -> RuntimeResult<Self> {
         if self.0.is_none() {
@@ -1450,6 +1592,18 @@ impl Debug for Chain {
 impl Drop for Chain {
     fn drop(&mut self) {
         self.0.release();
+
+        // Unwinds the chain of projections iteratively, such that dropping of
+        // a long chain does not overflow the thread stack.
+        let mut from = take(&mut self.0.from);
+
+        while let Some(chain) = take(&mut from.0) {
+            let Ok(mut chain) = Arc::try_unwrap(chain) else {
+                break;
+            };
+
+            from = take(&mut chain.0.from);
+        }
     }
 }
 
@@ -35,18 +35,23 @@
 use std::{
     any::TypeId,
     mem::{take, transmute},
+    slice::from_raw_parts,
     sync::Arc,
 };
 
-use crate::runtime::{
-    memory::MemorySlice,
-    Cell,
-    Origin,
-    RuntimeError,
-    RuntimeResult,
-    ScriptType,
-    TypeHint,
-    TypeMeta,
+use crate::{
+    exports::Bytes,
+    runtime::{
+        memory::MemorySlice,
+        Cell,
+        Origin,
+        RuntimeError,
+        RuntimeResult,
+        ScriptType,
+        StructSchema,
+        TypeHint,
+        TypeMeta,
+    },
 };
 
 /// A trait that casts Script data into Rust data.
@@ -450,6 +455,18 @@ pub trait Downcast<'a>: Sized + Send + Sync + 'a {
     /// up to indicating [TypeHint::dynamic], which means that the source
     /// type(s) are not known at compile time.
     fn hint() -> TypeHint;
+
+    /// Returns the description of the Rust struct fields if the target Rust
+    /// type can be constructed from a script struct (see
+    /// [FromScriptStruct](crate::runtime::FromScriptStruct)).
+    ///
+    /// The static analyzer uses this description to validate the struct
+    /// literals passed to the exported functions. The default implementation
+    /// returns None.
+    #[inline(always)]
+    fn schema() -> Option<&'static StructSchema> {
+        None
+    }
 }
 
 /// A wrapper around a [Cell] that provides either borrowing or owning access
@@ -952,6 +969,10 @@ pub trait Upcast<'a>: Sized + 'a {
     /// - `Vec<T>`: Where `T` is a script-registered type. Use this option to
     ///   create a Cell with an array of owned elements with `'static` lifetime.
     ///
+    /// - `Arc<T>`: Where `T` is a script-registered type. Use this option to
+    ///   create a read-only Cell that shares the Arc's allocation with the
+    ///   host code without copying the data.
+    ///
     /// - `&'a T`, `&'a mut T`, `&'a [T]`, `'a mut [T]`: Where `T` is a
     ///   script-registered type. Use these options to create a Cell that serves
     ///   as a projection of another memory allocation.
@@ -1107,6 +1128,19 @@ impl<T: ScriptType> Upcasted for Box<T> {
     }
 }
 
+impl<T: ScriptType> Upcasted for Arc<T> {
+    #[inline(always)]
+    fn into_chain(self, origin: Origin) -> RuntimeResult<UpcastedChain> {
+        if TypeId::of::<T>() == TypeId::of::<()>() {
+            return Ok(UpcastedChain::Cell(Cell::nil()));
+        }
+
+        Ok(UpcastedChain::Slice(MemorySlice::register_arc(
+            origin, self,
+        )?))
+    }
+}
+
 impl<T: ScriptType> Upcasted for Vec<T> {
     #[inline(always)]
     fn into_chain(self, origin: Origin) -> RuntimeResult<UpcastedChain> {
@@ -1114,8 +1148,13 @@ impl<T: ScriptType> Upcasted for Vec<T> {
             return Ok(UpcastedChain::Cell(Cell::nil()));
         }
 
+        let this = match Bytes::try_from_vec(self) {
+            Ok(bytes) => return Ok(UpcastedChain::Cell(Cell::give(origin, bytes)?)),
+            Err(this) => this,
+        };
+
         Ok(UpcastedChain::Slice(MemorySlice::register_vec(
-            origin, self,
+            origin, this,
         )?))
     }
 }
@@ -1168,6 +1207,16 @@ impl<'a, T: ScriptType> Upcasted for &'a [T] {
             return Ok(UpcastedChain::Cell(Cell::nil()));
         }
 
+        if TypeId::of::<T>() == TypeId::of::<u8>() {
+            // Safety: The item type checked above.
+            let bytes = unsafe { from_raw_parts(self.as_ptr() as *const u8, self.len()) };
+
+            return Ok(UpcastedChain::Cell(Cell::give(
+                origin,
+                Bytes::from(Vec::from(bytes)),
+            )?));
+        }
+
         Ok(UpcastedChain::Slice(MemorySlice::register_slice_ref(
             origin, self,
         )?))
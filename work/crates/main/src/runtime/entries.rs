@@ -0,0 +1,134 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::runtime::{Cell, Origin};
+
+type EntryProvider = Rc<dyn Fn(Origin, &Cell, &str) -> Option<Cell>>;
+
+thread_local! {
+    static PROVIDER: RefCell<Option<EntryProvider>> = const { RefCell::new(None) };
+}
+
+/// Sets a fallback resolver of the script struct entries for the current OS
+/// thread.
+///
+/// When the script reads an entry that the script struct does not have
+/// (e.g., `entity.sprite`), the Script Engine calls the `provider` function
+/// with the following arguments:
+///
+/// 1. The source code range of the entry access.
+/// 2. The struct object.
+/// 3. The name of the missing entry.
+///
+/// If the provider returns a value, the struct stores this value under the
+/// entry's name and returns it to the script. Therefore, the provider is
+/// called at most once per entry of a particular struct instance, which makes
+/// it suitable for lazy loading of the data (e.g., from the application's
+/// asset database).
+///
+/// If the provider returns None, the access result is a vacant entry, just as
+/// if the provider has not been configured. The script can still assign a
+/// value to this entry, but reading it results in a runtime error. The string
+/// representation of such an entry notes that the provider did not supply a
+/// value.
+///
+/// ```rust
+/// # use ad_astra::{
+/// #     analysis::{ModuleRead, ScriptModule},
+/// #     export,
+/// #     lady_deirdre::analysis::TriggerHandle,
+/// #     runtime::{remove_entry_provider, set_entry_provider, Cell, Origin, ScriptPackage},
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// let module = ScriptModule::new(
+///     Package::meta(),
+///     "let entity = struct { name: \"hero\" }; return entity.sprite;",
+/// );
+///
+/// let handle = TriggerHandle::new();
+/// let read_guard = module.read(&handle, 1).unwrap();
+/// let script_fn = read_guard.compile().unwrap();
+///
+/// set_entry_provider(|origin, _entity, entry| match entry {
+///     "sprite" => Cell::give(origin, String::from("hero.png")).ok(),
+///     _ => None,
+/// });
+///
+/// let result = script_fn.run().unwrap();
+///
+/// remove_entry_provider();
+///
+/// assert_eq!(result.stringify(false), "hero.png");
+/// ```
+///
+/// The provider is called on the thread that evaluates the script. You can
+/// install the provider right before the script evaluation and remove it
+/// afterward using the [remove_entry_provider] function to limit the
+/// provider to a particular evaluation.
+///
+/// The semantic analyzer is unaware of the provider: the analyzer infers the
+/// types of the missing entries as dynamic.
+#[inline(always)]
+pub fn set_entry_provider(provider: impl Fn(Origin, &Cell, &str) -> Option<Cell> + 'static) {
+    PROVIDER.with_borrow_mut(|current| *current = Some(Rc::new(provider)));
+}
+
+/// Unsets the script struct entries resolver previously set by the
+/// [set_entry_provider] function.
+///
+/// If a resolver has not been configured for the current OS thread, this
+/// function does nothing.
+#[inline(always)]
+pub fn remove_entry_provider() {
+    let provider = PROVIDER.with_borrow_mut(|current| current.take());
+
+    drop(provider);
+}
+
+// Returns None if the entry provider is not configured for the current thread.
+// Otherwise, returns the provider's result.
+pub(crate) fn provide_entry(origin: Origin, structure: &Cell, key: &str) -> Option<Option<Cell>> {
+    let provider = PROVIDER.with_borrow(|current| current.clone())?;
+
+    match provider(origin, structure, key) {
+        Some(entry) if !entry.is_nil() => Some(Some(entry)),
+        _ => Some(None),
+    }
+}
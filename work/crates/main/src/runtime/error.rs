@@ -40,6 +40,7 @@ use std::{
     result::Result as StdResult,
     str::Utf8Error,
     sync::Arc,
+    time::Duration,
 };
 
 use ahash::{AHashMap, AHashSet};
@@ -47,8 +48,17 @@ use lady_deirdre::{arena::Identifiable, format::AnnotationPriority, lexis::ToSpa
 
 use crate::{
     analysis::ModuleTextResolver,
-    format::{format_script_path, ScriptSnippet},
-    runtime::{ops::OperatorKind, Origin, TypeMeta},
+    format::{format_script_path, localize, ScriptSnippet},
+    interpret::{IndexPolicy, MemberAccess},
+    runtime::{
+        ops::OperatorKind,
+        Cell,
+        ContractKind,
+        Origin,
+        ScriptProvenance,
+        StructSchema,
+        TypeMeta,
+    },
 };
 
 /// A result of a runtime API call, which can either be a normal value or a
@@ -138,6 +148,22 @@ pub enum RuntimeError {
         actual: usize,
     },
 
+    /// The script code attempts to assign an array to an array of a different
+    /// length: `arr[1..3] = [x, y, z]`.
+    ///
+    /// Assignments to arrays are performed element by element, and they cannot
+    /// change the length of the target array.
+    LengthMismatch {
+        /// The range in Rust or Script source code where the data was accessed.
+        access_origin: Origin,
+
+        /// The length of the target array.
+        expected: usize,
+
+        /// The length of the assigned array.
+        actual: usize,
+    },
+
     /// The script code attempts to index into an array or string, but the index
     /// is out of bounds.
     OutOfBounds {
@@ -151,6 +177,24 @@ pub enum RuntimeError {
         length: usize,
     },
 
+    /// The script code attempts to index into an array or string using the
+    /// built-in index operator (`foo[index]`), but the current
+    /// [IndexPolicy] rejects the index or range.
+    IndexOutOfBounds {
+        /// The range in Rust or Script source code where the data was accessed.
+        access_origin: Origin,
+
+        /// The requested index for the array or string. For ranges, this is
+        /// the last index of the range.
+        index: isize,
+
+        /// The actual length of the array or string.
+        length: usize,
+
+        /// The indexing policy that was active when the access was rejected.
+        policy: IndexPolicy,
+    },
+
     /// The script code attempts to mutate an object that only provides
     /// read-only access.
     ReadOnly {
@@ -223,6 +267,24 @@ pub enum RuntimeError {
         limit: usize,
     },
 
+    /// The script attempts to access data that another access holds for
+    /// longer than the thread's [borrow deadline](crate::runtime::set_borrow_deadline).
+    BorrowTimeout {
+        /// The range in Rust or Script source code where the data was accessed.
+        access_origin: Origin,
+
+        /// The range in Rust or Script source code where the data was
+        /// previously borrowed.
+        borrow_origin: Origin,
+
+        /// The type of the contended data object.
+        data_type: &'static TypeMeta,
+
+        /// The time the access waited for the conflicting borrow to be
+        /// released.
+        deadline: Duration,
+    },
+
     /// The script attempts to use a data object as an argument for a function
     /// or an operator, but the data type does not meet the requirements.
     TypeMismatch {
@@ -364,6 +426,46 @@ pub enum RuntimeError {
         arguments: usize,
     },
 
+    /// The named arguments of a function invocation (`foo(name: value)`) do
+    /// not match the function's parameters.
+    ArgumentName {
+        /// The range in Rust or Script source code where the argument was
+        /// passed.
+        access_origin: Origin,
+
+        /// The range in Rust or Script source code where the function was
+        /// declared.
+        function_origin: Origin,
+
+        /// The name of the argument. If omitted, the argument is a positional
+        /// argument that follows the named arguments.
+        name: Option<String>,
+
+        /// If true, the function has a parameter with this name, but the
+        /// parameter has already been provided by another argument.
+        duplicate: bool,
+    },
+
+    /// The arguments or the result of a function invocation violate one of
+    /// the function's declared [contracts](crate::runtime::Contract).
+    ContractViolation {
+        /// The range in Rust or Script source code where the argument referred
+        /// to by the precondition was passed, or the range where the function
+        /// was invoked if the violated condition is a postcondition.
+        access_origin: Origin,
+
+        /// The range in Rust or Script source code where the function was
+        /// declared.
+        function_origin: Origin,
+
+        /// Whether the violated condition is a precondition or a
+        /// postcondition.
+        kind: ContractKind,
+
+        /// The source text of the violated condition.
+        condition: &'static str,
+    },
+
     /// The script attempts to apply an operator to an object, but the object's
     /// type does not support this operator.
     UndefinedOperator {
@@ -400,6 +502,44 @@ pub enum RuntimeError {
         field: String,
     },
 
+    /// The script attempts to call a local variable, or to access a field of
+    /// a local variable, whose name shadows a package member (e.g.,
+    /// `let dbg = 5; dbg(10);`), and the variable's value does not support
+    /// this operation.
+    ShadowedMember {
+        /// The range in Rust or Script source code where the shadowing
+        /// variable was declared.
+        decl_origin: Origin,
+
+        /// The name of the variable and of the shadowed package member.
+        name: String,
+
+        /// The underlying [UndefinedOperator](Self::UndefinedOperator) or
+        /// [UnknownField](Self::UnknownField) error.
+        cause: Box<RuntimeError>,
+    },
+
+    /// The script passes a struct to a function that expects a Rust struct
+    /// (see [FromScriptStruct](crate::runtime::FromScriptStruct)), but the
+    /// entries of the script struct do not match the fields of the Rust
+    /// struct.
+    StructFields {
+        /// The range in Rust or Script source code where the struct was
+        /// accessed.
+        access_origin: Origin,
+
+        /// The description of the expected Rust struct.
+        schema: &'static StructSchema,
+
+        /// The names of the required fields that are missing in the script
+        /// struct.
+        missing: Vec<&'static str>,
+
+        /// The names of the script struct entries that do not correspond to
+        /// any field of the Rust struct.
+        unknown: Vec<String>,
+    },
+
     /// The script attempts to format a data object using the [Debug] or
     /// [Display] implementations, but the formatter returns an error.
     FormatError {
@@ -425,6 +565,21 @@ pub enum RuntimeError {
         version: &'static str,
     },
 
+    /// The script called an exported async function (`#[export(block_on)]`),
+    /// but the function's package does not have an
+    /// [async runner](crate::runtime::PackageMeta::set_async_runner).
+    MissingAsyncRunner {
+        /// The range in Rust or Script source code where the function was
+        /// called.
+        access_origin: Origin,
+
+        /// The name of the function's package.
+        name: &'static str,
+
+        /// The version of the function's package.
+        version: &'static str,
+    },
+
     /// The script evaluation has been interrupted by the thread's
     /// [runtime hook](crate::interpret::set_runtime_hook).
     Interrupted {
@@ -434,54 +589,193 @@ pub enum RuntimeError {
     },
 
     /// The script has been interrupted because the interpreter's memory stack
-    /// for the thread overflowed.
+    /// for the thread overflowed, or because the operation (e.g., structs
+    /// comparison, or conversion of a script struct into a recursive Rust
+    /// struct) encountered too deeply nested data.
     StackOverflow {
         /// The range in Rust or Script source code where the interruption
         /// occurred.
         origin: Origin,
     },
+
+    /// The script has been interrupted because the data objects it allocated
+    /// exceeded the memory limit of the evaluation.
+    ///
+    /// See [ScriptFn::run_with_memory_limit](crate::interpret::ScriptFn::run_with_memory_limit)
+    /// for details.
+    MemoryLimitExceeded {
+        /// The range in Rust or Script source code where the exceeding
+        /// allocation occurred.
+        origin: Origin,
+
+        /// The number of bytes that the evaluation would have allocated,
+        /// including the exceeding allocation.
+        current: usize,
+
+        /// The memory limit of the evaluation in bytes.
+        limit: usize,
+    },
+
+    /// A [write observer](crate::interpret::set_write_observer) attempted to
+    /// evaluate a script function.
+    ///
+    /// The write observers are not allowed to re-enter script evaluation on
+    /// the thread that notifies them.
+    ObserverReentrance {
+        /// The range in Rust or Script source code of the script function that
+        /// the observer attempted to evaluate.
+        origin: Origin,
+    },
+
+    /// The script attempts to read an exported `OnceLock` static that the host
+    /// has not initialized yet.
+    UninitializedStatic {
+        /// The range in Rust or Script source code where the static was
+        /// accessed.
+        access_origin: Origin,
+
+        /// The script name of the static.
+        name: String,
+    },
+
+    /// A [finalizer](crate::runtime::set_finalizer) attempted to evaluate
+    /// a script function.
+    ///
+    /// The finalizers are not allowed to re-enter script evaluation on
+    /// the thread that drops the finalized data.
+    FinalizerReentrance {
+        /// The range in Rust or Script source code of the script function that
+        /// the finalizer attempted to evaluate.
+        origin: Origin,
+    },
+
+    /// The host attempts to [release](crate::runtime::Cell::release) a Cell
+    /// whose data is shared with other Cells.
+    SharedRelease {
+        /// The range in Rust or Script source code where the data was
+        /// released.
+        access_origin: Origin,
+
+        /// The range in Rust or Script source code where the data was created.
+        data_origin: Origin,
+    },
+
+    /// An [access guard](crate::interpret::set_access_guard) denied the
+    /// script's access to a member of an object.
+    AccessDenied {
+        /// A Rust or Script source code range, where the member was accessed.
+        access_origin: Origin,
+
+        /// The range in Rust or Script source code of the receiver object.
+        receiver_origin: Origin,
+
+        /// The type of the receiver object.
+        receiver_type: &'static TypeMeta,
+
+        /// The name of the accessed member.
+        member: String,
+
+        /// The kind of the denied access.
+        access: MemberAccess,
+
+        /// A host-provided explanation of the denial.
+        reason: String,
+    },
+
+    /// The script assigns to a value accessed through a component that
+    /// creates a new value on each access, and the assignment would not
+    /// persist (e.g., `foo.method.field = 10`).
+    ///
+    /// This error is reported only in the
+    /// [strict assignments](crate::interpret::set_strict_assignments) mode.
+    TemporaryAssignment {
+        /// The range in Rust or Script source code where the component was
+        /// accessed.
+        access_origin: Origin,
+
+        /// The range in Rust or Script source code of the receiver object.
+        receiver_origin: Origin,
+
+        /// The type of the receiver object.
+        receiver_type: &'static TypeMeta,
+
+        /// The name of the component.
+        component: String,
+    },
+
+    /// A host-defined error reported by an exported Rust function.
+    ///
+    /// You can create this error using the [RuntimeError::custom] constructor
+    /// or the [script_bail](crate::script_bail) macro.
+    Custom {
+        /// The range in Rust or Script source code where the error occurred.
+        access_origin: Origin,
+
+        /// A host-defined numeric code of the error.
+        code: u32,
+
+        /// A human-readable description of the error.
+        message: String,
+
+        /// Additional host-defined data attached to the error.
+        data: Option<Cell>,
+    },
 }
 
 impl Display for RuntimeError {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Nil { .. } => formatter.write_str("inaccessible data"),
+        let message = match self {
+            Self::Nil { .. } => localize!(RuntimeNil),
 
-            Self::NonSingleton { actual, .. } => formatter.write_fmt(format_args!(
-                "expected a single data instance, but the array with {actual} \
-                elements provided",
-            )),
+            Self::NonSingleton { actual, .. } => localize!(RuntimeNonSingleton, actual = actual),
 
             Self::ShortSlice {
                 minimum, actual, ..
-            } => formatter.write_fmt(format_args!(
-                "expected an array with at least {minimum} elements, but the \
-                array with {actual} elements provided",
-            )),
+            } => localize!(RuntimeShortSlice, minimum = minimum, actual = actual),
+
+            Self::LengthMismatch {
+                expected, actual, ..
+            } => localize!(RuntimeLengthMismatch, expected = expected, actual = actual),
 
             Self::OutOfBounds { index, length, .. } => {
-                formatter.write_fmt(format_args!("index {index} out of 0..{length} bounds",))
+                localize!(RuntimeOutOfBounds, index = index, length = length)
             }
 
-            Self::ReadOnly { .. } => formatter.write_str("read-only data"),
+            Self::IndexOutOfBounds {
+                index,
+                length,
+                policy,
+                ..
+            } => localize!(
+                RuntimeIndexOutOfBounds,
+                index = index,
+                length = length,
+                policy = policy,
+            ),
 
-            Self::WriteOnly { .. } => formatter.write_str("write-only data"),
+            Self::ReadOnly { .. } => localize!(RuntimeReadOnly),
 
-            Self::ReadToWrite { .. } => {
-                formatter.write_str("cannot access data for write while it is being read")
-            }
+            Self::WriteOnly { .. } => localize!(RuntimeWriteOnly),
 
-            Self::WriteToRead { .. } => {
-                formatter.write_str("cannot access data for read while it is being written")
-            }
+            Self::ReadToWrite { .. } => localize!(RuntimeReadToWrite),
 
-            Self::WriteToWrite { .. } => {
-                formatter.write_str("cannot access data for write more than once")
-            }
+            Self::WriteToRead { .. } => localize!(RuntimeWriteToRead),
+
+            Self::WriteToWrite { .. } => localize!(RuntimeWriteToWrite),
 
-            Self::Utf8Decoding { .. } => formatter.write_str("invalid utf-8 encoding"),
+            Self::Utf8Decoding { .. } => localize!(RuntimeUtf8Decoding),
 
-            Self::BorrowLimit { .. } => formatter.write_str("too many simultaneous data accesses"),
+            Self::BorrowLimit { .. } => localize!(RuntimeBorrowLimit),
+
+            Self::BorrowTimeout {
+                data_type,
+                deadline,
+                ..
+            } => localize!(
+                RuntimeBorrowTimeout,
+                data_type = data_type,
+                deadline = format!("{deadline:?}"),
+            ),
 
             Self::TypeMismatch {
                 data_type,
@@ -491,24 +785,19 @@ impl Display for RuntimeError {
                 let partition = Self::partition_types(expected_types);
 
                 match partition.is_empty() {
-                    true => formatter.write_fmt(format_args!("unexpected '{data_type}' data type")),
+                    true => localize!(RuntimeUnexpectedType, data_type = data_type),
 
-                    false => {
-                        let partition = partition.join(", or ");
-
-                        formatter.write_fmt(format_args!(
-                            "expected {partition}, but '{data_type}' data type provided"
-                        ))
-                    }
+                    false => localize!(
+                        RuntimeTypeMismatch,
+                        expected = partition.join(&localize!(RuntimeTypeAlternative)),
+                        data_type = data_type,
+                    ),
                 }
             }
 
-            Self::DowncastStatic { .. } => formatter
-                .write_str("cannot get static reference to the data owned by the script engine"),
+            Self::DowncastStatic { .. } => localize!(RuntimeDowncastStatic),
 
-            Self::UpcastResult { .. } => {
-                formatter.write_str("the function returned explicit error")
-            }
+            Self::UpcastResult { .. } => localize!(RuntimeUpcastResult),
 
             Self::NumberCast {
                 from,
@@ -520,19 +809,12 @@ impl Display for RuntimeError {
                 use NumberCastCause::*;
 
                 match cause {
-                    Infinite => formatter.write_fmt(format_args!(
-                        "cannot cast infinity value of {from} type to {to}"
-                    )),
-
-                    NAN => formatter
-                        .write_fmt(format_args!("cannot cast NAN value of {from} type to {to}")),
+                    Infinite => localize!(RuntimeCastInfinite, from = from, to = to),
 
-                    Overflow => {
-                        formatter.write_fmt(format_args!("cannot cast {value}{from} to {to} type"))
-                    }
+                    NAN => localize!(RuntimeCastNan, from = from, to = to),
 
-                    Underflow => {
-                        formatter.write_fmt(format_args!("cannot cast {value}{from} to {to} type"))
+                    Overflow | Underflow => {
+                        localize!(RuntimeCastBounds, value = value, from = from, to = to)
                     }
                 }
             }
@@ -540,66 +822,38 @@ impl Display for RuntimeError {
             Self::NumericOperation { kind, lhs, rhs, .. } => {
                 use NumericOperationKind::*;
 
-                match (lhs, kind, rhs) {
-                    ((lhs_ty, lhs_value), Add, Some((rhs_ty, rhs_value))) => formatter.write_fmt(
-                        format_args!("cannot add {rhs_value}{rhs_ty} to {lhs_value}{lhs_ty}"),
-                    ),
-
-                    ((lhs_ty, lhs_value), Sub, Some((rhs_ty, rhs_value))) => {
-                        formatter.write_fmt(format_args!(
-                            "cannot subtract {rhs_value}{rhs_ty} from {lhs_value}{lhs_ty}"
-                        ))
-                    }
-
-                    ((lhs_ty, lhs_value), Mul, Some((rhs_ty, rhs_value))) => formatter.write_fmt(
-                        format_args!("cannot multiply {rhs_value}{rhs_ty} by {lhs_value}{lhs_ty}"),
-                    ),
-
-                    ((lhs_ty, lhs_value), Div, Some((rhs_ty, rhs_value))) => formatter.write_fmt(
-                        format_args!("cannot divide {rhs_value}{rhs_ty} by {lhs_value}{lhs_ty}"),
-                    ),
-
-                    ((lhs_ty, lhs_value), Neg, None) => formatter.write_fmt(format_args!(
-                        "cannot get negative number of {lhs_value}{lhs_ty}"
-                    )),
-
-                    ((lhs_ty, lhs_value), Shl, Some((rhs_ty, rhs_value))) => {
-                        formatter.write_fmt(format_args!(
-                            "cannot shift {lhs_value}{lhs_ty} left by {rhs_value}{rhs_ty} bits"
-                        ))
-                    }
-
-                    ((lhs_ty, lhs_value), Shr, Some((rhs_ty, rhs_value))) => {
-                        formatter.write_fmt(format_args!(
-                            "cannot shift {lhs_value}{lhs_ty} right by {rhs_value}{rhs_ty} bits"
-                        ))
-                    }
-
-                    ((lhs_ty, lhs_value), Rem, Some((_rhs_ty, _rhs_value))) => {
-                        formatter.write_fmt(format_args!(
-                            "cannot get {lhs_value}{lhs_ty} reminder of \
-                            division by {lhs_value}{lhs_ty}"
-                        ))
-                    }
-
-                    _ => formatter.write_str("invalid numeric operation"),
+                let (lhs_ty, lhs_value) = lhs;
+                let lhs = format!("{lhs_value}{lhs_ty}");
+
+                let rhs = rhs
+                    .as_ref()
+                    .map(|(rhs_ty, rhs_value)| format!("{rhs_value}{rhs_ty}"));
+
+                match (kind, rhs) {
+                    (Add, Some(rhs)) => localize!(RuntimeAdd, lhs = lhs, rhs = rhs),
+                    (Sub, Some(rhs)) => localize!(RuntimeSub, lhs = lhs, rhs = rhs),
+                    (Mul, Some(rhs)) => localize!(RuntimeMul, lhs = lhs, rhs = rhs),
+                    (Div, Some(rhs)) => localize!(RuntimeDiv, lhs = lhs, rhs = rhs),
+                    (Neg, None) => localize!(RuntimeNeg, lhs = lhs),
+                    (Shl, Some(rhs)) => localize!(RuntimeShl, lhs = lhs, rhs = rhs),
+                    (Shr, Some(rhs)) => localize!(RuntimeShr, lhs = lhs, rhs = rhs),
+                    (Rem, Some(rhs)) => localize!(RuntimeRem, lhs = lhs, rhs = rhs),
+                    _ => localize!(RuntimeNumericOperation),
                 }
             }
 
-            Self::RangeCast { from, to, .. } => formatter.write_fmt(format_args!(
-                "cannot cast {from:?} range to {to} type. target type bounds mismatch"
-            )),
+            Self::RangeCast { from, to, .. } => {
+                localize!(RuntimeRangeCast, from = format!("{from:?}"), to = to)
+            }
 
             Self::MalformedRange {
                 start_bound,
                 end_bound,
                 ..
-            } => formatter.write_fmt(format_args!(
-                "range {start_bound} start bound is greater than the range end bound {end_bound}"
-            )),
+            } => localize!(RuntimeMalformedRange, start = start_bound, end = end_bound),
 
             Self::PrimitiveParse { from, to, .. } => {
-                formatter.write_fmt(format_args!("failed to parse {from:?} as {to}"))
+                localize!(RuntimePrimitiveParse, from = format!("{from:?}"), to = to)
             }
 
             Self::ArityMismatch {
@@ -607,41 +861,134 @@ impl Display for RuntimeError {
                 arguments,
                 ..
             } => match *parameters == 1 {
-                true => formatter.write_fmt(format_args!(
-                    "the function requires 1 argument, but {arguments} provided"
-                )),
-                false => formatter.write_fmt(format_args!(
-                    "the function requires {parameters} arguments, but {arguments} provided"
-                )),
+                true => localize!(RuntimeArityMismatchOne, arguments = arguments),
+                false => localize!(
+                    RuntimeArityMismatch,
+                    parameters = parameters,
+                    arguments = arguments,
+                ),
+            },
+
+            Self::ArgumentName {
+                name, duplicate, ..
+            } => match (name, duplicate) {
+                (None, _) => localize!(RuntimeMisplacedArgument),
+                (Some(name), true) => localize!(RuntimeDuplicateArgument, name = name),
+                (Some(name), false) => localize!(RuntimeUnknownArgument, name = name),
+            },
+
+            Self::ContractViolation {
+                kind, condition, ..
+            } => match kind {
+                ContractKind::Requires => {
+                    localize!(RuntimeRequiresViolation, condition = condition)
+                }
+                ContractKind::Ensures => localize!(RuntimeEnsuresViolation, condition = condition),
             },
 
             Self::UndefinedOperator {
                 receiver_type,
                 operator,
                 ..
-            } => formatter.write_fmt(format_args!(
-                "type '{receiver_type}' does not implement {operator}"
-            )),
+            } => localize!(
+                RuntimeUndefinedOperator,
+                receiver = receiver_type,
+                operator = operator,
+            ),
 
             Self::UnknownField {
                 receiver_type,
                 field,
                 ..
-            } => formatter.write_fmt(format_args!(
-                "type '{receiver_type}' does not have field '{field}'"
-            )),
+            } => localize!(RuntimeUnknownField, receiver = receiver_type, field = field),
+
+            Self::ShadowedMember { name, cause, .. } => {
+                localize!(RuntimeShadowedMember, cause = cause, name = name)
+            }
+
+            Self::StructFields {
+                schema,
+                missing,
+                unknown,
+                ..
+            } => {
+                let mut message = localize!(RuntimeStructFields, schema = schema.name);
+
+                if !missing.is_empty() {
+                    message.push_str(&localize!(
+                        RuntimeMissingFields,
+                        fields = Self::format_names(missing),
+                    ));
+                }
+
+                if !unknown.is_empty() {
+                    message.push_str(&localize!(
+                        RuntimeUnknownFields,
+                        fields = Self::format_names(unknown),
+                    ));
+                }
+
+                message
+            }
 
-            Self::FormatError { .. } => formatter
-                .write_str("an error occurred during Debug or Display format function call"),
+            Self::FormatError { .. } => localize!(RuntimeFormatError),
 
             Self::UnknownPackage { name, version, .. } => {
-                formatter.write_fmt(format_args!("unknown {name}@{version} script package"))
+                localize!(RuntimeUnknownPackage, name = name, version = version)
             }
 
-            Self::Interrupted { .. } => formatter.write_str("script evaluation interrupted"),
+            Self::MissingAsyncRunner { name, version, .. } => {
+                localize!(RuntimeMissingAsyncRunner, name = name, version = version)
+            }
 
-            Self::StackOverflow { .. } => formatter.write_str("script engine stack overflow"),
-        }
+            Self::Interrupted { .. } => localize!(RuntimeInterrupted),
+
+            Self::StackOverflow { .. } => localize!(RuntimeStackOverflow),
+
+            Self::MemoryLimitExceeded { current, limit, .. } => {
+                localize!(RuntimeMemoryLimitExceeded, current = current, limit = limit)
+            }
+
+            Self::ObserverReentrance { .. } => localize!(RuntimeObserverReentrance),
+
+            Self::UninitializedStatic { name, .. } => {
+                localize!(RuntimeUninitializedStatic, name = name)
+            }
+
+            Self::FinalizerReentrance { .. } => localize!(RuntimeFinalizerReentrance),
+
+            Self::SharedRelease { .. } => localize!(RuntimeSharedRelease),
+
+            Self::AccessDenied {
+                receiver_type,
+                member,
+                access,
+                reason,
+                ..
+            } => localize!(
+                RuntimeAccessDenied,
+                access = access,
+                receiver = receiver_type,
+                member = member,
+                reason = reason,
+            ),
+
+            Self::TemporaryAssignment {
+                receiver_type,
+                component,
+                ..
+            } => localize!(
+                RuntimeTemporaryAssignment,
+                receiver = receiver_type,
+                component = component,
+            ),
+
+            Self::Custom { code, message, .. } => {
+                localize!(RuntimeCustom, code = code, message = message)
+            }
+        };
+
+        formatter.write_str(&message)
     }
 }
 
@@ -652,12 +999,84 @@ impl StdError for RuntimeError {
             Self::Utf8Decoding { cause, .. } => Some(cause),
             Self::UpcastResult { cause, .. } => Some(cause),
             Self::PrimitiveParse { cause, .. } => Some(cause),
+            Self::ShadowedMember { cause, .. } => Some(cause.as_ref()),
             _ => None,
         }
     }
 }
 
 impl RuntimeError {
+    /// Creates a [Custom](Self::Custom) error with a host-defined error `code`.
+    ///
+    /// The `origin` parameter specifies the Rust or Script source code range
+    /// where the error occurred. If the exported function does not have access
+    /// to the invocation origin, you can pass [Origin::nil]. In this case, the
+    /// Script Engine replaces the nil origin with the function call site when
+    /// the error is returned from the exported function.
+    ///
+    /// The `message` is a human-readable description of the error, and the
+    /// optional `data` is an arbitrary value attached to the error.
+    ///
+    /// ```
+    /// use ad_astra::runtime::{Origin, RuntimeError};
+    ///
+    /// let error = RuntimeError::custom(Origin::nil(), 404, "file not found", None);
+    ///
+    /// assert_eq!(error.custom_code(), Some(404));
+    /// assert_eq!(error.custom_message(), Some("file not found"));
+    /// assert_eq!(error.to_string(), "error 404: file not found");
+    /// ```
+    ///
+    /// See also the [script_bail](crate::script_bail) macro.
+    #[inline(always)]
+    pub fn custom(
+        origin: Origin,
+        code: u32,
+        message: impl Into<String>,
+        data: Option<Cell>,
+    ) -> Self {
+        Self::Custom {
+            access_origin: origin,
+            code,
+            message: message.into(),
+            data,
+        }
+    }
+
+    /// Returns the host-defined code of the [Custom](Self::Custom) error.
+    ///
+    /// For other error variants, returns None.
+    #[inline(always)]
+    pub fn custom_code(&self) -> Option<u32> {
+        match self {
+            Self::Custom { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Returns the message of the [Custom](Self::Custom) error.
+    ///
+    /// For other error variants, returns None.
+    #[inline(always)]
+    pub fn custom_message(&self) -> Option<&str> {
+        match self {
+            Self::Custom { message, .. } => Some(message.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns the data attached to the [Custom](Self::Custom) error.
+    ///
+    /// Returns None for other error variants, or if the Custom error does not
+    /// have attached data.
+    #[inline(always)]
+    pub fn custom_data(&self) -> Option<&Cell> {
+        match self {
+            Self::Custom { data, .. } => data.as_ref(),
+            _ => None,
+        }
+    }
+
     /// Returns a printable object that renders the script's source code and
     /// annotates it with error messages describing the underlying error object
     /// and pointing to the source code location(s) where the error occurred.
@@ -712,7 +1131,7 @@ impl RuntimeError {
 
         let mut snippet = primary_text.snippet();
 
-        snippet.set_caption("runtime error");
+        snippet.set_caption(localize!(RuntimeErrorCaption));
         snippet.annotate(
             primary_origin,
             AnnotationPriority::Primary,
@@ -764,6 +1183,24 @@ impl RuntimeError {
         DisplayError::Snippet(snippet)
     }
 
+    /// Returns the [provenance](ScriptProvenance) record of the script module
+    /// in which the error occurred.
+    ///
+    /// Crash reporters can use this function to attach the asset name,
+    /// version, and host-defined tags to the error report.
+    ///
+    /// Returns None if the [primary origin](Self::primary_origin) of the error
+    /// is not a script origin, if the module does not have a provenance
+    /// record, or if the module no longer exists.
+    #[inline]
+    pub fn provenance(&self) -> Option<ScriptProvenance> {
+        let Origin::Script(origin) = self.primary_origin() else {
+            return None;
+        };
+
+        ScriptProvenance::of(origin.id())
+    }
+
     /// Returns the Rust or Script source code range where the error occurred.
     pub fn primary_origin(&self) -> &Origin {
         match self {
@@ -773,8 +1210,12 @@ impl RuntimeError {
 
             Self::ShortSlice { access_origin, .. } => access_origin,
 
+            Self::LengthMismatch { access_origin, .. } => access_origin,
+
             Self::OutOfBounds { access_origin, .. } => access_origin,
 
+            Self::IndexOutOfBounds { access_origin, .. } => access_origin,
+
             Self::ReadOnly { access_origin, .. } => access_origin,
 
             Self::WriteOnly { access_origin, .. } => access_origin,
@@ -789,6 +1230,8 @@ impl RuntimeError {
 
             Self::BorrowLimit { access_origin, .. } => access_origin,
 
+            Self::BorrowTimeout { access_origin, .. } => access_origin,
+
             Self::TypeMismatch { access_origin, .. } => access_origin,
 
             Self::DowncastStatic { access_origin, .. } => access_origin,
@@ -809,17 +1252,43 @@ impl RuntimeError {
                 invocation_origin, ..
             } => invocation_origin,
 
+            Self::ArgumentName { access_origin, .. } => access_origin,
+
+            Self::ContractViolation { access_origin, .. } => access_origin,
+
             Self::UndefinedOperator { access_origin, .. } => access_origin,
 
             Self::UnknownField { access_origin, .. } => access_origin,
 
+            Self::ShadowedMember { cause, .. } => cause.primary_origin(),
+
+            Self::StructFields { access_origin, .. } => access_origin,
+
             Self::FormatError { access_origin, .. } => access_origin,
 
             Self::UnknownPackage { access_origin, .. } => access_origin,
 
+            Self::MissingAsyncRunner { access_origin, .. } => access_origin,
+
             Self::Interrupted { origin } => origin,
 
             Self::StackOverflow { origin, .. } => origin,
+
+            Self::MemoryLimitExceeded { origin, .. } => origin,
+
+            Self::ObserverReentrance { origin } => origin,
+
+            Self::UninitializedStatic { access_origin, .. } => access_origin,
+
+            Self::FinalizerReentrance { origin } => origin,
+
+            Self::SharedRelease { access_origin, .. } => access_origin,
+
+            Self::AccessDenied { access_origin, .. } => access_origin,
+
+            Self::TemporaryAssignment { access_origin, .. } => access_origin,
+
+            Self::Custom { access_origin, .. } => access_origin,
         }
     }
 
@@ -839,8 +1308,12 @@ impl RuntimeError {
 
             Self::ShortSlice { .. } => None,
 
+            Self::LengthMismatch { .. } => None,
+
             Self::OutOfBounds { .. } => None,
 
+            Self::IndexOutOfBounds { .. } => None,
+
             Self::ReadOnly { data_origin, .. } => Some(data_origin),
 
             Self::WriteOnly { data_origin, .. } => Some(data_origin),
@@ -855,6 +1328,8 @@ impl RuntimeError {
 
             Self::BorrowLimit { .. } => None,
 
+            Self::BorrowTimeout { borrow_origin, .. } => Some(borrow_origin),
+
             Self::TypeMismatch { .. } => None,
 
             Self::DowncastStatic { .. } => None,
@@ -875,6 +1350,14 @@ impl RuntimeError {
                 function_origin, ..
             } => Some(function_origin),
 
+            Self::ArgumentName {
+                function_origin, ..
+            } => Some(function_origin),
+
+            Self::ContractViolation {
+                function_origin, ..
+            } => Some(function_origin),
+
             Self::UndefinedOperator {
                 receiver_origin, ..
             } => receiver_origin.as_ref(),
@@ -883,15 +1366,41 @@ impl RuntimeError {
                 receiver_origin, ..
             } => Some(receiver_origin),
 
+            Self::ShadowedMember { decl_origin, .. } => Some(decl_origin),
+
+            Self::StructFields { .. } => None,
+
             Self::FormatError {
                 receiver_origin, ..
             } => Some(receiver_origin),
 
             Self::UnknownPackage { .. } => None,
 
+            Self::MissingAsyncRunner { .. } => None,
+
             Self::Interrupted { .. } => None,
 
             Self::StackOverflow { .. } => None,
+
+            Self::MemoryLimitExceeded { .. } => None,
+
+            Self::ObserverReentrance { .. } => None,
+
+            Self::UninitializedStatic { .. } => None,
+
+            Self::FinalizerReentrance { .. } => None,
+
+            Self::SharedRelease { data_origin, .. } => Some(data_origin),
+
+            Self::AccessDenied {
+                receiver_origin, ..
+            } => Some(receiver_origin),
+
+            Self::TemporaryAssignment {
+                receiver_origin, ..
+            } => Some(receiver_origin),
+
+            Self::Custom { .. } => None,
         }
     }
 
@@ -918,22 +1427,28 @@ impl RuntimeError {
 
             Self::ShortSlice { .. } => String::new(),
 
+            Self::LengthMismatch { .. } => String::new(),
+
             Self::OutOfBounds { .. } => String::new(),
 
-            Self::ReadOnly { .. } => String::from("data object origin"),
+            Self::IndexOutOfBounds { .. } => String::new(),
+
+            Self::ReadOnly { .. } => localize!(RuntimeDataOrigin),
 
-            Self::WriteOnly { .. } => String::from("data object origin"),
+            Self::WriteOnly { .. } => localize!(RuntimeDataOrigin),
 
-            Self::ReadToWrite { .. } => String::from("active read access"),
+            Self::ReadToWrite { .. } => localize!(RuntimeActiveRead),
 
-            Self::WriteToRead { .. } => String::from("active write access"),
+            Self::WriteToRead { .. } => localize!(RuntimeActiveWrite),
 
-            Self::WriteToWrite { .. } => String::from("active write access"),
+            Self::WriteToWrite { .. } => localize!(RuntimeActiveWrite),
 
             Self::Utf8Decoding { .. } => String::new(),
 
             Self::BorrowLimit { .. } => String::new(),
 
+            Self::BorrowTimeout { .. } => localize!(RuntimeConflictingAccess),
+
             Self::TypeMismatch { .. } => String::new(),
 
             Self::DowncastStatic { .. } => String::new(),
@@ -950,23 +1465,51 @@ impl RuntimeError {
 
             Self::PrimitiveParse { .. } => String::new(),
 
-            Self::ArityMismatch { .. } => String::from("function origin"),
+            Self::ArityMismatch { .. } => localize!(RuntimeFunctionOrigin),
+
+            Self::ArgumentName { .. } => localize!(RuntimeFunctionOrigin),
+
+            Self::ContractViolation { .. } => localize!(RuntimeFunctionOrigin),
 
             Self::UndefinedOperator {
                 receiver_origin, ..
-            } if receiver_origin.is_some() => String::from("receiver origin"),
+            } if receiver_origin.is_some() => localize!(RuntimeReceiverOrigin),
 
             Self::UndefinedOperator { .. } => String::new(),
 
-            Self::UnknownField { .. } => String::from("receiver origin"),
+            Self::UnknownField { .. } => localize!(RuntimeReceiverOrigin),
 
-            Self::FormatError { .. } => String::from("receiver object"),
+            Self::ShadowedMember { name, .. } => {
+                localize!(RuntimeShadowingDeclaration, name = name)
+            }
+
+            Self::StructFields { .. } => String::new(),
+
+            Self::FormatError { .. } => localize!(RuntimeReceiverObject),
 
             Self::UnknownPackage { .. } => String::new(),
 
+            Self::MissingAsyncRunner { .. } => String::new(),
+
             Self::Interrupted { .. } => String::new(),
 
             Self::StackOverflow { .. } => String::new(),
+
+            Self::MemoryLimitExceeded { .. } => String::new(),
+
+            Self::ObserverReentrance { .. } => String::new(),
+
+            Self::UninitializedStatic { .. } => String::new(),
+
+            Self::FinalizerReentrance { .. } => String::new(),
+
+            Self::SharedRelease { .. } => localize!(RuntimeDataOrigin),
+
+            Self::AccessDenied { .. } => localize!(RuntimeReceiverOrigin),
+
+            Self::TemporaryAssignment { .. } => localize!(RuntimeReceiverOrigin),
+
+            Self::Custom { .. } => String::new(),
         }
     }
 
@@ -975,215 +1518,154 @@ impl RuntimeError {
     /// This message is the same one that would be printed in the footer of the
     /// [RuntimeError::display] object.
     pub fn summary(&self) -> String {
-        let result = match self {
-            Self::Nil { .. } => {
-                r#"The requested operation has been applied on the void data.
-
-The source of the void data could be:
-    - an empty array "[]",
-    - a struct field that does not exists in the struct,
-    - a function or operator that does not return any value,
-    - a function that returns "Option::None",
-    - or any other source.
-
-Use the ? operator to check if the value is void: "if a? {...}"."#
-            }
-
-            Self::NonSingleton { .. } => {
-                r#"Most script operations require singleton objects (just normal objects)
-and cannot be applied to arrays with zero or more than one element.
+        match self {
+            Self::Nil { .. } => localize!(SummaryNil),
 
-Consider using the index operator to retrieve a single element from the array:
-    "my_array[3] + 10" instead of "my_array + 10"."#
-            }
+            Self::NonSingleton { .. } => localize!(SummaryNonSingleton),
 
-            Self::ShortSlice { .. } => {
-                r#"The underlying operation requires an array of longer length."#
-            }
+            Self::ShortSlice { .. } => localize!(SummaryShortSlice),
 
-            Self::OutOfBounds { .. } => {
-                r#"The specified range or an index is out of the array bounds."#
-            }
+            Self::LengthMismatch { .. } => localize!(SummaryLengthMismatch),
 
-            Self::ReadOnly { .. } => {
-                r#"The underlying operation requires write access to one of its arguments,
-but the argument reference provides read-only access."#
-            }
+            Self::OutOfBounds { .. } => localize!(SummaryOutOfBounds),
 
-            Self::WriteOnly { .. } => {
-                r#"The underlying operation requires read access to one of its arguments,
-but the argument reference provides write-only access."#
-            }
+            Self::IndexOutOfBounds { policy, .. } => match policy {
+                IndexPolicy::Strict => localize!(SummaryStrictIndex),
+                IndexPolicy::Clamp => localize!(SummaryClampIndex),
+                IndexPolicy::WrapNegative => localize!(SummaryWrapNegativeIndex),
+            },
 
-            Self::ReadToWrite { .. } => {
-                r#"The underlying operation requires write access to one of its arguments,
-but the argument object is currently being read.
+            Self::ReadOnly { .. } => localize!(SummaryReadOnly),
 
-The script engine does not allow simultaneous read and write access
-to the same data.
+            Self::WriteOnly { .. } => localize!(SummaryWriteOnly),
 
-For instance, if the script calls a function (or an operator) with this object
-as an argument and the function returns a reference that indirectly points
-to the argument's data, the object is blocked for writing until the reference's
-lifetime ends."#
-            }
+            Self::ReadToWrite { .. } => localize!(SummaryReadToWrite),
 
-            Self::WriteToRead { .. } => {
-                r#"The underlying operation requires read access to one of its arguments,
-but the argument object is currently being written.
+            Self::WriteToRead { .. } => localize!(SummaryWriteToRead),
 
-The script engine does not allow simultaneous read and write access
-to the same data.
+            Self::WriteToWrite { .. } => localize!(SummaryWriteToWrite),
 
-For instance, if the script calls a function (or an operator) with this object
-as an argument and the function returns a reference that indirectly modifies
-argument's data, the object is blocked for reading until the reference's
-lifetime ends."#
+            Self::Utf8Decoding { cause, .. } => {
+                localize!(SummaryUtf8Decoding, cause = Self::indent_cause(cause))
             }
 
-            Self::WriteToWrite { .. } => {
-                r#"The underlying operation requires write access to one of its arguments,
-but the argument object is currently being written.
+            Self::BorrowLimit { .. } => localize!(SummaryBorrowLimit),
 
-The script engine mandates that the ongoing write access to the data object
-is exclusive.
+            Self::BorrowTimeout { .. } => localize!(SummaryBorrowTimeout),
 
-For instance, if the script calls a function (or an operator) with this object
-as an argument and the function returns a reference that indirectly modifies
-argument's data, the object is blocked from another write access until
-the reference's lifetime ends."#
-            }
+            Self::TypeMismatch { .. } => localize!(SummaryTypeMismatch),
 
-            Self::Utf8Decoding { cause, .. } => {
-                let mut result = String::from(
-                    r#"The underlying operation is attempting to reinterpret an array of bytes
-as a UTF-8 encoding of the Unicode text, but the script engine has detected
-that this encoding is invalid.
+            Self::DowncastStatic { .. } => localize!(SummaryDowncastStatic),
 
-Error description:"#,
-                );
+            Self::UpcastResult { cause, .. } => {
+                localize!(SummaryUpcastResult, cause = Self::indent_cause(cause))
+            }
 
-                for line in cause.to_string().split("\n") {
-                    result.push_str("\n    ");
-                    result.push_str(line);
-                }
+            Self::NumberCast { cause, .. } => match cause {
+                NumberCastCause::Infinite | NumberCastCause::NAN => localize!(SummaryNumberCast),
+                NumberCastCause::Overflow => localize!(SummaryCastOverflow),
+                NumberCastCause::Underflow => localize!(SummaryCastUnderflow),
+            },
 
-                return result;
+            Self::NumericOperation { target, .. } => {
+                localize!(SummaryNumericOperation, target = target)
             }
 
-            Self::BorrowLimit { .. } => {
-                r#"The script engine has a predefined limit for active references
-to the same data object.
+            Self::RangeCast { .. } => localize!(SummaryRangeCast),
 
-This limit may be exceeded, for example, if a recursive function attempts
-to access the same object too many times."#
-            }
+            Self::MalformedRange { .. } => localize!(SummaryMalformedRange),
 
-            Self::TypeMismatch { .. } => {
-                r#"The underlying function (or operator) requires an argument of a different type
-than the one being provided."#
+            Self::PrimitiveParse { cause, .. } => {
+                localize!(SummaryPrimitiveParse, cause = Self::indent_cause(cause))
             }
 
-            Self::DowncastStatic { .. } => {
-                r#"The underlying function (or operator) requested a data object
-with a lifetime that may be different from the actual object's lifetime."#
-            }
+            Self::ArityMismatch {
+                parameters,
+                arguments,
+                ..
+            } => match *parameters > *arguments {
+                true => localize!(SummaryNotEnoughArguments),
+                false => localize!(SummaryTooManyArguments),
+            },
 
-            Self::UpcastResult { cause, .. } => {
-                let mut result = String::from(
-                    r#"The invoked function (or operator) returned explicit error.
+            Self::ArgumentName { name: None, .. } => localize!(SummaryMisplacedArgument),
 
-Error description:"#,
-                );
+            Self::ArgumentName {
+                duplicate: true, ..
+            } => localize!(SummaryDuplicateArgument),
 
-                for line in cause.to_string().split("\n") {
-                    result.push_str("\n    ");
-                    result.push_str(line);
-                }
+            Self::ArgumentName { .. } => localize!(SummaryUnknownArgument),
 
-                return result;
-            }
+            Self::ContractViolation { kind, .. } => match kind {
+                ContractKind::Requires => localize!(SummaryRequiresViolation),
+                ContractKind::Ensures => localize!(SummaryEnsuresViolation),
+            },
 
-            Self::NumberCast { cause, .. } => match cause {
-                NumberCastCause::Infinite | NumberCastCause::NAN => {
-                    r#"Failed to cast primitive numeric type to another numeric type."#
-                }
+            Self::UndefinedOperator { .. } => localize!(SummaryUndefinedOperator),
 
-                NumberCastCause::Overflow => {
-                    r#"Failed to cast primitive numeric type to another numeric type.
+            Self::UnknownField { .. } => localize!(SummaryUnknownField),
 
-The source value is bigger than the target type upper bound.
-"#
-                }
+            Self::ShadowedMember { name, cause, .. } => {
+                localize!(SummaryShadowedMember, cause = cause.summary(), name = name)
+            }
 
-                NumberCastCause::Underflow => {
-                    r#"Failed to cast primitive numeric type to another numeric type.
+            Self::StructFields { schema, .. } => {
+                let mut result = localize!(SummaryStructFields, schema = schema);
 
-The source value is lesser than the target type lower bound.
-"#
+                if schema.fields.iter().any(|field| !field.required) {
+                    result.push_str(&localize!(SummaryOptionalFields));
                 }
-            },
 
-            Self::NumericOperation { target, .. } => {
-                let mut result = String::from(
-                    r#"Failed to perform numeric operation between two primitive types
+                result
+            }
 
-The result overflows "#,
-                );
+            Self::FormatError { .. } => localize!(SummaryFormatError),
 
-                result.push_str(&format!("{target}"));
+            Self::UnknownPackage { .. } => localize!(SummaryUnknownPackage),
 
-                result.push_str(" bounds.");
+            Self::MissingAsyncRunner { .. } => localize!(SummaryMissingAsyncRunner),
 
-                return result;
-            }
+            Self::Interrupted { .. } => localize!(SummaryInterrupted),
 
-            Self::RangeCast { .. } => r#"Failed to cast a range to another range type."#,
+            Self::StackOverflow { .. } => localize!(SummaryStackOverflow),
 
-            Self::MalformedRange { .. } => r#"Malformed range bounds."#,
+            Self::MemoryLimitExceeded { .. } => localize!(SummaryMemoryLimitExceeded),
 
-            Self::PrimitiveParse { cause, .. } => {
-                let mut result = String::from(r#"String parse error:"#);
+            Self::ObserverReentrance { .. } => localize!(SummaryObserverReentrance),
 
-                for line in cause.to_string().split("\n") {
-                    result.push_str("\n    ");
-                    result.push_str(line);
-                }
+            Self::UninitializedStatic { .. } => localize!(SummaryUninitializedStatic),
 
-                return result;
-            }
+            Self::FinalizerReentrance { .. } => localize!(SummaryFinalizerReentrance),
 
-            Self::ArityMismatch {
-                parameters,
-                arguments,
-                ..
-            } => match *parameters > *arguments {
-                true => r#"Not enough arguments."#,
-                false => r#"Too many arguments."#,
-            },
+            Self::SharedRelease { .. } => localize!(SummarySharedRelease),
 
-            Self::UndefinedOperator { .. } => {
-                r#"The object's type that is responsible to perform specified operation does not
-implement this operator."#
-            }
+            Self::AccessDenied { .. } => localize!(SummaryAccessDenied),
 
-            Self::UnknownField { .. } => r#"The object does not have specified field."#,
+            Self::TemporaryAssignment { .. } => localize!(SummaryTemporaryAssignment),
 
-            Self::FormatError { .. } => r#"Failed to turn the object into string representation."#,
+            Self::Custom { .. } => localize!(SummaryCustom),
+        }
+    }
 
-            Self::UnknownPackage { .. } => r#"Package lookup failure."#,
+    // Prints each line of the error cause's description on a separate
+    // indented line.
+    fn indent_cause(cause: &impl Display) -> String {
+        let mut result = String::new();
 
-            Self::Interrupted { .. } => r#"The script explicitly terminated by the host request."#,
+        for line in cause.to_string().split("\n") {
+            result.push_str("\n    ");
+            result.push_str(line);
+        }
 
-            Self::StackOverflow { .. } => {
-                r#"The script engine failed to invoke the function,
-because the engine's thread stack exceeded its limit.
-                
-This situation may occur in functions with unlimited recursion."#
-            }
-        };
+        result
+    }
 
-        String::from(result)
+    fn format_names(names: &[impl AsRef<str>]) -> String {
+        names
+            .iter()
+            .map(|name| format!("'{}'", name.as_ref()))
+            .collect::<Vec<_>>()
+            .join(", ")
     }
 
     fn partition_types(types: &[&'static TypeMeta]) -> Vec<String> {
@@ -1279,3 +1761,44 @@ pub enum NumericOperationKind {
 pub trait NumValue: Debug + Display + Send + Sync + 'static {}
 
 impl<T: Debug + Display + Send + Sync + 'static> NumValue for T {}
+
+/// Returns a [custom](RuntimeError::custom) RuntimeError from the current
+/// function.
+///
+/// The first argument is the [Origin](crate::runtime::Origin) of the error, the
+/// second argument is the host-defined numeric error code, and the remaining
+/// arguments are the [format](std::format) arguments of the error message.
+///
+/// The macro expands to
+/// `return Err(RuntimeError::custom(origin, code, format!(...), None))`, so it
+/// can be used in any function that returns a
+/// [RuntimeResult](crate::runtime::RuntimeResult).
+///
+/// ```
+/// use ad_astra::{
+///     runtime::{Origin, RuntimeResult},
+///     script_bail,
+/// };
+///
+/// const NOT_FOUND: u32 = 404;
+///
+/// fn open(path: &str) -> RuntimeResult<()> {
+///     script_bail!(Origin::nil(), NOT_FOUND, "file {path:?} not found");
+/// }
+///
+/// let error = open("foo.txt").unwrap_err();
+///
+/// assert_eq!(error.custom_code(), Some(NOT_FOUND));
+/// assert_eq!(error.custom_message(), Some("file \"foo.txt\" not found"));
+/// ```
+#[macro_export]
+macro_rules! script_bail {
+    ($origin:expr, $code:expr, $($message:tt)+) => {
+        return ::std::result::Result::Err($crate::runtime::RuntimeError::custom(
+            $origin,
+            $code,
+            ::std::format!($($message)+),
+            ::std::option::Option::None,
+        ))
+    };
+}
@@ -0,0 +1,357 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    cell::Cell as StdCell,
+    slice::from_raw_parts,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        RwLock,
+    },
+};
+
+use ahash::AHashMap;
+use lady_deirdre::sync::Lazy;
+
+use crate::runtime::{ScriptType, TypeMeta};
+
+type Finalizer = dyn Fn(*const (), usize) + Send + Sync + 'static;
+
+static FINALIZERS: Lazy<RwLock<AHashMap<&'static TypeMeta, Arc<Finalizer>>>> =
+    Lazy::new(|| RwLock::new(AHashMap::new()));
+
+// True if at least one finalizer is installed, meaning that the Script Engine
+// cannot skip the finalization of the dropped data.
+static FINALIZED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static FINALIZING: StdCell<bool> = const { StdCell::new(false) };
+}
+
+/// Installs a process-wide finalizer of the `T` type values owned by the
+/// Script Engine, replacing the previously installed finalizer of this type.
+///
+/// The Script Engine calls the `finalizer` function with a reference to each
+/// value of the `T` type right before the value is dropped, on the thread that
+/// drops the value. If the Script Engine owns an array of such values, the
+/// finalizer is called for each element of the array.
+///
+/// The finalizer is useful for logging and auditing the lifetime of the values
+/// that hold external resources.
+///
+/// ```rust
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// #
+/// # use ad_astra::{
+/// #     analysis::{ModuleRead, ScriptModule},
+/// #     export,
+/// #     lady_deirdre::analysis::TriggerHandle,
+/// #     runtime::{remove_finalizer, set_finalizer, ScriptPackage, ScriptType},
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// #[export]
+/// pub struct Texture {
+///     pub id: usize,
+/// }
+///
+/// #[export]
+/// pub fn texture(id: usize) -> Texture {
+///     Texture { id }
+/// }
+///
+/// static RELEASED: AtomicUsize = AtomicUsize::new(0);
+///
+/// set_finalizer::<Texture>(|texture| {
+///     RELEASED.fetch_add(texture.id, Ordering::SeqCst);
+/// });
+///
+/// let module = ScriptModule::new(Package::meta(), "let a = texture(1); let b = texture(2);");
+///
+/// let handle = TriggerHandle::new();
+/// let read_guard = module.read(&handle, 1).unwrap();
+///
+/// read_guard.compile().unwrap().run().unwrap();
+///
+/// remove_finalizer(Texture::type_meta());
+///
+/// assert_eq!(RELEASED.load(Ordering::SeqCst), 3);
+/// ```
+///
+/// The finalizer only observes the values owned by the Script Engine (e.g.,
+/// the values created by the script code or given to the Script Engine through
+/// the [Cell::give](crate::runtime::Cell::give) function). The values borrowed
+/// by the Script Engine from the host are not finalized.
+///
+/// The finalizer must not panic, and it must not evaluate scripts: any attempt
+/// to evaluate a script function during the finalization ends with the
+/// [FinalizerReentrance](crate::runtime::RuntimeError::FinalizerReentrance)
+/// error.
+///
+/// When no finalizers are installed, the Script Engine skips the finalization
+/// at virtually no cost.
+pub fn set_finalizer<T: ScriptType>(finalizer: impl Fn(&T) + Send + Sync + 'static) {
+    let finalizer = Arc::new(move |head: *const (), length: usize| {
+        // Safety: The finalizer is called with the slice of `T` values.
+        let slice = unsafe { from_raw_parts(head as *const T, length) };
+
+        for value in slice {
+            finalizer(value);
+        }
+    }) as Arc<Finalizer>;
+
+    let mut finalizers = match FINALIZERS.write() {
+        Ok(guard) => guard,
+        Err(poison) => poison.into_inner(),
+    };
+
+    let previous = finalizers.insert(T::type_meta(), finalizer);
+
+    FINALIZED.store(true, Ordering::Release);
+
+    drop(finalizers);
+    drop(previous);
+}
+
+/// Removes the finalizer of the `ty` type previously installed by the
+/// [set_finalizer] function.
+///
+/// If the type does not have a finalizer, this function does nothing.
+pub fn remove_finalizer(ty: &'static TypeMeta) {
+    let mut finalizers = match FINALIZERS.write() {
+        Ok(guard) => guard,
+        Err(poison) => poison.into_inner(),
+    };
+
+    let previous = finalizers.remove(ty);
+
+    FINALIZED.store(!finalizers.is_empty(), Ordering::Release);
+
+    drop(finalizers);
+    drop(previous);
+}
+
+// Returns true if the current thread is calling a finalizer, meaning that
+// the script evaluation is not allowed.
+#[inline(always)]
+pub(crate) fn is_finalizing() -> bool {
+    FINALIZING.with(|finalizing| finalizing.get())
+}
+
+// Calls the finalizer of the `ty` type, if any, for each of the `length`
+// values starting from the `head` address.
+//
+// Safety: `head` points to the slice of `length` initialized values of
+//         the `ty` type.
+#[inline(always)]
+pub(super) unsafe fn finalize(ty: &'static TypeMeta, head: *const (), length: usize) {
+    if !FINALIZED.load(Ordering::Acquire) {
+        return;
+    }
+
+    let finalizer = {
+        let finalizers = match FINALIZERS.read() {
+            Ok(guard) => guard,
+            Err(poison) => poison.into_inner(),
+        };
+
+        match finalizers.get(ty) {
+            Some(finalizer) => finalizer.clone(),
+            None => return,
+        }
+    };
+
+    struct Finalizing(bool);
+
+    impl Drop for Finalizing {
+        fn drop(&mut self) {
+            FINALIZING.with(|finalizing| finalizing.set(self.0));
+        }
+    }
+
+    let _finalizing = Finalizing(FINALIZING.with(|finalizing| finalizing.replace(true)));
+
+    finalizer(head, length);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell as StdCell,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ScriptModule},
+        export,
+        interpret::{remove_runtime_hook, set_runtime_hook},
+        runtime::{
+            remove_finalizer,
+            set_finalizer,
+            Cell,
+            Origin,
+            RuntimeError,
+            RuntimeResult,
+            ScriptPackage,
+            ScriptType,
+        },
+    };
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+    static FINALIZED: AtomicUsize = AtomicUsize::new(0);
+
+    #[export]
+    pub struct TestCanary {
+        pub id: usize,
+    }
+
+    impl Drop for TestCanary {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[export]
+    pub fn test_canary(id: usize) -> TestCanary {
+        TestCanary { id }
+    }
+
+    fn run(text: &str) -> Result<Cell, RuntimeError> {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        read.compile().unwrap().run()
+    }
+
+    fn canary_id(canary: &TestCanary) -> RuntimeResult<&usize> {
+        Ok(&canary.id)
+    }
+
+    fn counts() -> (usize, usize) {
+        (
+            DROPPED.load(Ordering::SeqCst),
+            FINALIZED.load(Ordering::SeqCst),
+        )
+    }
+
+    #[test]
+    fn test_drop_semantics() {
+        set_finalizer::<TestCanary>(|canary| {
+            assert!(canary.id > 0);
+
+            FINALIZED.fetch_add(1, Ordering::SeqCst);
+        });
+
+        run("let a = test_canary(1); let b = [test_canary(2), test_canary(3)];").unwrap();
+
+        assert_eq!(counts(), (3, 3));
+
+        let error = run("let a = test_canary(1); let b = test_canary(2); b.unknown;").unwrap_err();
+
+        assert!(matches!(error, RuntimeError::UnknownField { .. }));
+        assert_eq!(counts(), (5, 5));
+
+        let steps = StdCell::new(0);
+
+        set_runtime_hook(move |_| {
+            steps.set(steps.get() + 1);
+            steps.get() < 100
+        });
+
+        let error = run("let a = test_canary(1); loop { let b = test_canary(2); }").unwrap_err();
+
+        remove_runtime_hook();
+
+        assert!(matches!(error, RuntimeError::Interrupted { .. }));
+
+        let (dropped, finalized) = counts();
+
+        assert!(dropped >= 7);
+        assert_eq!(dropped, finalized);
+
+        let mut result = run("let a = test_canary(1); return test_canary(2);").unwrap();
+
+        assert_eq!(counts(), (dropped + 1, finalized + 1));
+
+        let mut shared = result.clone();
+
+        assert!(matches!(
+            result.release(Origin::nil()),
+            Err(RuntimeError::SharedRelease { .. }),
+        ));
+
+        assert!(matches!(
+            shared.release(Origin::nil()),
+            Err(RuntimeError::SharedRelease { .. }),
+        ));
+
+        assert!(!shared.is_nil());
+
+        drop(shared);
+
+        assert_eq!(counts(), (dropped + 1, finalized + 1));
+
+        result.release(Origin::nil()).unwrap();
+
+        assert!(result.is_nil());
+        assert_eq!(counts(), (dropped + 2, finalized + 2));
+
+        let mut field = Cell::give(Origin::nil(), TestCanary { id: 3 }).unwrap();
+        let projection = field.clone().map_ref(Origin::nil(), canary_id).unwrap();
+
+        assert!(matches!(
+            field.release(Origin::nil()),
+            Err(RuntimeError::ReadToWrite { .. }),
+        ));
+
+        drop(projection);
+
+        field.release(Origin::nil()).unwrap();
+
+        assert_eq!(counts(), (dropped + 3, finalized + 3));
+
+        remove_finalizer(TestCanary::type_meta());
+
+        run("let a = test_canary(1);").unwrap();
+
+        assert_eq!(counts(), (dropped + 4, finalized + 3));
+    }
+}
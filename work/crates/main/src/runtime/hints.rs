@@ -38,7 +38,7 @@ use std::{
     hash::Hash,
 };
 
-use crate::runtime::{InvocationMeta, PackageMeta, RustIdent, TypeFamily, TypeMeta};
+use crate::runtime::{InvocationMeta, PackageMeta, RustIdent, ScriptType, TypeFamily, TypeMeta};
 
 /// An extended type metadata that can be a [TypeMeta], [TypeFamily], or
 /// [InvocationMeta].
@@ -70,7 +70,7 @@ impl Display for TypeHint {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Type(meta) => {
-                if formatter.alternate() || meta.is_fn() {
+                if formatter.alternate() || meta.is_fn() || meta.family().is_namespace() {
                     return Display::fmt(meta, formatter);
                 }
 
@@ -153,6 +153,17 @@ impl TypeHint {
         }
     }
 
+    /// Returns true if the underlying type represents a nested namespace of
+    /// the Script package.
+    #[inline(always)]
+    pub fn is_namespace(&self) -> bool {
+        match self {
+            Self::Type(meta) => meta.family().is_namespace(),
+            Self::Family(meta) => meta.is_namespace(),
+            Self::Invocation(_) => false,
+        }
+    }
+
     /// Returns true if the underlying type represents a number (e.g., `u8`,
     /// `isize`, `f32`, etc.) or belongs to a family of numeric types.
     #[inline(always)]
@@ -193,6 +204,24 @@ impl TypeHint {
         }
     }
 
+    // Returns the hint of the script data into which the values of the casted
+    // type families are represented in scripts: durations are numbers, and
+    // paths are strings.
+    #[inline(always)]
+    pub(crate) fn casted(self) -> Self {
+        let family = self.type_family();
+
+        if family.is_duration() {
+            return Self::Family(TypeFamily::number());
+        }
+
+        if family.is_path() {
+            return Self::Type(<str>::type_meta());
+        }
+
+        self
+    }
+
     /// If the underlying type supports the
     /// [invocation operator](crate::runtime::Prototype::implements_invocation),
     /// returns metadata for this invocation.
@@ -228,7 +257,7 @@ impl TypeHint {
             Self::Type(meta) => {
                 let family = meta.family();
 
-                if family.len() > 1 && !family.is_package() {
+                if family.len() > 1 && !family.is_package() && !family.is_namespace() {
                     return family.doc();
                 }
 
@@ -279,6 +308,56 @@ pub struct ComponentHint {
     /// }
     /// ```
     pub doc: Option<&'static str>,
+
+    /// If true, the component's value is created anew each time the component
+    /// is accessed (e.g., the component is a method of the type). Mutations of
+    /// such a value do not persist in the object that owns the component.
+    ///
+    /// If false, the component is a reference into the object's data (e.g., a
+    /// Rust struct field), or it is exposed by a custom accessor function
+    /// (`#[export(component)]`) that controls the persistence itself.
+    pub owned: bool,
+
+    /// If true, the component is a function that the Rust author marked as
+    /// free of side effects (`#[export(pure)]`).
+    ///
+    /// The static analyzer may invoke such functions at analysis time when all
+    /// of their arguments are constants (see
+    /// [ModuleSymbol::expr_value](crate::analysis::symbols::ModuleSymbol::expr_value)),
+    /// and the compiler precomputes such calls.
+    pub pure: bool,
+
+    /// If true, the component is a property exposed by a pair of Rust
+    /// accessor methods (`#[export(getter)]` and `#[export(setter)]`) rather
+    /// than a direct reference into the object's data.
+    pub property: bool,
+
+    /// If false, the component cannot be assigned from scripts.
+    ///
+    /// This is the case for properties that have a getter but no setter.
+    pub writable: bool,
+
+    /// If set, the Rust author marked the component as deprecated
+    /// (`#[export(deprecated "<message>")]`), and the value is the deprecation
+    /// message.
+    ///
+    /// The static analyzer warns about the script code that refers to
+    /// deprecated components.
+    pub deprecated: Option<&'static str>,
+
+    /// If true, the Rust author excluded the component from the code
+    /// completion and the symbol lists (`#[export(hidden)]`).
+    ///
+    /// Hidden components are otherwise ordinary: the scripts can use them,
+    /// and the static analyzer resolves them as usual.
+    pub hidden: bool,
+
+    /// The registration order of the component among the components of
+    /// the type.
+    ///
+    /// The components exported by the same `#[export]` item are registered in
+    /// the order of their declaration in the Rust source code.
+    pub index: usize,
 }
 
 impl Display for ComponentHint {
@@ -40,9 +40,19 @@ use std::{
 
 use crate::{
     report::system_panic,
-    runtime::{Cell, Ident, Origin, Provider, TypeHint},
+    runtime::{Cell, Ident, Origin, Provider, StructSchema, TypeHint},
 };
 
+// The greatest number of parameters of the anonymous signature rendered
+// without elision.
+const ELISION_THRESHOLD: usize = 8;
+
+// The number of leading parameters rendered in the elided signature.
+const ELISION_HEAD: usize = 3;
+
+// The number of trailing parameters rendered in the elided signature.
+const ELISION_TAIL: usize = 1;
+
 /// Metadata for a function-like object.
 ///
 /// Typically, this object describes the signatures of exported Rust functions
@@ -54,7 +64,12 @@ use crate::{
 ///
 /// The [Display] implementation of this object renders a canonical, user-facing
 /// view of the function's signature, such as
-/// `fn foo(x: usize, y: bool) -> f32`.
+/// `fn foo(x: usize, y: bool) -> f32`. For the anonymous signatures (such as
+/// the signatures of the callback parameters) with more than 8 parameters,
+/// the view elides the middle parameters: `fn(usize, usize, usize, ..., bool)`.
+/// The alternate form (`{:#}`) always renders all parameters. The
+/// [variadic](Self::variadic) parameter is rendered with the trailing ellipsis:
+/// `fn log(values…)`.
 ///
 /// Generally, you don't need to instantiate this object manually, unless your
 /// crate introduces new types of invokable objects. In such cases, you should
@@ -72,6 +87,8 @@ use crate::{
 ///         inputs: Some(vec![Param {
 ///             name: None,
 ///             hint: <usize>::type_meta().into(),
+///             schema: None,
+///             doc: None,
 ///         }]),
 ///         ..InvocationMeta::new(Origin::nil())
 ///     }
@@ -106,6 +123,25 @@ pub struct InvocationMeta {
     /// If the return type is not specified, the `output` corresponds to the
     /// [TypeHint::dynamic].
     pub output: TypeHint,
+
+    /// The declarative pre- and postconditions of the function.
+    ///
+    /// The export macro populates this vector from the
+    /// `#[export(requires <condition>)]` and `#[export(ensures <condition>)]`
+    /// attributes. The function's invocation checks these conditions at
+    /// runtime, and the static analyzer uses the preconditions to check
+    /// constant arguments of the function calls.
+    pub contracts: Vec<Contract>,
+
+    /// If true, the last parameter of the [inputs](Self::inputs) collects all
+    /// remaining invocation arguments, such that the function accepts any
+    /// number of arguments starting from the number of the preceding
+    /// parameters.
+    ///
+    /// The export macro sets this flag for the exported functions whose last
+    /// parameter is
+    /// [VariadicArguments](crate::runtime::ops::VariadicArguments).
+    pub variadic: bool,
 }
 
 impl PartialEq for &'static InvocationMeta {
@@ -137,15 +173,31 @@ impl Display for InvocationMeta {
         if let Some(parameters) = &self.inputs {
             formatter.write_str("(")?;
 
+            let elide = self.name.is_none()
+                && !formatter.alternate()
+                && parameters.len() > ELISION_THRESHOLD;
+
             let mut is_first = true;
 
-            for parameter in parameters {
+            for (index, parameter) in parameters.iter().enumerate() {
+                if elide && index >= ELISION_HEAD && index + ELISION_TAIL < parameters.len() {
+                    if index == ELISION_HEAD {
+                        formatter.write_str(", ...")?;
+                    }
+
+                    continue;
+                }
+
                 match is_first {
                     true => is_first = false,
                     false => formatter.write_str(", ")?,
                 }
 
                 Display::fmt(parameter, formatter)?;
+
+                if self.variadic && index + 1 == parameters.len() {
+                    formatter.write_str("…")?;
+                }
             }
 
             formatter.write_str(")")?;
@@ -172,6 +224,8 @@ impl InvocationMeta {
             receiver: None,
             inputs: None,
             output: TypeHint::dynamic(),
+            contracts: Vec::new(),
+            variadic: false,
         }
     }
 
@@ -186,6 +240,99 @@ impl InvocationMeta {
     }
 }
 
+// Matches the invocation arguments with the function's parameters (see
+// InvocationMeta::inputs).
+//
+// The `names` are the names of the arguments in the invocation order
+// (`foo(<name>: arg)`), with None for the positional arguments. If
+// `closure` is true, the last argument is a trailing closure, which binds
+// to the last parameter.
+//
+// Returns the index of the parameter for each argument. The positional
+// arguments refer to the parameters by their position, even if the
+// position exceeds the function's arity.
+pub(crate) fn arrange_args<'a>(
+    params: &[Param],
+    names: impl IntoIterator<Item = Option<&'a str>>,
+    closure: bool,
+) -> Result<Vec<usize>, ArgNameError> {
+    let mut names = names.into_iter().collect::<Vec<_>>();
+
+    let closure = match closure {
+        true => names.pop().map(|_| names.len()),
+        false => None,
+    };
+
+    let mut provided = vec![false; params.len()];
+    let mut result = Vec::with_capacity(names.len() + 1);
+    let mut named = false;
+
+    for (index, name) in names.into_iter().enumerate() {
+        let param = match name {
+            None => {
+                if named {
+                    return Err(ArgNameError::Misplaced(index));
+                }
+
+                index
+            }
+
+            Some(name) => {
+                named = true;
+
+                let Some(param) = params.iter().position(|param| match &param.name {
+                    Some(param_name) => param_name.as_ref() == name,
+                    None => false,
+                }) else {
+                    return Err(ArgNameError::Unknown(index));
+                };
+
+                if provided[param] {
+                    return Err(ArgNameError::Duplicate(index, param));
+                }
+
+                param
+            }
+        };
+
+        if let Some(flag) = provided.get_mut(param) {
+            *flag = true;
+        }
+
+        result.push(param);
+    }
+
+    if let Some(index) = closure {
+        let param = match named {
+            true => params.len().checked_sub(1).unwrap_or_default(),
+            false => index,
+        };
+
+        if provided.get(param).copied().unwrap_or_default() {
+            return Err(ArgNameError::Duplicate(index, param));
+        }
+
+        result.push(param);
+    }
+
+    Ok(result)
+}
+
+// A failure to match the named invocation arguments with the function's
+// parameters. The first value is the index of the offending argument.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum ArgNameError {
+    // The function does not have a parameter with the argument's name.
+    Unknown(usize),
+
+    // The parameter (the second value) is already provided by another
+    // argument.
+    Duplicate(usize, usize),
+
+    // The positional argument follows a named argument.
+    Misplaced(usize),
+}
+
 /// A description of a parameter in the [InvocationMeta].
 #[derive(Clone, Debug)]
 pub struct Param {
@@ -194,6 +341,18 @@ pub struct Param {
 
     /// The type of the parameter.
     pub hint: TypeHint,
+
+    /// The description of the expected struct fields if the parameter is a
+    /// Rust struct constructed from a script struct (see
+    /// [FromScriptStruct](crate::runtime::FromScriptStruct)).
+    pub schema: Option<&'static StructSchema>,
+
+    /// The RustDoc description of the parameter, if any.
+    ///
+    /// The export macro extracts this text from the function's documentation
+    /// comments by recognizing argument list items such as
+    /// ``* `name` - description`` or `@param name description`.
+    pub doc: Option<&'static str>,
 }
 
 impl Display for Param {
@@ -215,6 +374,248 @@ impl Display for Param {
     }
 }
 
+/// A declarative condition of the function's contract in the
+/// [InvocationMeta].
+///
+/// The conditions are introduced by the `#[export(requires <condition>)]` and
+/// `#[export(ensures <condition>)]` attributes of the exported functions.
+/// The export macro splits the top-level `&&` conjunctions of the condition
+/// into separate contracts, such that each contract describes a single
+/// clause.
+#[derive(Clone, Debug)]
+pub struct Contract {
+    /// Whether the condition is a precondition or a postcondition.
+    pub kind: ContractKind,
+
+    /// The source text of the condition (e.g., `alpha <= 1.0`).
+    pub condition: &'static str,
+
+    /// The structured form of the condition suitable for static evaluation.
+    pub expr: ContractExpr,
+}
+
+impl Display for Contract {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_fmt(format_args!("{} {}", self.kind, self.condition))
+    }
+}
+
+impl Contract {
+    /// Returns the index of the first function parameter referred to by the
+    /// condition, if any.
+    #[inline(always)]
+    pub fn param(&self) -> Option<usize> {
+        self.expr.param()
+    }
+}
+
+/// A kind of the function's [Contract].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContractKind {
+    /// The condition over the function's arguments checked before the
+    /// function's invocation.
+    Requires,
+
+    /// The condition over the function's result checked after the function's
+    /// invocation.
+    Ensures,
+}
+
+impl Display for ContractKind {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Requires => formatter.write_str("requires"),
+            Self::Ensures => formatter.write_str("ensures"),
+        }
+    }
+}
+
+/// A structured form of the [Contract]'s condition.
+///
+/// The contract conditions are restricted to a simple subset of Rust
+/// expressions: boolean and numeric literals, references to the function's
+/// parameters (or to the function's `result` in postconditions), the `!` and
+/// `-` unary operators, and the logical, comparison, and arithmetic binary
+/// operators.
+#[derive(Clone, Debug)]
+pub enum ContractExpr {
+    /// A reference to the function's parameter by its index.
+    Param(usize),
+
+    /// A reference to the function's result.
+    Result,
+
+    /// A boolean literal.
+    Bool(bool),
+
+    /// A numeric literal.
+    Number(f64),
+
+    /// A logical negation `!<expr>`.
+    Not(Box<ContractExpr>),
+
+    /// A numeric negation `-<expr>`.
+    Neg(Box<ContractExpr>),
+
+    /// A binary operation `<lhs> <op> <rhs>`.
+    Binary(ContractOp, Box<ContractExpr>, Box<ContractExpr>),
+}
+
+impl ContractExpr {
+    /// Evaluates the condition.
+    ///
+    /// The `params` slice contains the statically known values of the
+    /// function's arguments, and the `result` is the statically known value of
+    /// the function's result.
+    ///
+    /// Returns None if the expression's value cannot be inferred from the
+    /// known values.
+    pub fn eval(
+        &self,
+        params: &[Option<ContractValue>],
+        result: Option<ContractValue>,
+    ) -> Option<ContractValue> {
+        match self {
+            Self::Param(index) => params.get(*index).copied().flatten(),
+
+            Self::Result => result,
+
+            Self::Bool(value) => Some(ContractValue::Bool(*value)),
+
+            Self::Number(value) => Some(ContractValue::Number(*value)),
+
+            Self::Not(inner) => Some(ContractValue::Bool(!inner.eval(params, result)?.as_bool()?)),
+
+            Self::Neg(inner) => Some(ContractValue::Number(
+                -inner.eval(params, result)?.as_number()?,
+            )),
+
+            Self::Binary(ContractOp::And, lhs, rhs) => {
+                let lhs = lhs.eval(params, result).and_then(ContractValue::as_bool);
+                let rhs = rhs.eval(params, result).and_then(ContractValue::as_bool);
+
+                match (lhs, rhs) {
+                    (Some(false), _) | (_, Some(false)) => Some(ContractValue::Bool(false)),
+                    (Some(true), Some(true)) => Some(ContractValue::Bool(true)),
+                    _ => None,
+                }
+            }
+
+            Self::Binary(ContractOp::Or, lhs, rhs) => {
+                let lhs = lhs.eval(params, result).and_then(ContractValue::as_bool);
+                let rhs = rhs.eval(params, result).and_then(ContractValue::as_bool);
+
+                match (lhs, rhs) {
+                    (Some(true), _) | (_, Some(true)) => Some(ContractValue::Bool(true)),
+                    (Some(false), Some(false)) => Some(ContractValue::Bool(false)),
+                    _ => None,
+                }
+            }
+
+            Self::Binary(op, lhs, rhs) => {
+                let lhs = lhs.eval(params, result)?;
+                let rhs = rhs.eval(params, result)?;
+
+                match (op, lhs, rhs) {
+                    (ContractOp::Eq, lhs, rhs) => Some(ContractValue::Bool(lhs == rhs)),
+                    (ContractOp::Ne, lhs, rhs) => Some(ContractValue::Bool(lhs != rhs)),
+
+                    (op, ContractValue::Number(lhs), ContractValue::Number(rhs)) => {
+                        Some(match op {
+                            ContractOp::Lt => ContractValue::Bool(lhs < rhs),
+                            ContractOp::Le => ContractValue::Bool(lhs <= rhs),
+                            ContractOp::Gt => ContractValue::Bool(lhs > rhs),
+                            ContractOp::Ge => ContractValue::Bool(lhs >= rhs),
+                            ContractOp::Add => ContractValue::Number(lhs + rhs),
+                            ContractOp::Sub => ContractValue::Number(lhs - rhs),
+                            ContractOp::Mul => ContractValue::Number(lhs * rhs),
+                            ContractOp::Div => ContractValue::Number(lhs / rhs),
+                            ContractOp::Rem => ContractValue::Number(lhs % rhs),
+                            _ => return None,
+                        })
+                    }
+
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Returns the index of the first function parameter referred to by this
+    /// expression, if any.
+    pub fn param(&self) -> Option<usize> {
+        match self {
+            Self::Param(index) => Some(*index),
+            Self::Not(inner) | Self::Neg(inner) => inner.param(),
+            Self::Binary(_, lhs, rhs) => lhs.param().or_else(|| rhs.param()),
+            _ => None,
+        }
+    }
+}
+
+/// A binary operator of the [ContractExpr].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContractOp {
+    /// `&&`
+    And,
+    /// `||`
+    Or,
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `+`
+    Add,
+    /// `-`
+    Sub,
+    /// `*`
+    Mul,
+    /// `/`
+    Div,
+    /// `%`
+    Rem,
+}
+
+/// A statically known value evaluated by the [ContractExpr::eval] function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContractValue {
+    /// A boolean value.
+    Bool(bool),
+
+    /// A numeric value. Integer values are converted to floats.
+    Number(f64),
+}
+
+impl ContractValue {
+    /// Returns the boolean value if this value is boolean.
+    #[inline(always)]
+    pub fn as_bool(self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(value),
+            Self::Number(_) => None,
+        }
+    }
+
+    /// Returns the numeric value if this value is numeric.
+    #[inline(always)]
+    pub fn as_number(self) -> Option<f64> {
+        match self {
+            Self::Bool(_) => None,
+            Self::Number(value) => Some(value),
+        }
+    }
+}
+
 /// A wrapper for a [Cell] that is intended to be an argument of a function or
 /// an operator.
 #[derive(Clone, Default)]
@@ -306,3 +707,419 @@ impl Arg {
         take(unsafe { arguments.get_unchecked_mut(index) })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::{
+        analysis::TriggerHandle,
+        lexis::{SourceCode, ToSpan},
+    };
+
+    use crate::{
+        analysis::{
+            symbols::tests::TestPackage,
+            AnalysisConfig,
+            IssueCode,
+            ModuleRead,
+            ModuleWrite,
+            ScriptModule,
+        },
+        export,
+        runtime::{
+            ops::{Fn9, VariadicArguments},
+            ContractKind,
+            Downcast,
+            Origin,
+            Provider,
+            RuntimeError,
+            RuntimeResult,
+            ScriptPackage,
+            TypeHint,
+        },
+    };
+
+    #[export(requires alpha >= 0.0 && alpha <= 1.0)]
+    #[export(requires steps > 0)]
+    #[export(ensures result <= 100.0)]
+    pub fn contract_blend(alpha: f32, steps: usize) -> f32 {
+        alpha * steps as f32
+    }
+
+    #[test]
+    fn test_contract_checks() {
+        assert_eq!(
+            crate::eval_as!(TestPackage, f32, "return contract_blend(0.5, 4);"),
+            2.0,
+        );
+
+        let handle = TriggerHandle::new();
+
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "let f = fn(a) { return contract_blend(a, 3); }; return f(1.5);",
+        );
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        let RuntimeError::ContractViolation {
+            kind, condition, ..
+        } = &error
+        else {
+            panic!("Unexpected error: {error}");
+        };
+
+        assert_eq!(*kind, ContractKind::Requires);
+        assert_eq!(*condition, "alpha <= 1.0");
+        assert_eq!(
+            error.to_string(),
+            "the function's precondition `alpha <= 1.0` is violated",
+        );
+
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "let f = fn(a) { return contract_blend(0.9, a); }; return f(200);",
+        );
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        let RuntimeError::ContractViolation {
+            kind, condition, ..
+        } = &error
+        else {
+            panic!("Unexpected error: {error}");
+        };
+
+        assert_eq!(*kind, ContractKind::Ensures);
+        assert_eq!(*condition, "result <= 100.0");
+    }
+
+    #[export]
+    pub fn named_span(start: usize, end: usize) -> usize {
+        end - start
+    }
+
+    #[test]
+    fn test_named_arguments() {
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return named_span(end: 10, start: 3);"),
+            7,
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return named_span(3, end: 10);"),
+            7,
+        );
+
+        let handle = TriggerHandle::new();
+
+        let run = |text: &str| {
+            let module = ScriptModule::new(TestPackage::meta(), text);
+            let read = module.read(&handle, 1).unwrap();
+
+            read.compile().unwrap().run().unwrap_err().to_string()
+        };
+
+        assert_eq!(
+            run("named_span(begin: 3, end: 10);"),
+            "the function does not have parameter 'begin'",
+        );
+
+        assert_eq!(
+            run("named_span(3, start: 10);"),
+            "parameter 'start' is provided more than once",
+        );
+
+        assert_eq!(
+            run("named_span(start: 3, 10);"),
+            "positional argument follows named arguments",
+        );
+
+        assert_eq!(
+            run("let f = fn(a, b) { return a; }; f(a: 1, b: 2);"),
+            "the function does not have parameter 'a'",
+        );
+    }
+
+    #[test]
+    fn test_named_argument_diagnostics() {
+        crate::assert_script_ok!(TestPackage, "named_span(end: 10, start: 3);");
+        crate::assert_script_ok!(TestPackage, "named_span(3, end: 10);");
+
+        crate::assert_script_err!(
+            TestPackage,
+            "named_span(strat: 3, end: 10);",
+            IssueCode::UnknownArgument,
+            "strat",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "named_span(3, start: 10);",
+            IssueCode::DuplicateArgument,
+            "start",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "named_span(start: 3, 10);",
+            IssueCode::MisplacedArgument,
+            "10",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "let f = fn(a, b) a; f(a: 1, a: 2);",
+            IssueCode::UnknownArgument,
+            "a",
+        );
+
+        let handle = TriggerHandle::new();
+        let module = ScriptModule::new(TestPackage::meta(), "named_span(strat: 3, end: 10);");
+        let read = module.read(&handle, 1).unwrap();
+        let diagnostics = read.diagnostics(2).unwrap();
+
+        let issue = diagnostics
+            .iter()
+            .find(|issue| issue.code() == IssueCode::UnknownArgument)
+            .unwrap();
+
+        assert_eq!(
+            issue.quickfix().unwrap().set_text_to_origin.as_deref(),
+            Some("start"),
+        );
+    }
+
+    #[test]
+    fn test_contract_diagnostics() {
+        let handle = TriggerHandle::new();
+
+        let violations = |text: &str| {
+            let module = ScriptModule::new(TestPackage::meta(), text);
+            let read = module.read(&handle, 1).unwrap();
+            let module_text = read.text();
+            let diagnostics = read.diagnostics(3).unwrap();
+
+            let mut violations = diagnostics
+                .iter()
+                .filter(|issue| issue.code() == IssueCode::ContractViolation)
+                .map(|issue| {
+                    let span = issue
+                        .origin(&module_text)
+                        .to_site_span(&module_text)
+                        .unwrap();
+
+                    format!(
+                        "{}: {}",
+                        module_text.substring(span),
+                        issue.verbose_message(&module_text),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            violations.sort();
+
+            violations
+        };
+
+        assert_eq!(
+            violations("contract_blend(1.5, 4);"),
+            ["1.5: argument 1 violates `alpha <= 1.0`"],
+        );
+
+        assert_eq!(
+            violations("contract_blend(-0.5, 0);"),
+            [
+                "-0.5: argument 1 violates `alpha >= 0.0`",
+                "0: argument 2 violates `steps > 0`",
+            ],
+        );
+
+        assert!(violations("contract_blend(0.5, 4);").is_empty());
+        assert!(violations("let a = 3.0; contract_blend(a, 4);").is_empty());
+    }
+
+    #[test]
+    fn test_shadowed_members() {
+        crate::assert_script_err!(
+            TestPackage,
+            "let named_span = 5; let f = fn(type_name) type_name;",
+            IssueCode::ShadowedMember,
+            "named_span",
+            "type_name",
+        );
+
+        crate::assert_script_ok!(TestPackage, "let span = 5; let f = fn(start) start;");
+
+        let handle = TriggerHandle::new();
+        let module = ScriptModule::new(TestPackage::meta(), "let named_span = 5;");
+
+        module
+            .write(&handle, 1)
+            .unwrap()
+            .set_analysis_config(AnalysisConfig {
+                shadowing_warnings: false,
+                ..AnalysisConfig::new()
+            })
+            .unwrap();
+
+        let read = module.read(&handle, 1).unwrap();
+
+        assert!(read.diagnostics(2).unwrap().is_empty());
+
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "let named_span = 5;\nreturn named_span(3, 10);",
+        );
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        let RuntimeError::ShadowedMember { name, .. } = &error else {
+            panic!("Unexpected error: {error}");
+        };
+
+        assert_eq!(name, "named_span");
+        assert!(error
+            .to_string()
+            .ends_with("(variable 'named_span' shadows the package member)"));
+
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "let f = fn(named_span) named_span(3, 10);\nreturn f(5);",
+        );
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert!(matches!(error, RuntimeError::ShadowedMember { .. }));
+
+        let module = ScriptModule::new(TestPackage::meta(), "let f = 5;\nreturn f(3, 10);");
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert!(!matches!(error, RuntimeError::ShadowedMember { .. }));
+    }
+
+    #[export]
+    pub fn contact_sum(
+        callback: Fn9<usize, usize, usize, usize, usize, usize, usize, usize, bool, usize>,
+    ) -> RuntimeResult<usize> {
+        callback(1, 2, 3, 4, 5, 6, 7, 8, true)
+    }
+
+    #[test]
+    fn test_long_callbacks() {
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "return contact_sum(fn(a, b, c, d, e, f, g, h, i) a + b + c + d + e + f + g + h);",
+            ),
+            36,
+        );
+
+        let component = TestPackage::meta()
+            .ty()
+            .prototype()
+            .hint_component("contact_sum")
+            .unwrap();
+
+        let invocation = component.ty.invocation().unwrap();
+        let inputs = invocation.inputs.as_ref().unwrap();
+
+        let TypeHint::Invocation(callback) = inputs[0].hint else {
+            panic!("Callback signature expected.");
+        };
+
+        assert_eq!(
+            callback.to_string(),
+            "fn(number, number, number, ..., bool) -> number"
+        );
+        assert_eq!(
+            format!("{callback:#}"),
+            "fn(number, number, number, number, number, number, number, number, bool) -> usize",
+        );
+    }
+
+    #[export]
+    pub fn variadic_join(
+        separator: &str,
+        values: VariadicArguments<usize>,
+    ) -> RuntimeResult<String> {
+        let mut result = Vec::with_capacity(values.len());
+
+        for arg in values.args {
+            let value = <usize>::downcast(arg.origin, Provider::Owned(arg.data))?;
+
+            result.push(value.to_string());
+        }
+
+        Ok(result.join(separator))
+    }
+
+    #[test]
+    fn test_variadic_functions() {
+        let handle = TriggerHandle::new();
+
+        let eval = |text: &str| {
+            let module = ScriptModule::new(TestPackage::meta(), text);
+            let read = module.read(&handle, 1).unwrap();
+
+            read.compile()
+                .unwrap()
+                .run()
+                .unwrap()
+                .take_string(Origin::nil())
+                .unwrap()
+        };
+
+        assert_eq!(eval("return variadic_join(\"-\");"), "");
+        assert_eq!(eval("return variadic_join(\"-\", 1, 2, 3);"), "1-2-3");
+        assert_eq!(
+            eval("return variadic_join(separator: \", \", values: 7);"),
+            "7"
+        );
+
+        crate::assert_script_ok!(TestPackage, "variadic_join(\"-\", 1, 2, 3, 4, 5);");
+
+        crate::assert_script_err!(
+            TestPackage,
+            "variadic_join();",
+            IssueCode::CallArityMismatch,
+            "()",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "variadic_join(\"-\", 1, true);",
+            IssueCode::TypeMismatch,
+            "true",
+        );
+
+        let module = ScriptModule::new(TestPackage::meta(), "let f = variadic_join; f();");
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert!(matches!(
+            error,
+            RuntimeError::ArityMismatch {
+                parameters: 1,
+                arguments: 0,
+                ..
+            }
+        ));
+
+        let component = TestPackage::meta()
+            .ty()
+            .prototype()
+            .hint_component("variadic_join")
+            .unwrap();
+
+        let invocation = component.ty.invocation().unwrap();
+
+        assert!(invocation.variadic);
+        assert_eq!(invocation.arity(), Some(2));
+        assert_eq!(
+            invocation.to_string(),
+            "fn variadic_join(separator: str, values: number…) -> str",
+        );
+    }
+}
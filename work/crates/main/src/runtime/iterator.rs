@@ -0,0 +1,416 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Mutex,
+};
+
+use crate::{
+    export,
+    runtime::{Cell, Downcast, Origin, Provider, RuntimeResult, ScriptType, TypeHint, Upcast},
+};
+
+type NextFn = dyn FnMut(Origin) -> Option<RuntimeResult<Cell>> + Send;
+
+/// A lazy sequence of script values produced by a Rust [Iterator].
+///
+/// The [export](crate::export) macro wraps the results of the exported
+/// functions that return `impl Iterator<Item = T>` into this object, so the
+/// script can iterate through the sequence using the `for` loop:
+///
+/// ```ignore
+/// #[export]
+/// pub fn enemies_in_radius(center: Vec2, radius: f32) -> impl Iterator<Item = Enemy> {
+///     // ...
+/// }
+/// ```
+///
+/// ```text
+/// for enemy in enemies_in_radius(player.position, 10.0) {
+///     enemy.alert();
+/// }
+/// ```
+///
+/// The object is lazy: the underlying Rust iterator produces the next item,
+/// and the item is converted into a script value, only when the script's
+/// `for` loop requests the next value. When the script drops the object
+/// (e.g., when the loop ends early with the `break` statement), the Rust
+/// iterator is dropped as well.
+///
+/// Since the object is a script value that can be stored and sent between
+/// threads, the Rust iterator must be [Send] and `'static`. Therefore, the
+/// iterator cannot borrow the arguments of the exported function or any
+/// other non-static data. Move the required state into the iterator instead,
+/// or share it through reference-counted pointers such as
+/// [Arc](std::sync::Arc).
+pub struct ScriptIterator {
+    next: Mutex<Box<NextFn>>,
+}
+
+impl Debug for ScriptIterator {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("ScriptIterator")
+    }
+}
+
+impl ScriptIterator {
+    /// Creates a script iterator from the Rust `iterator`.
+    ///
+    /// The item type of the iterator must be convertible into a script value
+    /// (i.e., it must implement the [Upcast] trait).
+    pub fn new<I>(mut iterator: I) -> Self
+    where
+        I: Iterator + Send + 'static,
+        I::Item: Upcast<'static>,
+    {
+        Self {
+            next: Mutex::new(Box::new(move |origin| {
+                iterator.next().map(|item| Cell::give(origin, item))
+            })),
+        }
+    }
+
+    // Turns the iterated object of the script `for` loop into a script
+    // iterator. The objects that implement the iteration operator produce
+    // their own iterators. Any other object iterates through the elements of
+    // its array, so the single values iterate once and nil values do not
+    // iterate at all. Strings iterate as a whole.
+    pub(crate) fn of(origin: Origin, cell: Cell) -> RuntimeResult<Cell> {
+        if cell.length() == 1 && cell.ty().prototype().implements_iterate() {
+            return cell.into_object().iterate(origin, origin);
+        }
+
+        let mut index = 0;
+
+        let length = match cell.is::<str>() {
+            true => 1,
+            false => cell.length(),
+        };
+
+        let iterator = Self {
+            next: Mutex::new(Box::new(move |origin| {
+                if index >= length {
+                    return None;
+                }
+
+                let item = match length == 1 {
+                    true => Ok(cell.clone()),
+                    false => cell.clone().map_slice(origin, index..=index),
+                };
+
+                index += 1;
+
+                Some(item)
+            })),
+        };
+
+        Cell::give(origin, iterator)
+    }
+
+    /// Pulls the next item from the underlying Rust iterator and converts it
+    /// into a script value.
+    ///
+    /// The `origin` argument specifies the Rust or Script source code range
+    /// that requests the item.
+    ///
+    /// Returns None if the iterator is exhausted.
+    #[inline(always)]
+    pub fn next(&mut self, origin: Origin) -> Option<RuntimeResult<Cell>> {
+        let next = self
+            .next
+            .get_mut()
+            .unwrap_or_else(|poison| poison.into_inner());
+
+        next(origin)
+    }
+}
+
+/// A lazy sequence of values produced by the host: `enemies_in_radius(p, 10.0)`.
+///
+/// The script can iterate through the sequence using the `for` loop.
+#[export(include)]
+#[export(name "iter")]
+pub(crate) type ScriptIteratorType = ScriptIterator;
+
+impl<'a> Downcast<'a> for ScriptIteratorType {
+    #[inline(always)]
+    fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+        let mut type_match = provider.type_match();
+
+        if type_match.is::<ScriptIteratorType>() {
+            return provider.to_owned().take::<ScriptIteratorType>(origin);
+        }
+
+        Err(type_match.mismatch(origin))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(ScriptIteratorType::type_meta())
+    }
+}
+
+impl<'a> Upcast<'a> for ScriptIteratorType {
+    type Output = Box<ScriptIteratorType>;
+
+    #[inline(always)]
+    fn upcast(_origin: Origin, this: Self) -> RuntimeResult<Self::Output> {
+        Ok(Box::new(this))
+    }
+
+    #[inline(always)]
+    fn hint() -> TypeHint {
+        TypeHint::Type(ScriptIteratorType::type_meta())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use crate::{analysis::symbols::tests::TestPackage, export, runtime::ScriptType};
+
+    static PULLED: AtomicUsize = AtomicUsize::new(0);
+    static DROPPED: AtomicBool = AtomicBool::new(false);
+
+    #[export]
+    pub fn test_counting_iterator(limit: usize) -> impl Iterator<Item = usize> {
+        (0..limit).inspect(|_| {
+            let _ = PULLED.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    struct DropGuard;
+
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            DROPPED.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[export]
+    pub fn test_guarded_iterator() -> impl Iterator<Item = usize> {
+        let guard = DropGuard;
+
+        (0..).map(move |index| {
+            let _ = &guard;
+            index
+        })
+    }
+
+    #[export]
+    #[derive(Clone, Debug)]
+    pub struct Tileset {
+        tiles: Vec<usize>,
+    }
+
+    #[export]
+    impl IntoIterator for Tileset {
+        type Item = usize;
+        type IntoIter = std::vec::IntoIter<usize>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.tiles.into_iter()
+        }
+    }
+
+    #[export]
+    #[derive(Clone, Debug)]
+    pub struct Countdown {
+        remaining: usize,
+    }
+
+    #[export]
+    impl Iterator for Countdown {
+        type Item = usize;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.remaining == 0 {
+                return None;
+            }
+
+            self.remaining -= 1;
+
+            Some(self.remaining)
+        }
+    }
+
+    #[export]
+    pub fn test_tileset() -> Tileset {
+        Tileset {
+            tiles: vec![3, 5, 7],
+        }
+    }
+
+    #[export]
+    pub fn test_countdown(remaining: usize) -> Countdown {
+        Countdown { remaining }
+    }
+
+    #[test]
+    fn test_script_iterate() {
+        let prototype = Tileset::type_meta().prototype();
+
+        assert!(prototype.implements_iterate());
+        assert_eq!(prototype.hint_iterate_item(), Some(usize::type_meta()));
+        assert!(!usize::type_meta().prototype().implements_iterate());
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                r#"
+                let sum = 0;
+
+                for tile in test_tileset() {
+                    sum += tile;
+                }
+
+                return sum;
+                "#
+            ),
+            15,
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                r#"
+                let sum = 0;
+
+                for item in test_countdown(4) {
+                    sum += item * 10;
+                }
+
+                return sum;
+                "#
+            ),
+            60,
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                r#"
+                let sum = 0;
+
+                for item in [1, 2, 3] {
+                    sum += item;
+                }
+
+                for item in 10 {
+                    sum += item;
+                }
+
+                for item in [] {
+                    sum += 100;
+                }
+
+                return sum;
+                "#
+            ),
+            16,
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                r#"
+                let count = 0;
+
+                for item in "abc" {
+                    count += 1;
+                }
+
+                return count;
+                "#
+            ),
+            1,
+        );
+    }
+
+    #[test]
+    fn test_script_iterator() {
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                r#"
+                let sum = 0;
+
+                for item in test_counting_iterator(5) {
+                    sum += item;
+
+                    if item == 2 {
+                        break;
+                    }
+                }
+
+                return sum;
+                "#
+            ),
+            3,
+        );
+
+        assert_eq!(PULLED.load(Ordering::SeqCst), 3);
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                r#"
+                let last = 0;
+
+                for item in test_guarded_iterator() {
+                    last = item;
+
+                    if item == 10 {
+                        break;
+                    }
+                }
+
+                return last;
+                "#
+            ),
+            10,
+        );
+
+        assert!(DROPPED.load(Ordering::SeqCst));
+    }
+}
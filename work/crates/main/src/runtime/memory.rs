@@ -35,7 +35,7 @@
 use std::{
     any::TypeId,
     fmt::{Debug, Formatter},
-    mem::{transmute, ManuallyDrop},
+    mem::{size_of, transmute, ManuallyDrop},
     num::NonZeroUsize,
     ops::Deref,
     ptr::{null, null_mut, NonNull},
@@ -48,7 +48,15 @@ use lady_deirdre::sync::{Lazy, Table};
 
 use crate::{
     report::debug_unreachable,
-    runtime::{borrow::BorrowTable, Origin, RuntimeResult, ScriptType, TypeMeta},
+    runtime::{
+        borrow::BorrowTable,
+        budget::MemoryCharge,
+        finalizer::finalize,
+        Origin,
+        RuntimeResult,
+        ScriptType,
+        TypeMeta,
+    },
 };
 
 #[repr(transparent)]
@@ -63,6 +71,25 @@ impl Debug for MemorySlice {
 
 impl Drop for MemorySlice {
     fn drop(&mut self) {
+        if let Some(release_fn) = self.0.release_fn {
+            let head = match self.0.head_ref {
+                Some(head) => head,
+
+                // Shared MemorySlice's head_ref is always specified.
+                None => unsafe { debug_unreachable!("Shared MemorySlice without head_ref.") },
+            };
+
+            // Safety: Shared MemorySlice holds one strong reference to the allocation.
+            unsafe { release_fn(head.as_ptr()) }
+
+            #[cfg(debug_assertions)]
+            {
+                self.0.table.access().assert_empty()
+            }
+
+            return;
+        }
+
         let drop_fn = match self.0.drop_fn {
             Some(drop_fn) => drop_fn,
             None => return,
@@ -75,6 +102,11 @@ impl Drop for MemorySlice {
             None => unsafe { debug_unreachable!("Owned MemorySlice without head_mut.") },
         };
 
+        if self.0.length > 0 {
+            // Safety: Owned MemorySlice holds `length` initialized items of its type.
+            unsafe { finalize(self.0.ty, head.as_ptr(), self.0.length) }
+        }
+
         // Safety: Ownership checked above.
         unsafe { drop_fn(head.as_ptr(), self.0.length, self.0.capacity) }
 
@@ -166,6 +198,55 @@ impl MemorySlice {
         }
     }
 
+    // The returned MemorySlice shares the Arc's allocation without copying its
+    // data. The slice is read-only and is not owned by the Script Engine:
+    // the data will be dropped by the Arc when the last strong reference
+    // is released.
+    #[inline(always)]
+    pub(super) fn register_arc<T: ScriptType>(
+        origin: Origin,
+        arc: Arc<T>,
+    ) -> RuntimeResult<Arc<Self>> {
+        // Safety: `Arc::into_raw` returns non null pointer.
+        let head = unsafe { NonNull::new_unchecked(Arc::into_raw(arc) as *mut ()) };
+
+        Ok(Arc::new(Self(MemorySliceInner {
+            unicode: false,
+            origin,
+            ty: T::type_meta(),
+            head_ref: Some(head),
+            head_mut: None,
+            length: 1,
+            capacity: 1,
+            table: BorrowTable::new(),
+            drop_fn: None,
+            release_fn: Some(release_arc::<T>),
+            charge: MemoryCharge::default(),
+        })))
+    }
+
+    // Returns a new strong reference to the Arc allocation if this
+    // MemorySlice was created by the `register_arc` function with
+    // the same `T` type.
+    #[inline(always)]
+    pub(super) fn to_arc<T: ScriptType>(&self) -> Option<Arc<T>> {
+        if self.0.release_fn.is_none() || self.0.ty != &TypeId::of::<T>() {
+            return None;
+        }
+
+        let head = self.0.head_ref?.as_ptr() as *const T;
+
+        // Safety:
+        //   1. Only `register_arc` sets `release_fn`.
+        //   2. The pointer originated from `Arc::<T>::into_raw`.
+        //   3. The MemorySlice holds a strong reference to the allocation.
+        unsafe {
+            Arc::increment_strong_count(head);
+
+            Some(Arc::from_raw(head))
+        }
+    }
+
     // Safety:
     //   1. If `by_ref` and `by_mut` non null both, they have the same address.
     //   2. This address points to allocated and fully initialized well-formed
@@ -264,6 +345,8 @@ impl MemorySlice {
             capacity: length,
             table: BorrowTable::new(),
             drop_fn: None,
+            release_fn: None,
+            charge: MemoryCharge::default(),
         })))
     }
 
@@ -280,6 +363,11 @@ impl MemorySlice {
             }
         }
 
+        let charge = MemoryCharge::new(
+            origin,
+            size_of::<Self>().saturating_add(vector.capacity().saturating_mul(size_of::<T>())),
+        )?;
+
         let head;
         let length;
         let capacity;
@@ -304,6 +392,8 @@ impl MemorySlice {
             capacity,
             table: BorrowTable::new(),
             drop_fn: Some(drop_vec::<T>),
+            release_fn: None,
+            charge,
         }));
 
         if length > 0 && ty.size() > 0 {
@@ -336,6 +426,11 @@ impl MemorySlice {
         Ok(strong_entry)
     }
 
+    #[inline(always)]
+    pub(super) fn charge(&self) -> &MemoryCharge {
+        &self.0.charge
+    }
+
     #[inline(always)]
     pub(super) fn data_origin(&self) -> &Origin {
         &self.0.origin
@@ -419,6 +514,31 @@ impl MemorySlice {
         }
     }
 
+    // Returns a non-owned projection of this MemorySlice that provides
+    // immutable access only.
+    //
+    // The projection is not registered in the MemoryRegistry: the registry
+    // would otherwise resolve the projection back to this writeable slice.
+    // The borrowing of the projection's data is tracked through the Cell's
+    // chain that refers to this slice.
+    //
+    // Safety: If the MemorySlice is readable, PlaceRef or PlaceMut access granted.
+    pub(super) unsafe fn read_only(&self, origin: Origin) -> RuntimeResult<Arc<Self>> {
+        Ok(Arc::new(MemorySlice(MemorySliceInner {
+            unicode: self.0.unicode,
+            origin,
+            ty: self.0.ty,
+            head_ref: self.0.head_ref,
+            head_mut: None,
+            length: self.0.length,
+            capacity: self.0.length,
+            table: BorrowTable::new(),
+            drop_fn: None,
+            release_fn: None,
+            charge: MemoryCharge::default(),
+        })))
+    }
+
     // Safety:
     //   1. MemorySlice is owned.
     //   2. T properly describes underlying item type.
@@ -588,9 +708,11 @@ impl MemorySlice {
             return Ok(Grant::ValueRef(u32::MAX));
         }
 
-        Ok(Grant::ValueRef(
-            self.0.table.access().grant_value_ref(origin)?,
-        ))
+        Ok(Grant::ValueRef(self.0.table.grant(
+            self.0.ty,
+            origin,
+            |access| access.grant_value_ref(origin),
+        )?))
     }
 
     #[inline(always)]
@@ -599,9 +721,11 @@ impl MemorySlice {
             return Ok(Grant::ValueMut(u32::MAX));
         }
 
-        Ok(Grant::ValueMut(
-            self.0.table.access().grant_value_mut(origin)?,
-        ))
+        Ok(Grant::ValueMut(self.0.table.grant(
+            self.0.ty,
+            origin,
+            |access| access.grant_value_mut(origin),
+        )?))
     }
 
     #[inline(always)]
@@ -610,9 +734,11 @@ impl MemorySlice {
             return Ok(Grant::PlaceRef(u32::MAX));
         }
 
-        Ok(Grant::PlaceRef(
-            self.0.table.access().grant_place_ref(origin)?,
-        ))
+        Ok(Grant::PlaceRef(self.0.table.grant(
+            self.0.ty,
+            origin,
+            |access| access.grant_place_ref(origin),
+        )?))
     }
 
     #[inline(always)]
@@ -621,9 +747,11 @@ impl MemorySlice {
             return Ok(Grant::PlaceMut(u32::MAX));
         }
 
-        Ok(Grant::PlaceMut(
-            self.0.table.access().grant_place_mut(origin)?,
-        ))
+        Ok(Grant::PlaceMut(self.0.table.grant(
+            self.0.ty,
+            origin,
+            |access| access.grant_place_mut(origin),
+        )?))
     }
 
     // Safety: Grant belongs to this MemorySlice.
@@ -680,6 +808,8 @@ struct MemorySliceInner {
     capacity: usize,
     table: BorrowTable,
     drop_fn: Option<unsafe fn(head: *mut (), length: usize, capacity: usize)>,
+    release_fn: Option<unsafe fn(head: *mut ())>,
+    charge: MemoryCharge,
 }
 
 impl Debug for MemorySliceInner {
@@ -687,6 +817,7 @@ impl Debug for MemorySliceInner {
         formatter
             .debug_struct("MemorySlice")
             .field("owned", &self.is_owned())
+            .field("shared", &self.release_fn.is_some())
             .field("unicode", &self.unicode)
             .field("origin", &self.origin)
             .field("ty", &self.ty)
@@ -726,6 +857,14 @@ unsafe fn drop_vec<T>(head: *mut (), length: usize, capacity: usize) {
     let _ = unsafe { Vec::from_raw_parts(head.cast::<T>(), length, capacity) };
 }
 
+// Safety: `head` originated from `Arc::<T>::into_raw` and holds one strong
+//         reference to the allocation.
+#[inline(always)]
+unsafe fn release_arc<T>(head: *mut ()) {
+    // Safety: Upheld by the caller.
+    unsafe { Arc::decrement_strong_count(head as *const T) }
+}
+
 #[repr(transparent)]
 struct MemoryRegistry {
     inner: Table<NonZeroUsize, Weak<MemorySlice>, RandomState>,
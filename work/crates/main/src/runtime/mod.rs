@@ -33,16 +33,23 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 mod borrow;
+mod budget;
+mod builder;
 mod cell;
 mod coercion;
+mod entries;
 mod error;
+mod finalizer;
 mod hints;
 mod ident;
 mod invoke;
+mod iterator;
 mod memory;
 mod object;
 mod origin;
 mod package;
+mod provenance;
+mod schema;
 mod ty;
 
 // This module is hidden.
@@ -113,15 +120,41 @@ pub mod __intrinsics;
 /// The Script Engine will panic if there are conflicts between export points.
 pub mod ops;
 
+pub use ad_astra_export::FromScriptStruct;
+
 pub use crate::runtime::{
+    borrow::{borrow_deadline, set_borrow_deadline},
+    builder::{ScriptArrayBuilder, ScriptStructBuilder},
     cell::Cell,
     coercion::{Downcast, Either, Provider, TypeMatch, Upcast},
+    entries::{remove_entry_provider, set_entry_provider},
     error::{NumberCastCause, NumericOperationKind, RuntimeError, RuntimeResult, RuntimeResultExt},
+    finalizer::{remove_finalizer, set_finalizer},
     hints::{ComponentHint, TypeHint},
     ident::{Ident, RustIdent, ScriptIdent},
-    invoke::{Arg, InvocationMeta, Param},
+    invoke::{
+        Arg,
+        Contract,
+        ContractExpr,
+        ContractKind,
+        ContractOp,
+        ContractValue,
+        InvocationMeta,
+        Param,
+    },
+    iterator::ScriptIterator,
     object::{Object, Prototype},
     origin::{Origin, RustCode, RustOrigin, ScriptOrigin},
-    package::{PackageMeta, ScriptPackage},
+    package::{CompositionError, NameConflict, PackageMeta, ScriptPackage, ValidationError},
+    provenance::ScriptProvenance,
+    schema::{FieldSchema, FromScriptStruct, ScriptStructFields, StructSchema},
     ty::{ScriptType, TypeFamily, TypeMeta},
 };
+pub(crate) use crate::runtime::{
+    budget::BudgetScope,
+    entries::provide_entry,
+    finalizer::is_finalizing,
+    invoke::{arrange_args, ArgNameError},
+    object::name_conflicts,
+    provenance::TextHasher,
+};
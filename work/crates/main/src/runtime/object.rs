@@ -44,20 +44,10 @@ use ahash::AHashMap;
 use lady_deirdre::sync::Lazy;
 
 use crate::{
+    exports::{Builtins, Struct, BUILTINS},
+    interpret::{ScriptEnv, ScriptEnvType, ENV_IDENT},
     report::{debug_unreachable, system_panic},
     runtime::{
-        ops::OperatorKind,
-        Arg,
-        Cell,
-        ComponentHint,
-        Ident,
-        InvocationMeta,
-        Origin,
-        RuntimeError,
-        RuntimeResult,
-        ScriptType,
-        TypeHint,
-        TypeMeta,
         __intrinsics::{
             AddAssignOperator,
             AddOperator,
@@ -73,6 +63,7 @@ use crate::{
             CloneOperator,
             ComponentDeclaration,
             ConcatOperator,
+            ConstDeclaration,
             DebugOperator,
             DeclarationGroup,
             DefaultOperator,
@@ -83,6 +74,7 @@ use crate::{
             FieldOperator,
             HashOperator,
             InvocationOperator,
+            IterateOperator,
             MulAssignOperator,
             MulOperator,
             NegOperator,
@@ -102,6 +94,21 @@ use crate::{
             SubAssignOperator,
             SubOperator,
         },
+        ops::OperatorKind,
+        Arg,
+        Cell,
+        ComponentHint,
+        Ident,
+        InvocationMeta,
+        NameConflict,
+        Origin,
+        RuntimeError,
+        RuntimeResult,
+        RustOrigin,
+        ScriptType,
+        TypeFamily,
+        TypeHint,
+        TypeMeta,
     },
 };
 
@@ -169,20 +176,27 @@ impl Object {
     pub fn component(self, origin: Origin, lhs: Origin, rhs: Ident) -> RuntimeResult<Cell> {
         let key = rhs.as_ref();
 
-        let Some(component) = self.prototype.components.get(key) else {
-            return Err(RuntimeError::UnknownField {
-                access_origin: origin,
-                receiver_origin: self.receiver.origin(),
-                receiver_type: self.ty,
-                field: String::from(key),
-            });
-        };
+        if let Some(component) = self.prototype.components.get(key) {
+            return (component.constructor)(origin, self.arg(lhs));
+        }
+
+        if let Some(constant) = self.prototype.constants.get(key) {
+            return constant.value.give(origin);
+        }
 
-        (component.constructor)(origin, self.arg(lhs))
+        Err(RuntimeError::UnknownField {
+            access_origin: origin,
+            receiver_origin: self.receiver.origin(),
+            receiver_type: self.ty,
+            field: String::from(key),
+        })
     }
 
     /// Similar to [Object::component], but if the Object's type does not have a
     /// component with the specified name, it falls back to [Object::field].
+    ///
+    /// The entries of script structs take precedence over the struct's
+    /// built-in methods with the same names.
     #[inline(always)]
     pub fn component_or_field(
         self,
@@ -192,10 +206,18 @@ impl Object {
     ) -> RuntimeResult<Cell> {
         let key = rhs.as_ref();
 
+        if let Some(entry) = Struct::entry(&self.receiver, lhs, key)? {
+            return Ok(entry);
+        }
+
         if let Some(component) = self.prototype.components.get(key) {
             return (component.constructor)(origin, self.arg(lhs));
         };
 
+        if let Some(constant) = self.prototype.constants.get(key) {
+            return constant.value.give(origin);
+        };
+
         if let Some(operator) = &self.prototype.field {
             return (operator.invoke)(origin, self.arg(lhs), rhs);
         };
@@ -208,6 +230,74 @@ impl Object {
         })
     }
 
+    // Returns true if the Object's type exports a property component
+    // (`#[export(getter)]`) with the specified name.
+    #[inline(always)]
+    pub(crate) fn is_property(&self, key: &str) -> bool {
+        let Some(component) = self.prototype.components.get(key) else {
+            return false;
+        };
+
+        component.property
+    }
+
+    // Returns true if the write observers should be notified about the
+    // assignments to the component with the specified name: the component is
+    // not a property (e.g., a struct field), or it is a property with an
+    // observed setter (`#[export(observed)]`).
+    #[inline(always)]
+    pub(crate) fn is_observed_component(&self, key: &str) -> bool {
+        let Some(component) = self.prototype.components.get(key) else {
+            return false;
+        };
+
+        !component.property || component.observed
+    }
+
+    // Returns true if the Object's type exports a method component (a
+    // component of the function type that is not a property) with
+    // the specified name.
+    #[inline(always)]
+    pub(crate) fn is_method(&self, key: &str) -> bool {
+        let Some(component) = self.prototype.components.get(key) else {
+            return false;
+        };
+
+        !component.property && component.hint.is_fn()
+    }
+
+    // Assigns the rhs value to the property component of this Object through
+    // the property's setter function (`#[export(setter)]`).
+    //
+    // Returns a ReadOnly error if the property does not have a setter.
+    pub(crate) fn assign_property(
+        self,
+        origin: Origin,
+        lhs: Origin,
+        rhs: Ident,
+        value: Arg,
+    ) -> RuntimeResult<()> {
+        let key = rhs.as_ref();
+
+        let Some(component) = self.prototype.components.get(key) else {
+            return Err(RuntimeError::UnknownField {
+                access_origin: origin,
+                receiver_origin: self.receiver.origin(),
+                receiver_type: self.ty,
+                field: String::from(key),
+            });
+        };
+
+        let Some(setter) = component.setter else {
+            return Err(RuntimeError::ReadOnly {
+                access_origin: origin,
+                data_origin: rhs.origin(),
+            });
+        };
+
+        setter(origin, self.arg(lhs), value)
+    }
+
     /// Returns a Cell that points to a field resolved at runtime, such as
     /// `foo.bar`.
     ///
@@ -506,12 +596,7 @@ impl Object {
     #[inline]
     pub fn invoke(self, origin: Origin, lhs: Origin, arguments: &mut [Arg]) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.invocation else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Invocation,
-            });
+            return self.overload(origin, lhs, OperatorKind::Invocation, arguments);
         };
 
         (operator.invoke)(origin, self.arg(lhs), arguments)
@@ -554,6 +639,35 @@ impl Object {
         (operator.invoke)(origin, self.arg(lhs), rhs)
     }
 
+    /// Calls an iteration operator on this Object: `for item in foo {}`.
+    ///
+    /// The operator returns a Cell that holds a
+    /// [ScriptIterator](crate::runtime::ScriptIterator) object through which
+    /// the script `for` loop iterates.
+    ///
+    /// The `origin` parameter specifies the Rust or Script source code range
+    /// that spans the iterated expression.
+    ///
+    /// The `lhs` parameter specifies the Rust or Script source code range
+    /// that spans the operand (this Object).
+    ///
+    /// The function returns a [RuntimeError] if the Object's type does not
+    /// support the ["iterate" operator](Prototype::implements_iterate) or if
+    /// the operator's implementation returns a RuntimeError.
+    #[inline]
+    pub fn iterate(self, origin: Origin, lhs: Origin) -> RuntimeResult<Cell> {
+        let Some(operator) = &self.prototype.iterate else {
+            return Err(RuntimeError::UndefinedOperator {
+                access_origin: origin,
+                receiver_origin: Some(self.receiver.origin()),
+                receiver_type: self.ty,
+                operator: OperatorKind::Iterate,
+            });
+        };
+
+        (operator.invoke)(origin, self.arg(lhs))
+    }
+
     /// Calls an addition operator (`lhs + rhs`) on this Object as the
     /// left-hand side (LHS) of the operation.
     ///
@@ -572,12 +686,7 @@ impl Object {
     #[inline]
     pub fn add(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.add else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Add,
-            });
+            return self.overload(origin, lhs, OperatorKind::Add, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -665,12 +774,7 @@ impl Object {
     #[inline]
     pub fn sub(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.sub else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Sub,
-            });
+            return self.overload(origin, lhs, OperatorKind::Sub, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -758,12 +862,7 @@ impl Object {
     #[inline]
     pub fn mul(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.mul else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Mul,
-            });
+            return self.overload(origin, lhs, OperatorKind::Mul, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -851,12 +950,7 @@ impl Object {
     #[inline]
     pub fn div(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.div else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Div,
-            });
+            return self.overload(origin, lhs, OperatorKind::Div, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -998,12 +1092,7 @@ impl Object {
     #[inline]
     pub fn not(self, origin: Origin, rhs: Origin) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.not else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Not,
-            });
+            return self.overload(origin, rhs, OperatorKind::Not, &mut []);
         };
 
         (operator.invoke)(origin, self.arg(rhs))
@@ -1023,12 +1112,7 @@ impl Object {
     #[inline]
     pub fn neg(self, origin: Origin, rhs: Origin) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.neg else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Neg,
-            });
+            return self.overload(origin, rhs, OperatorKind::Neg, &mut []);
         };
 
         (operator.invoke)(origin, self.arg(rhs))
@@ -1052,12 +1136,7 @@ impl Object {
     #[inline]
     pub fn bit_and(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.bit_and else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::BitAnd,
-            });
+            return self.overload(origin, lhs, OperatorKind::BitAnd, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -1153,12 +1232,7 @@ impl Object {
     #[inline]
     pub fn bit_or(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.bit_or else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::BitOr,
-            });
+            return self.overload(origin, lhs, OperatorKind::BitOr, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -1253,12 +1327,7 @@ impl Object {
     #[inline]
     pub fn bit_xor(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.bit_xor else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::BitXor,
-            });
+            return self.overload(origin, lhs, OperatorKind::BitXor, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -1355,12 +1424,7 @@ impl Object {
     #[inline]
     pub fn shl(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.shl else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Shl,
-            });
+            return self.overload(origin, lhs, OperatorKind::Shl, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -1448,12 +1512,7 @@ impl Object {
     #[inline]
     pub fn shr(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.shr else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Shr,
-            });
+            return self.overload(origin, lhs, OperatorKind::Shr, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -1541,12 +1600,7 @@ impl Object {
     #[inline]
     pub fn rem(self, origin: Origin, lhs: Origin, rhs: Arg) -> RuntimeResult<Cell> {
         let Some(operator) = &self.prototype.rem else {
-            return Err(RuntimeError::UndefinedOperator {
-                access_origin: origin,
-                receiver_origin: Some(self.receiver.origin()),
-                receiver_type: self.ty,
-                operator: OperatorKind::Rem,
-            });
+            return self.overload(origin, lhs, OperatorKind::Rem, &mut [rhs]);
         };
 
         (operator.invoke)(origin, self.arg(lhs), rhs)
@@ -1650,6 +1704,33 @@ impl Object {
             data: self.receiver,
         }
     }
+
+    // Calls the script structure entry that overloads the operator (see
+    // OperatorKind::entry_name). If there is no such entry, returns
+    // an UndefinedOperator error.
+    fn overload(
+        self,
+        origin: Origin,
+        lhs: Origin,
+        operator: OperatorKind,
+        arguments: &mut [Arg],
+    ) -> RuntimeResult<Cell> {
+        let receiver_origin = self.receiver.origin();
+        let receiver_type = self.ty;
+
+        if let Some(name) = operator.entry_name() {
+            if let Some(result) = Struct::overload(origin, &self.arg(lhs), name, arguments) {
+                return result;
+            }
+        }
+
+        Err(RuntimeError::UndefinedOperator {
+            access_origin: origin,
+            receiver_origin: Some(receiver_origin),
+            receiver_type,
+            operator,
+        })
+    }
 }
 
 impl Cell {
@@ -1681,6 +1762,8 @@ impl Cell {
 #[derive(Default)]
 pub struct Prototype {
     components: AHashMap<&'static str, ComponentDeclaration>,
+    constants: AHashMap<&'static str, &'static ConstDeclaration>,
+    order: AHashMap<&'static str, usize>,
     assign: Option<AssignOperator>,
     concat: Option<ConcatOperator>,
     field: Option<FieldOperator>,
@@ -1694,6 +1777,7 @@ pub struct Prototype {
     hash: Option<HashOperator>,
     invocation: Option<InvocationOperator>,
     binding: Option<BindingOperator>,
+    iterate: Option<IterateOperator>,
     add: Option<AddOperator>,
     add_assign: Option<AddAssignOperator>,
     sub: Option<SubOperator>,
@@ -1737,10 +1821,80 @@ impl Display for Prototype {
             debug_map.entry(&component.name.string, &format_args!("{}", component.hint));
         }
 
+        for constant in self.constants.values() {
+            debug_map.entry(
+                &constant.name.string,
+                &format_args!("{}", constant.value.hint()),
+            );
+        }
+
         debug_map.finish()
     }
 }
 
+// Generates the Prototype's operator registration functions from the table of
+// OperatorDeclaration variants and the corresponding Prototype fields.
+//
+// The None marker is not an operator, so it is handled separately.
+macro_rules! operator_slots {
+    ($($variant:ident => $field:ident,)*) => {
+        // Registers the operator declared for the type (or the type family)
+        // described by the `owner` parameter.
+        //
+        // Panics if the operator has already been declared.
+        fn declare_operator(&mut self, owner: &dyn Display, operator: OperatorDeclaration) {
+            match operator {
+                $(
+                    OperatorDeclaration::$variant(operator) => {
+                        if let Some(previous) = &self.$field {
+                            let previous = previous.origin;
+
+                            operator.origin.blame(&format!(
+                                "Duplicate {owner} \
+                                {} operator declaration. The same \
+                                operator already declared in {previous}.",
+                                stringify!($variant),
+                            ))
+                        }
+
+                        self.$field = Some(operator);
+                    }
+                )*
+
+                OperatorDeclaration::None(operator) => {
+                    if let Some(previous) = &self.none {
+                        let previous = previous.origin;
+
+                        operator.origin.blame(&format!(
+                            "Duplicate {owner} \
+                            None marker declaration. The same \
+                            marker already declared in {previous}.",
+                        ))
+                    }
+
+                    self.none = Some(operator);
+                }
+            }
+        }
+
+        // Registers the operator inherited by the type from its type family,
+        // unless the type declares the same operator itself.
+        fn inherit_operator(&mut self, operator: OperatorDeclaration) {
+            match operator {
+                $(
+                    OperatorDeclaration::$variant(operator) => {
+                        let _ = self.$field.get_or_insert(operator);
+                    }
+                )*
+
+                OperatorDeclaration::None(operator) => {
+                    let _ = self.none.get_or_insert(operator);
+                }
+            }
+        }
+    };
+}
+
 impl Prototype {
     /// Returns true if the underlying type has a component (a method or a
     /// field) with the specified `name`.
@@ -1752,7 +1906,7 @@ impl Prototype {
     /// attribute.
     #[inline(always)]
     pub fn implements_component(&self, name: &str) -> bool {
-        self.components.contains_key(name)
+        self.components.contains_key(name) || self.constants.contains_key(name)
     }
 
     /// Returns true if the underlying type has a dynamic field resolver.
@@ -1917,6 +2071,19 @@ impl Prototype {
         self.binding.is_some()
     }
 
+    /// Returns true if the underlying type supports an iteration operator:
+    /// `for item in foo {}`.
+    ///
+    /// If this function returns true, the [Object::iterate] operator is
+    /// generally supported.
+    ///
+    /// The iteration operators are exposed using the
+    /// [ScriptIterate](crate::runtime::ops::ScriptIterate) trait.
+    #[inline(always)]
+    pub fn implements_iterate(&self) -> bool {
+        self.iterate.is_some()
+    }
+
     /// Returns true if the underlying type supports an addition operator:
     /// `lhs + rhs`.
     ///
@@ -2290,11 +2457,36 @@ impl Prototype {
     /// component, the function returns None.
     #[inline(always)]
     pub fn hint_component(&self, name: impl AsRef<str>) -> Option<ComponentHint> {
-        if let Some(component) = self.components.get(name.as_ref()) {
+        let name = name.as_ref();
+        let index = self.order.get(name).copied().unwrap_or_default();
+
+        if let Some(component) = self.components.get(name) {
             return Some(ComponentHint {
                 name: component.name,
                 ty: TypeHint::Type(component.hint),
                 doc: component.doc,
+                owned: component.owned,
+                pure: component.pure,
+                property: component.property,
+                writable: !component.property || component.setter.is_some(),
+                deprecated: component.deprecated,
+                hidden: component.hidden,
+                index,
+            });
+        }
+
+        if let Some(constant) = self.constants.get(name) {
+            return Some(ComponentHint {
+                name: &constant.name,
+                ty: TypeHint::Type(constant.value.hint()),
+                doc: constant.doc,
+                owned: true,
+                pure: false,
+                property: false,
+                writable: true,
+                deprecated: None,
+                hidden: false,
+                index,
             });
         }
 
@@ -2303,21 +2495,75 @@ impl Prototype {
 
     /// Enumerates all exported components of this type (e.g., all Rust struct
     /// methods and fields). The iterator yields descriptions for each
-    /// component.
+    /// component in the order of their registration
+    /// (see [ComponentHint::index]).
     #[inline(always)]
     pub fn hint_all_components(&self) -> impl Iterator<Item = ComponentHint> + '_ {
-        self.components.values().map(|component| ComponentHint {
-            name: component.name,
-            ty: TypeHint::Type(component.hint),
-            doc: component.doc,
-        })
+        let mut components = self
+            .components
+            .keys()
+            .chain(self.constants.keys())
+            .filter_map(|name| self.hint_component(name))
+            .collect::<Vec<_>>();
+
+        components.sort_by_key(|component| component.index);
+
+        components.into_iter()
     }
 
     /// Returns the number of all known exported components of this type (e.g.,
     /// the number of all Rust struct methods and fields).
     #[inline(always)]
     pub fn components_len(&self) -> usize {
-        self.components.len()
+        self.components.len() + self.constants.len()
+    }
+
+    #[inline(always)]
+    fn register_order(&mut self, name: &'static str) {
+        let index = self.order.len();
+
+        let _ = self.order.entry(name).or_insert(index);
+    }
+
+    operator_slots! {
+        Assign => assign,
+        Concat => concat,
+        Field => field,
+        Clone => clone,
+        Debug => debug,
+        Display => display,
+        PartialEq => partial_eq,
+        Default => default,
+        PartialOrd => partial_ord,
+        Ord => ord,
+        Hash => hash,
+        Invocation => invocation,
+        Binding => binding,
+        Iterate => iterate,
+        Add => add,
+        AddAssign => add_assign,
+        Sub => sub,
+        SubAssign => sub_assign,
+        Mul => mul,
+        MulAssign => mul_assign,
+        Div => div,
+        DivAssign => div_assign,
+        And => and,
+        Or => or,
+        Not => not,
+        Neg => neg,
+        BitAnd => bit_and,
+        BitAndAssign => bit_and_assign,
+        BitOr => bit_or,
+        BitOrAssign => bit_or_assign,
+        BitXor => bit_xor,
+        BitXorAssign => bit_xor_assign,
+        Shl => shl,
+        ShlAssign => shl_assign,
+        Shr => shr,
+        ShrAssign => shr_assign,
+        Rem => rem,
+        RemAssign => rem_assign,
     }
 
     /// Returns the type of the result of objects concatenations:
@@ -2388,6 +2634,24 @@ impl Prototype {
         None
     }
 
+    /// Returns the type of the items of the iteration operator:
+    /// `for item in foo {}`.
+    ///
+    /// The returned type metadata corresponds to the
+    /// [ScriptIterate::Item](crate::runtime::ops::ScriptIterate::Item)
+    /// associated type.
+    ///
+    /// If the iteration operator is not
+    /// [supported](Self::implements_iterate), the function returns None.
+    #[inline(always)]
+    pub fn hint_iterate_item(&self) -> Option<&'static TypeMeta> {
+        if let Some(operator) = &self.iterate {
+            return Some(operator.hint_item);
+        }
+
+        None
+    }
+
     /// Returns the right-hand side type of the addition operator:
     /// `lhs + rhs`.
     ///
@@ -3174,8 +3438,17 @@ impl TypeMeta {
     }
 }
 
+// Returns the component declarations that have been skipped during
+// the registration of the type prototypes because the type already has
+// a component with the same name.
+#[inline(always)]
+pub(crate) fn name_conflicts() -> &'static [NameConflict] {
+    &PrototypeRegistry::get().conflicts
+}
+
 struct PrototypeRegistry {
     prototypes: AHashMap<TypeId, Prototype>,
+    conflicts: Vec<NameConflict>,
 }
 
 impl PrototypeRegistry {
@@ -3186,11 +3459,31 @@ impl PrototypeRegistry {
                 .map(|id| (*id, Prototype::default()))
                 .collect::<AHashMap<TypeId, _>>();
 
+            let mut conflicts = Vec::new();
+
+            // The operators exported for the whole type families. The family
+            // prototypes are used to detect the conflicts between the family
+            // operators only.
+            let mut family_prototypes = AHashMap::<&'static TypeFamily, Prototype>::new();
+            let mut family_declarations = Vec::new();
+
             for group in DeclarationGroup::enumerate() {
                 let origin = group.origin;
 
-                for declaration in &group.prototypes {
-                    let declaration = declaration();
+                for constructor in &group.prototypes {
+                    let declaration = constructor();
+
+                    if let Some(family) = declaration.family {
+                        let prototype = family_prototypes.entry(family).or_default();
+
+                        for operator in declaration.operators {
+                            prototype.declare_operator(family, operator);
+                        }
+
+                        family_declarations.push((origin, family, *constructor));
+
+                        continue;
+                    }
 
                     let type_meta = match TypeMeta::by_id(&declaration.receiver) {
                         Some(meta) => meta,
@@ -3198,37 +3491,7 @@ impl PrototypeRegistry {
                         None => origin.blame("Unregistered TypeMeta."),
                     };
 
-                    let type_meta_package = type_meta.origin().package;
-
-                    let origin_package = match origin.package {
-                        Some(package) => package,
-                        None => {
-                            system_panic!("DeclarationGroup origin without package.")
-                        }
-                    };
-
-                    match type_meta_package {
-                        Some(type_meta_package) => {
-                            if type_meta_package != origin_package {
-                                origin.blame(&format!(
-                                    "Type {} declared in the package \
-                                    {}@{}. Type semantics can not be extended \
-                                    from the foreign crate {}@{}.",
-                                    type_meta,
-                                    type_meta_package.0,
-                                    type_meta_package.1,
-                                    origin_package.0,
-                                    origin_package.1,
-                                ))
-                            }
-                        }
-
-                        None => origin.blame(&format!(
-                            "Built-in type {} can not be extended from the \
-                                foreign crate {}@{}.",
-                            type_meta, origin_package.0, origin_package.1,
-                        )),
-                    }
+                    PrototypeRegistry::check_extension(origin, type_meta);
 
                     let prototype = match prototypes.get_mut(&declaration.receiver) {
                         Some(prototype) => prototype,
@@ -3244,563 +3507,208 @@ impl PrototypeRegistry {
                     for component in declaration.components {
                         let name = component.name.string;
 
+                        // Each item exported into the nested namespace links
+                        // the namespace to its parent on its own.
                         if let Some(previous) = prototype.components.get(name) {
-                            let previous = previous.name.origin;
+                            if component.hint.family().is_namespace()
+                                && previous.hint.id() == component.hint.id()
+                            {
+                                continue;
+                            }
+                        }
 
-                            component.name.origin.blame(&format!(
-                                "Duplicate \"{type_meta}.{name}\" component \
-                                declaration. The same component already \
-                                declared in {previous}.",
-                            ))
+                        let previous = match prototype.components.get(name) {
+                            Some(previous) => Some(previous.name.origin),
+                            None => prototype
+                                .constants
+                                .get(name)
+                                .map(|previous| previous.name.origin),
+                        };
+
+                        if let Some(previous) = previous {
+                            conflicts.push(NameConflict {
+                                ty: type_meta,
+                                name,
+                                first: previous,
+                                second: component.name.origin,
+                            });
+
+                            continue;
                         }
 
                         if prototype.components.insert(name, component).is_some() {
                             // Safety: Uniqueness checked above.
                             unsafe { debug_unreachable!("Duplicate component entry.") };
                         }
-                    }
-
-                    for operator in declaration.operators {
-                        match operator {
-                            OperatorDeclaration::Assign(operator) => {
-                                if let Some(previous) = &prototype.assign {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Assign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.assign = Some(operator);
-                            }
-
-                            OperatorDeclaration::Concat(operator) => {
-                                if let Some(previous) = &prototype.concat {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Concat operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.concat = Some(operator);
-                            }
-
-                            OperatorDeclaration::Field(operator) => {
-                                if let Some(previous) = &prototype.field {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Field operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.field = Some(operator);
-                            }
-
-                            OperatorDeclaration::Clone(operator) => {
-                                if let Some(previous) = &prototype.clone {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Clone operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.clone = Some(operator);
-                            }
-
-                            OperatorDeclaration::Debug(operator) => {
-                                if let Some(previous) = &prototype.debug {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Debug operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.debug = Some(operator);
-                            }
-
-                            OperatorDeclaration::Display(operator) => {
-                                if let Some(previous) = &prototype.display {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Display operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.display = Some(operator);
-                            }
-
-                            OperatorDeclaration::PartialEq(operator) => {
-                                if let Some(previous) = &prototype.partial_eq {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        PartialEq operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.partial_eq = Some(operator);
-                            }
-
-                            OperatorDeclaration::Default(operator) => {
-                                if let Some(previous) = &prototype.default {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Default operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.default = Some(operator);
-                            }
-
-                            OperatorDeclaration::PartialOrd(operator) => {
-                                if let Some(previous) = &prototype.partial_ord {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        PartialOrd operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.partial_ord = Some(operator);
-                            }
-
-                            OperatorDeclaration::Ord(operator) => {
-                                if let Some(previous) = &prototype.ord {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Ord operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.ord = Some(operator);
-                            }
-
-                            OperatorDeclaration::Hash(operator) => {
-                                if let Some(previous) = &prototype.hash {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Hash operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.hash = Some(operator);
-                            }
-
-                            OperatorDeclaration::Invocation(operator) => {
-                                if let Some(previous) = &prototype.invocation {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Invocation operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.invocation = Some(operator);
-                            }
-
-                            OperatorDeclaration::Binding(operator) => {
-                                if let Some(previous) = &prototype.binding {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Binding operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.binding = Some(operator);
-                            }
-
-                            OperatorDeclaration::Add(operator) => {
-                                if let Some(previous) = &prototype.add {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Add operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.add = Some(operator);
-                            }
-
-                            OperatorDeclaration::AddAssign(operator) => {
-                                if let Some(previous) = &prototype.add_assign {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        AddAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.add_assign = Some(operator);
-                            }
-
-                            OperatorDeclaration::Sub(operator) => {
-                                if let Some(previous) = &prototype.sub {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Sub operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
 
-                                prototype.sub = Some(operator);
-                            }
-
-                            OperatorDeclaration::SubAssign(operator) => {
-                                if let Some(previous) = &prototype.sub_assign {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        SubAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.sub_assign = Some(operator);
-                            }
-
-                            OperatorDeclaration::Mul(operator) => {
-                                if let Some(previous) = &prototype.mul {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Mul operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.mul = Some(operator);
-                            }
-
-                            OperatorDeclaration::MulAssign(operator) => {
-                                if let Some(previous) = &prototype.mul_assign {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        MulAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.mul_assign = Some(operator);
-                            }
-
-                            OperatorDeclaration::Div(operator) => {
-                                if let Some(previous) = &prototype.div {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Div operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.div = Some(operator);
-                            }
-
-                            OperatorDeclaration::DivAssign(operator) => {
-                                if let Some(previous) = &prototype.div_assign {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        DivAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.div_assign = Some(operator);
-                            }
-
-                            OperatorDeclaration::And(operator) => {
-                                if let Some(previous) = &prototype.and {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        And operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.and = Some(operator);
-                            }
-
-                            OperatorDeclaration::Or(operator) => {
-                                if let Some(previous) = &prototype.or {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Or operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.or = Some(operator);
-                            }
-
-                            OperatorDeclaration::Not(operator) => {
-                                if let Some(previous) = &prototype.not {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Not operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.not = Some(operator);
-                            }
-
-                            OperatorDeclaration::Neg(operator) => {
-                                if let Some(previous) = &prototype.neg {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Neg operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.neg = Some(operator);
-                            }
-
-                            OperatorDeclaration::BitAnd(operator) => {
-                                if let Some(previous) = &prototype.bit_and {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        BitAnd operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.bit_and = Some(operator);
-                            }
-
-                            OperatorDeclaration::BitAndAssign(operator) => {
-                                if let Some(previous) = &prototype.bit_and_assign {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        BitAndAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.bit_and_assign = Some(operator);
-                            }
-
-                            OperatorDeclaration::BitOr(operator) => {
-                                if let Some(previous) = &prototype.bit_or {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        BitOr operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.bit_or = Some(operator);
-                            }
-
-                            OperatorDeclaration::BitOrAssign(operator) => {
-                                if let Some(previous) = &prototype.bit_or_assign {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        BitOrAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.bit_or_assign = Some(operator);
-                            }
-
-                            OperatorDeclaration::BitXor(operator) => {
-                                if let Some(previous) = &prototype.bit_xor {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        BitXor operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.bit_xor = Some(operator);
-                            }
-
-                            OperatorDeclaration::BitXorAssign(operator) => {
-                                if let Some(previous) = &prototype.bit_xor_assign {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        BitXorAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.bit_xor_assign = Some(operator);
-                            }
-
-                            OperatorDeclaration::Shl(operator) => {
-                                if let Some(previous) = &prototype.shl {
-                                    let previous = previous.origin;
-
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Shl operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
-
-                                prototype.shl = Some(operator);
-                            }
-
-                            OperatorDeclaration::ShlAssign(operator) => {
-                                if let Some(previous) = &prototype.shl_assign {
-                                    let previous = previous.origin;
+                        prototype.register_order(name);
+                    }
 
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        ShlAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
+                    for constant in declaration.constants {
+                        let name = constant.name.string;
+
+                        let previous = match prototype.components.get(name) {
+                            Some(previous) => Some(previous.name.origin),
+                            None => prototype
+                                .constants
+                                .get(name)
+                                .map(|previous| previous.name.origin),
+                        };
+
+                        if let Some(previous) = previous {
+                            conflicts.push(NameConflict {
+                                ty: type_meta,
+                                name,
+                                first: previous,
+                                second: constant.name.origin,
+                            });
+
+                            continue;
+                        }
 
-                                prototype.shl_assign = Some(operator);
-                            }
+                        let _ = prototype.constants.insert(name, constant);
 
-                            OperatorDeclaration::Shr(operator) => {
-                                if let Some(previous) = &prototype.shr {
-                                    let previous = previous.origin;
+                        prototype.register_order(name);
+                    }
 
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Shr operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
+                    for operator in declaration.operators {
+                        prototype.declare_operator(type_meta, operator);
+                    }
+                }
+            }
 
-                                prototype.shr = Some(operator);
-                            }
+            // The family operators are applied after all type-specific
+            // declarations, such that the operators implemented by the types
+            // themselves take precedence.
+            for (origin, family, constructor) in family_declarations {
+                for type_meta in family {
+                    PrototypeRegistry::check_extension(origin, type_meta);
 
-                            OperatorDeclaration::ShrAssign(operator) => {
-                                if let Some(previous) = &prototype.shr_assign {
-                                    let previous = previous.origin;
+                    let Some(prototype) = prototypes.get_mut(type_meta.id()) else {
+                        // Safety: Each TypeMeta has corresponding Prototype.
+                        unsafe { debug_unreachable!("Missing Prototype for registered type.") }
+                    };
 
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        ShrAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
+                    for operator in constructor().operators {
+                        prototype.inherit_operator(operator);
+                    }
+                }
+            }
 
-                                prototype.shr_assign = Some(operator);
-                            }
+            PrototypeRegistry::inject_builtins(&mut prototypes);
 
-                            OperatorDeclaration::Rem(operator) => {
-                                if let Some(previous) = &prototype.rem {
-                                    let previous = previous.origin;
+            PrototypeRegistry {
+                prototypes,
+                conflicts,
+            }
+        });
 
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        Rem operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
+        REGISTRY.deref()
+    }
 
-                                prototype.rem = Some(operator);
-                            }
+    // Panics if the declarations of the `origin` group attempt to extend the
+    // semantics of a type declared in another crate.
+    fn check_extension(origin: &'static RustOrigin, type_meta: &'static TypeMeta) {
+        let type_meta_package = type_meta.origin().package;
 
-                            OperatorDeclaration::RemAssign(operator) => {
-                                if let Some(previous) = &prototype.rem_assign {
-                                    let previous = previous.origin;
+        let origin_package = match origin.package {
+            Some(package) => package,
+            None => {
+                system_panic!("DeclarationGroup origin without package.")
+            }
+        };
 
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        RemAssign operator declaration. The same \
-                                        operator already declared in {previous}.",
-                                    ))
-                                }
+        match type_meta_package {
+            Some(type_meta_package) => {
+                if type_meta_package != origin_package {
+                    origin.blame(&format!(
+                        "Type {} declared in the package \
+                        {}@{}. Type semantics can not be extended \
+                        from the foreign crate {}@{}.",
+                        type_meta,
+                        type_meta_package.0,
+                        type_meta_package.1,
+                        origin_package.0,
+                        origin_package.1,
+                    ))
+                }
+            }
 
-                                prototype.rem_assign = Some(operator);
-                            }
+            None => origin.blame(&format!(
+                "Built-in type {} can not be extended from the \
+                    foreign crate {}@{}.",
+                type_meta, origin_package.0, origin_package.1,
+            )),
+        }
+    }
 
-                            OperatorDeclaration::None(operator) => {
-                                if let Some(previous) = &prototype.none {
-                                    let previous = previous.origin;
+    // Copies the built-in functions and the `env` variable into each
+    // package's namespace, unless the package declares a component with the
+    // same name.
+    fn inject_builtins(prototypes: &mut AHashMap<TypeId, Prototype>) {
+        let Some(builtins) = prototypes.get(&TypeId::of::<Builtins>()) else {
+            return;
+        };
 
-                                    operator.origin.blame(&format!(
-                                        "Duplicate {type_meta} \
-                                        None marker declaration. The same \
-                                        marker already declared in {previous}.",
-                                    ))
-                                }
+        let mut builtins = BUILTINS
+            .iter()
+            .filter_map(|(name, constructor)| {
+                let component = builtins.components.get(name)?;
+
+                Some((
+                    *name,
+                    component.name,
+                    *constructor,
+                    component.hint,
+                    component.doc,
+                    component.pure,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        builtins.push((
+            ENV_IDENT.string,
+            &ENV_IDENT,
+            ScriptEnv::component,
+            ScriptEnvType::type_meta(),
+            Some("The host-defined environment of the current script evaluation."),
+            false,
+        ));
+
+        for (id, prototype) in prototypes {
+            let Some(type_meta) = TypeMeta::by_id(id) else {
+                continue;
+            };
 
-                                prototype.none = Some(operator);
-                            }
-                        }
-                    }
-                }
+            if !type_meta.family().is_package() {
+                continue;
             }
 
-            PrototypeRegistry { prototypes }
-        });
+            for (name, ident, constructor, hint, doc, pure) in &builtins {
+                if prototype.components.contains_key(name) || prototype.constants.contains_key(name)
+                {
+                    continue;
+                }
 
-        REGISTRY.deref()
+                let _ = prototype.components.insert(
+                    name,
+                    ComponentDeclaration {
+                        name: ident,
+                        constructor: *constructor,
+                        hint,
+                        doc: *doc,
+                        owned: true,
+                        pure: *pure,
+                        property: false,
+                        setter: None,
+                        deprecated: None,
+                        hidden: false,
+                        observed: false,
+                    },
+                );
+
+                prototype.register_order(name);
+            }
+        }
     }
 }
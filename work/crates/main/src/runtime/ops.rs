@@ -40,6 +40,9 @@ use std::{
 
 pub(crate) use crate::runtime::ops::functions::{
     Fn0Repr,
+    Fn10Repr,
+    Fn11Repr,
+    Fn12Repr,
     Fn1Repr,
     Fn2Repr,
     Fn3Repr,
@@ -47,10 +50,12 @@ pub(crate) use crate::runtime::ops::functions::{
     Fn5Repr,
     Fn6Repr,
     Fn7Repr,
+    Fn8Repr,
+    Fn9Repr,
 };
 pub use crate::runtime::ops::{
-    functions::{Fn0, Fn1, Fn2, Fn3, Fn4, Fn5, Fn6, Fn7},
-    types::{DynamicArgument, DynamicReturn, DynamicType},
+    functions::{Fn0, Fn1, Fn10, Fn11, Fn12, Fn2, Fn3, Fn4, Fn5, Fn6, Fn7, Fn8, Fn9},
+    types::{DynamicArgument, DynamicReturn, DynamicType, VariadicArguments},
 };
 use crate::runtime::{Arg, Cell, Ident, InvocationMeta, Origin, RuntimeResult};
 
@@ -309,6 +314,44 @@ pub trait ScriptBinding {
     fn script_binding(origin: Origin, lhs: Arg, rhs: Arg) -> RuntimeResult<()>;
 }
 
+/// A script iteration operator: `for item in foo {}`.
+///
+/// Implementing this trait enables the
+/// [Object::iterate](crate::runtime::Object::iterate) operation, and the
+/// script `for` loop iterates through the items of the object.
+///
+/// The export macro implements this trait automatically for the exported
+/// [IntoIterator] and [Iterator] implementations:
+///
+/// ```ignore
+/// #[export]
+/// impl IntoIterator for Tileset {
+///     type Item = Tile;
+///     type IntoIter = IntoIter<Tile>;
+///
+///     fn into_iter(self) -> Self::IntoIter {
+///         self.tiles.into_iter()
+///     }
+/// }
+/// ```
+///
+/// The trait must be implemented for the
+/// [registered type](crate::runtime::ScriptType), and the implementation must
+/// be exported using the [export](crate::export) macro. For more details, see
+/// the [module documentation](crate::runtime::ops).
+pub trait ScriptIterate {
+    /// A rough estimation of the type of the iteration items.
+    ///
+    /// This type must implement [ScriptType](crate::runtime::ScriptType).
+    type Item: ?Sized;
+
+    /// Operation implementation.
+    ///
+    /// The parameters and return type of this function correspond to those of
+    /// the [Object::iterate](crate::runtime::Object::iterate) function.
+    fn script_iterate(origin: Origin, lhs: Arg) -> RuntimeResult<Cell>;
+}
+
 /// A script addition operator: `lhs + rhs`.
 ///
 /// Implementing this trait enables the
@@ -956,6 +999,9 @@ pub enum OperatorKind {
     /// A context binding operator.
     Binding,
 
+    /// An iteration operator: `for item in foo {}`.
+    Iterate,
+
     /// An addition operator: `lhs + rhs`.
     Add,
 
@@ -1045,6 +1091,7 @@ impl Display for OperatorKind {
             Self::Hash => formatter.write_str("hash interface"),
             Self::Invocation => formatter.write_str("invocation"),
             Self::Binding => formatter.write_str("binding"),
+            Self::Iterate => formatter.write_str("iteration"),
             Self::Add => formatter.write_str("+ operator"),
             Self::AddAssign => formatter.write_str("+= operator"),
             Self::Sub => formatter.write_str("- operator"),
@@ -1073,8 +1120,53 @@ impl Display for OperatorKind {
     }
 }
 
+impl OperatorKind {
+    /// Returns the name of the script structure entry that overloads this
+    /// operator, or None if the operator cannot be overloaded in scripts.
+    ///
+    /// When the script applies an operator to a `struct` object, and the
+    /// struct type does not provide a built-in implementation of the operator,
+    /// the interpreter calls the struct's method with this name instead.
+    /// The method receives the structure as `self` and the right-hand operand
+    /// (if any) as an argument:
+    ///
+    /// ```text
+    /// let a = struct {
+    ///     x: 1,
+    ///     y: 2,
+    ///     __add: fn(rhs) struct { x: self.x + rhs.x, y: self.y + rhs.y },
+    /// };
+    ///
+    /// let sum = a + struct { x: 3, y: 4 };
+    /// ```
+    ///
+    /// The invocation operator's method (`__call`) receives the invocation
+    /// arguments, and the equality operator's method (`__eq`) takes precedence
+    /// over the built-in structural equality of structs. The `!=` operator
+    /// uses the `__eq` method too.
+    pub fn entry_name(&self) -> Option<&'static str> {
+        match self {
+            Self::PartialEq => Some("__eq"),
+            Self::Invocation => Some("__call"),
+            Self::Add => Some("__add"),
+            Self::Sub => Some("__sub"),
+            Self::Mul => Some("__mul"),
+            Self::Div => Some("__div"),
+            Self::Not => Some("__not"),
+            Self::Neg => Some("__neg"),
+            Self::BitAnd => Some("__bit_and"),
+            Self::BitOr => Some("__bit_or"),
+            Self::BitXor => Some("__bit_xor"),
+            Self::Shl => Some("__shl"),
+            Self::Shr => Some("__shr"),
+            Self::Rem => Some("__rem"),
+            _ => None,
+        }
+    }
+}
+
 mod types {
-    use std::marker::PhantomData;
+    use std::{marker::PhantomData, mem::take};
 
     use crate::{
         export,
@@ -1221,6 +1313,94 @@ mod types {
         }
     }
 
+    /// A type of the function's last parameter that collects all remaining
+    /// invocation arguments.
+    ///
+    /// When the [export](crate::export) macro encounters this type in the
+    /// final position of the exported function's parameters, it makes the
+    /// function variadic: the script may call such a function with any number
+    /// of arguments starting from the number of the preceding parameters, and
+    /// the Script Engine passes the remaining arguments to the function
+    /// without [downcasting](Downcast).
+    ///
+    /// The analyzer will assume that the type of each collected argument
+    /// belongs to the [type family](crate::runtime::TypeFamily) of the `T`
+    /// type, and the [InvocationMeta] of the function will have the
+    /// [variadic](InvocationMeta::variadic) flag set.
+    ///
+    /// ```
+    /// # use ad_astra::{
+    /// #     export,
+    /// #     runtime::{ops::VariadicArguments, Downcast, Provider, RuntimeResult},
+    /// # };
+    /// #
+    /// #[export]
+    /// fn sum(first: f64, rest: VariadicArguments<f64>) -> RuntimeResult<f64> {
+    ///     let mut result = first;
+    ///
+    ///     for arg in rest.args {
+    ///         result += <f64>::downcast(arg.origin, Provider::Owned(arg.data))?;
+    ///     }
+    ///
+    ///     Ok(result)
+    /// }
+    /// ```
+    pub struct VariadicArguments<T = DynamicType> {
+        /// The collected arguments in the invocation order.
+        ///
+        /// Each argument includes the Rust or Script source code range where
+        /// the argument has been provided.
+        pub args: Vec<Arg>,
+
+        phantom: PhantomData<T>,
+    }
+
+    impl<T> VariadicArguments<T> {
+        /// Takes all `arguments` of the slice, leaving the slice items in their
+        /// default state.
+        ///
+        /// The exported variadic functions use this function to collect the
+        /// remaining invocation arguments.
+        #[inline(always)]
+        pub fn from_arguments(arguments: &mut [Arg]) -> Self {
+            Self {
+                args: arguments.iter_mut().map(take).collect(),
+                phantom: PhantomData,
+            }
+        }
+
+        /// Returns the number of the collected arguments.
+        #[inline(always)]
+        pub fn len(&self) -> usize {
+            self.args.len()
+        }
+
+        /// Returns true if the function has been invoked without the variadic
+        /// arguments.
+        #[inline(always)]
+        pub fn is_empty(&self) -> bool {
+            self.args.is_empty()
+        }
+    }
+
+    impl<'a, T: ScriptType> Downcast<'a> for VariadicArguments<T> {
+        #[inline(always)]
+        fn downcast(origin: Origin, provider: Provider<'a>) -> RuntimeResult<Self> {
+            Ok(VariadicArguments {
+                args: vec![Arg {
+                    origin,
+                    data: provider.to_owned(),
+                }],
+                phantom: PhantomData,
+            })
+        }
+
+        #[inline(always)]
+        fn hint() -> TypeHint {
+            TypeHint::Type(T::type_meta())
+        }
+    }
+
     /// A type for a function's return value that may have a more dynamic
     /// casting nature than usual.
     ///
@@ -1468,10 +1648,14 @@ mod functions {
                                         None
                                     },
                                     hint: TypeHint::dynamic(),
+                                    schema: None,
+                                    doc: None,
                                 },
                                 )*
                             ]),
                             output: TypeHint::dynamic(),
+                            contracts: Vec::new(),
+                            variadic: false,
                         }
                     });
 
@@ -1528,4 +1712,34 @@ mod functions {
         Fn7;
         Fn7Repr[7] as "fn(7)" => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6;
     );
+
+    impl_fn!(
+        /// A function with 8 arguments.
+        Fn8;
+        Fn8Repr[8] as "fn(8)" => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7;
+    );
+
+    impl_fn!(
+        /// A function with 9 arguments.
+        Fn9;
+        Fn9Repr[9] as "fn(9)" => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8;
+    );
+
+    impl_fn!(
+        /// A function with 10 arguments.
+        Fn10;
+        Fn10Repr[10] as "fn(10)" => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9;
+    );
+
+    impl_fn!(
+        /// A function with 11 arguments.
+        Fn11;
+        Fn11Repr[11] as "fn(11)" => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10;
+    );
+
+    impl_fn!(
+        /// A function with 12 arguments.
+        Fn12;
+        Fn12Repr[12] as "fn(12)" => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11;
+    );
 }
@@ -360,7 +360,7 @@ impl RustOrigin {
 
     /// Returns true if this instance is the [Nil RustOrigin](Self::nil).
     pub fn is_nil(&self) -> bool {
-        self == &NIL_RUST_ORIGIN
+        self.package.is_none() && self.code.is_none()
     }
 
     /// Returns the [script package metadata](PackageMeta) of the crate that
@@ -791,6 +791,14 @@ impl ScriptOrigin {
         self.end = other.end;
     }
 
+    #[inline(always)]
+    pub(crate) fn union_until(&mut self, other: &Self) {
+        self.end = match other.start {
+            Some(entry) => Bound::Excluded(entry),
+            None => Bound::Unbounded,
+        };
+    }
+
     #[inline(always)]
     pub(crate) fn unbound(&mut self) {
         self.end = Bound::Unbounded;
@@ -34,10 +34,13 @@
 
 use std::{
     cmp::Ordering,
+    error::Error as StdError,
     fmt::{Debug, Display, Formatter},
+    future::Future,
     hash::{Hash, Hasher},
     ops::Deref,
-    sync::{RwLock, RwLockReadGuard},
+    pin::Pin,
+    sync::{Arc, RwLock, RwLockReadGuard},
 };
 
 use ahash::{AHashMap, AHashSet, RandomState};
@@ -50,10 +53,11 @@ use semver::{Version, VersionReq};
 use crate::{
     report::debug_unreachable,
     runtime::{
+        __intrinsics::{DeclarationGroup, PackageDeclaration},
+        name_conflicts,
         Cell,
         RustOrigin,
         TypeMeta,
-        __intrinsics::{DeclarationGroup, PackageDeclaration},
     },
 };
 
@@ -144,8 +148,12 @@ pub struct PackageMeta {
     origin: &'static RustOrigin,
     declaration: PackageDeclaration,
     modules: RwLock<AHashSet<Id>>,
+    runner: RwLock<Option<Arc<AsyncRunner>>>,
 }
 
+pub(crate) type AsyncRunner =
+    dyn for<'a> Fn(Pin<&'a mut (dyn Future<Output = ()> + 'a)>) + Send + Sync + 'static;
+
 impl PartialEq for PackageMeta {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
@@ -258,6 +266,7 @@ impl PackageMeta {
             origin,
             declaration,
             modules: RwLock::new(AHashSet::new()),
+            runner: RwLock::new(None),
         }
     }
 
@@ -301,6 +310,64 @@ impl PackageMeta {
         Some(meta)
     }
 
+    /// Installs the executor hook of the package's exported async functions
+    /// (`#[export(block_on)]`), replacing the previously installed hook.
+    ///
+    /// When the script calls such a function, the function creates the
+    /// function's future and passes it to the `runner`, which should drive the
+    /// future to completion on the current thread (e.g., using the `block_on`
+    /// function of the async executor). The script's thread is blocked until
+    /// the runner returns.
+    ///
+    /// If the package does not have a runner, the calls of the exported async
+    /// functions end with the
+    /// [MissingAsyncRunner](crate::runtime::RuntimeError::MissingAsyncRunner)
+    /// error.
+    ///
+    /// While the runner waits for the future, the Virtual Machine keeps
+    /// consulting the thread's
+    /// [runtime hook](crate::interpret::set_runtime_hook) on each poll of the
+    /// future. If the hook interrupts the evaluation, the future is dropped,
+    /// and the call ends with the
+    /// [Interrupted](crate::runtime::RuntimeError::Interrupted) error. To
+    /// make sure the hook is consulted, the future reschedules itself after
+    /// each pending poll when the hook is installed.
+    pub fn set_async_runner(
+        &self,
+        runner: impl for<'a> Fn(Pin<&'a mut (dyn Future<Output = ()> + 'a)>) + Send + Sync + 'static,
+    ) {
+        let runner = Arc::new(runner) as Arc<AsyncRunner>;
+
+        let previous = match self.runner.write() {
+            Ok(mut guard) => guard.replace(runner),
+            Err(poison) => poison.into_inner().replace(runner),
+        };
+
+        drop(previous);
+    }
+
+    /// Removes the executor hook of the package's exported async functions
+    /// previously installed by the [set_async_runner](Self::set_async_runner)
+    /// function.
+    ///
+    /// If the package does not have a runner, this function does nothing.
+    pub fn remove_async_runner(&self) {
+        let previous = match self.runner.write() {
+            Ok(mut guard) => guard.take(),
+            Err(poison) => poison.into_inner().take(),
+        };
+
+        drop(previous);
+    }
+
+    #[inline(always)]
+    pub(crate) fn async_runner(&self) -> Option<Arc<AsyncRunner>> {
+        match self.runner.read() {
+            Ok(guard) => guard.clone(),
+            Err(poison) => poison.into_inner().clone(),
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn by_id(id: Id) -> Option<&'static Self> {
         let registry = ModuleRegistry::get();
@@ -343,6 +410,160 @@ impl PackageMeta {
         self.declaration.instance.deref().ty()
     }
 
+    /// Returns the packages on which this package depends.
+    ///
+    /// The dependencies are declared in the package export attribute:
+    /// `#[export(package, deps(core_crate::Package, other_crate::Package))]`.
+    /// The function returns them in the order of declaration.
+    ///
+    /// The dependency declarations are independent of the crates' dependency
+    /// graph in Cargo. They describe the order in which a composition of
+    /// several packages should be set up. See [PackageMeta::compose] for
+    /// details.
+    pub fn dependencies(&self) -> Vec<&'static PackageMeta> {
+        self.declaration
+            .dependencies
+            .iter()
+            .map(|dependency| dependency())
+            .collect()
+    }
+
+    /// Validates a composition of several packages and orders them according
+    /// to their [dependencies](PackageMeta::dependencies).
+    ///
+    /// In the returned vector, each package appears after all of its
+    /// dependencies. Packages that do not depend on each other retain their
+    /// relative order from the `packages` slice, so the result is
+    /// deterministic for the same input. Duplicate packages are listed once.
+    ///
+    /// The function returns a [CompositionError] if any package depends on
+    /// a package that is missing in the `packages` slice, or if the package
+    /// dependencies form a cycle.
+    ///
+    /// ```
+    /// use ad_astra::{
+    ///     export,
+    ///     runtime::{PackageMeta, ScriptPackage},
+    /// };
+    ///
+    /// #[export(package)]
+    /// #[derive(Default)]
+    /// struct Package;
+    ///
+    /// let order = PackageMeta::compose(&[Package::meta()]).unwrap();
+    ///
+    /// assert_eq!(order, [Package::meta()]);
+    /// ```
+    pub fn compose(
+        packages: &[&'static PackageMeta],
+    ) -> Result<Vec<&'static PackageMeta>, CompositionError> {
+        let members = packages.iter().copied().collect::<AHashSet<_>>();
+
+        let mut missing = Vec::new();
+
+        for package in packages {
+            for dependency in package.dependencies() {
+                if members.contains(dependency) {
+                    continue;
+                }
+
+                let entry = (*package, dependency);
+
+                if !missing.contains(&entry) {
+                    missing.push(entry);
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            return Err(CompositionError::MissingDependencies(missing));
+        }
+
+        let mut order = Vec::with_capacity(members.len());
+        let mut visited = AHashSet::with_capacity(members.len());
+        let mut stack = Vec::new();
+
+        for package in packages {
+            package.visit_dependencies(&mut order, &mut visited, &mut stack)?;
+        }
+
+        Ok(order)
+    }
+
+    fn visit_dependencies(
+        &'static self,
+        order: &mut Vec<&'static PackageMeta>,
+        visited: &mut AHashSet<&'static PackageMeta>,
+        stack: &mut Vec<&'static PackageMeta>,
+    ) -> Result<(), CompositionError> {
+        if visited.contains(self) {
+            return Ok(());
+        }
+
+        if let Some(position) = stack.iter().position(|member| *member == self) {
+            let mut cycle = stack[position..].to_vec();
+
+            cycle.push(self);
+
+            return Err(CompositionError::DependencyCycle(cycle));
+        }
+
+        stack.push(self);
+
+        for dependency in self.dependencies() {
+            dependency.visit_dependencies(order, visited, stack)?;
+        }
+
+        let _ = stack.pop();
+        let _ = visited.insert(self);
+
+        order.push(self);
+
+        Ok(())
+    }
+
+    /// Checks whether the exported Rust items of this package have conflicting
+    /// script names.
+    ///
+    /// The export macro reports most of the duplicate names within a crate
+    /// at compile time. However, some conflicts (e.g., two functions with
+    /// the same Rust name exported from different Rust modules) can only be
+    /// detected when the Script Engine registers the exported items. In this
+    /// case, the Engine keeps the first registered item and ignores
+    /// the others.
+    ///
+    /// The function returns a [ValidationError] that lists the script names
+    /// of the types of this package (including the package's namespace)
+    /// declared more than once, together with the Rust origins of both
+    /// declarations. You can call this function during the application's
+    /// startup to report such issues early.
+    ///
+    /// ```
+    /// use ad_astra::{
+    ///     export,
+    ///     runtime::{PackageMeta, ScriptPackage},
+    /// };
+    ///
+    /// #[export(package)]
+    /// #[derive(Default)]
+    /// struct Package;
+    ///
+    /// assert!(Package::meta().validate().is_ok());
+    /// ```
+    pub fn validate(&'static self) -> Result<(), ValidationError> {
+        let conflicts = name_conflicts()
+            .iter()
+            .filter(|conflict| conflict.ty.origin().package() == Some(self))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !conflicts.is_empty() {
+            return Err(ValidationError::DuplicateNames(conflicts));
+        }
+
+        Ok(())
+    }
+
     /// Returns a smart pointer to the instance of the Rust struct that
     /// represents the [ScriptPackage].
     ///
@@ -407,6 +628,117 @@ impl PackageMeta {
     }
 }
 
+/// An error returned by the [PackageMeta::compose] function.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CompositionError {
+    /// Some packages of the composition depend on packages that are missing in
+    /// the composition.
+    ///
+    /// Each entry of the vector is a pair of the dependent package and its
+    /// missing dependency.
+    MissingDependencies(Vec<(&'static PackageMeta, &'static PackageMeta)>),
+
+    /// The package dependencies form a cycle.
+    ///
+    /// The vector lists the members of the cycle in the order of dependency.
+    /// The first and the last entries of the vector are the same package.
+    DependencyCycle(Vec<&'static PackageMeta>),
+}
+
+impl Display for CompositionError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingDependencies(missing) => {
+                formatter.write_str("Missing package dependencies:")?;
+
+                for (package, dependency) in missing {
+                    formatter
+                        .write_fmt(format_args!("\n  - {package:#} depends on {dependency:#}",))?;
+                }
+
+                Ok(())
+            }
+
+            Self::DependencyCycle(cycle) => {
+                formatter.write_str("Package dependency cycle: ")?;
+
+                let mut first = true;
+
+                for package in cycle {
+                    if !first {
+                        formatter.write_str(" -> ")?;
+                    }
+
+                    first = false;
+
+                    formatter.write_fmt(format_args!("{package:#}"))?;
+                }
+
+                formatter.write_str(".")
+            }
+        }
+    }
+}
+
+impl StdError for CompositionError {}
+
+/// An error returned by the [PackageMeta::validate] function.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// Some script names are declared more than once.
+    DuplicateNames(Vec<NameConflict>),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateNames(conflicts) => {
+                formatter.write_str("Duplicate exported names:")?;
+
+                for conflict in conflicts {
+                    formatter.write_fmt(format_args!("\n  - {conflict}"))?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl StdError for ValidationError {}
+
+/// A description of two exported Rust items that have the same script name.
+///
+/// See [PackageMeta::validate] for details.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NameConflict {
+    /// The type to which both items belong.
+    ///
+    /// For crate-global functions, statics, and constants, this is the type
+    /// of the [ScriptPackage].
+    pub ty: &'static TypeMeta,
+
+    /// The script name of the items.
+    pub name: &'static str,
+
+    /// The Rust origin of the item that the Script Engine has registered.
+    pub first: &'static RustOrigin,
+
+    /// The Rust origin of the item that the Script Engine has ignored.
+    pub second: &'static RustOrigin,
+}
+
+impl Display for NameConflict {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_fmt(format_args!(
+            "\"{}.{}\" is declared in {} and in {}",
+            self.ty, self.name, self.first, self.second,
+        ))
+    }
+}
+
 struct PackageRegistry {
     index: AHashMap<&'static str, AHashMap<Version, PackageMeta>>,
 }
@@ -477,3 +809,143 @@ impl ModuleRegistry {
         &REGISTRY
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::sync::Lazy;
+
+    use crate::{
+        analysis::symbols::tests::TestPackage,
+        runtime::{
+            __intrinsics::PackageDeclaration,
+            Cell,
+            CompositionError,
+            PackageMeta,
+            RustOrigin,
+            ScriptPackage,
+            ValidationError,
+        },
+    };
+
+    macro_rules! package {
+        ($name:ident [$($dep:ident),*]) => {
+            fn $name() -> &'static PackageMeta {
+                static META: Lazy<PackageMeta> = Lazy::new(|| {
+                    PackageMeta::new(
+                        RustOrigin::nil(),
+                        PackageDeclaration {
+                            name: stringify!($name),
+                            version: "1.0.0",
+                            doc: None,
+                            dependencies: vec![$($dep),*],
+                            instance: Lazy::new(Cell::nil),
+                        },
+                    )
+                });
+
+                &META
+            }
+        };
+    }
+
+    package!(core []);
+    package!(util[core]);
+    package!(glue [core, util]);
+    package!(left[right]);
+    package!(right[left]);
+
+    #[test]
+    fn test_package_composition() {
+        assert_eq!(glue().dependencies(), [core(), util()]);
+
+        assert_eq!(
+            PackageMeta::compose(&[glue(), util(), core()]).unwrap(),
+            [core(), util(), glue()],
+        );
+
+        assert_eq!(
+            PackageMeta::compose(&[core(), glue(), core(), util()]).unwrap(),
+            [core(), util(), glue()],
+        );
+
+        let error = PackageMeta::compose(&[glue(), left()]).unwrap_err();
+
+        assert_eq!(
+            error,
+            CompositionError::MissingDependencies(vec![
+                (glue(), core()),
+                (glue(), util()),
+                (left(), right()),
+            ]),
+        );
+
+        assert_eq!(
+            error.to_string(),
+            "Missing package dependencies:\n  - glue@1.0.0 depends on core@1.0.0\n  - glue@1.0.0 \
+            depends on util@1.0.0\n  - left@1.0.0 depends on right@1.0.0",
+        );
+
+        let error = PackageMeta::compose(&[core(), left(), right()]).unwrap_err();
+
+        assert_eq!(
+            error,
+            CompositionError::DependencyCycle(vec![left(), right(), left()]),
+        );
+
+        assert_eq!(
+            error.to_string(),
+            "Package dependency cycle: left@1.0.0 -> right@1.0.0 -> left@1.0.0.",
+        );
+    }
+
+    mod first_twin {
+        use crate::export;
+
+        #[export]
+        pub fn validation_twin() -> usize {
+            1
+        }
+    }
+
+    mod second_twin {
+        use crate::export;
+
+        #[export]
+        pub fn validation_twin() -> usize {
+            2
+        }
+
+        #[export(name "renamed_twin")]
+        pub fn renamed_validation_twin() -> usize {
+            3
+        }
+
+        #[export(name "renamed_twin")]
+        pub fn another_renamed_validation_twin() -> usize {
+            4
+        }
+    }
+
+    #[test]
+    fn test_package_validation() {
+        let error = TestPackage::meta().validate().unwrap_err();
+
+        let ValidationError::DuplicateNames(conflicts) = &error;
+
+        for name in ["validation_twin", "renamed_twin"] {
+            let conflicts = conflicts
+                .iter()
+                .filter(|conflict| conflict.name == name)
+                .collect::<Vec<_>>();
+
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(conflicts[0].ty, TestPackage::meta().ty());
+            assert_ne!(conflicts[0].first, conflicts[0].second);
+        }
+
+        assert!(error.to_string().starts_with("Duplicate exported names:"));
+        assert!(error.to_string().contains("validation_twin"));
+
+        assert!(core().validate().is_ok());
+    }
+}
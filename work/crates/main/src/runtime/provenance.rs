@@ -0,0 +1,348 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    fmt::{Display, Formatter},
+    sync::RwLock,
+};
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+use lady_deirdre::{arena::Id, sync::Lazy};
+
+static REGISTRY: Lazy<RwLock<AHashMap<Id, ScriptProvenance>>> =
+    Lazy::new(|| RwLock::new(AHashMap::new()));
+
+/// A record that describes the origin of a script module's source code: the
+/// asset name, the asset version, and arbitrary host-defined tags.
+///
+/// The host application assigns the provenance to the
+/// [ScriptModule](crate::analysis::ScriptModule) at creation through the
+/// [with_provenance](crate::analysis::ScriptModule::with_provenance)
+/// constructor, or later through the
+/// [set_provenance](crate::analysis::ScriptModule::set_provenance) function.
+///
+/// The compiled [ScriptFn](crate::interpret::ScriptFn) objects keep a copy of
+/// the module's provenance (see
+/// [ScriptFn::provenance](crate::interpret::ScriptFn::provenance)), and the
+/// [RuntimeError::provenance](crate::runtime::RuntimeError::provenance)
+/// function returns the provenance of the module in which the error occurred.
+/// The headers of the printed source code snippets (e.g., runtime errors and
+/// diagnostics) include the asset's revision: the version if specified, or
+/// the prefix of the source code [text hash](Self::text_hash) otherwise.
+///
+/// ```
+/// # use ad_astra::{
+/// #     analysis::{ModuleRead, ScriptModule},
+/// #     export,
+/// #     lady_deirdre::analysis::TriggerHandle,
+/// #     runtime::{ScriptPackage, ScriptProvenance},
+/// # };
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// let mut provenance = ScriptProvenance::new("combat.adastra");
+///
+/// provenance.set_tag("asset", "12345");
+///
+/// let module = ScriptModule::with_provenance(Package::meta(), "return 1 + 2;", provenance);
+///
+/// let handle = TriggerHandle::new();
+/// let module_read = module.read(&handle, 1).unwrap();
+///
+/// let script_fn = module_read.compile().unwrap();
+/// let provenance = script_fn.provenance().unwrap();
+///
+/// assert_eq!(provenance.name(), "combat.adastra");
+/// assert_eq!(provenance.tag("asset"), Some("12345"));
+/// assert_eq!(provenance.text_hash(), Some(module_read.text_hash()));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct ScriptProvenance {
+    name: CompactString,
+    version: Option<CompactString>,
+    text_hash: Option<u64>,
+    tags: Vec<(CompactString, CompactString)>,
+}
+
+impl Display for ScriptProvenance {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(&self.name)?;
+
+        if let Some(revision) = self.revision() {
+            if !self.name.is_empty() {
+                formatter.write_str(" ")?;
+            }
+
+            formatter.write_fmt(format_args!("@ rev {revision}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ScriptProvenance {
+    /// Creates a provenance record of the asset with the specified `name`.
+    ///
+    /// The record does not have a version or tags by default.
+    #[inline(always)]
+    pub fn new(name: impl AsRef<str>) -> Self {
+        Self {
+            name: CompactString::from(name.as_ref()),
+            version: None,
+            text_hash: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Returns the name of the asset.
+    ///
+    /// The script module that owns this record uses this string as the
+    /// module's [name](crate::analysis::ScriptModule::rename).
+    #[inline(always)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the host-defined version of the asset, if specified.
+    #[inline(always)]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Sets the host-defined version of the asset (e.g., a commit hash or
+    /// a build number).
+    #[inline(always)]
+    pub fn set_version(&mut self, version: impl AsRef<str>) -> &mut Self {
+        self.version = Some(CompactString::from(version.as_ref()));
+
+        self
+    }
+
+    /// Returns the hash of the script module's source code text
+    /// (see [ModuleRead::text_hash](crate::analysis::ModuleRead::text_hash)).
+    ///
+    /// The value is known once the module computes the hash of its current
+    /// text: the module computes the hash at creation, and after the source
+    /// code edits, upon the text_hash request or the module's
+    /// [compilation](crate::analysis::ModuleRead::compile).
+    #[inline(always)]
+    pub fn text_hash(&self) -> Option<u64> {
+        self.text_hash
+    }
+
+    /// Returns the value of the tag with the specified `key`.
+    #[inline]
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag.as_str() == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the value of the tag with the specified `key`, replacing the
+    /// previous value of this tag.
+    pub fn set_tag(&mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> &mut Self {
+        let key = key.as_ref();
+        let value = CompactString::from(value.as_ref());
+
+        match self.tags.iter_mut().find(|(tag, _)| tag.as_str() == key) {
+            Some((_, previous)) => *previous = value,
+            None => self.tags.push((CompactString::from(key), value)),
+        }
+
+        self
+    }
+
+    /// Returns an iterator over the key-value pairs of the tags in the order
+    /// of their insertion.
+    #[inline(always)]
+    pub fn tags(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.tags
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns a short string that identifies the revision of the asset: the
+    /// [version](Self::version) if specified, or the first eight hex digits of
+    /// the [text hash](Self::text_hash) otherwise.
+    pub fn revision(&self) -> Option<String> {
+        if let Some(version) = &self.version {
+            return Some(String::from(version.as_str()));
+        }
+
+        self.text_hash.map(|hash| format!("{:08x}", hash >> 32))
+    }
+
+    #[inline(always)]
+    pub(crate) fn of(id: Id) -> Option<Self> {
+        REGISTRY
+            .read()
+            .unwrap_or_else(|poison| poison.into_inner())
+            .get(&id)
+            .cloned()
+    }
+
+    pub(crate) fn register(id: Id, provenance: Option<Self>) {
+        let mut registry = REGISTRY
+            .write()
+            .unwrap_or_else(|poison| poison.into_inner());
+
+        match provenance {
+            Some(provenance) => {
+                let _ = registry.insert(id, provenance);
+            }
+
+            None => {
+                let _ = registry.remove(&id);
+            }
+        }
+    }
+
+    pub(crate) fn update(id: Id, update: impl FnOnce(&mut Self)) {
+        let mut registry = REGISTRY
+            .write()
+            .unwrap_or_else(|poison| poison.into_inner());
+
+        if let Some(provenance) = registry.get_mut(&id) {
+            update(provenance);
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_text_hash(&mut self, text_hash: Option<u64>) {
+        self.text_hash = text_hash;
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_name(&mut self, name: &str) {
+        self.name = CompactString::from(name);
+    }
+}
+
+// A stable 64-bit FNV-1a hash of the source code text.
+//
+// Unlike the std and ahash hashers, the result does not depend on the process
+// or the compiler version, so the hosts can use it as a persistent cache key.
+pub(crate) struct TextHasher(u64);
+
+impl TextHasher {
+    #[inline(always)]
+    pub(crate) fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+
+    #[inline]
+    pub(crate) fn write(&mut self, text: &str) {
+        for byte in text.as_bytes() {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::{analysis::TriggerHandle, arena::Identifiable};
+
+    use crate::{
+        analysis::{symbols::tests::TestPackage, ModuleRead, ModuleWrite, ScriptModule},
+        runtime::{ScriptPackage, ScriptProvenance},
+    };
+
+    #[test]
+    fn test_script_provenance() {
+        let mut provenance = ScriptProvenance::new("combat.adastra");
+
+        provenance.set_tag("asset", "42");
+
+        let module = ScriptModule::with_provenance(
+            TestPackage::meta(),
+            "let f = fn() { return [1, 2][5]; }; return f();",
+            provenance,
+        );
+
+        let handle = TriggerHandle::new();
+
+        let initial_hash = module.read(&handle, 1).unwrap().text_hash();
+
+        {
+            let mut write = module.write(&handle, 1).unwrap();
+
+            let _ = write.edit(0..0, "  ").unwrap();
+
+            assert_ne!(write.text_hash(), initial_hash);
+
+            let _ = write.edit(0..2, "").unwrap();
+        }
+
+        let read = module.read(&handle, 1).unwrap();
+
+        assert_eq!(read.text_hash(), initial_hash);
+        assert_eq!(module.id().name(), "combat.adastra");
+
+        let revision = format!("{:08x}", initial_hash >> 32);
+
+        let script_fn = read.compile().unwrap();
+
+        assert_eq!(
+            script_fn.provenance().unwrap().text_hash(),
+            Some(initial_hash)
+        );
+
+        let error = script_fn.run().unwrap_err();
+        let provenance = error.provenance().unwrap();
+
+        assert_eq!(provenance.name(), "combat.adastra");
+        assert_eq!(provenance.tag("asset"), Some("42"));
+        assert_eq!(provenance.revision(), Some(revision.clone()));
+
+        let text = read.text();
+        let display = error.display(&text).to_string();
+
+        assert!(display.contains(&format!("@ rev {revision}")));
+
+        module.rename("arena.adastra");
+
+        assert_eq!(module.provenance().unwrap().name(), "arena.adastra");
+        assert_eq!(script_fn.provenance().unwrap().name(), "combat.adastra");
+    }
+}
@@ -0,0 +1,339 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+};
+
+use crate::{
+    exports::{NestingGuard, Struct},
+    runtime::{
+        Cell,
+        Downcast,
+        Ident,
+        Origin,
+        Provider,
+        RuntimeError,
+        RuntimeResult,
+        ScriptType,
+        TypeHint,
+    },
+};
+
+/// A Rust type that can be constructed from a script struct:
+/// `struct { foo: 10, bar: "baz" }`.
+///
+/// Implementing this trait together with the [Downcast] trait allows
+/// exported functions to accept plain Rust structs as parameters without
+/// exporting these types to the Script Engine field by field.
+///
+/// Usually, you don't need to implement this trait manually. Instead, you can
+/// derive it together with the Downcast implementation using the
+/// `#[derive(FromScriptStruct)]` macro:
+///
+/// ```
+/// use ad_astra::{export, runtime::FromScriptStruct};
+///
+/// #[derive(FromScriptStruct)]
+/// pub struct Config {
+///     /// Window width.
+///     pub width: usize,
+///
+///     /// Window height.
+///     pub height: usize,
+///
+///     /// Window title.
+///     pub title: Option<String>,
+/// }
+///
+/// #[export]
+/// pub fn open_window(config: Config) -> usize {
+///     config.width * config.height
+/// }
+/// ```
+///
+/// In the script code, the user passes a struct literal to such a function:
+/// `open_window(struct { width: 800, height: 600 })`. The entries of the
+/// script struct are matched to the Rust struct fields by name and are
+/// [downcasted](Downcast) to the field types. Fields of the [Option] type are
+/// optional; all other fields are required. If the script struct has unknown
+/// entries or misses required fields, the conversion fails with the
+/// [RuntimeError::StructFields] error.
+///
+/// The static analyzer uses the [StructSchema] of the type to validate the
+/// struct literals passed to such functions and to suggest field names in
+/// code completions.
+pub trait FromScriptStruct: Sized + Send + Sync + 'static {
+    /// Returns the description of the Rust struct fields.
+    fn schema() -> &'static StructSchema;
+
+    /// Constructs the Rust struct from the script struct entries.
+    ///
+    /// The entries have already been validated against the
+    /// [schema](Self::schema): the `fields` object does not have unknown
+    /// entries and has all required ones.
+    fn from_script_struct(fields: &mut ScriptStructFields) -> RuntimeResult<Self>;
+}
+
+/// A static description of the Rust struct that can be constructed from a
+/// script struct.
+///
+/// See [FromScriptStruct] for details.
+#[derive(Debug)]
+pub struct StructSchema {
+    /// The name of the Rust struct.
+    pub name: &'static str,
+
+    /// The RustDoc documentation of the Rust struct, if available.
+    pub doc: Option<&'static str>,
+
+    /// The descriptions of the struct fields.
+    pub fields: &'static [FieldSchema],
+}
+
+impl PartialEq for &'static StructSchema {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        *self as *const StructSchema as usize == *other as *const StructSchema as usize
+    }
+}
+
+impl Eq for &'static StructSchema {}
+
+impl Hash for &'static StructSchema {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self as *const StructSchema as usize).hash(state)
+    }
+}
+
+impl Display for StructSchema {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("struct {")?;
+
+        let mut first = true;
+
+        for field in self.fields {
+            match first {
+                true => first = false,
+                false => formatter.write_str(",")?,
+            }
+
+            formatter.write_fmt(format_args!(" {field}"))?;
+        }
+
+        if !first {
+            formatter.write_str(" ")?;
+        }
+
+        formatter.write_str("}")
+    }
+}
+
+impl StructSchema {
+    /// Returns the description of the field with the specified name, or None
+    /// if the struct does not have such a field.
+    #[inline]
+    pub fn field(&self, name: &str) -> Option<&'static FieldSchema> {
+        let fields: &'static [FieldSchema] = self.fields;
+
+        fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// A description of the field in the [StructSchema].
+pub struct FieldSchema {
+    /// The name of the field.
+    pub name: &'static str,
+
+    /// The RustDoc documentation of the field, if available.
+    pub doc: Option<&'static str>,
+
+    /// A function that returns the type of the field.
+    pub hint: fn() -> TypeHint,
+
+    /// If true, the script struct must have an entry for this field.
+    /// Otherwise, the field is constructed from the [nil](Cell::nil) value
+    /// when the entry is missing.
+    pub required: bool,
+}
+
+impl Debug for FieldSchema {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("FieldSchema")
+            .field("name", &self.name)
+            .field("hint", &(self.hint)())
+            .field("required", &self.required)
+            .finish()
+    }
+}
+
+impl Display for FieldSchema {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let hint = (self.hint)();
+
+        formatter.write_str(self.name)?;
+
+        if !self.required {
+            formatter.write_str("?")?;
+        }
+
+        if hint.is_dynamic() {
+            return Ok(());
+        }
+
+        formatter.write_fmt(format_args!(": {hint}"))
+    }
+}
+
+/// The entries of the script struct that are being converted into a Rust
+/// struct.
+///
+/// This object is the parameter of the
+/// [FromScriptStruct::from_script_struct] function.
+pub struct ScriptStructFields {
+    origin: Origin,
+    entries: BTreeMap<Ident, Cell>,
+}
+
+impl ScriptStructFields {
+    /// Returns the range in the Rust or Script source code where the
+    /// conversion has been requested.
+    #[inline(always)]
+    pub fn origin(&self) -> Origin {
+        self.origin
+    }
+
+    /// Returns true if the script struct has an entry with the specified name.
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.keys().any(|key| key.as_ref() == name)
+    }
+
+    /// Removes the entry with the specified name from the script struct and
+    /// downcasts it to the Rust type `T`.
+    ///
+    /// If the entry is missing, the function attempts to downcast the
+    /// [nil](Cell::nil) value, which succeeds for [Option] types.
+    pub fn take<T: Downcast<'static>>(&mut self, name: &str) -> RuntimeResult<T> {
+        let key = self
+            .entries
+            .keys()
+            .find(|key| key.as_ref() == name)
+            .cloned();
+
+        let cell = match key {
+            Some(key) => self.entries.remove(&key).unwrap_or_default(),
+            None => Cell::nil(),
+        };
+
+        <T as Downcast<'static>>::downcast(self.origin, Provider::Owned(cell))
+    }
+}
+
+#[inline(always)]
+pub fn script_struct_hint() -> TypeHint {
+    TypeHint::Type(<Struct>::type_meta())
+}
+
+pub fn downcast_script_struct<T: FromScriptStruct>(
+    origin: Origin,
+    provider: Provider<'_>,
+) -> RuntimeResult<T> {
+    // Recursive Rust structs (e.g., a field of the `Option<Box<Self>>` type)
+    // would otherwise descend as deep as the script data is nested.
+    let Some(_guard) = NestingGuard::enter() else {
+        return Err(RuntimeError::StackOverflow { origin });
+    };
+
+    let entries = <BTreeMap<Ident, Cell> as Downcast>::downcast(origin, provider)?;
+
+    let schema = T::schema();
+
+    let missing = schema
+        .fields
+        .iter()
+        .filter(|field| field.required && !entries.keys().any(|key| key.as_ref() == field.name))
+        .map(|field| field.name)
+        .collect::<Vec<_>>();
+
+    let unknown = entries
+        .keys()
+        .filter(|key| schema.field(key.as_ref()).is_none())
+        .map(|key| key.to_string())
+        .collect::<Vec<_>>();
+
+    if !missing.is_empty() || !unknown.is_empty() {
+        return Err(RuntimeError::StructFields {
+            access_origin: origin,
+            schema,
+            missing,
+            unknown,
+        });
+    }
+
+    T::from_script_struct(&mut ScriptStructFields { origin, entries })
+}
+
+pub fn downcast_exported_struct<T: FromScriptStruct + ScriptType>(
+    origin: Origin,
+    provider: Provider<'_>,
+) -> RuntimeResult<T> {
+    if provider.as_ref().is::<Struct>() {
+        return downcast_script_struct::<T>(origin, provider);
+    }
+
+    provider.to_owned().take::<T>(origin)
+}
+
+// Replaces the script struct in the `cell` with the exported Rust struct
+// constructed from the script struct entries. Leaves the cell untouched if it
+// holds data of any other type.
+pub fn coerce_exported_struct<T: FromScriptStruct + ScriptType>(
+    origin: Origin,
+    cell: &mut Cell,
+) -> RuntimeResult<()> {
+    if !cell.is::<Struct>() {
+        return Ok(());
+    }
+
+    let data = downcast_script_struct::<T>(origin, Provider::Borrowed(cell))?;
+
+    *cell = Cell::give_vec(origin, vec![data])?;
+
+    Ok(())
+}
@@ -50,9 +50,13 @@ use lady_deirdre::sync::Lazy;
 use crate::{
     report::debug_unreachable,
     runtime::{
+        __intrinsics::DeclarationGroup,
         ops::{
             DynamicType,
             Fn0Repr,
+            Fn10Repr,
+            Fn11Repr,
+            Fn12Repr,
             Fn1Repr,
             Fn2Repr,
             Fn3Repr,
@@ -60,9 +64,10 @@ use crate::{
             Fn5Repr,
             Fn6Repr,
             Fn7Repr,
+            Fn8Repr,
+            Fn9Repr,
         },
         RustOrigin,
-        __intrinsics::DeclarationGroup,
     },
 };
 
@@ -90,7 +95,7 @@ pub trait ScriptType: sealed::Sealed + Send + Sync + 'static {
 }
 
 mod sealed {
-    use crate::runtime::{ScriptType, __intrinsics::RegisteredType};
+    use crate::runtime::{__intrinsics::RegisteredType, ScriptType};
 
     pub trait Sealed {}
 
@@ -206,6 +211,11 @@ impl TypeMeta {
             5 => Some(Fn5Repr::type_meta()),
             6 => Some(Fn6Repr::type_meta()),
             7 => Some(Fn7Repr::type_meta()),
+            8 => Some(Fn8Repr::type_meta()),
+            9 => Some(Fn9Repr::type_meta()),
+            10 => Some(Fn10Repr::type_meta()),
+            11 => Some(Fn11Repr::type_meta()),
+            12 => Some(Fn12Repr::type_meta()),
             _ => None,
         }
     }
@@ -281,7 +291,7 @@ impl TypeMeta {
     }
 
     #[inline(always)]
-    pub(super) fn size(&self) -> usize {
+    pub(crate) fn size(&self) -> usize {
         self.size
     }
 
@@ -495,6 +505,14 @@ impl TypeFamily {
         &crate::runtime::__intrinsics::PACKAGE_FAMILY
     }
 
+    /// Returns a reference to the type family of the nested package
+    /// namespaces (the Rust items exported with the
+    /// `#[export(namespace "...")]` attribute).
+    #[inline(always)]
+    pub fn namespace() -> &'static Self {
+        &crate::runtime::__intrinsics::NAMESPACE_FAMILY
+    }
+
     /// Returns a reference to the type family of numeric objects.
     ///
     /// The [usize], [f32], and other Rust built-in numeric types belong to this
@@ -504,6 +522,35 @@ impl TypeFamily {
         &NUMBER_FAMILY
     }
 
+    /// Returns a reference to the type family of byte buffers.
+    ///
+    /// The exported Rust functions that return `Vec<u8>`, `&[u8]`, or
+    /// `Box<[u8]>` produce objects of this family.
+    #[inline(always)]
+    pub fn bytes() -> &'static Self {
+        &BYTES_FAMILY
+    }
+
+    /// Returns a reference to the type family of time spans.
+    ///
+    /// The exported Rust functions that accept or return
+    /// [Duration](std::time::Duration) objects represent them in scripts as
+    /// numbers of seconds.
+    #[inline(always)]
+    pub fn duration() -> &'static Self {
+        &DURATION_FAMILY
+    }
+
+    /// Returns a reference to the type family of file system paths.
+    ///
+    /// The exported Rust functions that accept or return
+    /// [PathBuf](std::path::PathBuf) or [Path](std::path::Path) objects
+    /// represent them in scripts as strings.
+    #[inline(always)]
+    pub fn path() -> &'static Self {
+        &PATH_FAMILY
+    }
+
     /// Returns true if this family is the [Nil Family](Self::nil).
     #[inline(always)]
     pub fn is_nil(&self) -> bool {
@@ -528,12 +575,37 @@ impl TypeFamily {
         self == Self::package()
     }
 
+    /// Returns true if this family is the
+    /// [Namespaces Family](Self::namespace).
+    #[inline(always)]
+    pub fn is_namespace(&self) -> bool {
+        self == Self::namespace()
+    }
+
     /// Returns true if this family is the [Numeric Family](Self::number).
     #[inline(always)]
     pub fn is_number(&self) -> bool {
         self == Self::number()
     }
 
+    /// Returns true if this family is the [Bytes Family](Self::bytes).
+    #[inline(always)]
+    pub fn is_bytes(&self) -> bool {
+        self == Self::bytes()
+    }
+
+    /// Returns true if this family is the [Duration Family](Self::duration).
+    #[inline(always)]
+    pub fn is_duration(&self) -> bool {
+        self == Self::duration()
+    }
+
+    /// Returns true if this family is the [Path Family](Self::path).
+    #[inline(always)]
+    pub fn is_path(&self) -> bool {
+        self == Self::path()
+    }
+
     /// Returns the number of types associated with this family.
     #[inline(always)]
     pub fn len(&self) -> usize {
@@ -822,6 +894,12 @@ impl TypeRegistry {
                     let declaration = declaration();
 
                     if let Some(previous) = type_index.get(&declaration.id) {
+                        // Each item exported into the namespace declares the
+                        // namespace type on its own.
+                        if previous.family().is_namespace() && previous.name == declaration.name {
+                            continue;
+                        }
+
                         origin.blame(&format!(
                             "Type {} already declared in {} as {}.",
                             declaration.name, previous.origin, previous.name,
@@ -874,4 +952,812 @@ impl TypeRegistry {
     }
 }
 
-use crate::exports::NUMBER_FAMILY;
+use crate::exports::{BYTES_FAMILY, DURATION_FAMILY, NUMBER_FAMILY, PATH_FAMILY};
+
+#[cfg(test)]
+mod tests {
+    use std::{ops::Add, sync::OnceLock};
+
+    use lady_deirdre::analysis::TriggerHandle;
+
+    use crate::{
+        analysis::{
+            symbols::{tests::TestPackage, LookupOptions, ModuleSymbol, SymbolKind},
+            DescriptionConfig,
+            IssueCode,
+            ModuleRead,
+            ModuleWrite,
+            ScriptModule,
+        },
+        export,
+        runtime::{
+            ops::ScriptAdd,
+            Arg,
+            Cell,
+            Origin,
+            RuntimeError,
+            RuntimeResult,
+            ScriptPackage,
+            ScriptType,
+        },
+    };
+
+    /// A color.
+    #[export]
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum TestColor {
+        Red,
+
+        #[export(name "Lime")]
+        Green,
+
+        /// An RGB triple.
+        Rgb {
+            r: u8,
+            g: u8,
+            b: u8,
+        },
+
+        Gray(u8),
+
+        #[export(exclude)]
+        Hidden,
+    }
+
+    #[export(name "TestFigure")]
+    #[derive(Clone)]
+    pub enum TestShape {
+        Dot,
+    }
+
+    #[test]
+    fn test_exported_enums() {
+        assert_eq!(TestColor::type_meta().name(), "TestColor");
+        assert_eq!(TestShape::type_meta().name(), "TestFigure");
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, TestColor, "return TestColor_Rgb(1, 2, 3);"),
+            TestColor::Rgb { r: 1, g: 2, b: 3 },
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, TestColor, "return TestColor_Gray(7);"),
+            TestColor::Gray(7),
+        );
+
+        assert_eq!(
+            crate::eval!(TestPackage, "return TestColor_Rgb(1, 2, 3).variant;").stringify(false),
+            "Rgb",
+        );
+
+        assert_eq!(
+            crate::eval!(TestPackage, "return TestColor_Lime().variant;").stringify(false),
+            "Lime",
+        );
+
+        assert_eq!(
+            crate::eval!(TestPackage, "return TestFigure_Dot().variant;").stringify(false),
+            "Dot",
+        );
+
+        assert!(crate::eval_as!(
+            TestPackage,
+            bool,
+            "return TestColor_Gray(5) == TestColor_Gray(5);"
+        ));
+
+        assert!(!crate::eval_as!(
+            TestPackage,
+            bool,
+            "return TestColor_Red() == TestColor_Lime();"
+        ));
+
+        assert!(TestPackage::meta()
+            .ty()
+            .prototype()
+            .hint_component("TestColor_Hidden")
+            .is_none());
+    }
+
+    #[export]
+    #[derive(Clone)]
+    pub struct TestSpeaker {
+        level: f32,
+    }
+
+    #[export]
+    impl TestSpeaker {
+        /// The speaker's volume level.
+        #[export(getter)]
+        pub fn volume(&self) -> f32 {
+            self.level
+        }
+
+        #[export(setter)]
+        pub fn set_volume(&mut self, volume: f32) {
+            self.level = volume.clamp(0.0, 1.0);
+        }
+
+        #[export(getter)]
+        pub fn muted(&self) -> bool {
+            self.level == 0.0
+        }
+    }
+
+    #[export]
+    pub fn test_speaker() -> TestSpeaker {
+        TestSpeaker { level: 0.5 }
+    }
+
+    #[test]
+    fn test_exported_properties() {
+        let prototype = TestSpeaker::type_meta().prototype();
+
+        let volume = prototype.hint_component("volume").unwrap();
+
+        assert!(volume.property);
+        assert!(volume.writable);
+        assert!(!volume.owned);
+        assert_eq!(volume.ty.type_meta(), Some(f32::type_meta()));
+        assert_eq!(volume.doc, Some(" The speaker's volume level."));
+
+        let muted = prototype.hint_component("muted").unwrap();
+
+        assert!(muted.property);
+        assert!(!muted.writable);
+
+        assert!(prototype.hint_component("set_volume").is_none());
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                f32,
+                "let s = test_speaker(); s.volume = 0.25; return s.volume;"
+            ),
+            0.25,
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                f32,
+                "let s = test_speaker(); s.volume += 0.25; return s.volume;"
+            ),
+            0.75,
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                f32,
+                "let s = test_speaker(); s.volume = 5; return s.volume;"
+            ),
+            1.0,
+        );
+
+        assert!(crate::eval_as!(
+            TestPackage,
+            bool,
+            "let s = test_speaker(); s.volume = 0; return s.muted;"
+        ));
+
+        let handle = TriggerHandle::new();
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "let s = test_speaker(); s.muted = true;",
+        );
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert!(matches!(error, RuntimeError::ReadOnly { .. }));
+    }
+
+    #[test]
+    fn test_property_diagnostics() {
+        crate::assert_script_ok!(TestPackage, "let s = test_speaker(); s.volume = 0.1;");
+
+        crate::assert_script_err!(
+            TestPackage,
+            "let s = test_speaker(); s.muted = true;",
+            IssueCode::ReadOnlyProperty,
+            "muted",
+        );
+    }
+
+    #[export]
+    #[derive(Default)]
+    pub struct TestProfile {
+        /// The profile's nickname.
+        pub nickname: Option<String>,
+        pub score: Option<usize>,
+        #[export(readonly)]
+        pub position: (usize, usize),
+    }
+
+    #[export]
+    pub fn test_profile() -> TestProfile {
+        TestProfile {
+            nickname: None,
+            score: Some(10),
+            position: (1, 2),
+        }
+    }
+
+    #[test]
+    fn test_casted_fields() {
+        let prototype = TestProfile::type_meta().prototype();
+
+        let nickname = prototype.hint_component("nickname").unwrap();
+
+        assert!(nickname.property);
+        assert!(nickname.writable);
+        assert_eq!(nickname.ty.type_meta(), Some(<str>::type_meta()));
+        assert_eq!(nickname.doc, Some(" The profile's nickname."));
+
+        let score = prototype.hint_component("score").unwrap();
+
+        assert!(score.writable);
+        assert_eq!(score.ty.type_meta(), Some(usize::type_meta()));
+
+        let position = prototype.hint_component("position").unwrap();
+
+        assert!(position.property);
+        assert!(!position.writable);
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "let p = test_profile(); p.score = p.score + 5; return p.score;"
+            ),
+            15,
+        );
+
+        assert_eq!(
+            crate::eval!(
+                TestPackage,
+                "let p = test_profile(); p.nickname = \"Bob\"; return p.nickname;"
+            )
+            .stringify(false),
+            "Bob",
+        );
+
+        assert!(crate::eval!(TestPackage, "return test_profile().nickname;").is_nil());
+
+        let handle = TriggerHandle::new();
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "let p = test_profile(); p.position = 5;",
+        );
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        assert!(matches!(error, RuntimeError::ReadOnly { .. }));
+    }
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct TestSpawner {
+        #[export(deprecated "use 'capacity' instead")]
+        pub limit: usize,
+        pub capacity: usize,
+    }
+
+    #[export]
+    impl TestSpawner {
+        #[export(deprecated "use 'spawn_at' instead")]
+        pub fn spawn(&self) -> usize {
+            self.capacity
+        }
+
+        pub fn spawn_at(&self, index: usize) -> usize {
+            self.capacity + index
+        }
+    }
+
+    #[export(deprecated "use 'test_new_spawner' instead")]
+    pub fn test_old_spawner() -> TestSpawner {
+        TestSpawner::default()
+    }
+
+    #[export]
+    pub fn test_new_spawner() -> TestSpawner {
+        TestSpawner::default()
+    }
+
+    #[test]
+    fn test_deprecated_members() {
+        let prototype = TestSpawner::type_meta().prototype();
+
+        assert_eq!(
+            prototype.hint_component("limit").unwrap().deprecated,
+            Some("use 'capacity' instead"),
+        );
+        assert_eq!(
+            prototype.hint_component("spawn").unwrap().deprecated,
+            Some("use 'spawn_at' instead"),
+        );
+        assert_eq!(
+            prototype.hint_component("capacity").unwrap().deprecated,
+            None
+        );
+        assert_eq!(
+            prototype.hint_component("spawn_at").unwrap().deprecated,
+            None
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_old_spawner().spawn();"),
+            0,
+        );
+
+        crate::assert_script_ok!(
+            TestPackage,
+            "let s = test_new_spawner(); s.capacity = s.spawn_at(1);"
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "let s = test_old_spawner(); s.limit = s.spawn();",
+            IssueCode::DeprecatedMember,
+            "test_old_spawner",
+            "limit",
+            "spawn",
+        );
+
+        let handle = TriggerHandle::new();
+        let module = ScriptModule::new(TestPackage::meta(), "test_new_spawner().");
+        let mut write = module.write(&handle, 1).unwrap();
+
+        let completions = write.completions(19).unwrap();
+
+        let deprecated = completions
+            .items
+            .iter()
+            .filter_map(|item| Some((item.label.to_string(), item.desc.deprecated?)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            deprecated,
+            [
+                (String::from("limit"), "use 'capacity' instead"),
+                (String::from("spawn"), "use 'spawn_at' instead"),
+            ],
+        );
+
+        static SOURCE: &str = "test_old_spawner().limit;";
+
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let read = module.read(&handle, 1).unwrap();
+
+        let hover = |needle: &str, kind: SymbolKind| {
+            let offset = SOURCE.find(needle).unwrap();
+
+            let symbols = read
+                .symbols(offset..offset, LookupOptions::default().filter(kind as u32))
+                .unwrap();
+
+            match symbols.first() {
+                Some(ModuleSymbol::Ident(symbol)) => symbol.ty(&read).unwrap(),
+                Some(ModuleSymbol::Field(symbol)) => symbol.ty(&read).unwrap(),
+                _ => panic!("{needle:?} lookup failed"),
+            }
+        };
+
+        let function = hover("test_old_spawner", SymbolKind::Ident);
+
+        assert_eq!(function.deprecated, Some("use 'test_new_spawner' instead"));
+        assert!(function
+            .render(&DescriptionConfig::detailed())
+            .contains("deprecated: use 'test_new_spawner' instead"));
+
+        let field = hover("limit", SymbolKind::Field);
+
+        assert_eq!(field.deprecated, Some("use 'capacity' instead"));
+    }
+
+    #[export]
+    #[derive(Clone, Default)]
+    pub struct TestShim {
+        /// Raw storage of the shim.
+        #[export(hidden)]
+        pub raw: usize,
+        pub value: usize,
+    }
+
+    #[export]
+    impl TestShim {
+        /// Migrates the legacy value.
+        #[export(hidden)]
+        pub fn migrate(&self) -> usize {
+            self.raw + 1
+        }
+
+        pub fn read(&self) -> usize {
+            self.value
+        }
+    }
+
+    /// Creates a shim for the legacy scripts.
+    #[export(hidden)]
+    pub fn test_legacy_shim() -> TestShim {
+        TestShim::default()
+    }
+
+    #[export]
+    pub fn test_modern_shim() -> TestShim {
+        TestShim::default()
+    }
+
+    #[test]
+    fn test_hidden_members() {
+        let prototype = TestShim::type_meta().prototype();
+
+        assert!(prototype.hint_component("raw").unwrap().hidden);
+        assert!(prototype.hint_component("migrate").unwrap().hidden);
+        assert!(!prototype.hint_component("value").unwrap().hidden);
+        assert!(!prototype.hint_component("read").unwrap().hidden);
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "let s = test_legacy_shim(); s.raw = 2; return s.migrate();"
+            ),
+            3,
+        );
+
+        crate::assert_script_ok!(
+            TestPackage,
+            "let s = test_legacy_shim(); s.value = s.migrate() + s.raw;"
+        );
+
+        let handle = TriggerHandle::new();
+        let module = ScriptModule::new(TestPackage::meta(), "test_modern_shim().");
+        let mut write = module.write(&handle, 1).unwrap();
+
+        let completions = write.completions(19).unwrap();
+
+        let labels = completions
+            .items
+            .iter()
+            .map(|item| item.label.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(labels, ["value", "read"]);
+
+        let module = ScriptModule::new(TestPackage::meta(), "test_");
+        let mut write = module.write(&handle, 1).unwrap();
+
+        let completions = write.completions(5).unwrap();
+
+        assert!(completions
+            .items
+            .iter()
+            .any(|item| item.label.to_string() == "test_modern_shim"));
+        assert!(!completions
+            .items
+            .iter()
+            .any(|item| item.label.to_string() == "test_legacy_shim"));
+
+        static SOURCE: &str = "test_legacy_shim().migrate();";
+
+        let module = ScriptModule::new(TestPackage::meta(), SOURCE);
+        let read = module.read(&handle, 1).unwrap();
+
+        let hover = |needle: &str, kind: SymbolKind| {
+            let offset = SOURCE.find(needle).unwrap();
+
+            let symbols = read
+                .symbols(offset..offset, LookupOptions::default().filter(kind as u32))
+                .unwrap();
+
+            match symbols.first() {
+                Some(ModuleSymbol::Ident(symbol)) => symbol.ty(&read).unwrap(),
+                Some(ModuleSymbol::Field(symbol)) => symbol.ty(&read).unwrap(),
+                _ => panic!("{needle:?} lookup failed"),
+            }
+        };
+
+        let function = hover("test_legacy_shim", SymbolKind::Ident);
+
+        assert_eq!(
+            function.doc,
+            Some(" Creates a shim for the legacy scripts.")
+        );
+
+        let method = hover("migrate", SymbolKind::Field);
+
+        assert_eq!(method.doc, Some(" Migrates the legacy value."));
+    }
+
+    /// A counter with the private state.
+    #[export(opaque)]
+    #[non_exhaustive]
+    #[derive(Clone)]
+    pub struct TestTally {
+        count: usize,
+    }
+
+    #[export]
+    impl TestTally {
+        pub fn bump(&mut self) {
+            self.count += 1;
+        }
+
+        pub fn count(&self) -> usize {
+            self.count
+        }
+    }
+
+    #[export]
+    pub fn test_tally() -> TestTally {
+        TestTally { count: 0 }
+    }
+
+    #[test]
+    fn test_opaque_types() {
+        let prototype = TestTally::type_meta().prototype();
+
+        assert!(!prototype.implements_assign());
+        assert!(prototype.implements_clone());
+        assert_eq!(
+            TestTally::type_meta().doc(),
+            Some(" A counter with the private state.")
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "let t = test_tally(); t.bump(); t.bump(); return t.count();"
+            ),
+            2,
+        );
+
+        let handle = TriggerHandle::new();
+        let module = ScriptModule::new(TestPackage::meta(), "test_tally().");
+        let mut write = module.write(&handle, 1).unwrap();
+
+        let completions = write.completions(13).unwrap();
+
+        let labels = completions
+            .items
+            .iter()
+            .map(|item| item.label.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(labels, ["bump", "count"]);
+    }
+
+    #[export]
+    pub trait TestGreeter {
+        fn name(&self) -> usize;
+
+        /// Greets the host.
+        fn greet(&self) -> usize {
+            self.name() + 1
+        }
+
+        fn farewell(&self) -> usize {
+            self.name() + 2
+        }
+    }
+
+    #[export]
+    #[derive(Clone)]
+    pub struct TestHost {
+        pub id: usize,
+    }
+
+    #[export(include_defaults)]
+    impl TestGreeter for TestHost {
+        fn name(&self) -> usize {
+            self.id
+        }
+
+        fn farewell(&self) -> usize {
+            self.id + 20
+        }
+    }
+
+    #[export]
+    pub fn test_host(id: usize) -> TestHost {
+        TestHost { id }
+    }
+
+    #[test]
+    fn test_trait_defaults() {
+        let prototype = TestHost::type_meta().prototype();
+
+        assert!(prototype.hint_component("name").is_some());
+        assert!(prototype.hint_component("greet").is_some());
+        assert!(prototype.hint_component("farewell").is_some());
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_host(10).greet();"),
+            11,
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_host(10).farewell();"),
+            30,
+        );
+    }
+
+    crate::type_family!(
+        static TEST_LENGTH_FAMILY = "length";
+    );
+
+    #[export(family &TEST_LENGTH_FAMILY)]
+    #[derive(Clone)]
+    pub struct TestMeters {
+        pub value: usize,
+    }
+
+    #[export(family &TEST_LENGTH_FAMILY)]
+    #[derive(Clone)]
+    pub struct TestFeet {
+        pub value: usize,
+    }
+
+    #[export]
+    impl Add<usize> for TestFeet {
+        type Output = usize;
+
+        fn add(self, rhs: usize) -> Self::Output {
+            self.value + rhs + 1000
+        }
+    }
+
+    pub struct TestLengthFamily;
+
+    #[export(family &TEST_LENGTH_FAMILY)]
+    impl ScriptAdd for TestLengthFamily {
+        type RHS = usize;
+        type Result = usize;
+
+        fn script_add(origin: Origin, lhs: Arg, rhs: Arg) -> RuntimeResult<Cell> {
+            let value = match lhs.data.is::<TestMeters>() {
+                true => lhs.data.take::<TestMeters>(lhs.origin)?.value,
+                false => lhs.data.take::<TestFeet>(lhs.origin)?.value,
+            };
+
+            let rhs = rhs.data.take::<usize>(rhs.origin)?;
+
+            Cell::give(origin, value + rhs)
+        }
+    }
+
+    #[export]
+    pub fn test_meters(value: usize) -> TestMeters {
+        TestMeters { value }
+    }
+
+    #[export]
+    pub fn test_feet(value: usize) -> TestFeet {
+        TestFeet { value }
+    }
+
+    #[test]
+    fn test_family_operators() {
+        assert!(TestMeters::type_meta().prototype().implements_add());
+        assert!(TestFeet::type_meta().prototype().implements_add());
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_meters(10) + 5;"),
+            15,
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_feet(10) + 5;"),
+            1015,
+        );
+    }
+
+    /// Adds two vectors.
+    #[export(namespace "test_math.vec")]
+    #[export(name "add")]
+    pub fn test_vec_add(a: usize, b: usize) -> usize {
+        a + b
+    }
+
+    #[export(namespace "test_math.vec")]
+    #[export(name "scale")]
+    pub fn test_vec_scale(a: usize, k: usize) -> usize {
+        a * k
+    }
+
+    #[export(namespace "test_math")]
+    #[export(name "add")]
+    pub fn test_math_add(a: usize, b: usize) -> usize {
+        a + b + 100
+    }
+
+    #[export(namespace "test_math")]
+    pub const TEST_ANSWER: usize = 42;
+
+    #[test]
+    fn test_namespaces() {
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return crate.test_math.vec.add(1, 2);"),
+            3,
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_math.vec.scale(3, 4);"),
+            12,
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_math.add(1, 2);"),
+            103,
+        );
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return test_math.TEST_ANSWER;"),
+            42,
+        );
+
+        let package = TestPackage::meta().ty().prototype();
+
+        assert!(package.hint_component("add").is_none());
+
+        let namespace = package.hint_component("test_math").unwrap().ty;
+
+        assert!(namespace.is_namespace());
+        assert_eq!(namespace.to_string(), "ad-astra.test_math");
+
+        let handle = TriggerHandle::new();
+        let module = ScriptModule::new(TestPackage::meta(), "test_math.vec.");
+        let mut write = module.write(&handle, 1).unwrap();
+
+        let completions = write.completions(14).unwrap();
+
+        let labels = completions
+            .items
+            .iter()
+            .map(|item| item.label.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(labels, ["add", "scale"]);
+    }
+
+    /// A host-configured limit.
+    #[export]
+    pub static TEST_LIMIT: OnceLock<usize> = OnceLock::new();
+
+    #[export]
+    pub static TEST_RATIO: OnceLock<usize> = OnceLock::new();
+
+    #[test]
+    fn test_once_lock_statics() {
+        let handle = TriggerHandle::new();
+        let module = ScriptModule::new(TestPackage::meta(), "return TEST_RATIO;");
+        let read = module.read(&handle, 1).unwrap();
+        let error = read.compile().unwrap().run().unwrap_err();
+
+        let RuntimeError::UninitializedStatic { name, .. } = &error else {
+            panic!("Unexpected error: {error}");
+        };
+
+        assert_eq!(name, "TEST_RATIO");
+        assert_eq!(
+            error.to_string(),
+            "static 'TEST_RATIO' is not initialized yet",
+        );
+
+        TEST_LIMIT.set(25).unwrap();
+
+        assert_eq!(
+            crate::eval_as!(TestPackage, usize, "return TEST_LIMIT + 1;"),
+            26,
+        );
+
+        let package = TestPackage::meta().ty().prototype();
+        let limit = package.hint_component("TEST_LIMIT").unwrap();
+
+        assert_eq!(limit.ty.type_meta(), Some(<usize>::type_meta()));
+        assert_eq!(limit.doc, Some(" A host-configured limit."));
+    }
+}
@@ -150,6 +150,12 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
             ScriptNode::Let { name, value, .. } => self.analyze_let(node_ref, name, value),
 
+            ScriptNode::Enum { name, .. } => self.analyze_enum(node_ref, name),
+
+            ScriptNode::EnumBody { .. } => Ok(()),
+
+            ScriptNode::EnumVariant { .. } => Ok(()),
+
             ScriptNode::Var { .. } => Ok(()),
 
             ScriptNode::For {
@@ -191,6 +197,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             ScriptNode::Array { items, .. } => self.analyze_array(node_ref, items),
 
             ScriptNode::String { .. } => self.analyze_string(node_ref),
+            ScriptNode::Embedded { .. } => self.analyze_string(node_ref),
 
             ScriptNode::Crate { .. } => self.analyze_crate(node_ref),
 
@@ -222,6 +229,8 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
             ScriptNode::CallArgs { .. } => Ok(()),
 
+            ScriptNode::Arg { .. } => Ok(()),
+
             ScriptNode::Index { left, arg, .. } => self.analyse_index(node_ref, left, arg),
 
             ScriptNode::IndexArg { .. } => Ok(()),
@@ -525,7 +534,8 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             return Ok(());
         };
 
-        let exhaustive = ScriptNode::is_match_exhaustive(self.doc, arms);
+        let exhaustive = ScriptNode::is_match_exhaustive(self.doc, arms)
+            || (!subject.is_nil() && self.is_enum_exhaustive(arms));
 
         let mut st_reachable = 0;
         let mut loop_reachable = 0;
@@ -665,6 +675,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             Shared::new(LocalMatchSyntax {
                 subject: *subject,
                 cases: match_cases,
+                exhaustive,
             }),
         );
 
@@ -735,6 +746,55 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
+    fn analyze_enum(&mut self, node_ref: &NodeRef, name: &NodeRef) -> AnalysisResult<()> {
+        self.check_st_reachability(node_ref);
+
+        let Some(names) = self.analysis.names.get_mut() else {
+            // Safety: `self` state is unique during analysis.
+            unsafe { debug_unreachable!("Non-unique access.") };
+        };
+
+        let Some(lets) = names.lets.get_mut() else {
+            // Safety: `self` state is unique during analysis.
+            unsafe { debug_unreachable!("Non-unique access.") };
+        };
+
+        let Some(inits) = lets.map.entry(*name).or_default().get_mut() else {
+            // Safety: `self` state is unique during analysis.
+            unsafe { debug_unreachable!("Non-unique access.") };
+        };
+
+        let _ = inits.set.insert(*node_ref);
+
+        let Some(syntax) = self.analysis.syntax.get_mut() else {
+            // Safety: `self` state is unique during analysis.
+            unsafe { debug_unreachable!("Non-unique access.") };
+        };
+
+        let Some(vars) = syntax.vars.get_mut() else {
+            // Safety: `self` state is unique during analysis.
+            unsafe { debug_unreachable!("Non-unique access.") };
+        };
+
+        let _ = vars.map.insert(*name, LocalVarSyntax::Let(*node_ref));
+
+        let Some(name_string) = ScriptNode::extract_atom_string(self.doc, name) else {
+            return Ok(());
+        };
+
+        let _ = self.namespace.insert(
+            CompactString::from(name_string),
+            NameDesc {
+                decl: *node_ref,
+                decl_depth: self.depth,
+                defs: vec![*node_ref],
+                init_depth: self.depth,
+            },
+        );
+
+        Ok(())
+    }
+
     fn analyze_for(
         &mut self,
         node_ref: &NodeRef,
@@ -1259,7 +1319,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             .map
             .insert(*node_ref, LocalExprSyntax::Ident(*node_ref));
 
-        let Some(token_string) = token.string(self.doc) else {
+        let Some(token_string) = ScriptToken::canonical_string(token, self.doc) else {
             return Ok(());
         };
 
@@ -1315,7 +1375,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             .map
             .insert(*node_ref, LocalExprSyntax::Number(*node_ref));
 
-        let Some(token_string) = token.string(self.doc) else {
+        let Some(token_string) = ScriptToken::canonical_string(token, self.doc) else {
             return Ok(());
         };
 
@@ -1363,7 +1423,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             .map
             .insert(*node_ref, LocalExprSyntax::Bool(*node_ref));
 
-        let Some(token_string) = token.string(self.doc) else {
+        let Some(token_string) = ScriptToken::canonical_string(token, self.doc) else {
             return Ok(());
         };
 
@@ -1448,13 +1508,27 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             .map
             .insert(*node_ref, LocalExprSyntax::Call(*node_ref));
 
-        let Some(ScriptNode::CallArgs {
-            args: arg_nodes, ..
-        }) = args.deref(self.doc)
-        else {
+        let Some(args_node) = args.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let (Some(arg_nodes), Some(arg_names)) = (
+            args_node.call_args(self.doc),
+            args_node.call_arg_names(self.doc),
+        ) else {
             return Ok(());
         };
 
+        let names = arg_names
+            .iter()
+            .map(|name| name.string(self.doc).map(CompactString::from))
+            .collect();
+
+        let closure = match args_node {
+            ScriptNode::CallArgs { closure, .. } => !closure.is_nil(),
+            _ => false,
+        };
+
         let Some(calls) = syntax.calls.get_mut() else {
             // Safety: `self` state is unique during analysis.
             unsafe { debug_unreachable!("Non-unique access.") };
@@ -1466,6 +1540,8 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 left: *left,
                 call_args_ref: *args,
                 args: arg_nodes.clone(),
+                names,
+                closure,
             }),
         );
 
@@ -1496,6 +1572,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 args.map.insert(
                     *arg_node,
                     LocalArgSyntax {
+                        call_ref: *node_ref,
                         call_left_ref: *left,
                         arg_index: index,
                     },
@@ -1559,7 +1636,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             unsafe { debug_unreachable!("Non-unique access.") };
         };
 
-        let Some(token_string) = token.string(self.doc) else {
+        let Some(token_string) = ScriptToken::canonical_string(token, self.doc) else {
             return Ok(());
         };
 
@@ -1650,6 +1727,60 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         parent_ref
     }
 
+    // Returns true if the match cases cover every variant of the same enum
+    // declaration: `match x { Color.Red => ..., Color.Green => ... }`.
+    //
+    // The enum name must refer to an enum declaration of the current scope
+    // that is never reassigned.
+    fn is_enum_exhaustive(&self, arms: &[NodeRef]) -> bool {
+        let mut enum_ref = NodeRef::nil();
+        let mut covered = AHashSet::new();
+
+        for arm in arms {
+            let Some(ScriptNode::MatchArm { case, .. }) = arm.deref(self.doc) else {
+                continue;
+            };
+
+            let Some((ident_ref, variant)) = ScriptNode::extract_variant(self.doc, *case) else {
+                continue;
+            };
+
+            let Some(name) = ScriptNode::extract_atom_string(self.doc, &ident_ref) else {
+                continue;
+            };
+
+            let Some(name_desc) = self.namespace.get(name) else {
+                continue;
+            };
+
+            if name_desc.defs.len() != 1 || name_desc.defs[0] != name_desc.decl {
+                continue;
+            }
+
+            match enum_ref.is_nil() {
+                true => enum_ref = name_desc.decl,
+                false if enum_ref != name_desc.decl => return false,
+                false => (),
+            }
+
+            let _ = covered.insert(variant);
+        }
+
+        if enum_ref.is_nil() {
+            return false;
+        }
+
+        ScriptNode::enum_variants(self.doc, &enum_ref)
+            .iter()
+            .all(|variant_ref| {
+                let Some(name) = ScriptNode::extract_atom_string(self.doc, variant_ref) else {
+                    return false;
+                };
+
+                covered.contains(name)
+            })
+    }
+
     fn check_st_reachability(&mut self, node_ref: &NodeRef) {
         if !self.st_reachable || !self.loop_reachable {
             let Some(flow) = self.analysis.flow.get_mut() else {
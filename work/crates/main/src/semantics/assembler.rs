@@ -45,14 +45,17 @@ use lady_deirdre::{
 };
 
 use crate::{
-    analysis::ModuleResultEx,
+    analysis::{ModuleResultEx, PureCalls},
     interpret::{
+        AppendCmd,
         Assembly,
+        AssignFieldCmd,
         BindCmd,
         ClosureIndex,
         Cmd,
         CmdIndex,
         ConcatCmd,
+        ConstCell,
         DupCmd,
         FieldCmd,
         IfFalseCmd,
@@ -63,9 +66,11 @@ use crate::{
         JumpCmd,
         LenCmd,
         LiftCmd,
+        NamedInvokeCmd,
         OpCmd,
         OriginIndex,
         PushClosureCmd,
+        PushConstCmd,
         PushFalseCmd,
         PushFloatCmd,
         PushFnCmd,
@@ -76,16 +81,18 @@ use crate::{
         PushStructCmd,
         PushTrueCmd,
         PushUsizeCmd,
+        PushVariantCmd,
         QueryCmd,
         RangeCmd,
         ShrinkCmd,
         StackDepth,
         StringIndex,
         SwapCmd,
+        TailInvokeCmd,
         RET,
     },
     report::system_panic,
-    runtime::{Origin, PackageMeta, ScriptOrigin},
+    runtime::{Arg, Cell, Origin, PackageMeta, ScriptOrigin, ScriptType},
     semantics::{setup::log_attr, *},
     syntax::{PolyRefOrigin, ScriptDoc, ScriptNode, ScriptToken, SpanBounds},
 };
@@ -125,7 +132,7 @@ impl SharedComputable for Assembly {
                     &ident_desc_map,
                     &closure_vec,
                     &lifetimes,
-                );
+                )?;
 
                 assembler.assemble_statements(statements)?;
 
@@ -151,6 +158,7 @@ impl SharedComputable for Assembly {
                 let ident_desc_map = fn_semantics.compilation.ident_desc_map.read(context)?;
                 let closure_vec = fn_semantics.compilation.closure_vec.read(context)?;
                 let lifetimes = fn_semantics.compilation.lifetimes.read(context)?;
+                let captures = fn_semantics.captures.read(context)?;
 
                 let mut assembler = Assembler::new(
                     doc,
@@ -160,7 +168,7 @@ impl SharedComputable for Assembly {
                     &ident_desc_map,
                     &closure_vec,
                     &lifetimes,
-                );
+                )?;
 
                 match body.deref(doc) {
                     Some(ScriptNode::Block { statements, .. }) => {
@@ -180,7 +188,33 @@ impl SharedComputable for Assembly {
 
                 assembler.shrink_ret();
 
-                Ok(Shared::new(assembler.assembly))
+                let mut assembly = assembler.assembly;
+
+                for var_ref in &captures.set {
+                    let Some(ScriptNode::Var { token, .. }) = var_ref.deref(doc) else {
+                        continue;
+                    };
+
+                    let Some(var_string) = token.string(doc) else {
+                        continue;
+                    };
+
+                    let Some(index) = closure_vec
+                        .vec
+                        .iter()
+                        .position(|name| name.as_str() == var_string)
+                    else {
+                        continue;
+                    };
+
+                    assembly
+                        .captures
+                        .push((CompactString::from(var_string), index + 1));
+                }
+
+                assembly.captures.sort_by_key(|(_, index)| *index);
+
+                Ok(Shared::new(assembly))
             }
 
             _ => Ok(Shared::default()),
@@ -204,6 +238,7 @@ struct Assembler<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher> {
     origin_index: AHashMap<Origin, OriginIndex>,
     string_index: AHashMap<Cow<'doc, str>, StringIndex>,
     string_rev_index: AHashMap<StringIndex, (usize, Cow<'doc, str>)>,
+    pure_calls: PureCalls,
 }
 
 impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
@@ -218,7 +253,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         ident_desc_map: &'doc IdentsDescMap,
         closure_vec: &'doc ClosureVec,
         lifetimes: &'doc Lifetimes,
-    ) -> Self {
+    ) -> AnalysisResult<Self> {
         let Some(package) = PackageMeta::by_id(doc.id()) else {
             system_panic!("Missing package.");
         };
@@ -271,7 +306,9 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
         let origin_index = AHashMap::from([(origin, 0)]);
 
-        Self {
+        let config = *context.common().analysis_config.read(context).forward()?;
+
+        Ok(Self {
             doc,
             context,
             package,
@@ -287,7 +324,8 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             origin_index,
             string_index: AHashMap::new(),
             string_rev_index: AHashMap::new(),
-        }
+            pure_calls: PureCalls::new(&config),
+        })
     }
 
     fn assemble_clause(&mut self, expr: &NodeRef) -> AnalysisResult<()> {
@@ -426,15 +464,26 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                         (false, None) => {
                             let object_origin = case.script_origin(self.doc, SpanBounds::Cover);
 
-                            let _ = self.cmd_dup(subject_depth);
-                            self.assemble_expr(inner)?;
+                            match ScriptNode::extract_range(self.doc, *inner) {
+                                Some((lower, upper)) => self.assemble_range_case(
+                                    subject_depth,
+                                    subject_origin,
+                                    &lower,
+                                    &upper,
+                                )?,
 
-                            let _ = self.cmd_op_binary(
-                                object_origin,
-                                subject_origin,
-                                object_origin,
-                                OpCmd::Equal,
-                            );
+                                None => {
+                                    let _ = self.cmd_dup(subject_depth);
+                                    self.assemble_expr(inner)?;
+
+                                    let _ = self.cmd_op_binary(
+                                        object_origin,
+                                        subject_origin,
+                                        object_origin,
+                                        OpCmd::Equal,
+                                    );
+                                }
+                            }
 
                             Some(self.cmd_if_true(object_origin))
                         }
@@ -559,6 +608,60 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
+    fn assemble_enum(
+        &mut self,
+        keyword: &TokenRef,
+        name: &NodeRef,
+        body: &NodeRef,
+    ) -> AnalysisResult<()> {
+        if self.unused_vars.contains(name) {
+            return Ok(());
+        }
+
+        let Some(ScriptNode::Var { token, .. }) = name.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let Some(var_string) = token.string(self.doc) else {
+            return Ok(());
+        };
+
+        let origin = ScriptOrigin::from(keyword);
+
+        let _ = self.cmd_push_struct(origin);
+
+        let var_depth = self.stack_top();
+
+        if let Some(ScriptNode::EnumBody { variants, .. }) = body.deref(self.doc) {
+            let enum_index = self.store_string(var_string);
+
+            for (tag, variant_ref) in variants.iter().enumerate() {
+                let Some(ScriptNode::EnumVariant { token, .. }) = variant_ref.deref(self.doc)
+                else {
+                    continue;
+                };
+
+                let Some(variant_string) = token.string(self.doc) else {
+                    continue;
+                };
+
+                let field = self.store_string(variant_string);
+                let field_origin = ScriptOrigin::from(token);
+
+                let _ = self.cmd_push_variant(field_origin, enum_index, field, tag);
+                let _ = self.cmd_dup(var_depth);
+                let _ = self.cmd_field(origin, field_origin, field, false);
+
+                let _ =
+                    self.cmd_op_assignment(field_origin, field_origin, field_origin, OpCmd::Assign);
+            }
+        }
+
+        let _ = self.flow_state.namespace.insert(var_string, var_depth);
+
+        Ok(())
+    }
+
     fn assemble_for(
         &mut self,
         iterator: &NodeRef,
@@ -727,6 +830,13 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
             ScriptNode::Let { name, value, .. } => self.assemble_let(name, value),
 
+            ScriptNode::Enum {
+                keyword,
+                name,
+                body,
+                ..
+            } => self.assemble_enum(keyword, name, body),
+
             ScriptNode::For {
                 iterator,
                 range,
@@ -788,7 +898,12 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
     }
 
     fn assemble_return(&mut self, result: &NodeRef) -> AnalysisResult<()> {
-        self.assemble_expr(result)?;
+        // The invocation's result is returned as is, so the invocation is in
+        // the tail position.
+        match self.unwrap_expr(result).deref(self.doc) {
+            Some(ScriptNode::Call { left, args, .. }) => self.assemble_call::<true>(left, args)?,
+            _ => self.assemble_expr(result)?,
+        }
 
         let top = self.stack_top();
 
@@ -819,6 +934,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             ScriptNode::Array { items, .. } => self.assemble_array(expr, items)?,
 
             ScriptNode::String { start, end, .. } => self.assemble_string(start, end)?,
+            ScriptNode::Embedded { start, end, .. } => self.assemble_embedded(start, end)?,
 
             ScriptNode::Crate { token, .. } => self.assemble_crate(token)?,
 
@@ -842,7 +958,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
             ScriptNode::Query { left, op, .. } => self.assemble_query(op, left)?,
 
-            ScriptNode::Call { left, args, .. } => self.assemble_call(left, args)?,
+            ScriptNode::Call { left, args, .. } => self.assemble_call::<false>(left, args)?,
 
             ScriptNode::Index { left, arg, .. } => self.assemble_index(left, arg)?,
 
@@ -940,10 +1056,18 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 self.assemble_expr(value)?;
 
                 let _ = self.cmd_dup(struct_index);
-                let _ = self.cmd_field(origin, field_origin, field);
 
-                let _ =
-                    self.cmd_op_assignment(field_origin, value_origin, field_origin, OpCmd::Assign);
+                let _ = self.cmd_assign_field(
+                    vec![
+                        field_origin,
+                        value_origin,
+                        field_origin,
+                        origin,
+                        field_origin,
+                    ],
+                    field,
+                    OpCmd::Assign,
+                );
             }
         }
 
@@ -1007,6 +1131,25 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
+    fn assemble_embedded(&mut self, start: &TokenRef, end: &TokenRef) -> AnalysisResult<()> {
+        let mut origin = ScriptOrigin::from(start);
+
+        origin.union(&ScriptOrigin::from(end));
+
+        let Some((_, span)) = ScriptNode::extract_embedded(self.doc, start, end) else {
+            let _ = self.cmd_push_nil();
+            return Ok(());
+        };
+
+        let string = self.doc.substring(span).into_owned();
+
+        let index = self.store_string(string);
+
+        let _ = self.cmd_push_string(origin, index);
+
+        Ok(())
+    }
+
     fn assemble_crate(&mut self, token: &TokenRef) -> AnalysisResult<()> {
         let origin = ScriptOrigin::from(token);
         let _ = self.cmd_push_package(origin, self.package);
@@ -1063,7 +1206,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 let field = self.store_string(name);
 
                 let _ = self.cmd_push_package(field_origin, *package);
-                let _ = self.cmd_field(field_origin, field_origin, field);
+                let _ = self.cmd_field(field_origin, field_origin, field, false);
             }
 
             _ => {
@@ -1217,7 +1360,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
             ScriptToken::Dot2 => self.assemble_binary_range(left, token, right)?,
 
-            ScriptToken::Dot => self.assemble_binary_field(left, right)?,
+            ScriptToken::Dot => self.assemble_binary_field::<false>(left, right)?,
 
             ScriptToken::Plus => self.assemble_binary_op(left, token, OpCmd::Add, right)?,
 
@@ -1279,10 +1422,6 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             return self.assemble_binary_assignment(left, op_token, OpCmd::Assign, right);
         };
 
-        let Some(IdentDesc::LocalWrite) = self.ident_desc_map.get(left) else {
-            return self.assemble_binary_assignment(left, op_token, OpCmd::Assign, right);
-        };
-
         let Some(token_string) = token.string(self.doc) else {
             return self.assemble_binary_assignment(left, op_token, OpCmd::Assign, right);
         };
@@ -1293,11 +1432,93 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
         let depth = *depth;
 
+        match self.ident_desc_map.get(left) {
+            Some(IdentDesc::LocalWrite) => (),
+
+            Some(IdentDesc::LocalRead) => {
+                if self.assemble_append(depth, left, op_token, right)? {
+                    return Ok(());
+                }
+
+                return self.assemble_binary_assignment(left, op_token, OpCmd::Assign, right);
+            }
+
+            _ => return self.assemble_binary_assignment(left, op_token, OpCmd::Assign, right),
+        }
+
         self.assemble_expr(right)?;
 
-        let _ = self.cmd_swap(depth);
+        let cmd = self.cmd_swap(depth);
 
-        Ok(())
+        self.mark_var_write(cmd, left, right)
+    }
+
+    // Recognizes the self-append assignment of a string variable
+    // (`s = [s, a, b]` or `s = s + a`), and assembles it into a single command
+    // that could grow the string in place instead of copying the entire
+    // content on every step.
+    //
+    // The type analyzer recognizes the same pattern (see
+    // TypeResolver::is_self_append).
+    fn assemble_append(
+        &mut self,
+        depth: StackDepth,
+        left: &NodeRef,
+        op_token: &TokenRef,
+        right: &NodeRef,
+    ) -> AnalysisResult<bool> {
+        let Some((right, first, rest)) = ScriptNode::extract_self_append(self.doc, left, *right)
+        else {
+            return Ok(false);
+        };
+
+        let Some(IdentDesc::LocalRead) = self.ident_desc_map.get(&first) else {
+            return Ok(false);
+        };
+
+        let Some(left_node) = left.deref(self.doc) else {
+            return Ok(false);
+        };
+
+        let left_type_resolution = left_node
+            .type_resolution()
+            .forward()?
+            .read(self.context)
+            .forward()?;
+
+        if left_type_resolution.tag.type_meta() != Some(<str>::type_meta()) {
+            return Ok(false);
+        }
+
+        let mut origins = Vec::with_capacity(rest.len() + 3);
+
+        origins.push(first.script_origin(self.doc, SpanBounds::Cover));
+
+        for item in rest {
+            origins.push(item.script_origin(self.doc, SpanBounds::Cover));
+
+            self.assemble_expr(item)?;
+        }
+
+        origins.push(right.script_origin(self.doc, SpanBounds::Cover));
+        origins.push(ScriptOrigin::from(op_token));
+
+        let cmd = self.cmd_append(depth, rest.len(), origins);
+
+        self.mark_var_write(cmd, left, &right)?;
+
+        Ok(true)
+    }
+
+    fn unwrap_expr<'a>(&self, mut node_ref: &'a NodeRef) -> &'a NodeRef
+    where
+        'doc: 'a,
+    {
+        while let Some(ScriptNode::Expr { inner, .. }) = node_ref.deref(self.doc) {
+            node_ref = inner;
+        }
+
+        node_ref
     }
 
     fn assemble_binary_assignment(
@@ -1307,6 +1528,11 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         op: OpCmd,
         right: &NodeRef,
     ) -> AnalysisResult<()> {
+        if self.assemble_field_assignment(left, op_token, op, right)? {
+            let _ = self.cmd_push_nil();
+            return Ok(());
+        }
+
         let op_origin = ScriptOrigin::from(op_token);
         let lhs_origin = left.script_origin(self.doc, SpanBounds::Cover);
         let rhs_origin = right.script_origin(self.doc, SpanBounds::Cover);
@@ -1314,12 +1540,104 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         self.assemble_expr(right)?;
         self.assemble_expr(left)?;
 
-        let _ = self.cmd_op_assignment(op_origin, rhs_origin, lhs_origin, op);
+        let cmd = self.cmd_op_assignment(op_origin, rhs_origin, lhs_origin, op);
+
+        self.mark_var_write(cmd, left, right)?;
+
         let _ = self.cmd_push_nil();
 
         Ok(())
     }
 
+    // Assembles an assignment to an object's field (`a.b = c` or `a.b += c`)
+    // into a single command, so that the engine could route the assignment
+    // through the property setter when the field is an exported property.
+    //
+    // Returns false if the left-hand side is not a field access expression.
+    fn assemble_field_assignment(
+        &mut self,
+        left: &NodeRef,
+        op_token: &TokenRef,
+        op: OpCmd,
+        right: &NodeRef,
+    ) -> AnalysisResult<bool> {
+        let Some(ScriptNode::Binary {
+            left: receiver,
+            op: dot,
+            right: field,
+            ..
+        }) = self.unwrap_expr(left).deref(self.doc)
+        else {
+            return Ok(false);
+        };
+
+        let Some(ScriptNode::Op { token: dot, .. }) = dot.deref(self.doc) else {
+            return Ok(false);
+        };
+
+        if dot.deref(self.doc) != Some(ScriptToken::Dot) {
+            return Ok(false);
+        }
+
+        let Some(ScriptNode::Field { token, .. }) = field.deref(self.doc) else {
+            return Ok(false);
+        };
+
+        if token.deref(self.doc) == Some(ScriptToken::Len) {
+            return Ok(false);
+        }
+
+        let Some(field_string) = token.string(self.doc) else {
+            return Ok(false);
+        };
+
+        let op_origin = ScriptOrigin::from(op_token);
+        let rhs_origin = right.script_origin(self.doc, SpanBounds::Cover);
+        let lhs_origin = left.script_origin(self.doc, SpanBounds::Cover);
+        let receiver_origin = receiver.script_origin(self.doc, SpanBounds::Cover);
+        let field_origin = ScriptOrigin::from(token);
+        let field_index = self.store_string(field_string);
+
+        self.assemble_expr(right)?;
+        self.assemble_assignment_receiver(receiver)?;
+
+        let _ = self.cmd_assign_field(
+            vec![
+                op_origin,
+                rhs_origin,
+                lhs_origin,
+                receiver_origin,
+                field_origin,
+            ],
+            field_index,
+            op,
+        );
+
+        Ok(true)
+    }
+
+    // Assembles the receiver of the field assignment (`a.b` in `a.b.c = x`)
+    // marking the field accesses of the receiver's path as the assignment
+    // targets.
+    fn assemble_assignment_receiver(&mut self, receiver: &NodeRef) -> AnalysisResult<()> {
+        let Some(ScriptNode::Binary {
+            left, op, right, ..
+        }) = self.unwrap_expr(receiver).deref(self.doc)
+        else {
+            return self.assemble_expr(receiver);
+        };
+
+        let Some(ScriptNode::Op { token, .. }) = op.deref(self.doc) else {
+            return self.assemble_expr(receiver);
+        };
+
+        if token.deref(self.doc) != Some(ScriptToken::Dot) {
+            return self.assemble_expr(receiver);
+        }
+
+        self.assemble_binary_field::<true>(left, right)
+    }
+
     fn assemble_binary_range(
         &mut self,
         left: &NodeRef,
@@ -1338,7 +1656,44 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
-    fn assemble_binary_field(&mut self, left: &NodeRef, right: &NodeRef) -> AnalysisResult<()> {
+    // Tests whether the match subject belongs to the range of the match arm:
+    // `<lower> <= subject && subject < <upper>`. Both bounds are evaluated
+    // regardless of the first comparison result.
+    fn assemble_range_case(
+        &mut self,
+        subject_depth: StackDepth,
+        subject_origin: ScriptOrigin,
+        lower: &NodeRef,
+        upper: &NodeRef,
+    ) -> AnalysisResult<()> {
+        let lower_origin = lower.script_origin(self.doc, SpanBounds::Cover);
+        let upper_origin = upper.script_origin(self.doc, SpanBounds::Cover);
+
+        let _ = self.cmd_dup(subject_depth);
+        self.assemble_expr(lower)?;
+
+        let _ = self.cmd_op_binary(
+            lower_origin,
+            subject_origin,
+            lower_origin,
+            OpCmd::GreaterOrEqual,
+        );
+
+        let _ = self.cmd_dup(subject_depth);
+        self.assemble_expr(upper)?;
+
+        let _ = self.cmd_op_binary(upper_origin, subject_origin, upper_origin, OpCmd::Lesser);
+
+        let _ = self.cmd_op_binary(lower_origin, lower_origin, upper_origin, OpCmd::And);
+
+        Ok(())
+    }
+
+    fn assemble_binary_field<const TARGET: bool>(
+        &mut self,
+        left: &NodeRef,
+        right: &NodeRef,
+    ) -> AnalysisResult<()> {
         let Some(ScriptNode::Field { token, .. }) = right.deref(self.doc) else {
             let _ = self.cmd_push_nil();
             return Ok(());
@@ -1368,9 +1723,14 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         let field_origin = ScriptOrigin::from(token);
         let field = self.store_string(field_string);
 
-        self.assemble_expr(left)?;
+        match TARGET {
+            true => self.assemble_assignment_receiver(left)?,
+            false => self.assemble_expr(left)?,
+        }
+
+        let cmd = self.cmd_field(lhs_origin, field_origin, field, TARGET);
 
-        let _ = self.cmd_field(lhs_origin, field_origin, field);
+        self.mark_shadowing(cmd, left)?;
 
         Ok(())
     }
@@ -1404,20 +1764,50 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
-    fn assemble_call(&mut self, left: &NodeRef, args: &NodeRef) -> AnalysisResult<()> {
+    fn assemble_call<const TAIL: bool>(
+        &mut self,
+        left: &NodeRef,
+        args: &NodeRef,
+    ) -> AnalysisResult<()> {
         let lhs_origin = left.script_origin(self.doc, SpanBounds::Cover);
         let rhs_origin = args.script_origin(self.doc, SpanBounds::Cover);
 
-        let Some(ScriptNode::CallArgs { args, .. }) = args.deref(self.doc) else {
+        if let Some(value) = self.fold_call(left, args)? {
+            // The engine pushes copies of the precomputed value.
+            if value.ty().prototype().implements_clone() {
+                let mut call_origin = lhs_origin;
+
+                call_origin.union(&rhs_origin);
+
+                let _ = self.cmd_push_const(call_origin, value);
+
+                return Ok(());
+            }
+        }
+
+        let Some(args_node) = args.deref(self.doc) else {
             self.cmd_push_nil();
             return Ok(());
         };
 
+        let (Some(args), Some(names)) = (
+            args_node.call_args(self.doc),
+            args_node.call_arg_names(self.doc),
+        ) else {
+            self.cmd_push_nil();
+            return Ok(());
+        };
+
+        let closure = match args_node {
+            ScriptNode::CallArgs { closure, .. } => !closure.is_nil(),
+            _ => false,
+        };
+
         let arity = args.len();
 
         let mut origins = Vec::with_capacity(arity + 2);
 
-        for arg_ref in args {
+        for arg_ref in &args {
             let arg_origin = arg_ref.script_origin(self.doc, SpanBounds::Cover);
 
             self.assemble_expr(arg_ref)?;
@@ -1430,7 +1820,317 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
         self.assemble_expr(left)?;
 
-        let _ = self.cmd_invoke(arity, origins);
+        if names.iter().any(|name| !name.is_nil()) {
+            let names = names
+                .iter()
+                .map(|name| name.string(self.doc).map(|name| self.store_string(name)))
+                .collect();
+
+            let cmd = self.cmd_named_invoke(names, closure, origins);
+
+            self.mark_shadowing(cmd, left)?;
+
+            return Ok(());
+        }
+
+        let cmd = match TAIL {
+            true => self.cmd_tail_invoke(arity, origins),
+            false => self.cmd_invoke(arity, origins),
+        };
+
+        self.mark_shadowing(cmd, left)?;
+
+        Ok(())
+    }
+
+    // Precomputes the call of an exported pure function (`#[export(pure)]`)
+    // if all arguments of the call are constant expressions (see
+    // ModuleSymbol::expr_value).
+    //
+    // All precomputed calls of the assembly share a single invocation budget.
+    //
+    // Returns None if the call cannot be precomputed.
+    fn fold_call(&mut self, left: &NodeRef, args: &NodeRef) -> AnalysisResult<Option<Cell>> {
+        let left = self.unwrap_expr(left);
+
+        let Some(ScriptNode::Ident { token, .. }) = left.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        let Some(IdentDesc::Import(package)) = self.ident_desc_map.get(left) else {
+            return Ok(None);
+        };
+
+        let Some(name) = token.string(self.doc) else {
+            return Ok(None);
+        };
+
+        let Some(component) = package.ty().prototype().hint_component(name) else {
+            return Ok(None);
+        };
+
+        if !component.pure {
+            return Ok(None);
+        }
+
+        let Some(ScriptNode::CallArgs { closure, .. }) = args.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        if !closure.is_nil() {
+            return Ok(None);
+        }
+
+        let Some(args_node) = args.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        let (Some(args), Some(names)) = (
+            args_node.call_args(self.doc),
+            args_node.call_arg_names(self.doc),
+        ) else {
+            return Ok(None);
+        };
+
+        if names.iter().any(|name| !name.is_nil()) {
+            return Ok(None);
+        }
+
+        let mut arguments = Vec::with_capacity(args.len());
+
+        for arg_ref in &args {
+            let Some(data) = self.fold_const(arg_ref)? else {
+                return Ok(None);
+            };
+
+            arguments.push(Arg {
+                origin: Origin::nil(),
+                data,
+            });
+        }
+
+        Ok(self.pure_calls.invoke(package, component.name, arguments))
+    }
+
+    fn fold_const(&mut self, expr: &NodeRef) -> AnalysisResult<Option<Cell>> {
+        let Some(script_node) = expr.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        match script_node {
+            ScriptNode::Expr { inner, .. } => self.fold_const(inner),
+
+            ScriptNode::Number { semantics, .. } => {
+                let number_semantics = semantics.get().forward()?;
+
+                let number_value = number_semantics.number_value.read(self.context).forward()?;
+
+                Ok(match number_value.deref() {
+                    LocalNumberValue::Usize(Ok(value)) => Cell::give(Origin::nil(), *value).ok(),
+                    LocalNumberValue::Isize(Ok(value)) => Cell::give(Origin::nil(), *value).ok(),
+                    LocalNumberValue::Float(Ok(value)) => Cell::give(Origin::nil(), value.0).ok(),
+                    _ => None,
+                })
+            }
+
+            ScriptNode::Max { .. } => Ok(Cell::give(Origin::nil(), usize::MAX).ok()),
+
+            // Variables assigned exactly once with an integer constant
+            // expression.
+            ScriptNode::Ident { .. } => {
+                let type_resolution = script_node
+                    .type_resolution()
+                    .forward()?
+                    .read(self.context)
+                    .forward()?;
+
+                let Some(value) = type_resolution.constant else {
+                    return Ok(None);
+                };
+
+                match type_resolution.tag.type_meta() {
+                    Some(ty) if ty == <usize>::type_meta() => {
+                        Ok(Cell::give(Origin::nil(), value as usize).ok())
+                    }
+
+                    _ => Ok(Cell::give(Origin::nil(), value).ok()),
+                }
+            }
+
+            ScriptNode::Bool { token, .. } => Ok(match token.deref(self.doc) {
+                Some(ScriptToken::True) => Cell::give(Origin::nil(), true).ok(),
+                Some(ScriptToken::False) => Cell::give(Origin::nil(), false).ok(),
+                _ => None,
+            }),
+
+            ScriptNode::String { start, end, .. } => {
+                let (Some(start), Some(end)) = (start.site(self.doc), end.site(self.doc)) else {
+                    return Ok(None);
+                };
+
+                let span = (start + 1)..end;
+
+                if !span.is_valid_span(self.doc) {
+                    return Ok(None);
+                }
+
+                let string = self.doc.substring(span).into_owned();
+
+                Ok(Cell::give(Origin::nil(), string).ok())
+            }
+
+            ScriptNode::Embedded { start, end, .. } => {
+                let Some((_, span)) = ScriptNode::extract_embedded(self.doc, start, end) else {
+                    return Ok(None);
+                };
+
+                let string = self.doc.substring(span).into_owned();
+
+                Ok(Cell::give(Origin::nil(), string).ok())
+            }
+
+            ScriptNode::UnaryLeft { op, right, .. } => {
+                let Some(ScriptNode::Op { token, .. }) = op.deref(self.doc) else {
+                    return Ok(None);
+                };
+
+                let Some(ScriptToken::Minus) = token.deref(self.doc) else {
+                    return Ok(None);
+                };
+
+                let Some(rhs) = self.fold_const(right)? else {
+                    return Ok(None);
+                };
+
+                Ok(rhs.into_object().neg(Origin::nil(), Origin::nil()).ok())
+            }
+
+            ScriptNode::Call { left, args, .. } => self.fold_call(left, args),
+
+            _ => Ok(None),
+        }
+    }
+
+    // If the expression is a local variable that shadows a package member,
+    // records the variable's declaration for the runtime error message of
+    // the command.
+    fn mark_shadowing(&mut self, cmd: CmdIndex, expr: &NodeRef) -> AnalysisResult<()> {
+        let expr = self.unwrap_expr(expr);
+
+        match self.ident_desc_map.get(expr) {
+            Some(IdentDesc::LocalRead | IdentDesc::Closure) => (),
+            _ => return Ok(()),
+        }
+
+        let Some(ScriptNode::Ident {
+            token, semantics, ..
+        }) = expr.deref(self.doc)
+        else {
+            return Ok(());
+        };
+
+        let Some(name) = token.string(self.doc) else {
+            return Ok(());
+        };
+
+        if self.package.ty().prototype().hint_component(name).is_none() {
+            return Ok(());
+        }
+
+        let ident_semantics = semantics.get().forward()?;
+
+        let cross_resolution = ident_semantics
+            .cross_resolution
+            .read(self.context)
+            .forward()?;
+
+        let IdentCrossResolution::Read { name: desc } = cross_resolution.deref() else {
+            return Ok(());
+        };
+
+        let Some(var_ref) = ScriptNode::decl_var(self.doc, &desc.as_ref().decl, name) else {
+            return Ok(());
+        };
+
+        let decl_origin = var_ref.script_origin(self.doc, SpanBounds::Cover);
+        let decl_origin = self.store_origin(decl_origin);
+        let name = self.store_string(name);
+
+        self.assembly.shadows.push((cmd, decl_origin, name));
+
+        Ok(())
+    }
+
+    // If the assignment's target is a local variable of the current frame,
+    // records the variable's declaration for the write notifications of
+    // the command.
+    fn mark_var_write(
+        &mut self,
+        cmd: CmdIndex,
+        left: &NodeRef,
+        right: &NodeRef,
+    ) -> AnalysisResult<()> {
+        if cmd == RET {
+            return Ok(());
+        }
+
+        let left = self.unwrap_expr(left);
+
+        match self.ident_desc_map.get(left) {
+            Some(IdentDesc::LocalRead | IdentDesc::LocalWrite) => (),
+            _ => return Ok(()),
+        }
+
+        let Some(ScriptNode::Ident {
+            token, semantics, ..
+        }) = left.deref(self.doc)
+        else {
+            return Ok(());
+        };
+
+        let Some(name) = token.string(self.doc) else {
+            return Ok(());
+        };
+
+        let Some(depth) = self.flow_state.namespace.get(name) else {
+            return Ok(());
+        };
+
+        let depth = *depth;
+
+        let ident_semantics = semantics.get().forward()?;
+
+        let cross_resolution = ident_semantics
+            .cross_resolution
+            .read(self.context)
+            .forward()?;
+
+        let decl = match cross_resolution.deref() {
+            IdentCrossResolution::Read { name: desc } => &desc.as_ref().decl,
+            IdentCrossResolution::Write { decl } => decl,
+            _ => return Ok(()),
+        };
+
+        let Some(var_ref) = ScriptNode::decl_var(self.doc, decl, name) else {
+            return Ok(());
+        };
+
+        let Some(ScriptNode::Var { token: var, .. }) = var_ref.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let decl_origin = self.store_origin(ScriptOrigin::from(var));
+
+        let mut origin = left.script_origin(self.doc, SpanBounds::Cover);
+
+        origin.union(&right.script_origin(self.doc, SpanBounds::Cover));
+
+        let origin = self.store_origin(origin);
+        let name = self.store_string(name);
+
+        self.assembly
+            .var_writes
+            .push((cmd, depth, decl_origin, name, origin));
 
         Ok(())
     }
@@ -1563,6 +2263,17 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         self.cmd_1(origin, Cmd::PushFloat(PushFloatCmd { value }))
     }
 
+    #[inline(always)]
+    fn cmd_push_const(&mut self, origin: impl Into<Origin>, value: Cell) -> CmdIndex {
+        let const_index = self.assembly.consts.len();
+
+        self.assembly.consts.push(ConstCell(value));
+
+        self.inc_stack(1);
+
+        self.cmd_1(origin, Cmd::PushConst(PushConstCmd { const_index }))
+    }
+
     #[inline(always)]
     fn cmd_push_string(
         &mut self,
@@ -1610,6 +2321,26 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         self.cmd_1(struct_origin, Cmd::PushStruct(PushStructCmd))
     }
 
+    #[inline(always)]
+    fn cmd_push_variant(
+        &mut self,
+        variant_origin: impl Into<Origin>,
+        enum_index: StringIndex,
+        name_index: StringIndex,
+        tag: usize,
+    ) -> CmdIndex {
+        self.inc_stack(1);
+
+        self.cmd_1(
+            variant_origin,
+            Cmd::PushVariant(PushVariantCmd {
+                enum_index,
+                name_index,
+                tag,
+            }),
+        )
+    }
+
     #[inline(always)]
     fn cmd_range(
         &mut self,
@@ -1637,17 +2368,34 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         self.cmd_many(origins, Cmd::Concat(ConcatCmd { items }))
     }
 
+    #[inline(always)]
+    fn cmd_append(
+        &mut self,
+        depth: StackDepth,
+        items: usize,
+        origins: Vec<ScriptOrigin>,
+    ) -> CmdIndex {
+        self.dec_stack(items);
+        self.inc_stack(1);
+
+        self.cmd_many(origins, Cmd::Append(AppendCmd { depth, items }))
+    }
+
     #[inline(always)]
     fn cmd_field(
         &mut self,
         lhs_origin: impl Into<Origin>,
         field_origin: impl Into<Origin>,
         field_index: StringIndex,
+        target: bool,
     ) -> CmdIndex {
         self.cmd_2(
             lhs_origin,
             field_origin,
-            Cmd::Field(FieldCmd { field_index }),
+            Cmd::Field(FieldCmd {
+                field_index,
+                target,
+            }),
         )
     }
 
@@ -1691,6 +2439,21 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         self.cmd_3(op_origin, rhs_origin, lhs_origin, Cmd::Op(op))
     }
 
+    #[inline(always)]
+    fn cmd_assign_field(
+        &mut self,
+        origins: Vec<ScriptOrigin>,
+        field_index: StringIndex,
+        op: OpCmd,
+    ) -> CmdIndex {
+        self.dec_stack(2);
+
+        self.cmd_many(
+            origins,
+            Cmd::AssignField(AssignFieldCmd { field_index, op }),
+        )
+    }
+
     #[inline(always)]
     fn cmd_op_unary(
         &mut self,
@@ -1708,6 +2471,25 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         self.cmd_many(origins, Cmd::Invoke(InvokeCmd { arity }))
     }
 
+    #[inline(always)]
+    fn cmd_tail_invoke(&mut self, arity: usize, origins: Vec<ScriptOrigin>) -> CmdIndex {
+        self.dec_stack(arity);
+
+        self.cmd_many(origins, Cmd::TailInvoke(TailInvokeCmd { arity }))
+    }
+
+    #[inline(always)]
+    fn cmd_named_invoke(
+        &mut self,
+        names: Vec<Option<StringIndex>>,
+        closure: bool,
+        origins: Vec<ScriptOrigin>,
+    ) -> CmdIndex {
+        self.dec_stack(names.len());
+
+        self.cmd_many(origins, Cmd::NamedInvoke(NamedInvokeCmd { names, closure }))
+    }
+
     #[inline(always)]
     fn cmd_index(
         &mut self,
@@ -1870,6 +2652,17 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                     diff -= 1;
                 }
 
+                Some(Cmd::PushConst(PushConstCmd { const_index })) => {
+                    if *const_index == self.assembly.consts.len() - 1 {
+                        let _ = self.assembly.consts.pop();
+                    }
+
+                    let _ = self.assembly.sources.pop();
+                    let _ = self.assembly.commands.pop();
+                    self.flow_state.stack_depth -= 1;
+                    diff -= 1;
+                }
+
                 Some(Cmd::PushString(PushStringCmd { string_index })) => {
                     if *string_index == self.assembly.strings.len() - 1 {
                         let Entry::Occupied(rev_entry) = self.string_rev_index.entry(*string_index)
@@ -0,0 +1,470 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::ops::Deref;
+
+use ahash::{AHashMap, AHashSet};
+use compact_str::CompactString;
+use lady_deirdre::{
+    analysis::{AnalysisResult, AttrContext, Computable, TaskHandle},
+    arena::Identifiable,
+    sync::{Shared, SyncBuildHasher},
+    syntax::{AbstractNode, NodeRef, PolyRef},
+};
+
+use crate::{
+    analysis::{ModuleResultEx, ScriptIssue},
+    exports::Struct,
+    runtime::PackageMeta,
+    semantics::{setup::log_attr, *},
+    syntax::{ScriptClass, ScriptDoc, ScriptNode, ScriptToken},
+};
+
+#[derive(Default, Clone, PartialEq, Eq)]
+pub(crate) struct DeadCodeAllow {
+    pub(crate) patterns: Vec<CompactString>,
+}
+
+impl DeadCodeAllow {
+    pub(crate) fn allows(&self, name: &str) -> bool {
+        let name = name.chars().collect::<Vec<_>>();
+
+        for pattern in &self.patterns {
+            let pattern = pattern.chars().collect::<Vec<_>>();
+
+            if match_wildcard(&pattern, &name) {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+#[derive(Default, Clone, PartialEq, Eq)]
+pub(crate) struct DeadCode {
+    pub(crate) issues: Shared<AHashSet<ScriptIssue>>,
+}
+
+impl Computable for DeadCode {
+    type Node = ScriptNode;
+
+    fn compute<H: TaskHandle, S: SyncBuildHasher>(
+        context: &mut AttrContext<Self::Node, H, S>,
+    ) -> AnalysisResult<Self> {
+        log_attr!(context);
+
+        let id = context.node_ref().id;
+
+        let doc_read = context.read_doc(id).forward()?;
+
+        let allow = context
+            .common()
+            .dead_code_allow
+            .read(context)
+            .forward()?
+            .deref()
+            .clone();
+
+        let mut collection = DeadCodeCollection {
+            doc: doc_read.deref(),
+            context,
+            allow,
+            reads: AHashMap::new(),
+            entry_reads: AHashSet::new(),
+            accessed_structs: AHashSet::new(),
+            issues: AHashSet::new(),
+        };
+
+        collection.collect_reads()?;
+        collection.collect_entry_reads()?;
+        collection.collect_issues()?;
+
+        Ok(Self {
+            issues: Shared::new(collection.issues),
+        })
+    }
+}
+
+struct DeadCodeCollection<'a, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher> {
+    doc: &'a ScriptDoc,
+    context: &'ctx mut AttrContext<'ctx_param, ScriptNode, H, S>,
+    allow: DeadCodeAllow,
+    reads: AHashMap<NodeRef, Vec<NodeRef>>,
+    entry_reads: AHashSet<(NodeRef, CompactString)>,
+    accessed_structs: AHashSet<NodeRef>,
+    issues: AHashSet<ScriptIssue>,
+}
+
+impl<'a, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
+    DeadCodeCollection<'a, 'ctx, 'ctx_param, H, S>
+{
+    fn collect_reads(&mut self) -> AnalysisResult<()> {
+        let all_ident_refs = self
+            .context
+            .read_class(self.doc.id(), &ScriptClass::AllIdents)
+            .forward()?;
+
+        for ident_ref in all_ident_refs.as_ref() {
+            let Some(ScriptNode::Ident { semantics, .. }) = ident_ref.deref(self.doc) else {
+                continue;
+            };
+
+            let ident_semantics = semantics.get().forward()?;
+
+            let cross_resolution = ident_semantics
+                .cross_resolution
+                .read(self.context)
+                .forward()?;
+
+            let IdentCrossResolution::Read { name } = cross_resolution.deref() else {
+                continue;
+            };
+
+            self.reads
+                .entry(name.as_ref().decl)
+                .or_default()
+                .push(*ident_ref);
+        }
+
+        Ok(())
+    }
+
+    fn collect_entry_reads(&mut self) -> AnalysisResult<()> {
+        let all_field_refs = self
+            .context
+            .read_class(self.doc.id(), &ScriptClass::AllFields)
+            .forward()?;
+
+        for field_ref in all_field_refs.as_ref() {
+            let Some(ScriptNode::Field { parent, token, .. }) = field_ref.deref(self.doc) else {
+                continue;
+            };
+
+            let Some(ScriptNode::Binary {
+                parent: access_parent,
+                left,
+                ..
+            }) = parent.deref(self.doc)
+            else {
+                continue;
+            };
+
+            let Some(left_node) = left.deref(self.doc) else {
+                continue;
+            };
+
+            let type_resolution = left_node
+                .type_resolution()
+                .forward()?
+                .read(self.context)
+                .forward()?;
+
+            let Tag::Struct(struct_ref) = &type_resolution.tag else {
+                continue;
+            };
+
+            let _ = self.accessed_structs.insert(*struct_ref);
+
+            if self.is_assignment_target(parent, access_parent) {
+                continue;
+            }
+
+            let Some(field_string) = token.string(self.doc) else {
+                continue;
+            };
+
+            let _ = self
+                .entry_reads
+                .insert((*struct_ref, CompactString::from(field_string)));
+        }
+
+        Ok(())
+    }
+
+    fn collect_issues(&mut self) -> AnalysisResult<()> {
+        let all_scopes_refs = self
+            .context
+            .read_class(self.doc.id(), &ScriptClass::AllScopes)
+            .forward()?;
+
+        for scope_ref in all_scopes_refs.as_ref() {
+            let Some(scope_node) = scope_ref.deref(self.doc) else {
+                continue;
+            };
+
+            let locals = scope_node.locals().forward()?;
+
+            let lets = locals.lets.read(self.context).forward()?;
+
+            for var_ref in lets.as_ref().map.keys() {
+                self.check_fn_var(var_ref);
+            }
+
+            let struct_keys = locals.struct_keys.read(self.context).forward()?;
+
+            for (struct_ref, entries) in &struct_keys.as_ref().map {
+                self.check_struct(struct_ref, entries.as_ref());
+            }
+
+            let packages = locals.packages.read(self.context).forward()?;
+
+            for (package_ref, resolution) in &packages.as_ref().map {
+                self.check_import(package_ref, resolution);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_fn_var(&mut self, var_ref: &NodeRef) {
+        let Some(ScriptNode::Var { parent, token, .. }) = var_ref.deref(self.doc) else {
+            return;
+        };
+
+        let Some(ScriptNode::Let { value, .. }) = parent.deref(self.doc) else {
+            return;
+        };
+
+        let Some(ScriptNode::Fn { node: fn_ref, .. }) = descend_expr(self.doc, value) else {
+            return;
+        };
+
+        let Some(var_string) = token.string(self.doc) else {
+            return;
+        };
+
+        if self.allow.allows(var_string) {
+            return;
+        }
+
+        if let Some(reads) = self.reads.get(parent) {
+            for ident_ref in reads {
+                if !self.is_descendant(ident_ref, fn_ref) {
+                    return;
+                }
+            }
+        }
+
+        let _ = self
+            .issues
+            .insert(ScriptIssue::UnusedFunction { var_ref: *var_ref });
+    }
+
+    fn check_struct(&mut self, struct_ref: &NodeRef, entries: &LocalStructEntriesVecSyntax) {
+        // Structures that are never accessed by field within the module are
+        // likely consumed by the host, so their entries are not reported.
+        if !self.accessed_structs.contains(struct_ref) {
+            return;
+        }
+
+        for (key, entry_key_ref, _) in &entries.vec {
+            if self.allow.allows(key) {
+                continue;
+            }
+
+            if Struct::is_operator_entry(key) {
+                continue;
+            }
+
+            if self.entry_reads.contains(&(*struct_ref, key.clone())) {
+                continue;
+            }
+
+            let _ = self.issues.insert(ScriptIssue::UnreadEntry {
+                entry_key_ref: *entry_key_ref,
+            });
+        }
+    }
+
+    fn check_import(&mut self, package_ref: &NodeRef, resolution: &LocalPackageResolution) {
+        let (Some(parent), Some(package)) = (resolution.parent, resolution.package) else {
+            return;
+        };
+
+        let Some(ScriptNode::Package {
+            parent: use_ref,
+            token,
+            ..
+        }) = package_ref.deref(self.doc)
+        else {
+            return;
+        };
+
+        let Some(ScriptNode::Use { packages, .. }) = use_ref.deref(self.doc) else {
+            return;
+        };
+
+        if packages.last() != Some(package_ref) {
+            return;
+        }
+
+        let Some(package_string) = token.string(self.doc) else {
+            return;
+        };
+
+        if self.allow.allows(package_string) {
+            return;
+        }
+
+        let Some(reads) = self.reads.get(use_ref) else {
+            let _ = self.issues.insert(ScriptIssue::UnusedImport {
+                use_ref: *use_ref,
+                package_ref: NodeRef::nil(),
+            });
+
+            return;
+        };
+
+        // If every imported name that the module reads is re-exported by the
+        // parent package, the last path component is redundant.
+        for ident_ref in reads {
+            let Some(ident_string) = ScriptNode::extract_atom_string(self.doc, ident_ref) else {
+                return;
+            };
+
+            if !is_reexport(parent, package, ident_string) {
+                return;
+            }
+        }
+
+        let _ = self.issues.insert(ScriptIssue::UnusedImport {
+            use_ref: *use_ref,
+            package_ref: match packages.len() > 1 {
+                true => *package_ref,
+                false => NodeRef::nil(),
+            },
+        });
+    }
+
+    fn is_assignment_target(&self, access_ref: &'a NodeRef, mut parent_ref: &'a NodeRef) -> bool {
+        let mut target_ref = access_ref;
+
+        loop {
+            match parent_ref.deref(self.doc) {
+                Some(ScriptNode::Expr { node, parent, .. }) => {
+                    target_ref = node;
+                    parent_ref = parent;
+                }
+
+                Some(ScriptNode::Binary { left, op, .. }) => {
+                    return left == target_ref
+                        && ScriptNode::extract_op(self.doc, op) == Some(ScriptToken::Assign);
+                }
+
+                _ => return false,
+            }
+        }
+    }
+
+    fn is_descendant(&self, node_ref: &NodeRef, ancestor_ref: &NodeRef) -> bool {
+        let mut current = *node_ref;
+
+        loop {
+            if &current == ancestor_ref {
+                return true;
+            }
+
+            let Some(script_node) = current.deref(self.doc) else {
+                return false;
+            };
+
+            current = script_node.parent_ref();
+
+            if current.is_nil() {
+                return false;
+            }
+        }
+    }
+}
+
+fn is_reexport(parent: &'static PackageMeta, package: &'static PackageMeta, name: &str) -> bool {
+    let Some(component) = package.ty().prototype().hint_component(name) else {
+        return false;
+    };
+
+    let Some(parent_component) = parent.ty().prototype().hint_component(name) else {
+        return false;
+    };
+
+    component.ty == parent_component.ty && component.doc == parent_component.doc
+}
+
+fn descend_expr<'a>(doc: &'a ScriptDoc, mut expr: &'a NodeRef) -> Option<&'a ScriptNode> {
+    loop {
+        let script_node = expr.deref(doc)?;
+
+        match script_node {
+            ScriptNode::Expr { inner, .. } => {
+                expr = inner;
+            }
+
+            _ => return Some(script_node),
+        }
+    }
+}
+
+fn match_wildcard(pattern: &[char], name: &[char]) -> bool {
+    let mut pattern_cursor = 0;
+    let mut name_cursor = 0;
+    let mut backtrack = None;
+
+    while name_cursor < name.len() {
+        match pattern.get(pattern_cursor) {
+            Some('*') => {
+                pattern_cursor += 1;
+                backtrack = Some((pattern_cursor, name_cursor));
+                continue;
+            }
+
+            Some(ch) if *ch == '?' || *ch == name[name_cursor] => {
+                pattern_cursor += 1;
+                name_cursor += 1;
+                continue;
+            }
+
+            _ => (),
+        }
+
+        let Some((pattern_restart, name_restart)) = backtrack else {
+            return false;
+        };
+
+        pattern_cursor = pattern_restart;
+        name_cursor = name_restart + 1;
+        backtrack = Some((pattern_restart, name_restart + 1));
+    }
+
+    pattern[pattern_cursor..].iter().all(|ch| *ch == '*')
+}
@@ -38,17 +38,26 @@ use ahash::AHashSet;
 use compact_str::CompactString;
 use lady_deirdre::{
     analysis::{AnalysisResult, AttrContext, Computable, TaskHandle, DOC_ERRORS_EVENT},
-    arena::Identifiable,
+    arena::{Id, Identifiable},
+    lexis::SourceCode,
     sync::{Shared, SyncBuildHasher},
     syntax::{NodeRef, PolyRef, SyntaxTree},
 };
 
 use crate::{
-    analysis::{Closeness, DiagnosticsDepth, ModuleResultEx, ScriptIssue, StringEstimation},
+    analysis::{
+        validate_embedded,
+        Closeness,
+        DiagnosticsDepth,
+        ModuleMetadata,
+        ModuleResultEx,
+        ScriptIssue,
+        StringEstimation,
+    },
     report::system_panic,
-    runtime::{PackageMeta, ScriptType},
+    runtime::{ArgNameError, PackageMeta, Param, ScriptIterator, ScriptType},
     semantics::{setup::log_attr, *},
-    syntax::{ScriptClass, ScriptDoc, ScriptNode, ScriptToken},
+    syntax::{Precedence, ScriptClass, ScriptDoc, ScriptNode, ScriptToken},
 };
 
 #[derive(Default, Clone, PartialEq, Eq)]
@@ -117,6 +126,19 @@ impl Computable for CrossDiagnostics<2> {
             }
         }
 
+        let ScriptNode::Root { semantics, .. } = doc_read.root() else {
+            system_panic!("Incorrect root variant.");
+        };
+
+        let module_header = semantics
+            .get()
+            .forward()?
+            .module_header
+            .read(context)
+            .forward()?;
+
+        collect_metadata_issues(id, module_header.metadata.as_ref(), &mut issues);
+
         Ok(Self {
             issues: Shared::new(issues),
         })
@@ -155,12 +177,66 @@ impl Computable for CrossDiagnostics<3> {
             }
         }
 
+        let ScriptNode::Root { semantics, .. } = doc_read.root() else {
+            system_panic!("Incorrect root variant.");
+        };
+
+        let dead_code = semantics
+            .get()
+            .forward()?
+            .dead_code
+            .read(context)
+            .forward()?;
+
+        issues.reserve(dead_code.issues.as_ref().len());
+
+        for issue in dead_code.issues.as_ref() {
+            let _ = issues.insert(issue.clone());
+        }
+
         Ok(Self {
             issues: Shared::new(issues),
         })
     }
 }
 
+fn collect_metadata_issues(id: Id, metadata: &ModuleMetadata, issues: &mut AHashSet<ScriptIssue>) {
+    for (origin, message) in &metadata.invalid {
+        let _ = issues.insert(ScriptIssue::InvalidMetadata {
+            origin: *origin,
+            message: message.clone(),
+        });
+    }
+
+    if let Some(engine) = metadata.engine() {
+        if !engine.is_satisfied() {
+            let _ = issues.insert(ScriptIssue::IncompatibleEngine {
+                origin: engine.origin(),
+                version: CompactString::from(engine.version()),
+            });
+        }
+    }
+
+    let Some(package) = PackageMeta::by_id(id) else {
+        system_panic!("Missing package.");
+    };
+
+    for requirement in metadata.packages() {
+        if requirement.is_satisfied_by(package) {
+            continue;
+        }
+
+        let _ = issues.insert(ScriptIssue::MissingPackage {
+            origin: requirement.origin(),
+            name: CompactString::from(requirement.name()),
+            version: requirement.version().map(CompactString::from),
+            found: requirement
+                .resolve_in(package)
+                .map(|package| package.version()),
+        });
+    }
+}
+
 #[derive(Default, Clone, PartialEq, Eq)]
 pub(crate) struct LocalDiagnostics<const DEPTH: DiagnosticsDepth> {
     pub(crate) issues: AHashSet<ScriptIssue>,
@@ -226,14 +302,17 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
                 self.collect_import_issues()?;
                 self.collect_loop_issues()?;
                 self.collect_signature_issues()?;
+                self.collect_shadowing_issues()?;
                 self.collect_expr_issues()?;
                 self.collect_reachability_issues()?;
             }
 
             3 => {
                 self.collect_expr_issues()?;
+                self.collect_var_issues()?;
                 self.collect_return_inconsistency_issues()?;
                 self.collect_st_type_issues()?;
+                self.collect_match_exhaustiveness_issues()?;
             }
 
             _ => (),
@@ -285,6 +364,10 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
             let mut best_match = (Closeness::zero(), "");
 
             for component in parent_prototype.hint_all_components() {
+                if !component.ty.is_package() {
+                    continue;
+                }
+
                 let estimation = component.name.estimate(package_atom);
 
                 if estimation <= best_match.0 {
@@ -340,27 +423,90 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
         Ok(())
     }
 
+    fn collect_shadowing_issues(&mut self) -> AnalysisResult<()> {
+        let config = *self
+            .context
+            .common()
+            .analysis_config
+            .read(self.context)
+            .forward()?;
+
+        if !config.shadowing_warnings {
+            return Ok(());
+        }
+
+        let Some(package) = PackageMeta::by_id(self.doc.id()) else {
+            return Ok(());
+        };
+
+        let prototype = package.ty().prototype();
+
+        let vars = self.local_analysis.syntax.as_ref().vars.as_ref();
+
+        for (var_ref, var_syntax) in &vars.map {
+            match var_syntax {
+                LocalVarSyntax::Let(..) | LocalVarSyntax::Param(..) => (),
+                _ => continue,
+            }
+
+            let Some(name) = ScriptNode::extract_atom_string(self.doc, var_ref) else {
+                continue;
+            };
+
+            let Some(component) = prototype.hint_component(name) else {
+                continue;
+            };
+
+            let _ = self.issues.insert(ScriptIssue::ShadowedMember {
+                var_ref: *var_ref,
+                component: component.name,
+            });
+        }
+
+        Ok(())
+    }
+
     fn collect_expr_issues(&mut self) -> AnalysisResult<()> {
         let exprs = self.local_analysis.syntax.as_ref().exprs.as_ref();
 
         for (expr_ref, expr_syntax) in &exprs.map {
             if expr_ref != expr_syntax.node_ref() {
+                // Parenthesized expressions share the inner expression's
+                // syntax, but they could reach the analysis limits on their own.
+                if DEPTH == 3 {
+                    self.collect_expr_type_issues(expr_ref)?;
+                }
+
                 continue;
             }
 
             match DEPTH {
                 2 => match expr_syntax {
                     LocalExprSyntax::Infix(..) => {
-                        self.collect_literal_assignment_issues(expr_ref)?
+                        self.collect_literal_assignment_issues(expr_ref)?;
+                        self.collect_precedence_issues(expr_ref)?;
                     }
                     LocalExprSyntax::Struct(..) => self.collect_struct_issues(expr_ref)?,
                     LocalExprSyntax::Number(..) => self.collect_number_issues(expr_ref)?,
+                    LocalExprSyntax::String(..) => self.collect_embedded_issues(expr_ref)?,
                     LocalExprSyntax::Ident(..) => self.collect_ident_issues(expr_ref)?,
+                    LocalExprSyntax::Call(..) => self.collect_call_issues(expr_ref)?,
 
                     _ => (),
                 },
 
-                3 => self.collect_expr_type_issues(expr_ref)?,
+                3 => {
+                    match expr_syntax {
+                        LocalExprSyntax::Infix(..) => {
+                            self.collect_temporary_assignment_issues(expr_ref)?
+                        }
+                        LocalExprSyntax::Fn(..) => self.collect_capture_issues(expr_ref)?,
+
+                        _ => (),
+                    }
+
+                    self.collect_expr_type_issues(expr_ref)?
+                }
 
                 _ => (),
             }
@@ -376,20 +522,8 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
             return Ok(());
         };
 
-        match infix_syntax.op {
-            ScriptToken::Assign
-            | ScriptToken::PlusAssign
-            | ScriptToken::MinusAssign
-            | ScriptToken::MulAssign
-            | ScriptToken::DivAssign
-            | ScriptToken::BitAndAssign
-            | ScriptToken::BitOrAssign
-            | ScriptToken::BitXorAssign
-            | ScriptToken::ShlAssign
-            | ScriptToken::ShrAssign
-            | ScriptToken::RemAssign => (),
-
-            _ => return Ok(()),
+        if !is_assignment_op(infix_syntax.op) {
+            return Ok(());
         }
 
         let exprs = self.local_analysis.syntax.as_ref().exprs.as_ref();
@@ -413,6 +547,208 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
         Ok(())
     }
 
+    // Suggests parentheses around the non-parenthesized operands of the binary
+    // operator that mix bitwise operators with arithmetic or comparison
+    // operators (e.g., `a + b << c`).
+    fn collect_precedence_issues(&mut self, infix_ref: &NodeRef) -> AnalysisResult<()> {
+        let infixes = self.local_analysis.syntax.as_ref().infixes.as_ref();
+
+        let Some(infix_syntax) = infixes.map.get(infix_ref) else {
+            return Ok(());
+        };
+
+        let Some(category) = OpCategory::of(infix_syntax.op) else {
+            return Ok(());
+        };
+
+        let exprs = self.local_analysis.syntax.as_ref().exprs.as_ref();
+
+        for operand_ref in [&infix_syntax.left, &infix_syntax.right] {
+            let Some(LocalExprSyntax::Infix(inner_ref)) = exprs.map.get(operand_ref) else {
+                continue;
+            };
+
+            // The operand is enclosed in parentheses.
+            if inner_ref != operand_ref {
+                continue;
+            }
+
+            let Some(inner_syntax) = infixes.map.get(inner_ref) else {
+                continue;
+            };
+
+            let Some(inner_category) = OpCategory::of(inner_syntax.op) else {
+                continue;
+            };
+
+            if !category.is_ambiguous_with(inner_category) {
+                continue;
+            }
+
+            let Some(span) = inner_ref.span(self.doc) else {
+                continue;
+            };
+
+            let mut quickfix = CompactString::from("(");
+
+            quickfix.push_str(self.doc.substring(span).as_ref());
+            quickfix.push(')');
+
+            let _ = self.issues.insert(ScriptIssue::AmbiguousPrecedence {
+                operand_ref: *inner_ref,
+                quickfix,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn collect_embedded_issues(&mut self, embedded_ref: &NodeRef) -> AnalysisResult<()> {
+        let Some(ScriptNode::Embedded { start, end, .. }) = embedded_ref.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let Some((language, body)) = ScriptNode::extract_embedded(self.doc, start, end) else {
+            return Ok(());
+        };
+
+        let body = self.doc.substring(body);
+
+        for issue in validate_embedded(language, body.as_ref()) {
+            let _ = self.issues.insert(ScriptIssue::Embedded {
+                embedded_ref: *embedded_ref,
+                span: issue.span,
+                severity: issue.severity,
+                message: CompactString::from(issue.message),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn collect_temporary_assignment_issues(&mut self, infix_ref: &NodeRef) -> AnalysisResult<()> {
+        let infixes = self.local_analysis.syntax.as_ref().infixes.as_ref();
+
+        let Some(infix_syntax) = infixes.map.get(infix_ref) else {
+            return Ok(());
+        };
+
+        if !is_assignment_op(infix_syntax.op) {
+            return Ok(());
+        }
+
+        let mut target_ref = &infix_syntax.left;
+        let mut direct = true;
+
+        loop {
+            let Some(target_syntax) = infixes.map.get(target_ref) else {
+                return Ok(());
+            };
+
+            if target_syntax.op != ScriptToken::Dot {
+                return Ok(());
+            }
+
+            let Some(ScriptNode::Field { semantics, .. }) = target_syntax.right.deref(self.doc)
+            else {
+                return Ok(());
+            };
+
+            let field_atom = semantics
+                .get()
+                .forward()?
+                .atom_syntax
+                .read(self.context)
+                .forward()?;
+
+            let Some(receiver_node) = target_syntax.left.deref(self.doc) else {
+                return Ok(());
+            };
+
+            let receiver_type_resolution = receiver_node
+                .type_resolution()
+                .forward()?
+                .read(self.context)
+                .forward()?;
+
+            if let Some(receiver) = receiver_type_resolution.tag.type_meta() {
+                if !receiver.is_dynamic() {
+                    if let Some(component) = receiver.prototype().hint_component(&field_atom.0) {
+                        if direct && !component.writable {
+                            let _ = self.issues.insert(ScriptIssue::ReadOnlyProperty {
+                                field_ref: target_syntax.right,
+                                receiver,
+                                component: component.name,
+                            });
+                        }
+
+                        // Property getters return a copy of the value.
+                        if component.owned || (!direct && component.property) {
+                            let _ = self.issues.insert(ScriptIssue::TemporaryAssignment {
+                                field_ref: target_syntax.right,
+                                receiver,
+                                component: component.name,
+                            });
+                        }
+                    }
+                }
+            }
+
+            target_ref = &target_syntax.left;
+            direct = false;
+        }
+    }
+
+    fn collect_capture_issues(&mut self, fn_ref: &NodeRef) -> AnalysisResult<()> {
+        let Some(ScriptNode::Fn { semantics, .. }) = fn_ref.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let Some(fn_span) = fn_ref.span(self.doc) else {
+            return Ok(());
+        };
+
+        let vars = self.local_analysis.syntax.as_ref().vars.as_ref();
+
+        let fn_semantics = semantics.get().forward()?;
+
+        let captures = fn_semantics.captures.read(self.context).forward()?;
+
+        for var_ref in &captures.set {
+            // The variables of the outer scopes are reported by the outer
+            // functions that capture them.
+            if !vars.map.contains_key(var_ref) {
+                continue;
+            }
+
+            let Some(ScriptNode::Var { semantics, .. }) = var_ref.deref(self.doc) else {
+                continue;
+            };
+
+            let var_semantics = semantics.get().forward()?;
+
+            let let_inits = var_semantics.let_inits.read(self.context).forward()?;
+
+            for init_ref in &let_inits.as_ref().set {
+                let Some(init_span) = init_ref.span(self.doc) else {
+                    continue;
+                };
+
+                if init_span.start < fn_span.end {
+                    continue;
+                }
+
+                let _ = self.issues.insert(ScriptIssue::LateCapture {
+                    fn_ref: *fn_ref,
+                    var_ref: *var_ref,
+                    init_ref: *init_ref,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     fn collect_struct_issues(&mut self, struct_ref: &NodeRef) -> AnalysisResult<()> {
         let struct_entries = self
             .local_analysis
@@ -440,6 +776,105 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
         Ok(())
     }
 
+    fn collect_call_issues(&mut self, call_ref: &NodeRef) -> AnalysisResult<()> {
+        let calls = self.local_analysis.syntax.as_ref().calls.as_ref();
+
+        let Some(call_syntax) = calls.map.get(call_ref) else {
+            return Ok(());
+        };
+
+        let call_syntax = call_syntax.as_ref();
+
+        if !call_syntax.is_named() {
+            return Ok(());
+        }
+
+        let Some(name_refs) = call_syntax
+            .call_args_ref
+            .deref(self.doc)
+            .and_then(|node| node.call_arg_names(self.doc))
+        else {
+            return Ok(());
+        };
+
+        let Some(left_node) = call_syntax.left.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let left_resolution = left_node
+            .type_resolution()
+            .forward()?
+            .read(self.context)
+            .forward()?;
+
+        let params = match left_resolution.tag.is_dynamic() {
+            true => None,
+            false => left_resolution
+                .tag
+                .invocation_meta()
+                .map(|meta| meta.inputs.as_deref().unwrap_or(&[])),
+        };
+
+        // The parameter names of dynamically typed functions are not known
+        // statically, but the arguments could still be malformed on their own.
+        let Some(params) = params else {
+            let positional = call_syntax.names.len() - call_syntax.closure as usize;
+
+            let mut names = AHashSet::new();
+
+            for (index, name) in call_syntax.names[..positional].iter().enumerate() {
+                let Some(arg_ref) = call_syntax.args.get(index) else {
+                    continue;
+                };
+
+                match name {
+                    None if !names.is_empty() => {
+                        let _ = self
+                            .issues
+                            .insert(ScriptIssue::MisplacedArgument { arg_ref: *arg_ref });
+                    }
+
+                    Some(name) if !names.insert(name.as_str()) => {
+                        let _ = self.issues.insert(ScriptIssue::DuplicateArgument {
+                            arg_ref: *arg_ref,
+                            name_ref: name_refs.get(index).copied().unwrap_or_default(),
+                            quickfix: CompactString::from(""),
+                        });
+                    }
+
+                    _ => (),
+                }
+            }
+
+            return Ok(());
+        };
+
+        let Err(error) = call_syntax.arrange(params) else {
+            return Ok(());
+        };
+
+        let issue = match error {
+            ArgNameError::Unknown(index) => ScriptIssue::UnknownArgument {
+                name_ref: name_refs.get(index).copied().unwrap_or_default(),
+                quickfix: closest_param(params, call_syntax, index),
+            },
+
+            ArgNameError::Duplicate(index, _) => ScriptIssue::DuplicateArgument {
+                arg_ref: call_syntax.args.get(index).copied().unwrap_or_default(),
+                name_ref: name_refs.get(index).copied().unwrap_or_default(),
+                quickfix: closest_param(params, call_syntax, index),
+            },
+
+            ArgNameError::Misplaced(index) => ScriptIssue::MisplacedArgument {
+                arg_ref: call_syntax.args.get(index).copied().unwrap_or_default(),
+            },
+        };
+
+        let _ = self.issues.insert(issue);
+
+        Ok(())
+    }
+
     fn collect_number_issues(&mut self, number_ref: &NodeRef) -> AnalysisResult<()> {
         let Some(ScriptNode::Number { semantics, .. }) = number_ref.deref(self.doc) else {
             return Ok(());
@@ -530,6 +965,7 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
             IdentCrossResolution::Read { name } if !name.as_ref().init => {
                 let _ = self.issues.insert(ScriptIssue::ReadUninit {
                     ident_ref: *ident_ref,
+                    decl_ref: name.as_ref().decl,
                 });
             }
 
@@ -557,6 +993,16 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
         Ok(())
     }
 
+    fn collect_var_issues(&mut self) -> AnalysisResult<()> {
+        let vars = self.local_analysis.syntax.as_ref().vars.as_ref();
+
+        for var_ref in vars.map.keys() {
+            self.collect_expr_type_issues(var_ref)?;
+        }
+
+        Ok(())
+    }
+
     fn collect_reachability_issues(&mut self) -> AnalysisResult<()> {
         let unreachable_statements = self
             .local_analysis
@@ -660,36 +1106,6 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
             }
         }
 
-        let range_family = <Range<usize>>::type_meta().family();
-
-        let fors = self.local_analysis.syntax.as_ref().fors.as_ref();
-
-        for (_, syntax) in &fors.map {
-            let Some(range_node) = syntax.range.deref(self.doc) else {
-                continue;
-            };
-
-            let range_type_resolution = range_node
-                .type_resolution()
-                .forward()?
-                .read(self.context)
-                .forward()?;
-
-            if range_type_resolution.tag.is_dynamic() {
-                continue;
-            }
-
-            let provided = range_type_resolution.tag.type_family();
-
-            if provided != range_family {
-                let _ = self.issues.insert(ScriptIssue::TypeMismatch {
-                    expr_ref: syntax.range,
-                    expected: range_family,
-                    provided,
-                });
-            }
-        }
-
         let matches = self.local_analysis.syntax.as_ref().matches.as_ref();
 
         for (_, syntax) in &matches.map {
@@ -732,7 +1148,15 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
                     continue;
                 }
 
-                let provided = case_type_resolution.tag.type_family();
+                let mut provided = case_type_resolution.tag.type_family();
+
+                // The range case tests whether the numeric subject belongs to
+                // the range: `match x { 0..10 => ... }`.
+                if !syntax.subject.is_nil()
+                    && ScriptNode::extract_range(self.doc, *case_ref).is_some()
+                {
+                    provided = <usize>::type_meta().family();
+                }
 
                 if provided != subject_family {
                     let _ = self.issues.insert(ScriptIssue::TypeMismatch {
@@ -746,6 +1170,167 @@ impl<'a, 'ctx, 'ctx_param, const DEPTH: DiagnosticsDepth, H: TaskHandle, S: Sync
 
         Ok(())
     }
+
+    fn collect_match_exhaustiveness_issues(&mut self) -> AnalysisResult<()> {
+        let matches = self.local_analysis.syntax.as_ref().matches.as_ref();
+
+        for (_, syntax) in &matches.map {
+            let syntax = syntax.as_ref();
+
+            if syntax.exhaustive {
+                continue;
+            }
+
+            let Some(subject_node) = syntax.subject.deref(self.doc) else {
+                continue;
+            };
+
+            let subject_type_resolution = subject_node
+                .type_resolution()
+                .forward()?
+                .read(self.context)
+                .forward()?;
+
+            let Tag::Variant(enum_ref) = subject_type_resolution.tag else {
+                continue;
+            };
+
+            let mut covered = AHashSet::new();
+
+            for case_ref in &syntax.cases {
+                let Some((_, variant)) = ScriptNode::extract_variant(self.doc, *case_ref) else {
+                    continue;
+                };
+
+                let Some(case_node) = case_ref.deref(self.doc) else {
+                    continue;
+                };
+
+                let case_type_resolution = case_node
+                    .type_resolution()
+                    .forward()?
+                    .read(self.context)
+                    .forward()?;
+
+                if case_type_resolution.tag != Tag::Variant(enum_ref) {
+                    continue;
+                }
+
+                let _ = covered.insert(variant);
+            }
+
+            let missing = ScriptNode::enum_variants(self.doc, &enum_ref)
+                .iter()
+                .filter_map(|variant_ref| ScriptNode::extract_atom_string(self.doc, variant_ref))
+                .filter(|variant| !covered.contains(variant))
+                .collect::<Vec<_>>();
+
+            if missing.is_empty() {
+                continue;
+            }
+
+            let _ = self.issues.insert(ScriptIssue::NonExhaustiveMatch {
+                subject_ref: syntax.subject,
+                missing: CompactString::from(missing.join(", ")),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[inline(always)]
+// Returns the name of the function's parameter that best matches the name of
+// the call's argument at `index`, skipping the parameters that are already
+// provided by other arguments. Returns an empty string if there is no match.
+fn closest_param(params: &[Param], call_syntax: &LocalCallSyntax, index: usize) -> CompactString {
+    let Some(Some(name)) = call_syntax.names.get(index) else {
+        return CompactString::from("");
+    };
+
+    let positional = call_syntax
+        .names
+        .iter()
+        .take_while(|name| name.is_none())
+        .count();
+
+    let mut best_match = (Closeness::zero(), "");
+
+    for (param_index, param) in params.iter().enumerate() {
+        if param_index < positional {
+            continue;
+        }
+
+        let Some(param_name) = &param.name else {
+            continue;
+        };
+
+        let provided = call_syntax
+            .names
+            .iter()
+            .any(|name| name.as_deref() == Some(param_name.as_ref()));
+
+        if provided {
+            continue;
+        }
+
+        let estimation = param_name.as_ref().estimate(name);
+
+        if estimation <= best_match.0 {
+            continue;
+        }
+
+        best_match = (estimation, param_name.as_ref());
+    }
+
+    CompactString::from(best_match.1)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OpCategory {
+    Arithmetic,
+    Bitwise,
+    Comparison,
+}
+
+impl OpCategory {
+    #[inline(always)]
+    fn of(op: ScriptToken) -> Option<Self> {
+        match op.bin_precedence() {
+            Precedence::AddSub | Precedence::MulDivRem => Some(Self::Arithmetic),
+
+            Precedence::BitOr | Precedence::BitXor | Precedence::BitAnd | Precedence::Shift => {
+                Some(Self::Bitwise)
+            }
+
+            Precedence::Compare => Some(Self::Comparison),
+
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    fn is_ambiguous_with(self, other: Self) -> bool {
+        (self == Self::Bitwise) != (other == Self::Bitwise)
+    }
+}
+
+fn is_assignment_op(op: ScriptToken) -> bool {
+    match op {
+        ScriptToken::Assign
+        | ScriptToken::PlusAssign
+        | ScriptToken::MinusAssign
+        | ScriptToken::MulAssign
+        | ScriptToken::DivAssign
+        | ScriptToken::BitAndAssign
+        | ScriptToken::BitOrAssign
+        | ScriptToken::BitXorAssign
+        | ScriptToken::ShlAssign
+        | ScriptToken::ShrAssign
+        | ScriptToken::RemAssign => true,
+
+        _ => false,
+    }
 }
 
 fn lookup_import(
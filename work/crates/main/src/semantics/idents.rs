@@ -34,7 +34,7 @@
 
 use std::{collections::BTreeSet, ops::Deref};
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use compact_str::CompactString;
 use lady_deirdre::{
     analysis::{AnalysisResult, AttrContext, Computable, TaskHandle},
@@ -398,3 +398,98 @@ impl Computable for ClosureVec {
         })
     }
 }
+
+#[derive(Default, Clone, PartialEq, Eq)]
+pub(crate) struct FnCaptures {
+    pub(crate) set: AHashSet<NodeRef>,
+}
+
+impl Computable for FnCaptures {
+    type Node = ScriptNode;
+
+    fn compute<H: TaskHandle, S: SyncBuildHasher>(
+        context: &mut AttrContext<Self::Node, H, S>,
+    ) -> AnalysisResult<Self> {
+        log_attr!(context);
+
+        let node_ref = context.node_ref();
+
+        let doc_read = context.read_doc(node_ref.id).forward()?;
+
+        let Some(script_node) = node_ref.deref(doc_read.deref()) else {
+            return Ok(Default::default());
+        };
+
+        let locals = script_node.locals().forward()?;
+        let compilation = script_node.compilation().forward()?;
+
+        let vars = locals.vars.read(context).forward()?;
+        let exprs = locals.exprs.read(context).forward()?;
+        let ident_desc_map = compilation.ident_desc_map.read(context).forward()?;
+
+        let mut set = AHashSet::new();
+
+        for (ident_ref, ident_desc) in &ident_desc_map.map {
+            let IdentDesc::Closure = ident_desc else {
+                continue;
+            };
+
+            let Some(ScriptNode::Ident { semantics, .. }) = ident_ref.deref(doc_read.deref())
+            else {
+                continue;
+            };
+
+            let ident_semantics = semantics.get().forward()?;
+
+            let cross_resolution = ident_semantics.cross_resolution.read(context).forward()?;
+
+            let IdentCrossResolution::Read { name } = cross_resolution.deref() else {
+                continue;
+            };
+
+            let var_ref = match name.as_ref().decl.deref(doc_read.deref()) {
+                Some(ScriptNode::Let { name, .. }) => *name,
+
+                Some(ScriptNode::For { iterator, .. }) => *iterator,
+
+                Some(ScriptNode::FnParams { .. }) => {
+                    let Some(param_ref) = name.as_ref().defs.iter().next() else {
+                        continue;
+                    };
+
+                    *param_ref
+                }
+
+                _ => continue,
+            };
+
+            let _ = set.insert(var_ref);
+        }
+
+        // The nested functions capture the variables through this function,
+        // unless these variables are declared within this function.
+        for (_, local_expr_syntax) in &exprs.as_ref().map {
+            let LocalExprSyntax::Fn(fn_ref) = local_expr_syntax else {
+                continue;
+            };
+
+            let Some(ScriptNode::Fn { semantics, .. }) = fn_ref.deref(doc_read.deref()) else {
+                continue;
+            };
+
+            let fn_semantics = semantics.get().forward()?;
+
+            let fn_captures = fn_semantics.captures.read(context).forward()?;
+
+            for var_ref in &fn_captures.set {
+                if vars.as_ref().map.contains_key(var_ref) {
+                    continue;
+                }
+
+                let _ = set.insert(*var_ref);
+            }
+        }
+
+        Ok(Self { set })
+    }
+}
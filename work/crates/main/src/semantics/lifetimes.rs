@@ -240,6 +240,8 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
             ScriptNode::Let { name, value, .. } => self.analyze_let(name, value),
 
+            ScriptNode::Enum { name, .. } => self.analyze_var_intro(name),
+
             ScriptNode::For {
                 iterator,
                 range,
@@ -280,7 +282,9 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             return Ok(());
         };
 
-        let mut new_gens = AHashMap::new();
+        // Arm cases are evaluated sequentially until one of them matches, so
+        // the gens of the later arms are visible from the earlier cases.
+        let mut tail_gens = AHashMap::new();
 
         for arm_ref in arms.iter().rev() {
             let Some(ScriptNode::MatchArm { case, handler, .. }) = arm_ref.deref(self.doc) else {
@@ -301,18 +305,8 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 _ => (),
             }
 
-            if let Some(ScriptNode::Expr { inner, .. }) = case.deref(self.doc) {
-                self.analyze_expr(inner)?;
-            }
-
-            let gen_after = replace(&mut self.gen, gen_before);
-
-            for (name, mut gens) in gen_after {
-                if self.gen.contains_key(&name) {
-                    continue;
-                }
-
-                match new_gens.entry(name) {
+            for (name, mut gens) in take(&mut tail_gens) {
+                match self.gen.entry(name) {
                     Entry::Vacant(entry) => {
                         let _ = entry.insert(gens);
                     }
@@ -322,12 +316,20 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                     }
                 }
             }
+
+            if let Some(ScriptNode::Expr { inner, .. }) = case.deref(self.doc) {
+                self.analyze_expr(inner)?;
+            }
+
+            tail_gens = replace(&mut self.gen, gen_before);
         }
 
-        for (name, gens) in new_gens {
-            if self.gen.insert(name, gens).is_some() {
-                system_panic!("Duplicate gen entry.");
+        for (name, gens) in tail_gens {
+            if self.gen.contains_key(&name) {
+                continue;
             }
+
+            let _ = self.gen.insert(name, gens);
         }
 
         Ok(())
@@ -548,7 +550,10 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
     fn analyze_call(&mut self, left: &NodeRef, args: &NodeRef) -> AnalysisResult<()> {
         self.analyze_expr(left)?;
 
-        let Some(ScriptNode::CallArgs { args, .. }) = args.deref(self.doc) else {
+        let Some(args) = args
+            .deref(self.doc)
+            .and_then(|node| node.call_args(self.doc))
+        else {
             return Ok(());
         };
 
@@ -0,0 +1,74 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::ops::Deref;
+
+use lady_deirdre::{
+    analysis::{AnalysisResult, AttrContext, Computable, TaskHandle, DOC_UPDATED_EVENT},
+    sync::{Shared, SyncBuildHasher},
+};
+
+use crate::{
+    analysis::{ModuleMetadata, ModuleResultEx},
+    semantics::setup::log_attr,
+    syntax::ScriptNode,
+};
+
+// The module's metadata header. The header consists of comments that do not
+// belong to any syntax tree node, so the attribute is recomputed on every
+// document update.
+#[derive(Default, Clone, PartialEq, Eq)]
+pub(crate) struct ModuleHeader {
+    pub(crate) metadata: Shared<ModuleMetadata>,
+}
+
+impl Computable for ModuleHeader {
+    type Node = ScriptNode;
+
+    fn compute<H: TaskHandle, S: SyncBuildHasher>(
+        context: &mut AttrContext<Self::Node, H, S>,
+    ) -> AnalysisResult<Self> {
+        log_attr!(context);
+
+        let id = context.node_ref().id;
+
+        let doc_read = context.read_doc(id).forward()?;
+
+        context.subscribe(id, DOC_UPDATED_EVENT);
+
+        Ok(Self {
+            metadata: Shared::new(ModuleMetadata::parse(doc_read.deref())),
+        })
+    }
+}
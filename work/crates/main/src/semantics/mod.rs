@@ -34,11 +34,13 @@
 
 mod analysis;
 mod assembler;
+mod dead_code;
 mod diagnostics;
 mod floats;
 mod flow;
 mod idents;
 mod lifetimes;
+mod metadata;
 mod names;
 mod setup;
 mod syntax;
@@ -47,11 +49,13 @@ mod ty;
 
 pub(crate) use crate::semantics::{
     analysis::*,
+    dead_code::*,
     diagnostics::*,
     floats::*,
     flow::*,
     idents::*,
     lifetimes::*,
+    metadata::*,
     names::*,
     setup::*,
     syntax::*,
@@ -33,11 +33,18 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use lady_deirdre::{
-    analysis::{AnalysisError, AnalysisResult, Attr, Feature},
+    analysis::{AnalysisError, AnalysisResult, Attr, Feature, Slot},
     sync::Shared,
 };
 
-use crate::{interpret::Assembly, semantics::*, syntax::ScriptNode};
+use crate::{analysis::AnalysisConfig, interpret::Assembly, semantics::*, syntax::ScriptNode};
+
+#[derive(Feature)]
+#[node(ScriptNode)]
+pub struct CommonSemantics {
+    pub(crate) dead_code_allow: Slot<ScriptNode, DeadCodeAllow>,
+    pub(crate) analysis_config: Slot<ScriptNode, AnalysisConfig>,
+}
 
 #[derive(Feature)]
 #[node(ScriptNode)]
@@ -104,6 +111,8 @@ pub struct RootSemantics {
     pub(crate) diagnostics_cross_1: Attr<CrossDiagnostics<1>>,
     pub(crate) diagnostics_cross_2: Attr<CrossDiagnostics<2>>,
     pub(crate) diagnostics_cross_3: Attr<CrossDiagnostics<3>>,
+    pub(crate) dead_code: Attr<DeadCode>,
+    pub(crate) module_header: Attr<ModuleHeader>,
 }
 
 #[derive(Feature)]
@@ -118,6 +127,7 @@ pub struct PackageSemantics {
 pub struct VarSemantics {
     pub(crate) var_syntax: Attr<LocalVarSyntax>,
     pub(crate) let_inits: Attr<Shared<LocalLetInits>>,
+    pub(crate) let_constant: Attr<LetConstant>,
     pub(crate) type_resolution: Attr<TypeResolution>,
 }
 
@@ -155,6 +165,7 @@ pub struct FnSemantics {
     pub(crate) compilation: Compilation,
 
     pub(crate) namespace: Attr<Shared<LocalNamespace>>,
+    pub(crate) captures: Attr<FnCaptures>,
     pub(crate) fn_context_syntax: Attr<LocalFnContextSyntax>,
     pub(crate) arg_syntax: Attr<LocalArgSyntax>,
     pub(crate) result_resolution: Attr<ResultResolution>,
@@ -169,6 +180,12 @@ pub struct StructSemantics {
     pub(crate) type_resolution: Attr<TypeResolution>,
 }
 
+#[derive(Feature)]
+#[node(ScriptNode)]
+pub struct EnumSemantics {
+    pub(crate) type_resolution: Attr<TypeResolution>,
+}
+
 #[derive(Feature)]
 #[node(ScriptNode)]
 pub struct ArraySemantics {
@@ -298,8 +315,10 @@ impl ScriptNode {
             Self::Var { semantics, .. } => Ok(&semantics.get()?.type_resolution),
             Self::Fn { semantics, .. } => Ok(&semantics.get()?.type_resolution),
             Self::Struct { semantics, .. } => Ok(&semantics.get()?.type_resolution),
+            Self::Enum { semantics, .. } => Ok(&semantics.get()?.type_resolution),
             Self::Array { semantics, .. } => Ok(&semantics.get()?.type_resolution),
             Self::String { semantics, .. } => Ok(&semantics.get()?.type_resolution),
+            Self::Embedded { semantics, .. } => Ok(&semantics.get()?.type_resolution),
             Self::Crate { semantics, .. } => Ok(&semantics.get()?.type_resolution),
             Self::This { semantics, .. } => Ok(&semantics.get()?.type_resolution),
             Self::Ident { semantics, .. } => Ok(&semantics.get()?.type_resolution),
@@ -47,6 +47,7 @@ use lady_deirdre::{
 
 use crate::{
     analysis::ModuleResultEx,
+    runtime::{arrange_args, ArgNameError, Param},
     semantics::*,
     syntax::{ScriptNode, ScriptToken},
 };
@@ -560,6 +561,7 @@ pub(crate) struct LocalForSyntax {
 pub(crate) struct LocalMatchSyntax {
     pub(crate) subject: NodeRef,
     pub(crate) cases: AHashSet<NodeRef>,
+    pub(crate) exhaustive: bool,
 }
 
 #[derive(Default, Clone, PartialEq, Eq)]
@@ -799,6 +801,26 @@ pub(crate) struct LocalCallSyntax {
     pub(crate) left: NodeRef,
     pub(crate) call_args_ref: NodeRef,
     pub(crate) args: Vec<NodeRef>,
+    pub(crate) names: Vec<Option<CompactString>>,
+    pub(crate) closure: bool,
+}
+
+impl LocalCallSyntax {
+    #[inline(always)]
+    pub(crate) fn is_named(&self) -> bool {
+        self.names.iter().any(Option::is_some)
+    }
+
+    // Returns the index of the function's parameter for each invocation
+    // argument.
+    #[inline(always)]
+    pub(crate) fn arrange(&self, params: &[Param]) -> Result<Vec<usize>, ArgNameError> {
+        arrange_args(
+            params,
+            self.names.iter().map(|name| name.as_deref()),
+            self.closure,
+        )
+    }
 }
 
 impl SharedComputable for LocalCallSyntax {
@@ -971,15 +993,22 @@ impl Computable for LocalNumberValue {
             .read(context)
             .forward()?;
 
-        if atom_syntax.0.contains(&['.', 'e']) {
-            return Ok(Self::Float(atom_syntax.0.parse()));
+        Ok(Self::parse(atom_syntax.0.as_str()))
+    }
+}
+
+impl LocalNumberValue {
+    // The `text` is expected to be a single Int or Float token string.
+    pub(crate) fn parse(text: &str) -> Self {
+        if text.contains(&['.', 'e']) {
+            return Self::Float(text.parse());
         }
 
-        if atom_syntax.0.starts_with(&['-', '+']) {
-            return Ok(Self::Isize(atom_syntax.0.parse()));
+        if text.starts_with(&['-', '+']) {
+            return Self::Isize(text.parse());
         }
 
-        Ok(Self::Usize(atom_syntax.0.parse()))
+        Self::Usize(text.parse())
     }
 }
 
@@ -1155,6 +1184,7 @@ impl Computable for LocalVarSyntax {
 
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct LocalArgSyntax {
+    pub(crate) call_ref: NodeRef,
     pub(crate) call_left_ref: NodeRef,
     pub(crate) arg_index: usize,
 }
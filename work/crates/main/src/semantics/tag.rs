@@ -37,7 +37,7 @@ use std::mem::swap;
 use lady_deirdre::syntax::NodeRef;
 
 use crate::{
-    exports::Struct,
+    exports::{Struct, Variant},
     runtime::{ops::DynamicType, InvocationMeta, ScriptType, TypeFamily, TypeHint, TypeMeta},
 };
 
@@ -47,6 +47,8 @@ pub(crate) enum Tag {
     Type(&'static TypeMeta),
     Family(&'static TypeFamily),
     Struct(NodeRef),
+    Enum(NodeRef),
+    Variant(NodeRef),
     Fn((NodeRef, usize)),
     Invocation(&'static InvocationMeta),
 }
@@ -61,7 +63,7 @@ impl Default for Tag {
 impl From<TypeHint> for Tag {
     #[inline(always)]
     fn from(value: TypeHint) -> Self {
-        match value {
+        match value.casted() {
             TypeHint::Type(meta) => {
                 if let Some(meta) = meta.prototype().hint_invocation() {
                     return Self::Invocation(meta);
@@ -99,6 +101,10 @@ impl Tag {
 
             Self::Struct(_) => TypeHint::from(<Struct>::type_meta().family()),
 
+            Self::Enum(_) => TypeHint::from(<Struct>::type_meta()),
+
+            Self::Variant(_) => TypeHint::from(<Variant>::type_meta()),
+
             Self::Fn((_, arity)) => match TypeMeta::script_fn(*arity) {
                 Some(ty) => TypeHint::from(ty),
                 None => TypeHint::from(TypeFamily::fn_family()),
@@ -113,7 +119,10 @@ impl Tag {
     pub(crate) fn type_source(&self) -> Option<&NodeRef> {
         match self {
             Self::Unset | Self::Type(_) | Self::Family(_) | Self::Invocation(_) => None,
-            Self::Struct(source) | Self::Fn((source, _)) => Some(source),
+            Self::Struct(source)
+            | Self::Enum(source)
+            | Self::Variant(source)
+            | Self::Fn((source, _)) => Some(source),
         }
     }
 
@@ -127,6 +136,10 @@ impl Tag {
 
             Self::Struct(_) => Some(<Struct>::type_meta()),
 
+            Self::Enum(_) => Some(<Struct>::type_meta()),
+
+            Self::Variant(_) => Some(<Variant>::type_meta()),
+
             Self::Fn((_, arity)) => TypeMeta::script_fn(*arity),
 
             Self::Invocation(meta) => {
@@ -148,6 +161,15 @@ impl Tag {
         false
     }
 
+    #[inline(always)]
+    pub(crate) fn is_integer(&self) -> bool {
+        let Self::Type(ty) = self else {
+            return false;
+        };
+
+        *ty == <usize>::type_meta() || *ty == <isize>::type_meta()
+    }
+
     pub(crate) fn type_family(&self) -> &'static TypeFamily {
         match self {
             Self::Unset => TypeFamily::dynamic(),
@@ -158,6 +180,10 @@ impl Tag {
 
             Self::Struct(_) => <Struct>::type_meta().family(),
 
+            Self::Enum(_) => <Struct>::type_meta().family(),
+
+            Self::Variant(_) => <Variant>::type_meta().family(),
+
             Self::Fn(_) => TypeFamily::fn_family(),
 
             Self::Invocation(_) => TypeFamily::fn_family(),
@@ -182,6 +208,21 @@ impl Tag {
         match (&self, other) {
             (Self::Unset, other) => *self = other,
 
+            (Self::Enum(this), Self::Enum(other)) if this == &other => (),
+
+            (Self::Variant(this), Self::Variant(other)) if this == &other => (),
+
+            (Self::Enum(_) | Self::Variant(_), Self::Unset) => (),
+
+            // The values of different enums are merged as their runtime types.
+            (Self::Enum(_) | Self::Variant(_), other) => {
+                if let Some(ty) = self.type_meta() {
+                    *self = Self::Type(ty);
+                }
+
+                self.merge(other);
+            }
+
             (Self::Type(this), Self::Type(other)) => {
                 if this != &other {
                     let this_family = this.family();
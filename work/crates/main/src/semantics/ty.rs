@@ -32,23 +32,51 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::ops::{Deref, Range};
+use std::{
+    cell::Cell,
+    convert::identity,
+    iter::repeat,
+    ops::{Deref, Range},
+};
 
 use ahash::AHashSet;
 use compact_str::CompactString;
 use lady_deirdre::{
     analysis::{AnalysisResult, AttrContext, Computable, Semantics, TaskHandle},
     arena::Identifiable,
+    lexis::{SourceCode, ToSpan},
     sync::SyncBuildHasher,
-    syntax::NodeRef,
+    syntax::{AbstractNode, NodeRef},
 };
 
 use crate::{
-    analysis::{Closeness, ModuleResultEx, ScriptIssue, StringEstimation},
+    analysis::{
+        AnalysisConfig,
+        AnalysisLimit,
+        Closeness,
+        ModuleResultEx,
+        ScriptIssue,
+        StringEstimation,
+    },
+    exports::Struct,
+    interpret::ScriptEnvType,
     report::system_panic,
-    runtime::{ops::OperatorKind, PackageMeta, Prototype, ScriptType, TypeFamily, TypeMeta},
+    runtime::{
+        ops::OperatorKind,
+        ComponentHint,
+        ContractKind,
+        ContractValue,
+        InvocationMeta,
+        PackageMeta,
+        Prototype,
+        ScriptIterator,
+        ScriptType,
+        StructSchema,
+        TypeFamily,
+        TypeMeta,
+    },
     semantics::*,
-    syntax::{ScriptDoc, ScriptNode, ScriptToken},
+    syntax::{Precedence, ScriptClass, ScriptDoc, ScriptNode, ScriptToken},
 };
 
 #[derive(Default, Clone, PartialEq, Eq)]
@@ -112,6 +140,8 @@ pub(crate) struct TypeResolution {
     pub(crate) tag: Tag,
     pub(crate) alt: bool,
     pub(crate) issues: AHashSet<ScriptIssue>,
+    // The integer value of the expression if it is statically known.
+    pub(crate) constant: Option<isize>,
 }
 
 impl Computable for TypeResolution {
@@ -130,10 +160,38 @@ impl Computable for TypeResolution {
             return Ok(Default::default());
         };
 
+        let config = *context.common().analysis_config.read(context).forward()?;
+
+        let Some(_step) = ResolutionStep::enter(config.max_resolution_steps) else {
+            return Ok(Self::limited(
+                *node_ref,
+                AnalysisLimit::ResolutionSteps(config.max_resolution_steps),
+            ));
+        };
+
+        match ExprDepth::of(doc_read.deref(), script_node, config.max_expr_depth) {
+            ExprDepth::Within => (),
+
+            ExprDepth::Outermost => {
+                return Ok(Self::limited(
+                    *node_ref,
+                    AnalysisLimit::ExprDepth(config.max_expr_depth),
+                ));
+            }
+
+            ExprDepth::Beyond => {
+                return Ok(Self {
+                    tag: Tag::dynamic(),
+                    ..Self::default()
+                })
+            }
+        }
+
         let mut resolver = TypeResolver {
             doc: doc_read.deref(),
             node_ref,
             context,
+            config,
             resolution: Self::default(),
         };
 
@@ -163,8 +221,12 @@ impl Computable for TypeResolution {
             ScriptNode::StructBody { .. } => (),
             ScriptNode::StructEntry { .. } => (),
             ScriptNode::StructEntryKey { .. } => (),
+            ScriptNode::Enum { .. } => resolver.resolve_enum()?,
+            ScriptNode::EnumBody { .. } => (),
+            ScriptNode::EnumVariant { .. } => (),
             ScriptNode::Array { semantics, .. } => resolver.resolve_array(semantics)?,
             ScriptNode::String { .. } => resolver.resolve_string()?,
+            ScriptNode::Embedded { .. } => resolver.resolve_string()?,
             ScriptNode::Crate { .. } => resolver.resolve_crate()?,
             ScriptNode::This { semantics, .. } => resolver.resolve_this(semantics)?,
             ScriptNode::Ident { semantics, .. } => resolver.resolve_ident(semantics)?,
@@ -177,6 +239,7 @@ impl Computable for TypeResolution {
             ScriptNode::Query { .. } => resolver.resolve_query()?,
             ScriptNode::Call { semantics, .. } => resolver.resolve_call(semantics)?,
             ScriptNode::CallArgs { .. } => (),
+            ScriptNode::Arg { .. } => (),
             ScriptNode::Index { semantics, .. } => resolver.resolve_index(semantics)?,
             ScriptNode::IndexArg { .. } => (),
             ScriptNode::Field { .. } => (),
@@ -191,10 +254,277 @@ impl Computable for TypeResolution {
     }
 }
 
+impl TypeResolution {
+    #[inline(always)]
+    fn limited(node_ref: NodeRef, limit: AnalysisLimit) -> Self {
+        let mut issues = AHashSet::with_capacity(1);
+
+        let _ = issues.insert(ScriptIssue::AnalysisLimit { node_ref, limit });
+
+        Self {
+            tag: Tag::dynamic(),
+            alt: false,
+            issues,
+            constant: None,
+        }
+    }
+}
+
+// The statically known facts about a variable introduced by a let statement.
+//
+// The analyzer propagates the value of a variable only if the variable is
+// assigned exactly once, by the initializer of the let statement. Any other
+// assignment (including the assignments within loops and closures) makes the
+// value unknown.
+//
+// The length of an array or string literal is propagated only if the variable
+// is never used other than as an indexing target or as the receiver of
+// the `len` field, because the value could otherwise be mutated elsewhere.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LetConstant {
+    pub(crate) value: Option<isize>,
+    pub(crate) length: Option<usize>,
+}
+
+impl Computable for LetConstant {
+    type Node = ScriptNode;
+
+    fn compute<H: TaskHandle, S: SyncBuildHasher>(
+        context: &mut AttrContext<Self::Node, H, S>,
+    ) -> AnalysisResult<Self> {
+        log_attr!(context);
+
+        let node_ref = context.node_ref();
+
+        let doc_read = context.read_doc(node_ref.id).forward()?;
+        let doc = doc_read.deref();
+
+        let Some(ScriptNode::Var {
+            token, semantics, ..
+        }) = node_ref.deref(doc)
+        else {
+            return Ok(Self::default());
+        };
+
+        let var_semantics = semantics.get().forward()?;
+
+        let LocalVarSyntax::Let(let_ref) = *var_semantics.var_syntax.read(context).forward()?
+        else {
+            return Ok(Self::default());
+        };
+
+        let Some(ScriptNode::Let { value, .. }) = let_ref.deref(doc) else {
+            return Ok(Self::default());
+        };
+
+        let Some(value_node) = value.deref(doc) else {
+            return Ok(Self::default());
+        };
+
+        let let_inits = var_semantics.let_inits.read(context).forward()?;
+
+        if let_inits.as_ref().set.len() != 1 || !let_inits.as_ref().set.contains(value) {
+            return Ok(Self::default());
+        }
+
+        let mut result = Self {
+            value: value_node
+                .type_resolution()
+                .forward()?
+                .read(context)
+                .forward()?
+                .constant,
+            length: literal_length(context, doc, value)?,
+        };
+
+        if result == Self::default() {
+            return Ok(result);
+        }
+
+        let Some(name) = token.string(doc) else {
+            return Ok(Self::default());
+        };
+
+        let ident_refs = context
+            .read_class(doc.id(), &ScriptClass::Ident(CompactString::from(name)))
+            .forward()?;
+
+        for ident_ref in ident_refs.as_ref() {
+            let Some(ScriptNode::Ident { semantics, .. }) = ident_ref.deref(doc) else {
+                continue;
+            };
+
+            let ident_semantics = semantics.get().forward()?;
+
+            let cross_resolution = ident_semantics.cross_resolution.read(context).forward()?;
+
+            match cross_resolution.deref() {
+                IdentCrossResolution::Read { name } if name.as_ref().decl == let_ref => (),
+                IdentCrossResolution::Write { decl } if decl == &let_ref => (),
+                _ => continue,
+            }
+
+            match LetUse::of(doc, ident_ref) {
+                LetUse::Inspect => (),
+                LetUse::Read => result.length = None,
+                LetUse::Write => return Ok(Self::default()),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+enum LetUse {
+    Inspect,
+    Read,
+    Write,
+}
+
+impl LetUse {
+    fn of(doc: &ScriptDoc, ident_ref: &NodeRef) -> Self {
+        let mut node_ref = *ident_ref;
+
+        loop {
+            let Some(node) = node_ref.deref(doc) else {
+                return Self::Read;
+            };
+
+            let parent_ref = node.parent_ref();
+
+            match parent_ref.deref(doc) {
+                Some(ScriptNode::Expr { .. }) => node_ref = parent_ref,
+
+                Some(ScriptNode::Index { left, .. }) if left == &node_ref => return Self::Inspect,
+
+                Some(ScriptNode::Binary {
+                    left, op, right, ..
+                }) if left == &node_ref => {
+                    let Some(ScriptNode::Op { token, .. }) = op.deref(doc) else {
+                        return Self::Read;
+                    };
+
+                    let Some(token) = token.deref(doc) else {
+                        return Self::Read;
+                    };
+
+                    if let Precedence::Assign = token.bin_precedence() {
+                        return Self::Write;
+                    }
+
+                    if let (ScriptToken::Dot, Some("len")) =
+                        (token, ScriptNode::extract_atom_string(doc, right))
+                    {
+                        return Self::Inspect;
+                    }
+
+                    return Self::Read;
+                }
+
+                _ => return Self::Read,
+            }
+        }
+    }
+}
+
+thread_local! {
+    // The number of type resolution attributes that are currently being
+    // computed on this thread, each nested in the previous one.
+    static RESOLUTION_STEPS: Cell<usize> = const { Cell::new(0) };
+}
+
+struct ResolutionStep;
+
+impl Drop for ResolutionStep {
+    #[inline(always)]
+    fn drop(&mut self) {
+        RESOLUTION_STEPS.with(|steps| steps.set(steps.get() - 1));
+    }
+}
+
+impl ResolutionStep {
+    #[inline(always)]
+    fn enter(max_steps: usize) -> Option<Self> {
+        RESOLUTION_STEPS.with(|steps| {
+            let current = steps.get();
+
+            if current >= max_steps {
+                return None;
+            }
+
+            steps.set(current + 1);
+
+            Some(Self)
+        })
+    }
+}
+
+enum ExprDepth {
+    Within,
+    Outermost,
+    Beyond,
+}
+
+impl ExprDepth {
+    // Counts the nesting depth of the expression within the enclosing function
+    // (or the module's top-level code). The walk stops as soon as the depth
+    // exceeds the limit by two levels, such that the cost of the check does
+    // not depend on the actual nesting.
+    fn of(doc: &ScriptDoc, script_node: &ScriptNode, max_depth: usize) -> Self {
+        if !Self::is_nesting(script_node) {
+            return Self::Within;
+        }
+
+        let mut depth = 1;
+        let mut current = script_node.parent_ref();
+
+        while depth <= max_depth.saturating_add(1) {
+            let Some(parent) = current.deref(doc) else {
+                break;
+            };
+
+            match parent {
+                ScriptNode::Root { .. } | ScriptNode::Fn { .. } => break,
+
+                _ => {
+                    if Self::is_nesting(parent) {
+                        depth += 1;
+                    }
+                }
+            }
+
+            current = parent.parent_ref();
+        }
+
+        match depth {
+            depth if depth <= max_depth => Self::Within,
+            depth if depth == max_depth.saturating_add(1) => Self::Outermost,
+            _ => Self::Beyond,
+        }
+    }
+
+    #[inline(always)]
+    fn is_nesting(script_node: &ScriptNode) -> bool {
+        match script_node {
+            ScriptNode::Expr { .. }
+            | ScriptNode::Binary { .. }
+            | ScriptNode::UnaryLeft { .. }
+            | ScriptNode::Query { .. }
+            | ScriptNode::Call { .. }
+            | ScriptNode::Index { .. }
+            | ScriptNode::Array { .. }
+            | ScriptNode::Struct { .. } => true,
+
+            _ => false,
+        }
+    }
+}
+
 struct TypeResolver<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher> {
     doc: &'doc ScriptDoc,
     node_ref: &'ctx NodeRef,
     context: &'ctx mut AttrContext<'ctx_param, ScriptNode, H, S>,
+    config: AnalysisConfig,
     resolution: TypeResolution,
 }
 
@@ -215,13 +545,18 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             LocalVarSyntax::Param(fn_ref, param_index) => {
                 self.resolve_var_param(fn_ref, *param_index)
             }
-            LocalVarSyntax::For(_) => self.resolve_var_for(),
+            LocalVarSyntax::For(for_ref) => self.resolve_var_for(for_ref),
         }
     }
 
     fn resolve_var_let(&mut self, var_semantics: &VarSemantics) -> AnalysisResult<()> {
         let let_inits = var_semantics.let_inits.read(self.context).forward()?;
 
+        if let_inits.as_ref().set.len() > self.config.max_fan_out {
+            self.limit_fan_out(*self.node_ref);
+            return Ok(());
+        }
+
         for expr in &let_inits.as_ref().set {
             let Some(expr_node) = expr.deref(self.doc) else {
                 continue;
@@ -266,7 +601,30 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             return Ok(());
         };
 
-        let Some(input) = inputs.get(arg_syntax.arg_index) else {
+        let mut input_index = arg_syntax.arg_index;
+
+        if let Some(ScriptNode::Call { semantics, .. }) = arg_syntax.call_ref.deref(self.doc) {
+            let call_syntax = semantics
+                .get()
+                .forward()?
+                .call_syntax
+                .read(self.context)
+                .forward()?;
+
+            if call_syntax.as_ref().is_named() {
+                let Ok(order) = call_syntax.as_ref().arrange(inputs) else {
+                    return Ok(());
+                };
+
+                let Some(param) = order.get(arg_syntax.arg_index) else {
+                    return Ok(());
+                };
+
+                input_index = *param;
+            }
+        }
+
+        let Some(input) = inputs.get(input_index) else {
             return Ok(());
         };
 
@@ -287,8 +645,48 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
-    fn resolve_var_for(&mut self) -> AnalysisResult<()> {
-        self.resolution.tag = Tag::Type(<usize>::type_meta());
+    fn resolve_var_for(&mut self, for_ref: &NodeRef) -> AnalysisResult<()> {
+        let Some(ScriptNode::For { range, .. }) = for_ref.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let Some(range_node) = range.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let range_resolution = range_node
+            .type_resolution()
+            .forward()?
+            .read(self.context)
+            .forward()?;
+
+        let tag = &range_resolution.tag;
+
+        if tag.is_dynamic() {
+            return Ok(());
+        }
+
+        let family = tag.type_family();
+
+        // Iterator items are not known statically.
+        if family == <ScriptIterator>::type_meta().family() {
+            return Ok(());
+        }
+
+        if family == <Range<usize>>::type_meta().family() {
+            self.resolution.tag = Tag::Type(<usize>::type_meta());
+            return Ok(());
+        }
+
+        if let Some(ty) = tag.type_meta() {
+            if let Some(item) = ty.prototype().hint_iterate_item() {
+                self.resolution.tag = Tag::Type(item);
+                return Ok(());
+            }
+        }
+
+        // Any other object iterates through the elements of its array.
+        self.resolution.tag = tag.clone();
 
         Ok(())
     }
@@ -311,11 +709,17 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
+    fn resolve_enum(&mut self) -> AnalysisResult<()> {
+        self.resolution.tag = Tag::Enum(*self.node_ref);
+
+        Ok(())
+    }
+
     fn resolve_array(&mut self, semantics: &Semantics<ArraySemantics>) -> AnalysisResult<()> {
         enum TailMode {
             Unknown,
             CheckDisplay,
-            CheckFamily(&'static TypeFamily),
+            CheckFamily(&'static TypeMeta),
             MergeEach(&'static TypeFamily),
         }
 
@@ -330,6 +734,11 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             return Ok(());
         };
 
+        if items.len() > self.config.max_fan_out {
+            self.limit_fan_out(*self.node_ref);
+            return Ok(());
+        }
+
         let mut items_iter = items.iter();
 
         let mut mode = TailMode::Unknown;
@@ -393,7 +802,7 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 return Ok(());
             };
 
-            mode = TailMode::CheckFamily(ty.family());
+            mode = TailMode::CheckFamily(ty);
             self.resolution.tag = Tag::Type(result);
             break;
         }
@@ -436,7 +845,9 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 }
             }
 
-            TailMode::CheckFamily(expected) => {
+            TailMode::CheckFamily(first) => {
+                let expected = first.family();
+
                 while let Some(expr) = items_iter.next() {
                     let Some(expr_node) = expr.deref(self.doc) else {
                         continue;
@@ -464,6 +875,15 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                             expected,
                             provided,
                         });
+
+                        continue;
+                    }
+
+                    // The concatenation of the same family items with distinct
+                    // types (e.g., integers and floats) may produce a value of
+                    // any type of this family.
+                    if tag.type_meta() != Some(first) {
+                        self.resolution.tag = Tag::Family(expected);
                     }
                 }
             }
@@ -615,6 +1035,8 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                     return Ok(());
                 };
 
+                self.check_deprecation(*self.node_ref, &component);
+
                 self.resolution.tag = Tag::from(component.ty);
             }
 
@@ -646,6 +1068,8 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                     return Ok(());
                 };
 
+                self.check_deprecation(*self.node_ref, &component);
+
                 self.resolution.tag = Tag::from(component.ty);
             }
 
@@ -663,12 +1087,35 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
 
                     self.resolution.tag.merge(def_type_resolution.tag);
                 }
+
+                if let ScriptNode::Let { name, .. } = decl_node {
+                    if self.resolution.tag.is_integer() {
+                        self.resolution.constant =
+                            let_constant(self.context, self.doc, name)?.value;
+                    }
+                }
             }
         }
 
         Ok(())
     }
 
+    #[inline(always)]
+    fn check_deprecation(&mut self, name_ref: NodeRef, component: &ComponentHint) {
+        let Some(message) = component.deprecated else {
+            return;
+        };
+
+        let _ = self
+            .resolution
+            .issues
+            .insert(ScriptIssue::DeprecatedMember {
+                name_ref,
+                component: component.name,
+                message,
+            });
+    }
+
     fn resolve_ident_write(&mut self, decl: &NodeRef) -> AnalysisResult<()> {
         let Some(ScriptNode::Var { semantics, .. }) = decl.deref(self.doc) else {
             return Ok(());
@@ -696,6 +1143,12 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             LocalNumberValue::Float(_) => self.resolution.tag = Tag::Type(<FloatRepr>::type_meta()),
         }
 
+        self.resolution.constant = match number_value.deref() {
+            LocalNumberValue::Usize(Ok(value)) => isize::try_from(*value).ok(),
+            LocalNumberValue::Isize(Ok(value)) => Some(*value),
+            _ => None,
+        };
+
         Ok(())
     }
 
@@ -740,6 +1193,23 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             return Ok(());
         };
 
+        let overload = match infix_syntax.op {
+            ScriptToken::Minus => Some(OperatorKind::Neg),
+            ScriptToken::Not => Some(OperatorKind::Not),
+            _ => None,
+        };
+
+        if let Some(operator) = overload {
+            if self.resolve_operator_overload(
+                &right_type_resolution.tag,
+                operator,
+                &infix_syntax.op_ref,
+                0,
+            )? {
+                return Ok(());
+            }
+        }
+
         let receiver_prototype = receiver.prototype();
 
         match infix_syntax.op {
@@ -773,6 +1243,12 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 if let Some(result) = receiver_prototype.hint_neg_result() {
                     self.resolution.tag = Tag::Type(result);
                 }
+
+                self.resolution.constant = self.integer_constant(
+                    right_type_resolution
+                        .constant
+                        .and_then(|value| value.checked_neg()),
+                );
             }
 
             ScriptToken::Not => {
@@ -807,10 +1283,76 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             ScriptToken::Dot => self.resolve_binary_dot(infix_syntax.deref()),
             ScriptToken::Dot2 => self.resolve_binary_dot2(infix_syntax.deref()),
             ScriptToken::Assign => self.resolve_binary_assign(infix_syntax.deref()),
+
+            ScriptToken::Plus
+            | ScriptToken::Minus
+            | ScriptToken::Mul
+            | ScriptToken::Div
+            | ScriptToken::Rem => {
+                self.resolve_binary_op(infix_syntax.deref())?;
+                self.fold_binary_constant(infix_syntax.deref())
+            }
+
             _ => self.resolve_binary_op(infix_syntax.deref()),
         }
     }
 
+    // Folds integer arithmetic over the operands with statically known values.
+    // Overflows, division by zero, and negative unsigned results leave
+    // the value unknown, as the operation fails at runtime in these cases.
+    fn fold_binary_constant(&mut self, infix_syntax: &LocalInfixSyntax) -> AnalysisResult<()> {
+        let Some(lhs) = self.node_constant(&infix_syntax.left)? else {
+            return Ok(());
+        };
+
+        let Some(rhs) = self.node_constant(&infix_syntax.right)? else {
+            return Ok(());
+        };
+
+        let value = match infix_syntax.op {
+            ScriptToken::Plus => lhs.checked_add(rhs),
+            ScriptToken::Minus => lhs.checked_sub(rhs),
+            ScriptToken::Mul => lhs.checked_mul(rhs),
+            ScriptToken::Div => lhs.checked_div(rhs),
+            ScriptToken::Rem => lhs.checked_rem(rhs),
+            _ => None,
+        };
+
+        self.resolution.constant = self.integer_constant(value);
+
+        Ok(())
+    }
+
+    fn node_constant(&mut self, node_ref: &NodeRef) -> AnalysisResult<Option<isize>> {
+        let Some(node) = node_ref.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        Ok(node
+            .type_resolution()
+            .forward()?
+            .read(self.context)
+            .forward()?
+            .constant)
+    }
+
+    // Keeps the value only if the resolved type of the expression is an integer
+    // type that can represent it.
+    #[inline(always)]
+    fn integer_constant(&self, value: Option<isize>) -> Option<isize> {
+        let value = value?;
+
+        if !self.resolution.tag.is_integer() {
+            return None;
+        }
+
+        if value < 0 && self.resolution.tag.type_meta() == Some(<usize>::type_meta()) {
+            return None;
+        }
+
+        Some(value)
+    }
+
     fn resolve_binary_assign(&mut self, infix_syntax: &LocalInfixSyntax) -> AnalysisResult<()> {
         let Some(ScriptNode::Ident { semantics, .. }) = infix_syntax.left.deref(self.doc) else {
             return self.resolve_binary_op(infix_syntax);
@@ -824,6 +1366,11 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             .forward()?
             .deref()
         else {
+            if self.is_self_append(infix_syntax)? {
+                self.resolution.tag = Tag::nil();
+                return Ok(());
+            }
+
             return self.resolve_binary_op(infix_syntax);
         };
 
@@ -832,12 +1379,37 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
-    fn resolve_binary_dot(&mut self, infix_syntax: &LocalInfixSyntax) -> AnalysisResult<()> {
-        let Some(ScriptNode::Field { semantics, .. }) = infix_syntax.right.deref(self.doc) else {
-            return Ok(());
-        };
+    // The self-append assignment of a string variable (`s = [s, a]` or
+    // `s = s + a`) rebinds the variable to the new string instead of calling
+    // the assignment operator, which the string type does not implement.
+    //
+    // The Assembler recognizes the same pattern.
+    fn is_self_append(&mut self, infix_syntax: &LocalInfixSyntax) -> AnalysisResult<bool> {
+        if ScriptNode::extract_self_append(self.doc, &infix_syntax.left, infix_syntax.right)
+            .is_none()
+        {
+            return Ok(false);
+        }
 
-        let field_semantics = semantics.get().forward()?;
+        let Some(left_node) = infix_syntax.left.deref(self.doc) else {
+            return Ok(false);
+        };
+
+        let left_type_resolution = left_node
+            .type_resolution()
+            .forward()?
+            .read(self.context)
+            .forward()?;
+
+        Ok(left_type_resolution.tag.type_meta() == Some(<str>::type_meta()))
+    }
+
+    fn resolve_binary_dot(&mut self, infix_syntax: &LocalInfixSyntax) -> AnalysisResult<()> {
+        let Some(ScriptNode::Field { semantics, .. }) = infix_syntax.right.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let field_semantics = semantics.get().forward()?;
 
         let field_atom = field_semantics.atom_syntax.read(self.context).forward()?;
 
@@ -857,6 +1429,15 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             .forward()?;
 
         if let Tag::Struct(struct_ref) = left_type_resolution.tag {
+            // The built-in struct methods take precedence over the entries.
+            if let Some(component) = <Struct>::type_meta()
+                .prototype()
+                .hint_component(&field_atom.0)
+            {
+                self.resolution.tag = Tag::from(component.ty);
+                return Ok(());
+            }
+
             let Some(ScriptNode::Struct { semantics, .. }) = struct_ref.deref(self.doc) else {
                 return Ok(());
             };
@@ -888,6 +1469,13 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             return Ok(());
         }
 
+        if let Tag::Enum(enum_ref) = left_type_resolution.tag {
+            if ScriptNode::enum_variant(self.doc, &enum_ref, &field_atom.0).is_some() {
+                self.resolution.tag = Tag::Variant(enum_ref);
+                return Ok(());
+            }
+        }
+
         let Some(receiver) = left_type_resolution.tag.type_meta() else {
             return Ok(());
         };
@@ -899,6 +1487,8 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         let receiver_prototype = receiver.prototype();
 
         if let Some(component) = receiver_prototype.hint_component(&field_atom.0) {
+            self.check_deprecation(infix_syntax.right, &component);
+
             self.resolution.tag = Tag::from(component.ty);
             return Ok(());
         }
@@ -999,6 +1589,15 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             return Ok(());
         };
 
+        if self.resolve_operator_overload(
+            &left_type_resolution.tag,
+            op_description.primary,
+            &infix_syntax.op_ref,
+            1,
+        )? {
+            return Ok(());
+        }
+
         let mut rhs_provided = TypeMeta::dynamic();
 
         if let Some(right_node) = infix_syntax.right.deref(self.doc) {
@@ -1124,6 +1723,97 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
+    // Resolves an operator applied to a script structure that overloads
+    // the operator with an entry (see OperatorKind::entry_name).
+    //
+    // The result type of the operator is unknown, because the entry function's
+    // result may depend on the operator application itself.
+    //
+    // Returns false if the receiver does not overload the operator.
+    fn resolve_operator_overload(
+        &mut self,
+        receiver: &Tag,
+        operator: OperatorKind,
+        op_ref: &NodeRef,
+        arguments: usize,
+    ) -> AnalysisResult<bool> {
+        let Some(name) = operator.entry_name() else {
+            return Ok(false);
+        };
+
+        let Some(entry) = self.resolve_operator_entry(receiver, name)? else {
+            return Ok(false);
+        };
+
+        self.check_operator_entry_arity(&entry, op_ref, arguments);
+
+        self.resolution.tag = match operator {
+            OperatorKind::PartialEq => Tag::Type(<bool>::type_meta()),
+            _ => Tag::dynamic(),
+        };
+
+        Ok(true)
+    }
+
+    // Returns the type of the struct entry with the specified name if
+    // the receiver is a script structure that has such an entry.
+    fn resolve_operator_entry(
+        &mut self,
+        receiver: &Tag,
+        name: &str,
+    ) -> AnalysisResult<Option<Tag>> {
+        let Tag::Struct(struct_ref) = receiver else {
+            return Ok(None);
+        };
+
+        let Some(ScriptNode::Struct { semantics, .. }) = struct_ref.deref(self.doc) else {
+            return Ok(None);
+        };
+
+        let struct_semantics = semantics.get().forward()?;
+
+        let struct_entries_map_syntax = struct_semantics
+            .struct_entries_map_syntax
+            .read(self.context)
+            .forward()?;
+
+        let Some((_, entry_value_ref)) = struct_entries_map_syntax.as_ref().map.get(name) else {
+            return Ok(None);
+        };
+
+        let Some(entry_value_node) = entry_value_ref.deref(self.doc) else {
+            return Ok(Some(Tag::dynamic()));
+        };
+
+        Ok(Some(
+            entry_value_node
+                .type_resolution()
+                .forward()?
+                .read(self.context)
+                .forward()?
+                .tag,
+        ))
+    }
+
+    fn check_operator_entry_arity(&mut self, entry: &Tag, op_ref: &NodeRef, arguments: usize) {
+        let Tag::Fn((_, arity)) = entry else {
+            return;
+        };
+
+        if *arity == arguments {
+            return;
+        }
+
+        let _ = self
+            .resolution
+            .issues
+            .insert(ScriptIssue::CallArityMismatch {
+                args_ref: *op_ref,
+                expected: *arity,
+                provided: arguments,
+            });
+    }
+
     fn resolve_query(&mut self) -> AnalysisResult<()> {
         self.resolution.tag = Tag::Type(<bool>::type_meta());
 
@@ -1150,6 +1840,15 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         }
 
         let Some(meta) = left_type_resolution.tag.invocation_meta() else {
+            if self.resolve_operator_overload(
+                &left_type_resolution.tag,
+                OperatorKind::Invocation,
+                &call_syntax.as_ref().call_args_ref,
+                call_syntax.as_ref().args.len(),
+            )? {
+                return Ok(());
+            }
+
             if let Some(receiver) = left_type_resolution.tag.type_meta() {
                 let _ = self
                     .resolution
@@ -1170,10 +1869,43 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             return Ok(());
         };
 
-        let expected_args = inputs.len();
+        let arranged;
+
+        let args = match call_syntax.as_ref().is_named() {
+            false => call_syntax.as_ref().args.as_slice(),
+
+            true => {
+                // Argument name issues are reported by the local diagnostics.
+                let Ok(order) = call_syntax.as_ref().arrange(inputs) else {
+                    return Ok(());
+                };
+
+                let mut slots = vec![NodeRef::nil(); inputs.len().max(order.len())];
+
+                for (param, arg_ref) in order.into_iter().zip(&call_syntax.as_ref().args) {
+                    slots[param] = *arg_ref;
+                }
+
+                arranged = slots;
+
+                arranged.as_slice()
+            }
+        };
+
+        let variadic = match meta.variadic {
+            true => inputs.last(),
+            false => None,
+        };
+
+        let expected_args = inputs.len() - variadic.is_some() as usize;
         let provided_args = call_syntax.as_ref().args.len();
 
-        if expected_args != provided_args {
+        let arity_mismatch = match variadic {
+            None => expected_args != provided_args,
+            Some(_) => expected_args > provided_args,
+        };
+
+        if arity_mismatch {
             let _ = self
                 .resolution
                 .issues
@@ -1182,9 +1914,14 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                     expected: expected_args,
                     provided: provided_args,
                 });
+        } else {
+            self.check_contracts(meta, args)?;
         }
 
-        let zip = inputs.iter().zip(call_syntax.as_ref().args.iter());
+        // The variadic parameter describes each of the remaining arguments.
+        let params = inputs.iter().chain(repeat(variadic).map_while(identity));
+
+        let zip = params.zip(args.iter());
 
         for (param, arg_ref) in zip {
             if param.hint.is_dynamic() {
@@ -1205,7 +1942,17 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 continue;
             }
 
-            let expected_family = param.hint.type_family();
+            // The struct literals are coerced to the parameter types that
+            // can be constructed from script structs, including the exported
+            // Rust structs.
+            if let (Some(schema), Tag::Struct(struct_ref)) =
+                (param.schema, &arg_type_resolution.tag)
+            {
+                self.check_struct_schema(arg_ref, struct_ref, schema)?;
+                continue;
+            }
+
+            let expected_family = param.hint.casted().type_family();
             let provided_family = arg_type_resolution.tag.type_family();
 
             if expected_family != provided_family {
@@ -1317,6 +2064,93 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
+    fn check_struct_schema(
+        &mut self,
+        arg_ref: &NodeRef,
+        struct_ref: &NodeRef,
+        schema: &'static StructSchema,
+    ) -> AnalysisResult<()> {
+        let Some(ScriptNode::Struct { semantics, .. }) = struct_ref.deref(self.doc) else {
+            return Ok(());
+        };
+
+        let struct_semantics = semantics.get().forward()?;
+
+        let struct_entries_map_syntax = struct_semantics
+            .struct_entries_map_syntax
+            .read(self.context)
+            .forward()?;
+
+        let entries = &struct_entries_map_syntax.as_ref().map;
+
+        if entries.len() > self.config.max_fan_out {
+            self.limit_fan_out(*arg_ref);
+            return Ok(());
+        }
+
+        for (key, (key_ref, value_ref)) in entries {
+            let Some(field) = schema.field(key.as_str()) else {
+                let _ = self
+                    .resolution
+                    .issues
+                    .insert(ScriptIssue::UnknownStructField {
+                        entry_key_ref: *key_ref,
+                        schema,
+                    });
+
+                continue;
+            };
+
+            let expected = (field.hint)();
+
+            if expected.is_dynamic() {
+                continue;
+            }
+
+            let Some(value_node) = value_ref.deref(self.doc) else {
+                continue;
+            };
+
+            let value_type_resolution = value_node
+                .type_resolution()
+                .forward()?
+                .read(self.context)
+                .forward()?;
+
+            if value_type_resolution.tag.is_dynamic() {
+                continue;
+            }
+
+            let expected_family = expected.casted().type_family();
+            let provided_family = value_type_resolution.tag.type_family();
+
+            if expected_family != provided_family {
+                let _ = self.resolution.issues.insert(ScriptIssue::TypeMismatch {
+                    expr_ref: *value_ref,
+                    expected: expected_family,
+                    provided: provided_family,
+                });
+            }
+        }
+
+        for field in schema.fields {
+            if !field.required || entries.contains_key(field.name) {
+                continue;
+            }
+
+            let _ = self
+                .resolution
+                .issues
+                .insert(ScriptIssue::MissingStructField {
+                    arg_ref: *arg_ref,
+                    schema,
+                    field: field.name,
+                });
+        }
+
+        Ok(())
+    }
+
     fn resolve_index(&mut self, semantics: &Semantics<IndexSemantics>) -> AnalysisResult<()> {
         let index_semantics = semantics.get().forward()?;
 
@@ -1329,12 +2163,39 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
                 .read(self.context)
                 .forward()?;
 
+            if let Some(entry) =
+                self.resolve_operator_entry(&left_type_resolution.tag, Struct::INDEX_ENTRY)?
+            {
+                self.check_operator_entry_arity(&entry, &index_syntax.index_arg_ref, 1);
+                self.resolution.tag = Tag::dynamic();
+
+                return Ok(());
+            }
+
             if let Tag::Type(receiver) = left_type_resolution.tag {
                 if receiver.is_nil() {
                     let _ = self.resolution.issues.insert(ScriptIssue::NilIndex {
                         op_ref: index_syntax.index_arg_ref,
                     });
                 }
+
+                // The environment's entries are host-defined, and their types
+                // are unknown statically.
+                if receiver == ScriptEnvType::type_meta() {
+                    self.resolution.tag = Tag::dynamic();
+
+                    return Ok(());
+                }
+            }
+
+            // The struct entries accessed by the string keys are resolved at
+            // runtime, and their types are unknown statically.
+            if left_type_resolution.tag.type_meta() == Some(<Struct>::type_meta())
+                && self.is_key_index(&index_syntax.index)?
+            {
+                self.resolution.tag = Tag::dynamic();
+
+                return Ok(());
             }
 
             self.resolution.tag = left_type_resolution.tag;
@@ -1354,6 +2215,16 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             let index_family = index_type_resolution.tag.type_family();
 
             if index_family.is_number() {
+                if self.resolution.tag.type_family().is_bytes() {
+                    self.resolution.tag = Tag::Type(<u8>::type_meta());
+                }
+
+                self.check_constant_index(
+                    &index_syntax.left,
+                    &index_syntax.index,
+                    index_type_resolution.constant,
+                )?;
+
                 return Ok(());
             }
 
@@ -1373,6 +2244,122 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
         Ok(())
     }
 
+    // Returns true if the index expression's type is a string, or if the type
+    // is unknown statically.
+    fn is_key_index(&mut self, index_ref: &NodeRef) -> AnalysisResult<bool> {
+        let Some(index_node) = index_ref.deref(self.doc) else {
+            return Ok(false);
+        };
+
+        let index_type_resolution = index_node
+            .type_resolution()
+            .forward()?
+            .read(self.context)
+            .forward()?;
+
+        let tag = &index_type_resolution.tag;
+
+        Ok(tag.is_dynamic() || tag.type_meta() == Some(<str>::type_meta()))
+    }
+
+    // Reports constant arguments that violate the function's preconditions.
+    fn check_contracts(
+        &mut self,
+        meta: &'static InvocationMeta,
+        args: &[NodeRef],
+    ) -> AnalysisResult<()> {
+        let mut preconditions = meta
+            .contracts
+            .iter()
+            .filter(|contract| contract.kind == ContractKind::Requires)
+            .peekable();
+
+        if preconditions.peek().is_none() {
+            return Ok(());
+        }
+
+        let mut values = Vec::with_capacity(args.len());
+
+        for arg_ref in args {
+            values.push(self.contract_value(arg_ref)?);
+        }
+
+        for contract in preconditions {
+            let Some(ContractValue::Bool(false)) = contract.expr.eval(&values, None) else {
+                continue;
+            };
+
+            let Some(index) = contract.param() else {
+                continue;
+            };
+
+            let Some(arg_ref) = args.get(index) else {
+                continue;
+            };
+
+            let _ = self
+                .resolution
+                .issues
+                .insert(ScriptIssue::ContractViolation {
+                    arg_ref: *arg_ref,
+                    argument: index + 1,
+                    condition: contract.condition,
+                });
+        }
+
+        Ok(())
+    }
+
+    fn contract_value(&mut self, arg_ref: &NodeRef) -> AnalysisResult<Option<ContractValue>> {
+        if let Some(value) = ScriptNode::extract_bool(self.doc, *arg_ref) {
+            return Ok(Some(ContractValue::Bool(value)));
+        }
+
+        if let Some(value) = self.node_constant(arg_ref)? {
+            return Ok(Some(ContractValue::Number(value as f64)));
+        }
+
+        Ok(float_literal(self.context, self.doc, arg_ref)?
+            .map(|value| ContractValue::Number(f64::from(value))))
+    }
+
+    // Reports constant indices that are out of the bounds of array and string
+    // literals regardless of the runtime IndexPolicy.
+    fn check_constant_index(
+        &mut self,
+        left_ref: &NodeRef,
+        index_ref: &NodeRef,
+        index: Option<isize>,
+    ) -> AnalysisResult<()> {
+        let Some(index) = index else {
+            return Ok(());
+        };
+
+        let Some(length) = literal_length(self.context, self.doc, left_ref)? else {
+            return Ok(());
+        };
+
+        let in_bounds = match usize::try_from(index) {
+            Ok(index) => index < length,
+            Err(_) => index.unsigned_abs() <= length,
+        };
+
+        if in_bounds {
+            return Ok(());
+        }
+
+        let _ = self
+            .resolution
+            .issues
+            .insert(ScriptIssue::IndexOutOfBounds {
+                arg_ref: *index_ref,
+                index,
+                length,
+            });
+
+        Ok(())
+    }
+
     fn resolve_expr(&mut self, semantics: &Semantics<ExprSemantics>) -> AnalysisResult<()> {
         let expr_semantics = semantics.get().forward()?;
 
@@ -1389,12 +2376,142 @@ impl<'doc, 'ctx, 'ctx_param, H: TaskHandle, S: SyncBuildHasher>
             .forward()?;
 
         self.resolution.tag = inner_type_resolution.tag;
+        self.resolution.constant = inner_type_resolution.constant;
 
         Ok(())
     }
+
+    #[inline(always)]
+    fn limit_fan_out(&mut self, node_ref: NodeRef) {
+        let _ = self.resolution.issues.insert(ScriptIssue::AnalysisLimit {
+            node_ref,
+            limit: AnalysisLimit::FanOut(self.config.max_fan_out),
+        });
+    }
+}
+
+fn let_constant<H: TaskHandle, S: SyncBuildHasher>(
+    context: &mut AttrContext<ScriptNode, H, S>,
+    doc: &ScriptDoc,
+    var_ref: &NodeRef,
+) -> AnalysisResult<LetConstant> {
+    let Some(ScriptNode::Var { semantics, .. }) = var_ref.deref(doc) else {
+        return Ok(LetConstant::default());
+    };
+
+    Ok(*semantics
+        .get()
+        .forward()?
+        .let_constant
+        .read(context)
+        .forward()?)
+}
+
+// Returns the value of the float literal, possibly negated.
+fn float_literal<H: TaskHandle, S: SyncBuildHasher>(
+    context: &mut AttrContext<ScriptNode, H, S>,
+    doc: &ScriptDoc,
+    node_ref: &NodeRef,
+) -> AnalysisResult<Option<FloatRepr>> {
+    match node_ref.deref(doc) {
+        Some(ScriptNode::Expr { inner, .. }) => float_literal(context, doc, inner),
+
+        Some(ScriptNode::UnaryLeft { op, right, .. }) => {
+            if ScriptNode::extract_op(doc, op) != Some(ScriptToken::Minus) {
+                return Ok(None);
+            }
+
+            Ok(float_literal(context, doc, right)?.map(|value| -value))
+        }
+
+        Some(ScriptNode::Number { semantics, .. }) => {
+            let number_value = semantics
+                .get()
+                .forward()?
+                .number_value
+                .read(context)
+                .forward()?;
+
+            match number_value.deref() {
+                LocalNumberValue::Float(Ok(value)) => Ok(Some(value.0)),
+                _ => Ok(None),
+            }
+        }
+
+        _ => Ok(None),
+    }
+}
+
+// Returns the number of items of the array or string literal, or of the literal
+// assigned to a let variable that cannot change its length.
+fn literal_length<H: TaskHandle, S: SyncBuildHasher>(
+    context: &mut AttrContext<ScriptNode, H, S>,
+    doc: &ScriptDoc,
+    node_ref: &NodeRef,
+) -> AnalysisResult<Option<usize>> {
+    match node_ref.deref(doc) {
+        Some(ScriptNode::Expr { inner, .. }) => literal_length(context, doc, inner),
+
+        Some(ScriptNode::String { start, end, .. }) => {
+            let (Some(start), Some(end)) = (start.site(doc), end.site(doc)) else {
+                return Ok(None);
+            };
+
+            let span = (start + 1)..end;
+
+            if !span.is_valid_span(doc) {
+                return Ok(None);
+            }
+
+            Ok(Some(doc.substring(span).chars().count()))
+        }
+
+        // The array constructor flattens nested arrays and concatenates
+        // strings, so the length is only known for scalar items.
+        Some(ScriptNode::Array { items, .. }) => {
+            for item in items {
+                let mut item = item;
+
+                while let Some(ScriptNode::Expr { inner, .. }) = item.deref(doc) {
+                    item = inner;
+                }
+
+                match item.deref(doc) {
+                    Some(ScriptNode::Number { .. } | ScriptNode::Bool { .. }) => (),
+                    Some(ScriptNode::Max { .. }) => (),
+                    _ => return Ok(None),
+                }
+            }
+
+            Ok(Some(items.len()))
+        }
+
+        Some(ScriptNode::Ident { semantics, .. }) => {
+            let ident_semantics = semantics.get().forward()?;
+
+            let cross_resolution = ident_semantics.cross_resolution.read(context).forward()?;
+
+            let IdentCrossResolution::Read { name } = cross_resolution.deref() else {
+                return Ok(None);
+            };
+
+            let Some(ScriptNode::Let { name, .. }) = name.as_ref().decl.deref(doc) else {
+                return Ok(None);
+            };
+
+            Ok(let_constant(context, doc, name)?.length)
+        }
+
+        _ => Ok(None),
+    }
 }
 
 impl ScriptToken {
+    #[inline(always)]
+    pub(crate) fn binary_operator(self) -> Option<OperatorKind> {
+        Some(self.describe_binary()?.primary)
+    }
+
     #[inline(always)]
     fn describe_binary(self) -> Option<BinaryOpDescription> {
         match self {
@@ -1577,6 +2694,7 @@ impl OperatorKind {
             Self::Hash => receiver.implements_hash(),
             Self::Invocation => receiver.implements_invocation(),
             Self::Binding => receiver.implements_binding(),
+            Self::Iterate => receiver.implements_iterate(),
             Self::Add => receiver.implements_add(),
             Self::AddAssign => receiver.implements_add_assign(),
             Self::Sub => receiver.implements_sub(),
@@ -1619,6 +2737,7 @@ impl OperatorKind {
             Self::Hash => None,
             Self::Invocation => None,
             Self::Binding => None,
+            Self::Iterate => None,
             Self::Add => receiver.hint_add_rhs(),
             Self::AddAssign => receiver.hint_add_assign_rhs(),
             Self::Sub => receiver.hint_sub_rhs(),
@@ -1683,6 +2802,8 @@ impl OperatorKind {
 
             Self::Binding => Tag::dynamic(),
 
+            Self::Iterate => Tag::Type(<ScriptIterator>::type_meta()),
+
             Self::Add => receiver
                 .hint_add_result()
                 .map(Tag::Type)
@@ -1775,3 +2896,222 @@ impl OperatorKind {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lady_deirdre::{
+        analysis::TriggerHandle,
+        lexis::{SourceCode, ToSpan},
+    };
+
+    use crate::{
+        analysis::{
+            symbols::{tests::TestPackage, LookupOptions, ModuleSymbol, SymbolKind},
+            IssueCode,
+            ModuleRead,
+            ScriptModule,
+        },
+        export,
+        runtime::{RuntimeError, ScriptPackage},
+    };
+
+    #[export]
+    #[derive(Default)]
+    pub struct CoercedWindow {
+        pub width: usize,
+        pub height: usize,
+    }
+
+    #[export]
+    pub fn coerced_window_area(window: &CoercedWindow) -> usize {
+        window.width * window.height
+    }
+
+    #[export]
+    pub fn coerced_window_width(window: CoercedWindow) -> usize {
+        window.width
+    }
+
+    fn out_of_bounds(text: &str) -> Vec<String> {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+        let module_text = read.text();
+
+        let mut hints = Vec::new();
+
+        for depth in 1..=3 {
+            let diagnostics = read.diagnostics(depth).unwrap();
+
+            for issue in &diagnostics {
+                if issue.code() == IssueCode::IndexOutOfBounds {
+                    let span = issue
+                        .origin(&module_text)
+                        .to_site_span(&module_text)
+                        .unwrap();
+
+                    hints.push(module_text.substring(span).to_string());
+                }
+            }
+        }
+
+        hints
+    }
+
+    fn ident_values(text: &str) -> Vec<String> {
+        let module = ScriptModule::new(TestPackage::meta(), text);
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let symbols = read
+            .symbols(.., LookupOptions::new().filter(SymbolKind::Ident as u32))
+            .unwrap();
+
+        symbols
+            .iter()
+            .map(|symbol| {
+                let ModuleSymbol::Ident(ident) = symbol else {
+                    panic!("Unexpected symbol.");
+                };
+
+                let name = ident.name(&read).unwrap().to_string();
+
+                match symbol.expr_value(&read).unwrap() {
+                    Some(value) => format!("{name} = {}", value.stringify(false)),
+                    None => name,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_constant_index_propagation() {
+        assert_eq!(out_of_bounds("let a = [1, 2, 3]; a[5];"), vec!["5"]);
+        assert_eq!(out_of_bounds("let i = 5; [1, 2, 3][i];"), vec!["i"]);
+        assert_eq!(
+            out_of_bounds("let a = [1, 2, 3]; let i = 12; a[i];"),
+            vec!["i"]
+        );
+        assert_eq!(
+            out_of_bounds("let a = \"abc\"; let i = -4; a[i];"),
+            vec!["i"]
+        );
+        assert!(out_of_bounds("let a = [1, 2, 3]; let i = -3; a[i];").is_empty());
+        assert!(out_of_bounds("let a = [1, 2, 3]; let i = 2; a[i];").is_empty());
+
+        assert_eq!(
+            out_of_bounds("let a = [1, 2, 3]; let i = 1; let j = i + 1; let k = j * 2; a[k];"),
+            vec!["k"],
+        );
+
+        assert_eq!(
+            out_of_bounds("let a = [1, 2, 3]; let i = 7; let j = (i % 4) - 1; a[j + 1];"),
+            vec!["j + 1"],
+        );
+
+        assert!(out_of_bounds("let a = [1, 2, 3]; let i = 12; i = 1; a[i];").is_empty());
+        assert!(out_of_bounds("let a = [1, 2, 3]; let i = 12; i -= 11; a[i];").is_empty());
+        assert!(out_of_bounds("let a = [1, 2, 3]; let i = 1; let j = i; i = 9; a[i];").is_empty());
+        assert!(out_of_bounds("let a = [1, 2, 3]; let i = 5 / 0; a[i];").is_empty());
+        assert!(out_of_bounds("let a = [1, 2, 3]; let i; i = 5; a[i];").is_empty());
+
+        assert!(out_of_bounds(
+            "let a = [1, 2, 3]; let i = 0; loop { a[i]; i += 1; if i == 5 { break; } }"
+        )
+        .is_empty());
+
+        assert!(
+            out_of_bounds("let a = [1, 2, 3]; let i = 4; let f = fn() { i = 0; }; f(); a[i];")
+                .is_empty()
+        );
+
+        assert!(out_of_bounds("let a = [1, 2, 3]; a += [4, 5]; a[4];").is_empty());
+        assert!(out_of_bounds("let a = [1, 2, 3]; let b = a; b[4];").is_empty());
+        assert_eq!(
+            out_of_bounds("let a = [1, 2, 3]; a[0] = a.len; a[4];"),
+            vec!["4"]
+        );
+    }
+
+    #[test]
+    fn test_constant_ident_values() {
+        assert_eq!(
+            ident_values("let i = 12; let j = i * 2 - 1; let k = j; k = 0; i + j + k;"),
+            vec!["i = 12", "j = 23", "k", "i = 12", "j = 23", "k"],
+        );
+
+        assert_eq!(ident_values("let i = 2.5; i;"), vec!["i"]);
+    }
+
+    #[test]
+    fn test_exported_struct_coercion() {
+        crate::assert_script_ok!(
+            TestPackage,
+            "coerced_window_area(struct { width: 8, height: 6 });",
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "return coerced_window_area(struct { width: 8, height: 6 });",
+            ),
+            48,
+        );
+
+        assert_eq!(
+            crate::eval_as!(
+                TestPackage,
+                usize,
+                "return coerced_window_width(struct { height: 6, width: 8 });",
+            ),
+            8,
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "coerced_window_area(struct { width: 8 });",
+            IssueCode::MissingStructField,
+            "struct",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "coerced_window_area(struct { width: 8, height: 6, depth: 1 });",
+            IssueCode::UnknownStructField,
+            "depth",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "coerced_window_area(struct { width: 8, height: true });",
+            IssueCode::TypeMismatch,
+            "true",
+        );
+
+        crate::assert_script_err!(
+            TestPackage,
+            "coerced_window_area(10);",
+            IssueCode::TypeMismatch,
+            "10",
+        );
+
+        let module = ScriptModule::new(
+            TestPackage::meta(),
+            "let w = struct { width: 8, height: 6 }; w.depth = 1; coerced_window_area(w);",
+        );
+
+        let handle = TriggerHandle::new();
+        let read = module.read(&handle, 1).unwrap();
+
+        let Err(RuntimeError::StructFields {
+            missing, unknown, ..
+        }) = read.compile().unwrap().run()
+        else {
+            panic!("Missing struct fields error.");
+        };
+
+        assert!(missing.is_empty());
+        assert_eq!(unknown, ["depth"]);
+    }
+}
@@ -41,8 +41,10 @@ use lsp_types::{
     request::CodeActionRequest,
     CodeAction,
     CodeActionContext,
+    CodeActionDisabled,
     CodeActionKind,
     CodeActionOrCommand,
+    CodeActionTriggerKind,
     Range,
     TextEdit,
     Uri,
@@ -50,14 +52,19 @@ use lsp_types::{
 };
 
 use crate::{
-    analysis::{ModuleError, ModuleRead},
+    analysis::{
+        symbols::{LookupOptions, ModuleSymbol, SymbolKind, VarKind},
+        InlinePlan,
+        ModuleError,
+        ModuleRead,
+    },
     server::{
         diagnostics::DiagnosticData,
         file::{LspModule, ANALYSIS_PRIORITY},
         logger::LSP_SERVER_LOG,
         rpc::{LspHandle, OutgoingEx, RpcId, RpcLatches},
         tasks::{Task, TaskExecution, COOL_DOWN},
-        utils::span_to_range,
+        utils::{range_to_span, span_to_range},
         RpcSender,
     },
 };
@@ -137,6 +144,120 @@ impl Task for SendCodeAction {
 
             let mut actions = Vec::new();
 
+            let symbols = match module_read_guard.symbols(
+                range_to_span(&message.range),
+                LookupOptions::new().filter(SymbolKind::Var as u32),
+            ) {
+                Ok(symbols) => symbols,
+
+                Err(ModuleError::Interrupted(_)) => {
+                    if message.cancel.is_active() {
+                        warn!(target: LSP_SERVER_LOG, "[{}] Send code action cancelled by the client.", message.uri.as_str());
+
+                        self.outgoing.send_err_response(
+                            &self.latches,
+                            message.id,
+                            REQUEST_CANCELLED,
+                            "Send code action cancelled by the client.",
+                        );
+
+                        break;
+                    }
+
+                    warn!(target: LSP_SERVER_LOG, "[{}] Send code action interrupted.", message.uri.as_str());
+                    park_timeout(COOL_DOWN);
+                    continue;
+                }
+
+                Err(_) => Vec::new(),
+            };
+
+            if let Some(ModuleSymbol::Var(symbol)) = symbols.first() {
+                if symbol.kind(&module_read_guard) == VarKind::LetVar {
+                    let plan = match module_read_guard.inline_variable(symbol) {
+                        Ok(plan) => plan,
+
+                        Err(ModuleError::Interrupted(_)) => {
+                            if message.cancel.is_active() {
+                                warn!(target: LSP_SERVER_LOG, "[{}] Send code action cancelled by the client.", message.uri.as_str());
+
+                                self.outgoing.send_err_response(
+                                    &self.latches,
+                                    message.id,
+                                    REQUEST_CANCELLED,
+                                    "Send code action cancelled by the client.",
+                                );
+
+                                break;
+                            }
+
+                            warn!(target: LSP_SERVER_LOG, "[{}] Send code action interrupted.", message.uri.as_str());
+                            park_timeout(COOL_DOWN);
+                            continue;
+                        }
+
+                        Err(error) => {
+                            error!(target: LSP_SERVER_LOG, "[{}] Inline variable error. {error}", message.uri.as_str());
+
+                            InlinePlan::Edits(Vec::new())
+                        }
+                    };
+
+                    let title = match symbol.var_name(&module_read_guard) {
+                        Some(name) => format!("inline variable `{name}`"),
+                        None => String::from("inline variable"),
+                    };
+
+                    match plan {
+                        InlinePlan::Edits(edits) => {
+                            let edits = edits
+                                .into_iter()
+                                .filter_map(|edit| {
+                                    let span = edit.origin.to_position_span(&module_text)?;
+
+                                    Some(TextEdit {
+                                        range: span_to_range(&span),
+                                        new_text: edit.text,
+                                    })
+                                })
+                                .collect::<Vec<_>>();
+
+                            if !edits.is_empty() {
+                                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                                    title,
+                                    kind: Some(CodeActionKind::REFACTOR_INLINE),
+                                    edit: Some(WorkspaceEdit {
+                                        changes: Some(HashMap::from([(
+                                            message.uri.clone(),
+                                            edits,
+                                        )])),
+
+                                        ..WorkspaceEdit::default()
+                                    }),
+
+                                    ..CodeAction::default()
+                                }));
+                            }
+                        }
+
+                        InlinePlan::Refused(refusal) => {
+                            if message.context.trigger_kind == Some(CodeActionTriggerKind::INVOKED)
+                            {
+                                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                                    title,
+                                    kind: Some(CodeActionKind::REFACTOR_INLINE),
+                                    disabled: Some(CodeActionDisabled {
+                                        reason: refusal.to_string(),
+                                    }),
+
+                                    ..CodeAction::default()
+                                }));
+                            }
+                        }
+                    }
+                }
+            }
+
             for diagnostic in message.context.diagnostics {
                 let Some(data) = &diagnostic.data else {
                     continue;
@@ -152,7 +273,10 @@ impl Task for SendCodeAction {
                         title.push_str(" and ");
                     }
 
-                    title.push_str(&format!("rename to {text:?}"));
+                    match text.is_empty() {
+                        true => title.push_str("remove"),
+                        false => title.push_str(&format!("rename to {text:?}")),
+                    }
 
                     edits.push(TextEdit {
                         range: diagnostic.range,
@@ -221,7 +345,6 @@ pub(super) struct SendCodeActionMessage {
     pub(super) id: RpcId,
     pub(super) uri: Uri,
     pub(super) cancel: Trigger,
-    #[allow(unused)]
     pub(super) range: Range,
     pub(super) context: CodeActionContext,
 }
@@ -60,10 +60,11 @@ use serde_json::Value;
 use crate::{
     analysis::{ModuleError, ModuleRead},
     interpret::{set_runtime_hook, ScriptFn},
-    runtime::{Origin, RuntimeError, ScriptOrigin},
+    runtime::{set_borrow_deadline, Origin, RuntimeError, ScriptOrigin},
     server::{
         file::{LspModule, COMMAND_PRIORITY},
         logger::{LSP_CLIENT_LOG, LSP_SERVER_LOG},
+        protocol::{CMD_CLEANUP, CMD_LAUNCH, CMD_STOP},
         rpc::{LspHandle, OutgoingEx, RpcId, RpcLatches},
         tasks::{Task, TaskExecution, COOL_DOWN, TIMEOUT},
         LspServerConfig,
@@ -71,10 +72,6 @@ use crate::{
     },
 };
 
-pub(super) static CMD_LAUNCH: &'static str = "script_launch";
-pub(super) static CMD_STOP: &'static str = "script_stop";
-pub(super) static CMD_CLEANUP: &'static str = "script_cleanup";
-
 thread_local! {
     static THREAD_RUNNER_STATE: UnsafeCell<Option<RunnerThreadData>> = const {
         UnsafeCell::new(None)
@@ -558,6 +555,7 @@ impl SendExecuteCommand {
             let runner_state = self.runner_state.clone();
             let outgoing = self.outgoing.clone();
             let inlay_hints_refresher = self.spawn_inlay_hints_refresher(&uri);
+            let borrow_deadline = self.config.borrow_deadline;
 
             builder.spawn(move || {
                 let uri = uri;
@@ -599,6 +597,8 @@ impl SendExecuteCommand {
                     }
                 });
 
+                set_borrow_deadline(borrow_deadline);
+
                 set_runner_thread_data(Some(RunnerThreadData {
                     message_index: AHashMap::new(),
                     runner_state: runner_state.clone(),
@@ -41,6 +41,8 @@ use lsp_types::{
     request::Completion,
     CompletionItem as LSPCompletionItem,
     CompletionItemKind,
+    CompletionItemTag,
+    CompletionList,
     CompletionResponse,
     Documentation,
     InsertTextFormat,
@@ -51,11 +53,13 @@ use lsp_types::{
 use crate::{
     analysis::{
         symbols::ModuleSymbol,
+        Closeness,
         CompletionItem,
         CompletionScope,
         ModuleError,
         ModuleRead,
         ModuleWrite,
+        StringEstimation,
     },
     server::{
         file::{LspModule, COMPLETION_PRIORITY},
@@ -64,6 +68,8 @@ use crate::{
         snippets::*,
         tasks::{Task, TaskExecution, COOL_DOWN},
         utils::{lsp_position_to_ld, make_doc},
+        LspCompletionConfig,
+        LspCompletionMatching,
         LspServerConfig,
         RpcSender,
     },
@@ -185,9 +191,12 @@ impl Task for SendCompletion {
 
             let text = module_write_guard.text();
 
+            let (items, is_incomplete) =
+                select_items(&message.config, &completions.pattern, completions.items);
+
             let mut result = Vec::new();
 
-            for item in completions.items {
+            for (rank, item) in items.into_iter().enumerate() {
                 let mut label = item.label.to_string();
 
                 let kind = completion_kind(completions.scope, &item);
@@ -218,9 +227,10 @@ impl Task for SendCompletion {
                     }
                 }
 
-                let detail = match item.desc.type_hint.is_dynamic() {
-                    true => None,
-                    false => Some(item.desc.type_hint.to_string()),
+                let detail = match (item.desc.package(), item.desc.type_hint.is_dynamic()) {
+                    (Some(package), _) => Some(format!("{} {}", package.name(), package.version())),
+                    (None, true) => None,
+                    (None, false) => Some(item.desc.type_hint.to_string()),
                 };
 
                 let doc = make_doc(
@@ -237,8 +247,15 @@ impl Task for SendCompletion {
                     kind: Some(kind),
                     detail,
                     documentation: doc.map(|doc| Documentation::MarkupContent(doc)),
+                    tags: item
+                        .desc
+                        .deprecated
+                        .map(|_| vec![CompletionItemTag::DEPRECATED]),
                     insert_text,
                     insert_text_format,
+                    // Preserves the server-side ranking in the clients that
+                    // sort the items by labels otherwise.
+                    sort_text: Some(format!("{rank:05}")),
 
                     ..Default::default()
                 });
@@ -259,6 +276,7 @@ impl Task for SendCompletion {
                     CompletionScope::Statement => {
                         result.push(SnippetUse::item(&self.config));
                         result.push(SnippetLet::item(&self.config));
+                        result.push(SnippetEnum::item(&self.config));
                         result.push(SnippetFor::item(&self.config));
                         result.push(SnippetLoop::item(&self.config));
                         result.push(SnippetIf::item(&self.config));
@@ -289,7 +307,10 @@ impl Task for SendCompletion {
             self.outgoing.send_ok_response::<Completion>(
                 &self.latches,
                 message.id,
-                Some(CompletionResponse::Array(result)),
+                Some(CompletionResponse::List(CompletionList {
+                    is_incomplete,
+                    items: result,
+                })),
             );
 
             break;
@@ -309,6 +330,62 @@ pub(super) struct SendCompletionMessage {
     pub(super) uri: Uri,
     pub(super) cancel: Trigger,
     pub(super) position: Position,
+    pub(super) config: LspCompletionConfig,
+}
+
+// Filters the completion candidates by the typed text (the `pattern`), ranks
+// them by closeness, and keeps at most `config.max_items` of the best-ranked
+// candidates.
+//
+// The returned flag is true if the client should re-query the server when
+// the user types more characters: either because some candidates were cut
+// off by the limit, or because the closeness threshold filtered out
+// candidates that may match a longer pattern.
+fn select_items(
+    config: &LspCompletionConfig,
+    pattern: &str,
+    items: Vec<CompletionItem>,
+) -> (Vec<CompletionItem>, bool) {
+    let mut is_incomplete = false;
+    let mut ranked = Vec::with_capacity(items.len());
+
+    for item in items {
+        let label = item.label.to_string();
+
+        let closeness = match (label.starts_with(pattern), config.matching) {
+            (true, _) => Closeness::one(),
+
+            (false, LspCompletionMatching::Prefix) => continue,
+
+            (false, _) => {
+                let closeness = label.estimate(pattern);
+
+                if closeness < config.min_closeness {
+                    is_incomplete = true;
+                    continue;
+                }
+
+                closeness
+            }
+        };
+
+        ranked.push((closeness, item));
+    }
+
+    // The items are already sorted in their declaration order, and the sorting
+    // is stable.
+    ranked.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    if let Some(max_items) = config.max_items {
+        if ranked.len() > max_items {
+            ranked.truncate(max_items);
+            is_incomplete = true;
+        }
+    }
+
+    let items = ranked.into_iter().map(|(_, item)| item).collect();
+
+    (items, is_incomplete)
 }
 
 fn completion_kind(scope: CompletionScope, item: &CompletionItem) -> CompletionItemKind {
@@ -322,7 +399,7 @@ fn completion_kind(scope: CompletionScope, item: &CompletionItem) -> CompletionI
                 return CompletionItemKind::FUNCTION;
             }
 
-            if item.desc.type_hint.is_package() {
+            if item.desc.type_hint.is_package() || item.desc.type_hint.is_namespace() {
                 return CompletionItemKind::MODULE;
             }
 
@@ -338,11 +415,13 @@ fn completion_kind(scope: CompletionScope, item: &CompletionItem) -> CompletionI
                 return CompletionItemKind::METHOD;
             }
 
-            if item.desc.type_hint.is_package() {
+            if item.desc.type_hint.is_package() || item.desc.type_hint.is_namespace() {
                 return CompletionItemKind::MODULE;
             }
 
             CompletionItemKind::FIELD
         }
+
+        CompletionScope::StructEntry => CompletionItemKind::FIELD,
     }
 }
@@ -32,17 +32,31 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Formatter},
+    hash::{Hash, Hasher},
+    net::SocketAddr,
+    time::Duration,
+};
 
 use log::{Level, LevelFilter};
-use lsp_types::{ClientCapabilities, MarkupKind};
+use lsp_types::{ClientCapabilities, Location, MarkupKind};
+use serde_json::Value;
+
+use crate::{
+    analysis::{AnalysisConfig, Closeness, LintPass},
+    runtime::RustOrigin,
+    server::{provider::LspDocumentProvider, rpc::LspHandle},
+};
 
 /// A general configuration object for the Language Server.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 #[non_exhaustive]
 pub struct LspServerConfig {
-    /// If set to true, the server's job manager will use dedicated threads.
-    /// Otherwise, all LSP messages will be handled by a single thread.
+    /// If set to true, the server's job manager will execute the jobs on a
+    /// pool of worker threads. Otherwise, all LSP messages will be handled by
+    /// a single thread.
     ///
     /// In general, multi-threading mode improves the server's performance.
     ///
@@ -50,6 +64,21 @@ pub struct LspServerConfig {
     /// For wasm targets, the default value is false.
     pub multi_thread: bool,
 
+    /// The number of worker threads in the pool shared by the background jobs
+    /// of all script modules opened in the editor.
+    ///
+    /// The pool schedules the jobs of different modules in a round-robin
+    /// fashion, such that a large module does not delay the jobs of other
+    /// modules.
+    ///
+    /// If the value is zero, the pool size matches the number of CPU cores
+    /// (see [available_parallelism](std::thread::available_parallelism)).
+    ///
+    /// This option requires the `multi_thread` flag to be enabled.
+    ///
+    /// The default value is zero.
+    pub analysis_threads: usize,
+
     /// If set to true, the server provides script running functionality in
     /// the code editor.
     ///
@@ -60,9 +89,23 @@ pub struct LspServerConfig {
     /// wasm target; otherwise, the default value is false.
     pub scripts_runner: bool,
 
+    /// The [borrow deadline](crate::runtime::set_borrow_deadline) of the
+    /// script runner threads.
+    ///
+    /// If the script being run in the code editor accesses data that the host
+    /// application holds on another thread, the runner waits for the release
+    /// of the data no longer than this deadline and then fails with a
+    /// [BorrowTimeout](crate::runtime::RuntimeError::BorrowTimeout) error.
+    ///
+    /// If the value is None, the runner fails immediately on the conflicting
+    /// access.
+    ///
+    /// The default value is None.
+    pub borrow_deadline: Option<Duration>,
+
     /// If specified, the server spawns a dedicated thread that periodically
-    /// checks the health of other tasks. If a worker thread is not responding,
-    /// the server attempts to cancel its task and respawn the thread.
+    /// checks the health of the worker threads. If a worker thread does not
+    /// finish its job in time, the server attempts to cancel the job.
     ///
     /// This option requires the `multi_thread` flag to be enabled.
     ///
@@ -85,6 +128,99 @@ pub struct LspServerConfig {
     /// using this option.
     pub file_ext: &'static str,
 
+    /// A list of name patterns excluded from the dead code hints.
+    ///
+    /// Script functions, struct entries, and imported packages whose names
+    /// match any of these patterns are never reported as unused. See
+    /// [set_dead_code_allow](crate::analysis::ModuleWrite::set_dead_code_allow)
+    /// for the pattern syntax.
+    ///
+    /// The default value is an empty list.
+    pub dead_code_allow: &'static [&'static str],
+
+    /// The limits of the semantic analysis of the opened files.
+    ///
+    /// The limits keep the server responsive when the user opens a file with
+    /// pathological source code. See [AnalysisConfig] for details.
+    ///
+    /// The default value is [AnalysisConfig::default].
+    pub analysis_config: AnalysisConfig,
+
+    /// If true, the hover popup over the name of a function marked with the
+    /// `#[export(pure)]` attribute shows the result of the invocation when all
+    /// invocation arguments are constants (see
+    /// [expr_value](crate::analysis::symbols::ModuleSymbol::expr_value)).
+    ///
+    /// The language server evaluates such functions on its own threads. Turn
+    /// this option off if the server analyzes scripts in a context where
+    /// invoking the exported Rust functions is undesirable.
+    ///
+    /// The default value is true.
+    pub pure_folding: bool,
+
+    /// If true, the hover popup over a struct entry access with an unknown
+    /// type notes that the host may resolve missing struct entries at runtime.
+    ///
+    /// Enable this option if the application evaluates the scripts with the
+    /// [entry provider](crate::runtime::set_entry_provider) installed. The
+    /// semantic analyzer is unaware of the provider's entries.
+    ///
+    /// The default value is false.
+    pub dynamic_entries: bool,
+
+    /// If true, the server measures the wall time of the analysis queries
+    /// of the opened files (see
+    /// [set_stats_enabled](crate::analysis::ScriptModule::set_stats_enabled)).
+    ///
+    /// The client can fetch the collected statistics through the custom
+    /// `adastra/perf` request, and the host can fetch them through the
+    /// [LspServer::module_stats](crate::server::LspServer::module_stats)
+    /// function. The server also logs the aggregated statistics on shutdown.
+    ///
+    /// The default value is false.
+    pub perf_stats: bool,
+
+    /// A storage through which the server loads the script documents that
+    /// the client has not opened in the editor (see [DocumentProvider](crate::server::DocumentProvider)
+    /// for details).
+    ///
+    /// The default value is [LspDocumentProvider::filesystem], which reads
+    /// `file:` URIs from the local file system.
+    pub document_provider: LspDocumentProvider,
+
+    /// The host's custom [lint passes](LintPass) that the server registers in
+    /// each opened file.
+    ///
+    /// The issues reported by the passes are published to the client together
+    /// with the built-in diagnostics, using the lint's own code as the
+    /// diagnostic's code.
+    ///
+    /// The default value is [LspLintPasses::new], which does not contain any
+    /// passes.
+    pub lint_passes: LspLintPasses,
+
+    /// A function that resolves the location of the Rust source code of the
+    /// exported Rust types.
+    ///
+    /// The server uses this function to respond to the "go to type definition"
+    /// requests when the inferred type of the script construction is an
+    /// exported Rust type (see
+    /// [type_definition](crate::analysis::ModuleRead::type_definition)). If
+    /// the value is None, or if the function returns None, the server responds
+    /// with the location of the type's module documentation on docs.rs (see
+    /// [DefinitionTarget::docs_url](crate::analysis::DefinitionTarget::docs_url)).
+    ///
+    /// The default value is None.
+    pub rust_sources: Option<fn(&'static RustOrigin) -> Option<Location>>,
+
+    /// Configures the size and the matching algorithm of the code completion
+    /// responses.
+    ///
+    /// The client can override these options at runtime through the
+    /// `workspace/didChangeConfiguration` notification (see
+    /// [LspCompletionConfig] for details).
+    pub completion: LspCompletionConfig,
+
     /// Configures the client-side and server-side logger.
     pub logger: LspLoggerConfig,
 
@@ -123,16 +259,271 @@ impl LspServerConfig {
 
         Self {
             multi_thread,
+            analysis_threads: 0,
             scripts_runner: scripts_runtime,
+            borrow_deadline: None,
             health_check,
             language_id: "adastra",
             file_ext: "adastra",
+            dead_code_allow: &[],
+            analysis_config: AnalysisConfig::new(),
+            pure_folding: true,
+            dynamic_entries: false,
+            perf_stats: false,
+            document_provider: LspDocumentProvider::filesystem(),
+            lint_passes: LspLintPasses::new(),
+            rust_sources: None,
+            completion: LspCompletionConfig::new(),
             logger: LspLoggerConfig::new(),
             capabilities: LspCapabilities::new(),
         }
     }
 }
 
+/// A configuration of the code completion responses of the Language Server.
+///
+/// The server filters the completion candidates by the text the user has
+/// typed so far, ranks them, and sends at most
+/// [max_items](Self::max_items) of the best-ranked candidates to the client.
+/// If some candidates were cut off, the server marks the response as
+/// incomplete, such that the client re-queries the server as the user types
+/// more characters, and each subsequent response refines the previous one.
+///
+/// The client can override these options through the
+/// `workspace/didChangeConfiguration` notification with the following
+/// settings object (all fields are optional):
+///
+/// ```json
+/// {
+///     "adastra": {
+///         "completion": {
+///             "maxItems": 100,
+///             "minCloseness": 50,
+///             "matching": "fuzzy"
+///         }
+///     }
+/// }
+/// ```
+///
+/// The root key is the [language_id](LspServerConfig::language_id) of the
+/// server. The `maxItems` value of zero removes the limit, and the
+/// `minCloseness` value is a percentage.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub struct LspCompletionConfig {
+    /// The maximum number of completion candidates in a single response.
+    ///
+    /// If None, the number of candidates is unlimited.
+    ///
+    /// The default value is 200.
+    pub max_items: Option<usize>,
+
+    /// The minimum similarity score between a candidate's label and the typed
+    /// text that the candidate needs to be included in the response.
+    ///
+    /// Candidates whose labels start with the typed text are always included.
+    /// This option is ignored by the [Prefix](LspCompletionMatching::Prefix)
+    /// matching algorithm.
+    ///
+    /// The default value is [Closeness::zero], meaning that the score
+    /// only affects the ranking of the candidates.
+    pub min_closeness: Closeness,
+
+    /// The algorithm that matches the completion candidates with the typed
+    /// text.
+    ///
+    /// The default value is [LspCompletionMatching::Fuzzy].
+    pub matching: LspCompletionMatching,
+}
+
+impl Default for LspCompletionConfig {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LspCompletionConfig {
+    /// The default constructor for this configuration object.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            max_items: Some(200),
+            min_closeness: Closeness::zero(),
+            matching: LspCompletionMatching::Fuzzy,
+        }
+    }
+
+    // Applies the client-side settings object received through
+    // the "workspace/didChangeConfiguration" notification.
+    pub(super) fn apply_settings(&mut self, settings: &Value) {
+        let Some(Value::Object(settings)) = settings.get("completion") else {
+            return;
+        };
+
+        if let Some(max_items) = settings.get("maxItems").and_then(Value::as_u64) {
+            self.max_items = match max_items {
+                0 => None,
+                _ => Some(max_items as usize),
+            };
+        }
+
+        if let Some(percents) = settings.get("minCloseness").and_then(Value::as_u64) {
+            self.min_closeness = Closeness::from_percents(percents.min(100) as u16);
+        }
+
+        if let Some(matching) = settings.get("matching").and_then(Value::as_str) {
+            match matching {
+                "prefix" => self.matching = LspCompletionMatching::Prefix,
+                "fuzzy" => self.matching = LspCompletionMatching::Fuzzy,
+                _ => (),
+            }
+        }
+    }
+}
+
+/// An algorithm that matches code completion candidates with the text typed
+/// by the user.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub enum LspCompletionMatching {
+    /// Only the candidates whose labels start with the typed text are
+    /// included in the response.
+    ///
+    /// This is the fastest option, suitable for packages with a large number
+    /// of exported items.
+    Prefix,
+
+    /// All candidates are included in the response, ranked by their
+    /// [similarity](crate::analysis::StringEstimation) to the typed text.
+    ///
+    /// The candidates whose labels start with the typed text go first.
+    ///
+    /// This is the default variant.
+    Fuzzy,
+}
+
+impl Default for LspCompletionMatching {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LspCompletionMatching {
+    /// The default constructor for this enum.
+    ///
+    /// The default variant is `Fuzzy`.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self::Fuzzy
+    }
+}
+
+/// A list of the host's [lint passes](LintPass) used by the language server.
+///
+/// See [LspServerConfig::lint_passes] for details.
+///
+/// Two LspLintPasses objects are equal if they refer to the same slice of
+/// passes.
+///
+/// ```
+/// use ad_astra::{
+///     analysis::{LintContext, LintPass, ModuleReadGuard, ModuleResult},
+///     lady_deirdre::analysis::TaskHandle,
+///     server::{LspLintPasses, LspServerConfig},
+/// };
+///
+/// struct MyLint;
+///
+/// impl<H: TaskHandle> LintPass<H> for MyLint {
+///     fn name(&self) -> &str {
+///         "my-lint"
+///     }
+///
+///     fn check(&self, _read: &ModuleReadGuard<H>, _context: &mut LintContext) -> ModuleResult<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut config = LspServerConfig::new();
+///
+/// config.lint_passes = LspLintPasses::from_slice(&[&MyLint]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct LspLintPasses(&'static [&'static dyn LintPass<LspHandle>]);
+
+impl Default for LspLintPasses {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for LspLintPasses {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_list()
+            .entries(self.0.iter().map(|pass| pass.name()))
+            .finish()
+    }
+}
+
+impl PartialEq for LspLintPasses {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl Eq for LspLintPasses {}
+
+impl PartialOrd for LspLintPasses {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LspLintPasses {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+impl Hash for LspLintPasses {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state)
+    }
+}
+
+impl LspLintPasses {
+    /// Creates an empty list of passes.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self(&[])
+    }
+
+    /// Creates a list of the specified `passes`.
+    #[inline(always)]
+    pub const fn from_slice(passes: &'static [&'static dyn LintPass<LspHandle>]) -> Self {
+        Self(passes)
+    }
+
+    /// Returns the underlying passes.
+    #[inline(always)]
+    pub fn get(&self) -> &'static [&'static dyn LintPass<LspHandle>] {
+        self.0
+    }
+
+    #[inline(always)]
+    fn addr(&self) -> (usize, usize) {
+        (self.0.as_ptr() as usize, self.0.len())
+    }
+}
+
 /// An LSP capabilities configuration object for the Language Server.
 ///
 /// By default, all flags are set to true.
@@ -193,6 +584,11 @@ pub struct LspCapabilities {
     /// implementation.
     pub goto_implementation: bool,
 
+    /// When enabled, the editor provides a jump-to-type-definition feature for
+    /// code symbols. For example, the user can jump from a variable to the
+    /// constructor of the struct assigned to this variable.
+    pub goto_type_definition: bool,
+
     /// When enabled, the editor provides quickfix refactoring suggestions to
     /// address some of the diagnostic messages.
     ///
@@ -263,6 +659,7 @@ impl LspCapabilities {
             goto_definition: true,
             document_highlight: true,
             goto_implementation: true,
+            goto_type_definition: true,
             code_action: true,
             signature_help: true,
             signature_help_markdown: true,
@@ -289,6 +686,7 @@ impl LspCapabilities {
             goto_definition: false,
             document_highlight: false,
             goto_implementation: false,
+            goto_type_definition: false,
             code_action: false,
             signature_help: false,
             signature_help_markdown: false,
@@ -338,6 +736,7 @@ impl LspCapabilities {
             result.goto_definition = text_document.definition.is_some();
             result.document_highlight = text_document.document_highlight.is_some();
             result.goto_implementation = text_document.implementation.is_some();
+            result.goto_type_definition = text_document.type_definition.is_some();
             result.code_action = text_document.code_action.is_some();
 
             if let Some(signature_help) = &text_document.signature_help {
@@ -382,6 +781,7 @@ impl LspCapabilities {
         self.goto_definition = self.goto_definition && other.goto_definition;
         self.document_highlight = self.document_highlight && other.document_highlight;
         self.goto_implementation = self.goto_implementation && other.goto_implementation;
+        self.goto_type_definition = self.goto_type_definition && other.goto_type_definition;
         self.code_action = self.code_action && other.code_action;
         self.signature_help = self.signature_help && other.signature_help;
         self.signature_help_markdown =
@@ -137,7 +137,8 @@ impl Task for SendGotoDefinition {
             const GOTO_SYMBOLS: u32 = (SymbolKind::Break as u32)
                 | (SymbolKind::Return as u32)
                 | (SymbolKind::Ident as u32)
-                | (SymbolKind::Field as u32);
+                | (SymbolKind::Field as u32)
+                | (SymbolKind::Operator as u32);
 
             let symbols = match module_read_guard.symbols(
                 position..position,
@@ -205,6 +206,13 @@ impl Task for SendGotoDefinition {
                             None => ModuleSymbol::Nil,
                         }),
 
+                    Some(ModuleSymbol::Operator(symbol)) => symbol
+                        .declaration(&module_read_guard)
+                        .map(|symbol| match symbol {
+                            Some(symbol) => ModuleSymbol::Entry(symbol),
+                            None => ModuleSymbol::Nil,
+                        }),
+
                     _ => {
                         self.outgoing.send_ok_response::<GotoDefinition>(
                             &self.latches,
@@ -32,14 +32,18 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::sync::Mutex;
+use std::time::Duration;
 
-use lady_deirdre::{lexis::ToSpan, sync::Shared};
+use ahash::AHashMap;
+use lady_deirdre::lexis::{SiteSpan, ToSpan};
 use log::{error, warn};
 use lsp_types::{
     notification::PublishDiagnostics,
     Diagnostic,
+    DiagnosticRelatedInformation,
     DiagnosticSeverity,
+    DiagnosticTag,
+    Location,
     NumberOrString,
     PublishDiagnosticsParams,
     Uri,
@@ -49,6 +53,7 @@ use serde_json::{Map, Value};
 use crate::{
     analysis::{
         DiagnosticsDepth,
+        IssueCode,
         IssueQuickfix,
         IssueSeverity,
         ModuleDiagnostics,
@@ -60,21 +65,23 @@ use crate::{
         file::{LspModule, DIAGNOSTICS_PRIORITY},
         logger::{LSP_CLIENT_LOG, LSP_SERVER_LOG},
         rpc::{LspHandle, OutgoingEx},
-        tasks::{Task, TaskExecution},
+        tasks::{Task, TaskExecution, DIAGNOSTICS_COOL_DOWN},
         utils::span_to_range,
         RpcSender,
     },
 };
 
-pub(super) struct DiagnosticsPublisher<const DEPTH: DiagnosticsDepth> {
+pub(super) struct DiagnosticsPublisher {
     pub(super) outgoing: RpcSender,
     pub(super) module: LspModule,
-    pub(super) diagnostics: Shared<Mutex<FileDiagnostics>>,
+    pub(super) diagnostics: FileDiagnostics,
 }
 
-impl<const DEPTH: DiagnosticsDepth> Task for DiagnosticsPublisher<DEPTH> {
+impl Task for DiagnosticsPublisher {
     const EXECUTION: TaskExecution = TaskExecution::ExecuteLatest;
 
+    const COOL_DOWN: Duration = DIAGNOSTICS_COOL_DOWN;
+
     type Config = Self;
 
     type Message = PublishContext;
@@ -84,7 +91,11 @@ impl<const DEPTH: DiagnosticsDepth> Task for DiagnosticsPublisher<DEPTH> {
         config
     }
 
-    #[inline(always)]
+    // Publishes the diagnostics in waves of increasing depth. Syntax errors are
+    // cheap to compute and reach the client right after the edit, while the
+    // deeper semantic waves follow as soon as they are ready. Each wave
+    // republishes the latest results of all depths, such that the issues of
+    // the depths that have not been recomputed yet remain visible.
     fn handle(&mut self, message: Self::Message) -> bool {
         let handle = LspHandle::default();
 
@@ -92,48 +103,44 @@ impl<const DEPTH: DiagnosticsDepth> Task for DiagnosticsPublisher<DEPTH> {
             Ok(guard) => guard,
 
             Err(ModuleError::Interrupted(_)) => {
-                warn!(target: LSP_SERVER_LOG, "[{}] Diagnostics {DEPTH:?} interrupted.", message.uri.as_str());
+                warn!(target: LSP_SERVER_LOG, "[{}] Diagnostics interrupted.", message.uri.as_str());
                 return false;
             }
 
             Err(error) => {
-                error!(target: LSP_CLIENT_LOG, "[{}] Diagnostics {DEPTH:?} error. {error}", message.uri.as_str());
+                error!(target: LSP_CLIENT_LOG, "[{}] Diagnostics error. {error}", message.uri.as_str());
                 return true;
             }
         };
 
-        let diagnostics = match module_read_guard.diagnostics(DEPTH) {
-            Ok(diagnostics) => diagnostics,
-
-            Err(ModuleError::Interrupted(_)) => {
-                warn!(target: LSP_SERVER_LOG, "[{}] Diagnostics {DEPTH:?} interrupted.", message.uri.as_str());
-                return false;
-            }
+        let text = module_read_guard.text();
 
-            Err(error) => {
-                error!(target: LSP_CLIENT_LOG, "[{}] Diagnostics {DEPTH:?} error. {error}", message.uri.as_str());
-                return true;
-            }
-        };
+        for depth in 1..=3 {
+            let diagnostics = match module_read_guard.diagnostics(depth) {
+                Ok(diagnostics) => diagnostics,
 
-        let mut field_diagnostics_guard = self
-            .diagnostics
-            .as_ref()
-            .lock()
-            .unwrap_or_else(|poison| poison.into_inner());
+                Err(ModuleError::Interrupted(_)) => {
+                    warn!(target: LSP_SERVER_LOG, "[{}] Diagnostics {depth:?} interrupted.", message.uri.as_str());
+                    return false;
+                }
 
-        if !field_diagnostics_guard.update(diagnostics) {
-            return true;
-        };
+                Err(error) => {
+                    error!(target: LSP_CLIENT_LOG, "[{}] Diagnostics {depth:?} error. {error}", message.uri.as_str());
+                    return true;
+                }
+            };
 
-        let text = module_read_guard.text();
+            if !self.diagnostics.update(diagnostics) {
+                continue;
+            };
 
-        self.outgoing
-            .notify::<PublishDiagnostics>(PublishDiagnosticsParams {
-                uri: message.uri,
-                diagnostics: field_diagnostics_guard.snapshot(&text),
-                version: Some(message.version),
-            });
+            self.outgoing
+                .notify::<PublishDiagnostics>(PublishDiagnosticsParams {
+                    uri: message.uri.clone(),
+                    diagnostics: self.diagnostics.snapshot(&message.uri, &text),
+                    version: Some(message.version),
+                });
+        }
 
         true
     }
@@ -168,8 +175,17 @@ impl FileDiagnostics {
         true
     }
 
-    fn snapshot(&self, text: &ModuleText) -> Vec<Diagnostic> {
-        let mut result = Vec::new();
+    // Semantic issues that fall inside the span of a syntax error are usually
+    // derived from this error. Instead of publishing them as separate
+    // diagnostics, the snapshot attaches them to the syntax error as related
+    // information.
+    fn snapshot(&self, uri: &Uri, text: &ModuleText) -> Vec<Diagnostic> {
+        let mut result = Vec::<Diagnostic>::new();
+        let mut syntax_errors = AHashMap::<SiteSpan, usize>::new();
+
+        let syntax_index = self.diagnostics[0]
+            .as_ref()
+            .map(|diagnostics| diagnostics.span_index(text));
 
         for disagnostics in &self.diagnostics {
             let Some(diagnostics) = disagnostics else {
@@ -177,18 +193,71 @@ impl FileDiagnostics {
             };
 
             for issue in diagnostics {
-                let Some(span) = issue.origin(text).to_position_span(text) else {
+                let origin = issue.origin(text);
+
+                let Some(span) = origin.to_position_span(text) else {
                     continue;
                 };
 
                 let range = span_to_range(&span);
 
+                let site_span = origin.to_site_span(text);
+
+                if issue.depth() > 1 {
+                    let syntax_error = site_span.as_ref().and_then(|site_span| {
+                        syntax_index
+                            .as_ref()?
+                            .in_span(text, site_span.clone())
+                            .into_iter()
+                            .find_map(|error| {
+                                let error_span = error.origin(text).to_site_span(text)?;
+
+                                if error_span.start > site_span.start
+                                    || site_span.end > error_span.end
+                                {
+                                    return None;
+                                }
+
+                                syntax_errors.get(&error_span)
+                            })
+                    });
+
+                    if let Some(index) = syntax_error {
+                        result[*index]
+                            .related_information
+                            .get_or_insert_with(Vec::new)
+                            .push(DiagnosticRelatedInformation {
+                                location: Location {
+                                    uri: uri.clone(),
+                                    range,
+                                },
+                                message: issue.verbose_message(text),
+                            });
+
+                        continue;
+                    }
+                }
+
                 let severity = match issue.severity() {
                     IssueSeverity::Error => DiagnosticSeverity::ERROR,
                     IssueSeverity::Warning => DiagnosticSeverity::WARNING,
+                    IssueSeverity::Hint => DiagnosticSeverity::HINT,
+                };
+
+                let tags = match issue.code() {
+                    IssueCode::UnusedFunction
+                    | IssueCode::UnreadEntry
+                    | IssueCode::UnusedImport => Some(vec![DiagnosticTag::UNNECESSARY]),
+
+                    IssueCode::DeprecatedMember => Some(vec![DiagnosticTag::DEPRECATED]),
+
+                    _ => None,
                 };
 
-                let code = NumberOrString::Number(issue.code() as i32);
+                let code = match issue.lint_code() {
+                    Some(code) => NumberOrString::String(String::from(code)),
+                    None => NumberOrString::Number(issue.code() as i32),
+                };
 
                 let message = issue.verbose_message(text);
 
@@ -196,6 +265,28 @@ impl FileDiagnostics {
                     .quickfix()
                     .map(|quickfix| Value::from(DiagnosticData(quickfix)));
 
+                let mut related_information = Vec::new();
+
+                for related in issue.related(text) {
+                    let Some(span) = related.origin.to_position_span(text) else {
+                        continue;
+                    };
+
+                    related_information.push(DiagnosticRelatedInformation {
+                        location: Location {
+                            uri: uri.clone(),
+                            range: span_to_range(&span),
+                        },
+                        message: related.message,
+                    });
+                }
+
+                if issue.depth() == 1 {
+                    if let Some(site_span) = site_span {
+                        let _ = syntax_errors.entry(site_span).or_insert(result.len());
+                    }
+                }
+
                 result.push(Diagnostic {
                     range,
                     severity: Some(severity),
@@ -203,6 +294,9 @@ impl FileDiagnostics {
                     //todo consider providing a link to the RustDoc
                     code_description: None,
                     message,
+                    related_information: (!related_information.is_empty())
+                        .then_some(related_information),
+                    tags,
                     data,
 
                     ..Diagnostic::default()
@@ -36,9 +36,10 @@ use std::{collections::VecDeque, str::FromStr, thread::park_timeout};
 
 use lady_deirdre::{
     analysis::TaskPriority,
+    arena::Identifiable,
     sync::{Shared, Trigger},
 };
-use log::{error, warn};
+use log::{debug, error, warn};
 use lsp_types::{
     request::{
         CodeActionRequest,
@@ -49,6 +50,7 @@ use lsp_types::{
         Formatting,
         GotoDefinition,
         GotoImplementation,
+        GotoTypeDefinition,
         HoverRequest,
         InlayHintRequest,
         LinkedEditingRange,
@@ -66,14 +68,14 @@ use lsp_types::{
 use serde_json::Value;
 
 use crate::{
-    analysis::{ModuleError, ModuleWrite, ScriptModule},
+    analysis::{AnalysisConfig, ModuleError, ModuleWrite, ScriptModule},
     runtime::PackageMeta,
     server::{
         action::{SendCodeAction, SendCodeActionMessage},
         command::{SendExecuteCommand, SendExecuteCommandMessage},
         completion::{SendCompletion, SendCompletionMessage},
         definition::{SendGotoDefinition, SendGotoDefinitionMessage},
-        diagnostics::{DiagnosticsPublisher, PublishContext},
+        diagnostics::{DiagnosticsPublisher, FileDiagnostics, PublishContext},
         format::{SendFormatting, SendFormattingMessage},
         highlight::{SendDocumentHighlight, SendDocumentHighlightMessage},
         hints::{SendInlayHints, SendInlayHintsMessage},
@@ -81,6 +83,7 @@ use crate::{
         implementation::{SendGotoImplementation, SendGotoImplementationMessage},
         lens::{SendCodeLens, SendCodeLensMessage},
         logger::LSP_CLIENT_LOG,
+        perf::PerfRegistry,
         rename::{
             SendLinkedEditingRange,
             SendLinkedEditingRangeMessage,
@@ -91,9 +94,10 @@ use crate::{
         },
         rpc::{LspHandle, OutgoingEx, RpcId, RpcLatches},
         signature::{SendSignatureHelp, SendSignatureHelpMessage},
-        tasks::{LocalOrRemote, Task, TaskExecution, COOL_DOWN},
+        tasks::{LocalOrRemote, Task, TaskExecution, TaskGroup, TaskScheduler, COOL_DOWN},
+        type_definition::{SendGotoTypeDefinition, SendGotoTypeDefinitionMessage},
         utils::{range_to_span, uri_to_name},
-        HealthCheck,
+        LspCompletionConfig,
         LspServerConfig,
         RpcSender,
     },
@@ -111,11 +115,10 @@ pub(super) struct File {
     latches: RpcLatches,
     outgoing: RpcSender,
     module: LspModule,
+    perf: PerfRegistry,
     uri: Uri,
     version: i32,
-    publish_diagnostics_1: Option<LocalOrRemote<DiagnosticsPublisher<1>>>,
-    publish_diagnostics_2: Option<LocalOrRemote<DiagnosticsPublisher<2>>>,
-    publish_diagnostics_3: Option<LocalOrRemote<DiagnosticsPublisher<3>>>,
+    publish_diagnostics: Option<LocalOrRemote<DiagnosticsPublisher>>,
     send_inlay_hints: Option<LocalOrRemote<SendInlayHints>>,
     send_formatting: Option<LocalOrRemote<SendFormatting>>,
     send_completion: Option<LocalOrRemote<SendCompletion>>,
@@ -123,6 +126,7 @@ pub(super) struct File {
     send_goto_definition: Option<LocalOrRemote<SendGotoDefinition>>,
     send_document_highlight: Option<LocalOrRemote<SendDocumentHighlight>>,
     send_goto_implementation: Option<LocalOrRemote<SendGotoImplementation>>,
+    send_goto_type_definition: Option<LocalOrRemote<SendGotoTypeDefinition>>,
     send_code_action: Option<LocalOrRemote<SendCodeAction>>,
     send_signature_help: Option<LocalOrRemote<SendSignatureHelp>>,
     send_rename: Option<LocalOrRemote<SendRename>>,
@@ -135,6 +139,7 @@ pub(super) struct File {
 impl Drop for File {
     fn drop(&mut self) {
         self.module.as_ref().deny_access();
+        self.perf.unregister(self.module.as_ref().id());
     }
 }
 
@@ -151,45 +156,65 @@ impl Task for File {
             module.as_ref().rename(name);
         }
 
-        let runner_state = Shared::default();
+        if config.config.perf_stats {
+            module.as_ref().set_stats_enabled(true);
+            config.perf.register(&module);
+        }
 
-        let mut publish_diagnostics_1 = None;
-        let mut publish_diagnostics_2 = None;
-        let mut publish_diagnostics_3 = None;
+        if !config.config.dead_code_allow.is_empty() {
+            let handle = LspHandle::default();
 
-        if config.config.capabilities.publish_diagnostics {
-            let diagnostics = Shared::default();
+            let result = module
+                .as_ref()
+                .write(&handle, EDIT_PRIORITY)
+                .and_then(|mut guard| guard.set_dead_code_allow(config.config.dead_code_allow));
 
-            publish_diagnostics_1 = Some(LocalOrRemote::new(
-                format!("[{}] (diagnostics 1)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
-                DiagnosticsPublisher {
-                    outgoing: config.outgoing.clone(),
-                    module: module.clone(),
-                    diagnostics: diagnostics.clone(),
-                },
-            ));
+            if let Err(error) = result {
+                error!(target: LSP_CLIENT_LOG, "[{}] Dead code config error. {error}", config.uri.as_str());
+            }
+        }
 
-            publish_diagnostics_2 = Some(LocalOrRemote::new(
-                format!("[{}] (diagnostics 2)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
-                DiagnosticsPublisher {
-                    outgoing: config.outgoing.clone(),
-                    module: module.clone(),
-                    diagnostics: diagnostics.clone(),
-                },
-            ));
+        if config.config.analysis_config != AnalysisConfig::default() {
+            let handle = LspHandle::default();
+
+            let result = module
+                .as_ref()
+                .write(&handle, EDIT_PRIORITY)
+                .and_then(|mut guard| guard.set_analysis_config(config.config.analysis_config));
+
+            if let Err(error) = result {
+                error!(target: LSP_CLIENT_LOG, "[{}] Analysis config error. {error}", config.uri.as_str());
+            }
+        }
 
-            publish_diagnostics_3 = Some(LocalOrRemote::new(
-                format!("[{}] (diagnostics 3)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+        if !config.config.lint_passes.get().is_empty() {
+            let handle = LspHandle::default();
+
+            let result = module
+                .as_ref()
+                .write(&handle, EDIT_PRIORITY)
+                .map(|mut guard| guard.set_lint_passes(config.config.lint_passes.get()));
+
+            if let Err(error) = result {
+                error!(target: LSP_CLIENT_LOG, "[{}] Lint passes config error. {error}", config.uri.as_str());
+            }
+        }
+
+        let runner_state = Shared::default();
+
+        let group = TaskGroup::new(config.uri.as_str());
+
+        let mut publish_diagnostics = None;
+
+        if config.config.capabilities.publish_diagnostics {
+            publish_diagnostics = Some(LocalOrRemote::new(
+                format!("[{}] (diagnostics)", config.uri.as_str()),
+                group,
+                &config.scheduler,
                 DiagnosticsPublisher {
                     outgoing: config.outgoing.clone(),
                     module: module.clone(),
-                    diagnostics,
+                    diagnostics: FileDiagnostics::default(),
                 },
             ));
         }
@@ -199,8 +224,8 @@ impl Task for File {
         if config.config.capabilities.inlay_hints {
             send_inlay_hints = Some(LocalOrRemote::new(
                 format!("[{}] (send hints)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendInlayHints {
                     config: config.config,
                     latches: config.latches.clone(),
@@ -216,8 +241,8 @@ impl Task for File {
         if config.config.capabilities.formatting {
             send_formatting = Some(LocalOrRemote::new(
                 format!("[{}] (send formatting)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendFormatting {
                     latches: config.latches.clone(),
                     outgoing: config.outgoing.clone(),
@@ -231,8 +256,8 @@ impl Task for File {
         if config.config.capabilities.completion {
             send_completion = Some(LocalOrRemote::new(
                 format!("[{}] (send completion)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendCompletion {
                     config: config.config,
                     latches: config.latches.clone(),
@@ -247,8 +272,8 @@ impl Task for File {
         if config.config.capabilities.hover {
             send_hover = Some(LocalOrRemote::new(
                 format!("[{}] (send hover)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendHover {
                     config: config.config,
                     latches: config.latches.clone(),
@@ -263,8 +288,8 @@ impl Task for File {
         if config.config.capabilities.goto_definition {
             send_goto_definition = Some(LocalOrRemote::new(
                 format!("[{}] (send goto definition)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendGotoDefinition {
                     latches: config.latches.clone(),
                     outgoing: config.outgoing.clone(),
@@ -278,8 +303,8 @@ impl Task for File {
         if config.config.capabilities.document_highlight {
             send_document_highlight = Some(LocalOrRemote::new(
                 format!("[{}] (send goto definition)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendDocumentHighlight {
                     latches: config.latches.clone(),
                     outgoing: config.outgoing.clone(),
@@ -293,8 +318,8 @@ impl Task for File {
         if config.config.capabilities.goto_implementation {
             send_goto_implementation = Some(LocalOrRemote::new(
                 format!("[{}] (send goto implementation)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendGotoImplementation {
                     latches: config.latches.clone(),
                     outgoing: config.outgoing.clone(),
@@ -303,13 +328,29 @@ impl Task for File {
             ));
         }
 
+        let mut send_goto_type_definition = None;
+
+        if config.config.capabilities.goto_type_definition {
+            send_goto_type_definition = Some(LocalOrRemote::new(
+                format!("[{}] (send goto type definition)", config.uri.as_str()),
+                group,
+                &config.scheduler,
+                SendGotoTypeDefinition {
+                    latches: config.latches.clone(),
+                    outgoing: config.outgoing.clone(),
+                    module: module.clone(),
+                    rust_sources: config.config.rust_sources,
+                },
+            ));
+        }
+
         let mut send_code_action = None;
 
         if config.config.capabilities.code_action {
             send_code_action = Some(LocalOrRemote::new(
                 format!("[{}] (send code action)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendCodeAction {
                     latches: config.latches.clone(),
                     outgoing: config.outgoing.clone(),
@@ -323,8 +364,8 @@ impl Task for File {
         if config.config.capabilities.signature_help {
             send_signature_help = Some(LocalOrRemote::new(
                 format!("[{}] (send signature help)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendSignatureHelp {
                     config: config.config,
                     latches: config.latches.clone(),
@@ -339,8 +380,8 @@ impl Task for File {
         if config.config.capabilities.rename {
             send_rename = Some(LocalOrRemote::new(
                 format!("[{}] (send rename)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendRename {
                     latches: config.latches.clone(),
                     outgoing: config.outgoing.clone(),
@@ -354,8 +395,8 @@ impl Task for File {
         if config.config.capabilities.rename_prepare {
             send_prepare_rename = Some(LocalOrRemote::new(
                 format!("[{}] (send prepare rename)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendPrepareRename {
                     latches: config.latches.clone(),
                     outgoing: config.outgoing.clone(),
@@ -369,8 +410,8 @@ impl Task for File {
         if config.config.capabilities.linked_editing_range {
             send_linked_editing_range = Some(LocalOrRemote::new(
                 format!("[{}] (send linked editing range)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendLinkedEditingRange {
                     latches: config.latches.clone(),
                     outgoing: config.outgoing.clone(),
@@ -384,8 +425,8 @@ impl Task for File {
         if config.config.capabilities.code_lens {
             send_code_lens = Some(LocalOrRemote::new(
                 format!("[{}] (send code lens)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendCodeLens {
                     config: config.config,
                     latches: config.latches.clone(),
@@ -401,8 +442,8 @@ impl Task for File {
         if config.config.capabilities.execute_command {
             send_execute_command = Some(LocalOrRemote::new(
                 format!("[{}] (send execute command)", config.uri.as_str()),
-                config.config.multi_thread,
-                &config.health_check,
+                group,
+                &config.scheduler,
                 SendExecuteCommand {
                     config: config.config,
                     latches: config.latches.clone(),
@@ -417,11 +458,10 @@ impl Task for File {
             latches: config.latches,
             outgoing: config.outgoing,
             module,
+            perf: config.perf,
             uri: config.uri,
             version: config.version,
-            publish_diagnostics_1,
-            publish_diagnostics_2,
-            publish_diagnostics_3,
+            publish_diagnostics,
             send_inlay_hints,
             send_formatting,
             send_completion,
@@ -429,6 +469,7 @@ impl Task for File {
             send_goto_definition,
             send_document_highlight,
             send_goto_implementation,
+            send_goto_type_definition,
             send_code_action,
             send_signature_help,
             send_rename,
@@ -466,7 +507,8 @@ impl Task for File {
                 id,
                 cancel,
                 position,
-            } => self.handle_completion(id, cancel, position),
+                config,
+            } => self.handle_completion(id, cancel, position, config),
 
             FileMessage::Hover {
                 id,
@@ -492,6 +534,12 @@ impl Task for File {
                 position,
             } => self.handle_goto_implementation(id, cancel, position),
 
+            FileMessage::GotoTypeDefinition {
+                id,
+                cancel,
+                position,
+            } => self.handle_goto_type_definition(id, cancel, position),
+
             FileMessage::CodeAction {
                 id,
                 cancel,
@@ -590,11 +638,18 @@ impl File {
             while let Some(change) = changes.pop_front() {
                 let result = match change.range {
                     Some(range) => module_write_guard.edit(range_to_span(&range), change.text),
-                    None => module_write_guard.edit(.., change.text),
+                    None => match module_write_guard.replace_from_reader(change.text.as_bytes()) {
+                        Ok(summary) => {
+                            debug!(target: LSP_CLIENT_LOG, "[{}] Text replaced: {summary}.", self.uri.as_str());
+                            Ok(summary.revision)
+                        }
+
+                        Err(error) => Err(error),
+                    },
                 };
 
                 match result {
-                    Ok(()) => (),
+                    Ok(_) => (),
 
                     Err(ModuleError::Interrupted(_)) => {
                         if !self.module.as_ref().is_access_allowed() {
@@ -658,7 +713,13 @@ impl File {
         });
     }
 
-    fn handle_completion(&mut self, id: RpcId, cancel: Trigger, position: Position) {
+    fn handle_completion(
+        &mut self,
+        id: RpcId,
+        cancel: Trigger,
+        position: Position,
+        config: LspCompletionConfig,
+    ) {
         let Some(send_formatting) = &mut self.send_completion else {
             error!(target: LSP_CLIENT_LOG, "[{}] Completion sender is not initialized.", self.uri.as_str());
 
@@ -673,6 +734,7 @@ impl File {
             uri: self.uri.clone(),
             cancel,
             position,
+            config,
         });
     }
 
@@ -748,6 +810,24 @@ impl File {
         });
     }
 
+    fn handle_goto_type_definition(&mut self, id: RpcId, cancel: Trigger, position: Position) {
+        let Some(send_goto_type_definition) = &mut self.send_goto_type_definition else {
+            error!(target: LSP_CLIENT_LOG, "[{}] Goto type definition sender is not initialized.", self.uri.as_str());
+
+            self.outgoing
+                .send_ok_response::<GotoTypeDefinition>(&self.latches, id, None);
+
+            return;
+        };
+
+        send_goto_type_definition.send(SendGotoTypeDefinitionMessage {
+            id,
+            uri: self.uri.clone(),
+            cancel,
+            position,
+        });
+    }
+
     fn handle_code_action(
         &mut self,
         id: RpcId,
@@ -889,21 +969,7 @@ impl File {
     }
 
     fn trigger_diagnostics(&mut self) {
-        if let Some(publisher) = &mut self.publish_diagnostics_1 {
-            publisher.send(PublishContext {
-                uri: self.uri.clone(),
-                version: self.version,
-            });
-        }
-
-        if let Some(publisher) = &mut self.publish_diagnostics_2 {
-            publisher.send(PublishContext {
-                uri: self.uri.clone(),
-                version: self.version,
-            });
-        }
-
-        if let Some(publisher) = &mut self.publish_diagnostics_3 {
+        if let Some(publisher) = &mut self.publish_diagnostics {
             publisher.send(PublishContext {
                 uri: self.uri.clone(),
                 version: self.version,
@@ -915,9 +981,10 @@ impl File {
 pub(super) struct FileConfig {
     pub(super) config: LspServerConfig,
     pub(super) package: &'static PackageMeta,
-    pub(super) health_check: Option<HealthCheck>,
+    pub(super) scheduler: Option<TaskScheduler>,
     pub(super) latches: RpcLatches,
     pub(super) outgoing: RpcSender,
+    pub(super) perf: PerfRegistry,
     pub(super) uri: Uri,
     pub(super) version: i32,
     pub(super) text: String,
@@ -949,6 +1016,7 @@ pub(super) enum FileMessage {
         id: RpcId,
         cancel: Trigger,
         position: Position,
+        config: LspCompletionConfig,
     },
 
     Hover {
@@ -975,6 +1043,12 @@ pub(super) enum FileMessage {
         position: Position,
     },
 
+    GotoTypeDefinition {
+        id: RpcId,
+        cancel: Trigger,
+        position: Position,
+    },
+
     CodeAction {
         id: RpcId,
         cancel: Trigger,
@@ -32,9 +32,9 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::{ops::RangeFull, thread::park_timeout};
+use std::thread::park_timeout;
 
-use lady_deirdre::{lexis::ToSpan, sync::Trigger};
+use lady_deirdre::sync::Trigger;
 use log::{error, warn};
 use lsp_types::{
     error_codes::{REQUEST_CANCELLED, REQUEST_FAILED},
@@ -47,7 +47,6 @@ use lsp_types::{
 use crate::{
     analysis::{ModuleError, ModuleRead},
     format::ScriptFormatConfig,
-    report::system_panic,
     server::{
         file::{LspModule, ANALYSIS_PRIORITY},
         logger::LSP_SERVER_LOG,
@@ -136,23 +135,30 @@ impl Task for SendFormatting {
                 ..Default::default()
             };
 
-            let Some(new_text) = text.format(config) else {
+            let Some(output) = text.format_with_warnings(config) else {
                 self.outgoing
                     .send_ok_response::<Formatting>(&self.latches, message.id, None);
 
                 break;
             };
 
-            let range = match RangeFull.to_position_span(&text) {
-                Some(span) => span_to_range(&span),
-                None => system_panic!("Span to position conversion failure."),
-            };
+            for warning in &output.warnings {
+                warn!(target: LSP_SERVER_LOG, "[{}] Formatting warning. {warning}.", message.uri.as_str());
+            }
 
-            self.outgoing.send_ok_response::<Formatting>(
-                &self.latches,
-                message.id,
-                Some(vec![TextEdit { range, new_text }]),
-            );
+            // Sending minimal edits instead of the entire text preserves
+            // the cursor positions and the undo history of the editor.
+            let edits = text
+                .diff(&output.text)
+                .into_iter()
+                .map(|patch| TextEdit {
+                    range: span_to_range(&patch.position_span),
+                    new_text: patch.text,
+                })
+                .collect();
+
+            self.outgoing
+                .send_ok_response::<Formatting>(&self.latches, message.id, Some(edits));
 
             break;
         }
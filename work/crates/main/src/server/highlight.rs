@@ -54,6 +54,7 @@ use crate::{
         symbols::{
             BreakSymbol,
             EntrySymbol,
+            EnumSymbol,
             FieldSymbol,
             FnSymbol,
             IdentKind,
@@ -166,7 +167,8 @@ impl Task for SendDocumentHighlight {
                 | (SymbolKind::Struct as u32)
                 | (SymbolKind::Entry as u32)
                 | (SymbolKind::Ident as u32)
-                | (SymbolKind::Field as u32);
+                | (SymbolKind::Field as u32)
+                | (SymbolKind::Enum as u32);
 
             let position = lsp_position_to_ld(&message.position);
 
@@ -319,6 +321,7 @@ impl<'a> HighlightsBuilder<'a> {
             ModuleSymbol::Operator(_) => Ok(()),
             ModuleSymbol::Call(_) => Ok(()),
             ModuleSymbol::Index(_) => Ok(()),
+            ModuleSymbol::Enum(symbol) => self.highlight_enum(symbol),
         }
     }
 
@@ -506,6 +509,14 @@ impl<'a> HighlightsBuilder<'a> {
         Ok(())
     }
 
+    fn highlight_enum(&mut self, symbol: &EnumSymbol) -> ModuleResult<()> {
+        let Some(var_symbol) = symbol.var(self.read) else {
+            return Ok(());
+        };
+
+        self.highlight_var(&var_symbol)
+    }
+
     fn highlight_entry(&mut self, symbol: &EntrySymbol) -> ModuleResult<()> {
         let entry_origin = symbol.origin(self.read);
 
@@ -534,6 +545,8 @@ impl<'a> HighlightsBuilder<'a> {
         match symbol.declaration(self.read)? {
             ModuleSymbol::Var(var_symbol) => self.highlight_var(&var_symbol),
 
+            ModuleSymbol::Enum(enum_symbol) => self.highlight_enum(&enum_symbol),
+
             ModuleSymbol::Struct(struct_symbol) => {
                 let struct_origin = struct_symbol.origin(self.read);
 
@@ -39,6 +39,7 @@ use lady_deirdre::{
     arena::Identifiable,
     lexis::{Position, SourceCode, ToSite, ToSpan},
     sync::Trigger,
+    units::Lexis,
 };
 use log::{error, warn};
 use lsp_types::{
@@ -68,6 +69,7 @@ use crate::{
             LookupOptions,
             ModuleSymbol,
             SymbolKind,
+            VarKind,
             VarSymbol,
         },
         Closeness,
@@ -300,7 +302,7 @@ pub(super) struct SendInlayHintsMessage {
 
 struct HintsBuilder<'a> {
     markdown: bool,
-    language_id: &'a str,
+    language_id: &'static str,
     read: &'a ModuleReadGuard<'a, LspHandle>,
     text: &'a ModuleText<'a>,
     hint_index: AHashSet<Position>,
@@ -321,7 +323,11 @@ impl<'a> HintsBuilder<'a> {
 
             let position = Position::new(
                 span.start.line,
-                self.text.lines().line_length(span.start.line).max(1),
+                self.text
+                    .lexis()
+                    .lines()
+                    .line_length(span.start.line)
+                    .max(1),
             );
 
             let tooltip = match tooltip.is_empty() {
@@ -362,10 +368,15 @@ impl<'a> HintsBuilder<'a> {
             ModuleSymbol::Operator(_) => Ok(()),
             ModuleSymbol::Call(symbol) => self.add_call(symbol),
             ModuleSymbol::Index(symbol) => self.add_index(symbol),
+            ModuleSymbol::Enum(_) => Ok(()),
         }
     }
 
     fn add_var(&mut self, symbol: VarSymbol) -> ModuleResult<()> {
+        if symbol.kind(self.read) == VarKind::EnumName {
+            return Ok(());
+        }
+
         let origin = symbol.origin(self.read);
 
         let Some(span) = origin.to_position_span(self.text) else {
@@ -581,7 +592,7 @@ impl<'a> HintsBuilder<'a> {
                     params.push(Some((Ident::Script(name), ty)));
                 }
 
-                let args = symbol.args(self.read);
+                let args = self.positional_args(&symbol);
 
                 self.add_args(params, args)?;
             }
@@ -603,12 +614,13 @@ impl<'a> HintsBuilder<'a> {
                                 type_hint: param.hint,
                                 impl_symbol: ModuleSymbol::Nil,
                                 doc,
+                                deprecated: None,
                             };
 
                             params.push(Some((name.clone(), desc)));
                         }
 
-                        let args = symbol.args(self.read);
+                        let args = self.positional_args(&symbol);
 
                         self.add_args(params, args)?;
                     }
@@ -645,6 +657,21 @@ impl<'a> HintsBuilder<'a> {
         Ok(())
     }
 
+    // The named arguments already display their parameter names.
+    fn positional_args(&self, symbol: &CallSymbol) -> Vec<ModuleSymbol> {
+        let mut args = symbol.args(self.read);
+
+        let positional = symbol
+            .arg_names(self.read)
+            .iter()
+            .take_while(|name| name.is_none())
+            .count();
+
+        args.truncate(positional);
+
+        args
+    }
+
     fn add_args(
         &mut self,
         params: Vec<Option<(Ident, Description)>>,
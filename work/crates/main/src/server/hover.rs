@@ -41,6 +41,8 @@ use lsp_types::{
     request::HoverRequest,
     Hover,
     HoverContents,
+    MarkupContent,
+    MarkupKind,
     Position,
     Uri,
 };
@@ -139,7 +141,9 @@ impl Task for SendHover {
             const HOVER_SYMBOLS: u32 = (SymbolKind::Package as u32)
                 | (SymbolKind::Ident as u32)
                 | (SymbolKind::Field as u32)
-                | (SymbolKind::Entry as u32);
+                | (SymbolKind::Entry as u32)
+                | (SymbolKind::Fn as u32)
+                | (SymbolKind::Operator as u32);
 
             let symbols = match module_read_guard.symbols(
                 position..position,
@@ -182,6 +186,7 @@ impl Task for SendHover {
 
             let origin;
             let ty;
+            let mut captures = Vec::new();
 
             match symbols.first() {
                 Some(ModuleSymbol::Package(symbol)) => {
@@ -204,6 +209,37 @@ impl Task for SendHover {
                     ty = symbol.ty(&module_read_guard);
                 }
 
+                Some(ModuleSymbol::Operator(symbol)) => {
+                    origin = symbol.origin(&module_read_guard);
+                    ty = match symbol.declaration(&module_read_guard) {
+                        Ok(Some(entry)) => entry.ty(&module_read_guard),
+
+                        Ok(None) => {
+                            self.outgoing.send_ok_response::<HoverRequest>(
+                                &self.latches,
+                                message.id,
+                                None,
+                            );
+
+                            break;
+                        }
+
+                        Err(error) => Err(error),
+                    };
+                }
+
+                Some(ModuleSymbol::Fn(symbol)) => {
+                    origin = symbol.origin(&module_read_guard);
+                    ty = match symbol.captures(&module_read_guard) {
+                        Ok(vars) => {
+                            captures = vars;
+                            Ok(symbol.ty(&module_read_guard))
+                        }
+
+                        Err(error) => Err(error),
+                    };
+                }
+
                 _ => {
                     self.outgoing
                         .send_ok_response::<HoverRequest>(&self.latches, message.id, None);
@@ -248,7 +284,11 @@ impl Task for SendHover {
                 }
             };
 
-            if ty.type_hint.is_dynamic() {
+            let dynamic_entry = ty.type_hint.is_dynamic()
+                && self.config.dynamic_entries
+                && matches!(symbols.first(), Some(ModuleSymbol::Field(_)));
+
+            if ty.type_hint.is_dynamic() && !dynamic_entry {
                 self.outgoing
                     .send_ok_response::<HoverRequest>(&self.latches, message.id, None);
 
@@ -264,7 +304,7 @@ impl Task for SendHover {
                 break;
             };
 
-            let doc = make_doc(
+            let mut doc = make_doc(
                 &module_read_guard,
                 &text,
                 self.config.capabilities.hover_markdown,
@@ -273,6 +313,57 @@ impl Task for SendHover {
                 &ty,
             );
 
+            if let (Some(doc), false) = (&mut doc, captures.is_empty()) {
+                let names = captures
+                    .iter()
+                    .filter_map(|var| Some(var.var_name(&module_read_guard)?.to_string()))
+                    .collect::<Vec<_>>();
+
+                match self.config.capabilities.hover_markdown {
+                    false => doc
+                        .value
+                        .push_str(&format!("\n\ncaptures: {}", names.join(", "))),
+
+                    true => doc
+                        .value
+                        .push_str(&format!("\n\n---\n\ncaptures: `{}`", names.join("`, `"),)),
+                }
+            }
+
+            if let (Some(doc), Some(symbol)) = (&mut doc, symbols.first()) {
+                if let Some(value) = self.folded_value(&module_read_guard, symbol) {
+                    match self.config.capabilities.hover_markdown {
+                        false => doc.value.push_str(&format!("\n\n= {value}")),
+
+                        true => doc.value.push_str(&format!(
+                            "\n\n---\n\n```{}\n= {value}\n```",
+                            self.config.language_id,
+                        )),
+                    }
+                }
+            }
+
+            if dynamic_entry {
+                let note = "The host resolves missing struct entries at runtime.";
+
+                match &mut doc {
+                    Some(doc) => match self.config.capabilities.hover_markdown {
+                        false => doc.value.push_str(&format!("\n\n{note}")),
+                        true => doc.value.push_str(&format!("\n\n---\n\n{note}")),
+                    },
+
+                    None => {
+                        doc = Some(MarkupContent {
+                            kind: match self.config.capabilities.hover_markdown {
+                                true => MarkupKind::Markdown,
+                                false => MarkupKind::PlainText,
+                            },
+                            value: format!("dynamic entry\n\n{note}"),
+                        })
+                    }
+                }
+            }
+
             self.outgoing.send_ok_response::<HoverRequest>(
                 &self.latches,
                 message.id,
@@ -294,6 +385,47 @@ impl Task for SendHover {
     }
 }
 
+impl SendHover {
+    // Folds the invocation of a pure function if the symbol is the name of the
+    // function being called: `<symbol>(10, 20)`, or shows the constant value
+    // of a variable: `let <symbol> = 12;`.
+    fn folded_value(
+        &self,
+        read: &impl ModuleRead<LspHandle>,
+        symbol: &ModuleSymbol,
+    ) -> Option<String> {
+        const MAX_LENGTH: usize = 200;
+
+        let ModuleSymbol::Ident(ident) = symbol else {
+            return None;
+        };
+
+        let call = ident.parent_expr(read);
+
+        let value = match &call {
+            ModuleSymbol::Call(call_symbol) if &call_symbol.receiver(read) == symbol => {
+                if !self.config.pure_folding {
+                    return None;
+                }
+
+                call.expr_value(read).ok()??.stringify(false)
+            }
+
+            _ => symbol.expr_value(read).ok()??.stringify(false),
+        };
+
+        if value.chars().count() <= MAX_LENGTH {
+            return Some(value);
+        }
+
+        let mut value = value.chars().take(MAX_LENGTH).collect::<String>();
+
+        value.push_str("...");
+
+        Some(value)
+    }
+}
+
 pub(super) struct SendHoverMessage {
     pub(super) id: RpcId,
     pub(super) uri: Uri,
@@ -38,9 +38,10 @@ use lsp_types::{error_codes::REQUEST_CANCELLED, request::CodeLensRequest, CodeLe
 use serde_json::{Number, Value};
 
 use crate::server::{
-    command::{SharedRunnerState, CMD_CLEANUP, CMD_LAUNCH, CMD_STOP},
+    command::SharedRunnerState,
     file::LspModule,
     logger::LSP_SERVER_LOG,
+    protocol::{CMD_CLEANUP, CMD_LAUNCH, CMD_STOP},
     rpc::{OutgoingEx, RpcId, RpcLatches},
     tasks::{Task, TaskExecution},
     LspServerConfig,
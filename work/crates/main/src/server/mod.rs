@@ -46,6 +46,15 @@ mod hover;
 mod implementation;
 mod lens;
 mod logger;
+mod perf;
+/// Custom extensions of the Language Server Protocol.
+///
+/// This module describes the custom requests and commands that the
+/// [LspServer] supports in addition to the standard LSP methods, and provides
+/// the [schema](protocol::schema) function that dumps their machine-readable
+/// description.
+pub mod protocol;
+mod provider;
 mod rename;
 mod rpc;
 mod server;
@@ -53,6 +62,7 @@ mod signature;
 mod snippets;
 mod startup;
 mod tasks;
+mod type_definition;
 mod utils;
 
 pub use log::{Level, LevelFilter};
@@ -61,13 +71,17 @@ pub use crate::server::{
     command::inlay_hint,
     config::{
         LspCapabilities,
+        LspCompletionConfig,
+        LspCompletionMatching,
+        LspLintPasses,
         LspLoggerClientConfig,
         LspLoggerConfig,
         LspLoggerServerConfig,
         LspServerConfig,
         LspTransportConfig,
     },
-    rpc::{RpcMessage, RpcReceiver, RpcSender},
+    provider::{DocumentProvider, DocumentWatcher, FileSystemProvider, LspDocumentProvider},
+    rpc::{LspHandle, RpcMessage, RpcReceiver, RpcSender},
     server::LspServer,
-    tasks::HealthCheck,
+    tasks::{HealthCheck, PoolStats},
 };
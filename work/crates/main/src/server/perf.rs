@@ -0,0 +1,146 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+use ahash::AHashMap;
+use lady_deirdre::{
+    arena::{Id, Identifiable},
+    sync::Shared,
+};
+
+use crate::{
+    analysis::{ModuleStats, QueryStats},
+    server::{
+        file::LspModule,
+        protocol::{PerfFile, PerfQuery, PerfResult},
+    },
+};
+
+// A registry of the opened script modules whose query statistics are
+// collected by the server.
+#[derive(Clone, Default)]
+pub(super) struct PerfRegistry {
+    modules: Shared<Mutex<AHashMap<Id, LspModule>>>,
+}
+
+impl PerfRegistry {
+    #[inline(always)]
+    pub(super) fn register(&self, module: &LspModule) {
+        let id = module.as_ref().id();
+
+        let _ = self.lock().insert(id, module.clone());
+    }
+
+    #[inline(always)]
+    pub(super) fn unregister(&self, id: Id) {
+        let _ = self.lock().remove(&id);
+    }
+
+    pub(super) fn total(&self) -> ModuleStats {
+        let mut total = ModuleStats::default();
+
+        for module in self.lock().values() {
+            total.merge(&module.as_ref().stats());
+        }
+
+        total
+    }
+
+    pub(super) fn report(&self, reset: bool) -> PerfResult {
+        let mut total = ModuleStats::default();
+        let mut files = Vec::new();
+
+        for module in self.lock().values() {
+            let module = module.as_ref();
+            let stats = module.stats();
+
+            if reset {
+                module.reset_stats();
+            }
+
+            total.merge(&stats);
+
+            files.push(PerfFile {
+                name: module.id().name(),
+                queries: perf_queries(&stats),
+            });
+        }
+
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        PerfResult {
+            total: perf_queries(&total),
+            files,
+        }
+    }
+
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<AHashMap<Id, LspModule>> {
+        self.modules
+            .as_ref()
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+    }
+}
+
+impl From<QueryStats> for PerfQuery {
+    #[inline(always)]
+    fn from(stats: QueryStats) -> Self {
+        Self {
+            count: stats.count,
+            total_ms: millis(stats.total),
+            average_ms: millis(stats.average()),
+            last_ms: millis(stats.last),
+        }
+    }
+}
+
+#[inline(always)]
+fn perf_queries(stats: &ModuleStats) -> BTreeMap<String, PerfQuery> {
+    stats
+        .iter()
+        .filter(|(_, stats)| stats.count > 0)
+        .map(|(kind, stats)| (kind.to_string(), PerfQuery::from(stats)))
+        .collect()
+}
+
+#[inline(always)]
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,515 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::collections::BTreeMap;
+
+use lsp_types::request::Request;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The current version of the custom protocol extensions of the
+/// [LspServer](crate::server::LspServer).
+///
+/// The version increases each time the server introduces a new custom
+/// request, notification, or command, or changes the format of an existing
+/// one.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The name of the entry in the `experimental` section of the client and
+/// server capabilities that describes the custom protocol extensions.
+///
+/// See [ServerExtensions] and [ClientExtensions] for details.
+pub const EXPERIMENTAL_KEY: &'static str = "adastra";
+
+/// The JSON-RPC error code that the server returns in response to a request
+/// with an unknown method.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The name of the command that runs the script module.
+///
+/// The client sends this command through the `workspace/executeCommand`
+/// request. The first argument of the command is the URI of the script file.
+pub const CMD_LAUNCH: &'static str = "script_launch";
+
+/// The name of the command that stops the script module execution.
+///
+/// The client sends this command through the `workspace/executeCommand`
+/// request. The first argument of the command is the URI of the script file.
+pub const CMD_STOP: &'static str = "script_stop";
+
+/// The name of the command that removes the inlay hints produced by the
+/// script module execution.
+///
+/// The client sends this command through the `workspace/executeCommand`
+/// request. The first argument of the command is the URI of the script file.
+pub const CMD_CLEANUP: &'static str = "script_cleanup";
+
+/// A custom request through which the client fetches the analysis query
+/// statistics of the opened files.
+///
+/// The server handles this request only if the
+/// [perf_stats](crate::server::LspServerConfig::perf_stats) option is
+/// enabled.
+pub enum PerfRequest {}
+
+impl Request for PerfRequest {
+    type Params = Option<PerfParams>;
+    type Result = PerfResult;
+    const METHOD: &'static str = "adastra/perf";
+}
+
+/// The parameters of the [PerfRequest].
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfParams {
+    /// If true, the server clears the statistics after the response.
+    #[serde(default)]
+    pub reset: bool,
+}
+
+/// The result of the [PerfRequest].
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfResult {
+    /// The statistics of all opened files together, keyed by the query name.
+    pub total: BTreeMap<String, PerfQuery>,
+
+    /// The statistics of each opened file.
+    pub files: Vec<PerfFile>,
+}
+
+/// The statistics of a single file in the [PerfResult].
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfFile {
+    /// The name of the file.
+    pub name: String,
+
+    /// The statistics of the file, keyed by the query name.
+    pub queries: BTreeMap<String, PerfQuery>,
+}
+
+/// The measurements of a single analysis query in the [PerfResult].
+#[derive(Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfQuery {
+    /// The number of the query executions.
+    pub count: u64,
+
+    /// The total wall time of all executions in milliseconds.
+    pub total_ms: f64,
+
+    /// The average wall time of a single execution in milliseconds.
+    pub average_ms: f64,
+
+    /// The wall time of the latest execution in milliseconds.
+    pub last_ms: f64,
+}
+
+/// The custom protocol extensions that the server advertises in the
+/// `experimental.adastra` entry of the server capabilities.
+///
+/// The server advertises only the extensions that are enabled in the server
+/// configuration and are known to the client (see [ClientExtensions]). The
+/// client should not send the requests and commands that the server did not
+/// advertise.
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerExtensions {
+    /// The version of the protocol that the server uses in this session.
+    ///
+    /// This version is the minimum of the [PROTOCOL_VERSION] and the version
+    /// requested by the client.
+    pub protocol: u32,
+
+    /// The custom requests that the server handles.
+    pub requests: Vec<Extension>,
+
+    /// The custom notifications that the server handles or sends.
+    pub notifications: Vec<Extension>,
+
+    /// The commands of the `workspace/executeCommand` request that the server
+    /// handles.
+    pub commands: Vec<Extension>,
+}
+
+/// A description of a single custom protocol extension in the
+/// [ServerExtensions].
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Extension {
+    /// The JSON-RPC method name of the request or notification, or the name
+    /// of the command.
+    pub name: String,
+
+    /// The version of the extension's format.
+    pub version: u32,
+}
+
+/// The custom protocol extensions that the client declares in the
+/// `experimental.adastra` entry of the client capabilities.
+///
+/// Clients that do not declare this entry receive all extensions of the
+/// current [PROTOCOL_VERSION]. Older clients that declare an older protocol
+/// version receive only the extensions that existed in that version.
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientExtensions {
+    /// The version of the protocol that the client supports.
+    #[serde(default)]
+    pub protocol: Option<u32>,
+}
+
+impl ClientExtensions {
+    /// Reads the client extensions from the `experimental` section of the
+    /// client capabilities.
+    ///
+    /// Returns the default object if the section is missing or malformed.
+    pub fn from_experimental(experimental: Option<&Value>) -> Self {
+        let Some(entry) = experimental.and_then(|value| value.get(EXPERIMENTAL_KEY)) else {
+            return Self::default();
+        };
+
+        Self::deserialize(entry).unwrap_or_default()
+    }
+}
+
+/// Returns a machine-readable description of all custom protocol extensions
+/// of the server.
+///
+/// The result is a JSON object that lists the custom requests, notifications,
+/// and commands together with the JSON Schemas of their parameters and
+/// results, and the schemas of the `experimental.adastra` capability entries.
+/// The client authors can generate the client-side bindings from this
+/// description.
+pub fn schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Ad Astra language server protocol extensions",
+        "protocol": PROTOCOL_VERSION,
+        "capabilities": {
+            "key": EXPERIMENTAL_KEY,
+            "server": server_extensions_schema(),
+            "client": client_extensions_schema(),
+        },
+        "requests": REQUESTS
+            .iter()
+            .map(|meta| json!({
+                "method": meta.name,
+                "version": meta.version,
+                "since": meta.since,
+                "params": (meta.params)(),
+                "result": (meta.result)(),
+            }))
+            .collect::<Vec<_>>(),
+        "notifications": NOTIFICATIONS
+            .iter()
+            .map(|meta| json!({
+                "method": meta.name,
+                "version": meta.version,
+                "since": meta.since,
+                "params": (meta.params)(),
+            }))
+            .collect::<Vec<_>>(),
+        "commands": COMMANDS
+            .iter()
+            .map(|meta| json!({
+                "command": meta.name,
+                "version": meta.version,
+                "since": meta.since,
+                "arguments": (meta.params)(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+impl ServerExtensions {
+    // Selects the extensions available in the session with the client.
+    //
+    // The `enabled` function filters the extensions by the server
+    // configuration.
+    pub(super) fn negotiate(
+        client: &ClientExtensions,
+        enabled: impl Fn(&'static str) -> bool,
+    ) -> Self {
+        let protocol = client
+            .protocol
+            .unwrap_or(PROTOCOL_VERSION)
+            .min(PROTOCOL_VERSION);
+
+        let select = |metas: &[ExtensionMeta]| {
+            metas
+                .iter()
+                .filter(|meta| meta.since <= protocol && enabled(meta.name))
+                .map(|meta| Extension {
+                    name: String::from(meta.name),
+                    version: meta.version,
+                })
+                .collect()
+        };
+
+        Self {
+            protocol,
+            requests: select(REQUESTS),
+            notifications: select(NOTIFICATIONS),
+            commands: select(COMMANDS),
+        }
+    }
+
+    // Returns the `experimental` section of the server capabilities.
+    #[inline(always)]
+    pub(super) fn to_experimental(&self) -> Value {
+        let mut experimental = serde_json::Map::new();
+
+        let _ = experimental.insert(
+            String::from(EXPERIMENTAL_KEY),
+            serde_json::to_value(self).unwrap_or_default(),
+        );
+
+        Value::Object(experimental)
+    }
+}
+
+// A static description of the custom protocol extension.
+struct ExtensionMeta {
+    name: &'static str,
+    version: u32,
+    // The protocol version that introduced this extension.
+    since: u32,
+    params: fn() -> Value,
+    result: fn() -> Value,
+}
+
+static REQUESTS: &[ExtensionMeta] = &[ExtensionMeta {
+    name: PerfRequest::METHOD,
+    version: 1,
+    since: 1,
+    params: perf_params_schema,
+    result: perf_result_schema,
+}];
+
+static NOTIFICATIONS: &[ExtensionMeta] = &[];
+
+static COMMANDS: &[ExtensionMeta] = &[
+    ExtensionMeta {
+        name: CMD_LAUNCH,
+        version: 1,
+        since: 1,
+        params: command_arguments_schema,
+        result: null_schema,
+    },
+    ExtensionMeta {
+        name: CMD_STOP,
+        version: 1,
+        since: 1,
+        params: command_arguments_schema,
+        result: null_schema,
+    },
+    ExtensionMeta {
+        name: CMD_CLEANUP,
+        version: 1,
+        since: 1,
+        params: command_arguments_schema,
+        result: null_schema,
+    },
+];
+
+fn null_schema() -> Value {
+    json!({ "type": "null" })
+}
+
+fn perf_params_schema() -> Value {
+    json!({
+        "type": ["object", "null"],
+        "properties": {
+            "reset": { "type": "boolean", "default": false },
+        },
+    })
+}
+
+fn perf_result_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["total", "files"],
+        "properties": {
+            "total": perf_queries_schema(),
+            "files": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["name", "queries"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "queries": perf_queries_schema(),
+                    },
+                },
+            },
+        },
+    })
+}
+
+fn perf_queries_schema() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": {
+            "type": "object",
+            "required": ["count", "totalMs", "averageMs", "lastMs"],
+            "properties": {
+                "count": { "type": "integer", "minimum": 0 },
+                "totalMs": { "type": "number" },
+                "averageMs": { "type": "number" },
+                "lastMs": { "type": "number" },
+            },
+        },
+    })
+}
+
+fn command_arguments_schema() -> Value {
+    json!({
+        "type": "array",
+        "prefixItems": [{ "type": "string", "description": "The script file URI." }],
+        "minItems": 1,
+    })
+}
+
+fn server_extensions_schema() -> Value {
+    let extensions = json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "required": ["name", "version"],
+            "properties": {
+                "name": { "type": "string" },
+                "version": { "type": "integer", "minimum": 1 },
+            },
+        },
+    });
+
+    json!({
+        "type": "object",
+        "required": ["protocol", "requests", "notifications", "commands"],
+        "properties": {
+            "protocol": { "type": "integer", "minimum": 1 },
+            "requests": extensions,
+            "notifications": extensions,
+            "commands": extensions,
+        },
+    })
+}
+
+fn client_extensions_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "protocol": { "type": "integer", "minimum": 1 },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::request::Request;
+    use serde_json::json;
+
+    use crate::server::protocol::{
+        schema,
+        ClientExtensions,
+        PerfParams,
+        PerfRequest,
+        ServerExtensions,
+        CMD_LAUNCH,
+        PROTOCOL_VERSION,
+    };
+
+    #[test]
+    fn test_protocol_negotiation() {
+        let experimental = json!({ "adastra": { "protocol": 1 } });
+
+        let client = ClientExtensions::from_experimental(Some(&experimental));
+
+        assert_eq!(client.protocol, Some(1));
+
+        let server = ServerExtensions::negotiate(&client, |_| true);
+
+        assert_eq!(server.protocol, 1);
+        assert_eq!(server.requests.len(), 1);
+        assert_eq!(server.requests[0].name, PerfRequest::METHOD);
+        assert_eq!(server.commands.len(), 3);
+
+        let client = ClientExtensions::from_experimental(None);
+
+        assert_eq!(client.protocol, None);
+
+        let server = ServerExtensions::negotiate(&client, |name| name != PerfRequest::METHOD);
+
+        assert_eq!(server.protocol, PROTOCOL_VERSION);
+        assert!(server.requests.is_empty());
+
+        let experimental = json!({ "adastra": { "protocol": 0 } });
+        let client = ClientExtensions::from_experimental(Some(&experimental));
+        let server = ServerExtensions::negotiate(&client, |_| true);
+
+        assert_eq!(server.protocol, 0);
+        assert!(server.requests.is_empty());
+        assert!(server.commands.is_empty());
+
+        let experimental = json!({ "adastra": "malformed" });
+        let client = ClientExtensions::from_experimental(Some(&experimental));
+
+        assert_eq!(client, ClientExtensions::default());
+
+        let advertised = ServerExtensions::negotiate(&client, |_| true).to_experimental();
+
+        assert_eq!(advertised["adastra"]["requests"][0]["name"], "adastra/perf");
+        assert_eq!(advertised["adastra"]["commands"][0]["name"], CMD_LAUNCH);
+    }
+
+    #[test]
+    fn test_protocol_schema() {
+        let schema = schema();
+
+        assert_eq!(schema["protocol"], PROTOCOL_VERSION);
+        assert_eq!(schema["capabilities"]["key"], "adastra");
+        assert_eq!(schema["requests"][0]["method"], "adastra/perf");
+        assert_eq!(
+            schema["requests"][0]["result"]["required"],
+            json!(["total", "files"]),
+        );
+        assert_eq!(schema["commands"].as_array().map(Vec::len), Some(3));
+
+        let params = serde_json::from_value::<PerfParams>(json!({})).unwrap();
+
+        assert_eq!(params, PerfParams { reset: false });
+    }
+}
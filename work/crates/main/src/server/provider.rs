@@ -0,0 +1,312 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Formatter},
+    fs::read_to_string,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use ahash::AHashSet;
+use lsp_types::Uri;
+
+/// A storage of script documents that the language server accesses on its
+/// own, without receiving their content from the client.
+///
+/// The client sends the content of the documents that the user opens in the
+/// code editor explicitly. However, the server may need to access other
+/// documents as well: for example, if the client sends a request related to a
+/// document that has not been opened, the server loads this document through
+/// the DocumentProvider and keeps it in sync with the provider until the user
+/// opens it in the editor.
+///
+/// By default, the server uses the [FileSystemProvider], which reads
+/// `file:` URIs from the local file system. If your scripts are stored
+/// elsewhere (e.g., in a database with URIs like `app://scripts/123`),
+/// implement this trait and specify your implementation in the
+/// [LspServerConfig::document_provider](crate::server::LspServerConfig::document_provider)
+/// configuration option.
+///
+/// ```
+/// use ad_astra::server::{DocumentProvider, LspDocumentProvider, LspServerConfig};
+/// use lsp_types::Uri;
+///
+/// struct AppStorage;
+///
+/// impl DocumentProvider for AppStorage {
+///     fn schemes(&self) -> &[&'static str] {
+///         &["app"]
+///     }
+///
+///     fn exists(&self, uri: &Uri) -> bool {
+///         uri.as_str() == "app://scripts/123"
+///     }
+///
+///     fn read(&self, uri: &Uri) -> Option<String> {
+///         match self.exists(uri) {
+///             true => Some(String::from("let x = 10;")),
+///             false => None,
+///         }
+///     }
+/// }
+///
+/// static APP_STORAGE: AppStorage = AppStorage;
+///
+/// let mut config = LspServerConfig::new();
+///
+/// config.document_provider = LspDocumentProvider::new(&APP_STORAGE);
+/// ```
+pub trait DocumentProvider: Send + Sync + 'static {
+    /// The URI schemes of the documents served by this provider (e.g.,
+    /// `"file"` or `"app"`).
+    ///
+    /// The server uses this list to register the file operation filters on
+    /// the client side.
+    fn schemes(&self) -> &[&'static str];
+
+    /// Returns true if the document with the specified `uri` exists in this
+    /// storage.
+    fn exists(&self, uri: &Uri) -> bool;
+
+    /// Returns the full text of the document with the specified `uri`.
+    ///
+    /// Returns None if the document does not exist or cannot be read.
+    fn read(&self, uri: &Uri) -> Option<String>;
+
+    /// Called by the server when it starts tracking the document loaded from
+    /// this provider.
+    ///
+    /// The implementation may store the `watcher` object and call
+    /// [DocumentWatcher::notify] whenever the document's content changes in
+    /// the storage. The server will then [read](Self::read) the new content.
+    ///
+    /// The default implementation does nothing, meaning that the server
+    /// does not receive change notifications from this provider.
+    #[inline(always)]
+    fn subscribe(&self, uri: &Uri, watcher: DocumentWatcher) {
+        let _ = uri;
+        let _ = watcher;
+    }
+
+    /// Called by the server when it stops tracking the document previously
+    /// [subscribed](Self::subscribe) to.
+    ///
+    /// After this call, the notifications of the document's watcher are
+    /// ignored by the server.
+    ///
+    /// The default implementation does nothing.
+    #[inline(always)]
+    fn unsubscribe(&self, uri: &Uri) {
+        let _ = uri;
+    }
+}
+
+/// A handle through which a [DocumentProvider] notifies the language server
+/// about changes in the document's content.
+///
+/// The object is cheap to [Clone], and it can be sent to other threads.
+///
+/// The server handles the notifications before handling the next incoming
+/// client message.
+#[derive(Clone)]
+pub struct DocumentWatcher {
+    pub(super) uri: Uri,
+    pub(super) changes: DocumentChanges,
+}
+
+impl Debug for DocumentWatcher {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("DocumentWatcher")
+            .field("uri", &self.uri.as_str())
+            .finish_non_exhaustive()
+    }
+}
+
+impl DocumentWatcher {
+    /// Returns the URI of the watched document.
+    #[inline(always)]
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// Informs the server that the document's content has been changed in the
+    /// storage.
+    #[inline(always)]
+    pub fn notify(&self) {
+        let Ok(mut changes) = self.changes.lock() else {
+            return;
+        };
+
+        let _ = changes.insert(self.uri.to_string());
+    }
+}
+
+pub(super) type DocumentChanges = Arc<Mutex<AHashSet<String>>>;
+
+/// The default [DocumentProvider] that reads `file:` URIs from the local
+/// file system.
+///
+/// This provider does not watch file changes.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct FileSystemProvider;
+
+impl DocumentProvider for FileSystemProvider {
+    #[inline(always)]
+    fn schemes(&self) -> &[&'static str] {
+        &["file"]
+    }
+
+    fn exists(&self, uri: &Uri) -> bool {
+        let Some(path) = Self::uri_to_path(uri) else {
+            return false;
+        };
+
+        path.is_file()
+    }
+
+    fn read(&self, uri: &Uri) -> Option<String> {
+        let path = Self::uri_to_path(uri)?;
+
+        read_to_string(path).ok()
+    }
+}
+
+impl FileSystemProvider {
+    fn uri_to_path(uri: &Uri) -> Option<PathBuf> {
+        if uri.scheme()?.as_str() != "file" {
+            return None;
+        }
+
+        let path = uri.path().as_estr().decode().into_string().ok()?;
+
+        #[cfg(target_os = "windows")]
+        {
+            // "/C:/foo/bar" -> "C:/foo/bar"
+            if let Some(path) = path.strip_prefix('/') {
+                if path.as_bytes().get(1) == Some(&b':') {
+                    return Some(PathBuf::from(path));
+                }
+            }
+        }
+
+        Some(PathBuf::from(path.as_ref()))
+    }
+}
+
+/// A reference to the [DocumentProvider] used by the language server.
+///
+/// See [LspServerConfig::document_provider](crate::server::LspServerConfig::document_provider)
+/// for details.
+///
+/// Two LspDocumentProvider objects are equal if they refer to the same
+/// provider instance.
+#[derive(Clone, Copy)]
+pub struct LspDocumentProvider(&'static dyn DocumentProvider);
+
+impl Default for LspDocumentProvider {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::filesystem()
+    }
+}
+
+impl Debug for LspDocumentProvider {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_tuple("LspDocumentProvider")
+            .field(&self.0.schemes())
+            .finish()
+    }
+}
+
+impl PartialEq for LspDocumentProvider {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl Eq for LspDocumentProvider {}
+
+impl PartialOrd for LspDocumentProvider {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LspDocumentProvider {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+impl Hash for LspDocumentProvider {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state)
+    }
+}
+
+impl LspDocumentProvider {
+    /// Creates a reference to the specified `provider`.
+    #[inline(always)]
+    pub const fn new(provider: &'static dyn DocumentProvider) -> Self {
+        Self(provider)
+    }
+
+    /// Creates a reference to the default [FileSystemProvider].
+    #[inline(always)]
+    pub const fn filesystem() -> Self {
+        Self(&FileSystemProvider)
+    }
+
+    /// Returns the underlying provider.
+    #[inline(always)]
+    pub fn get(&self) -> &'static dyn DocumentProvider {
+        self.0
+    }
+
+    #[inline(always)]
+    fn addr(&self) -> usize {
+        self.0 as *const dyn DocumentProvider as *const () as usize
+    }
+}
@@ -774,6 +774,7 @@ impl<'a> RenameAnalyzer<'a> {
             ModuleSymbol::Operator(_) => self.error = "operators cannot be renamed",
             ModuleSymbol::Call(_) => self.error = "nothing to rename in this position",
             ModuleSymbol::Index(_) => self.error = "nothing to rename in this position",
+            ModuleSymbol::Enum(_) => self.error = "cannot rename keyword",
         }
 
         Ok(ScriptOrigin::nil())
@@ -822,6 +823,7 @@ impl<'a> RenameAnalyzer<'a> {
             ModuleSymbol::Operator(_) => self.error = "operators cannot be renamed",
             ModuleSymbol::Call(_) => self.error = "nothing to rename in this position",
             ModuleSymbol::Index(_) => self.error = "nothing to rename in this position",
+            ModuleSymbol::Enum(_) => self.error = "cannot rename keyword",
         }
 
         Ok(())
@@ -891,6 +893,12 @@ impl<'a> RenameAnalyzer<'a> {
             IdentKind::VarAccess | IdentKind::VarDefinition => {
                 match symbol.declaration(self.read)? {
                     ModuleSymbol::Var(decl) => self.rename_var(&decl, new_name)?,
+
+                    ModuleSymbol::Enum(decl) => match decl.var(self.read) {
+                        Some(var) => self.rename_var(&var, new_name)?,
+                        None => self.check_new_ident_name(new_name),
+                    },
+
                     _ => self.check_new_ident_name(new_name),
                 }
             }
@@ -601,8 +601,15 @@ impl Debug for RpcId {
 
 pub(super) type RpcLatches = Shared<Table<RpcId, Trigger, RandomState>>;
 
+/// The [TaskHandle] of the analysis tasks spawned by the language server.
+///
+/// The server interrupts the task through this handle when the server shuts
+/// down or when the client cancels the request. The host's
+/// [lint passes](crate::analysis::LintPass) registered through the
+/// [LspServerConfig::lint_passes](crate::server::LspServerConfig::lint_passes)
+/// option receive the read guards parameterized by this handle.
 #[derive(Default, Clone)]
-pub(super) struct LspHandle {
+pub struct LspHandle {
     server: Trigger,
     client: Option<Trigger>,
 }
@@ -629,7 +636,7 @@ impl TaskHandle for LspHandle {
 
 impl LspHandle {
     #[inline(always)]
-    pub fn new(lsp_latch: &Trigger) -> Self {
+    pub(super) fn new(lsp_latch: &Trigger) -> Self {
         Self {
             server: Trigger::new(),
             client: Some(lsp_latch.clone()),
@@ -41,6 +41,7 @@ use lsp_types::{
     error_codes::{REQUEST_FAILED, SERVER_NOT_INITIALIZED},
     notification::{
         Cancel,
+        DidChangeConfiguration,
         DidChangeTextDocument,
         DidCloseTextDocument,
         DidOpenTextDocument,
@@ -57,12 +58,14 @@ use lsp_types::{
         Formatting,
         GotoDefinition,
         GotoImplementation,
+        GotoTypeDefinition,
         HoverRequest,
         Initialize,
         InlayHintRequest,
         LinkedEditingRange,
         PrepareRenameRequest,
         Rename,
+        Request,
         Shutdown,
         SignatureHelpRequest,
         WillRenameFiles,
@@ -73,6 +76,7 @@ use lsp_types::{
     CodeActionProviderCapability,
     CodeLensOptions,
     CompletionOptions,
+    DidChangeConfigurationParams,
     DidChangeTextDocumentParams,
     ExecuteCommandOptions,
     FileOperationFilter,
@@ -90,21 +94,34 @@ use lsp_types::{
     ServerCapabilities,
     ServerInfo,
     SignatureHelpOptions,
+    TextDocumentContentChangeEvent,
     TextDocumentItem,
     TextDocumentSyncCapability,
     TextDocumentSyncKind,
     TextDocumentSyncOptions,
+    TypeDefinitionProviderCapability,
     Uri,
     WorkspaceFileOperationsServerCapabilities,
     WorkspaceServerCapabilities,
 };
 
 use crate::{
+    analysis::ModuleStats,
     runtime::PackageMeta,
     server::{
-        command::{CMD_CLEANUP, CMD_LAUNCH, CMD_STOP},
         file::{File, FileConfig, FileMessage},
         logger::{LspLogger, LSP_CLIENT_LOG, LSP_SERVER_LOG},
+        perf::PerfRegistry,
+        protocol::{
+            ClientExtensions,
+            PerfRequest,
+            ServerExtensions,
+            CMD_CLEANUP,
+            CMD_LAUNCH,
+            CMD_STOP,
+            METHOD_NOT_FOUND,
+        },
+        provider::{DocumentChanges, DocumentWatcher},
         rpc::{
             OutgoingEx,
             RpcId,
@@ -114,7 +131,7 @@ use crate::{
             RpcRequest,
             RpcResponse,
         },
-        tasks::LocalOrRemote,
+        tasks::{LocalOrRemote, PoolStats, TaskGroup, TaskPool, TaskScheduler},
         HealthCheck,
         LspCapabilities,
         LspLoggerConfig,
@@ -142,6 +159,10 @@ pub struct LspServer {
     pub(super) latches: RpcLatches,
     pub(super) health_check: Option<HealthCheck>,
     pub(super) files: AHashMap<String, LocalOrRemote<File>>,
+    pub(super) provided: AHashMap<String, i32>,
+    pub(super) provided_changes: DocumentChanges,
+    pub(super) pool: Option<TaskPool>,
+    pub(super) perf: PerfRegistry,
 }
 
 impl LspServer {
@@ -198,6 +219,11 @@ impl LspServer {
             None => None,
         };
 
+        let pool = match config.multi_thread {
+            true => TaskPool::new(config.analysis_threads, health_check.clone()),
+            false => None,
+        };
+
         Self {
             config,
             package,
@@ -206,6 +232,10 @@ impl LspServer {
             latches: Shared::new(latches),
             health_check,
             files: AHashMap::new(),
+            provided: AHashMap::new(),
+            provided_changes: DocumentChanges::default(),
+            pool,
+            perf: PerfRegistry::default(),
         }
     }
 
@@ -231,6 +261,32 @@ impl LspServer {
         self.health_check.as_ref()
     }
 
+    /// Returns the current statistics of the worker threads pool on which
+    /// the server executes the background jobs of all opened script modules.
+    ///
+    /// The function returns `None` if the `multi_thread` flag in
+    /// [LspServerConfig] is set to false, or if the server failed to spawn
+    /// the worker threads. In this case, the server executes all jobs on the
+    /// thread that calls the [handle](LspServer::handle) function.
+    #[inline(always)]
+    pub fn pool_stats(&self) -> Option<PoolStats> {
+        self.pool.as_ref().map(TaskPool::stats)
+    }
+
+    /// Returns the aggregated query statistics of all script modules
+    /// currently opened in the editor.
+    ///
+    /// The function returns `None` if the `perf_stats` flag in
+    /// [LspServerConfig] is set to false.
+    #[inline(always)]
+    pub fn module_stats(&self) -> Option<ModuleStats> {
+        if !self.config.perf_stats {
+            return None;
+        }
+
+        Some(self.perf.total())
+    }
+
     /// Returns true if the communication session has been established between
     /// the server and the client, and both the server and the client are fully
     /// initialized and ready to work.
@@ -255,7 +311,13 @@ impl LspServer {
     ///
     /// You can create this `message` object by deserializing the incoming raw
     /// text of the message using the [RpcMessage::from_input_bytes] function.
+    ///
+    /// Before handling the message, the server synchronizes the documents
+    /// loaded from the [DocumentProvider](crate::server::DocumentProvider)
+    /// that have been changed since the previous call.
     pub fn handle(&mut self, message: RpcMessage) {
+        self.sync_provided();
+
         match message.0 {
             RpcMessageInner::Request(message) => self.handle_request(message),
             RpcMessageInner::Response(message) => self.handle_response(message),
@@ -307,12 +369,19 @@ impl LspServer {
         }
     }
 
+    #[inline(always)]
+    fn scheduler(&self) -> Option<TaskScheduler> {
+        self.pool.as_ref().map(|pool| pool.scheduler().clone())
+    }
+
     #[inline(always)]
     fn register_latch(
         &mut self,
         id: &RpcId,
         uri: &Uri,
     ) -> Option<(Trigger, &mut LocalOrRemote<File>)> {
+        self.provide(uri);
+
         let Some(file) = self.files.get_mut(uri.as_str()) else {
             error!(target: LSP_CLIENT_LOG, "[{}] Missing document.", uri.as_str());
 
@@ -332,6 +401,129 @@ impl LspServer {
 
         Some((cancel, file))
     }
+
+    // Attaches the document that is not opened by the client, loading its
+    // content from the document provider.
+    fn provide(&mut self, uri: &Uri) {
+        if self.files.contains_key(uri.as_str()) {
+            return;
+        }
+
+        if !uri
+            .as_str()
+            .ends_with(&format!(".{}", self.config.file_ext))
+        {
+            return;
+        }
+
+        let provider = self.config.document_provider.get();
+
+        let Some(text) = provider.read(uri) else {
+            return;
+        };
+
+        debug!(target: LSP_CLIENT_LOG, "[{}] Document provided.", uri.as_str());
+
+        let version = 0;
+
+        let scheduler = self.scheduler();
+
+        let file = LocalOrRemote::new(
+            format!("[{}] (file)", uri.as_str()),
+            TaskGroup::new(uri.as_str()),
+            &scheduler,
+            FileConfig {
+                config: self.config,
+                package: self.package,
+                scheduler: scheduler.clone(),
+                latches: self.latches.clone(),
+                outgoing: self.outgoing.deref().clone(),
+                perf: self.perf.clone(),
+                uri: uri.clone(),
+                version,
+                text,
+            },
+        );
+
+        let _ = self.files.insert(uri.to_string(), file);
+        let _ = self.provided.insert(uri.to_string(), version);
+
+        provider.subscribe(
+            uri,
+            DocumentWatcher {
+                uri: uri.clone(),
+                changes: self.provided_changes.clone(),
+            },
+        );
+    }
+
+    // Stops tracking the document in the document provider. Returns false if
+    // the document was not loaded from the provider.
+    fn release_provided(&mut self, uri: &str) -> bool {
+        if self.provided.remove(uri).is_none() {
+            return false;
+        }
+
+        if let Ok(uri) = Uri::from_str(uri) {
+            self.config.document_provider.get().unsubscribe(&uri);
+        }
+
+        true
+    }
+
+    fn sync_provided(&mut self) {
+        let changes = {
+            let Ok(mut changes) = self.provided_changes.lock() else {
+                return;
+            };
+
+            if changes.is_empty() {
+                return;
+            }
+
+            changes.drain().collect::<Vec<_>>()
+        };
+
+        let provider = self.config.document_provider.get();
+
+        for uri in changes {
+            let Some(version) = self.provided.get_mut(&uri) else {
+                continue;
+            };
+
+            let Ok(parsed) = Uri::from_str(&uri) else {
+                continue;
+            };
+
+            let Some(text) = provider.read(&parsed) else {
+                let _ = self.release_provided(&uri);
+                let _ = self.files.remove(&uri);
+
+                debug!(target: LSP_CLIENT_LOG, "[{uri}] Provided document detached.");
+
+                continue;
+            };
+
+            *version += 1;
+
+            let version = *version;
+
+            let Some(file) = self.files.get_mut(&uri) else {
+                continue;
+            };
+
+            debug!(target: LSP_CLIENT_LOG, "[{uri}] Provided document changed.");
+
+            file.send(FileMessage::ChangeText {
+                version,
+                changes: vec![TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text,
+                }],
+            });
+        }
+    }
 }
 
 // Requests
@@ -378,6 +570,10 @@ impl LspServer {
             return self.handle_goto_implementation(message);
         }
 
+        if message.is::<GotoTypeDefinition>() {
+            return self.handle_goto_type_definition(message);
+        }
+
         if message.is::<CodeActionRequest>() {
             return self.handle_code_action(message);
         }
@@ -406,13 +602,17 @@ impl LspServer {
             return self.handle_execute_command(message);
         }
 
+        if message.is::<PerfRequest>() {
+            return self.handle_request_perf(message);
+        }
+
         error!(target: LSP_CLIENT_LOG, "Unhandled {:?}.", message);
 
         self.outgoing.send_err_response(
             &self.latches,
             message.id,
-            REQUEST_FAILED,
-            format!("Unimplemented request handler for {:?}.", message.method),
+            METHOD_NOT_FOUND,
+            format!("Unknown request method {:?}.", message.method),
         );
     }
 
@@ -463,6 +663,11 @@ impl LspServer {
             .capabilities
             .intersect(LspCapabilities::from_client(&params.capabilities));
 
+        let extensions = ServerExtensions::negotiate(
+            &ClientExtensions::from_experimental(params.capabilities.experimental.as_ref()),
+            |name| self.extension_enabled(name),
+        );
+
         self.outgoing.send_ok_response::<Initialize>(
             &self.latches,
             id,
@@ -483,14 +688,21 @@ impl LspServer {
                         workspace_folders: None,
                         file_operations: Some(WorkspaceFileOperationsServerCapabilities {
                             will_rename: Some(FileOperationRegistrationOptions {
-                                filters: vec![FileOperationFilter {
-                                    scheme: Some(String::from("file")),
-                                    pattern: FileOperationPattern {
-                                        glob: format!("**/*.{}", self.config.file_ext),
-                                        matches: Some(FileOperationPatternKind::File),
-                                        options: None,
-                                    },
-                                }],
+                                filters: self
+                                    .config
+                                    .document_provider
+                                    .get()
+                                    .schemes()
+                                    .iter()
+                                    .map(|scheme| FileOperationFilter {
+                                        scheme: Some(String::from(*scheme)),
+                                        pattern: FileOperationPattern {
+                                            glob: format!("**/*.{}", self.config.file_ext),
+                                            matches: Some(FileOperationPatternKind::File),
+                                            options: None,
+                                        },
+                                    })
+                                    .collect(),
                             }),
 
                             ..Default::default()
@@ -532,9 +744,17 @@ impl LspServer {
                         false => None,
                     },
 
+                    type_definition_provider: match self.config.capabilities.goto_type_definition {
+                        true => Some(TypeDefinitionProviderCapability::Simple(true)),
+                        false => None,
+                    },
+
                     code_action_provider: match self.config.capabilities.code_action {
                         true => Some(CodeActionProviderCapability::Options(CodeActionOptions {
-                            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                            code_action_kinds: Some(vec![
+                                CodeActionKind::QUICKFIX,
+                                CodeActionKind::REFACTOR_INLINE,
+                            ]),
 
                             ..CodeActionOptions::default()
                         })),
@@ -601,6 +821,8 @@ impl LspServer {
                         false => None,
                     },
 
+                    experimental: Some(extensions.to_experimental()),
+
                     ..Default::default()
                 },
 
@@ -614,6 +836,22 @@ impl LspServer {
         self.state = ServerState::Initializing;
     }
 
+    fn extension_enabled(&self, name: &str) -> bool {
+        if name == PerfRequest::METHOD {
+            return self.config.perf_stats;
+        }
+
+        if !self.config.capabilities.execute_command || !self.config.scripts_runner {
+            return false;
+        }
+
+        if name == CMD_CLEANUP {
+            return self.config.capabilities.inlay_hints;
+        }
+
+        name == CMD_LAUNCH || name == CMD_STOP
+    }
+
     fn handle_request_shutdown(&mut self, request: RpcRequest) {
         if !self.check_state(Some(&request.id)) {
             return;
@@ -621,12 +859,45 @@ impl LspServer {
 
         self.state = ServerState::Shutdown;
 
+        if let Some(stats) = self.module_stats() {
+            info!(target: LSP_SERVER_LOG, "Analysis query stats:\n{stats}");
+        }
+
+        for uri in self.provided.keys().cloned().collect::<Vec<_>>() {
+            let _ = self.release_provided(&uri);
+        }
+
         self.files.clear();
 
         self.outgoing
             .send_ok_response::<Shutdown>(&self.latches, request.id, ());
     }
 
+    fn handle_request_perf(&mut self, request: RpcRequest) {
+        if !self.check_state(Some(&request.id)) {
+            return;
+        }
+
+        if !self.config.perf_stats {
+            self.outgoing.send_err_response(
+                &self.latches,
+                request.id,
+                REQUEST_FAILED,
+                "Query statistics collection is disabled.",
+            );
+            return;
+        }
+
+        let (id, params) = request.extract::<PerfRequest>();
+
+        let reset = params.unwrap_or_default().reset;
+
+        let result = self.perf.report(reset);
+
+        self.outgoing
+            .send_ok_response::<PerfRequest>(&self.latches, id, result);
+    }
+
     fn handle_request_will_rename_files(&mut self, request: RpcRequest) {
         if !self.check_state(Some(&request.id)) {
             return;
@@ -639,6 +910,12 @@ impl LspServer {
                 continue;
             }
 
+            // The provided documents will be reloaded on demand.
+            if self.release_provided(old_uri.as_str()) {
+                let _ = self.files.remove(old_uri.as_str());
+                continue;
+            }
+
             let Some(mut file) = self.files.remove(old_uri.as_str()) else {
                 error!(target: LSP_CLIENT_LOG, "[{old_uri}] Missing document.");
                 continue;
@@ -667,6 +944,8 @@ impl LspServer {
 
         let (id, params) = request.extract::<InlayHintRequest>();
 
+        self.provide(&params.text_document.uri);
+
         let Some(file) = self.files.get_mut(params.text_document.uri.as_str()) else {
             error!(target: LSP_CLIENT_LOG, "[{}] Missing document.", params.text_document.uri.as_str());
             self.outgoing.send_err_response(
@@ -696,6 +975,8 @@ impl LspServer {
 
         let (id, params) = request.extract::<Formatting>();
 
+        self.provide(&params.text_document.uri);
+
         let Some(file) = self.files.get_mut(params.text_document.uri.as_str()) else {
             error!(target: LSP_CLIENT_LOG, "[{}] Missing document.", params.text_document.uri.as_str());
             self.outgoing.send_err_response(
@@ -725,6 +1006,8 @@ impl LspServer {
 
         let (id, params) = request.extract::<Completion>();
 
+        let config = self.config.completion;
+
         let Some((cancel, file)) =
             self.register_latch(&id, &params.text_document_position.text_document.uri)
         else {
@@ -735,6 +1018,7 @@ impl LspServer {
             id,
             cancel,
             position: params.text_document_position.position,
+            config,
         });
     }
 
@@ -818,6 +1102,26 @@ impl LspServer {
         });
     }
 
+    fn handle_goto_type_definition(&mut self, request: RpcRequest) {
+        if !self.check_state(Some(&request.id)) {
+            return;
+        }
+
+        let (id, params) = request.extract::<GotoTypeDefinition>();
+
+        let Some((cancel, file)) =
+            self.register_latch(&id, &params.text_document_position_params.text_document.uri)
+        else {
+            return;
+        };
+
+        file.send(FileMessage::GotoTypeDefinition {
+            id,
+            cancel,
+            position: params.text_document_position_params.position,
+        });
+    }
+
     fn handle_code_action(&mut self, request: RpcRequest) {
         if !self.check_state(Some(&request.id)) {
             return;
@@ -1032,6 +1336,17 @@ impl LspServer {
             return self.handle_notification_did_change_text_document(message);
         }
 
+        if message.is::<DidChangeConfiguration>() {
+            return self.handle_notification_did_change_configuration(message);
+        }
+
+        // Optional and custom notifications that the server does not support
+        // are silently ignored.
+        if message.method.starts_with("$/") || message.method.starts_with("adastra/") {
+            debug!(target: LSP_CLIENT_LOG, "Ignored {:?}.", message);
+            return;
+        }
+
         error!(target: LSP_CLIENT_LOG, "Unhandled {:?}.", message);
     }
 
@@ -1095,14 +1410,36 @@ impl LspServer {
             text,
         } = params.text_document;
 
+        let provided = self.release_provided(uri.as_str());
+
         match self.files.entry(uri.to_string()) {
-            Entry::Occupied(entry) => match language_id == self.config.language_id {
+            Entry::Occupied(mut entry) => match language_id == self.config.language_id {
                 true => {
-                    debug!(
-                        target: LSP_CLIENT_LOG,
-                        "[{}] Document preserved (language preserved).",
-                        uri.as_str(),
-                    );
+                    // The document is synchronized with the client's text
+                    // through the line diff, which is cheap if the text is
+                    // the same.
+                    entry.get_mut().send(FileMessage::ChangeText {
+                        version,
+                        changes: vec![TextDocumentContentChangeEvent {
+                            range: None,
+                            range_length: None,
+                            text,
+                        }],
+                    });
+
+                    match provided {
+                        true => debug!(
+                            target: LSP_CLIENT_LOG,
+                            "[{}] Provided document opened by the client.",
+                            uri.as_str(),
+                        ),
+
+                        false => debug!(
+                            target: LSP_CLIENT_LOG,
+                            "[{}] Document preserved (language preserved).",
+                            uri.as_str(),
+                        ),
+                    }
                 }
 
                 false => {
@@ -1129,16 +1466,19 @@ impl LspServer {
 
                 debug!(target: LSP_CLIENT_LOG, "[{}] Document attached.", entry.key());
 
+                let scheduler = self.pool.as_ref().map(|pool| pool.scheduler().clone());
+
                 let value = LocalOrRemote::new(
                     format!("[{}] (file)", entry.key()),
-                    self.config.multi_thread,
-                    &self.health_check,
+                    TaskGroup::new(entry.key().as_str()),
+                    &scheduler,
                     FileConfig {
                         config: self.config,
                         package: self.package,
-                        health_check: self.health_check.clone(),
+                        scheduler: scheduler.clone(),
                         latches: self.latches.clone(),
                         outgoing: self.outgoing.deref().clone(),
+                        perf: self.perf.clone(),
                         uri,
                         version,
                         text,
@@ -1157,6 +1497,8 @@ impl LspServer {
 
         let uri = message.extract::<DidCloseTextDocument>().text_document.uri;
 
+        let _ = self.release_provided(uri.as_str());
+
         if self.files.remove(uri.as_str()).is_none() {
             return;
         }
@@ -1184,6 +1526,22 @@ impl LspServer {
             changes: content_changes,
         });
     }
+
+    fn handle_notification_did_change_configuration(&mut self, message: RpcNotification) {
+        if !self.check_state(None) {
+            return;
+        }
+
+        let DidChangeConfigurationParams { settings } = message.extract::<DidChangeConfiguration>();
+
+        let Some(settings) = settings.get(self.config.language_id) else {
+            return;
+        };
+
+        self.config.completion.apply_settings(settings);
+
+        debug!(target: LSP_CLIENT_LOG, "Server configuration changed.");
+    }
 }
 
 #[derive(Clone)]
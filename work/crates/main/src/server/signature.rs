@@ -198,7 +198,19 @@ impl Task for SendSignatureHelp {
             let text = module_read_guard.text();
 
             let args_origin = symbol.origin(&module_read_guard);
-            let param_index = infer_param_index(&text, &position, args_origin);
+            let mut param_index = infer_param_index(&text, &position, args_origin);
+
+            // The trailing closure is the last argument of the invocation.
+            let closure = symbol.closure(&module_read_guard);
+
+            let mut in_closure = false;
+
+            if let Some(closure_span) = closure.origin(&module_read_guard).to_site_span(&text) {
+                if position.to_site(&text).unwrap_or_default() >= closure_span.start {
+                    param_index = symbol.args(&module_read_guard).len().checked_sub(1);
+                    in_closure = true;
+                }
+            }
 
             let receiver = symbol.receiver(&module_read_guard);
 
@@ -248,6 +260,27 @@ impl Task for SendSignatureHelp {
                 break;
             };
 
+            // Named arguments refer to the parameters by their names, and the
+            // trailing closure of such invocations binds to the last parameter.
+            if let Some(inputs) = &invocation.inputs {
+                let arg_names = symbol.arg_names(&module_read_guard);
+
+                if arg_names.iter().any(Option::is_some) {
+                    param_index = match in_closure {
+                        true => inputs.len().checked_sub(1),
+
+                        false => match param_index.and_then(|index| arg_names.get(index)) {
+                            Some(Some(name)) => inputs.iter().position(|param| match &param.name {
+                                Some(param_name) => param_name.as_ref() == name,
+                                None => false,
+                            }),
+
+                            _ => param_index,
+                        },
+                    };
+                }
+            }
+
             let doc = make_doc(
                 &module_read_guard,
                 &text,
@@ -257,13 +290,30 @@ impl Task for SendSignatureHelp {
                 &ty,
             );
 
+            // The remaining arguments of the variadic function refer to the
+            // last parameter.
+            if invocation.variadic {
+                if let Some(inputs) = &invocation.inputs {
+                    let last = inputs.len().checked_sub(1);
+
+                    param_index = match (param_index, last) {
+                        (Some(index), Some(last)) => Some(index.min(last)),
+                        _ => param_index,
+                    };
+                }
+            }
+
             let mut params = Vec::with_capacity(invocation.arity().unwrap_or(0));
 
             if let Some(inputs) = &invocation.inputs {
                 for param in inputs {
-                    let documentation = match param.hint.is_dynamic() {
-                        true => None,
-                        false => Some(Documentation::String(param.hint.to_string())),
+                    let documentation = match (param.hint.is_dynamic(), param.doc) {
+                        (true, None) => None,
+                        (true, Some(doc)) => Some(Documentation::String(doc.to_string())),
+                        (false, None) => Some(Documentation::String(param.hint.to_string())),
+                        (false, Some(doc)) => {
+                            Some(Documentation::String(format!("{}\n\n{doc}", param.hint,)))
+                        }
                     };
 
                     params.push(ParameterInformation {
@@ -322,6 +322,29 @@ impl Snippet for SnippetLet {
     };
 }
 
+pub(super) struct SnippetEnum;
+
+impl Snippet for SnippetEnum {
+    const LABEL: &'static str = "enum";
+
+    const DOCUMENTATION: &'static str = markup! {
+        /// Enum declaration.
+        ///
+        /// ```<adastra>
+        /// enum EnumName {
+        ///     Variant1,
+        ///     Variant2,
+        /// }
+        /// ```
+    };
+
+    const SNIPPET: &'static str = markup! {
+        /// enum $1 {
+        ///     $2
+        /// }
+    };
+}
+
 pub(super) struct SnippetIf;
 
 impl Snippet for SnippetIf {
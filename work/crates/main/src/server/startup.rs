@@ -186,8 +186,6 @@ impl LspServer {
 
                     let half = timeout / 2;
 
-                    health_check.ping();
-
                     park_timeout(half);
 
                     for (name, pong) in health_check.check(true) {
@@ -33,38 +33,35 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use std::{
-    any::Any,
-    collections::VecDeque,
+    collections::{hash_map::DefaultHasher, VecDeque},
     fmt::{Debug, Formatter},
-    mem::take,
+    hash::{Hash, Hasher},
+    mem::{replace, take},
+    num::NonZeroUsize,
     ops::Deref,
-    sync::{
-        mpsc::{channel, sync_channel, Receiver, SendError, Sender, TryRecvError},
-        Mutex,
-    },
-    thread::{current, park_timeout, Builder, JoinHandle, ThreadId},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread::{available_parallelism, current, Builder, JoinHandle, ThreadId},
     time::{Duration, Instant},
 };
 
-use ahash::RandomState;
+use ahash::{AHashMap, RandomState};
 use lady_deirdre::sync::{Shared, Table};
 use log::{error, trace, warn};
 
 use crate::{
-    report::{debug_unreachable, system_panic},
-    server::{
-        file::LspModule,
-        logger::{LSP_CLIENT_LOG, LSP_SERVER_LOG},
-    },
+    report::debug_unreachable,
+    server::{file::LspModule, logger::LSP_CLIENT_LOG},
 };
 
 pub(super) const COOL_DOWN: Duration = Duration::from_millis(100);
+pub(super) const DIAGNOSTICS_COOL_DOWN: Duration = Duration::from_millis(20);
 pub(super) const TIMEOUT: Duration = Duration::from_millis(500);
 
 const STATS: usize = 10;
+const STATS_WINDOW: Duration = Duration::from_secs(1);
 
-/// An object through which you can monitor the health status of the separate
-/// job threads spawned by the server.
+/// An object through which you can monitor the health status of the jobs
+/// executed by the server's worker threads.
 ///
 /// The health check status is available only if the `health_check` option
 /// in [LspServerConfig](crate::server::LspServerConfig) is enabled.
@@ -72,8 +69,8 @@ const STATS: usize = 10;
 /// You can access the server's HealthCheck object using the
 /// [LspServer::health_check](crate::server::LspServer::health_check) function.
 ///
-/// The [Debug] implementation of this object prints a list of threads that
-/// haven't responded for a prolonged period.
+/// The [Debug] implementation of this object prints a list of jobs that
+/// haven't finished for a prolonged period.
 #[derive(Clone)]
 pub struct HealthCheck {
     timeout: Shared<Mutex<Duration>>,
@@ -137,16 +134,11 @@ impl HealthCheck {
     /// Sends ping signals to all currently running worker threads of the
     /// server.
     ///
-    /// In response, the threads should update their health check timestamps.
-    pub fn ping(&self) {
-        for shard in self.tasks.as_ref().shards() {
-            let shard_read_guard = shard.read().unwrap_or_else(|poison| poison.into_inner());
-
-            for health in shard_read_guard.deref().values() {
-                let _ = health.sender.send(TaskThreadCommand::Ping);
-            }
-        }
-    }
+    /// The worker threads of the shared [pool](crate::server::PoolStats)
+    /// timestamp every job when they start executing it, so this function
+    /// does nothing. It is preserved for backward compatibility.
+    #[inline(always)]
+    pub fn ping(&self) {}
 
     /// Manually checks if the worker thread timestamps are up to date
     /// within the current [timeout](Self::timeout) duration.
@@ -185,399 +177,632 @@ impl HealthCheck {
     }
 }
 
-pub(super) enum LocalOrRemote<T: Task> {
-    Local(T),
-    Remote(TaskHandle),
+/// A snapshot of the shared thread pool statistics.
+///
+/// The language server executes the background jobs of all opened script
+/// modules on a single pool of worker threads. The size of the pool is
+/// configured by the `analysis_threads` option of the
+/// [LspServerConfig](crate::server::LspServerConfig).
+///
+/// You can obtain the current statistics using the
+/// [LspServer::pool_stats](crate::server::LspServer::pool_stats) function.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[non_exhaustive]
+pub struct PoolStats {
+    /// The number of worker threads in the pool.
+    pub threads: usize,
+
+    /// The number of worker threads that are currently executing jobs.
+    pub active: usize,
+
+    /// The number of jobs waiting for a free worker thread, including the
+    /// jobs postponed until the end of their cool-down period.
+    pub queue_depth: usize,
+
+    /// The total number of jobs completed since the pool creation.
+    pub completed: u64,
+
+    /// The number of jobs completed per second, measured over the last
+    /// second.
+    pub tasks_per_second: f64,
 }
 
-impl<T: Task> LocalOrRemote<T> {
-    pub(super) fn new(
-        name: impl AsRef<str>,
-        multi_thread: bool,
-        health_check: &Option<HealthCheck>,
-        config: T::Config,
-    ) -> Self {
-        if !multi_thread {
-            return Self::Local(T::init(config));
+// Owns the worker threads of the pool. Dropping this object waits for the
+// workers to complete all scheduled jobs.
+pub(super) struct TaskPool {
+    scheduler: TaskScheduler,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Drop for TaskPool {
+    fn drop(&mut self) {
+        {
+            let mut state = self.scheduler.lock();
+
+            state.finished = true;
         }
 
-        match T::spawn(name, health_check.clone(), config) {
-            Ok(handle) => Self::Remote(handle),
-            Err(local) => Self::Local(local),
+        self.scheduler.inner.condvar.notify_all();
+
+        for worker in take(&mut self.workers) {
+            let name = String::from(worker.thread().name().unwrap_or(""));
+
+            if worker.join().is_err() {
+                error!(target: LSP_CLIENT_LOG, "{name} Thread release failure.");
+            }
         }
+
+        // Breaks the reference cycles between the pool and the jobs that
+        // have not been executed.
+        let mut state = self.scheduler.lock();
+
+        state.groups.clear();
+        state.ready.clear();
+        state.delayed.clear();
+
+        trace!(target: LSP_CLIENT_LOG, "Task pool released.");
     }
+}
 
-    pub(super) fn send(&mut self, message: T::Message) {
-        match self {
-            Self::Local(task) => {
-                let _ = task.handle(message);
-            }
+impl TaskPool {
+    // Returns None if the pool failed to spawn any worker thread.
+    pub(super) fn new(threads: usize, health_check: Option<HealthCheck>) -> Option<Self> {
+        let threads = match NonZeroUsize::new(threads) {
+            Some(threads) => threads.get(),
+            None => available_parallelism().map(NonZeroUsize::get).unwrap_or(1),
+        };
+
+        let scheduler = TaskScheduler {
+            inner: Arc::new(PoolInner {
+                state: Mutex::new(PoolState::default()),
+                condvar: Condvar::new(),
+                health_check,
+            }),
+        };
+
+        let mut workers = Vec::with_capacity(threads);
+
+        for index in 0..threads {
+            let name = format!("Worker #{index}");
+
+            let result = {
+                let scheduler = scheduler.clone();
+
+                Builder::new()
+                    .name(name.clone())
+                    .spawn(move || scheduler.run_worker())
+            };
 
-            Self::Remote(task) => {
-                if task
-                    .sender()
-                    .send(TaskThreadCommand::Handle(Box::new(message)))
-                    .is_err()
-                {
-                    error!(target: LSP_CLIENT_LOG, "Task channel closed.");
+            match result {
+                Ok(handle) => workers.push(handle),
+
+                Err(error) => {
+                    trace!(target: LSP_CLIENT_LOG, "{name} Thread creation failure. {error}");
+                    break;
                 }
             }
         }
+
+        if workers.is_empty() {
+            return None;
+        }
+
+        scheduler.lock().threads = workers.len();
+
+        trace!(target: LSP_CLIENT_LOG, "Task pool initialized with {} threads.", workers.len());
+
+        Some(Self { scheduler, workers })
+    }
+
+    #[inline(always)]
+    pub(super) fn scheduler(&self) -> &TaskScheduler {
+        &self.scheduler
+    }
+
+    #[inline(always)]
+    pub(super) fn stats(&self) -> PoolStats {
+        self.scheduler.lock().stats()
     }
 }
 
-pub(super) enum TaskExecution {
-    ExecuteEach,
-    ExecuteLatest,
+#[derive(Clone)]
+pub(super) struct TaskScheduler {
+    inner: Arc<PoolInner>,
 }
 
-pub(super) trait Task: Sized {
-    const EXECUTION: TaskExecution;
+impl TaskScheduler {
+    fn schedule(&self, job: Arc<dyn Job>, ready_at: Option<Instant>) {
+        {
+            let mut state = self.lock();
 
-    type Config: Sized + Send + 'static;
+            match ready_at {
+                Some(ready_at) if ready_at > Instant::now() => state.delayed.push((ready_at, job)),
+                _ => state.push(job),
+            }
+        }
 
-    type Message: Sized + Send + 'static;
+        self.inner.condvar.notify_one();
+    }
 
-    fn init(config: Self::Config) -> Self;
+    fn run_worker(self) {
+        let mut state = self.lock();
 
-    fn handle(&mut self, message: Self::Message) -> bool;
+        loop {
+            if let Some(job) = state.pop() {
+                state.active += 1;
 
-    fn module(&self) -> &LspModule;
+                drop(state);
 
-    fn spawn(
-        name: impl AsRef<str>,
-        health_check: Option<HealthCheck>,
-        config: Self::Config,
-    ) -> Result<TaskHandle, Self> {
-        let name = name.as_ref();
-        let (init_sender, init_receiver) = sync_channel(1);
-        let (commends_sender, commands_receiver) = channel();
-
-        let result = {
-            let name = String::from(name);
-            let commends_sender = commends_sender.clone();
-
-            Builder::new().name(name.clone()).spawn(move || {
-                let initializer = TaskThreadInitializer::<Self> {
-                    name,
-                    init_receiver,
-                    commends_sender,
-                    commands_receiver,
-                };
-
-                let Some(handler) = initializer.init() else {
-                    return;
-                };
-
-                handler.run();
-            })
-        };
+                job.run(self.inner.health_check.as_ref());
 
-        match result {
-            Ok(handle) => {
-                if let Err(SendError((_, config))) = init_sender.send((health_check, config)) {
-                    error!(target: LSP_CLIENT_LOG, "{name} Thread config channel disconnected.");
-                    return Err(Self::init(config));
-                }
+                state = self.lock();
 
-                drop(init_sender);
+                state.active -= 1;
+                state.complete();
 
-                Ok(TaskHandle {
-                    inner: Some((commends_sender, handle)),
-                })
+                continue;
             }
 
-            Err(error) => {
-                trace!(target: LSP_CLIENT_LOG, "{name} Thread creation failure. {error}");
-                Err(Self::init(config))
+            if state.finished && state.delayed.is_empty() {
+                break;
             }
+
+            let next = state.delayed.iter().map(|(ready_at, _)| *ready_at).min();
+
+            state = match next {
+                Some(ready_at) => {
+                    let timeout = ready_at.saturating_duration_since(Instant::now());
+
+                    match self.inner.condvar.wait_timeout(state, timeout) {
+                        Ok((guard, _)) => guard,
+                        Err(poison) => poison.into_inner().0,
+                    }
+                }
+
+                None => self
+                    .inner
+                    .condvar
+                    .wait(state)
+                    .unwrap_or_else(|poison| poison.into_inner()),
+            };
         }
+
+        trace!(target: LSP_CLIENT_LOG, "{} Thread finished.", current().name().unwrap_or(""));
+    }
+
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<PoolState> {
+        self.inner
+            .state
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
     }
 }
 
-pub(super) struct TaskHandle {
-    inner: Option<(Sender<TaskThreadCommand>, JoinHandle<()>)>,
+struct PoolInner {
+    state: Mutex<PoolState>,
+    condvar: Condvar,
+    health_check: Option<HealthCheck>,
 }
 
-impl Drop for TaskHandle {
-    fn drop(&mut self) {
-        let Some((sender, handle)) = take(&mut self.inner) else {
-            return;
-        };
+// The ready jobs are grouped by the script modules. The workers pick the jobs
+// from the groups in a round-robin fashion, such that the jobs of a module
+// with a long queue do not delay the jobs of other modules.
+struct PoolState {
+    groups: VecDeque<TaskGroup>,
+    ready: AHashMap<TaskGroup, VecDeque<Arc<dyn Job>>>,
+    delayed: Vec<(Instant, Arc<dyn Job>)>,
+    finished: bool,
+    threads: usize,
+    active: usize,
+    completed: u64,
+    window_start: Instant,
+    window_completed: u64,
+    tasks_per_second: f64,
+}
 
-        let _ = sender.send(TaskThreadCommand::Finish);
+impl Default for PoolState {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            groups: VecDeque::new(),
+            ready: AHashMap::new(),
+            delayed: Vec::new(),
+            finished: false,
+            threads: 0,
+            active: 0,
+            completed: 0,
+            window_start: Instant::now(),
+            window_completed: 0,
+            tasks_per_second: 0.0,
+        }
+    }
+}
 
-        drop(sender);
+impl PoolState {
+    fn push(&mut self, job: Arc<dyn Job>) {
+        let group = job.group();
 
-        let name = String::from(handle.thread().name().unwrap_or(""));
+        let queue = self.ready.entry(group).or_default();
+
+        if queue.is_empty() {
+            self.groups.push_back(group);
+        }
+
+        queue.push_back(job);
+    }
 
-        trace!(target: LSP_CLIENT_LOG, "{name} Thread join...");
+    fn pop(&mut self) -> Option<Arc<dyn Job>> {
+        let now = Instant::now();
+        let mut index = 0;
 
-        match handle.join() {
-            Ok(()) => {
-                trace!(target: LSP_CLIENT_LOG, "{name} Thread released.");
+        while index < self.delayed.len() {
+            if self.delayed[index].0 > now {
+                index += 1;
+                continue;
             }
 
-            Err(_) => {
-                error!(target: LSP_CLIENT_LOG, "{name} Thread release failure.");
+            let (_, job) = self.delayed.swap_remove(index);
+
+            self.push(job);
+        }
+
+        let group = self.groups.pop_front()?;
+
+        let Some(queue) = self.ready.get_mut(&group) else {
+            // Each enqueued group has a non-empty queue.
+            unsafe { debug_unreachable!("Missing group queue.") }
+        };
+
+        let job = queue.pop_front();
+
+        match queue.is_empty() {
+            true => {
+                let _ = self.ready.remove(&group);
             }
+
+            false => self.groups.push_back(group),
         }
+
+        job
     }
-}
 
-impl TaskHandle {
-    #[inline(always)]
-    pub(super) fn sender(&self) -> &Sender<TaskThreadCommand> {
-        let Some((sender, _)) = &self.inner else {
-            // Inner state is always available when the instance is externally accessed.
-            unsafe { debug_unreachable!("Missing inner state.") }
-        };
+    fn complete(&mut self) {
+        self.completed += 1;
+        self.window_completed += 1;
+
+        let elapsed = self.window_start.elapsed();
 
-        sender
+        if elapsed < STATS_WINDOW {
+            return;
+        }
+
+        self.tasks_per_second = self.window_completed as f64 / elapsed.as_secs_f64();
+        self.window_start = Instant::now();
+        self.window_completed = 0;
     }
 
-    #[inline(always)]
-    #[allow(unused)]
-    pub(super) fn detach(mut self) -> Sender<TaskThreadCommand> {
-        let Some((sender, _)) = take(&mut self.inner) else {
-            // Inner state is always available when the instance is externally accessed.
-            unsafe { debug_unreachable!("Missing inner state.") }
+    fn stats(&self) -> PoolStats {
+        let elapsed = self.window_start.elapsed();
+
+        let tasks_per_second = match elapsed < STATS_WINDOW {
+            true => self.tasks_per_second,
+            false => self.window_completed as f64 / elapsed.as_secs_f64(),
         };
 
-        sender
+        PoolStats {
+            threads: self.threads,
+            active: self.active,
+            queue_depth: self.ready.values().map(VecDeque::len).sum::<usize>() + self.delayed.len(),
+            completed: self.completed,
+            tasks_per_second,
+        }
     }
 }
 
-pub(super) enum TaskThreadCommand {
-    Handle(Box<dyn Any + Send>),
-    Ping,
-    Continue,
-    Finish,
+// A key of the job fairness group. All tasks of the same script module
+// share the same group.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct TaskGroup(u64);
+
+impl TaskGroup {
+    #[inline(always)]
+    pub(super) fn new(key: impl Hash) -> Self {
+        let mut hasher = DefaultHasher::new();
+
+        key.hash(&mut hasher);
+
+        Self(hasher.finish())
+    }
 }
 
-struct Health {
-    name: String,
-    pong: Instant,
-    sender: Sender<TaskThreadCommand>,
-    module: LspModule,
+trait Job: Send + Sync + 'static {
+    fn group(&self) -> TaskGroup;
+
+    fn run(self: Arc<Self>, health_check: Option<&HealthCheck>);
 }
 
-struct TaskThreadInitializer<T: Task> {
-    name: String,
-    init_receiver: Receiver<(Option<HealthCheck>, T::Config)>,
-    commends_sender: Sender<TaskThreadCommand>,
-    commands_receiver: Receiver<TaskThreadCommand>,
+pub(super) enum LocalOrRemote<T: Task> {
+    Local(T),
+    Remote(TaskHandle<T>),
 }
 
-impl<T: Task> TaskThreadInitializer<T> {
-    fn init(self) -> Option<TaskThreadExecutor<T>> {
-        let Ok((health_check, config)) = self.init_receiver.recv() else {
-            error!(target: LSP_CLIENT_LOG, "{} Thread was not initialized.", self.name);
-            return None;
+impl<T: Task> LocalOrRemote<T> {
+    // If the `scheduler` is None, the task handles the messages on the
+    // sender's thread.
+    pub(super) fn new(
+        name: impl AsRef<str>,
+        group: TaskGroup,
+        scheduler: &Option<TaskScheduler>,
+        config: T::Config,
+    ) -> Self {
+        let Some(scheduler) = scheduler else {
+            return Self::Local(T::init(config));
         };
 
-        drop(self.init_receiver);
-
-        let task = T::init(config);
+        let actor = Arc::new(Actor {
+            name: String::from(name.as_ref()),
+            group,
+            scheduler: scheduler.clone(),
+            state: Mutex::new(ActorState {
+                task: TaskState::Uninit(config),
+                mailbox: VecDeque::new(),
+                scheduled: true,
+                closed: false,
+                last_message: Instant::now(),
+                stats: VecDeque::with_capacity(STATS),
+            }),
+        });
+
+        // The first job initializes the task.
+        scheduler.schedule(actor.clone(), None);
+
+        Self::Remote(TaskHandle { actor })
+    }
 
-        let thread_id = current().id();
+    pub(super) fn send(&mut self, message: T::Message) {
+        match self {
+            Self::Local(task) => {
+                let _ = task.handle(message);
+            }
 
-        if let Some(health_check) = &health_check {
-            let _ = health_check.tasks.as_ref().insert(
-                thread_id,
-                Health {
-                    name: self.name.clone(),
-                    pong: Instant::now(),
-                    sender: self.commends_sender.clone(),
-                    module: task.module().clone(),
-                },
-            );
+            Self::Remote(task) => task.send(message),
         }
+    }
+}
 
-        let handler = TaskThreadExecutor {
-            name: self.name.clone(),
-            thread_id,
-            health_check,
-            commands_receiver: self.commands_receiver,
-            task,
-            stats: VecDeque::with_capacity(STATS),
-        };
+pub(super) enum TaskExecution {
+    ExecuteEach,
+    ExecuteLatest,
+}
+
+pub(super) trait Task: Send + Sized + 'static {
+    const EXECUTION: TaskExecution;
 
-        trace!(target: LSP_CLIENT_LOG, "{} Thread initialized.", self.name);
+    const COOL_DOWN: Duration = COOL_DOWN;
 
-        Some(handler)
-    }
+    type Config: Sized + Send + 'static;
+
+    type Message: Sized + Send + 'static;
+
+    fn init(config: Self::Config) -> Self;
+
+    fn handle(&mut self, message: Self::Message) -> bool;
+
+    fn module(&self) -> &LspModule;
 }
 
-struct TaskThreadExecutor<T: Task> {
-    name: String,
-    thread_id: ThreadId,
-    health_check: Option<HealthCheck>,
-    commands_receiver: Receiver<TaskThreadCommand>,
-    task: T,
-    stats: VecDeque<Duration>,
+// A handle of the task scheduled onto the pool. Dropping the handle lets the
+// task handle the remaining messages, and then releases the task.
+pub(super) struct TaskHandle<T: Task> {
+    actor: Arc<Actor<T>>,
 }
 
-impl<T: Task> TaskThreadExecutor<T> {
-    #[inline(always)]
-    fn run(mut self) {
-        match T::EXECUTION {
-            TaskExecution::ExecuteEach => self.run_each(),
-            TaskExecution::ExecuteLatest => self.run_latest(),
-        }
+impl<T: Task> Drop for TaskHandle<T> {
+    fn drop(&mut self) {
+        let task = {
+            let mut state = self.actor.lock();
 
-        if let Some(health_check) = &self.health_check {
-            let _ = health_check.tasks.as_ref().remove(&self.thread_id);
-        }
+            state.closed = true;
 
-        trace!(target: LSP_CLIENT_LOG, "{} Thread finished.", self.name);
-    }
+            if state.scheduled {
+                return;
+            }
 
-    fn run_each(&mut self) {
-        loop {
-            let message = match self.get_message() {
-                Ok(message) => message,
-                Err(true) => continue,
-                Err(false) => break,
-            };
+            replace(&mut state.task, TaskState::Finished)
+        };
 
-            if !self.task.handle(message) {
-                break;
-            }
-        }
-    }
+        drop(task);
 
-    fn run_latest(&mut self) {
-        loop {
-            let message = match self.get_latest_message() {
-                Ok(message) => message,
-                Err(true) => continue,
-                Err(false) => break,
-            };
+        trace!(target: LSP_CLIENT_LOG, "{} Task released.", self.actor.name);
+    }
+}
 
-            let start = Instant::now();
+impl<T: Task> TaskHandle<T> {
+    fn send(&self, message: T::Message) {
+        let ready_at = {
+            let mut state = self.actor.lock();
 
-            if !self.task.handle(message) {
-                continue;
+            if let TaskState::Finished = &state.task {
+                error!(target: LSP_CLIENT_LOG, "{} Task finished.", self.actor.name);
+                return;
             }
 
-            let end = start.elapsed();
+            state.mailbox.push_back(message);
+            state.last_message = Instant::now();
 
-            if end >= Self::mean(&self.stats) {
-                warn!(
-                    target: LSP_CLIENT_LOG,
-                    "{} Execution time: {end:?}.",
-                    self.name
-                );
+            if state.scheduled {
+                return;
             }
 
-            if self.stats.len() == STATS {
-                let _ = self.stats.pop_front();
+            state.scheduled = true;
+
+            match T::EXECUTION {
+                TaskExecution::ExecuteEach => None,
+                TaskExecution::ExecuteLatest => Some(state.last_message + T::COOL_DOWN),
             }
+        };
 
-            self.stats.push_back(end);
-        }
+        self.actor.scheduler.schedule(self.actor.clone(), ready_at);
     }
+}
 
-    fn get_message(&self) -> Result<T::Message, bool> {
-        match self.receive_command() {
-            TaskThreadCommand::Handle(message) => Ok(self.downcast(message)),
+struct Actor<T: Task> {
+    name: String,
+    group: TaskGroup,
+    scheduler: TaskScheduler,
+    state: Mutex<ActorState<T>>,
+}
 
-            TaskThreadCommand::Ping => {
-                self.pong();
-                Err(true)
-            }
+impl<T: Task> Job for Actor<T> {
+    #[inline(always)]
+    fn group(&self) -> TaskGroup {
+        self.group
+    }
 
-            TaskThreadCommand::Continue => Err(true),
+    fn run(self: Arc<Self>, health_check: Option<&HealthCheck>) {
+        let (task, message) = {
+            let mut state = self.lock();
 
-            TaskThreadCommand::Finish => Err(false),
-        }
-    }
+            let message = match T::EXECUTION {
+                TaskExecution::ExecuteEach => state.mailbox.pop_front(),
+
+                TaskExecution::ExecuteLatest => {
+                    // Postpones the execution until the sender stops sending
+                    // new messages for the cool-down period.
+                    let ready_at = state.last_message + T::COOL_DOWN;
+
+                    if !state.mailbox.is_empty() && ready_at > Instant::now() {
+                        drop(state);
+
+                        self.scheduler.schedule(self.clone(), Some(ready_at));
+
+                        return;
+                    }
+
+                    let message = state.mailbox.pop_back();
 
-    fn get_latest_message(&self) -> Result<T::Message, bool> {
-        let mut message = match self.receive_command() {
-            TaskThreadCommand::Handle(message) => message,
+                    state.mailbox.clear();
 
-            TaskThreadCommand::Ping => {
-                self.pong();
-                return Err(true);
+                    message
+                }
+            };
+
+            (replace(&mut state.task, TaskState::Running), message)
+        };
+
+        let mut task = match task {
+            TaskState::Uninit(config) => {
+                let task = T::init(config);
+
+                trace!(target: LSP_CLIENT_LOG, "{} Task initialized.", self.name);
+
+                task
             }
 
-            TaskThreadCommand::Continue => return Err(true),
+            TaskState::Ready(task) => task,
 
-            TaskThreadCommand::Finish => return Err(false),
+            TaskState::Running | TaskState::Finished => {
+                // The actor is scheduled only once at a time, and the finished
+                // actors are never scheduled.
+                unsafe { debug_unreachable!("Task is not available.") }
+            }
         };
 
-        loop {
-            park_timeout(COOL_DOWN);
+        let mut proceed = true;
 
-            let mut new = false;
+        if let Some(message) = message {
+            let thread_id = current().id();
 
-            loop {
-                message = match self.try_receive_command() {
-                    TaskThreadCommand::Handle(message) => {
-                        new = true;
-                        message
-                    }
+            if let Some(health_check) = health_check {
+                let _ = health_check.tasks.as_ref().insert(
+                    thread_id,
+                    Health {
+                        name: self.name.clone(),
+                        pong: Instant::now(),
+                        module: task.module().clone(),
+                    },
+                );
+            }
 
-                    TaskThreadCommand::Ping => {
-                        self.pong();
-                        continue;
-                    }
+            let start = Instant::now();
 
-                    TaskThreadCommand::Continue => break,
+            let handled = task.handle(message);
+
+            let end = start.elapsed();
 
-                    TaskThreadCommand::Finish => return Err(false),
-                };
+            if let Some(health_check) = health_check {
+                let _ = health_check.tasks.as_ref().remove(&thread_id);
             }
 
-            if !new {
-                break;
+            match T::EXECUTION {
+                TaskExecution::ExecuteEach => proceed = handled,
+
+                TaskExecution::ExecuteLatest => {
+                    if handled {
+                        self.measure(end);
+                    }
+                }
             }
         }
 
-        Ok(self.downcast(message))
-    }
+        let mut state = self.lock();
 
-    #[inline(always)]
-    fn receive_command(&self) -> TaskThreadCommand {
-        let Ok(task_thread_message) = self.commands_receiver.recv() else {
-            return TaskThreadCommand::Finish;
-        };
+        if !proceed || (state.closed && state.mailbox.is_empty()) {
+            state.task = TaskState::Finished;
+            state.mailbox.clear();
+            state.scheduled = false;
 
-        task_thread_message
-    }
+            drop(state);
+            drop(task);
 
-    #[inline(always)]
-    fn try_receive_command(&self) -> TaskThreadCommand {
-        match self.commands_receiver.try_recv() {
-            Ok(task_thread_message) => task_thread_message,
-            Err(TryRecvError::Empty) => TaskThreadCommand::Continue,
-            Err(TryRecvError::Disconnected) => TaskThreadCommand::Finish,
-        }
-    }
+            trace!(target: LSP_CLIENT_LOG, "{} Task released.", self.name);
 
-    #[inline(always)]
-    fn pong(&self) {
-        let Some(health_check) = &self.health_check else {
             return;
-        };
+        }
+
+        state.task = TaskState::Ready(task);
 
-        let Some(mut health) = health_check.tasks.as_ref().get_mut(&self.thread_id) else {
-            error!(target: LSP_SERVER_LOG, "{} Missing health-check record.", self.name);
+        if state.mailbox.is_empty() {
+            state.scheduled = false;
             return;
+        }
+
+        let ready_at = match T::EXECUTION {
+            TaskExecution::ExecuteEach => None,
+            TaskExecution::ExecuteLatest => Some(state.last_message + T::COOL_DOWN),
         };
 
-        health.pong = Instant::now();
+        drop(state);
+
+        self.scheduler.schedule(self.clone(), ready_at);
     }
+}
 
-    #[inline(always)]
-    fn downcast(&self, message: Box<dyn Any + Send>) -> T::Message {
-        let Ok(boxed) = message.downcast() else {
-            system_panic!("{} Invalid thread message type.", self.name,);
-        };
+impl<T: Task> Actor<T> {
+    fn measure(&self, end: Duration) {
+        let mut state = self.lock();
 
-        *boxed
+        if end >= Self::mean(&state.stats) {
+            warn!(
+                target: LSP_CLIENT_LOG,
+                "{} Execution time: {end:?}.",
+                self.name
+            );
+        }
+
+        if state.stats.len() == STATS {
+            let _ = state.stats.pop_front();
+        }
+
+        state.stats.push_back(end);
+    }
+
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<ActorState<T>> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
     }
 
     #[inline(always)]
@@ -591,3 +816,25 @@ impl<T: Task> TaskThreadExecutor<T> {
         delay
     }
 }
+
+struct ActorState<T: Task> {
+    task: TaskState<T>,
+    mailbox: VecDeque<T::Message>,
+    scheduled: bool,
+    closed: bool,
+    last_message: Instant,
+    stats: VecDeque<Duration>,
+}
+
+enum TaskState<T: Task> {
+    Uninit(T::Config),
+    Ready(T),
+    Running,
+    Finished,
+}
+
+struct Health {
+    name: String,
+    pong: Instant,
+    module: LspModule,
+}
@@ -0,0 +1,219 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::{str::FromStr, thread::park_timeout};
+
+use lady_deirdre::{lexis::ToSpan, sync::Trigger};
+use log::{error, warn};
+use lsp_types::{
+    error_codes::{REQUEST_CANCELLED, REQUEST_FAILED},
+    request::{GotoTypeDefinition, GotoTypeDefinitionResponse},
+    Location,
+    Position,
+    Range,
+    Uri,
+};
+
+use crate::{
+    analysis::{DefinitionTarget, ModuleError, ModuleRead},
+    runtime::RustOrigin,
+    server::{
+        file::{LspModule, ANALYSIS_PRIORITY},
+        logger::LSP_SERVER_LOG,
+        rpc::{LspHandle, OutgoingEx, RpcId, RpcLatches},
+        tasks::{Task, TaskExecution, COOL_DOWN},
+        utils::{lsp_position_to_ld, span_to_range},
+        RpcSender,
+    },
+};
+
+pub(super) struct SendGotoTypeDefinition {
+    pub(super) latches: RpcLatches,
+    pub(super) outgoing: RpcSender,
+    pub(super) module: LspModule,
+    pub(super) rust_sources: Option<fn(&'static RustOrigin) -> Option<Location>>,
+}
+
+impl Task for SendGotoTypeDefinition {
+    const EXECUTION: TaskExecution = TaskExecution::ExecuteEach;
+
+    type Config = Self;
+
+    type Message = SendGotoTypeDefinitionMessage;
+
+    #[inline(always)]
+    fn init(config: Self::Config) -> Self {
+        config
+    }
+
+    fn handle(&mut self, message: Self::Message) -> bool {
+        loop {
+            if message.cancel.is_active() {
+                warn!(target: LSP_SERVER_LOG, "[{}] Send goto type definition cancelled by the client.", message.uri.as_str());
+
+                self.outgoing.send_err_response(
+                    &self.latches,
+                    message.id,
+                    REQUEST_CANCELLED,
+                    "Send goto type definition cancelled by the client.",
+                );
+
+                break;
+            }
+
+            let handle = LspHandle::new(&message.cancel);
+
+            let module_read_guard = match self.module.as_ref().read(&handle, ANALYSIS_PRIORITY) {
+                Ok(guard) => guard,
+
+                Err(ModuleError::Interrupted(_)) => {
+                    if message.cancel.is_active() {
+                        warn!(target: LSP_SERVER_LOG, "[{}] Send goto type definition cancelled by the client.", message.uri.as_str());
+
+                        self.outgoing.send_err_response(
+                            &self.latches,
+                            message.id,
+                            REQUEST_CANCELLED,
+                            "Send goto type definition cancelled by the client.",
+                        );
+
+                        break;
+                    }
+
+                    warn!(target: LSP_SERVER_LOG, "[{}] Send goto type definition interrupted.", message.uri.as_str());
+                    park_timeout(COOL_DOWN);
+                    continue;
+                }
+
+                Err(error) => {
+                    error!(target: LSP_SERVER_LOG, "[{}] Send goto type definition error. {error}", message.uri.as_str());
+
+                    self.outgoing.send_err_response(
+                        &self.latches,
+                        message.id,
+                        REQUEST_FAILED,
+                        "Send goto type definition error.",
+                    );
+
+                    break;
+                }
+            };
+
+            let position = lsp_position_to_ld(&message.position);
+
+            let target = match module_read_guard.type_definition(position) {
+                Ok(target) => target,
+
+                Err(ModuleError::Interrupted(_)) => {
+                    if message.cancel.is_active() {
+                        warn!(target: LSP_SERVER_LOG, "[{}] Send goto type definition cancelled by the client.", message.uri.as_str());
+
+                        self.outgoing.send_err_response(
+                            &self.latches,
+                            message.id,
+                            REQUEST_CANCELLED,
+                            "Send goto type definition cancelled by the client.",
+                        );
+
+                        break;
+                    }
+
+                    warn!(target: LSP_SERVER_LOG, "[{}] Send goto type definition interrupted.", message.uri.as_str());
+                    park_timeout(COOL_DOWN);
+                    continue;
+                }
+
+                Err(error) => {
+                    error!(target: LSP_SERVER_LOG, "[{}] Send goto type definition error. {error}", message.uri.as_str());
+
+                    self.outgoing.send_err_response(
+                        &self.latches,
+                        message.id,
+                        REQUEST_FAILED,
+                        "Send goto type definition error.",
+                    );
+
+                    break;
+                }
+            };
+
+            let location = match target {
+                Some(DefinitionTarget::Script(origin)) => {
+                    let text = module_read_guard.text();
+
+                    origin.to_position_span(&text).map(|span| Location {
+                        uri: message.uri.clone(),
+                        range: span_to_range(&span),
+                    })
+                }
+
+                Some(target @ DefinitionTarget::Rust(origin)) => match self.rust_sources {
+                    Some(rust_sources) => rust_sources(origin),
+
+                    None => target
+                        .docs_url()
+                        .and_then(|url| Uri::from_str(&url).ok())
+                        .map(|uri| Location {
+                            uri,
+                            range: Range::default(),
+                        }),
+                },
+
+                None => None,
+            };
+
+            self.outgoing.send_ok_response::<GotoTypeDefinition>(
+                &self.latches,
+                message.id,
+                location.map(GotoTypeDefinitionResponse::Scalar),
+            );
+
+            break;
+        }
+
+        true
+    }
+
+    #[inline(always)]
+    fn module(&self) -> &LspModule {
+        &self.module
+    }
+}
+
+pub(super) struct SendGotoTypeDefinitionMessage {
+    pub(super) id: RpcId,
+    pub(super) uri: Uri,
+    pub(super) cancel: Trigger,
+    pub(super) position: Position,
+}
@@ -39,9 +39,9 @@ use lady_deirdre::{
 use lsp_types::{MarkupContent, MarkupKind, Position, Range, Uri};
 
 use crate::{
-    analysis::{symbols::SymbolKind, Description, ModuleRead, ModuleText},
+    analysis::{symbols::SymbolKind, Description, DescriptionConfig, ModuleRead, ModuleText},
     format::ScriptSnippetConfig,
-    runtime::{ScriptOrigin, TypeHint},
+    runtime::ScriptOrigin,
     server::rpc::LspHandle,
 };
 
@@ -87,7 +87,7 @@ pub(super) fn make_doc(
     read: &impl ModuleRead<LspHandle>,
     text: &ModuleText,
     markdown: bool,
-    language_id: &str,
+    language_id: &'static str,
     fallback_to_type: bool,
     desc: &Description,
 ) -> Option<MarkupContent> {
@@ -115,20 +115,44 @@ pub(super) fn make_doc(
             }
         }
 
-        false => match desc.doc {
-            Some(doc) if !doc.is_empty() => match desc.type_hint {
-                TypeHint::Invocation(meta) if fallback_to_type => {
-                    format!("{meta}\n\n{doc}")
+        false if fallback_to_type => {
+            if desc.type_hint.is_dynamic() && desc.doc.unwrap_or_default().is_empty() {
+                return None;
+            }
+
+            let mut config = DescriptionConfig::detailed();
+
+            config.markdown = markdown;
+            config.language_id = language_id;
+
+            desc.render(&config)
+        }
+
+        false => match (desc.doc, desc.deprecated) {
+            (Some(doc), None) if !doc.is_empty() => String::from(doc),
+
+            (doc, Some(message)) => {
+                let mut value = match markdown {
+                    false => String::from("deprecated"),
+                    true => String::from("**Deprecated**"),
+                };
+
+                if !message.is_empty() {
+                    value.push_str(": ");
+                    value.push_str(message);
                 }
 
-                _ => String::from(doc),
-            },
+                if let Some(doc) = doc {
+                    if !doc.is_empty() {
+                        value.push_str("\n\n");
+                        value.push_str(doc);
+                    }
+                }
 
-            _ => match fallback_to_type && !desc.type_hint.is_dynamic() {
-                true => desc.type_hint.to_string(),
+                value
+            }
 
-                _ => return None,
-            },
+            _ => return None,
         },
     };
 
@@ -32,11 +32,11 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::{marker::PhantomData, mem::take};
+use std::{cell::Cell, marker::PhantomData, mem::take};
 
 use lady_deirdre::{
     analysis::{Feature, Semantics},
-    lexis::{SiteRef, TokenRef, TokenSet},
+    lexis::{SiteRef, TokenRef, TokenSet, EMPTY_TOKEN_SET},
     syntax::{
         NodeRef,
         NodeRule,
@@ -110,6 +110,7 @@ const OUTER_TERMINALS: TokenSet = {
         Continue as u8,
         Return as u8,
         Use as u8,
+        Crate as u8,
         Comma as u8,
         Arrow as u8,
         BraceOpen as u8,
@@ -141,6 +142,66 @@ static RECOVERY_RIGHT: Recovery = {
         .group(ParenOpen as u8, ParenClose as u8)
 };
 
+// The maximum nesting of the expressions the parser descends into. Each
+// nested expression and each operator applied to the expression count as one
+// level, and each nested syntax rule that parses an expression (e.g., an array
+// item or a function body) counts as several more levels, because such
+// descents consume considerably more stack space. The parser skips the deeper
+// expressions as syntax errors, such that neither the parser nor
+// the analyzer of the pathologically nested code exhausts the thread's stack.
+const MAX_NESTING: usize = 128;
+
+// The nesting cost of the syntax rule that parses an expression.
+const RULE_NESTING: usize = 7;
+
+thread_local! {
+    // The current nesting level of the expressions that are being parsed on
+    // this thread, across all nested expression parsers.
+    static NESTING: Cell<usize> = const { Cell::new(0) };
+}
+
+struct Nesting(usize);
+
+impl Drop for Nesting {
+    #[inline(always)]
+    fn drop(&mut self) {
+        NESTING.with(|nesting| nesting.set(nesting.get() - self.0));
+    }
+}
+
+impl Nesting {
+    #[inline(always)]
+    fn enter(cost: usize) -> Option<Self> {
+        NESTING.with(|nesting| {
+            let current = nesting.get();
+
+            if current + cost > MAX_NESTING {
+                return None;
+            }
+
+            nesting.set(current + cost);
+
+            Some(Self(cost))
+        })
+    }
+
+    #[inline(always)]
+    fn extend(&mut self) -> bool {
+        NESTING.with(|nesting| {
+            let current = nesting.get();
+
+            if current >= MAX_NESTING {
+                return false;
+            }
+
+            nesting.set(current + 1);
+            self.0 += 1;
+
+            true
+        })
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(u8)]
 pub(crate) enum Precedence {
@@ -171,7 +232,7 @@ impl Precedence {
     }
 
     #[inline(always)]
-    pub(crate) fn assoc(self) -> Assoc {
+    pub(crate) const fn assoc(self) -> Assoc {
         match self {
             Precedence::Assign | Precedence::UnaryLeft => Assoc::Right,
             _ => Assoc::Left,
@@ -251,9 +312,15 @@ impl Operand {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub(crate) enum Assoc {
+/// An associativity of the operator.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Assoc {
+    /// The operator groups from left to right: `a - b - c` means
+    /// `(a - b) - c`.
     Left,
+
+    /// The operator groups from right to left: `a = b = c` means
+    /// `a = (b = c)`.
     Right,
 }
 
@@ -265,6 +332,7 @@ impl ScriptNode {
             | Self::Struct { .. }
             | Self::Array { .. }
             | Self::String { .. }
+            | Self::Embedded { .. }
             | Self::Crate { .. }
             | Self::This { .. }
             | Self::Ident { .. }
@@ -294,7 +362,7 @@ impl ScriptNode {
 }
 
 impl ScriptToken {
-    pub(crate) fn bin_precedence(&self) -> Precedence {
+    pub(crate) const fn bin_precedence(&self) -> Precedence {
         match self {
             Self::Assign
             | Self::PlusAssign
@@ -362,7 +430,10 @@ where
             _code: PhantomData,
         };
 
-        let inner = parser.parse_expr(ScriptNode::EXPR, Precedence::Outer);
+        let inner = match Nesting::enter(RULE_NESTING) {
+            Some(_nesting) => parser.parse_expr(ScriptNode::EXPR, Precedence::Outer),
+            None => parser.skip_nested(ScriptNode::EXPR),
+        };
 
         ScriptNode::Expr {
             node,
@@ -374,7 +445,64 @@ where
         }
     }
 
+    // Parses an invocation argument, which is either an expression or a named
+    // argument `<name>: <expr>`. The parser looks ahead for the colon after
+    // the identifier to distinguish between these two forms.
+    pub(super) fn parse_arg(session: &'session mut S) -> ScriptNode {
+        let node = session.node_ref();
+        let parent = session.parent_ref();
+        let step_start_ref = session.site_ref(0);
+
+        let mut parser = Self {
+            session,
+            step_start_ref,
+            _code: PhantomData,
+        };
+
+        let mut name = TokenRef::nil();
+
+        if parser.is_named_arg() {
+            name = parser.read_token();
+
+            parser.skip_trivia();
+
+            let _ = parser.session.advance();
+
+            parser.skip_trivia();
+        }
+
+        let value = parser.session.descend(ScriptNode::EXPR);
+
+        ScriptNode::Arg {
+            node,
+            parent,
+            name,
+            value,
+            semantics: Semantics::new(node),
+        }
+    }
+
+    fn is_named_arg(&mut self) -> bool {
+        if self.session.token(0) != ScriptToken::Ident {
+            return false;
+        }
+
+        let mut distance = 1;
+
+        loop {
+            match self.session.token(distance) {
+                ScriptToken::Whitespace | ScriptToken::Linebreak => distance += 1,
+                ScriptToken::Colon => return true,
+                _ => return false,
+            }
+        }
+    }
+
     fn parse_expr(&mut self, context: NodeRule, parent_op: Precedence) -> NodeRef {
+        let Some(mut nesting) = Nesting::enter(1) else {
+            return self.skip_nested(context);
+        };
+
         let assoc = match parent_op == Precedence::Outer {
             true => Assoc::Left,
             false => Assoc::Right,
@@ -387,6 +515,14 @@ where
 
             let token = self.session.token(0);
 
+            if token != ScriptToken::EOI
+                && !OUTER_TERMINALS.contains(token as u8)
+                && !nesting.extend()
+            {
+                let _ = self.skip_nested(context);
+                break;
+            }
+
             match token {
                 ScriptToken::Query => {
                     if !self.reduce_query(&mut accumulator, parent_op) {
@@ -639,6 +775,7 @@ where
             ScriptNode::MAX,
             ScriptNode::BOOL,
             ScriptNode::STRING,
+            ScriptNode::EMBEDDED,
             ScriptNode::FN,
             ScriptNode::STRUCT,
             ScriptNode::ARRAY,
@@ -654,6 +791,7 @@ where
             ScriptToken::False as u8,
             ScriptToken::Max as u8,
             ScriptToken::DoubleQuote as u8,
+            ScriptToken::Fence as u8,
             ScriptToken::Fn as u8,
             ScriptToken::Struct as u8,
             ScriptToken::BracketOpen as u8,
@@ -695,6 +833,10 @@ where
                     return self.parse_primary(ScriptNode::STRING);
                 }
 
+                ScriptToken::Fence => {
+                    return self.parse_primary(ScriptNode::EMBEDDED);
+                }
+
                 ScriptToken::Fn => {
                     return self.parse_primary(ScriptNode::FN);
                 }
@@ -947,6 +1089,54 @@ where
         recovery.recovered()
     }
 
+    // Skips the expression that is nested too deeply, including all of its
+    // bracketed groups, up to the end of the enclosing group, list item, or
+    // statement. The skipped fragment is reported as a syntax error without
+    // expectations, which distinguishes this error from the others.
+    fn skip_nested(&mut self, context: NodeRule) -> NodeRef {
+        self.step_start_ref = self.session.site_ref(0);
+
+        let mut depth = 0usize;
+
+        let recovery = loop {
+            match self.session.token(0) {
+                ScriptToken::EOI => break RecoveryResult::UnexpectedEOI,
+
+                ScriptToken::ParenOpen | ScriptToken::BracketOpen | ScriptToken::BraceOpen => {
+                    depth += 1;
+                }
+
+                ScriptToken::ParenClose | ScriptToken::BracketClose | ScriptToken::BraceClose => {
+                    if depth == 0 {
+                        break RecoveryResult::UnexpectedToken;
+                    }
+
+                    depth -= 1;
+                }
+
+                ScriptToken::Comma | ScriptToken::Semicolon if depth == 0 => {
+                    break RecoveryResult::UnexpectedToken;
+                }
+
+                _ => (),
+            }
+
+            let _ = self.session.advance();
+        };
+
+        let step_end_ref = self.session.site_ref(0);
+
+        let _ = self.session.failure(SyntaxError {
+            span: self.step_start_ref..step_end_ref,
+            context,
+            recovery,
+            expected_tokens: &EMPTY_TOKEN_SET,
+            expected_nodes: &EMPTY_NODE_SET,
+        });
+
+        NodeRef::nil()
+    }
+
     fn skip_trivia(&mut self) {
         self.step_start_ref = self.session.site_ref(0);
 
@@ -1030,4 +1220,37 @@ mod tests {
         let mut checker = InheritanceChecker::new(&doc);
         doc.traverse_tree(&mut checker);
     }
+
+    #[test]
+    fn test_expr_nesting_limit() {
+        static NESTING: &[(&str, &str)] = &[
+            ("(", ")"),
+            ("[", "]"),
+            ("-", ""),
+            ("a = ", ""),
+            ("1 + ", ""),
+            ("foo(", ")"),
+            ("a[", "]"),
+            ("struct { a: ", " }"),
+            ("fn() { return ", "; }"),
+        ];
+
+        for (open, close) in NESTING {
+            let doc = ScriptDoc::from(format!(
+                "let x = {}1{}; let y = 2;",
+                open.repeat(10),
+                close.repeat(10),
+            ));
+
+            assert_eq!(doc.errors().count(), 0);
+
+            let doc = ScriptDoc::from(format!(
+                "let x = {}1{}; let y = 2;",
+                open.repeat(2000),
+                close.repeat(2000),
+            ));
+
+            assert_eq!(doc.errors().count(), 1);
+        }
+    }
 }
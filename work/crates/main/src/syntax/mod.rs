@@ -36,14 +36,19 @@ mod classes;
 mod expr;
 mod node;
 mod span;
+mod table;
 mod token;
 
 pub(crate) use crate::syntax::{
     classes::ScriptClass,
-    expr::{Assoc, Precedence},
+    expr::Precedence,
     node::ScriptNode,
     span::{PolyRefOrigin, SpanBounds},
     token::ScriptToken,
 };
+pub use crate::syntax::{
+    expr::Assoc,
+    table::{operator_table, OperatorInfo, OperatorPosition},
+};
 
-pub type ScriptDoc = lady_deirdre::units::Document<ScriptNode>;
+pub(crate) type ScriptDoc = lady_deirdre::units::Document<ScriptNode>;
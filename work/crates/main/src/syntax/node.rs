@@ -32,10 +32,12 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
+use std::slice::from_ref;
+
 use lady_deirdre::{
     analysis::{Semantics, VoidFeature},
-    lexis::TokenRef,
-    syntax::{Node, NodeRef},
+    lexis::{SiteSpan, SourceCode, TokenRef},
+    syntax::{Node, NodeRef, PolyRef},
 };
 
 use crate::{
@@ -46,9 +48,10 @@ use crate::{
 #[derive(Node)]
 #[token(ScriptToken)]
 #[classifier(ScriptClassifier)]
+#[semantics(CommonSemantics)]
 #[trivia($Whitespace | $Linebreak | InlineComment | MultilineComment)]
 #[recovery(
-    $If, $Match, $Let, $For, $Loop, $Break, $Continue, $Return, $Use,
+    $If, $Match, $Let, $Enum, $For, $Loop, $Break, $Continue, $Return, $Use, $Crate,
     $BraceOpen, $BraceClose, $Semicolon,
     [$BraceOpen..$BraceClose],
     [$BracketOpen..$BracketClose],
@@ -58,6 +61,7 @@ use crate::{
     | statements: If
     | statements: Match
     | statements: Let
+    | statements: Enum
     | statements: For
     | statements: Loop
     | statements: Block
@@ -80,6 +84,7 @@ use crate::{
     | $Mul
     | $Not
     | $DoubleQuote
+    | $Fence
     | $Crate
     | $This
     | $Ident
@@ -300,7 +305,7 @@ pub enum ScriptNode {
         (handler: Expr | handler: Block)
     )]
     #[recovery(
-        $If, $Match, $Let, $For, $Loop, $Break, $Continue, $Return, $Use,
+        $If, $Match, $Let, $Enum, $For, $Loop, $Break, $Continue, $Return, $Use, $Crate,
         $BraceOpen, $BraceClose, $Semicolon,
         [$BraceOpen..$BraceClose],
         [$BracketOpen..$BracketClose],
@@ -531,13 +536,13 @@ pub enum ScriptNode {
         end: $ParenClose
     )]
     #[recovery(
-        $If, $Match, $Let, $For, $Loop, $Break, $Continue, $Return, $Use,
+        $If, $Match, $Let, $Enum, $For, $Loop, $Break, $Continue, $Return, $Use, $Crate,
         $BraceOpen, $BraceClose, $Semicolon,
         [$BraceOpen..$BraceClose],
         [$BracketOpen..$BracketClose],
         [$ParenOpen..$ParenClose],
         $Struct, $Fn, $True, $False, $ParenOpen, $BracketOpen, $Minus,
-        $Mul, $Not, $DoubleQuote, $Int, $Float,
+        $Mul, $Not, $DoubleQuote, $Fence, $Int, $Float,
     )]
     #[describe("fn parameters", "'(<fn params>)'")]
     #[denote(FN_PARAMETERS)]
@@ -603,7 +608,7 @@ pub enum ScriptNode {
         value: Expr
     )]
     #[recovery(
-        $If, $Match, $Let, $For, $Loop, $Break, $Continue, $Return, $Use,
+        $If, $Match, $Let, $Enum, $For, $Loop, $Break, $Continue, $Return, $Use, $Crate,
         $BraceOpen, $BraceClose, $Semicolon,
         [$BraceOpen..$BraceClose],
         [$BracketOpen..$BracketClose],
@@ -639,6 +644,64 @@ pub enum ScriptNode {
         semantics: Semantics<VoidFeature<ScriptNode>>,
     },
 
+    #[rule(
+        keyword: $Enum
+        name: Var
+        body: EnumBody
+    )]
+    #[describe("statement", "'enum <name> {<variants>}'")]
+    #[denote(ENUM)]
+    Enum {
+        #[node]
+        node: NodeRef,
+        #[parent]
+        parent: NodeRef,
+        #[child]
+        keyword: TokenRef,
+        #[child]
+        name: NodeRef,
+        #[child]
+        body: NodeRef,
+        #[semantics]
+        semantics: Semantics<EnumSemantics>,
+    },
+
+    #[rule(
+        start: $BraceOpen
+        (variants: EnumVariant & ($Comma & variants: EnumVariant)* & $Comma?)?
+        end: $BraceClose
+    )]
+    #[describe("enum body", "'{<variant>, ...}'")]
+    #[denote(ENUM_BODY)]
+    EnumBody {
+        #[node]
+        node: NodeRef,
+        #[parent]
+        parent: NodeRef,
+        #[child]
+        start: TokenRef,
+        #[child]
+        variants: Vec<NodeRef>,
+        #[child]
+        end: TokenRef,
+        #[semantics]
+        semantics: Semantics<VoidFeature<ScriptNode>>,
+    },
+
+    #[rule(token: $Ident)]
+    #[describe("enum variant", "'<variant>'")]
+    #[denote(ENUM_VARIANT)]
+    EnumVariant {
+        #[node]
+        node: NodeRef,
+        #[parent]
+        parent: NodeRef,
+        #[child]
+        token: TokenRef,
+        #[semantics]
+        semantics: Semantics<VoidFeature<ScriptNode>>,
+    },
+
     #[rule(
         start: $BracketOpen
         (items: Expr & ($Comma & items: Expr)* & $Comma?)?
@@ -683,6 +746,27 @@ pub enum ScriptNode {
         semantics: Semantics<StringSemantics>,
     },
 
+    #[rule(
+        start: $Fence
+        ^[$Fence]*
+        end: $Fence
+    )]
+    #[trivia]
+    #[describe("literal", "'```<lang> <embedded block>```'")]
+    #[denote(EMBEDDED)]
+    Embedded {
+        #[node]
+        node: NodeRef,
+        #[parent]
+        parent: NodeRef,
+        #[child]
+        start: TokenRef,
+        #[child]
+        end: TokenRef,
+        #[semantics]
+        semantics: Semantics<StringSemantics>,
+    },
+
     #[describe("ident", "'<ident>'")]
     #[denote(CRATE)]
     Crate {
@@ -838,11 +922,12 @@ pub enum ScriptNode {
 
     #[rule(
         start: $ParenOpen
-        (args: Expr & ($Comma & args: Expr)* & $Comma?)?
+        (args: Arg & ($Comma & args: Arg)* & $Comma?)?
         end: $ParenClose
+        closure: Fn?
     )]
     #[recovery(
-        $If, $Match, $Let, $For, $Loop, $Break, $Continue, $Return, $Use,
+        $If, $Match, $Let, $Enum, $For, $Loop, $Break, $Continue, $Return, $Use, $Crate,
         $BraceOpen, $BraceClose, $Semicolon,
         [$BraceOpen..$BraceClose],
         [$BracketOpen..$BracketClose],
@@ -861,6 +946,26 @@ pub enum ScriptNode {
         args: Vec<NodeRef>,
         #[child]
         end: TokenRef,
+        #[child]
+        closure: NodeRef,
+        #[semantics]
+        semantics: Semantics<VoidFeature<ScriptNode>>,
+    },
+
+    #[rule(Operand)]
+    #[parser(ExprParser::parse_arg(session))]
+    #[describe("argument", "'<arg>' or '<name>: <arg>'")]
+    #[denote(ARG)]
+    #[secondary]
+    Arg {
+        #[node]
+        node: NodeRef,
+        #[parent]
+        parent: NodeRef,
+        #[child]
+        name: TokenRef,
+        #[child]
+        value: NodeRef,
         #[semantics]
         semantics: Semantics<VoidFeature<ScriptNode>>,
     },
@@ -887,7 +992,7 @@ pub enum ScriptNode {
         end: $BracketClose
     )]
     #[recovery(
-        $If, $Match, $Let, $For, $Loop, $Break, $Continue, $Return, $Use,
+        $If, $Match, $Let, $Enum, $For, $Loop, $Break, $Continue, $Return, $Use, $Crate,
         $BraceOpen, $BraceClose, $Semicolon,
         [$BraceOpen..$BraceClose],
         [$BracketOpen..$BracketClose],
@@ -947,6 +1052,53 @@ pub enum ScriptNode {
 }
 
 impl ScriptNode {
+    // Returns the invocation argument expressions of the CallArgs node. The
+    // trailing closure (`foo(a, b) fn(x) {...}`), if any, is the last argument.
+    pub(crate) fn call_args(&self, doc: &ScriptDoc) -> Option<Vec<NodeRef>> {
+        let Self::CallArgs { args, closure, .. } = self else {
+            return None;
+        };
+
+        let mut result = Vec::with_capacity(args.len() + 1);
+
+        for arg in args {
+            match arg.deref(doc) {
+                Some(Self::Arg { value, .. }) => result.push(*value),
+                _ => result.push(NodeRef::nil()),
+            }
+        }
+
+        if !closure.is_nil() {
+            result.push(*closure);
+        }
+
+        Some(result)
+    }
+
+    // Returns the name tokens of the invocation arguments of the CallArgs node
+    // (`foo(<name>: arg)`) in the order of the `call_args` function. The
+    // positional arguments have nil names.
+    pub(crate) fn call_arg_names(&self, doc: &ScriptDoc) -> Option<Vec<TokenRef>> {
+        let Self::CallArgs { args, closure, .. } = self else {
+            return None;
+        };
+
+        let mut result = Vec::with_capacity(args.len() + 1);
+
+        for arg in args {
+            match arg.deref(doc) {
+                Some(Self::Arg { name, .. }) => result.push(*name),
+                _ => result.push(TokenRef::nil()),
+            }
+        }
+
+        if !closure.is_nil() {
+            result.push(TokenRef::nil());
+        }
+
+        Some(result)
+    }
+
     #[inline(always)]
     pub(crate) fn extract_atom_string<'a>(
         doc: &'a ScriptDoc,
@@ -962,10 +1114,85 @@ impl ScriptNode {
             ScriptNode::Bool { token, .. } => token,
             ScriptNode::Field { token, .. } => token,
             ScriptNode::StructEntryKey { token, .. } => token,
+            ScriptNode::EnumVariant { token, .. } => token,
             _ => return None,
         };
 
-        token_ref.string(doc)
+        ScriptToken::canonical_string(token_ref, doc)
+    }
+
+    // Returns the variable node named `name` that is introduced by the
+    // declaration node: a let statement, an enum declaration, a for loop, or
+    // a function's parameter list.
+    pub(crate) fn decl_var(doc: &ScriptDoc, decl_ref: &NodeRef, name: &str) -> Option<NodeRef> {
+        let var_ref = match decl_ref.deref(doc)? {
+            ScriptNode::Let { name, .. } => *name,
+            ScriptNode::Enum { name, .. } => *name,
+            ScriptNode::For { iterator, .. } => *iterator,
+
+            ScriptNode::FnParams { params, .. } => *params
+                .iter()
+                .find(|param_ref| Self::extract_atom_string(doc, param_ref) == Some(name))?,
+
+            _ => return None,
+        };
+
+        match Self::extract_atom_string(doc, &var_ref) == Some(name) {
+            true => Some(var_ref),
+            false => None,
+        }
+    }
+
+    // Returns the variant nodes of the enum declaration node in the order of
+    // their declaration.
+    pub(crate) fn enum_variants<'a>(doc: &'a ScriptDoc, enum_ref: &NodeRef) -> &'a [NodeRef] {
+        let Some(ScriptNode::Enum { body, .. }) = enum_ref.deref(doc) else {
+            return &[];
+        };
+
+        let Some(ScriptNode::EnumBody { variants, .. }) = body.deref(doc) else {
+            return &[];
+        };
+
+        variants
+    }
+
+    // Returns the variant node named `name` of the enum declaration node.
+    #[inline(always)]
+    pub(crate) fn enum_variant(doc: &ScriptDoc, enum_ref: &NodeRef, name: &str) -> Option<NodeRef> {
+        Self::enum_variants(doc, enum_ref)
+            .iter()
+            .find(|variant_ref| Self::extract_atom_string(doc, variant_ref) == Some(name))
+            .copied()
+    }
+
+    // Returns the language id and the body span of the embedded block:
+    // "```<lang>\n<body>```". The body span excludes the first line break that
+    // follows the language id.
+    pub(crate) fn extract_embedded<'a>(
+        doc: &'a ScriptDoc,
+        start: &TokenRef,
+        end: &TokenRef,
+    ) -> Option<(&'a str, SiteSpan)> {
+        let fence = start.string(doc)?;
+        let language = fence.get(3..)?;
+
+        let mut body_start = start.site(doc)? + fence.chars().count();
+        let body_end = end.site(doc)?;
+
+        if body_start > body_end {
+            return None;
+        }
+
+        let mut chars = doc.chars(body_start..body_end);
+
+        match (chars.next(), chars.next()) {
+            (Some('\n'), _) => body_start += 1,
+            (Some('\r'), Some('\n')) => body_start += 2,
+            _ => (),
+        }
+
+        Some((language, body_start..body_end))
     }
 
     #[inline(always)]
@@ -999,6 +1226,123 @@ impl ScriptNode {
         }
     }
 
+    // Returns the lower and the upper bounds of the range expression:
+    // `<lower>..<upper>`.
+    pub(crate) fn extract_range(doc: &ScriptDoc, mut expr: NodeRef) -> Option<(NodeRef, NodeRef)> {
+        loop {
+            match expr.deref(doc)? {
+                ScriptNode::Binary {
+                    left, op, right, ..
+                } => {
+                    if Self::extract_op(doc, op)? != ScriptToken::Dot2 {
+                        return None;
+                    }
+
+                    return Some((*left, *right));
+                }
+
+                ScriptNode::Expr { inner, .. } => {
+                    expr = *inner;
+                    continue;
+                }
+
+                _ => return None,
+            }
+        }
+    }
+
+    // Returns the identifier and the field name of the enum variant access
+    // expression: `<ident>.<field>`.
+    pub(crate) fn extract_variant(doc: &ScriptDoc, mut expr: NodeRef) -> Option<(NodeRef, &str)> {
+        let (left, right) = loop {
+            match expr.deref(doc)? {
+                ScriptNode::Binary {
+                    left, op, right, ..
+                } => {
+                    if Self::extract_op(doc, op)? != ScriptToken::Dot {
+                        return None;
+                    }
+
+                    break (*left, *right);
+                }
+
+                ScriptNode::Expr { inner, .. } => expr = *inner,
+
+                _ => return None,
+            }
+        };
+
+        let Some(ScriptNode::Ident { .. }) = left.deref(doc) else {
+            return None;
+        };
+
+        let Some(ScriptNode::Field { .. }) = right.deref(doc) else {
+            return None;
+        };
+
+        Some((left, Self::extract_atom_string(doc, &right)?))
+    }
+
+    // Recognizes the right-hand side of the self-append assignment
+    // `s = [s, a, b]` or `s = s + a`, where `s` is the `left` identifier.
+    //
+    // Returns the right-hand side expression, the occurrence of `s` inside
+    // this expression, and the appended items.
+    pub(crate) fn extract_self_append<'a>(
+        doc: &'a ScriptDoc,
+        left: &NodeRef,
+        mut right: NodeRef,
+    ) -> Option<(NodeRef, NodeRef, &'a [NodeRef])> {
+        while let ScriptNode::Expr { inner, .. } = right.deref(doc)? {
+            right = *inner;
+        }
+
+        let (mut first, rest) = match right.deref(doc)? {
+            ScriptNode::Array { items, .. } => {
+                let (first, rest) = items.split_first()?;
+
+                if rest.is_empty() {
+                    return None;
+                }
+
+                (*first, rest)
+            }
+
+            ScriptNode::Binary {
+                left, op, right, ..
+            } => {
+                if Self::extract_op(doc, op)? != ScriptToken::Plus {
+                    return None;
+                }
+
+                (*left, from_ref(right))
+            }
+
+            _ => return None,
+        };
+
+        while let ScriptNode::Expr { inner, .. } = first.deref(doc)? {
+            first = *inner;
+        }
+
+        let Some(ScriptNode::Ident { token: left, .. }) = left.deref(doc) else {
+            return None;
+        };
+
+        let Some(ScriptNode::Ident {
+            token: first_token, ..
+        }) = first.deref(doc)
+        else {
+            return None;
+        };
+
+        if left.string(doc)? != first_token.string(doc)? {
+            return None;
+        }
+
+        Some((right, first, rest))
+    }
+
     #[inline(always)]
     pub(crate) fn is_default_case(doc: &ScriptDoc, case: &NodeRef) -> bool {
         let Some(ScriptNode::Else { .. }) = case.deref(doc) else {
@@ -0,0 +1,219 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::syntax::{Assoc, Precedence, ScriptToken};
+
+/// Returns the table of the Ad Astra language operators.
+///
+/// The table is ordered by operator precedence, starting with the operators
+/// that bind the tightest (e.g., `a.field`) and ending with the assignment
+/// operators. The precedence and associativity values are taken from the
+/// source code parser, so external documentation and editor plugins can render
+/// this table without keeping their own copy of the rules.
+///
+/// ```rust
+/// use ad_astra::syntax::{operator_table, Assoc, OperatorPosition};
+///
+/// let shl = operator_table()
+///     .iter()
+///     .find(|op| op.symbol == "<<" && op.position == OperatorPosition::Infix)
+///     .unwrap();
+///
+/// let plus = operator_table()
+///     .iter()
+///     .find(|op| op.symbol == "+" && op.position == OperatorPosition::Infix)
+///     .unwrap();
+///
+/// // `a + b << c` is parsed as `(a + b) << c`.
+/// assert!(plus.precedence > shl.precedence);
+/// assert_eq!(shl.assoc, Assoc::Left);
+/// ```
+#[inline(always)]
+pub fn operator_table() -> &'static [OperatorInfo] {
+    OPERATOR_TABLE
+}
+
+/// An entry of the [operator table](operator_table).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub struct OperatorInfo {
+    /// The operator's source code symbol: `"+"`, `"<<="`, `"()"`, etc.
+    pub symbol: &'static str,
+
+    /// A short human-readable name of the operator: `"addition"`.
+    pub name: &'static str,
+
+    /// The position of the operator relative to its operands.
+    pub position: OperatorPosition,
+
+    /// The operator's precedence. The operators with a higher precedence value
+    /// bind tighter: `a + b * c` means `a + (b * c)`.
+    ///
+    /// The lowest precedence value is 1 (assignment operators).
+    pub precedence: u8,
+
+    /// The operator's associativity: `a - b - c` means `(a - b) - c`, because
+    /// the subtraction is left-associative.
+    pub assoc: Assoc,
+}
+
+/// A position of the operator relative to its operands.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum OperatorPosition {
+    /// The operator precedes its operand: `-a`.
+    Prefix,
+
+    /// The operator is placed between two operands: `a + b`.
+    Infix,
+
+    /// The operator follows its operand: `a?`, `a(arg)`.
+    Postfix,
+}
+
+static OPERATOR_TABLE: &[OperatorInfo] = &[
+    postfix(".", "field access"),
+    postfix("()", "invocation"),
+    postfix("[]", "index"),
+    postfix("?", "nil test"),
+    prefix("-", "negation"),
+    prefix("!", "logical negation"),
+    prefix("*", "clone"),
+    infix(ScriptToken::Mul, "*", "multiplication"),
+    infix(ScriptToken::Div, "/", "division"),
+    infix(ScriptToken::Rem, "%", "remainder"),
+    infix(ScriptToken::Plus, "+", "addition"),
+    infix(ScriptToken::Minus, "-", "subtraction"),
+    infix(ScriptToken::Shl, "<<", "left shift"),
+    infix(ScriptToken::Shr, ">>", "right shift"),
+    infix(ScriptToken::BitAnd, "&", "bitwise conjunction"),
+    infix(ScriptToken::BitXor, "^", "bitwise exclusive disjunction"),
+    infix(ScriptToken::BitOr, "|", "bitwise disjunction"),
+    infix(ScriptToken::Dot2, "..", "range"),
+    infix(ScriptToken::Equal, "==", "equality"),
+    infix(ScriptToken::NotEqual, "!=", "inequality"),
+    infix(ScriptToken::Lesser, "<", "less than"),
+    infix(ScriptToken::LesserOrEqual, "<=", "less than or equal"),
+    infix(ScriptToken::Greater, ">", "greater than"),
+    infix(ScriptToken::GreaterOrEqual, ">=", "greater than or equal"),
+    infix(ScriptToken::And, "&&", "logical conjunction"),
+    infix(ScriptToken::Or, "||", "logical disjunction"),
+    infix(ScriptToken::Assign, "=", "assignment"),
+    infix(ScriptToken::PlusAssign, "+=", "addition assignment"),
+    infix(ScriptToken::MinusAssign, "-=", "subtraction assignment"),
+    infix(ScriptToken::MulAssign, "*=", "multiplication assignment"),
+    infix(ScriptToken::DivAssign, "/=", "division assignment"),
+    infix(ScriptToken::RemAssign, "%=", "remainder assignment"),
+    infix(
+        ScriptToken::BitAndAssign,
+        "&=",
+        "bitwise conjunction assignment",
+    ),
+    infix(
+        ScriptToken::BitXorAssign,
+        "^=",
+        "bitwise exclusive disjunction assignment",
+    ),
+    infix(
+        ScriptToken::BitOrAssign,
+        "|=",
+        "bitwise disjunction assignment",
+    ),
+    infix(ScriptToken::ShlAssign, "<<=", "left shift assignment"),
+    infix(ScriptToken::ShrAssign, ">>=", "right shift assignment"),
+];
+
+const fn prefix(symbol: &'static str, name: &'static str) -> OperatorInfo {
+    entry(
+        symbol,
+        name,
+        OperatorPosition::Prefix,
+        Precedence::UnaryLeft,
+    )
+}
+
+const fn postfix(symbol: &'static str, name: &'static str) -> OperatorInfo {
+    entry(
+        symbol,
+        name,
+        OperatorPosition::Postfix,
+        Precedence::UnaryRight,
+    )
+}
+
+const fn infix(token: ScriptToken, symbol: &'static str, name: &'static str) -> OperatorInfo {
+    entry(
+        symbol,
+        name,
+        OperatorPosition::Infix,
+        token.bin_precedence(),
+    )
+}
+
+const fn entry(
+    symbol: &'static str,
+    name: &'static str,
+    position: OperatorPosition,
+    precedence: Precedence,
+) -> OperatorInfo {
+    OperatorInfo {
+        symbol,
+        name,
+        position,
+        precedence: precedence as u8 / 2,
+        assoc: precedence.assoc(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ahash::AHashSet;
+
+    use crate::syntax::operator_table;
+
+    #[test]
+    fn test_operator_table() {
+        let table = operator_table();
+
+        for pair in table.windows(2) {
+            assert!(pair[0].precedence >= pair[1].precedence);
+        }
+
+        let mut unique = AHashSet::new();
+
+        for op in table {
+            assert!(op.precedence > 0, "{op:?}");
+            assert!(unique.insert((op.symbol, op.position)), "{op:?}");
+        }
+    }
+}
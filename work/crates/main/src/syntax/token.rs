@@ -32,7 +32,9 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use lady_deirdre::lexis::Token;
+use std::cell::Cell;
+
+use lady_deirdre::lexis::{SourceCode, Token, TokenRef};
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Token)]
 #[repr(u8)]
@@ -58,6 +60,11 @@ pub enum ScriptToken {
     #[describe("keyword")]
     Struct,
 
+    #[rule("enum")]
+    #[priority(1)]
+    #[describe("keyword")]
+    Enum,
+
     #[rule("use")]
     #[priority(1)]
     #[describe("keyword")]
@@ -307,6 +314,10 @@ pub enum ScriptToken {
     #[rule('\\' .)]
     Escaped,
 
+    #[rule("```" ['a'..'z', 'A'..'Z', '0'..'9', '_', '-', '+', '.']*)]
+    #[describe("fence")]
+    Fence,
+
     #[rule("crate")]
     #[priority(1)]
     #[describe("crate")]
@@ -318,6 +329,7 @@ pub enum ScriptToken {
     This,
 
     #[rule(['a'..'z', 'A'..'Z', '_'] ['a'..'z', 'A'..'Z', '0'..'9', '_']*)]
+    #[constructor(ScriptToken::scan_ident(fragment))]
     Ident,
 
     #[rule(
@@ -354,6 +366,111 @@ impl Default for ScriptToken {
     }
 }
 
+thread_local! {
+    static KEYWORD_ALIASES: Cell<&'static [(&'static str, &'static str)]> = const {
+        Cell::new(&[])
+    };
+}
+
+impl ScriptToken {
+    // Runs `scan` with the keyword alias table in effect for the lexer on the
+    // current thread.
+    pub(crate) fn with_keyword_aliases<R>(
+        aliases: &'static [(&'static str, &'static str)],
+        scan: impl FnOnce() -> R,
+    ) -> R {
+        struct Restore(&'static [(&'static str, &'static str)]);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                KEYWORD_ALIASES.set(self.0);
+            }
+        }
+
+        let _restore = Restore(KEYWORD_ALIASES.replace(aliases));
+
+        scan()
+    }
+
+    // Returns the canonical spelling of the keyword token, or None if the
+    // token is not a keyword.
+    pub(crate) const fn keyword_string(self) -> Option<&'static str> {
+        Some(match self {
+            Self::Fn => "fn",
+            Self::Let => "let",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Use => "use",
+            Self::For => "for",
+            Self::In => "in",
+            Self::Loop => "loop",
+            Self::Break => "break",
+            Self::Continue => "continue",
+            Self::Return => "return",
+            Self::If => "if",
+            Self::Else => "else",
+            Self::Match => "match",
+            Self::True => "true",
+            Self::False => "false",
+            Self::Max => "max",
+            Self::Len => "len",
+            Self::Crate => "crate",
+            Self::This => "self",
+            _ => return None,
+        })
+    }
+
+    // Returns the text of the token, replacing the keyword aliases with the
+    // canonical keyword spelling.
+    pub(crate) fn canonical_string<'a>(
+        token_ref: &TokenRef,
+        code: &'a impl SourceCode<Token = Self>,
+    ) -> Option<&'a str> {
+        if let Some(keyword) = token_ref.deref(code).and_then(Self::keyword_string) {
+            return Some(keyword);
+        }
+
+        token_ref.string(code)
+    }
+
+    fn scan_ident(fragment: &str) -> Self {
+        KEYWORD_ALIASES.with(|aliases| {
+            let Some((_, keyword)) = aliases.get().iter().find(|(alias, _)| *alias == fragment)
+            else {
+                return Self::Ident;
+            };
+
+            Self::keyword(keyword).unwrap_or(Self::Ident)
+        })
+    }
+
+    fn keyword(string: &str) -> Option<Self> {
+        Some(match string {
+            "fn" => Self::Fn,
+            "let" => Self::Let,
+            "struct" => Self::Struct,
+            "enum" => Self::Enum,
+            "use" => Self::Use,
+            "for" => Self::For,
+            "in" => Self::In,
+            "loop" => Self::Loop,
+            "break" => Self::Break,
+            "continue" => Self::Continue,
+            "return" => Self::Return,
+            "if" => Self::If,
+            "else" => Self::Else,
+            "match" => Self::Match,
+            "true" => Self::True,
+            "false" => Self::False,
+            "max" => Self::Max,
+            "len" => Self::Len,
+            "crate" => Self::Crate,
+            "self" => Self::This,
+            _ => return None,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use lady_deirdre::lexis::Token;
@@ -0,0 +1,347 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use lady_deirdre::{
+    analysis::TriggerHandle,
+    lexis::{SourceCode, ToSpan},
+    units::Lexis,
+};
+
+use crate::{
+    analysis::{DiagnosticsDepth, IssueCode, IssueSeverity, ModuleRead, ScriptModule},
+    runtime::{Cell, Origin, PackageMeta, ScriptType},
+};
+
+/// Asserts that the script source code compiles without errors and warnings
+/// up to the specified diagnostics `depth` (inclusive).
+///
+/// Hints are ignored by this function, since they do not indicate runtime
+/// problems.
+///
+/// If the assertion fails, the function panics with a snippet of the source
+/// code annotated with the reported issues.
+///
+/// See also the [assert_script_ok](crate::assert_script_ok) macro.
+#[track_caller]
+pub fn assert_ok(package: &'static PackageMeta, source: &str, depth: DiagnosticsDepth) {
+    let module = ScriptModule::new(package, source);
+    let handle = TriggerHandle::new();
+    let read = module.read(&handle, 1).expect("Module read failure.");
+    let text = read.text();
+
+    let mask = IssueSeverity::Error as u8 | IssueSeverity::Warning as u8;
+
+    for depth in 1..=depth {
+        let diagnostics = read.diagnostics(depth).expect("Diagnostics failure.");
+
+        if diagnostics.len(mask) == 0 {
+            continue;
+        }
+
+        panic!(
+            "Script diagnostics at depth {depth}:\n{}",
+            diagnostics.highlight(&text, mask),
+        );
+    }
+}
+
+/// Asserts that the script source code contains at least one diagnostic
+/// issue of the specified `code` at any diagnostics depth.
+///
+/// The function returns the source code fragments covered by the matching
+/// issues, ordered by their position in the source code. You can compare this
+/// vector with the expected snapshot.
+///
+/// If the script does not have issues of this kind, the function panics with a
+/// snippet of the source code annotated with all reported issues.
+///
+/// See also the [assert_script_err](crate::assert_script_err) macro.
+#[track_caller]
+pub fn assert_issue(package: &'static PackageMeta, source: &str, code: IssueCode) -> Vec<String> {
+    let module = ScriptModule::new(package, source);
+    let handle = TriggerHandle::new();
+    let read = module.read(&handle, 1).expect("Module read failure.");
+    let text = read.text();
+
+    let mut spans = Vec::new();
+    let mut report = String::new();
+
+    for depth in 1..=3 {
+        let diagnostics = read.diagnostics(depth).expect("Diagnostics failure.");
+
+        for issue in &diagnostics {
+            if issue.code() != code {
+                continue;
+            }
+
+            let span = issue
+                .origin(&text)
+                .to_site_span(&text)
+                .expect("Invalid issue span.");
+
+            spans.push(span);
+        }
+
+        if !diagnostics.is_empty() {
+            report.push_str(&diagnostics.highlight(&text, !0).to_string());
+            report.push('\n');
+        }
+    }
+
+    if spans.is_empty() {
+        panic!(
+            "Script does not have {code} ({}) issues.\n{report}",
+            code as u16
+        );
+    }
+
+    spans.sort_by_key(|span| (span.start, span.end));
+
+    spans
+        .into_iter()
+        .map(|span| text.lexis().substring(span).to_string())
+        .collect()
+}
+
+/// Compiles and runs the script source code, and returns the evaluation
+/// result.
+///
+/// The function panics if the script has syntax errors, if the compiler fails
+/// to compile the script, or if the script execution ends with a
+/// [runtime error](crate::runtime::RuntimeError). The panic message includes
+/// a snippet of the source code annotated with the failure description.
+///
+/// See also the [eval](crate::eval) macro.
+#[track_caller]
+pub fn eval(package: &'static PackageMeta, source: &str) -> Cell {
+    let module = ScriptModule::new(package, source);
+    let handle = TriggerHandle::new();
+    let read = module.read(&handle, 1).expect("Module read failure.");
+    let text = read.text();
+
+    let diagnostics = read.diagnostics(1).expect("Diagnostics failure.");
+
+    if diagnostics.len(IssueSeverity::Error as u8) > 0 {
+        panic!(
+            "Script syntax errors:\n{}",
+            diagnostics.highlight(&text, IssueSeverity::Error as u8),
+        );
+    }
+
+    let assembly = read.compile().expect("Script compilation failure.");
+
+    match assembly.run() {
+        Ok(result) => result,
+        Err(error) => panic!("Script evaluation error:\n{}", error.display(&text)),
+    }
+}
+
+/// Compiles and runs the script source code, and converts the evaluation
+/// result into the Rust type `T`.
+///
+/// In addition to the failure cases of the [eval] function, this function
+/// panics if the result cannot be converted into `T`.
+///
+/// See also the [eval_as](crate::eval_as) macro.
+#[track_caller]
+pub fn eval_as<T: ScriptType>(package: &'static PackageMeta, source: &str) -> T {
+    let result = eval(package, source);
+
+    match result.take::<T>(Origin::nil()) {
+        Ok(value) => value,
+        Err(error) => {
+            let module = ScriptModule::new(package, source);
+            let handle = TriggerHandle::new();
+            let read = module.read(&handle, 1).expect("Module read failure.");
+
+            panic!(
+                "Script result type mismatch:\n{}",
+                error.display(&read.text())
+            );
+        }
+    }
+}
+
+/// Asserts that the script source code has no errors and warnings.
+///
+/// The first argument is a type of the [package](crate::runtime::ScriptPackage)
+/// that exports Rust APIs to the script, and the second argument is the script
+/// source code. The optional third argument is the maximum
+/// [diagnostics depth](crate::analysis::DiagnosticsDepth) to check (`3` by
+/// default).
+///
+/// On failure, the macro panics with the annotated source code snippet.
+///
+/// ```rust
+/// # use ad_astra::{assert_script_ok, export};
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// assert_script_ok!(Package, "let x = 10; return x + 1;");
+/// assert_script_ok!(Package, "let x = 10;", 1);
+/// ```
+///
+/// This macro is a shortcut for the [assert_ok](crate::testing::assert_ok)
+/// function.
+#[macro_export]
+macro_rules! assert_script_ok {
+    ($package:ty, $source:expr $(,)?) => {
+        $crate::assert_script_ok!($package, $source, 3)
+    };
+
+    ($package:ty, $source:expr, $depth:expr $(,)?) => {
+        $crate::testing::assert_ok(
+            <$package as $crate::runtime::ScriptPackage>::meta(),
+            $source,
+            $depth,
+        )
+    };
+}
+
+/// Asserts that the script source code has a diagnostic issue of the specified
+/// [IssueCode](crate::analysis::IssueCode).
+///
+/// The first argument is a type of the [package](crate::runtime::ScriptPackage)
+/// that exports Rust APIs to the script, the second argument is the script
+/// source code, and the third argument is the expected issue code.
+///
+/// The macro returns a vector of the source code fragments covered by the
+/// matching issues. Optionally, you can pass the expected fragments as the
+/// remaining arguments, in which case the macro asserts that the fragments
+/// match this snapshot.
+///
+/// ```rust
+/// # use ad_astra::{analysis::IssueCode, assert_script_err, export};
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// assert_script_err!(Package, "let x = 10; x = ;", IssueCode::Parse);
+///
+/// assert_script_err!(
+///     Package,
+///     "return [1, 2, 3][5];",
+///     IssueCode::IndexOutOfBounds,
+///     "5",
+/// );
+/// ```
+///
+/// This macro is a shortcut for the [assert_issue](crate::testing::assert_issue)
+/// function.
+#[macro_export]
+macro_rules! assert_script_err {
+    ($package:ty, $source:expr, $code:expr $(,)?) => {
+        $crate::testing::assert_issue(
+            <$package as $crate::runtime::ScriptPackage>::meta(),
+            $source,
+            $code,
+        )
+    };
+
+    ($package:ty, $source:expr, $code:expr, $($span:expr),+ $(,)?) => {{
+        let spans = $crate::assert_script_err!($package, $source, $code);
+
+        ::std::assert_eq!(spans, [$($span),+], "Issue spans mismatch.");
+
+        spans
+    }};
+}
+
+/// Compiles and runs the script source code, and returns the evaluation
+/// result [Cell](crate::runtime::Cell).
+///
+/// The first argument is a type of the [package](crate::runtime::ScriptPackage)
+/// that exports Rust APIs to the script, and the second argument is the script
+/// source code.
+///
+/// On failure, the macro panics with the annotated source code snippet.
+///
+/// ```rust
+/// # use ad_astra::{eval, export};
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// let result = eval!(Package, "return \"foo\";");
+///
+/// assert_eq!(result.stringify(false), "foo");
+/// ```
+///
+/// This macro is a shortcut for the [eval](crate::testing::eval) function.
+#[macro_export]
+macro_rules! eval {
+    ($package:ty, $source:expr $(,)?) => {
+        $crate::testing::eval(
+            <$package as $crate::runtime::ScriptPackage>::meta(),
+            $source,
+        )
+    };
+}
+
+/// Compiles and runs the script source code, and converts the evaluation
+/// result into the specified Rust type.
+///
+/// The first argument is a type of the [package](crate::runtime::ScriptPackage)
+/// that exports Rust APIs to the script, the second argument is the type of
+/// the result, and the third argument is the script source code.
+///
+/// On failure, the macro panics with the annotated source code snippet.
+///
+/// ```rust
+/// # use ad_astra::{eval_as, export};
+/// #
+/// # #[export(package)]
+/// # #[derive(Default)]
+/// # struct Package;
+/// #
+/// let result = eval_as!(Package, usize, "return 10 + 20;");
+///
+/// assert_eq!(result, 30);
+/// ```
+///
+/// This macro is a shortcut for the [eval_as](crate::testing::eval_as)
+/// function.
+#[macro_export]
+macro_rules! eval_as {
+    ($package:ty, $ty:ty, $source:expr $(,)?) => {
+        $crate::testing::eval_as::<$ty>(
+            <$package as $crate::runtime::ScriptPackage>::meta(),
+            $source,
+        )
+    };
+}
@@ -0,0 +1,421 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+// Compile-fail checks of the Export macro.
+//
+// The checks are doctests of the main crate rather than the Export macro
+// crate, because the macro expansions refer to this crate's APIs, and the
+// compiler type-checks these expansions only here. Each failing snippet has
+// a compiling counterpart that differs only in the rejected part, such that
+// the failing snippet fails for the intended reason. The errors reported by
+// the compiler itself are additionally pinned by their error codes.
+//
+// The checks of the generic specialization bounds are snapshot tests of the
+// complete compiler output instead (see "tests/compile_fail.rs"), because the
+// point of these checks is the wording of the reported error.
+
+/// ```
+/// # use ad_astra::export;
+/// #
+/// #[export]
+/// #[repr(align(8))]
+/// pub struct Header {
+///     pub tag: u8,
+///     pub size: u32,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export]
+/// #[repr(C, packed)]
+/// pub struct Header {
+///     pub tag: u8,
+///     pub size: u32,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export]
+/// #[repr(C)]
+/// pub struct Header {
+///     pub tag: u8,
+///     pub size: u32,
+/// }
+/// ```
+///
+/// ```
+/// # use ad_astra::export;
+/// #
+/// #[export(opaque)]
+/// #[repr(C, packed)]
+/// pub struct Header {
+///     tag: u8,
+///     size: u32,
+/// }
+/// ```
+#[allow(dead_code)]
+struct StructRepr;
+
+/// ```
+/// # use ad_astra::export;
+/// #
+/// #[export]
+/// pub struct Buffer {
+///     pub size: usize,
+///
+///     #[export(exclude)]
+///     pub data: *mut u8,
+/// }
+/// #
+/// # unsafe impl Send for Buffer {}
+/// # unsafe impl Sync for Buffer {}
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export]
+/// pub struct Buffer {
+///     pub size: usize,
+///     pub data: *mut u8,
+/// }
+/// #
+/// # unsafe impl Send for Buffer {}
+/// # unsafe impl Sync for Buffer {}
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export]
+/// pub struct Buffer {
+///     pub size: usize,
+///     pub data: (usize, *const u8),
+/// }
+/// #
+/// # unsafe impl Send for Buffer {}
+/// # unsafe impl Sync for Buffer {}
+/// ```
+///
+/// ```
+/// # use ad_astra::export;
+/// # use std::cell::UnsafeCell;
+/// #
+/// #[export]
+/// pub struct Counter {
+///     pub total: usize,
+///
+///     #[export(exclude)]
+///     pub value: UnsafeCell<usize>,
+/// }
+/// #
+/// # unsafe impl Sync for Counter {}
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// # use std::cell::UnsafeCell;
+/// #
+/// #[export]
+/// pub struct Counter {
+///     pub total: usize,
+///     pub value: UnsafeCell<usize>,
+/// }
+/// #
+/// # unsafe impl Sync for Counter {}
+/// ```
+#[allow(dead_code)]
+struct StructFields;
+
+/// ```
+/// # use ad_astra::export;
+/// #
+/// #[export(name "function")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ValidName;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "fn")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedFn;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "let")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedLet;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "struct")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedStruct;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "use")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedUse;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "for")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedFor;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "in")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedIn;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "loop")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedLoop;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "break")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedBreak;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "continue")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedContinue;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "return")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedReturn;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "if")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedIf;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "else")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedElse;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "match")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedMatch;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "true")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedTrue;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "false")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedFalse;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "max")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedMax;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "len")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedLen;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "crate")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedCrate;
+
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name "self")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct ReservedSelf;
+
+/// ```
+/// # use ad_astra::export;
+/// #
+/// #[export]
+/// pub const ADASTRA_FOO: usize = 10;
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export]
+/// #[allow(non_upper_case_globals)]
+/// pub const __adastra_foo: usize = 10;
+/// ```
+#[allow(dead_code)]
+struct ReservedPrefix;
+
+/// ```
+/// # use ad_astra::export;
+/// #
+/// pub struct Matcher;
+///
+/// #[export(name "__" Type:Lower[T])]
+/// pub fn foo<#[export(type Matcher)] T>() {}
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// pub struct Adastra;
+///
+/// #[export(name "__" Type:Lower[T])]
+/// pub fn foo<#[export(type Adastra)] T>() {}
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// pub struct Match;
+///
+/// #[export(name Type:Lower[T])]
+/// pub fn foo<#[export(type Match)] T>() {}
+/// ```
+#[allow(dead_code)]
+struct SanitizedName;
+
+/// ```
+/// # use ad_astra::export;
+/// #
+/// #[export(name "d" 3)]
+/// pub fn foo() {}
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export(name 3 "d")]
+/// pub fn foo() {}
+/// ```
+#[allow(dead_code)]
+struct LeadingDigit;
+
+/// ```
+/// # use ad_astra::export;
+/// #
+/// #[export(name "len_")]
+/// pub fn len() {}
+/// ```
+///
+/// ```compile_fail
+/// # use ad_astra::export;
+/// #
+/// #[export]
+/// pub fn len() {}
+/// ```
+#[allow(dead_code)]
+struct RenamedReserved;
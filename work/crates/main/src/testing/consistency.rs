@@ -0,0 +1,349 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use std::fmt::{Display, Formatter};
+
+use lady_deirdre::{
+    analysis::TriggerHandle,
+    lexis::{SourceCode, ToSpan},
+};
+
+use crate::{
+    analysis::{
+        symbols::{LookupOptions, ModuleSymbol, SymbolKind},
+        IssueSeverity,
+        ModuleRead,
+        ScriptModule,
+    },
+    runtime::{PackageMeta, TypeHint},
+};
+
+/// A single divergence between the analyzer's expectations and the script's
+/// actual behavior found by the [check_consistency] function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// A one-based line number of the script source code where the divergence
+    /// has been found.
+    pub line: usize,
+
+    /// A human-readable description of the divergence.
+    pub message: String,
+}
+
+impl Display for Divergence {
+    #[inline(always)]
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_fmt(format_args!("line {}: {}", self.line, self.message))
+    }
+}
+
+/// Checks that the static analyzer's predictions about the script agree with
+/// the actual script evaluation.
+///
+/// The script source code may contain expectation directives in the form of
+/// full-line comments:
+///
+///  - `// expect type <var>: <type>` asserts that the type of the closest
+///    preceding variable `<var>` inferred by the analyzer is `<type>`. The
+///    `<type>` is a precise type name (e.g., `usize`) if the analyzer infers
+///    the exact type, or a type family name (e.g., `number`) otherwise.
+///  - `// expect result: <value>` asserts that the script evaluates to a value
+///    whose string representation is `<value>`.
+///
+/// In addition, the function checks that each `return` statement of the
+/// script's top-level code with a precisely inferred result type agrees with
+/// the type of the actual evaluation result.
+///
+/// The function returns the list of all found divergences, ordered by their
+/// line numbers. An empty list means that the analyzer and the interpreter
+/// agree on the script semantics.
+///
+/// See also the [assert_consistent] function.
+pub fn check_consistency(package: &'static PackageMeta, source: &str) -> Vec<Divergence> {
+    let module = ScriptModule::new(package, source);
+    let handle = TriggerHandle::new();
+    let read = module.read(&handle, 1).expect("Module read failure.");
+    let text = read.text();
+
+    let mut divergences = Vec::new();
+
+    let diagnostics = read.diagnostics(1).expect("Diagnostics failure.");
+
+    if diagnostics.len(IssueSeverity::Error as u8) > 0 {
+        divergences.push(Divergence {
+            line: 1,
+            message: format!(
+                "script has syntax errors:\n{}",
+                diagnostics.highlight(&text, IssueSeverity::Error as u8),
+            ),
+        });
+
+        return divergences;
+    }
+
+    let mut expected_result = None;
+
+    let vars = read
+        .symbols(.., LookupOptions::new().filter(SymbolKind::Var as u32))
+        .expect("Symbols lookup failure.");
+
+    let mut offset = 0;
+
+    for (index, line) in source.split('\n').enumerate() {
+        let line_offset = offset;
+
+        offset += line.len() + 1;
+
+        let Some(directive) = line.trim().strip_prefix("//") else {
+            continue;
+        };
+
+        let Some(directive) = directive.trim().strip_prefix("expect ") else {
+            continue;
+        };
+
+        let directive = directive.trim();
+
+        if let Some(expected) = directive.strip_prefix("result:") {
+            expected_result = Some((index + 1, expected.trim()));
+            continue;
+        }
+
+        let Some((name, expected)) = directive
+            .strip_prefix("type ")
+            .and_then(|directive| directive.split_once(':'))
+        else {
+            divergences.push(Divergence {
+                line: index + 1,
+                message: format!("malformed directive {directive:?}"),
+            });
+            continue;
+        };
+
+        let name = name.trim();
+        let expected = expected.trim();
+
+        let var = vars.iter().rev().find_map(|symbol| {
+            let ModuleSymbol::Var(var) = symbol else {
+                return None;
+            };
+
+            if var.var_name(&read)?.to_string() != name {
+                return None;
+            }
+
+            let span = var.origin(&read).to_site_span(&text)?;
+
+            match span.start < line_offset {
+                true => Some(*var),
+                false => None,
+            }
+        });
+
+        let Some(var) = var else {
+            divergences.push(Divergence {
+                line: index + 1,
+                message: format!("variable {name:?} not found"),
+            });
+            continue;
+        };
+
+        let inferred = var
+            .var_type(&read)
+            .expect("Type inference failure.")
+            .type_hint;
+
+        let inferred = match inferred {
+            TypeHint::Type(_) => format!("{inferred:#}"),
+            _ => format!("{inferred}"),
+        };
+
+        if inferred != expected {
+            divergences.push(Divergence {
+                line: index + 1,
+                message: format!(
+                    "variable {name:?} inferred as {inferred:?}, expected {expected:?}",
+                ),
+            });
+        }
+    }
+
+    let assembly = read.compile().expect("Script compilation failure.");
+
+    let result = match assembly.run() {
+        Ok(result) => result,
+
+        Err(error) => {
+            divergences.push(Divergence {
+                line: 1,
+                message: format!("script evaluation error:\n{}", error.display(&text)),
+            });
+
+            divergences.sort_by_key(|divergence| divergence.line);
+
+            return divergences;
+        }
+    };
+
+    if let Some((line, expected)) = expected_result {
+        let actual = result.stringify(false);
+
+        if actual != expected {
+            divergences.push(Divergence {
+                line,
+                message: format!("script evaluated to {actual:?}, expected {expected:?}"),
+            });
+        }
+    }
+
+    let returns = read
+        .symbols(.., LookupOptions::new().filter(SymbolKind::Return as u32))
+        .expect("Symbols lookup failure.");
+
+    let actual_ty = result.ty();
+
+    for symbol in returns {
+        let ModuleSymbol::Return(symbol) = symbol else {
+            continue;
+        };
+
+        if symbol
+            .fn_symbol(&read)
+            .expect("Function lookup failure.")
+            .is_some()
+        {
+            continue;
+        }
+
+        let TypeHint::Type(inferred_ty) = symbol
+            .result_type(&read)
+            .expect("Type inference failure.")
+            .type_hint
+        else {
+            continue;
+        };
+
+        if inferred_ty.is_nil()
+            || inferred_ty.is_dynamic()
+            || actual_ty.is_nil()
+            || inferred_ty == actual_ty
+        {
+            continue;
+        }
+
+        let line = symbol
+            .origin(&read)
+            .to_position_span(&text)
+            .map(|span| span.start.line)
+            .unwrap_or(1);
+
+        divergences.push(Divergence {
+            line,
+            message: format!(
+                "return statement inferred as {inferred_ty}, but the script evaluated to {actual_ty}",
+            ),
+        });
+    }
+
+    divergences.sort_by_key(|divergence| divergence.line);
+
+    divergences
+}
+
+/// Asserts that the static analyzer's predictions about each script of the
+/// `corpus` agree with the actual script evaluation.
+///
+/// The `corpus` is a list of pairs of the script names and the script source
+/// codes. The expectation directives of each script are described in the
+/// [check_consistency] function documentation.
+///
+/// If any script of the corpus diverges, the function panics with a report
+/// listing all divergences of all scripts.
+///
+/// ```rust
+/// use ad_astra::{export, runtime::ScriptPackage, testing::assert_consistent};
+///
+/// #[export(package)]
+/// #[derive(Default)]
+/// struct Package;
+///
+/// assert_consistent(
+///     Package::meta(),
+///     &[(
+///         "sum",
+///         r#"
+///         let x = 10 + 20;
+///         // expect type x: usize
+///         return x;
+///         // expect result: 30
+///         "#,
+///     )],
+/// );
+/// ```
+#[track_caller]
+pub fn assert_consistent(package: &'static PackageMeta, corpus: &[(&str, &str)]) {
+    let mut report = String::new();
+
+    for (name, source) in corpus {
+        for divergence in check_consistency(package, source) {
+            report.push_str(&format!("{name}:{divergence}\n"));
+        }
+    }
+
+    if !report.is_empty() {
+        panic!("Analysis and runtime divergences:\n{report}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        analysis::symbols::tests::TestPackage,
+        runtime::ScriptPackage,
+        testing::assert_consistent,
+    };
+
+    #[test]
+    fn test_semantics_corpus() {
+        assert_consistent(
+            TestPackage::meta(),
+            &[
+                ("arithmetic", include_str!("corpus/arithmetic.adastra")),
+                ("logic", include_str!("corpus/logic.adastra")),
+                ("strings", include_str!("corpus/strings.adastra")),
+                ("structs", include_str!("corpus/structs.adastra")),
+            ],
+        );
+    }
+}
@@ -0,0 +1,213 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+//! Compile-fail snapshot tests of the Export macro.
+//!
+//! Every `compile_fail/<case>.rs` file is compiled as a separate binary of
+//! a temporary Cargo project that depends on this crate. If the case has
+//! a sibling `<case>.stderr` file, the compilation must fail, and the
+//! compiler's output must match the file's content. Otherwise, the case must
+//! compile.
+//!
+//! The compiler's output refers to the case file as `$DIR/<case>.rs`.
+//! To regenerate the snapshots after an intended change, run the tests with
+//! the `AD_ASTRA_BLESS` environment variable set.
+
+#![cfg(not(target_family = "wasm"))]
+
+use std::{
+    env::var_os,
+    fs::{copy, create_dir_all, read_dir, read_to_string, write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+#[test]
+fn compile_fail() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let cases_dir = manifest_dir.join("tests").join("compile_fail");
+    let project_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("compile_fail");
+
+    let mut cases = read_dir(&cases_dir)
+        .expect("Cases directory read error.")
+        .map(|entry| entry.expect("Cases directory read error.").path())
+        .filter(|path| path.extension().map(|ext| ext == "rs").unwrap_or(false))
+        .collect::<Vec<_>>();
+
+    cases.sort();
+
+    setup_project(manifest_dir, &project_dir, &cases);
+
+    let bless = var_os("AD_ASTRA_BLESS").is_some();
+    let mut failures = Vec::new();
+
+    for case in &cases {
+        let name = case_name(case);
+        let snapshot_path = case.with_extension("stderr");
+        let expected = read_to_string(&snapshot_path).ok();
+
+        let output = Command::new(option_env!("CARGO").unwrap_or("cargo"))
+            .arg("check")
+            .arg("--quiet")
+            .arg("--color=never")
+            .arg("--bin")
+            .arg(&name)
+            .current_dir(&project_dir)
+            .output()
+            .expect("Cargo execution error.");
+
+        let actual = normalize(&String::from_utf8_lossy(&output.stderr), &cases_dir);
+
+        match (expected, output.status.success()) {
+            (None, true) => (),
+
+            (None, false) => failures.push(format!("Case {name:?} failed to compile:\n{actual}")),
+
+            (Some(_), true) => failures.push(format!("Case {name:?} compiled successfully.")),
+
+            (Some(expected), false) => {
+                if expected == actual {
+                    continue;
+                }
+
+                if bless {
+                    write(&snapshot_path, &actual).expect("Snapshot write error.");
+                    continue;
+                }
+
+                failures.push(format!(
+                    "Case {name:?} output mismatch.\n\
+                    --- expected ---\n{expected}\n--- actual ---\n{actual}",
+                ));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!("{}", failures.join("\n\n"));
+    }
+}
+
+// Creates the Cargo project with one binary target per case. The project
+// shares the lock file of the workspace, such that the cases are compiled
+// against the same dependency versions.
+fn setup_project(manifest_dir: &Path, project_dir: &Path, cases: &[PathBuf]) {
+    create_dir_all(project_dir).expect("Project directory creation error.");
+
+    let mut manifest = format!(
+        "[package]\n\
+        name = \"ad-astra-compile-fail\"\n\
+        version = \"0.0.0\"\n\
+        edition = \"2021\"\n\
+        publish = false\n\
+        \n\
+        [workspace]\n\
+        \n\
+        [dependencies.ad-astra]\n\
+        path = {:?}\n",
+        manifest_dir,
+    );
+
+    for case in cases {
+        manifest.push_str(&format!(
+            "\n[[bin]]\nname = {:?}\npath = {:?}\n",
+            case_name(case),
+            case,
+        ));
+    }
+
+    write(project_dir.join("Cargo.toml"), manifest).expect("Manifest write error.");
+
+    let lock_file = manifest_dir.join("../../Cargo.lock");
+
+    if lock_file.exists() {
+        copy(lock_file, project_dir.join("Cargo.lock")).expect("Lock file copy error.");
+    }
+}
+
+fn case_name(case: &Path) -> String {
+    case.file_stem()
+        .expect("Case file name error.")
+        .to_string_lossy()
+        .into_owned()
+}
+
+// Replaces the absolute paths of the cases with `$DIR`, and removes the
+// Cargo's summary lines that depend on the project setup.
+fn normalize(stderr: &str, cases_dir: &Path) -> String {
+    let cases_dir = cases_dir.to_string_lossy();
+
+    let mut result = String::new();
+
+    for line in stderr.lines() {
+        if line.starts_with("error: could not compile") || line.starts_with("warning: build failed")
+        {
+            continue;
+        }
+
+        result.push_str(&normalize_hash(&line.replace(cases_dir.as_ref(), "$DIR")));
+        result.push('\n');
+    }
+
+    result.trim_end().to_string() + "\n"
+}
+
+// The export macro mangles generated item names with a build-specific hash.
+fn normalize_hash(line: &str) -> String {
+    const PREFIX: &str = "__ADASTRA_EXPORT_";
+
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(index) = rest.find(PREFIX) {
+        let (head, tail) = rest.split_at(index + PREFIX.len());
+        result.push_str(head);
+
+        let hash_len = tail
+            .find(|ch: char| !ch.is_ascii_hexdigit())
+            .unwrap_or(tail.len());
+
+        match hash_len > 0 && tail[hash_len..].starts_with('_') {
+            true => {
+                result.push_str("$HASH");
+                rest = &tail[hash_len..];
+            }
+            false => rest = tail,
+        }
+    }
+
+    result.push_str(rest);
+
+    result
+}
@@ -52,6 +52,7 @@ export function activate(_context: ExtensionContext) {
             documentSelector: [{scheme: "file", language: "adastra"}],
             synchronize: {
                 fileEvents: workspace.createFileSystemWatcher("**/.adastra"),
+                configurationSection: "adastra",
             },
         };
 
@@ -0,0 +1,91 @@
+////////////////////////////////////////////////////////////////////////////////
+// This file is part of "Ad Astra", an embeddable scripting programming       //
+// language platform.                                                         //
+//                                                                            //
+// This work is proprietary software with source-available code.              //
+//                                                                            //
+// To copy, use, distribute, or contribute to this work, you must agree to    //
+// the terms of the General License Agreement:                                //
+//                                                                            //
+// https://github.com/Eliah-Lakhin/ad-astra/blob/master/EULA.md               //
+//                                                                            //
+// The agreement grants a Basic Commercial License, allowing you to use       //
+// this work in non-commercial and limited commercial products with a total   //
+// gross revenue cap. To remove this commercial limit for one of your         //
+// products, you must acquire a Full Commercial License.                      //
+//                                                                            //
+// If you contribute to the source code, documentation, or related materials, //
+// you must grant me an exclusive license to these contributions.             //
+// Contributions are governed by the "Contributions" section of the General   //
+// License Agreement.                                                         //
+//                                                                            //
+// Copying the work in parts is strictly forbidden, except as permitted       //
+// under the General License Agreement.                                       //
+//                                                                            //
+// If you do not or cannot agree to the terms of this Agreement,              //
+// do not use this work.                                                      //
+//                                                                            //
+// This work is provided "as is", without any warranties, express or implied, //
+// except where such disclaimers are legally invalid.                         //
+//                                                                            //
+// Copyright (c) 2024 Ilya Lakhin (Илья Александрович Лахин).                 //
+// All rights reserved.                                                       //
+////////////////////////////////////////////////////////////////////////////////
+
+use ad_astra::{
+    analysis::{
+        symbols::{IdentKind, LookupOptions, ModuleSymbol, SymbolKind},
+        DiagnosticsDepth,
+        IssueSeverity,
+        LintContext,
+        LintPass,
+        ModuleRead,
+        ModuleReadGuard,
+        ModuleResult,
+    },
+    lady_deirdre::analysis::TaskHandle,
+};
+
+/// Warns about the leftover invocations of the `dbg` debugging function.
+pub struct NoDbg;
+
+impl<H: TaskHandle> LintPass<H> for NoDbg {
+    fn name(&self) -> &str {
+        "no-dbg"
+    }
+
+    fn depth(&self) -> DiagnosticsDepth {
+        2
+    }
+
+    fn check(&self, read: &ModuleReadGuard<H>, context: &mut LintContext) -> ModuleResult<()> {
+        let calls = read.symbols(.., LookupOptions::default().filter(SymbolKind::Call as u32))?;
+
+        for symbol in calls {
+            let ModuleSymbol::Call(call) = symbol else {
+                continue;
+            };
+
+            let ModuleSymbol::Ident(ident) = call.receiver(read) else {
+                continue;
+            };
+
+            if ident.kind(read)? != IdentKind::CrateAccess {
+                continue;
+            }
+
+            if ident.name(read).as_deref() != Some("dbg") {
+                continue;
+            }
+
+            context.report(
+                ident.origin(read),
+                IssueSeverity::Warning,
+                "no-dbg",
+                "debugging call. remove it before committing the script",
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -32,25 +32,23 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
+mod lint;
+
 use std::net::SocketAddr;
 
 use ad_astra::{
+    analysis::ScriptEnvironment,
     export,
     runtime::{
         ops::{DynamicArgument, DynamicReturn, DynamicType},
         ScriptPackage,
     },
-    server::{
-        inlay_hint,
-        LspLoggerConfig,
-        LspLoggerServerConfig,
-        LspServer,
-        LspServerConfig,
-        LspTransportConfig,
-    },
+    server::{inlay_hint, LspLintPasses, LspServerConfig, LspTransportConfig},
 };
 use clap::Parser;
 
+use crate::lint::NoDbg;
+
 #[export(package)]
 #[derive(Default)]
 struct Package;
@@ -92,24 +90,16 @@ struct Cli {
 fn main() {
     let cli = Cli::parse();
 
-    let server_config = LspServerConfig::new();
+    let mut server_config = LspServerConfig::new();
 
-    let mut logger_config = LspLoggerConfig::new();
+    server_config.lint_passes = LspLintPasses::from_slice(&[&NoDbg]);
 
-    let mut transport_config = LspTransportConfig::Stdio;
-
-    if let Some(addr) = cli.tcp {
-        transport_config = LspTransportConfig::TcpServer(addr);
-    }
-
-    if let LspTransportConfig::Stdio = &transport_config {
-        logger_config.server = LspLoggerServerConfig::Off;
-    }
+    let transport = match cli.tcp {
+        Some(addr) => LspTransportConfig::TcpServer(addr),
+        None => LspTransportConfig::Stdio,
+    };
 
-    LspServer::startup(
-        server_config,
-        logger_config,
-        transport_config,
-        Package::meta(),
-    );
+    ScriptEnvironment::new(Package::meta())
+        .with_server_config(server_config)
+        .serve_lsp(transport);
 }
@@ -32,17 +32,27 @@
 // All rights reserved.                                                       //
 ////////////////////////////////////////////////////////////////////////////////
 
-use std::{fs::read_to_string, path::PathBuf, str::FromStr, sync::mpsc::channel, time::Instant};
+use std::{path::PathBuf, sync::mpsc::channel, time::Instant};
 
 use ad_astra::{
-    analysis::{ModuleRead, ModuleWrite, ScriptModule},
+    analysis::{
+        symbols::{LookupOptions, ModuleSymbol, SymbolKind},
+        EnvironmentError,
+        EnvironmentResult,
+        ModuleRead,
+        ModuleText,
+        ScriptEnvironment,
+    },
     export,
+    interpret::{WatchHit, Watchpoints},
     lady_deirdre::{
         analysis::TriggerHandle,
-        format::{Style, TerminalString},
+        format::{AnnotationPriority, Style, TerminalString},
     },
     runtime::{
         ops::{DynamicArgument, DynamicReturn, DynamicType},
+        Cell,
+        Origin,
         ScriptPackage,
     },
 };
@@ -83,24 +93,30 @@ struct Cli {
     /// Disabled by default.
     #[arg(short, long, default_value_t = false)]
     watch: bool,
+
+    /// Prints each change of the script variable with this name.
+    /// Disabled by default.
+    #[arg(long)]
+    watch_var: Option<String>,
 }
 
 fn main() {
     let cli = Cli::parse();
 
-    let text = read_to_string(&cli.path).expect("Script file read error.");
+    let mut environment = ScriptEnvironment::new(Package::meta());
+
+    let file_path = PathBuf::from(cli.path);
 
-    let module = ScriptModule::new(Package::meta(), text);
-    module.rename(&cli.path);
+    environment
+        .load(file_path.as_path())
+        .expect("Script file read error.");
 
-    compile_and_run(&module);
+    check_and_run(&environment, cli.watch_var.as_deref());
 
     if !cli.watch {
         return;
     }
 
-    let file_path = PathBuf::from_str(&cli.path).expect("Invalid file path.");
-
     let (tx, rx) = channel();
     let mut watcher =
         RecommendedWatcher::new(tx, Config::default()).expect("File watcher setup error.");
@@ -122,63 +138,84 @@ fn main() {
             continue;
         };
 
-        let Some(file_path) = event.paths.first() else {
+        // The environment re-reads the file only if its modification time or
+        // size has changed.
+        if !environment.reload().expect("Script file read error.") {
             continue;
-        };
+        }
 
         println!("Script file modified: {}", file_path.display());
 
-        let text = read_to_string(file_path).expect("Script file read error");
-
-        update_module(&module, &text);
-        compile_and_run(&module);
+        check_and_run(&environment, cli.watch_var.as_deref());
     }
 }
 
-fn compile_and_run(module: &ScriptModule) {
-    let handle = TriggerHandle::new();
-    let read_guard = module.read(&handle, 1).expect("Module read error.");
+fn check_and_run(environment: &ScriptEnvironment, watch_var: Option<&str>) {
+    let diagnostics = environment.check().expect("Module analysis error.");
 
-    for depth in 1..=3 {
-        let diagnostics = read_guard
-            .diagnostics(depth)
-            .expect("Module analysis error.");
+    if !diagnostics.is_empty() {
+        println!("{}", environment.highlight(&diagnostics).unwrap());
+        return;
+    }
 
-        if diagnostics.is_empty() {
-            continue;
-        }
+    println!("Script execution started...");
+
+    let time = Instant::now();
 
-        println!("{}", diagnostics.highlight(&read_guard.text(), !0));
+    let result = match watch_var {
+        None => environment.run(),
+        Some(name) => run_watched(environment, name),
+    };
 
-        return;
+    match result {
+        Ok(_) => println!("Script execution finished in {:?}.", time.elapsed()),
+        Err(error) => println!("Script execution failure:\n{}", environment.report(&error)),
     }
+}
 
-    println!("Compiling script module...");
+fn run_watched(environment: &ScriptEnvironment, name: &str) -> EnvironmentResult<Cell> {
+    let Some(module) = environment.module() else {
+        return Err(EnvironmentError::NotLoaded);
+    };
 
-    let time = Instant::now();
-    let script_fn = read_guard.compile().expect("Script compilation error.");
-    let time = time.elapsed();
+    let handle = TriggerHandle::new();
+    let read_guard = module.read(&handle, 1)?;
+    let text = read_guard.text();
 
-    println!("Compilation finished in {time:?}. Script execution started...");
+    let mut watchpoints = Watchpoints::new().on_hit(|hit| print_hit(&text, hit));
 
-    let time = Instant::now();
-    match script_fn.run() {
-        Ok(_) => {
-            let time = time.elapsed();
-            println!("Script execution finished in {time:?}.");
+    let vars = read_guard.symbols(.., LookupOptions::new().filter(SymbolKind::Var as u32))?;
+
+    for symbol in vars {
+        let ModuleSymbol::Var(var) = symbol else {
+            continue;
+        };
+
+        match var.var_name(&read_guard) {
+            Some(var_name) if var_name.as_ref() == name => (),
+            _ => continue,
         }
-        Err(error) => println!(
-            "Script execution failure:\n{}",
-            error.display(&read_guard.text()),
-        ),
+
+        watchpoints = watchpoints.watch_var(&read_guard, &var);
     }
+
+    let script_fn = read_guard.compile()?;
+
+    Ok(script_fn.instrument(&mut watchpoints).run()?)
 }
 
-fn update_module(module: &ScriptModule, text: &str) {
-    let handle = TriggerHandle::new();
-    let mut write_guard = module.write(&handle, 1).expect("Module write error.");
+fn print_hit(text: &ModuleText, hit: &WatchHit) {
+    let message = format!("{} -> {}", hit.old_value, hit.new_value);
+
+    let Origin::Script(origin) = hit.origin else {
+        println!("'{}' changed: {message}", hit.name);
+        return;
+    };
+
+    let mut snippet = text.snippet();
+
+    snippet.set_caption(format!("'{}' changed", hit.name));
+    snippet.annotate(origin, AnnotationPriority::Primary, message);
 
-    write_guard
-        .edit(.., text)
-        .expect("Module content synchronization error.");
+    println!("{snippet}");
 }